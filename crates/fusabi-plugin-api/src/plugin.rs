@@ -174,6 +174,7 @@ impl Provider for PluginProvider {
             items_removed: 0,
             errors: vec![],
             duration_ms: 0,
+            streams: Vec::new(),
         })
     }
 
@@ -185,6 +186,19 @@ impl Provider for PluginProvider {
             has_collections: provider_config.map(|p| p.has_collections).unwrap_or(false),
             has_saved_items: provider_config.map(|p| p.has_saved_items).unwrap_or(false),
             has_communities: provider_config.map(|p| p.has_communities).unwrap_or(false),
+            supports_search: provider_config.map(|p| p.supports_search).unwrap_or(false),
+            supports_write_actions: provider_config
+                .map(|p| p.supports_write_actions)
+                .unwrap_or(false),
+            supports_incremental_sync: provider_config
+                .map(|p| p.supports_incremental_sync)
+                .unwrap_or(false),
+            supports_pagination: provider_config
+                .map(|p| p.supports_pagination)
+                .unwrap_or(false),
+            supports_notifications: provider_config
+                .map(|p| p.supports_notifications)
+                .unwrap_or(false),
         }
     }
 
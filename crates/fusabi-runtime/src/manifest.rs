@@ -121,6 +121,26 @@ pub struct ProviderConfig {
     #[serde(default)]
     pub has_communities: bool,
 
+    /// Whether this provider can search its source.
+    #[serde(default)]
+    pub supports_search: bool,
+
+    /// Whether item actions are written back to the source.
+    #[serde(default)]
+    pub supports_write_actions: bool,
+
+    /// Whether syncs fetch only items newer than the last sync.
+    #[serde(default)]
+    pub supports_incremental_sync: bool,
+
+    /// Whether feed limit and offset are honored.
+    #[serde(default)]
+    pub supports_pagination: bool,
+
+    /// Whether the source pushes new items instead of being polled.
+    #[serde(default)]
+    pub supports_notifications: bool,
+
     /// OAuth provider name (for Sigilforge integration).
     #[serde(default)]
     pub oauth_provider: Option<String>,
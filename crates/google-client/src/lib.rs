@@ -0,0 +1,291 @@
+//! # google-client
+//!
+//! Google API HTTP client shared by the Scryforge Google providers (YouTube,
+//! and Gmail, Calendar, or Drive as they are added).
+//!
+//! [`GoogleClient`] fetches a token from Sigilforge for its service on every
+//! request, maps rejected tokens, throttling, and spent quota to
+//! [`GoogleError`], and follows `pageToken` paging. Every request names what
+//! it costs in quota units; the client keeps count in a [`QuotaTracker`] (see
+//! [`quota`]) and stops sending once Google reports the day's budget as used
+//! up.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use google_client::GoogleClient;
+//! use scryforge_provider_core::auth::MockTokenFetcher;
+//! use std::sync::Arc;
+//!
+//! # #[derive(serde::Deserialize)]
+//! # struct Subscription { id: String }
+//! # async fn example() -> Result<(), google_client::GoogleError> {
+//! let tokens = Arc::new(MockTokenFetcher::empty()
+//!     .with_token("youtube".to_string(), "personal".to_string(), "token123".to_string()));
+//! let google = GoogleClient::new(
+//!     tokens,
+//!     "youtube",
+//!     "personal".to_string(),
+//!     "https://www.googleapis.com/youtube/v3".to_string(),
+//! )
+//! .with_daily_quota(10_000);
+//!
+//! let subscriptions: Vec<Subscription> = google
+//!     .get_all("/subscriptions", &[("part", "snippet"), ("mine", "true")], 1, "list subscriptions")
+//!     .await?;
+//! println!("{} units spent today", google.quota().spent);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod quota;
+
+use std::sync::Arc;
+
+use reqwest::{Client, Response, StatusCode};
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::StreamError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use quota::{QuotaTracker, QuotaUsage};
+pub use reqwest::Method;
+
+#[derive(Debug, Error)]
+pub enum GoogleError {
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited: retry after {0} seconds")]
+    RateLimited(u64),
+
+    /// The project's daily quota is used up until it resets.
+    #[error("Google API quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("API request failed: {0}")]
+    ApiRequest(String),
+
+    /// A server error that may pass on retry.
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Failed to parse response: {0}")]
+    Parse(String),
+}
+
+impl From<GoogleError> for StreamError {
+    fn from(err: GoogleError) -> Self {
+        match err {
+            GoogleError::Auth(msg) => StreamError::AuthRequired(msg),
+            GoogleError::RateLimited(secs) => StreamError::RateLimited(secs),
+            GoogleError::QuotaExceeded(msg) => StreamError::Provider(msg),
+            GoogleError::ApiRequest(msg) => StreamError::Provider(msg),
+            GoogleError::Unavailable(msg) => StreamError::Transient(msg),
+            GoogleError::Http(e) => StreamError::Network(e.to_string()),
+            GoogleError::Parse(msg) => StreamError::Internal(msg),
+        }
+    }
+}
+
+/// One page of a Google list response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Page<T> {
+    /// Missing when a list is empty
+    #[serde(default = "Vec::new")]
+    pub items: Vec<T>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
+
+/// Seconds to wait when a throttled response has no `Retry-After`.
+const DEFAULT_RETRY_AFTER: u64 = 60;
+
+/// Whether a 403 body reports the daily quota as spent. Per-second
+/// `rateLimitExceeded` errors clear up on their own and are not counted.
+fn is_quota_error(body: &str) -> bool {
+    body.contains("\"quotaExceeded\"") || body.contains("\"dailyLimitExceeded\"")
+}
+
+/// Authenticated Google API client for one Sigilforge service and account.
+///
+/// Clones share one quota count.
+#[derive(Clone)]
+pub struct GoogleClient {
+    token_fetcher: Arc<dyn TokenFetcher>,
+    service_id: &'static str,
+    account: String,
+    client: Client,
+    base_url: String,
+    quota: Arc<QuotaTracker>,
+}
+
+impl GoogleClient {
+    /// Create a client using the token of `service_id`/`account`, with no
+    /// known daily budget.
+    pub fn new(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        service_id: &'static str,
+        account: String,
+        base_url: String,
+    ) -> Self {
+        Self {
+            token_fetcher,
+            service_id,
+            account,
+            client: Client::new(),
+            base_url,
+            quota: Arc::new(QuotaTracker::new(None)),
+        }
+    }
+
+    /// Count quota against a daily budget of `units`.
+    pub fn with_daily_quota(mut self, units: u32) -> Self {
+        self.quota = Arc::new(QuotaTracker::new(Some(units)));
+        self
+    }
+
+    /// Send requests with `client`, such as one going through a proxy.
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Quota spent today.
+    pub fn quota(&self) -> QuotaUsage {
+        self.quota.usage()
+    }
+
+    /// Fetch a fresh access token.
+    pub async fn token(&self) -> Result<String, GoogleError> {
+        self.token_fetcher
+            .fetch_token(self.service_id, &self.account)
+            .await
+            .map_err(|e| GoogleError::Auth(e.to_string()))
+    }
+
+    /// Send a request costing `cost` quota units, failing unless it
+    /// succeeds. `what` completes the "Failed to ..." error message.
+    pub async fn send<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        body: Option<&B>,
+        cost: u32,
+        what: &str,
+    ) -> Result<Response, GoogleError> {
+        if self.quota.usage().exhausted {
+            return Err(GoogleError::QuotaExceeded(format!(
+                "Failed to {}: the daily quota is used up until it resets",
+                what
+            )));
+        }
+
+        let token = self.token().await?;
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, endpoint))
+            .bearer_auth(&token)
+            .query(params);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        // Google charges failed requests too
+        self.quota.spend(cost);
+        let response = request.send().await?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(GoogleError::Auth("Invalid or expired token".to_string()));
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(DEFAULT_RETRY_AFTER);
+            return Err(GoogleError::RateLimited(retry_after));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Failed to {}: {} - {}", what, status, body);
+            if status == StatusCode::FORBIDDEN && is_quota_error(&body) {
+                self.quota.exhaust();
+                return Err(GoogleError::QuotaExceeded(message));
+            }
+            if status.is_server_error() {
+                return Err(GoogleError::Unavailable(message));
+            }
+            return Err(GoogleError::ApiRequest(message));
+        }
+        Ok(response)
+    }
+
+    /// Send a request and parse the JSON response.
+    pub async fn send_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        body: Option<&B>,
+        cost: u32,
+        what: &str,
+    ) -> Result<T, GoogleError> {
+        self.send(method, endpoint, params, body, cost, what)
+            .await?
+            .json()
+            .await
+            .map_err(|e| GoogleError::Parse(e.to_string()))
+    }
+
+    /// GET a JSON resource.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        cost: u32,
+        what: &str,
+    ) -> Result<T, GoogleError> {
+        self.send_json(Method::GET, endpoint, params, None::<&()>, cost, what)
+            .await
+    }
+
+    /// GET every page of a list, following `nextPageToken`. Each page costs
+    /// `cost` units.
+    pub async fn get_all<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        cost: u32,
+        what: &str,
+    ) -> Result<Vec<T>, GoogleError> {
+        let mut all = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut page_params = params.to_vec();
+            if let Some(ref token) = page_token {
+                page_params.push(("pageToken", token.as_str()));
+            }
+            let page: Page<T> = self.get(endpoint, &page_params, cost, what).await?;
+            all.extend(page.items);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(all),
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Daily quota accounting.
+//!
+//! Google APIs such as the YouTube Data API charge every request a number of
+//! units against a per-project daily budget; a channel search costs 100, a
+//! playlist listing 1. [`QuotaTracker`] adds up what this client spent, and
+//! remembers when Google reports the budget as used up so later requests can
+//! fail without a round trip. The budget is shared with any other client of
+//! the same project, so the count is a lower bound.
+//!
+//! Quotas reset at midnight Pacific time. The tracker uses standard time
+//! (UTC-8) all year, so during daylight saving time it starts the new day an
+//! hour late.
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Offset of Pacific standard time from UTC, in hours.
+const RESET_OFFSET_HOURS: i64 = -8;
+
+/// Usage of the current quota day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// Units this client spent today
+    pub spent: u32,
+    /// Daily budget, when known
+    pub limit: Option<u32>,
+    /// Whether Google reported the budget as used up today
+    pub exhausted: bool,
+}
+
+impl QuotaUsage {
+    /// Units left of the budget, as far as this client knows.
+    pub fn remaining(&self) -> Option<u32> {
+        if self.exhausted {
+            return Some(0);
+        }
+        self.limit.map(|limit| limit.saturating_sub(self.spent))
+    }
+}
+
+#[derive(Debug)]
+struct QuotaDay {
+    day: NaiveDate,
+    spent: u32,
+    exhausted: bool,
+}
+
+/// Units spent against a daily quota, reset when the quota day changes.
+#[derive(Debug)]
+pub struct QuotaTracker {
+    limit: Option<u32>,
+    today: Mutex<QuotaDay>,
+}
+
+impl QuotaTracker {
+    pub fn new(limit: Option<u32>) -> Self {
+        Self {
+            limit,
+            today: Mutex::new(QuotaDay {
+                day: quota_day(Utc::now()),
+                spent: 0,
+                exhausted: false,
+            }),
+        }
+    }
+
+    /// Record a request costing `units`.
+    pub fn spend(&self, units: u32) {
+        self.spend_at(units, Utc::now());
+    }
+
+    /// Record that Google rejected a request for lack of quota.
+    pub fn exhaust(&self) {
+        self.with_today(Utc::now(), |today| today.exhausted = true);
+    }
+
+    pub fn usage(&self) -> QuotaUsage {
+        self.usage_at(Utc::now())
+    }
+
+    fn spend_at(&self, units: u32, now: DateTime<Utc>) {
+        self.with_today(now, |today| today.spent = today.spent.saturating_add(units));
+    }
+
+    fn usage_at(&self, now: DateTime<Utc>) -> QuotaUsage {
+        self.with_today(now, |today| QuotaUsage {
+            spent: today.spent,
+            limit: self.limit,
+            exhausted: today.exhausted,
+        })
+    }
+
+    fn with_today<R>(&self, now: DateTime<Utc>, f: impl FnOnce(&mut QuotaDay) -> R) -> R {
+        let mut today = self.today.lock().unwrap();
+        let day = quota_day(now);
+        if today.day != day {
+            *today = QuotaDay {
+                day,
+                spent: 0,
+                exhausted: false,
+            };
+        }
+        f(&mut today)
+    }
+}
+
+/// The quota day `now` falls in.
+fn quota_day(now: DateTime<Utc>) -> NaiveDate {
+    (now + Duration::hours(RESET_OFFSET_HOURS)).date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn usage_resets_at_pacific_midnight() {
+        let quota = QuotaTracker::new(Some(10_000));
+        quota.spend_at(100, at("2024-03-02T07:30:00Z"));
+        quota.spend_at(1, at("2024-03-02T07:59:00Z"));
+        assert_eq!(quota.usage_at(at("2024-03-02T07:59:30Z")).spent, 101);
+
+        let usage = quota.usage_at(at("2024-03-02T08:00:00Z"));
+        assert_eq!(usage.spent, 0);
+        assert_eq!(usage.remaining(), Some(10_000));
+    }
+
+    #[test]
+    fn exhausted_quota_has_nothing_left() {
+        let quota = QuotaTracker::new(Some(10_000));
+        quota.spend(50);
+        quota.exhaust();
+
+        let usage = quota.usage();
+        assert!(usage.exhausted);
+        assert_eq!(usage.remaining(), Some(0));
+        assert_eq!(QuotaTracker::new(None).usage().remaining(), None);
+    }
+}
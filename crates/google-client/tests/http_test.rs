@@ -0,0 +1,132 @@
+//! Mock-server tests for the Google client's paging, error mapping, and quota
+//! tracking.
+
+use std::sync::Arc;
+
+use google_client::{GoogleClient, GoogleError, Method};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::testing::{fixture, MockApi};
+use serde_json::Value;
+
+fn client_for(api: &MockApi) -> GoogleClient {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "youtube".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    GoogleClient::new(
+        Arc::new(tokens),
+        "youtube",
+        "personal".to_string(),
+        api.uri(),
+    )
+    .with_daily_quota(10_000)
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Subscription {
+    id: String,
+}
+
+#[tokio::test]
+async fn get_all_follows_page_tokens_and_counts_quota() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/subscriptions",
+        "pageToken",
+        vec![
+            (None, fixture("youtube/subscriptions_page1.json")),
+            (Some("PAGE2"), fixture("youtube/subscriptions_page2.json")),
+        ],
+    )
+    .await;
+    let google = client_for(&api);
+
+    let subscriptions: Vec<Subscription> = google
+        .get_all(
+            "/subscriptions",
+            &[("mine", "true")],
+            1,
+            "list subscriptions",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(subscriptions.len(), 3);
+    assert!(subscriptions.iter().all(|sub| !sub.id.is_empty()));
+    let quota = google.quota();
+    assert_eq!(quota.spent, 2);
+    assert_eq!(quota.remaining(), Some(9_998));
+}
+
+#[tokio::test]
+async fn spent_daily_quota_stops_further_requests() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/search",
+        403,
+        fixture("youtube/error_daily_quota.json"),
+    )
+    .await;
+    let google = client_for(&api);
+
+    let err = google
+        .get::<Value>("/search", &[], 100, "search")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GoogleError::QuotaExceeded(_)), "{:?}", err);
+
+    let err = google
+        .send(
+            Method::POST,
+            "/videos/rate",
+            &[],
+            None::<&()>,
+            50,
+            "rate video",
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GoogleError::QuotaExceeded(_)), "{:?}", err);
+    assert_eq!(api.request_count().await, 1);
+    assert!(google.quota().exhausted);
+    assert_eq!(google.quota().remaining(), Some(0));
+}
+
+#[tokio::test]
+async fn per_second_limits_do_not_spend_the_day() {
+    let api = MockApi::start().await;
+    api.mock_status("GET", "/videos", 403, fixture("youtube/error_quota.json"))
+        .await;
+    let google = client_for(&api);
+
+    let err = google
+        .get::<Value>("/videos", &[], 1, "fetch videos")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GoogleError::ApiRequest(_)), "{:?}", err);
+    assert!(err
+        .to_string()
+        .contains("Failed to fetch videos: 403 Forbidden"));
+    assert!(!google.quota().exhausted);
+}
+
+#[tokio::test]
+async fn rejected_token_and_throttling_are_mapped() {
+    let api = MockApi::start().await;
+    api.mock_unauthorized("/channels").await;
+    api.mock_rate_limited("/playlists", 30).await;
+    let google = client_for(&api);
+
+    let err = google
+        .get::<Value>("/channels", &[], 1, "fetch channel")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GoogleError::Auth(_)), "{:?}", err);
+    let err = google
+        .get::<Value>("/playlists", &[("mine", "true")], 1, "list playlists")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GoogleError::RateLimited(30)), "{:?}", err);
+}
@@ -0,0 +1,144 @@
+//! JSON batching: several Graph requests in one `POST /$batch`.
+//!
+//! Graph accepts up to [`MAX_BATCH_SIZE`] requests per batch and answers each
+//! with its own status, so one failing request doesn't fail the others.
+//! [`GraphClient::batch`] splits longer lists into several batches and
+//! returns the responses in request order; [`BatchResponse::into_result`]
+//! turns each into a value or the [`GraphError`] a single request would have
+//! given.
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{failed, throttled, GraphClient, GraphError, Method};
+
+/// Most requests Graph takes in one batch.
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// One request of a batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchRequest {
+    /// Matches the request to its response; unique within the batch.
+    pub id: String,
+    pub method: String,
+    /// Path relative to the API version, e.g. `/me/todo/lists`.
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+impl BatchRequest {
+    pub fn new(id: impl Into<String>, method: Method, url: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            method: method.as_str().to_string(),
+            url: url.into(),
+            body: None,
+            headers: HashMap::new(),
+        }
+    }
+
+    pub fn get(id: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(id, Method::GET, url)
+    }
+
+    /// Send a JSON body with the request.
+    pub fn with_body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self.headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        self
+    }
+}
+
+/// Graph's answer to one request of a batch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchResponse {
+    pub id: String,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: Value,
+}
+
+impl BatchResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse the body, or fail as the request would have on its own. `what`
+    /// completes the "Failed to ..." error message.
+    pub fn into_result<T: DeserializeOwned>(self, what: &str) -> Result<T, GraphError> {
+        if let Some(err) = throttled(self.status, self.header("retry-after")) {
+            return Err(err);
+        }
+        if self.status == 410 {
+            return Err(GraphError::DeltaExpired(format!("{}: {}", what, self.body)));
+        }
+        if !self.is_success() {
+            let status = reqwest::StatusCode::from_u16(self.status)
+                .map(|status| status.to_string())
+                .unwrap_or_else(|_| self.status.to_string());
+            return Err(failed(self.status, what, status, self.body));
+        }
+        Ok(serde_json::from_value(self.body)?)
+    }
+}
+
+#[derive(Deserialize)]
+struct BatchEnvelope {
+    responses: Vec<BatchResponse>,
+}
+
+impl GraphClient {
+    /// Send `requests` as JSON batches of up to [`MAX_BATCH_SIZE`].
+    ///
+    /// Returns one response per request, in the order given. Only failures of
+    /// a whole batch, such as a rejected token, are errors here.
+    pub async fn batch(
+        &self,
+        requests: Vec<BatchRequest>,
+    ) -> Result<Vec<BatchResponse>, GraphError> {
+        let mut responses = Vec::with_capacity(requests.len());
+        for chunk in requests.chunks(MAX_BATCH_SIZE) {
+            let envelope: BatchEnvelope = self
+                .send_json(
+                    Method::POST,
+                    "/$batch",
+                    Some(&serde_json::json!({ "requests": chunk })),
+                    "send batch request",
+                )
+                .await?;
+
+            // Graph may answer in any order
+            let mut by_id: HashMap<String, BatchResponse> = envelope
+                .responses
+                .into_iter()
+                .map(|response| (response.id.clone(), response))
+                .collect();
+            for request in chunk {
+                let response = by_id.remove(&request.id).ok_or_else(|| {
+                    GraphError::InvalidResponse(format!(
+                        "Batch response is missing request {}",
+                        request.id
+                    ))
+                })?;
+                responses.push(response);
+            }
+        }
+        Ok(responses)
+    }
+}
@@ -0,0 +1,287 @@
+//! # graph-client
+//!
+//! Microsoft Graph HTTP client shared by the Scryforge Microsoft providers
+//! (To Do, Outlook Mail, and the ones to come).
+//!
+//! [`GraphClient`] fetches a token from Sigilforge for its service on every
+//! request, maps rejected tokens and throttling to [`GraphError`], follows
+//! `@odata.nextLink` paging, and sends JSON `$batch` requests (see
+//! [`batch`]). Each provider passes its own service ID, so mail and To Do can
+//! be authorized with different scopes.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use graph_client::{GraphClient, GRAPH_BASE_URL};
+//! use scryforge_provider_core::auth::MockTokenFetcher;
+//! use std::sync::Arc;
+//!
+//! # #[derive(serde::Deserialize)]
+//! # struct TaskList { id: String }
+//! # async fn example() -> Result<(), graph_client::GraphError> {
+//! let tokens = Arc::new(MockTokenFetcher::empty()
+//!     .with_token("mstodo".to_string(), "personal".to_string(), "token123".to_string()));
+//! let graph = GraphClient::new(tokens, "mstodo", "personal".to_string(), GRAPH_BASE_URL.to_string());
+//!
+//! let lists: Vec<TaskList> = graph.get_all("/me/todo/lists", "fetch task lists").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod batch;
+
+use std::sync::Arc;
+
+use reqwest::{Client, RequestBuilder, Response};
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::StreamError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub use batch::{BatchRequest, BatchResponse};
+pub use reqwest::Method;
+
+/// Graph endpoint of the v1.0 API.
+pub const GRAPH_BASE_URL: &str = "https://graph.microsoft.com/v1.0";
+
+#[derive(Debug, Error)]
+pub enum GraphError {
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited: retry after {0} seconds")]
+    RateLimited(u64),
+
+    #[error("API request failed: {0}")]
+    ApiRequest(String),
+
+    /// A server error that may pass on retry.
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("JSON serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    /// The delta link is too old; start over with a fresh delta query.
+    #[error("Delta token expired: {0}")]
+    DeltaExpired(String),
+}
+
+impl From<GraphError> for StreamError {
+    fn from(err: GraphError) -> Self {
+        match err {
+            GraphError::Auth(msg) => StreamError::AuthRequired(msg),
+            GraphError::RateLimited(secs) => StreamError::RateLimited(secs),
+            GraphError::Http(e) => StreamError::Network(e.to_string()),
+            GraphError::ApiRequest(msg) => StreamError::Provider(msg),
+            GraphError::Unavailable(msg) => StreamError::Transient(msg),
+            GraphError::Json(e) => StreamError::Internal(e.to_string()),
+            GraphError::InvalidResponse(msg) => StreamError::Internal(msg),
+            GraphError::DeltaExpired(msg) => StreamError::Provider(msg),
+        }
+    }
+}
+
+/// One page of a Graph collection.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Page<T> {
+    pub value: Vec<T>,
+    #[serde(rename = "@odata.nextLink")]
+    pub next_link: Option<String>,
+    /// Set on the last page of a delta query; fetch it later for the changes
+    /// since.
+    #[serde(rename = "@odata.deltaLink")]
+    pub delta_link: Option<String>,
+}
+
+/// Seconds to wait when a throttled response has no `Retry-After`.
+const DEFAULT_RETRY_AFTER: u64 = 60;
+
+/// Classify a response status as a rejected token or throttling.
+///
+/// Graph throttles with `429`, and sometimes with `503` or `504` carrying a
+/// `Retry-After`; without one those are ordinary server errors.
+fn throttled(status: u16, retry_after: Option<&str>) -> Option<GraphError> {
+    let retry_after = retry_after.and_then(|s| s.trim().parse::<u64>().ok());
+    match (status, retry_after) {
+        (401, _) => Some(GraphError::Auth("Access token was rejected".to_string())),
+        (429, secs) => Some(GraphError::RateLimited(secs.unwrap_or(DEFAULT_RETRY_AFTER))),
+        (503 | 504, Some(secs)) => Some(GraphError::RateLimited(secs)),
+        _ => None,
+    }
+}
+
+/// The error of a failed request that isn't throttled: server errors may
+/// pass, others won't.
+fn failed(
+    status: u16,
+    what: &str,
+    status_text: impl std::fmt::Display,
+    body: impl std::fmt::Display,
+) -> GraphError {
+    let message = format!("Failed to {}: {} - {}", what, status_text, body);
+    if (500..600).contains(&status) {
+        GraphError::Unavailable(message)
+    } else {
+        GraphError::ApiRequest(message)
+    }
+}
+
+/// Map Graph's rejected-token and throttling responses to their own errors.
+pub fn check_throttled(response: &Response) -> Result<(), GraphError> {
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok());
+    match throttled(response.status().as_u16(), retry_after) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Authenticated Microsoft Graph client for one Sigilforge service and
+/// account.
+#[derive(Clone)]
+pub struct GraphClient {
+    token_fetcher: Arc<dyn TokenFetcher>,
+    service_id: &'static str,
+    account: String,
+    client: Client,
+    base_url: String,
+}
+
+impl GraphClient {
+    /// Create a client using the token of `service_id`/`account`.
+    pub fn new(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        service_id: &'static str,
+        account: String,
+        base_url: String,
+    ) -> Self {
+        Self {
+            token_fetcher,
+            service_id,
+            account,
+            client: Client::new(),
+            base_url,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Fetch a fresh access token.
+    pub async fn token(&self) -> Result<String, GraphError> {
+        self.token_fetcher
+            .fetch_token(self.service_id, &self.account)
+            .await
+            .map_err(|e| GraphError::Auth(e.to_string()))
+    }
+
+    /// `path` relative to the base URL, or an absolute URL such as a
+    /// `nextLink` unchanged.
+    fn url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.base_url, path)
+        }
+    }
+
+    /// Send a request, failing unless it succeeds. `what` completes the
+    /// "Failed to ..." error message.
+    pub async fn send<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        what: &str,
+    ) -> Result<Response, GraphError> {
+        let token = self.token().await?;
+        let mut request = self
+            .client
+            .request(method, self.url(path))
+            .bearer_auth(&token);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        Self::execute(request, what).await
+    }
+
+    async fn execute(request: RequestBuilder, what: &str) -> Result<Response, GraphError> {
+        let response = request.send().await?;
+        check_throttled(&response)?;
+
+        if response.status() == reqwest::StatusCode::GONE {
+            let body = response.text().await.unwrap_or_default();
+            return Err(GraphError::DeltaExpired(format!("{}: {}", what, body)));
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(failed(status.as_u16(), what, status, body));
+        }
+        Ok(response)
+    }
+
+    /// Send a request and parse the JSON response.
+    pub async fn send_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        what: &str,
+    ) -> Result<T, GraphError> {
+        Ok(self.send(method, path, body, what).await?.json().await?)
+    }
+
+    /// GET a JSON resource.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str, what: &str) -> Result<T, GraphError> {
+        self.send_json(Method::GET, path, None::<&()>, what).await
+    }
+
+    /// GET every page of a collection, following `@odata.nextLink`.
+    pub async fn get_all<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        what: &str,
+    ) -> Result<Vec<T>, GraphError> {
+        let mut url = path.to_string();
+        let mut all = Vec::new();
+        loop {
+            let page: Page<T> = self.get(&url, what).await?;
+            all.extend(page.value);
+            match page.next_link {
+                Some(next) => url = next,
+                None => return Ok(all),
+            }
+        }
+    }
+
+    /// GET one page of a delta query, asking for up to `page_size` entries.
+    ///
+    /// Delta queries size pages with a `Prefer` header rather than `$top`.
+    /// A [`GraphError::DeltaExpired`] means the link can't be resumed.
+    pub async fn get_delta_page<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        page_size: u32,
+        what: &str,
+    ) -> Result<Page<T>, GraphError> {
+        let token = self.token().await?;
+        let request = self
+            .client
+            .get(self.url(url))
+            .bearer_auth(&token)
+            .header("Prefer", format!("odata.maxpagesize={}", page_size));
+        Ok(Self::execute(request, what).await?.json().await?)
+    }
+}
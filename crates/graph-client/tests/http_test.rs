@@ -0,0 +1,190 @@
+//! Mock-server tests for the Graph client's paging, throttling, and batching.
+
+use std::sync::Arc;
+
+use graph_client::{BatchRequest, GraphClient, GraphError, Method, Page};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::testing::wiremock::matchers::{header, method, path};
+use scryforge_provider_core::testing::wiremock::{Mock, Request, ResponseTemplate};
+use scryforge_provider_core::testing::MockApi;
+use serde_json::{json, Value};
+
+fn client_for(api: &MockApi) -> GraphClient {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "mstodo".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    GraphClient::new(
+        Arc::new(tokens),
+        "mstodo",
+        "personal".to_string(),
+        api.uri(),
+    )
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Named {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
+
+#[tokio::test]
+async fn get_all_follows_next_link() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/me/todo/lists",
+        "$skiptoken",
+        vec![
+            (None, api.fixture("graph/todo_lists_page1.json")),
+            (Some("page2"), api.fixture("graph/todo_lists_page2.json")),
+        ],
+    )
+    .await;
+
+    let lists: Vec<Named> = client_for(&api)
+        .get_all("/me/todo/lists", "fetch task lists")
+        .await
+        .unwrap();
+
+    let names: Vec<&str> = lists.iter().map(|l| l.display_name.as_str()).collect();
+    assert_eq!(names, vec!["Tasks", "Groceries"]);
+}
+
+#[tokio::test]
+async fn service_unavailable_with_retry_after_is_throttling() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/busy"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "12"))
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/me/down"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(api.server())
+        .await;
+    let graph = client_for(&api);
+
+    let err = graph.get::<Value>("/me/busy", "fetch").await.unwrap_err();
+    assert!(matches!(err, GraphError::RateLimited(12)), "{:?}", err);
+    let err = graph.get::<Value>("/me/down", "fetch").await.unwrap_err();
+    assert!(matches!(err, GraphError::Unavailable(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn gone_delta_link_has_expired() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/messages/delta"))
+        .and(header("prefer", "odata.maxpagesize=10"))
+        .respond_with(ResponseTemplate::new(410))
+        .mount(api.server())
+        .await;
+
+    let err = client_for(&api)
+        .get_delta_page::<Value>("/me/messages/delta", 10, "sync")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GraphError::DeltaExpired(_)), "{:?}", err);
+}
+
+/// Answer a batch in reverse order, failing the requests for `/missing` and
+/// throttling those for `/busy`.
+fn answer_batch(request: &Request) -> ResponseTemplate {
+    let batch: Value = serde_json::from_slice(&request.body).unwrap();
+    let responses: Vec<Value> = batch["requests"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .rev()
+        .map(|request| {
+            let url = request["url"].as_str().unwrap();
+            match url {
+                "/missing" => json!({ "id": request["id"], "status": 404, "body": {} }),
+                "/busy" => json!({
+                    "id": request["id"],
+                    "status": 429,
+                    "headers": { "Retry-After": "3" },
+                }),
+                _ => json!({
+                    "id": request["id"],
+                    "status": 200,
+                    "body": { "value": [{ "displayName": url }] },
+                }),
+            }
+        })
+        .collect();
+    ResponseTemplate::new(200).set_body_json(json!({ "responses": responses }))
+}
+
+#[tokio::test]
+async fn batch_splits_and_keeps_request_order() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/$batch"))
+        .respond_with(answer_batch)
+        .expect(2)
+        .mount(api.server())
+        .await;
+
+    let requests: Vec<BatchRequest> = (0..25)
+        .map(|i| BatchRequest::get(i.to_string(), format!("/lists/{}", i)))
+        .collect();
+    let responses = client_for(&api).batch(requests).await.unwrap();
+
+    assert_eq!(responses.len(), 25);
+    let urls: Vec<String> = responses
+        .into_iter()
+        .map(|response| {
+            let page: Page<Named> = response.into_result("fetch").unwrap();
+            page.value[0].display_name.clone()
+        })
+        .collect();
+    let expected: Vec<String> = (0..25).map(|i| format!("/lists/{}", i)).collect();
+    assert_eq!(urls, expected);
+}
+
+#[tokio::test]
+async fn batch_responses_fail_individually() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/$batch"))
+        .respond_with(answer_batch)
+        .mount(api.server())
+        .await;
+
+    let requests = vec![
+        BatchRequest::get("a", "/ok"),
+        BatchRequest::get("b", "/missing"),
+        BatchRequest::new("c", Method::PATCH, "/busy").with_body(json!({ "x": 1 })),
+    ];
+    let mut responses = client_for(&api).batch(requests).await.unwrap().into_iter();
+
+    assert!(responses.next().unwrap().into_result::<Value>("a").is_ok());
+    let err = responses
+        .next()
+        .unwrap()
+        .into_result::<Value>("fetch b")
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to fetch b: 404 Not Found"));
+    let err = responses
+        .next()
+        .unwrap()
+        .into_result::<Value>("c")
+        .unwrap_err();
+    assert!(matches!(err, GraphError::RateLimited(3)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn rejected_token_fails_the_whole_batch() {
+    let api = MockApi::start().await;
+    api.mock_status("POST", "/$batch", 401, json!({ "error": {} }))
+        .await;
+
+    let err = client_for(&api)
+        .batch(vec![BatchRequest::get("1", "/me")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GraphError::Auth(_)), "{:?}", err);
+}
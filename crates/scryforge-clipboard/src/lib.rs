@@ -0,0 +1,201 @@
+//! # scryforge-clipboard
+//!
+//! Clipboard access shared by the Scryforge TUI and the `scry` CLI.
+//!
+//! Providers' `CopyLink` actions only hand the link back to the client; this
+//! crate does the copying. [`Clipboard`] writes to the system clipboard with
+//! `arboard`. Over SSH, or when no system clipboard is reachable (a headless
+//! session, a console without X11 or Wayland), it sends the text to the
+//! terminal as an OSC 52 escape sequence instead, which most terminal
+//! emulators put on the clipboard of the machine they run on. Inside tmux the
+//! sequence is wrapped so tmux passes it through.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use scryforge_clipboard::Clipboard;
+//!
+//! let mut clipboard = Clipboard::new();
+//! match clipboard.copy("https://example.com/post") {
+//!     Ok(backend) => println!("Copied with {}", backend),
+//!     Err(e) => eprintln!("{}", e),
+//! }
+//! ```
+
+use std::fmt;
+use std::io::Write;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    #[error("Failed to copy to the system clipboard: {0}")]
+    System(String),
+
+    #[error("Failed to write to the terminal: {0}")]
+    Terminal(#[from] std::io::Error),
+}
+
+/// How text reached the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The system clipboard of this machine
+    System,
+    /// An OSC 52 escape sequence to the terminal
+    Osc52,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::System => f.write_str("the system clipboard"),
+            Backend::Osc52 => f.write_str("OSC 52"),
+        }
+    }
+}
+
+/// A clipboard for copying text.
+///
+/// On X11 and Wayland the copied text is served by this process, so it stays
+/// on the clipboard while the `Clipboard` is alive; short-lived programs use
+/// [`Clipboard::copy_and_wait`].
+pub struct Clipboard {
+    system: Option<arboard::Clipboard>,
+    passthrough: bool,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clipboard {
+    /// Open the system clipboard, or fall back to OSC 52 when running over
+    /// SSH or when it cannot be opened.
+    pub fn new() -> Self {
+        let system = if in_ssh_session() {
+            None
+        } else {
+            arboard::Clipboard::new().ok()
+        };
+        Self {
+            system,
+            passthrough: in_tmux(),
+        }
+    }
+
+    /// Always copy with OSC 52.
+    pub fn osc52() -> Self {
+        Self {
+            system: None,
+            passthrough: in_tmux(),
+        }
+    }
+
+    /// The backend [`copy`](Self::copy) tries first.
+    pub fn backend(&self) -> Backend {
+        if self.system.is_some() {
+            Backend::System
+        } else {
+            Backend::Osc52
+        }
+    }
+
+    /// Put `text` on the clipboard, returning how it got there.
+    pub fn copy(&mut self, text: &str) -> Result<Backend, ClipboardError> {
+        if let Some(system) = self.system.as_mut() {
+            if system.set_text(text).is_ok() {
+                return Ok(Backend::System);
+            }
+        }
+        self.copy_osc52(text)
+    }
+
+    /// Like [`copy`](Self::copy), but on Linux keep serving the text until
+    /// another program takes over the clipboard, which clipboard managers do
+    /// right away. Blocks until then.
+    pub fn copy_and_wait(&mut self, text: &str) -> Result<Backend, ClipboardError> {
+        #[cfg(target_os = "linux")]
+        if let Some(system) = self.system.as_mut() {
+            use arboard::SetExtLinux;
+            return system
+                .set()
+                .wait()
+                .text(text)
+                .map(|()| Backend::System)
+                .map_err(|e| ClipboardError::System(e.to_string()));
+        }
+        self.copy(text)
+    }
+
+    fn copy_osc52(&self, text: &str) -> Result<Backend, ClipboardError> {
+        let sequence = osc52_sequence(text, self.passthrough);
+        let mut terminal = terminal()?;
+        terminal.write_all(sequence.as_bytes())?;
+        terminal.flush()?;
+        Ok(Backend::Osc52)
+    }
+}
+
+/// The OSC 52 sequence setting the clipboard to `text`, wrapped in a tmux
+/// passthrough sequence when `passthrough` is set.
+pub fn osc52_sequence(text: &str, passthrough: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if passthrough {
+        // tmux forwards the contents of `ESC P tmux; ... ESC \` with ESC doubled
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+fn in_ssh_session() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+fn in_tmux() -> bool {
+    std::env::var_os("TMUX").is_some()
+}
+
+/// The controlling terminal, so the sequence reaches it even when stdout is
+/// redirected.
+fn terminal() -> std::io::Result<Box<dyn Write>> {
+    #[cfg(unix)]
+    if let Ok(tty) = std::fs::OpenOptions::new().write(true).open("/dev/tty") {
+        return Ok(Box::new(tty));
+    }
+    Ok(Box::new(std::io::stdout()))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence_encodes_text() {
+        assert_eq!(
+            osc52_sequence("https://example.com", false),
+            "\x1b]52;c;aHR0cHM6Ly9leGFtcGxlLmNvbQ==\x07"
+        );
+    }
+
+    #[test]
+    fn test_osc52_sequence_passes_through_tmux() {
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_forced_osc52_skips_the_system_clipboard() {
+        assert_eq!(Clipboard::osc52().backend(), Backend::Osc52);
+    }
+}
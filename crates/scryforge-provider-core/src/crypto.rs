@@ -0,0 +1,168 @@
+//! At-rest encryption for locally stored provider data.
+//!
+//! [`StoreCipher`] seals data with XChaCha20-Poly1305 under a 32-byte key.
+//! Sealed values are text, `enc1:` followed by the base64-encoded nonce and
+//! ciphertext, so they fit in the same TEXT columns and JSON files as the
+//! plaintext they replace. Every value gets a fresh random nonce.
+//!
+//! The key itself is never stored next to the data; the daemon fetches it
+//! from the keyring through Sigilforge and hands a cipher to whatever
+//! persists provider data.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use thiserror::Error;
+
+/// Prefix marking a sealed value.
+pub const SEALED_PREFIX: &str = "enc1:";
+
+/// Length of an encryption key in bytes.
+pub const KEY_LEN: usize = 32;
+
+const NONCE_LEN: usize = 24;
+
+#[derive(Error, Debug)]
+pub enum CipherError {
+    #[error("Encryption key must be {KEY_LEN} base64-encoded bytes")]
+    InvalidKey,
+
+    #[error("Value is not sealed")]
+    NotSealed,
+
+    #[error("Sealed value is corrupt or was sealed with a different key")]
+    Corrupt,
+}
+
+/// Seals and opens values with one encryption key.
+#[derive(Clone)]
+pub struct StoreCipher {
+    cipher: XChaCha20Poly1305,
+}
+
+impl std::fmt::Debug for StoreCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreCipher").finish_non_exhaustive()
+    }
+}
+
+impl StoreCipher {
+    /// Create a cipher from a raw key.
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Create a cipher from a base64-encoded key, as stored in the keyring.
+    pub fn from_base64(encoded: &str) -> Result<Self, CipherError> {
+        let key: [u8; KEY_LEN] = STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(CipherError::InvalidKey)?;
+        Ok(Self::new(&key))
+    }
+
+    /// Generate a random base64-encoded key.
+    pub fn generate_key() -> String {
+        STANDARD.encode(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    /// Whether `value` was produced by [`StoreCipher::seal`].
+    pub fn is_sealed(value: &str) -> bool {
+        value.starts_with(SEALED_PREFIX)
+    }
+
+    /// Encrypt `plaintext`.
+    pub fn seal(&self, plaintext: &[u8]) -> String {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .expect("XChaCha20-Poly1305 encryption does not fail for in-memory buffers");
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        format!("{}{}", SEALED_PREFIX, STANDARD.encode(sealed))
+    }
+
+    /// Decrypt a value produced by [`StoreCipher::seal`].
+    pub fn open(&self, sealed: &str) -> Result<Vec<u8>, CipherError> {
+        let encoded = sealed
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or(CipherError::NotSealed)?;
+        let bytes = STANDARD.decode(encoded).map_err(|_| CipherError::Corrupt)?;
+        if bytes.len() < NONCE_LEN {
+            return Err(CipherError::Corrupt);
+        }
+
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CipherError::Corrupt)
+    }
+
+    /// Encrypt a string.
+    pub fn seal_str(&self, plaintext: &str) -> String {
+        self.seal(plaintext.as_bytes())
+    }
+
+    /// Decrypt a value produced by [`StoreCipher::seal_str`].
+    pub fn open_str(&self, sealed: &str) -> Result<String, CipherError> {
+        String::from_utf8(self.open(sealed)?).map_err(|_| CipherError::Corrupt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let cipher = StoreCipher::from_base64(&StoreCipher::generate_key()).unwrap();
+
+        let sealed = cipher.seal_str("Quarterly numbers attached");
+        assert!(StoreCipher::is_sealed(&sealed));
+        assert!(!sealed.contains("Quarterly"));
+        assert_ne!(sealed, cipher.seal_str("Quarterly numbers attached"));
+        assert_eq!(
+            cipher.open_str(&sealed).unwrap(),
+            "Quarterly numbers attached"
+        );
+
+        assert!(matches!(
+            cipher.open_str("Quarterly numbers attached"),
+            Err(CipherError::NotSealed)
+        ));
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_key_and_tampering() {
+        let cipher = StoreCipher::new(&[7; KEY_LEN]);
+        let sealed = cipher.seal_str("secret");
+
+        let other = StoreCipher::new(&[8; KEY_LEN]);
+        assert!(matches!(other.open(&sealed), Err(CipherError::Corrupt)));
+
+        let flip = SEALED_PREFIX.len() + 40;
+        let replacement = if &sealed[flip..=flip] == "A" {
+            "B"
+        } else {
+            "A"
+        };
+        let mut tampered = sealed.clone();
+        tampered.replace_range(flip..=flip, replacement);
+        assert!(matches!(cipher.open(&tampered), Err(CipherError::Corrupt)));
+        assert!(matches!(
+            cipher.open("enc1:AAAA"),
+            Err(CipherError::Corrupt)
+        ));
+
+        assert!(matches!(
+            StoreCipher::from_base64("c2hvcnQ="),
+            Err(CipherError::InvalidKey)
+        ));
+    }
+}
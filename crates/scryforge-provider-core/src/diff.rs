@@ -0,0 +1,218 @@
+//! Reading unified diffs, for [`ItemContent::Patch`](crate::ItemContent::Patch).
+//!
+//! [`lines`] tells each line of a diff apart the way `git diff --color`
+//! does, so a viewer can highlight it; [`DiffStat`] sums up what it changes.
+//! Hunk headers (`@@ -1,4 +1,5 @@`) are followed to know where a hunk ends,
+//! so a removed line that reads `--- a` isn't taken for a file header.
+//!
+//! ```
+//! use scryforge_provider_core::diff::DiffStat;
+//!
+//! let stat = DiffStat::of("diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n-old\n+new\n+more\n");
+//! assert_eq!(stat.files, vec!["src/lib.rs"]);
+//! assert_eq!((stat.additions, stat.deletions), (2, 1));
+//! ```
+
+/// What a line of a unified diff is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine {
+    /// `diff --git`, `index`, `---` / `+++`, mode and rename lines
+    FileHeader,
+    /// `@@ -a,b +c,d @@`
+    HunkHeader,
+    Added,
+    Removed,
+    Context,
+    /// Anything outside a file: commit message, diffstat, signature
+    Other,
+}
+
+/// Each line of `diff` with what it is.
+pub fn lines(diff: &str) -> impl Iterator<Item = (DiffLine, &str)> {
+    let mut old_left = 0u32;
+    let mut new_left = 0u32;
+    let mut in_file = false;
+    diff.lines().map(move |line| {
+        if old_left > 0 || new_left > 0 {
+            let kind = match line.as_bytes().first() {
+                Some(b'+') => {
+                    new_left = new_left.saturating_sub(1);
+                    DiffLine::Added
+                }
+                Some(b'-') => {
+                    old_left = old_left.saturating_sub(1);
+                    DiffLine::Removed
+                }
+                // "\ No newline at end of file" doesn't count
+                Some(b'\\') => DiffLine::Context,
+                _ => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                    DiffLine::Context
+                }
+            };
+            return (kind, line);
+        }
+
+        if let Some((old, new)) = hunk_lengths(line) {
+            old_left = old;
+            new_left = new;
+            in_file = true;
+            return (DiffLine::HunkHeader, line);
+        }
+        if line.starts_with("diff ") || line.starts_with("--- ") || line.starts_with("+++ ") {
+            in_file = true;
+            return (DiffLine::FileHeader, line);
+        }
+        const GIT_HEADERS: [&str; 10] = [
+            "index ",
+            "new file mode",
+            "deleted file mode",
+            "old mode",
+            "new mode",
+            "similarity index",
+            "rename from",
+            "rename to",
+            "copy from",
+            "copy to",
+        ];
+        if in_file && GIT_HEADERS.iter().any(|header| line.starts_with(header)) {
+            return (DiffLine::FileHeader, line);
+        }
+        if line.starts_with("\\ ") && in_file {
+            return (DiffLine::Context, line);
+        }
+        in_file = false;
+        (DiffLine::Other, line)
+    })
+}
+
+/// The old and new line counts of a hunk header. A count left out is 1.
+fn hunk_lengths(line: &str) -> Option<(u32, u32)> {
+    let ranges = line.strip_prefix("@@ -")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(" +")?;
+    let length = |range: &str| match range.split_once(',') {
+        Some((_, length)) => length.parse().ok(),
+        None => range.parse::<u32>().ok().map(|_| 1),
+    };
+    Some((length(old)?, length(new)?))
+}
+
+/// Files, added lines, and removed lines of a diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffStat {
+    /// Paths the diff touches, in order; the new path of a renamed file
+    pub files: Vec<String>,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+impl DiffStat {
+    pub fn of(diff: &str) -> Self {
+        let mut stat = DiffStat::default();
+        let mut add_file = |path: &str| {
+            let path = path.split('\t').next().unwrap_or(path).trim();
+            let path = path
+                .strip_prefix("a/")
+                .or_else(|| path.strip_prefix("b/"))
+                .unwrap_or(path);
+            if path != "/dev/null" && !stat.files.iter().any(|file| file == path) {
+                stat.files.push(path.to_string());
+            }
+        };
+
+        let mut removed_path = None;
+        let mut counts = (0, 0);
+        for (kind, line) in lines(diff) {
+            match kind {
+                DiffLine::Added => counts.0 += 1,
+                DiffLine::Removed => counts.1 += 1,
+                DiffLine::FileHeader => {
+                    if let Some(path) = line.strip_prefix("--- ") {
+                        removed_path = Some(path);
+                    } else if let Some(path) = line.strip_prefix("+++ ") {
+                        // A deleted file is named by its old path
+                        match (path.trim(), removed_path.take()) {
+                            ("/dev/null", Some(old)) => add_file(old),
+                            (new, _) => add_file(new),
+                        }
+                    } else if let Some(paths) = line.strip_prefix("diff --git ") {
+                        // Files without a hunk, such as renames, have no ---/+++
+                        if let Some((_, new)) = paths.split_once(" b/") {
+                            add_file(new);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        stat.additions = counts.0;
+        stat.deletions = counts.1;
+        stat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = r"Fix the parser.
+
+---
+ src/a.rs | 3 ++-
+
+diff --git a/src/a.rs b/src/a.rs
+index 1111111..2222222 100644
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -1,3 +1,4 @@ fn main() {
+ keep
+--- removed line that looks like a header
++++ added line that looks like a header
++added
+ keep
+diff --git a/old.rs b/old.rs
+deleted file mode 100644
+--- a/old.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/x.rs b/y.rs
+similarity index 100%
+rename from x.rs
+rename to y.rs
+-- 
+2.43.0
+";
+
+    #[test]
+    fn test_line_kinds() {
+        let kinds: Vec<DiffLine> = lines(DIFF).map(|(kind, _)| kind).collect();
+        use DiffLine::*;
+        assert_eq!(
+            kinds,
+            vec![
+                Other, Other, Other, Other, Other, FileHeader, FileHeader, FileHeader, FileHeader,
+                HunkHeader, Context, Removed, Added, Added, Context, FileHeader, FileHeader,
+                FileHeader, FileHeader, HunkHeader, Removed, FileHeader, FileHeader, FileHeader,
+                FileHeader, Other, Other,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_stat() {
+        let stat = DiffStat::of(DIFF);
+        assert_eq!(stat.files, vec!["src/a.rs", "old.rs", "y.rs"]);
+        assert_eq!(stat.additions, 2);
+        assert_eq!(stat.deletions, 2);
+    }
+
+    #[test]
+    fn test_hunk_lengths() {
+        assert_eq!(hunk_lengths("@@ -10,7 +10,8 @@ fn x()"), Some((7, 8)));
+        assert_eq!(hunk_lengths("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(hunk_lengths("@@ -0,0 +1,3 @@"), Some((0, 3)));
+        assert_eq!(hunk_lengths("@@ nonsense"), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! iCalendar (RFC 5545) content lines, shared by providers that read
+//! calendar data.
+//!
+//! An iCalendar object is a list of content lines such as
+//! `DTSTART;TZID=Europe/Berlin:20240501T090000`, folded at 75 octets.
+//! [`unfold`] joins them back, [`Property::parse`] splits one into its name,
+//! parameters, and value, and [`Property::line`] with [`fold`] writes one.
+//! What the properties mean is up to the caller.
+//!
+//! ```
+//! use scryforge_provider_core::ical::{unfold, Property};
+//!
+//! let lines = unfold("SUMMARY:Planning\\, Q3\r\nORGANIZER;CN=\"Doe, Jane\":mailto:\r\n jane@example.com\r\n");
+//! let organizer = Property::parse(&lines[1]).unwrap();
+//! assert_eq!(organizer.param("CN"), Some("Doe, Jane"));
+//! assert_eq!(organizer.address(), Some("jane@example.com"));
+//! assert_eq!(Property::parse(&lines[0]).unwrap().text().as_deref(), Some("Planning, Q3"));
+//! ```
+
+/// Longest line, in octets, that iCalendar allows before folding.
+pub const MAX_LINE: usize = 75;
+
+/// A content line, such as `ATTENDEE;CN=Jane:mailto:jane@example.com`.
+///
+/// Names and parameter keys are upper-cased; the value is kept as written,
+/// escapes and all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    /// Parse an unfolded content line. Colons and semicolons inside quoted
+    /// parameter values don't end them.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut quoted = false;
+        let mut value_start = None;
+        let mut separators = Vec::new();
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => quoted = !quoted,
+                ';' if !quoted => separators.push(i),
+                ':' if !quoted => {
+                    value_start = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let value_start = value_start?;
+
+        let head = &line[..value_start];
+        let name_end = separators.first().copied().unwrap_or(head.len());
+        let mut params = Vec::new();
+        let mut starts = separators.iter().peekable();
+        while let Some(&start) = starts.next() {
+            let end = starts.peek().map_or(head.len(), |&&end| end);
+            if let Some((key, value)) = head[start + 1..end].split_once('=') {
+                params.push((
+                    key.to_ascii_uppercase(),
+                    value.trim_matches('"').to_string(),
+                ));
+            }
+        }
+
+        Some(Self {
+            name: head[..name_end].to_ascii_uppercase(),
+            params,
+            value: line[value_start + 1..].trim_end().to_string(),
+        })
+    }
+
+    /// The value of parameter `key`, given upper-case.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The unescaped value, or `None` if it is blank.
+    pub fn text(&self) -> Option<String> {
+        Some(unescape(&self.value)).filter(|text| !text.is_empty())
+    }
+
+    /// The address of a `mailto:` value, as in `ORGANIZER` and `ATTENDEE`.
+    pub fn address(&self) -> Option<&str> {
+        let value = self.value.trim();
+        value
+            .get(..7)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+            .map(|_| &value[7..])
+            .filter(|address| !address.is_empty())
+    }
+
+    /// Write the property back as a content line, unfolded.
+    pub fn line(&self) -> String {
+        let mut line = self.name.clone();
+        for (key, value) in &self.params {
+            if value.contains([':', ';', ',']) {
+                line.push_str(&format!(";{}=\"{}\"", key, value));
+            } else {
+                line.push_str(&format!(";{}={}", key, value));
+            }
+        }
+        line.push(':');
+        line.push_str(&self.value);
+        line
+    }
+}
+
+/// Join folded content lines, whose continuations start with a space or tab.
+pub fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Fold `line` into CRLF-terminated lines of at most [`MAX_LINE`] octets,
+/// never inside a UTF-8 sequence.
+pub fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Undo iCalendar text escaping: `\n`, `\,`, `\;`, and `\\`.
+pub fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_property() {
+        let property =
+            Property::parse("ATTENDEE;CN=\"Doe; Jane\";rsvp=TRUE:mailto:jane@example.com \r")
+                .unwrap();
+        assert_eq!(property.name, "ATTENDEE");
+        assert_eq!(property.param("CN"), Some("Doe; Jane"));
+        assert_eq!(property.param("RSVP"), Some("TRUE"));
+        assert_eq!(property.value, "mailto:jane@example.com");
+        assert_eq!(property.address(), Some("jane@example.com"));
+        assert_eq!(
+            property.line(),
+            "ATTENDEE;CN=\"Doe; Jane\";RSVP=TRUE:mailto:jane@example.com"
+        );
+
+        assert!(Property::parse("no value here").is_none());
+        let url = Property::parse("URL:https://example.com").unwrap();
+        assert_eq!(url.address(), None);
+    }
+
+    #[test]
+    fn test_fold_and_unfold() {
+        let line = format!("DESCRIPTION:{}", "ü".repeat(60));
+        let folded = fold(&line);
+        assert!(folded.lines().all(|l| l.len() <= MAX_LINE));
+        assert_eq!(unfold(&folded), vec![line]);
+        assert_eq!(
+            unfold("A:1\r\n\tcontinued\r\nB:2"),
+            vec!["A:1continued", "B:2"]
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(
+            unescape("Planning\\, Q3\\nRoom 4\\; B\\\\"),
+            "Planning, Q3\nRoom 4; B\\"
+        );
+        assert_eq!(Property::parse("SUMMARY: ").unwrap().text(), None);
+    }
+}
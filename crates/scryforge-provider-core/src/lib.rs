@@ -19,6 +19,24 @@
 //! [dependencies]
 //! scryforge-provider-core = { version = "0.1", features = ["sigilforge"] }
 //! ```
+//!
+//! ## Encryption
+//!
+//! The `encryption` feature adds the `crypto` module, whose `StoreCipher`
+//! seals provider data kept on disk, such as cached items or a local
+//! bookmarks file.
+//!
+//! ## HTTP
+//!
+//! The `http` feature adds the `http` module, whose `client_builder` sets up
+//! providers' HTTP clients with a shared user agent and timeout and the
+//! provider's proxy, including SOCKS5 proxies such as Tor.
+//!
+//! ## Testing
+//!
+//! The `test-harness` feature adds the `testing` module: a mock HTTP server
+//! with canned YouTube, Reddit, and Microsoft Graph responses for provider
+//! integration tests.
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
@@ -51,6 +69,15 @@ pub enum StreamError {
     #[error("Network error: {0}")]
     Network(String),
 
+    /// The service answered but failed in a way that may pass, such as a
+    /// `503` or a timeout on its side.
+    #[error("Temporarily unavailable: {0}")]
+    Transient(String),
+
+    /// The provider or its service can't do what was asked.
+    #[error("Not supported: {0}")]
+    NotSupported(String),
+
     #[error("Provider error: {0}")]
     Provider(String),
 
@@ -60,6 +87,71 @@ pub enum StreamError {
 
 pub type Result<T> = std::result::Result<T, StreamError>;
 
+/// What a [`StreamError`] means for retrying, independent of the provider.
+///
+/// The daemon picks its retry schedule from the category alone, so providers
+/// only need to choose the right [`StreamError`] variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// Likely to pass on its own: network failures and server errors
+    Transient,
+    /// The service asked to wait at least `retry_after` seconds
+    RateLimited { retry_after: u64 },
+    /// The credentials were missing, rejected, or have expired
+    AuthExpired,
+    /// Retrying the same request will fail the same way
+    PermanentProviderError,
+    /// The operation isn't available for this provider
+    NotSupported,
+}
+
+impl ErrorCategory {
+    /// Whether retrying without any change may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transient | Self::RateLimited { .. })
+    }
+}
+
+impl StreamError {
+    /// What this error means for retrying.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Network(_) | Self::Transient(_) => ErrorCategory::Transient,
+            Self::RateLimited(secs) => ErrorCategory::RateLimited { retry_after: *secs },
+            Self::AuthRequired(_) => ErrorCategory::AuthExpired,
+            Self::NotSupported(_) => ErrorCategory::NotSupported,
+            Self::ProviderNotFound(_)
+            | Self::StreamNotFound(_)
+            | Self::ItemNotFound(_)
+            | Self::Provider(_)
+            | Self::Internal(_) => ErrorCategory::PermanentProviderError,
+        }
+    }
+
+    /// Map an unsuccessful HTTP response to the variant of its category.
+    ///
+    /// `retry_after` is the response's `Retry-After` in seconds, if it had
+    /// one. Only `401` means rejected credentials here: services differ on
+    /// whether `403` is an expired token or a forbidden resource, and a `404`
+    /// may be an item or a stream. Map those yourself where the service is
+    /// clear about them; otherwise they are [`StreamError::Provider`].
+    pub fn from_status(status: u16, retry_after: Option<u64>, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match (status, retry_after) {
+            (401, _) => Self::AuthRequired(message),
+            (429, secs) => Self::RateLimited(secs.unwrap_or(DEFAULT_RETRY_AFTER_SECS)),
+            (503, Some(secs)) => Self::RateLimited(secs),
+            (501, _) => Self::NotSupported(message),
+            (408 | 500..=599, _) => Self::Transient(message),
+            _ => Self::Provider(message),
+        }
+    }
+}
+
+/// Seconds to wait after a `429` without a `Retry-After`.
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
 // ============================================================================
 // Core ID Types
 // ============================================================================
@@ -135,20 +227,29 @@ pub enum StreamType {
 }
 
 /// An entry within a stream.
+///
+/// Items are persisted and exchanged between daemon and client versions, so
+/// fields added later must be optional or `#[serde(default)]`, and content
+/// variants this version does not know are read as [`ItemContent::Generic`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Item {
     pub id: ItemId,
     pub stream_id: StreamId,
     pub title: String,
+    #[serde(deserialize_with = "deserialize_content_lenient")]
     pub content: ItemContent,
     pub author: Option<Author>,
     pub published: Option<DateTime<Utc>>,
     pub updated: Option<DateTime<Utc>>,
     pub url: Option<String>,
     pub thumbnail_url: Option<String>,
+    #[serde(default)]
     pub is_read: bool,
+    #[serde(default)]
     pub is_saved: bool,
+    #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
 }
 
@@ -211,10 +312,210 @@ pub enum ItemContent {
     /// Bookmark content
     Bookmark { description: Option<String> },
 
+    /// A single image
+    Image {
+        url: String,
+        alt: Option<String>,
+        width: Option<u32>,
+        height: Option<u32>,
+    },
+
+    /// Several images shown together, such as a Reddit gallery
+    Gallery { images: Vec<GalleryImage> },
+
+    /// Chat message (Matrix, Discord, Telegram, ...)
+    Message {
+        text: String,
+        sender: String,
+        /// Room or channel the message was posted in
+        channel: Option<String>,
+        /// Root message ID when the message is a thread reply
+        thread_id: Option<String>,
+        #[serde(default)]
+        reactions: Vec<Reaction>,
+    },
+
+    /// A patch or change set: a pull request, a merge request, or a patch
+    /// mailed to a list. Build one with [`ItemContent::patch`].
+    Patch {
+        /// Commit message, cover letter, or description
+        description: Option<String>,
+        /// Unified diff, as `git diff` or `git format-patch` prints it
+        diff: String,
+        /// Paths the diff touches
+        files_changed: Vec<String>,
+        additions: u32,
+        deletions: u32,
+    },
+
     /// Generic/fallback content
     Generic { body: Option<String> },
 }
 
+impl ItemContent {
+    /// Patch content for a diff, with the files and line counts read from
+    /// it.
+    pub fn patch(description: Option<String>, diff: String) -> Self {
+        let stat = diff::DiffStat::of(&diff);
+        ItemContent::Patch {
+            description,
+            diff,
+            files_changed: stat.files,
+            additions: stat.additions,
+            deletions: stat.deletions,
+        }
+    }
+
+    /// Best-effort plain-text body, for previews, excerpts, and matching.
+    ///
+    /// HTML tags are stripped and whitespace is collapsed. Returns `None` when
+    /// the content has no textual body.
+    pub fn plain_text(&self) -> Option<String> {
+        let text = match self {
+            ItemContent::Text(text) | ItemContent::Markdown(text) => text.clone(),
+            ItemContent::Html(html) => strip_tags(html, " "),
+            ItemContent::Email {
+                body_text, snippet, ..
+            } => body_text.clone().unwrap_or_else(|| snippet.clone()),
+            ItemContent::Article {
+                summary,
+                full_content,
+            } => strip_tags(summary.as_deref().or(full_content.as_deref())?, " "),
+            ItemContent::Video { description, .. } => description.clone(),
+            ItemContent::Track { album, artists, .. } => match album {
+                Some(album) => format!("{} — {}", artists.join(", "), album),
+                None => artists.join(", "),
+            },
+            ItemContent::Task { body, .. } => body.clone()?,
+            ItemContent::Event { description, .. } => description.clone()?,
+            ItemContent::Bookmark { description } => description.clone()?,
+            ItemContent::Image { alt, .. } => alt.clone()?,
+            ItemContent::Gallery { images } => images
+                .iter()
+                .filter_map(|image| image.alt.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ItemContent::Message { text, .. } => text.clone(),
+            ItemContent::Patch {
+                description,
+                files_changed,
+                ..
+            } => match description {
+                Some(description) => format!("{}\n{}", description, files_changed.join("\n")),
+                None => files_changed.join("\n"),
+            },
+            ItemContent::Generic { body } => body.clone()?,
+        };
+
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+
+    /// Complete plain-text body, for reading or summarizing the whole item.
+    ///
+    /// Like [`plain_text`](Self::plain_text), but prefers an article's full
+    /// content over its summary and an email's HTML body over its snippet.
+    pub fn full_text(&self) -> Option<String> {
+        match self {
+            ItemContent::Article {
+                full_content: Some(full),
+                ..
+            } => ItemContent::Html(full.clone()).plain_text(),
+            ItemContent::Email {
+                body_text: None,
+                body_html: Some(html),
+                ..
+            } => ItemContent::Html(html.clone()).plain_text(),
+            _ => self.plain_text(),
+        }
+    }
+}
+
+/// One image of an [`ItemContent::Gallery`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GalleryImage {
+    pub url: String,
+    pub alt: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// An emoji reaction to an [`ItemContent::Message`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reaction {
+    pub emoji: String,
+    pub count: u32,
+}
+
+/// Remove HTML tags, leaving only text content, with `separator` in place
+/// of each tag: a space keeps the words of adjacent elements apart, while
+/// `""` keeps the text of highlighted code intact. Entities are left as they
+/// are; see [`decode_entities`].
+pub fn strip_tags(html: &str, separator: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push_str(separator);
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Decode the named entities of HTML's markup characters, `&nbsp;` as a
+/// space, and numeric character references, in one pass so `&amp;lt;`
+/// becomes `&lt;`. Anything else, such as a bare `&`, is left as it is.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..=end]);
+        let ch = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let num = entity.strip_prefix('#')?;
+                let code = match num.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => num.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (ch, entity) {
+            (Some(ch), Some(entity)) => {
+                decoded.push(ch);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
 /// Author/creator information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Author {
@@ -224,6 +525,121 @@ pub struct Author {
     pub avatar_url: Option<String>,
 }
 
+/// Metadata key of an email's `To` recipients: [`format_mailbox`] entries
+/// joined by `", "`. The daemon reads it, with [`CC_METADATA_KEY`], to learn
+/// contacts for autocompletion.
+pub const TO_METADATA_KEY: &str = "to";
+
+/// Metadata key of an email's `Cc` recipients, in the format of
+/// [`TO_METADATA_KEY`].
+pub const CC_METADATA_KEY: &str = "cc";
+
+/// Format an address as `Name <address>`, or bare without a name. Names that
+/// would split a list, such as `Doe, Jane`, are quoted.
+pub fn format_mailbox(name: Option<&str>, address: &str) -> String {
+    match name
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != address)
+    {
+        Some(name) if name.contains([',', ';', '<', '>', '"', '@']) => {
+            format!(
+                "\"{}\" <{}>",
+                name.replace('\\', "\\\\").replace('"', "\\\""),
+                address
+            )
+        }
+        Some(name) => format!("{} <{}>", name, address),
+        None => address.to_string(),
+    }
+}
+
+/// Metadata key marking an item its service still lists but has removed,
+/// such as a Reddit post deleted by its author or taken down by moderators.
+/// The value says why, such as `deleted` or `moderator`.
+///
+/// The daemon keeps its cached copy of such an item as it was, so the
+/// removed content stays readable as context, and never lets a later sync
+/// overwrite it.
+pub const REMOVED_METADATA_KEY: &str = "removed";
+
+/// Metadata key marking an item listed without its body, such as an email
+/// fetched as headers alone.
+///
+/// The daemon keeps the body of a cached copy that has one rather than
+/// replacing it with the empty one.
+pub const LAZY_BODY_METADATA_KEY: &str = "lazy_body";
+
+/// Metadata key of the meeting invitation an email carries, as a
+/// [`CalendarInvite`] in JSON.
+pub const INVITE_METADATA_KEY: &str = "invite";
+
+/// A meeting invitation found in an email's `text/calendar` part.
+///
+/// Times are kept as the organizer wrote them, formatted for display: a UTC
+/// time such as `2024-06-03 14:00 UTC`, a local time followed by its time
+/// zone, or a bare date for all-day events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarInvite {
+    /// iTIP method: `REQUEST` for an invitation that can be answered,
+    /// `CANCEL` for a cancelled meeting
+    pub method: String,
+    pub summary: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub location: Option<String>,
+    /// The organizer as a [`format_mailbox`] entry
+    pub organizer: Option<String>,
+    /// Invited attendees as [`format_mailbox`] entries
+    #[serde(default)]
+    pub attendees: Vec<String>,
+}
+
+impl CalendarInvite {
+    /// The invitation stored on `item` under [`INVITE_METADATA_KEY`].
+    pub fn from_item(item: &Item) -> Option<Self> {
+        serde_json::from_str(item.metadata.get(INVITE_METADATA_KEY)?).ok()
+    }
+
+    /// Whether the invitation asks for a reply, rather than announcing a
+    /// cancellation or an update.
+    pub fn is_request(&self) -> bool {
+        self.method.eq_ignore_ascii_case("REQUEST")
+    }
+}
+
+// ============================================================================
+// Serialization Format
+// ============================================================================
+
+/// Deserialize [`ItemContent`], mapping variants this version does not know
+/// to [`ItemContent::Generic`] instead of failing the whole item.
+fn deserialize_content_lenient<'de, D>(
+    deserializer: D,
+) -> std::result::Result<ItemContent, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Lenient {
+        Known(ItemContent),
+        Unknown(HashMap<String, serde_json::Value>),
+    }
+
+    Ok(match Lenient::deserialize(deserializer)? {
+        Lenient::Known(content) => content,
+        Lenient::Unknown(variant) => {
+            // Keep whatever text the unknown variant carries
+            let body = variant.values().find_map(|fields| {
+                ["text", "body", "description", "summary"]
+                    .iter()
+                    .find_map(|key| fields.get(*key)?.as_str().map(str::to_string))
+            });
+            ItemContent::Generic { body }
+        }
+    })
+}
+
 // ============================================================================
 // Actions
 // ============================================================================
@@ -292,6 +708,65 @@ pub struct ProviderHealth {
     pub error_count: u32,
 }
 
+/// Outcome of a diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but with reduced functionality or soon to stop working
+    Warning,
+    Failed,
+}
+
+/// One check reported by `scry doctor`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticCheck {
+    /// Short name of what was checked, e.g. "yt-dlp"
+    pub name: String,
+    pub status: CheckStatus,
+    /// What was found
+    pub detail: String,
+    /// What the user can do about a warning or failure
+    pub fix: Option<String>,
+}
+
+impl DiagnosticCheck {
+    pub fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warning(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warning,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn failed(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Failed,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
 /// Result of a sync operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncResult {
@@ -301,15 +776,103 @@ pub struct SyncResult {
     pub items_removed: u32,
     pub errors: Vec<String>,
     pub duration_ms: u64,
+    /// Results of the individual feeds or collections synced, for providers
+    /// that sync them one by one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub streams: Vec<StreamSyncResult>,
+}
+
+impl SyncResult {
+    /// A result made of per-stream results, with totals summed from them.
+    ///
+    /// The sync succeeds if any stream did, or there were none, so one
+    /// broken feed doesn't fail the rest; each failed stream's error is also
+    /// listed in `errors`.
+    pub fn from_streams(streams: Vec<StreamSyncResult>, duration_ms: u64) -> Self {
+        let errors: Vec<String> = streams
+            .iter()
+            .filter_map(|stream| {
+                let error = stream.error.as_ref()?;
+                Some(format!("{}: {}", stream.stream_id.as_str(), error))
+            })
+            .collect();
+        Self {
+            success: streams.is_empty() || errors.len() < streams.len(),
+            items_added: streams.iter().map(|stream| stream.items_added).sum(),
+            items_updated: streams.iter().map(|stream| stream.items_updated).sum(),
+            items_removed: 0,
+            errors,
+            duration_ms,
+            streams,
+        }
+    }
+
+    /// The streams that failed to sync.
+    pub fn failed_streams(&self) -> impl Iterator<Item = &StreamSyncResult> {
+        self.streams.iter().filter(|stream| stream.error.is_some())
+    }
+}
+
+/// Result of syncing one feed or collection within a provider sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamSyncResult {
+    pub stream_id: StreamId,
+    pub items_added: u32,
+    pub items_updated: u32,
+    /// Why the stream failed to sync, if it did
+    pub error: Option<String>,
+}
+
+impl StreamSyncResult {
+    pub fn ok(stream_id: StreamId, items_added: u32, items_updated: u32) -> Self {
+        Self {
+            stream_id,
+            items_added,
+            items_updated,
+            error: None,
+        }
+    }
+
+    pub fn failed(stream_id: StreamId, error: impl Into<String>) -> Self {
+        Self {
+            stream_id,
+            items_added: 0,
+            items_updated: 0,
+            error: Some(error.into()),
+        }
+    }
 }
 
 /// Capabilities that a provider supports.
+///
+/// The `has_*` flags mirror the capability traits a provider implements. The
+/// `supports_*` flags describe how those traits behave, so the daemon and
+/// clients can adapt up front instead of discovering limits from errors.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProviderCapabilities {
     pub has_feeds: bool,
     pub has_collections: bool,
     pub has_saved_items: bool,
     pub has_communities: bool,
+    /// The provider can search its source for items beyond the cached ones.
+    #[serde(default)]
+    pub supports_search: bool,
+    /// Actions such as mark read, save, or complete are written back to the
+    /// source; otherwise they only change the local cache.
+    #[serde(default)]
+    pub supports_write_actions: bool,
+    /// `FeedOptions::since` is passed to the source, so a sync only fetches
+    /// new items instead of refetching and filtering everything.
+    #[serde(default)]
+    pub supports_incremental_sync: bool,
+    /// `FeedOptions::limit` and `offset` are honored, so large feeds can be
+    /// fetched page by page.
+    #[serde(default)]
+    pub supports_pagination: bool,
+    /// The source pushes new items (IMAP IDLE, webhooks, streaming APIs)
+    /// rather than needing to be polled.
+    #[serde(default)]
+    pub supports_notifications: bool,
 }
 
 /// Base trait for all providers.
@@ -341,6 +904,63 @@ pub trait Provider: Send + Sync {
 
     /// Support for downcasting to concrete types
     fn as_any(&self) -> &dyn Any;
+
+    /// Access this provider as a read-later target, if it is one.
+    fn as_read_later(&self) -> Option<&dyn HasReadLater> {
+        None
+    }
+
+    /// Access this provider's notification inbox, if it has one.
+    fn as_notifications(&self) -> Option<&dyn HasNotifications> {
+        None
+    }
+
+    /// Access this provider's source search, if it has one.
+    fn as_search(&self) -> Option<&dyn HasSearch> {
+        None
+    }
+
+    /// Access this provider's task operations, if it has tasks.
+    fn as_tasks(&self) -> Option<&dyn HasTasks> {
+        None
+    }
+
+    /// Access this provider's feeds, if it has any.
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        None
+    }
+
+    /// Access this provider's collections, if it has any.
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        None
+    }
+
+    /// Access this provider's item attachments, if items can have them.
+    fn as_attachments(&self) -> Option<&dyn HasAttachments> {
+        None
+    }
+
+    /// Access this provider's outgoing mail, if it can send messages.
+    fn as_compose(&self) -> Option<&dyn HasCompose> {
+        None
+    }
+
+    /// Access this provider's tag management, if it keeps tags itself.
+    fn as_tags(&self) -> Option<&dyn HasTags> {
+        None
+    }
+
+    /// Access this provider's communities, if it has any.
+    fn as_communities(&self) -> Option<&dyn HasCommunities> {
+        None
+    }
+
+    /// Checks beyond `health_check`, such as a real API request or external
+    /// tools the provider shells out to. Run by `scry doctor`, so they may be
+    /// slower than a health check.
+    async fn diagnose(&self) -> Vec<DiagnosticCheck> {
+        Vec::new()
+    }
 }
 
 // ============================================================================
@@ -365,6 +985,10 @@ pub struct FeedOptions {
     pub offset: Option<u32>,
     pub since: Option<DateTime<Utc>>,
     pub include_read: bool,
+    /// The connection is metered: fetch as little as the provider can, such
+    /// as headers without message bodies
+    #[serde(default)]
+    pub low_bandwidth: bool,
 }
 
 /// Providers that have feeds (streams of items over time).
@@ -443,6 +1067,20 @@ pub trait HasSavedItems: Provider {
     async fn unsave_item(&self, item_id: &ItemId) -> Result<()>;
 }
 
+/// Providers that can store items originating from other providers.
+///
+/// Used by the daemon to hand "Save" off to a read-later service.
+/// Implementors should also override [`Provider::as_read_later`].
+///
+/// Examples: Local bookmarks, Wallabag, Pocket
+#[async_trait]
+pub trait HasReadLater: Provider {
+    /// Store an item from any provider, returning the ID of the new entry.
+    ///
+    /// Saving an item that is already stored should not create a duplicate.
+    async fn save_for_later(&self, item: &Item) -> Result<ItemId>;
+}
+
 /// A community or subscription (subreddit, channel, publication).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Community {
@@ -452,11 +1090,19 @@ pub struct Community {
     pub icon: Option<String>,
     pub member_count: Option<u64>,
     pub url: Option<String>,
+    /// Whether the user has joined the community
+    #[serde(default)]
+    pub is_member: bool,
 }
 
-/// Providers that have communities/subscriptions.
+/// Providers that have communities/subscriptions that can be browsed,
+/// joined, and left.
 ///
-/// Examples: Reddit subreddits, YouTube channels, Medium publications
+/// Community items are read live from the source, so communities the user
+/// hasn't joined can be browsed too. Implementors should also override
+/// [`Provider::as_communities`].
+///
+/// Examples: Reddit subreddits, Lemmy communities, Discord servers
 #[async_trait]
 pub trait HasCommunities: Provider {
     /// List subscribed communities
@@ -464,18 +1110,209 @@ pub trait HasCommunities: Provider {
 
     /// Get details for a specific community
     async fn get_community(&self, id: &CommunityId) -> Result<Community>;
+
+    /// Get recent items posted in a community, newest first
+    async fn get_community_items(
+        &self,
+        id: &CommunityId,
+        options: FeedOptions,
+    ) -> Result<Vec<Item>>;
+
+    /// Join (subscribe to) a community
+    async fn join_community(&self, id: &CommunityId) -> Result<()>;
+
+    /// Leave (unsubscribe from) a community
+    async fn leave_community(&self, id: &CommunityId) -> Result<()>;
+}
+
+/// A task to create in a task list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskDraft {
+    pub title: String,
+    pub body: Option<String>,
+    pub due_date: Option<NaiveDate>,
+}
+
+/// Changes to a task's text. Fields left as `None` are not changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskUpdate {
+    pub title: Option<String>,
+    pub body: Option<String>,
 }
 
-/// Providers that support task completion operations.
+/// Providers whose items are tasks that can be completed, edited, and
+/// scheduled.
 ///
-/// Examples: Microsoft To Do, Todoist, Google Tasks
+/// Tasks are items with [`ItemContent::Task`] content, and task lists are
+/// the provider's collections. Operations on an existing task take the whole
+/// item, since many sources address a task through the list it belongs to;
+/// providers find that list in the item's stream ID or metadata. Operations
+/// that change a task return it as it is now, ready to replace the cached
+/// copy. Implementors should also override [`Provider::as_tasks`].
+///
+/// Examples: Microsoft To Do, CalDAV VTODO lists, Todoist
 #[async_trait]
 pub trait HasTasks: Provider {
     /// Mark a task as completed.
-    async fn complete_task(&self, task_id: &str) -> Result<()>;
+    async fn complete_task(&self, task: &Item) -> Result<()>;
 
     /// Mark a task as not completed.
-    async fn uncomplete_task(&self, task_id: &str) -> Result<()>;
+    async fn uncomplete_task(&self, task: &Item) -> Result<()>;
+
+    /// Create a task in the list `list_id`.
+    async fn create_task(&self, list_id: &CollectionId, task: TaskDraft) -> Result<Item>;
+
+    /// Change a task's title or body.
+    async fn update_task(&self, task: &Item, update: TaskUpdate) -> Result<Item>;
+
+    /// Delete a task.
+    async fn delete_task(&self, task: &Item) -> Result<()>;
+
+    /// Set a task's due date, or clear it with `None`.
+    async fn set_due_date(&self, task: &Item, due_date: Option<NaiveDate>) -> Result<Item>;
+}
+
+/// Providers with an inbox of replies, mentions, and messages addressed to
+/// the user.
+///
+/// Notifications are ordinary items, usually with [`ItemContent::Message`]
+/// content; `is_read` reflects their read state at the source.
+/// Implementors should also override [`Provider::as_notifications`].
+///
+/// Examples: Reddit inbox, GitHub notifications, Mastodon mentions
+#[async_trait]
+pub trait HasNotifications: Provider {
+    /// List notifications, newest first. Only unread ones are returned unless
+    /// `options.include_read` is set.
+    async fn list_notifications(&self, options: FeedOptions) -> Result<Vec<Item>>;
+
+    /// Mark a notification as read at the source.
+    async fn mark_notification_read(&self, item_id: &ItemId) -> Result<()>;
+
+    /// Post `text` as a reply to a notification.
+    async fn reply_to_notification(&self, item_id: &ItemId, text: &str) -> Result<()>;
+}
+
+/// Options for searching a provider's source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchOptions {
+    pub limit: Option<u32>,
+    /// Only search within this stream, such as a single subreddit
+    pub stream_id: Option<StreamId>,
+    /// Provider-specific sort order, e.g. "relevance", "new", or "top"
+    pub sort: Option<String>,
+    /// Provider-specific time window, e.g. "day", "week", or "all"
+    pub time: Option<String>,
+    /// The connection is metered, as in [`FeedOptions::low_bandwidth`]
+    #[serde(default)]
+    pub low_bandwidth: bool,
+}
+
+/// Providers that can search their source for items that are not cached.
+///
+/// Results come best match first, each with a 1-based `search_rank`
+/// metadata entry so the order survives merging with other results.
+/// Implementors should also override [`Provider::as_search`] and set
+/// [`ProviderCapabilities::supports_search`].
+///
+/// Examples: Reddit search, YouTube search, Hacker News Algolia
+#[async_trait]
+pub trait HasSearch: Provider {
+    /// Search for `query`.
+    async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<Item>>;
+}
+
+/// Metadata key of the number of files attached to an item, for providers
+/// that know it without listing them.
+///
+/// The daemon only lists the attachments of items where it is above zero
+/// when indexing attachment text.
+pub const ATTACHMENTS_METADATA_KEY: &str = "attachments";
+
+/// A file attached to an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Provider-specific ID, passed back to [`HasAttachments::fetch_attachment`]
+    pub id: String,
+    pub file_name: String,
+    pub mime_type: Option<String>,
+    /// Size in bytes, if known before fetching
+    pub size: Option<u64>,
+}
+
+/// Providers whose items carry files that are not part of the cached item,
+/// such as email attachments.
+///
+/// The daemon's download manager lists them and saves each to disk.
+/// Implementors should also override [`Provider::as_attachments`].
+///
+/// Examples: IMAP and Gmail attachments, Outlook file attachments
+#[async_trait]
+pub trait HasAttachments: Provider {
+    /// List the files attached to `item`.
+    async fn list_attachments(&self, item: &Item) -> Result<Vec<Attachment>>;
+
+    /// Fetch the contents of the attachment `attachment_id` of `item`.
+    async fn fetch_attachment(&self, item: &Item, attachment_id: &str) -> Result<Vec<u8>>;
+}
+
+/// A new message to send, as plain text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutgoingMessage {
+    /// Recipient addresses
+    pub to: Vec<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Providers that can send new messages from the user's account, not only
+/// replies to existing ones.
+///
+/// The daemon uses this to share items by email. Implementors should also
+/// override [`Provider::as_compose`].
+///
+/// Examples: Outlook through Graph `sendMail`, Gmail, SMTP
+#[async_trait]
+pub trait HasCompose: Provider {
+    /// Send `message`, keeping a copy in the sent folder where the source
+    /// has one.
+    async fn send_message(&self, message: &OutgoingMessage) -> Result<()>;
+}
+
+/// A tag and the number of items carrying it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    pub name: String,
+    pub count: u32,
+}
+
+/// Providers whose tags are kept by the provider itself and can be
+/// reorganized in bulk, rather than mirrored from a remote service.
+///
+/// Tags are matched exactly. Implementors should also override
+/// [`Provider::as_tags`].
+///
+/// Examples: local bookmarks
+#[async_trait]
+pub trait HasTags: Provider {
+    /// List the tags in use, most used first.
+    async fn list_tags(&self) -> Result<Vec<TagCount>>;
+
+    /// Get the items tagged `tag`.
+    async fn tagged_items(&self, tag: &str) -> Result<Vec<Item>>;
+
+    /// Replace each of the `sources` tags with `into` on every item carrying
+    /// one, returning how many items changed.
+    async fn merge_tags(&self, sources: &[String], into: &str) -> Result<u32>;
+
+    /// Remove `tag` from every item, returning how many items changed.
+    async fn delete_tag(&self, tag: &str) -> Result<u32>;
+
+    /// Rename `from` to `to`, merging it into `to` on items that already
+    /// carry both.
+    async fn rename_tag(&self, from: &str, to: &str) -> Result<u32> {
+        self.merge_tags(&[from.to_string()], to).await
+    }
 }
 
 // ============================================================================
@@ -520,16 +1357,65 @@ pub mod auth {
     pub use scryforge_sigilforge_client::{default_socket_path, SigilforgeClient};
 }
 
+// ============================================================================
+// URL Canonicalization
+// ============================================================================
+
+pub mod url;
+
+// ============================================================================
+// Search Results
+// ============================================================================
+
+pub mod search;
+pub use search::SearchResult;
+
+// ============================================================================
+// Unified Diffs
+// ============================================================================
+
+pub mod diff;
+
+// ============================================================================
+// iCalendar Content Lines
+// ============================================================================
+
+pub mod ical;
+
+// ============================================================================
+// At-Rest Encryption (Optional)
+// ============================================================================
+
+#[cfg(feature = "encryption")]
+pub mod crypto;
+
+// ============================================================================
+// HTTP Clients (Optional)
+// ============================================================================
+
+#[cfg(feature = "http")]
+pub mod http;
+
+// ============================================================================
+// Test Harness (Optional)
+// ============================================================================
+
+#[cfg(feature = "test-harness")]
+pub mod testing;
+
 // ============================================================================
 // Re-exports
 // ============================================================================
 
 pub mod prelude {
     pub use crate::{
-        Action, ActionKind, ActionResult, Author, Collection, CollectionId, Community, CommunityId,
-        Feed, FeedId, FeedOptions, HasCollections, HasCommunities, HasFeeds, HasSavedItems,
-        HasTasks, Item, ItemContent, ItemId, Provider, ProviderCapabilities, ProviderHealth,
-        Result, SavedItemsOptions, Stream, StreamError, StreamId, StreamType, SyncResult,
+        Action, ActionKind, ActionResult, Attachment, Author, CheckStatus, Collection,
+        CollectionId, Community, CommunityId, DiagnosticCheck, ErrorCategory, Feed, FeedId,
+        FeedOptions, GalleryImage, HasAttachments, HasCollections, HasCommunities, HasCompose,
+        HasFeeds, HasNotifications, HasReadLater, HasSavedItems, HasSearch, HasTags, HasTasks,
+        Item, ItemContent, ItemId, OutgoingMessage, Provider, ProviderCapabilities, ProviderHealth,
+        Reaction, Result, SavedItemsOptions, SearchOptions, SearchResult, Stream, StreamError,
+        StreamId, StreamSyncResult, StreamType, SyncResult, TagCount, TaskDraft, TaskUpdate,
     };
 
     #[cfg(feature = "sigilforge")]
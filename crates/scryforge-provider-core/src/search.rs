@@ -0,0 +1,290 @@
+//! Search results that show why an item matched.
+//!
+//! A [`SearchResult`] is an [`Item`] with the parts of it that matched a
+//! search: byte ranges of its title, and a short snippet of its content
+//! around the first match, with the ranges of the snippet that matched.
+//! Clients emphasize those ranges, so a result found for a word in the
+//! middle of a long email shows that word in context.
+//!
+//! Results serialize as the item's own fields plus the match fields, so a
+//! client that only knows [`Item`] reads them as items.
+//!
+//! ```
+//! use scryforge_provider_core::search::{highlight_parts, match_ranges};
+//!
+//! let title = "Async closures are stable";
+//! let ranges = match_ranges(title, &["CLOSURES", "stable"]);
+//! assert_eq!(ranges, vec![6..14, 19..25]);
+//! assert_eq!(
+//!     highlight_parts(title, &ranges),
+//!     vec![
+//!         ("Async ", false),
+//!         ("closures", true),
+//!         (" are ", false),
+//!         ("stable", true),
+//!     ]
+//! );
+//! ```
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Item;
+
+/// Longest snippet, in characters, not counting the `…` at either end.
+pub const SNIPPET_CHARS: usize = 160;
+
+/// An item found by a search, with what matched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    #[serde(flatten)]
+    pub item: Item,
+    /// Relevance, higher is better, if the search ranked its results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    /// Byte ranges of the title that matched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub title_highlights: Vec<Range<usize>>,
+    /// Text around the first match in the item's content, if it matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+    /// Byte ranges of the snippet that matched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snippet_highlights: Vec<Range<usize>>,
+}
+
+impl SearchResult {
+    /// A result for `item`, highlighting where each of `needles` (words or
+    /// phrases, ignoring case) appears in its title and content.
+    pub fn new(item: Item, needles: &[&str]) -> Self {
+        let title_highlights = match_ranges(&item.title, needles);
+        let (snippet, snippet_highlights) = item
+            .content
+            .full_text()
+            .and_then(|text| snippet(&text, needles, SNIPPET_CHARS))
+            .map_or((None, Vec::new()), |(snippet, ranges)| {
+                (Some(snippet), ranges)
+            });
+        Self {
+            item,
+            score: None,
+            title_highlights,
+            snippet,
+            snippet_highlights,
+        }
+    }
+
+    pub fn with_score(mut self, score: f32) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    /// Use a snippet cut by the search itself, such as a search index's.
+    pub fn with_snippet(mut self, snippet: String, highlights: Vec<Range<usize>>) -> Self {
+        self.snippet = Some(snippet);
+        self.snippet_highlights = highlights;
+        self
+    }
+
+    /// The title, split into runs that did and didn't match.
+    pub fn title_parts(&self) -> Vec<(&str, bool)> {
+        highlight_parts(&self.item.title, &self.title_highlights)
+    }
+
+    /// The snippet, split into runs that did and didn't match.
+    pub fn snippet_parts(&self) -> Vec<(&str, bool)> {
+        self.snippet
+            .as_deref()
+            .map(|snippet| highlight_parts(snippet, &self.snippet_highlights))
+            .unwrap_or_default()
+    }
+}
+
+/// Byte ranges of `text` where any of `needles` appears, ignoring ASCII
+/// case, in order and with overlapping ranges merged.
+pub fn match_ranges(text: &str, needles: &[&str]) -> Vec<Range<usize>> {
+    // ASCII lowercasing keeps byte offsets, so ranges in the lowercased
+    // text are ranges in `text`
+    let haystack = text.to_ascii_lowercase();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for needle in needles {
+        let needle = needle.to_ascii_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        let mut from = 0;
+        while let Some(at) = haystack[from..].find(&needle) {
+            let start = from + at;
+            ranges.push(start..start + needle.len());
+            from = start + needle.len();
+        }
+    }
+
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Up to `max_chars` of `text` around the first place any of `needles`
+/// appears, cut at word boundaries with `…` where text was left out and
+/// whitespace collapsed, and the ranges of the snippet that matched. `None`
+/// if nothing matched.
+pub fn snippet(
+    text: &str,
+    needles: &[&str],
+    max_chars: usize,
+) -> Option<(String, Vec<Range<usize>>)> {
+    let first = match_ranges(text, needles).into_iter().next()?;
+
+    // A quarter of the snippet leads up to the match
+    let mut start = text[..first.start]
+        .char_indices()
+        .rev()
+        .take(max_chars / 4)
+        .last()
+        .map_or(first.start, |(at, _)| at);
+    if start > 0 {
+        if let Some(space) = text[start..first.start].find(char::is_whitespace) {
+            start += space;
+        }
+    }
+
+    let mut end = text[start..]
+        .char_indices()
+        .nth(max_chars)
+        .map_or(text.len(), |(at, _)| start + at);
+    if end < text.len() && end > first.end {
+        if let Some(space) = text[first.end..end].rfind(char::is_whitespace) {
+            end = first.end + space;
+        }
+    }
+
+    let mut snippet = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    let ranges = match_ranges(&snippet, needles);
+    Some((snippet, ranges))
+}
+
+/// Split `text` into runs inside and outside `highlights`, which must be
+/// in order, not overlap, and fall on character boundaries.
+pub fn highlight_parts<'a>(text: &'a str, highlights: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut parts = Vec::new();
+    let mut at = 0;
+    for range in highlights {
+        let (start, end) = (range.start.max(at), range.end.min(text.len()));
+        if start >= end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            continue;
+        }
+        if start > at {
+            parts.push((&text[at..start], false));
+        }
+        parts.push((&text[start..end], true));
+        at = end;
+    }
+    if at < text.len() {
+        parts.push((&text[at..], false));
+    }
+    parts
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ItemContent, ItemId, StreamId};
+    use std::collections::HashMap;
+
+    fn item(title: &str, body: &str) -> Item {
+        Item {
+            id: ItemId("rss:1".to_string()),
+            stream_id: StreamId("rss:feed:blog".to_string()),
+            title: title.to_string(),
+            content: ItemContent::Text(body.to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_ranges_merge() {
+        assert_eq!(
+            match_ranges("Rust rustaceans", &["rust", "rustacean"]),
+            vec![0..4, 5..14]
+        );
+        assert_eq!(match_ranges("Café au lait", &["au"]), vec![6..8]);
+        assert!(match_ranges("nothing here", &["absent", ""]).is_empty());
+    }
+
+    #[test]
+    fn test_snippet_around_match() {
+        let words: Vec<String> = (0..100).map(|n| format!("w{}", n)).collect();
+        let text = format!("{} needle\n\n{}", words.join(" "), words.join(" "));
+        let (snippet, ranges) = snippet(&text, &["needle"], 40).unwrap();
+
+        assert!(
+            snippet.starts_with('…') && snippet.ends_with('…'),
+            "{}",
+            snippet
+        );
+        assert!(!snippet.contains('\n'));
+        assert!(snippet.chars().count() <= 42, "{}", snippet);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(&snippet[ranges[0].clone()], "needle");
+
+        assert_eq!(
+            super::snippet("short needle text", &["NEEDLE"], 40),
+            Some(("short needle text".to_string(), vec![6..12]))
+        );
+        assert!(super::snippet("no match", &["needle"], 40).is_none());
+    }
+
+    #[test]
+    fn test_result_serializes_as_item() {
+        let result = SearchResult::new(
+            item("Weekly notes", "Compiler work on async closures landed."),
+            &["async closures"],
+        );
+        assert!(result.title_highlights.is_empty());
+        assert_eq!(
+            result.snippet_parts(),
+            vec![
+                ("Compiler work on ", false),
+                ("async closures", true),
+                (" landed.", false)
+            ]
+        );
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["title"], "Weekly notes");
+        assert!(json.get("title_highlights").is_none());
+        let item: Item = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(item.id.as_str(), "rss:1");
+        let back: SearchResult = serde_json::from_value(json).unwrap();
+        assert_eq!(back.snippet_highlights, vec![17..31]);
+    }
+}
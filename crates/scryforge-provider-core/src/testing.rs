@@ -0,0 +1,226 @@
+//! Mock HTTP harness for provider integration tests.
+//!
+//! Enable the `test-harness` feature as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! scryforge-provider-core = { workspace = true, features = ["test-harness"] }
+//! ```
+//!
+//! [`MockApi`] wraps a [`wiremock::MockServer`] with helpers for the
+//! responses API providers must cope with: paginated listings, rejected
+//! tokens, and rate limits. [`fixture`] returns canned YouTube, Reddit,
+//! Microsoft Graph, and Stack Exchange payloads, so tests exercise the real
+//! request and parsing code without reaching live APIs.
+//!
+//! Providers need a way to point at the mock server, usually a
+//! `with_base_url` constructor.
+
+pub use wiremock;
+
+use serde_json::Value;
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Placeholder in fixtures for the mock server's URL, used in absolute
+/// pagination links such as Graph's `@odata.nextLink`.
+pub const BASE_URL_PLACEHOLDER: &str = "{{base_url}}";
+
+const FIXTURES: &[(&str, &str)] = &[
+    (
+        "youtube/subscriptions_page1.json",
+        include_str!("../fixtures/youtube/subscriptions_page1.json"),
+    ),
+    (
+        "youtube/subscriptions_page2.json",
+        include_str!("../fixtures/youtube/subscriptions_page2.json"),
+    ),
+    (
+        "youtube/error_quota.json",
+        include_str!("../fixtures/youtube/error_quota.json"),
+    ),
+    (
+        "youtube/error_daily_quota.json",
+        include_str!("../fixtures/youtube/error_daily_quota.json"),
+    ),
+    (
+        "reddit/listing_page1.json",
+        include_str!("../fixtures/reddit/listing_page1.json"),
+    ),
+    (
+        "reddit/listing_page2.json",
+        include_str!("../fixtures/reddit/listing_page2.json"),
+    ),
+    (
+        "reddit/inbox.json",
+        include_str!("../fixtures/reddit/inbox.json"),
+    ),
+    (
+        "graph/todo_lists_page1.json",
+        include_str!("../fixtures/graph/todo_lists_page1.json"),
+    ),
+    (
+        "graph/todo_lists_page2.json",
+        include_str!("../fixtures/graph/todo_lists_page2.json"),
+    ),
+    (
+        "graph/todo_tasks.json",
+        include_str!("../fixtures/graph/todo_tasks.json"),
+    ),
+    (
+        "graph/error_unauthorized.json",
+        include_str!("../fixtures/graph/error_unauthorized.json"),
+    ),
+    (
+        "graph/mail_folders.json",
+        include_str!("../fixtures/graph/mail_folders.json"),
+    ),
+    (
+        "graph/mail_child_folders.json",
+        include_str!("../fixtures/graph/mail_child_folders.json"),
+    ),
+    (
+        "graph/mail_delta_page1.json",
+        include_str!("../fixtures/graph/mail_delta_page1.json"),
+    ),
+    (
+        "graph/mail_delta_page2.json",
+        include_str!("../fixtures/graph/mail_delta_page2.json"),
+    ),
+    (
+        "graph/mail_delta_changes.json",
+        include_str!("../fixtures/graph/mail_delta_changes.json"),
+    ),
+    (
+        "stackexchange/questions_page1.json",
+        include_str!("../fixtures/stackexchange/questions_page1.json"),
+    ),
+    (
+        "stackexchange/questions_page2.json",
+        include_str!("../fixtures/stackexchange/questions_page2.json"),
+    ),
+    (
+        "stackexchange/inbox.json",
+        include_str!("../fixtures/stackexchange/inbox.json"),
+    ),
+    (
+        "stackexchange/error_throttle.json",
+        include_str!("../fixtures/stackexchange/error_throttle.json"),
+    ),
+];
+
+/// A canned API response by name, e.g. `"reddit/listing_page1.json"`.
+///
+/// # Panics
+///
+/// Panics if there is no fixture with that name.
+pub fn fixture(name: &str) -> Value {
+    let (_, json) = FIXTURES
+        .iter()
+        .find(|(fixture, _)| *fixture == name)
+        .unwrap_or_else(|| panic!("unknown fixture: {}", name));
+    serde_json::from_str(json).unwrap_or_else(|e| panic!("invalid fixture {}: {}", name, e))
+}
+
+/// A mock API server.
+pub struct MockApi {
+    server: MockServer,
+}
+
+impl MockApi {
+    /// Start a server on a random local port.
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Base URL of the server, for a provider's `with_base_url`.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The underlying server, for mocks the helpers don't cover.
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// A fixture with [`BASE_URL_PLACEHOLDER`] replaced by this server's URL.
+    pub fn fixture(&self, name: &str) -> Value {
+        let json = fixture(name).to_string();
+        serde_json::from_str(&json.replace(BASE_URL_PLACEHOLDER, &self.uri()))
+            .expect("fixture stays valid JSON")
+    }
+
+    /// Respond to `http_method url_path` with a JSON body.
+    pub async fn mock_json(&self, http_method: &str, url_path: &str, body: Value) {
+        self.mock_status(http_method, url_path, 200, body).await;
+    }
+
+    /// Respond to `http_method url_path` with a status code and JSON body.
+    pub async fn mock_status(&self, http_method: &str, url_path: &str, status: u16, body: Value) {
+        Mock::given(method(http_method))
+            .and(path(url_path))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Serve a paginated `GET url_path`.
+    ///
+    /// Each page is paired with the value of the `token_param` query
+    /// parameter that requests it; the first page is usually `None`, served
+    /// when the parameter is absent. Pages must carry their own links to the
+    /// next page, as the real API's do.
+    pub async fn mock_pages(
+        &self,
+        url_path: &str,
+        token_param: &str,
+        pages: Vec<(Option<&str>, Value)>,
+    ) {
+        for (token, body) in pages {
+            let mock = Mock::given(method("GET")).and(path(url_path));
+            let mock = match token {
+                Some(token) => mock.and(query_param(token_param, token)),
+                None => mock.and(query_param_is_missing(token_param)),
+            };
+            mock.respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .mount(&self.server)
+                .await;
+        }
+    }
+
+    /// Reject `GET url_path` with 401 Unauthorized, as for an expired token.
+    pub async fn mock_unauthorized(&self, url_path: &str) {
+        self.mock_status(
+            "GET",
+            url_path,
+            401,
+            serde_json::json!({ "error": "unauthorized" }),
+        )
+        .await;
+    }
+
+    /// Reject `GET url_path` with 429 Too Many Requests and a `Retry-After`
+    /// header.
+    pub async fn mock_rate_limited(&self, url_path: &str, retry_after_secs: u64) {
+        Mock::given(method("GET"))
+            .and(path(url_path))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", retry_after_secs.to_string().as_str())
+                    .set_body_json(serde_json::json!({ "error": "rate limited" })),
+            )
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Number of requests the server has received.
+    pub async fn request_count(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .map(|requests| requests.len())
+            .unwrap_or_default()
+    }
+}
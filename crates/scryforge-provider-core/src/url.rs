@@ -0,0 +1,299 @@
+//! Canonical form of item URLs.
+//!
+//! Links arrive carrying campaign parameters (`utm_source`, `fbclid`, ...)
+//! or wrapped in a redirector such as `https://www.google.com/url?q=...`.
+//! Providers pass [`Item::url`](crate::Item::url) through
+//! [`canonicalize_url`] when converting entries to items, so copied links
+//! are clean and the same page reached from two providers has the same URL.
+//!
+//! Link shorteners can only be expanded by following them over HTTP.
+//! [`is_shortener`] tells providers that offer that, opt-in, which links are
+//! worth a request.
+//!
+//! ```
+//! use scryforge_provider_core::url::canonicalize_url;
+//!
+//! assert_eq!(
+//!     canonicalize_url("https://example.com/post?id=7&utm_source=rss&fbclid=abc#comments"),
+//!     "https://example.com/post?id=7#comments"
+//! );
+//! ```
+
+/// Query parameters that only identify a campaign or click.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid",
+    "gclid",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "twclid",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "mkt_tok",
+    "_hsenc",
+    "_hsmi",
+    "oly_anon_id",
+    "oly_enc_id",
+    "vero_id",
+    "ref_src",
+    "ref_url",
+];
+
+/// Query parameter prefixes that only identify a campaign.
+const TRACKING_PREFIXES: &[&str] = &["utm_", "pk_", "mtm_"];
+
+/// Share-tracking parameters stripped only on the hosts that add them.
+const HOST_TRACKING_PARAMS: &[(&str, &str)] = &[
+    ("youtube.com", "si"),
+    ("youtu.be", "si"),
+    ("open.spotify.com", "si"),
+    ("twitter.com", "s"),
+    ("x.com", "s"),
+];
+
+/// Redirector pages and the parameter holding their destination.
+const REDIRECTORS: &[(&str, &str, &str)] = &[
+    ("google.com", "/url", "q"),
+    ("google.com", "/url", "url"),
+    ("l.facebook.com", "/l.php", "u"),
+    ("lm.facebook.com", "/l.php", "u"),
+    ("l.instagram.com", "/", "u"),
+    ("out.reddit.com", "", "url"),
+    ("youtube.com", "/redirect", "q"),
+    ("slack-redir.net", "/link", "url"),
+];
+
+/// Hosts that only redirect to a longer URL.
+const SHORTENERS: &[&str] = &[
+    "t.co",
+    "bit.ly",
+    "buff.ly",
+    "dlvr.it",
+    "fb.me",
+    "ift.tt",
+    "is.gd",
+    "lnkd.in",
+    "ow.ly",
+    "tinyurl.com",
+    "trib.al",
+    "feedproxy.google.com",
+];
+
+/// Redirectors wrapping redirectors are unwrapped at most this many times.
+const MAX_UNWRAP: usize = 3;
+
+/// Whether a query parameter name only identifies a campaign or click.
+pub fn is_tracking_param(name: &str) -> bool {
+    TRACKING_PARAMS.contains(&name) || TRACKING_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// Canonical form of an `http` or `https` URL: the destination of a known
+/// redirector, with tracking parameters removed and everything else,
+/// including parameter order and the fragment, left as it was.
+///
+/// Other URLs, such as `mailto:` links, are returned unchanged.
+pub fn canonicalize_url(url: &str) -> String {
+    let mut url = url.trim().to_string();
+    for _ in 0..MAX_UNWRAP {
+        match redirect_target(&url) {
+            Some(target) => url = target,
+            None => break,
+        }
+    }
+
+    let Some(parts) = UrlParts::parse(&url) else {
+        return url;
+    };
+    let host = parts.host();
+    let query: Vec<&str> = parts
+        .query
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            !param.is_empty()
+                && !is_tracking_param(name)
+                && !HOST_TRACKING_PARAMS
+                    .iter()
+                    .any(|&(h, p)| p == name && host_matches(&host, h))
+        })
+        .collect();
+
+    let mut canonical = parts.base.to_string();
+    if !query.is_empty() {
+        canonical.push('?');
+        canonical.push_str(&query.join("&"));
+    }
+    if let Some(fragment) = parts.fragment {
+        canonical.push('#');
+        canonical.push_str(fragment);
+    }
+    canonical
+}
+
+/// Comparison key for a URL, shared by links to the same page: the
+/// [canonical](canonicalize_url) URL without its scheme, `www.`, default
+/// port, fragment, or trailing slash, with the host lowercased and the query
+/// parameters sorted.
+///
+/// `http://www.Example.com/post/?utm_source=x#top` and
+/// `https://example.com/post` share the key `example.com/post`. Other URLs,
+/// such as `mailto:` links, are only trimmed. `None` for an `http(s)` URL
+/// without a host.
+pub fn url_key(url: &str) -> Option<String> {
+    let url = canonicalize_url(url);
+    let Some(parts) = UrlParts::parse(&url) else {
+        return Some(url);
+    };
+    let host = parts.host();
+    if host.is_empty() {
+        return None;
+    }
+
+    let mut key = host;
+    if let Some(port) = parts.port().filter(|&port| port != "80" && port != "443") {
+        key.push(':');
+        key.push_str(port);
+    }
+    key.push_str(parts.path().trim_end_matches('/'));
+    let mut query: Vec<&str> = parts.query.split('&').filter(|p| !p.is_empty()).collect();
+    if !query.is_empty() {
+        query.sort_unstable();
+        key.push('?');
+        key.push_str(&query.join("&"));
+    }
+    Some(key)
+}
+
+/// Lowercased host of an `http(s)` URL, without `www.` or a port. `None`
+/// for other URLs and URLs without a host.
+pub fn url_host(url: &str) -> Option<String> {
+    let host = UrlParts::parse(url.trim())?.host();
+    (!host.is_empty()).then_some(host)
+}
+
+/// Whether `url` points at a link shortener.
+pub fn is_shortener(url: &str) -> bool {
+    UrlParts::parse(url).is_some_and(|parts| {
+        let host = parts.host();
+        SHORTENERS.iter().any(|&h| host == h)
+    })
+}
+
+/// An `http(s)` URL split into scheme, host, and path; query; and fragment.
+struct UrlParts<'a> {
+    base: &'a str,
+    query: &'a str,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> UrlParts<'a> {
+    fn parse(url: &'a str) -> Option<Self> {
+        let scheme_len = ["https://", "http://"]
+            .iter()
+            .find(|scheme| {
+                url.get(..scheme.len())
+                    .is_some_and(|s| s.eq_ignore_ascii_case(scheme))
+            })?
+            .len();
+        if url.len() == scheme_len {
+            return None;
+        }
+        let (rest, fragment) = match url.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (url, None),
+        };
+        let (base, query) = rest.split_once('?').unwrap_or((rest, ""));
+        Some(Self {
+            base,
+            query,
+            fragment,
+        })
+    }
+
+    /// Lowercased host without `www.` or a port.
+    fn host(&self) -> String {
+        let authority = self.base.split_once("://").map_or("", |(_, rest)| rest);
+        let authority = authority.split('/').next().unwrap_or_default();
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        let host = host
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        host.strip_prefix("www.")
+            .map(str::to_string)
+            .unwrap_or(host)
+    }
+
+    /// Port after the host, if one is given.
+    fn port(&self) -> Option<&'a str> {
+        let authority = self.base.split_once("://").map_or("", |(_, rest)| rest);
+        let authority = authority.split('/').next().unwrap_or_default();
+        let host = authority.rsplit('@').next().unwrap_or_default();
+        host.split_once(':').map(|(_, port)| port)
+    }
+
+    /// Path after the host, starting with `/` unless empty.
+    fn path(&self) -> &str {
+        let authority_start = self.base.find("://").map_or(0, |i| i + 3);
+        self.base[authority_start..]
+            .find('/')
+            .map_or("", |i| &self.base[authority_start + i..])
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn host_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Destination of a redirector link, if `url` is one.
+fn redirect_target(url: &str) -> Option<String> {
+    let parts = UrlParts::parse(url)?;
+    let host = parts.host();
+    let path = parts.path();
+    REDIRECTORS
+        .iter()
+        .filter(|&&(h, p, _)| host == h && (p.is_empty() || path == p))
+        .find_map(|&(_, _, param)| {
+            parts.query.split('&').find_map(|pair| {
+                let value = pair.strip_prefix(param)?.strip_prefix('=')?;
+                let target = percent_decode(value);
+                UrlParts::parse(&target).is_some().then_some(target)
+            })
+        })
+}
+
+/// Decode `%XX` escapes and `+`, leaving malformed escapes as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,88 @@
+//! Tests for the retry categories of provider errors.
+
+use scryforge_provider_core::{ErrorCategory, StreamError};
+
+#[test]
+fn variants_map_to_categories() {
+    let cases = [
+        (
+            StreamError::Network("reset".to_string()),
+            ErrorCategory::Transient,
+        ),
+        (
+            StreamError::Transient("503".to_string()),
+            ErrorCategory::Transient,
+        ),
+        (
+            StreamError::RateLimited(30),
+            ErrorCategory::RateLimited { retry_after: 30 },
+        ),
+        (
+            StreamError::AuthRequired("expired".to_string()),
+            ErrorCategory::AuthExpired,
+        ),
+        (
+            StreamError::NotSupported("IDLE".to_string()),
+            ErrorCategory::NotSupported,
+        ),
+        (
+            StreamError::Provider("bad request".to_string()),
+            ErrorCategory::PermanentProviderError,
+        ),
+        (
+            StreamError::ItemNotFound("rss:1".to_string()),
+            ErrorCategory::PermanentProviderError,
+        ),
+    ];
+
+    for (error, category) in cases {
+        assert_eq!(error.category(), category, "{:?}", error);
+    }
+}
+
+#[test]
+fn only_transient_and_throttled_errors_are_retryable() {
+    assert!(ErrorCategory::Transient.is_retryable());
+    assert!(ErrorCategory::RateLimited { retry_after: 1 }.is_retryable());
+    assert!(!ErrorCategory::AuthExpired.is_retryable());
+    assert!(!ErrorCategory::PermanentProviderError.is_retryable());
+    assert!(!ErrorCategory::NotSupported.is_retryable());
+}
+
+#[test]
+fn status_codes_map_to_categories() {
+    let category =
+        |status, retry_after| StreamError::from_status(status, retry_after, "failed").category();
+
+    assert_eq!(category(401, None), ErrorCategory::AuthExpired);
+    assert_eq!(
+        category(429, Some(12)),
+        ErrorCategory::RateLimited { retry_after: 12 }
+    );
+    assert_eq!(
+        category(429, None),
+        ErrorCategory::RateLimited { retry_after: 60 }
+    );
+    assert_eq!(
+        category(503, Some(5)),
+        ErrorCategory::RateLimited { retry_after: 5 }
+    );
+    assert_eq!(category(503, None), ErrorCategory::Transient);
+    assert_eq!(category(500, None), ErrorCategory::Transient);
+    assert_eq!(category(408, None), ErrorCategory::Transient);
+    assert_eq!(category(501, None), ErrorCategory::NotSupported);
+    assert_eq!(category(403, None), ErrorCategory::PermanentProviderError);
+    assert_eq!(category(404, None), ErrorCategory::PermanentProviderError);
+}
+
+#[test]
+fn categories_serialize_with_a_kind_tag() {
+    assert_eq!(
+        serde_json::to_value(ErrorCategory::RateLimited { retry_after: 7 }).unwrap(),
+        serde_json::json!({ "kind": "rate_limited", "retry_after": 7 })
+    );
+    assert_eq!(
+        serde_json::to_value(ErrorCategory::PermanentProviderError).unwrap(),
+        serde_json::json!({ "kind": "permanent_provider_error" })
+    );
+}
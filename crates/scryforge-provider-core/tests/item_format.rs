@@ -0,0 +1,102 @@
+//! Compatibility tests for the serialized item format.
+//!
+//! The fixtures are frozen copies of what each version wrote. Never edit
+//! them; add a new fixture when the format changes.
+
+use scryforge_provider_core::{Item, ItemContent};
+
+/// An item with every field of the first format.
+const FULL_ITEM: &str = r#"{
+    "id": "rss:https://example.com/post",
+    "stream_id": "rss:feed:0",
+    "title": "Hello",
+    "content": {"Article": {"summary": "Short", "full_content": null}},
+    "author": {"name": "Jane", "email": null, "url": null, "avatar_url": null},
+    "published": "2024-03-01T12:00:00Z",
+    "updated": null,
+    "url": "https://example.com/post",
+    "thumbnail_url": null,
+    "is_read": true,
+    "is_saved": false,
+    "tags": ["rust"],
+    "metadata": {"feed_url": "https://example.com/rss"}
+}"#;
+
+/// An item with only the required fields.
+const MINIMAL_ITEM: &str = r#"{
+    "id": "email:1",
+    "stream_id": "email:inbox",
+    "title": "Meeting",
+    "content": {"Text": "See you at ten"}
+}"#;
+
+/// An item from a hypothetical newer version with fields and a content
+/// variant this version does not know about.
+const FUTURE_ITEM: &str = r#"{
+    "id": "matrix:$event",
+    "stream_id": "matrix:room:rust",
+    "title": "New message",
+    "content": {"Poll": {"text": "Lunch?", "options": ["Pizza", "Sushi"]}},
+    "is_read": false,
+    "is_saved": true,
+    "tags": [],
+    "metadata": {},
+    "priority": 3
+}"#;
+
+fn parse(json: &str) -> Item {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn test_reads_full_items() {
+    let item = parse(FULL_ITEM);
+
+    assert_eq!(item.title, "Hello");
+    assert!(item.is_read);
+    assert_eq!(item.tags, vec!["rust".to_string()]);
+    assert!(matches!(
+        item.content,
+        ItemContent::Article { summary: Some(ref s), .. } if s == "Short"
+    ));
+}
+
+#[test]
+fn test_missing_fields_use_defaults() {
+    let item = parse(MINIMAL_ITEM);
+
+    assert!(item.author.is_none());
+    assert!(!item.is_read);
+    assert!(!item.is_saved);
+    assert!(item.tags.is_empty());
+    assert!(item.metadata.is_empty());
+}
+
+#[test]
+fn test_reads_items_from_newer_versions() {
+    let item = parse(FUTURE_ITEM);
+
+    assert!(item.is_saved);
+    match item.content {
+        ItemContent::Generic { body } => assert_eq!(body.as_deref(), Some("Lunch?")),
+        other => panic!("expected generic content, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let item = parse(FULL_ITEM);
+    let json = serde_json::to_string(&item).unwrap();
+
+    let read = parse(&json);
+    assert_eq!(read.id, item.id);
+    assert_eq!(read.published, item.published);
+    assert_eq!(read.metadata, item.metadata);
+}
+
+#[test]
+fn test_unknown_content_without_text() {
+    let json = r#"{"id": "x:1", "stream_id": "x:s", "title": "T", "content": {"Hologram": {}}}"#;
+    let item = parse(json);
+    assert!(matches!(item.content, ItemContent::Generic { body: None }));
+}
@@ -0,0 +1,24 @@
+//! Tests for reading text out of HTML.
+
+use scryforge_provider_core::{decode_entities, strip_tags};
+
+#[test]
+fn tags_are_replaced_by_the_separator() {
+    let html = "<pre><span class=\"k\">fn</span> <b>main</b>()</pre>";
+    assert_eq!(strip_tags(html, ""), "fn main()");
+    assert_eq!(strip_tags("<p>One</p><p>Two</p>", " "), " One  Two ");
+}
+
+#[test]
+fn entities_are_decoded_once() {
+    assert_eq!(
+        decode_entities("Why isn&#39;t &quot;x &lt; y&quot; &amp;&amp; z?"),
+        "Why isn't \"x < y\" && z?"
+    );
+    assert_eq!(decode_entities("&amp;lt;b&amp;gt;"), "&lt;b&gt;");
+    assert_eq!(
+        decode_entities("caf&#xE9;&nbsp;au&#32;lait"),
+        "café au lait"
+    );
+    assert_eq!(decode_entities("AT&T & co &bogus;"), "AT&T & co &bogus;");
+}
@@ -0,0 +1,139 @@
+//! Tests for item URL canonicalization.
+
+use scryforge_provider_core::url::{
+    canonicalize_url, is_shortener, is_tracking_param, url_host, url_key,
+};
+
+#[test]
+fn tracking_parameters_are_removed() {
+    let cases = [
+        (
+            "https://example.com/a?utm_source=rss&utm_medium=feed",
+            "https://example.com/a",
+        ),
+        (
+            "https://example.com/a?page=2&fbclid=IwAR0&sort=new",
+            "https://example.com/a?page=2&sort=new",
+        ),
+        (
+            "https://example.com/a?mc_cid=1&mc_eid=2#section-3",
+            "https://example.com/a#section-3",
+        ),
+        ("https://example.com/a?", "https://example.com/a"),
+        (
+            "https://github.com/owner/repo?ref=main",
+            "https://github.com/owner/repo?ref=main",
+        ),
+    ];
+
+    for (url, expected) in cases {
+        assert_eq!(canonicalize_url(url), expected, "{}", url);
+    }
+}
+
+#[test]
+fn share_parameters_are_removed_only_where_they_track() {
+    assert_eq!(
+        canonicalize_url("https://youtu.be/dQw4w9WgXcQ?si=Xy12&t=42"),
+        "https://youtu.be/dQw4w9WgXcQ?t=42"
+    );
+    assert_eq!(
+        canonicalize_url("https://open.spotify.com/track/4u7E?si=abc"),
+        "https://open.spotify.com/track/4u7E"
+    );
+    assert_eq!(
+        canonicalize_url("https://example.com/search?si=1"),
+        "https://example.com/search?si=1"
+    );
+}
+
+#[test]
+fn redirectors_are_unwrapped() {
+    assert_eq!(
+        canonicalize_url(
+            "https://www.google.com/url?sa=t&q=https%3A%2F%2Fexample.com%2Fpost%3Fid%3D7%26utm_source%3Dg&usg=x"
+        ),
+        "https://example.com/post?id=7"
+    );
+    assert_eq!(
+        canonicalize_url("https://l.facebook.com/l.php?u=https%3A%2F%2Fexample.org%2F&h=AT0"),
+        "https://example.org/"
+    );
+    assert_eq!(
+        canonicalize_url("https://out.reddit.com/t3_abc?url=https%3A%2F%2Fexample.net%2Fx&token=1"),
+        "https://example.net/x"
+    );
+    // Not a destination URL, so the link is kept
+    assert_eq!(
+        canonicalize_url("https://www.google.com/url?q=rust"),
+        "https://www.google.com/url?q=rust"
+    );
+}
+
+#[test]
+fn other_urls_are_unchanged() {
+    for url in [
+        "mailto:me@example.com?subject=hi",
+        "/relative?utm_source=x",
+        "https://",
+    ] {
+        assert_eq!(canonicalize_url(url), url);
+    }
+    assert_eq!(
+        canonicalize_url("  https://example.com/a  "),
+        "https://example.com/a"
+    );
+}
+
+#[test]
+fn urls_of_the_same_page_share_a_key() {
+    let key = url_key("https://example.com/post");
+    assert_eq!(key.as_deref(), Some("example.com/post"));
+    for url in [
+        "http://example.com/post",
+        "https://www.Example.com/post/",
+        "HTTPS://example.com:443/post?utm_source=rss&fbclid=abc",
+        "https://example.com/post#comments",
+    ] {
+        assert_eq!(url_key(url), key, "{}", url);
+    }
+
+    assert_eq!(
+        url_key("https://Example.com/a/?b=2&utm_medium=x&a=1#frag").as_deref(),
+        Some("example.com/a?a=1&b=2")
+    );
+    assert_eq!(
+        url_key("https://example.com/").as_deref(),
+        Some("example.com")
+    );
+    assert_ne!(url_key("https://example.com:8080/post"), key);
+    assert_ne!(url_key("https://example.com/Post"), key);
+    assert_eq!(
+        url_key(" mailto:me@example.com").as_deref(),
+        Some("mailto:me@example.com")
+    );
+    assert_eq!(url_key("https:///path"), None);
+}
+
+#[test]
+fn hosts_are_lowercased_without_www_or_port() {
+    assert_eq!(
+        url_host("https://user@WWW.Example.com:8080/post?q=1").as_deref(),
+        Some("example.com")
+    );
+    assert_eq!(
+        url_host("http://news.example.com#top").as_deref(),
+        Some("news.example.com")
+    );
+    assert_eq!(url_host("https:///path"), None);
+    assert_eq!(url_host("mailto:me@example.com"), None);
+}
+
+#[test]
+fn shorteners_and_tracking_names_are_recognized() {
+    assert!(is_shortener("https://t.co/AbC123"));
+    assert!(is_shortener("http://www.bit.ly/xyz"));
+    assert!(!is_shortener("https://example.com/t.co"));
+    assert!(is_tracking_param("utm_campaign"));
+    assert!(!is_tracking_param("q"));
+}
@@ -0,0 +1,224 @@
+//! Finding bookmarks that point at the same page.
+//!
+//! Two bookmarks are duplicates when their URLs share a [`url_key`], which
+//! ignores the scheme, a `www.` prefix, tracking parameters, the fragment,
+//! and a trailing slash, or when their titles are nearly the same: they share
+//! most of their words, ignoring case and punctuation.
+
+use std::collections::{HashMap, HashSet};
+
+use scryforge_provider_core::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::Bookmark;
+
+/// Titles with fewer words than this are too generic to match on.
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Share of the shorter title's words the longer one must contain to match,
+/// so a site name appended to one copy doesn't keep them apart.
+const TITLE_SIMILARITY: f64 = 0.8;
+
+/// Share of their combined words two titles must have in common to match,
+/// so a short title doesn't match every longer one containing it.
+const TITLE_OVERLAP: f64 = 0.5;
+
+/// Why the bookmarks in a [`DuplicateGroup`] were grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    /// Their URLs are the same page
+    SameUrl,
+    /// Their URLs differ but their titles are nearly the same
+    SimilarTitle,
+}
+
+/// Bookmarks that look like copies of each other, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub reason: DuplicateReason,
+    pub items: Vec<Item>,
+}
+
+/// Comparison key for a bookmark URL: its
+/// [`url_key`](scryforge_provider_core::url::url_key), or the trimmed URL if
+/// it has no host.
+pub fn url_key(url: &str) -> String {
+    scryforge_provider_core::url::url_key(url).unwrap_or_else(|| url.trim().to_string())
+}
+
+/// Whether two titles are nearly the same.
+pub fn similar_titles(a: &str, b: &str) -> bool {
+    match (title_words(a), title_words(b)) {
+        (Some(a), Some(b)) => {
+            let shared = a.intersection(&b).count() as f64;
+            let shorter = a.len().min(b.len()) as f64;
+            let combined = a.union(&b).count() as f64;
+            shared >= TITLE_SIMILARITY * shorter && shared >= TITLE_OVERLAP * combined
+        }
+        _ => false,
+    }
+}
+
+/// The distinct lowercase words of a title, if it has enough to match on.
+fn title_words(title: &str) -> Option<HashSet<String>> {
+    let words: HashSet<String> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    (words.len() >= MIN_TITLE_WORDS).then_some(words)
+}
+
+/// Group duplicate bookmarks, returning indices into `bookmarks`.
+///
+/// Each bookmark is in at most one group. Same-URL groups are found first;
+/// the rest are grouped by title, comparing each with the oldest member of a
+/// group. Groups list their members oldest first.
+pub(crate) fn group_duplicates(bookmarks: &[Bookmark]) -> Vec<(DuplicateReason, Vec<usize>)> {
+    let mut order: Vec<usize> = (0..bookmarks.len()).collect();
+    order.sort_by_key(|&i| bookmarks[i].created_at);
+
+    let mut by_url: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut url_groups: Vec<String> = Vec::new();
+    for &i in &order {
+        let key = url_key(&bookmarks[i].url);
+        let members = by_url.entry(key.clone()).or_default();
+        if members.is_empty() {
+            url_groups.push(key);
+        }
+        members.push(i);
+    }
+
+    let mut groups = Vec::new();
+    let mut single = Vec::new();
+    for key in url_groups {
+        let members = by_url.remove(&key).unwrap_or_default();
+        if members.len() > 1 {
+            groups.push((DuplicateReason::SameUrl, members));
+        } else {
+            single.extend(members);
+        }
+    }
+
+    let mut by_title: Vec<Vec<usize>> = Vec::new();
+    for i in single {
+        let title = &bookmarks[i].title;
+        match by_title
+            .iter_mut()
+            .find(|group| similar_titles(&bookmarks[group[0]].title, title))
+        {
+            Some(group) => group.push(i),
+            None => by_title.push(vec![i]),
+        }
+    }
+    groups.extend(
+        by_title
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .map(|group| (DuplicateReason::SimilarTitle, group)),
+    );
+    groups
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn bookmark(id: &str, title: &str, url: &str, age_days: i64) -> Bookmark {
+        Bookmark {
+            id: id.to_string(),
+            folder_id: None,
+            title: title.to_string(),
+            url: url.to_string(),
+            description: None,
+            created_at: Utc::now() - Duration::days(age_days),
+            updated_at: None,
+            tags: vec![],
+            favicon_url: None,
+            to_read: false,
+            shared: false,
+        }
+    }
+
+    #[test]
+    fn test_url_key() {
+        let key = url_key("https://example.com/post");
+        assert_eq!(key, "example.com/post");
+        for url in [
+            "http://example.com/post",
+            "https://www.Example.com/post/",
+            "HTTPS://example.com:443/post?utm_source=rss&fbclid=abc",
+            "https://example.com/post#comments",
+        ] {
+            assert_eq!(url_key(url), key, "{}", url);
+        }
+
+        assert_eq!(url_key("https://example.com/"), "example.com");
+        assert_eq!(url_key("https://example.com/?id=7"), "example.com?id=7");
+        assert_ne!(url_key("https://example.com/post?id=7"), key);
+        assert_ne!(url_key("https://example.com/Post"), key);
+        assert_eq!(url_key(" mailto:me@example.com"), "mailto:me@example.com");
+    }
+
+    #[test]
+    fn test_similar_titles() {
+        assert!(similar_titles(
+            "Announcing Rust 1.80 | Rust Blog",
+            "Announcing Rust 1.80 - Rust Blog"
+        ));
+        assert!(similar_titles(
+            "How to write a parser in Rust",
+            "How to Write a Parser in Rust (2024)"
+        ));
+        assert!(similar_titles(
+            "Writing a Tiny Parser in Rust",
+            "Writing a tiny parser in Rust | Example Blog"
+        ));
+        assert!(!similar_titles(
+            "How to write a parser in Rust",
+            "How to write a lexer in Go"
+        ));
+        assert!(!similar_titles(
+            "Getting started with Rust",
+            "Getting started with Rust on embedded devices using Embassy and probe-rs"
+        ));
+        // Short titles are too generic to match
+        assert!(!similar_titles("Home", "Home"));
+    }
+
+    #[test]
+    fn test_group_duplicates() {
+        let bookmarks = vec![
+            bookmark("new", "Example Post", "https://www.example.com/post/", 1),
+            bookmark("old", "Example Post", "http://example.com/post", 5),
+            bookmark(
+                "mirror",
+                "Writing a Tiny Parser in Rust",
+                "https://a.test/p",
+                3,
+            ),
+            bookmark("other", "Something else entirely", "https://b.test/", 2),
+            bookmark(
+                "copy",
+                "Writing a tiny parser in Rust!",
+                "https://c.test/p",
+                4,
+            ),
+        ];
+
+        let groups = group_duplicates(&bookmarks);
+        assert_eq!(
+            groups,
+            vec![
+                (DuplicateReason::SameUrl, vec![1, 0]),
+                (DuplicateReason::SimilarTitle, vec![4, 2]),
+            ]
+        );
+    }
+}
@@ -3,15 +3,37 @@
 //! Local bookmarks provider for Scryforge.
 //!
 //! This provider manages bookmarks stored locally in JSON format and supports
-//! importing bookmarks from Chrome and Firefox browser exports.
+//! importing bookmarks from Chrome and Firefox browser exports and from HTML
+//! bookmark files.
 //!
 //! ## Features
 //!
 //! - Store bookmarks locally in XDG_DATA_HOME/scryforge/bookmarks.json
 //! - Organize bookmarks into folders (collections)
-//! - Import bookmarks from Chrome/Firefox JSON exports
+//! - Import bookmarks from Chrome/Firefox JSON exports and from the HTML
+//!   bookmark files browsers export and Google Takeout delivers
 //! - Full-text search across bookmark titles and descriptions
-//! - Automatic URL deduplication
+//! - Automatic URL deduplication, ignoring the scheme, tracking parameters,
+//!   and trailing slashes
+//! - Duplicate detection by URL and title, with merging
+//! - Optional at-rest encryption of the bookmarks file
+//! - Tag management: rename, merge, and delete tags across all bookmarks
+//!
+//! ## Tag Collections
+//!
+//! Besides its folders, the provider lists one virtual collection per tag,
+//! with the ID `tag:<name>` (see [`TAG_COLLECTION_PREFIX`]), holding the
+//! bookmarks carrying it. Adding a bookmark to a tag collection tags it, and
+//! removing it untags it. A tag collection exists for as long as some bookmark
+//! carries the tag.
+//!
+//! ## Duplicates
+//!
+//! Adding, importing, or capturing a page that is already bookmarked under
+//! an equivalent URL (see [`duplicates::url_key`]) keeps the existing
+//! bookmark. [`BookmarksProvider::find_duplicates`] also reports bookmarks
+//! with nearly the same title, and [`BookmarksProvider::merge_bookmarks`]
+//! folds a group into the bookmark to keep.
 //!
 //! ## Storage Format
 //!
@@ -38,10 +60,16 @@
 //!   ]
 //! }
 //! ```
+//!
+//! A provider created with [`BookmarksProvider::with_cipher`] can read a file
+//! sealed with a [`StoreCipher`], and writes it sealed when encryption is
+//! on. An existing file is converted on load when the setting changed.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use scryforge_provider_core::crypto::{CipherError, StoreCipher};
 use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::canonicalize_url;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::collections::HashMap;
@@ -50,6 +78,17 @@ use std::sync::{Arc, RwLock};
 use thiserror::Error;
 use uuid::Uuid;
 
+pub mod duplicates;
+mod netscape;
+
+use duplicates::url_key;
+pub use duplicates::{DuplicateGroup, DuplicateReason};
+use netscape::NetscapeFolder;
+
+/// Prefix of the IDs of the virtual collections listing each tag, such as
+/// `tag:rust`.
+pub const TAG_COLLECTION_PREFIX: &str = "tag:";
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -70,6 +109,12 @@ pub enum BookmarkError {
 
     #[error("Invalid bookmark data: {0}")]
     InvalidData(String),
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] CipherError),
+
+    #[error("Bookmarks file is encrypted and no encryption key was provided")]
+    Locked,
 }
 
 impl From<BookmarkError> for StreamError {
@@ -80,6 +125,10 @@ impl From<BookmarkError> for StreamError {
             BookmarkError::InvalidData(msg) => StreamError::Provider(msg),
             BookmarkError::Io(e) => StreamError::Internal(format!("IO error: {}", e)),
             BookmarkError::Json(e) => StreamError::Internal(format!("JSON error: {}", e)),
+            BookmarkError::Encryption(e) => {
+                StreamError::Internal(format!("Encryption error: {}", e))
+            }
+            e @ BookmarkError::Locked => StreamError::Internal(e.to_string()),
         }
     }
 }
@@ -109,6 +158,12 @@ pub struct Bookmark {
     pub updated_at: Option<DateTime<Utc>>,
     pub tags: Vec<String>,
     pub favicon_url: Option<String>,
+    /// Saved to read later; shown unread until cleared
+    #[serde(default)]
+    pub to_read: bool,
+    /// Public on services the bookmark is synced to, such as Pinboard
+    #[serde(default)]
+    pub shared: bool,
 }
 
 /// The root storage structure for bookmarks.
@@ -185,6 +240,7 @@ struct FirefoxBookmark {
 pub struct BookmarksProvider {
     storage_path: PathBuf,
     storage: Arc<RwLock<BookmarkStorage>>,
+    cipher: Option<StoreCipher>,
 }
 
 impl BookmarksProvider {
@@ -196,28 +252,46 @@ impl BookmarksProvider {
 
     /// Create a new bookmarks provider with a custom storage path.
     pub fn with_path(storage_path: PathBuf) -> Result<Self> {
+        Self::open(storage_path, None, false)
+    }
+
+    /// Create a bookmarks provider that reads an encrypted storage file with
+    /// `cipher`, and writes it encrypted if `encrypt` is set.
+    pub fn with_cipher(storage_path: PathBuf, cipher: StoreCipher, encrypt: bool) -> Result<Self> {
+        Self::open(storage_path, Some(cipher), encrypt)
+    }
+
+    fn open(storage_path: PathBuf, cipher: Option<StoreCipher>, encrypt: bool) -> Result<Self> {
         // Ensure parent directory exists
         if let Some(parent) = storage_path.parent() {
             std::fs::create_dir_all(parent).map_err(BookmarkError::Io)?;
         }
 
+        let write_cipher = cipher.clone().filter(|_| encrypt);
+
         // Load or create storage
         let storage = if storage_path.exists() {
-            Self::load_storage(&storage_path)?
+            let (storage, sealed) = Self::load_storage(&storage_path, cipher.as_ref())?;
+            // Bring the file in line with the encryption setting
+            if sealed != encrypt {
+                Self::save_storage(&storage_path, &storage, write_cipher.as_ref())?;
+            }
+            storage
         } else {
             let default_storage = BookmarkStorage::default();
-            Self::save_storage(&storage_path, &default_storage)?;
+            Self::save_storage(&storage_path, &default_storage, write_cipher.as_ref())?;
             default_storage
         };
 
         Ok(Self {
             storage_path,
             storage: Arc::new(RwLock::new(storage)),
+            cipher: write_cipher,
         })
     }
 
     /// Get the default storage path in XDG_DATA_HOME.
-    fn default_storage_path() -> Result<PathBuf> {
+    pub fn default_storage_path() -> Result<PathBuf> {
         let dirs =
             directories::ProjectDirs::from("com", "raibid-labs", "scryforge").ok_or_else(|| {
                 StreamError::Internal("Could not determine data directory".to_string())
@@ -231,17 +305,31 @@ impl BookmarksProvider {
         Ok(data_dir.join("bookmarks.json"))
     }
 
-    /// Load bookmark storage from disk.
-    fn load_storage(path: &Path) -> Result<BookmarkStorage> {
-        let contents = std::fs::read_to_string(path).map_err(BookmarkError::Io)?;
+    /// Load bookmark storage from disk, and whether the file was encrypted.
+    fn load_storage(path: &Path, cipher: Option<&StoreCipher>) -> Result<(BookmarkStorage, bool)> {
+        let mut contents = std::fs::read_to_string(path).map_err(BookmarkError::Io)?;
+        let sealed = StoreCipher::is_sealed(&contents);
+        if sealed {
+            let cipher = cipher.ok_or(BookmarkError::Locked)?;
+            contents = cipher
+                .open_str(contents.trim_end())
+                .map_err(BookmarkError::Encryption)?;
+        }
         let storage: BookmarkStorage =
             serde_json::from_str(&contents).map_err(BookmarkError::Json)?;
-        Ok(storage)
+        Ok((storage, sealed))
     }
 
-    /// Save bookmark storage to disk.
-    fn save_storage(path: &Path, storage: &BookmarkStorage) -> Result<()> {
-        let contents = serde_json::to_string_pretty(storage).map_err(BookmarkError::Json)?;
+    /// Save bookmark storage to disk, encrypted if there is a cipher.
+    fn save_storage(
+        path: &Path,
+        storage: &BookmarkStorage,
+        cipher: Option<&StoreCipher>,
+    ) -> Result<()> {
+        let mut contents = serde_json::to_string_pretty(storage).map_err(BookmarkError::Json)?;
+        if let Some(cipher) = cipher {
+            contents = cipher.seal_str(&contents);
+        }
         std::fs::write(path, contents).map_err(BookmarkError::Io)?;
         Ok(())
     }
@@ -249,7 +337,59 @@ impl BookmarksProvider {
     /// Persist the current storage state to disk.
     fn persist(&self) -> Result<()> {
         let storage = self.storage.read().unwrap();
-        Self::save_storage(&self.storage_path, &storage)
+        Self::save_storage(&self.storage_path, &storage, self.cipher.as_ref())
+    }
+
+    /// Apply `retag` to the tags of every bookmark, persisting if any changed.
+    ///
+    /// `retag` returns whether it changed the tags. Returns how many bookmarks
+    /// changed.
+    fn retag_all(&self, retag: impl Fn(&mut Vec<String>) -> bool) -> Result<u32> {
+        let mut storage = self.storage.write().unwrap();
+        let now = Utc::now();
+        let mut changed = 0;
+        for bookmark in &mut storage.bookmarks {
+            if retag(&mut bookmark.tags) {
+                bookmark.updated_at = Some(now);
+                changed += 1;
+            }
+        }
+        drop(storage);
+
+        if changed > 0 {
+            self.persist()?;
+        }
+        Ok(changed)
+    }
+
+    /// Apply `retag` to the tags of the bookmark behind `item_id`.
+    fn retag_item(
+        &self,
+        item_id: &ItemId,
+        retag: impl FnOnce(&mut Vec<String>) -> bool,
+    ) -> Result<bool> {
+        let bookmark_id = item_id
+            .0
+            .strip_prefix("bookmarks:")
+            .ok_or_else(|| StreamError::ItemNotFound("Invalid item ID".to_string()))?;
+
+        let mut storage = self.storage.write().unwrap();
+        let bookmark = storage
+            .bookmarks
+            .iter_mut()
+            .find(|b| b.id == bookmark_id)
+            .ok_or_else(|| BookmarkError::NotFound(bookmark_id.to_string()))?;
+
+        let changed = retag(&mut bookmark.tags);
+        if changed {
+            bookmark.updated_at = Some(Utc::now());
+        }
+        drop(storage);
+
+        if changed {
+            self.persist()?;
+        }
+        Ok(changed)
     }
 
     /// Add a new bookmark.
@@ -280,6 +420,8 @@ impl BookmarksProvider {
             updated_at: None,
             tags,
             favicon_url: None,
+            to_read: false,
+            shared: false,
         };
 
         storage.bookmarks.push(bookmark.clone());
@@ -289,6 +431,182 @@ impl BookmarksProvider {
         Ok(bookmark)
     }
 
+    /// Save a page sent from a browser, returning its bookmark and whether
+    /// the bookmark is new.
+    ///
+    /// A page bookmarked before keeps its bookmark, which gains any new
+    /// tags. `folder` names a folder by ID or, ignoring case, by name.
+    pub fn capture(
+        &self,
+        title: String,
+        url: String,
+        folder: Option<&str>,
+        description: Option<String>,
+        tags: Vec<String>,
+    ) -> Result<(Bookmark, bool)> {
+        let key = url_key(&url);
+        let mut storage = self.storage.write().unwrap();
+        if let Some(bookmark) = storage
+            .bookmarks
+            .iter_mut()
+            .find(|b| url_key(&b.url) == key)
+        {
+            let new_tags: Vec<String> = tags
+                .into_iter()
+                .filter(|tag| !bookmark.tags.contains(tag))
+                .collect();
+            if new_tags.is_empty() {
+                return Ok((bookmark.clone(), false));
+            }
+            bookmark.tags.extend(new_tags);
+            bookmark.updated_at = Some(Utc::now());
+            let bookmark = bookmark.clone();
+            drop(storage);
+            self.persist()?;
+            return Ok((bookmark, false));
+        }
+
+        let folder_id = match folder {
+            Some(folder) => Some(
+                storage
+                    .folders
+                    .iter()
+                    .find(|f| f.id == folder || f.name.eq_ignore_ascii_case(folder))
+                    .map(|f| f.id.clone())
+                    .ok_or_else(|| BookmarkError::FolderNotFound(folder.to_string()))?,
+            ),
+            None => None,
+        };
+        drop(storage);
+
+        let bookmark = self.add_bookmark(title, url, folder_id, description, tags)?;
+        Ok((bookmark, true))
+    }
+
+    /// The bookmark for `url` or an equivalent URL, if there is one.
+    pub fn find_by_url(&self, url: &str) -> Option<Bookmark> {
+        let key = url_key(url);
+        let storage = self.storage.read().unwrap();
+        storage
+            .bookmarks
+            .iter()
+            .find(|b| url_key(&b.url) == key)
+            .cloned()
+    }
+
+    /// Every bookmark, in storage order.
+    pub fn bookmarks(&self) -> Vec<Bookmark> {
+        self.storage.read().unwrap().bookmarks.clone()
+    }
+
+    /// Store `bookmarks`, each replacing the bookmark with its ID or added
+    /// if there is none, persisting once.
+    pub fn put_bookmarks(&self, bookmarks: Vec<Bookmark>) -> Result<()> {
+        if bookmarks.is_empty() {
+            return Ok(());
+        }
+
+        let mut storage = self.storage.write().unwrap();
+        for bookmark in bookmarks {
+            match storage.bookmarks.iter_mut().find(|b| b.id == bookmark.id) {
+                Some(existing) => *existing = bookmark,
+                None => storage.bookmarks.push(bookmark),
+            }
+        }
+        drop(storage);
+        self.persist()
+    }
+
+    /// Delete the bookmarks with the given IDs, persisting once. Returns how
+    /// many existed.
+    pub fn delete_bookmarks(&self, ids: &[String]) -> Result<usize> {
+        let mut storage = self.storage.write().unwrap();
+        let before = storage.bookmarks.len();
+        storage.bookmarks.retain(|b| !ids.contains(&b.id));
+        let removed = before - storage.bookmarks.len();
+        drop(storage);
+
+        if removed > 0 {
+            self.persist()?;
+        }
+        Ok(removed)
+    }
+
+    fn is_bookmarked(&self, url: &str) -> bool {
+        self.find_by_url(url).is_some()
+    }
+
+    /// Groups of bookmarks that look like copies of each other: the same
+    /// page under different URLs, or nearly the same title.
+    pub fn find_duplicates(&self) -> Vec<DuplicateGroup> {
+        let bookmarks = self.storage.read().unwrap().bookmarks.clone();
+        duplicates::group_duplicates(&bookmarks)
+            .into_iter()
+            .map(|(reason, members)| DuplicateGroup {
+                reason,
+                items: members
+                    .into_iter()
+                    .map(|i| self.bookmark_to_item(&bookmarks[i]))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Fold `duplicates` into the bookmark `keep`, deleting them.
+    ///
+    /// The kept bookmark gains their tags, takes their description or
+    /// favicon when it has none, moves into their folder when it is in
+    /// none, and keeps the earliest creation date. Nothing changes unless
+    /// every bookmark exists.
+    pub fn merge_bookmarks(&self, keep: &str, duplicates: &[String]) -> Result<Bookmark> {
+        let mut storage = self.storage.write().unwrap();
+        let mut merged = storage
+            .bookmarks
+            .iter()
+            .find(|b| b.id == keep)
+            .cloned()
+            .ok_or_else(|| BookmarkError::NotFound(keep.to_string()))?;
+
+        let mut removed = Vec::new();
+        for id in duplicates.iter().filter(|id| *id != keep) {
+            let duplicate = storage
+                .bookmarks
+                .iter()
+                .find(|b| &b.id == id)
+                .ok_or_else(|| BookmarkError::NotFound(id.clone()))?;
+            for tag in &duplicate.tags {
+                if !merged.tags.contains(tag) {
+                    merged.tags.push(tag.clone());
+                }
+            }
+            if merged.description.is_none() {
+                merged.description = duplicate.description.clone();
+            }
+            if merged.favicon_url.is_none() {
+                merged.favicon_url = duplicate.favicon_url.clone();
+            }
+            if merged.folder_id.is_none() {
+                merged.folder_id = duplicate.folder_id.clone();
+            }
+            merged.created_at = merged.created_at.min(duplicate.created_at);
+            removed.push(id.as_str());
+        }
+        if removed.is_empty() {
+            return Ok(merged);
+        }
+
+        merged.updated_at = Some(Utc::now());
+        storage
+            .bookmarks
+            .retain(|b| !removed.contains(&b.id.as_str()));
+        if let Some(bookmark) = storage.bookmarks.iter_mut().find(|b| b.id == keep) {
+            *bookmark = merged.clone();
+        }
+        drop(storage);
+        self.persist()?;
+        Ok(merged)
+    }
+
     /// Add a new folder.
     pub fn add_folder(
         &self,
@@ -356,12 +674,8 @@ impl BookmarksProvider {
             }
             "url" => {
                 if let Some(ref url) = node.url {
-                    // Check for duplicates
-                    let storage = self.storage.read().unwrap();
-                    let exists = storage.bookmarks.iter().any(|b| &b.url == url);
-                    drop(storage);
-
-                    if !exists {
+                    // Skip pages that are already bookmarked
+                    if !self.is_bookmarked(url) {
                         self.add_bookmark(
                             node.name.clone(),
                             url.clone(),
@@ -414,12 +728,8 @@ impl BookmarksProvider {
             }
             "text/x-moz-place" => {
                 if let Some(ref url) = node.uri {
-                    // Check for duplicates
-                    let storage = self.storage.read().unwrap();
-                    let exists = storage.bookmarks.iter().any(|b| &b.url == url);
-                    drop(storage);
-
-                    if !exists {
+                    // Skip pages that are already bookmarked
+                    if !self.is_bookmarked(url) {
                         self.add_bookmark(
                             node.title.clone(),
                             url.clone(),
@@ -442,8 +752,74 @@ impl BookmarksProvider {
         Ok(())
     }
 
+    /// Import bookmarks from an HTML bookmark file, as exported by browsers
+    /// and included in Google Takeout as `Chrome/Bookmarks.html`.
+    pub fn import_from_html(&self, html_path: &Path) -> Result<(usize, Vec<String>)> {
+        let contents = std::fs::read_to_string(html_path).map_err(BookmarkError::Io)?;
+        Ok(self.import_html(&contents))
+    }
+
+    /// Import bookmarks from the contents of an HTML bookmark file.
+    ///
+    /// Folders are matched to existing ones by name, so importing the same
+    /// file again adds only the pages bookmarked since.
+    pub fn import_html(&self, html: &str) -> (usize, Vec<String>) {
+        let root = netscape::parse(html);
+
+        let mut imported_count = 0;
+        let mut errors = Vec::new();
+        if let Err(e) = self.import_html_folder(&root, None, &mut imported_count) {
+            errors.push(format!("HTML import error: {}", e));
+        }
+
+        (imported_count, errors)
+    }
+
+    /// Recursively import a folder of an HTML bookmark file.
+    fn import_html_folder(
+        &self,
+        folder: &NetscapeFolder,
+        folder_id: Option<String>,
+        count: &mut usize,
+    ) -> Result<()> {
+        for bookmark in &folder.bookmarks {
+            // Skip pages that are already bookmarked
+            if !self.is_bookmarked(&bookmark.url) {
+                self.add_bookmark(
+                    bookmark.title.clone(),
+                    bookmark.url.clone(),
+                    folder_id.clone(),
+                    None,
+                    vec![],
+                )?;
+                *count += 1;
+            }
+        }
+
+        for child in &folder.folders {
+            let existing = self
+                .storage
+                .read()
+                .unwrap()
+                .folders
+                .iter()
+                .find(|f| f.name == child.name)
+                .map(|f| f.id.clone());
+            let child_id = match existing {
+                Some(id) => id,
+                None => {
+                    self.add_folder(child.name.clone(), None, Some("📁".to_string()))?
+                        .id
+                }
+            };
+            self.import_html_folder(child, Some(child_id), count)?;
+        }
+
+        Ok(())
+    }
+
     /// Convert a bookmark to an Item.
-    fn bookmark_to_item(&self, bookmark: &Bookmark) -> Item {
+    pub fn bookmark_to_item(&self, bookmark: &Bookmark) -> Item {
         let folder_name = if let Some(ref folder_id) = bookmark.folder_id {
             let storage = self.storage.read().unwrap();
             storage
@@ -476,9 +852,9 @@ impl BookmarksProvider {
             author: None,
             published: Some(bookmark.created_at),
             updated: bookmark.updated_at,
-            url: Some(bookmark.url.clone()),
+            url: Some(canonicalize_url(&bookmark.url)),
             thumbnail_url: bookmark.favicon_url.clone(),
-            is_read: true,  // Bookmarks are always "read"
+            is_read: !bookmark.to_read,
             is_saved: true, // All bookmarks are saved by definition
             tags: bookmark.tags.clone(),
             metadata,
@@ -522,7 +898,7 @@ impl Provider for BookmarksProvider {
         // Reload from disk to pick up any external changes
         let start = std::time::Instant::now();
 
-        let new_storage = Self::load_storage(&self.storage_path)?;
+        let (new_storage, _) = Self::load_storage(&self.storage_path, self.cipher.as_ref())?;
         let mut storage = self.storage.write().unwrap();
         *storage = new_storage;
 
@@ -535,6 +911,7 @@ impl Provider for BookmarksProvider {
             items_removed: 0,
             errors: vec![],
             duration_ms,
+            streams: Vec::new(),
         })
     }
 
@@ -544,6 +921,11 @@ impl Provider for BookmarksProvider {
             has_collections: true,
             has_saved_items: true,
             has_communities: false,
+            supports_search: true,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
@@ -652,14 +1034,27 @@ impl Provider for BookmarksProvider {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        Some(self)
+    }
+
+    fn as_read_later(&self) -> Option<&dyn HasReadLater> {
+        Some(self)
+    }
+
+    fn as_tags(&self) -> Option<&dyn HasTags> {
+        Some(self)
+    }
 }
 
 #[async_trait]
 impl HasCollections for BookmarksProvider {
     async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let tags = self.list_tags().await?;
         let storage = self.storage.read().unwrap();
 
-        let collections = storage
+        let mut collections = storage
             .folders
             .iter()
             .map(|folder| {
@@ -679,12 +1074,26 @@ impl HasCollections for BookmarksProvider {
                     owner: None,
                 }
             })
-            .collect();
+            .collect::<Vec<_>>();
+
+        collections.extend(tags.into_iter().map(|tag| Collection {
+            id: CollectionId(format!("{}{}", TAG_COLLECTION_PREFIX, tag.name)),
+            name: format!("#{}", tag.name),
+            description: None,
+            icon: Some("🏷".to_string()),
+            item_count: tag.count,
+            is_editable: true,
+            owner: None,
+        }));
 
         Ok(collections)
     }
 
     async fn get_collection_items(&self, collection_id: &CollectionId) -> Result<Vec<Item>> {
+        if let Some(tag) = collection_id.0.strip_prefix(TAG_COLLECTION_PREFIX) {
+            return self.tagged_items(tag).await;
+        }
+
         let storage = self.storage.read().unwrap();
 
         // Verify collection exists
@@ -707,6 +1116,17 @@ impl HasCollections for BookmarksProvider {
         collection_id: &CollectionId,
         item_id: &ItemId,
     ) -> Result<()> {
+        if let Some(tag) = collection_id.0.strip_prefix(TAG_COLLECTION_PREFIX) {
+            self.retag_item(item_id, |tags| {
+                let untagged = !tags.iter().any(|t| t == tag);
+                if untagged {
+                    tags.push(tag.to_string());
+                }
+                untagged
+            })?;
+            return Ok(());
+        }
+
         // Extract bookmark ID from item ID
         let bookmark_id = item_id
             .0
@@ -741,6 +1161,21 @@ impl HasCollections for BookmarksProvider {
         collection_id: &CollectionId,
         item_id: &ItemId,
     ) -> Result<()> {
+        if let Some(tag) = collection_id.0.strip_prefix(TAG_COLLECTION_PREFIX) {
+            let untagged = self.retag_item(item_id, |tags| {
+                let before = tags.len();
+                tags.retain(|t| t != tag);
+                tags.len() < before
+            })?;
+            if !untagged {
+                return Err(StreamError::Provider(format!(
+                    "Bookmark is not tagged '{}'",
+                    tag
+                )));
+            }
+            return Ok(());
+        }
+
         // Extract bookmark ID from item ID
         let bookmark_id = item_id
             .0
@@ -882,6 +1317,103 @@ impl HasSavedItems for BookmarksProvider {
     }
 }
 
+#[async_trait]
+impl HasTags for BookmarksProvider {
+    async fn list_tags(&self) -> Result<Vec<TagCount>> {
+        let storage = self.storage.read().unwrap();
+
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        for bookmark in &storage.bookmarks {
+            let mut seen = Vec::new();
+            for tag in &bookmark.tags {
+                if !seen.contains(&tag) {
+                    seen.push(tag);
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+        }
+
+        let mut tags: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(name, count)| TagCount {
+                name: name.to_string(),
+                count,
+            })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        Ok(tags)
+    }
+
+    async fn tagged_items(&self, tag: &str) -> Result<Vec<Item>> {
+        let storage = self.storage.read().unwrap();
+        let tagged: Vec<Bookmark> = storage
+            .bookmarks
+            .iter()
+            .filter(|b| b.tags.iter().any(|t| t == tag))
+            .cloned()
+            .collect();
+        drop(storage);
+
+        Ok(tagged.iter().map(|b| self.bookmark_to_item(b)).collect())
+    }
+
+    async fn merge_tags(&self, sources: &[String], into: &str) -> Result<u32> {
+        let into = into.trim();
+        if into.is_empty() {
+            return Err(
+                BookmarkError::InvalidData("Tag names must not be empty".to_string()).into(),
+            );
+        }
+
+        self.retag_all(|tags| {
+            let Some(first) = tags.iter().position(|t| sources.contains(t) && t != into) else {
+                return false;
+            };
+            let had_target = tags.iter().any(|t| t == into);
+            tags.retain(|t| !sources.contains(t) || t == into);
+            if !had_target {
+                tags.insert(first.min(tags.len()), into.to_string());
+            }
+            true
+        })
+    }
+
+    async fn delete_tag(&self, tag: &str) -> Result<u32> {
+        self.retag_all(|tags| {
+            let before = tags.len();
+            tags.retain(|t| t != tag);
+            tags.len() < before
+        })
+    }
+}
+
+#[async_trait]
+impl HasReadLater for BookmarksProvider {
+    async fn save_for_later(&self, item: &Item) -> Result<ItemId> {
+        let url = item.url.clone().ok_or_else(|| {
+            StreamError::Provider(format!(
+                "Item '{}' has no URL to bookmark",
+                item.id.as_str()
+            ))
+        })?;
+
+        // Saving the same link twice returns the existing bookmark
+        if let Some(id) = self.find_by_url(&url).map(|b| b.id) {
+            return Ok(ItemId::new("bookmarks", &id));
+        }
+
+        let bookmark = self.add_bookmark(
+            item.title.clone(),
+            url,
+            None,
+            item.content.plain_text(),
+            item.tags.clone(),
+        )?;
+
+        Ok(ItemId::new("bookmarks", &bookmark.id))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1210,6 +1742,48 @@ mod tests {
         assert!(items.iter().any(|i| i.title == "MDN"));
     }
 
+    #[tokio::test]
+    async fn test_import_html_bookmarks() {
+        let (provider, _temp_dir) = create_temp_provider();
+        provider
+            .add_bookmark(
+                "Rust".to_string(),
+                "https://www.rust-lang.org/".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<DL><p>
+    <DT><H3 PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://www.rust-lang.org">Rust</A>
+        <DT><A HREF="https://docs.rs/">docs.rs</A>
+    </DL><p>
+    <DT><A HREF="https://crates.io/">crates.io</A>
+</DL><p>"#;
+        let (count, errors) = provider.import_html(html);
+        assert_eq!(count, 2);
+        assert!(errors.is_empty());
+
+        let storage = provider.storage.read().unwrap().clone();
+        assert_eq!(storage.folders.len(), 1);
+        assert_eq!(storage.folders[0].name, "Bookmarks bar");
+        let docs = provider.find_by_url("https://docs.rs/").unwrap();
+        assert_eq!(docs.folder_id.as_ref(), Some(&storage.folders[0].id));
+        assert!(provider
+            .find_by_url("https://crates.io/")
+            .unwrap()
+            .folder_id
+            .is_none());
+
+        // A second import reuses the folder and skips known pages
+        assert_eq!(provider.import_html(html), (0, vec![]));
+        assert_eq!(provider.storage.read().unwrap().folders.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_available_actions() {
         let (provider, _temp_dir) = create_temp_provider();
@@ -1300,6 +1874,52 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_encrypted_storage() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage_path = temp_dir.path().join("bookmarks.json");
+        let cipher = StoreCipher::new(&[3; 32]);
+
+        // A plaintext file is encrypted when a cipher is first used
+        BookmarksProvider::with_path(storage_path.clone())
+            .unwrap()
+            .add_bookmark(
+                "Private".to_string(),
+                "https://private.example.com".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+        let provider =
+            BookmarksProvider::with_cipher(storage_path.clone(), cipher.clone(), true).unwrap();
+        let contents = std::fs::read_to_string(&storage_path).unwrap();
+        assert!(StoreCipher::is_sealed(&contents));
+        assert!(!contents.contains("private.example.com"));
+
+        provider.sync().await.unwrap();
+        let items = provider
+            .get_saved_items(SavedItemsOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(items[0].title, "Private");
+
+        // Without the key the file cannot be read
+        assert!(BookmarksProvider::with_path(storage_path.clone()).is_err());
+        assert!(BookmarksProvider::with_cipher(
+            storage_path.clone(),
+            StoreCipher::new(&[4; 32]),
+            true
+        )
+        .is_err());
+
+        // Turning encryption off again decrypts the file
+        drop(provider);
+        BookmarksProvider::with_cipher(storage_path.clone(), cipher, false).unwrap();
+        let contents = std::fs::read_to_string(&storage_path).unwrap();
+        assert!(contents.contains("private.example.com"));
+    }
+
     #[tokio::test]
     async fn test_sync() {
         let (provider, _temp_dir) = create_temp_provider();
@@ -1427,4 +2047,342 @@ mod tests {
             .unwrap();
         assert_eq!(items.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_save_for_later_deduplicates_by_url() {
+        let (provider, _temp_dir) = create_temp_provider();
+
+        let item = Item {
+            id: ItemId::new("rss", "post-1"),
+            stream_id: StreamId::new("rss", "feed", "news"),
+            title: "Interesting post".to_string(),
+            content: ItemContent::Text("Worth reading later".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("https://example.com/post".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec!["rust".to_string()],
+            metadata: HashMap::new(),
+        };
+
+        let reader = provider.as_read_later().unwrap();
+        let first = reader.save_for_later(&item).await.unwrap();
+        let second = reader.save_for_later(&item).await.unwrap();
+        assert_eq!(first, second);
+
+        let saved = provider
+            .get_saved_items(SavedItemsOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].title, "Interesting post");
+
+        let no_url = Item { url: None, ..item };
+        assert!(reader.save_for_later(&no_url).await.is_err());
+    }
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_tags() {
+        let (provider, _temp_dir) = create_temp_provider();
+        for (title, url, tag_list) in [
+            ("Rust", "https://rust-lang.org", &["rust", "lang"][..]),
+            ("Cargo", "https://crates.io", &["rust"][..]),
+            ("Go", "https://go.dev", &["go", "lang"][..]),
+        ] {
+            provider
+                .add_bookmark(
+                    title.to_string(),
+                    url.to_string(),
+                    None,
+                    None,
+                    tags(tag_list),
+                )
+                .unwrap();
+        }
+
+        let counts = provider.list_tags().await.unwrap();
+        let summary: Vec<(&str, u32)> = counts.iter().map(|t| (t.name.as_str(), t.count)).collect();
+        assert_eq!(summary, vec![("lang", 2), ("rust", 2), ("go", 1)]);
+
+        let collections = provider.list_collections().await.unwrap();
+        let rust = collections.iter().find(|c| c.id.0 == "tag:rust").unwrap();
+        assert_eq!(rust.name, "#rust");
+        assert_eq!(rust.item_count, 2);
+
+        let items = provider
+            .get_collection_items(&CollectionId("tag:go".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Go");
+    }
+
+    #[tokio::test]
+    async fn test_rename_and_merge_tags() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let first = provider
+            .add_bookmark(
+                "Rust".to_string(),
+                "https://rust-lang.org".to_string(),
+                None,
+                None,
+                tags(&["rustlang", "lang", "rs"]),
+            )
+            .unwrap();
+        provider
+            .add_bookmark(
+                "Cargo".to_string(),
+                "https://crates.io".to_string(),
+                None,
+                None,
+                tags(&["rust"]),
+            )
+            .unwrap();
+
+        assert_eq!(provider.rename_tag("lang", "languages").await.unwrap(), 1);
+        assert_eq!(
+            provider
+                .merge_tags(&tags(&["rustlang", "rs"]), "rust")
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(provider.merge_tags(&tags(&["rust"]), " ").await.is_err());
+
+        let merged = provider.storage.read().unwrap().bookmarks[0].clone();
+        assert_eq!(merged.id, first.id);
+        assert_eq!(merged.tags, tags(&["rust", "languages"]));
+        assert!(merged.updated_at.is_some());
+
+        let counts = provider.list_tags().await.unwrap();
+        assert_eq!(counts[0].name, "rust");
+        assert_eq!(counts[0].count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_tag_and_tag_collections() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let bookmark = provider
+            .add_bookmark(
+                "Rust".to_string(),
+                "https://rust-lang.org".to_string(),
+                None,
+                None,
+                tags(&["rust"]),
+            )
+            .unwrap();
+        let item_id = ItemId::new("bookmarks", &bookmark.id);
+        let reading = CollectionId("tag:reading".to_string());
+
+        provider
+            .add_to_collection(&reading, &item_id)
+            .await
+            .unwrap();
+        assert_eq!(provider.tagged_items("reading").await.unwrap().len(), 1);
+
+        provider
+            .remove_from_collection(&reading, &item_id)
+            .await
+            .unwrap();
+        assert!(provider
+            .remove_from_collection(&reading, &item_id)
+            .await
+            .is_err());
+
+        assert_eq!(provider.delete_tag("rust").await.unwrap(), 1);
+        assert_eq!(provider.delete_tag("rust").await.unwrap(), 0);
+        assert!(provider.list_tags().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_capture() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let folder = provider
+            .add_folder("Inbox".to_string(), None, None)
+            .unwrap();
+
+        let (first, created) = provider
+            .capture(
+                "Rust".to_string(),
+                "https://rust-lang.org".to_string(),
+                Some("inbox"),
+                None,
+                tags(&["rust"]),
+            )
+            .unwrap();
+        assert!(created);
+        assert_eq!(first.folder_id, Some(folder.id));
+
+        // Capturing the page again only adds the new tags
+        let (again, created) = provider
+            .capture(
+                "Rust Programming Language".to_string(),
+                "https://rust-lang.org".to_string(),
+                None,
+                None,
+                tags(&["rust", "lang"]),
+            )
+            .unwrap();
+        assert!(!created);
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.title, "Rust");
+        assert_eq!(again.tags, tags(&["rust", "lang"]));
+
+        assert!(provider
+            .capture(
+                "Go".to_string(),
+                "https://go.dev".to_string(),
+                Some("missing"),
+                None,
+                vec![],
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_equivalent_urls_are_not_added_twice() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let (first, _) = provider
+            .capture(
+                "Post".to_string(),
+                "https://example.com/post".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+
+        let (again, created) = provider
+            .capture(
+                "Post".to_string(),
+                "http://www.example.com/post/?utm_source=newsletter".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+        assert!(!created);
+        assert_eq!(again.id, first.id);
+        assert_eq!(
+            provider
+                .find_by_url("https://example.com/post#comments")
+                .map(|b| b.id),
+            Some(first.id)
+        );
+        assert!(provider
+            .find_by_url("https://example.com/post?page=2")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_and_merge_duplicates() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let folder = provider.add_folder("Rust".to_string(), None, None).unwrap();
+        let keep = provider
+            .add_bookmark(
+                "Writing a Tiny Parser in Rust".to_string(),
+                "https://blog.example.com/parser".to_string(),
+                None,
+                None,
+                tags(&["rust"]),
+            )
+            .unwrap();
+        let mirror = provider
+            .add_bookmark(
+                "Writing a tiny parser in Rust | Example Blog".to_string(),
+                "https://mirror.example.net/parser".to_string(),
+                Some(folder.id.clone()),
+                Some("Part one".to_string()),
+                tags(&["parsing", "rust"]),
+            )
+            .unwrap();
+        provider
+            .add_bookmark(
+                "Unrelated".to_string(),
+                "https://example.org".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+
+        let groups = provider.find_duplicates();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::SimilarTitle);
+        let ids: Vec<&str> = groups[0].items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                format!("bookmarks:{}", keep.id),
+                format!("bookmarks:{}", mirror.id)
+            ]
+        );
+
+        // Nothing changes when a duplicate is missing
+        assert!(provider
+            .merge_bookmarks(&keep.id, &[mirror.id.clone(), "missing".to_string()])
+            .is_err());
+        assert_eq!(provider.storage.read().unwrap().bookmarks.len(), 3);
+
+        let merged = provider
+            .merge_bookmarks(&keep.id, std::slice::from_ref(&mirror.id))
+            .unwrap();
+        assert_eq!(merged.title, "Writing a Tiny Parser in Rust");
+        assert_eq!(merged.tags, tags(&["rust", "parsing"]));
+        assert_eq!(merged.description.as_deref(), Some("Part one"));
+        assert_eq!(merged.folder_id, Some(folder.id));
+        assert_eq!(provider.storage.read().unwrap().bookmarks.len(), 2);
+        assert!(provider.find_duplicates().is_empty());
+
+        // The merge was persisted
+        let reloaded = BookmarksProvider::with_path(provider.storage_path.clone()).unwrap();
+        let saved = reloaded
+            .find_by_url("https://blog.example.com/parser")
+            .unwrap();
+        assert_eq!(saved.tags, tags(&["rust", "parsing"]));
+    }
+
+    #[tokio::test]
+    async fn test_put_and_delete_bookmarks() {
+        let (provider, _temp_dir) = create_temp_provider();
+        let mut existing = provider
+            .add_bookmark(
+                "Rust".to_string(),
+                "https://rust-lang.org".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap();
+
+        existing.to_read = true;
+        let mut added = existing.clone();
+        added.id = "pinboard-import".to_string();
+        added.url = "https://crates.io".to_string();
+        provider
+            .put_bookmarks(vec![existing.clone(), added])
+            .unwrap();
+
+        let bookmarks = provider.bookmarks();
+        assert_eq!(bookmarks.len(), 2);
+        assert!(bookmarks[0].to_read);
+        let item = provider.bookmark_to_item(&bookmarks[0]);
+        assert!(!item.is_read);
+
+        let removed = provider
+            .delete_bookmarks(&[existing.id, "missing".to_string()])
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let reloaded = BookmarksProvider::with_path(provider.storage_path.clone()).unwrap();
+        let ids: Vec<String> = reloaded.bookmarks().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["pinboard-import"]);
+    }
 }
@@ -0,0 +1,164 @@
+//! Parsing of the Netscape bookmark file format.
+//!
+//! Browsers export bookmarks as HTML in this format, and Google Takeout
+//! delivers Chrome bookmarks as `Bookmarks.html`. Folders are `<H3>`
+//! headings followed by a `<DL>` list of their contents; bookmarks are
+//! `<A HREF="...">` links. The markup is rarely well formed, so only those
+//! tags are looked at.
+
+use scryforge_provider_core::decode_entities;
+
+/// A folder of a bookmark file; the file itself is the unnamed root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NetscapeFolder {
+    pub name: String,
+    pub bookmarks: Vec<NetscapeBookmark>,
+    pub folders: Vec<NetscapeFolder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NetscapeBookmark {
+    pub title: String,
+    pub url: String,
+}
+
+/// Parse a bookmark file into its root folder.
+pub(crate) fn parse(html: &str) -> NetscapeFolder {
+    // Folders still open, innermost last
+    let mut stack = vec![NetscapeFolder::default()];
+    // A heading names the folder whose list follows it
+    let mut pending: Option<String> = None;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match name.as_str() {
+            "h3" => {
+                let (text, after) = element_text(rest, "</h3");
+                pending = Some(text);
+                rest = after;
+            }
+            "dl" => {
+                if let Some(name) = pending.take() {
+                    stack.push(NetscapeFolder {
+                        name,
+                        ..Default::default()
+                    });
+                }
+            }
+            "/dl" => {
+                if stack.len() > 1 {
+                    let folder = stack.pop().unwrap();
+                    stack.last_mut().unwrap().folders.push(folder);
+                }
+            }
+            "a" => {
+                let (title, after) = element_text(rest, "</a");
+                rest = after;
+                if let Some(url) = attribute(tag, "href").filter(|url| !url.is_empty()) {
+                    let title = if title.is_empty() { url.clone() } else { title };
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .bookmarks
+                        .push(NetscapeBookmark { title, url });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Close folders left open by a truncated file
+    while stack.len() > 1 {
+        let folder = stack.pop().unwrap();
+        stack.last_mut().unwrap().folders.push(folder);
+    }
+    stack.pop().unwrap()
+}
+
+/// The decoded text up to the closing tag `close`, and the input after it.
+fn element_text<'a>(input: &'a str, close: &str) -> (String, &'a str) {
+    let lower = input.to_ascii_lowercase();
+    let end = lower.find(close).unwrap_or(input.len());
+    let after = input[end..].find('>').map_or("", |i| &input[end + i + 1..]);
+    (decode_entities(input[..end].trim()), after)
+}
+
+/// The decoded value of a tag attribute, matched ignoring case.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(found) = lower[from..].find(name) {
+        let start = from + found;
+        from = start + name.len();
+        // Skip matches inside other attribute names, such as `data-href`
+        if !lower[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let value = tag[from..].trim_start().strip_prefix('=')?.trim_start();
+        let value = match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+            _ => value.split(char::is_whitespace).next()?,
+        };
+        return Some(decode_entities(value));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1700000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://www.rust-lang.org/" ADD_DATE="1700000001" ICON="data:image/png;base64,AAAA">Rust</A>
+        <DT><H3>Dev &amp; Tools</H3>
+        <DL><p>
+            <DT><A href='https://docs.rs/?q=a&amp;b'>docs.rs</A>
+        </DL><p>
+    </DL><p>
+    <DT><A HREF="https://example.com/"></A>
+</DL><p>
+"#;
+        let root = parse(html);
+        assert_eq!(
+            root.bookmarks,
+            vec![NetscapeBookmark {
+                title: "https://example.com/".to_string(),
+                url: "https://example.com/".to_string(),
+            }]
+        );
+        assert_eq!(root.folders.len(), 1);
+        let bar = &root.folders[0];
+        assert_eq!(bar.name, "Bookmarks bar");
+        assert_eq!(bar.bookmarks[0].title, "Rust");
+        assert_eq!(bar.bookmarks[0].url, "https://www.rust-lang.org/");
+        assert_eq!(bar.folders[0].name, "Dev & Tools");
+        assert_eq!(bar.folders[0].bookmarks[0].url, "https://docs.rs/?q=a&b");
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let root = parse("<DL><DT><H3>Open</H3><DL><DT><A HREF=https://a.example>A</A>");
+        assert_eq!(root.folders[0].name, "Open");
+        assert_eq!(root.folders[0].bookmarks[0].url, "https://a.example");
+    }
+}
@@ -5,12 +5,73 @@
 //! This provider returns static fixture data and does not connect to any real services.
 //! It implements the `Provider` and `HasFeeds` traits to demonstrate the provider pattern
 //! and to facilitate testing of the daemon and TUI components.
+//!
+//! ## Fault Injection
+//!
+//! [`DummyProvider::with_faults`] makes the provider misbehave in repeatable
+//! ways: slow responses, every Nth call failing or rate limited, a feed of
+//! thousands of items, and feeds that trickle in item by item. The daemon
+//! reads a [`FaultConfig`] from `[providers.dummy.settings]`:
+//!
+//! ```toml
+//! [providers.dummy.settings]
+//! latency_ms = 500
+//! fail_every = 3
+//! giant_feed_items = 10000
+//! ```
+//!
+//! ## Scenarios
+//!
+//! [`DummyProvider::with_scenario`] replays a scripted timeline of syncs and
+//! new items; see the [`scenario`] module. The daemon loads one from the
+//! `scenario` setting, a path to a TOML or JSON file.
+
+pub mod scenario;
+
+pub use scenario::{Scenario, ScenarioItem, ScenarioStep};
 
 use async_trait::async_trait;
 use chrono::Utc;
 use scryforge_provider_core::prelude::*;
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::scenario::Playback;
+
+/// ID of the feed served when [`FaultConfig::giant_feed_items`] is set.
+pub const GIANT_FEED_ID: &str = "dummy:giant";
+
+/// Faults for the dummy provider to inject. The default injects none.
+///
+/// Failures are counted over sync, feed, and collection calls, so a given
+/// sequence of calls always fails at the same points.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Delay before every call returns, in milliseconds
+    pub latency_ms: u64,
+    /// Fail every Nth call with a network error (0 disables)
+    pub fail_every: u64,
+    /// Reject every Nth call as rate limited (0 disables)
+    pub rate_limit_every: u64,
+    /// Retry-after seconds reported for rate-limited calls
+    pub retry_after_secs: u64,
+    /// Number of items in an extra [`GIANT_FEED_ID`] feed (0 disables)
+    pub giant_feed_items: usize,
+    /// Extra delay per returned feed item, in milliseconds, to simulate a
+    /// slow stream
+    pub item_delay_ms: u64,
+}
+
+impl FaultConfig {
+    /// Whether any fault is configured.
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
 
 /// Internal state for collections.
 #[derive(Debug, Clone)]
@@ -73,14 +134,157 @@ impl Default for CollectionState {
 /// A dummy provider that returns static test data.
 pub struct DummyProvider {
     state: Arc<Mutex<CollectionState>>,
+    faults: FaultConfig,
+    calls: AtomicU64,
+    playback: Option<Mutex<Playback>>,
 }
 
 impl DummyProvider {
     /// Create a new dummy provider instance.
     pub fn new() -> Self {
+        Self::with_faults(FaultConfig::default())
+    }
+
+    /// Create a dummy provider that injects `faults`.
+    pub fn with_faults(faults: FaultConfig) -> Self {
         Self {
             state: Arc::new(Mutex::new(CollectionState::default())),
+            faults,
+            calls: AtomicU64::new(0),
+            playback: None,
+        }
+    }
+
+    /// Replay `scenario`, one step per sync.
+    pub fn with_scenario(mut self, scenario: Scenario) -> Self {
+        self.playback = Some(Mutex::new(Playback::new(scenario)));
+        self
+    }
+
+    /// Play the next scenario step, if a scenario is loaded and unfinished.
+    async fn play_step(&self) -> Result<Option<SyncResult>> {
+        let Some(playback) = self.playback.as_ref() else {
+            return Ok(None);
+        };
+        let Some(step) = playback.lock().unwrap().advance() else {
+            return Ok(None);
+        };
+
+        if step.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(step.delay_ms)).await;
+        }
+        if let Some(retry_after) = step.rate_limited {
+            return Err(StreamError::RateLimited(retry_after));
         }
+
+        Ok(Some(SyncResult {
+            success: step.error.is_none(),
+            items_added: if step.error.is_none() {
+                step.items.len() as u32
+            } else {
+                0
+            },
+            items_updated: 0,
+            items_removed: 0,
+            errors: step.error.into_iter().collect(),
+            duration_ms: step.delay_ms,
+            streams: Vec::new(),
+        }))
+    }
+
+    /// Static feeds plus any feeds a scenario has published into.
+    fn feeds_with_scenario(&self) -> Vec<Feed> {
+        let mut feeds = Self::dummy_feeds();
+        let Some(playback) = self.playback.as_ref() else {
+            return feeds;
+        };
+
+        let playback = playback.lock().unwrap();
+        for feed_id in playback.feed_ids() {
+            let items = playback.items(&feed_id);
+            let unread = items.iter().filter(|item| !item.is_read).count() as u32;
+            let total = items.len() as u32;
+
+            match feeds.iter_mut().find(|feed| feed.id.0 == feed_id) {
+                Some(feed) => {
+                    feed.unread_count = feed.unread_count.map(|count| count + unread);
+                    feed.total_count = feed.total_count.map(|count| count + total);
+                }
+                None => feeds.push(Feed {
+                    name: feed_id
+                        .strip_prefix("dummy:")
+                        .unwrap_or(&feed_id)
+                        .to_string(),
+                    id: FeedId(feed_id),
+                    description: Some(format!("Published by scenario {}", playback.name())),
+                    icon: Some("🎬".to_string()),
+                    unread_count: Some(unread),
+                    total_count: Some(total),
+                }),
+            }
+        }
+        feeds
+    }
+
+    /// Injected faults.
+    pub fn faults(&self) -> &FaultConfig {
+        &self.faults
+    }
+
+    /// Apply the configured latency, then fail if this call is due to.
+    async fn inject_faults(&self) -> Result<()> {
+        if self.faults.latency_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.faults.latency_ms)).await;
+        }
+
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.faults.rate_limit_every > 0 && call.is_multiple_of(self.faults.rate_limit_every) {
+            return Err(StreamError::RateLimited(self.faults.retry_after_secs));
+        }
+        if self.faults.fail_every > 0 && call.is_multiple_of(self.faults.fail_every) {
+            return Err(StreamError::Network(format!(
+                "Injected failure on call {}",
+                call
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Feed of generated text items, sized by `giant_feed_items`.
+    fn giant_feed(&self) -> Feed {
+        let count = self.faults.giant_feed_items as u32;
+        Feed {
+            id: FeedId(GIANT_FEED_ID.to_string()),
+            name: "Giant Feed".to_string(),
+            description: Some(format!("{} generated items", count)),
+            icon: Some("🐘".to_string()),
+            unread_count: Some(count),
+            total_count: Some(count),
+        }
+    }
+
+    fn giant_items(&self) -> Vec<Item> {
+        let stream_id = StreamId::new("dummy", "feed", GIANT_FEED_ID);
+        let now = Utc::now();
+
+        (0..self.faults.giant_feed_items)
+            .map(|n| Item {
+                id: ItemId::new("dummy", &format!("giant-{}", n)),
+                stream_id: stream_id.clone(),
+                title: format!("Generated item {}", n + 1),
+                content: ItemContent::Text(format!("Body of generated item {}", n + 1)),
+                author: None,
+                published: Some(now - chrono::Duration::minutes(n as i64)),
+                updated: None,
+                url: None,
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: false,
+                tags: vec![],
+                metadata: Default::default(),
+            })
+            .collect()
     }
 
     /// Generate static dummy feeds.
@@ -515,6 +719,11 @@ impl Provider for DummyProvider {
     }
 
     async fn sync(&self) -> Result<SyncResult> {
+        self.inject_faults().await?;
+        if let Some(result) = self.play_step().await? {
+            return Ok(result);
+        }
+
         // Simulate a successful sync
         Ok(SyncResult {
             success: true,
@@ -523,6 +732,7 @@ impl Provider for DummyProvider {
             items_removed: 0,
             errors: vec![],
             duration_ms: 10,
+            streams: Vec::new(),
         })
     }
 
@@ -532,6 +742,11 @@ impl Provider for DummyProvider {
             has_collections: true,
             has_saved_items: false,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
@@ -572,16 +787,40 @@ impl Provider for DummyProvider {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        Some(self)
+    }
 }
 
 #[async_trait]
 impl HasFeeds for DummyProvider {
     async fn list_feeds(&self) -> Result<Vec<Feed>> {
-        Ok(Self::dummy_feeds())
+        self.inject_faults().await?;
+
+        let mut feeds = self.feeds_with_scenario();
+        if self.faults.giant_feed_items > 0 {
+            feeds.push(self.giant_feed());
+        }
+        Ok(feeds)
     }
 
     async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
-        let mut items = Self::dummy_items(feed_id);
+        self.inject_faults().await?;
+
+        let mut items = if feed_id.0 == GIANT_FEED_ID && self.faults.giant_feed_items > 0 {
+            self.giant_items()
+        } else {
+            Self::dummy_items(feed_id)
+        };
+        if let Some(playback) = self.playback.as_ref() {
+            let published = playback.lock().unwrap().items(&feed_id.0);
+            items.splice(0..0, published);
+        }
 
         // Apply filtering based on options
         if !options.include_read {
@@ -598,12 +837,17 @@ impl HasFeeds for DummyProvider {
         let limit = options.limit.map(|l| l as usize);
 
         let items = items.into_iter().skip(offset);
-        let items = if let Some(limit) = limit {
+        let items: Vec<Item> = if let Some(limit) = limit {
             items.take(limit).collect()
         } else {
             items.collect()
         };
 
+        if self.faults.item_delay_ms > 0 {
+            let delay = self.faults.item_delay_ms * items.len() as u64;
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+
         Ok(items)
     }
 }
@@ -611,12 +855,16 @@ impl HasFeeds for DummyProvider {
 #[async_trait]
 impl HasCollections for DummyProvider {
     async fn list_collections(&self) -> Result<Vec<Collection>> {
+        self.inject_faults().await?;
+
         let state = self.state.lock().unwrap();
         let collections: Vec<Collection> = state.collections.values().cloned().collect();
         Ok(collections)
     }
 
     async fn get_collection_items(&self, collection_id: &CollectionId) -> Result<Vec<Item>> {
+        self.inject_faults().await?;
+
         let state = self.state.lock().unwrap();
 
         let item_ids = state
@@ -909,6 +1157,148 @@ mod tests {
         assert!(result.success);
     }
 
+    #[tokio::test]
+    async fn test_no_faults_by_default() {
+        let provider = DummyProvider::new();
+        assert!(!provider.faults().is_active());
+
+        for _ in 0..10 {
+            provider.sync().await.unwrap();
+        }
+        assert_eq!(provider.list_feeds().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_intermittent_failures() {
+        let provider = DummyProvider::with_faults(FaultConfig {
+            fail_every: 3,
+            rate_limit_every: 4,
+            retry_after_secs: 30,
+            ..Default::default()
+        });
+
+        let results: Vec<_> = sync_results(&provider, 8).await;
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(matches!(results[2], Err(StreamError::Network(_))));
+        assert!(matches!(results[3], Err(StreamError::RateLimited(30))));
+        assert!(results[4].is_ok());
+        assert!(matches!(results[5], Err(StreamError::Network(_))));
+        assert!(results[6].is_ok());
+        // Rate limits take precedence when both are due
+        assert!(matches!(results[7], Err(StreamError::RateLimited(30))));
+    }
+
+    async fn sync_results(provider: &DummyProvider, calls: usize) -> Vec<Result<SyncResult>> {
+        let mut results = Vec::new();
+        for _ in 0..calls {
+            results.push(provider.sync().await);
+        }
+        results
+    }
+
+    #[tokio::test]
+    async fn test_giant_feed() {
+        let provider = DummyProvider::with_faults(FaultConfig {
+            giant_feed_items: 10_000,
+            ..Default::default()
+        });
+
+        let feeds = provider.list_feeds().await.unwrap();
+        let giant = feeds.iter().find(|f| f.id.0 == GIANT_FEED_ID).unwrap();
+        assert_eq!(giant.total_count, Some(10_000));
+
+        let items = provider
+            .get_feed_items(&giant.id, FeedOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 10_000);
+
+        let page = provider
+            .get_feed_items(
+                &giant.id,
+                FeedOptions {
+                    offset: Some(9_990),
+                    limit: Some(50),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 10);
+        assert_eq!(page[0].title, "Generated item 9991");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_latency_and_slow_streams() {
+        let provider = DummyProvider::with_faults(FaultConfig {
+            latency_ms: 200,
+            item_delay_ms: 100,
+            ..Default::default()
+        });
+        let feed_id = FeedId("dummy:subscriptions".to_string());
+
+        let start = tokio::time::Instant::now();
+        let items = provider
+            .get_feed_items(&feed_id, FeedOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 5);
+        assert_eq!(start.elapsed(), Duration::from_millis(200 + 5 * 100));
+    }
+
+    #[test]
+    fn test_fault_config_from_settings() {
+        let config: FaultConfig = serde_json::from_str(
+            r#"{"latency_ms": 500, "fail_every": 3, "giant_feed_items": 10000}"#,
+        )
+        .unwrap();
+        assert_eq!(config.latency_ms, 500);
+        assert_eq!(config.fail_every, 3);
+        assert_eq!(config.giant_feed_items, 10_000);
+        assert_eq!(config.item_delay_ms, 0);
+        assert!(config.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_scenario_playback() {
+        let scenario = Scenario::from_toml(include_str!("../scenarios/demo.toml")).unwrap();
+        let provider = DummyProvider::new().with_scenario(scenario);
+        let subscriptions = FeedId("dummy:subscriptions".to_string());
+
+        let first = provider.sync().await.unwrap();
+        assert!(first.success);
+        assert_eq!(first.items_added, 0);
+
+        let second = provider.sync().await.unwrap();
+        assert_eq!(second.items_added, 2);
+        let items = provider
+            .get_feed_items(&subscriptions, FeedOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 7);
+        assert_eq!(items[0].title, "Writing a Ratatui Widget");
+
+        let failed = provider.sync().await.unwrap();
+        assert!(!failed.success);
+        assert_eq!(failed.errors, vec!["Connection reset by peer".to_string()]);
+
+        assert!(matches!(
+            provider.sync().await,
+            Err(StreamError::RateLimited(30))
+        ));
+
+        provider.sync().await.unwrap();
+        let feeds = provider.list_feeds().await.unwrap();
+        assert_eq!(feeds.len(), 4);
+        assert_eq!(feeds[0].total_count, Some(12));
+        assert_eq!(feeds[3].id.0, "dummy:newsletters");
+        assert_eq!(feeds[3].name, "newsletters");
+
+        // Once the scenario ends, syncs succeed quietly
+        assert!(provider.sync().await.unwrap().success);
+    }
+
     #[tokio::test]
     async fn test_list_collections() {
         let provider = DummyProvider::new();
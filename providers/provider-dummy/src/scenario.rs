@@ -0,0 +1,273 @@
+//! Scripted scenario playback.
+//!
+//! A [`Scenario`] is a timeline for the dummy provider to replay, written in
+//! TOML or JSON. Each sync plays the next [`ScenarioStep`]: it can succeed
+//! and publish new items, fail, or be rate limited. Feeds then include every
+//! item published so far, so demos and end-to-end tests see the same
+//! realistic sequence of events on every run.
+//!
+//! ```toml
+//! name = "new-videos"
+//!
+//! [[steps]]
+//! [[steps.items]]
+//! feed = "dummy:subscriptions"
+//! id = "new-1"
+//! title = "Fresh upload"
+//! body = "Just posted."
+//!
+//! [[steps]]
+//! error = "Connection reset by peer"
+//!
+//! [[steps]]
+//! rate_limited = 30
+//! ```
+
+use std::path::Path;
+
+use chrono::Utc;
+use scryforge_provider_core::prelude::*;
+use serde::Deserialize;
+
+/// A timeline of syncs for the dummy provider to replay.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Scenario {
+    /// Name shown in logs
+    #[serde(default)]
+    pub name: String,
+    /// Steps played in order, one per sync
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// What happens on one sync.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ScenarioStep {
+    /// Delay before the sync returns, in milliseconds
+    pub delay_ms: u64,
+    /// Report the sync as failed with this error
+    pub error: Option<String>,
+    /// Reject the sync as rate limited, retrying after this many seconds
+    pub rate_limited: Option<u64>,
+    /// Items published by a successful sync
+    pub items: Vec<ScenarioItem>,
+}
+
+/// An item published by a scenario step.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioItem {
+    /// Feed the item appears in, such as `dummy:subscriptions`. Feeds the
+    /// dummy provider doesn't have are added when their first item appears.
+    pub feed: String,
+    /// Local item ID, unique within the scenario
+    pub id: String,
+    pub title: String,
+    /// Text body, used when `content` is not given
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Full item content, in the API's tagged form (e.g. `content.Article = {...}`)
+    #[serde(default)]
+    pub content: Option<ItemContent>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub is_read: bool,
+}
+
+impl Scenario {
+    /// Parse a scenario from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|e| StreamError::Provider(format!("Invalid scenario: {}", e)))
+    }
+
+    /// Parse a scenario from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| StreamError::Provider(format!("Invalid scenario: {}", e)))
+    }
+
+    /// Load a scenario file, as JSON if it has a `.json` extension and as
+    /// TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            StreamError::Provider(format!("Failed to read scenario {}: {}", path.display(), e))
+        })?;
+
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json(&contents)
+        } else {
+            Self::from_toml(&contents)
+        }
+    }
+}
+
+impl ScenarioItem {
+    /// The item as published now.
+    fn to_item(&self) -> Item {
+        Item {
+            id: ItemId::new("dummy", &self.id),
+            stream_id: StreamId::new("dummy", "feed", &self.feed),
+            title: self.title.clone(),
+            content: self
+                .content
+                .clone()
+                .unwrap_or_else(|| ItemContent::Text(self.body.clone().unwrap_or_default())),
+            author: self.author.as_ref().map(|name| Author {
+                name: name.clone(),
+                email: None,
+                url: None,
+                avatar_url: None,
+            }),
+            published: Some(Utc::now()),
+            updated: None,
+            url: self.url.clone(),
+            thumbnail_url: None,
+            is_read: self.is_read,
+            is_saved: false,
+            tags: self.tags.clone(),
+            metadata: Default::default(),
+        }
+    }
+}
+
+/// Progress through a scenario.
+#[derive(Debug)]
+pub(crate) struct Playback {
+    scenario: Scenario,
+    next_step: usize,
+    /// Published items with their feed IDs, oldest first
+    published: Vec<(String, Item)>,
+}
+
+impl Playback {
+    pub(crate) fn new(scenario: Scenario) -> Self {
+        Self {
+            scenario,
+            next_step: 0,
+            published: Vec::new(),
+        }
+    }
+
+    /// Advance to the next step, publishing its items if it succeeds.
+    ///
+    /// Returns `None` once every step has been played.
+    pub(crate) fn advance(&mut self) -> Option<ScenarioStep> {
+        let step = self.scenario.steps.get(self.next_step)?.clone();
+        self.next_step += 1;
+
+        if step.error.is_none() && step.rate_limited.is_none() {
+            self.published.extend(
+                step.items
+                    .iter()
+                    .map(|item| (item.feed.clone(), item.to_item())),
+            );
+        }
+        Some(step)
+    }
+
+    /// Published items in `feed_id`, newest first.
+    pub(crate) fn items(&self, feed_id: &str) -> Vec<Item> {
+        self.published
+            .iter()
+            .rev()
+            .filter(|(feed, _)| feed == feed_id)
+            .map(|(_, item)| item.clone())
+            .collect()
+    }
+
+    /// IDs of feeds with published items, in order of first appearance.
+    pub(crate) fn feed_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for (feed, _) in &self.published {
+            if !ids.contains(feed) {
+                ids.push(feed.clone());
+            }
+        }
+        ids
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.scenario.name
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEMO: &str = include_str!("../scenarios/demo.toml");
+
+    #[test]
+    fn test_parse_demo_scenario() {
+        let scenario = Scenario::from_toml(DEMO).unwrap();
+
+        assert_eq!(scenario.name, "morning-catch-up");
+        assert_eq!(scenario.steps.len(), 5);
+        assert_eq!(scenario.steps[1].delay_ms, 250);
+        assert!(matches!(
+            scenario.steps[1].items[0].content,
+            Some(ItemContent::Video {
+                duration_seconds: Some(912),
+                ..
+            })
+        ));
+        assert_eq!(
+            scenario.steps[2].error.as_deref(),
+            Some("Connection reset by peer")
+        );
+        assert_eq!(scenario.steps[3].rate_limited, Some(30));
+    }
+
+    #[test]
+    fn test_json_matches_toml() {
+        let json = r#"{"steps": [{"items": [{"feed": "dummy:inbox", "id": "a", "title": "A"}]}]}"#;
+        let toml = "[[steps]]\nitems = [{ feed = 'dummy:inbox', id = 'a', title = 'A' }]\n";
+
+        for scenario in [Scenario::from_json(json), Scenario::from_toml(toml)] {
+            let item = &scenario.unwrap().steps[0].items[0];
+            assert_eq!(item.feed, "dummy:inbox");
+            assert_eq!(item.title, "A");
+            assert!(item.content.is_none());
+        }
+        assert!(Scenario::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_playback_publishes_items_of_successful_steps() {
+        let mut playback = Playback::new(Scenario::from_toml(DEMO).unwrap());
+
+        playback.advance().unwrap();
+        assert!(playback.items("dummy:subscriptions").is_empty());
+
+        playback.advance().unwrap();
+        let titles: Vec<String> = playback
+            .items("dummy:subscriptions")
+            .into_iter()
+            .map(|item| item.title)
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Writing a Ratatui Widget",
+                "Rust 2024 Edition: What Changed"
+            ]
+        );
+
+        playback.advance().unwrap();
+        playback.advance().unwrap();
+        playback.advance().unwrap();
+        assert_eq!(
+            playback.feed_ids(),
+            vec!["dummy:subscriptions", "dummy:newsletters"]
+        );
+        assert!(playback.advance().is_none());
+    }
+}
@@ -0,0 +1,89 @@
+//! Benchmarks for converting raw messages into items.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use provider_email_imap::{ImapConfig, ImapProvider};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::FeedId;
+
+const PLAIN: &str = "From: Alice <alice@example.com>\r\n\
+To: user@example.com\r\n\
+Subject: Quarterly report\r\n\
+Date: Mon, 1 Jan 2024 12:00:00 +0000\r\n\
+Message-ID: <report@example.com>\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n";
+
+const MULTIPART: &str = "From: \"Newsletter\" <news@example.com>\r\n\
+Subject: This week's digest\r\n\
+Date: Tue, 2 Jan 2024 08:30:00 +0000\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/alternative; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+PLAIN_BODY\r\n\
+--b1\r\n\
+Content-Type: text/html; charset=utf-8\r\n\
+\r\n\
+<html><body>HTML_BODY</body></html>\r\n\
+--b1--\r\n";
+
+fn provider() -> ImapProvider {
+    let config = ImapConfig {
+        server: "imap.example.com".to_string(),
+        port: 993,
+        username: "user@example.com".to_string(),
+        account_name: "bench".to_string(),
+        use_tls: true,
+        lazy_bodies: false,
+        mailboxes: vec![],
+        smtp: None,
+    };
+    ImapProvider::new(config, Arc::new(MockTokenFetcher::new(HashMap::new())))
+}
+
+fn messages() -> Vec<(&'static str, Vec<u8>)> {
+    let paragraph = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(20);
+    let long_body = format!("{}\r\n", paragraph).repeat(50);
+
+    vec![
+        (
+            "plain_short",
+            format!("{}See you at ten.\r\n", PLAIN).into_bytes(),
+        ),
+        ("plain_long", format!("{}{}", PLAIN, long_body).into_bytes()),
+        (
+            "multipart",
+            MULTIPART
+                .replace("PLAIN_BODY", &long_body)
+                .replace("HTML_BODY", &format!("<p>{}</p>", paragraph).repeat(50))
+                .into_bytes(),
+        ),
+    ]
+}
+
+fn bench_parse_email(c: &mut Criterion) {
+    let provider = provider();
+    let feed_id = FeedId("imap:INBOX".to_string());
+    let mut group = c.benchmark_group("parse_email");
+
+    for (name, raw) in messages() {
+        group.throughput(Throughput::Bytes(raw.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                provider
+                    .parse_email(&feed_id, 1, &[], black_box(&raw))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_email);
+criterion_main!(benches);
@@ -0,0 +1,391 @@
+//! Calendar invitations carried by email.
+//!
+//! Meeting invitations arrive as `text/calendar` parts holding an iTIP
+//! (RFC 5546) `REQUEST`. [`Invite::parse`] reads the first event of such a
+//! part, and [`reply_message`] writes the email that accepts or declines it:
+//! a `REPLY` with the attendee's new participation status, sent back to the
+//! organizer.
+
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use mailparse::ParsedMail;
+use scryforge_provider_core::ical::{fold, unfold, Property};
+use scryforge_provider_core::{format_mailbox, CalendarInvite};
+
+/// A meeting request, or its cancellation, from a `text/calendar` part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Invite {
+    method: String,
+    uid: String,
+    summary: Option<Property>,
+    start: Option<Property>,
+    end: Option<Property>,
+    location: Option<Property>,
+    organizer: Option<Property>,
+    attendees: Vec<Property>,
+    /// `SEQUENCE` and `RECURRENCE-ID`, which a reply must repeat
+    revision: Vec<Property>,
+}
+
+impl Invite {
+    /// Parse the first event of an iCalendar object. Returns `None` unless
+    /// the object has a method and an event with a `UID`, so a plain `.ics`
+    /// export isn't taken for an invitation.
+    pub(crate) fn parse(ics: &str) -> Option<Self> {
+        let mut method = None;
+        let mut event: Option<Vec<Property>> = None;
+        let mut depth = 0;
+        for property in unfold(ics).iter().filter_map(|line| Property::parse(line)) {
+            match (property.name.as_str(), property.value.trim()) {
+                ("BEGIN", "VEVENT") if event.is_none() => {
+                    event = Some(Vec::new());
+                    depth = 1;
+                }
+                ("BEGIN", _) if depth > 0 => depth += 1,
+                ("END", _) if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                ("METHOD", value) if event.is_none() => {
+                    method = Some(value.to_ascii_uppercase());
+                }
+                // Alarms and other components nested in the event are skipped
+                _ if depth == 1 => event.as_mut()?.push(property),
+                _ => {}
+            }
+        }
+
+        let event = event?;
+        let find = |name: &str| event.iter().find(|p| p.name == name).cloned();
+        Some(Self {
+            method: method?,
+            uid: find("UID")?.value,
+            summary: find("SUMMARY"),
+            start: find("DTSTART"),
+            end: find("DTEND"),
+            location: find("LOCATION"),
+            organizer: find("ORGANIZER"),
+            attendees: event
+                .iter()
+                .filter(|p| p.name == "ATTENDEE")
+                .cloned()
+                .collect(),
+            revision: event
+                .iter()
+                .filter(|p| p.name == "SEQUENCE" || p.name == "RECURRENCE-ID")
+                .cloned()
+                .collect(),
+        })
+    }
+
+    pub(crate) fn is_request(&self) -> bool {
+        self.method == "REQUEST"
+    }
+
+    /// The organizer's address, where replies go.
+    pub(crate) fn organizer_address(&self) -> Option<&str> {
+        self.organizer.as_ref()?.address()
+    }
+
+    /// The first of `addresses` that is invited, compared case-insensitively.
+    pub(crate) fn attendee_among<'a>(&self, addresses: &[&'a str]) -> Option<&'a str> {
+        addresses.iter().copied().find(|address| {
+            self.attendees.iter().any(|attendee| {
+                attendee
+                    .address()
+                    .is_some_and(|invited| invited.eq_ignore_ascii_case(address))
+            })
+        })
+    }
+
+    fn summary(&self) -> String {
+        self.summary
+            .as_ref()
+            .and_then(Property::text)
+            .unwrap_or_else(|| "(No title)".to_string())
+    }
+
+    /// The invitation as shown in item metadata.
+    pub(crate) fn details(&self) -> CalendarInvite {
+        CalendarInvite {
+            method: self.method.clone(),
+            summary: self.summary(),
+            start: self.start.as_ref().map(display_time),
+            end: self.end.as_ref().map(display_time),
+            location: self.location.as_ref().and_then(Property::text),
+            organizer: self.organizer.as_ref().and_then(mailbox),
+            attendees: self.attendees.iter().filter_map(mailbox).collect(),
+        }
+    }
+
+    /// The iCalendar `REPLY` in which `attendee` accepts or declines.
+    pub(crate) fn reply(&self, attendee: &str, accept: bool, now: DateTime<Utc>) -> String {
+        let status = if accept { "ACCEPTED" } else { "DECLINED" };
+        let mut answer = self
+            .attendees
+            .iter()
+            .find(|p| {
+                p.address()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(attendee))
+            })
+            .cloned()
+            .unwrap_or_else(|| Property {
+                name: "ATTENDEE".to_string(),
+                params: Vec::new(),
+                value: format!("mailto:{}", attendee),
+            });
+        answer
+            .params
+            .retain(|(key, _)| key != "PARTSTAT" && key != "RSVP");
+        answer
+            .params
+            .insert(0, ("PARTSTAT".to_string(), status.to_string()));
+
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "PRODID:-//Scryforge//Scryforge//EN".to_string(),
+            "VERSION:2.0".to_string(),
+            "METHOD:REPLY".to_string(),
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", self.uid),
+            format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")),
+        ];
+        let copied = [&self.summary, &self.start, &self.end, &self.organizer];
+        lines.extend(self.revision.iter().map(Property::line));
+        lines.extend(copied.into_iter().flatten().map(Property::line));
+        lines.push(answer.line());
+        lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".to_string()]);
+
+        lines.iter().map(|line| fold(line)).collect()
+    }
+}
+
+/// The text of the first `text/calendar` part of `mail`, attached or inline.
+pub(crate) fn calendar_part(mail: &ParsedMail) -> Option<String> {
+    if mail.ctype.mimetype.eq_ignore_ascii_case("text/calendar") {
+        return mail.get_body().ok();
+    }
+    mail.subparts.iter().find_map(calendar_part)
+}
+
+/// Write the email that sends `invite`'s reply from `attendee` to its
+/// organizer. `in_reply_to` is the invitation's `Message-ID`, if it has one.
+pub(crate) fn reply_message(
+    invite: &Invite,
+    organizer: &str,
+    attendee: &str,
+    accept: bool,
+    in_reply_to: Option<&str>,
+    now: DateTime<Utc>,
+) -> String {
+    let verb = if accept { "Accepted" } else { "Declined" };
+    let summary = invite.summary();
+    let boundary = format!(
+        "scryforge-{}",
+        now.timestamp_nanos_opt().unwrap_or_default()
+    );
+    let domain = attendee.rsplit_once('@').map_or("localhost", |(_, d)| d);
+
+    let mut headers = vec![
+        format!("From: <{}>", attendee),
+        format!("To: <{}>", organizer),
+        format!(
+            "Subject: {}",
+            encode_header(&format!("{}: {}", verb, summary))
+        ),
+        format!("Date: {}", now.to_rfc2822()),
+        format!("Message-ID: <{}@{}>", boundary, domain),
+    ];
+    if let Some(message_id) = in_reply_to {
+        headers.push(format!("In-Reply-To: {}", message_id));
+        headers.push(format!("References: {}", message_id));
+    }
+    headers.extend([
+        "MIME-Version: 1.0".to_string(),
+        format!(
+            "Content-Type: multipart/alternative; boundary=\"{}\"",
+            boundary
+        ),
+    ]);
+
+    format!(
+        "{headers}\r\n\r\n\
+         --{boundary}\r\n\
+         Content-Type: text/plain; charset=utf-8\r\n\
+         Content-Transfer-Encoding: 8bit\r\n\r\n\
+         {attendee} has {verb_lower} the invitation: {summary}\r\n\r\n\
+         --{boundary}\r\n\
+         Content-Type: text/calendar; charset=utf-8; method=REPLY\r\n\
+         Content-Transfer-Encoding: 8bit\r\n\r\n\
+         {calendar}\r\n\
+         --{boundary}--\r\n",
+        headers = headers.join("\r\n"),
+        verb_lower = verb.to_lowercase(),
+        calendar = invite.reply(attendee, accept, now),
+    )
+}
+
+/// The `CN` and address of an `ORGANIZER` or `ATTENDEE` as a
+/// [`format_mailbox`] entry.
+fn mailbox(property: &Property) -> Option<String> {
+    property
+        .address()
+        .map(|address| format_mailbox(property.param("CN"), address))
+}
+
+/// A `DTSTART` or `DTEND` as a [`CalendarInvite`] time.
+fn display_time(property: &Property) -> String {
+    let value = property.value.trim();
+    if let Some(utc) = value.strip_suffix('Z') {
+        if let Ok(time) = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S") {
+            return format!("{} UTC", time.format("%Y-%m-%d %H:%M"));
+        }
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        let time = time.format("%Y-%m-%d %H:%M");
+        return match property.param("TZID") {
+            Some(zone) => format!("{} ({})", time, zone),
+            None => time.to_string(),
+        };
+    }
+    match NaiveDate::parse_from_str(value, "%Y%m%d") {
+        Ok(date) => date.format("%Y-%m-%d").to_string(),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Encode a header value with RFC 2047 when it isn't plain ASCII.
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    format!(
+        "=?utf-8?b?{}?=",
+        base64::engine::general_purpose::STANDARD.encode(value)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use mailparse::MailHeaderMap;
+    use scryforge_provider_core::ical::MAX_LINE;
+
+    const REQUEST: &str = "BEGIN:VCALENDAR\r\n\
+        PRODID:-//Example//Calendar//EN\r\n\
+        VERSION:2.0\r\n\
+        METHOD:REQUEST\r\n\
+        BEGIN:VTIMEZONE\r\n\
+        TZID:Europe/Berlin\r\n\
+        END:VTIMEZONE\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:meeting-42@example.com\r\n\
+        SEQUENCE:2\r\n\
+        DTSTAMP:20240601T090000Z\r\n\
+        DTSTART;TZID=Europe/Berlin:20240603T140000\r\n\
+        DTEND;TZID=Europe/Berlin:20240603T150000\r\n\
+        SUMMARY:Planning\\, Q3\r\n\
+        LOCATION:Room 4\r\n\
+        ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r\n\
+        ATTENDEE;CN=Sam;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:\r\n \
+         sam@example.com\r\n\
+        ATTENDEE;CN=Lee:mailto:lee@example.com\r\n\
+        BEGIN:VALARM\r\n\
+        SUMMARY:Reminder\r\n\
+        END:VALARM\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_invite() {
+        let invite = Invite::parse(REQUEST).unwrap();
+        assert!(invite.is_request());
+        assert_eq!(invite.organizer_address(), Some("jane@example.com"));
+        assert_eq!(
+            invite.details(),
+            CalendarInvite {
+                method: "REQUEST".to_string(),
+                summary: "Planning, Q3".to_string(),
+                start: Some("2024-06-03 14:00 (Europe/Berlin)".to_string()),
+                end: Some("2024-06-03 15:00 (Europe/Berlin)".to_string()),
+                location: Some("Room 4".to_string()),
+                organizer: Some("\"Doe, Jane\" <jane@example.com>".to_string()),
+                attendees: vec![
+                    "Sam <sam@example.com>".to_string(),
+                    "Lee <lee@example.com>".to_string()
+                ],
+            }
+        );
+        assert_eq!(
+            invite.attendee_among(&["me@example.com", "SAM@example.com"]),
+            Some("SAM@example.com")
+        );
+
+        // A published calendar has no method and can't be answered
+        assert!(Invite::parse(&REQUEST.replace("METHOD:REQUEST\r\n", "")).is_none());
+        assert!(Invite::parse("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n").is_none());
+    }
+
+    #[test]
+    fn test_display_time() {
+        let time = |line: &str| display_time(&Property::parse(line).unwrap());
+        assert_eq!(time("DTSTART:20240603T120000Z"), "2024-06-03 12:00 UTC");
+        assert_eq!(time("DTSTART:20240603T120000"), "2024-06-03 12:00");
+        assert_eq!(time("DTSTART;VALUE=DATE:20240603"), "2024-06-03");
+    }
+
+    #[test]
+    fn test_reply() {
+        let invite = Invite::parse(REQUEST).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 10, 0, 0).unwrap();
+        let reply = invite.reply("sam@example.com", true, now);
+
+        assert!(reply.lines().all(|line| line.len() <= MAX_LINE));
+        let lines = unfold(&reply);
+        assert!(lines.contains(&"METHOD:REPLY".to_string()));
+        assert!(lines.contains(&"UID:meeting-42@example.com".to_string()));
+        assert!(lines.contains(&"SEQUENCE:2".to_string()));
+        assert!(lines.contains(&"DTSTAMP:20240601T100000Z".to_string()));
+        assert!(lines.contains(&"ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com".to_string()));
+        assert!(lines.contains(
+            &"ATTENDEE;PARTSTAT=ACCEPTED;CN=Sam;ROLE=REQ-PARTICIPANT:mailto:sam@example.com"
+                .to_string()
+        ));
+        assert!(!reply.contains("lee@example.com"));
+        assert!(!reply.contains("VALARM"));
+
+        let declined = invite.reply("other@example.com", false, now);
+        assert!(unfold(&declined)
+            .contains(&"ATTENDEE;PARTSTAT=DECLINED:mailto:other@example.com".to_string()));
+    }
+
+    #[test]
+    fn test_reply_message() {
+        let invite = Invite::parse(REQUEST).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 10, 0, 0).unwrap();
+        let message = reply_message(
+            &invite,
+            "jane@example.com",
+            "sam@example.com",
+            false,
+            Some("<invite@example.com>"),
+            now,
+        );
+
+        let parsed = mailparse::parse_mail(message.as_bytes()).unwrap();
+        assert_eq!(
+            parsed.headers.get_first_value("Subject").unwrap(),
+            "Declined: Planning, Q3"
+        );
+        assert_eq!(
+            parsed.headers.get_first_value("In-Reply-To").unwrap(),
+            "<invite@example.com>"
+        );
+        let calendar = calendar_part(&parsed).unwrap();
+        assert_eq!(parsed.subparts[1].ctype.params["method"], "REPLY");
+        assert!(calendar.contains("PARTSTAT=DECLINED"));
+    }
+}
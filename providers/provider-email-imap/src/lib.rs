@@ -12,6 +12,13 @@
 //! expects the password to be stored with the provider ID "email-imap" and the
 //! account name as the alias.
 //!
+//! ## Multiple Accounts
+//!
+//! [`ImapProvider::with_accounts`] serves several accounts from one provider.
+//! Feed IDs then name the account, as in `imap:work:INBOX`, and the virtual
+//! [`ALL_INBOXES_FEED`] merges every account's INBOX. Each account can limit
+//! its feeds to the mailboxes in [`ImapConfig::mailboxes`].
+//!
 //! ## Configuration
 //!
 //! ```rust
@@ -26,6 +33,9 @@
 //!     username: "user@gmail.com".to_string(),
 //!     account_name: "personal".to_string(),
 //!     use_tls: true,
+//!     lazy_bodies: false,
+//!     mailboxes: vec![],
+//!     smtp: None,
 //! };
 //!
 //! let mut tokens = HashMap::new();
@@ -36,19 +46,90 @@
 //! let token_fetcher = Arc::new(MockTokenFetcher::new(tokens));
 //! let provider = ImapProvider::new(config, token_fetcher);
 //! ```
+//!
+//! ## Flags
+//!
+//! Messages are fetched with `BODY.PEEK[]`, so listing a folder does not mark
+//! anything read. `\Seen` and `\Flagged` map to `is_read` and `is_saved`, and
+//! the mark read/unread actions store `\Seen` back to the server.
+//!
+//! Starring stores `\Flagged`, and the virtual [`STARRED_FEED`] lists starred
+//! messages from every mailbox. Where the server marks a mailbox as holding
+//! every flagged message, such as Gmail's `[Gmail]/Starred`, the feed lists
+//! that mailbox alone so each message appears once. Gmail accounts also get
+//! the virtual [`IMPORTANT_FEED`], from the mailbox Gmail lists with the
+//! `\Important` attribute. Other keywords, such as `$Work`, can be added
+//! with [`ImapProvider::set_flag`] and appear in an item's `tags`. Messages
+//! move between mailboxes with `UID MOVE`, or with `COPY` and `EXPUNGE` on
+//! servers without the MOVE extension.
+//!
+//! ## Snooze
+//!
+//! [`ImapProvider::snooze`] hides a message until a chosen time by moving it
+//! to the [`SNOOZED_MAILBOX`], or by leaving it out of listings where the
+//! message can't be moved and found again. The schedule is kept locally (see
+//! [`ImapProvider::with_snooze_path`]); due messages wake on the next sync or
+//! listing, moved back and optionally marked unread.
+//!
+//! ## Large Mailboxes
+//!
+//! With `lazy_bodies` set, listings fetch only each message's `ENVELOPE` and
+//! `BODYSTRUCTURE`, so opening a 10k-message folder transfers headers rather
+//! than every body. Such items are marked with [`LAZY_BODY_KEY`] in their
+//! metadata; [`ImapProvider::load_body`], or the preview action, fetches the
+//! full message when it is opened. Listings and searches with
+//! `low_bandwidth` set in their options are fetched the same way, whatever
+//! the account's setting.
+//!
+//! ## Calendar Invitations
+//!
+//! Messages with a `text/calendar` meeting request carry the event as a
+//! [`CalendarInvite`] under [`INVITE_METADATA_KEY`]. On accounts with an
+//! [`SmtpConfig`], the `accept_invite` and `decline_invite` actions send the
+//! organizer an iCalendar `REPLY` ([`ImapProvider::respond_to_invite`]).
+//!
+//! ## Search
+//!
+//! [`HasSearch`] runs on the server with `UID SEARCH`, so finding an email
+//! doesn't require its body to be cached. [`search_criteria`] translates a
+//! query such as `from:alice subject:"lunch plans" since:2024-01-01 menu` into
+//! IMAP criteria; bare words match anywhere in the message (`TEXT`).
 
+use async_imap::imap_proto::types::{Address, BodyStructure, Envelope, NameAttribute};
+use async_imap::types::{Fetch, Flag};
 use async_imap::Session;
 use async_native_tls::{TlsConnector, TlsStream};
 use async_std::net::TcpStream;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures::io::{AsyncRead, AsyncWrite};
 use futures::stream::StreamExt;
-use mailparse::{parse_mail, MailHeaderMap};
+use mailparse::{
+    addrparse_header, parse_mail, DispositionType, MailAddr, MailHeaderMap, ParsedMail,
+};
 use scryforge_provider_core::auth::TokenFetcher;
 use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::{
+    format_mailbox, CalendarInvite, ATTACHMENTS_METADATA_KEY, CC_METADATA_KEY, INVITE_METADATA_KEY,
+    TO_METADATA_KEY,
+};
 use std::any::Any;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::io;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+mod invite;
+mod smtp;
+mod snooze;
+
+use invite::Invite;
+pub use smtp::{SmtpConfig, SmtpSecurity};
+use snooze::SnoozeStore;
+pub use snooze::{Snooze, SNOOZED_MAILBOX};
 
 // ============================================================================
 // Configuration
@@ -66,76 +147,730 @@ pub struct ImapConfig {
     /// Account name for credential lookup in sigilforge
     pub account_name: String,
     /// Whether to use TLS (recommended: true)
+    ///
+    /// Plain connections send the password in cleartext, so they are only
+    /// allowed to loopback servers such as Proton Mail Bridge or a test server.
     pub use_tls: bool,
+    /// List messages from their headers and structure alone, fetching bodies
+    /// only when an item is previewed
+    pub lazy_bodies: bool,
+    /// Mailboxes to list as feeds, such as `["INBOX", "Receipts"]`; empty
+    /// lists all of them
+    pub mailboxes: Vec<String>,
+    /// Server to send replies through, such as answers to meeting
+    /// invitations; without one the account is read-only
+    pub smtp: Option<SmtpConfig>,
+}
+
+impl ImapConfig {
+    /// Whether `mailbox` is listed as a feed.
+    fn lists_mailbox(&self, mailbox: &str) -> bool {
+        self.mailboxes.is_empty() || self.mailboxes.iter().any(|name| name == mailbox)
+    }
+
+    /// Whether the account is on Gmail, whose `\Important` label is listed
+    /// as the [`IMPORTANT_FEED`].
+    fn is_gmail(&self) -> bool {
+        let server = self.server.to_ascii_lowercase();
+        server == "imap.gmail.com" || server == "imap.googlemail.com"
+    }
 }
 
+/// Metadata key set on items whose body has not been fetched yet.
+pub const LAZY_BODY_KEY: &str = scryforge_provider_core::LAZY_BODY_METADATA_KEY;
+
+/// ID of the virtual feed of starred (`\Flagged`) messages from all mailboxes.
+pub const STARRED_FEED: &str = "starred";
+
+/// ID of the virtual feed merging the INBOX of every account, listed when the
+/// provider has more than one.
+pub const ALL_INBOXES_FEED: &str = "all_inboxes";
+
+/// ID of the virtual feed of messages Gmail marks important, listed when the
+/// provider has a Gmail account.
+pub const IMPORTANT_FEED: &str = "important";
+
+/// Names of junk mailboxes on servers that list none with the `\Junk`
+/// special-use attribute, compared case-insensitively.
+const JUNK_MAILBOX_NAMES: &[&str] = &["Junk", "Spam", "[Gmail]/Spam", "Junk E-mail", "Junk Email"];
+
+/// System flags that [`ImapProvider::set_flag`] accepts besides keywords.
+/// `\Recent` is set by the server alone.
+const SYSTEM_FLAGS: &[&str] = &["\\Seen", "\\Answered", "\\Flagged", "\\Deleted", "\\Draft"];
+
+// ============================================================================
+// Connection
+// ============================================================================
+
+/// A connection to the server, with or without TLS.
+#[derive(Debug)]
+enum ImapStream {
+    Tls(TlsStream<TcpStream>),
+    Plain(TcpStream),
+}
+
+impl AsyncRead for ImapStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            ImapStream::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+            ImapStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for ImapStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            ImapStream::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+            ImapStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            ImapStream::Tls(stream) => Pin::new(stream).poll_flush(cx),
+            ImapStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            ImapStream::Tls(stream) => Pin::new(stream).poll_close(cx),
+            ImapStream::Plain(stream) => Pin::new(stream).poll_close(cx),
+        }
+    }
+}
+
+type ImapSession = Session<ImapStream>;
+
+/// Accounts to search, each with the mailboxes to search.
+type SearchScope<'a> = Vec<(&'a ImapConfig, Mailboxes)>;
+
+/// Mailboxes of one account that a search covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mailboxes {
+    /// Every mailbox the account lists as a feed
+    Listed,
+    /// These mailboxes, whether listed or not
+    Named(Vec<String>),
+    /// The mailbox the server marks with a special-use attribute such as
+    /// `\Flagged`; if there is none, every listed mailbox when `fallback` is
+    /// set and no mailbox otherwise
+    SpecialUse {
+        attribute: &'static str,
+        fallback: bool,
+    },
+}
+
+/// Fetch query for a message's flags and full body. `BODY.PEEK[]` leaves
+/// `\Seen` untouched, where `RFC822` would set it.
+const FETCH_QUERY: &str = "(UID FLAGS BODY.PEEK[])";
+
+/// Fetch query for listing a message without its body.
+const HEADERS_QUERY: &str = "(UID FLAGS RFC822.SIZE ENVELOPE BODYSTRUCTURE)";
+
 // ============================================================================
 // Provider Implementation
 // ============================================================================
 
 /// IMAP email provider.
 pub struct ImapProvider {
-    config: ImapConfig,
+    accounts: Vec<ImapConfig>,
     token_fetcher: Arc<dyn TokenFetcher>,
+    /// Mailbox feeds with their counts, from the last sync or listing; `None`
+    /// once a change on the server may have made them stale
+    mailbox_feeds: RwLock<Option<Vec<Feed>>>,
+    snoozes: SnoozeStore,
 }
 
 impl ImapProvider {
     /// Create a new IMAP provider instance.
     pub fn new(config: ImapConfig, token_fetcher: Arc<dyn TokenFetcher>) -> Self {
+        Self::with_accounts(vec![config], token_fetcher)
+    }
+
+    /// Create a provider serving several accounts, told apart by
+    /// `account_name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `accounts` is empty.
+    pub fn with_accounts(accounts: Vec<ImapConfig>, token_fetcher: Arc<dyn TokenFetcher>) -> Self {
+        assert!(!accounts.is_empty(), "an IMAP provider needs an account");
         Self {
-            config,
+            accounts,
             token_fetcher,
+            mailbox_feeds: RwLock::new(None),
+            snoozes: SnoozeStore::default(),
+        }
+    }
+
+    /// Keep the snooze schedule in a JSON file at `path`, loading any snoozes
+    /// already saved there. Without one, snoozes last until the provider is
+    /// dropped.
+    pub fn with_snooze_path(mut self, path: PathBuf) -> Result<Self> {
+        self.snoozes = SnoozeStore::open(path)?;
+        Ok(self)
+    }
+
+    /// Feed ID of `mailbox`: `imap:INBOX` with one account, and
+    /// `imap:<account_name>:INBOX` with several.
+    fn feed_id(&self, account: &ImapConfig, mailbox: &str) -> FeedId {
+        if self.accounts.len() > 1 {
+            FeedId(format!("imap:{}:{}", account.account_name, mailbox))
+        } else {
+            FeedId(format!("imap:{}", mailbox))
         }
     }
 
-    /// Connect to the IMAP server and authenticate.
-    async fn connect(&self) -> Result<Session<TlsStream<TcpStream>>> {
-        // Fetch password from sigilforge
-        let password = self
-            .token_fetcher
-            .fetch_token("email-imap", &self.config.account_name)
+    /// Account and mailbox behind a feed ID from [`Self::feed_id`].
+    fn resolve_feed<'a>(&'a self, feed_id: &'a FeedId) -> Result<(&'a ImapConfig, &'a str)> {
+        let invalid = || StreamError::StreamNotFound(format!("Invalid feed ID: {}", feed_id.0));
+        let rest = feed_id.0.strip_prefix("imap:").ok_or_else(invalid)?;
+        if self.accounts.len() == 1 {
+            return Ok((&self.accounts[0], rest));
+        }
+
+        let (name, mailbox) = rest.split_once(':').ok_or_else(invalid)?;
+        let account = self
+            .accounts
+            .iter()
+            .find(|account| account.account_name == name)
+            .ok_or_else(|| {
+                StreamError::StreamNotFound(format!("Unknown IMAP account: {}", name))
+            })?;
+        Ok((account, mailbox))
+    }
+
+    /// Account, UID, and mailbox of the message behind `item`.
+    fn locate<'a>(&'a self, item: &'a Item) -> Result<(&'a ImapConfig, &'a str, &'a str)> {
+        let (uid, mailbox) = message_location(item)?;
+        let account = match item.metadata.get("account") {
+            Some(name) => self.account(name)?,
+            None => &self.accounts[0],
+        };
+        Ok((account, uid, mailbox))
+    }
+
+    /// The account called `account_name`.
+    fn account(&self, account_name: &str) -> Result<&ImapConfig> {
+        self.accounts
+            .iter()
+            .find(|account| account.account_name == account_name)
+            .ok_or_else(|| {
+                StreamError::ItemNotFound(format!("Unknown IMAP account: {}", account_name))
+            })
+    }
+
+    /// Fetch the account's password from sigilforge.
+    async fn password(&self, account: &ImapConfig) -> Result<String> {
+        self.token_fetcher
+            .fetch_token("email-imap", &account.account_name)
             .await
-            .map_err(|e| StreamError::AuthRequired(format!("Failed to fetch password: {}", e)))?;
+            .map_err(|e| StreamError::AuthRequired(format!("Failed to fetch password: {}", e)))
+    }
+
+    /// Connect to the account's IMAP server and authenticate.
+    async fn connect(&self, account: &ImapConfig) -> Result<ImapSession> {
+        if !account.use_tls && !is_loopback(&account.server) {
+            return Err(StreamError::Provider(format!(
+                "Plain IMAP is only allowed to loopback servers; enable use_tls for {}",
+                account.server
+            )));
+        }
+        let password = self.password(account).await?;
 
         // Connect to server
-        let addr = format!("{}:{}", self.config.server, self.config.port);
+        let addr = format!("{}:{}", account.server, account.port);
         let tcp_stream = TcpStream::connect(&addr)
             .await
             .map_err(|e| StreamError::Network(format!("Failed to connect to {}: {}", addr, e)))?;
 
         // Wrap in TLS if configured
-        let tls_stream = if self.config.use_tls {
+        let stream = if account.use_tls {
             let connector = TlsConnector::new();
-            connector
-                .connect(&self.config.server, tcp_stream)
-                .await
-                .map_err(|e| StreamError::Network(format!("TLS connection failed: {}", e)))?
+            ImapStream::Tls(
+                connector
+                    .connect(&account.server, tcp_stream)
+                    .await
+                    .map_err(|e| StreamError::Network(format!("TLS connection failed: {}", e)))?,
+            )
         } else {
-            return Err(StreamError::Provider(
-                "Non-TLS connections are not currently supported".to_string(),
-            ));
+            ImapStream::Plain(tcp_stream)
         };
 
-        // Create IMAP client
-        let client = async_imap::Client::new(tls_stream);
+        // Create IMAP client and read the server greeting
+        let mut client = async_imap::Client::new(stream);
+        client
+            .read_response()
+            .await
+            .ok_or_else(|| StreamError::Network("Server closed connection".to_string()))?
+            .map_err(|e| StreamError::Network(format!("Failed to read greeting: {}", e)))?;
 
         // Login
         let session = client
-            .login(&self.config.username, &password)
+            .login(&account.username, &password)
             .await
             .map_err(|e| {
                 StreamError::AuthRequired(format!(
                     "IMAP login failed for {}: {}",
-                    self.config.username, e.0
+                    account.username, e.0
                 ))
             })?;
 
         Ok(session)
     }
 
+    /// Connect and select the mailbox behind `feed_id`.
+    async fn open_mailbox(&self, feed_id: &FeedId) -> Result<ImapSession> {
+        let (account, mailbox_name) = self.resolve_feed(feed_id)?;
+        let mut session = self.connect(account).await?;
+
+        session
+            .select(mailbox_name)
+            .await
+            .map_err(|e| StreamError::StreamNotFound(format!("Mailbox not found: {}", e)))?;
+
+        Ok(session)
+    }
+
+    /// Add or remove a flag on the message behind `item`.
+    ///
+    /// `flag` is a system flag such as `\Flagged`, or a keyword such as
+    /// `$Work`.
+    pub async fn set_flag(&self, item: &Item, flag: &str, set: bool) -> Result<()> {
+        validate_flag(flag)?;
+        let (account, uid, mailbox) = self.locate(item)?;
+
+        let mut session = self.open_mailbox(&self.feed_id(account, mailbox)).await?;
+        let query = format!("{}FLAGS ({})", if set { "+" } else { "-" }, flag);
+        store_flags(&mut session, uid, &query).await?;
+        self.invalidate_counts();
+
+        session
+            .logout()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))
+    }
+
+    /// Move the message behind `item` to the mailbox `folder` of the same
+    /// account.
+    pub async fn move_to_folder(&self, item: &Item, folder: &str) -> Result<()> {
+        let (account, uid, mailbox) = self.locate(item)?;
+        if folder == mailbox {
+            return Ok(());
+        }
+
+        let mut session = self.open_mailbox(&self.feed_id(account, mailbox)).await?;
+        move_message(&mut session, uid, folder).await?;
+        self.invalidate_counts();
+
+        session
+            .logout()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))
+    }
+
+    /// Move the message behind `item` to its account's junk mailbox,
+    /// returning that mailbox's name.
+    ///
+    /// The message also gets the `$Junk` keyword of RFC 5788, which server
+    /// side filters learn from, where the server accepts keywords.
+    pub async fn mark_spam(&self, item: &Item) -> Result<String> {
+        let (account, uid, mailbox) = self.locate(item)?;
+
+        let mut session = self.open_mailbox(&self.feed_id(account, mailbox)).await?;
+        let list = Self::mailbox_list(&mut session).await?;
+        let junk = junk_mailbox(&list)
+            .ok_or_else(|| {
+                StreamError::Provider(format!(
+                    "Account '{}' has no junk mailbox",
+                    account.account_name
+                ))
+            })?
+            .to_string();
+        set_junk_keywords(&mut session, uid, true).await;
+        if junk != mailbox {
+            move_message(&mut session, uid, &junk).await?;
+        }
+        self.invalidate_counts();
+
+        let _ = session.logout().await;
+        Ok(junk)
+    }
+
+    /// Move the message behind `item` from the junk mailbox back to INBOX,
+    /// with the `$NotJunk` keyword in place of `$Junk`.
+    pub async fn mark_not_spam(&self, item: &Item) -> Result<()> {
+        let (account, uid, mailbox) = self.locate(item)?;
+
+        let mut session = self.open_mailbox(&self.feed_id(account, mailbox)).await?;
+        set_junk_keywords(&mut session, uid, false).await;
+        if !mailbox.eq_ignore_ascii_case("INBOX") {
+            move_message(&mut session, uid, "INBOX").await?;
+        }
+        self.invalidate_counts();
+
+        let _ = session.logout().await;
+        Ok(())
+    }
+
+    /// Accept or decline the meeting invitation in the message behind `item`,
+    /// returning the organizer the reply went to.
+    ///
+    /// The reply is sent through the account's [`SmtpConfig`] on behalf of
+    /// the first attendee the invitation names among the account's username
+    /// and the message's recipients. The message is then marked `\Answered`.
+    pub async fn respond_to_invite(&self, item: &Item, accept: bool) -> Result<String> {
+        let (account, _, _) = self.locate(item)?;
+        let smtp = account.smtp.as_ref().ok_or_else(|| {
+            StreamError::NotSupported(format!(
+                "Account '{}' has no SMTP server to reply with",
+                account.account_name
+            ))
+        })?;
+
+        let source = self.fetch_source(item).await?;
+        let parsed = parse_mail(&source)
+            .map_err(|e| StreamError::Provider(format!("Failed to parse email: {}", e)))?;
+        let found = invite::calendar_part(&parsed)
+            .and_then(|ics| Invite::parse(&ics))
+            .filter(Invite::is_request)
+            .ok_or_else(|| {
+                StreamError::NotSupported(format!("{} has no invitation to answer", item.id.0))
+            })?;
+        let organizer = found
+            .organizer_address()
+            .ok_or_else(|| StreamError::Provider("Invitation has no organizer".to_string()))?
+            .to_string();
+
+        let recipients = recipient_addresses(&parsed);
+        let mut candidates = vec![account.username.as_str()];
+        candidates.extend(recipients.iter().map(String::as_str));
+        let attendee = found
+            .attendee_among(&candidates)
+            .unwrap_or(&account.username)
+            .to_string();
+
+        let message = invite::reply_message(
+            &found,
+            &organizer,
+            &attendee,
+            accept,
+            parsed.headers.get_first_value("Message-ID").as_deref(),
+            Utc::now(),
+        );
+        let password = self.password(account).await?;
+        smtp::send(
+            smtp,
+            &account.username,
+            &password,
+            &attendee,
+            std::slice::from_ref(&organizer),
+            &message,
+        )
+        .await?;
+
+        if let Err(e) = self.set_flag(item, "\\Answered", true).await {
+            eprintln!("Failed to mark {} answered: {}", item.id.0, e);
+        }
+        Ok(organizer)
+    }
+
+    /// Hide the message behind `item` until `until`, marking it unread when
+    /// it reappears if `mark_unread` is set.
+    ///
+    /// The message moves to [`SNOOZED_MAILBOX`], which is created if needed.
+    /// If it can't be moved, or has no `Message-ID` to find it by afterwards,
+    /// it stays in its mailbox and is left out of listings instead.
+    pub async fn snooze(&self, item: &Item, until: DateTime<Utc>, mark_unread: bool) -> Result<()> {
+        if until <= Utc::now() {
+            return Err(StreamError::Provider(
+                "Snooze time must be in the future".to_string(),
+            ));
+        }
+        let (account, uid, mailbox) = self.locate(item)?;
+        let message_id = item
+            .id
+            .0
+            .strip_prefix("email-imap:")
+            .filter(|id| !id.starts_with("uid-"))
+            .map(str::to_string);
+
+        let moved = match &message_id {
+            Some(_) if mailbox != SNOOZED_MAILBOX => {
+                match self.move_to_snoozed(account, uid, mailbox).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        eprintln!("Snoozing {} in place: {}", item.id.0, e);
+                        false
+                    }
+                }
+            }
+            _ => false,
+        };
+
+        self.snoozes.add(Snooze {
+            item_id: item.id.clone(),
+            account: account.account_name.clone(),
+            mailbox: mailbox.to_string(),
+            uid: uid.to_string(),
+            message_id,
+            until,
+            mark_unread,
+            moved,
+        })?;
+        self.invalidate_counts();
+        Ok(())
+    }
+
+    /// Scheduled snoozes, soonest first.
+    pub fn snoozed(&self) -> Vec<Snooze> {
+        self.snoozes.list()
+    }
+
+    /// Bring back every message whose snooze is due at `now`, returning how
+    /// many woke. A message that fails to wake is retried next time.
+    pub async fn wake_snoozed(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut woken = 0;
+        for snooze in self.snoozes.due(now) {
+            match self.wake(&snooze).await {
+                Ok(()) => {
+                    self.snoozes.remove(&snooze)?;
+                    woken += 1;
+                }
+                Err(e) => eprintln!("Failed to wake {}: {}", snooze.item_id.0, e),
+            }
+        }
+        if woken > 0 {
+            self.invalidate_counts();
+        }
+        Ok(woken)
+    }
+
+    /// Move a message to [`SNOOZED_MAILBOX`], creating it if needed.
+    async fn move_to_snoozed(&self, account: &ImapConfig, uid: &str, mailbox: &str) -> Result<()> {
+        let mut session = self.open_mailbox(&self.feed_id(account, mailbox)).await?;
+        if !Self::mailbox_names(&mut session)
+            .await?
+            .iter()
+            .any(|name| name == SNOOZED_MAILBOX)
+        {
+            session.create(SNOOZED_MAILBOX).await.map_err(|e| {
+                StreamError::Provider(format!("Failed to create {}: {}", SNOOZED_MAILBOX, e))
+            })?;
+        }
+        move_message(&mut session, uid, SNOOZED_MAILBOX).await?;
+        let _ = session.logout().await;
+        Ok(())
+    }
+
+    /// Return a snoozed message to its mailbox, or just mark it unread if it
+    /// never left.
+    async fn wake(&self, snooze: &Snooze) -> Result<()> {
+        let account = self.account(&snooze.account)?;
+        let unseen = "-FLAGS.SILENT (\\Seen)";
+
+        if let (true, Some(message_id)) = (snooze.moved, &snooze.message_id) {
+            let mut session = self
+                .open_mailbox(&self.feed_id(account, SNOOZED_MAILBOX))
+                .await?;
+            let uids = session
+                .uid_search(format!("HEADER Message-ID {}", imap_quote(message_id)))
+                .await
+                .map_err(|e| StreamError::Provider(format!("Search failed: {}", e)))?;
+            // A message deleted or moved away meanwhile has nothing to wake
+            for uid in uids {
+                let uid = uid.to_string();
+                if snooze.mark_unread {
+                    store_flags(&mut session, &uid, unseen).await?;
+                }
+                move_message(&mut session, &uid, &snooze.mailbox).await?;
+            }
+            let _ = session.logout().await;
+        } else if snooze.mark_unread {
+            let mut session = self
+                .open_mailbox(&self.feed_id(account, &snooze.mailbox))
+                .await?;
+            store_flags(&mut session, &snooze.uid, unseen).await?;
+            let _ = session.logout().await;
+        }
+        Ok(())
+    }
+
+    /// List every account's mailboxes with their message counts, and cache
+    /// the result for `list_feeds`. Mailboxes whose counts can't be read are
+    /// returned as failed streams instead.
+    ///
+    /// With several accounts, one that can't be reached is left out rather
+    /// than failing the listing, unless none can.
+    async fn refresh_mailbox_feeds(&self) -> Result<(Vec<Feed>, Vec<StreamSyncResult>)> {
+        let mut feeds = Vec::new();
+        let mut failed = Vec::new();
+        let mut last_error = None;
+        for account in &self.accounts {
+            match self.account_feeds(account, &mut failed).await {
+                Ok(account_feeds) => feeds.extend(account_feeds),
+                Err(e) if self.accounts.len() > 1 => {
+                    eprintln!("Failed to list account '{}': {}", account.account_name, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if let (true, Some(e)) = (feeds.is_empty(), last_error) {
+            return Err(e);
+        }
+
+        *self.mailbox_feeds.write().unwrap() = Some(feeds.clone());
+        Ok((feeds, failed))
+    }
+
+    /// Feeds for the listed mailboxes of one account, adding those whose
+    /// counts can't be read to `failed`.
+    async fn account_feeds(
+        &self,
+        account: &ImapConfig,
+        failed: &mut Vec<StreamSyncResult>,
+    ) -> Result<Vec<Feed>> {
+        let mut session = self.connect(account).await?;
+        let mut mailbox_names = Self::mailbox_names(&mut session).await?;
+        mailbox_names.retain(|name| account.lists_mailbox(name));
+
+        let mut feeds = Vec::new();
+
+        // Count each mailbox's messages. STATUS reports the number of unseen messages, where SELECT only
+        // reports the first unseen one.
+        for name in mailbox_names {
+            match session.status(&name, "(MESSAGES UNSEEN)").await {
+                Ok(mailbox_status) => {
+                    let exists = mailbox_status.exists;
+                    let unseen = mailbox_status.unseen.unwrap_or(0);
+                    feeds.push(self.mailbox_to_feed(account, name, exists, unseen));
+                }
+                Err(e) => {
+                    // Report the mailbox but continue with the others
+                    let feed_id = self.feed_id(account, &name);
+                    failed.push(StreamSyncResult::failed(
+                        StreamId::new(self.id(), "feed", &feed_id.0),
+                        format!("Failed to get status of mailbox '{}': {}", name, e),
+                    ));
+                }
+            }
+        }
+
+        // Logout
+        session
+            .logout()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))?;
+
+        Ok(feeds)
+    }
+
+    /// Drop cached counts after a change they don't reflect, such as a flag
+    /// stored from here.
+    fn invalidate_counts(&self) {
+        *self.mailbox_feeds.write().unwrap() = None;
+    }
+
+    /// Mailboxes a message in `mailbox` of `account` can be moved to.
+    async fn move_targets(&self, account: &ImapConfig, mailbox: &str) -> Result<Vec<String>> {
+        let mut session = self.connect(account).await?;
+        let mut names = Self::mailbox_names(&mut session).await?;
+        let _ = session.logout().await;
+
+        names.retain(|name| name != mailbox);
+        Ok(names)
+    }
+
+    /// Fetch the full message behind a lazily listed item.
+    ///
+    /// Items that already have their body are returned unchanged.
+    pub async fn load_body(&self, item: &Item) -> Result<Item> {
+        if !item.metadata.contains_key(LAZY_BODY_KEY) {
+            return Ok(item.clone());
+        }
+        let (account, uid, mailbox) = self.locate(item)?;
+        let feed_id = self.feed_id(account, mailbox);
+        let mut session = self.open_mailbox(&feed_id).await?;
+
+        let mut loaded = None;
+        {
+            let mut fetch_stream = session
+                .uid_fetch(uid, FETCH_QUERY)
+                .await
+                .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
+            while let Some(fetch_result) = fetch_stream.next().await {
+                let msg = fetch_result
+                    .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
+                if let (Some(uid), Some(body)) = (msg.uid, msg.body()) {
+                    let flags: Vec<Flag<'_>> = msg.flags().collect();
+                    loaded = Some(self.parse_email(&feed_id, uid, &flags, body)?);
+                }
+            }
+        }
+        let _ = session.logout().await;
+
+        let mut loaded = loaded.ok_or_else(|| {
+            StreamError::ItemNotFound(format!("Message {} is no longer in {}", uid, mailbox))
+        })?;
+        loaded.id = item.id.clone();
+        for (key, value) in &item.metadata {
+            if key != LAZY_BODY_KEY {
+                loaded
+                    .metadata
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Fetch the raw RFC 822 source of the message behind `item`.
+    async fn fetch_source(&self, item: &Item) -> Result<Vec<u8>> {
+        let (account, uid, mailbox) = self.locate(item)?;
+        let feed_id = self.feed_id(account, mailbox);
+        let mut session = self.open_mailbox(&feed_id).await?;
+
+        let mut source = None;
+        {
+            let mut fetch_stream = session
+                .uid_fetch(uid, FETCH_QUERY)
+                .await
+                .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
+            while let Some(fetch_result) = fetch_stream.next().await {
+                let msg = fetch_result
+                    .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
+                if let Some(body) = msg.body() {
+                    source = Some(body.to_vec());
+                }
+            }
+        }
+        let _ = session.logout().await;
+
+        source.ok_or_else(|| {
+            StreamError::ItemNotFound(format!("Message {} is no longer in {}", uid, mailbox))
+        })
+    }
+
     /// Convert IMAP mailbox name to a feed.
-    fn mailbox_to_feed(&self, name: String, exists: u32, unseen: u32) -> Feed {
-        let id = FeedId(format!("imap:{}", name));
+    fn mailbox_to_feed(
+        &self,
+        account: &ImapConfig,
+        name: String,
+        exists: u32,
+        unseen: u32,
+    ) -> Feed {
+        let id = self.feed_id(account, &name);
         let icon = match name.to_uppercase().as_str() {
             "INBOX" => Some("📥".to_string()),
             "SENT" => Some("📤".to_string()),
@@ -146,6 +881,13 @@ impl ImapProvider {
             _ => Some("📁".to_string()),
         };
 
+        // Several accounts usually each have an INBOX
+        let name = if self.accounts.len() > 1 {
+            format!("{} ({})", name, account.account_name)
+        } else {
+            name
+        };
+
         Feed {
             id,
             name,
@@ -156,8 +898,16 @@ impl ImapProvider {
         }
     }
 
-    /// Parse an email message into an Item.
-    fn parse_email(&self, feed_id: &FeedId, uid: u32, data: &[u8]) -> Result<Item> {
+    /// Parse a raw RFC 822 message into an Item.
+    ///
+    /// `flags` are the message's IMAP flags, such as `\Seen`.
+    pub fn parse_email(
+        &self,
+        feed_id: &FeedId,
+        uid: u32,
+        flags: &[Flag<'_>],
+        data: &[u8],
+    ) -> Result<Item> {
         let parsed = parse_mail(data)
             .map_err(|e| StreamError::Provider(format!("Failed to parse email: {}", e)))?;
 
@@ -195,14 +945,18 @@ impl ImapProvider {
                 }
             }
         } else {
-            // Multipart message - extract text and HTML alternatives
+            // Multipart message - extract text and HTML alternatives. A
+            // calendar part is an invitation, stored below rather than shown
             for part in &parsed.subparts {
                 let content_type = part.ctype.mimetype.to_lowercase();
 
                 if let Ok(body) = part.get_body() {
                     if content_type.contains("html") && body_html.is_none() {
                         body_html = Some(body);
-                    } else if content_type.contains("text") && body_text.is_none() {
+                    } else if content_type.contains("text")
+                        && content_type != "text/calendar"
+                        && body_text.is_none()
+                    {
                         body_text = Some(body);
                     }
                 }
@@ -250,6 +1004,28 @@ impl ImapProvider {
         let item_id = ItemId::new("email-imap", &item_local_id);
         let stream_id = StreamId::new("email-imap", "feed", &feed_id.0);
 
+        // Where the message lives, for actions that change it on the server
+        let mut metadata = HashMap::new();
+        metadata.insert("uid".to_string(), uid.to_string());
+        if let Ok((account, mailbox)) = self.resolve_feed(feed_id) {
+            metadata.insert("mailbox".to_string(), mailbox.to_string());
+            metadata.insert("account".to_string(), account.account_name.clone());
+        }
+        for (key, header) in [(TO_METADATA_KEY, "To"), (CC_METADATA_KEY, "Cc")] {
+            if let Some(recipients) = parsed
+                .headers
+                .get_first_header(header)
+                .and_then(header_recipients)
+            {
+                metadata.insert(key.to_string(), recipients);
+            }
+        }
+        if let Some(found) = invite::calendar_part(&parsed).and_then(|ics| Invite::parse(&ics)) {
+            if let Ok(json) = serde_json::to_string(&found.details()) {
+                metadata.insert(INVITE_METADATA_KEY.to_string(), json);
+            }
+        }
+
         Ok(Item {
             id: item_id,
             stream_id,
@@ -265,12 +1041,852 @@ impl ImapProvider {
             updated: None,
             url: None,
             thumbnail_url: None,
-            is_read: false, // TODO: Check IMAP flags for \Seen
-            is_saved: false,
-            tags: vec![],
-            metadata: HashMap::new(),
+            is_read: flags.contains(&Flag::Seen),
+            is_saved: flags.contains(&Flag::Flagged),
+            tags: keywords(flags),
+            metadata,
+        })
+    }
+
+    /// Convert a header-only fetch into an Item without a body.
+    fn envelope_to_item(
+        &self,
+        feed_id: &FeedId,
+        uid: u32,
+        flags: &[Flag<'_>],
+        envelope: &Envelope<'_>,
+        structure: Option<&BodyStructure<'_>>,
+        size: Option<u32>,
+    ) -> Item {
+        let subject = envelope
+            .subject
+            .as_deref()
+            .map(|subject| decode_header("Subject", subject))
+            .filter(|subject| !subject.is_empty())
+            .unwrap_or_else(|| "(No Subject)".to_string());
+
+        let published = envelope.date.as_deref().and_then(|date| {
+            mailparse::dateparse(&String::from_utf8_lossy(date))
+                .ok()
+                .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        });
+
+        let author = envelope
+            .from
+            .as_ref()
+            .and_then(|from| from.first())
+            .map(|address| {
+                let email = match (&address.mailbox, &address.host) {
+                    (Some(mailbox), Some(host)) => Some(format!(
+                        "{}@{}",
+                        String::from_utf8_lossy(mailbox),
+                        String::from_utf8_lossy(host)
+                    )),
+                    _ => None,
+                };
+                let name = address
+                    .name
+                    .as_deref()
+                    .map(|name| decode_header("From", name))
+                    .or_else(|| email.clone())
+                    .unwrap_or_default();
+
+                Author {
+                    name,
+                    email,
+                    url: None,
+                    avatar_url: None,
+                }
+            });
+
+        let item_local_id = envelope
+            .message_id
+            .as_deref()
+            .map(|id| String::from_utf8_lossy(id).trim().to_string())
+            .unwrap_or_else(|| format!("uid-{}", uid));
+
+        let mut metadata = HashMap::new();
+        metadata.insert("uid".to_string(), uid.to_string());
+        if let Ok((account, mailbox)) = self.resolve_feed(feed_id) {
+            metadata.insert("mailbox".to_string(), mailbox.to_string());
+            metadata.insert("account".to_string(), account.account_name.clone());
+        }
+        for (key, addresses) in [
+            (TO_METADATA_KEY, &envelope.to),
+            (CC_METADATA_KEY, &envelope.cc),
+        ] {
+            if let Some(recipients) = addresses.as_deref().and_then(envelope_recipients) {
+                metadata.insert(key.to_string(), recipients);
+            }
+        }
+        metadata.insert(LAZY_BODY_KEY.to_string(), "true".to_string());
+        if let Some(size) = size {
+            metadata.insert("size".to_string(), size.to_string());
+        }
+        let attachments = structure.map(count_attachments).unwrap_or(0);
+        if attachments > 0 {
+            metadata.insert(
+                ATTACHMENTS_METADATA_KEY.to_string(),
+                attachments.to_string(),
+            );
+        }
+
+        Item {
+            id: ItemId::new("email-imap", &item_local_id),
+            stream_id: StreamId::new("email-imap", "feed", &feed_id.0),
+            title: subject.clone(),
+            content: ItemContent::Email {
+                subject,
+                body_text: None,
+                body_html: None,
+                snippet: String::new(),
+            },
+            author,
+            published,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: flags.contains(&Flag::Seen),
+            is_saved: flags.contains(&Flag::Flagged),
+            tags: keywords(flags),
+            metadata,
+        }
+    }
+
+    /// Fetch messages of the selected mailbox in one round trip, most recent
+    /// first. Only headers are fetched if bodies load lazily or
+    /// `low_bandwidth` is set.
+    async fn fetch_messages(
+        &self,
+        session: &mut ImapSession,
+        feed_id: &FeedId,
+        uids: &[u32],
+        low_bandwidth: bool,
+    ) -> Result<Vec<Item>> {
+        let (account, _) = self.resolve_feed(feed_id)?;
+        let lazy = account.lazy_bodies || low_bandwidth;
+        let query = if lazy { HEADERS_QUERY } else { FETCH_QUERY };
+        let uid_set = uids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut fetched: Vec<(u32, Item)> = Vec::new();
+        if !uid_set.is_empty() {
+            let mut fetch_stream = session
+                .uid_fetch(&uid_set, query)
+                .await
+                .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
+
+            while let Some(fetch_result) = fetch_stream.next().await {
+                match fetch_result {
+                    Ok(msg) => match self.fetch_to_item(feed_id, lazy, &msg) {
+                        Some(Ok(item)) => fetched.push(item),
+                        Some(Err(e)) => {
+                            eprintln!("Failed to parse email UID {:?}: {}", msg.uid, e);
+                        }
+                        None => {}
+                    },
+                    Err(e) => {
+                        eprintln!("Failed to fetch messages: {}", e);
+                    }
+                }
+            }
+        }
+
+        // Most recent first, whatever order the server answered in
+        fetched.sort_by(|(a, _), (b, _)| b.cmp(a));
+        Ok(fetched.into_iter().map(|(_, item)| item).collect())
+    }
+
+    /// Names of all mailboxes on the server.
+    async fn mailbox_names(session: &mut ImapSession) -> Result<Vec<String>> {
+        Ok(Self::mailbox_list(session)
+            .await?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect())
+    }
+
+    /// All mailboxes on the server, each with its special-use attributes,
+    /// such as `\Flagged` or Gmail's `\Important`.
+    async fn mailbox_list(session: &mut ImapSession) -> Result<Vec<(String, Vec<String>)>> {
+        let mut mailbox_stream = session
+            .list(Some(""), Some("*"))
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to list mailboxes: {}", e)))?;
+
+        let mut mailboxes = Vec::new();
+        while let Some(mailbox_result) = mailbox_stream.next().await {
+            match mailbox_result {
+                Ok(mailbox) => {
+                    let attributes = mailbox
+                        .attributes()
+                        .iter()
+                        .filter_map(special_use)
+                        .map(str::to_string)
+                        .collect();
+                    mailboxes.push((mailbox.name().to_string(), attributes));
+                }
+                Err(e) => {
+                    eprintln!("Failed to list mailbox: {}", e);
+                }
+            }
+        }
+
+        Ok(mailboxes)
+    }
+
+    /// Accounts and mailboxes behind a virtual feed, or `None` if `feed` is
+    /// a plain mailbox.
+    ///
+    /// Returns the criterion selecting the feed's messages with the mailboxes
+    /// to search in each account, `None` meaning all of them.
+    fn virtual_feed(&self, feed: &str) -> Option<(&'static str, SearchScope<'_>)> {
+        match feed {
+            STARRED_FEED => Some((
+                "FLAGGED",
+                self.accounts
+                    .iter()
+                    .map(|account| {
+                        let mailboxes = Mailboxes::SpecialUse {
+                            attribute: "\\Flagged",
+                            fallback: true,
+                        };
+                        (account, mailboxes)
+                    })
+                    .collect(),
+            )),
+            ALL_INBOXES_FEED => Some((
+                "ALL",
+                self.accounts
+                    .iter()
+                    .map(|account| (account, Mailboxes::Named(vec!["INBOX".to_string()])))
+                    .collect(),
+            )),
+            // Gmail files important mail in a mailbox of its own, named in
+            // the account's language
+            IMPORTANT_FEED => Some((
+                "ALL",
+                self.accounts
+                    .iter()
+                    .filter(|account| account.is_gmail())
+                    .map(|account| {
+                        let mailboxes = Mailboxes::SpecialUse {
+                            attribute: "\\Important",
+                            fallback: false,
+                        };
+                        (account, mailboxes)
+                    })
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Messages of a virtual feed from [`Self::virtual_feed`], most recent
+    /// first.
+    async fn virtual_feed_items(
+        &self,
+        feed: &str,
+        criterion: &str,
+        scope: SearchScope<'_>,
+        options: FeedOptions,
+    ) -> Result<Vec<Item>> {
+        let criteria = if options.include_read {
+            criterion.to_string()
+        } else {
+            format!("{} UNSEEN", criterion)
+        };
+        let offset = options.offset.unwrap_or(0) as usize;
+        let limit = options.limit.unwrap_or(50) as usize;
+
+        let items = self
+            .search_accounts(scope, &criteria, offset + limit, options.low_bandwidth)
+            .await?;
+        let stream_id = StreamId::new("email-imap", "feed", feed);
+        Ok(items
+            .into_iter()
+            .skip(offset)
+            .map(|item| Item {
+                stream_id: stream_id.clone(),
+                ..item
+            })
+            .collect())
+    }
+
+    /// Messages matching `criteria` across `scope`, most recent first.
+    ///
+    /// As with listing, an unreachable account is skipped when there are
+    /// others.
+    async fn search_accounts(
+        &self,
+        scope: SearchScope<'_>,
+        criteria: &str,
+        limit: usize,
+        low_bandwidth: bool,
+    ) -> Result<Vec<Item>> {
+        let searched = scope.len();
+        let mut items = Vec::new();
+        let mut last_error = None;
+        for (account, mailboxes) in scope {
+            match self
+                .search_mailboxes(account, criteria, mailboxes, limit, low_bandwidth)
+                .await
+            {
+                Ok(found) => items.extend(found),
+                Err(e) if searched > 1 => {
+                    eprintln!("Failed to search account '{}': {}", account.account_name, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if let (true, Some(e)) = (items.is_empty(), last_error) {
+            return Err(e);
+        }
+
+        // UIDs only order messages within a mailbox
+        items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    /// Messages of `account` matching `criteria` in `mailboxes`.
+    async fn search_mailboxes(
+        &self,
+        account: &ImapConfig,
+        criteria: &str,
+        mailboxes: Mailboxes,
+        limit: usize,
+        low_bandwidth: bool,
+    ) -> Result<Vec<Item>> {
+        let mut session = self.connect(account).await?;
+        let mailboxes = match mailboxes {
+            Mailboxes::Named(mailboxes) => mailboxes,
+            mailboxes => {
+                let list = Self::mailbox_list(&mut session).await?;
+                resolve_mailboxes(account, &list, &mailboxes)
+            }
+        };
+
+        let mut items = Vec::new();
+        for mailbox in mailboxes {
+            let feed_id = self.feed_id(account, &mailbox);
+            if let Err(e) = session.select(&mailbox).await {
+                eprintln!("Failed to select mailbox '{}': {}", mailbox, e);
+                continue;
+            }
+            let uids = session
+                .uid_search(criteria)
+                .await
+                .map_err(|e| StreamError::Provider(format!("Search failed: {}", e)))?;
+
+            let mut uids: Vec<u32> = uids.into_iter().collect();
+            uids.sort_unstable_by(|a, b| b.cmp(a));
+            uids.truncate(limit);
+            items.extend(
+                self.fetch_messages(&mut session, &feed_id, &uids, low_bandwidth)
+                    .await?,
+            );
+        }
+        let _ = session.logout().await;
+
+        Ok(items)
+    }
+
+    /// Messages of one mailbox, most recent first.
+    async fn mailbox_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let mut session = self.open_mailbox(feed_id).await?;
+
+        // Build search criteria
+        let search_query = if options.include_read {
+            "ALL"
+        } else {
+            "UNSEEN"
+        };
+
+        // Search for messages
+        let message_uids = session
+            .uid_search(search_query)
+            .await
+            .map_err(|e| StreamError::Provider(format!("Search failed: {}", e)))?;
+
+        // Convert HashSet to sorted Vec for consistent ordering
+        let mut uids_vec: Vec<u32> = message_uids.into_iter().collect();
+        uids_vec.sort_unstable();
+
+        // Apply limit and offset
+        let offset = options.offset.unwrap_or(0) as usize;
+        let limit = options.limit.unwrap_or(50) as usize; // Default limit of 50
+
+        let uids_to_fetch: Vec<u32> = uids_vec
+            .into_iter()
+            .rev() // Most recent first
+            .skip(offset)
+            .take(limit)
+            .collect();
+
+        let items = self
+            .fetch_messages(&mut session, feed_id, &uids_to_fetch, options.low_bandwidth)
+            .await?;
+
+        // Logout
+        session
+            .logout()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))?;
+
+        Ok(items)
+    }
+
+    /// Convert one message of a listing fetch into an Item.
+    fn fetch_to_item(
+        &self,
+        feed_id: &FeedId,
+        lazy: bool,
+        msg: &Fetch,
+    ) -> Option<Result<(u32, Item)>> {
+        let uid = msg.uid?;
+        let flags: Vec<Flag<'_>> = msg.flags().collect();
+
+        if lazy {
+            let envelope = msg.envelope()?;
+            let item = self.envelope_to_item(
+                feed_id,
+                uid,
+                &flags,
+                envelope,
+                msg.bodystructure(),
+                msg.size,
+            );
+            Some(Ok((uid, item)))
+        } else {
+            let body = msg.body()?;
+            Some(
+                self.parse_email(feed_id, uid, &flags, body)
+                    .map(|item| (uid, item)),
+            )
+        }
+    }
+}
+
+/// Translate a search query into IMAP `SEARCH` criteria.
+///
+/// `from:`, `subject:`, and `body:` terms become `FROM`, `SUBJECT`, and
+/// `BODY`; `since:YYYY-MM-DD` becomes `SINCE`. Other words, and unknown
+/// prefixes, become `TEXT`. Values may be double-quoted to include spaces.
+/// Criteria are ANDed, as IMAP does by default; a blank query matches `ALL`.
+pub fn search_criteria(query: &str) -> Result<String> {
+    let mut criteria = Vec::new();
+
+    for term in query_terms(query) {
+        let (key, value) = match term.split_once(':') {
+            Some((key, value)) if !value.is_empty() => (key.to_lowercase(), value),
+            _ => (String::new(), term.as_str()),
+        };
+        let value = value.trim_matches('"');
+        let criterion = match key.as_str() {
+            "from" => format!("FROM {}", imap_quote(value)),
+            "subject" => format!("SUBJECT {}", imap_quote(value)),
+            "body" => format!("BODY {}", imap_quote(value)),
+            "since" => {
+                let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+                    StreamError::Provider(format!(
+                        "Invalid date '{}' in search, expected YYYY-MM-DD",
+                        value
+                    ))
+                })?;
+                format!("SINCE {}", date.format("%-d-%b-%Y"))
+            }
+            _ => format!("TEXT {}", imap_quote(term.trim_matches('"'))),
+        };
+        criteria.push(criterion);
+    }
+
+    if criteria.is_empty() {
+        return Ok("ALL".to_string());
+    }
+    let criteria = criteria.join(" ");
+    if criteria.is_ascii() {
+        Ok(criteria)
+    } else {
+        Ok(format!("CHARSET UTF-8 {}", criteria))
+    }
+}
+
+/// Whitespace-separated terms of a query, keeping double-quoted runs such as
+/// `subject:"lunch plans"` together.
+fn query_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                term.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            c => term.push(c),
+        }
+    }
+    if !term.is_empty() {
+        terms.push(term);
+    }
+
+    terms
+}
+
+/// An IMAP quoted string.
+fn imap_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Check that `flag` is a settable system flag or a valid keyword atom.
+fn validate_flag(flag: &str) -> Result<()> {
+    let valid = if flag.starts_with('\\') {
+        SYSTEM_FLAGS.iter().any(|f| f.eq_ignore_ascii_case(flag))
+    } else {
+        !flag.is_empty()
+            && flag.chars().all(|c| {
+                c.is_ascii_graphic() && !matches!(c, '(' | ')' | '{' | '%' | '*' | '"' | '\\' | ']')
+            })
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(StreamError::Provider(format!(
+            "Invalid IMAP flag '{}'",
+            flag
+        )))
+    }
+}
+
+/// Keywords among a message's flags, such as `$Work`. System flags are
+/// reported through `is_read` and `is_saved` instead.
+fn keywords(flags: &[Flag<'_>]) -> Vec<String> {
+    flags
+        .iter()
+        .filter_map(|flag| match flag {
+            Flag::Custom(keyword) if !keyword.starts_with('\\') => Some(keyword.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The special-use attribute of a LIST response, such as `\Flagged`, or
+/// `None` for attributes such as `\Noselect` that describe the hierarchy.
+fn special_use<'a>(attribute: &'a NameAttribute<'a>) -> Option<&'a str> {
+    match attribute {
+        NameAttribute::All => Some("\\All"),
+        NameAttribute::Archive => Some("\\Archive"),
+        NameAttribute::Drafts => Some("\\Drafts"),
+        NameAttribute::Flagged => Some("\\Flagged"),
+        NameAttribute::Junk => Some("\\Junk"),
+        NameAttribute::Sent => Some("\\Sent"),
+        NameAttribute::Trash => Some("\\Trash"),
+        // Gmail's `\Important`, and attributes of other extensions
+        NameAttribute::Extension(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// The names of the mailboxes `mailboxes` covers, from the server's `list`
+/// of mailboxes and their special-use attributes.
+fn resolve_mailboxes(
+    account: &ImapConfig,
+    list: &[(String, Vec<String>)],
+    mailboxes: &Mailboxes,
+) -> Vec<String> {
+    let listed = || {
+        list.iter()
+            .map(|(name, _)| name.clone())
+            .filter(|name| account.lists_mailbox(name))
+            .collect()
+    };
+    match mailboxes {
+        Mailboxes::Listed => listed(),
+        Mailboxes::Named(names) => names.clone(),
+        Mailboxes::SpecialUse {
+            attribute,
+            fallback,
+        } => {
+            let special = list.iter().find(|(_, attributes)| {
+                attributes
+                    .iter()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(attribute))
+            });
+            match special {
+                Some((name, _)) => vec![name.clone()],
+                None if *fallback => listed(),
+                None => Vec::new(),
+            }
+        }
+    }
+}
+
+/// Whether `name` is a junk mailbox by name alone.
+fn is_junk_name(name: &str) -> bool {
+    JUNK_MAILBOX_NAMES
+        .iter()
+        .any(|junk| junk.eq_ignore_ascii_case(name))
+}
+
+/// Whether the message behind `item` is in a junk mailbox, judging by the
+/// mailbox name, since actions are listed without asking the server.
+fn in_junk_mailbox(item: &Item) -> bool {
+    message_location(item).is_ok_and(|(_, mailbox)| is_junk_name(mailbox))
+}
+
+/// The junk mailbox in the server's `list`: the one with the `\Junk`
+/// special-use attribute, or else one of [`JUNK_MAILBOX_NAMES`].
+fn junk_mailbox(list: &[(String, Vec<String>)]) -> Option<&str> {
+    list.iter()
+        .find(|(_, attributes)| attributes.iter().any(|a| a.eq_ignore_ascii_case("\\Junk")))
+        .or_else(|| list.iter().find(|(name, _)| is_junk_name(name)))
+        .map(|(name, _)| name.as_str())
+}
+
+/// Swap the `$Junk` and `$NotJunk` keywords on `uid` in the selected
+/// mailbox. Servers that refuse keywords still get the message moved, so a
+/// failure is only logged.
+async fn set_junk_keywords(session: &mut ImapSession, uid: &str, junk: bool) {
+    let (set, clear) = if junk {
+        ("$Junk", "$NotJunk")
+    } else {
+        ("$NotJunk", "$Junk")
+    };
+    for query in [
+        format!("-FLAGS.SILENT ({})", clear),
+        format!("+FLAGS.SILENT ({})", set),
+    ] {
+        if let Err(e) = store_flags(session, uid, &query).await {
+            eprintln!("Failed to store junk keywords on UID {}: {}", uid, e);
+        }
+    }
+}
+
+/// Store flags on `uid` in the selected mailbox, as in `+FLAGS (\Seen)`.
+async fn store_flags(session: &mut ImapSession, uid: &str, query: &str) -> Result<()> {
+    let mut updates = session
+        .uid_store(uid, query)
+        .await
+        .map_err(|e| StreamError::Provider(format!("Failed to store flags: {}", e)))?;
+    while let Some(update) = updates.next().await {
+        update.map_err(|e| StreamError::Provider(format!("Failed to store flags: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Move `uid` from the selected mailbox to `folder`.
+async fn move_message(session: &mut ImapSession, uid: &str, folder: &str) -> Result<()> {
+    let capabilities = session
+        .capabilities()
+        .await
+        .map_err(|e| StreamError::Provider(format!("Failed to read capabilities: {}", e)))?;
+    let move_error =
+        |e| StreamError::Provider(format!("Failed to move message to {}: {}", folder, e));
+
+    if capabilities.has_str("MOVE") {
+        session.uid_mv(uid, folder).await.map_err(move_error)?;
+    } else {
+        // RFC 6851's fallback; without UIDPLUS, EXPUNGE also removes any
+        // other message already marked \Deleted
+        session
+            .uid_copy(uid, imap_quote(folder))
+            .await
+            .map_err(move_error)?;
+        store_flags(session, uid, "+FLAGS.SILENT (\\Deleted)").await?;
+        if capabilities.has_str("UIDPLUS") {
+            let expunged = session.uid_expunge(uid).await.map_err(move_error)?;
+            futures::pin_mut!(expunged);
+            while let Some(result) = expunged.next().await {
+                result.map_err(move_error)?;
+            }
+        } else {
+            let expunged = session.expunge().await.map_err(move_error)?;
+            futures::pin_mut!(expunged);
+            while let Some(result) = expunged.next().await {
+                result.map_err(move_error)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decode a raw header value, including RFC 2047 encoded words.
+fn decode_header(name: &str, value: &[u8]) -> String {
+    let mut line = format!("{}: ", name).into_bytes();
+    line.extend_from_slice(value);
+    match mailparse::parse_header(&line) {
+        Ok((header, _)) => header.get_value(),
+        Err(_) => String::from_utf8_lossy(value).into_owned(),
+    }
+}
+
+/// The addresses of a `To` or `Cc` header, formatted with
+/// [`format_mailbox`], or `None` if it has none.
+fn header_recipients(header: &mailparse::MailHeader<'_>) -> Option<String> {
+    let list = addrparse_header(header).ok()?;
+    let mut recipients = Vec::new();
+    for addr in list.iter() {
+        match addr {
+            MailAddr::Single(single) => {
+                recipients.push(format_mailbox(single.display_name.as_deref(), &single.addr));
+            }
+            MailAddr::Group(group) => recipients.extend(
+                group
+                    .addrs
+                    .iter()
+                    .map(|single| format_mailbox(single.display_name.as_deref(), &single.addr)),
+            ),
+        }
+    }
+    Some(recipients.join(", ")).filter(|recipients| !recipients.is_empty())
+}
+
+/// The bare addresses a parsed message was sent to, from `To` and `Cc`.
+fn recipient_addresses(parsed: &ParsedMail<'_>) -> Vec<String> {
+    let mut addresses = Vec::new();
+    for header in parsed.headers.iter() {
+        let name = header.get_key_ref();
+        if !name.eq_ignore_ascii_case("To") && !name.eq_ignore_ascii_case("Cc") {
+            continue;
+        }
+        for addr in addrparse_header(header)
+            .map(|list| list.into_inner())
+            .unwrap_or_default()
+        {
+            match addr {
+                MailAddr::Single(single) => addresses.push(single.addr),
+                MailAddr::Group(group) => {
+                    addresses.extend(group.addrs.into_iter().map(|single| single.addr))
+                }
+            }
+        }
+    }
+    addresses
+}
+
+/// The addresses of an envelope's `To` or `Cc` list, formatted like
+/// [`header_recipients`].
+fn envelope_recipients(addresses: &[Address<'_>]) -> Option<String> {
+    let recipients: Vec<String> = addresses
+        .iter()
+        .filter_map(|address| {
+            // Group markers have a mailbox but no host
+            let (mailbox, host) = (address.mailbox.as_deref()?, address.host.as_deref()?);
+            let email = format!(
+                "{}@{}",
+                String::from_utf8_lossy(mailbox),
+                String::from_utf8_lossy(host)
+            );
+            let name = address
+                .name
+                .as_deref()
+                .map(|name| decode_header("To", name));
+            Some(format_mailbox(name.as_deref(), &email))
         })
+        .collect();
+    Some(recipients.join(", ")).filter(|recipients| !recipients.is_empty())
+}
+
+/// Number of parts a message structure marks as attachments.
+fn count_attachments(structure: &BodyStructure<'_>) -> usize {
+    match structure {
+        BodyStructure::Multipart { bodies, .. } => bodies.iter().map(count_attachments).sum(),
+        BodyStructure::Basic { common, .. }
+        | BodyStructure::Text { common, .. }
+        | BodyStructure::Message { common, .. } => usize::from(
+            common
+                .disposition
+                .as_ref()
+                .is_some_and(|d| d.ty.eq_ignore_ascii_case("attachment")),
+        ),
+    }
+}
+
+/// Parts of a parsed message marked as attachments, with their IMAP part
+/// numbers ("1", "2.1", ...) as IDs.
+fn message_attachments<'a>(parsed: &'a ParsedMail<'a>) -> Vec<(Attachment, &'a ParsedMail<'a>)> {
+    fn walk<'a>(
+        part: &'a ParsedMail<'a>,
+        number: String,
+        found: &mut Vec<(Attachment, &'a ParsedMail<'a>)>,
+    ) {
+        if !part.subparts.is_empty() {
+            for (i, subpart) in part.subparts.iter().enumerate() {
+                let child = if number.is_empty() {
+                    (i + 1).to_string()
+                } else {
+                    format!("{}.{}", number, i + 1)
+                };
+                walk(subpart, child, found);
+            }
+            return;
+        }
+
+        let disposition = part.get_content_disposition();
+        if disposition.disposition != DispositionType::Attachment {
+            return;
+        }
+        // A message that is not multipart is its own part 1
+        let number = if number.is_empty() {
+            "1".to_string()
+        } else {
+            number
+        };
+        let file_name = disposition
+            .params
+            .get("filename")
+            .or_else(|| part.ctype.params.get("name"))
+            .cloned()
+            .unwrap_or_else(|| format!("attachment-{}", number));
+        let size = part.get_body_raw().ok().map(|body| body.len() as u64);
+        found.push((
+            Attachment {
+                id: number,
+                file_name,
+                mime_type: Some(part.ctype.mimetype.to_lowercase()),
+                size,
+            },
+            part,
+        ));
     }
+
+    let mut found = Vec::new();
+    walk(parsed, String::new(), &mut found);
+    found
+}
+
+/// Whether `server` is this machine, so a plain connection never leaves it.
+fn is_loopback(server: &str) -> bool {
+    server.eq_ignore_ascii_case("localhost")
+        || server
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// UID and mailbox of the message behind `item`.
+fn message_location(item: &Item) -> Result<(&str, &str)> {
+    let uid = item
+        .metadata
+        .get("uid")
+        .ok_or_else(|| StreamError::ItemNotFound(format!("No IMAP UID for {}", item.id.0)))?;
+    let mailbox = item
+        .metadata
+        .get("mailbox")
+        .ok_or_else(|| StreamError::ItemNotFound(format!("No IMAP mailbox for {}", item.id.0)))?;
+    Ok((uid, mailbox))
 }
 
 #[async_trait]
@@ -287,46 +1903,77 @@ impl Provider for ImapProvider {
         self
     }
 
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_search(&self) -> Option<&dyn HasSearch> {
+        Some(self)
+    }
+
+    fn as_attachments(&self) -> Option<&dyn HasAttachments> {
+        Some(self)
+    }
+
     async fn health_check(&self) -> Result<ProviderHealth> {
-        match self.connect().await {
-            Ok(mut session) => {
-                // Logout cleanly
-                let _ = session.logout().await;
-
-                Ok(ProviderHealth {
-                    is_healthy: true,
-                    message: Some(format!(
-                        "Connected to {} as {}",
-                        self.config.server, self.config.username
-                    )),
-                    last_sync: Some(Utc::now()),
-                    error_count: 0,
-                })
+        let mut connected = Vec::new();
+        let mut errors = Vec::new();
+        for account in &self.accounts {
+            match self.connect(account).await {
+                Ok(mut session) => {
+                    // Logout cleanly
+                    let _ = session.logout().await;
+                    connected.push(format!("{} as {}", account.server, account.username));
+                }
+                Err(e) if self.accounts.len() > 1 => {
+                    errors.push(format!("{}: {}", account.account_name, e));
+                }
+                Err(e) => errors.push(e.to_string()),
             }
-            Err(e) => Ok(ProviderHealth {
+        }
+
+        if errors.is_empty() {
+            Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!("Connected to {}", connected.join(", "))),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            })
+        } else {
+            Ok(ProviderHealth {
                 is_healthy: false,
-                message: Some(format!("Connection failed: {}", e)),
+                message: Some(format!("Connection failed: {}", errors.join("; "))),
                 last_sync: None,
-                error_count: 1,
-            }),
+                error_count: errors.len() as u32,
+            })
         }
     }
 
     async fn sync(&self) -> Result<SyncResult> {
         let start = std::time::Instant::now();
 
-        // For IMAP, sync is just checking that we can connect
-        match self.connect().await {
-            Ok(mut session) => {
-                let _ = session.logout().await;
-                Ok(SyncResult {
-                    success: true,
-                    items_added: 0,
-                    items_updated: 0,
-                    items_removed: 0,
-                    errors: vec![],
-                    duration_ms: start.elapsed().as_millis() as u64,
-                })
+        // Items are fetched from the server when listed, so sync only wakes
+        // snoozed messages and refreshes the mailbox counts
+        let woken = match self.wake_snoozed(Utc::now()).await {
+            Ok(woken) => woken,
+            Err(e) => {
+                eprintln!("Failed to wake snoozed messages: {}", e);
+                0
+            }
+        };
+        match self.refresh_mailbox_feeds().await {
+            Ok((feeds, failed)) => {
+                let streams = feeds
+                    .iter()
+                    .map(|feed| {
+                        StreamSyncResult::ok(StreamId::new(self.id(), "feed", &feed.id.0), 0, 0)
+                    })
+                    .chain(failed)
+                    .collect();
+                let mut result =
+                    SyncResult::from_streams(streams, start.elapsed().as_millis() as u64);
+                result.items_updated += woken as u32;
+                Ok(result)
             }
             Err(e) => Ok(SyncResult {
                 success: false,
@@ -335,6 +1982,7 @@ impl Provider for ImapProvider {
                 items_removed: 0,
                 errors: vec![format!("Sync failed: {}", e)],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -345,11 +1993,16 @@ impl Provider for ImapProvider {
             has_collections: false,
             has_saved_items: false,
             has_communities: false,
+            supports_search: true,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
-    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
-        Ok(vec![
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = vec![
             Action {
                 id: "preview".to_string(),
                 name: "Preview".to_string(),
@@ -378,11 +2031,201 @@ impl Provider for ImapProvider {
                 kind: ActionKind::Archive,
                 keyboard_shortcut: Some("a".to_string()),
             },
-        ])
+        ];
+
+        actions.push(if item.is_saved {
+            Action {
+                id: "unstar".to_string(),
+                name: "Unstar".to_string(),
+                description: "Remove the star from email".to_string(),
+                kind: ActionKind::Custom("unstar".to_string()),
+                keyboard_shortcut: Some("F".to_string()),
+            }
+        } else {
+            Action {
+                id: "star".to_string(),
+                name: "Star".to_string(),
+                description: "Star email".to_string(),
+                kind: ActionKind::Custom("star".to_string()),
+                keyboard_shortcut: Some("f".to_string()),
+            }
+        });
+        actions.push(if in_junk_mailbox(item) {
+            Action {
+                id: "not_spam".to_string(),
+                name: "Not Spam".to_string(),
+                description: "Move email back to the inbox".to_string(),
+                kind: ActionKind::Custom("not_spam".to_string()),
+                keyboard_shortcut: Some("J".to_string()),
+            }
+        } else {
+            Action {
+                id: "mark_spam".to_string(),
+                name: "Mark as Spam".to_string(),
+                description: "Move email to the junk folder".to_string(),
+                kind: ActionKind::Custom("mark_spam".to_string()),
+                keyboard_shortcut: Some("J".to_string()),
+            }
+        });
+        actions.push(Action {
+            id: "move_to_folder".to_string(),
+            name: "Move to Folder".to_string(),
+            description: "Move email to another folder".to_string(),
+            kind: ActionKind::Custom("move_to_folder".to_string()),
+            keyboard_shortcut: Some("m".to_string()),
+        });
+        actions.push(Action {
+            id: "add_keyword".to_string(),
+            name: "Add Keyword".to_string(),
+            description: "Set a custom keyword flag".to_string(),
+            kind: ActionKind::Custom("add_keyword".to_string()),
+            keyboard_shortcut: Some("k".to_string()),
+        });
+        actions.push(Action {
+            id: "snooze".to_string(),
+            name: "Snooze".to_string(),
+            description: "Hide email until later".to_string(),
+            kind: ActionKind::Custom("snooze".to_string()),
+            keyboard_shortcut: Some("z".to_string()),
+        });
+        if !item.tags.is_empty() {
+            actions.push(Action {
+                id: "remove_keyword".to_string(),
+                name: "Remove Keyword".to_string(),
+                description: "Clear a custom keyword flag".to_string(),
+                kind: ActionKind::Custom("remove_keyword".to_string()),
+                keyboard_shortcut: Some("K".to_string()),
+            });
+        }
+        let can_reply = self
+            .locate(item)
+            .is_ok_and(|(account, _, _)| account.smtp.is_some());
+        if can_reply && CalendarInvite::from_item(item).is_some_and(|invite| invite.is_request()) {
+            actions.push(Action {
+                id: "accept_invite".to_string(),
+                name: "Accept Invitation".to_string(),
+                description: "Tell the organizer you will attend".to_string(),
+                kind: ActionKind::Custom("accept_invite".to_string()),
+                keyboard_shortcut: Some("Y".to_string()),
+            });
+            actions.push(Action {
+                id: "decline_invite".to_string(),
+                name: "Decline Invitation".to_string(),
+                description: "Tell the organizer you won't attend".to_string(),
+                kind: ActionKind::Custom("decline_invite".to_string()),
+                keyboard_shortcut: Some("N".to_string()),
+            });
+        }
+
+        Ok(actions)
     }
 
-    async fn execute_action(&self, _item: &Item, action: &Action) -> Result<ActionResult> {
-        // TODO: Implement actual IMAP operations (STORE flags, MOVE, etc.)
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        let seen = match action.kind {
+            ActionKind::MarkRead => Some(true),
+            ActionKind::MarkUnread => Some(false),
+            _ => None,
+        };
+        if let Some(seen) = seen {
+            self.set_flag(item, "\\Seen", seen).await?;
+            return Ok(ActionResult {
+                success: true,
+                message: None,
+                data: None,
+            });
+        }
+
+        if action.kind == ActionKind::Preview {
+            let loaded = self.load_body(item).await?;
+            return Ok(ActionResult {
+                success: true,
+                message: None,
+                data: Some(serde_json::to_value(&loaded).map_err(|e| {
+                    StreamError::Internal(format!("Failed to serialize item: {}", e))
+                })?),
+            });
+        }
+
+        if let ActionKind::Custom(name) = &action.kind {
+            let (account, _, mailbox) = self.locate(item)?;
+            let data = match name.as_str() {
+                "star" | "unstar" => {
+                    self.set_flag(item, "\\Flagged", name == "star").await?;
+                    None
+                }
+                "mark_spam" => {
+                    let junk = self.mark_spam(item).await?;
+                    return Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("Moved to {}", junk)),
+                        data: None,
+                    });
+                }
+                "not_spam" => {
+                    self.mark_not_spam(item).await?;
+                    return Ok(ActionResult {
+                        success: true,
+                        message: Some("Moved to INBOX".to_string()),
+                        data: None,
+                    });
+                }
+                "accept_invite" | "decline_invite" => {
+                    let accept = name == "accept_invite";
+                    let organizer = self.respond_to_invite(item, accept).await?;
+                    let verb = if accept { "Accepted" } else { "Declined" };
+                    return Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("{}; reply sent to {}", verb, organizer)),
+                        data: None,
+                    });
+                }
+                // The client picks a folder, then calls `move_to_folder`
+                "move_to_folder" => Some(serde_json::json!({
+                    "mailbox": mailbox,
+                    "folders": self.move_targets(account, mailbox).await?,
+                    "requires_input": true,
+                    "input_type": "folder",
+                })),
+                // The client asks for a keyword, then calls `set_flag`
+                "add_keyword" => Some(serde_json::json!({
+                    "requires_input": true,
+                    "input_type": "keyword",
+                })),
+                // The client picks a time, then calls `snooze`
+                "snooze" => {
+                    let now = Utc::now();
+                    let preset = |label: &str, after: chrono::Duration| serde_json::json!({ "label": label, "until": (now + after).to_rfc3339() });
+                    Some(serde_json::json!({
+                        "options": [
+                            preset("Later today", chrono::Duration::hours(3)),
+                            preset("Tomorrow", chrono::Duration::days(1)),
+                            preset("Next week", chrono::Duration::weeks(1)),
+                        ],
+                        "requires_input": true,
+                        "input_type": "datetime",
+                    }))
+                }
+                "remove_keyword" => Some(serde_json::json!({
+                    "keywords": item.tags,
+                    "requires_input": true,
+                    "input_type": "keyword",
+                })),
+                _ => {
+                    return Ok(ActionResult {
+                        success: false,
+                        message: Some(format!("Unknown action: {}", name)),
+                        data: None,
+                    })
+                }
+            };
+            return Ok(ActionResult {
+                success: true,
+                message: None,
+                data,
+            });
+        }
+
+        // TODO: Implement MOVE for archiving
         Ok(ActionResult {
             success: false,
             message: Some(format!(
@@ -397,133 +2240,142 @@ impl Provider for ImapProvider {
 #[async_trait]
 impl HasFeeds for ImapProvider {
     async fn list_feeds(&self) -> Result<Vec<Feed>> {
-        let mut session = self.connect().await?;
-
-        // List all mailboxes and collect names first (to release the borrow on session)
-        let mut mailbox_stream = session
-            .list(Some(""), Some("*"))
-            .await
-            .map_err(|e| StreamError::Provider(format!("Failed to list mailboxes: {}", e)))?;
+        // Counts stay as of the last sync unless something here changed them
+        let cached = self.mailbox_feeds.read().unwrap().clone();
+        let mut feeds = match cached {
+            Some(feeds) => feeds,
+            None => self.refresh_mailbox_feeds().await?.0,
+        };
 
-        let mut mailbox_names = Vec::new();
-        while let Some(mailbox_result) = mailbox_stream.next().await {
-            match mailbox_result {
-                Ok(mailbox) => {
-                    mailbox_names.push(mailbox.name().to_string());
-                }
-                Err(e) => {
-                    eprintln!("Failed to list mailbox: {}", e);
-                }
-            }
+        if self.accounts.len() > 1 {
+            let inboxes: Vec<&Feed> = feeds
+                .iter()
+                .filter(|feed| {
+                    self.resolve_feed(&feed.id)
+                        .is_ok_and(|(_, mailbox)| mailbox.eq_ignore_ascii_case("INBOX"))
+                })
+                .collect();
+            let all_inboxes = Feed {
+                id: FeedId(ALL_INBOXES_FEED.to_string()),
+                name: "All Inboxes".to_string(),
+                description: Some("INBOX of every account".to_string()),
+                icon: Some("📥".to_string()),
+                unread_count: inboxes.iter().map(|feed| feed.unread_count).sum(),
+                total_count: inboxes.iter().map(|feed| feed.total_count).sum(),
+            };
+            feeds.insert(0, all_inboxes);
         }
 
-        // Drop the stream to release the borrow on session
-        drop(mailbox_stream);
-
-        let mut feeds = Vec::new();
-
-        // Now we can use session again to select each mailbox
-        for name in mailbox_names {
-            match session.select(&name).await {
-                Ok(mailbox_status) => {
-                    let exists = mailbox_status.exists;
-                    let unseen = mailbox_status.unseen.unwrap_or(0);
-                    feeds.push(self.mailbox_to_feed(name, exists, unseen));
-                }
-                Err(e) => {
-                    // Log error but continue with other mailboxes
-                    eprintln!("Failed to select mailbox '{}': {}", name, e);
-                }
-            }
+        feeds.push(Feed {
+            id: FeedId(STARRED_FEED.to_string()),
+            name: "Starred".to_string(),
+            description: Some("Flagged messages from every folder".to_string()),
+            icon: Some("⭐".to_string()),
+            unread_count: None,
+            total_count: None,
+        });
+        if self.accounts.iter().any(ImapConfig::is_gmail) {
+            feeds.push(Feed {
+                id: FeedId(IMPORTANT_FEED.to_string()),
+                name: "Important".to_string(),
+                description: Some("Messages Gmail marks important".to_string()),
+                icon: Some("❗".to_string()),
+                unread_count: None,
+                total_count: None,
+            });
         }
 
-        // Logout
-        session
-            .logout()
-            .await
-            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))?;
-
         Ok(feeds)
     }
 
     async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
-        let mut session = self.connect().await?;
-
-        // Extract mailbox name from feed_id (format: "imap:INBOX")
-        let mailbox_name = feed_id.0.strip_prefix("imap:").ok_or_else(|| {
-            StreamError::StreamNotFound(format!("Invalid feed ID: {}", feed_id.0))
-        })?;
-
-        // Select the mailbox
-        session
-            .select(mailbox_name)
-            .await
-            .map_err(|e| StreamError::StreamNotFound(format!("Mailbox not found: {}", e)))?;
+        if let Err(e) = self.wake_snoozed(Utc::now()).await {
+            eprintln!("Failed to wake snoozed messages: {}", e);
+        }
 
-        // Build search criteria
-        let search_query = if options.include_read {
-            "ALL"
-        } else {
-            "UNSEEN"
+        let mut items = match self.virtual_feed(&feed_id.0) {
+            Some((criterion, scope)) => {
+                self.virtual_feed_items(&feed_id.0, criterion, scope, options)
+                    .await?
+            }
+            None => self.mailbox_items(feed_id, options).await?,
         };
+        items.retain(|item| !self.snoozes.hides(item));
+        Ok(items)
+    }
+}
 
-        // Search for messages
-        let message_uids = session
-            .uid_search(search_query)
-            .await
-            .map_err(|e| StreamError::Provider(format!("Search failed: {}", e)))?;
-
-        // Convert HashSet to sorted Vec for consistent ordering
-        let mut uids_vec: Vec<u32> = message_uids.into_iter().collect();
-        uids_vec.sort_unstable();
-
-        // Apply limit and offset
-        let offset = options.offset.unwrap_or(0) as usize;
-        let limit = options.limit.unwrap_or(50) as usize; // Default limit of 50
-
-        let uids_to_fetch: Vec<u32> = uids_vec
-            .into_iter()
-            .rev() // Most recent first
-            .skip(offset)
-            .take(limit)
-            .collect();
-
-        let mut items = Vec::new();
-
-        // Fetch messages
-        for uid in uids_to_fetch {
-            let mut fetch_stream = session
-                .uid_fetch(uid.to_string(), "RFC822")
-                .await
-                .map_err(|e| StreamError::Provider(format!("Fetch failed: {}", e)))?;
-
-            // Process the fetch stream
-            while let Some(fetch_result) = fetch_stream.next().await {
-                match fetch_result {
-                    Ok(msg) => {
-                        if let Some(body) = msg.body() {
-                            match self.parse_email(feed_id, uid, body) {
-                                Ok(item) => items.push(item),
-                                Err(e) => {
-                                    eprintln!("Failed to parse email UID {}: {}", uid, e);
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to fetch message UID {}: {}", uid, e);
-                    }
+#[async_trait]
+impl HasSearch for ImapProvider {
+    /// Search one mailbox or virtual feed when `options.stream_id` names it,
+    /// otherwise every mailbox of every account. Results are most recent
+    /// first; `sort` and `time` are ignored.
+    async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<Item>> {
+        let mut criteria = search_criteria(query)?;
+        let feed = options
+            .stream_id
+            .as_ref()
+            .and_then(|stream_id| stream_id.as_str().strip_prefix("email-imap:feed:"))
+            .map(|feed| FeedId(feed.to_string()));
+        let scope = match &feed {
+            Some(feed) => match self.virtual_feed(&feed.0) {
+                Some((criterion, scope)) => {
+                    criteria = format!("{} {}", criterion, criteria);
+                    scope
                 }
-            }
+                None => {
+                    let (account, mailbox) = self.resolve_feed(feed)?;
+                    vec![(account, Mailboxes::Named(vec![mailbox.to_string()]))]
+                }
+            },
+            None => self
+                .accounts
+                .iter()
+                .map(|account| (account, Mailboxes::Listed))
+                .collect(),
+        };
+
+        let limit = options.limit.unwrap_or(50) as usize;
+        let mut items = self
+            .search_accounts(scope, &criteria, limit, options.low_bandwidth)
+            .await?;
+        for (rank, item) in items.iter_mut().enumerate() {
+            item.metadata
+                .insert("search_rank".to_string(), (rank + 1).to_string());
         }
+        Ok(items)
+    }
+}
 
-        // Logout
-        session
-            .logout()
-            .await
-            .map_err(|e| StreamError::Provider(format!("Failed to logout: {}", e)))?;
+#[async_trait]
+impl HasAttachments for ImapProvider {
+    /// Fetches the whole message, since the cached item only records how
+    /// many attachments it has.
+    async fn list_attachments(&self, item: &Item) -> Result<Vec<Attachment>> {
+        let source = self.fetch_source(item).await?;
+        let parsed = parse_mail(&source)
+            .map_err(|e| StreamError::Provider(format!("Failed to parse email: {}", e)))?;
+        Ok(message_attachments(&parsed)
+            .into_iter()
+            .map(|(attachment, _)| attachment)
+            .collect())
+    }
 
-        Ok(items)
+    async fn fetch_attachment(&self, item: &Item, attachment_id: &str) -> Result<Vec<u8>> {
+        let source = self.fetch_source(item).await?;
+        let parsed = parse_mail(&source)
+            .map_err(|e| StreamError::Provider(format!("Failed to parse email: {}", e)))?;
+        let (_, part) = message_attachments(&parsed)
+            .into_iter()
+            .find(|(attachment, _)| attachment.id == attachment_id)
+            .ok_or_else(|| {
+                StreamError::ItemNotFound(format!(
+                    "No attachment {} in {}",
+                    attachment_id, item.id.0
+                ))
+            })?;
+        part.get_body_raw()
+            .map_err(|e| StreamError::Provider(format!("Failed to decode attachment: {}", e)))
     }
 }
 
@@ -543,6 +2395,9 @@ mod tests {
             username: "test@example.com".to_string(),
             account_name: "test-account".to_string(),
             use_tls: true,
+            lazy_bodies: false,
+            mailboxes: vec![],
+            smtp: None,
         }
     }
 
@@ -577,7 +2432,7 @@ mod tests {
         let token_fetcher = create_test_token_fetcher();
         let provider = ImapProvider::new(config, token_fetcher);
 
-        let feed = provider.mailbox_to_feed("INBOX".to_string(), 100, 5);
+        let feed = provider.mailbox_to_feed(&provider.accounts[0], "INBOX".to_string(), 100, 5);
         assert_eq!(feed.id.0, "imap:INBOX");
         assert_eq!(feed.name, "INBOX");
         assert_eq!(feed.icon, Some("📥".to_string()));
@@ -601,7 +2456,9 @@ mod tests {
                           This is a test email body.";
 
         let feed_id = FeedId("imap:INBOX".to_string());
-        let item = provider.parse_email(&feed_id, 123, email_data).unwrap();
+        let item = provider
+            .parse_email(&feed_id, 123, &[Flag::Seen], email_data)
+            .unwrap();
 
         assert_eq!(item.title, "Test Email");
         assert_eq!(item.id.0, "email-imap:<test@example.com>");
@@ -621,11 +2478,51 @@ mod tests {
             panic!("Expected Email content");
         }
 
+        assert!(item.is_read);
+        assert!(!item.is_saved);
+        assert_eq!(item.metadata.get("uid"), Some(&"123".to_string()));
+        assert_eq!(item.metadata.get("mailbox"), Some(&"INBOX".to_string()));
+
         assert!(item.author.is_some());
         let author = item.author.unwrap();
         assert_eq!(author.email, Some("sender@example.com".to_string()));
     }
 
+    #[test]
+    fn test_recipients_metadata() {
+        let provider = ImapProvider::new(create_test_config(), create_test_token_fetcher());
+        let email_data = b"From: sender@example.com\r\n\
+                          To: Alice <alice@example.com>, \"Doe, Jane\" <jane@example.com>\r\n\
+                          Cc: Team: bob@example.com, carol@example.com;\r\n\
+                          Subject: Plans\r\n\
+                          \r\n\
+                          Body";
+
+        let item = provider
+            .parse_email(&FeedId("INBOX".to_string()), 7, &[], email_data)
+            .unwrap();
+        assert_eq!(
+            item.metadata.get(TO_METADATA_KEY).map(String::as_str),
+            Some(r#"Alice <alice@example.com>, "Doe, Jane" <jane@example.com>"#)
+        );
+        assert_eq!(
+            item.metadata.get(CC_METADATA_KEY).map(String::as_str),
+            Some("bob@example.com, carol@example.com")
+        );
+
+        let addresses = [Address {
+            name: Some(b"Bob"[..].into()),
+            adl: None,
+            mailbox: Some(b"bob"[..].into()),
+            host: Some(b"example.com"[..].into()),
+        }];
+        assert_eq!(
+            envelope_recipients(&addresses),
+            Some("Bob <bob@example.com>".to_string())
+        );
+        assert_eq!(envelope_recipients(&[]), None);
+    }
+
     #[test]
     fn test_parse_multipart_email() {
         let config = create_test_config();
@@ -649,7 +2546,9 @@ mod tests {
                           --boundary--";
 
         let feed_id = FeedId("imap:INBOX".to_string());
-        let item = provider.parse_email(&feed_id, 456, email_data).unwrap();
+        let item = provider
+            .parse_email(&feed_id, 456, &[], email_data)
+            .unwrap();
 
         if let ItemContent::Email {
             body_text,
@@ -671,6 +2570,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_message_attachments() {
+        let email_data = b"From: sender@example.com\r\n\
+                          Subject: Invoice\r\n\
+                          MIME-Version: 1.0\r\n\
+                          Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+                          \r\n\
+                          --outer\r\n\
+                          Content-Type: multipart/alternative; boundary=\"inner\"\r\n\
+                          \r\n\
+                          --inner\r\n\
+                          Content-Type: text/plain\r\n\
+                          \r\n\
+                          See attached\r\n\
+                          --inner--\r\n\
+                          --outer\r\n\
+                          Content-Type: application/pdf; name=\"invoice.pdf\"\r\n\
+                          Content-Disposition: attachment; filename=\"invoice.pdf\"\r\n\
+                          Content-Transfer-Encoding: base64\r\n\
+                          \r\n\
+                          JVBERi0xLjQ=\r\n\
+                          --outer\r\n\
+                          Content-Type: text/csv\r\n\
+                          Content-Disposition: attachment\r\n\
+                          Content-Transfer-Encoding: base64\r\n\
+                          \r\n\
+                          YSxi\r\n\
+                          --outer--";
+
+        let parsed = parse_mail(email_data).unwrap();
+        let attachments = message_attachments(&parsed);
+
+        let found: Vec<&Attachment> = attachments.iter().map(|(a, _)| a).collect();
+        assert_eq!(
+            found,
+            vec![
+                &Attachment {
+                    id: "2".to_string(),
+                    file_name: "invoice.pdf".to_string(),
+                    mime_type: Some("application/pdf".to_string()),
+                    size: Some(8),
+                },
+                &Attachment {
+                    id: "3".to_string(),
+                    file_name: "attachment-3".to_string(),
+                    mime_type: Some("text/csv".to_string()),
+                    size: Some(3),
+                },
+            ]
+        );
+        assert_eq!(attachments[0].1.get_body_raw().unwrap(), b"%PDF-1.4");
+    }
+
     #[tokio::test]
     async fn test_available_actions() {
         let config = create_test_config();
@@ -699,11 +2651,69 @@ mod tests {
         };
 
         let actions = provider.available_actions(&item).await.unwrap();
-        assert_eq!(actions.len(), 4);
+        assert_eq!(actions.len(), 9);
         assert_eq!(actions[0].kind, ActionKind::Preview);
         assert_eq!(actions[1].kind, ActionKind::MarkRead);
         assert_eq!(actions[2].kind, ActionKind::MarkUnread);
         assert_eq!(actions[3].kind, ActionKind::Archive);
+        assert_eq!(actions[4].id, "star");
+        assert_eq!(actions[5].id, "mark_spam");
+        assert_eq!(actions[6].id, "move_to_folder");
+        assert_eq!(actions[7].id, "add_keyword");
+        assert_eq!(actions[8].id, "snooze");
+
+        // Starred messages offer unstar, and keywords can be removed
+        let item = Item {
+            is_saved: true,
+            tags: vec!["$Work".to_string()],
+            ..item
+        };
+        let ids: Vec<String> = provider
+            .available_actions(&item)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|action| action.id)
+            .collect();
+        assert!(ids.contains(&"unstar".to_string()));
+        assert!(!ids.contains(&"star".to_string()));
+        assert!(ids.contains(&"remove_keyword".to_string()));
+    }
+
+    #[test]
+    fn test_is_loopback() {
+        assert!(is_loopback("127.0.0.1"));
+        assert!(is_loopback("localhost"));
+        assert!(is_loopback("::1"));
+        assert!(is_loopback("[::1]"));
+        assert!(!is_loopback("imap.example.com"));
+        assert!(!is_loopback("192.168.1.10"));
+    }
+
+    #[test]
+    fn test_validate_flag() {
+        assert!(validate_flag("\\Flagged").is_ok());
+        assert!(validate_flag("\\seen").is_ok());
+        assert!(validate_flag("$Work").is_ok());
+        assert!(validate_flag("Receipt_2024").is_ok());
+
+        assert!(validate_flag("").is_err());
+        assert!(validate_flag("\\Recent").is_err());
+        assert!(validate_flag("\\Custom").is_err());
+        assert!(validate_flag("two words").is_err());
+        assert!(validate_flag("(Work)").is_err());
+        assert!(validate_flag("Büro").is_err());
+    }
+
+    #[test]
+    fn test_keywords_skip_system_flags() {
+        let flags = [
+            Flag::Seen,
+            Flag::Flagged,
+            Flag::Custom("$Work".into()),
+            Flag::Custom("\\Important".into()),
+        ];
+        assert_eq!(keywords(&flags), vec!["$Work"]);
     }
 
     #[test]
@@ -712,19 +2722,147 @@ mod tests {
         let token_fetcher = create_test_token_fetcher();
         let provider = ImapProvider::new(config, token_fetcher);
 
-        let inbox = provider.mailbox_to_feed("INBOX".to_string(), 0, 0);
+        let inbox = provider.mailbox_to_feed(&provider.accounts[0], "INBOX".to_string(), 0, 0);
         assert_eq!(inbox.icon, Some("📥".to_string()));
 
-        let sent = provider.mailbox_to_feed("Sent".to_string(), 0, 0);
+        let sent = provider.mailbox_to_feed(&provider.accounts[0], "Sent".to_string(), 0, 0);
         assert_eq!(sent.icon, Some("📤".to_string()));
 
-        let drafts = provider.mailbox_to_feed("Drafts".to_string(), 0, 0);
+        let drafts = provider.mailbox_to_feed(&provider.accounts[0], "Drafts".to_string(), 0, 0);
         assert_eq!(drafts.icon, Some("📝".to_string()));
 
-        let trash = provider.mailbox_to_feed("Trash".to_string(), 0, 0);
+        let trash = provider.mailbox_to_feed(&provider.accounts[0], "Trash".to_string(), 0, 0);
         assert_eq!(trash.icon, Some("🗑️".to_string()));
 
-        let custom = provider.mailbox_to_feed("CustomFolder".to_string(), 0, 0);
+        let custom =
+            provider.mailbox_to_feed(&provider.accounts[0], "CustomFolder".to_string(), 0, 0);
         assert_eq!(custom.icon, Some("📁".to_string()));
     }
+    #[test]
+    fn test_virtual_feed_scopes() {
+        let gmail = ImapConfig {
+            server: "imap.gmail.com".to_string(),
+            account_name: "personal".to_string(),
+            ..create_test_config()
+        };
+        let provider = ImapProvider::with_accounts(
+            vec![gmail, create_test_config()],
+            create_test_token_fetcher(),
+        );
+
+        let (criterion, scope) = provider.virtual_feed(STARRED_FEED).unwrap();
+        assert_eq!(criterion, "FLAGGED");
+        assert_eq!(scope.len(), 2);
+
+        // Only Gmail accounts have an Important mailbox
+        let (criterion, scope) = provider.virtual_feed(IMPORTANT_FEED).unwrap();
+        assert_eq!(criterion, "ALL");
+        assert_eq!(scope.len(), 1);
+        assert_eq!(scope[0].0.account_name, "personal");
+
+        assert!(provider.virtual_feed("INBOX").is_none());
+    }
+
+    #[test]
+    fn test_resolve_mailboxes() {
+        let account = ImapConfig {
+            mailboxes: vec!["INBOX".to_string(), "Receipts".to_string()],
+            ..create_test_config()
+        };
+        let list = vec![
+            ("INBOX".to_string(), vec![]),
+            ("Receipts".to_string(), vec![]),
+            ("[Gmail]/Starred".to_string(), vec!["\\Flagged".to_string()]),
+            (
+                "[Gmail]/Wichtig".to_string(),
+                vec!["\\Important".to_string()],
+            ),
+        ];
+
+        assert_eq!(
+            resolve_mailboxes(&account, &list, &Mailboxes::Listed),
+            vec!["INBOX", "Receipts"]
+        );
+        let starred = Mailboxes::SpecialUse {
+            attribute: "\\Flagged",
+            fallback: true,
+        };
+        assert_eq!(
+            resolve_mailboxes(&account, &list, &starred),
+            vec!["[Gmail]/Starred"]
+        );
+        let important = Mailboxes::SpecialUse {
+            attribute: "\\important",
+            fallback: false,
+        };
+        assert_eq!(
+            resolve_mailboxes(&account, &list, &important),
+            vec!["[Gmail]/Wichtig"]
+        );
+
+        // Without a special-use mailbox, starred falls back to every listed one
+        let plain = &list[..2];
+        assert_eq!(
+            resolve_mailboxes(&account, plain, &starred),
+            vec!["INBOX", "Receipts"]
+        );
+        assert!(resolve_mailboxes(&account, plain, &important).is_empty());
+    }
+
+    #[test]
+    fn test_special_use_attributes() {
+        assert_eq!(special_use(&NameAttribute::Flagged), Some("\\Flagged"));
+        assert_eq!(
+            special_use(&NameAttribute::Extension("\\Important".into())),
+            Some("\\Important")
+        );
+        assert_eq!(special_use(&NameAttribute::NoSelect), None);
+    }
+
+    #[test]
+    fn test_junk_mailbox() {
+        let list = |mailboxes: &[(&str, &[&str])]| -> Vec<(String, Vec<String>)> {
+            mailboxes
+                .iter()
+                .map(|(name, attributes)| {
+                    let attributes = attributes.iter().map(|a| a.to_string()).collect();
+                    (name.to_string(), attributes)
+                })
+                .collect()
+        };
+
+        let gmail = list(&[("INBOX", &[]), ("Spam", &[]), ("[Gmail]/Spam", &["\\Junk"])]);
+        assert_eq!(junk_mailbox(&gmail), Some("[Gmail]/Spam"));
+        let plain = list(&[("INBOX", &[]), ("junk", &[])]);
+        assert_eq!(junk_mailbox(&plain), Some("junk"));
+        assert_eq!(junk_mailbox(&list(&[("INBOX", &[])])), None);
+
+        assert!(is_junk_name("Junk E-mail"));
+        assert!(!is_junk_name("Junk/Old"));
+    }
+
+    #[test]
+    fn test_search_criteria() {
+        assert_eq!(
+            search_criteria(r#"from:alice subject:"lunch plans" since:2024-03-05 menu"#).unwrap(),
+            r#"FROM "alice" SUBJECT "lunch plans" SINCE 5-Mar-2024 TEXT "menu""#
+        );
+        assert_eq!(
+            search_criteria(r#"body:invoice "quarterly report" re:lunch"#).unwrap(),
+            r#"BODY "invoice" TEXT "quarterly report" TEXT "re:lunch""#
+        );
+        assert_eq!(
+            search_criteria(r"subject:C:\temp").unwrap(),
+            r#"SUBJECT "C:\\temp""#
+        );
+        assert_eq!(
+            search_criteria("from:zoë").unwrap(),
+            r#"CHARSET UTF-8 FROM "zoë""#
+        );
+        assert_eq!(search_criteria("   ").unwrap(), "ALL");
+        assert!(matches!(
+            search_criteria("since:yesterday"),
+            Err(StreamError::Provider(_))
+        ));
+    }
 }
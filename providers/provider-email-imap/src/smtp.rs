@@ -0,0 +1,216 @@
+//! Sending mail through the account's SMTP submission server.
+//!
+//! The provider only sends messages it writes itself, such as replies to
+//! calendar invitations, so this speaks just the part of SMTP (RFC 5321) a
+//! submission needs: EHLO, STARTTLS, AUTH PLAIN, MAIL, RCPT, and DATA.
+
+use async_native_tls::{TlsConnector, TlsStream};
+use async_std::net::TcpStream;
+use base64::Engine;
+use futures::io::{AsyncReadExt, AsyncWriteExt};
+use scryforge_provider_core::prelude::*;
+
+use crate::ImapStream;
+
+/// How the connection to the SMTP server is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// TLS from the start, usually on port 465
+    Tls,
+    /// Plain connection upgraded with `STARTTLS`, usually on port 587
+    StartTls,
+    /// No encryption; only for local bridges and tests
+    Plain,
+}
+
+/// SMTP server that sends mail for an IMAP account.
+///
+/// It authenticates with the account's IMAP username and password.
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    /// SMTP server hostname (e.g., "smtp.gmail.com")
+    pub server: String,
+    /// SMTP server port (typically 587 with STARTTLS, 465 with TLS)
+    pub port: u16,
+    pub security: SmtpSecurity,
+}
+
+/// Send `message`, a complete RFC 5322 message, from `from` to `recipients`.
+pub(crate) async fn send(
+    config: &SmtpConfig,
+    username: &str,
+    password: &str,
+    from: &str,
+    recipients: &[String],
+    message: &str,
+) -> Result<()> {
+    let mut connection = Connection::open(config).await?;
+    connection.expect(220).await?;
+
+    let domain = from
+        .rsplit_once('@')
+        .map_or("localhost", |(_, domain)| domain);
+    let mut extensions = connection.command(&format!("EHLO {}", domain), 250).await?;
+    if config.security == SmtpSecurity::StartTls {
+        connection.command("STARTTLS", 220).await?;
+        connection = connection.start_tls(&config.server).await?;
+        extensions = connection.command(&format!("EHLO {}", domain), 250).await?;
+    }
+
+    if extensions
+        .lines()
+        .any(|line| line.to_ascii_uppercase().starts_with("AUTH"))
+    {
+        let credentials = format!("\0{}\0{}", username, password);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        connection
+            .command(&format!("AUTH PLAIN {}", encoded), 235)
+            .await
+            .map_err(|e| StreamError::AuthRequired(format!("SMTP login failed: {}", e)))?;
+    }
+
+    connection
+        .command(&format!("MAIL FROM:<{}>", from), 250)
+        .await?;
+    for recipient in recipients {
+        connection
+            .command(&format!("RCPT TO:<{}>", recipient), 250)
+            .await?;
+    }
+    connection.command("DATA", 354).await?;
+    connection.write(&dot_stuff(message)).await?;
+    connection.command(".", 250).await?;
+    let _ = connection.command("QUIT", 221).await;
+    Ok(())
+}
+
+/// `message` with CRLF line endings and leading dots doubled, as DATA
+/// requires.
+fn dot_stuff(message: &str) -> String {
+    let mut data = String::with_capacity(message.len() + 64);
+    for line in message.lines() {
+        if line.starts_with('.') {
+            data.push('.');
+        }
+        data.push_str(line);
+        data.push_str("\r\n");
+    }
+    data
+}
+
+/// A connection to the SMTP server with the replies read so far.
+struct Connection {
+    stream: ImapStream,
+    buffer: Vec<u8>,
+}
+
+impl Connection {
+    async fn open(config: &SmtpConfig) -> Result<Self> {
+        let addr = format!("{}:{}", config.server, config.port);
+        let tcp_stream = TcpStream::connect(&addr)
+            .await
+            .map_err(|e| StreamError::Network(format!("Failed to connect to {}: {}", addr, e)))?;
+        let stream = match config.security {
+            SmtpSecurity::Tls => ImapStream::Tls(tls(&config.server, tcp_stream).await?),
+            SmtpSecurity::StartTls | SmtpSecurity::Plain => ImapStream::Plain(tcp_stream),
+        };
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Switch the connection to TLS after the server accepted `STARTTLS`.
+    async fn start_tls(self, server: &str) -> Result<Self> {
+        match self.stream {
+            ImapStream::Plain(tcp_stream) => Ok(Self {
+                stream: ImapStream::Tls(tls(server, tcp_stream).await?),
+                buffer: Vec::new(),
+            }),
+            ImapStream::Tls(_) => Ok(self),
+        }
+    }
+
+    async fn write(&mut self, data: &str) -> Result<()> {
+        self.stream
+            .write_all(data.as_bytes())
+            .await
+            .map_err(|e| StreamError::Network(format!("SMTP write failed: {}", e)))?;
+        self.stream
+            .flush()
+            .await
+            .map_err(|e| StreamError::Network(format!("SMTP write failed: {}", e)))
+    }
+
+    /// Send `line` and wait for a reply with code `code`, returning its text.
+    async fn command(&mut self, line: &str, code: u16) -> Result<String> {
+        self.write(&format!("{}\r\n", line)).await?;
+        self.expect(code).await
+    }
+
+    /// Read the next reply, which may span several lines, and fail unless it
+    /// has code `code`.
+    async fn expect(&mut self, code: u16) -> Result<String> {
+        let mut text = Vec::new();
+        loop {
+            let line = self.read_line().await?;
+            let reply: u16 = line
+                .get(..3)
+                .and_then(|c| c.parse().ok())
+                .ok_or_else(|| StreamError::Provider(format!("Malformed SMTP reply: {}", line)))?;
+            text.push(line.get(4..).unwrap_or_default().to_string());
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            if reply != code {
+                return Err(StreamError::Provider(format!(
+                    "SMTP server replied {} {}",
+                    reply,
+                    text.join(" ")
+                )));
+            }
+            return Ok(text.join("\n"));
+        }
+    }
+
+    async fn read_line(&mut self) -> Result<String> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                return Ok(String::from_utf8_lossy(&line).trim_end().to_string());
+            }
+            let mut chunk = [0u8; 1024];
+            let read = self
+                .stream
+                .read(&mut chunk)
+                .await
+                .map_err(|e| StreamError::Network(format!("SMTP read failed: {}", e)))?;
+            if read == 0 {
+                return Err(StreamError::Network(
+                    "SMTP server closed connection".to_string(),
+                ));
+            }
+            self.buffer.extend_from_slice(&chunk[..read]);
+        }
+    }
+}
+
+async fn tls(server: &str, tcp_stream: TcpStream) -> Result<TlsStream<TcpStream>> {
+    TlsConnector::new()
+        .connect(server, tcp_stream)
+        .await
+        .map_err(|e| StreamError::Network(format!("TLS connection failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_stuff() {
+        assert_eq!(
+            dot_stuff("Hello\n.hidden\r\n..\nend"),
+            "Hello\r\n..hidden\r\n...\r\nend\r\n"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Snoozed messages.
+//!
+//! A snooze hides a message until a chosen time. Where the server lets the
+//! provider create or find the [`SNOOZED_MAILBOX`], the message waits there
+//! and is moved back when it wakes; otherwise it stays where it is and is left
+//! out of listings until then. Either way the schedule is kept locally, in a
+//! [`SnoozeStore`] that can be saved as JSON so snoozes survive a restart.
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Mailbox snoozed messages wait in.
+pub const SNOOZED_MAILBOX: &str = "Snoozed";
+
+/// A message hidden until `until`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snooze {
+    /// ID of the snoozed item
+    pub item_id: ItemId,
+    /// Account the message belongs to
+    pub account: String,
+    /// Mailbox the message was snoozed from, and returns to
+    pub mailbox: String,
+    /// UID of the message in `mailbox` when it was snoozed
+    pub uid: String,
+    /// `Message-ID` header, to find the message again after a move
+    pub message_id: Option<String>,
+    /// When the message reappears
+    pub until: DateTime<Utc>,
+    /// Clear `\Seen` when the message reappears
+    pub mark_unread: bool,
+    /// Whether the message was moved to [`SNOOZED_MAILBOX`]
+    pub moved: bool,
+}
+
+/// The snooze schedule, optionally saved to a file.
+#[derive(Debug, Default)]
+pub(crate) struct SnoozeStore {
+    path: Option<PathBuf>,
+    snoozes: RwLock<Vec<Snooze>>,
+}
+
+impl SnoozeStore {
+    /// Load the schedule saved at `path`, or start an empty one there.
+    pub(crate) fn open(path: PathBuf) -> Result<Self> {
+        let snoozes = if path.exists() {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                StreamError::Internal(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                StreamError::Internal(format!("Invalid snooze file {}: {}", path.display(), e))
+            })?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            snoozes: RwLock::new(snoozes),
+        })
+    }
+
+    /// All snoozes, soonest first.
+    pub(crate) fn list(&self) -> Vec<Snooze> {
+        let mut snoozes = self.snoozes.read().unwrap().clone();
+        snoozes.sort_by_key(|snooze| snooze.until);
+        snoozes
+    }
+
+    /// Snoozes due at `now`.
+    pub(crate) fn due(&self, now: DateTime<Utc>) -> Vec<Snooze> {
+        self.list()
+            .into_iter()
+            .filter(|snooze| snooze.until <= now)
+            .collect()
+    }
+
+    /// Add a snooze, replacing any earlier one for the same item.
+    pub(crate) fn add(&self, snooze: Snooze) -> Result<()> {
+        let mut snoozes = self.snoozes.write().unwrap();
+        snoozes.retain(|s| !(s.item_id == snooze.item_id && s.account == snooze.account));
+        snoozes.push(snooze);
+        self.save(&snoozes)
+    }
+
+    /// Remove a snooze once it has woken.
+    pub(crate) fn remove(&self, snooze: &Snooze) -> Result<()> {
+        let mut snoozes = self.snoozes.write().unwrap();
+        snoozes.retain(|s| s != snooze);
+        self.save(&snoozes)
+    }
+
+    /// Whether `item` is snoozed in place and should be left out of listings.
+    pub(crate) fn hides(&self, item: &Item) -> bool {
+        let account = item.metadata.get("account");
+        let mailbox = item.metadata.get("mailbox");
+        self.snoozes.read().unwrap().iter().any(|snooze| {
+            !snooze.moved
+                && snooze.item_id == item.id
+                && account == Some(&snooze.account)
+                && mailbox == Some(&snooze.mailbox)
+        })
+    }
+
+    fn save(&self, snoozes: &[Snooze]) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents = serde_json::to_string_pretty(snoozes)
+            .map_err(|e| StreamError::Internal(format!("Failed to serialize snoozes: {}", e)))?;
+        std::fs::write(path, contents).map_err(|e| {
+            StreamError::Internal(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn snooze(id: &str, until: DateTime<Utc>) -> Snooze {
+        Snooze {
+            item_id: ItemId::new("email-imap", id),
+            account: "personal".to_string(),
+            mailbox: "INBOX".to_string(),
+            uid: "1".to_string(),
+            message_id: Some(id.to_string()),
+            until,
+            mark_unread: true,
+            moved: false,
+        }
+    }
+
+    #[test]
+    fn test_due_and_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snoozes.json");
+        let now = Utc::now();
+
+        let store = SnoozeStore::open(path.clone()).unwrap();
+        store
+            .add(snooze("<b@x>", now + Duration::hours(2)))
+            .unwrap();
+        store
+            .add(snooze("<a@x>", now + Duration::hours(1)))
+            .unwrap();
+        // Snoozing again moves the time rather than adding a second snooze
+        store
+            .add(snooze("<b@x>", now + Duration::hours(3)))
+            .unwrap();
+
+        let store = SnoozeStore::open(path).unwrap();
+        let ids: Vec<String> = store.list().into_iter().map(|s| s.item_id.0).collect();
+        assert_eq!(ids, vec!["email-imap:<a@x>", "email-imap:<b@x>"]);
+        assert!(store.due(now).is_empty());
+
+        let due = store.due(now + Duration::hours(2));
+        assert_eq!(due.len(), 1);
+        store.remove(&due[0]).unwrap();
+        assert_eq!(store.list().len(), 1);
+    }
+
+    #[test]
+    fn test_hides_only_snoozes_in_place() {
+        let store = SnoozeStore::default();
+        let mut moved = snooze("<moved@x>", Utc::now());
+        moved.moved = true;
+        store.add(moved).unwrap();
+        store.add(snooze("<kept@x>", Utc::now())).unwrap();
+
+        let item = |id: &str, mailbox: &str| Item {
+            id: ItemId::new("email-imap", id),
+            stream_id: StreamId::new("email-imap", "feed", "imap:INBOX"),
+            title: "Test".to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: [
+                ("account".to_string(), "personal".to_string()),
+                ("mailbox".to_string(), mailbox.to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        assert!(store.hides(&item("<kept@x>", "INBOX")));
+        assert!(!store.hides(&item("<kept@x>", "Archive")));
+        assert!(!store.hides(&item("<moved@x>", "Snoozed")));
+    }
+}
@@ -0,0 +1,972 @@
+//! Tests for `provider-email-imap` against an in-process IMAP server.
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_imap::extensions::idle::IdleResponse;
+use async_std::net::TcpStream;
+use provider_email_imap::{ImapConfig, ImapProvider, SmtpConfig, SmtpSecurity};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::CalendarInvite;
+use support::{
+    dated_message, invitation, message, message_with_attachment, ImapServer, SmtpServer, PASSWORD,
+    USERNAME,
+};
+
+fn provider_for(server: &ImapServer, password: &str) -> ImapProvider {
+    provider_with(server, password, false)
+}
+
+fn lazy_provider_for(server: &ImapServer) -> ImapProvider {
+    provider_with(server, PASSWORD, true)
+}
+
+fn provider_with(server: &ImapServer, password: &str, lazy_bodies: bool) -> ImapProvider {
+    let config = ImapConfig {
+        server: "127.0.0.1".to_string(),
+        port: server.port(),
+        username: USERNAME.to_string(),
+        account_name: "personal".to_string(),
+        use_tls: false,
+        lazy_bodies,
+        mailboxes: vec![],
+        smtp: None,
+    };
+    let tokens = MockTokenFetcher::empty().with_token(
+        "email-imap".to_string(),
+        "personal".to_string(),
+        password.to_string(),
+    );
+    ImapProvider::new(config, Arc::new(tokens))
+}
+
+fn account(server: &ImapServer, account_name: &str, mailboxes: &[&str]) -> ImapConfig {
+    ImapConfig {
+        server: "127.0.0.1".to_string(),
+        port: server.port(),
+        username: USERNAME.to_string(),
+        account_name: account_name.to_string(),
+        use_tls: false,
+        lazy_bodies: false,
+        mailboxes: mailboxes.iter().map(|m| m.to_string()).collect(),
+        smtp: None,
+    }
+}
+
+fn multi_account_provider(accounts: Vec<ImapConfig>) -> ImapProvider {
+    let mut tokens = MockTokenFetcher::empty();
+    for account in &accounts {
+        tokens = tokens.with_token(
+            "email-imap".to_string(),
+            account.account_name.clone(),
+            PASSWORD.to_string(),
+        );
+    }
+    ImapProvider::with_accounts(accounts, Arc::new(tokens))
+}
+
+fn inbox() -> FeedId {
+    FeedId("imap:INBOX".to_string())
+}
+
+fn all_items() -> FeedOptions {
+    FeedOptions {
+        include_read: true,
+        ..FeedOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn list_feeds_counts_unseen_messages() {
+    let server = ImapServer::start(&["INBOX", "Archive"]);
+    server.deliver("INBOX", &message("Old news", "Read already"), &["\\Seen"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+    server.deliver("INBOX", &message("Invoice", "Attached"), &[]);
+
+    let feeds = provider_for(&server, PASSWORD).list_feeds().await.unwrap();
+
+    assert_eq!(feeds.len(), 3);
+    assert_eq!(feeds[0].id, inbox());
+    assert_eq!(feeds[0].total_count, Some(3));
+    assert_eq!(feeds[0].unread_count, Some(2));
+    assert_eq!(feeds[1].name, "Archive");
+    assert_eq!(feeds[1].total_count, Some(0));
+    assert_eq!(feeds[2].id.0, provider_email_imap::STARRED_FEED);
+}
+
+#[tokio::test]
+async fn feed_items_carry_flags() {
+    let server = ImapServer::start(&["INBOX"]);
+    let read = server.deliver("INBOX", &message("Old news", "Read already"), &["\\Seen"]);
+    let starred = server.deliver("INBOX", &message("Lunch", "Noon?"), &["\\Flagged"]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let items = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap();
+
+    // Most recent first
+    let titles: Vec<&str> = items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Lunch", "Old news"]);
+    assert!(!items[0].is_read);
+    assert!(items[0].is_saved);
+    assert!(items[1].is_read);
+    assert_eq!(items[0].metadata.get("uid"), Some(&starred.to_string()));
+    assert!(matches!(
+        items[1].content,
+        ItemContent::Email { ref body_text, .. } if body_text.as_deref().map(str::trim) == Some("Read already")
+    ));
+
+    // Fetching must not mark messages read
+    assert_eq!(server.flags("INBOX", starred), vec!["\\Flagged"]);
+    assert_eq!(server.flags("INBOX", read), vec!["\\Seen"]);
+
+    let unread = provider
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(unread.len(), 1);
+    assert_eq!(unread[0].title, "Lunch");
+}
+
+#[tokio::test]
+async fn mark_read_and_unread_store_seen_flag() {
+    let server = ImapServer::start(&["INBOX"]);
+    let uid = server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let action = |kind: ActionKind| actions.iter().find(|a| a.kind == kind).unwrap();
+
+    let result = provider
+        .execute_action(&item, action(ActionKind::MarkRead))
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(server.flags("INBOX", uid), vec!["\\Seen"]);
+
+    provider
+        .execute_action(&item, action(ActionKind::MarkUnread))
+        .await
+        .unwrap();
+    assert!(server.flags("INBOX", uid).is_empty());
+}
+
+/// A session with INBOX selected, for commands the provider doesn't issue.
+async fn inbox_session(server: &ImapServer) -> async_imap::Session<TcpStream> {
+    let stream = TcpStream::connect(("127.0.0.1", server.port()))
+        .await
+        .unwrap();
+    let mut client = async_imap::Client::new(stream);
+    client.read_response().await.unwrap().unwrap();
+    let mut session = client
+        .login(USERNAME, PASSWORD)
+        .await
+        .map_err(|(e, _)| e)
+        .unwrap();
+    session.select("INBOX").await.unwrap();
+    session
+}
+
+#[tokio::test]
+async fn idle_reports_new_mail() {
+    let server = ImapServer::start(&["INBOX"]);
+    let mut idle = inbox_session(&server).await.idle();
+    idle.init().await.unwrap();
+    for _ in 0..200 {
+        if server.is_idling() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(server.is_idling());
+
+    // Dropping the stop source interrupts the wait, so keep it alive
+    let (wait, _stop) = idle.wait_with_timeout(Duration::from_secs(10));
+    server.deliver("INBOX", &message("Breaking", "New mail"), &[]);
+
+    assert!(matches!(wait.await.unwrap(), IdleResponse::NewData(_)));
+}
+
+#[tokio::test]
+async fn idle_times_out_without_changes() {
+    let server = ImapServer::start(&["INBOX"]);
+    let mut idle = inbox_session(&server).await.idle();
+    idle.init().await.unwrap();
+
+    let (wait, _stop) = idle.wait_with_timeout(Duration::from_millis(200));
+    assert!(matches!(wait.await.unwrap(), IdleResponse::Timeout));
+}
+
+#[tokio::test]
+async fn wrong_password_is_auth_error() {
+    let server = ImapServer::start(&["INBOX"]);
+
+    let result = provider_for(&server, "wrong").list_feeds().await;
+    assert!(matches!(result, Err(StreamError::AuthRequired(_))));
+
+    let health = provider_for(&server, "wrong").health_check().await.unwrap();
+    assert!(!health.is_healthy);
+}
+
+#[tokio::test]
+async fn unknown_mailbox_is_not_found() {
+    let server = ImapServer::start(&["INBOX"]);
+
+    let result = provider_for(&server, PASSWORD)
+        .get_feed_items(&FeedId("imap:Nope".to_string()), all_items())
+        .await;
+    assert!(matches!(result, Err(StreamError::StreamNotFound(_))));
+}
+
+#[tokio::test]
+async fn lazy_listing_skips_bodies() {
+    let server = ImapServer::start(&["INBOX"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &["\\Seen"]);
+    let uid = server.deliver(
+        "INBOX",
+        &message_with_attachment("Invoice", "See attached", "invoice.pdf"),
+        &[],
+    );
+
+    let items = lazy_provider_for(&server)
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Invoice", "Lunch"]);
+    assert_eq!(items[0].id.0, "email-imap:<invoice@example.com>");
+    assert_eq!(items[0].author.as_ref().unwrap().name, "Alice");
+    assert_eq!(
+        items[0].author.as_ref().unwrap().email.as_deref(),
+        Some("alice@example.com")
+    );
+    assert!(items[0].published.is_some());
+    assert!(!items[0].is_read);
+    assert!(items[1].is_read);
+    assert!(matches!(
+        items[0].content,
+        ItemContent::Email {
+            body_text: None,
+            body_html: None,
+            ..
+        }
+    ));
+    assert_eq!(items[0].metadata.get("uid"), Some(&uid.to_string()));
+    assert_eq!(items[0].metadata.get("attachments"), Some(&"1".to_string()));
+    assert!(items[0].metadata.contains_key("size"));
+    assert!(items[0]
+        .metadata
+        .contains_key(provider_email_imap::LAZY_BODY_KEY));
+    assert!(!items[1].metadata.contains_key("attachments"));
+}
+
+#[tokio::test]
+async fn low_bandwidth_listing_skips_bodies() {
+    let server = ImapServer::start(&["INBOX"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let options = FeedOptions {
+        low_bandwidth: true,
+        ..all_items()
+    };
+    let item = provider
+        .get_feed_items(&inbox(), options)
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(item.title, "Lunch");
+    assert!(matches!(
+        item.content,
+        ItemContent::Email {
+            body_text: None,
+            ..
+        }
+    ));
+    assert!(item
+        .metadata
+        .contains_key(provider_email_imap::LAZY_BODY_KEY));
+
+    let results = provider
+        .search(
+            "lunch",
+            SearchOptions {
+                low_bandwidth: true,
+                ..SearchOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(results[0]
+        .metadata
+        .contains_key(provider_email_imap::LAZY_BODY_KEY));
+}
+
+#[tokio::test]
+async fn preview_loads_lazy_body() {
+    let server = ImapServer::start(&["INBOX"]);
+    let uid = server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+
+    let provider = lazy_provider_for(&server);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let preview = actions
+        .iter()
+        .find(|a| a.kind == ActionKind::Preview)
+        .unwrap();
+
+    let result = provider.execute_action(&item, preview).await.unwrap();
+    let loaded: Item = serde_json::from_value(result.data.unwrap()).unwrap();
+
+    assert_eq!(loaded.id, item.id);
+    assert!(matches!(
+        loaded.content,
+        ItemContent::Email { ref body_text, .. } if body_text.as_deref().map(str::trim) == Some("Noon?")
+    ));
+    assert!(!loaded
+        .metadata
+        .contains_key(provider_email_imap::LAZY_BODY_KEY));
+    assert_eq!(loaded.metadata.get("size"), item.metadata.get("size"));
+
+    // Previewing peeks at the body without marking it read
+    assert!(server.flags("INBOX", uid).is_empty());
+}
+
+#[tokio::test]
+async fn search_runs_on_the_server() {
+    let server = ImapServer::start(&["INBOX", "Archive"]);
+    server.deliver(
+        "INBOX",
+        &dated_message(
+            "Lunch plans",
+            "Tacos at noon?",
+            "Fri, 1 Mar 2024 09:00:00 +0000",
+        ),
+        &["\\Seen"],
+    );
+    server.deliver(
+        "INBOX",
+        &dated_message(
+            "Invoice",
+            "Lunch receipt attached",
+            "Mon, 4 Mar 2024 09:00:00 +0000",
+        ),
+        &[],
+    );
+    server.deliver(
+        "Archive",
+        &dated_message(
+            "Old lunch",
+            "From last year",
+            "Fri, 1 Dec 2023 09:00:00 +0000",
+        ),
+        &[],
+    );
+
+    let provider = provider_for(&server, PASSWORD);
+    let search = |query: &'static str, stream: Option<&'static str>| {
+        let options = SearchOptions {
+            stream_id: stream.map(|s| StreamId(s.to_string())),
+            ..Default::default()
+        };
+        let provider = &provider;
+        async move {
+            provider
+                .search(query, options)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|item| item.title)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Every mailbox, most recent first, read or not
+    assert_eq!(
+        search("lunch", None).await,
+        vec!["Invoice", "Lunch plans", "Old lunch"]
+    );
+    assert_eq!(
+        search("subject:lunch", None).await,
+        vec!["Lunch plans", "Old lunch"]
+    );
+    assert_eq!(
+        search("body:receipt from:alice", None).await,
+        vec!["Invoice"]
+    );
+    assert_eq!(
+        search("lunch since:2024-01-01", None).await,
+        vec!["Invoice", "Lunch plans"]
+    );
+    assert_eq!(
+        search("lunch", Some("email-imap:feed:imap:Archive")).await,
+        vec!["Old lunch"]
+    );
+    assert!(search("from:bob", None).await.is_empty());
+
+    let items = provider
+        .search("lunch", SearchOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(items[0].metadata.get("search_rank"), Some(&"1".to_string()));
+    assert_eq!(
+        items[2].metadata.get("mailbox"),
+        Some(&"Archive".to_string())
+    );
+}
+
+#[tokio::test]
+async fn star_and_keywords_store_flags() {
+    let server = ImapServer::start(&["INBOX", "Archive"]);
+    let lunch = server.deliver("INBOX", &message("Lunch", "Noon?"), &["\\Seen"]);
+    server.deliver("INBOX", &message("Invoice", "Attached"), &[]);
+    server.deliver(
+        "Archive",
+        &message("Old lunch", "Last year"),
+        &["\\Flagged"],
+    );
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|item| item.title == "Lunch")
+        .unwrap();
+    let actions = provider.available_actions(&item).await.unwrap();
+    let star = actions.iter().find(|a| a.id == "star").unwrap();
+    assert!(actions.iter().all(|a| a.id != "remove_keyword"));
+
+    provider.execute_action(&item, star).await.unwrap();
+    provider.set_flag(&item, "$Work", true).await.unwrap();
+    assert_eq!(
+        server.flags("INBOX", lunch),
+        vec!["\\Seen", "\\Flagged", "$Work"]
+    );
+
+    // The starred feed gathers flagged messages from every mailbox
+    let starred = provider
+        .get_feed_items(
+            &FeedId(provider_email_imap::STARRED_FEED.to_string()),
+            all_items(),
+        )
+        .await
+        .unwrap();
+    let titles: Vec<&str> = starred.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Lunch", "Old lunch"]);
+    assert_eq!(starred[0].stream_id.as_str(), "email-imap:feed:starred");
+    assert_eq!(starred[0].tags, vec!["$Work"]);
+    assert!(starred[0].is_saved);
+
+    // Unread only by default
+    let unread = provider
+        .get_feed_items(
+            &FeedId(provider_email_imap::STARRED_FEED.to_string()),
+            FeedOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(unread.len(), 1);
+    assert_eq!(unread[0].title, "Old lunch");
+
+    let actions = provider.available_actions(&starred[0]).await.unwrap();
+    let unstar = actions.iter().find(|a| a.id == "unstar").unwrap();
+    let remove = actions.iter().find(|a| a.id == "remove_keyword").unwrap();
+    let result = provider.execute_action(&starred[0], remove).await.unwrap();
+    assert_eq!(
+        result.data.unwrap()["keywords"],
+        serde_json::json!(["$Work"])
+    );
+
+    provider.execute_action(&starred[0], unstar).await.unwrap();
+    provider
+        .set_flag(&starred[0], "$Work", false)
+        .await
+        .unwrap();
+    assert_eq!(server.flags("INBOX", lunch), vec!["\\Seen"]);
+
+    assert!(provider.set_flag(&item, "\\Recent", true).await.is_err());
+    assert!(provider.set_flag(&item, "two words", true).await.is_err());
+}
+
+#[tokio::test]
+async fn move_to_folder_uses_move() {
+    let server = ImapServer::start(&["INBOX", "Archive", "Receipts"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+    server.deliver("INBOX", &message("Invoice", "Attached"), &["\\Seen"]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(item.title, "Invoice");
+
+    // The action asks for a folder, offering all but the current one
+    let actions = provider.available_actions(&item).await.unwrap();
+    let action = actions.iter().find(|a| a.id == "move_to_folder").unwrap();
+    let result = provider.execute_action(&item, action).await.unwrap();
+    let data = result.data.unwrap();
+    assert_eq!(data["requires_input"], true);
+    assert_eq!(data["input_type"], "folder");
+    assert_eq!(data["folders"], serde_json::json!(["Archive", "Receipts"]));
+
+    provider.move_to_folder(&item, "Receipts").await.unwrap();
+    assert_eq!(server.subjects("INBOX"), vec!["Lunch"]);
+    assert_eq!(server.subjects("Receipts"), vec!["Invoice"]);
+
+    let moved = provider
+        .get_feed_items(&FeedId("imap:Receipts".to_string()), all_items())
+        .await
+        .unwrap();
+    assert!(moved[0].is_read);
+
+    assert!(provider.move_to_folder(&moved[0], "Nowhere").await.is_err());
+    assert_eq!(server.subjects("Receipts"), vec!["Invoice"]);
+}
+
+#[tokio::test]
+async fn move_to_folder_falls_back_to_copy_and_expunge() {
+    let server = ImapServer::start(&["INBOX", "Archive"]);
+    server.disable_move();
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+    server.deliver("INBOX", &message("Invoice", "Attached"), &["\\Flagged"]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+
+    provider.move_to_folder(&item, "Archive").await.unwrap();
+    assert_eq!(server.subjects("INBOX"), vec!["Lunch"]);
+    assert_eq!(server.subjects("Archive"), vec!["Invoice"]);
+
+    // The copy keeps its flags, not the \Deleted set on the original
+    let archived = provider
+        .get_feed_items(&FeedId("imap:Archive".to_string()), all_items())
+        .await
+        .unwrap();
+    assert!(archived[0].is_saved);
+    let uid: u32 = archived[0].metadata["uid"].parse().unwrap();
+    assert_eq!(server.flags("Archive", uid), vec!["\\Flagged"]);
+}
+
+#[tokio::test]
+async fn spam_moves_to_junk_and_back() {
+    let server = ImapServer::start(&["INBOX", "Spam"]);
+    server.deliver("INBOX", &message("Prize", "Click here"), &[]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let spam = actions.iter().find(|a| a.id == "mark_spam").unwrap();
+    assert!(actions.iter().all(|a| a.id != "not_spam"));
+
+    let result = provider.execute_action(&item, spam).await.unwrap();
+    assert_eq!(result.message.as_deref(), Some("Moved to Spam"));
+    assert!(server.subjects("INBOX").is_empty());
+    assert_eq!(server.subjects("Spam"), vec!["Prize"]);
+
+    let junk = provider
+        .get_feed_items(&FeedId("imap:Spam".to_string()), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(junk.tags, vec!["$Junk"]);
+    let actions = provider.available_actions(&junk).await.unwrap();
+    assert!(actions.iter().all(|a| a.id != "mark_spam"));
+
+    provider.mark_not_spam(&junk).await.unwrap();
+    assert_eq!(server.subjects("INBOX"), vec!["Prize"]);
+    assert!(server.subjects("Spam").is_empty());
+    let restored = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    assert_eq!(restored.tags, vec!["$NotJunk"]);
+
+    // Without a junk mailbox there is nowhere to move spam to
+    let bare = ImapServer::start(&["INBOX"]);
+    bare.deliver("INBOX", &message("Prize", "Click here"), &[]);
+    let provider = provider_for(&bare, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    assert!(provider.mark_spam(&item).await.is_err());
+    assert_eq!(bare.subjects("INBOX"), vec!["Prize"]);
+}
+
+#[tokio::test]
+async fn invitations_are_answered_over_smtp() {
+    let server = ImapServer::start(&["INBOX"]);
+    let uid = server.deliver("INBOX", &invitation("Planning"), &[]);
+
+    // Without an SMTP server the invitation shows but can't be answered
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    let invite = CalendarInvite::from_item(&item).unwrap();
+    assert!(invite.is_request());
+    assert_eq!(invite.summary, "Planning");
+    assert_eq!(invite.start.as_deref(), Some("2024-06-03 14:00 UTC"));
+    assert_eq!(
+        invite.organizer.as_deref(),
+        Some("Alice <alice@example.com>")
+    );
+    match &item.content {
+        ItemContent::Email { body_text, .. } => {
+            assert_eq!(
+                body_text.as_deref().map(str::trim),
+                Some("Join us to talk about Planning.")
+            );
+        }
+        other => panic!("expected an email, got {:?}", other),
+    }
+    let actions = provider.available_actions(&item).await.unwrap();
+    assert!(actions.iter().all(|a| a.id != "accept_invite"));
+    assert!(provider.respond_to_invite(&item, true).await.is_err());
+
+    let smtp = SmtpServer::start();
+    let provider = ImapProvider::new(
+        ImapConfig {
+            smtp: Some(SmtpConfig {
+                server: "127.0.0.1".to_string(),
+                port: smtp.port(),
+                security: SmtpSecurity::Plain,
+            }),
+            ..account(&server, "personal", &[])
+        },
+        Arc::new(MockTokenFetcher::empty().with_token(
+            "email-imap".to_string(),
+            "personal".to_string(),
+            PASSWORD.to_string(),
+        )),
+    );
+    let actions = provider.available_actions(&item).await.unwrap();
+    assert!(actions.iter().any(|a| a.id == "decline_invite"));
+    let accept = actions.iter().find(|a| a.id == "accept_invite").unwrap();
+
+    let result = provider.execute_action(&item, accept).await.unwrap();
+    assert_eq!(
+        result.message.as_deref(),
+        Some("Accepted; reply sent to alice@example.com")
+    );
+    let sent = smtp.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].from, USERNAME);
+    assert_eq!(sent[0].to, vec!["alice@example.com"]);
+    assert!(sent[0].data.contains("Subject: Accepted: Planning"));
+    assert!(sent[0].data.contains("In-Reply-To: <invite@example.com>"));
+    assert!(sent[0].data.contains("METHOD:REPLY"));
+    assert!(sent[0]
+        .data
+        .contains("ATTENDEE;PARTSTAT=ACCEPTED:mailto:user@example.com"));
+    assert!(server
+        .flags("INBOX", uid)
+        .contains(&"\\Answered".to_string()));
+}
+
+#[tokio::test]
+async fn feed_counts_are_cached_between_syncs() {
+    let server = ImapServer::start(&["INBOX"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+
+    let provider = provider_for(&server, PASSWORD);
+    let counts = |feeds: Vec<Feed>| (feeds[0].unread_count, feeds[0].total_count);
+    assert_eq!(
+        counts(provider.list_feeds().await.unwrap()),
+        (Some(1), Some(1))
+    );
+
+    // New mail shows up at the next sync, not at every listing
+    server.deliver("INBOX", &message("Invoice", "Attached"), &[]);
+    assert_eq!(
+        counts(provider.list_feeds().await.unwrap()),
+        (Some(1), Some(1))
+    );
+    assert!(provider.sync().await.unwrap().success);
+    assert_eq!(
+        counts(provider.list_feeds().await.unwrap()),
+        (Some(2), Some(2))
+    );
+
+    // Changes made through the provider are counted right away
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let mark_read = actions
+        .iter()
+        .find(|a| a.kind == ActionKind::MarkRead)
+        .unwrap();
+    provider.execute_action(&item, mark_read).await.unwrap();
+    assert_eq!(
+        counts(provider.list_feeds().await.unwrap()),
+        (Some(1), Some(2))
+    );
+}
+
+#[tokio::test]
+async fn accounts_share_one_provider() {
+    let personal = ImapServer::start(&["INBOX", "Archive"]);
+    let work = ImapServer::start(&["INBOX", "Spam"]);
+    personal.deliver(
+        "INBOX",
+        &dated_message("Lunch", "Noon?", "Fri, 1 Mar 2024 09:00:00 +0000"),
+        &[],
+    );
+    personal.deliver("Archive", &message("Old lunch", "Last year"), &["\\Seen"]);
+    let standup = work.deliver(
+        "INBOX",
+        &dated_message("Standup", "Moved to 10", "Mon, 4 Mar 2024 09:00:00 +0000"),
+        &[],
+    );
+    work.deliver("INBOX", &message("Offsite", "Agenda"), &["\\Seen"]);
+    work.deliver("Spam", &message("Prize", "Click here"), &[]);
+
+    let provider = multi_account_provider(vec![
+        account(&personal, "personal", &[]),
+        account(&work, "work", &["INBOX"]),
+    ]);
+
+    // Work lists only its INBOX; All Inboxes adds up both INBOX counts
+    let feeds = provider.list_feeds().await.unwrap();
+    let ids: Vec<&str> = feeds.iter().map(|f| f.id.0.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            provider_email_imap::ALL_INBOXES_FEED,
+            "imap:personal:INBOX",
+            "imap:personal:Archive",
+            "imap:work:INBOX",
+            provider_email_imap::STARRED_FEED,
+        ]
+    );
+    assert_eq!(feeds[1].name, "INBOX (personal)");
+    assert_eq!(feeds[0].unread_count, Some(2));
+    assert_eq!(feeds[0].total_count, Some(3));
+
+    let unread = provider
+        .get_feed_items(
+            &FeedId(provider_email_imap::ALL_INBOXES_FEED.to_string()),
+            FeedOptions::default(),
+        )
+        .await
+        .unwrap();
+    let titles: Vec<&str> = unread.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Standup", "Lunch"]);
+    assert_eq!(unread[0].metadata.get("account"), Some(&"work".to_string()));
+    assert_eq!(unread[0].stream_id.as_str(), "email-imap:feed:all_inboxes");
+
+    // Actions reach the server of the item's account
+    let actions = provider.available_actions(&unread[0]).await.unwrap();
+    let mark_read = actions
+        .iter()
+        .find(|a| a.kind == ActionKind::MarkRead)
+        .unwrap();
+    provider
+        .execute_action(&unread[0], mark_read)
+        .await
+        .unwrap();
+    assert_eq!(work.flags("INBOX", standup), vec!["\\Seen"]);
+
+    let work_inbox = provider
+        .get_feed_items(&FeedId("imap:work:INBOX".to_string()), all_items())
+        .await
+        .unwrap();
+    assert_eq!(work_inbox.len(), 2);
+
+    // Search skips mailboxes an account doesn't list
+    let found = provider
+        .search("lunch prize", SearchOptions::default())
+        .await
+        .unwrap();
+    assert!(found.is_empty());
+    let found = provider
+        .search("lunch", SearchOptions::default())
+        .await
+        .unwrap();
+    let titles: Vec<&str> = found.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Lunch", "Old lunch"]);
+
+    assert!(provider
+        .get_feed_items(&FeedId("imap:school:INBOX".to_string()), all_items())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn unreachable_account_does_not_hide_the_others() {
+    let personal = ImapServer::start(&["INBOX"]);
+    let work = ImapServer::start(&["INBOX"]);
+    personal.deliver("INBOX", &message("Lunch", "Noon?"), &[]);
+    let mut broken = account(&work, "work", &[]);
+    broken.username = "nobody@example.com".to_string();
+
+    let provider = multi_account_provider(vec![account(&personal, "personal", &[]), broken]);
+
+    let feeds = provider.list_feeds().await.unwrap();
+    assert!(feeds.iter().any(|f| f.id.0 == "imap:personal:INBOX"));
+    assert!(!feeds.iter().any(|f| f.id.0 == "imap:work:INBOX"));
+
+    let items = provider
+        .get_feed_items(
+            &FeedId(provider_email_imap::ALL_INBOXES_FEED.to_string()),
+            all_items(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+
+    let health = provider.health_check().await.unwrap();
+    assert!(!health.is_healthy);
+    assert!(health.message.unwrap().contains("work:"));
+}
+
+#[tokio::test]
+async fn snooze_moves_message_until_it_wakes() {
+    let server = ImapServer::start(&["INBOX"]);
+    server.deliver("INBOX", &message("Lunch", "Noon?"), &["\\Seen"]);
+    server.deliver("INBOX", &message("Invoice", "Attached"), &[]);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("snoozes.json");
+
+    let provider = provider_for(&server, PASSWORD)
+        .with_snooze_path(path.clone())
+        .unwrap();
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|item| item.title == "Lunch")
+        .unwrap();
+
+    // The action offers preset times, then the client calls `snooze`
+    let actions = provider.available_actions(&item).await.unwrap();
+    let action = actions.iter().find(|a| a.id == "snooze").unwrap();
+    let data = provider
+        .execute_action(&item, action)
+        .await
+        .unwrap()
+        .data
+        .unwrap();
+    assert_eq!(data["input_type"], "datetime");
+    assert_eq!(data["options"].as_array().unwrap().len(), 3);
+
+    let now = chrono::Utc::now();
+    assert!(provider
+        .snooze(&item, now - chrono::Duration::minutes(1), true)
+        .await
+        .is_err());
+    provider
+        .snooze(&item, now + chrono::Duration::hours(1), true)
+        .await
+        .unwrap();
+    assert_eq!(server.subjects("INBOX"), vec!["Invoice"]);
+    assert_eq!(
+        server.subjects(provider_email_imap::SNOOZED_MAILBOX),
+        vec!["Lunch"]
+    );
+    assert!(provider.snoozed()[0].moved);
+
+    // Not due yet, even after a restart
+    let provider = provider_for(&server, PASSWORD)
+        .with_snooze_path(path)
+        .unwrap();
+    assert_eq!(provider.wake_snoozed(now).await.unwrap(), 0);
+    assert_eq!(provider.snoozed().len(), 1);
+
+    assert_eq!(
+        provider
+            .wake_snoozed(now + chrono::Duration::hours(2))
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(provider.snoozed().is_empty());
+    assert!(server
+        .subjects(provider_email_imap::SNOOZED_MAILBOX)
+        .is_empty());
+    let woken = provider
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap();
+    let titles: Vec<&str> = woken.iter().map(|i| i.title.as_str()).collect();
+    assert_eq!(titles, vec!["Lunch", "Invoice"]);
+}
+
+#[tokio::test]
+async fn snooze_without_message_id_hides_in_place() {
+    let server = ImapServer::start(&["INBOX"]);
+    let uid = server.deliver(
+        "INBOX",
+        "From: alice@example.com\nSubject: No ID\nDate: Mon, 1 Jan 2024 12:00:00 +0000\n\nHi\n",
+        &["\\Seen"],
+    );
+
+    let provider = provider_for(&server, PASSWORD);
+    let item = provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .remove(0);
+
+    let now = chrono::Utc::now();
+    provider
+        .snooze(&item, now + chrono::Duration::hours(1), true)
+        .await
+        .unwrap();
+    assert!(!provider.snoozed()[0].moved);
+    assert_eq!(server.subjects("INBOX"), vec!["No ID"]);
+    assert!(provider
+        .get_feed_items(&inbox(), all_items())
+        .await
+        .unwrap()
+        .is_empty());
+
+    provider
+        .wake_snoozed(now + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert!(server.flags("INBOX", uid).is_empty());
+    assert_eq!(
+        provider
+            .get_feed_items(&inbox(), all_items())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,813 @@
+//! A scripted, in-process IMAP server for provider tests.
+//!
+//! [`ImapServer`] speaks just enough IMAP4rev1 to test the provider against:
+//! LOGIN, LIST, STATUS, SELECT, CREATE, UID SEARCH, UID FETCH,
+//! UID STORE, UID MOVE, UID COPY, EXPUNGE, IDLE, and LOGOUT. SEARCH
+//! understands the criteria the provider sends: ALL, UNSEEN, FLAGGED, FROM,
+//! SUBJECT, BODY, TEXT, HEADER, and SINCE. Each connection is served on its own thread over plain
+//! TCP, against mailboxes the test fills with [`ImapServer::deliver`].
+//! Header-only fetches get an ENVELOPE and BODYSTRUCTURE built from the raw
+//! message.
+//!
+//! [`SmtpServer`] stands in for the account's SMTP server, recording the
+//! messages the provider sends.
+
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+mod smtp;
+
+pub use smtp::SmtpServer;
+
+pub const USERNAME: &str = "user@example.com";
+pub const PASSWORD: &str = "secret";
+
+struct Message {
+    uid: u32,
+    flags: Vec<String>,
+    raw: String,
+}
+
+struct Mailbox {
+    name: String,
+    messages: Vec<Message>,
+}
+
+#[derive(Default)]
+struct State {
+    mailboxes: Vec<Mailbox>,
+    next_uid: u32,
+    idling: usize,
+    supports_move: bool,
+}
+
+impl State {
+    fn mailbox(&self, name: &str) -> Option<&Mailbox> {
+        self.mailboxes.iter().find(|m| m.name == name)
+    }
+
+    fn mailbox_mut(&mut self, name: &str) -> Option<&mut Mailbox> {
+        self.mailboxes.iter_mut().find(|m| m.name == name)
+    }
+}
+
+/// An IMAP server on a random local port.
+pub struct ImapServer {
+    port: u16,
+    state: Arc<Mutex<State>>,
+}
+
+impl ImapServer {
+    /// Start a server with the given mailboxes, all empty.
+    pub fn start(mailboxes: &[&str]) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind IMAP stub");
+        let port = listener.local_addr().unwrap().port();
+        let state = Arc::new(Mutex::new(State {
+            mailboxes: mailboxes
+                .iter()
+                .map(|name| Mailbox {
+                    name: name.to_string(),
+                    messages: Vec::new(),
+                })
+                .collect(),
+            next_uid: 1,
+            idling: 0,
+            supports_move: true,
+        }));
+
+        let shared = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let _ = Session::new(stream, state).and_then(|mut s| s.serve());
+                });
+            }
+        });
+
+        Self { port, state }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Stop advertising the MOVE extension, as older servers do.
+    pub fn disable_move(&self) {
+        self.state.lock().unwrap().supports_move = false;
+    }
+
+    /// Add a message to `mailbox`, returning its UID.
+    pub fn deliver(&self, mailbox: &str, raw: &str, flags: &[&str]) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        let uid = state.next_uid;
+        state.next_uid += 1;
+        state
+            .mailbox_mut(mailbox)
+            .expect("unknown mailbox")
+            .messages
+            .push(Message {
+                uid,
+                flags: flags.iter().map(|f| f.to_string()).collect(),
+                raw: raw.replace('\n', "\r\n"),
+            });
+        uid
+    }
+
+    /// Flags of the message with `uid` in `mailbox`.
+    pub fn flags(&self, mailbox: &str, uid: u32) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .mailbox(mailbox)
+            .and_then(|m| m.messages.iter().find(|msg| msg.uid == uid))
+            .map(|msg| msg.flags.clone())
+            .expect("unknown message")
+    }
+
+    /// Subjects of the messages in `mailbox`, in order.
+    pub fn subjects(&self, mailbox: &str) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .mailbox(mailbox)
+            .expect("unknown mailbox")
+            .messages
+            .iter()
+            .map(|msg| {
+                let parsed = mailparse::parse_mail(msg.raw.as_bytes()).unwrap();
+                parsed
+                    .headers
+                    .iter()
+                    .find(|h| h.get_key().eq_ignore_ascii_case("Subject"))
+                    .map(|h| h.get_value())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Whether a client is currently in IDLE.
+    pub fn is_idling(&self) -> bool {
+        self.state.lock().unwrap().idling > 0
+    }
+}
+
+/// A simple RFC 822 message.
+pub fn message(subject: &str, body: &str) -> String {
+    dated_message(subject, body, "Mon, 1 Jan 2024 12:00:00 +0000")
+}
+
+/// A simple RFC 822 message sent at `date`, an RFC 2822 date.
+pub fn dated_message(subject: &str, body: &str, date: &str) -> String {
+    format!(
+        "From: Alice <alice@example.com>\n\
+         To: user@example.com\n\
+         Subject: {}\n\
+         Date: {}\n\
+         Message-ID: <{}@example.com>\n\
+         Content-Type: text/plain\n\
+         \n\
+         {}\n",
+        subject,
+        date,
+        subject.to_lowercase().replace(' ', "-"),
+        body
+    )
+}
+
+/// A multipart message from Alice with a PDF attachment.
+pub fn message_with_attachment(subject: &str, body: &str, filename: &str) -> String {
+    format!(
+        "From: Alice <alice@example.com>\n\
+         To: user@example.com\n\
+         Subject: {subject}\n\
+         Date: Mon, 1 Jan 2024 13:00:00 +0000\n\
+         Message-ID: <{id}@example.com>\n\
+         MIME-Version: 1.0\n\
+         Content-Type: multipart/mixed; boundary=\"sep\"\n\
+         \n\
+         --sep\n\
+         Content-Type: text/plain\n\
+         \n\
+         {body}\n\
+         --sep\n\
+         Content-Type: application/pdf\n\
+         Content-Disposition: attachment; filename=\"{filename}\"\n\
+         Content-Transfer-Encoding: base64\n\
+         \n\
+         JVBERi0xLjQK\n\
+         --sep--\n",
+        id = subject.to_lowercase().replace(' ', "-"),
+    )
+}
+
+/// A meeting invitation from Alice: a text part and a `text/calendar`
+/// REQUEST inviting [`USERNAME`].
+pub fn invitation(subject: &str) -> String {
+    format!(
+        "From: Alice <alice@example.com>\n\
+         To: user@example.com\n\
+         Subject: Invitation: {subject}\n\
+         Date: Mon, 1 Jan 2024 14:00:00 +0000\n\
+         Message-ID: <invite@example.com>\n\
+         MIME-Version: 1.0\n\
+         Content-Type: multipart/alternative; boundary=\"sep\"\n\
+         \n\
+         --sep\n\
+         Content-Type: text/plain\n\
+         \n\
+         Join us to talk about {subject}.\n\
+         --sep\n\
+         Content-Type: text/calendar; method=REQUEST\n\
+         \n\
+         BEGIN:VCALENDAR\n\
+         VERSION:2.0\n\
+         METHOD:REQUEST\n\
+         BEGIN:VEVENT\n\
+         UID:{subject}@example.com\n\
+         DTSTART:20240603T140000Z\n\
+         DTEND:20240603T150000Z\n\
+         SUMMARY:{subject}\n\
+         ORGANIZER;CN=Alice:mailto:alice@example.com\n\
+         ATTENDEE;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:user@example.com\n\
+         END:VEVENT\n\
+         END:VCALENDAR\n\
+         --sep--\n",
+    )
+}
+
+struct Session {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    state: Arc<Mutex<State>>,
+    selected: Option<String>,
+    selected_count: usize,
+}
+
+impl Session {
+    fn new(stream: TcpStream, state: Arc<Mutex<State>>) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            state,
+            selected: None,
+            selected_count: 0,
+        })
+    }
+
+    fn send(&mut self, line: &str) -> io::Result<()> {
+        self.writer.write_all(format!("{}\r\n", line).as_bytes())
+    }
+
+    fn serve(&mut self) -> io::Result<()> {
+        self.send(&format!(
+            "* OK [CAPABILITY {}] IMAP stub ready",
+            self.capabilities()
+        ))?;
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            let line = line.trim_end();
+            let (tag, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let (command, args) = rest.split_once(' ').unwrap_or((rest, ""));
+
+            match command.to_uppercase().as_str() {
+                "CAPABILITY" => {
+                    self.send(&format!("* CAPABILITY {}", self.capabilities()))?;
+                    self.send(&format!("{} OK CAPABILITY completed", tag))?;
+                }
+                "LOGIN" => {
+                    let args = atoms(args);
+                    if args.len() == 2 && args[0] == USERNAME && args[1] == PASSWORD {
+                        self.send(&format!("{} OK LOGIN completed", tag))?;
+                    } else {
+                        self.send(&format!(
+                            "{} NO [AUTHENTICATIONFAILED] Invalid credentials",
+                            tag
+                        ))?;
+                    }
+                }
+                "LIST" => {
+                    let names: Vec<String> = {
+                        let state = self.state.lock().unwrap();
+                        state.mailboxes.iter().map(|m| m.name.clone()).collect()
+                    };
+                    for name in names {
+                        self.send(&format!("* LIST (\\HasNoChildren) \"/\" \"{}\"", name))?;
+                    }
+                    self.send(&format!("{} OK LIST completed", tag))?;
+                }
+                "STATUS" => {
+                    let name = atoms(args).into_iter().next().unwrap_or_default();
+                    let counts = self.state.lock().unwrap().mailbox(&name).map(|m| {
+                        let unseen = m.messages.iter().filter(|msg| !is_seen(msg)).count();
+                        (m.messages.len(), unseen)
+                    });
+                    match counts {
+                        Some((messages, unseen)) => {
+                            self.send(&format!(
+                                "* STATUS \"{}\" (MESSAGES {} UNSEEN {})",
+                                name, messages, unseen
+                            ))?;
+                            self.send(&format!("{} OK STATUS completed", tag))?;
+                        }
+                        None => self.send(&format!("{} NO No such mailbox", tag))?,
+                    }
+                }
+                "SELECT" => {
+                    let name = atoms(args).into_iter().next().unwrap_or_default();
+                    let count = self.message_count(&name);
+                    match count {
+                        Some(count) => {
+                            self.selected = Some(name);
+                            self.selected_count = count;
+                            self.send("* FLAGS (\\Seen \\Flagged \\Deleted)")?;
+                            self.send(&format!("* {} EXISTS", count))?;
+                            self.send("* 0 RECENT")?;
+                            self.send("* OK [UIDVALIDITY 1] UIDs valid")?;
+                            self.send(&format!("{} OK [READ-WRITE] SELECT completed", tag))?;
+                        }
+                        None => self.send(&format!("{} NO No such mailbox", tag))?,
+                    }
+                }
+                "CREATE" => {
+                    let name = atoms(args).into_iter().next().unwrap_or_default();
+                    let mut state = self.state.lock().unwrap();
+                    if state.mailbox(&name).is_some() {
+                        drop(state);
+                        self.send(&format!("{} NO [ALREADYEXISTS] Mailbox exists", tag))?;
+                    } else {
+                        state.mailboxes.push(Mailbox {
+                            name,
+                            messages: Vec::new(),
+                        });
+                        drop(state);
+                        self.send(&format!("{} OK CREATE completed", tag))?;
+                    }
+                }
+                "UID" => self.uid_command(tag, args)?,
+                "EXPUNGE" => self.expunge(tag)?,
+                "IDLE" => self.idle(tag)?,
+                "LOGOUT" => {
+                    self.send("* BYE Logging out")?;
+                    self.send(&format!("{} OK LOGOUT completed", tag))?;
+                    return Ok(());
+                }
+                _ => self.send(&format!("{} BAD Unknown command", tag))?,
+            }
+        }
+    }
+
+    fn capabilities(&self) -> &'static str {
+        if self.state.lock().unwrap().supports_move {
+            "IMAP4rev1 IDLE MOVE"
+        } else {
+            "IMAP4rev1 IDLE"
+        }
+    }
+
+    /// Remove messages flagged \Deleted from the selected mailbox.
+    fn expunge(&mut self, tag: &str) -> io::Result<()> {
+        let Some(mailbox) = self.selected.clone() else {
+            return self.send(&format!("{} BAD No mailbox selected", tag));
+        };
+        let mut responses = Vec::new();
+        {
+            let mut state = self.state.lock().unwrap();
+            let messages = &mut state.mailbox_mut(&mailbox).unwrap().messages;
+            let mut seq = 1;
+            messages.retain(|msg| {
+                let deleted = msg.flags.iter().any(|f| f == "\\Deleted");
+                if deleted {
+                    responses.push(format!("* {} EXPUNGE", seq));
+                } else {
+                    seq += 1;
+                }
+                !deleted
+            });
+            self.selected_count = messages.len();
+        }
+
+        for response in responses {
+            self.send(&response)?;
+        }
+        self.send(&format!("{} OK EXPUNGE completed", tag))
+    }
+
+    fn message_count(&self, mailbox: &str) -> Option<usize> {
+        let state = self.state.lock().unwrap();
+        state.mailbox(mailbox).map(|m| m.messages.len())
+    }
+
+    fn uid_command(&mut self, tag: &str, args: &str) -> io::Result<()> {
+        let Some(mailbox) = self.selected.clone() else {
+            return self.send(&format!("{} BAD No mailbox selected", tag));
+        };
+        let (command, args) = args.split_once(' ').unwrap_or((args, ""));
+        let mut responses = Vec::new();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            let command = command.to_uppercase();
+            if command == "MOVE" || command == "COPY" {
+                if command == "MOVE" && !state.supports_move {
+                    drop(state);
+                    return self.send(&format!("{} BAD Unknown UID command", tag));
+                }
+                let (set, target) = args.split_once(' ').unwrap_or((args, ""));
+                let set = uid_set(set);
+                let target = atoms(target).into_iter().next().unwrap_or_default();
+                if state.mailbox(&target).is_none() {
+                    drop(state);
+                    return self.send(&format!("{} NO [TRYCREATE] No such mailbox", tag));
+                }
+
+                let source = &mut state.mailbox_mut(&mailbox).unwrap().messages;
+                let mut selected = Vec::new();
+                let mut seq = 1;
+                source.retain(|msg| {
+                    if set.contains(&msg.uid) {
+                        selected.push((msg.flags.clone(), msg.raw.clone()));
+                        if command == "MOVE" {
+                            responses.push(format!("* {} EXPUNGE", seq));
+                            return false;
+                        }
+                    }
+                    seq += 1;
+                    true
+                });
+                if command == "MOVE" {
+                    self.selected_count = source.len();
+                }
+                for (flags, raw) in selected {
+                    let uid = state.next_uid;
+                    state.next_uid += 1;
+                    state
+                        .mailbox_mut(&target)
+                        .unwrap()
+                        .messages
+                        .push(Message { uid, flags, raw });
+                }
+            }
+            let messages = &mut state.mailbox_mut(&mailbox).unwrap().messages;
+
+            match command.as_str() {
+                "MOVE" | "COPY" => {}
+                "SEARCH" => {
+                    let criteria = atoms(args);
+                    let uids: Vec<String> = messages
+                        .iter()
+                        .filter(|msg| matches_search(msg, &criteria))
+                        .map(|msg| msg.uid.to_string())
+                        .collect();
+                    responses.push(format!("* SEARCH {}", uids.join(" ")).trim().to_string());
+                }
+                "FETCH" => {
+                    let (set, query) = args.split_once(' ').unwrap_or((args, ""));
+                    let query = query.to_uppercase();
+                    // RFC822 and BODY[] set \Seen, where BODY.PEEK[] leaves it alone
+                    let rfc822 = query
+                        .split(|c: char| c == ' ' || c == '(' || c == ')')
+                        .any(|item| item == "RFC822");
+                    let with_body =
+                        rfc822 || query.contains("BODY[]") || query.contains("BODY.PEEK[]");
+                    let marks_seen = with_body && !query.contains("BODY.PEEK[]");
+                    for (i, msg) in messages.iter_mut().enumerate() {
+                        if !uid_set(set).contains(&msg.uid) {
+                            continue;
+                        }
+                        if marks_seen && !is_seen(msg) {
+                            msg.flags.push("\\Seen".to_string());
+                        }
+                        let mut response = format!(
+                            "* {} FETCH (UID {} FLAGS ({})",
+                            i + 1,
+                            msg.uid,
+                            msg.flags.join(" ")
+                        );
+                        if query.contains("RFC822.SIZE") {
+                            response.push_str(&format!(" RFC822.SIZE {}", msg.raw.len()));
+                        }
+                        if query.contains("ENVELOPE") {
+                            response.push_str(&format!(" ENVELOPE {}", envelope(&msg.raw)));
+                        }
+                        if query.contains("BODYSTRUCTURE") {
+                            let parsed = mailparse::parse_mail(msg.raw.as_bytes()).unwrap();
+                            response
+                                .push_str(&format!(" BODYSTRUCTURE {}", body_structure(&parsed)));
+                        }
+                        if with_body {
+                            response.push_str(&format!(
+                                " {} {{{}}}\r\n{}",
+                                if rfc822 { "RFC822" } else { "BODY[]" },
+                                msg.raw.len(),
+                                msg.raw
+                            ));
+                        }
+                        response.push(')');
+                        responses.push(response);
+                    }
+                }
+                "STORE" => {
+                    let mut parts = args.splitn(3, ' ');
+                    let set = uid_set(parts.next().unwrap_or_default());
+                    let operation = parts.next().unwrap_or_default().to_uppercase();
+                    let silent = operation.ends_with(".SILENT");
+                    let operation = operation.trim_end_matches(".SILENT");
+                    let flags: Vec<String> = parts
+                        .next()
+                        .unwrap_or_default()
+                        .trim_matches(|c| c == '(' || c == ')')
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect();
+
+                    for (i, msg) in messages.iter_mut().enumerate() {
+                        if !set.contains(&msg.uid) {
+                            continue;
+                        }
+                        match operation {
+                            "+FLAGS" => {
+                                for flag in &flags {
+                                    if !msg.flags.contains(flag) {
+                                        msg.flags.push(flag.clone());
+                                    }
+                                }
+                            }
+                            "-FLAGS" => msg.flags.retain(|f| !flags.contains(f)),
+                            _ => msg.flags = flags.clone(),
+                        }
+                        if silent {
+                            continue;
+                        }
+                        responses.push(format!(
+                            "* {} FETCH (UID {} FLAGS ({}))",
+                            i + 1,
+                            msg.uid,
+                            msg.flags.join(" ")
+                        ));
+                    }
+                }
+                _ => {
+                    drop(state);
+                    return self.send(&format!("{} BAD Unknown UID command", tag));
+                }
+            }
+        }
+
+        for response in responses {
+            self.send(&response)?;
+        }
+        self.send(&format!("{} OK UID {} completed", tag, command))
+    }
+
+    /// Report new messages in the selected mailbox until the client sends
+    /// DONE.
+    fn idle(&mut self, tag: &str) -> io::Result<()> {
+        let Some(mailbox) = self.selected.clone() else {
+            return self.send(&format!("{} BAD No mailbox selected", tag));
+        };
+        self.send("+ idling")?;
+        self.state.lock().unwrap().idling += 1;
+        self.writer
+            .set_read_timeout(Some(Duration::from_millis(20)))?;
+
+        let mut line = String::new();
+        let result = loop {
+            match self.reader.read_line(&mut line) {
+                Ok(0) => break Ok(()),
+                Ok(_) if line.ends_with('\n') => {
+                    if line.trim().eq_ignore_ascii_case("DONE") {
+                        break self.send(&format!("{} OK IDLE terminated", tag));
+                    }
+                    line.clear();
+                }
+                Ok(_) => {}
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    let count = self.message_count(&mailbox).unwrap_or(0);
+                    if count != self.selected_count {
+                        self.selected_count = count;
+                        if let Err(e) = self.send(&format!("* {} EXISTS", count)) {
+                            break Err(e);
+                        }
+                    }
+                }
+                Err(e) => break Err(e),
+            }
+        };
+
+        self.state.lock().unwrap().idling -= 1;
+        self.writer.set_read_timeout(None)?;
+        result
+    }
+}
+
+fn is_seen(message: &Message) -> bool {
+    message.flags.iter().any(|f| f == "\\Seen")
+}
+
+/// Whether a message matches SEARCH criteria, all of which must hold.
+/// String criteria match case-insensitively, as on real servers.
+fn matches_search(message: &Message, criteria: &[String]) -> bool {
+    let parsed = mailparse::parse_mail(message.raw.as_bytes()).unwrap();
+    let header = |name: &str| {
+        parsed
+            .headers
+            .iter()
+            .find(|h| h.get_key().eq_ignore_ascii_case(name))
+            .map(|h| h.get_value())
+            .unwrap_or_default()
+    };
+
+    let mut criteria = criteria.iter();
+    while let Some(key) = criteria.next() {
+        let key = key.to_uppercase();
+        let matched = match key.as_str() {
+            "ALL" => true,
+            "UNSEEN" => !is_seen(message),
+            "FLAGGED" => message.flags.iter().any(|f| f == "\\Flagged"),
+            "CHARSET" => criteria.next().is_some(),
+            "HEADER" => {
+                let name = criteria.next().cloned().unwrap_or_default();
+                let value = criteria.next().cloned().unwrap_or_default().to_lowercase();
+                header(&name).to_lowercase().contains(&value)
+            }
+            _ => {
+                let value = criteria.next().cloned().unwrap_or_default().to_lowercase();
+                match key.as_str() {
+                    "FROM" => header("From").to_lowercase().contains(&value),
+                    "SUBJECT" => header("Subject").to_lowercase().contains(&value),
+                    "BODY" => parsed
+                        .get_body()
+                        .unwrap_or_default()
+                        .to_lowercase()
+                        .contains(&value),
+                    "TEXT" => message.raw.to_lowercase().contains(&value),
+                    "SINCE" => {
+                        let since = chrono::NaiveDate::parse_from_str(&value, "%d-%b-%Y")
+                            .unwrap()
+                            .and_hms_opt(0, 0, 0)
+                            .unwrap()
+                            .and_utc()
+                            .timestamp();
+                        mailparse::dateparse(&header("Date")).unwrap() >= since
+                    }
+                    _ => false,
+                }
+            }
+        };
+        if !matched {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// ENVELOPE of a raw message, built from its headers.
+fn envelope(raw: &str) -> String {
+    let parsed = mailparse::parse_mail(raw.as_bytes()).unwrap();
+    let header = |name: &str| {
+        parsed
+            .headers
+            .iter()
+            .find(|h| h.get_key().eq_ignore_ascii_case(name))
+            .map(|h| String::from_utf8_lossy(h.get_value_raw()).into_owned())
+    };
+    let addresses = |name: &str| {
+        let Some(value) = header(name) else {
+            return "NIL".to_string();
+        };
+        let list = mailparse::addrparse(&value).unwrap();
+        let mut out = String::from("(");
+        for addr in list.iter() {
+            if let mailparse::MailAddr::Single(info) = addr {
+                let (mailbox, host) = info.addr.split_once('@').unwrap_or((&info.addr, ""));
+                out.push_str(&format!(
+                    "({} NIL {} {})",
+                    nstring(info.display_name.as_deref()),
+                    quoted(mailbox),
+                    quoted(host)
+                ));
+            }
+        }
+        out.push(')');
+        out
+    };
+
+    let from = addresses("From");
+    format!(
+        "({} {} {} {} {} {} {} NIL NIL {})",
+        nstring(header("Date").as_deref()),
+        nstring(header("Subject").as_deref()),
+        from,
+        from,
+        from,
+        addresses("To"),
+        addresses("Cc"),
+        nstring(header("Message-ID").as_deref()),
+    )
+}
+
+/// BODYSTRUCTURE of a parsed message, with dispositions for attachments.
+fn body_structure(part: &mailparse::ParsedMail<'_>) -> String {
+    if !part.subparts.is_empty() {
+        let subtype = part.ctype.mimetype.split('/').nth(1).unwrap_or("mixed");
+        let bodies: String = part.subparts.iter().map(body_structure).collect();
+        return format!("({} {})", bodies, quoted(subtype));
+    }
+
+    let (ty, subtype) = part
+        .ctype
+        .mimetype
+        .split_once('/')
+        .unwrap_or(("text", "plain"));
+    let size = part.get_body_raw().map(|b| b.len()).unwrap_or(0);
+    let disposition = part.get_content_disposition();
+    let disposition = match disposition.disposition {
+        mailparse::DispositionType::Attachment => {
+            let filename = disposition.params.get("filename").map(String::as_str);
+            format!("(\"attachment\" (\"filename\" {}))", nstring(filename))
+        }
+        _ => "NIL".to_string(),
+    };
+    let lines = if ty.eq_ignore_ascii_case("text") {
+        format!(
+            " {}",
+            part.get_body_raw()
+                .map(|b| b.split(|&c| c == b'\n').count())
+                .unwrap_or(0)
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "({} {} (\"charset\" {}) NIL NIL \"7bit\" {}{} NIL {} NIL NIL)",
+        quoted(ty),
+        quoted(subtype),
+        quoted(&part.ctype.charset),
+        size,
+        lines,
+        disposition
+    )
+}
+
+fn nstring(value: Option<&str>) -> String {
+    value.map(quoted).unwrap_or_else(|| "NIL".to_string())
+}
+
+fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// UIDs in a set such as `3` or `1,4`. Ranges are not needed by the provider.
+fn uid_set(set: &str) -> Vec<u32> {
+    set.split(',').filter_map(|uid| uid.parse().ok()).collect()
+}
+
+/// Space-separated atoms and quoted strings, stopping at a parenthesized
+/// list.
+fn atoms(args: &str) -> Vec<String> {
+    let mut atoms = Vec::new();
+    let mut chars = args.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' => {
+                chars.next();
+            }
+            '(' => break,
+            '"' => {
+                chars.next();
+                let mut atom = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => atom.extend(chars.next()),
+                        '"' => break,
+                        _ => atom.push(c),
+                    }
+                }
+                atoms.push(atom);
+            }
+            _ => {
+                let mut atom = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == ' ' {
+                        break;
+                    }
+                    atom.push(c);
+                    chars.next();
+                }
+                atoms.push(atom);
+            }
+        }
+    }
+
+    atoms
+}
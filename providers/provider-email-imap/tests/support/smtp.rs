@@ -0,0 +1,138 @@
+//! A scripted, in-process SMTP server that records what it is sent.
+//!
+//! [`SmtpServer`] answers EHLO, AUTH PLAIN, MAIL, RCPT, DATA, and QUIT over
+//! plain TCP, advertising AUTH and accepting only [`USERNAME`] and
+//! [`PASSWORD`].
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use base64::Engine;
+
+use super::{PASSWORD, USERNAME};
+
+/// A message the server accepted.
+#[derive(Debug, Clone, Default)]
+pub struct SentMessage {
+    pub from: String,
+    pub to: Vec<String>,
+    /// The message as sent, with dot-stuffing undone
+    pub data: String,
+}
+
+/// An SMTP server on a random local port.
+pub struct SmtpServer {
+    port: u16,
+    sent: Arc<Mutex<Vec<SentMessage>>>,
+}
+
+impl SmtpServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind SMTP stub");
+        let port = listener.local_addr().unwrap().port();
+        let sent = Arc::new(Mutex::new(Vec::new()));
+
+        let shared = Arc::clone(&sent);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let sent = Arc::clone(&shared);
+                thread::spawn(move || {
+                    let _ = serve(stream, sent);
+                });
+            }
+        });
+
+        Self { port, sent }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Messages accepted so far, in order.
+    pub fn sent(&self) -> Vec<SentMessage> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+fn serve(stream: TcpStream, sent: Arc<Mutex<Vec<SentMessage>>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut reply = |line: &str| writer.write_all(format!("{}\r\n", line).as_bytes());
+    reply("220 SMTP stub ready")?;
+
+    let mut authenticated = false;
+    let mut message = SentMessage::default();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let line = line.trim_end();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+
+        match command.to_uppercase().as_str() {
+            "EHLO" => {
+                reply("250-localhost")?;
+                reply("250 AUTH PLAIN")?;
+            }
+            "AUTH" => {
+                let expected = format!("\0{}\0{}", USERNAME, PASSWORD);
+                let given = args
+                    .strip_prefix("PLAIN ")
+                    .and_then(|encoded| {
+                        base64::engine::general_purpose::STANDARD
+                            .decode(encoded)
+                            .ok()
+                    })
+                    .unwrap_or_default();
+                if given == expected.as_bytes() {
+                    authenticated = true;
+                    reply("235 Authenticated")?;
+                } else {
+                    reply("535 Invalid credentials")?;
+                }
+            }
+            _ if !authenticated && command != "QUIT" => reply("530 Authentication required")?,
+            "MAIL" => {
+                message.from = address(args);
+                reply("250 OK")?;
+            }
+            "RCPT" => {
+                message.to.push(address(args));
+                reply("250 OK")?;
+            }
+            "DATA" => {
+                reply("354 End data with <CR><LF>.<CR><LF>")?;
+                loop {
+                    let mut data = String::new();
+                    if reader.read_line(&mut data)? == 0 {
+                        return Ok(());
+                    }
+                    if data.trim_end() == "." {
+                        break;
+                    }
+                    let data = data.strip_prefix('.').unwrap_or(&data);
+                    message.data.push_str(data);
+                }
+                sent.lock().unwrap().push(std::mem::take(&mut message));
+                reply("250 Queued")?;
+            }
+            "QUIT" => {
+                reply("221 Bye")?;
+                return Ok(());
+            }
+            _ => reply("502 Command not implemented")?,
+        }
+    }
+}
+
+/// The address in `FROM:<address>` or `TO:<address>`.
+fn address(args: &str) -> String {
+    args.split_once('<')
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(address, _)| address.to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,91 @@
+//! Configuration for the iCal provider.
+
+use serde::{Deserialize, Serialize};
+
+/// A subscribed calendar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IcalCalendar {
+    /// Feed name, e.g. `holidays`
+    pub name: String,
+    /// The `.ics` URL; `webcal://` and `webcals://` are fetched over HTTPS
+    pub url: String,
+}
+
+impl IcalCalendar {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+        }
+    }
+
+    /// The URL to fetch, with a `webcal` scheme replaced by `https`.
+    pub fn http_url(&self) -> String {
+        let url = self.url.trim();
+        for scheme in ["webcals://", "webcal://"] {
+            if let Some(rest) = url
+                .get(..scheme.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+                .map(|_| &url[scheme.len()..])
+            {
+                return format!("https://{}", rest);
+            }
+        }
+        url.to_string()
+    }
+}
+
+/// Configuration for the [`IcalProvider`](crate::IcalProvider).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IcalProviderConfig {
+    /// Calendars to subscribe to
+    pub calendars: Vec<IcalCalendar>,
+    /// How many days of upcoming events a feed shows
+    pub days_ahead: u32,
+}
+
+impl Default for IcalProviderConfig {
+    fn default() -> Self {
+        Self {
+            calendars: Vec::new(),
+            days_ahead: 30,
+        }
+    }
+}
+
+impl IcalProviderConfig {
+    /// Configuration subscribing to `calendars`, showing 30 days ahead.
+    pub fn new(calendars: Vec<IcalCalendar>) -> Self {
+        Self {
+            calendars,
+            ..Default::default()
+        }
+    }
+
+    /// Show this many days of upcoming events.
+    pub fn with_days_ahead(mut self, days: u32) -> Self {
+        self.days_ahead = days;
+        self
+    }
+
+    /// The calendar a feed name belongs to.
+    pub fn calendar(&self, name: &str) -> Option<&IcalCalendar> {
+        self.calendars.iter().find(|calendar| calendar.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webcal_urls_use_https() {
+        let holidays = IcalCalendar::new("holidays", "webcal://example.com/holidays.ics");
+        assert_eq!(holidays.http_url(), "https://example.com/holidays.ics");
+        let team = IcalCalendar::new("team", "WEBCALS://example.com/team.ics");
+        assert_eq!(team.http_url(), "https://example.com/team.ics");
+        let plain = IcalCalendar::new("plain", "http://localhost:8080/cal.ics");
+        assert_eq!(plain.http_url(), "http://localhost:8080/cal.ics");
+    }
+}
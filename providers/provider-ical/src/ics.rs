@@ -0,0 +1,365 @@
+//! Parsing iCalendar (RFC 5545) subscription feeds.
+//!
+//! Only what a read-only subscription needs is read: the calendar's name
+//! and, for each `VEVENT`, its text fields, times, and recurrence. Alarms,
+//! to-dos, and time zone definitions are skipped; `TZID`s are looked up in
+//! the IANA database instead.
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use scryforge_provider_core::ical::{unescape, unfold, Property};
+
+/// When an event starts or ends, as written in the feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    /// An all-day date (`VALUE=DATE`)
+    Date(NaiveDate),
+    /// A UTC time (`...Z`)
+    Utc(DateTime<Utc>),
+    /// A wall-clock time in a time zone (`TZID=...`)
+    Zoned(NaiveDateTime, Tz),
+    /// A wall-clock time without a zone, or with a `TZID` that isn't an IANA
+    /// name; read as local time
+    Floating(NaiveDateTime),
+}
+
+impl EventTime {
+    fn parse(property: &Property) -> Option<Self> {
+        let value = property.value.trim();
+        if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+            return NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .map(EventTime::Date);
+        }
+        if let Some(utc) = value.strip_suffix('Z') {
+            return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+                .ok()
+                .map(|time| EventTime::Utc(time.and_utc()));
+        }
+        let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+        // Some producers write the zone as a path, e.g. `/Europe/Berlin`
+        let zone = property
+            .param("TZID")
+            .and_then(|tzid| tzid.trim_start_matches('/').parse::<Tz>().ok());
+        Some(match zone {
+            Some(zone) => EventTime::Zoned(time, zone),
+            None => EventTime::Floating(time),
+        })
+    }
+
+    /// Every time of a comma-separated list, as in `EXDATE`.
+    fn parse_list(property: &Property) -> Vec<Self> {
+        property
+            .value
+            .split(',')
+            .filter_map(|value| {
+                EventTime::parse(&Property {
+                    value: value.to_string(),
+                    ..property.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Whether this is an all-day date.
+    pub fn is_date(&self) -> bool {
+        matches!(self, EventTime::Date(_))
+    }
+
+    /// The date, in the time's own zone.
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            EventTime::Date(date) => *date,
+            EventTime::Utc(time) => time.date_naive(),
+            EventTime::Zoned(time, _) | EventTime::Floating(time) => time.date(),
+        }
+    }
+
+    /// The same wall-clock time on another date, for recurrences.
+    pub fn on_date(&self, date: NaiveDate) -> Self {
+        match self {
+            EventTime::Date(_) => EventTime::Date(date),
+            EventTime::Utc(time) => EventTime::Utc(date.and_time(time.time()).and_utc()),
+            EventTime::Zoned(time, zone) => EventTime::Zoned(date.and_time(time.time()), *zone),
+            EventTime::Floating(time) => EventTime::Floating(date.and_time(time.time())),
+        }
+    }
+
+    /// The instant. All-day dates are midnight UTC, so they show on the
+    /// same date everywhere.
+    pub fn to_utc(&self) -> DateTime<Utc> {
+        match self {
+            EventTime::Date(date) => date.and_time(NaiveTime::MIN).and_utc(),
+            EventTime::Utc(time) => *time,
+            EventTime::Zoned(time, zone) => zone
+                .from_local_datetime(time)
+                .earliest()
+                .map_or_else(|| time.and_utc(), |local| local.with_timezone(&Utc)),
+            EventTime::Floating(time) => chrono::Local
+                .from_local_datetime(time)
+                .earliest()
+                .map_or_else(|| time.and_utc(), |local| local.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// One `VEVENT`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub categories: Vec<String>,
+    pub start: EventTime,
+    /// How long each occurrence lasts
+    pub duration: Duration,
+    /// The `RRULE`, unparsed
+    pub rrule: Option<String>,
+    /// Occurrences left out of the recurrence
+    pub exdates: Vec<EventTime>,
+    /// The occurrence of a recurring event this one replaces
+    pub recurrence_id: Option<EventTime>,
+    pub cancelled: bool,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// A parsed iCalendar feed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Calendar {
+    /// `X-WR-CALNAME`, the name most producers give the calendar
+    pub name: Option<String>,
+    pub events: Vec<Event>,
+}
+
+/// Parse an iCalendar feed. Events without a `UID` or a valid `DTSTART` are
+/// skipped.
+pub fn parse(ics: &str) -> Calendar {
+    let mut calendar = Calendar::default();
+    let mut event: Option<Vec<Property>> = None;
+    let mut depth = 0;
+    for property in unfold(ics).iter().filter_map(|line| Property::parse(line)) {
+        match (property.name.as_str(), property.value.trim()) {
+            ("BEGIN", "VEVENT") if depth == 0 => {
+                event = Some(Vec::new());
+                depth = 1;
+            }
+            ("BEGIN", _) if depth > 0 => depth += 1,
+            ("END", _) if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(event) = event.take().and_then(|props| build_event(&props)) {
+                        calendar.events.push(event);
+                    }
+                }
+            }
+            ("X-WR-CALNAME", _) if depth == 0 => calendar.name = property.text(),
+            // Alarms and other components nested in the event are skipped
+            _ if depth == 1 => {
+                if let Some(event) = event.as_mut() {
+                    event.push(property);
+                }
+            }
+            _ => {}
+        }
+    }
+    calendar
+}
+
+fn build_event(properties: &[Property]) -> Option<Event> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+    let uid = find("UID")?.value.trim().to_string();
+    let start = EventTime::parse(find("DTSTART")?)?;
+
+    let end = find("DTEND").and_then(EventTime::parse);
+    let duration = match (end, find("DURATION")) {
+        (Some(end), _) => end.to_utc() - start.to_utc(),
+        (None, Some(duration)) => parse_duration(&duration.value).unwrap_or_else(Duration::zero),
+        (None, None) if start.is_date() => Duration::days(1),
+        (None, None) => Duration::zero(),
+    };
+
+    Some(Event {
+        uid,
+        summary: find("SUMMARY")
+            .and_then(Property::text)
+            .unwrap_or_else(|| "(No title)".to_string()),
+        description: find("DESCRIPTION").and_then(Property::text),
+        location: find("LOCATION").and_then(Property::text),
+        url: find("URL").map(|url| url.value.trim().to_string()),
+        categories: properties
+            .iter()
+            .filter(|p| p.name == "CATEGORIES")
+            .flat_map(|p| split_list(&p.value))
+            .collect(),
+        start,
+        duration: duration.max(Duration::zero()),
+        rrule: find("RRULE").map(|rule| rule.value.trim().to_string()),
+        exdates: properties
+            .iter()
+            .filter(|p| p.name == "EXDATE")
+            .flat_map(EventTime::parse_list)
+            .collect(),
+        recurrence_id: find("RECURRENCE-ID").and_then(EventTime::parse),
+        cancelled: find("STATUS")
+            .is_some_and(|status| status.value.eq_ignore_ascii_case("CANCELLED")),
+        last_modified: find("LAST-MODIFIED")
+            .or_else(|| find("DTSTAMP"))
+            .and_then(EventTime::parse)
+            .map(|time| time.to_utc()),
+    })
+}
+
+/// Parse a `DURATION` value such as `PT1H30M`, `P1D`, or `P2W`.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: i64 = std::mem::take(&mut number).parse().ok()?;
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(if negative { -total } else { total })
+}
+
+/// Split a comma-separated text list, such as `CATEGORIES`, honouring
+/// escaped commas.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(std::mem::take(&mut current)),
+            '\\' if !escaped => {
+                escaped = true;
+                current.push(c);
+                continue;
+            }
+            _ => current.push(c),
+        }
+        escaped = false;
+    }
+    items.push(current);
+    items
+        .iter()
+        .map(|item| unescape(item))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        PRODID:-//Example//Holidays//EN\r\n\
+        X-WR-CALNAME:Team Calendar\r\n\
+        BEGIN:VTIMEZONE\r\n\
+        TZID:Europe/Berlin\r\n\
+        BEGIN:STANDARD\r\n\
+        DTSTART:19701025T030000\r\n\
+        TZOFFSETTO:+0100\r\n\
+        END:STANDARD\r\n\
+        END:VTIMEZONE\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:standup@example.com\r\n\
+        SUMMARY:Stand-up\\, daily\r\n\
+        DESCRIPTION:Bring updates.\\nKeep it short.\r\n\
+        DTSTART;TZID=Europe/Berlin:20240506T093000\r\n\
+        DURATION:PT15M\r\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR\r\n\
+        EXDATE;TZID=Europe/Berlin:20240508T093000,20240510T093000\r\n\
+        CATEGORIES:Work,Meetings\r\n\
+        BEGIN:VALARM\r\n\
+        ACTION:DISPLAY\r\n\
+        DESCRIPTION:Reminder\r\n\
+        END:VALARM\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:holiday-1@example.com\r\n\
+        SUMMARY:Labour Day\r\n\
+        DTSTART;VALUE=DATE:20240501\r\n\
+        LOCATION:Everywhere in a long folded loc\r\n \
+        ation\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        SUMMARY:No UID\r\n\
+        DTSTART:20240501T100000Z\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_feed() {
+        let calendar = parse(FEED);
+        assert_eq!(calendar.name.as_deref(), Some("Team Calendar"));
+        assert_eq!(calendar.events.len(), 2);
+
+        let standup = &calendar.events[0];
+        assert_eq!(standup.summary, "Stand-up, daily");
+        assert_eq!(
+            standup.description.as_deref(),
+            Some("Bring updates.\nKeep it short.")
+        );
+        assert_eq!(standup.duration, Duration::minutes(15));
+        assert_eq!(
+            standup.start.to_utc().to_rfc3339(),
+            "2024-05-06T07:30:00+00:00"
+        );
+        assert_eq!(standup.rrule.as_deref(), Some("FREQ=WEEKLY;BYDAY=MO,WE,FR"));
+        assert_eq!(standup.exdates.len(), 2);
+        assert_eq!(standup.categories, vec!["Work", "Meetings"]);
+
+        let holiday = &calendar.events[1];
+        assert_eq!(
+            holiday.start,
+            EventTime::Date(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap())
+        );
+        assert_eq!(holiday.duration, Duration::days(1));
+        assert_eq!(
+            holiday.location.as_deref(),
+            Some("Everywhere in a long folded location")
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(parse_duration("P1DT12H"), Some(Duration::hours(36)));
+        assert_eq!(parse_duration("-PT5M"), Some(Duration::minutes(-5)));
+        assert_eq!(parse_duration("1H"), None);
+    }
+
+    #[test]
+    fn test_unknown_tzid_is_floating() {
+        let property =
+            Property::parse("DTSTART;TZID=W. Europe Standard Time:20240501T090000").unwrap();
+        assert!(matches!(
+            EventTime::parse(&property),
+            Some(EventTime::Floating(_))
+        ));
+    }
+}
@@ -0,0 +1,430 @@
+//! # provider-ical
+//!
+//! Read-only iCalendar subscription provider for Scryforge.
+//!
+//! Public holiday calendars, team calendars, sports fixtures, and most
+//! calendar apps' "secret address" exports are plain `.ics` files served
+//! over HTTP(S), often with a `webcal://` URL. This provider fetches them,
+//! expands recurring events ([`recurrence`]), and shows the upcoming
+//! occurrences as feeds. It needs no account and no CalDAV server.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_ical::{IcalCalendar, IcalProvider, IcalProviderConfig};
+//!
+//! let config = IcalProviderConfig::new(vec![
+//!     IcalCalendar::new("holidays", "webcal://example.com/holidays.ics"),
+//!     IcalCalendar::new("team", "https://calendar.example.com/team/basic.ics"),
+//! ])
+//! .with_days_ahead(60);
+//! let provider = IcalProvider::new(config);
+//! ```
+//!
+//! Each calendar is a feed of its own; the `upcoming` feed merges them all.
+
+pub mod config;
+pub mod ics;
+pub mod recurrence;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER};
+use reqwest::{Client, StatusCode};
+use scryforge_provider_core::prelude::*;
+
+pub use config::{IcalCalendar, IcalProviderConfig};
+pub use ics::{Calendar, Event, EventTime};
+pub use recurrence::Occurrence;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "ical";
+
+/// Feed of the upcoming events of every calendar.
+pub const UPCOMING_FEED: &str = "upcoming";
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// A fetched feed, kept to make the next request conditional.
+#[derive(Debug, Clone)]
+struct CachedFeed {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// iCalendar (webcal) subscription provider for Scryforge.
+pub struct IcalProvider {
+    config: IcalProviderConfig,
+    client: Client,
+    /// Last response per URL
+    cache: Mutex<HashMap<String, CachedFeed>>,
+}
+
+impl IcalProvider {
+    pub fn new(config: IcalProviderConfig) -> Self {
+        // Some calendar hosts refuse requests without a User-Agent
+        let client = Client::builder()
+            .user_agent("scryforge/0.1.0")
+            .build()
+            .unwrap();
+
+        Self {
+            config,
+            client,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fetch the feed of `calendar`. Feeds that haven't changed since the
+    /// last fetch aren't downloaded again.
+    async fn fetch_calendar(&self, calendar: &IcalCalendar) -> Result<Calendar> {
+        let url = calendar.http_url();
+        let cached = self.cache.lock().unwrap().get(&url).cloned();
+
+        let mut request = self.client.get(&url);
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(ics::parse(&cached.body));
+            }
+        }
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok());
+            if status == StatusCode::NOT_FOUND {
+                return Err(StreamError::StreamNotFound(format!(
+                    "Calendar {} not found at {}",
+                    calendar.name, url
+                )));
+            }
+            let body = response.text().await.unwrap_or_default();
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                retry_after,
+                format!("Calendar {} error ({}): {}", calendar.name, status, body),
+            ));
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+        if !body.contains("BEGIN:VCALENDAR") {
+            return Err(StreamError::Provider(format!(
+                "{} is not an iCalendar feed",
+                url
+            )));
+        }
+
+        let parsed = ics::parse(&body);
+        self.cache.lock().unwrap().insert(
+            url,
+            CachedFeed {
+                etag,
+                last_modified,
+                body,
+            },
+        );
+        Ok(parsed)
+    }
+
+    /// Items for the occurrences of `calendar`'s events in `[from, to)`.
+    async fn upcoming(
+        &self,
+        calendar: &IcalCalendar,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Item>> {
+        let parsed = self.fetch_calendar(calendar).await?;
+        Ok(recurrence::occurrences(&parsed, from, to)
+            .iter()
+            .map(|occurrence| occurrence_to_item(&calendar.name, occurrence))
+            .collect())
+    }
+
+    /// The window of events a feed shows, starting at `since` or now.
+    fn window(&self, since: Option<DateTime<Utc>>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let from = since.unwrap_or_else(Utc::now);
+        (from, from + Duration::days(self.config.days_ahead as i64))
+    }
+}
+
+/// Convert an occurrence of an event in the calendar `name` into an [`Item`].
+fn occurrence_to_item(name: &str, occurrence: &Occurrence<'_>) -> Item {
+    let event = occurrence.event;
+    let start = occurrence.start_utc();
+    let is_all_day = occurrence.start.is_date();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("calendar".to_string(), name.to_string());
+    metadata.insert("uid".to_string(), event.uid.clone());
+    if is_all_day {
+        metadata.insert("all_day".to_string(), "true".to_string());
+    }
+    if occurrence.recurring {
+        metadata.insert("recurring".to_string(), "true".to_string());
+    }
+
+    Item {
+        // Occurrences of a recurring event share the UID
+        id: ItemId::new(
+            PROVIDER_ID,
+            &format!("{}:{}@{}", name, event.uid, start.timestamp()),
+        ),
+        stream_id: StreamId::new(PROVIDER_ID, "feed", name),
+        title: event.summary.clone(),
+        content: ItemContent::Event {
+            description: event.description.clone(),
+            start,
+            end: occurrence.end_utc(),
+            location: event.location.clone(),
+            is_all_day,
+        },
+        author: None,
+        published: Some(start),
+        updated: event.last_modified,
+        url: event.url.clone(),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: event.categories.clone(),
+        metadata,
+    }
+}
+
+#[async_trait]
+impl Provider for IcalProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "iCal"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        Ok(ProviderHealth {
+            is_healthy: true,
+            message: Some(format!(
+                "Subscribed to {} calendars",
+                self.config.calendars.len()
+            )),
+            last_sync: None,
+            error_count: 0,
+        })
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        let (from, to) = self.window(None);
+
+        // One unreachable calendar shouldn't hide the others
+        let mut items_added = 0;
+        let mut errors = Vec::new();
+        for calendar in &self.config.calendars {
+            match self.upcoming(calendar, from, to).await {
+                Ok(items) => items_added += items.len() as u32,
+                Err(e) => errors.push(format!("{}: {}", calendar.name, e)),
+            }
+        }
+
+        Ok(SyncResult {
+            success: errors.is_empty(),
+            items_added,
+            items_updated: 0,
+            items_removed: 0,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = Vec::new();
+        if item.url.is_some() {
+            actions.push(Action {
+                id: "open_browser".to_string(),
+                name: "Open in Browser".to_string(),
+                description: "Open the event's page".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            });
+            actions.push(Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the event's link to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            });
+        }
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match (&action.kind, &item.url) {
+            (ActionKind::OpenInBrowser, Some(url)) => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Opening: {}", url)),
+                data: Some(serde_json::json!({ "url": url })),
+            }),
+            (ActionKind::CopyLink, Some(url)) => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Copied: {}", url)),
+                data: Some(serde_json::json!({ "url": url })),
+            }),
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for IcalProvider {
+    /// One feed per calendar, plus `upcoming`.
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let mut feeds = vec![Feed {
+            id: FeedId(UPCOMING_FEED.to_string()),
+            name: "Upcoming".to_string(),
+            description: Some(format!(
+                "Events of every calendar in the next {} days",
+                self.config.days_ahead
+            )),
+            icon: Some("📅".to_string()),
+            unread_count: None,
+            total_count: None,
+        }];
+        feeds.extend(self.config.calendars.iter().map(|calendar| Feed {
+            id: FeedId(calendar.name.clone()),
+            name: calendar.name.clone(),
+            description: Some(calendar.url.clone()),
+            icon: Some("🗓".to_string()),
+            unread_count: None,
+            total_count: None,
+        }));
+        Ok(feeds)
+    }
+
+    /// Occurrences in the `days_ahead` days from `since` (now by default),
+    /// soonest first.
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let (from, to) = self.window(options.since);
+
+        let mut items = Vec::new();
+        if feed_id.0 == UPCOMING_FEED {
+            for calendar in &self.config.calendars {
+                items.extend(self.upcoming(calendar, from, to).await?);
+            }
+            items.sort_by_key(|item| item.published);
+        } else {
+            let calendar = self
+                .config
+                .calendar(&feed_id.0)
+                .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+            items = self.upcoming(calendar, from, to).await?;
+        }
+
+        Ok(items
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_occurrence_to_item() {
+        let calendar = ics::parse(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:standup@example.com\r\n\
+             SUMMARY:Standup\r\n\
+             DTSTART:20240506T090000Z\r\n\
+             DURATION:PT15M\r\n\
+             RRULE:FREQ=DAILY;COUNT=5\r\n\
+             LOCATION:Room 1\r\n\
+             CATEGORIES:work,meetings\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        );
+        let from = "2024-05-07T00:00:00Z".parse().unwrap();
+        let to = "2024-05-08T00:00:00Z".parse().unwrap();
+        let occurrences = recurrence::occurrences(&calendar, from, to);
+        assert_eq!(occurrences.len(), 1);
+
+        let item = occurrence_to_item("team", &occurrences[0]);
+        assert_eq!(item.id.0, "ical:team:standup@example.com@1715072400");
+        assert_eq!(item.stream_id.0, "ical:feed:team");
+        assert_eq!(item.title, "Standup");
+        assert_eq!(item.tags, vec!["work", "meetings"]);
+        assert_eq!(
+            item.metadata.get("recurring").map(String::as_str),
+            Some("true")
+        );
+        assert!(!item.metadata.contains_key("all_day"));
+        assert!(matches!(
+            &item.content,
+            ItemContent::Event { start, end, location: Some(location), is_all_day: false, .. }
+                if start.to_rfc3339() == "2024-05-07T09:00:00+00:00"
+                    && end.to_rfc3339() == "2024-05-07T09:15:00+00:00"
+                    && location == "Room 1"
+        ));
+    }
+}
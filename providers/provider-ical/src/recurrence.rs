@@ -0,0 +1,497 @@
+//! Expanding recurring events into occurrences.
+//!
+//! [`Rule`] covers the `RRULE` parts subscription feeds use in practice:
+//! `FREQ` (daily to yearly), `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY` (with
+//! ordinals such as `-1FR` in monthly and yearly rules), `BYMONTHDAY`, and
+//! `BYMONTH`. A rule with any other part isn't expanded: the event shows
+//! its first occurrence only, rather than a wrong set of them.
+
+use std::collections::{HashSet, VecDeque};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+use crate::ics::{Calendar, Event, EventTime};
+
+/// Periods in a row without an occurrence before a rule is given up on,
+/// such as `FREQ=MONTHLY;BYMONTHDAY=31` hitting short months, or a rule
+/// that can never match.
+const MAX_EMPTY_PERIODS: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed `RRULE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<EventTime>,
+    /// Weekdays, with an optional ordinal within the month
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+}
+
+impl Rule {
+    /// Parse an `RRULE` value. Returns `None` for rules using parts that
+    /// aren't supported.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut rule = Rule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=')?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|n| *n > 0)?,
+                "COUNT" => rule.count = Some(value.parse().ok()?),
+                "UNTIL" => rule.until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Option<Vec<_>>>()?
+                }
+                "BYMONTHDAY" => {
+                    rule.by_month_day = value
+                        .split(',')
+                        .map(|day| {
+                            day.parse()
+                                .ok()
+                                .filter(|day: &i32| (1..=31).contains(&day.abs()))
+                        })
+                        .collect::<Option<Vec<_>>>()?
+                }
+                "BYMONTH" => {
+                    rule.by_month = value
+                        .split(',')
+                        .map(|month| {
+                            month
+                                .parse()
+                                .ok()
+                                .filter(|month: &u32| (1..=12).contains(month))
+                        })
+                        .collect::<Option<Vec<_>>>()?
+                }
+                // The week start only matters with BYWEEKNO, which isn't
+                // supported anyway
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+        rule.frequency = frequency?;
+
+        let has_ordinals = rule.by_day.iter().any(|(ordinal, _)| ordinal.is_some());
+        let ordinals_allowed = match rule.frequency {
+            Frequency::Daily | Frequency::Weekly => false,
+            Frequency::Monthly => true,
+            // Ordinals within the whole year (`20MO`) aren't supported
+            Frequency::Yearly => !rule.by_month.is_empty(),
+        };
+        if has_ordinals && !ordinals_allowed {
+            return None;
+        }
+        Some(rule)
+    }
+
+    /// Whether an occurrence starting at `start` is within `UNTIL`.
+    fn allows(&self, start: &EventTime) -> bool {
+        match &self.until {
+            None => true,
+            Some(EventTime::Date(until)) => start.date() <= *until,
+            Some(until) => start.to_utc() <= until.to_utc(),
+        }
+    }
+
+    /// The dates of the rule's occurrences, from `start` on, ignoring
+    /// `UNTIL`.
+    pub fn dates(&self, start: NaiveDate) -> impl Iterator<Item = NaiveDate> + '_ {
+        let mut pending = VecDeque::new();
+        let mut period = 0;
+        let mut empty_periods = 0;
+        let mut emitted = 0;
+        std::iter::from_fn(move || loop {
+            if let Some(date) = pending.pop_front() {
+                if date < start {
+                    continue;
+                }
+                if self.count.is_some_and(|count| emitted >= count) {
+                    return None;
+                }
+                emitted += 1;
+                return Some(date);
+            }
+            if empty_periods >= MAX_EMPTY_PERIODS {
+                return None;
+            }
+            let dates = self.period_dates(start, period)?;
+            period += 1;
+            if dates.is_empty() {
+                empty_periods += 1;
+            } else {
+                empty_periods = 0;
+            }
+            pending.extend(dates);
+        })
+    }
+
+    /// The dates of the `period`th period after `start`'s, in order. `None`
+    /// once past the calendar's range.
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Option<Vec<NaiveDate>> {
+        let step = period.checked_mul(self.interval)?;
+        let mut dates = match self.frequency {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(step as i64))?;
+                vec![date]
+            }
+            Frequency::Weekly => {
+                let monday = start
+                    .checked_sub_signed(Duration::days(
+                        start.weekday().num_days_from_monday() as i64
+                    ))?
+                    .checked_add_signed(Duration::weeks(step as i64))?;
+                let weekdays = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, weekday)| *weekday).collect()
+                };
+                weekdays
+                    .into_iter()
+                    .filter_map(|weekday| {
+                        monday.checked_add_signed(Duration::days(
+                            weekday.num_days_from_monday() as i64
+                        ))
+                    })
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let months = start.month0() + step;
+                let year = start.year().checked_add((months / 12) as i32)?;
+                self.month_dates(year, months % 12 + 1, start)?
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(step as i32)?;
+                let months = if self.by_month.is_empty() {
+                    vec![start.month()]
+                } else {
+                    self.by_month.clone()
+                };
+                let mut dates = Vec::new();
+                for month in months {
+                    dates.extend(self.month_dates(year, month, start)?);
+                }
+                dates
+            }
+        };
+
+        dates.retain(|date| self.matches_filters(date));
+        dates.sort();
+        dates.dedup();
+        Some(dates)
+    }
+
+    /// The dates in one month that `BYMONTHDAY` and `BYDAY` select, or
+    /// `start`'s day of the month without either.
+    fn month_dates(&self, year: i32, month: u32, start: NaiveDate) -> Option<Vec<NaiveDate>> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let days_in_month = first
+            .checked_add_months(chrono::Months::new(1))?
+            .signed_duration_since(first)
+            .num_days() as i32;
+        let day = |day: i32| NaiveDate::from_ymd_opt(year, month, day as u32);
+
+        let by_month_day: Vec<NaiveDate> = self
+            .by_month_day
+            .iter()
+            .filter_map(|&n| day(if n > 0 { n } else { days_in_month + n + 1 }))
+            .collect();
+
+        let by_day: Vec<NaiveDate> = self
+            .by_day
+            .iter()
+            .flat_map(|&(ordinal, weekday)| {
+                let all: Vec<NaiveDate> = (1..=days_in_month)
+                    .filter_map(day)
+                    .filter(|date| date.weekday() == weekday)
+                    .collect();
+                match ordinal {
+                    None => all,
+                    Some(n) if n > 0 => all.get(n as usize - 1).copied().into_iter().collect(),
+                    Some(n) => all
+                        .len()
+                        .checked_sub(n.unsigned_abs() as usize)
+                        .and_then(|i| all.get(i).copied())
+                        .into_iter()
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Some(
+            match (self.by_month_day.is_empty(), self.by_day.is_empty()) {
+                (true, true) => day(start.day() as i32).into_iter().collect(),
+                (false, true) => by_month_day,
+                (true, false) => by_day,
+                // BYDAY narrows BYMONTHDAY: "Friday the 13th"
+                (false, false) => by_month_day
+                    .into_iter()
+                    .filter(|date| by_day.contains(date))
+                    .collect(),
+            },
+        )
+    }
+
+    /// `BYMONTH`, and for daily rules `BYDAY` and `BYMONTHDAY`, which only
+    /// narrow the dates down.
+    fn matches_filters(&self, date: &NaiveDate) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&date.month()) {
+            return false;
+        }
+        if self.frequency != Frequency::Daily {
+            return true;
+        }
+        let weekday_ok = self.by_day.is_empty()
+            || self
+                .by_day
+                .iter()
+                .any(|(_, weekday)| *weekday == date.weekday());
+        let month_day_ok = self.by_month_day.is_empty()
+            || self.by_month_day.iter().any(|&n| {
+                let days_in_month = NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+                    .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+                    .map_or(31, |next| {
+                        next.pred_opt().map_or(31, |last| last.day() as i32)
+                    });
+                let day = if n > 0 { n } else { days_in_month + n + 1 };
+                day == date.day() as i32
+            });
+        weekday_ok && month_day_ok
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim();
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, day) = value.split_at(split);
+    let weekday = match day.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i32>()
+                .ok()
+                .filter(|n| *n != 0)?,
+        ),
+    };
+    Some((ordinal, weekday))
+}
+
+fn parse_until(value: &str) -> Option<EventTime> {
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(EventTime::Date);
+    }
+    match value.strip_suffix('Z') {
+        Some(utc) => NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|time| EventTime::Utc(time.and_utc())),
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(EventTime::Floating),
+    }
+}
+
+/// One occurrence of an event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence<'a> {
+    pub event: &'a Event,
+    pub start: EventTime,
+    /// Whether the event repeats
+    pub recurring: bool,
+}
+
+impl Occurrence<'_> {
+    pub fn start_utc(&self) -> DateTime<Utc> {
+        self.start.to_utc()
+    }
+
+    pub fn end_utc(&self) -> DateTime<Utc> {
+        self.start.to_utc() + self.event.duration
+    }
+
+    /// Whether the occurrence overlaps `[from, to)`. Events without a
+    /// duration count when they start in it.
+    fn overlaps(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        let start = self.start_utc();
+        start < to && (self.end_utc() > from || start >= from)
+    }
+}
+
+/// The occurrences of the calendar's events that overlap `[from, to)`,
+/// ordered by start. Cancelled events, excluded dates, and occurrences
+/// replaced by a modified copy (`RECURRENCE-ID`) are left out.
+pub fn occurrences(
+    calendar: &Calendar,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<Occurrence<'_>> {
+    let replaced: HashSet<(&str, DateTime<Utc>)> = calendar
+        .events
+        .iter()
+        .filter_map(|event| Some((event.uid.as_str(), event.recurrence_id?.to_utc())))
+        .collect();
+
+    let mut occurrences = Vec::new();
+    for event in calendar.events.iter().filter(|event| !event.cancelled) {
+        let rule = match (&event.recurrence_id, &event.rrule) {
+            (None, Some(rule)) => Rule::parse(rule),
+            _ => None,
+        };
+        let Some(rule) = rule else {
+            let occurrence = Occurrence {
+                event,
+                start: event.start,
+                recurring: event.recurrence_id.is_some(),
+            };
+            if occurrence.overlaps(from, to) {
+                occurrences.push(occurrence);
+            }
+            continue;
+        };
+
+        let excluded: HashSet<DateTime<Utc>> =
+            event.exdates.iter().map(EventTime::to_utc).collect();
+        for date in rule.dates(event.start.date()) {
+            let start = event.start.on_date(date);
+            if !rule.allows(&start) || start.to_utc() >= to {
+                break;
+            }
+            let occurrence = Occurrence {
+                event,
+                start,
+                recurring: true,
+            };
+            let start = start.to_utc();
+            if occurrence.overlaps(from, to)
+                && !excluded.contains(&start)
+                && !replaced.contains(&(event.uid.as_str(), start))
+            {
+                occurrences.push(occurrence);
+            }
+        }
+    }
+
+    occurrences.sort_by(|a, b| {
+        a.start_utc()
+            .cmp(&b.start_utc())
+            .then_with(|| a.event.summary.cmp(&b.event.summary))
+    });
+    occurrences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn first_dates(rule: &str, start: NaiveDate, n: usize) -> Vec<NaiveDate> {
+        Rule::parse(rule).unwrap().dates(start).take(n).collect()
+    }
+
+    #[test]
+    fn test_weekly_by_day() {
+        assert_eq!(
+            first_dates("FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH", date(2024, 5, 7), 4),
+            vec![
+                date(2024, 5, 7),
+                date(2024, 5, 9),
+                date(2024, 5, 21),
+                date(2024, 5, 23)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_ordinals_and_month_days() {
+        assert_eq!(
+            first_dates("FREQ=MONTHLY;BYDAY=-1FR", date(2024, 1, 26), 3),
+            vec![date(2024, 1, 26), date(2024, 2, 23), date(2024, 3, 29)]
+        );
+        // Months without a 31st are skipped
+        assert_eq!(
+            first_dates("FREQ=MONTHLY;BYMONTHDAY=31", date(2024, 1, 31), 3),
+            vec![date(2024, 1, 31), date(2024, 3, 31), date(2024, 5, 31)]
+        );
+        assert_eq!(
+            first_dates("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13", date(2024, 1, 1), 2),
+            vec![date(2024, 9, 13), date(2024, 12, 13)]
+        );
+    }
+
+    #[test]
+    fn test_yearly_holidays() {
+        // US Thanksgiving
+        assert_eq!(
+            first_dates("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH", date(2023, 11, 23), 2),
+            vec![date(2023, 11, 23), date(2024, 11, 28)]
+        );
+        // Leap days only come every four years
+        assert_eq!(
+            first_dates("FREQ=YEARLY", date(2024, 2, 29), 2),
+            vec![date(2024, 2, 29), date(2028, 2, 29)]
+        );
+    }
+
+    #[test]
+    fn test_count_until_and_unsupported_parts() {
+        assert_eq!(
+            first_dates("FREQ=DAILY;COUNT=3", date(2024, 5, 1), 10).len(),
+            3
+        );
+
+        let rule = Rule::parse("FREQ=DAILY;UNTIL=20240503T235959Z").unwrap();
+        assert!(rule.allows(&EventTime::Date(date(2024, 5, 3))));
+        assert!(!rule.allows(&EventTime::Date(date(2024, 5, 4))));
+
+        assert!(Rule::parse("FREQ=MONTHLY;BYSETPOS=-1;BYDAY=MO,TU,WE,TH,FR").is_none());
+        assert!(Rule::parse("FREQ=YEARLY;BYDAY=20MO").is_none());
+        assert!(Rule::parse("FREQ=HOURLY").is_none());
+        assert!(Rule::parse("INTERVAL=2").is_none());
+    }
+}
@@ -0,0 +1,252 @@
+//! Mock-server tests for `provider-ical`'s HTTP paths.
+
+use chrono::{DateTime, Utc};
+use provider_ical::{IcalCalendar, IcalProvider, IcalProviderConfig};
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{header, method, path};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::MockApi;
+
+const HOLIDAYS: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+X-WR-CALNAME:Holidays\r
+BEGIN:VEVENT\r
+UID:labour-day\r
+SUMMARY:Labour Day\r
+DTSTART;VALUE=DATE:20240501\r
+DTEND;VALUE=DATE:20240502\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:ascension\r
+SUMMARY:Ascension Day\r
+DTSTART;VALUE=DATE:20240509\r
+DTEND;VALUE=DATE:20240510\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:easter\r
+SUMMARY:Easter Monday\r
+DTSTART;VALUE=DATE:20240401\r
+DTEND;VALUE=DATE:20240402\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:christmas\r
+SUMMARY:Christmas Day\r
+DTSTART;VALUE=DATE:20241225\r
+DTEND;VALUE=DATE:20241226\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+const TEAM: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+UID:planning\r
+SUMMARY:Sprint planning\r
+DTSTART;TZID=Europe/Berlin:20240506T100000\r
+DTEND;TZID=Europe/Berlin:20240506T110000\r
+RRULE:FREQ=WEEKLY;BYDAY=MO\r
+EXDATE;TZID=Europe/Berlin:20240513T100000\r
+URL:https://wiki.example.com/planning\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:planning\r
+RECURRENCE-ID;TZID=Europe/Berlin:20240520T100000\r
+SUMMARY:Sprint planning (moved)\r
+DTSTART;TZID=Europe/Berlin:20240521T140000\r
+DTEND;TZID=Europe/Berlin:20240521T150000\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+fn since(time: &str) -> FeedOptions {
+    FeedOptions {
+        since: Some(time.parse::<DateTime<Utc>>().unwrap()),
+        ..Default::default()
+    }
+}
+
+async fn mock_ics(api: &MockApi, url_path: &str, body: &str) {
+    Mock::given(method("GET"))
+        .and(path(url_path))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(api.server())
+        .await;
+}
+
+fn provider_for(api: &MockApi, calendars: &[(&str, &str)]) -> IcalProvider {
+    IcalProvider::new(
+        IcalProviderConfig::new(
+            calendars
+                .iter()
+                .map(|(name, url_path)| {
+                    IcalCalendar::new(*name, format!("{}{}", api.uri(), url_path))
+                })
+                .collect(),
+        )
+        .with_days_ahead(30),
+    )
+}
+
+#[tokio::test]
+async fn calendar_feed_shows_events_in_window() {
+    let api = MockApi::start().await;
+    mock_ics(&api, "/holidays.ics", HOLIDAYS).await;
+
+    let provider = provider_for(&api, &[("holidays", "/holidays.ics")]);
+    let items = provider
+        .get_feed_items(
+            &FeedId("holidays".to_string()),
+            since("2024-05-01T12:00:00Z"),
+        )
+        .await
+        .unwrap();
+
+    // Labour Day is still going on; Easter is over and Christmas too far off
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["Labour Day", "Ascension Day"]);
+    let ascension = &items[1];
+    assert_eq!(ascension.id.as_str(), "ical:holidays:ascension@1715212800");
+    assert_eq!(ascension.metadata["all_day"], "true");
+    assert!(matches!(
+        &ascension.content,
+        ItemContent::Event { start, end, is_all_day: true, .. }
+            if start.to_rfc3339() == "2024-05-09T00:00:00+00:00"
+                && end.to_rfc3339() == "2024-05-10T00:00:00+00:00"
+    ));
+}
+
+#[tokio::test]
+async fn recurring_events_skip_exdates_and_use_overrides() {
+    let api = MockApi::start().await;
+    mock_ics(&api, "/team.ics", TEAM).await;
+
+    let provider = provider_for(&api, &[("team", "/team.ics")]);
+    let items = provider
+        .get_feed_items(&FeedId("team".to_string()), since("2024-05-01T00:00:00Z"))
+        .await
+        .unwrap();
+
+    let starts: Vec<(String, &str)> = items
+        .iter()
+        .map(|item| (item.published.unwrap().to_rfc3339(), item.title.as_str()))
+        .collect();
+    assert_eq!(
+        starts,
+        vec![
+            ("2024-05-06T08:00:00+00:00".to_string(), "Sprint planning"),
+            (
+                "2024-05-21T12:00:00+00:00".to_string(),
+                "Sprint planning (moved)"
+            ),
+            ("2024-05-27T08:00:00+00:00".to_string(), "Sprint planning"),
+        ]
+    );
+    assert_eq!(items[0].metadata["recurring"], "true");
+    assert_eq!(
+        items[0].url.as_deref(),
+        Some("https://wiki.example.com/planning")
+    );
+}
+
+#[tokio::test]
+async fn unchanged_feed_is_reused() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/holidays.ics"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/holidays.ics"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_string(HOLIDAYS),
+        )
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api, &[("holidays", "/holidays.ics")]);
+    let feed = FeedId("holidays".to_string());
+    let first = provider
+        .get_feed_items(&feed, since("2024-05-01T12:00:00Z"))
+        .await
+        .unwrap();
+    let second = provider
+        .get_feed_items(&feed, since("2024-05-01T12:00:00Z"))
+        .await
+        .unwrap();
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn upcoming_merges_calendars_by_start() {
+    let api = MockApi::start().await;
+    mock_ics(&api, "/holidays.ics", HOLIDAYS).await;
+    mock_ics(&api, "/team.ics", TEAM).await;
+
+    let provider = provider_for(
+        &api,
+        &[("holidays", "/holidays.ics"), ("team", "/team.ics")],
+    );
+    let items = provider
+        .get_feed_items(
+            &FeedId("upcoming".to_string()),
+            FeedOptions {
+                limit: Some(3),
+                ..since("2024-05-01T12:00:00Z")
+            },
+        )
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec!["Labour Day", "Sprint planning", "Ascension Day"]
+    );
+    assert_eq!(items[1].stream_id.as_str(), "ical:feed:team");
+}
+
+#[tokio::test]
+async fn fetch_errors_are_mapped() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/busy.ics", 120).await;
+    mock_ics(&api, "/login.ics", "<html>Please sign in</html>").await;
+
+    let provider = provider_for(
+        &api,
+        &[
+            ("busy", "/busy.ics"),
+            ("login", "/login.ics"),
+            ("gone", "/gone.ics"),
+        ],
+    );
+    let feed = |name: &str| FeedId(name.to_string());
+    let options = || since("2024-05-01T00:00:00Z");
+
+    assert!(matches!(
+        provider.get_feed_items(&feed("busy"), options()).await,
+        Err(StreamError::RateLimited(120))
+    ));
+    assert!(matches!(
+        provider.get_feed_items(&feed("login"), options()).await,
+        Err(StreamError::Provider(_))
+    ));
+    assert!(matches!(
+        provider.get_feed_items(&feed("gone"), options()).await,
+        Err(StreamError::StreamNotFound(_))
+    ));
+    assert!(matches!(
+        provider.get_feed_items(&feed("missing"), options()).await,
+        Err(StreamError::StreamNotFound(_))
+    ));
+
+    let sync = provider.sync().await.unwrap();
+    assert!(!sync.success);
+    assert_eq!(sync.errors.len(), 3);
+}
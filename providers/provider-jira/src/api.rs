@@ -0,0 +1,448 @@
+//! Typed Jira REST API client.
+//!
+//! This module is a thin wrapper around `reqwest` that exposes only the subset
+//! of the Jira REST API (version 2) used by [`JiraProvider`](crate::JiraProvider).
+//! Version 2 takes and returns plain-text descriptions and comments, and is
+//! served by both Jira Cloud and Jira Server/Data Center.
+
+use chrono::NaiveDate;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::JiraProviderConfig;
+
+/// Issue fields requested from the search endpoint.
+const ISSUE_FIELDS: &str = "summary,description,status,priority,issuetype,project,assignee,\
+                            reporter,labels,duedate,created,updated,watches";
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors raised by the Jira API client.
+#[derive(Debug, Error)]
+pub enum JiraApiError {
+    /// Underlying transport error (DNS, TLS, connection refused, decode, ...).
+    #[error("HTTP transport error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// 401 Unauthorized — wrong email/token pair or an expired token.
+    #[error("Unauthorized (check email and API token)")]
+    Unauthorized,
+
+    /// 403 Forbidden — the account may not see or change the resource.
+    #[error("Forbidden")]
+    Forbidden,
+
+    /// 404 Not Found — issue or filter does not exist, or is not visible.
+    #[error("Not found")]
+    NotFound,
+
+    /// 429 Too Many Requests, with the `Retry-After` delay in seconds.
+    #[error("Rate limited")]
+    RateLimited(u64),
+
+    /// Generic non-success response with the body text.
+    #[error("Jira API error ({status}): {body}")]
+    Status { status: u16, body: String },
+}
+
+// ============================================================================
+// Response types (subset)
+// ============================================================================
+
+/// A Jira user, as returned by `GET /rest/api/2/myself` and embedded in issues.
+///
+/// Jira Cloud identifies users by `accountId`; Server and Data Center by
+/// `name`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(default)]
+    pub account_id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub email_address: Option<String>,
+}
+
+/// A saved JQL filter returned by `GET /rest/api/2/filter/favourite`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Filter {
+    pub id: String,
+    pub name: String,
+    pub jql: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Workflow status category. `key` is `"new"`, `"indeterminate"`, or `"done"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusCategory {
+    pub key: String,
+}
+
+/// Workflow status of an issue, or the target of a transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    pub name: String,
+    pub status_category: Option<StatusCategory>,
+}
+
+impl Status {
+    /// The status category key, if Jira sent one.
+    pub fn category(&self) -> Option<&str> {
+        self.status_category.as_ref().map(|c| c.key.as_str())
+    }
+}
+
+/// A named reference such as a priority or issue type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Named {
+    pub name: String,
+}
+
+/// Project an issue belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub key: String,
+    pub name: String,
+}
+
+/// Watch state of an issue for the authenticated user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Watches {
+    #[serde(default)]
+    pub is_watching: bool,
+    #[serde(default)]
+    pub watch_count: u32,
+}
+
+/// The fields requested for each issue.
+///
+/// Timestamps are kept as sent; Jira writes offsets without a colon
+/// (`2024-01-01T12:00:00.000+0000`), which is parsed in
+/// [`mapping`](crate::mapping).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueFields {
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub status: Option<Status>,
+    #[serde(default)]
+    pub priority: Option<Named>,
+    #[serde(default)]
+    pub issuetype: Option<Named>,
+    #[serde(default)]
+    pub project: Option<Project>,
+    #[serde(default)]
+    pub assignee: Option<User>,
+    #[serde(default)]
+    pub reporter: Option<User>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub duedate: Option<NaiveDate>,
+    #[serde(default)]
+    pub created: Option<String>,
+    #[serde(default)]
+    pub updated: Option<String>,
+    #[serde(default)]
+    pub watches: Option<Watches>,
+}
+
+/// An issue, with the subset of fields in [`IssueFields`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub id: String,
+    pub key: String,
+    #[serde(default)]
+    pub fields: IssueFields,
+}
+
+/// One page of results from `GET /rest/api/2/search`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    #[serde(default)]
+    pub start_at: u32,
+    #[serde(default)]
+    pub max_results: u32,
+    #[serde(default)]
+    pub total: u32,
+    #[serde(default)]
+    pub issues: Vec<Issue>,
+}
+
+/// A workflow transition available on an issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    pub to: Status,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransitionsResponse {
+    #[serde(default)]
+    transitions: Vec<Transition>,
+}
+
+/// A comment returned after posting one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TransitionRequest<'a> {
+    transition: TransitionRef<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct TransitionRef<'a> {
+    id: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CommentRequest<'a> {
+    body: &'a str,
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// How requests are authenticated.
+#[derive(Debug, Clone)]
+enum Auth {
+    /// Jira Cloud: email and API token as HTTP basic auth.
+    Basic { email: String, token: String },
+    /// Jira Server/Data Center: personal access token as a bearer token.
+    Bearer(String),
+}
+
+/// Minimal typed Jira API client.
+///
+/// Re-uses a single `reqwest::Client` across calls so connections are pooled.
+#[derive(Debug, Clone)]
+pub struct JiraClient {
+    base_url: String,
+    auth: Auth,
+    http: Client,
+}
+
+impl JiraClient {
+    /// Build a new client from a [`JiraProviderConfig`]. Trailing slashes on
+    /// the server URL are normalised away.
+    pub fn new(config: JiraProviderConfig) -> Self {
+        let http = Client::builder()
+            .user_agent("scryforge-provider-jira/0.1.0")
+            .build()
+            .unwrap_or_default();
+        Self::with_http(config, http)
+    }
+
+    /// Build a new client with an explicit `reqwest::Client` (mainly for tests).
+    pub fn with_http(config: JiraProviderConfig, http: Client) -> Self {
+        let auth = match config.email {
+            Some(email) => Auth::Basic {
+                email,
+                token: config.api_token,
+            },
+            None => Auth::Bearer(config.api_token),
+        };
+        Self {
+            base_url: config.server_url.trim_end_matches('/').to_string(),
+            auth,
+            http,
+        }
+    }
+
+    /// Server base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Browser link to an issue.
+    pub fn browse_url(&self, key: &str) -> String {
+        format!("{}/browse/{}", self.base_url, key)
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}/rest/api/2{}", self.base_url, path))
+            .header("Accept", "application/json");
+        match &self.auth {
+            Auth::Basic { email, token } => request.basic_auth(email, Some(token)),
+            Auth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+
+    async fn send(request: RequestBuilder) -> Result<reqwest::Response, JiraApiError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        match status {
+            StatusCode::UNAUTHORIZED => Err(JiraApiError::Unauthorized),
+            StatusCode::FORBIDDEN => Err(JiraApiError::Forbidden),
+            StatusCode::NOT_FOUND => Err(JiraApiError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(60);
+                Err(JiraApiError::RateLimited(retry_after))
+            }
+            other => {
+                let body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "<unreadable body>".to_string());
+                Err(JiraApiError::Status {
+                    status: other.as_u16(),
+                    body,
+                })
+            }
+        }
+    }
+
+    async fn get_json<T>(&self, path: &str) -> Result<T, JiraApiError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let response = Self::send(self.request(reqwest::Method::GET, path)).await?;
+        Ok(response.json::<T>().await?)
+    }
+
+    /// `GET /rest/api/2/myself` — the authenticated user.
+    pub async fn myself(&self) -> Result<User, JiraApiError> {
+        self.get_json("/myself").await
+    }
+
+    /// `GET /rest/api/2/filter/favourite` — the user's starred saved filters.
+    pub async fn favourite_filters(&self) -> Result<Vec<Filter>, JiraApiError> {
+        self.get_json("/filter/favourite").await
+    }
+
+    /// `GET /rest/api/2/filter/<id>` — a single saved filter.
+    pub async fn filter(&self, filter_id: &str) -> Result<Filter, JiraApiError> {
+        self.get_json(&format!("/filter/{}", filter_id)).await
+    }
+
+    /// `GET /rest/api/2/search` — one page of issues matching `jql`.
+    pub async fn search(
+        &self,
+        jql: &str,
+        start_at: u32,
+        max_results: u32,
+    ) -> Result<SearchResponse, JiraApiError> {
+        let request = self.request(reqwest::Method::GET, "/search").query(&[
+            ("jql", jql.to_string()),
+            ("startAt", start_at.to_string()),
+            ("maxResults", max_results.to_string()),
+            ("fields", ISSUE_FIELDS.to_string()),
+        ]);
+        let response = Self::send(request).await?;
+        Ok(response.json::<SearchResponse>().await?)
+    }
+
+    /// `GET /rest/api/2/issue/<key>/transitions` — transitions the user may
+    /// make from the issue's current status.
+    pub async fn transitions(&self, key: &str) -> Result<Vec<Transition>, JiraApiError> {
+        let response: TransitionsResponse = self
+            .get_json(&format!("/issue/{}/transitions", key))
+            .await?;
+        Ok(response.transitions)
+    }
+
+    /// `POST /rest/api/2/issue/<key>/transitions` — move the issue through
+    /// `transition_id`.
+    pub async fn transition(&self, key: &str, transition_id: &str) -> Result<(), JiraApiError> {
+        let body = TransitionRequest {
+            transition: TransitionRef { id: transition_id },
+        };
+        let request = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/issue/{}/transitions", key),
+            )
+            .json(&body);
+        Self::send(request).await?;
+        Ok(())
+    }
+
+    /// `POST /rest/api/2/issue/<key>/comment` — add a plain-text comment.
+    pub async fn add_comment(&self, key: &str, body: &str) -> Result<Comment, JiraApiError> {
+        let request = self
+            .request(reqwest::Method::POST, &format!("/issue/{}/comment", key))
+            .json(&CommentRequest { body });
+        let response = Self::send(request).await?;
+        Ok(response.json::<Comment>().await?)
+    }
+
+    /// `POST /rest/api/2/issue/<key>/watchers` — start watching as `user`.
+    ///
+    /// The body is the bare JSON string identifying the user: the account ID
+    /// on Cloud, the username on Server/Data Center.
+    pub async fn add_watcher(&self, key: &str, user: &User) -> Result<(), JiraApiError> {
+        let id = user.account_id.as_ref().or(user.name.as_ref());
+        let request = self
+            .request(reqwest::Method::POST, &format!("/issue/{}/watchers", key))
+            .json(&id);
+        Self::send(request).await?;
+        Ok(())
+    }
+
+    /// `DELETE /rest/api/2/issue/<key>/watchers` — stop watching as `user`.
+    pub async fn remove_watcher(&self, key: &str, user: &User) -> Result<(), JiraApiError> {
+        let query = match (&user.account_id, &user.name) {
+            (Some(account_id), _) => ("accountId", account_id.as_str()),
+            (None, Some(name)) => ("username", name.as_str()),
+            (None, None) => ("username", ""),
+        };
+        let request = self
+            .request(reqwest::Method::DELETE, &format!("/issue/{}/watchers", key))
+            .query(&[query]);
+        Self::send(request).await?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Error mapping
+// ============================================================================
+
+impl From<JiraApiError> for scryforge_provider_core::StreamError {
+    fn from(err: JiraApiError) -> Self {
+        use scryforge_provider_core::StreamError;
+        match err {
+            JiraApiError::Http(e) => StreamError::Network(e.to_string()),
+            JiraApiError::Unauthorized => {
+                StreamError::AuthRequired("Jira credentials missing or invalid".to_string())
+            }
+            JiraApiError::Forbidden => {
+                StreamError::AuthRequired("Jira account lacks permission".to_string())
+            }
+            JiraApiError::NotFound => StreamError::Provider("Jira: not found".to_string()),
+            JiraApiError::RateLimited(secs) => StreamError::RateLimited(secs),
+            JiraApiError::Status { status, body } => {
+                StreamError::from_status(status, None, format!("Jira HTTP {status}: {body}"))
+            }
+        }
+    }
+}
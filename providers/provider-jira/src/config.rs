@@ -0,0 +1,66 @@
+//! Configuration for the Jira provider.
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the [`JiraProvider`](crate::JiraProvider).
+///
+/// `server_url` is the root of the Jira site (e.g.
+/// `https://example.atlassian.net`). Jira Cloud authenticates with the
+/// account's email address and an API token created at
+/// <https://id.atlassian.com/manage-profile/security/api-tokens>; Jira Server
+/// and Data Center accept a personal access token as a bearer token instead,
+/// in which case `email` is left unset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraProviderConfig {
+    /// Base URL of the Jira site, e.g. `https://example.atlassian.net`.
+    pub server_url: String,
+    /// Account email for Jira Cloud basic authentication.
+    #[serde(default)]
+    pub email: Option<String>,
+    /// API token (Cloud) or personal access token (Server/Data Center).
+    pub api_token: String,
+}
+
+impl JiraProviderConfig {
+    /// Configuration for Jira Cloud, authenticating as `email` with an API token.
+    pub fn cloud(
+        server_url: impl Into<String>,
+        email: impl Into<String>,
+        api_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            server_url: server_url.into(),
+            email: Some(email.into()),
+            api_token: api_token.into(),
+        }
+    }
+
+    /// Configuration for Jira Server or Data Center with a personal access token.
+    pub fn server(server_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            server_url: server_url.into(),
+            email: None,
+            api_token: token.into(),
+        }
+    }
+
+    /// Construct a configuration by fetching the token from Sigilforge.
+    ///
+    /// Looks up the token under the service identifier `"jira"` and the
+    /// supplied `account` label. Pass `email` for Jira Cloud, `None` for a
+    /// Server/Data Center personal access token.
+    #[cfg(feature = "sigilforge")]
+    pub async fn from_sigilforge(
+        token_fetcher: &dyn scryforge_sigilforge_client::TokenFetcher,
+        server_url: impl Into<String>,
+        email: Option<String>,
+        account: &str,
+    ) -> std::result::Result<Self, scryforge_sigilforge_client::SigilforgeError> {
+        let token = token_fetcher.fetch_token("jira", account).await?;
+        Ok(Self {
+            server_url: server_url.into(),
+            email,
+            api_token: token,
+        })
+    }
+}
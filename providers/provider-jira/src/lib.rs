@@ -0,0 +1,520 @@
+//! # provider-jira
+//!
+//! [Jira](https://www.atlassian.com/software/jira) provider for Scryforge.
+//!
+//! This crate implements [`Provider`] and [`HasFeeds`] over the Jira REST
+//! API. Unresolved issues assigned to the user form the "Assigned to me"
+//! feed, and each of the user's favourite saved JQL filters becomes a feed of
+//! its own. Issues are task items, and can be moved to In Progress or Done,
+//! commented on, and watched or unwatched from the TUI.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_jira::{JiraProvider, JiraProviderConfig};
+//!
+//! // Jira Cloud: account email and an API token
+//! let config = JiraProviderConfig::cloud(
+//!     "https://example.atlassian.net",
+//!     "me@example.com",
+//!     "your-api-token",
+//! );
+//! let provider = JiraProvider::new(config);
+//!
+//! // Jira Server / Data Center: a personal access token
+//! let config = JiraProviderConfig::server("https://jira.example.com", "your-pat");
+//! let provider = JiraProvider::new(config);
+//! ```
+//!
+//! ## Sigilforge integration (optional)
+//!
+//! Enable the `sigilforge` cargo feature to fetch the token from a
+//! [Sigilforge] daemon with [`JiraProviderConfig::from_sigilforge`] instead of
+//! passing it inline.
+//!
+//! [Sigilforge]: https://github.com/raibid-labs/sigilforge
+
+pub mod api;
+pub mod config;
+pub mod mapping;
+
+pub use config::JiraProviderConfig;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use scryforge_provider_core::prelude::*;
+use std::any::Any;
+use std::time::Instant;
+use tokio::sync::OnceCell;
+
+use crate::mapping::{
+    filter_to_feed, issue_to_item, parse_item_key, restrict_jql, updated_since, JiraFeed,
+    ASSIGNED_JQL, DONE_CATEGORY, IN_PROGRESS_CATEGORY, PROVIDER_ID,
+};
+
+pub use crate::api::{JiraApiError, JiraClient, Transition};
+
+/// Issues fetched per feed request when the caller sets no limit.
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Jira provider implementing `Provider + HasFeeds`.
+pub struct JiraProvider {
+    client: JiraClient,
+    /// The authenticated user, looked up once for watch/unwatch.
+    myself: OnceCell<api::User>,
+}
+
+impl JiraProvider {
+    /// Create a new provider from a [`JiraProviderConfig`].
+    pub fn new(config: JiraProviderConfig) -> Self {
+        Self::with_client(JiraClient::new(config))
+    }
+
+    /// Create a new provider from a pre-constructed [`JiraClient`].
+    /// Useful for tests that want to inject a custom `reqwest::Client`.
+    pub fn with_client(client: JiraClient) -> Self {
+        Self {
+            client,
+            myself: OnceCell::new(),
+        }
+    }
+
+    /// Borrow the underlying API client (mainly for tests).
+    pub fn client(&self) -> &JiraClient {
+        &self.client
+    }
+
+    fn issue_key(item: &Item) -> Result<&str> {
+        parse_item_key(&item.id).ok_or_else(|| {
+            StreamError::ItemNotFound(format!(
+                "item id is not a Jira issue key: {}",
+                item.id.as_str()
+            ))
+        })
+    }
+
+    /// Move an issue to a status in `category` (`"indeterminate"` for In
+    /// Progress, `"done"` for Done).
+    ///
+    /// Workflows differ between projects, so the transition is picked from
+    /// those currently available by where it leads. When several lead into
+    /// the category, the one whose target status is named `preferred` wins.
+    /// Returns the transition that was made.
+    pub async fn transition_to(
+        &self,
+        item: &Item,
+        category: &str,
+        preferred: &str,
+    ) -> Result<Transition> {
+        let key = Self::issue_key(item)?;
+        let candidates: Vec<Transition> = self
+            .client
+            .transitions(key)
+            .await?
+            .into_iter()
+            .filter(|t| t.to.category() == Some(category))
+            .collect();
+        let transition = candidates
+            .iter()
+            .find(|t| t.to.name.eq_ignore_ascii_case(preferred))
+            .or_else(|| candidates.first())
+            .cloned()
+            .ok_or_else(|| {
+                StreamError::Provider(format!("{key} has no transition to {preferred}"))
+            })?;
+
+        self.client.transition(key, &transition.id).await?;
+        Ok(transition)
+    }
+
+    /// Post `text` as a comment on an issue.
+    pub async fn add_comment(&self, item: &Item, text: &str) -> Result<()> {
+        let key = Self::issue_key(item)?;
+        if text.trim().is_empty() {
+            return Err(StreamError::Provider("Comment is empty".to_string()));
+        }
+        self.client.add_comment(key, text).await?;
+        Ok(())
+    }
+
+    /// Start or stop watching an issue as the authenticated user.
+    pub async fn set_watching(&self, item: &Item, watching: bool) -> Result<()> {
+        let key = Self::issue_key(item)?;
+        let user = self.myself.get_or_try_init(|| self.client.myself()).await?;
+        if watching {
+            self.client.add_watcher(key, user).await?;
+        } else {
+            self.client.remove_watcher(key, user).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Provider for JiraProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Jira"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        match self.client.myself().await {
+            Ok(user) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!("Connected to Jira as {}", user.display_name)),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("Jira health check failed: {e}")),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = Instant::now();
+        // Issues are fetched per feed; a sync only counts what is assigned.
+        match self.client.search(ASSIGNED_JQL, 0, 0).await {
+            Ok(page) => Ok(SyncResult {
+                success: true,
+                items_added: page.total,
+                items_updated: 0,
+                items_removed: 0,
+                errors: Vec::new(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+            Err(e) => Ok(SyncResult {
+                success: false,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![e.to_string()],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let category = item
+            .metadata
+            .get("jira_status_category")
+            .map(String::as_str);
+        let watching = item.metadata.get("jira_watching").map(String::as_str) == Some("true");
+
+        let mut actions = vec![
+            Action {
+                id: "open_browser".to_string(),
+                name: "Open in Browser".to_string(),
+                description: "Open the issue in Jira".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the issue URL to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+        ];
+        if category != Some(IN_PROGRESS_CATEGORY) && category != Some(DONE_CATEGORY) {
+            actions.push(Action {
+                id: "start_progress".to_string(),
+                name: "Start Progress".to_string(),
+                description: "Move the issue to In Progress".to_string(),
+                kind: ActionKind::Custom("start_progress".to_string()),
+                keyboard_shortcut: None,
+            });
+        }
+        if category != Some(DONE_CATEGORY) {
+            actions.push(Action {
+                id: "done".to_string(),
+                name: "Mark Done".to_string(),
+                description: "Move the issue to Done".to_string(),
+                kind: ActionKind::Custom("done".to_string()),
+                keyboard_shortcut: None,
+            });
+        }
+        actions.push(Action {
+            id: "comment".to_string(),
+            name: "Add Comment".to_string(),
+            description: "Post a comment on the issue".to_string(),
+            kind: ActionKind::Custom("comment".to_string()),
+            keyboard_shortcut: None,
+        });
+        actions.push(if watching {
+            Action {
+                id: "unwatch".to_string(),
+                name: "Stop Watching".to_string(),
+                description: "Stop receiving notifications about the issue".to_string(),
+                kind: ActionKind::Custom("unwatch".to_string()),
+                keyboard_shortcut: None,
+            }
+        } else {
+            Action {
+                id: "watch".to_string(),
+                name: "Watch".to_string(),
+                description: "Receive notifications about the issue".to_string(),
+                kind: ActionKind::Custom("watch".to_string()),
+                keyboard_shortcut: None,
+            }
+        });
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match action.kind {
+            ActionKind::OpenInBrowser => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some(format!("Opening {url}")),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::CopyLink => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some("Copied link to clipboard".to_string()),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::Custom(ref custom) if custom == "start_progress" || custom == "done" => {
+                let (category, preferred) = if custom == "done" {
+                    (DONE_CATEGORY, "Done")
+                } else {
+                    (IN_PROGRESS_CATEGORY, "In Progress")
+                };
+                let transition = self.transition_to(item, category, preferred).await?;
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(format!("Moved to {}", transition.to.name)),
+                    data: Some(serde_json::json!({
+                        "transition_id": transition.id,
+                        "status": transition.to.name,
+                    })),
+                })
+            }
+            // The client prompts for the text and sends it with `add_comment`
+            ActionKind::Custom(ref custom) if custom == "comment" => Ok(ActionResult {
+                success: true,
+                message: Some("Enter your comment:".to_string()),
+                data: Some(serde_json::json!({
+                    "issue_key": Self::issue_key(item)?,
+                    "requires_input": true,
+                    "input_type": "text",
+                })),
+            }),
+            ActionKind::Custom(ref custom) if custom == "watch" || custom == "unwatch" => {
+                let watching = custom == "watch";
+                self.set_watching(item, watching).await?;
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(if watching {
+                        "Watching issue".to_string()
+                    } else {
+                        "Stopped watching issue".to_string()
+                    }),
+                    data: None,
+                })
+            }
+            _ => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Executed action: {}", action.name)),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for JiraProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let mut feeds = vec![Feed {
+            id: JiraFeed::Assigned.feed_id(),
+            name: "Assigned to me".to_string(),
+            description: Some("Unresolved issues assigned to you".to_string()),
+            icon: Some("📋".to_string()),
+            unread_count: None,
+            total_count: None,
+        }];
+        let filters = self.client.favourite_filters().await?;
+        feeds.extend(filters.iter().map(filter_to_feed));
+        Ok(feeds)
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let feed = JiraFeed::parse(feed_id)
+            .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+
+        let mut jql = match &feed {
+            JiraFeed::Assigned => ASSIGNED_JQL.to_string(),
+            JiraFeed::Filter(id) => self.client.filter(id).await?.jql,
+        };
+        if let Some(since) = options.since {
+            jql = restrict_jql(&jql, &updated_since(since));
+        }
+
+        // Jira has no read state, so `include_read` does not narrow the feed
+        let page = self
+            .client
+            .search(
+                &jql,
+                options.offset.unwrap_or(0),
+                options.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            )
+            .await?;
+        let stream_id = feed.stream_id();
+        Ok(page
+            .issues
+            .iter()
+            .map(|issue| {
+                issue_to_item(issue, stream_id.clone(), self.client.browse_url(&issue.key))
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// Tests (unit only — wiremock-driven integration tests live in tests/)
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> JiraProvider {
+        JiraProvider::new(JiraProviderConfig::server(
+            "http://localhost:0",
+            "test-token",
+        ))
+    }
+
+    fn issue_item(metadata: &[(&str, &str)]) -> Item {
+        Item {
+            id: ItemId::new(PROVIDER_ID, "PROJ-1"),
+            stream_id: JiraFeed::Assigned.stream_id(),
+            title: "PROJ-1: Test".to_string(),
+            content: ItemContent::Task {
+                body: None,
+                due_date: None,
+                is_completed: false,
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("http://localhost:0/browse/PROJ-1".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn action_ids(actions: Vec<Action>) -> Vec<String> {
+        actions.into_iter().map(|a| a.id).collect()
+    }
+
+    #[test]
+    fn provider_basics() {
+        let p = provider();
+        assert_eq!(p.id(), "jira");
+        assert_eq!(p.name(), "Jira");
+        let caps = p.capabilities();
+        assert!(caps.has_feeds);
+        assert!(caps.supports_write_actions);
+        assert!(!caps.has_saved_items);
+    }
+
+    #[tokio::test]
+    async fn actions_follow_issue_state() {
+        let p = provider();
+
+        let open = p
+            .available_actions(&issue_item(&[("jira_status_category", "new")]))
+            .await
+            .unwrap();
+        assert_eq!(
+            action_ids(open),
+            vec![
+                "open_browser",
+                "copy_link",
+                "start_progress",
+                "done",
+                "comment",
+                "watch"
+            ]
+        );
+
+        let started = p
+            .available_actions(&issue_item(&[
+                ("jira_status_category", "indeterminate"),
+                ("jira_watching", "true"),
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(
+            action_ids(started),
+            vec!["open_browser", "copy_link", "done", "comment", "unwatch"]
+        );
+    }
+
+    #[tokio::test]
+    async fn comment_action_asks_for_text() {
+        let p = provider();
+        let action = Action {
+            id: "comment".to_string(),
+            name: "Add Comment".to_string(),
+            description: String::new(),
+            kind: ActionKind::Custom("comment".to_string()),
+            keyboard_shortcut: None,
+        };
+        let result = p.execute_action(&issue_item(&[]), &action).await.unwrap();
+        let data = result.data.unwrap();
+        assert_eq!(data["issue_key"], "PROJ-1");
+        assert_eq!(data["requires_input"], true);
+    }
+}
@@ -0,0 +1,267 @@
+//! Conversion between Jira API types and Scryforge domain types.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::prelude::*;
+use std::collections::HashMap;
+
+use crate::api;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+pub(crate) const PROVIDER_ID: &str = "jira";
+
+/// Local id of the "Assigned to me" feed.
+pub(crate) const ASSIGNED_FEED: &str = "assigned";
+
+/// JQL behind the "Assigned to me" feed.
+pub(crate) const ASSIGNED_JQL: &str =
+    "assignee = currentUser() AND resolution = Unresolved ORDER BY updated DESC";
+
+/// Status category of finished issues.
+pub(crate) const DONE_CATEGORY: &str = "done";
+
+/// Status category of issues being worked on.
+pub(crate) const IN_PROGRESS_CATEGORY: &str = "indeterminate";
+
+/// The feeds a Jira account exposes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum JiraFeed {
+    /// Unresolved issues assigned to the user.
+    Assigned,
+    /// A saved JQL filter, by filter id.
+    Filter(String),
+}
+
+impl JiraFeed {
+    /// Public `FeedId`: `jira:assigned` or `jira:filter:<id>`.
+    pub(crate) fn feed_id(&self) -> FeedId {
+        match self {
+            Self::Assigned => FeedId(format!("{PROVIDER_ID}:{ASSIGNED_FEED}")),
+            Self::Filter(id) => FeedId(format!("{PROVIDER_ID}:filter:{id}")),
+        }
+    }
+
+    /// Stream the feed's items are filed under.
+    pub(crate) fn stream_id(&self) -> StreamId {
+        match self {
+            Self::Assigned => StreamId::new(PROVIDER_ID, "feed", ASSIGNED_FEED),
+            Self::Filter(id) => StreamId::new(PROVIDER_ID, "filter", id),
+        }
+    }
+
+    /// Decode a `FeedId` produced by [`JiraFeed::feed_id`].
+    pub(crate) fn parse(id: &FeedId) -> Option<Self> {
+        let local = id.0.strip_prefix("jira:")?;
+        if local == ASSIGNED_FEED {
+            return Some(Self::Assigned);
+        }
+        local
+            .strip_prefix("filter:")
+            .filter(|filter_id| !filter_id.is_empty())
+            .map(|filter_id| Self::Filter(filter_id.to_string()))
+    }
+}
+
+/// Decode a `jira:<KEY>` `ItemId` back into the issue key.
+pub(crate) fn parse_item_key(id: &ItemId) -> Option<&str> {
+    id.0.strip_prefix("jira:").filter(|key| !key.is_empty())
+}
+
+/// Narrow `jql` with an extra `clause`, keeping any `ORDER BY` at the end.
+pub(crate) fn restrict_jql(jql: &str, clause: &str) -> String {
+    let (query, order) = match jql.to_ascii_lowercase().rfind("order by") {
+        Some(at) => (jql[..at].trim(), Some(jql[at..].trim())),
+        None => (jql.trim(), None),
+    };
+    let restricted = if query.is_empty() {
+        clause.to_string()
+    } else {
+        format!("({query}) AND {clause}")
+    };
+    match order {
+        Some(order) => format!("{restricted} {order}"),
+        None => restricted,
+    }
+}
+
+/// JQL clause for issues updated since `since`.
+///
+/// JQL dates have minute precision and are read in the user's Jira time zone,
+/// so this can include a few issues from just before `since`.
+pub(crate) fn updated_since(since: DateTime<Utc>) -> String {
+    format!("updated >= \"{}\"", since.format("%Y-%m-%d %H:%M"))
+}
+
+/// Parse a Jira timestamp such as `2024-01-01T12:00:00.000+0000`.
+pub(crate) fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Convert a saved [`api::Filter`] into a Scryforge [`Feed`].
+pub fn filter_to_feed(filter: &api::Filter) -> Feed {
+    Feed {
+        id: JiraFeed::Filter(filter.id.clone()).feed_id(),
+        name: filter.name.clone(),
+        description: Some(
+            filter
+                .description
+                .clone()
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| filter.jql.clone()),
+        ),
+        icon: Some("🔎".to_string()),
+        unread_count: None,
+        total_count: None,
+    }
+}
+
+/// Convert a Jira [`api::Issue`] into a Scryforge [`Item`].
+///
+/// Issues become tasks: the description is the body, and an issue counts as
+/// completed once its status is in the "done" category. `browse_url` is the
+/// issue's page in the Jira web UI.
+pub fn issue_to_item(issue: &api::Issue, stream_id: StreamId, browse_url: String) -> Item {
+    let fields = &issue.fields;
+    let status_category = fields.status.as_ref().and_then(|s| s.category());
+
+    let mut metadata = HashMap::new();
+    metadata.insert("jira_key".to_string(), issue.key.clone());
+    if let Some(status) = &fields.status {
+        metadata.insert("jira_status".to_string(), status.name.clone());
+    }
+    if let Some(category) = status_category {
+        metadata.insert("jira_status_category".to_string(), category.to_string());
+    }
+    if let Some(priority) = &fields.priority {
+        metadata.insert("jira_priority".to_string(), priority.name.clone());
+    }
+    if let Some(issue_type) = &fields.issuetype {
+        metadata.insert("jira_issue_type".to_string(), issue_type.name.clone());
+    }
+    if let Some(project) = &fields.project {
+        metadata.insert("jira_project".to_string(), project.key.clone());
+    }
+    if let Some(assignee) = &fields.assignee {
+        metadata.insert("jira_assignee".to_string(), assignee.display_name.clone());
+    }
+    if let Some(watches) = &fields.watches {
+        metadata.insert("jira_watching".to_string(), watches.is_watching.to_string());
+    }
+
+    let title = if fields.summary.is_empty() {
+        issue.key.clone()
+    } else {
+        format!("{}: {}", issue.key, fields.summary)
+    };
+
+    Item {
+        id: ItemId::new(PROVIDER_ID, &issue.key),
+        stream_id,
+        title,
+        content: ItemContent::Task {
+            body: fields.description.clone().filter(|d| !d.is_empty()),
+            due_date: fields.duedate,
+            is_completed: status_category == Some(DONE_CATEGORY),
+        },
+        author: fields.reporter.as_ref().map(|reporter| Author {
+            name: reporter.display_name.clone(),
+            email: reporter.email_address.clone(),
+            url: None,
+            avatar_url: None,
+        }),
+        published: fields.created.as_deref().and_then(parse_timestamp),
+        updated: fields.updated.as_deref().and_then(parse_timestamp),
+        url: Some(browse_url),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: fields.labels.clone(),
+        metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_issue() -> api::Issue {
+        serde_json::from_value(serde_json::json!({
+            "id": "10001",
+            "key": "PROJ-7",
+            "fields": {
+                "summary": "Fix the login page",
+                "description": "It crashes on submit",
+                "status": {"name": "Done", "statusCategory": {"key": "done"}},
+                "priority": {"name": "High"},
+                "issuetype": {"name": "Bug"},
+                "project": {"key": "PROJ", "name": "Project"},
+                "assignee": {"accountId": "abc", "displayName": "Ada"},
+                "reporter": {"accountId": "def", "displayName": "Grace"},
+                "labels": ["frontend"],
+                "duedate": "2024-02-01",
+                "created": "2024-01-01T12:00:00.000+0000",
+                "updated": "2024-01-02T08:30:00.000+0100",
+                "watches": {"isWatching": true, "watchCount": 2}
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn issue_maps_to_task_item() {
+        let item = issue_to_item(
+            &sample_issue(),
+            JiraFeed::Assigned.stream_id(),
+            "https://jira.example.com/browse/PROJ-7".to_string(),
+        );
+
+        assert_eq!(item.id.as_str(), "jira:PROJ-7");
+        assert_eq!(item.stream_id.as_str(), "jira:feed:assigned");
+        assert_eq!(item.title, "PROJ-7: Fix the login page");
+        assert!(matches!(
+            item.content,
+            ItemContent::Task {
+                is_completed: true,
+                due_date: Some(_),
+                ..
+            }
+        ));
+        assert_eq!(item.author.unwrap().name, "Grace");
+        assert_eq!(
+            item.updated.unwrap().to_rfc3339(),
+            "2024-01-02T07:30:00+00:00"
+        );
+        assert_eq!(item.tags, vec!["frontend".to_string()]);
+        assert_eq!(item.metadata["jira_status"], "Done");
+        assert_eq!(item.metadata["jira_priority"], "High");
+        assert_eq!(item.metadata["jira_watching"], "true");
+    }
+
+    #[test]
+    fn feed_ids_round_trip() {
+        for feed in [JiraFeed::Assigned, JiraFeed::Filter("10200".to_string())] {
+            assert_eq!(JiraFeed::parse(&feed.feed_id()), Some(feed));
+        }
+        assert_eq!(JiraFeed::parse(&FeedId("jira:filter:".to_string())), None);
+        assert_eq!(JiraFeed::parse(&FeedId("rss:assigned".to_string())), None);
+    }
+
+    #[test]
+    fn restrict_jql_keeps_ordering_last() {
+        assert_eq!(
+            restrict_jql(ASSIGNED_JQL, "updated >= \"2024-01-01 00:00\""),
+            "(assignee = currentUser() AND resolution = Unresolved) AND \
+             updated >= \"2024-01-01 00:00\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            restrict_jql("project = PROJ", "x = 1"),
+            "(project = PROJ) AND x = 1"
+        );
+        assert_eq!(
+            restrict_jql("order by created", "x = 1"),
+            "x = 1 order by created"
+        );
+    }
+}
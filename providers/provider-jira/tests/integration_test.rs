@@ -0,0 +1,292 @@
+//! Wiremock-driven integration tests for `provider-jira`.
+//!
+//! These tests stand up a `wiremock::MockServer` to impersonate the Jira REST
+//! API and exercise the trait surface end-to-end against canned responses.
+
+use provider_jira::{JiraProvider, JiraProviderConfig};
+use scryforge_provider_core::prelude::*;
+use serde_json::json;
+use wiremock::matchers::{body_json, body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider_for(server: &MockServer) -> JiraProvider {
+    JiraProvider::new(JiraProviderConfig::cloud(
+        server.uri(),
+        "me@example.com",
+        "test-token",
+    ))
+}
+
+fn issue_json(key: &str, status: &str, category: &str) -> serde_json::Value {
+    json!({
+        "id": "10001",
+        "key": key,
+        "fields": {
+            "summary": "Fix the login page",
+            "description": "It crashes on submit",
+            "status": {"name": status, "statusCategory": {"key": category}},
+            "labels": [],
+            "updated": "2024-01-02T08:30:00.000+0000",
+            "watches": {"isWatching": false, "watchCount": 0}
+        }
+    })
+}
+
+fn issue_item(key: &str) -> Item {
+    Item {
+        id: ItemId::new("jira", key),
+        stream_id: StreamId::new("jira", "feed", "assigned"),
+        title: key.to_string(),
+        content: ItemContent::Task {
+            body: None,
+            due_date: None,
+            is_completed: false,
+        },
+        author: None,
+        published: None,
+        updated: None,
+        url: None,
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: vec![],
+        metadata: Default::default(),
+    }
+}
+
+fn custom_action(id: &str) -> Action {
+    Action {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: String::new(),
+        kind: ActionKind::Custom(id.to_string()),
+        keyboard_shortcut: None,
+    }
+}
+
+#[tokio::test]
+async fn list_feeds_includes_assigned_and_favourite_filters() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/filter/favourite"))
+        // base64("me@example.com:test-token")
+        .and(header(
+            "Authorization",
+            "Basic bWVAZXhhbXBsZS5jb206dGVzdC10b2tlbg==",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": "10200", "name": "Team bugs", "jql": "project = PROJ AND type = Bug"}
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let feeds = provider_for(&server).list_feeds().await.unwrap();
+    assert_eq!(feeds.len(), 2);
+    assert_eq!(feeds[0].id.0, "jira:assigned");
+    assert_eq!(feeds[1].id.0, "jira:filter:10200");
+    assert_eq!(feeds[1].name, "Team bugs");
+    assert_eq!(
+        feeds[1].description.as_deref(),
+        Some("project = PROJ AND type = Bug")
+    );
+}
+
+#[tokio::test]
+async fn filter_feed_runs_saved_jql() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/filter/10200"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "10200",
+            "name": "Team bugs",
+            "jql": "project = PROJ ORDER BY priority DESC"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/search"))
+        .and(query_param(
+            "jql",
+            "(project = PROJ) AND updated >= \"2024-01-01 00:00\" ORDER BY priority DESC",
+        ))
+        .and(query_param("startAt", "10"))
+        .and(query_param("maxResults", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "startAt": 10,
+            "maxResults": 5,
+            "total": 11,
+            "issues": [issue_json("PROJ-7", "To Do", "new")]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = FeedOptions {
+        limit: Some(5),
+        offset: Some(10),
+        since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        include_read: false,
+        low_bandwidth: false,
+    };
+    let items = provider_for(&server)
+        .get_feed_items(&FeedId("jira:filter:10200".to_string()), options)
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item.id.as_str(), "jira:PROJ-7");
+    assert_eq!(item.stream_id.as_str(), "jira:filter:10200");
+    assert_eq!(
+        item.url.as_deref(),
+        Some(format!("{}/browse/PROJ-7", server.uri()).as_str())
+    );
+    assert!(matches!(
+        item.content,
+        ItemContent::Task {
+            is_completed: false,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn unknown_feed_is_stream_not_found() {
+    let server = MockServer::start().await;
+    let err = provider_for(&server)
+        .get_feed_items(&FeedId("jira:bogus".to_string()), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::StreamNotFound(_)));
+}
+
+#[tokio::test]
+async fn done_action_picks_transition_by_status_category() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/issue/PROJ-7/transitions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "transitions": [
+                {"id": "11", "name": "Start", "to": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}}},
+                {"id": "21", "name": "Won't do", "to": {"name": "Closed", "statusCategory": {"key": "done"}}},
+                {"id": "31", "name": "Resolve", "to": {"name": "Done", "statusCategory": {"key": "done"}}}
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/2/issue/PROJ-7/transitions"))
+        .and(body_json(json!({"transition": {"id": "31"}})))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = provider_for(&server)
+        .execute_action(&issue_item("PROJ-7"), &custom_action("done"))
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.message.as_deref(), Some("Moved to Done"));
+}
+
+#[tokio::test]
+async fn missing_transition_is_an_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/issue/PROJ-7/transitions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"transitions": []})))
+        .mount(&server)
+        .await;
+
+    let err = provider_for(&server)
+        .execute_action(&issue_item("PROJ-7"), &custom_action("start_progress"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::Provider(_)));
+}
+
+#[tokio::test]
+async fn add_comment_posts_plain_text() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/rest/api/2/issue/PROJ-7/comment"))
+        .and(body_partial_json(json!({"body": "Looking into it"})))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(json!({"id": "500", "body": "Looking into it"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    provider
+        .add_comment(&issue_item("PROJ-7"), "Looking into it")
+        .await
+        .unwrap();
+    assert!(provider
+        .add_comment(&issue_item("PROJ-7"), "  ")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn watch_and_unwatch_use_account_id() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/myself"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "accountId": "5b10ac8d82e05b22cc7d4ef5",
+            "displayName": "Ada"
+        })))
+        // The user is looked up once and reused
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/2/issue/PROJ-7/watchers"))
+        .and(body_json(json!("5b10ac8d82e05b22cc7d4ef5")))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/rest/api/2/issue/PROJ-7/watchers"))
+        .and(query_param("accountId", "5b10ac8d82e05b22cc7d4ef5"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let item = issue_item("PROJ-7");
+    for action in ["watch", "unwatch"] {
+        let result = provider
+            .execute_action(&item, &custom_action(action))
+            .await
+            .unwrap();
+        assert!(result.success);
+    }
+}
+
+#[tokio::test]
+async fn unauthorized_maps_to_auth_required() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/2/filter/favourite"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let err = provider_for(&server).list_feeds().await.unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)));
+}
@@ -0,0 +1,378 @@
+//! Typed Linkding REST API client.
+//!
+//! This module is a thin wrapper around `reqwest` that exposes only the subset
+//! of the Linkding API surface used by [`LinkdingProvider`](crate::LinkdingProvider).
+//!
+//! All requests authenticate via an `Authorization: Token <token>` header.
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Bookmarks requested per page when following a listing.
+const PAGE_SIZE: u32 = 100;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors raised by the Linkding API client.
+#[derive(Debug, Error)]
+pub enum LinkdingApiError {
+    /// Underlying transport error (DNS, TLS, connection refused, decode, ...).
+    #[error("HTTP transport error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// 401 Unauthorized or 403 Forbidden — a missing or revoked API token.
+    #[error("Unauthorized (check API token)")]
+    Unauthorized,
+
+    /// 404 Not Found — the bookmark id does not exist.
+    #[error("Not found")]
+    NotFound,
+
+    /// 429 Too Many Requests.
+    #[error("Rate limited")]
+    RateLimited,
+
+    /// Generic non-success response with the body text.
+    #[error("Linkding API error ({status}): {body}")]
+    Status { status: u16, body: String },
+
+    /// JSON serialization/deserialization error.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+// ============================================================================
+// Request and response types (subset)
+// ============================================================================
+
+/// A bookmark as returned by `GET /api/bookmarks/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: i64,
+    pub url: String,
+    /// Title set by the user; empty when Linkding uses `website_title`
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// Markdown notes
+    #[serde(default)]
+    pub notes: String,
+    /// Title scraped from the page
+    #[serde(default)]
+    pub website_title: Option<String>,
+    /// Description scraped from the page
+    #[serde(default)]
+    pub website_description: Option<String>,
+    #[serde(default)]
+    pub favicon_url: Option<String>,
+    #[serde(default)]
+    pub preview_image_url: Option<String>,
+    #[serde(default)]
+    pub is_archived: bool,
+    #[serde(default)]
+    pub unread: bool,
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default)]
+    pub tag_names: Vec<String>,
+    pub date_added: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+}
+
+/// One page of a bookmark listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkPage {
+    pub count: i64,
+    /// URL of the next page, if there is one
+    pub next: Option<String>,
+    #[serde(default)]
+    pub results: Vec<Bookmark>,
+}
+
+/// Response of `GET /api/bookmarks/check/`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckResponse {
+    /// The bookmark for the URL, if there is one
+    pub bookmark: Option<Bookmark>,
+}
+
+/// Body of `POST /api/bookmarks/`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NewBookmark {
+    pub url: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub tag_names: Vec<String>,
+    pub unread: bool,
+    pub shared: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct UnreadPatch {
+    unread: bool,
+}
+
+/// Filter knobs for `GET /api/bookmarks/`.
+#[derive(Debug, Clone, Default)]
+pub struct BookmarkQuery {
+    /// List archived bookmarks instead of active ones
+    pub archived: bool,
+    /// Search phrase in Linkding's syntax, e.g. `rust #tools !unread`
+    pub q: Option<String>,
+    /// Most bookmarks to return; every page is followed when unset
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// Minimal typed Linkding API client.
+///
+/// Re-uses a single `reqwest::Client` across calls so connections are pooled.
+#[derive(Debug, Clone)]
+pub struct LinkdingClient {
+    base_url: String,
+    api_token: String,
+    http: Client,
+}
+
+impl LinkdingClient {
+    /// Build a new client. `base_url` must be the server root; trailing slashes
+    /// are normalised away.
+    pub fn new(base_url: impl Into<String>, api_token: impl Into<String>) -> Self {
+        let http = Client::builder()
+            .user_agent("scryforge-provider-linkding/0.1.0")
+            .build()
+            .unwrap_or_default();
+        Self::with_http(base_url, api_token, http)
+    }
+
+    /// Build a new client with an explicit `reqwest::Client` (mainly for tests).
+    pub fn with_http(
+        base_url: impl Into<String>,
+        api_token: impl Into<String>,
+        http: Client,
+    ) -> Self {
+        let base = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            base_url: base,
+            api_token: api_token.into(),
+            http,
+        }
+    }
+
+    /// Server base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        request.header("Authorization", format!("Token {}", self.api_token))
+    }
+
+    async fn check_status(
+        response: reqwest::Response,
+    ) -> std::result::Result<reqwest::Response, LinkdingApiError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(LinkdingApiError::Unauthorized),
+            StatusCode::NOT_FOUND => Err(LinkdingApiError::NotFound),
+            StatusCode::TOO_MANY_REQUESTS => Err(LinkdingApiError::RateLimited),
+            other => {
+                let body = response
+                    .text()
+                    .await
+                    .unwrap_or_else(|_| "<unreadable body>".to_string());
+                Err(LinkdingApiError::Status {
+                    status: other.as_u16(),
+                    body,
+                })
+            }
+        }
+    }
+
+    async fn send_json<T>(
+        &self,
+        request: RequestBuilder,
+    ) -> std::result::Result<T, LinkdingApiError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let response = self.authorized(request).send().await?;
+        let response = Self::check_status(response).await?;
+        Ok(response.json::<T>().await?)
+    }
+
+    async fn send_empty(
+        &self,
+        request: RequestBuilder,
+    ) -> std::result::Result<(), LinkdingApiError> {
+        let response = self.authorized(request).send().await?;
+        Self::check_status(response).await?;
+        Ok(())
+    }
+
+    /// `GET /api/user/profile/` — the authenticated user's preferences.
+    pub async fn profile(&self) -> std::result::Result<serde_json::Value, LinkdingApiError> {
+        self.send_json(self.http.get(self.url("/api/user/profile/")))
+            .await
+    }
+
+    /// `GET /api/bookmarks/` (or `/api/bookmarks/archived/`), following pages
+    /// until `query.limit` bookmarks are read.
+    pub async fn list_bookmarks(
+        &self,
+        query: &BookmarkQuery,
+    ) -> std::result::Result<Vec<Bookmark>, LinkdingApiError> {
+        let path = if query.archived {
+            "/api/bookmarks/archived/"
+        } else {
+            "/api/bookmarks/"
+        };
+
+        let mut bookmarks = Vec::new();
+        let mut offset = query.offset.unwrap_or(0);
+        loop {
+            let wanted = match query.limit {
+                Some(limit) => limit.saturating_sub(bookmarks.len() as u32),
+                None => PAGE_SIZE,
+            };
+            if wanted == 0 {
+                break;
+            }
+            let page_size = wanted.min(PAGE_SIZE);
+
+            let mut params = vec![
+                ("limit", page_size.to_string()),
+                ("offset", offset.to_string()),
+            ];
+            if let Some(q) = &query.q {
+                params.push(("q", q.clone()));
+            }
+            let page: BookmarkPage = self
+                .send_json(self.http.get(self.url(path)).query(&params))
+                .await?;
+
+            let read = page.results.len() as u32;
+            bookmarks.extend(page.results);
+            if page.next.is_none() || read == 0 {
+                break;
+            }
+            offset += read;
+        }
+        Ok(bookmarks)
+    }
+
+    /// `GET /api/bookmarks/<id>/`.
+    pub async fn get_bookmark(&self, id: i64) -> std::result::Result<Bookmark, LinkdingApiError> {
+        self.send_json(self.http.get(self.url(&format!("/api/bookmarks/{}/", id))))
+            .await
+    }
+
+    /// `GET /api/bookmarks/check/` — the bookmark for `url`, if there is one.
+    pub async fn check_url(
+        &self,
+        url: &str,
+    ) -> std::result::Result<Option<Bookmark>, LinkdingApiError> {
+        let response: CheckResponse = self
+            .send_json(
+                self.http
+                    .get(self.url("/api/bookmarks/check/"))
+                    .query(&[("url", url)]),
+            )
+            .await?;
+        Ok(response.bookmark)
+    }
+
+    /// `POST /api/bookmarks/` — create a bookmark.
+    pub async fn create_bookmark(
+        &self,
+        bookmark: &NewBookmark,
+    ) -> std::result::Result<Bookmark, LinkdingApiError> {
+        self.send_json(self.http.post(self.url("/api/bookmarks/")).json(bookmark))
+            .await
+    }
+
+    /// `PATCH /api/bookmarks/<id>/` — mark a bookmark read or unread.
+    pub async fn set_unread(
+        &self,
+        id: i64,
+        unread: bool,
+    ) -> std::result::Result<Bookmark, LinkdingApiError> {
+        self.send_json(
+            self.http
+                .patch(self.url(&format!("/api/bookmarks/{}/", id)))
+                .json(&UnreadPatch { unread }),
+        )
+        .await
+    }
+
+    /// `POST /api/bookmarks/<id>/archive/`.
+    pub async fn archive(&self, id: i64) -> std::result::Result<(), LinkdingApiError> {
+        self.send_empty(
+            self.http
+                .post(self.url(&format!("/api/bookmarks/{}/archive/", id))),
+        )
+        .await
+    }
+
+    /// `POST /api/bookmarks/<id>/unarchive/`.
+    pub async fn unarchive(&self, id: i64) -> std::result::Result<(), LinkdingApiError> {
+        self.send_empty(
+            self.http
+                .post(self.url(&format!("/api/bookmarks/{}/unarchive/", id))),
+        )
+        .await
+    }
+
+    /// `DELETE /api/bookmarks/<id>/`.
+    pub async fn delete_bookmark(&self, id: i64) -> std::result::Result<(), LinkdingApiError> {
+        self.send_empty(
+            self.http
+                .delete(self.url(&format!("/api/bookmarks/{}/", id))),
+        )
+        .await
+    }
+}
+
+// ============================================================================
+// Error mapping
+// ============================================================================
+
+impl From<LinkdingApiError> for scryforge_provider_core::StreamError {
+    fn from(err: LinkdingApiError) -> Self {
+        use scryforge_provider_core::StreamError;
+        match err {
+            LinkdingApiError::Http(e) => StreamError::Network(e.to_string()),
+            LinkdingApiError::Unauthorized => {
+                StreamError::AuthRequired("Linkding API token missing or invalid".to_string())
+            }
+            LinkdingApiError::NotFound => {
+                StreamError::ItemNotFound("Linkding: bookmark not found".to_string())
+            }
+            LinkdingApiError::RateLimited => StreamError::RateLimited(60),
+            LinkdingApiError::Status { status, body } => {
+                StreamError::from_status(status, None, format!("Linkding HTTP {status}: {body}"))
+            }
+            LinkdingApiError::Json(e) => {
+                StreamError::Provider(format!("Linkding JSON decode failed: {e}"))
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Configuration for the Linkding provider.
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the [`LinkdingProvider`](crate::LinkdingProvider).
+///
+/// `server_url` should point at the root of the Linkding server (e.g.
+/// `https://links.example.com`). The `api_token` is shown in the Linkding UI
+/// under Settings → Integrations and is sent to the server in an
+/// `Authorization: Token <token>` header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkdingProviderConfig {
+    /// Base URL of the Linkding server, e.g. `https://links.example.com`.
+    pub server_url: String,
+    /// REST API token.
+    pub api_token: String,
+}
+
+impl LinkdingProviderConfig {
+    /// Create a new configuration from a server URL and API token.
+    pub fn new(server_url: impl Into<String>, api_token: impl Into<String>) -> Self {
+        Self {
+            server_url: server_url.into(),
+            api_token: api_token.into(),
+        }
+    }
+
+    /// Construct a configuration by fetching the API token from Sigilforge.
+    ///
+    /// Looks up the token under the service identifier `"linkding"` and the
+    /// supplied `account` label.
+    #[cfg(feature = "sigilforge")]
+    pub async fn from_sigilforge(
+        token_fetcher: &dyn scryforge_sigilforge_client::TokenFetcher,
+        server_url: impl Into<String>,
+        account: &str,
+    ) -> std::result::Result<Self, scryforge_sigilforge_client::SigilforgeError> {
+        let token = token_fetcher.fetch_token("linkding", account).await?;
+        Ok(Self::new(server_url, token))
+    }
+}
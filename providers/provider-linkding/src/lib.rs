@@ -0,0 +1,551 @@
+//! # provider-linkding
+//!
+//! [Linkding](https://github.com/sissbruecker/linkding) provider for Scryforge.
+//!
+//! This crate implements [`Provider`], [`HasFeeds`], [`HasSavedItems`],
+//! [`HasReadLater`] and [`HasSearch`] by delegating to a self-hosted Linkding
+//! server's REST API. It is the server-backed counterpart to
+//! [`provider-bookmarks`](../provider-bookmarks): Linkding owns the bookmark
+//! collection, and Scryforge becomes a terminal frontend to it.
+//!
+//! Active bookmarks are the saved items, the unread and archived bookmarks are
+//! feeds, and "save for later" from any other provider creates an unread
+//! bookmark on the server.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_linkding::{LinkdingProvider, LinkdingProviderConfig};
+//!
+//! let config = LinkdingProviderConfig::new(
+//!     "https://links.example.com",
+//!     "your-api-token",
+//! );
+//! let provider = LinkdingProvider::new(config);
+//! ```
+//!
+//! ## Sigilforge integration (optional)
+//!
+//! Enable the `sigilforge` cargo feature to fetch the API token from a
+//! [Sigilforge] daemon instead of passing it inline:
+//!
+//! ```no_run
+//! # #[cfg(feature = "sigilforge")]
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use provider_linkding::{LinkdingProvider, LinkdingProviderConfig};
+//! use scryforge_sigilforge_client::MockTokenFetcher;
+//!
+//! let fetcher = MockTokenFetcher::empty();
+//! let config = LinkdingProviderConfig::from_sigilforge(
+//!     &fetcher,
+//!     "https://links.example.com",
+//!     "personal",
+//! ).await?;
+//! let provider = LinkdingProvider::new(config);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Sigilforge]: https://github.com/raibid-labs/sigilforge
+
+pub mod api;
+pub mod config;
+pub mod mapping;
+
+pub use config::LinkdingProviderConfig;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use scryforge_provider_core::prelude::*;
+use std::any::Any;
+use std::time::Instant;
+
+use crate::api::{BookmarkQuery, NewBookmark};
+use crate::mapping::{
+    bookmark_to_item, feed_id, feed_stream_id, parse_item_id, saved_stream_id, ARCHIVED_FEED,
+    PROVIDER_ID, UNREAD_FEED,
+};
+
+pub use crate::api::{LinkdingApiError, LinkdingClient};
+
+/// Linkding provider implementing `Provider + HasFeeds + HasSavedItems +
+/// HasReadLater + HasSearch`.
+pub struct LinkdingProvider {
+    client: LinkdingClient,
+}
+
+impl LinkdingProvider {
+    /// Create a new provider from a [`LinkdingProviderConfig`].
+    pub fn new(config: LinkdingProviderConfig) -> Self {
+        let client = LinkdingClient::new(config.server_url, config.api_token);
+        Self { client }
+    }
+
+    /// Create a new provider from a pre-constructed [`LinkdingClient`].
+    /// Useful for tests that want to inject a custom `reqwest::Client`.
+    pub fn with_client(client: LinkdingClient) -> Self {
+        Self { client }
+    }
+
+    /// Borrow the underlying API client (mainly for tests).
+    pub fn client(&self) -> &LinkdingClient {
+        &self.client
+    }
+
+    fn bookmark_id(item_id: &ItemId) -> Result<i64> {
+        parse_item_id(item_id).ok_or_else(|| {
+            StreamError::ItemNotFound(format!(
+                "item id is not a Linkding bookmark id: {}",
+                item_id.as_str()
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for LinkdingProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Linkding"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        match self.client.profile().await {
+            Ok(_) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!(
+                    "Connected to Linkding at {}",
+                    self.client.base_url()
+                )),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("Linkding health check failed: {e}")),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = Instant::now();
+        // Linkding is the store of record; reading one page of bookmarks is
+        // a sanity probe that the server and token work.
+        let query = BookmarkQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        match self.client.list_bookmarks(&query).await {
+            Ok(_) => Ok(SyncResult {
+                success: true,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: Vec::new(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+            Err(e) => Ok(SyncResult {
+                success: false,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![e.to_string()],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: true,
+            has_communities: false,
+            supports_search: true,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let archived = item.metadata.get("archived").map(String::as_str) == Some("true");
+        Ok(vec![
+            Action {
+                id: "open_browser".to_string(),
+                name: "Open in Browser".to_string(),
+                description: "Open the bookmarked URL in the default browser".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the bookmarked URL to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+            Action {
+                id: "mark_read".to_string(),
+                name: "Mark as Read".to_string(),
+                description: "Mark the bookmark as read on the Linkding server".to_string(),
+                kind: ActionKind::MarkRead,
+                keyboard_shortcut: Some("r".to_string()),
+            },
+            Action {
+                id: "mark_unread".to_string(),
+                name: "Mark as Unread".to_string(),
+                description: "Mark the bookmark as unread on the Linkding server".to_string(),
+                kind: ActionKind::MarkUnread,
+                keyboard_shortcut: Some("u".to_string()),
+            },
+            Action {
+                id: "archive".to_string(),
+                name: if archived { "Unarchive" } else { "Archive" }.to_string(),
+                description: "Move the bookmark into or out of the Linkding archive".to_string(),
+                kind: ActionKind::Archive,
+                keyboard_shortcut: Some("a".to_string()),
+            },
+            Action {
+                id: "delete".to_string(),
+                name: "Delete".to_string(),
+                description: "Delete the bookmark from the Linkding server".to_string(),
+                kind: ActionKind::Delete,
+                keyboard_shortcut: Some("d".to_string()),
+            },
+        ])
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match action.kind {
+            ActionKind::OpenInBrowser => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some(format!("Opening {url}")),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::CopyLink => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some("Copied link to clipboard".to_string()),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::MarkRead | ActionKind::MarkUnread => {
+                let bookmark_id = Self::bookmark_id(&item.id)?;
+                let unread = action.kind == ActionKind::MarkUnread;
+                self.client.set_unread(bookmark_id, unread).await?;
+                let target = if unread { "unread" } else { "read" };
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(format!("Marked bookmark {bookmark_id} as {target}")),
+                    data: Some(serde_json::json!({
+                        "bookmark_id": bookmark_id,
+                        "unread": unread,
+                    })),
+                })
+            }
+            ActionKind::Archive => {
+                let bookmark_id = Self::bookmark_id(&item.id)?;
+                // Archiving toggles, like the Linkding UI
+                let archived = item.metadata.get("archived").map(String::as_str) == Some("true");
+                if archived {
+                    self.client.unarchive(bookmark_id).await?;
+                } else {
+                    self.client.archive(bookmark_id).await?;
+                }
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(format!(
+                        "{} bookmark {bookmark_id}",
+                        if archived { "Unarchived" } else { "Archived" }
+                    )),
+                    data: Some(serde_json::json!({
+                        "bookmark_id": bookmark_id,
+                        "archived": !archived,
+                    })),
+                })
+            }
+            ActionKind::Delete => {
+                let bookmark_id = Self::bookmark_id(&item.id)?;
+                self.client.delete_bookmark(bookmark_id).await?;
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(format!("Deleted bookmark {bookmark_id}")),
+                    data: Some(serde_json::json!({ "bookmark_id": bookmark_id })),
+                })
+            }
+            _ => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Executed action: {}", action.name)),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_read_later(&self) -> Option<&dyn HasReadLater> {
+        Some(self)
+    }
+
+    fn as_search(&self) -> Option<&dyn HasSearch> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for LinkdingProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        Ok(vec![
+            Feed {
+                id: feed_id(UNREAD_FEED),
+                name: "Unread".to_string(),
+                description: Some("Bookmarks marked to read later".to_string()),
+                icon: Some("🔖".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+            Feed {
+                id: feed_id(ARCHIVED_FEED),
+                name: "Archived".to_string(),
+                description: Some("Bookmarks moved to the archive".to_string()),
+                icon: Some("🗄".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+        ])
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let name = feed_id
+            .0
+            .strip_prefix("linkding:")
+            .filter(|name| *name == UNREAD_FEED || *name == ARCHIVED_FEED)
+            .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+
+        let query = BookmarkQuery {
+            archived: name == ARCHIVED_FEED,
+            q: (name == UNREAD_FEED).then(|| "!unread".to_string()),
+            limit: options.limit,
+            offset: options.offset,
+        };
+        let bookmarks = self.client.list_bookmarks(&query).await?;
+        let stream_id = feed_stream_id(name);
+        let items = bookmarks
+            .iter()
+            // The API has no date filter, and lists newest first
+            .filter(|b| match (options.since, b.date_added) {
+                (Some(since), Some(added)) => added >= since,
+                _ => true,
+            })
+            .map(|b| bookmark_to_item(b, stream_id.clone()))
+            .collect();
+        Ok(items)
+    }
+}
+
+#[async_trait]
+impl HasSavedItems for LinkdingProvider {
+    async fn get_saved_items(&self, options: SavedItemsOptions) -> Result<Vec<Item>> {
+        let query = BookmarkQuery {
+            q: options.category.map(|tag| format!("#{tag}")),
+            limit: options.limit,
+            offset: options.offset,
+            ..Default::default()
+        };
+        let bookmarks = self.client.list_bookmarks(&query).await?;
+        let stream_id = saved_stream_id();
+        Ok(bookmarks
+            .iter()
+            .map(|b| bookmark_to_item(b, stream_id.clone()))
+            .collect())
+    }
+
+    async fn is_saved(&self, item_id: &ItemId) -> Result<bool> {
+        let bookmark_id = Self::bookmark_id(item_id)?;
+        match self.client.get_bookmark(bookmark_id).await {
+            Ok(bookmark) => Ok(!bookmark.is_archived),
+            Err(LinkdingApiError::NotFound) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save_item(&self, item_id: &ItemId) -> Result<()> {
+        // Only bookmarks already on the server can be saved by id; saving
+        // brings an archived one back
+        let bookmark_id = Self::bookmark_id(item_id)?;
+        self.client.unarchive(bookmark_id).await?;
+        Ok(())
+    }
+
+    async fn unsave_item(&self, item_id: &ItemId) -> Result<()> {
+        // Archiving keeps the bookmark on the server, as the Linkding UI does
+        let bookmark_id = Self::bookmark_id(item_id)?;
+        self.client.archive(bookmark_id).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HasReadLater for LinkdingProvider {
+    async fn save_for_later(&self, item: &Item) -> Result<ItemId> {
+        let url = item.url.clone().ok_or_else(|| {
+            StreamError::Provider(format!(
+                "Item '{}' has no URL to bookmark",
+                item.id.as_str()
+            ))
+        })?;
+
+        // Saving the same link twice returns the existing bookmark
+        if let Some(existing) = self.client.check_url(&url).await? {
+            return Ok(ItemId::new(PROVIDER_ID, &existing.id.to_string()));
+        }
+
+        let bookmark = self
+            .client
+            .create_bookmark(&NewBookmark {
+                url,
+                title: item.title.clone(),
+                description: item.content.plain_text().unwrap_or_default(),
+                tag_names: item.tags.clone(),
+                unread: true,
+                shared: false,
+            })
+            .await?;
+        Ok(ItemId::new(PROVIDER_ID, &bookmark.id.to_string()))
+    }
+}
+
+#[async_trait]
+impl HasSearch for LinkdingProvider {
+    async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<Item>> {
+        // Linkding's own search syntax (`#tag`, `!unread`) passes through
+        let archived = options.stream_id.as_ref() == Some(&feed_stream_id(ARCHIVED_FEED));
+        let bookmark_query = BookmarkQuery {
+            archived,
+            q: Some(query.to_string()),
+            limit: options.limit,
+            offset: None,
+        };
+        let bookmarks = self.client.list_bookmarks(&bookmark_query).await?;
+        let stream_id = if archived {
+            feed_stream_id(ARCHIVED_FEED)
+        } else {
+            saved_stream_id()
+        };
+
+        let mut items: Vec<Item> = bookmarks
+            .iter()
+            .map(|b| bookmark_to_item(b, stream_id.clone()))
+            .collect();
+        for (rank, item) in items.iter_mut().enumerate() {
+            item.metadata
+                .insert("search_rank".to_string(), (rank + 1).to_string());
+        }
+        Ok(items)
+    }
+}
+
+// ============================================================================
+// Tests (unit only — wiremock-driven integration tests live in tests/)
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider() -> LinkdingProvider {
+        LinkdingProvider::new(LinkdingProviderConfig::new(
+            "http://localhost:0",
+            "test-token",
+        ))
+    }
+
+    fn item(archived: bool) -> Item {
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("archived".to_string(), archived.to_string());
+        Item {
+            id: ItemId::new(PROVIDER_ID, "1"),
+            stream_id: saved_stream_id(),
+            title: "x".to_string(),
+            content: ItemContent::Bookmark { description: None },
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("https://example.com".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: !archived,
+            tags: vec![],
+            metadata,
+        }
+    }
+
+    #[tokio::test]
+    async fn provider_basics() {
+        let p = provider();
+        assert_eq!(p.id(), "linkding");
+        assert_eq!(p.name(), "Linkding");
+        let caps = p.capabilities();
+        assert!(caps.has_feeds);
+        assert!(caps.has_saved_items);
+        assert!(caps.supports_search);
+        assert!(!caps.has_collections);
+        assert!(p.as_read_later().is_some());
+        assert!(p.as_search().is_some());
+    }
+
+    #[tokio::test]
+    async fn archive_action_names_follow_state() {
+        let p = provider();
+        let name_of = |actions: Vec<Action>| {
+            actions
+                .into_iter()
+                .find(|a| a.kind == ActionKind::Archive)
+                .map(|a| a.name)
+        };
+        let active = p.available_actions(&item(false)).await.unwrap();
+        assert_eq!(name_of(active).as_deref(), Some("Archive"));
+        let archived = p.available_actions(&item(true)).await.unwrap();
+        assert_eq!(name_of(archived).as_deref(), Some("Unarchive"));
+    }
+
+    #[tokio::test]
+    async fn unknown_feed_is_stream_not_found() {
+        let p = provider();
+        let err = p
+            .get_feed_items(
+                &FeedId("linkding:starred".to_string()),
+                FeedOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StreamError::StreamNotFound(_)));
+    }
+}
@@ -0,0 +1,185 @@
+//! Conversion between Linkding API types and Scryforge domain types.
+
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::canonicalize_url;
+use std::collections::HashMap;
+
+use crate::api;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+pub(crate) const PROVIDER_ID: &str = "linkding";
+
+/// Local name of the unread feed.
+pub(crate) const UNREAD_FEED: &str = "unread";
+
+/// Local name of the archived feed.
+pub(crate) const ARCHIVED_FEED: &str = "archived";
+
+/// Build the saved-items stream id used for active (non-archived) bookmarks.
+pub(crate) fn saved_stream_id() -> StreamId {
+    StreamId::new(PROVIDER_ID, "saved", "all")
+}
+
+/// Build a stream id of the form `linkding:feed:<name>`.
+pub(crate) fn feed_stream_id(name: &str) -> StreamId {
+    StreamId::new(PROVIDER_ID, "feed", name)
+}
+
+/// Build the public `FeedId` for one of the Linkding feeds.
+pub(crate) fn feed_id(name: &str) -> FeedId {
+    FeedId(format!("linkding:{}", name))
+}
+
+/// Decode a `linkding:<n>` `ItemId` back into the numeric Linkding bookmark id.
+pub(crate) fn parse_item_id(id: &ItemId) -> Option<i64> {
+    id.0.strip_prefix("linkding:")
+        .and_then(|s| s.parse::<i64>().ok())
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Convert a Linkding [`api::Bookmark`] into a Scryforge [`Item`].
+///
+/// Linkding leaves `title` and `description` empty when the user kept the
+/// ones scraped from the page, so those fall back to `website_title` and
+/// `website_description`.
+pub fn bookmark_to_item(bookmark: &api::Bookmark, stream_id: StreamId) -> Item {
+    let id = ItemId::new(PROVIDER_ID, &bookmark.id.to_string());
+
+    let title = non_empty(&bookmark.title)
+        .or_else(|| bookmark.website_title.as_deref().and_then(non_empty))
+        .unwrap_or_else(|| bookmark.url.clone());
+
+    let description = non_empty(&bookmark.description)
+        .or_else(|| bookmark.website_description.as_deref().and_then(non_empty));
+
+    let thumbnail_url = bookmark
+        .preview_image_url
+        .clone()
+        .or_else(|| bookmark.favicon_url.clone());
+
+    let mut metadata = HashMap::new();
+    metadata.insert("linkding_id".to_string(), bookmark.id.to_string());
+    metadata.insert("archived".to_string(), bookmark.is_archived.to_string());
+    metadata.insert("shared".to_string(), bookmark.shared.to_string());
+    if let Some(notes) = non_empty(&bookmark.notes) {
+        metadata.insert("notes".to_string(), notes);
+    }
+
+    Item {
+        id,
+        stream_id,
+        title,
+        content: ItemContent::Bookmark { description },
+        author: None,
+        published: bookmark.date_added,
+        updated: bookmark.date_modified,
+        url: Some(canonicalize_url(&bookmark.url)),
+        thumbnail_url,
+        is_read: !bookmark.unread,
+        is_saved: !bookmark.is_archived,
+        tags: bookmark.tag_names.clone(),
+        metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bookmark() -> api::Bookmark {
+        api::Bookmark {
+            id: 42,
+            url: "https://example.com/post".to_string(),
+            title: "Hello, world".to_string(),
+            description: "A post".to_string(),
+            notes: "Read the second half".to_string(),
+            website_title: Some("Example Post".to_string()),
+            website_description: Some("Scraped description".to_string()),
+            favicon_url: Some("https://links.example.com/static/favicon.png".to_string()),
+            preview_image_url: Some("https://example.com/preview.png".to_string()),
+            is_archived: false,
+            unread: true,
+            shared: false,
+            tag_names: vec!["rust".to_string()],
+            date_added: None,
+            date_modified: None,
+        }
+    }
+
+    #[test]
+    fn bookmark_maps_to_item_with_expected_fields() {
+        let bookmark = sample_bookmark();
+        let item = bookmark_to_item(&bookmark, saved_stream_id());
+
+        assert_eq!(item.id.as_str(), "linkding:42");
+        assert_eq!(item.stream_id.as_str(), "linkding:saved:all");
+        assert_eq!(item.title, "Hello, world");
+        assert_eq!(item.url.as_deref(), Some("https://example.com/post"));
+        assert!(matches!(
+            &item.content,
+            ItemContent::Bookmark { description: Some(d) } if d == "A post"
+        ));
+        assert_eq!(
+            item.thumbnail_url.as_deref(),
+            Some("https://example.com/preview.png")
+        );
+        assert!(!item.is_read);
+        assert!(item.is_saved);
+        assert_eq!(item.tags, vec!["rust".to_string()]);
+        assert_eq!(
+            item.metadata.get("notes").map(|s| s.as_str()),
+            Some("Read the second half")
+        );
+        assert_eq!(
+            item.metadata.get("archived").map(|s| s.as_str()),
+            Some("false")
+        );
+    }
+
+    #[test]
+    fn empty_fields_fall_back_to_scraped_ones() {
+        let mut bookmark = sample_bookmark();
+        bookmark.title = String::new();
+        bookmark.description = " ".to_string();
+        bookmark.notes = String::new();
+        bookmark.preview_image_url = None;
+        let item = bookmark_to_item(&bookmark, saved_stream_id());
+
+        assert_eq!(item.title, "Example Post");
+        assert_eq!(
+            item.content.plain_text().as_deref(),
+            Some("Scraped description")
+        );
+        assert_eq!(
+            item.thumbnail_url.as_deref(),
+            Some("https://links.example.com/static/favicon.png")
+        );
+        assert!(!item.metadata.contains_key("notes"));
+
+        bookmark.website_title = None;
+        let item = bookmark_to_item(&bookmark, saved_stream_id());
+        assert_eq!(item.title, "https://example.com/post");
+    }
+
+    #[test]
+    fn archived_bookmark_is_not_saved() {
+        let mut bookmark = sample_bookmark();
+        bookmark.is_archived = true;
+        bookmark.unread = false;
+        let item = bookmark_to_item(&bookmark, feed_stream_id(ARCHIVED_FEED));
+        assert!(item.is_read);
+        assert!(!item.is_saved);
+        assert_eq!(item.stream_id.as_str(), "linkding:feed:archived");
+    }
+
+    #[test]
+    fn item_id_round_trips() {
+        let id = ItemId::new(PROVIDER_ID, "987");
+        assert_eq!(parse_item_id(&id), Some(987));
+        assert_eq!(parse_item_id(&ItemId("miniflux:987".to_string())), None);
+    }
+}
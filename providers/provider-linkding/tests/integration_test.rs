@@ -0,0 +1,370 @@
+//! Wiremock-driven integration tests for `provider-linkding`.
+//!
+//! These tests stand up a `wiremock::MockServer` to impersonate a Linkding
+//! API and exercise the trait surface end-to-end against canned responses.
+
+use provider_linkding::{LinkdingProvider, LinkdingProviderConfig};
+use scryforge_provider_core::prelude::*;
+use serde_json::json;
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider_for(server: &MockServer) -> LinkdingProvider {
+    LinkdingProvider::new(LinkdingProviderConfig::new(server.uri(), "test-token"))
+}
+
+fn bookmark(id: i64, url: &str, title: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "url": url,
+        "title": title,
+        "description": "",
+        "notes": "",
+        "website_title": null,
+        "website_description": null,
+        "is_archived": false,
+        "unread": false,
+        "shared": false,
+        "tag_names": ["rust"],
+        "date_added": "2024-01-01T12:00:00Z",
+        "date_modified": "2024-01-02T12:00:00Z"
+    })
+}
+
+fn page(results: Vec<serde_json::Value>, next: Option<&str>) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "count": results.len(),
+        "next": next,
+        "previous": null,
+        "results": results
+    }))
+}
+
+#[tokio::test]
+async fn saved_items_follow_pages() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/"))
+        .and(query_param("offset", "0"))
+        .and(header("Authorization", "Token test-token"))
+        .respond_with(page(
+            vec![
+                bookmark(1, "https://example.com/one", "One"),
+                bookmark(2, "https://example.com/two", "Two"),
+            ],
+            Some("https://links.example.com/api/bookmarks/?limit=100&offset=2"),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/"))
+        .and(query_param("offset", "2"))
+        .respond_with(page(
+            vec![bookmark(3, "https://example.com/three", "Three")],
+            None,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let items = provider
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap();
+    let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec!["linkding:1", "linkding:2", "linkding:3"]);
+    assert_eq!(items[0].stream_id.as_str(), "linkding:saved:all");
+    assert!(items.iter().all(|i| i.is_saved));
+}
+
+#[tokio::test]
+async fn saved_items_category_is_a_tag_query() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/"))
+        .and(query_param("q", "#rust"))
+        .and(query_param("limit", "5"))
+        .respond_with(page(vec![bookmark(1, "https://example.com/", "One")], None))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let items = provider
+        .get_saved_items(SavedItemsOptions {
+            limit: Some(5),
+            offset: None,
+            category: Some("rust".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+}
+
+#[tokio::test]
+async fn archived_feed_lists_the_archive() {
+    let server = MockServer::start().await;
+
+    let mut archived = bookmark(9, "https://example.com/old", "Old");
+    archived["is_archived"] = json!(true);
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/archived/"))
+        .respond_with(page(vec![archived], None))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let feeds = provider.list_feeds().await.unwrap();
+    assert_eq!(feeds.len(), 2);
+    let items = provider
+        .get_feed_items(&feeds[1].id, FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].stream_id.as_str(), "linkding:feed:archived");
+    assert!(!items[0].is_saved);
+}
+
+#[tokio::test]
+async fn save_for_later_reuses_existing_bookmark() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/check/"))
+        .and(query_param("url", "https://example.com/known"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "bookmark": bookmark(5, "https://example.com/known", "Known"),
+            "metadata": {}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/bookmarks/"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let item = Item {
+        id: ItemId::new("rss", "abc"),
+        stream_id: StreamId::new("rss", "feed", "1"),
+        title: "Known".to_string(),
+        content: ItemContent::Text(String::new()),
+        author: None,
+        published: None,
+        updated: None,
+        url: Some("https://example.com/known".to_string()),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: vec![],
+        metadata: Default::default(),
+    };
+    let id = provider.save_for_later(&item).await.unwrap();
+    assert_eq!(id.as_str(), "linkding:5");
+}
+
+#[tokio::test]
+async fn save_for_later_creates_unread_bookmark() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/check/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "bookmark": null,
+            "metadata": {}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/bookmarks/"))
+        .and(header("Authorization", "Token test-token"))
+        .and(body_json(json!({
+            "url": "https://example.com/new",
+            "title": "New post",
+            "description": "A summary",
+            "tag_names": ["news"],
+            "unread": true,
+            "shared": false
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(bookmark(
+            77,
+            "https://example.com/new",
+            "New post",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let item = Item {
+        id: ItemId::new("rss", "abc"),
+        stream_id: StreamId::new("rss", "feed", "1"),
+        title: "New post".to_string(),
+        content: ItemContent::Article {
+            summary: Some("A summary".to_string()),
+            full_content: None,
+        },
+        author: None,
+        published: None,
+        updated: None,
+        url: Some("https://example.com/new".to_string()),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: vec!["news".to_string()],
+        metadata: Default::default(),
+    };
+    let id = provider.save_for_later(&item).await.unwrap();
+    assert_eq!(id.as_str(), "linkding:77");
+}
+
+#[tokio::test]
+async fn mark_read_patches_unread_flag() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/api/bookmarks/3/"))
+        .and(body_json(json!({ "unread": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(bookmark(
+            3,
+            "https://example.com/",
+            "Three",
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let item = Item {
+        id: ItemId::new("linkding", "3"),
+        stream_id: StreamId::new("linkding", "saved", "all"),
+        title: "Three".to_string(),
+        content: ItemContent::Bookmark { description: None },
+        author: None,
+        published: None,
+        updated: None,
+        url: Some("https://example.com/".to_string()),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: true,
+        tags: vec![],
+        metadata: Default::default(),
+    };
+    let actions = provider.available_actions(&item).await.unwrap();
+    let mark_read = actions
+        .iter()
+        .find(|a| a.kind == ActionKind::MarkRead)
+        .unwrap();
+    let result = provider.execute_action(&item, mark_read).await.unwrap();
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn unsave_archives_and_save_unarchives() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/bookmarks/4/archive/"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/bookmarks/4/unarchive/"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let id = ItemId::new("linkding", "4");
+    provider.unsave_item(&id).await.unwrap();
+    provider.save_item(&id).await.unwrap();
+}
+
+#[tokio::test]
+async fn is_saved_treats_missing_bookmark_as_unsaved() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/404/"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    assert!(!provider
+        .is_saved(&ItemId::new("linkding", "404"))
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn search_ranks_results() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/"))
+        .and(query_param("q", "rust #tools"))
+        .and(query_param("limit", "10"))
+        .respond_with(page(
+            vec![
+                bookmark(1, "https://example.com/a", "A"),
+                bookmark(2, "https://example.com/b", "B"),
+            ],
+            None,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let items = provider
+        .search(
+            "rust #tools",
+            SearchOptions {
+                limit: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let ranks: Vec<&str> = items
+        .iter()
+        .map(|i| i.metadata["search_rank"].as_str())
+        .collect();
+    assert_eq!(ranks, vec!["1", "2"]);
+}
+
+#[tokio::test]
+async fn unauthorized_maps_to_auth_required() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/bookmarks/"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/user/profile/"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let provider = provider_for(&server);
+    let err = provider
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+    let health = provider.health_check().await.unwrap();
+    assert!(!health.is_healthy);
+}
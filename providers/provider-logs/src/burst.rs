@@ -0,0 +1,104 @@
+//! Grouping log entries into bursts.
+//!
+//! A failing service rarely logs one error: it logs the same few lines
+//! every retry. Entries at or above the severity threshold that follow each
+//! other within the burst gap become one [`Burst`], which becomes one item.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::severity::Severity;
+
+/// One entry read from a journal or log file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub severity: Severity,
+    /// The entry's text, continuation lines included
+    pub message: String,
+}
+
+/// Entries that followed each other closely, oldest first. Never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Burst {
+    pub entries: Vec<LogEntry>,
+}
+
+impl Burst {
+    pub fn first(&self) -> &LogEntry {
+        &self.entries[0]
+    }
+
+    pub fn last(&self) -> &LogEntry {
+        &self.entries[self.entries.len() - 1]
+    }
+
+    /// The most severe entry's severity.
+    pub fn severity(&self) -> Severity {
+        self.entries
+            .iter()
+            .map(|entry| entry.severity)
+            .min()
+            .unwrap_or(Severity::Error)
+    }
+}
+
+/// Group the entries at least `min_severity` into bursts, oldest first.
+///
+/// A burst ends when the next matching entry comes more than `gap` after
+/// the burst's last one; entries below the threshold in between don't end
+/// it.
+pub fn group_bursts(entries: &[LogEntry], min_severity: Severity, gap: Duration) -> Vec<Burst> {
+    let mut matching: Vec<&LogEntry> = entries
+        .iter()
+        .filter(|entry| entry.severity.is_at_least(min_severity))
+        .collect();
+    matching.sort_by_key(|entry| entry.timestamp);
+
+    let mut bursts: Vec<Burst> = Vec::new();
+    for entry in matching {
+        match bursts.last_mut() {
+            Some(burst) if entry.timestamp - burst.last().timestamp <= gap => {
+                burst.entries.push(entry.clone());
+            }
+            _ => bursts.push(Burst {
+                entries: vec![entry.clone()],
+            }),
+        }
+    }
+    bursts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(minute: u32, severity: Severity, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: format!("2024-05-01T12:{:02}:00Z", minute).parse().unwrap(),
+            severity,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_group_bursts() {
+        let entries = vec![
+            entry(0, Severity::Error, "connect failed"),
+            entry(1, Severity::Info, "retrying"),
+            entry(2, Severity::Error, "connect failed"),
+            entry(3, Severity::Critical, "giving up"),
+            entry(30, Severity::Warning, "slow request"),
+            entry(45, Severity::Error, "connect failed"),
+        ];
+        let bursts = group_bursts(&entries, Severity::Error, Duration::minutes(5));
+
+        assert_eq!(bursts.len(), 2);
+        assert_eq!(bursts[0].entries.len(), 3);
+        assert_eq!(bursts[0].severity(), Severity::Critical);
+        assert_eq!(bursts[0].last().message, "giving up");
+        assert_eq!(bursts[1].entries.len(), 1);
+
+        let with_warnings = group_bursts(&entries, Severity::Warning, Duration::minutes(5));
+        assert_eq!(with_warnings.len(), 3);
+    }
+}
@@ -0,0 +1,177 @@
+//! Configuration for the logs provider.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::severity::Severity;
+
+/// What a [`LogSource`] reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LogTarget {
+    /// A systemd unit's journal, read with `journalctl --unit`.
+    Journald { unit: String },
+    /// A plain-text log file, of which only the tail is read.
+    File { path: PathBuf },
+}
+
+/// One log to watch; each becomes a feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogSource {
+    /// Feed name. Defaults to the unit name without `.service`, or the file
+    /// name without extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub target: LogTarget,
+    /// Overrides [`LogsProviderConfig::min_severity`] for this source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<Severity>,
+}
+
+impl LogSource {
+    /// Watch a systemd unit's journal.
+    pub fn journald(unit: impl Into<String>) -> Self {
+        Self {
+            name: None,
+            target: LogTarget::Journald { unit: unit.into() },
+            min_severity: None,
+        }
+    }
+
+    /// Watch a log file.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: None,
+            target: LogTarget::File { path: path.into() },
+            min_severity: None,
+        }
+    }
+
+    /// Use `name` for the feed instead of the derived one.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only show entries at least this severe from this source.
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// The feed name.
+    pub fn name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        match &self.target {
+            LogTarget::Journald { unit } => unit.trim_end_matches(".service").to_string(),
+            LogTarget::File { path } => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+        }
+    }
+}
+
+/// Configuration for the [`LogsProvider`](crate::LogsProvider).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogsProviderConfig {
+    /// Logs to watch
+    pub sources: Vec<LogSource>,
+    /// Least severe entries shown, `error` by default
+    pub min_severity: Severity,
+    /// Entries closer together than this many seconds form one burst
+    pub burst_gap_secs: u64,
+    /// How far back to read when the caller gives no `since`
+    pub lookback_hours: u32,
+    /// Most entries read per source and request
+    pub max_entries: usize,
+    /// Bytes read from the end of a log file
+    pub tail_bytes: u64,
+    /// The `journalctl` binary
+    pub journalctl: PathBuf,
+}
+
+impl Default for LogsProviderConfig {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            min_severity: Severity::Error,
+            burst_gap_secs: 300,
+            lookback_hours: 24,
+            max_entries: 5000,
+            tail_bytes: 1024 * 1024,
+            journalctl: PathBuf::from("journalctl"),
+        }
+    }
+}
+
+impl LogsProviderConfig {
+    /// Configuration watching `sources` with the default thresholds.
+    pub fn new(sources: Vec<LogSource>) -> Self {
+        Self {
+            sources,
+            ..Default::default()
+        }
+    }
+
+    /// Only show entries at least this severe.
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Run a different `journalctl`, such as one in a container or a stub in
+    /// tests.
+    pub fn with_journalctl(mut self, journalctl: impl AsRef<Path>) -> Self {
+        self.journalctl = journalctl.as_ref().to_path_buf();
+        self
+    }
+
+    /// The source a feed name belongs to.
+    pub fn source(&self, name: &str) -> Option<&LogSource> {
+        self.sources.iter().find(|source| source.name() == name)
+    }
+
+    /// The threshold of `source`.
+    pub fn min_severity_of(&self, source: &LogSource) -> Severity {
+        source.min_severity.unwrap_or(self.min_severity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_deserialize() {
+        let config: LogsProviderConfig = serde_json::from_value(serde_json::json!({
+            "sources": [
+                { "unit": "nginx.service" },
+                { "path": "/var/log/app/app.log", "name": "app", "min_severity": "warn" }
+            ],
+            "burst_gap_secs": 60
+        }))
+        .unwrap();
+
+        assert_eq!(config.sources[0], LogSource::journald("nginx.service"));
+        assert_eq!(config.sources[0].name(), "nginx");
+        assert_eq!(
+            config.sources[1],
+            LogSource::file("/var/log/app/app.log")
+                .with_name("app")
+                .with_min_severity(Severity::Warning)
+        );
+        assert_eq!(config.burst_gap_secs, 60);
+        assert_eq!(config.min_severity, Severity::Error);
+        assert_eq!(
+            config.min_severity_of(&config.sources[1]),
+            Severity::Warning
+        );
+        assert_eq!(LogSource::file("/var/log/syslog").name(), "syslog");
+    }
+}
@@ -0,0 +1,187 @@
+//! Reading plain-text log files.
+//!
+//! Only the last [`tail_bytes`](crate::LogsProviderConfig::tail_bytes) of a
+//! file are read. Each line starting with a timestamp begins an entry, and
+//! its severity is guessed from the level marker after it. Indented lines,
+//! such as stack traces, continue the entry before them.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Utc};
+
+use crate::burst::LogEntry;
+use crate::severity::Severity;
+
+/// Timestamp formats of the first two words of a line, as local time.
+const LOCAL_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y/%m/%d %H:%M:%S%.f"];
+
+/// The last `max_bytes` of the file at `path`, as whole lines, and the
+/// file's modification time.
+pub fn read_tail(path: &Path, max_bytes: u64) -> io::Result<(String, DateTime<Utc>)> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let modified = metadata.modified().map(DateTime::<Utc>::from)?;
+
+    let start = metadata.len().saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let mut text = String::from_utf8_lossy(&bytes).into_owned();
+    if start > 0 {
+        // The first line is most likely cut in half
+        let first_newline = text.find('\n').map_or(text.len(), |i| i + 1);
+        text.drain(..first_newline);
+    }
+    Ok((text, modified))
+}
+
+/// Split a log file's text into entries.
+///
+/// Lines without a timestamp of their own take the previous entry's, or
+/// `fallback` (the file's modification time) before the first timestamp.
+/// Lines without a level marker are [`Severity::Info`].
+pub fn parse_entries(text: &str, fallback: DateTime<Utc>) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some(previous) = entries.last_mut() {
+                previous.message.push('\n');
+                previous.message.push_str(line.trim_end());
+                continue;
+            }
+        }
+
+        let (timestamp, message) = match parse_timestamp(line) {
+            Some((timestamp, rest)) => (timestamp, rest),
+            None => (
+                entries
+                    .last()
+                    .map_or(fallback, |previous| previous.timestamp),
+                line,
+            ),
+        };
+        entries.push(LogEntry {
+            timestamp,
+            severity: Severity::detect(message).unwrap_or(Severity::Info),
+            message: message.trim().to_string(),
+        });
+    }
+    entries
+}
+
+/// The timestamp a line starts with, and the rest of the line.
+///
+/// Understands RFC 3339; `2024-05-01 12:00:00.123` with a `T`, `/`, or a
+/// comma before the fraction, optionally in brackets, as local time; and
+/// syslog's `May  1 12:00:00`, as local time this year.
+pub fn parse_timestamp(line: &str) -> Option<(DateTime<Utc>, &str)> {
+    let unbracketed = line.strip_prefix('[').unwrap_or(line);
+    let mut words = unbracketed.split_whitespace();
+    let first = words.next()?.trim_end_matches(']');
+
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(first) {
+        return Some((timestamp.with_timezone(&Utc), after_words(unbracketed, 1)));
+    }
+    if let Ok(naive) = NaiveDateTime::parse_from_str(first, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some((from_local(naive)?, after_words(unbracketed, 1)));
+    }
+
+    let second = words.next()?.trim_end_matches(']');
+    let joined = format!("{} {}", first, second.replace(',', "."));
+    for format in LOCAL_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(&joined, format) {
+            return Some((from_local(naive)?, after_words(unbracketed, 2)));
+        }
+    }
+
+    // Syslog has no year: assume this one, unless that's in the future
+    let third = words.next()?;
+    let now = Local::now();
+    let syslog = format!("{} {} {} {}", now.year(), first, second, third);
+    let naive = NaiveDateTime::parse_from_str(&syslog, "%Y %b %d %H:%M:%S").ok()?;
+    let mut timestamp = from_local(naive)?;
+    if timestamp > now.with_timezone(&Utc) + chrono::Duration::days(1) {
+        timestamp = from_local(naive.with_year(now.year() - 1)?)?;
+    }
+    Some((timestamp, after_words(unbracketed, 3)))
+}
+
+fn from_local(naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+}
+
+/// `text` after its first `count` whitespace-separated words.
+fn after_words(text: &str, count: usize) -> &str {
+    let mut rest = text.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest = rest[end..].trim_start();
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(text: &str) -> DateTime<Utc> {
+        from_local(NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f").unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        let (timestamp, rest) = parse_timestamp("2024-05-01T12:00:00Z ERROR boom").unwrap();
+        assert_eq!(timestamp.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+        assert_eq!(rest, "ERROR boom");
+
+        let (timestamp, rest) = parse_timestamp("2024-05-01 12:00:00,250 ERROR:root:boom").unwrap();
+        assert_eq!(timestamp, local("2024-05-01 12:00:00.250"));
+        assert_eq!(rest, "ERROR:root:boom");
+
+        let (timestamp, rest) =
+            parse_timestamp("2024/05/01 12:00:00 [error] 12#0: failed").unwrap();
+        assert_eq!(timestamp, local("2024-05-01 12:00:00"));
+        assert_eq!(rest, "[error] 12#0: failed");
+
+        let (timestamp, rest) = parse_timestamp("[2024-05-01 12:00:00] FATAL oom").unwrap();
+        assert_eq!(timestamp, local("2024-05-01 12:00:00"));
+        assert_eq!(rest, "FATAL oom");
+
+        let (_, rest) = parse_timestamp("Jan  2 03:04:05 host sshd[1]: error: bad").unwrap();
+        assert_eq!(rest, "host sshd[1]: error: bad");
+
+        assert!(parse_timestamp("Traceback (most recent call last):").is_none());
+    }
+
+    #[test]
+    fn test_parse_entries_joins_continuations() {
+        let fallback = "2024-05-01T00:00:00Z".parse().unwrap();
+        let text = "\
+starting up
+2024-05-01T12:00:00Z ERROR request failed
+    at handler (app.js:10)
+    at router (app.js:20)
+2024-05-01T12:00:05Z INFO recovered
+";
+        let entries = parse_entries(text, fallback);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].timestamp, fallback);
+        assert_eq!(entries[0].severity, Severity::Info);
+        assert_eq!(entries[1].severity, Severity::Error);
+        assert_eq!(
+            entries[1].message,
+            "ERROR request failed\n    at handler (app.js:10)\n    at router (app.js:20)"
+        );
+        assert_eq!(entries[2].severity, Severity::Info);
+    }
+}
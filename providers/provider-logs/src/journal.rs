@@ -0,0 +1,135 @@
+//! Reading the systemd journal through `journalctl`.
+//!
+//! `journalctl --output json` prints one JSON object per entry. Field
+//! values are strings, except `MESSAGE`, which is an array of bytes when it
+//! isn't valid UTF-8, and `null` when it is too large.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tokio::process::Command;
+
+use crate::burst::LogEntry;
+use crate::severity::Severity;
+
+/// Read a unit's entries since `since` that are at least `min_severity`,
+/// keeping the newest `max_entries`.
+pub async fn read_unit(
+    journalctl: &Path,
+    unit: &str,
+    since: DateTime<Utc>,
+    min_severity: Severity,
+    max_entries: usize,
+) -> std::io::Result<Vec<LogEntry>> {
+    let output = Command::new(journalctl)
+        .args(["--unit", unit])
+        .arg(format!("--since=@{}", since.timestamp()))
+        .arg(format!("--priority={}", min_severity.priority()))
+        .arg(format!("--lines={}", max_entries))
+        .args(["--output=json", "--no-pager", "--quiet"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "journalctl exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(parse_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Whether `journalctl` can be run at all.
+pub async fn available(journalctl: &Path) -> bool {
+    Command::new(journalctl)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+/// Parse `journalctl --output json` output, skipping lines that aren't
+/// entries.
+pub fn parse_output(output: &str) -> Vec<LogEntry> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|entry| parse_entry(&entry))
+        .collect()
+}
+
+fn parse_entry(entry: &Value) -> Option<LogEntry> {
+    let micros: i64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+    let timestamp = DateTime::<Utc>::from_timestamp_micros(micros)?;
+    let severity = entry
+        .get("PRIORITY")
+        .and_then(Value::as_str)
+        .and_then(|priority| priority.parse().ok())
+        .and_then(Severity::from_priority)
+        .unwrap_or(Severity::Info);
+
+    let message = match entry.get("MESSAGE") {
+        Some(Value::String(message)) => message.clone(),
+        Some(Value::Array(bytes)) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        _ => "[message not available]".to_string(),
+    };
+
+    // Prefix the process the way `journalctl`'s short output does
+    let field = |name: &str| entry.get(name).and_then(Value::as_str);
+    let identifier = field("SYSLOG_IDENTIFIER").or_else(|| field("_COMM"));
+    let message = match (identifier, field("_PID")) {
+        (Some(identifier), Some(pid)) => format!("{}[{}]: {}", identifier, pid, message.trim_end()),
+        (Some(identifier), None) => format!("{}: {}", identifier, message.trim_end()),
+        _ => message.trim_end().to_string(),
+    };
+
+    Some(LogEntry {
+        timestamp,
+        severity,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let output = r#"{"__REALTIME_TIMESTAMP":"1714564800000000","PRIORITY":"3","SYSLOG_IDENTIFIER":"nginx","_PID":"812","MESSAGE":"connect() failed (111: Connection refused)"}
+{"__REALTIME_TIMESTAMP":"1714564801500000","PRIORITY":"2","_COMM":"app","MESSAGE":[111,111,112,115,10]}
+-- No entries --
+{"__REALTIME_TIMESTAMP":"1714564802000000","MESSAGE":null}
+"#;
+        let entries = parse_output(output);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0].timestamp.to_rfc3339(),
+            "2024-05-01T12:00:00+00:00"
+        );
+        assert_eq!(entries[0].severity, Severity::Error);
+        assert_eq!(
+            entries[0].message,
+            "nginx[812]: connect() failed (111: Connection refused)"
+        );
+        assert_eq!(entries[1].severity, Severity::Critical);
+        assert_eq!(entries[1].message, "app: oops");
+        assert_eq!(entries[2].severity, Severity::Info);
+        assert_eq!(entries[2].message, "[message not available]");
+    }
+}
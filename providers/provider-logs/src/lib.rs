@@ -0,0 +1,426 @@
+//! # provider-logs
+//!
+//! Server log provider for Scryforge: a lightweight ops inbox.
+//!
+//! The provider tails systemd units through `journalctl` ([`journal`]) and
+//! plain-text log files ([`file`]), keeps the entries at or above a severity
+//! threshold, and groups entries that follow each other closely into bursts
+//! ([`burst`]). Each burst is one item, so a service failing every few
+//! seconds for ten minutes shows up once, with its count and its lines.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_logs::{LogSource, LogsProvider, LogsProviderConfig, Severity};
+//!
+//! let config = LogsProviderConfig::new(vec![
+//!     LogSource::journald("nginx.service"),
+//!     LogSource::file("/var/log/app/app.log").with_min_severity(Severity::Warning),
+//! ]);
+//! let provider = LogsProvider::new(config);
+//! ```
+//!
+//! Each source is a feed, named after the unit or file unless given a
+//! name; the `all` feed holds the bursts of every source.
+
+pub mod burst;
+pub mod config;
+pub mod file;
+pub mod journal;
+pub mod severity;
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use scryforge_provider_core::prelude::*;
+
+pub use burst::{Burst, LogEntry};
+pub use config::{LogSource, LogTarget, LogsProviderConfig};
+pub use severity::Severity;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "logs";
+
+/// Feed of the bursts of every source.
+pub const ALL_FEED: &str = "all";
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Most lines of a burst shown in its item.
+const MAX_BURST_LINES: usize = 100;
+
+/// Longest first line used in an item title.
+const MAX_TITLE_CHARS: usize = 120;
+
+/// journald and log-file tail provider for Scryforge.
+pub struct LogsProvider {
+    config: LogsProviderConfig,
+}
+
+impl LogsProvider {
+    pub fn new(config: LogsProviderConfig) -> Self {
+        Self { config }
+    }
+
+    /// Read the entries of `source` since `since`.
+    async fn read_source(&self, source: &LogSource, since: DateTime<Utc>) -> Result<Vec<LogEntry>> {
+        let min_severity = self.config.min_severity_of(source);
+        match &source.target {
+            LogTarget::Journald { unit } => journal::read_unit(
+                &self.config.journalctl,
+                unit,
+                since,
+                min_severity,
+                self.config.max_entries,
+            )
+            .await
+            .map_err(|e| {
+                StreamError::Provider(format!("Failed to read journal of {}: {}", unit, e))
+            }),
+            LogTarget::File { path } => {
+                let tail_bytes = self.config.tail_bytes;
+                let blocking_path = path.clone();
+                let (text, modified) = tokio::task::spawn_blocking(move || {
+                    file::read_tail(&blocking_path, tail_bytes)
+                })
+                .await
+                .map_err(|e| StreamError::Internal(e.to_string()))?
+                .map_err(|e| {
+                    StreamError::Provider(format!("Failed to read {}: {}", path.display(), e))
+                })?;
+
+                let mut entries: Vec<LogEntry> = file::parse_entries(&text, modified)
+                    .into_iter()
+                    .filter(|entry| entry.timestamp >= since)
+                    .collect();
+                let excess = entries.len().saturating_sub(self.config.max_entries);
+                entries.drain(..excess);
+                Ok(entries)
+            }
+        }
+    }
+
+    /// The bursts of `source` since `since`, newest first.
+    async fn bursts(&self, source: &LogSource, since: DateTime<Utc>) -> Result<Vec<Item>> {
+        let entries = self.read_source(source, since).await?;
+        let gap = Duration::seconds(self.config.burst_gap_secs as i64);
+        let mut bursts = burst::group_bursts(&entries, self.config.min_severity_of(source), gap);
+        bursts.reverse();
+        Ok(bursts
+            .iter()
+            .map(|burst| burst_to_item(source, burst))
+            .collect())
+    }
+
+    fn default_since(&self) -> DateTime<Utc> {
+        Utc::now() - Duration::hours(self.config.lookback_hours as i64)
+    }
+}
+
+/// Convert a burst of `source` into an [`Item`].
+fn burst_to_item(source: &LogSource, burst: &Burst) -> Item {
+    let name = source.name();
+    let first = burst.first();
+    let last = burst.last();
+    let severity = burst.severity();
+
+    let first_line = first.message.lines().next().unwrap_or_default();
+    let mut summary: String = first_line.chars().take(MAX_TITLE_CHARS).collect();
+    if summary.len() < first_line.len() {
+        summary.push('…');
+    }
+    let title = match burst.entries.len() {
+        1 => format!("{}: {}", name, summary),
+        count => format!("{}: {} × {}", name, count, summary),
+    };
+
+    let mut body: Vec<String> = burst
+        .entries
+        .iter()
+        .take(MAX_BURST_LINES)
+        .map(|entry| {
+            format!(
+                "{} {:<8} {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.severity.as_str(),
+                entry.message
+            )
+        })
+        .collect();
+    if burst.entries.len() > MAX_BURST_LINES {
+        body.push(format!(
+            "… {} more entries",
+            burst.entries.len() - MAX_BURST_LINES
+        ));
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("source".to_string(), name.clone());
+    metadata.insert("severity".to_string(), severity.as_str().to_string());
+    metadata.insert("count".to_string(), burst.entries.len().to_string());
+    metadata.insert("first_seen".to_string(), first.timestamp.to_rfc3339());
+    metadata.insert("last_seen".to_string(), last.timestamp.to_rfc3339());
+    match &source.target {
+        LogTarget::Journald { unit } => {
+            metadata.insert("unit".to_string(), unit.clone());
+            metadata.insert(
+                "log_command".to_string(),
+                format!(
+                    "journalctl --unit {} --since @{} --until @{}",
+                    unit,
+                    first.timestamp.timestamp(),
+                    last.timestamp.timestamp() + 1
+                ),
+            );
+        }
+        LogTarget::File { path } => {
+            metadata.insert("path".to_string(), path.display().to_string());
+        }
+    }
+
+    Item {
+        // The first entry doesn't change as the burst grows
+        id: ItemId::new(
+            PROVIDER_ID,
+            &format!("{}:{}", name, first.timestamp.timestamp_micros()),
+        ),
+        stream_id: StreamId::new(PROVIDER_ID, "feed", &name),
+        title,
+        content: ItemContent::Text(body.join("\n")),
+        author: None,
+        published: Some(first.timestamp),
+        updated: Some(last.timestamp),
+        url: None,
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: vec![severity.as_str().to_string()],
+        metadata,
+    }
+}
+
+#[async_trait]
+impl Provider for LogsProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Logs"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        let mut problems = Vec::new();
+        let uses_journal = self
+            .config
+            .sources
+            .iter()
+            .any(|source| matches!(source.target, LogTarget::Journald { .. }));
+        if uses_journal && !journal::available(&self.config.journalctl).await {
+            problems.push(format!(
+                "{} is not available",
+                self.config.journalctl.display()
+            ));
+        }
+        for source in &self.config.sources {
+            if let LogTarget::File { path } = &source.target {
+                if let Err(e) = std::fs::File::open(path) {
+                    problems.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+
+        Ok(ProviderHealth {
+            is_healthy: problems.is_empty(),
+            message: Some(if problems.is_empty() {
+                format!("Watching {} logs", self.config.sources.len())
+            } else {
+                problems.join("; ")
+            }),
+            last_sync: Some(Utc::now()),
+            error_count: problems.len() as u32,
+        })
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        let since = self.default_since();
+
+        // One unreadable log shouldn't hide the others
+        let mut items_added = 0;
+        let mut errors = Vec::new();
+        for source in &self.config.sources {
+            match self.bursts(source, since).await {
+                Ok(bursts) => items_added += bursts.len() as u32,
+                Err(e) => errors.push(format!("{}: {}", source.name(), e)),
+            }
+        }
+
+        Ok(SyncResult {
+            success: errors.is_empty(),
+            items_added,
+            items_updated: 0,
+            items_removed: 0,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = Vec::new();
+        if item.metadata.contains_key("log_command") {
+            actions.push(Action {
+                id: "copy_log_command".to_string(),
+                name: "Copy journalctl Command".to_string(),
+                description: "Copy the command that shows the burst in the journal".to_string(),
+                kind: ActionKind::Custom("copy_log_command".to_string()),
+                keyboard_shortcut: Some("y".to_string()),
+            });
+        }
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match &action.kind {
+            ActionKind::Custom(name) if name == "copy_log_command" => {
+                Ok(match item.metadata.get("log_command") {
+                    Some(command) => ActionResult {
+                        success: true,
+                        message: Some(format!("Copied: {}", command)),
+                        data: Some(serde_json::json!({ "text": command })),
+                    },
+                    None => ActionResult {
+                        success: false,
+                        message: Some("Item has no journal command".to_string()),
+                        data: None,
+                    },
+                })
+            }
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for LogsProvider {
+    /// One feed per source, plus `all`.
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let mut feeds = vec![Feed {
+            id: FeedId(ALL_FEED.to_string()),
+            name: "All Logs".to_string(),
+            description: Some("Error bursts of every watched log".to_string()),
+            icon: Some("🚨".to_string()),
+            unread_count: None,
+            total_count: None,
+        }];
+        feeds.extend(self.config.sources.iter().map(|source| {
+            let description = match &source.target {
+                LogTarget::Journald { unit } => format!("journal of {}", unit),
+                LogTarget::File { path } => path.display().to_string(),
+            };
+            Feed {
+                id: FeedId(source.name()),
+                name: source.name(),
+                description: Some(format!(
+                    "{} and worse from {}",
+                    self.config.min_severity_of(source),
+                    description
+                )),
+                icon: Some("📜".to_string()),
+                unread_count: None,
+                total_count: None,
+            }
+        }));
+        Ok(feeds)
+    }
+
+    /// Bursts of a feed, most recent first. `since` bounds how far back the
+    /// logs are read, `lookback_hours` by default.
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let since = options.since.unwrap_or_else(|| self.default_since());
+
+        let mut items = Vec::new();
+        if feed_id.0 == ALL_FEED {
+            for source in &self.config.sources {
+                items.extend(self.bursts(source, since).await?);
+            }
+        } else {
+            let source = self
+                .config
+                .source(&feed_id.0)
+                .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+            items = self.bursts(source, since).await?;
+        }
+        items.sort_by_key(|item| std::cmp::Reverse(item.updated));
+
+        Ok(items
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_to_item() {
+        let entry = |second: u32, severity, message: &str| LogEntry {
+            timestamp: format!("2024-05-01T12:00:{:02}Z", second).parse().unwrap(),
+            severity,
+            message: message.to_string(),
+        };
+        let burst = Burst {
+            entries: vec![
+                entry(0, Severity::Error, "nginx[812]: connect() failed"),
+                entry(4, Severity::Critical, "nginx[812]: upstream gone"),
+            ],
+        };
+        let item = burst_to_item(&LogSource::journald("nginx.service"), &burst);
+
+        assert_eq!(item.id.as_str(), "logs:nginx:1714564800000000");
+        assert_eq!(item.stream_id.as_str(), "logs:feed:nginx");
+        assert_eq!(item.title, "nginx: 2 × nginx[812]: connect() failed");
+        assert_eq!(item.tags, vec!["critical"]);
+        assert_eq!(item.metadata["count"], "2");
+        assert_eq!(
+            item.metadata["log_command"],
+            "journalctl --unit nginx.service --since @1714564800 --until @1714564805"
+        );
+        assert!(matches!(
+            &item.content,
+            ItemContent::Text(body) if body.lines().nth(1)
+                == Some("2024-05-01 12:00:04 critical nginx[812]: upstream gone")
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+//! Log severities, as syslog and journald number them.
+
+use serde::{Deserialize, Serialize};
+
+/// Severity of a log entry, most severe first.
+///
+/// The order follows syslog priorities, so `Emergency < Error < Debug`: an
+/// entry passes a threshold when its severity is less than or equal to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[serde(alias = "emerg")]
+    Emergency,
+    Alert,
+    #[serde(alias = "crit")]
+    Critical,
+    #[serde(alias = "err")]
+    Error,
+    #[serde(alias = "warn")]
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl Severity {
+    const ALL: [Severity; 8] = [
+        Severity::Emergency,
+        Severity::Alert,
+        Severity::Critical,
+        Severity::Error,
+        Severity::Warning,
+        Severity::Notice,
+        Severity::Info,
+        Severity::Debug,
+    ];
+
+    /// The severity of a syslog / journald `PRIORITY` (0-7).
+    pub fn from_priority(priority: u8) -> Option<Self> {
+        Self::ALL.get(priority as usize).copied()
+    }
+
+    /// The syslog priority, as `journalctl --priority` takes it.
+    pub fn priority(self) -> u8 {
+        self as u8
+    }
+
+    /// Whether an entry of this severity passes the `threshold`.
+    pub fn is_at_least(self, threshold: Severity) -> bool {
+        self <= threshold
+    }
+
+    /// Name used in settings, tags, and metadata.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Emergency => "emergency",
+            Severity::Alert => "alert",
+            Severity::Critical => "critical",
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Notice => "notice",
+            Severity::Info => "info",
+            Severity::Debug => "debug",
+        }
+    }
+
+    /// The severity a level word names: `ERROR`, `warn`, `FATAL`, ...
+    pub fn from_keyword(word: &str) -> Option<Self> {
+        match word.to_ascii_lowercase().as_str() {
+            "emerg" | "emergency" | "panic" => Some(Severity::Emergency),
+            "alert" => Some(Severity::Alert),
+            "crit" | "critical" | "fatal" | "severe" => Some(Severity::Critical),
+            "err" | "error" | "eror" => Some(Severity::Error),
+            "warn" | "warning" | "wrn" => Some(Severity::Warning),
+            "notice" => Some(Severity::Notice),
+            "info" | "inf" => Some(Severity::Info),
+            "debug" | "dbg" | "trace" => Some(Severity::Debug),
+            _ => None,
+        }
+    }
+
+    /// Guess the severity of a plain-text log line from its level marker.
+    ///
+    /// Only the first few words are looked at, and a level word only counts
+    /// when it is marked as one: `[error]`, `ERROR`, `ERROR:root:...`,
+    /// `level=error`. That keeps "connection error while ..." in an info
+    /// line from being read as an error.
+    pub fn detect(line: &str) -> Option<Self> {
+        for token in line.split_whitespace().take(8) {
+            let level = token
+                .strip_prefix("level=")
+                .or_else(|| token.strip_prefix("severity="));
+            if let Some(level) = level {
+                return Self::from_keyword(level.trim_matches('"'));
+            }
+
+            let unbracketed = token.trim_start_matches(['[', '(', '<']);
+            let end = unbracketed
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(unbracketed.len());
+            let (word, rest) = unbracketed.split_at(end);
+            let marked = unbracketed.len() < token.len()
+                || rest.starts_with([':', ']', ')', '>'])
+                || (word.len() >= 3 && word.chars().all(|c| c.is_ascii_uppercase()));
+            if marked {
+                if let Some(severity) = Self::from_keyword(word) {
+                    return Some(severity);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_order() {
+        assert_eq!(Severity::from_priority(3), Some(Severity::Error));
+        assert_eq!(Severity::from_priority(8), None);
+        assert_eq!(Severity::Warning.priority(), 4);
+        assert!(Severity::Critical.is_at_least(Severity::Error));
+        assert!(!Severity::Warning.is_at_least(Severity::Error));
+    }
+
+    #[test]
+    fn test_detect_level_markers() {
+        let cases = [
+            (
+                "2024-05-01T12:00:00Z ERROR db: timeout",
+                Some(Severity::Error),
+            ),
+            (
+                "2024/05/01 12:00:00 [error] 123#0: *1 connect() failed",
+                Some(Severity::Error),
+            ),
+            ("ERROR:root:boom", Some(Severity::Error)),
+            (
+                "time=12:00 level=warn msg=\"slow\"",
+                Some(Severity::Warning),
+            ),
+            (
+                "[2024-05-01 12:00:00] FATAL out of memory",
+                Some(Severity::Critical),
+            ),
+            ("<crit> disk failing", Some(Severity::Critical)),
+            ("12:00:00 INFO started", Some(Severity::Info)),
+            ("Connection error while reading", None),
+            ("nothing to see here", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(Severity::detect(line), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_settings_names() {
+        let severity: Severity = serde_json::from_str("\"err\"").unwrap();
+        assert_eq!(severity, Severity::Error);
+        assert_eq!(
+            serde_json::to_string(&Severity::Warning).unwrap(),
+            "\"warning\""
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Reads log files and a stub `journalctl` through the provider.
+
+use std::path::Path;
+
+use provider_logs::{LogSource, LogsProvider, LogsProviderConfig, Severity};
+use scryforge_provider_core::prelude::*;
+
+const APP_LOG: &str = "\
+2024-05-01T12:00:00Z INFO server started
+2024-05-01T12:01:00Z ERROR db: connection refused
+2024-05-01T12:01:30Z ERROR db: connection refused
+    at pool.connect (pool.rs:42)
+2024-05-01T12:02:00Z WARN slow request /api/items
+2024-05-01T12:02:10Z ERROR db: connection refused
+2024-05-01T13:00:00Z ERROR disk almost full
+";
+
+fn since_2024() -> FeedOptions {
+    FeedOptions {
+        since: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn feed(name: &str) -> FeedId {
+    FeedId(name.to_string())
+}
+
+fn write_file(dir: &Path, name: &str, text: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn file_errors_group_into_bursts() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_file(dir.path(), "app.log", APP_LOG);
+    let provider = LogsProvider::new(LogsProviderConfig::new(vec![LogSource::file(&path)]));
+
+    let items = provider
+        .get_feed_items(&feed("app"), since_2024())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec![
+            "app: ERROR disk almost full",
+            "app: 3 × ERROR db: connection refused"
+        ]
+    );
+    let burst = &items[1];
+    assert_eq!(burst.metadata["count"], "3");
+    assert_eq!(burst.metadata["path"], path.display().to_string());
+    assert_eq!(
+        burst.updated.unwrap().to_rfc3339(),
+        "2024-05-01T12:02:10+00:00"
+    );
+    assert!(matches!(
+        &burst.content,
+        ItemContent::Text(body) if body.contains("at pool.connect (pool.rs:42)")
+    ));
+}
+
+#[tokio::test]
+async fn source_threshold_lets_warnings_in() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_file(dir.path(), "app.log", APP_LOG);
+    let config = LogsProviderConfig::new(vec![LogSource::file(&path)
+        .with_name("api")
+        .with_min_severity(Severity::Warning)]);
+    let provider = LogsProvider::new(config);
+
+    let items = provider
+        .get_feed_items(&feed("api"), since_2024())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].metadata["count"], "4");
+    assert_eq!(items[1].metadata["severity"], "error");
+}
+
+#[tokio::test]
+async fn since_skips_older_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write_file(dir.path(), "app.log", APP_LOG);
+    let provider = LogsProvider::new(LogsProviderConfig::new(vec![LogSource::file(&path)]));
+
+    let items = provider
+        .get_feed_items(
+            &feed("app"),
+            FeedOptions {
+                since: Some("2024-05-01T12:30:00Z".parse().unwrap()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title, "app: ERROR disk almost full");
+}
+
+#[tokio::test]
+async fn all_feed_merges_sources_and_unknown_feeds_fail() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = write_file(dir.path(), "app.log", APP_LOG);
+    let worker = write_file(
+        dir.path(),
+        "worker.log",
+        "2024-05-01T14:00:00Z [error] job 7 failed\n",
+    );
+    let provider = LogsProvider::new(LogsProviderConfig::new(vec![
+        LogSource::file(&app),
+        LogSource::file(&worker),
+    ]));
+
+    let feeds = provider.list_feeds().await.unwrap();
+    let ids: Vec<&str> = feeds.iter().map(|feed| feed.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["all", "app", "worker"]);
+
+    let items = provider
+        .get_feed_items(&feed("all"), since_2024())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].stream_id.as_str(), "logs:feed:worker");
+
+    let err = provider
+        .get_feed_items(&feed("nginx"), since_2024())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::StreamNotFound(_)));
+}
+
+#[tokio::test]
+async fn missing_file_fails_sync_and_health() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = write_file(dir.path(), "app.log", APP_LOG);
+    let provider = LogsProvider::new(LogsProviderConfig::new(vec![
+        LogSource::file(&app),
+        LogSource::file(dir.path().join("gone.log")),
+    ]));
+
+    let result = provider.sync().await.unwrap();
+    assert!(!result.success);
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].starts_with("gone:"));
+
+    let health = provider.health_check().await.unwrap();
+    assert!(!health.is_healthy);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn journald_units_are_read_through_journalctl() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let args = dir.path().join("args");
+    let script = format!(
+        "#!/bin/sh\n\
+         [ \"$1\" = --version ] && exit 0\n\
+         printf '%s\\n' \"$@\" > '{}'\n\
+         cat <<'EOF'\n\
+         {{\"__REALTIME_TIMESTAMP\":\"1714564800000000\",\"PRIORITY\":\"3\",\"SYSLOG_IDENTIFIER\":\"nginx\",\"_PID\":\"812\",\"MESSAGE\":\"connect() failed\"}}\n\
+         {{\"__REALTIME_TIMESTAMP\":\"1714564830000000\",\"PRIORITY\":\"3\",\"SYSLOG_IDENTIFIER\":\"nginx\",\"_PID\":\"812\",\"MESSAGE\":\"connect() failed\"}}\n\
+         EOF\n",
+        args.display()
+    );
+    let journalctl = write_file(dir.path(), "journalctl", &script);
+    std::fs::set_permissions(&journalctl, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = LogsProviderConfig::new(vec![LogSource::journald("nginx.service")])
+        .with_journalctl(&journalctl);
+    let provider = LogsProvider::new(config);
+    assert!(provider.health_check().await.unwrap().is_healthy);
+
+    let items = provider
+        .get_feed_items(&feed("nginx"), since_2024())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title, "nginx: 2 × nginx[812]: connect() failed");
+    assert_eq!(items[0].metadata["unit"], "nginx.service");
+
+    let args = std::fs::read_to_string(args).unwrap();
+    let args: Vec<&str> = args.lines().collect();
+    assert_eq!(
+        args,
+        vec![
+            "--unit",
+            "nginx.service",
+            "--since=@1704067200",
+            "--priority=3",
+            "--lines=5000",
+            "--output=json",
+            "--no-pager",
+            "--quiet"
+        ]
+    );
+
+    let actions = provider.available_actions(&items[0]).await.unwrap();
+    let result = provider
+        .execute_action(&items[0], &actions[0])
+        .await
+        .unwrap();
+    assert_eq!(
+        result.data,
+        Some(serde_json::json!({
+            "text": "journalctl --unit nginx.service --since @1714564800 --until @1714564831"
+        }))
+    );
+}
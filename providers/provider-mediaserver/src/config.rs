@@ -0,0 +1,95 @@
+//! Configuration for the media-server provider.
+
+use serde::{Deserialize, Serialize};
+
+/// Which media server a [`MediaServerConfig`] points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaServerKind {
+    Plex,
+    Jellyfin,
+}
+
+impl MediaServerKind {
+    /// Provider id, and the Sigilforge service the token is stored under.
+    pub fn id(self) -> &'static str {
+        match self {
+            MediaServerKind::Plex => "plex",
+            MediaServerKind::Jellyfin => "jellyfin",
+        }
+    }
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            MediaServerKind::Plex => "Plex",
+            MediaServerKind::Jellyfin => "Jellyfin",
+        }
+    }
+}
+
+/// Configuration for the [`MediaServerProvider`](crate::MediaServerProvider).
+///
+/// `server_url` should point at the server itself (e.g.
+/// `http://192.168.1.10:32400` for Plex, `https://jellyfin.example.com` for
+/// Jellyfin), not at a hosted web app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaServerConfig {
+    pub kind: MediaServerKind,
+    /// Base URL of the server.
+    pub server_url: String,
+    /// `X-Plex-Token` for Plex, an API key or access token for Jellyfin.
+    pub token: String,
+    /// Jellyfin user whose progress is shown. Looked up from the token when
+    /// unset, which only works for user access tokens, not API keys.
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+impl MediaServerConfig {
+    /// Create a Plex configuration.
+    pub fn plex(server_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            kind: MediaServerKind::Plex,
+            server_url: server_url.into(),
+            token: token.into(),
+            user_id: None,
+        }
+    }
+
+    /// Create a Jellyfin configuration.
+    pub fn jellyfin(server_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            kind: MediaServerKind::Jellyfin,
+            server_url: server_url.into(),
+            token: token.into(),
+            user_id: None,
+        }
+    }
+
+    /// Show the progress of this Jellyfin user.
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Construct a configuration by fetching the token from Sigilforge.
+    ///
+    /// Looks up the token under the service identifier `"plex"` or
+    /// `"jellyfin"` and the supplied `account` label.
+    #[cfg(feature = "sigilforge")]
+    pub async fn from_sigilforge(
+        token_fetcher: &dyn scryforge_sigilforge_client::TokenFetcher,
+        kind: MediaServerKind,
+        server_url: impl Into<String>,
+        account: &str,
+    ) -> std::result::Result<Self, scryforge_sigilforge_client::SigilforgeError> {
+        let token = token_fetcher.fetch_token(kind.id(), account).await?;
+        Ok(Self {
+            kind,
+            server_url: server_url.into(),
+            token,
+            user_id: None,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+//! Errors shared by the Plex and Jellyfin clients.
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Errors raised by the media-server clients.
+#[derive(Debug, Error)]
+pub enum MediaServerError {
+    /// Underlying transport error (DNS, TLS, connection refused, decode, ...).
+    #[error("HTTP transport error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// 401 Unauthorized or 403 Forbidden — a missing or revoked token.
+    #[error("Unauthorized (check server token)")]
+    Unauthorized,
+
+    /// 404 Not Found — the item or user does not exist.
+    #[error("Not found")]
+    NotFound,
+
+    /// Generic non-success response with the body text.
+    #[error("Media server error ({status}): {body}")]
+    Status { status: u16, body: String },
+}
+
+/// Pass a successful response through, or turn the failure into an error.
+pub(crate) async fn check_status(
+    response: reqwest::Response,
+) -> std::result::Result<reqwest::Response, MediaServerError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(MediaServerError::Unauthorized),
+        StatusCode::NOT_FOUND => Err(MediaServerError::NotFound),
+        other => {
+            let body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "<unreadable body>".to_string());
+            Err(MediaServerError::Status {
+                status: other.as_u16(),
+                body,
+            })
+        }
+    }
+}
+
+impl From<MediaServerError> for scryforge_provider_core::StreamError {
+    fn from(err: MediaServerError) -> Self {
+        use scryforge_provider_core::StreamError;
+        match err {
+            MediaServerError::Http(e) => StreamError::Network(e.to_string()),
+            MediaServerError::Unauthorized => {
+                StreamError::AuthRequired("Media server token missing or invalid".to_string())
+            }
+            MediaServerError::NotFound => {
+                StreamError::ItemNotFound("Media server: item not found".to_string())
+            }
+            MediaServerError::Status { status, body } => StreamError::from_status(
+                status,
+                None,
+                format!("Media server HTTP {status}: {body}"),
+            ),
+        }
+    }
+}
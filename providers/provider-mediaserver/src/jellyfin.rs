@@ -0,0 +1,262 @@
+//! Jellyfin client.
+//!
+//! Requests authenticate with a `MediaBrowser` `Authorization` header
+//! carrying the token. Progress is per user, so listings go through
+//! `/Users/<id>/...`.
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tokio::sync::OnceCell;
+
+use crate::error::{check_status, MediaServerError};
+use crate::mapping::MediaEntry;
+
+/// Jellyfin counts time in ticks of 100ns.
+const TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Extra fields listings should include.
+const FIELDS: &str = "Overview,DateCreated,PremiereDate";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IdOnly {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ItemList {
+    #[serde(default)]
+    items: Vec<BaseItem>,
+}
+
+/// One entry of a Jellyfin item listing (subset).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct BaseItem {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// `Movie`, `Episode`, `Series`, ...
+    #[serde(rename = "Type", default)]
+    pub item_type: String,
+    pub series_name: Option<String>,
+    /// Season number, for episodes
+    pub parent_index_number: Option<u32>,
+    /// Episode number, for episodes
+    pub index_number: Option<u32>,
+    pub production_year: Option<i32>,
+    #[serde(default)]
+    pub overview: Option<String>,
+    pub run_time_ticks: Option<u64>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub premiere_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub image_tags: HashMap<String, String>,
+    pub user_data: Option<UserData>,
+}
+
+/// The user's progress on an item.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UserData {
+    #[serde(default)]
+    pub playback_position_ticks: u64,
+    #[serde(default)]
+    pub play_count: u64,
+    #[serde(default)]
+    pub played: bool,
+}
+
+/// Minimal Jellyfin client.
+#[derive(Debug)]
+pub struct JellyfinClient {
+    base_url: String,
+    token: String,
+    http: Client,
+    user_id: OnceCell<String>,
+    server_id: OnceCell<String>,
+}
+
+impl JellyfinClient {
+    /// Build a new client. Trailing slashes on `base_url` are normalised away.
+    /// Without a `user_id`, the user is looked up from the token.
+    pub fn new(
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+        user_id: Option<String>,
+    ) -> Self {
+        let http = Client::builder()
+            .user_agent("scryforge-provider-mediaserver/0.1.0")
+            .build()
+            .unwrap_or_default();
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            http,
+            user_id: OnceCell::new_with(user_id),
+            server_id: OnceCell::new(),
+        }
+    }
+
+    /// Server base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        request.header(
+            "Authorization",
+            format!(
+                "MediaBrowser Client=\"Scryforge\", Device=\"Scryforge\", DeviceId=\"scryforge\", Version=\"0.1.0\", Token=\"{}\"",
+                self.token
+            ),
+        )
+    }
+
+    async fn get_json<T>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> std::result::Result<T, MediaServerError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = self
+            .http
+            .get(format!("{}{}", self.base_url, path))
+            .query(query);
+        let response = check_status(self.authorized(request).send().await?).await?;
+        Ok(response.json::<T>().await?)
+    }
+
+    /// The user whose progress is shown: the configured one, else
+    /// `GET /Users/Me`, fetched once.
+    pub async fn user_id(&self) -> std::result::Result<&str, MediaServerError> {
+        let id = self
+            .user_id
+            .get_or_try_init(|| async {
+                let me: IdOnly = self.get_json("/Users/Me", &[]).await?;
+                Ok::<_, MediaServerError>(me.id)
+            })
+            .await?;
+        Ok(id)
+    }
+
+    /// `GET /System/Info/Public` — the server id deep links need, fetched once.
+    pub async fn server_id(&self) -> std::result::Result<&str, MediaServerError> {
+        let id = self
+            .server_id
+            .get_or_try_init(|| async {
+                let info: IdOnly = self.get_json("/System/Info/Public", &[]).await?;
+                Ok::<_, MediaServerError>(info.id)
+            })
+            .await?;
+        Ok(id)
+    }
+
+    fn to_entries(&self, items: &[BaseItem], server_id: &str) -> Vec<MediaEntry> {
+        items
+            .iter()
+            .map(|item| to_entry(item, &self.base_url, server_id))
+            .collect()
+    }
+
+    /// `GET /Users/<id>/Items/Resume` — partly watched videos.
+    pub async fn resume(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        let user_id = self.user_id().await?;
+        let mut query = vec![
+            ("Fields", FIELDS.to_string()),
+            ("MediaTypes", "Video".to_string()),
+        ];
+        if let Some(limit) = limit {
+            query.push(("Limit", limit.to_string()));
+        }
+        let list: ItemList = self
+            .get_json(&format!("/Users/{}/Items/Resume", user_id), &query)
+            .await?;
+        Ok(self.to_entries(&list.items, self.server_id().await?))
+    }
+
+    /// `GET /Users/<id>/Items/Latest` — recently added items.
+    pub async fn latest(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        let user_id = self.user_id().await?;
+        let mut query = vec![("Fields", FIELDS.to_string())];
+        if let Some(limit) = limit {
+            query.push(("Limit", limit.to_string()));
+        }
+        // Unlike the other listings, this one is a bare array
+        let items: Vec<BaseItem> = self
+            .get_json(&format!("/Users/{}/Items/Latest", user_id), &query)
+            .await?;
+        Ok(self.to_entries(&items, self.server_id().await?))
+    }
+
+    /// `POST` or `DELETE /Users/<id>/PlayedItems/<item>` — mark an item
+    /// watched or not.
+    pub async fn set_watched(
+        &self,
+        item_id: &str,
+        watched: bool,
+    ) -> std::result::Result<(), MediaServerError> {
+        let url = format!(
+            "{}/Users/{}/PlayedItems/{}",
+            self.base_url,
+            self.user_id().await?,
+            item_id
+        );
+        let request = if watched {
+            self.http.post(url)
+        } else {
+            self.http.delete(url)
+        };
+        check_status(self.authorized(request).send().await?).await?;
+        Ok(())
+    }
+}
+
+/// Deep link to an item's page in the server's own web client.
+pub(crate) fn deep_link(base_url: &str, server_id: &str, item_id: &str) -> String {
+    format!(
+        "{}/web/#/details?id={}&serverId={}",
+        base_url, item_id, server_id
+    )
+}
+
+fn to_entry(item: &BaseItem, base_url: &str, server_id: &str) -> MediaEntry {
+    let user_data = item.user_data.clone().unwrap_or_default();
+    let is_episode = item.item_type == "Episode";
+    let position = user_data.playback_position_ticks / TICKS_PER_SECOND;
+
+    MediaEntry {
+        id: item.id.clone(),
+        media_type: item.item_type.to_ascii_lowercase(),
+        title: item.name.clone(),
+        show: item.series_name.clone(),
+        season: item.parent_index_number.filter(|_| is_episode),
+        episode: item.index_number.filter(|_| is_episode),
+        year: item.production_year,
+        summary: item.overview.clone().unwrap_or_default(),
+        duration_seconds: item
+            .run_time_ticks
+            .map(|ticks| (ticks / TICKS_PER_SECOND) as u32),
+        position_seconds: (position > 0).then_some(position as u32),
+        view_count: Some(user_data.play_count),
+        watched: user_data.played,
+        added: item.date_created,
+        released: item.premiere_date,
+        thumbnail_url: item
+            .image_tags
+            .get("Primary")
+            .map(|tag| format!("{}/Items/{}/Images/Primary?tag={}", base_url, item.id, tag)),
+        link: deep_link(base_url, server_id, &item.id),
+    }
+}
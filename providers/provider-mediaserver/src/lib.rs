@@ -0,0 +1,437 @@
+//! # provider-mediaserver
+//!
+//! [Plex](https://www.plex.tv) and [Jellyfin](https://jellyfin.org) provider
+//! for Scryforge.
+//!
+//! This crate implements [`Provider`] and [`HasFeeds`] over a home media
+//! server, for people using Scryforge as a home dashboard. Each server has two
+//! feeds:
+//!
+//! - **Continue Watching**: Plex's "On Deck", Jellyfin's resume list
+//! - **Recently Added**: the newest library entries
+//!
+//! Every item links into the server's web client, so opening it (or the
+//! "Play in Client" action) continues playback there. Mark Read and Mark
+//! Unread mark the entry watched or unwatched on the server.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_mediaserver::{MediaServerConfig, MediaServerProvider};
+//!
+//! let plex = MediaServerProvider::new(MediaServerConfig::plex(
+//!     "http://192.168.1.10:32400",
+//!     "your-plex-token",
+//! ));
+//! let jellyfin = MediaServerProvider::new(MediaServerConfig::jellyfin(
+//!     "https://jellyfin.example.com",
+//!     "your-api-key",
+//! ).with_user_id("4f2b..."));
+//! ```
+//!
+//! ## Sigilforge integration (optional)
+//!
+//! Enable the `sigilforge` cargo feature to fetch the token from a
+//! [Sigilforge] daemon, under the service `"plex"` or `"jellyfin"`:
+//!
+//! ```no_run
+//! # #[cfg(feature = "sigilforge")]
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use provider_mediaserver::{MediaServerConfig, MediaServerKind, MediaServerProvider};
+//! use scryforge_sigilforge_client::MockTokenFetcher;
+//!
+//! let fetcher = MockTokenFetcher::empty();
+//! let config = MediaServerConfig::from_sigilforge(
+//!     &fetcher,
+//!     MediaServerKind::Plex,
+//!     "http://192.168.1.10:32400",
+//!     "home",
+//! ).await?;
+//! let provider = MediaServerProvider::new(config);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Sigilforge]: https://github.com/raibid-labs/sigilforge
+
+pub mod config;
+pub mod error;
+pub mod jellyfin;
+pub mod mapping;
+pub mod plex;
+
+pub use config::{MediaServerConfig, MediaServerKind};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use scryforge_provider_core::prelude::*;
+use std::any::Any;
+use std::time::Instant;
+
+use crate::mapping::{
+    entry_to_item, feed_id, feed_stream_id, parse_item_id, MediaEntry, CONTINUE_FEED, RECENT_FEED,
+};
+
+pub use crate::error::MediaServerError;
+pub use crate::jellyfin::JellyfinClient;
+pub use crate::plex::PlexClient;
+
+/// Entries fetched per feed request when the caller sets no limit.
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// The server a provider talks to.
+pub enum MediaServerClient {
+    Plex(PlexClient),
+    Jellyfin(JellyfinClient),
+}
+
+impl MediaServerClient {
+    fn kind(&self) -> MediaServerKind {
+        match self {
+            MediaServerClient::Plex(_) => MediaServerKind::Plex,
+            MediaServerClient::Jellyfin(_) => MediaServerKind::Jellyfin,
+        }
+    }
+
+    fn base_url(&self) -> &str {
+        match self {
+            MediaServerClient::Plex(client) => client.base_url(),
+            MediaServerClient::Jellyfin(client) => client.base_url(),
+        }
+    }
+
+    async fn continue_watching(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        match self {
+            MediaServerClient::Plex(client) => client.on_deck(limit).await,
+            MediaServerClient::Jellyfin(client) => client.resume(limit).await,
+        }
+    }
+
+    async fn recently_added(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        match self {
+            MediaServerClient::Plex(client) => client.recently_added(limit).await,
+            MediaServerClient::Jellyfin(client) => client.latest(limit).await,
+        }
+    }
+
+    async fn set_watched(
+        &self,
+        id: &str,
+        watched: bool,
+    ) -> std::result::Result<(), MediaServerError> {
+        match self {
+            MediaServerClient::Plex(client) => client.set_watched(id, watched).await,
+            MediaServerClient::Jellyfin(client) => client.set_watched(id, watched).await,
+        }
+    }
+
+    /// Check the server answers and accepts the token.
+    async fn probe(&self) -> std::result::Result<(), MediaServerError> {
+        match self {
+            MediaServerClient::Plex(client) => client.machine_identifier().await.map(|_| ()),
+            MediaServerClient::Jellyfin(client) => client.user_id().await.map(|_| ()),
+        }
+    }
+}
+
+/// Media-server provider implementing `Provider + HasFeeds`.
+pub struct MediaServerProvider {
+    client: MediaServerClient,
+}
+
+impl MediaServerProvider {
+    /// Create a new provider from a [`MediaServerConfig`].
+    pub fn new(config: MediaServerConfig) -> Self {
+        let client = match config.kind {
+            MediaServerKind::Plex => {
+                MediaServerClient::Plex(PlexClient::new(config.server_url, config.token))
+            }
+            MediaServerKind::Jellyfin => MediaServerClient::Jellyfin(JellyfinClient::new(
+                config.server_url,
+                config.token,
+                config.user_id,
+            )),
+        };
+        Self { client }
+    }
+
+    /// Create a new provider from a pre-constructed client.
+    pub fn with_client(client: MediaServerClient) -> Self {
+        Self { client }
+    }
+
+    /// Borrow the underlying client (mainly for tests).
+    pub fn client(&self) -> &MediaServerClient {
+        &self.client
+    }
+
+    fn entry_id<'a>(&self, item: &'a Item) -> Result<&'a str> {
+        parse_item_id(self.id(), &item.id).ok_or_else(|| {
+            StreamError::ItemNotFound(format!(
+                "item id is not a {} item id: {}",
+                self.name(),
+                item.id.as_str()
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for MediaServerProvider {
+    fn id(&self) -> &'static str {
+        self.client.kind().id()
+    }
+
+    fn name(&self) -> &'static str {
+        self.client.kind().name()
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        match self.client.probe().await {
+            Ok(()) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!(
+                    "Connected to {} at {}",
+                    self.name(),
+                    self.client.base_url()
+                )),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("{} health check failed: {e}", self.name())),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = Instant::now();
+        // The server tracks progress itself; feeds are read live, so sync
+        // only checks the server answers.
+        match self.client.probe().await {
+            Ok(()) => Ok(SyncResult {
+                success: true,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: Vec::new(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+            Err(e) => Ok(SyncResult {
+                success: false,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![e.to_string()],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: false,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
+        Ok(vec![
+            Action {
+                id: "play".to_string(),
+                name: "Play in Client".to_string(),
+                description: format!("Continue playback in the {} web client", self.name()),
+                kind: ActionKind::Open,
+                keyboard_shortcut: Some("enter".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the client link to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+            Action {
+                id: "mark_watched".to_string(),
+                name: "Mark as Watched".to_string(),
+                description: format!("Mark the entry watched on the {} server", self.name()),
+                kind: ActionKind::MarkRead,
+                keyboard_shortcut: Some("r".to_string()),
+            },
+            Action {
+                id: "mark_unwatched".to_string(),
+                name: "Mark as Unwatched".to_string(),
+                description: format!("Mark the entry unwatched on the {} server", self.name()),
+                kind: ActionKind::MarkUnread,
+                keyboard_shortcut: Some("u".to_string()),
+            },
+        ])
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match action.kind {
+            ActionKind::Open | ActionKind::OpenInBrowser => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some(format!("Opening {url}")),
+                    data: Some(serde_json::json!({
+                        "url": url,
+                        "resume_seconds": item.metadata.get("resume_seconds"),
+                    })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::CopyLink => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some("Copied link to clipboard".to_string()),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::MarkRead | ActionKind::MarkUnread => {
+                let id = self.entry_id(item)?;
+                let watched = action.kind == ActionKind::MarkRead;
+                self.client.set_watched(id, watched).await?;
+                Ok(ActionResult {
+                    success: true,
+                    message: Some(format!(
+                        "Marked {} as {}",
+                        item.title,
+                        if watched { "watched" } else { "unwatched" }
+                    )),
+                    data: Some(serde_json::json!({ "id": id, "watched": watched })),
+                })
+            }
+            _ => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Executed action: {}", action.name)),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for MediaServerProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let provider = self.id();
+        Ok(vec![
+            Feed {
+                id: feed_id(provider, CONTINUE_FEED),
+                name: "Continue Watching".to_string(),
+                description: Some(format!("Partly watched and up next on {}", self.name())),
+                icon: Some("▶".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+            Feed {
+                id: feed_id(provider, RECENT_FEED),
+                name: "Recently Added".to_string(),
+                description: Some(format!("Newest in the {} library", self.name())),
+                icon: Some("🎬".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+        ])
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let provider = self.id();
+        let name = feed_id
+            .0
+            .strip_prefix(provider)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+
+        let limit = Some(options.limit.unwrap_or(DEFAULT_PAGE_SIZE));
+        let entries = match name {
+            CONTINUE_FEED => self.client.continue_watching(limit).await?,
+            RECENT_FEED => self.client.recently_added(limit).await?,
+            _ => return Err(StreamError::StreamNotFound(feed_id.0.clone())),
+        };
+
+        let stream_id = feed_stream_id(provider, name);
+        Ok(entries
+            .iter()
+            .filter(|entry| options.include_read || !entry.watched)
+            .filter(|entry| match (options.since, entry.added) {
+                (Some(since), Some(added)) => added >= since,
+                _ => true,
+            })
+            .map(|entry| entry_to_item(provider, entry, stream_id.clone()))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Tests (unit only — wiremock-driven integration tests live in tests/)
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_basics() {
+        let plex = MediaServerProvider::new(MediaServerConfig::plex("http://localhost:0", "t"));
+        assert_eq!(plex.id(), "plex");
+        assert_eq!(plex.name(), "Plex");
+        assert!(plex.capabilities().has_feeds);
+        assert!(!plex.capabilities().has_saved_items);
+
+        let jellyfin =
+            MediaServerProvider::new(MediaServerConfig::jellyfin("http://localhost:0", "t"));
+        assert_eq!(jellyfin.id(), "jellyfin");
+        assert_eq!(jellyfin.name(), "Jellyfin");
+    }
+
+    #[tokio::test]
+    async fn feeds_are_namespaced_by_server() {
+        let jellyfin =
+            MediaServerProvider::new(MediaServerConfig::jellyfin("http://localhost:0", "t"));
+        let feeds = jellyfin.list_feeds().await.unwrap();
+        let ids: Vec<&str> = feeds.iter().map(|f| f.id.0.as_str()).collect();
+        assert_eq!(ids, vec!["jellyfin:continue", "jellyfin:recent"]);
+
+        let err = jellyfin
+            .get_feed_items(&FeedId("plex:continue".to_string()), FeedOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StreamError::StreamNotFound(_)));
+    }
+}
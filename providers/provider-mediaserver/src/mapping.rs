@@ -0,0 +1,192 @@
+//! The server-neutral media entry and its conversion to Scryforge items.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::prelude::*;
+use std::collections::HashMap;
+
+/// Local name of the Continue Watching (Plex "On Deck") feed.
+pub(crate) const CONTINUE_FEED: &str = "continue";
+
+/// Local name of the Recently Added feed.
+pub(crate) const RECENT_FEED: &str = "recent";
+
+/// A movie, episode, or other library entry, as either server reports it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaEntry {
+    /// Plex `ratingKey` or Jellyfin item id
+    pub id: String,
+    /// Lowercase media type, e.g. `movie`, `episode`, `season`
+    pub media_type: String,
+    pub title: String,
+    /// Series the entry belongs to
+    pub show: Option<String>,
+    pub season: Option<u32>,
+    pub episode: Option<u32>,
+    pub year: Option<i32>,
+    pub summary: String,
+    pub duration_seconds: Option<u32>,
+    /// Where playback stopped, for partly watched entries
+    pub position_seconds: Option<u32>,
+    pub view_count: Option<u64>,
+    pub watched: bool,
+    pub added: Option<DateTime<Utc>>,
+    pub released: Option<DateTime<Utc>>,
+    pub thumbnail_url: Option<String>,
+    /// Deep link to the entry in the server's web client
+    pub link: String,
+}
+
+/// Build a stream id of the form `<provider>:feed:<name>`.
+pub(crate) fn feed_stream_id(provider: &str, name: &str) -> StreamId {
+    StreamId::new(provider, "feed", name)
+}
+
+/// Build the public `FeedId` for one of the feeds.
+pub(crate) fn feed_id(provider: &str, name: &str) -> FeedId {
+    FeedId(format!("{}:{}", provider, name))
+}
+
+/// Decode an `ItemId` produced by [`entry_to_item`] back into the server's id.
+pub(crate) fn parse_item_id<'a>(provider: &str, id: &'a ItemId) -> Option<&'a str> {
+    id.0.strip_prefix(provider)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .filter(|rest| !rest.is_empty())
+}
+
+/// Display title: `Show · S01E02 · Episode` for episodes, `Movie (1999)` for
+/// movies.
+fn display_title(entry: &MediaEntry) -> String {
+    match (&entry.show, entry.season, entry.episode) {
+        (Some(show), Some(season), Some(episode)) => {
+            format!("{} · S{:02}E{:02} · {}", show, season, episode, entry.title)
+        }
+        (Some(show), _, _) if show != &entry.title => format!("{} · {}", show, entry.title),
+        _ => match entry.year {
+            Some(year) if entry.media_type == "movie" => format!("{} ({})", entry.title, year),
+            _ => entry.title.clone(),
+        },
+    }
+}
+
+/// Convert a [`MediaEntry`] into a Scryforge [`Item`] filed under `stream_id`.
+///
+/// The item's URL is the web-client deep link, so opening the item plays it
+/// in the client.
+pub fn entry_to_item(provider: &str, entry: &MediaEntry, stream_id: StreamId) -> Item {
+    let mut metadata = HashMap::new();
+    metadata.insert("media_type".to_string(), entry.media_type.clone());
+    if let Some(show) = &entry.show {
+        metadata.insert("show".to_string(), show.clone());
+    }
+    if let Some(season) = entry.season {
+        metadata.insert("season".to_string(), season.to_string());
+    }
+    if let Some(episode) = entry.episode {
+        metadata.insert("episode".to_string(), episode.to_string());
+    }
+    if let Some(year) = entry.year {
+        metadata.insert("year".to_string(), year.to_string());
+    }
+    if let Some(position) = entry.position_seconds.filter(|p| *p > 0) {
+        metadata.insert("resume_seconds".to_string(), position.to_string());
+        if let Some(duration) = entry.duration_seconds.filter(|d| *d > 0) {
+            let percent = (u64::from(position) * 100 / u64::from(duration)).min(100);
+            metadata.insert("progress_percent".to_string(), percent.to_string());
+        }
+    }
+
+    Item {
+        id: ItemId::new(provider, &entry.id),
+        stream_id,
+        title: display_title(entry),
+        content: ItemContent::Video {
+            description: entry.summary.clone(),
+            duration_seconds: entry.duration_seconds,
+            view_count: entry.view_count,
+        },
+        author: None,
+        published: entry.released.or(entry.added),
+        updated: entry.added,
+        url: Some(entry.link.clone()),
+        thumbnail_url: entry.thumbnail_url.clone(),
+        is_read: entry.watched,
+        is_saved: false,
+        tags: Vec::new(),
+        metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episode() -> MediaEntry {
+        MediaEntry {
+            id: "1234".to_string(),
+            media_type: "episode".to_string(),
+            title: "The Pilot".to_string(),
+            show: Some("Example Show".to_string()),
+            season: Some(1),
+            episode: Some(2),
+            year: Some(2020),
+            summary: "It begins.".to_string(),
+            duration_seconds: Some(1800),
+            position_seconds: Some(600),
+            view_count: Some(0),
+            link: "https://media.example.com/web/#/details?id=1234".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn episode_maps_to_video_item() {
+        let item = entry_to_item(
+            "jellyfin",
+            &episode(),
+            feed_stream_id("jellyfin", CONTINUE_FEED),
+        );
+
+        assert_eq!(item.id.as_str(), "jellyfin:1234");
+        assert_eq!(item.stream_id.as_str(), "jellyfin:feed:continue");
+        assert_eq!(item.title, "Example Show · S01E02 · The Pilot");
+        assert!(matches!(
+            item.content,
+            ItemContent::Video {
+                duration_seconds: Some(1800),
+                ..
+            }
+        ));
+        assert_eq!(
+            item.url.as_deref(),
+            Some("https://media.example.com/web/#/details?id=1234")
+        );
+        assert!(!item.is_read);
+        assert_eq!(item.metadata["resume_seconds"], "600");
+        assert_eq!(item.metadata["progress_percent"], "33");
+        assert_eq!(item.metadata["season"], "1");
+    }
+
+    #[test]
+    fn movie_title_carries_the_year() {
+        let movie = MediaEntry {
+            id: "9".to_string(),
+            media_type: "movie".to_string(),
+            title: "Example Film".to_string(),
+            year: Some(1999),
+            watched: true,
+            ..Default::default()
+        };
+        let item = entry_to_item("plex", &movie, feed_stream_id("plex", RECENT_FEED));
+        assert_eq!(item.title, "Example Film (1999)");
+        assert!(item.is_read);
+        assert!(!item.metadata.contains_key("resume_seconds"));
+    }
+
+    #[test]
+    fn item_id_round_trips() {
+        let id = ItemId::new("plex", "5678");
+        assert_eq!(parse_item_id("plex", &id), Some("5678"));
+        assert_eq!(parse_item_id("jellyfin", &id), None);
+        assert_eq!(parse_item_id("plex", &ItemId("plexx:1".to_string())), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! Plex Media Server client.
+//!
+//! Requests authenticate with an `X-Plex-Token` header and ask for JSON with
+//! `Accept: application/json`; every listing comes wrapped in a
+//! `MediaContainer`.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+use crate::error::{check_status, MediaServerError};
+use crate::mapping::MediaEntry;
+
+/// Web app that deep links open in.
+const WEB_APP: &str = "https://app.plex.tv/desktop/#!";
+
+#[derive(Debug, Deserialize)]
+struct Envelope<T> {
+    #[serde(rename = "MediaContainer")]
+    media_container: T,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Identity {
+    machine_identifier: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MetadataList {
+    #[serde(rename = "Metadata", default)]
+    metadata: Vec<Metadata>,
+}
+
+/// One entry of a Plex library listing (subset).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub rating_key: String,
+    #[serde(rename = "type", default)]
+    pub media_type: String,
+    #[serde(default)]
+    pub title: String,
+    /// Show title, for episodes
+    pub grandparent_title: Option<String>,
+    /// Show title, for seasons
+    pub parent_title: Option<String>,
+    /// Season number, for episodes
+    pub parent_index: Option<u32>,
+    /// Episode number, for episodes
+    pub index: Option<u32>,
+    pub year: Option<i32>,
+    #[serde(default)]
+    pub summary: String,
+    /// Milliseconds
+    pub duration: Option<u64>,
+    /// Milliseconds watched, for partly watched entries
+    pub view_offset: Option<u64>,
+    pub view_count: Option<u64>,
+    /// Unix seconds
+    pub added_at: Option<i64>,
+    /// `YYYY-MM-DD`
+    pub originally_available_at: Option<String>,
+}
+
+/// Minimal Plex Media Server client.
+#[derive(Debug)]
+pub struct PlexClient {
+    base_url: String,
+    token: String,
+    http: Client,
+    machine_identifier: OnceCell<String>,
+}
+
+impl PlexClient {
+    /// Build a new client. Trailing slashes on `base_url` are normalised away.
+    pub fn new(base_url: impl Into<String>, token: impl Into<String>) -> Self {
+        let http = Client::builder()
+            .user_agent("scryforge-provider-mediaserver/0.1.0")
+            .build()
+            .unwrap_or_default();
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            http,
+            machine_identifier: OnceCell::new(),
+        }
+    }
+
+    /// Server base URL (without trailing slash).
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.http
+            .get(format!("{}{}", self.base_url, path))
+            .header("X-Plex-Token", &self.token)
+            .header("X-Plex-Product", "Scryforge")
+            .header("X-Plex-Client-Identifier", "scryforge")
+            .header("Accept", "application/json")
+    }
+
+    /// `GET /identity` — the server's machine identifier, fetched once.
+    pub async fn machine_identifier(&self) -> std::result::Result<&str, MediaServerError> {
+        let id = self
+            .machine_identifier
+            .get_or_try_init(|| async {
+                let response = check_status(self.get("/identity").send().await?).await?;
+                let identity: Envelope<Identity> = response.json().await?;
+                Ok::<_, MediaServerError>(identity.media_container.machine_identifier)
+            })
+            .await?;
+        Ok(id)
+    }
+
+    async fn list(
+        &self,
+        path: &str,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        let mut request = self.get(path);
+        if let Some(limit) = limit {
+            request = request
+                .header("X-Plex-Container-Start", "0")
+                .header("X-Plex-Container-Size", limit.to_string());
+        }
+        let response = check_status(request.send().await?).await?;
+        let list: Envelope<MetadataList> = response.json().await?;
+        let machine = self.machine_identifier().await?;
+        Ok(list
+            .media_container
+            .metadata
+            .iter()
+            .map(|m| to_entry(m, machine))
+            .collect())
+    }
+
+    /// `GET /library/onDeck` — partly watched entries and next episodes.
+    pub async fn on_deck(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        self.list("/library/onDeck", limit).await
+    }
+
+    /// `GET /library/recentlyAdded`.
+    pub async fn recently_added(
+        &self,
+        limit: Option<u32>,
+    ) -> std::result::Result<Vec<MediaEntry>, MediaServerError> {
+        self.list("/library/recentlyAdded", limit).await
+    }
+
+    /// `GET /:/scrobble` or `/:/unscrobble` — mark an entry watched or not.
+    pub async fn set_watched(
+        &self,
+        rating_key: &str,
+        watched: bool,
+    ) -> std::result::Result<(), MediaServerError> {
+        let path = if watched {
+            "/:/scrobble"
+        } else {
+            "/:/unscrobble"
+        };
+        let request = self.get(path).query(&[
+            ("key", rating_key),
+            ("identifier", "com.plexapp.plugins.library"),
+        ]);
+        check_status(request.send().await?).await?;
+        Ok(())
+    }
+}
+
+/// Deep link to an entry's page in the Plex web app.
+pub(crate) fn deep_link(machine_identifier: &str, rating_key: &str) -> String {
+    format!(
+        "{}/server/{}/details?key=%2Flibrary%2Fmetadata%2F{}",
+        WEB_APP, machine_identifier, rating_key
+    )
+}
+
+fn to_entry(metadata: &Metadata, machine_identifier: &str) -> MediaEntry {
+    let show = match metadata.media_type.as_str() {
+        "episode" => metadata.grandparent_title.clone(),
+        "season" => metadata.parent_title.clone(),
+        _ => None,
+    };
+    let (season, episode) = if metadata.media_type == "episode" {
+        (metadata.parent_index, metadata.index)
+    } else {
+        (None, None)
+    };
+    let released = metadata
+        .originally_available_at
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc());
+
+    MediaEntry {
+        id: metadata.rating_key.clone(),
+        media_type: metadata.media_type.clone(),
+        title: metadata.title.clone(),
+        show,
+        season,
+        episode,
+        year: metadata.year,
+        summary: metadata.summary.clone(),
+        duration_seconds: metadata.duration.map(|ms| (ms / 1000) as u32),
+        position_seconds: metadata.view_offset.map(|ms| (ms / 1000) as u32),
+        view_count: metadata.view_count,
+        watched: metadata.view_count.unwrap_or(0) > 0 && metadata.view_offset.is_none(),
+        added: metadata
+            .added_at
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0)),
+        released,
+        // Plex artwork needs the token in the URL, which shouldn't end up in
+        // the cache
+        thumbnail_url: None,
+        link: deep_link(machine_identifier, &metadata.rating_key),
+    }
+}
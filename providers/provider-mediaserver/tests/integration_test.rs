@@ -0,0 +1,312 @@
+//! Wiremock-driven integration tests for `provider-mediaserver`.
+//!
+//! These tests stand up a `wiremock::MockServer` to impersonate a Plex or
+//! Jellyfin server and exercise the feeds and actions against canned
+//! responses.
+
+use provider_mediaserver::{MediaServerConfig, MediaServerProvider};
+use scryforge_provider_core::prelude::*;
+use serde_json::json;
+use wiremock::matchers::{header, header_exists, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const JELLYFIN_USER: &str = "a1b2c3";
+
+async fn mock_plex_identity(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/identity"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "MediaContainer": { "machineIdentifier": "machine-1", "version": "1.40.0" }
+        })))
+        .mount(server)
+        .await;
+}
+
+async fn mock_jellyfin_server_id(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/System/Info/Public"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Id": "server-9",
+            "ServerName": "Home",
+            "Version": "10.9.0"
+        })))
+        .mount(server)
+        .await;
+}
+
+fn jellyfin_for(server: &MockServer) -> MediaServerProvider {
+    MediaServerProvider::new(
+        MediaServerConfig::jellyfin(server.uri(), "test-token").with_user_id(JELLYFIN_USER),
+    )
+}
+
+fn item_for(provider: &str, id: &str) -> Item {
+    Item {
+        id: ItemId::new(provider, id),
+        stream_id: StreamId::new(provider, "feed", "continue"),
+        title: "Example".to_string(),
+        content: ItemContent::Video {
+            description: String::new(),
+            duration_seconds: None,
+            view_count: None,
+        },
+        author: None,
+        published: None,
+        updated: None,
+        url: None,
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: vec![],
+        metadata: Default::default(),
+    }
+}
+
+fn action(kind: ActionKind) -> Action {
+    Action {
+        id: String::new(),
+        name: String::new(),
+        description: String::new(),
+        kind,
+        keyboard_shortcut: None,
+    }
+}
+
+#[tokio::test]
+async fn plex_on_deck_links_into_the_web_app() {
+    let server = MockServer::start().await;
+    mock_plex_identity(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/library/onDeck"))
+        .and(header("X-Plex-Token", "plex-token"))
+        .and(header("Accept", "application/json"))
+        .and(header("X-Plex-Container-Size", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "MediaContainer": {
+                "size": 1,
+                "Metadata": [{
+                    "ratingKey": "5021",
+                    "type": "episode",
+                    "title": "The Pilot",
+                    "grandparentTitle": "Example Show",
+                    "parentIndex": 1,
+                    "index": 2,
+                    "summary": "It begins.",
+                    "duration": 1800000,
+                    "viewOffset": 900000,
+                    "addedAt": 1704067200,
+                    "originallyAvailableAt": "2020-03-01"
+                }]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = MediaServerProvider::new(MediaServerConfig::plex(server.uri(), "plex-token"));
+    let items = provider
+        .get_feed_items(
+            &FeedId("plex:continue".to_string()),
+            FeedOptions {
+                limit: Some(10),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item.id.as_str(), "plex:5021");
+    assert_eq!(item.stream_id.as_str(), "plex:feed:continue");
+    assert_eq!(item.title, "Example Show · S01E02 · The Pilot");
+    assert_eq!(
+        item.url.as_deref(),
+        Some("https://app.plex.tv/desktop/#!/server/machine-1/details?key=%2Flibrary%2Fmetadata%2F5021")
+    );
+    assert_eq!(item.metadata["progress_percent"], "50");
+    assert_eq!(
+        item.published.unwrap().to_rfc3339(),
+        "2020-03-01T00:00:00+00:00"
+    );
+    assert!(item.thumbnail_url.is_none());
+}
+
+#[tokio::test]
+async fn plex_mark_watched_scrobbles() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/:/scrobble"))
+        .and(query_param("key", "5021"))
+        .and(query_param("identifier", "com.plexapp.plugins.library"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = MediaServerProvider::new(MediaServerConfig::plex(server.uri(), "plex-token"));
+    let result = provider
+        .execute_action(&item_for("plex", "5021"), &action(ActionKind::MarkRead))
+        .await
+        .unwrap();
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn jellyfin_recently_added_hides_watched_by_default() {
+    let server = MockServer::start().await;
+    mock_jellyfin_server_id(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/Users/{}/Items/Latest", JELLYFIN_USER)))
+        .and(header_exists("Authorization"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "Id": "m1",
+                "Name": "Example Film",
+                "Type": "Movie",
+                "ProductionYear": 1999,
+                "Overview": "A film.",
+                "RunTimeTicks": 72000000000u64,
+                "DateCreated": "2024-05-01T10:00:00Z",
+                "ImageTags": { "Primary": "abc" },
+                "UserData": { "PlaybackPositionTicks": 0, "PlayCount": 0, "Played": false }
+            },
+            {
+                "Id": "m2",
+                "Name": "Seen Film",
+                "Type": "Movie",
+                "UserData": { "PlaybackPositionTicks": 0, "PlayCount": 1, "Played": true }
+            }
+        ])))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider = jellyfin_for(&server);
+    let feed = FeedId("jellyfin:recent".to_string());
+    let items = provider
+        .get_feed_items(&feed, FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    let film = &items[0];
+    assert_eq!(film.title, "Example Film (1999)");
+    assert_eq!(
+        film.url,
+        Some(format!(
+            "{}/web/#/details?id=m1&serverId=server-9",
+            server.uri()
+        ))
+    );
+    assert_eq!(
+        film.thumbnail_url,
+        Some(format!("{}/Items/m1/Images/Primary?tag=abc", server.uri()))
+    );
+    assert!(matches!(
+        film.content,
+        ItemContent::Video {
+            duration_seconds: Some(7200),
+            ..
+        }
+    ));
+
+    let all = provider
+        .get_feed_items(
+            &feed,
+            FeedOptions {
+                include_read: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 2);
+}
+
+#[tokio::test]
+async fn jellyfin_looks_up_the_user_once() {
+    let server = MockServer::start().await;
+    mock_jellyfin_server_id(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/Users/Me"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "Id": "me-7" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/Users/me-7/Items/Resume"))
+        .and(query_param("MediaTypes", "Video"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "Items": [{
+                "Id": "e1",
+                "Name": "Part Two",
+                "Type": "Episode",
+                "SeriesName": "Example Show",
+                "ParentIndexNumber": 3,
+                "IndexNumber": 4,
+                "RunTimeTicks": 18000000000u64,
+                "UserData": { "PlaybackPositionTicks": 4500000000u64, "PlayCount": 0, "Played": false }
+            }],
+            "TotalRecordCount": 1
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider =
+        MediaServerProvider::new(MediaServerConfig::jellyfin(server.uri(), "test-token"));
+    let feed = FeedId("jellyfin:continue".to_string());
+    for _ in 0..2 {
+        let items = provider
+            .get_feed_items(&feed, FeedOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(items[0].title, "Example Show · S03E04 · Part Two");
+        assert_eq!(items[0].metadata["resume_seconds"], "450");
+    }
+}
+
+#[tokio::test]
+async fn jellyfin_mark_unwatched_deletes_played_item() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("DELETE"))
+        .and(path(format!("/Users/{}/PlayedItems/e1", JELLYFIN_USER)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "Played": false })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = jellyfin_for(&server);
+    let result = provider
+        .execute_action(&item_for("jellyfin", "e1"), &action(ActionKind::MarkUnread))
+        .await
+        .unwrap();
+    assert!(result.success);
+}
+
+#[tokio::test]
+async fn rejected_token_is_auth_required() {
+    let server = MockServer::start().await;
+
+    for endpoint in ["/identity", "/library/recentlyAdded"] {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+    }
+
+    let provider = MediaServerProvider::new(MediaServerConfig::plex(server.uri(), "bad"));
+    let err = provider
+        .get_feed_items(&FeedId("plex:recent".to_string()), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+    let health = provider.health_check().await.unwrap();
+    assert!(!health.is_healthy);
+}
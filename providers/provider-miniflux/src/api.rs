@@ -381,7 +381,7 @@ impl From<MinifluxApiError> for scryforge_provider_core::StreamError {
             MinifluxApiError::NotFound => StreamError::Provider("Miniflux: not found".to_string()),
             MinifluxApiError::RateLimited => StreamError::RateLimited(60),
             MinifluxApiError::Status { status, body } => {
-                StreamError::Provider(format!("Miniflux HTTP {status}: {body}"))
+                StreamError::from_status(status, None, format!("Miniflux HTTP {status}: {body}"))
             }
             MinifluxApiError::Json(e) => {
                 StreamError::Provider(format!("Miniflux JSON decode failed: {e}"))
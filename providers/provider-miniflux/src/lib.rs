@@ -131,6 +131,7 @@ impl Provider for MinifluxProvider {
                 items_removed: 0,
                 errors: Vec::new(),
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
             Err(e) => Ok(SyncResult {
                 success: false,
@@ -139,6 +140,7 @@ impl Provider for MinifluxProvider {
                 items_removed: 0,
                 errors: vec![e.to_string()],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -149,6 +151,11 @@ impl Provider for MinifluxProvider {
             has_collections: false,
             has_saved_items: true,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
@@ -274,6 +281,10 @@ impl Provider for MinifluxProvider {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
 }
 
 #[async_trait]
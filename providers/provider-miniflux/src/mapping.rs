@@ -1,6 +1,7 @@
 //! Conversion between Miniflux API types and Scryforge domain types.
 
 use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::canonicalize_url;
 use std::collections::HashMap;
 
 use crate::api;
@@ -100,7 +101,7 @@ pub fn entry_to_item(entry: &api::Entry, stream_id: StreamId) -> Item {
     let url = if entry.url.is_empty() {
         None
     } else {
-        Some(entry.url.clone())
+        Some(canonicalize_url(&entry.url))
     };
 
     let mut metadata = HashMap::new();
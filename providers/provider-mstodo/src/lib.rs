@@ -6,7 +6,11 @@
 //! to fetch task lists and tasks. It supports:
 //! - Listing task lists (collections)
 //! - Fetching tasks from lists
-//! - Marking tasks as complete/incomplete
+//! - Creating, editing, and deleting tasks, completing them, and setting
+//!   due dates through [`HasTasks`]
+//!
+//! Requests go through the shared [`graph_client`] crate; syncing fetches
+//! every list's tasks in one `$batch` request.
 //!
 //! ## Authentication
 //!
@@ -37,48 +41,20 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
-use reqwest::Client;
+use graph_client::{BatchRequest, GraphClient, GraphError, Method, Page, GRAPH_BASE_URL};
 use scryforge_provider_core::auth::TokenFetcher;
 use scryforge_provider_core::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
-use thiserror::Error;
 
 // ============================================================================
 // Error Types
 // ============================================================================
 
-#[derive(Debug, Error)]
-pub enum MsTodoError {
-    #[error("Authentication failed: {0}")]
-    Auth(String),
-
-    #[error("API request failed: {0}")]
-    ApiRequest(String),
-
-    #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
-
-    #[error("JSON serialization error: {0}")]
-    Json(#[from] serde_json::Error),
-
-    #[error("Invalid response: {0}")]
-    InvalidResponse(String),
-}
-
-impl From<MsTodoError> for StreamError {
-    fn from(err: MsTodoError) -> Self {
-        match err {
-            MsTodoError::Auth(msg) => StreamError::AuthRequired(msg),
-            MsTodoError::Http(e) => StreamError::Network(e.to_string()),
-            MsTodoError::ApiRequest(msg) => StreamError::Provider(msg),
-            MsTodoError::Json(e) => StreamError::Internal(e.to_string()),
-            MsTodoError::InvalidResponse(msg) => StreamError::Internal(msg),
-        }
-    }
-}
+/// Errors from To Do requests.
+pub type MsTodoError = GraphError;
 
 // ============================================================================
 // Microsoft Graph API Response Types
@@ -95,11 +71,6 @@ struct TaskList {
     is_shared: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct TaskListsResponse {
-    value: Vec<TaskList>,
-}
-
 #[derive(Debug, Clone, Deserialize)]
 struct TodoTask {
     id: String,
@@ -118,15 +89,23 @@ struct TodoTask {
     completed_date_time: Option<DateTimeTimeZone>,
 }
 
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct TaskBody {
     #[serde(default)]
     content: String,
     #[serde(rename = "contentType", default)]
-    #[allow(dead_code)]
     content_type: String,
 }
 
+impl TaskBody {
+    fn text(content: String) -> Self {
+        Self {
+            content,
+            content_type: "text".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 enum TaskStatus {
@@ -137,23 +116,36 @@ enum TaskStatus {
     Deferred,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DateTimeTimeZone {
     #[serde(rename = "dateTime")]
     date_time: String,
     #[serde(rename = "timeZone")]
-    #[allow(dead_code)]
     time_zone: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-struct TasksResponse {
-    value: Vec<TodoTask>,
+impl DateTimeTimeZone {
+    /// Graph stores due dates as midnight on the day.
+    fn due(date: NaiveDate) -> Self {
+        Self {
+            date_time: format!("{}T00:00:00", date.format("%Y-%m-%d")),
+            time_zone: "UTC".to_string(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct UpdateTaskRequest {
-    status: String,
+/// Body of a task create or update; only the fields that are set are sent.
+#[derive(Debug, Clone, Default, Serialize)]
+struct TaskRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<TaskBody>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    /// `Some(None)` clears the due date
+    #[serde(rename = "dueDateTime", skip_serializing_if = "Option::is_none")]
+    due_date_time: Option<Option<DateTimeTimeZone>>,
 }
 
 // ============================================================================
@@ -165,15 +157,11 @@ struct UpdateTaskRequest {
 /// Connects to Microsoft Graph API to fetch task lists and tasks.
 /// Requires OAuth authentication via Sigilforge.
 pub struct MsTodoProvider {
-    token_fetcher: Arc<dyn TokenFetcher>,
-    account: String,
-    client: Client,
-    base_url: String,
+    graph: GraphClient,
 }
 
 impl MsTodoProvider {
     const SERVICE_ID: &'static str = "mstodo";
-    const GRAPH_BASE_URL: &'static str = "https://graph.microsoft.com/v1.0";
 
     /// Create a new Microsoft To Do provider.
     ///
@@ -182,75 +170,69 @@ impl MsTodoProvider {
     /// * `token_fetcher` - Token fetcher for OAuth authentication
     /// * `account` - Account identifier for token lookup (e.g., "personal", "work")
     pub fn new(token_fetcher: Arc<dyn TokenFetcher>, account: String) -> Self {
-        Self {
-            token_fetcher,
-            account,
-            client: Client::new(),
-            base_url: Self::GRAPH_BASE_URL.to_string(),
-        }
+        Self::with_base_url(token_fetcher, account, GRAPH_BASE_URL.to_string())
     }
 
     /// Create a provider with a custom base URL (useful for testing).
-    #[cfg(test)]
     pub fn with_base_url(
         token_fetcher: Arc<dyn TokenFetcher>,
         account: String,
         base_url: String,
     ) -> Self {
         Self {
-            token_fetcher,
-            account,
-            client: Client::new(),
-            base_url,
+            graph: GraphClient::new(token_fetcher, Self::SERVICE_ID, account, base_url),
         }
     }
 
-    /// Fetch a fresh access token.
-    async fn get_token(&self) -> std::result::Result<String, MsTodoError> {
-        self.token_fetcher
-            .fetch_token(Self::SERVICE_ID, &self.account)
+    /// Fetch all task lists from Microsoft To Do, following `@odata.nextLink`.
+    async fn fetch_task_lists(&self) -> std::result::Result<Vec<TaskList>, MsTodoError> {
+        self.graph
+            .get_all("/me/todo/lists", "fetch task lists")
             .await
-            .map_err(|e| MsTodoError::Auth(e.to_string()))
     }
 
-    /// Fetch all task lists from Microsoft To Do.
-    async fn fetch_task_lists(&self) -> std::result::Result<Vec<TaskList>, MsTodoError> {
-        let token = self.get_token().await?;
-        let url = format!("{}/me/todo/lists", self.base_url);
-
-        let response = self.client.get(&url).bearer_auth(&token).send().await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(MsTodoError::ApiRequest(format!(
-                "Failed to fetch task lists: {} - {}",
-                status, body
-            )));
-        }
-
-        let lists_response: TaskListsResponse = response.json().await?;
-        Ok(lists_response.value)
+    /// Fetch tasks from a specific task list, following `@odata.nextLink`.
+    async fn fetch_tasks(&self, list_id: &str) -> std::result::Result<Vec<TodoTask>, MsTodoError> {
+        self.graph
+            .get_all(
+                &format!("/me/todo/lists/{}/tasks", list_id),
+                &format!("fetch tasks from list {}", list_id),
+            )
+            .await
     }
 
-    /// Fetch tasks from a specific task list.
-    async fn fetch_tasks(&self, list_id: &str) -> std::result::Result<Vec<TodoTask>, MsTodoError> {
-        let token = self.get_token().await?;
-        let url = format!("{}/me/todo/lists/{}/tasks", self.base_url, list_id);
-
-        let response = self.client.get(&url).bearer_auth(&token).send().await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(MsTodoError::ApiRequest(format!(
-                "Failed to fetch tasks from list {}: {} - {}",
-                list_id, status, body
-            )));
+    /// Fetch the tasks of several lists, their first pages in one batch.
+    ///
+    /// Lists whose tasks can't be fetched are left out.
+    async fn fetch_all_tasks(
+        &self,
+        lists: &[TaskList],
+    ) -> std::result::Result<Vec<Vec<TodoTask>>, MsTodoError> {
+        let requests = lists
+            .iter()
+            .enumerate()
+            .map(|(i, list)| {
+                BatchRequest::get(i.to_string(), format!("/me/todo/lists/{}/tasks", list.id))
+            })
+            .collect();
+        let responses = self.graph.batch(requests).await?;
+
+        let mut all = Vec::new();
+        for (list, response) in lists.iter().zip(responses) {
+            let what = format!("fetch tasks from list {}", list.id);
+            let Ok(page) = response.into_result::<Page<TodoTask>>(&what) else {
+                continue;
+            };
+            let mut tasks = page.value;
+            if let Some(next) = page.next_link {
+                match self.graph.get_all(&next, &what).await {
+                    Ok(rest) => tasks.extend(rest),
+                    Err(_) => continue,
+                }
+            }
+            all.push(tasks);
         }
-
-        let tasks_response: TasksResponse = response.json().await?;
-        Ok(tasks_response.value)
+        Ok(all)
     }
 
     /// Convert a Microsoft To Do task to a Scryforge Item.
@@ -302,6 +284,55 @@ impl MsTodoProvider {
         }
     }
 
+    /// The task list and Graph task IDs of a task item.
+    fn task_location(item: &Item) -> Result<(&str, &str)> {
+        let task_id = item
+            .id
+            .0
+            .strip_prefix("mstodo:")
+            .ok_or_else(|| StreamError::ItemNotFound(item.id.0.clone()))?;
+        let list_id = item
+            .stream_id
+            .0
+            .split(':')
+            .nth(2)
+            .ok_or_else(|| StreamError::Internal("Invalid stream ID".to_string()))?;
+        Ok((list_id, task_id))
+    }
+
+    /// Create a task in a list, returning it as stored.
+    async fn post_task(
+        &self,
+        list_id: &str,
+        request: &TaskRequest,
+    ) -> std::result::Result<TodoTask, MsTodoError> {
+        self.graph
+            .send_json(
+                Method::POST,
+                &format!("/me/todo/lists/{}/tasks", list_id),
+                Some(request),
+                &format!("create task in list {}", list_id),
+            )
+            .await
+    }
+
+    /// Change the fields set in `request`, returning the updated task.
+    async fn patch_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        request: &TaskRequest,
+    ) -> std::result::Result<TodoTask, MsTodoError> {
+        self.graph
+            .send_json(
+                Method::PATCH,
+                &format!("/me/todo/lists/{}/tasks/{}", list_id, task_id),
+                Some(request),
+                &format!("update task {}", task_id),
+            )
+            .await
+    }
+
     /// Update a task's completion status.
     async fn update_task_status(
         &self,
@@ -309,35 +340,13 @@ impl MsTodoProvider {
         task_id: &str,
         completed: bool,
     ) -> std::result::Result<(), MsTodoError> {
-        let token = self.get_token().await?;
-        let url = format!(
-            "{}/me/todo/lists/{}/tasks/{}",
-            self.base_url, list_id, task_id
-        );
-
         let status = if completed { "completed" } else { "notStarted" };
-
-        let request_body = UpdateTaskRequest {
-            status: status.to_string(),
+        let request = TaskRequest {
+            status: Some(status.to_string()),
+            ..TaskRequest::default()
         };
 
-        let response = self
-            .client
-            .patch(&url)
-            .bearer_auth(&token)
-            .json(&request_body)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(MsTodoError::ApiRequest(format!(
-                "Failed to update task {}: {} - {}",
-                task_id, status, body
-            )));
-        }
-
+        self.patch_task(list_id, task_id, &request).await?;
         Ok(())
     }
 }
@@ -377,14 +386,14 @@ impl Provider for MsTodoProvider {
     async fn sync(&self) -> Result<SyncResult> {
         let start = std::time::Instant::now();
 
-        match self.fetch_task_lists().await {
-            Ok(lists) => {
-                let mut total_tasks = 0;
-                for list in lists {
-                    if let Ok(tasks) = self.fetch_tasks(&list.id).await {
-                        total_tasks += tasks.len();
-                    }
-                }
+        let tasks = match self.fetch_task_lists().await {
+            Ok(lists) => self.fetch_all_tasks(&lists).await,
+            Err(e) => Err(e),
+        };
+
+        match tasks {
+            Ok(tasks) => {
+                let total_tasks: usize = tasks.iter().map(Vec::len).sum();
 
                 Ok(SyncResult {
                     success: true,
@@ -393,6 +402,7 @@ impl Provider for MsTodoProvider {
                     items_removed: 0,
                     errors: vec![],
                     duration_ms: start.elapsed().as_millis() as u64,
+                    streams: Vec::new(),
                 })
             }
             Err(e) => Ok(SyncResult {
@@ -402,6 +412,7 @@ impl Provider for MsTodoProvider {
                 items_removed: 0,
                 errors: vec![e.to_string()],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -412,6 +423,11 @@ impl Provider for MsTodoProvider {
             has_collections: true,
             has_saved_items: false,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: false,
+            supports_notifications: false,
         }
     }
 
@@ -460,18 +476,7 @@ impl Provider for MsTodoProvider {
     async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
         match action.kind {
             ActionKind::Custom(ref custom) if custom == "complete" => {
-                // Extract list_id from stream_id and task_id from item_id
-                let task_id = item.id.0.strip_prefix("mstodo:").unwrap_or(&item.id.0);
-                let list_id = item
-                    .stream_id
-                    .0
-                    .split(':')
-                    .nth(2)
-                    .ok_or_else(|| StreamError::Internal("Invalid stream ID".to_string()))?;
-
-                self.update_task_status(list_id, task_id, true)
-                    .await
-                    .map_err(|e| StreamError::Provider(e.to_string()))?;
+                self.complete_task(item).await?;
 
                 Ok(ActionResult {
                     success: true,
@@ -480,17 +485,7 @@ impl Provider for MsTodoProvider {
                 })
             }
             ActionKind::Custom(ref custom) if custom == "uncomplete" => {
-                let task_id = item.id.0.strip_prefix("mstodo:").unwrap_or(&item.id.0);
-                let list_id = item
-                    .stream_id
-                    .0
-                    .split(':')
-                    .nth(2)
-                    .ok_or_else(|| StreamError::Internal("Invalid stream ID".to_string()))?;
-
-                self.update_task_status(list_id, task_id, false)
-                    .await
-                    .map_err(|e| StreamError::Provider(e.to_string()))?;
+                self.uncomplete_task(item).await?;
 
                 Ok(ActionResult {
                     success: true,
@@ -509,15 +504,20 @@ impl Provider for MsTodoProvider {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        Some(self)
+    }
+
+    fn as_tasks(&self) -> Option<&dyn HasTasks> {
+        Some(self)
+    }
 }
 
 #[async_trait]
 impl HasCollections for MsTodoProvider {
     async fn list_collections(&self) -> Result<Vec<Collection>> {
-        let lists = self
-            .fetch_task_lists()
-            .await
-            .map_err(|e| StreamError::Provider(e.to_string()))?;
+        let lists = self.fetch_task_lists().await.map_err(StreamError::from)?;
 
         let collections = lists
             .into_iter()
@@ -543,7 +543,7 @@ impl HasCollections for MsTodoProvider {
         let tasks = self
             .fetch_tasks(&collection_id.0)
             .await
-            .map_err(|e| StreamError::Provider(e.to_string()))?;
+            .map_err(StreamError::from)?;
 
         let items = tasks
             .into_iter()
@@ -560,7 +560,7 @@ impl HasCollections for MsTodoProvider {
     ) -> Result<()> {
         // Microsoft To Do doesn't support moving tasks between lists via this operation.
         // Tasks are created in specific lists and can't be added like playlist items.
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Adding tasks to collections is not supported by Microsoft To Do API".to_string(),
         ))
     }
@@ -572,18 +572,12 @@ impl HasCollections for MsTodoProvider {
     ) -> Result<()> {
         // Removing a task from a list would be equivalent to deleting the task.
         // This operation is not implemented in the current phase.
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Removing tasks from collections is not supported in this implementation".to_string(),
         ))
     }
 
     async fn create_collection(&self, name: &str) -> Result<Collection> {
-        let token = self
-            .get_token()
-            .await
-            .map_err(|e| StreamError::AuthRequired(e.to_string()))?;
-        let url = format!("{}/me/todo/lists", self.base_url);
-
         #[derive(Serialize)]
         struct CreateListRequest {
             #[serde(rename = "displayName")]
@@ -594,28 +588,16 @@ impl HasCollections for MsTodoProvider {
             display_name: name.to_string(),
         };
 
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&token)
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to create task list: {} - {}",
-                status, body
-            )));
-        }
-
-        let task_list: TaskList = response
-            .json()
+        let task_list: TaskList = self
+            .graph
+            .send_json(
+                Method::POST,
+                "/me/todo/lists",
+                Some(&request_body),
+                "create task list",
+            )
             .await
-            .map_err(|e| StreamError::Internal(e.to_string()))?;
+            .map_err(StreamError::from)?;
 
         Ok(Collection {
             id: CollectionId(task_list.id.clone()),
@@ -635,40 +617,76 @@ impl HasCollections for MsTodoProvider {
 
 #[async_trait]
 impl HasTasks for MsTodoProvider {
-    async fn complete_task(&self, task_id: &str) -> Result<()> {
-        // Extract list_id and task_id from the format "list_id/task_id"
-        let parts: Vec<&str> = task_id.split('/').collect();
-        if parts.len() != 2 {
-            return Err(StreamError::Internal(format!(
-                "Invalid task_id format: {}",
-                task_id
-            )));
-        }
+    async fn complete_task(&self, task: &Item) -> Result<()> {
+        let (list_id, task_id) = Self::task_location(task)?;
+        self.update_task_status(list_id, task_id, true)
+            .await
+            .map_err(StreamError::from)
+    }
 
-        let list_id = parts[0];
-        let task_id = parts[1];
+    async fn uncomplete_task(&self, task: &Item) -> Result<()> {
+        let (list_id, task_id) = Self::task_location(task)?;
+        self.update_task_status(list_id, task_id, false)
+            .await
+            .map_err(StreamError::from)
+    }
 
-        self.update_task_status(list_id, task_id, true)
+    async fn create_task(&self, list_id: &CollectionId, task: TaskDraft) -> Result<Item> {
+        let request = TaskRequest {
+            title: Some(task.title),
+            body: task.body.map(TaskBody::text),
+            due_date_time: task.due_date.map(|date| Some(DateTimeTimeZone::due(date))),
+            ..TaskRequest::default()
+        };
+
+        let created = self
+            .post_task(&list_id.0, &request)
             .await
-            .map_err(|e| StreamError::Provider(e.to_string()))
+            .map_err(StreamError::from)?;
+        Ok(self.task_to_item(created, &list_id.0))
     }
 
-    async fn uncomplete_task(&self, task_id: &str) -> Result<()> {
-        // Extract list_id and task_id from the format "list_id/task_id"
-        let parts: Vec<&str> = task_id.split('/').collect();
-        if parts.len() != 2 {
-            return Err(StreamError::Internal(format!(
-                "Invalid task_id format: {}",
-                task_id
-            )));
-        }
+    async fn update_task(&self, task: &Item, update: TaskUpdate) -> Result<Item> {
+        let (list_id, task_id) = Self::task_location(task)?;
+        let request = TaskRequest {
+            title: update.title,
+            body: update.body.map(TaskBody::text),
+            ..TaskRequest::default()
+        };
 
-        let list_id = parts[0];
-        let task_id = parts[1];
+        let updated = self
+            .patch_task(list_id, task_id, &request)
+            .await
+            .map_err(StreamError::from)?;
+        Ok(self.task_to_item(updated, list_id))
+    }
 
-        self.update_task_status(list_id, task_id, false)
+    async fn delete_task(&self, task: &Item) -> Result<()> {
+        let (list_id, task_id) = Self::task_location(task)?;
+        self.graph
+            .send(
+                Method::DELETE,
+                &format!("/me/todo/lists/{}/tasks/{}", list_id, task_id),
+                None::<&()>,
+                &format!("delete task {}", task_id),
+            )
+            .await
+            .map_err(StreamError::from)?;
+        Ok(())
+    }
+
+    async fn set_due_date(&self, task: &Item, due_date: Option<NaiveDate>) -> Result<Item> {
+        let (list_id, task_id) = Self::task_location(task)?;
+        let request = TaskRequest {
+            due_date_time: Some(due_date.map(DateTimeTimeZone::due)),
+            ..TaskRequest::default()
+        };
+
+        let updated = self
+            .patch_task(list_id, task_id, &request)
             .await
-            .map_err(|e| StreamError::Provider(e.to_string()))
+            .map_err(StreamError::from)?;
+        Ok(self.task_to_item(updated, list_id))
     }
 }
 
@@ -707,7 +725,7 @@ mod tests {
     #[tokio::test]
     async fn test_get_token() {
         let provider = create_test_provider();
-        let token = provider.get_token().await.unwrap();
+        let token = provider.graph.token().await.unwrap();
         assert_eq!(token, "test_token_123");
     }
 
@@ -0,0 +1,264 @@
+//! Mock-server tests for `provider-mstodo`'s Microsoft Graph HTTP paths.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use provider_mstodo::MsTodoProvider;
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{body_partial_json, method, path};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::{fixture, MockApi};
+use serde_json::json;
+
+fn provider_for(api: &MockApi) -> MsTodoProvider {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "mstodo".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    MsTodoProvider::with_base_url(Arc::new(tokens), "personal".to_string(), api.uri())
+}
+
+#[tokio::test]
+async fn list_collections_follows_next_link() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/me/todo/lists",
+        "$skiptoken",
+        vec![
+            (None, api.fixture("graph/todo_lists_page1.json")),
+            (Some("page2"), api.fixture("graph/todo_lists_page2.json")),
+        ],
+    )
+    .await;
+
+    let collections = provider_for(&api).list_collections().await.unwrap();
+
+    let names: Vec<&str> = collections.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Tasks", "Groceries"]);
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn sync_batches_task_requests() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/me/todo/lists",
+        "$skiptoken",
+        vec![
+            (None, api.fixture("graph/todo_lists_page1.json")),
+            (Some("page2"), api.fixture("graph/todo_lists_page2.json")),
+        ],
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/$batch"))
+        .and(body_partial_json(json!({
+            "requests": [
+                { "id": "0", "method": "GET", "url": "/me/todo/lists/list-1/tasks" },
+                { "id": "1", "method": "GET", "url": "/me/todo/lists/list-2/tasks" }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "responses": [
+                { "id": "1", "status": 404, "body": {} },
+                { "id": "0", "status": 200, "body": fixture("graph/todo_tasks.json") }
+            ]
+        })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let result = provider_for(&api).sync().await.unwrap();
+
+    // A list whose tasks fail to load is skipped
+    assert!(result.success);
+    assert_eq!(result.items_added, 2);
+}
+
+#[tokio::test]
+async fn collection_items_are_tasks() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/me/todo/lists/list-1/tasks",
+        fixture("graph/todo_tasks.json"),
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_collection_items(&CollectionId("list-1".to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert!(matches!(
+        items[1].content,
+        ItemContent::Task {
+            is_completed: true,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn expired_token_requires_auth() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/me/todo/lists",
+        401,
+        fixture("graph/error_unauthorized.json"),
+    )
+    .await;
+
+    let err = provider_for(&api).list_collections().await.unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn throttling_is_rate_limited() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/me/todo/lists", 5).await;
+
+    let err = provider_for(&api).list_collections().await.unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(5)), "{:?}", err);
+}
+
+fn task_json(id: &str, title: &str, due: Option<&str>) -> serde_json::Value {
+    let mut task = json!({
+        "id": id,
+        "title": title,
+        "body": { "content": "", "contentType": "text" },
+        "status": "notStarted",
+        "createdDateTime": "2024-01-01T09:00:00Z",
+        "lastModifiedDateTime": "2024-01-01T09:00:00Z"
+    });
+    if let Some(due) = due {
+        task["dueDateTime"] = json!({ "dateTime": due, "timeZone": "UTC" });
+    }
+    task
+}
+
+#[tokio::test]
+async fn create_and_schedule_tasks() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/me/todo/lists/list-1/tasks"))
+        .and(body_partial_json(json!({
+            "title": "Renew passport",
+            "dueDateTime": { "dateTime": "2024-03-01T00:00:00", "timeZone": "UTC" }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(task_json(
+            "task-9",
+            "Renew passport",
+            Some("2024-03-01T00:00:00.0000000"),
+        )))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/me/todo/lists/list-1/tasks/task-9"))
+        .and(body_partial_json(json!({ "dueDateTime": null })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json(
+            "task-9",
+            "Renew passport",
+            None,
+        )))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let tasks = provider.as_tasks().unwrap();
+    let created = tasks
+        .create_task(
+            &CollectionId("list-1".to_string()),
+            TaskDraft {
+                title: "Renew passport".to_string(),
+                body: None,
+                due_date: NaiveDate::from_ymd_opt(2024, 3, 1),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(created.id.as_str(), "mstodo:task-9");
+    assert_eq!(created.stream_id.as_str(), "mstodo:collection:list-1");
+    assert!(matches!(
+        created.content,
+        ItemContent::Task {
+            due_date: Some(_),
+            ..
+        }
+    ));
+
+    let cleared = tasks.set_due_date(&created, None).await.unwrap();
+    assert!(matches!(
+        cleared.content,
+        ItemContent::Task { due_date: None, .. }
+    ));
+}
+
+#[tokio::test]
+async fn update_complete_and_delete_tasks() {
+    let api = MockApi::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/me/todo/lists/list-1/tasks/task-1"))
+        .and(body_partial_json(json!({ "title": "Write annual report" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json(
+            "task-1",
+            "Write annual report",
+            None,
+        )))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/me/todo/lists/list-1/tasks/task-1"))
+        .and(body_partial_json(json!({ "status": "completed" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(task_json(
+            "task-1",
+            "Write annual report",
+            None,
+        )))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/me/todo/lists/list-1/tasks/task-1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    api.mock_json(
+        "GET",
+        "/me/todo/lists/list-1/tasks",
+        fixture("graph/todo_tasks.json"),
+    )
+    .await;
+
+    let provider = provider_for(&api);
+    let task = provider
+        .get_collection_items(&CollectionId("list-1".to_string()))
+        .await
+        .unwrap()
+        .remove(0);
+    let tasks = provider.as_tasks().unwrap();
+
+    let updated = tasks
+        .update_task(
+            &task,
+            TaskUpdate {
+                title: Some("Write annual report".to_string()),
+                body: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Write annual report");
+
+    tasks.complete_task(&task).await.unwrap();
+    tasks.delete_task(&task).await.unwrap();
+}
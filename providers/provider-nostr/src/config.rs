@@ -0,0 +1,35 @@
+//! Configuration for the Nostr provider.
+
+use serde::{Deserialize, Serialize};
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+/// Configuration for the [`NostrProvider`](crate::NostrProvider).
+///
+/// `pubkey` is the account to read as, either hex or a NIP-19 `npub`. Only
+/// the public key is needed: its contact list names the people to follow,
+/// and its reactions and bookmark list are read back as saved items.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NostrProviderConfig {
+    /// Public key of the account, hex or `npub1...`.
+    pub pubkey: String,
+    /// Relay URLs (`wss://...`) to query. Every relay is asked and the
+    /// results are merged.
+    pub relays: Vec<String>,
+    /// How long to wait for each relay to send all stored events.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl NostrProviderConfig {
+    /// Configuration reading `pubkey`'s view of `relays`.
+    pub fn new(pubkey: impl Into<String>, relays: Vec<String>) -> Self {
+        Self {
+            pubkey: pubkey.into(),
+            relays,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
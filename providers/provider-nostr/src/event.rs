@@ -0,0 +1,227 @@
+//! Nostr events and subscription filters (NIP-01).
+
+use serde::{Deserialize, Serialize};
+
+/// Profile metadata (NIP-01).
+pub const KIND_METADATA: u16 = 0;
+/// Short text note (NIP-01).
+pub const KIND_TEXT_NOTE: u16 = 1;
+/// Follow list (NIP-02).
+pub const KIND_CONTACTS: u16 = 3;
+/// Reaction (NIP-25).
+pub const KIND_REACTION: u16 = 7;
+/// Bookmark list (NIP-51).
+pub const KIND_BOOKMARKS: u16 = 10003;
+
+/// A signed Nostr event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// Lowercase hex SHA-256 of the serialized event.
+    pub id: String,
+    /// Lowercase hex public key of the author.
+    pub pubkey: String,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+    pub kind: u16,
+    #[serde(default)]
+    pub tags: Vec<Vec<String>>,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub sig: String,
+}
+
+impl Event {
+    /// Values of every `name` tag, in order.
+    pub fn tag_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags
+            .iter()
+            .filter(move |tag| tag.first().map(String::as_str) == Some(name))
+            .filter_map(|tag| tag.get(1).map(String::as_str))
+    }
+
+    /// The event a note replies to and the thread root, per NIP-10.
+    ///
+    /// Marked `e` tags (`root` / `reply`) win; unmarked tags use the
+    /// deprecated positional scheme, first root and last reply.
+    pub fn thread(&self) -> (Option<&str>, Option<&str>) {
+        let e_tags: Vec<&Vec<String>> = self
+            .tags
+            .iter()
+            .filter(|tag| tag.len() >= 2 && tag[0] == "e")
+            .collect();
+        let marked = |marker: &str| {
+            e_tags
+                .iter()
+                .find(|tag| tag.get(3).map(String::as_str) == Some(marker))
+                .map(|tag| tag[1].as_str())
+        };
+
+        let root = marked("root");
+        let reply = marked("reply");
+        if root.is_some() || reply.is_some() {
+            return (root, reply.or(root));
+        }
+        let first = e_tags.first().map(|tag| tag[1].as_str());
+        let last = e_tags.last().map(|tag| tag[1].as_str());
+        (first, last)
+    }
+
+    /// The event a reaction reacts to: its last `e` tag (NIP-25).
+    pub fn reacted_event(&self) -> Option<&str> {
+        self.tag_values("e").last()
+    }
+
+    /// Whether a reaction is a like: anything but a `-` dislike (NIP-25).
+    pub fn is_like(&self) -> bool {
+        self.kind == KIND_REACTION && self.content != "-"
+    }
+}
+
+/// Profile fields of a kind-0 event's JSON content.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub picture: Option<String>,
+    pub nip05: Option<String>,
+}
+
+impl Profile {
+    /// Parse the profile in a metadata event, if it is one.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if event.kind != KIND_METADATA {
+            return None;
+        }
+        serde_json::from_str(&event.content).ok()
+    }
+
+    /// The name to show: display name, then name.
+    pub fn best_name(&self) -> Option<&str> {
+        [&self.display_name, &self.name]
+            .into_iter()
+            .flatten()
+            .map(|name| name.trim())
+            .find(|name| !name.is_empty())
+    }
+}
+
+/// A subscription filter. Empty lists and unset fields are left out, which
+/// relays read as "no constraint".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Filter {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl Filter {
+    /// Events of `kinds` by `authors`.
+    pub fn by_authors(authors: Vec<String>, kinds: &[u16]) -> Self {
+        Self {
+            authors,
+            kinds: kinds.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    /// Events with the given IDs.
+    pub fn by_ids(ids: Vec<String>) -> Self {
+        Self {
+            ids,
+            ..Default::default()
+        }
+    }
+
+    pub fn since(mut self, since: Option<i64>) -> Self {
+        self.since = since;
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// Of several events, the newest per author; for replaceable kinds such as
+/// profiles, follow lists, and bookmark lists.
+pub fn latest_by_author(events: &[Event], kind: u16) -> Vec<&Event> {
+    let mut latest: Vec<&Event> = Vec::new();
+    for event in events.iter().filter(|event| event.kind == kind) {
+        match latest.iter_mut().find(|e| e.pubkey == event.pubkey) {
+            Some(existing) if existing.created_at < event.created_at => *existing = event,
+            Some(_) => {}
+            None => latest.push(event),
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: u16, tags: &[&[&str]], content: &str) -> Event {
+        Event {
+            id: "id".to_string(),
+            pubkey: "pk".to_string(),
+            created_at: 0,
+            kind,
+            tags: tags
+                .iter()
+                .map(|tag| tag.iter().map(|s| s.to_string()).collect())
+                .collect(),
+            content: content.to_string(),
+            sig: String::new(),
+        }
+    }
+
+    #[test]
+    fn thread_prefers_markers() {
+        let marked = event(
+            KIND_TEXT_NOTE,
+            &[
+                &["e", "reply-id", "", "reply"],
+                &["p", "someone"],
+                &["e", "root-id", "wss://relay", "root"],
+            ],
+            "",
+        );
+        assert_eq!(marked.thread(), (Some("root-id"), Some("reply-id")));
+
+        let positional = event(KIND_TEXT_NOTE, &[&["e", "a"], &["e", "b"], &["e", "c"]], "");
+        assert_eq!(positional.thread(), (Some("a"), Some("c")));
+
+        assert_eq!(event(KIND_TEXT_NOTE, &[], "").thread(), (None, None));
+    }
+
+    #[test]
+    fn reactions() {
+        let like = event(
+            KIND_REACTION,
+            &[&["e", "x"], &["p", "y"], &["e", "target"]],
+            "🤙",
+        );
+        assert!(like.is_like());
+        assert_eq!(like.reacted_event(), Some("target"));
+        assert!(!event(KIND_REACTION, &[&["e", "target"]], "-").is_like());
+    }
+
+    #[test]
+    fn filter_skips_empty_fields() {
+        let filter = Filter::by_authors(vec!["pk".to_string()], &[KIND_TEXT_NOTE]).limit(20);
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            serde_json::json!({"authors": ["pk"], "kinds": [1], "limit": 20})
+        );
+    }
+}
@@ -0,0 +1,587 @@
+//! # provider-nostr
+//!
+//! [Nostr](https://nostr.com) provider for Scryforge.
+//!
+//! This crate implements [`Provider`], [`HasFeeds`], and [`HasSavedItems`] by
+//! querying the configured relays over WebSockets. The account's follow list
+//! (NIP-02) decides whose notes appear in the "Following" feed, and the
+//! notes it has liked (NIP-25 reactions) or added to its bookmark list
+//! (NIP-51) are its saved items.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_nostr::{NostrProvider, NostrProviderConfig};
+//!
+//! let config = NostrProviderConfig::new(
+//!     "npub180cvv07tjdrrgpa0j7j7tmnyl2yr6yr7l8j4s3evf6u64th6gkwsyjh6w6",
+//!     vec!["wss://relay.damus.io".to_string(), "wss://nos.lol".to_string()],
+//! );
+//! let provider = NostrProvider::new(config);
+//! ```
+//!
+//! ## Read-only
+//!
+//! The provider only knows the account's public key. Publishing reactions or
+//! changing the bookmark list means signing events, so saving and unsaving
+//! report an error and the provider doesn't claim write support. Event
+//! signatures aren't verified either: relays are trusted to serve what their
+//! authors signed.
+
+pub mod config;
+pub mod event;
+pub mod nip19;
+pub mod relay;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub use config::NostrProviderConfig;
+pub use relay::RelayError;
+
+use event::{
+    latest_by_author, Event, Filter, Profile, KIND_BOOKMARKS, KIND_CONTACTS, KIND_METADATA,
+    KIND_REACTION, KIND_TEXT_NOTE,
+};
+use relay::RelayPool;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "nostr";
+
+/// Local id of the feed of notes by followed accounts.
+const FOLLOWING_FEED: &str = "following";
+
+/// Saved-item categories, one per source.
+const BOOKMARKS: &str = "bookmarks";
+const REACTIONS: &str = "reactions";
+
+/// Web viewer for notes and profiles.
+const VIEWER_URL: &str = "https://njump.me";
+
+/// Reactions read back for saved items.
+const REACTION_LIMIT: u32 = 500;
+
+const DEFAULT_LIMIT: u32 = 50;
+
+/// A saved note and what saved it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SavedRef {
+    event_id: String,
+    category: &'static str,
+    /// The reaction's content, such as `+` or an emoji
+    reaction: Option<String>,
+}
+
+/// Nostr provider for Scryforge.
+pub struct NostrProvider {
+    config: NostrProviderConfig,
+    pool: RelayPool,
+}
+
+impl NostrProvider {
+    /// Create a provider reading `config.pubkey`'s view of `config.relays`.
+    pub fn new(config: NostrProviderConfig) -> Self {
+        let pool = RelayPool::new(
+            config.relays.clone(),
+            Duration::from_secs(config.timeout_secs),
+        );
+        Self { config, pool }
+    }
+
+    /// The account's public key as hex.
+    fn pubkey(&self) -> Result<String> {
+        nip19::parse_pubkey(&self.config.pubkey).ok_or_else(|| {
+            StreamError::Provider(format!("Invalid public key `{}`", self.config.pubkey))
+        })
+    }
+
+    /// Public keys in the account's newest follow list.
+    pub async fn follows(&self) -> Result<Vec<String>> {
+        let pubkey = self.pubkey()?;
+        let events = self
+            .pool
+            .query(&[Filter::by_authors(vec![pubkey], &[KIND_CONTACTS]).limit(1)])
+            .await?;
+
+        let mut follows: Vec<String> = Vec::new();
+        if let Some(contacts) = latest_by_author(&events, KIND_CONTACTS).first() {
+            for key in contacts.tag_values("p").filter_map(nip19::parse_pubkey) {
+                if !follows.contains(&key) {
+                    follows.push(key);
+                }
+            }
+        }
+        Ok(follows)
+    }
+
+    /// Saved notes, newest first: the bookmark list, then liked notes.
+    async fn saved_refs(&self) -> Result<Vec<SavedRef>> {
+        let pubkey = self.pubkey()?;
+        let events = self
+            .pool
+            .query(&[
+                Filter::by_authors(vec![pubkey.clone()], &[KIND_BOOKMARKS]).limit(1),
+                Filter::by_authors(vec![pubkey], &[KIND_REACTION]).limit(REACTION_LIMIT),
+            ])
+            .await?;
+
+        let mut refs: Vec<SavedRef> = Vec::new();
+        // New bookmarks are appended to the list
+        if let Some(list) = latest_by_author(&events, KIND_BOOKMARKS).first() {
+            let ids: Vec<&str> = list.tag_values("e").collect();
+            for id in ids.into_iter().rev() {
+                refs.push(SavedRef {
+                    event_id: id.to_string(),
+                    category: BOOKMARKS,
+                    reaction: None,
+                });
+            }
+        }
+        // `events` is newest first
+        for reaction in events.iter().filter(|event| event.is_like()) {
+            if let Some(id) = reaction.reacted_event() {
+                if !refs.iter().any(|saved| saved.event_id == id) {
+                    refs.push(SavedRef {
+                        event_id: id.to_string(),
+                        category: REACTIONS,
+                        reaction: Some(reaction.content.clone()),
+                    });
+                }
+            }
+        }
+        Ok(refs)
+    }
+
+    /// Newest profile of each of `authors`.
+    async fn profiles(&self, authors: Vec<String>) -> HashMap<String, Profile> {
+        if authors.is_empty() {
+            return HashMap::new();
+        }
+        // Names are cosmetic, so a failed lookup leaves notes unnamed
+        let events = self
+            .pool
+            .query(&[Filter::by_authors(authors, &[KIND_METADATA])])
+            .await
+            .unwrap_or_default();
+        latest_by_author(&events, KIND_METADATA)
+            .into_iter()
+            .filter_map(|event| Some((event.pubkey.clone(), Profile::from_event(event)?)))
+            .collect()
+    }
+
+    /// Look up the profiles of `notes`' authors and convert the notes.
+    async fn notes_to_items(
+        &self,
+        notes: Vec<Event>,
+        stream_id: impl Fn(&Event) -> StreamId,
+    ) -> Vec<Item> {
+        let mut authors: Vec<String> = notes.iter().map(|note| note.pubkey.clone()).collect();
+        authors.sort();
+        authors.dedup();
+        let profiles = self.profiles(authors).await;
+
+        notes
+            .into_iter()
+            .map(|note| {
+                let stream = stream_id(&note);
+                note_to_item(&note, profiles.get(&note.pubkey), stream)
+            })
+            .collect()
+    }
+}
+
+/// Title for a note: its first non-empty line, shortened.
+fn note_title(content: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("(empty note)");
+    if line.chars().count() > MAX_CHARS {
+        let short: String = line.chars().take(MAX_CHARS - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Convert a text note into an [`Item`].
+fn note_to_item(note: &Event, profile: Option<&Profile>, stream_id: StreamId) -> Item {
+    let npub = nip19::encode("npub", &note.pubkey);
+
+    let mut metadata = HashMap::new();
+    metadata.insert("nostr_pubkey".to_string(), note.pubkey.clone());
+    if let Some(npub) = &npub {
+        metadata.insert("nostr_npub".to_string(), npub.clone());
+    }
+    metadata.insert("nostr_kind".to_string(), note.kind.to_string());
+    let (root, reply) = note.thread();
+    if let Some(root) = root {
+        metadata.insert("nostr_root".to_string(), root.to_string());
+    }
+    if let Some(reply) = reply {
+        metadata.insert("nostr_reply_to".to_string(), reply.to_string());
+    }
+
+    let author_name = profile
+        .and_then(Profile::best_name)
+        .map(str::to_string)
+        .or_else(|| npub.as_ref().map(|npub| format!("{}…", &npub[..16])))
+        .unwrap_or_else(|| note.pubkey.clone());
+
+    Item {
+        id: ItemId::new(PROVIDER_ID, &note.id),
+        stream_id,
+        title: note_title(&note.content),
+        content: ItemContent::Text(note.content.clone()),
+        author: Some(Author {
+            name: author_name,
+            email: profile.and_then(|p| p.nip05.clone()),
+            url: npub.as_ref().map(|npub| format!("{VIEWER_URL}/{npub}")),
+            avatar_url: profile.and_then(|p| p.picture.clone()),
+        }),
+        published: DateTime::<Utc>::from_timestamp(note.created_at, 0),
+        updated: None,
+        url: nip19::encode("note", &note.id).map(|note_id| format!("{VIEWER_URL}/{note_id}")),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: note.tag_values("t").map(str::to_lowercase).collect(),
+        metadata,
+    }
+}
+
+#[async_trait]
+impl Provider for NostrProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Nostr"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        let pubkey = match self.pubkey() {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                return Ok(ProviderHealth {
+                    is_healthy: false,
+                    message: Some(e.to_string()),
+                    last_sync: None,
+                    error_count: 1,
+                })
+            }
+        };
+
+        let total = self.pool.relays().len();
+        let result = self
+            .pool
+            .query_all(&[Filter::by_authors(vec![pubkey], &[KIND_CONTACTS]).limit(1)])
+            .await;
+        let failed = result.errors.len();
+        let mut message = format!("{} of {} relays answering", total - failed, total);
+        for (relay, err) in &result.errors {
+            message.push_str(&format!("; {relay}: {err}"));
+        }
+
+        Ok(ProviderHealth {
+            is_healthy: total > 0 && failed < total,
+            message: Some(message),
+            last_sync: (failed < total).then(Utc::now),
+            error_count: failed as u32,
+        })
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        match self.follows().await {
+            Ok(_) => Ok(SyncResult {
+                success: true,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+            Err(e) => Ok(SyncResult {
+                success: false,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![e.to_string()],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: true,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
+        Ok(vec![
+            Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                description: "Open the note in a web viewer".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the note's link".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+        ])
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match action.kind {
+            ActionKind::OpenInBrowser | ActionKind::Open | ActionKind::CopyLink => {
+                match &item.url {
+                    Some(url) => Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("Opening: {}", url)),
+                        data: Some(serde_json::json!({ "url": url })),
+                    }),
+                    None => Ok(ActionResult {
+                        success: false,
+                        message: Some("No URL available".to_string()),
+                        data: None,
+                    }),
+                }
+            }
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for NostrProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        Ok(vec![Feed {
+            id: FeedId(format!("{PROVIDER_ID}:{FOLLOWING_FEED}")),
+            name: "Following".to_string(),
+            description: Some("Notes from the accounts you follow".to_string()),
+            icon: Some("🟣".to_string()),
+            unread_count: None,
+            total_count: None,
+        }])
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        if feed_id.0 != format!("{PROVIDER_ID}:{FOLLOWING_FEED}") {
+            return Err(StreamError::StreamNotFound(feed_id.0.clone()));
+        }
+
+        let follows = self.follows().await?;
+        if follows.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Relays have no offset, so fetch through the end of the page
+        let limit = options.limit.unwrap_or(DEFAULT_LIMIT);
+        let offset = options.offset.unwrap_or(0);
+        let filter = Filter::by_authors(follows, &[KIND_TEXT_NOTE])
+            .since(options.since.map(|since| since.timestamp()))
+            .limit(offset + limit);
+        let mut notes = self.pool.query(&[filter]).await?;
+        notes.truncate((offset + limit) as usize);
+        let notes: Vec<Event> = notes.into_iter().skip(offset as usize).collect();
+
+        let stream_id = StreamId::new(PROVIDER_ID, "feed", FOLLOWING_FEED);
+        Ok(self.notes_to_items(notes, |_| stream_id.clone()).await)
+    }
+}
+
+#[async_trait]
+impl HasSavedItems for NostrProvider {
+    /// Bookmarked and liked notes; `category` narrows to `bookmarks` or
+    /// `reactions`.
+    async fn get_saved_items(&self, options: SavedItemsOptions) -> Result<Vec<Item>> {
+        let refs: Vec<SavedRef> = self
+            .saved_refs()
+            .await?
+            .into_iter()
+            .filter(|saved| {
+                options
+                    .category
+                    .as_deref()
+                    .is_none_or(|category| category == saved.category)
+            })
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .collect();
+        if refs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids = refs.iter().map(|saved| saved.event_id.clone()).collect();
+        let notes = self.pool.query(&[Filter::by_ids(ids)]).await?;
+        let by_id: HashMap<&str, &SavedRef> = refs
+            .iter()
+            .map(|saved| (saved.event_id.as_str(), saved))
+            .collect();
+        let mut items = self
+            .notes_to_items(notes, |note| {
+                let category = by_id
+                    .get(note.id.as_str())
+                    .map_or(BOOKMARKS, |s| s.category);
+                StreamId::new(PROVIDER_ID, "saved", category)
+            })
+            .await;
+
+        // Keep the order things were saved in, not the notes' own dates
+        let position = |item: &Item| {
+            refs.iter()
+                .position(|saved| ItemId::new(PROVIDER_ID, &saved.event_id) == item.id)
+        };
+        items.sort_by_key(|item| position(item));
+        for item in &mut items {
+            item.is_saved = true;
+            let saved = refs
+                .iter()
+                .find(|saved| ItemId::new(PROVIDER_ID, &saved.event_id) == item.id);
+            if let Some(reaction) = saved.and_then(|saved| saved.reaction.as_ref()) {
+                item.metadata
+                    .insert("nostr_reaction".to_string(), reaction.clone());
+            }
+        }
+        Ok(items)
+    }
+
+    async fn is_saved(&self, item_id: &ItemId) -> Result<bool> {
+        Ok(self
+            .saved_refs()
+            .await?
+            .iter()
+            .any(|saved| ItemId::new(PROVIDER_ID, &saved.event_id) == *item_id))
+    }
+
+    async fn save_item(&self, _item_id: &ItemId) -> Result<()> {
+        Err(read_only())
+    }
+
+    async fn unsave_item(&self, _item_id: &ItemId) -> Result<()> {
+        Err(read_only())
+    }
+}
+
+fn read_only() -> StreamError {
+    StreamError::Provider(
+        "Nostr is read-only here: publishing needs a signing key, which the provider doesn't hold"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(content: &str) -> Event {
+        Event {
+            id: "5c83da77af1dec6d7289834998ad7aafbd9e2191396d75ec3cc27f5a77226f36".to_string(),
+            pubkey: "3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d".to_string(),
+            created_at: 1700000000,
+            kind: KIND_TEXT_NOTE,
+            tags: vec![
+                vec!["t".to_string(), "Nostr".to_string()],
+                vec![
+                    "e".to_string(),
+                    "root".to_string(),
+                    String::new(),
+                    "root".to_string(),
+                ],
+            ],
+            content: content.to_string(),
+            sig: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_note_title() {
+        assert_eq!(note_title("\n  gm  \nsecond line"), "gm");
+        assert_eq!(note_title(""), "(empty note)");
+        let long = "a".repeat(100);
+        assert_eq!(note_title(&long).chars().count(), 80);
+    }
+
+    #[test]
+    fn test_note_to_item() {
+        let profile = Profile {
+            name: Some("fiatjaf".to_string()),
+            picture: Some("https://example.com/pic.png".to_string()),
+            ..Default::default()
+        };
+        let item = note_to_item(
+            &note("hello nostr"),
+            Some(&profile),
+            StreamId::new("nostr", "feed", "following"),
+        );
+
+        assert_eq!(
+            item.id.as_str(),
+            "nostr:5c83da77af1dec6d7289834998ad7aafbd9e2191396d75ec3cc27f5a77226f36"
+        );
+        assert_eq!(item.title, "hello nostr");
+        assert_eq!(item.tags, vec!["nostr"]);
+        let author = item.author.unwrap();
+        assert_eq!(author.name, "fiatjaf");
+        assert_eq!(
+            author.url.as_deref(),
+            Some(
+                "https://njump.me/npub180cvv07tjdrrgpa0j7j7tmnyl2yr6yr7l8j4s3evf6u64th6gkwsyjh6w6"
+            )
+        );
+        assert!(item.url.unwrap().starts_with("https://njump.me/note1"));
+        assert_eq!(item.metadata["nostr_root"], "root");
+        assert_eq!(item.metadata["nostr_reply_to"], "root");
+    }
+
+    #[test]
+    fn test_unnamed_author_falls_back_to_npub() {
+        let item = note_to_item(
+            &note("x"),
+            None,
+            StreamId::new("nostr", "feed", "following"),
+        );
+        assert_eq!(item.author.unwrap().name, "npub180cvv07tjdr…");
+    }
+
+    #[test]
+    fn test_invalid_pubkey() {
+        let provider = NostrProvider::new(NostrProviderConfig::new("not-a-key", vec![]));
+        assert!(matches!(provider.pubkey(), Err(StreamError::Provider(_))));
+    }
+}
@@ -0,0 +1,145 @@
+//! NIP-19 bech32 encoding of keys and note IDs (`npub1...`, `note1...`).
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for &value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ u32::from(value);
+        for (i, gen) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|b| b & 31));
+    expanded
+}
+
+/// Regroup bits, e.g. bytes into 5-bit bech32 groups and back.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max = (1 << to) - 1;
+    let mut out = Vec::new();
+    for &value in data {
+        if u32::from(value) >> from != 0 {
+            return None;
+        }
+        acc = (acc << from) | u32::from(value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+/// Encode `hex` (a 32-byte key or event ID) with the prefix `hrp`.
+///
+/// Returns `None` if `hex` isn't valid hex.
+pub fn encode(hrp: &str, hex: &str) -> Option<String> {
+    let bytes = decode_hex(hex)?;
+    let data = convert_bits(&bytes, 8, 5, true)?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend(&data);
+    values.extend([0u8; 6]);
+    let checksum = polymod(&values) ^ 1;
+
+    let mut encoded = format!("{hrp}1");
+    for value in data {
+        encoded.push(CHARSET[value as usize] as char);
+    }
+    for i in 0..6 {
+        encoded.push(CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+    Some(encoded)
+}
+
+/// Decode a bech32 string with the prefix `hrp` back into hex.
+pub fn decode(hrp: &str, encoded: &str) -> Option<String> {
+    let encoded = encoded.to_ascii_lowercase();
+    let (prefix, data) = encoded.rsplit_once('1')?;
+    if prefix != hrp || data.len() < 6 {
+        return None;
+    }
+    let values: Vec<u8> = data
+        .bytes()
+        .map(|b| CHARSET.iter().position(|&c| c == b).map(|p| p as u8))
+        .collect::<Option<_>>()?;
+
+    let mut check = hrp_expand(hrp);
+    check.extend(&values);
+    if polymod(&check) != 1 {
+        return None;
+    }
+
+    let bytes = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+    Some(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Normalize a public key given as hex or `npub` to lowercase hex.
+pub fn parse_pubkey(key: &str) -> Option<String> {
+    let key = key.trim();
+    let hex = if key.starts_with("npub1") {
+        decode("npub", key)?
+    } else {
+        key.to_ascii_lowercase()
+    };
+    (hex.len() == 64 && decode_hex(&hex).is_some()).then_some(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A published key and its npub, as shown by Nostr clients
+    const HEX: &str = "3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d";
+    const NPUB: &str = "npub180cvv07tjdrrgpa0j7j7tmnyl2yr6yr7l8j4s3evf6u64th6gkwsyjh6w6";
+
+    #[test]
+    fn npub_round_trips() {
+        assert_eq!(encode("npub", HEX).as_deref(), Some(NPUB));
+        assert_eq!(decode("npub", NPUB).as_deref(), Some(HEX));
+    }
+
+    #[test]
+    fn parse_pubkey_accepts_hex_and_npub() {
+        assert_eq!(parse_pubkey(NPUB).as_deref(), Some(HEX));
+        assert_eq!(
+            parse_pubkey(&HEX.to_ascii_uppercase()).as_deref(),
+            Some(HEX)
+        );
+        assert_eq!(parse_pubkey("npub1invalid"), None);
+        assert_eq!(parse_pubkey("abcd"), None);
+        // A single flipped character fails the checksum
+        assert_eq!(decode("npub", &NPUB.replace("w6", "w7")), None);
+    }
+}
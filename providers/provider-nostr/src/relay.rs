@@ -0,0 +1,227 @@
+//! A small relay client: one WebSocket per query, closed once the relay has
+//! sent its stored events (NIP-01 `REQ` ... `EOSE`).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use scryforge_provider_core::StreamError;
+use serde_json::Value;
+use soketto::handshake::{Client as Handshake, ServerResponse};
+use soketto::Data;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+use url::Url;
+
+use crate::event::{Event, Filter};
+
+/// Largest relay message accepted, well above any sane event.
+const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Errors talking to a single relay.
+#[derive(Debug, Error)]
+pub enum RelayError {
+    #[error("invalid relay URL `{0}`")]
+    InvalidUrl(String),
+    #[error("connection failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+    #[error("relay refused the subscription: {0}")]
+    Closed(String),
+    #[error("no answer within {0:?}")]
+    Timeout(Duration),
+}
+
+impl From<soketto::handshake::Error> for RelayError {
+    fn from(err: soketto::handshake::Error) -> Self {
+        Self::WebSocket(err.to_string())
+    }
+}
+
+impl From<soketto::connection::Error> for RelayError {
+    fn from(err: soketto::connection::Error) -> Self {
+        Self::WebSocket(err.to_string())
+    }
+}
+
+trait Io: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Io for T {}
+
+fn tls_connector() -> Result<TlsConnector, RelayError> {
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| RelayError::WebSocket(e.to_string()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Open the TCP (and for `wss://`, TLS) connection to a relay.
+async fn connect(url: &Url) -> Result<Box<dyn Io>, RelayError> {
+    let invalid = || RelayError::InvalidUrl(url.to_string());
+    let host = url.host_str().ok_or_else(invalid)?;
+    let port = url.port_or_known_default().ok_or_else(invalid)?;
+    let tcp = TcpStream::connect((host, port)).await?;
+
+    match url.scheme() {
+        "ws" => Ok(Box::new(tcp)),
+        "wss" => {
+            let server_name = ServerName::try_from(host.to_string()).map_err(|_| invalid())?;
+            let tls = tls_connector()?.connect(server_name, tcp).await?;
+            Ok(Box::new(tls))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Ask one relay for the events matching `filters`, returning once it
+/// signals the end of stored events.
+pub async fn query(relay_url: &str, filters: &[Filter]) -> Result<Vec<Event>, RelayError> {
+    let url = Url::parse(relay_url).map_err(|_| RelayError::InvalidUrl(relay_url.to_string()))?;
+    let io = connect(&url).await?;
+
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    let resource = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let mut handshake = Handshake::new(io.compat(), &host, &resource);
+    match handshake.handshake().await? {
+        ServerResponse::Accepted { .. } => {}
+        ServerResponse::Redirect { status_code, .. } | ServerResponse::Rejected { status_code } => {
+            return Err(RelayError::WebSocket(format!(
+                "handshake failed with status {status_code}"
+            )));
+        }
+    }
+    let mut builder = handshake.into_builder();
+    builder.set_max_message_size(MAX_MESSAGE_SIZE);
+    let (mut sender, mut receiver) = builder.finish();
+
+    let subscription = "scryforge";
+    let mut request = vec![Value::from("REQ"), Value::from(subscription)];
+    request.extend(
+        filters
+            .iter()
+            .map(|filter| serde_json::to_value(filter).unwrap_or_default()),
+    );
+    sender.send_text(Value::Array(request).to_string()).await?;
+    sender.flush().await?;
+
+    let mut events = Vec::new();
+    let mut message = Vec::new();
+    loop {
+        message.clear();
+        if let Data::Binary(_) = receiver.receive_data(&mut message).await? {
+            continue;
+        }
+        let Ok(Value::Array(parts)) = serde_json::from_slice::<Value>(&message) else {
+            continue;
+        };
+        let sub_matches = parts.get(1).and_then(Value::as_str) == Some(subscription);
+        match parts.first().and_then(Value::as_str) {
+            Some("EVENT") if sub_matches => {
+                // Events that don't parse are skipped rather than failing the query
+                if let Some(event) = parts
+                    .get(2)
+                    .and_then(|event| serde_json::from_value::<Event>(event.clone()).ok())
+                {
+                    events.push(event);
+                }
+            }
+            Some("EOSE") if sub_matches => break,
+            Some("CLOSED") if sub_matches => {
+                let reason = parts.get(2).and_then(Value::as_str).unwrap_or_default();
+                return Err(RelayError::Closed(reason.to_string()));
+            }
+            // NOTICE and messages for other subscriptions
+            _ => {}
+        }
+    }
+
+    // Best effort: the relay forgets the subscription with the connection
+    let close = Value::Array(vec![Value::from("CLOSE"), Value::from(subscription)]);
+    let _ = sender.send_text(close.to_string()).await;
+    let _ = sender.close().await;
+
+    Ok(events)
+}
+
+/// The relays of one account, queried together.
+#[derive(Debug, Clone)]
+pub struct RelayPool {
+    relays: Vec<String>,
+    timeout: Duration,
+}
+
+/// Merged events from every relay that answered, plus the failures.
+#[derive(Debug, Default)]
+pub struct PoolResult {
+    pub events: Vec<Event>,
+    pub errors: Vec<(String, RelayError)>,
+}
+
+impl RelayPool {
+    pub fn new(relays: Vec<String>, timeout: Duration) -> Self {
+        Self { relays, timeout }
+    }
+
+    pub fn relays(&self) -> &[String] {
+        &self.relays
+    }
+
+    /// Query every relay at once; events seen on several relays are kept
+    /// once. Results are newest first.
+    pub async fn query_all(&self, filters: &[Filter]) -> PoolResult {
+        let queries = self.relays.iter().map(|relay| async move {
+            let result = tokio::time::timeout(self.timeout, query(relay, filters))
+                .await
+                .unwrap_or(Err(RelayError::Timeout(self.timeout)));
+            (relay.clone(), result)
+        });
+
+        let mut seen = HashSet::new();
+        let mut merged = PoolResult::default();
+        for (relay, result) in join_all(queries).await {
+            match result {
+                Ok(events) => merged.events.extend(
+                    events
+                        .into_iter()
+                        .filter(|event| seen.insert(event.id.clone())),
+                ),
+                Err(err) => merged.errors.push((relay, err)),
+            }
+        }
+        merged
+            .events
+            .sort_by_key(|event| std::cmp::Reverse(event.created_at));
+        merged
+    }
+
+    /// Like [`RelayPool::query_all`], failing only when no relay answered.
+    pub async fn query(&self, filters: &[Filter]) -> Result<Vec<Event>, StreamError> {
+        if self.relays.is_empty() {
+            return Err(StreamError::Provider("No relays configured".to_string()));
+        }
+        let result = self.query_all(filters).await;
+        if result.errors.len() == self.relays.len() {
+            let (relay, err) = &result.errors[0];
+            return Err(StreamError::Network(format!("{relay}: {err}")));
+        }
+        Ok(result.events)
+    }
+}
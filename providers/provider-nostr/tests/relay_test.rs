@@ -0,0 +1,277 @@
+//! Tests against in-process fake relays speaking NIP-01 over WebSockets.
+
+use provider_nostr::{NostrProvider, NostrProviderConfig};
+use scryforge_provider_core::prelude::*;
+use serde_json::{json, Value};
+use soketto::handshake::server::Response;
+use soketto::handshake::Server;
+use tokio::net::TcpListener;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+fn key(c: char) -> String {
+    c.to_string().repeat(64)
+}
+
+fn event_id(n: u32) -> String {
+    format!("{n:064x}")
+}
+
+fn event(n: u32, pubkey: &str, kind: u16, created_at: i64, tags: Value, content: &str) -> Value {
+    json!({
+        "id": event_id(n),
+        "pubkey": pubkey,
+        "created_at": created_at,
+        "kind": kind,
+        "tags": tags,
+        "content": content,
+        "sig": "",
+    })
+}
+
+fn matches(filter: &Value, event: &Value) -> bool {
+    let allows = |field: &str, value: &Value| {
+        filter
+            .get(field)
+            .and_then(Value::as_array)
+            .is_none_or(|allowed| allowed.contains(value))
+    };
+    allows("ids", &event["id"])
+        && allows("authors", &event["pubkey"])
+        && allows("kinds", &event["kind"])
+        && filter
+            .get("since")
+            .and_then(Value::as_i64)
+            .is_none_or(|since| event["created_at"].as_i64().unwrap() >= since)
+}
+
+/// Start a relay serving `events`; returns its `ws://` URL.
+async fn start_relay(mut events: Vec<Value>) -> String {
+    events.sort_by_key(|event| std::cmp::Reverse(event["created_at"].as_i64().unwrap()));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            let events = events.clone();
+            tokio::spawn(async move {
+                let mut server = Server::new(stream.compat());
+                let key = server.receive_request().await.unwrap().key();
+                server
+                    .send_response(&Response::Accept {
+                        key,
+                        protocol: None,
+                    })
+                    .await
+                    .unwrap();
+                let (mut sender, mut receiver) = server.into_builder().finish();
+
+                let mut message = Vec::new();
+                receiver.receive_data(&mut message).await.unwrap();
+                let request: Vec<Value> = serde_json::from_slice(&message).unwrap();
+                assert_eq!(request[0], "REQ");
+                let subscription = request[1].clone();
+
+                let mut sent = Vec::new();
+                for filter in &request[2..] {
+                    let limit = filter["limit"].as_u64().unwrap_or(u64::MAX) as usize;
+                    for event in events
+                        .iter()
+                        .filter(|event| matches(filter, event))
+                        .take(limit)
+                    {
+                        if !sent.contains(&event["id"]) {
+                            sent.push(event["id"].clone());
+                            let reply = json!(["EVENT", subscription, event]);
+                            sender.send_text(reply.to_string()).await.unwrap();
+                        }
+                    }
+                }
+                let notice = json!(["NOTICE", "hello"]);
+                sender.send_text(notice.to_string()).await.unwrap();
+                let eose = json!(["EOSE", subscription]);
+                sender.send_text(eose.to_string()).await.unwrap();
+                sender.flush().await.unwrap();
+
+                // Wait for CLOSE and the client hanging up
+                while receiver.receive_data(&mut message).await.is_ok() {}
+            });
+        }
+    });
+
+    url
+}
+
+/// URL of a port nothing listens on.
+async fn dead_relay() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    format!("ws://{}", listener.local_addr().unwrap())
+}
+
+fn provider(relays: Vec<String>) -> NostrProvider {
+    NostrProvider::new(NostrProviderConfig::new(key('a'), relays))
+}
+
+fn following() -> FeedId {
+    FeedId("nostr:following".to_string())
+}
+
+/// Account `a` follows `b` and `c`; `d` is a stranger.
+fn social_graph() -> Vec<Value> {
+    vec![
+        event(1, &key('a'), 3, 100, json!([["p", key('b')]]), ""),
+        event(
+            2,
+            &key('a'),
+            3,
+            200,
+            json!([["p", key('b')], ["p", key('c')]]),
+            "",
+        ),
+        event(
+            10,
+            &key('b'),
+            1,
+            1000,
+            json!([["t", "Rust"]]),
+            "hello from b",
+        ),
+        event(11, &key('c'), 1, 1100, json!([]), "c says gm"),
+        event(12, &key('d'), 1, 1200, json!([]), "stranger"),
+        event(13, &key('b'), 1, 900, json!([]), "older note"),
+        event(
+            20,
+            &key('b'),
+            0,
+            50,
+            json!([]),
+            r#"{"name": "bob", "display_name": "Bob"}"#,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn following_feed_shows_followed_notes() {
+    let relay = start_relay(social_graph()).await;
+
+    let items = provider(vec![relay])
+        .get_feed_items(&following(), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["c says gm", "hello from b", "older note"]);
+    assert_eq!(items[1].author.as_ref().unwrap().name, "Bob");
+    assert_eq!(items[1].tags, vec!["rust"]);
+    assert_eq!(items[1].stream_id.as_str(), "nostr:feed:following");
+    assert_eq!(items[1].id.as_str(), format!("nostr:{}", event_id(10)));
+}
+
+#[tokio::test]
+async fn relays_are_merged_and_dead_ones_skipped() {
+    let mut graph = social_graph();
+    let extra = event(
+        14,
+        &key('c'),
+        1,
+        1300,
+        json!([]),
+        "only on the second relay",
+    );
+    let first = start_relay(graph.clone()).await;
+    graph.push(extra);
+    let second = start_relay(graph).await;
+
+    let provider = provider(vec![first, dead_relay().await, second]);
+    let options = FeedOptions {
+        limit: Some(2),
+        since: chrono::DateTime::from_timestamp(950, 0),
+        ..Default::default()
+    };
+    let items = provider
+        .get_feed_items(&following(), options)
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["only on the second relay", "c says gm"]);
+
+    let health = provider.health_check().await.unwrap();
+    assert!(health.is_healthy);
+    assert_eq!(health.error_count, 1);
+    assert!(health
+        .message
+        .unwrap()
+        .starts_with("2 of 3 relays answering"));
+}
+
+#[tokio::test]
+async fn no_relay_answering_is_a_network_error() {
+    let err = provider(vec![dead_relay().await])
+        .get_feed_items(&following(), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::Network(_)));
+}
+
+fn saved_graph() -> Vec<Value> {
+    let mut events = social_graph();
+    events.extend([
+        // Bookmark list: 10 first, 11 added later
+        event(
+            30,
+            &key('a'),
+            10003,
+            500,
+            json!([["e", event_id(10)], ["e", event_id(11)]]),
+            "",
+        ),
+        event(
+            31,
+            &key('a'),
+            7,
+            600,
+            json!([["e", event_id(13)], ["p", key('b')]]),
+            "🤙",
+        ),
+        // A dislike isn't saved
+        event(32, &key('a'), 7, 700, json!([["e", event_id(12)]]), "-"),
+        // Liking a bookmarked note doesn't list it twice
+        event(33, &key('a'), 7, 800, json!([["e", event_id(10)]]), "+"),
+    ]);
+    events
+}
+
+#[tokio::test]
+async fn bookmarks_and_likes_are_saved_items() {
+    let relay = start_relay(saved_graph()).await;
+    let provider = provider(vec![relay]);
+
+    let items = provider
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["c says gm", "hello from b", "older note"]);
+    assert!(items.iter().all(|item| item.is_saved));
+    assert_eq!(items[0].stream_id.as_str(), "nostr:saved:bookmarks");
+    assert_eq!(items[2].stream_id.as_str(), "nostr:saved:reactions");
+    assert_eq!(items[2].metadata["nostr_reaction"], "🤙");
+
+    let reactions = provider
+        .get_saved_items(SavedItemsOptions {
+            category: Some("reactions".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(reactions.len(), 1);
+
+    let stranger = ItemId::new("nostr", &event_id(12));
+    assert!(!provider.is_saved(&stranger).await.unwrap());
+    assert!(provider.is_saved(&items[0].id).await.unwrap());
+    assert!(provider.save_item(&stranger).await.is_err());
+}
@@ -0,0 +1,984 @@
+//! # provider-outlook
+//!
+//! Outlook Mail provider for Scryforge.
+//!
+//! This provider reads Outlook.com and Microsoft 365 mailboxes through the
+//! Microsoft Graph API, using the shared [`GraphClient`]. It supports:
+//! - Mail folders, including nested ones, as feeds
+//! - Delta sync: after the first listing of a folder, only changes are
+//!   fetched
+//! - Marking read, flagging, archiving, moving, and replying
+//! - Sending new mail, used by the daemon's email share targets
+//!
+//! ## Authentication
+//!
+//! This provider requires OAuth tokens via the Sigilforge daemon, with the
+//! `Mail.ReadWrite` and `Mail.Send` scopes. The service identifier is
+//! "outlook".
+//!
+//! ## Delta Sync
+//!
+//! Each folder is fetched with a Graph delta query, starting with the last
+//! [`OutlookSettings::initial_sync_days`] days of mail. The messages and the
+//! `@odata.deltaLink` are kept in memory; the next listing or
+//! [`Provider::sync`] asks only for what changed since, and a delta link
+//! Graph no longer accepts starts the folder over.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use provider_outlook::OutlookProvider;
+//! use scryforge_provider_core::auth::MockTokenFetcher;
+//! use scryforge_provider_core::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<()> {
+//! let token_fetcher = Arc::new(MockTokenFetcher::empty()
+//!     .with_token("outlook".to_string(), "work".to_string(), "token123".to_string()));
+//! let provider = OutlookProvider::new(token_fetcher, "work".to_string());
+//!
+//! let inbox = FeedId("inbox".to_string());
+//! for item in provider.get_feed_items(&inbox, FeedOptions::default()).await? {
+//!     println!("{}", item.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use graph_client::{BatchRequest, GraphClient, GraphError, Method, Page, GRAPH_BASE_URL};
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::{format_mailbox, CC_METADATA_KEY, TO_METADATA_KEY};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "outlook";
+
+/// Folder shown when no feed is chosen; Graph accepts the well-known name.
+pub const INBOX_FEED: &str = "inbox";
+
+/// Messages per delta page.
+const PAGE_SIZE: u32 = 50;
+
+const DEFAULT_LIMIT: u32 = 50;
+
+/// Message properties fetched with every listing.
+const MESSAGE_FIELDS: &str = "subject,bodyPreview,body,from,toRecipients,ccRecipients,\
+receivedDateTime,lastModifiedDateTime,isRead,flag,webLink,conversationId,hasAttachments,importance,\
+categories";
+
+// ============================================================================
+// Microsoft Graph API Response Types
+// ============================================================================
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MailFolder {
+    id: String,
+    display_name: String,
+    #[serde(default)]
+    child_folder_count: u32,
+    #[serde(default)]
+    unread_item_count: u32,
+    #[serde(default)]
+    total_item_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Message {
+    id: String,
+    subject: Option<String>,
+    body_preview: Option<String>,
+    body: Option<MessageBody>,
+    from: Option<Recipient>,
+    #[serde(default)]
+    to_recipients: Vec<Recipient>,
+    #[serde(default)]
+    cc_recipients: Vec<Recipient>,
+    received_date_time: Option<DateTime<Utc>>,
+    last_modified_date_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    is_read: bool,
+    flag: Option<FollowupFlag>,
+    web_link: Option<String>,
+    conversation_id: Option<String>,
+    #[serde(default)]
+    has_attachments: bool,
+    importance: Option<String>,
+    #[serde(default)]
+    categories: Vec<String>,
+    /// Present, and most other fields missing, on delta entries for messages
+    /// that were deleted or moved out of the folder.
+    #[serde(rename = "@removed")]
+    removed: Option<serde_json::Value>,
+}
+
+impl Message {
+    fn is_flagged(&self) -> bool {
+        self.flag
+            .as_ref()
+            .is_some_and(|flag| flag.flag_status == "flagged")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageBody {
+    content_type: String,
+    content: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Recipient {
+    email_address: EmailAddress,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EmailAddress {
+    name: Option<String>,
+    address: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FollowupFlag {
+    flag_status: String,
+}
+
+// ============================================================================
+// Provider Implementation
+// ============================================================================
+
+/// Settings for the Outlook provider.
+#[derive(Debug, Clone)]
+pub struct OutlookSettings {
+    /// How many days of mail the first sync of a folder fetches
+    pub initial_sync_days: u32,
+}
+
+impl Default for OutlookSettings {
+    fn default() -> Self {
+        Self {
+            initial_sync_days: 30,
+        }
+    }
+}
+
+/// Messages of one folder and where its delta query left off.
+#[derive(Debug, Default)]
+struct FolderState {
+    delta_link: Option<String>,
+    messages: HashMap<String, Message>,
+}
+
+/// What a delta round changed in a folder.
+#[derive(Debug, Default, Clone, Copy)]
+struct FolderChanges {
+    added: u32,
+    updated: u32,
+    removed: u32,
+}
+
+/// Outlook Mail provider.
+///
+/// Connects to Microsoft Graph to list mail folders and their messages.
+/// Requires OAuth authentication via Sigilforge.
+pub struct OutlookProvider {
+    graph: GraphClient,
+    settings: OutlookSettings,
+    folders: Mutex<HashMap<String, FolderState>>,
+}
+
+impl OutlookProvider {
+    const SERVICE_ID: &'static str = "outlook";
+
+    /// Create a new Outlook provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_fetcher` - Token fetcher for OAuth authentication
+    /// * `account` - Account identifier for token lookup (e.g., "personal", "work")
+    pub fn new(token_fetcher: Arc<dyn TokenFetcher>, account: String) -> Self {
+        Self::with_base_url(token_fetcher, account, GRAPH_BASE_URL.to_string())
+    }
+
+    /// Create a provider with a custom base URL (useful for testing).
+    pub fn with_base_url(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        account: String,
+        base_url: String,
+    ) -> Self {
+        Self {
+            graph: GraphClient::new(token_fetcher, Self::SERVICE_ID, account, base_url),
+            settings: OutlookSettings::default(),
+            folders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the default settings.
+    pub fn with_settings(mut self, settings: OutlookSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Every mail folder, with nested folders named by their path.
+    async fn fetch_folders(&self) -> std::result::Result<Vec<(String, MailFolder)>, GraphError> {
+        let top: Vec<MailFolder> = self
+            .graph
+            .get_all("/me/mailFolders?$top=100", "fetch mail folders")
+            .await?;
+
+        // One batch per level of nesting
+        let mut children: HashMap<String, Vec<MailFolder>> = HashMap::new();
+        let mut parents: Vec<String> = top
+            .iter()
+            .filter(|folder| folder.child_folder_count > 0)
+            .map(|folder| folder.id.clone())
+            .collect();
+        while !parents.is_empty() {
+            let requests = parents
+                .iter()
+                .enumerate()
+                .map(|(i, id)| {
+                    BatchRequest::get(
+                        i.to_string(),
+                        format!("/me/mailFolders/{}/childFolders?$top=100", id),
+                    )
+                })
+                .collect();
+            let responses = self.graph.batch(requests).await?;
+
+            let mut next_level = Vec::new();
+            for (id, response) in parents.iter().zip(responses) {
+                let what = format!("fetch child folders of {}", id);
+                let page: Page<MailFolder> = response.into_result(&what)?;
+                let mut folders = page.value;
+                if let Some(next) = page.next_link {
+                    folders.extend(self.graph.get_all::<MailFolder>(&next, &what).await?);
+                }
+                next_level.extend(
+                    folders
+                        .iter()
+                        .filter(|folder| folder.child_folder_count > 0)
+                        .map(|folder| folder.id.clone()),
+                );
+                children.insert(id.clone(), folders);
+            }
+            parents = next_level;
+        }
+
+        // Depth-first, so children follow their parent
+        let mut pending: Vec<(String, MailFolder)> = top
+            .into_iter()
+            .rev()
+            .map(|folder| (folder.display_name.clone(), folder))
+            .collect();
+        let mut folders = Vec::new();
+        while let Some((path, folder)) = pending.pop() {
+            if let Some(nested) = children.remove(&folder.id) {
+                pending.extend(
+                    nested
+                        .into_iter()
+                        .rev()
+                        .map(|child| (format!("{}/{}", path, child.display_name), child)),
+                );
+            }
+            folders.push((path, folder));
+        }
+        Ok(folders)
+    }
+
+    /// Delta query covering the initial sync window of a folder.
+    fn initial_delta_url(&self, folder_id: &str) -> String {
+        let since = Utc::now() - Duration::days(i64::from(self.settings.initial_sync_days));
+        format!(
+            "/me/mailFolders/{}/messages/delta?$select={}&$filter=receivedDateTime ge {}&$orderby=receivedDateTime desc",
+            folder_id,
+            MESSAGE_FIELDS,
+            since.format("%Y-%m-%dT%H:%M:%SZ")
+        )
+    }
+
+    /// Bring a folder's cached messages up to date.
+    async fn sync_folder(&self, folder_id: &str) -> Result<FolderChanges> {
+        let resume = self
+            .folders
+            .lock()
+            .unwrap()
+            .get(folder_id)
+            .and_then(|state| state.delta_link.clone());
+
+        let changes = match resume {
+            Some(link) => match self.run_delta(folder_id, &link, false).await {
+                Err(GraphError::DeltaExpired(_)) => {
+                    let initial = self.initial_delta_url(folder_id);
+                    self.run_delta(folder_id, &initial, true).await
+                }
+                other => other,
+            },
+            None => {
+                let initial = self.initial_delta_url(folder_id);
+                self.run_delta(folder_id, &initial, true).await
+            }
+        };
+        changes.map_err(StreamError::from)
+    }
+
+    /// Fetch every page of a delta round and apply it to the cache. With
+    /// `restart`, the round replaces what was cached.
+    async fn run_delta(
+        &self,
+        folder_id: &str,
+        start: &str,
+        restart: bool,
+    ) -> std::result::Result<FolderChanges, GraphError> {
+        let what = format!("sync mail folder {}", folder_id);
+        let mut url = start.to_string();
+        let mut entries = Vec::new();
+        let delta_link = loop {
+            let page: Page<Message> = self.graph.get_delta_page(&url, PAGE_SIZE, &what).await?;
+            entries.extend(page.value);
+            match (page.next_link, page.delta_link) {
+                (Some(next), _) => url = next,
+                (None, Some(delta)) => break delta,
+                (None, None) => {
+                    return Err(GraphError::InvalidResponse(format!(
+                        "Delta query for folder {} ended without a delta link",
+                        folder_id
+                    )))
+                }
+            }
+        };
+
+        // Applied only once every page arrived, so a failed round leaves the
+        // cache and delta link as they were
+        let mut folders = self.folders.lock().unwrap();
+        let state = folders.entry(folder_id.to_string()).or_default();
+        let previous = if restart {
+            std::mem::take(&mut state.messages)
+        } else {
+            HashMap::new()
+        };
+
+        let mut changes = FolderChanges::default();
+        for message in entries {
+            if message.removed.is_some() {
+                if state.messages.remove(&message.id).is_some() {
+                    changes.removed += 1;
+                }
+            } else if state.messages.insert(message.id.clone(), message).is_some() {
+                changes.updated += 1;
+            } else {
+                changes.added += 1;
+            }
+        }
+        if restart && !previous.is_empty() {
+            // Counted against what was cached before starting over
+            let kept = previous
+                .keys()
+                .filter(|id| state.messages.contains_key(*id))
+                .count() as u32;
+            changes.removed = previous.len() as u32 - kept;
+            changes.updated = kept;
+            changes.added = state.messages.len() as u32 - kept;
+        }
+        state.delta_link = Some(delta_link);
+        Ok(changes)
+    }
+
+    /// Apply `change` to the cached copy of a message, wherever it is.
+    fn update_cached(&self, message_id: &str, change: impl Fn(&mut Message)) {
+        let mut folders = self.folders.lock().unwrap();
+        for state in folders.values_mut() {
+            if let Some(message) = state.messages.get_mut(message_id) {
+                change(message);
+            }
+        }
+    }
+
+    /// The Graph message ID of an item.
+    fn message_id(item: &Item) -> Result<&str> {
+        item.id
+            .0
+            .strip_prefix("outlook:")
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| StreamError::ItemNotFound(item.id.0.clone()))
+    }
+
+    /// Mark a message read or unread.
+    pub async fn set_read(&self, item: &Item, read: bool) -> Result<()> {
+        let id = Self::message_id(item)?;
+        self.graph
+            .send(
+                Method::PATCH,
+                &format!("/me/messages/{}", id),
+                Some(&json!({ "isRead": read })),
+                &format!("update message {}", id),
+            )
+            .await
+            .map_err(StreamError::from)?;
+        self.update_cached(id, |message| message.is_read = read);
+        Ok(())
+    }
+
+    /// Flag a message for follow-up, or clear the flag.
+    pub async fn set_flagged(&self, item: &Item, flagged: bool) -> Result<()> {
+        let id = Self::message_id(item)?;
+        let flag = FollowupFlag {
+            flag_status: if flagged { "flagged" } else { "notFlagged" }.to_string(),
+        };
+        self.graph
+            .send(
+                Method::PATCH,
+                &format!("/me/messages/{}", id),
+                Some(&json!({ "flag": flag })),
+                &format!("update message {}", id),
+            )
+            .await
+            .map_err(StreamError::from)?;
+        self.update_cached(id, |message| message.flag = Some(flag.clone()));
+        Ok(())
+    }
+
+    /// Move a message to another folder, by ID or well-known name such as
+    /// `archive` or `deleteditems`.
+    ///
+    /// Graph gives the moved message a new ID; it appears in the destination
+    /// folder on its next listing.
+    pub async fn move_message(&self, item: &Item, destination: &str) -> Result<()> {
+        let id = Self::message_id(item)?;
+        self.graph
+            .send(
+                Method::POST,
+                &format!("/me/messages/{}/move", id),
+                Some(&json!({ "destinationId": destination })),
+                &format!("move message {}", id),
+            )
+            .await
+            .map_err(StreamError::from)?;
+
+        let mut folders = self.folders.lock().unwrap();
+        for state in folders.values_mut() {
+            state.messages.remove(id);
+        }
+        Ok(())
+    }
+
+    /// Reply to the sender with `comment`, quoting the message.
+    pub async fn reply(&self, item: &Item, comment: &str) -> Result<()> {
+        let id = Self::message_id(item)?;
+        self.graph
+            .send(
+                Method::POST,
+                &format!("/me/messages/{}/reply", id),
+                Some(&json!({ "comment": comment })),
+                &format!("reply to message {}", id),
+            )
+            .await
+            .map_err(StreamError::from)?;
+        Ok(())
+    }
+}
+
+/// The `sendMail` request body for `message`.
+fn send_mail_body(message: &OutgoingMessage) -> serde_json::Value {
+    let recipients: Vec<_> = message
+        .to
+        .iter()
+        .map(|address| json!({ "emailAddress": { "address": address } }))
+        .collect();
+    json!({
+        "message": {
+            "subject": message.subject,
+            "body": { "contentType": "Text", "content": message.body },
+            "toRecipients": recipients,
+        },
+        "saveToSentItems": true,
+    })
+}
+
+/// Convert a Graph message into an [`Item`] of `folder_id`.
+fn message_to_item(message: &Message, folder_id: &str) -> Item {
+    let subject = message
+        .subject
+        .clone()
+        .filter(|subject| !subject.trim().is_empty())
+        .unwrap_or_else(|| "(No subject)".to_string());
+    let (body_text, body_html) = match &message.body {
+        Some(body) if body.content_type.eq_ignore_ascii_case("html") => {
+            (None, Some(body.content.clone()))
+        }
+        Some(body) => (Some(body.content.clone()), None),
+        None => (None, None),
+    };
+
+    let author = message.from.as_ref().map(|from| {
+        let address = from.email_address.address.clone();
+        Author {
+            name: from
+                .email_address
+                .name
+                .clone()
+                .or_else(|| address.clone())
+                .unwrap_or_default(),
+            email: address,
+            url: None,
+            avatar_url: None,
+        }
+    });
+
+    let mut metadata = HashMap::new();
+    metadata.insert("outlook_folder".to_string(), folder_id.to_string());
+    if let Some(conversation) = &message.conversation_id {
+        metadata.insert("outlook_conversation".to_string(), conversation.clone());
+    }
+    if let Some(importance) = &message.importance {
+        metadata.insert("outlook_importance".to_string(), importance.clone());
+    }
+    if message.has_attachments {
+        metadata.insert("has_attachments".to_string(), "true".to_string());
+    }
+    for (key, recipients) in [
+        (TO_METADATA_KEY, &message.to_recipients),
+        (CC_METADATA_KEY, &message.cc_recipients),
+    ] {
+        let recipients: Vec<String> = recipients
+            .iter()
+            .filter_map(|recipient| {
+                let address = recipient.email_address.address.as_deref()?;
+                Some(format_mailbox(
+                    recipient.email_address.name.as_deref(),
+                    address,
+                ))
+            })
+            .collect();
+        if !recipients.is_empty() {
+            metadata.insert(key.to_string(), recipients.join(", "));
+        }
+    }
+
+    Item {
+        id: ItemId::new(PROVIDER_ID, &message.id),
+        stream_id: StreamId::new(PROVIDER_ID, "feed", folder_id),
+        title: subject.clone(),
+        content: ItemContent::Email {
+            subject,
+            body_text,
+            body_html,
+            snippet: message.body_preview.clone().unwrap_or_default(),
+        },
+        author,
+        published: message.received_date_time,
+        updated: message.last_modified_date_time,
+        url: message.web_link.clone(),
+        thumbnail_url: None,
+        is_read: message.is_read,
+        is_saved: message.is_flagged(),
+        tags: message.categories.clone(),
+        metadata,
+    }
+}
+
+// ============================================================================
+// Provider Trait Implementations
+// ============================================================================
+
+#[async_trait]
+impl Provider for OutlookProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Outlook Mail"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        let inbox: std::result::Result<MailFolder, GraphError> = self
+            .graph
+            .get(&format!("/me/mailFolders/{}", INBOX_FEED), "fetch inbox")
+            .await;
+        match inbox {
+            Ok(inbox) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!(
+                    "Connected to Outlook ({} unread in Inbox)",
+                    inbox.unread_item_count
+                )),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("Health check failed: {}", e)),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    /// Run a delta round for every folder listed so far, or the inbox.
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        let mut folder_ids: Vec<String> = self.folders.lock().unwrap().keys().cloned().collect();
+        if folder_ids.is_empty() {
+            folder_ids.push(INBOX_FEED.to_string());
+        }
+        folder_ids.sort();
+
+        let mut total = FolderChanges::default();
+        let mut errors = Vec::new();
+        for folder_id in &folder_ids {
+            match self.sync_folder(folder_id).await {
+                Ok(changes) => {
+                    total.added += changes.added;
+                    total.updated += changes.updated;
+                    total.removed += changes.removed;
+                }
+                Err(e) => errors.push(format!("{}: {}", folder_id, e)),
+            }
+        }
+
+        Ok(SyncResult {
+            success: errors.len() < folder_ids.len(),
+            items_added: total.added,
+            items_updated: total.updated,
+            items_removed: total.removed,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = Vec::new();
+        if item.url.is_some() {
+            actions.push(Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                description: "Open in Outlook on the web".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            });
+        }
+        actions.push(if item.is_read {
+            Action {
+                id: "mark_unread".to_string(),
+                name: "Mark as Unread".to_string(),
+                description: "Mark email as unread".to_string(),
+                kind: ActionKind::MarkUnread,
+                keyboard_shortcut: Some("u".to_string()),
+            }
+        } else {
+            Action {
+                id: "mark_read".to_string(),
+                name: "Mark as Read".to_string(),
+                description: "Mark email as read".to_string(),
+                kind: ActionKind::MarkRead,
+                keyboard_shortcut: Some("r".to_string()),
+            }
+        });
+        actions.push(if item.is_saved {
+            Action {
+                id: "unflag".to_string(),
+                name: "Clear Flag".to_string(),
+                description: "Clear the follow-up flag".to_string(),
+                kind: ActionKind::Custom("unflag".to_string()),
+                keyboard_shortcut: Some("F".to_string()),
+            }
+        } else {
+            Action {
+                id: "flag".to_string(),
+                name: "Flag".to_string(),
+                description: "Flag email for follow-up".to_string(),
+                kind: ActionKind::Custom("flag".to_string()),
+                keyboard_shortcut: Some("f".to_string()),
+            }
+        });
+        actions.push(Action {
+            id: "archive".to_string(),
+            name: "Archive".to_string(),
+            description: "Move email to the Archive folder".to_string(),
+            kind: ActionKind::Archive,
+            keyboard_shortcut: Some("a".to_string()),
+        });
+        actions.push(Action {
+            id: "move_to_folder".to_string(),
+            name: "Move to Folder".to_string(),
+            description: "Move email to another folder".to_string(),
+            kind: ActionKind::Custom("move_to_folder".to_string()),
+            keyboard_shortcut: Some("m".to_string()),
+        });
+        actions.push(Action {
+            id: "reply".to_string(),
+            name: "Reply".to_string(),
+            description: "Reply to the sender".to_string(),
+            kind: ActionKind::Custom("reply".to_string()),
+            keyboard_shortcut: Some("R".to_string()),
+        });
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        let done = |message: &str| ActionResult {
+            success: true,
+            message: Some(message.to_string()),
+            data: None,
+        };
+
+        match &action.kind {
+            ActionKind::OpenInBrowser | ActionKind::Open => match &item.url {
+                Some(url) => Ok(ActionResult {
+                    success: true,
+                    message: Some(format!("Opening: {}", url)),
+                    data: Some(json!({ "url": url })),
+                }),
+                None => Ok(ActionResult {
+                    success: false,
+                    message: Some("No URL available".to_string()),
+                    data: None,
+                }),
+            },
+            ActionKind::MarkRead | ActionKind::MarkUnread => {
+                let read = action.kind == ActionKind::MarkRead;
+                self.set_read(item, read).await?;
+                Ok(done(if read {
+                    "Marked as read"
+                } else {
+                    "Marked as unread"
+                }))
+            }
+            ActionKind::Archive => {
+                self.move_message(item, "archive").await?;
+                Ok(done("Archived"))
+            }
+            ActionKind::Custom(name) if name == "flag" || name == "unflag" => {
+                self.set_flagged(item, name == "flag").await?;
+                Ok(done(if name == "flag" {
+                    "Flagged"
+                } else {
+                    "Flag cleared"
+                }))
+            }
+            // The client picks a folder, then calls `move_message`
+            ActionKind::Custom(name) if name == "move_to_folder" => {
+                let folders = self.fetch_folders().await.map_err(StreamError::from)?;
+                let current = item.metadata.get("outlook_folder");
+                let targets: Vec<serde_json::Value> = folders
+                    .iter()
+                    .filter(|(_, folder)| Some(&folder.id) != current)
+                    .map(|(path, folder)| json!({ "id": folder.id, "name": path }))
+                    .collect();
+                Ok(ActionResult {
+                    success: true,
+                    message: None,
+                    data: Some(json!({
+                        "folders": targets,
+                        "requires_input": true,
+                        "input_type": "folder",
+                    })),
+                })
+            }
+            // The client prompts for the text and sends it with `reply`
+            ActionKind::Custom(name) if name == "reply" => Ok(ActionResult {
+                success: true,
+                message: Some("Enter your reply:".to_string()),
+                data: Some(json!({
+                    "requires_input": true,
+                    "input_type": "text",
+                })),
+            }),
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some(format!("Unknown action: {}", action.name)),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_compose(&self) -> Option<&dyn HasCompose> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasCompose for OutlookProvider {
+    async fn send_message(&self, message: &OutgoingMessage) -> Result<()> {
+        self.graph
+            .send(
+                Method::POST,
+                "/me/sendMail",
+                Some(&send_mail_body(message)),
+                &format!("send mail to {}", message.to.join(", ")),
+            )
+            .await
+            .map_err(StreamError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HasFeeds for OutlookProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let folders = self.fetch_folders().await.map_err(StreamError::from)?;
+        Ok(folders
+            .into_iter()
+            .map(|(path, folder)| Feed {
+                id: FeedId(folder.id),
+                name: path,
+                description: None,
+                icon: Some("📧".to_string()),
+                unread_count: Some(folder.unread_item_count),
+                total_count: Some(folder.total_item_count),
+            })
+            .collect())
+    }
+
+    /// Messages of a folder, newest first, after catching up with its
+    /// changes. Only unread messages unless `include_read` is set.
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        self.sync_folder(&feed_id.0).await?;
+
+        let folders = self.folders.lock().unwrap();
+        let Some(state) = folders.get(&feed_id.0) else {
+            return Ok(Vec::new());
+        };
+        let mut messages: Vec<&Message> = state
+            .messages
+            .values()
+            .filter(|message| options.include_read || !message.is_read)
+            .filter(|message| {
+                options.since.is_none_or(|since| {
+                    message
+                        .received_date_time
+                        .is_some_and(|received| received >= since)
+                })
+            })
+            .collect();
+        messages.sort_by_key(|message| std::cmp::Reverse(message.received_date_time));
+
+        Ok(messages
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .map(|message| message_to_item(message, &feed_id.0))
+            .collect())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(json: serde_json::Value) -> Message {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_message_to_item() {
+        let message = message(json!({
+            "id": "msg-1",
+            "subject": "Quarterly report",
+            "bodyPreview": "The numbers are in",
+            "body": { "contentType": "html", "content": "<p>The numbers are in</p>" },
+            "from": { "emailAddress": { "name": "Dana Lee", "address": "dana@contoso.com" } },
+            "toRecipients": [
+                { "emailAddress": { "name": "Finance Team", "address": "finance@contoso.com" } },
+                { "emailAddress": { "address": "cfo@contoso.com" } }
+            ],
+            "receivedDateTime": "2024-05-02T09:30:00Z",
+            "isRead": false,
+            "flag": { "flagStatus": "flagged" },
+            "hasAttachments": true,
+            "categories": ["Finance"]
+        }));
+
+        let item = message_to_item(&message, "inbox");
+        assert_eq!(item.id.as_str(), "outlook:msg-1");
+        assert_eq!(item.stream_id.as_str(), "outlook:feed:inbox");
+        assert!(item.is_saved);
+        assert!(!item.is_read);
+        assert_eq!(item.tags, vec!["Finance"]);
+        assert_eq!(item.metadata["has_attachments"], "true");
+        assert_eq!(
+            item.metadata[TO_METADATA_KEY],
+            "Finance Team <finance@contoso.com>, cfo@contoso.com"
+        );
+        assert!(!item.metadata.contains_key(CC_METADATA_KEY));
+        let author = item.author.unwrap();
+        assert_eq!(author.email.as_deref(), Some("dana@contoso.com"));
+        assert!(matches!(
+            item.content,
+            ItemContent::Email {
+                body_text: None,
+                body_html: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_message_without_subject_or_sender_name() {
+        let message = message(json!({
+            "id": "msg-2",
+            "subject": "",
+            "from": { "emailAddress": { "address": "noreply@contoso.com" } },
+            "body": { "contentType": "text", "content": "hello" }
+        }));
+
+        let item = message_to_item(&message, "inbox");
+        assert_eq!(item.title, "(No subject)");
+        assert_eq!(item.author.unwrap().name, "noreply@contoso.com");
+        assert!(!item.is_saved);
+    }
+
+    #[test]
+    fn test_send_mail_body() {
+        let body = send_mail_body(&OutgoingMessage {
+            to: vec!["dana@contoso.com".to_string()],
+            subject: "Worth a read".to_string(),
+            body: "https://example.com/post".to_string(),
+        });
+        assert_eq!(
+            body["message"]["toRecipients"][0]["emailAddress"]["address"],
+            "dana@contoso.com"
+        );
+        assert_eq!(body["message"]["body"]["contentType"], "Text");
+        assert_eq!(body["saveToSentItems"], true);
+    }
+}
@@ -0,0 +1,304 @@
+//! Mock-server tests for `provider-outlook`'s Microsoft Graph HTTP paths.
+
+use std::sync::Arc;
+
+use provider_outlook::OutlookProvider;
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{
+    body_partial_json, header, method, path, query_param, query_param_is_missing,
+};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::{fixture, MockApi};
+use serde_json::{json, Value};
+
+const DELTA_PATH: &str = "/me/mailFolders/inbox/messages/delta";
+
+fn provider_for(api: &MockApi) -> OutlookProvider {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "outlook".to_string(),
+        "work".to_string(),
+        "test-token".to_string(),
+    );
+    OutlookProvider::with_base_url(Arc::new(tokens), "work".to_string(), api.uri())
+}
+
+fn inbox() -> FeedId {
+    FeedId("inbox".to_string())
+}
+
+fn all_mail() -> FeedOptions {
+    FeedOptions {
+        include_read: true,
+        ..Default::default()
+    }
+}
+
+async fn mock_delta(api: &MockApi, token: Option<(&str, &str)>, status: u16, body: Value) {
+    let mock = Mock::given(method("GET")).and(path(DELTA_PATH));
+    let mock = match token {
+        Some((param, value)) => mock.and(query_param(param, value)),
+        None => mock
+            .and(query_param_is_missing("$skiptoken"))
+            .and(query_param_is_missing("$deltatoken")),
+    };
+    mock.respond_with(ResponseTemplate::new(status).set_body_json(body))
+        .mount(api.server())
+        .await;
+}
+
+/// The initial two-page delta round of the inbox.
+async fn mock_initial_sync(api: &MockApi) {
+    mock_delta(api, None, 200, api.fixture("graph/mail_delta_page1.json")).await;
+    mock_delta(
+        api,
+        Some(("$skiptoken", "page2")),
+        200,
+        api.fixture("graph/mail_delta_page2.json"),
+    )
+    .await;
+}
+
+/// A delta page with no changes, resuming from `token` next time.
+fn no_changes(api: &MockApi, token: &str) -> Value {
+    json!({
+        "value": [],
+        "@odata.deltaLink": format!("{}{}?$deltatoken={}", api.uri(), DELTA_PATH, token)
+    })
+}
+
+fn titles(items: &[Item]) -> Vec<&str> {
+    items.iter().map(|item| item.title.as_str()).collect()
+}
+
+#[tokio::test]
+async fn folders_are_feeds_with_nested_paths() {
+    let api = MockApi::start().await;
+    api.mock_json("GET", "/me/mailFolders", fixture("graph/mail_folders.json"))
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/$batch"))
+        .and(body_partial_json(json!({
+            "requests": [{
+                "id": "0",
+                "method": "GET",
+                "url": "/me/mailFolders/folder-inbox/childFolders?$top=100"
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "responses": [{
+                "id": "0",
+                "status": 200,
+                "body": fixture("graph/mail_child_folders.json")
+            }]
+        })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let feeds = provider_for(&api).list_feeds().await.unwrap();
+
+    let names: Vec<&str> = feeds.iter().map(|feed| feed.name.as_str()).collect();
+    assert_eq!(names, vec!["Inbox", "Inbox/Receipts", "Archive"]);
+    assert_eq!(feeds[1].id.0, "folder-receipts");
+    assert_eq!(feeds[0].unread_count, Some(2));
+}
+
+#[tokio::test]
+async fn delta_sync_fetches_only_changes() {
+    let api = MockApi::start().await;
+    mock_initial_sync(&api).await;
+    mock_delta(
+        &api,
+        Some(("$deltatoken", "token1")),
+        200,
+        api.fixture("graph/mail_delta_changes.json"),
+    )
+    .await;
+    mock_delta(
+        &api,
+        Some(("$deltatoken", "token2")),
+        200,
+        no_changes(&api, "token2"),
+    )
+    .await;
+    let provider = provider_for(&api);
+
+    let items = provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+    assert_eq!(titles(&items), vec!["Quarterly report", "Lunch?"]);
+    assert_eq!(api.request_count().await, 2);
+    assert!(items[0].is_saved);
+    assert_eq!(items[0].author.as_ref().unwrap().name, "Dana Lee");
+
+    // The second round resumes from the delta link: msg-3 arrives, msg-1 is
+    // read elsewhere, msg-2 is deleted
+    let sync = provider.sync().await.unwrap();
+    assert!(sync.success);
+    assert_eq!(
+        (sync.items_added, sync.items_updated, sync.items_removed),
+        (1, 1, 1)
+    );
+
+    let items = provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+    assert_eq!(titles(&items), vec!["Build failed", "Quarterly report"]);
+    assert!(items[1].is_read);
+
+    let unread = provider
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(titles(&unread), vec!["Build failed"]);
+}
+
+#[tokio::test]
+async fn expired_delta_link_starts_over() {
+    let api = MockApi::start().await;
+    mock_initial_sync(&api).await;
+    mock_delta(
+        &api,
+        Some(("$deltatoken", "token1")),
+        410,
+        json!({
+            "error": { "code": "SyncStateNotFound", "message": "The sync state is invalid" }
+        }),
+    )
+    .await;
+    let provider = provider_for(&api);
+
+    provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+    let items = provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+
+    assert_eq!(titles(&items), vec!["Quarterly report", "Lunch?"]);
+    // Initial round, rejected delta link, fresh round
+    assert_eq!(api.request_count().await, 5);
+}
+
+#[tokio::test]
+async fn delta_pages_are_sized_with_prefer() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path(DELTA_PATH))
+        .and(header("prefer", "odata.maxpagesize=50"))
+        .and(query_param("$orderby", "receivedDateTime desc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(no_changes(&api, "t")))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+}
+
+#[tokio::test]
+async fn flag_read_move_and_reply() {
+    let api = MockApi::start().await;
+    mock_initial_sync(&api).await;
+    mock_delta(
+        &api,
+        Some(("$deltatoken", "token1")),
+        200,
+        no_changes(&api, "token1"),
+    )
+    .await;
+    Mock::given(method("PATCH"))
+        .and(path("/me/messages/msg-1"))
+        .and(body_partial_json(
+            json!({ "flag": { "flagStatus": "notFlagged" } }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "msg-1" })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/me/messages/msg-1"))
+        .and(body_partial_json(json!({ "isRead": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "msg-1" })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/me/messages/msg-1/reply"))
+        .and(body_partial_json(json!({ "comment": "Thanks!" })))
+        .respond_with(ResponseTemplate::new(202))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/me/messages/msg-1/move"))
+        .and(body_partial_json(json!({ "destinationId": "archive" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": "msg-1-moved" })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let item = provider
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let action = |id: &str| actions.iter().find(|a| a.id == id).unwrap();
+    assert!(actions
+        .iter()
+        .all(|a| a.id != "flag" && a.id != "mark_unread"));
+
+    provider
+        .execute_action(&item, action("unflag"))
+        .await
+        .unwrap();
+    provider
+        .execute_action(&item, action("mark_read"))
+        .await
+        .unwrap();
+
+    let reply = provider
+        .execute_action(&item, action("reply"))
+        .await
+        .unwrap();
+    assert_eq!(reply.data.unwrap()["requires_input"], true);
+    provider.reply(&item, "Thanks!").await.unwrap();
+
+    // The changes show before the delta round reports them
+    let items = provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+    assert_eq!(items[0].id, item.id);
+    assert!(items[0].is_read);
+    assert!(!items[0].is_saved);
+
+    provider
+        .execute_action(&item, action("archive"))
+        .await
+        .unwrap();
+    let items = provider.get_feed_items(&inbox(), all_mail()).await.unwrap();
+    assert_eq!(titles(&items), vec!["Lunch?"]);
+}
+
+#[tokio::test]
+async fn expired_token_requires_auth() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/me/mailFolders",
+        401,
+        fixture("graph/error_unauthorized.json"),
+    )
+    .await;
+
+    let err = provider_for(&api).list_feeds().await.unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn throttling_is_rate_limited() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited(DELTA_PATH, 7).await;
+
+    let err = provider_for(&api)
+        .get_feed_items(&inbox(), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(7)), "{:?}", err);
+}
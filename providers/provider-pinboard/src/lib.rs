@@ -0,0 +1,558 @@
+//! # provider-pinboard
+//!
+//! Pinboard provider for Scryforge.
+//!
+//! This provider keeps the local bookmarks of `provider-bookmarks` in step
+//! with a [Pinboard] account, in both directions. It has no streams of its
+//! own: synced bookmarks are listed by the bookmarks provider.
+//!
+//! ## Features
+//!
+//! - Bookmarks added, edited, or deleted on either side reach the other on
+//!   the next sync
+//! - Titles, notes, tags, the "to read" flag, and private/public status are
+//!   synced
+//! - An account that hasn't changed since the last sync costs one request
+//!
+//! ## Syncing
+//!
+//! Bookmarks are matched by URL, ignoring the differences
+//! [`provider_bookmarks::duplicates::url_key`] ignores. Each sync compares
+//! both sides with what they held after the previous one, which is kept in
+//! a state file: a side that changed a bookmark since then wins, and when
+//! both did the local copy wins. Deleting a bookmark on one side deletes it
+//! on the other, unless the other changed it meanwhile.
+//!
+//! Pinboard tags can't contain spaces, so spaces in local tags become
+//! dashes. Local bookmarks are private on Pinboard unless marked shared.
+//!
+//! ## Authentication
+//!
+//! The API token, `username:TOKEN` from Pinboard's password settings page,
+//! is fetched via the Sigilforge client using the service name "pinboard".
+//!
+//! [Pinboard]: https://pinboard.in
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use provider_bookmarks::duplicates::url_key;
+use provider_bookmarks::{Bookmark, BookmarksProvider};
+use reqwest::Client;
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+mod merge;
+
+use merge::{Change, Post, Posts};
+
+// ============================================================================
+// Pinboard API Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct PinboardPost {
+    href: String,
+    /// The title; Pinboard calls it the description
+    description: String,
+    /// Notes
+    #[serde(default)]
+    extended: String,
+    /// Space-separated tags
+    #[serde(default)]
+    tags: String,
+    time: Option<DateTime<Utc>>,
+    /// `"yes"` or `"no"`
+    #[serde(default)]
+    shared: String,
+    /// `"yes"` or `"no"`
+    #[serde(default)]
+    toread: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTime {
+    update_time: String,
+}
+
+/// Response of the write methods.
+#[derive(Debug, Deserialize)]
+struct ResultCode {
+    result_code: String,
+}
+
+impl From<PinboardPost> for Post {
+    fn from(post: PinboardPost) -> Self {
+        Post {
+            url: post.href,
+            title: post.description,
+            notes: Some(post.extended).filter(|notes| !notes.is_empty()),
+            tags: post.tags.split_whitespace().map(String::from).collect(),
+            shared: post.shared == "yes",
+            to_read: post.toread == "yes",
+        }
+    }
+}
+
+/// The synced fields of a local bookmark, as Pinboard would store them.
+fn bookmark_post(bookmark: &Bookmark) -> Post {
+    Post {
+        url: bookmark.url.clone(),
+        title: bookmark.title.clone(),
+        notes: bookmark
+            .description
+            .clone()
+            .filter(|notes| !notes.is_empty()),
+        tags: bookmark
+            .tags
+            .iter()
+            .map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("-"))
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        shared: bookmark.shared,
+        to_read: bookmark.to_read,
+    }
+}
+
+fn yes_no(flag: bool) -> String {
+    if flag { "yes" } else { "no" }.to_string()
+}
+
+// ============================================================================
+// Sync State
+// ============================================================================
+
+/// What both sides held after the last sync.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    /// Pinboard's `update_time` when its posts were last read
+    update_time: Option<String>,
+    /// Synced bookmarks by URL key
+    posts: Posts,
+}
+
+impl SyncState {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| StreamError::Internal(format!("Failed to read sync state: {}", e)))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| StreamError::Internal(format!("Invalid sync state: {}", e)))
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                StreamError::Internal(format!("Failed to create state directory: {}", e))
+            })?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| StreamError::Internal(format!("Failed to encode sync state: {}", e)))?;
+        std::fs::write(path, contents)
+            .map_err(|e| StreamError::Internal(format!("Failed to write sync state: {}", e)))
+    }
+}
+
+// ============================================================================
+// Pinboard Provider
+// ============================================================================
+
+const PROVIDER_ID: &str = "pinboard";
+
+/// Pinboard provider for Scryforge.
+pub struct PinboardProvider {
+    token_fetcher: Arc<dyn TokenFetcher>,
+    account: String,
+    bookmarks: Arc<BookmarksProvider>,
+    state_path: PathBuf,
+    client: Client,
+    base_url: String,
+    request_interval: Duration,
+    /// When the last request went out
+    last_request: Mutex<Option<Instant>>,
+    /// Held for the length of a sync, so two can't interleave
+    sync_lock: Mutex<()>,
+}
+
+impl PinboardProvider {
+    const API_BASE: &'static str = "https://api.pinboard.in/v1";
+    /// Pinboard asks clients to wait three seconds between requests.
+    const REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+    /// Create a new Pinboard provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_fetcher` - Token fetcher for the API token
+    /// * `account` - Account identifier for the token (e.g., "personal")
+    /// * `bookmarks` - The local bookmarks to keep in step with Pinboard
+    /// * `state_path` - File recording what both sides held after the last
+    ///   sync
+    pub fn new(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        account: String,
+        bookmarks: Arc<BookmarksProvider>,
+        state_path: PathBuf,
+    ) -> Self {
+        let client = Client::builder()
+            .user_agent("scryforge/0.1.0")
+            .build()
+            .unwrap();
+
+        Self {
+            token_fetcher,
+            account,
+            bookmarks,
+            state_path,
+            client,
+            base_url: Self::API_BASE.to_string(),
+            request_interval: Self::REQUEST_INTERVAL,
+            last_request: Mutex::new(None),
+            sync_lock: Mutex::new(()),
+        }
+    }
+
+    /// Talk to a different API base URL, such as a mock server in tests.
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Change the least time between requests.
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    async fn token(&self) -> Result<String> {
+        self.token_fetcher
+            .fetch_token(PROVIDER_ID, &self.account)
+            .await
+            .map_err(|e| StreamError::AuthRequired(format!("Failed to fetch token: {}", e)))
+    }
+
+    /// Every API method is a GET, writes included.
+    async fn api_get<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+    ) -> Result<T> {
+        let token = self.token().await?;
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        let mut last_request = self.last_request.lock().await;
+        if let Some(at) = *last_request {
+            let wait = self.request_interval.saturating_sub(at.elapsed());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("auth_token", token.as_str()), ("format", "json")])
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()));
+        *last_request = Some(Instant::now());
+        drop(last_request);
+        let response = response?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok());
+            let body = response.text().await.unwrap_or_default();
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                retry_after,
+                format!("Pinboard API error ({}): {}", status, body),
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))
+    }
+
+    /// Run a write method, which reports failure in its result code.
+    async fn api_write(&self, endpoint: &str, params: &[(&str, String)]) -> Result<String> {
+        let result: ResultCode = self.api_get(endpoint, params).await?;
+        Ok(result.result_code)
+    }
+
+    /// Add `post` to Pinboard, replacing any post for its URL.
+    async fn add_post(&self, post: &Post, created_at: DateTime<Utc>) -> Result<()> {
+        // Pinboard rejects posts without a title
+        let title = if post.title.trim().is_empty() {
+            post.url.clone()
+        } else {
+            post.title.clone()
+        };
+        let params = [
+            ("url", post.url.clone()),
+            ("description", title),
+            ("extended", post.notes.clone().unwrap_or_default()),
+            ("tags", post.tags.join(" ")),
+            ("dt", created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+            ("replace", "yes".to_string()),
+            ("shared", yes_no(post.shared)),
+            ("toread", yes_no(post.to_read)),
+        ];
+        match self.api_write("/posts/add", &params).await?.as_str() {
+            "done" => Ok(()),
+            code => Err(StreamError::Provider(format!(
+                "Pinboard rejected {}: {}",
+                post.url, code
+            ))),
+        }
+    }
+
+    async fn delete_post(&self, url: &str) -> Result<()> {
+        match self
+            .api_write("/posts/delete", &[("url", url.to_string())])
+            .await?
+            .as_str()
+        {
+            "done" | "item not found" => Ok(()),
+            code => Err(StreamError::Provider(format!(
+                "Pinboard refused to delete {}: {}",
+                url, code
+            ))),
+        }
+    }
+
+    /// Bring the local bookmarks and Pinboard in step.
+    ///
+    /// Pinboard is written first and the state file last, so a sync that
+    /// fails part way is finished by the next one.
+    async fn sync_bookmarks(&self) -> Result<SyncResult> {
+        let start = Instant::now();
+        let _sync = self.sync_lock.lock().await;
+        let mut state = SyncState::load(&self.state_path)?;
+
+        // Pick up edits made to the bookmarks file outside this process
+        self.bookmarks.sync().await?;
+        let mut local_bookmarks: HashMap<String, Bookmark> = HashMap::new();
+        let mut local = Posts::new();
+        for bookmark in self.bookmarks.bookmarks() {
+            let key = url_key(&bookmark.url);
+            if !local.contains_key(&key) {
+                local.insert(key.clone(), bookmark_post(&bookmark));
+                local_bookmarks.insert(key, bookmark);
+            }
+        }
+
+        let update: UpdateTime = self.api_get("/posts/update", &[]).await?;
+        let mut created = HashMap::new();
+        let remote = if state.update_time.as_ref() == Some(&update.update_time) {
+            // Nothing changed on Pinboard since the last sync
+            state.posts.clone()
+        } else {
+            let posts: Vec<PinboardPost> = self.api_get("/posts/all", &[]).await?;
+            let mut remote = Posts::new();
+            for post in posts {
+                let key = url_key(&post.href);
+                if let Some(time) = post.time {
+                    created.insert(key.clone(), time);
+                }
+                remote.entry(key).or_insert_with(|| post.into());
+            }
+            remote
+        };
+
+        let (changes, merged) = merge::plan(&state.posts, &local, &remote);
+        let now = Utc::now();
+        let mut put = Vec::new();
+        let mut delete = Vec::new();
+        let (mut added, mut updated) = (0, 0);
+        for (key, change) in changes {
+            match change {
+                Change::Push => {
+                    let created_at = local_bookmarks[&key].created_at;
+                    self.add_post(&local[&key], created_at).await?;
+                }
+                Change::DeleteRemote => self.delete_post(&remote[&key].url).await?,
+                Change::Pull => {
+                    let post = &remote[&key];
+                    let bookmark = match local_bookmarks.get(&key) {
+                        Some(existing) => {
+                            updated += 1;
+                            Bookmark {
+                                title: post.title.clone(),
+                                description: post.notes.clone(),
+                                tags: post.tags.clone(),
+                                shared: post.shared,
+                                to_read: post.to_read,
+                                updated_at: Some(now),
+                                ..existing.clone()
+                            }
+                        }
+                        None => {
+                            added += 1;
+                            Bookmark {
+                                id: Uuid::new_v4().to_string(),
+                                folder_id: None,
+                                title: post.title.clone(),
+                                url: post.url.clone(),
+                                description: post.notes.clone(),
+                                created_at: created.get(&key).copied().unwrap_or(now),
+                                updated_at: None,
+                                tags: post.tags.clone(),
+                                favicon_url: None,
+                                to_read: post.to_read,
+                                shared: post.shared,
+                            }
+                        }
+                    };
+                    put.push(bookmark);
+                }
+                Change::DeleteLocal => delete.push(local_bookmarks[&key].id.clone()),
+            }
+        }
+
+        self.bookmarks.put_bookmarks(put)?;
+        let removed = self.bookmarks.delete_bookmarks(&delete)?;
+
+        state.update_time = Some(update.update_time);
+        state.posts = merged;
+        state.save(&self.state_path)?;
+
+        Ok(SyncResult {
+            success: true,
+            items_added: added,
+            items_updated: updated,
+            items_removed: removed as u32,
+            errors: vec![],
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for PinboardProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Pinboard"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        match self.api_get::<UpdateTime>("/posts/update", &[]).await {
+            Ok(_) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some("Connected to Pinboard".to_string()),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("API error: {}", e)),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        self.sync_bookmarks().await
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: false,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: true,
+            supports_pagination: false,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
+        // Synced bookmarks are items of the bookmarks provider
+        Ok(vec![])
+    }
+
+    async fn execute_action(&self, _item: &Item, action: &Action) -> Result<ActionResult> {
+        Ok(ActionResult {
+            success: false,
+            message: Some(format!("Action '{}' not supported", action.name)),
+            data: None,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_from_pinboard() {
+        let post: PinboardPost = serde_json::from_value(serde_json::json!({
+            "href": "https://crates.io/",
+            "description": "crates.io",
+            "extended": "",
+            "meta": "0d3f",
+            "hash": "9a1b",
+            "time": "2024-05-01T12:00:00Z",
+            "shared": "no",
+            "toread": "yes",
+            "tags": "rust  packages"
+        }))
+        .unwrap();
+        let post = Post::from(post);
+
+        assert_eq!(post.title, "crates.io");
+        assert_eq!(post.notes, None);
+        assert_eq!(post.tags, vec!["rust", "packages"]);
+        assert!(!post.shared);
+        assert!(post.to_read);
+    }
+
+    #[test]
+    fn test_bookmark_post_dashes_spaces_in_tags() {
+        let bookmark = Bookmark {
+            id: "1".to_string(),
+            folder_id: None,
+            title: "The Book".to_string(),
+            url: "https://doc.rust-lang.org/book/".to_string(),
+            description: Some(String::new()),
+            created_at: Utc::now(),
+            updated_at: None,
+            tags: vec!["rust".to_string(), "must read".to_string(), " ".to_string()],
+            favicon_url: None,
+            to_read: false,
+            shared: true,
+        };
+        let post = bookmark_post(&bookmark);
+
+        assert_eq!(post.tags, vec!["rust", "must-read"]);
+        assert_eq!(post.notes, None);
+        assert!(post.shared);
+    }
+}
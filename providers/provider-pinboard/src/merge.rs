@@ -0,0 +1,177 @@
+//! Three-way merge of local bookmarks and Pinboard posts.
+//!
+//! Both sides are compared, by URL key, with what they held after the last
+//! sync. A side that changed a bookmark since then wins; when both did, or
+//! a bookmark was added to both, the local copy wins. A bookmark missing
+//! from one side is deleted from the other, unless the other changed it
+//! since the last sync, in which case it is copied back.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+/// The fields of a bookmark kept in step with Pinboard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Post {
+    pub url: String,
+    pub title: String,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+    pub shared: bool,
+    pub to_read: bool,
+}
+
+/// Posts by URL key.
+pub(crate) type Posts = BTreeMap<String, Post>;
+
+/// What a sync does with one bookmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Change {
+    /// Write the local copy to Pinboard
+    Push,
+    /// Write Pinboard's copy locally
+    Pull,
+    /// Delete the post from Pinboard
+    DeleteRemote,
+    /// Delete the local bookmark
+    DeleteLocal,
+}
+
+/// The changes that bring `local` and `remote` in step, given `base` as both
+/// held after the last sync, and the posts both hold once they are made.
+pub(crate) fn plan(base: &Posts, local: &Posts, remote: &Posts) -> (Vec<(String, Change)>, Posts) {
+    let keys: BTreeSet<&String> = base
+        .keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut merged = Posts::new();
+    for key in keys {
+        let base = base.get(key);
+        let (change, post) = match (local.get(key), remote.get(key)) {
+            (Some(local), Some(remote)) if local == remote => (None, Some(local)),
+            // Pinboard's copy only wins over an unchanged local one
+            (Some(local), Some(remote)) if base == Some(local) => {
+                (Some(Change::Pull), Some(remote))
+            }
+            (Some(local), Some(_)) => (Some(Change::Push), Some(local)),
+            (Some(local), None) if base == Some(local) => (Some(Change::DeleteLocal), None),
+            (Some(local), None) => (Some(Change::Push), Some(local)),
+            (None, Some(remote)) if base == Some(remote) => (Some(Change::DeleteRemote), None),
+            (None, Some(remote)) => (Some(Change::Pull), Some(remote)),
+            (None, None) => (None, None),
+        };
+        if let Some(change) = change {
+            changes.push((key.clone(), change));
+        }
+        if let Some(post) = post {
+            merged.insert(key.clone(), post.clone());
+        }
+    }
+    (changes, merged)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(url: &str, title: &str) -> Post {
+        Post {
+            url: format!("https://{}", url),
+            title: title.to_string(),
+            notes: None,
+            tags: vec![],
+            shared: false,
+            to_read: false,
+        }
+    }
+
+    fn posts(list: &[(&str, &str)]) -> Posts {
+        list.iter()
+            .map(|(url, title)| (url.to_string(), post(url, title)))
+            .collect()
+    }
+
+    #[test]
+    fn test_first_sync_unions_both_sides() {
+        let local = posts(&[("a.com", "A"), ("both.com", "Local title")]);
+        let remote = posts(&[("b.com", "B"), ("both.com", "Remote title")]);
+
+        let (changes, merged) = plan(&Posts::new(), &local, &remote);
+        assert_eq!(
+            changes,
+            vec![
+                ("a.com".to_string(), Change::Push),
+                ("b.com".to_string(), Change::Pull),
+                ("both.com".to_string(), Change::Push),
+            ]
+        );
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged["both.com"].title, "Local title");
+    }
+
+    #[test]
+    fn test_changed_side_wins() {
+        let base = posts(&[("a.com", "A"), ("b.com", "B"), ("c.com", "C")]);
+        let local = posts(&[("a.com", "A edited"), ("b.com", "B"), ("c.com", "C mine")]);
+        let remote = posts(&[("a.com", "A"), ("b.com", "B edited"), ("c.com", "C theirs")]);
+
+        let (changes, merged) = plan(&base, &local, &remote);
+        assert_eq!(
+            changes,
+            vec![
+                ("a.com".to_string(), Change::Push),
+                ("b.com".to_string(), Change::Pull),
+                ("c.com".to_string(), Change::Push),
+            ]
+        );
+        assert_eq!(merged["a.com"].title, "A edited");
+        assert_eq!(merged["b.com"].title, "B edited");
+        assert_eq!(merged["c.com"].title, "C mine");
+    }
+
+    #[test]
+    fn test_deletions_propagate_unless_edited() {
+        let base = posts(&[
+            ("a.com", "A"),
+            ("b.com", "B"),
+            ("c.com", "C"),
+            ("d.com", "D"),
+        ]);
+        // a deleted locally, b deleted on Pinboard, c deleted locally but
+        // edited on Pinboard, d deleted on Pinboard but edited locally
+        let local = posts(&[("b.com", "B"), ("d.com", "D edited")]);
+        let remote = posts(&[("a.com", "A"), ("c.com", "C edited")]);
+
+        let (changes, merged) = plan(&base, &local, &remote);
+        assert_eq!(
+            changes,
+            vec![
+                ("a.com".to_string(), Change::DeleteRemote),
+                ("b.com".to_string(), Change::DeleteLocal),
+                ("c.com".to_string(), Change::Pull),
+                ("d.com".to_string(), Change::Push),
+            ]
+        );
+        let keys: Vec<&str> = merged.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["c.com", "d.com"]);
+    }
+
+    #[test]
+    fn test_nothing_to_do_when_in_step() {
+        let both = posts(&[("a.com", "A")]);
+        let (changes, merged) = plan(&both, &both, &both);
+        assert!(changes.is_empty());
+        assert_eq!(merged, both);
+
+        let (changes, merged) = plan(&both, &Posts::new(), &Posts::new());
+        assert!(changes.is_empty());
+        assert!(merged.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+//! Mock-server tests for `provider-pinboard`'s sync.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use provider_bookmarks::BookmarksProvider;
+use provider_pinboard::PinboardProvider;
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{method, path, query_param};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::MockApi;
+use serde_json::json;
+use tempfile::TempDir;
+
+const TOKEN: &str = "maciej:ABC123";
+
+fn provider_for(api: &MockApi, dir: &TempDir) -> (PinboardProvider, Arc<BookmarksProvider>) {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "pinboard".to_string(),
+        "personal".to_string(),
+        TOKEN.to_string(),
+    );
+    let bookmarks =
+        Arc::new(BookmarksProvider::with_path(dir.path().join("bookmarks.json")).unwrap());
+    let provider = PinboardProvider::new(
+        Arc::new(tokens),
+        "personal".to_string(),
+        bookmarks.clone(),
+        dir.path().join("pinboard-state.json"),
+    )
+    .with_base_url(api.uri())
+    .with_request_interval(Duration::ZERO);
+    (provider, bookmarks)
+}
+
+async fn mock_update_time(api: &MockApi, update_time: &str) {
+    Mock::given(method("GET"))
+        .and(path("/posts/update"))
+        .and(query_param("auth_token", TOKEN))
+        .and(query_param("format", "json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "update_time": update_time
+        })))
+        .mount(api.server())
+        .await;
+}
+
+fn done() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({ "result_code": "done" }))
+}
+
+#[tokio::test]
+async fn first_sync_merges_both_sides() {
+    let api = MockApi::start().await;
+    let dir = TempDir::new().unwrap();
+    let (provider, bookmarks) = provider_for(&api, &dir);
+    bookmarks
+        .add_bookmark(
+            "The Rust Programming Language".to_string(),
+            "https://doc.rust-lang.org/book/".to_string(),
+            None,
+            Some("Chapter 10 next".to_string()),
+            vec!["rust".to_string(), "must read".to_string()],
+        )
+        .unwrap();
+
+    mock_update_time(&api, "2024-05-01T12:00:00Z").await;
+    Mock::given(method("GET"))
+        .and(path("/posts/all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "href": "https://crates.io/",
+            "description": "crates.io",
+            "extended": "",
+            "meta": "5c1d",
+            "hash": "9a1b",
+            "time": "2023-11-05T08:30:00Z",
+            "shared": "yes",
+            "toread": "yes",
+            "tags": "rust packages"
+        }])))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/posts/add"))
+        .and(query_param("url", "https://doc.rust-lang.org/book/"))
+        .and(query_param("description", "The Rust Programming Language"))
+        .and(query_param("extended", "Chapter 10 next"))
+        .and(query_param("tags", "rust must-read"))
+        .and(query_param("shared", "no"))
+        .and(query_param("toread", "no"))
+        .and(query_param("replace", "yes"))
+        .respond_with(done())
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let result = provider.sync().await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.items_added, 1);
+    assert_eq!(result.items_updated, 0);
+
+    let pulled = bookmarks.find_by_url("https://crates.io/").unwrap();
+    assert_eq!(pulled.title, "crates.io");
+    assert_eq!(pulled.tags, vec!["rust", "packages"]);
+    assert!(pulled.to_read);
+    assert!(pulled.shared);
+    assert_eq!(pulled.created_at.to_rfc3339(), "2023-11-05T08:30:00+00:00");
+    assert_eq!(bookmarks.bookmarks().len(), 2);
+
+    // Both sides agree now, and Pinboard hasn't changed since
+    let result = provider.sync().await.unwrap();
+    assert_eq!(result.items_added, 0);
+}
+
+#[tokio::test]
+async fn unchanged_account_skips_listing_and_gets_local_deletions() {
+    let api = MockApi::start().await;
+    let dir = TempDir::new().unwrap();
+    let (provider, bookmarks) = provider_for(&api, &dir);
+
+    mock_update_time(&api, "2024-05-01T12:00:00Z").await;
+    Mock::given(method("GET"))
+        .and(path("/posts/all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "href": "https://example.com/post",
+            "description": "A post",
+            "time": "2024-04-01T00:00:00Z",
+            "shared": "no",
+            "toread": "no",
+            "tags": ""
+        }])))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/posts/delete"))
+        .and(query_param("url", "https://example.com/post"))
+        .respond_with(done())
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    provider.sync().await.unwrap();
+    let post = bookmarks.find_by_url("https://example.com/post").unwrap();
+    assert!(!post.to_read);
+    assert!(!post.shared);
+
+    bookmarks.delete_bookmarks(&[post.id]).unwrap();
+    provider.sync().await.unwrap();
+    assert!(bookmarks.bookmarks().is_empty());
+}
+
+#[tokio::test]
+async fn posts_deleted_on_pinboard_are_deleted_locally() {
+    let api = MockApi::start().await;
+    let dir = TempDir::new().unwrap();
+    let (provider, bookmarks) = provider_for(&api, &dir);
+
+    // The first listing has the post, the second, after the update time
+    // moved, doesn't
+    Mock::given(method("GET"))
+        .and(path("/posts/update"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "update_time": "2024-05-01T12:00:00Z" })),
+        )
+        .up_to_n_times(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/posts/all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "href": "https://example.com/gone",
+            "description": "Soon gone",
+            "shared": "yes",
+            "toread": "no",
+            "tags": "temp"
+        }])))
+        .up_to_n_times(1)
+        .mount(api.server())
+        .await;
+    provider.sync().await.unwrap();
+    assert_eq!(bookmarks.bookmarks().len(), 1);
+
+    mock_update_time(&api, "2024-05-02T09:00:00Z").await;
+    Mock::given(method("GET"))
+        .and(path("/posts/all"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .mount(api.server())
+        .await;
+    let result = provider.sync().await.unwrap();
+    assert_eq!(result.items_removed, 1);
+    assert!(bookmarks.bookmarks().is_empty());
+}
+
+#[tokio::test]
+async fn rejected_token_is_auth_required() {
+    let api = MockApi::start().await;
+    let dir = TempDir::new().unwrap();
+    let (provider, _bookmarks) = provider_for(&api, &dir);
+    api.mock_unauthorized("/posts/update").await;
+
+    let err = provider.sync().await.unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+    let health = provider.health_check().await.unwrap();
+    assert!(!health.is_healthy);
+}
@@ -0,0 +1,198 @@
+//! Reading a public-inbox Atom feed.
+//!
+//! public-inbox serves the newest messages of a list at `new.atom`, and
+//! search results with `?x=A`. Each entry is one message: its link is the
+//! message's page (`{list}/{Message-ID}/`), `thr:in-reply-to` (RFC 4685)
+//! links the parent, and the content is the body inside an XHTML `<pre>`.
+
+use chrono::{DateTime, Utc};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use scryforge_provider_core::Author;
+
+use crate::message::{unescape_id, Message};
+
+/// Parse a public-inbox Atom feed. Entries without a message link are
+/// skipped.
+pub fn parse(xml: &str) -> Result<Vec<Message>, quick_xml::Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut messages = Vec::new();
+    let mut entry: Option<Entry> = None;
+    // Local names of the open elements inside the current entry
+    let mut path: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = local_name(&element);
+                if name == "entry" {
+                    entry = Some(Entry::default());
+                    path.clear();
+                } else if let Some(entry) = &mut entry {
+                    entry.attributes(&name, &element);
+                    path.push(name);
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(entry) = &mut entry {
+                    entry.attributes(&local_name(&element), &element);
+                }
+            }
+            Event::End(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if name == "entry" {
+                    messages.extend(entry.take().and_then(Entry::into_message));
+                } else if entry.is_some() {
+                    path.pop();
+                }
+            }
+            Event::Text(text) => {
+                if let Some(entry) = &mut entry {
+                    // An entity XML doesn't define shouldn't lose the entry
+                    let text = text
+                        .unescape()
+                        .unwrap_or_else(|_| String::from_utf8_lossy(&text).into_owned().into());
+                    entry.text(&path, &text);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(entry) = &mut entry {
+                    entry.text(&path, &String::from_utf8_lossy(&data));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(messages)
+}
+
+fn local_name(element: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(element.local_name().as_ref()).into_owned()
+}
+
+fn attribute(element: &BytesStart<'_>, key: &str) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attribute| attribute.key.local_name().as_ref() == key.as_bytes())
+        .and_then(|attribute| attribute.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// The `Message-ID` a public-inbox message URL points at.
+fn id_from_url(url: &str) -> Option<String> {
+    let path = url.split(['#', '?']).next()?.trim_end_matches('/');
+    let id = path.rsplit('/').next()?;
+    Some(unescape_id(id)).filter(|id| !id.is_empty())
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    title: String,
+    author_name: String,
+    author_email: String,
+    updated: String,
+    link: Option<String>,
+    in_reply_to: Option<String>,
+    content: String,
+}
+
+impl Entry {
+    fn attributes(&mut self, name: &str, element: &BytesStart<'_>) {
+        match name {
+            "link" if self.link.is_none() => {
+                let rel = attribute(element, "rel");
+                if rel.is_none() || rel.as_deref() == Some("alternate") {
+                    self.link = attribute(element, "href");
+                }
+            }
+            "in-reply-to" => {
+                self.in_reply_to = attribute(element, "href").as_deref().and_then(id_from_url);
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, path: &[String], text: &str) {
+        let field = match path.first().map(String::as_str) {
+            Some("title") => &mut self.title,
+            Some("updated") => &mut self.updated,
+            Some("author") => match path.get(1).map(String::as_str) {
+                Some("name") => &mut self.author_name,
+                Some("email") => &mut self.author_email,
+                _ => return,
+            },
+            Some("content") => &mut self.content,
+            _ => return,
+        };
+        field.push_str(text);
+    }
+
+    fn into_message(self) -> Option<Message> {
+        let link = self.link?;
+        let author_name = self.author_name.trim();
+        let author_email = self.author_email.trim();
+        let author = (!author_name.is_empty() || !author_email.is_empty()).then(|| Author {
+            name: author_name.to_string(),
+            email: Some(author_email.to_string()).filter(|email| !email.is_empty()),
+            url: None,
+            avatar_url: None,
+        });
+
+        Some(Message {
+            message_id: id_from_url(&link)?,
+            subject: self.title.trim().to_string(),
+            author,
+            date: DateTime::parse_from_rfc3339(self.updated.trim())
+                .ok()
+                .map(|date| date.with_timezone(&Utc)),
+            in_reply_to: self.in_reply_to,
+            references: Vec::new(),
+            body: self.content.trim_matches('\n').to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        let xml = r#"<?xml version="1.0" encoding="us-ascii"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">
+<title>rust-for-linux.vger.kernel.org archive mirror</title>
+<link rel="alternate" type="text/html" href="https://lore.kernel.org/rust-for-linux/"/>
+<updated>2024-05-01T12:05:00Z</updated>
+<entry><author><name>Bob</name><email>bob@example.com</email></author>
+<title>Re: [PATCH 1/2] rust: add foo</title>
+<updated>2024-05-01T12:05:00Z</updated>
+<link href="https://lore.kernel.org/rust-for-linux/reply%2F1@example.com/"/>
+<id>urn:uuid:1</id>
+<thr:in-reply-to ref="urn:uuid:0" href="https://lore.kernel.org/rust-for-linux/patch-1@example.com/"/>
+<content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><pre style="white-space:pre-wrap">On Wed, Alice wrote:
+&gt; +fn foo() {}
+
+Looks good &amp; builds.
+</pre></div></content></entry>
+<entry><title>no link</title></entry>
+</feed>"#;
+        let messages = parse(xml).unwrap();
+
+        assert_eq!(messages.len(), 1);
+        let reply = &messages[0];
+        assert_eq!(reply.message_id, "reply/1@example.com");
+        assert_eq!(reply.subject, "Re: [PATCH 1/2] rust: add foo");
+        assert_eq!(reply.sender(), "Bob");
+        assert_eq!(reply.in_reply_to.as_deref(), Some("patch-1@example.com"));
+        assert_eq!(
+            reply.date.unwrap().to_rfc3339(),
+            "2024-05-01T12:05:00+00:00"
+        );
+        assert_eq!(
+            reply.body,
+            "On Wed, Alice wrote:\n> +fn foo() {}\n\nLooks good & builds."
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Configuration for the public-inbox provider.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::message::escape_id;
+
+/// A mailing list archive, or a search within one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MailingList {
+    /// Feed name, e.g. `rust-for-linux`
+    pub name: String,
+    /// The list's archive, e.g. `https://lore.kernel.org/rust-for-linux`
+    pub url: String,
+    /// A public-inbox search query (`s:`, `f:`, `dfn:`, ...) to follow
+    /// instead of every new message
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+impl MailingList {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            query: None,
+        }
+    }
+
+    /// Follow the messages matching a search query, such as
+    /// `dfn:rust/kernel/ d:7.days.ago..`.
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// The archive URL without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        self.url.trim_end_matches('/')
+    }
+
+    /// The web page of the thread a message is in.
+    pub fn thread_url(&self, message_id: &str) -> String {
+        format!("{}/{}/T/#u", self.base_url(), escape_id(message_id))
+    }
+
+    /// The gzipped mbox of the thread a message is in.
+    pub fn mbox_url(&self, message_id: &str) -> String {
+        format!("{}/{}/t.mbox.gz", self.base_url(), escape_id(message_id))
+    }
+}
+
+/// Configuration for the [`PublicInboxProvider`](crate::PublicInboxProvider).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublicInboxConfig {
+    /// Lists to follow
+    pub lists: Vec<MailingList>,
+    /// Where downloaded series are saved for `git am`
+    pub download_dir: PathBuf,
+    /// A git checkout series are applied to with Apply with git am
+    pub repo: Option<PathBuf>,
+}
+
+impl Default for PublicInboxConfig {
+    fn default() -> Self {
+        Self {
+            lists: Vec::new(),
+            download_dir: std::env::temp_dir().join("scryforge-patches"),
+            repo: None,
+        }
+    }
+}
+
+impl PublicInboxConfig {
+    /// Configuration following `lists`.
+    pub fn new(lists: Vec<MailingList>) -> Self {
+        Self {
+            lists,
+            ..Default::default()
+        }
+    }
+
+    /// Save downloaded series to `dir`.
+    pub fn with_download_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.download_dir = dir.into();
+        self
+    }
+
+    /// Apply series to the git checkout at `repo`.
+    pub fn with_repo(mut self, repo: impl Into<PathBuf>) -> Self {
+        self.repo = Some(repo.into());
+        self
+    }
+
+    /// The list a feed name belongs to.
+    pub fn list(&self, name: &str) -> Option<&MailingList> {
+        self.lists.iter().find(|list| list.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urls() {
+        let list = MailingList::new("rust", "https://lore.kernel.org/rust-for-linux/");
+        assert_eq!(
+            list.thread_url("a/b@example.com"),
+            "https://lore.kernel.org/rust-for-linux/a%2Fb@example.com/T/#u"
+        );
+        assert_eq!(
+            list.mbox_url("a@example.com"),
+            "https://lore.kernel.org/rust-for-linux/a@example.com/t.mbox.gz"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_settings() {
+        let config: PublicInboxConfig = serde_json::from_value(serde_json::json!({
+            "lists": [
+                { "name": "rust", "url": "https://lore.kernel.org/rust-for-linux" },
+                { "name": "mine", "url": "https://lore.kernel.org/all", "query": "f:alice" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(config.lists.len(), 2);
+        assert_eq!(
+            config.list("mine").unwrap().query.as_deref(),
+            Some("f:alice")
+        );
+        assert!(config.download_dir.ends_with("scryforge-patches"));
+        assert_eq!(config.repo, None);
+    }
+}
@@ -0,0 +1,602 @@
+//! # provider-public-inbox
+//!
+//! Mailing list provider for Scryforge, reading public-inbox archives such
+//! as lore.kernel.org.
+//!
+//! Each list's Atom feed ([`atom`]) lists its newest messages, or the
+//! results of a search. Messages are grouped into threads ([`thread`]), and
+//! a thread carrying patches is read as a series from its subject tags
+//! ([`subject`]): `[PATCH v3 0/7]` and the seven patches and their reviews
+//! are one item. A series can be downloaded from the thread's mbox
+//! ([`mbox`]), ready for `git am`, or applied to a configured checkout.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! use provider_public_inbox::{MailingList, PublicInboxConfig, PublicInboxProvider};
+//!
+//! let config = PublicInboxConfig::new(vec![
+//!     MailingList::new("rust-for-linux", "https://lore.kernel.org/rust-for-linux"),
+//!     MailingList::new("my-patches", "https://lore.kernel.org/all")
+//!         .with_query("f:alice@example.com"),
+//! ]);
+//! let provider = PublicInboxProvider::new(config);
+//! ```
+
+pub mod atom;
+pub mod config;
+pub mod mbox;
+pub mod message;
+pub mod subject;
+pub mod thread;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use scryforge_provider_core::prelude::*;
+use tokio::process::Command;
+
+pub use config::{MailingList, PublicInboxConfig};
+pub use message::Message;
+pub use subject::Subject;
+pub use thread::{Series, Thread};
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "public-inbox";
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Length of the snippet taken from a thread's first message.
+const SNIPPET_CHARS: usize = 200;
+
+/// Mailing list provider for public-inbox archives.
+pub struct PublicInboxProvider {
+    config: PublicInboxConfig,
+    client: Client,
+}
+
+impl PublicInboxProvider {
+    pub fn new(config: PublicInboxConfig) -> Self {
+        let client = Client::builder()
+            .user_agent("scryforge/0.1.0")
+            .build()
+            .unwrap();
+
+        Self { config, client }
+    }
+
+    /// Send `request`, turning an error status into a [`StreamError`].
+    async fn send(&self, request: RequestBuilder, what: &str) -> Result<Response> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        if status == StatusCode::NOT_FOUND {
+            return Err(StreamError::StreamNotFound(format!("{} not found", what)));
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(StreamError::from_status(
+            status.as_u16(),
+            retry_after,
+            format!("{} error ({}): {}", what, status, body),
+        ))
+    }
+
+    /// The newest messages of `list`, or of its search.
+    async fn fetch_messages(&self, list: &MailingList) -> Result<Vec<Message>> {
+        let request = match &list.query {
+            Some(query) => self
+                .client
+                .get(format!("{}/", list.base_url()))
+                .query(&[("q", query.as_str()), ("x", "A")]),
+            None => self.client.get(format!("{}/new.atom", list.base_url())),
+        };
+        let body = self
+            .send(request, &format!("List {}", list.name))
+            .await?
+            .text()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        atom::parse(&body).map_err(|e| {
+            StreamError::Provider(format!("Failed to parse feed of {}: {}", list.name, e))
+        })
+    }
+
+    /// The threads of `list`, most recently active first.
+    async fn thread_items(&self, list: &MailingList) -> Result<Vec<Item>> {
+        let messages = self.fetch_messages(list).await?;
+        Ok(thread::threads(&messages)
+            .iter()
+            .map(|thread| thread_to_item(list, thread))
+            .collect())
+    }
+
+    /// Save the newest series in an item's thread as an mbox for `git am`:
+    /// where it was saved and how many patches it holds, or `None` when
+    /// the thread has no patches.
+    async fn save_series(&self, item: &Item) -> Result<Option<(PathBuf, usize)>> {
+        let (Some(url), Some(message_id)) = (
+            item.metadata.get("mbox_url"),
+            item.metadata.get("message_id"),
+        ) else {
+            return Ok(None);
+        };
+
+        let bytes = self
+            .send(self.client.get(url), "Thread")
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+        let text = mbox::decompress(&bytes)
+            .map_err(|e| StreamError::Provider(format!("Failed to read mbox: {}", e)))?;
+        let messages = mbox::parse(&text);
+        let patches = mbox::series_patches(&messages);
+        if patches.is_empty() {
+            return Ok(None);
+        }
+
+        let dir = &self.config.download_dir;
+        let path = dir.join(format!("{}.mbx", file_stem(message_id)));
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| StreamError::Internal(format!("{}: {}", dir.display(), e)))?;
+        tokio::fs::write(&path, mbox::to_mbox(&patches))
+            .await
+            .map_err(|e| StreamError::Internal(format!("{}: {}", path.display(), e)))?;
+        Ok(Some((path, patches.len())))
+    }
+
+    async fn download_series(&self, item: &Item) -> Result<ActionResult> {
+        let Some((path, count)) = self.save_series(item).await? else {
+            return Ok(no_patches());
+        };
+
+        let command = format!("git am {}", path.display());
+        Ok(ActionResult {
+            success: true,
+            message: Some(format!("Saved {} patches to {}", count, path.display())),
+            data: Some(serde_json::json!({
+                "path": path.display().to_string(),
+                "text": command,
+            })),
+        })
+    }
+
+    /// Download the newest series in an item's thread and apply it to the
+    /// configured checkout with `git am --3way`. A series that doesn't
+    /// apply is backed out with `git am --abort`, leaving the checkout as
+    /// it was.
+    async fn apply_series(&self, item: &Item) -> Result<ActionResult> {
+        let Some(repo) = &self.config.repo else {
+            return Ok(ActionResult {
+                success: false,
+                message: Some("No repository configured to apply patches to".to_string()),
+                data: None,
+            });
+        };
+        let Some((path, count)) = self.save_series(item).await? else {
+            return Ok(no_patches());
+        };
+
+        let git = |args: &[&str]| {
+            let mut command = Command::new("git");
+            command.arg("-C").arg(repo).args(args);
+            command
+        };
+        let output = git(&["am", "--3way"])
+            .arg(&path)
+            .output()
+            .await
+            .map_err(|e| StreamError::Internal(format!("Failed to run git: {}", e)))?;
+        let data = Some(serde_json::json!({
+            "path": path.display().to_string(),
+            "repo": repo.display().to_string(),
+        }));
+        if output.status.success() {
+            return Ok(ActionResult {
+                success: true,
+                message: Some(format!("Applied {} patches to {}", count, repo.display())),
+                data,
+            });
+        }
+
+        let _ = git(&["am", "--abort"]).output().await;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let reason = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        Ok(ActionResult {
+            success: false,
+            message: Some(format!("git am failed: {}", reason)),
+            data,
+        })
+    }
+}
+
+fn no_patches() -> ActionResult {
+    ActionResult {
+        success: false,
+        message: Some("Thread has no patches".to_string()),
+        data: None,
+    }
+}
+
+/// A file name for a `Message-ID`.
+fn file_stem(message_id: &str) -> String {
+    message_id
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' | '@' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Convert a thread of `list` into an [`Item`].
+fn thread_to_item(list: &MailingList, thread: &Thread<'_>) -> Item {
+    let root = thread.root();
+    let series = thread.series();
+
+    let outline: Vec<String> = thread
+        .messages
+        .iter()
+        .map(|message| {
+            let date = message
+                .date
+                .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            format!("{:<16}  {}: {}", date, message.sender(), message.subject)
+        })
+        .collect();
+    let outline = format!(
+        "── Thread ({} messages) ──\n{}",
+        thread.messages.len(),
+        outline.join("\n")
+    );
+
+    // A series reads as its patches' diffs, one after another, under the
+    // cover letter or the lone patch's commit message
+    let diffs: Vec<String> = thread
+        .patches()
+        .iter()
+        .filter_map(|patch| Some(format!("{}\n{}", patch.subject, patch.split_patch().1?)))
+        .collect();
+
+    let mut snippet: String = root.body.trim().chars().take(SNIPPET_CHARS).collect();
+    if snippet.len() < root.body.trim().len() {
+        snippet.push('…');
+    }
+
+    let participants = thread.participants();
+    let mut metadata = HashMap::new();
+    metadata.insert("list".to_string(), list.name.clone());
+    metadata.insert("message_id".to_string(), thread.id.clone());
+    metadata.insert("messages".to_string(), thread.messages.len().to_string());
+    metadata.insert("replies".to_string(), thread.replies().to_string());
+    metadata.insert("participants".to_string(), participants.join(", "));
+    metadata.insert("mbox_url".to_string(), list.mbox_url(&thread.id));
+
+    let mut tags = Vec::new();
+    if let Some(series) = &series {
+        metadata.insert("series_version".to_string(), series.version.to_string());
+        metadata.insert("series_total".to_string(), series.total.to_string());
+        metadata.insert("series_received".to_string(), series.received.to_string());
+        tags.push("patch".to_string());
+        if series.is_rfc {
+            tags.push("rfc".to_string());
+        }
+    }
+
+    Item {
+        id: ItemId::new(PROVIDER_ID, &format!("{}:{}", list.name, thread.id)),
+        stream_id: StreamId::new(PROVIDER_ID, "feed", &list.name),
+        title: root.subject.clone(),
+        content: if diffs.is_empty() {
+            ItemContent::Email {
+                subject: root.subject.clone(),
+                body_text: Some(format!("{}\n\n{}", root.body.trim_end(), outline)),
+                body_html: None,
+                snippet,
+            }
+        } else {
+            let (message, _) = root.split_patch();
+            ItemContent::patch(
+                Some(format!("{}\n\n{}", message, outline)),
+                diffs.join("\n"),
+            )
+        },
+        author: root.author.clone(),
+        published: root.date,
+        updated: thread.last_activity(),
+        url: Some(list.thread_url(&thread.id)),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags,
+        metadata,
+    }
+}
+
+#[async_trait]
+impl Provider for PublicInboxProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Mailing Lists"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        Ok(ProviderHealth {
+            is_healthy: true,
+            message: Some(format!("Following {} lists", self.config.lists.len())),
+            last_sync: None,
+            error_count: 0,
+        })
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+
+        // One unreachable archive shouldn't hide the others
+        let mut items_added = 0;
+        let mut errors = Vec::new();
+        for list in &self.config.lists {
+            match self.thread_items(list).await {
+                Ok(items) => items_added += items.len() as u32,
+                Err(e) => errors.push(format!("{}: {}", list.name, e)),
+            }
+        }
+
+        Ok(SyncResult {
+            success: errors.is_empty(),
+            items_added,
+            items_updated: 0,
+            items_removed: 0,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = vec![
+            Action {
+                id: "open_browser".to_string(),
+                name: "Open in Browser".to_string(),
+                description: "Open the thread in the archive".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the thread's link to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+        ];
+        if item.metadata.contains_key("series_total") {
+            actions.push(Action {
+                id: "download_series".to_string(),
+                name: "Download Series".to_string(),
+                description: "Save the newest version of the series for git am".to_string(),
+                kind: ActionKind::Custom("download_series".to_string()),
+                keyboard_shortcut: Some("a".to_string()),
+            });
+            if self.config.repo.is_some() {
+                actions.push(Action {
+                    id: "git_am".to_string(),
+                    name: "Apply with git am".to_string(),
+                    description:
+                        "Apply the newest version of the series to the configured repository"
+                            .to_string(),
+                    kind: ActionKind::Custom("git_am".to_string()),
+                    keyboard_shortcut: Some("A".to_string()),
+                });
+            }
+        }
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match (&action.kind, &item.url) {
+            (ActionKind::OpenInBrowser, Some(url)) => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Opening: {}", url)),
+                data: Some(serde_json::json!({ "url": url })),
+            }),
+            (ActionKind::CopyLink, Some(url)) => Ok(ActionResult {
+                success: true,
+                message: Some(format!("Copied: {}", url)),
+                data: Some(serde_json::json!({ "url": url })),
+            }),
+            (ActionKind::Custom(name), _) if name == "download_series" => {
+                self.download_series(item).await
+            }
+            (ActionKind::Custom(name), _) if name == "git_am" => self.apply_series(item).await,
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for PublicInboxProvider {
+    /// One feed per list.
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        Ok(self
+            .config
+            .lists
+            .iter()
+            .map(|list| Feed {
+                id: FeedId(list.name.clone()),
+                name: list.name.clone(),
+                description: Some(match &list.query {
+                    Some(query) => format!("{} matching {}", list.base_url(), query),
+                    None => list.base_url().to_string(),
+                }),
+                icon: Some("📨".to_string()),
+                unread_count: None,
+                total_count: None,
+            })
+            .collect())
+    }
+
+    /// Threads of a list, most recently active first. With `since`, only
+    /// threads with a message since then.
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let list = self
+            .config
+            .list(&feed_id.0)
+            .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+        let items = self.thread_items(list).await?;
+
+        Ok(items
+            .into_iter()
+            .filter(|item| {
+                options
+                    .since
+                    .is_none_or(|since| item.updated.is_some_and(|updated| updated >= since))
+            })
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(
+            file_stem("20240501.1-foo/bar+baz@example.com"),
+            "20240501.1-foo_bar_baz@example.com"
+        );
+    }
+
+    #[test]
+    fn test_thread_to_item() {
+        let message = |id: &str, parent: Option<&str>, subject: &str, minute: u32| Message {
+            message_id: id.to_string(),
+            subject: subject.to_string(),
+            author: None,
+            date: Some(format!("2024-05-01T12:{:02}:00Z", minute).parse().unwrap()),
+            in_reply_to: parent.map(str::to_string),
+            references: Vec::new(),
+            body: format!("body of {}", id),
+        };
+        let messages = vec![
+            message("cover", None, "[RFC PATCH 0/2] foo", 0),
+            message("p1", Some("cover"), "[RFC PATCH 1/2] a", 1),
+        ];
+        let threads = thread::threads(&messages);
+        let list = MailingList::new("rust", "https://lore.kernel.org/rust-for-linux");
+        let item = thread_to_item(&list, &threads[0]);
+
+        assert_eq!(item.id.as_str(), "public-inbox:rust:cover");
+        assert_eq!(item.title, "[RFC PATCH 0/2] foo");
+        assert_eq!(item.tags, vec!["patch", "rfc"]);
+        assert_eq!(item.metadata["series_received"], "1");
+        assert_eq!(item.metadata["series_total"], "2");
+        assert_eq!(
+            item.url.as_deref(),
+            Some("https://lore.kernel.org/rust-for-linux/cover/T/#u")
+        );
+        assert!(matches!(
+            &item.content,
+            ItemContent::Email { body_text: Some(body), snippet, .. }
+                if body.starts_with("body of cover\n\n── Thread (2 messages) ──\n")
+                    && body.ends_with("(unknown): [RFC PATCH 1/2] a")
+                    && snippet == "body of cover"
+        ));
+    }
+
+    #[test]
+    fn test_series_with_diffs_is_a_patch() {
+        let patch = |id: &str, subject: &str, file: &str| Message {
+            message_id: id.to_string(),
+            subject: subject.to_string(),
+            author: None,
+            date: None,
+            in_reply_to: Some("cover".to_string()).filter(|_| id != "cover"),
+            references: Vec::new(),
+            body: format!(
+                "Change {file}.\n---\n\ndiff --git a/{file} b/{file}\n--- a/{file}\n\
+                 +++ b/{file}\n@@ -1 +1,2 @@\n-old\n+new\n+more\n-- \n2.43.0\n"
+            ),
+        };
+        let mut cover = patch("cover", "[PATCH 0/2] foo", "");
+        cover.body = "Two changes.\n".to_string();
+        let messages = vec![
+            cover,
+            patch("p2", "[PATCH 2/2] b", "b.rs"),
+            patch("p1", "[PATCH 1/2] a", "a.rs"),
+        ];
+        let threads = thread::threads(&messages);
+        let list = MailingList::new("rust", "https://lore.kernel.org/rust-for-linux");
+        let item = thread_to_item(&list, &threads[0]);
+
+        let ItemContent::Patch {
+            description: Some(description),
+            diff,
+            files_changed,
+            additions,
+            deletions,
+        } = &item.content
+        else {
+            panic!("expected a patch, got {:?}", item.content);
+        };
+        assert!(description.starts_with("Two changes.\n\n── Thread (3 messages) ──"));
+        assert!(diff.starts_with("[PATCH 1/2] a\ndiff --git a/a.rs b/a.rs\n"));
+        assert!(!diff.contains("2.43.0"));
+        assert_eq!(files_changed, &vec!["a.rs", "b.rs"]);
+        assert_eq!((*additions, *deletions), (4, 2));
+    }
+}
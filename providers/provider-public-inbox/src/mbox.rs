@@ -0,0 +1,223 @@
+//! Reading the mbox downloads of a public-inbox archive.
+//!
+//! `{list}/{Message-ID}/t.mbox.gz` is a whole thread as a gzipped mboxrd
+//! file: messages start with a `From ` line, and body lines that begin with
+//! `From ` (after any number of `>`) have one more `>` in front.
+
+use std::io::Read;
+
+use chrono::DateTime;
+use flate2::read::GzDecoder;
+use mailparse::{MailHeaderMap, ParsedMail};
+use scryforge_provider_core::Author;
+
+use crate::message::{bare_id, header_ids, Message};
+use crate::subject::Subject;
+
+/// One message of an mbox, as sent.
+#[derive(Debug, Clone)]
+pub struct RawMessage {
+    /// The message without its `From ` line, unescaped
+    pub raw: String,
+    pub message: Message,
+}
+
+/// Decompress a download if it's gzipped; mbox files served plain are
+/// returned as they are.
+pub fn decompress(bytes: &[u8]) -> std::io::Result<String> {
+    if !bytes.starts_with(&[0x1f, 0x8b]) {
+        return Ok(String::from_utf8_lossy(bytes).into_owned());
+    }
+    let mut decoded = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut decoded)?;
+    Ok(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Split an mboxrd file into messages, skipping ones that don't parse.
+pub fn parse(mbox: &str) -> Vec<RawMessage> {
+    split(mbox)
+        .into_iter()
+        .filter_map(|raw| {
+            let message = parse_message(&raw)?;
+            Some(RawMessage { raw, message })
+        })
+        .collect()
+}
+
+/// The patches of the newest series version in a thread, in order, for
+/// `git am`. Cover letters and replies are left out.
+pub fn series_patches(messages: &[RawMessage]) -> Vec<&RawMessage> {
+    let patches: Vec<(Subject, &RawMessage)> = messages
+        .iter()
+        .map(|message| (message.message.parsed_subject(), message))
+        .filter(|(subject, _)| subject.is_patch() && !subject.is_cover_letter())
+        .collect();
+    let Some(version) = patches.iter().map(|(subject, _)| subject.version).max() else {
+        return Vec::new();
+    };
+
+    let mut latest: Vec<(u32, &RawMessage)> = patches
+        .into_iter()
+        .filter(|(subject, _)| subject.version == version)
+        .map(|(subject, message)| (subject.index.map_or(1, |(index, _)| index), message))
+        .collect();
+    // A patch sent twice counts once
+    latest.sort_by_key(|(index, _)| *index);
+    latest.dedup_by_key(|(index, _)| *index);
+    latest.into_iter().map(|(_, message)| message).collect()
+}
+
+/// Write messages back out as an mboxrd file.
+pub fn to_mbox(messages: &[&RawMessage]) -> String {
+    let mut mbox = String::new();
+    for message in messages {
+        mbox.push_str("From mboxrd@z Thu Jan  1 00:00:00 1970\n");
+        for line in message.raw.split_inclusive('\n') {
+            if line.trim_start_matches('>').starts_with("From ") {
+                mbox.push('>');
+            }
+            mbox.push_str(line);
+        }
+        if !mbox.ends_with('\n') {
+            mbox.push('\n');
+        }
+        mbox.push('\n');
+    }
+    mbox
+}
+
+fn split(mbox: &str) -> Vec<String> {
+    // The blank line before a `From ` line separates messages
+    fn finish(mut message: String) -> String {
+        if message.ends_with("\n\n") {
+            message.pop();
+        }
+        message
+    }
+
+    let mut messages = Vec::new();
+    let mut current: Option<String> = None;
+    for line in mbox.split_inclusive('\n') {
+        if line.starts_with("From ") {
+            messages.extend(current.replace(String::new()).map(finish));
+            continue;
+        }
+        let Some(message) = &mut current else {
+            continue;
+        };
+        let quotes = line.len() - line.trim_start_matches('>').len();
+        if quotes > 0 && line[quotes..].starts_with("From ") {
+            message.push_str(&line[1..]);
+        } else {
+            message.push_str(line);
+        }
+    }
+    messages.extend(current.map(finish));
+    messages
+}
+
+fn parse_message(raw: &str) -> Option<Message> {
+    let parsed = mailparse::parse_mail(raw.as_bytes()).ok()?;
+    let header = |name: &str| parsed.headers.get_first_value(name);
+
+    let author = header("From")
+        .and_then(|from| mailparse::addrparse(&from).ok())
+        .and_then(|addresses| match addresses.first()? {
+            mailparse::MailAddr::Single(single) => Some(Author {
+                name: single.display_name.clone().unwrap_or_default(),
+                email: Some(single.addr.clone()),
+                url: None,
+                avatar_url: None,
+            }),
+            mailparse::MailAddr::Group(_) => None,
+        });
+
+    Some(Message {
+        message_id: bare_id(&header("Message-ID")?),
+        subject: header("Subject").unwrap_or_default(),
+        author,
+        date: header("Date")
+            .and_then(|date| mailparse::dateparse(&date).ok())
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+        in_reply_to: header("In-Reply-To").and_then(|ids| header_ids(&ids).into_iter().next()),
+        references: header("References")
+            .map(|ids| header_ids(&ids))
+            .unwrap_or_default(),
+        body: text_body(&parsed).unwrap_or_default(),
+    })
+}
+
+/// The first `text/plain` part.
+fn text_body(mail: &ParsedMail<'_>) -> Option<String> {
+    if !mail.subparts.is_empty() {
+        return mail.subparts.iter().find_map(text_body);
+    }
+    if !mail.ctype.mimetype.eq_ignore_ascii_case("text/plain") {
+        return None;
+    }
+    mail.get_body().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBOX: &str = "From mboxrd@z Thu Jan  1 00:00:00 1970\n\
+Message-ID: <cover@example.com>\n\
+From: Alice <alice@example.com>\n\
+Subject: [PATCH 0/1] add foo\n\
+Date: Wed, 01 May 2024 12:00:00 +0000\n\
+\n\
+Cover letter.\n\
+>From the archives, unescaped.\n\
+\n\
+From mboxrd@z Thu Jan  1 00:00:00 1970\n\
+Message-ID: <patch@example.com>\n\
+From: alice@example.com\n\
+Subject: [PATCH 1/1] add foo\n\
+In-Reply-To: <cover@example.com>\n\
+References: <cover@example.com>\n\
+Date: Wed, 01 May 2024 12:00:01 +0000\n\
+\n\
+---\n\
+foo.rs | 1 +\n";
+
+    #[test]
+    fn test_parse_mboxrd() {
+        let messages = parse(MBOX);
+        assert_eq!(messages.len(), 2);
+
+        let cover = &messages[0];
+        assert_eq!(cover.message.message_id, "cover@example.com");
+        assert_eq!(cover.message.sender(), "Alice");
+        assert!(cover.raw.contains("\nFrom the archives, unescaped.\n"));
+        assert_eq!(
+            cover.message.body,
+            "Cover letter.\nFrom the archives, unescaped.\n"
+        );
+
+        let patch = &messages[1];
+        assert_eq!(patch.message.parent(), Some("cover@example.com"));
+        assert_eq!(patch.message.sender(), "alice@example.com");
+        assert_eq!(
+            patch.message.date.unwrap().to_rfc3339(),
+            "2024-05-01T12:00:01+00:00"
+        );
+    }
+
+    #[test]
+    fn test_series_patches_round_trip() {
+        let messages = parse(MBOX);
+        let patches = series_patches(&messages);
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].message.message_id, "patch@example.com");
+
+        let reparsed = parse(&to_mbox(&[&messages[0]]));
+        assert_eq!(reparsed[0].raw, messages[0].raw);
+    }
+
+    #[test]
+    fn test_decompress_plain_text() {
+        assert_eq!(decompress(b"From x\n").unwrap(), "From x\n");
+    }
+}
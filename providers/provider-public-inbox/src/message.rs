@@ -0,0 +1,187 @@
+//! Messages of an archive, however they were read.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::diff::{self, DiffLine};
+use scryforge_provider_core::Author;
+
+use crate::subject::Subject;
+
+/// One message of a mailing list.
+#[derive(Debug, Clone)]
+pub struct Message {
+    /// `Message-ID`, without the angle brackets
+    pub message_id: String,
+    pub subject: String,
+    pub author: Option<Author>,
+    pub date: Option<DateTime<Utc>>,
+    /// The message this one replies to
+    pub in_reply_to: Option<String>,
+    /// `References`, oldest first; empty when read from Atom
+    pub references: Vec<String>,
+    /// Plain-text body
+    pub body: String,
+}
+
+impl Message {
+    pub fn parsed_subject(&self) -> Subject {
+        Subject::parse(&self.subject)
+    }
+
+    /// The name to show for the sender.
+    pub fn sender(&self) -> &str {
+        match &self.author {
+            Some(author) if !author.name.is_empty() => &author.name,
+            Some(Author {
+                email: Some(email), ..
+            }) => email,
+            _ => "(unknown)",
+        }
+    }
+
+    /// The parent this message replies to: `In-Reply-To`, or else the last
+    /// of `References`.
+    pub fn parent(&self) -> Option<&str> {
+        self.in_reply_to
+            .as_deref()
+            .or_else(|| self.references.last().map(String::as_str))
+    }
+
+    /// A patch's body split into its commit message, which ends at the
+    /// `---` before the diffstat, and its diff, which runs from the first
+    /// file header to the signature. The diff is `None` when there is none.
+    pub fn split_patch(&self) -> (String, Option<String>) {
+        let lines: Vec<(DiffLine, &str)> = diff::lines(&self.body).collect();
+        let Some(start) = lines
+            .iter()
+            .position(|(kind, _)| *kind == DiffLine::FileHeader)
+        else {
+            return (self.body.trim_end().to_string(), None);
+        };
+        let end = lines[start..]
+            .iter()
+            .position(|(kind, line)| *kind == DiffLine::Other && line.trim_end() == "--")
+            .map_or(lines.len(), |end| start + end);
+        let message_end = lines[..start]
+            .iter()
+            .position(|(_, line)| *line == "---")
+            .unwrap_or(start);
+
+        let join = |lines: &[(DiffLine, &str)]| {
+            lines
+                .iter()
+                .map(|(_, line)| *line)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let diff = format!("{}\n", join(&lines[start..end]).trim_end());
+        (
+            join(&lines[..message_end]).trim_end().to_string(),
+            Some(diff),
+        )
+    }
+}
+
+/// Strip the angle brackets and whitespace around a `Message-ID`.
+pub fn bare_id(id: &str) -> String {
+    id.trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .to_string()
+}
+
+/// Every `<id>` in a `References` or `In-Reply-To` header.
+pub fn header_ids(header: &str) -> Vec<String> {
+    header
+        .split('<')
+        .skip(1)
+        .filter_map(|part| part.split_once('>'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Escape a `Message-ID` for a public-inbox URL path, the way public-inbox
+/// does: `/` and anything outside the URL-safe set is percent-encoded.
+pub fn escape_id(id: &str) -> String {
+    let mut escaped = String::with_capacity(id.len());
+    for byte in id.bytes() {
+        let safe = byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte);
+        if safe {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+/// Undo [`escape_id`].
+pub fn unescape_id(escaped: &str) -> String {
+    let bytes = escaped.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_ids() {
+        assert_eq!(
+            header_ids("<a@example.com>\r\n <b/c@example.com> junk"),
+            vec!["a@example.com", "b/c@example.com"]
+        );
+        assert_eq!(bare_id(" <a@example.com> "), "a@example.com");
+    }
+
+    #[test]
+    fn test_split_patch() {
+        let mut message = Message {
+            message_id: "p1@x".to_string(),
+            subject: "[PATCH 1/1] a".to_string(),
+            author: None,
+            date: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            body: "Fix a.\n\nSigned-off-by: A <a@x>\n---\n a.rs | 2 +-\n\n\
+                   diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n\
+                   @@ -1 +1 @@\n-old\n+new\n-- \n2.43.0\n"
+                .to_string(),
+        };
+        let (description, diff) = message.split_patch();
+        assert_eq!(description, "Fix a.\n\nSigned-off-by: A <a@x>");
+        assert_eq!(
+            diff.as_deref(),
+            Some("diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-old\n+new\n")
+        );
+
+        message.body = "Looks good.\n".to_string();
+        assert_eq!(message.split_patch(), ("Looks good.".to_string(), None));
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let id = "20240501.12345-1-foo/bar@example.com";
+        assert_eq!(escape_id(id), "20240501.12345-1-foo%2Fbar@example.com");
+        assert_eq!(unescape_id(&escape_id(id)), id);
+        assert_eq!(unescape_id("100%"), "100%");
+    }
+}
@@ -0,0 +1,136 @@
+//! Reading patch tags out of mailing-list subjects.
+//!
+//! `git format-patch` and `git send-email` mark patches with a bracketed
+//! prefix: `[PATCH v3 2/7] rust: add foo`, `[RFC PATCH net-next 0/4] ...`.
+//! The tags say which series a message belongs to and where in it.
+
+/// A parsed subject line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subject {
+    /// Bracketed words other than the version and index, such as `PATCH`,
+    /// `RFC`, or a tree name like `net-next`
+    pub tags: Vec<String>,
+    /// Whether the subject starts with `Re:`
+    pub is_reply: bool,
+    /// The series version, 1 without a `vN` tag
+    pub version: u32,
+    /// Position and length of the series, from `2/7`
+    pub index: Option<(u32, u32)>,
+    /// The subject without `Re:` and the bracketed prefix
+    pub topic: String,
+}
+
+impl Subject {
+    pub fn parse(subject: &str) -> Self {
+        let mut parsed = Subject {
+            tags: Vec::new(),
+            is_reply: false,
+            version: 1,
+            index: None,
+            topic: String::new(),
+        };
+
+        let mut rest = subject.trim();
+        loop {
+            if let Some(after) = strip_reply_prefix(rest) {
+                parsed.is_reply = true;
+                rest = after.trim_start();
+            } else if let Some((inside, after)) = rest
+                .strip_prefix('[')
+                .and_then(|inner| inner.split_once(']'))
+            {
+                for word in inside.split_whitespace() {
+                    parsed.add_word(word);
+                }
+                rest = after.trim_start();
+            } else {
+                break;
+            }
+        }
+        parsed.topic = rest.to_string();
+        parsed
+    }
+
+    fn add_word(&mut self, word: &str) {
+        let version = word
+            .strip_prefix(['v', 'V'])
+            .and_then(|version| version.parse().ok());
+        let index = word
+            .split_once('/')
+            .and_then(|(index, total)| Some((index.parse().ok()?, total.parse().ok()?)));
+        match (version, index) {
+            (Some(version), _) => self.version = version,
+            (None, Some(index)) => self.index = Some(index),
+            (None, None) => self.tags.push(word.to_string()),
+        }
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether this is a patch or a cover letter, rather than a reply or a
+    /// plain discussion.
+    pub fn is_patch(&self) -> bool {
+        !self.is_reply && (self.has_tag("PATCH") || self.index.is_some())
+    }
+
+    /// Whether this is a series' cover letter (`0/N`).
+    pub fn is_cover_letter(&self) -> bool {
+        self.is_patch() && self.index.is_some_and(|(index, _)| index == 0)
+    }
+
+    pub fn is_rfc(&self) -> bool {
+        self.has_tag("RFC")
+    }
+}
+
+/// The subject after a reply marker such as `Re:` or `AW:`.
+fn strip_reply_prefix(subject: &str) -> Option<&str> {
+    let (marker, rest) = subject.split_once(':')?;
+    ["re", "aw", "sv"]
+        .iter()
+        .any(|prefix| marker.eq_ignore_ascii_case(prefix))
+        .then_some(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_patch_subjects() {
+        let patch = Subject::parse("[PATCH v3 2/7] rust: add foo");
+        assert!(patch.is_patch());
+        assert!(!patch.is_cover_letter());
+        assert_eq!(patch.version, 3);
+        assert_eq!(patch.index, Some((2, 7)));
+        assert_eq!(patch.topic, "rust: add foo");
+
+        let cover = Subject::parse("[RFC PATCH net-next 0/4] tcp: faster");
+        assert!(cover.is_cover_letter());
+        assert!(cover.is_rfc());
+        assert_eq!(cover.version, 1);
+        assert_eq!(cover.tags, vec!["RFC", "PATCH", "net-next"]);
+
+        let single = Subject::parse("[PATCH] [doc] fix typo");
+        assert!(single.is_patch());
+        assert_eq!(single.index, None);
+        assert_eq!(single.tags, vec!["PATCH", "doc"]);
+        assert_eq!(single.topic, "fix typo");
+    }
+
+    #[test]
+    fn test_parse_replies() {
+        let reply = Subject::parse("Re: RE: [PATCH v2 1/2] mm: fix leak");
+        assert!(reply.is_reply);
+        assert!(!reply.is_patch());
+        assert_eq!(reply.version, 2);
+        assert_eq!(reply.topic, "mm: fix leak");
+
+        let plain = Subject::parse("Question about the build: ccache");
+        assert!(!plain.is_reply);
+        assert!(!plain.is_patch());
+        assert_eq!(plain.topic, "Question about the build: ccache");
+    }
+}
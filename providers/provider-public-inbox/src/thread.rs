@@ -0,0 +1,253 @@
+//! Grouping messages into threads and patch series.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::message::Message;
+use crate::subject::Subject;
+
+/// A patch series, as far as the thread holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Series {
+    /// The newest version posted in the thread
+    pub version: u32,
+    /// Patches in the series, from `n/N`
+    pub total: u32,
+    /// Patches of that version that are in the thread
+    pub received: u32,
+    pub has_cover_letter: bool,
+    pub is_rfc: bool,
+}
+
+/// Messages that reply, directly or not, to the same message.
+#[derive(Debug, Clone)]
+pub struct Thread<'a> {
+    /// The `Message-ID` the thread hangs off: its first message, or the
+    /// message its oldest known ones reply to when that isn't at hand
+    pub id: String,
+    /// Oldest first
+    pub messages: Vec<&'a Message>,
+}
+
+impl<'a> Thread<'a> {
+    /// The first message: the thread's root when it's at hand.
+    pub fn root(&self) -> &'a Message {
+        self.messages
+            .iter()
+            .find(|message| message.message_id == self.id)
+            .copied()
+            .unwrap_or(self.messages[0])
+    }
+
+    pub fn last_activity(&self) -> Option<DateTime<Utc>> {
+        self.messages
+            .iter()
+            .filter_map(|message| message.date)
+            .max()
+    }
+
+    /// The patch series the thread carries, if any. A series reposted in
+    /// reply to an older version counts as its newest version.
+    pub fn series(&self) -> Option<Series> {
+        let patches: Vec<Subject> = self
+            .messages
+            .iter()
+            .map(|message| message.parsed_subject())
+            .filter(Subject::is_patch)
+            .collect();
+        let version = patches.iter().map(|patch| patch.version).max()?;
+        let latest: Vec<&Subject> = patches
+            .iter()
+            .filter(|patch| patch.version == version)
+            .collect();
+
+        let indices: HashSet<u32> = latest
+            .iter()
+            .map(|patch| patch.index.map_or(1, |(index, _)| index))
+            .filter(|index| *index > 0)
+            .collect();
+        Some(Series {
+            version,
+            total: latest
+                .iter()
+                .filter_map(|patch| patch.index.map(|(_, total)| total))
+                .max()
+                .unwrap_or(1),
+            received: indices.len() as u32,
+            has_cover_letter: latest.iter().any(|patch| patch.is_cover_letter()),
+            is_rfc: latest.iter().any(|patch| patch.is_rfc()),
+        })
+    }
+
+    /// The patches of the series' newest version in order, without the
+    /// cover letter. A patch sent twice counts once.
+    pub fn patches(&self) -> Vec<&'a Message> {
+        let patches: Vec<(Subject, &'a Message)> = self
+            .messages
+            .iter()
+            .map(|message| (message.parsed_subject(), *message))
+            .filter(|(subject, _)| subject.is_patch() && !subject.is_cover_letter())
+            .collect();
+        let Some(version) = patches.iter().map(|(subject, _)| subject.version).max() else {
+            return Vec::new();
+        };
+
+        let mut latest: Vec<(u32, &'a Message)> = patches
+            .into_iter()
+            .filter(|(subject, _)| subject.version == version)
+            .map(|(subject, message)| (subject.index.map_or(1, |(index, _)| index), message))
+            .collect();
+        latest.sort_by_key(|(index, _)| *index);
+        latest.dedup_by_key(|(index, _)| *index);
+        latest.into_iter().map(|(_, message)| message).collect()
+    }
+
+    /// Messages other than the root and the patches.
+    pub fn replies(&self) -> usize {
+        let root = self.root();
+        self.messages
+            .iter()
+            .filter(|message| !std::ptr::eq(**message, root))
+            .filter(|message| !message.parsed_subject().is_patch())
+            .count()
+    }
+
+    /// Everyone who posted, in order of their first message.
+    pub fn participants(&self) -> Vec<&'a str> {
+        let mut participants = Vec::new();
+        for message in &self.messages {
+            let sender = message.sender();
+            if !participants.contains(&sender) {
+                participants.push(sender);
+            }
+        }
+        participants
+    }
+}
+
+/// Group messages into threads, most recently active first.
+///
+/// `References` names a thread's root directly. Messages read from Atom
+/// only know their parent, so the chain is followed up through the
+/// messages at hand.
+pub fn threads(messages: &[Message]) -> Vec<Thread<'_>> {
+    let by_id: HashMap<&str, &Message> = messages
+        .iter()
+        .map(|message| (message.message_id.as_str(), message))
+        .collect();
+
+    let root_of = |message: &Message| -> String {
+        let mut current = message;
+        let mut seen = HashSet::new();
+        loop {
+            if let Some(root) = current.references.first() {
+                return root.clone();
+            }
+            let Some(parent) = current.parent() else {
+                return current.message_id.clone();
+            };
+            match by_id.get(parent) {
+                Some(next) if seen.insert(parent) => current = *next,
+                _ => return parent.to_string(),
+            }
+        }
+    };
+
+    let mut order = Vec::new();
+    let mut grouped: HashMap<String, Vec<&Message>> = HashMap::new();
+    for message in messages {
+        let root = root_of(message);
+        if !grouped.contains_key(&root) {
+            order.push(root.clone());
+        }
+        grouped.entry(root).or_default().push(message);
+    }
+
+    let mut threads: Vec<Thread<'_>> = order
+        .into_iter()
+        .map(|id| {
+            let mut messages = grouped.remove(&id).unwrap_or_default();
+            messages.sort_by_key(|message| message.date);
+            Thread { id, messages }
+        })
+        .collect();
+    threads.sort_by_key(|thread| std::cmp::Reverse(thread.last_activity()));
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::Author;
+
+    fn message(id: &str, parent: Option<&str>, subject: &str, minute: u32, from: &str) -> Message {
+        Message {
+            message_id: id.to_string(),
+            subject: subject.to_string(),
+            author: Some(Author {
+                name: from.to_string(),
+                email: None,
+                url: None,
+                avatar_url: None,
+            }),
+            date: Some(format!("2024-05-01T12:{:02}:00Z", minute).parse().unwrap()),
+            in_reply_to: parent.map(str::to_string),
+            references: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_series_threading() {
+        let messages = vec![
+            message("review", Some("p2"), "Re: [PATCH v2 2/2] b", 30, "Bob"),
+            message("cover", None, "[PATCH v2 0/2] foo", 0, "Alice"),
+            message("p1", Some("cover"), "[PATCH v2 1/2] a", 1, "Alice"),
+            message("p2", Some("cover"), "[PATCH v2 2/2] b", 2, "Alice"),
+            message("other", Some("unseen"), "Re: build question", 10, "Carol"),
+        ];
+        let threads = threads(&messages);
+
+        assert_eq!(threads.len(), 2);
+        let series = &threads[0];
+        assert_eq!(series.id, "cover");
+        assert_eq!(series.root().subject, "[PATCH v2 0/2] foo");
+        assert_eq!(series.messages.len(), 4);
+        assert_eq!(series.replies(), 1);
+        let patches: Vec<&str> = series
+            .patches()
+            .iter()
+            .map(|patch| patch.message_id.as_str())
+            .collect();
+        assert_eq!(patches, vec!["p1", "p2"]);
+        assert_eq!(series.participants(), vec!["Alice", "Bob"]);
+        assert_eq!(
+            series.series(),
+            Some(Series {
+                version: 2,
+                total: 2,
+                received: 2,
+                has_cover_letter: true,
+                is_rfc: false,
+            })
+        );
+
+        // The root isn't in the feed: the thread hangs off its id
+        let discussion = &threads[1];
+        assert_eq!(discussion.id, "unseen");
+        assert_eq!(discussion.root().message_id, "other");
+        assert_eq!(discussion.series(), None);
+    }
+
+    #[test]
+    fn test_references_name_the_root() {
+        let mut reply = message("r", Some("mid"), "Re: x", 5, "Bob");
+        reply.references = vec!["root".to_string(), "mid".to_string()];
+        let messages = vec![message("root", None, "x", 0, "Alice"), reply];
+
+        let threads = threads(&messages);
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id, "root");
+    }
+}
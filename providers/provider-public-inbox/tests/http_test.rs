@@ -0,0 +1,393 @@
+//! Mock-server tests for `provider-public-inbox`'s HTTP paths.
+
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use provider_public_inbox::{MailingList, PublicInboxConfig, PublicInboxProvider};
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{method, path, query_param};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::MockApi;
+
+fn entry(
+    api: &MockApi,
+    id: &str,
+    parent: Option<&str>,
+    from: &str,
+    title: &str,
+    time: &str,
+) -> String {
+    let in_reply_to = parent
+        .map(|parent| {
+            format!(
+                r#"<thr:in-reply-to ref="urn:uuid:{parent}" href="{}/list/{parent}/"/>"#,
+                api.uri()
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        r#"<entry><author><name>{from}</name><email>{from}@example.com</email></author>
+<title>{title}</title><updated>{time}</updated>
+<link href="{}/list/{id}/"/><id>urn:uuid:{id}</id>{in_reply_to}
+<content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><pre>Body of {id}.
+</pre></div></content></entry>
+"#,
+        api.uri()
+    )
+}
+
+fn feed(entries: &[String]) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="us-ascii"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:thr="http://purl.org/syndication/thread/1.0">
+<title>list archive</title>
+{}</feed>"#,
+        entries.concat()
+    )
+}
+
+/// A series with a cover letter and a review, and an unrelated question.
+fn list_feed(api: &MockApi) -> String {
+    feed(&[
+        entry(
+            api,
+            "review@x",
+            Some("p2@x"),
+            "bob",
+            "Re: [PATCH v2 2/2] b",
+            "2024-05-02T09:00:00Z",
+        ),
+        entry(
+            api,
+            "question@x",
+            None,
+            "carol",
+            "How to build docs?",
+            "2024-05-01T15:00:00Z",
+        ),
+        entry(
+            api,
+            "p2@x",
+            Some("cover@x"),
+            "alice",
+            "[PATCH v2 2/2] b",
+            "2024-05-01T12:00:02Z",
+        ),
+        entry(
+            api,
+            "p1@x",
+            Some("cover@x"),
+            "alice",
+            "[PATCH v2 1/2] a",
+            "2024-05-01T12:00:01Z",
+        ),
+        entry(
+            api,
+            "cover@x",
+            None,
+            "alice",
+            "[PATCH v2 0/2] foo",
+            "2024-05-01T12:00:00Z",
+        ),
+    ])
+}
+
+async fn mock_text(api: &MockApi, url_path: &str, body: String) {
+    Mock::given(method("GET"))
+        .and(path(url_path))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(api.server())
+        .await;
+}
+
+fn provider_for(lists: Vec<MailingList>) -> PublicInboxProvider {
+    PublicInboxProvider::new(PublicInboxConfig::new(lists))
+}
+
+#[tokio::test]
+async fn list_feed_groups_series_into_threads() {
+    let api = MockApi::start().await;
+    mock_text(&api, "/list/new.atom", list_feed(&api)).await;
+
+    let list = MailingList::new("list", format!("{}/list/", api.uri()));
+    let provider = provider_for(vec![list]);
+    let items = provider
+        .get_feed_items(&FeedId("list".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["[PATCH v2 0/2] foo", "How to build docs?"]);
+
+    let series = &items[0];
+    assert_eq!(series.id.as_str(), "public-inbox:list:cover@x");
+    assert_eq!(series.author.as_ref().unwrap().name, "alice");
+    assert_eq!(
+        series.updated.unwrap().to_rfc3339(),
+        "2024-05-02T09:00:00+00:00"
+    );
+    assert_eq!(series.metadata["messages"], "4");
+    assert_eq!(series.metadata["replies"], "1");
+    assert_eq!(series.metadata["series_version"], "2");
+    assert_eq!(series.metadata["series_received"], "2");
+    assert_eq!(series.metadata["participants"], "alice, bob");
+    assert_eq!(series.tags, vec!["patch"]);
+    assert!(matches!(
+        &series.content,
+        ItemContent::Email { snippet, .. } if snippet == "Body of cover@x."
+    ));
+
+    let actions = provider.available_actions(series).await.unwrap();
+    assert!(actions.iter().any(|action| action.id == "download_series"));
+    // Without a repository there's nowhere to apply it
+    assert!(!actions.iter().any(|action| action.id == "git_am"));
+    let actions = provider.available_actions(&items[1]).await.unwrap();
+    assert!(!actions.iter().any(|action| action.id == "download_series"));
+
+    // Only the series has had a message since the question was asked
+    let recent = provider
+        .get_feed_items(
+            &FeedId("list".to_string()),
+            FeedOptions {
+                since: Some("2024-05-01T18:00:00Z".parse().unwrap()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 1);
+}
+
+#[tokio::test]
+async fn search_feed_sends_query() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/all/"))
+        .and(query_param("q", "f:carol"))
+        .and(query_param("x", "A"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(feed(&[entry(
+            &api,
+            "question@x",
+            None,
+            "carol",
+            "How to build docs?",
+            "2024-05-01T15:00:00Z",
+        )])))
+        .mount(api.server())
+        .await;
+
+    let list = MailingList::new("mine", format!("{}/all", api.uri())).with_query("f:carol");
+    let provider = provider_for(vec![list]);
+    let items = provider
+        .get_feed_items(&FeedId("mine".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].title, "How to build docs?");
+}
+
+#[tokio::test]
+async fn download_series_saves_newest_patches() {
+    let api = MockApi::start().await;
+    mock_text(&api, "/list/new.atom", list_feed(&api)).await;
+
+    let message = |id: &str, subject: &str, body: &str| {
+        format!(
+            "From mboxrd@z Thu Jan  1 00:00:00 1970\n\
+             Message-ID: <{id}>\n\
+             From: Alice <alice@example.com>\n\
+             Subject: {subject}\n\
+             Date: Wed, 01 May 2024 12:00:00 +0000\n\
+             \n\
+             {body}\n\
+             \n"
+        )
+    };
+    let mbox = [
+        message("cover@x", "[PATCH v2 0/2] foo", "Cover."),
+        message("old@x", "[PATCH 1/2] a", "v1, superseded"),
+        message("p2@x", "[PATCH v2 2/2] b", "second"),
+        message("p1@x", "[PATCH v2 1/2] a", "first"),
+        message("review@x", "Re: [PATCH v2 2/2] b", "Looks good."),
+    ]
+    .concat();
+    Mock::given(method("GET"))
+        .and(path("/list/cover@x/t.mbox.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(gzip(&mbox)))
+        .mount(api.server())
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let provider = PublicInboxProvider::new(
+        PublicInboxConfig::new(vec![MailingList::new(
+            "list",
+            format!("{}/list", api.uri()),
+        )])
+        .with_download_dir(dir.path()),
+    );
+    let items = provider
+        .get_feed_items(&FeedId("list".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+    let action = provider
+        .available_actions(&items[0])
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|action| action.id == "download_series")
+        .unwrap();
+    let result = provider.execute_action(&items[0], &action).await.unwrap();
+
+    assert!(result.success);
+    let saved = dir.path().join("cover@x.mbx");
+    let data = result.data.unwrap();
+    assert_eq!(data["text"], format!("git am {}", saved.display()));
+    let written = std::fs::read_to_string(&saved).unwrap();
+    let subjects: Vec<&str> = written
+        .lines()
+        .filter(|line| line.starts_with("Subject: "))
+        .collect();
+    assert_eq!(
+        subjects,
+        vec!["Subject: [PATCH v2 1/2] a", "Subject: [PATCH v2 2/2] b"]
+    );
+}
+
+fn gzip(text: &str) -> Vec<u8> {
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(text.as_bytes()).unwrap();
+    gzip.finish().unwrap()
+}
+
+fn git(repo: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[tokio::test]
+async fn git_am_applies_series_to_repo() {
+    let api = MockApi::start().await;
+    mock_text(
+        &api,
+        "/list/new.atom",
+        feed(&[entry(
+            &api,
+            "p1@x",
+            None,
+            "alice",
+            "[PATCH] greet",
+            "2024-05-01T12:00:00Z",
+        )]),
+    )
+    .await;
+    let patch = |old: &str, new: &str| {
+        format!(
+            "From mboxrd@z Thu Jan  1 00:00:00 1970\n\
+             Message-ID: <p1@x>\n\
+             From: Alice <alice@example.com>\n\
+             Subject: [PATCH] greet\n\
+             Date: Wed, 01 May 2024 12:00:00 +0000\n\
+             \n\
+             Say hello.\n\
+             ---\n\
+             diff --git a/hello.txt b/hello.txt\n\
+             --- a/hello.txt\n\
+             +++ b/hello.txt\n\
+             @@ -1 +1 @@\n\
+             -{old}\n\
+             +{new}\n\
+             -- \n\
+             2.43.0\n\
+             \n"
+        )
+    };
+    Mock::given(method("GET"))
+        .and(path("/list/p1@x/t.mbox.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(gzip(&patch("hi", "hello"))))
+        .mount(api.server())
+        .await;
+
+    let repo = tempfile::tempdir().unwrap();
+    git(repo.path(), &["init", "-q"]);
+    git(repo.path(), &["config", "user.name", "Test"]);
+    git(repo.path(), &["config", "user.email", "test@example.com"]);
+    std::fs::write(repo.path().join("hello.txt"), "hi\n").unwrap();
+    git(repo.path(), &["add", "hello.txt"]);
+    git(repo.path(), &["commit", "-q", "-m", "Initial"]);
+
+    let downloads = tempfile::tempdir().unwrap();
+    let provider = PublicInboxProvider::new(
+        PublicInboxConfig::new(vec![MailingList::new(
+            "list",
+            format!("{}/list", api.uri()),
+        )])
+        .with_download_dir(downloads.path())
+        .with_repo(repo.path()),
+    );
+    let items = provider
+        .get_feed_items(&FeedId("list".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+    let action = provider
+        .available_actions(&items[0])
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|action| action.id == "git_am")
+        .unwrap();
+
+    let result = provider.execute_action(&items[0], &action).await.unwrap();
+    assert!(result.success, "{:?}", result.message);
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("hello.txt")).unwrap(),
+        "hello\n"
+    );
+
+    // Applied a second time the patch no longer fits, and is backed out
+    let result = provider.execute_action(&items[0], &action).await.unwrap();
+    assert!(!result.success);
+    assert!(result.message.unwrap().starts_with("git am failed"));
+    assert!(!repo.path().join(".git/rebase-apply").exists());
+}
+
+#[tokio::test]
+async fn fetch_errors_are_mapped() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/busy/new.atom", 30).await;
+
+    let provider = provider_for(vec![
+        MailingList::new("busy", format!("{}/busy", api.uri())),
+        MailingList::new("gone", format!("{}/gone", api.uri())),
+    ]);
+    let feed = |name: &str| FeedId(name.to_string());
+
+    assert!(matches!(
+        provider
+            .get_feed_items(&feed("busy"), FeedOptions::default())
+            .await,
+        Err(StreamError::RateLimited(30))
+    ));
+    assert!(matches!(
+        provider
+            .get_feed_items(&feed("gone"), FeedOptions::default())
+            .await,
+        Err(StreamError::StreamNotFound(_))
+    ));
+    assert!(matches!(
+        provider
+            .get_feed_items(&feed("missing"), FeedOptions::default())
+            .await,
+        Err(StreamError::StreamNotFound(_))
+    ));
+
+    let sync = provider.sync().await.unwrap();
+    assert!(!sync.success);
+    assert_eq!(sync.errors.len(), 2);
+}
@@ -10,8 +10,17 @@
 //! - Fetch home feed, popular feed, and subscribed subreddit feeds
 //! - Retrieve saved posts and comments
 //! - List subscribed subreddits
+//! - Inbox, mentions, post replies, and modmail as notifications
+//! - Site-wide and per-subreddit search with sort and time filters
 //! - OAuth authentication via Sigilforge
 //!
+//! ## Notifications
+//!
+//! The inbox is exposed through [`HasNotifications`] and as the `inbox`,
+//! `mentions`, `post_replies`, and `modmail` feeds. Notification item IDs
+//! carry the Reddit fullname (`reddit:t1_...` for comments, `reddit:t4_...`
+//! for private messages) so they can be marked read or replied to.
+//!
 //! ## Authentication
 //!
 //! This provider requires OAuth tokens from Reddit. Tokens are fetched
@@ -21,7 +30,10 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use reqwest::{Client, StatusCode};
 use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::canonicalize_url;
+use scryforge_provider_core::REMOVED_METADATA_KEY;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 // ============================================================================
@@ -69,6 +81,58 @@ struct RedditPost {
     num_comments: i32,
     saved: Option<bool>,
     over_18: bool,
+    is_gallery: Option<bool>,
+    gallery_data: Option<RedditGalleryData>,
+    media_metadata: Option<HashMap<String, RedditMediaMetadata>>,
+    post_hint: Option<String>,
+    preview: Option<RedditPreview>,
+    /// Who took the post down (`moderator`, `deleted`, ...), if anyone
+    removed_by_category: Option<String>,
+}
+
+/// Order and captions of a gallery post's images.
+#[derive(Debug, Deserialize)]
+struct RedditGalleryData {
+    items: Vec<RedditGalleryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditGalleryItem {
+    media_id: String,
+    caption: Option<String>,
+}
+
+/// Entry of a post's `media_metadata`, keyed by media ID.
+#[derive(Debug, Deserialize)]
+struct RedditMediaMetadata {
+    /// Full-size source; missing while media is still processing
+    s: Option<RedditMediaSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditMediaSource {
+    /// Image URL
+    u: Option<String>,
+    /// Animated GIF URL, used instead of `u` for animated images
+    gif: Option<String>,
+    x: Option<u32>,
+    y: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditPreview {
+    images: Vec<RedditPreviewImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditPreviewImage {
+    source: RedditImageSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditImageSource {
+    width: u32,
+    height: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -84,6 +148,42 @@ struct RedditSubreddit {
     community_icon: Option<String>,
     subscribers: Option<i64>,
     url: String,
+    user_is_subscriber: Option<bool>,
+}
+
+/// A comment reply, mention, or private message from the inbox.
+#[derive(Debug, Deserialize)]
+struct RedditMessage {
+    id: String,
+    name: String,
+    author: Option<String>,
+    subject: String,
+    body: String,
+    created_utc: f64,
+    /// Unread
+    #[serde(default)]
+    new: bool,
+    /// Permalink with context for comments, empty for private messages
+    context: Option<String>,
+    subreddit: Option<String>,
+    link_title: Option<String>,
+    /// "comment_reply", "post_reply", or "username_mention" for comments
+    #[serde(rename = "type")]
+    message_type: Option<String>,
+    first_message_name: Option<String>,
+}
+
+/// Body of a POST made with `api_type=json`, which reports failures in
+/// `json.errors` with a 200 status.
+#[derive(Debug, Deserialize)]
+struct RedditJsonResponse {
+    json: RedditJsonErrors,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditJsonErrors {
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -93,6 +193,31 @@ struct RedditErrorResponse {
     error: Option<String>,
 }
 
+/// Sort orders accepted by Reddit search.
+const SEARCH_SORTS: &[&str] = &["relevance", "hot", "top", "new", "comments"];
+
+/// Time windows accepted by Reddit search.
+const SEARCH_TIMES: &[&str] = &["hour", "day", "week", "month", "year", "all"];
+
+/// Percent-encode a query string value.
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Undo the HTML escaping Reddit applies to media URLs unless `raw_json=1`
+/// is requested.
+fn unescape_url(url: &str) -> String {
+    url.replace("&amp;", "&")
+}
+
 // ============================================================================
 // Reddit Provider
 // ============================================================================
@@ -104,9 +229,15 @@ pub struct RedditProvider {
     token_fetcher: Arc<dyn auth::TokenFetcher>,
     account: String,
     client: Client,
+    base_url: String,
 }
 
 impl RedditProvider {
+    const API_BASE: &'static str = "https://oauth.reddit.com";
+
+    /// Most posts Reddit returns per listing request.
+    const PAGE_SIZE: u32 = 100;
+
     /// Create a new Reddit provider instance.
     ///
     /// # Arguments
@@ -130,6 +261,16 @@ impl RedditProvider {
     /// # fn main() {}
     /// ```
     pub fn new(token_fetcher: Arc<dyn auth::TokenFetcher>, account: String) -> Self {
+        Self::with_base_url(token_fetcher, account, Self::API_BASE.to_string())
+    }
+
+    /// Create a provider that talks to a different API base URL, such as a
+    /// mock server in tests.
+    pub fn with_base_url(
+        token_fetcher: Arc<dyn auth::TokenFetcher>,
+        account: String,
+        base_url: String,
+    ) -> Self {
         let client = Client::builder()
             .user_agent("scryforge/0.1.0")
             .build()
@@ -139,6 +280,7 @@ impl RedditProvider {
             token_fetcher,
             account,
             client,
+            base_url,
         }
     }
 
@@ -153,7 +295,7 @@ impl RedditProvider {
     /// Make an authenticated GET request to the Reddit API.
     async fn api_get(&self, endpoint: &str) -> Result<serde_json::Value> {
         let token = self.get_token().await?;
-        let url = format!("https://oauth.reddit.com{}", endpoint);
+        let url = format!("{}{}", self.base_url, endpoint);
 
         let response = self
             .client
@@ -163,6 +305,37 @@ impl RedditProvider {
             .await
             .map_err(|e| StreamError::Network(format!("Request failed: {}", e)))?;
 
+        Self::check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))
+    }
+
+    /// Make an authenticated form POST to the Reddit API, returning the
+    /// response body.
+    async fn api_post(&self, endpoint: &str, form: &[(&str, &str)]) -> Result<String> {
+        let token = self.get_token().await?;
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&token)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(format!("Request failed: {}", e)))?;
+
+        Self::check_response(response)
+            .await?
+            .text()
+            .await
+            .map_err(|e| StreamError::Network(format!("Failed to read response: {}", e)))
+    }
+
+    /// Map auth, rate limit, and other error statuses to stream errors.
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response> {
         let status = response.status();
 
         if status == StatusCode::UNAUTHORIZED {
@@ -186,41 +359,132 @@ impl RedditProvider {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(StreamError::Provider(format!(
-                "API error ({}): {}",
-                status, error_body
-            )));
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                None,
+                format!("API error ({}): {}", status, error_body),
+            ));
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))
+        Ok(response)
     }
 
-    /// Fetch posts from a Reddit listing endpoint.
+    /// Fetch up to `limit` posts from a Reddit listing endpoint.
     async fn fetch_posts(&self, endpoint: &str, limit: Option<u32>) -> Result<Vec<Item>> {
-        let limit = limit.unwrap_or(25).min(100);
-        let endpoint_with_params = format!("{}?limit={}", endpoint, limit);
+        self.fetch_listing(endpoint, limit, |thing| {
+            if thing.kind != "t3" {
+                return Ok(None);
+            }
+            // t3 is a post
+            let post: RedditPost = serde_json::from_value(thing.data)
+                .map_err(|e| StreamError::Provider(format!("Failed to parse post: {}", e)))?;
+            self.post_to_item(post).map(Some)
+        })
+        .await
+    }
 
-        let response = self.api_get(&endpoint_with_params).await?;
-        let listing: RedditListing = serde_json::from_value(response)
-            .map_err(|e| StreamError::Provider(format!("Failed to parse listing: {}", e)))?;
+    /// Fetch up to `limit` inbox messages from a `/message/...` endpoint as
+    /// items of the `feed` stream.
+    async fn fetch_messages(
+        &self,
+        endpoint: &str,
+        feed: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Item>> {
+        self.fetch_listing(endpoint, limit, |thing| {
+            if thing.kind != "t1" && thing.kind != "t4" {
+                return Ok(None);
+            }
+            // t1 is a comment reply or mention, t4 a private message
+            let message: RedditMessage = serde_json::from_value(thing.data)
+                .map_err(|e| StreamError::Provider(format!("Failed to parse message: {}", e)))?;
+            Self::message_to_item(message, &thing.kind, feed).map(Some)
+        })
+        .await
+    }
+
+    /// Fetch up to `limit` things from a Reddit listing endpoint, following
+    /// the listing's `after` cursor when more than one page is needed.
+    /// `to_item` returns `None` for things that should be skipped.
+    async fn fetch_listing(
+        &self,
+        endpoint: &str,
+        limit: Option<u32>,
+        mut to_item: impl FnMut(RedditThing) -> Result<Option<Item>>,
+    ) -> Result<Vec<Item>> {
+        let limit = limit.unwrap_or(25);
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
 
         let mut items = Vec::new();
+        let mut after: Option<String> = None;
 
-        for thing in listing.data.children {
-            if thing.kind == "t3" {
-                // t3 is a post
-                let post: RedditPost = serde_json::from_value(thing.data)
-                    .map_err(|e| StreamError::Provider(format!("Failed to parse post: {}", e)))?;
-                items.push(self.post_to_item(post)?);
+        while (items.len() as u32) < limit {
+            let page_size = (limit - items.len() as u32).min(Self::PAGE_SIZE);
+            let mut endpoint_with_params = format!("{}{}limit={}", endpoint, separator, page_size);
+            if let Some(ref after) = after {
+                endpoint_with_params.push_str(&format!("&after={}", after));
+            }
+
+            let response = self.api_get(&endpoint_with_params).await?;
+            let listing: RedditListing = serde_json::from_value(response)
+                .map_err(|e| StreamError::Provider(format!("Failed to parse listing: {}", e)))?;
+            let page_len = listing.data.children.len();
+
+            for thing in listing.data.children {
+                items.extend(to_item(thing)?);
+            }
+
+            match listing.data.after {
+                Some(next) if page_len > 0 => after = Some(next),
+                _ => break,
             }
         }
 
+        items.truncate(limit as usize);
         Ok(items)
     }
 
+    /// Images of a gallery or image post, or `None` for other posts.
+    fn post_images(post: &RedditPost) -> Option<Vec<GalleryImage>> {
+        if post.is_gallery == Some(true) {
+            let metadata = post.media_metadata.as_ref()?;
+            let images: Vec<GalleryImage> = post
+                .gallery_data
+                .as_ref()?
+                .items
+                .iter()
+                .filter_map(|item| {
+                    let source = metadata.get(&item.media_id)?.s.as_ref()?;
+                    let url = source.gif.as_ref().or(source.u.as_ref())?;
+                    Some(GalleryImage {
+                        url: unescape_url(url),
+                        alt: item.caption.clone().filter(|c| !c.is_empty()),
+                        width: source.x,
+                        height: source.y,
+                    })
+                })
+                .collect();
+            return (!images.is_empty()).then_some(images);
+        }
+
+        if post.post_hint.as_deref() == Some("image") {
+            let url = post.url.clone()?;
+            let source = post
+                .preview
+                .as_ref()
+                .and_then(|preview| preview.images.first())
+                .map(|image| &image.source);
+            return Some(vec![GalleryImage {
+                url,
+                alt: None,
+                width: source.map(|s| s.width),
+                height: source.map(|s| s.height),
+            }]);
+        }
+
+        None
+    }
+
     /// Convert a Reddit post to a Scryforge Item.
     fn post_to_item(&self, post: RedditPost) -> Result<Item> {
         let published = DateTime::from_timestamp(post.created_utc as i64, 0)
@@ -241,15 +505,33 @@ impl RedditProvider {
             None
         };
 
-        let content = ItemContent::Article {
-            summary,
-            full_content,
+        let content = match Self::post_images(&post) {
+            Some(mut images) if images.len() == 1 && post.is_gallery != Some(true) => {
+                let image = images.remove(0);
+                ItemContent::Image {
+                    url: image.url,
+                    alt: image.alt,
+                    width: image.width,
+                    height: image.height,
+                }
+            }
+            Some(images) => ItemContent::Gallery { images },
+            // Selftext is Markdown, which the TUI renders itself
+            None => match summary {
+                Some(selftext) if post.is_self && !selftext.trim().is_empty() => {
+                    ItemContent::Markdown(selftext)
+                }
+                summary => ItemContent::Article {
+                    summary,
+                    full_content,
+                },
+            },
         };
 
         let url = if post.is_self {
             Some(format!("https://reddit.com{}", post.permalink))
         } else {
-            post.url.clone()
+            post.url.as_deref().map(canonicalize_url)
         };
 
         let thumbnail_url = post.thumbnail.and_then(|t| {
@@ -260,6 +542,23 @@ impl RedditProvider {
             }
         });
 
+        let mut metadata: HashMap<String, String> = [
+            ("score".to_string(), post.score.to_string()),
+            ("num_comments".to_string(), post.num_comments.to_string()),
+            ("subreddit".to_string(), post.subreddit.clone()),
+            ("over_18".to_string(), post.over_18.to_string()),
+        ]
+        .into_iter()
+        .collect();
+        // Deleted posts keep their listing with the author blanked out
+        let removed = post
+            .removed_by_category
+            .clone()
+            .or_else(|| (post.author == "[deleted]").then(|| "deleted".to_string()));
+        if let Some(reason) = removed {
+            metadata.insert(REMOVED_METADATA_KEY.to_string(), reason);
+        }
+
         Ok(Item {
             id: ItemId::new("reddit", &post.id),
             stream_id: StreamId::new("reddit", "feed", &post.subreddit),
@@ -278,14 +577,7 @@ impl RedditProvider {
             is_read: false,
             is_saved: post.saved.unwrap_or(false),
             tags: vec![post.subreddit_name_prefixed.clone()],
-            metadata: [
-                ("score".to_string(), post.score.to_string()),
-                ("num_comments".to_string(), post.num_comments.to_string()),
-                ("subreddit".to_string(), post.subreddit.clone()),
-                ("over_18".to_string(), post.over_18.to_string()),
-            ]
-            .into_iter()
-            .collect(),
+            metadata,
         })
     }
 
@@ -297,13 +589,165 @@ impl RedditProvider {
             .filter(|s| !s.is_empty());
 
         Community {
-            id: CommunityId(subreddit.name.clone()),
+            id: CommunityId(subreddit.display_name.clone()),
             name: subreddit.display_name_prefixed.clone(),
             description: subreddit.public_description,
             icon: icon_url,
             member_count: subreddit.subscribers.map(|s| s as u64),
             url: Some(format!("https://reddit.com{}", subreddit.url)),
+            is_member: subreddit.user_is_subscriber.unwrap_or(false),
+        }
+    }
+
+    /// The subreddit name of a community ID, with or without its `r/` prefix.
+    fn subreddit_name(id: &CommunityId) -> Result<&str> {
+        let name = id.0.strip_prefix("r/").unwrap_or(&id.0);
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(StreamError::StreamNotFound(format!(
+                "Invalid subreddit: {}",
+                id.0
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Subscribe to or unsubscribe from a subreddit.
+    async fn set_subscribed(&self, id: &CommunityId, subscribed: bool) -> Result<()> {
+        let name = Self::subreddit_name(id)?;
+        let action = if subscribed { "sub" } else { "unsub" };
+        self.api_post("/api/subscribe", &[("action", action), ("sr_name", name)])
+            .await?;
+        Ok(())
+    }
+
+    /// Convert an inbox message of the given thing `kind` to an Item.
+    fn message_to_item(message: RedditMessage, kind: &str, feed: &str) -> Result<Item> {
+        let published = DateTime::from_timestamp(message.created_utc as i64, 0)
+            .ok_or_else(|| StreamError::Provider("Invalid timestamp".to_string()))?;
+
+        let notification_type = match (kind, message.message_type) {
+            ("t1", Some(message_type)) => message_type,
+            ("t1", None) => "comment_reply".to_string(),
+            _ if feed == "modmail" => "modmail".to_string(),
+            _ => "private_message".to_string(),
+        };
+
+        let title = if kind == "t1" {
+            message.link_title.unwrap_or(message.subject)
+        } else {
+            message.subject
+        };
+
+        let url = match message.context.filter(|c| !c.is_empty()) {
+            Some(context) => format!("https://reddit.com{}", context),
+            None => format!("https://reddit.com/message/messages/{}", message.id),
+        };
+
+        let sender = message.author.unwrap_or_else(|| "[deleted]".to_string());
+        let channel = message.subreddit.as_ref().map(|s| format!("r/{}", s));
+
+        let mut metadata: HashMap<String, String> =
+            [("notification_type".to_string(), notification_type)]
+                .into_iter()
+                .collect();
+        if let Some(subreddit) = &message.subreddit {
+            metadata.insert("subreddit".to_string(), subreddit.clone());
         }
+
+        Ok(Item {
+            id: ItemId::new("reddit", &message.name),
+            stream_id: StreamId::new("reddit", "feed", feed),
+            title,
+            content: ItemContent::Message {
+                text: message.body,
+                sender: sender.clone(),
+                channel: channel.clone(),
+                thread_id: message.first_message_name,
+                reactions: vec![],
+            },
+            author: Some(Author {
+                name: sender.clone(),
+                email: None,
+                url: Some(format!("https://reddit.com/u/{}", sender)),
+                avatar_url: None,
+            }),
+            published: Some(published),
+            updated: None,
+            url: Some(url),
+            thumbnail_url: None,
+            is_read: !message.new,
+            is_saved: false,
+            tags: channel.into_iter().collect(),
+            metadata,
+        })
+    }
+
+    /// Search endpoint for `query`, scoped to the subreddit of a
+    /// `reddit:feed:<subreddit>` stream when one is given.
+    fn search_endpoint(query: &str, options: &SearchOptions) -> Result<String> {
+        let subreddit = options
+            .stream_id
+            .as_ref()
+            .and_then(|stream_id| stream_id.as_str().strip_prefix("reddit:feed:"))
+            .map(|feed| feed.strip_prefix("r/").unwrap_or(feed))
+            .filter(|feed| {
+                !matches!(
+                    *feed,
+                    "home" | "popular" | "all" | "inbox" | "mentions" | "post_replies" | "modmail"
+                )
+            });
+
+        let mut endpoint = match subreddit {
+            Some(subreddit) => format!(
+                "/r/{}/search?q={}&restrict_sr=on",
+                subreddit,
+                encode_query_value(query)
+            ),
+            None => format!("/search?q={}", encode_query_value(query)),
+        };
+        endpoint.push_str("&type=link");
+
+        if let Some(sort) = &options.sort {
+            if !SEARCH_SORTS.contains(&sort.as_str()) {
+                return Err(StreamError::Provider(format!(
+                    "Unsupported search sort '{}', expected one of: {}",
+                    sort,
+                    SEARCH_SORTS.join(", ")
+                )));
+            }
+            endpoint.push_str(&format!("&sort={}", sort));
+        }
+        if let Some(time) = &options.time {
+            if !SEARCH_TIMES.contains(&time.as_str()) {
+                return Err(StreamError::Provider(format!(
+                    "Unsupported search time '{}', expected one of: {}",
+                    time,
+                    SEARCH_TIMES.join(", ")
+                )));
+            }
+            endpoint.push_str(&format!("&t={}", time));
+        }
+
+        Ok(endpoint)
+    }
+
+    /// Whether `item` came from the inbox rather than a post listing.
+    fn is_notification(item: &Item) -> bool {
+        item.metadata.contains_key("notification_type")
+    }
+
+    /// Reddit fullname (`t1_...` or `t4_...`) of a notification item.
+    fn notification_fullname(item_id: &ItemId) -> Result<&str> {
+        item_id
+            .as_str()
+            .strip_prefix("reddit:")
+            .filter(|name| name.starts_with("t1_") || name.starts_with("t4_"))
+            .ok_or_else(|| {
+                StreamError::ItemNotFound(format!(
+                    "Not a Reddit notification: {}",
+                    item_id.as_str()
+                ))
+            })
     }
 }
 
@@ -361,6 +805,7 @@ impl Provider for RedditProvider {
                 items_removed: 0,
                 errors: vec![],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
             Ok(health) => Ok(SyncResult {
                 success: false,
@@ -369,6 +814,7 @@ impl Provider for RedditProvider {
                 items_removed: 0,
                 errors: vec![health.message.unwrap_or_default()],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
             Err(e) => Ok(SyncResult {
                 success: false,
@@ -377,6 +823,7 @@ impl Provider for RedditProvider {
                 items_removed: 0,
                 errors: vec![e.to_string()],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -387,6 +834,11 @@ impl Provider for RedditProvider {
             has_collections: false,
             has_saved_items: true,
             has_communities: true,
+            supports_search: true,
+            supports_write_actions: true,
+            supports_incremental_sync: false,
+            supports_pagination: false,
+            supports_notifications: false,
         }
     }
 
@@ -408,7 +860,24 @@ impl Provider for RedditProvider {
             },
         ];
 
-        if !item.is_saved {
+        if Self::is_notification(item) {
+            if !item.is_read {
+                actions.push(Action {
+                    id: "mark_read".to_string(),
+                    name: "Mark as Read".to_string(),
+                    description: "Mark as read on Reddit".to_string(),
+                    kind: ActionKind::MarkRead,
+                    keyboard_shortcut: Some("r".to_string()),
+                });
+            }
+            actions.push(Action {
+                id: "reply".to_string(),
+                name: "Reply".to_string(),
+                description: "Reply on Reddit".to_string(),
+                kind: ActionKind::Custom("reply".to_string()),
+                keyboard_shortcut: Some("R".to_string()),
+            });
+        } else if !item.is_saved {
             actions.push(Action {
                 id: "save".to_string(),
                 name: "Save".to_string(),
@@ -451,6 +920,38 @@ impl Provider for RedditProvider {
                 message: Some("Preview action triggered".to_string()),
                 data: None,
             }),
+            ActionKind::MarkRead => match self.mark_notification_read(&item.id).await {
+                Ok(()) => Ok(ActionResult {
+                    success: true,
+                    message: Some("Marked as read".to_string()),
+                    data: None,
+                }),
+                Err(e) => Ok(ActionResult {
+                    success: false,
+                    message: Some(format!("Failed to mark as read: {}", e)),
+                    data: None,
+                }),
+            },
+            ActionKind::Custom(ref custom) if custom == "reply" => {
+                // The client prompts for the text and sends it with
+                // `reply_to_notification`
+                match Self::notification_fullname(&item.id) {
+                    Ok(thing_id) => Ok(ActionResult {
+                        success: true,
+                        message: Some("Enter your reply:".to_string()),
+                        data: Some(serde_json::json!({
+                            "thing_id": thing_id,
+                            "requires_input": true,
+                            "input_type": "text"
+                        })),
+                    }),
+                    Err(e) => Ok(ActionResult {
+                        success: false,
+                        message: Some(e.to_string()),
+                        data: None,
+                    }),
+                }
+            }
             ActionKind::Save => match self.save_item(&item.id).await {
                 Ok(()) => Ok(ActionResult {
                     success: true,
@@ -486,6 +987,22 @@ impl Provider for RedditProvider {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_notifications(&self) -> Option<&dyn HasNotifications> {
+        Some(self)
+    }
+
+    fn as_search(&self) -> Option<&dyn HasSearch> {
+        Some(self)
+    }
+
+    fn as_communities(&self) -> Option<&dyn HasCommunities> {
+        Some(self)
+    }
 }
 
 // ============================================================================
@@ -520,10 +1037,55 @@ impl HasFeeds for RedditProvider {
                 unread_count: None,
                 total_count: None,
             },
+            Feed {
+                id: FeedId("inbox".to_string()),
+                name: "Inbox".to_string(),
+                description: Some("Replies, mentions, and private messages".to_string()),
+                icon: Some("📥".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+            Feed {
+                id: FeedId("mentions".to_string()),
+                name: "Mentions".to_string(),
+                description: Some("Comments mentioning your username".to_string()),
+                icon: Some("📣".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+            Feed {
+                id: FeedId("post_replies".to_string()),
+                name: "Post Replies".to_string(),
+                description: Some("Comments on your posts".to_string()),
+                icon: Some("💬".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
+            Feed {
+                id: FeedId("modmail".to_string()),
+                name: "Modmail".to_string(),
+                description: Some("Messages to subreddits you moderate".to_string()),
+                icon: Some("🛡".to_string()),
+                unread_count: None,
+                total_count: None,
+            },
         ])
     }
 
     async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let inbox_endpoint = match feed_id.0.as_str() {
+            "inbox" => Some("/message/inbox"),
+            "mentions" => Some("/message/mentions"),
+            "post_replies" => Some("/message/selfreply"),
+            "modmail" => Some("/message/moderator"),
+            _ => None,
+        };
+        if let Some(endpoint) = inbox_endpoint {
+            return self
+                .fetch_messages(endpoint, &feed_id.0, options.limit)
+                .await;
+        }
+
         let endpoint = match feed_id.0.as_str() {
             "home" => "/",
             "popular" => "/r/popular",
@@ -548,10 +1110,9 @@ impl HasFeeds for RedditProvider {
 #[async_trait]
 impl HasSavedItems for RedditProvider {
     async fn get_saved_items(&self, options: SavedItemsOptions) -> Result<Vec<Item>> {
-        let limit = options.limit.unwrap_or(25).min(100);
-        let endpoint = format!("/user/{}/saved?limit={}", self.account, limit);
+        let endpoint = format!("/user/{}/saved", self.account);
 
-        self.fetch_posts(&endpoint, Some(limit)).await
+        self.fetch_posts(&endpoint, options.limit).await
     }
 
     async fn is_saved(&self, item_id: &ItemId) -> Result<bool> {
@@ -578,26 +1139,28 @@ impl HasSavedItems for RedditProvider {
 
         // Call Reddit API to save the item
         let token = self.get_token().await?;
-        let url = "https://oauth.reddit.com/api/save";
+        let url = format!("{}/api/save", self.base_url);
 
         let response = self
             .client
-            .post(url)
+            .post(&url)
             .bearer_auth(&token)
             .form(&[("id", format!("t3_{}", reddit_id))])
             .send()
             .await
             .map_err(|e| StreamError::Network(format!("Request failed: {}", e)))?;
 
-        if !response.status().is_success() {
+        let status = response.status();
+        if !status.is_success() {
             let error_body = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(StreamError::Provider(format!(
-                "Failed to save item: {}",
-                error_body
-            )));
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                None,
+                format!("Failed to save item: {}", error_body),
+            ));
         }
 
         Ok(())
@@ -612,26 +1175,28 @@ impl HasSavedItems for RedditProvider {
 
         // Call Reddit API to unsave the item
         let token = self.get_token().await?;
-        let url = "https://oauth.reddit.com/api/unsave";
+        let url = format!("{}/api/unsave", self.base_url);
 
         let response = self
             .client
-            .post(url)
+            .post(&url)
             .bearer_auth(&token)
             .form(&[("id", format!("t3_{}", reddit_id))])
             .send()
             .await
             .map_err(|e| StreamError::Network(format!("Request failed: {}", e)))?;
 
-        if !response.status().is_success() {
+        let status = response.status();
+        if !status.is_success() {
             let error_body = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(StreamError::Provider(format!(
-                "Failed to unsave item: {}",
-                error_body
-            )));
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                None,
+                format!("Failed to unsave item: {}", error_body),
+            ));
         }
 
         Ok(())
@@ -657,7 +1222,10 @@ impl HasCommunities for RedditProvider {
                     serde_json::from_value(thing.data).map_err(|e| {
                         StreamError::Provider(format!("Failed to parse subreddit: {}", e))
                     })?;
-                communities.push(self.subreddit_to_community(subreddit));
+                communities.push(Community {
+                    is_member: true,
+                    ..self.subreddit_to_community(subreddit)
+                });
             }
         }
 
@@ -665,7 +1233,7 @@ impl HasCommunities for RedditProvider {
     }
 
     async fn get_community(&self, id: &CommunityId) -> Result<Community> {
-        let endpoint = format!("/r/{}/about", id.0);
+        let endpoint = format!("/r/{}/about", Self::subreddit_name(id)?);
         let response = self.api_get(&endpoint).await?;
 
         let thing: RedditThing = serde_json::from_value(response)
@@ -676,6 +1244,80 @@ impl HasCommunities for RedditProvider {
 
         Ok(self.subreddit_to_community(subreddit))
     }
+
+    async fn get_community_items(
+        &self,
+        id: &CommunityId,
+        options: FeedOptions,
+    ) -> Result<Vec<Item>> {
+        let endpoint = format!("/r/{}/new", Self::subreddit_name(id)?);
+        self.fetch_posts(&endpoint, options.limit).await
+    }
+
+    async fn join_community(&self, id: &CommunityId) -> Result<()> {
+        self.set_subscribed(id, true).await
+    }
+
+    async fn leave_community(&self, id: &CommunityId) -> Result<()> {
+        self.set_subscribed(id, false).await
+    }
+}
+
+#[async_trait]
+impl HasNotifications for RedditProvider {
+    async fn list_notifications(&self, options: FeedOptions) -> Result<Vec<Item>> {
+        let endpoint = if options.include_read {
+            "/message/inbox"
+        } else {
+            "/message/unread"
+        };
+
+        self.fetch_messages(endpoint, "inbox", options.limit).await
+    }
+
+    async fn mark_notification_read(&self, item_id: &ItemId) -> Result<()> {
+        let fullname = Self::notification_fullname(item_id)?;
+
+        self.api_post("/api/read_message", &[("id", fullname)])
+            .await?;
+        Ok(())
+    }
+
+    async fn reply_to_notification(&self, item_id: &ItemId, text: &str) -> Result<()> {
+        let fullname = Self::notification_fullname(item_id)?;
+
+        let body = self
+            .api_post(
+                "/api/comment",
+                &[("api_type", "json"), ("thing_id", fullname), ("text", text)],
+            )
+            .await?;
+        let response: RedditJsonResponse = serde_json::from_str(&body)
+            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))?;
+
+        if !response.json.errors.is_empty() {
+            return Err(StreamError::Provider(format!(
+                "Failed to post reply: {}",
+                serde_json::Value::Array(response.json.errors)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HasSearch for RedditProvider {
+    async fn search(&self, query: &str, options: SearchOptions) -> Result<Vec<Item>> {
+        let endpoint = Self::search_endpoint(query, &options)?;
+
+        let mut items = self.fetch_posts(&endpoint, options.limit).await?;
+        for (rank, item) in items.iter_mut().enumerate() {
+            item.metadata
+                .insert("search_rank".to_string(), (rank + 1).to_string());
+        }
+        Ok(items)
+    }
 }
 
 // ============================================================================
@@ -716,10 +1358,12 @@ mod tests {
         let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
         let feeds = provider.list_feeds().await.unwrap();
 
-        assert_eq!(feeds.len(), 3);
+        assert_eq!(feeds.len(), 7);
         assert_eq!(feeds[0].id.0, "home");
         assert_eq!(feeds[1].id.0, "popular");
         assert_eq!(feeds[2].id.0, "all");
+        assert_eq!(feeds[3].id.0, "inbox");
+        assert_eq!(feeds[6].id.0, "modmail");
     }
 
     #[tokio::test]
@@ -744,12 +1388,22 @@ mod tests {
             num_comments: 10,
             saved: Some(false),
             over_18: false,
+            is_gallery: None,
+            gallery_data: None,
+            media_metadata: None,
+            post_hint: None,
+            preview: None,
+            removed_by_category: None,
         };
 
         let item = provider.post_to_item(post).unwrap();
 
         assert_eq!(item.id.as_str(), "reddit:abc123");
         assert_eq!(item.title, "Test Post");
+        assert!(matches!(
+            &item.content,
+            ItemContent::Markdown(selftext) if selftext == "This is a test post"
+        ));
         assert_eq!(item.stream_id.as_str(), "reddit:feed:rust");
         assert!(!item.is_saved);
         assert_eq!(item.tags, vec!["r/rust"]);
@@ -760,6 +1414,108 @@ mod tests {
         assert_eq!(item.metadata.get("subreddit"), Some(&"rust".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_gallery_and_image_posts() {
+        let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
+        let post = |extra: serde_json::Value| -> RedditPost {
+            let mut data = serde_json::json!({
+                "id": "g1",
+                "name": "t3_g1",
+                "title": "Photos",
+                "author": "photographer",
+                "subreddit": "pics",
+                "subreddit_name_prefixed": "r/pics",
+                "created_utc": 1700000000.0,
+                "url": "https://www.reddit.com/gallery/g1",
+                "permalink": "/r/pics/comments/g1/photos/",
+                "is_self": false,
+                "score": 1,
+                "num_comments": 0,
+                "over_18": false,
+            });
+            data.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(data).unwrap()
+        };
+
+        let gallery = post(serde_json::json!({
+            "is_gallery": true,
+            "gallery_data": { "items": [
+                { "media_id": "b", "caption": "Second" },
+                { "media_id": "a" },
+                { "media_id": "missing" },
+            ]},
+            "media_metadata": {
+                "a": { "status": "valid", "s": { "u": "https://preview.redd.it/a.jpg?w=1&amp;s=x", "x": 640, "y": 480 } },
+                "b": { "status": "valid", "s": { "gif": "https://i.redd.it/b.gif", "x": 320, "y": 240 } },
+            },
+        }));
+        match provider.post_to_item(gallery).unwrap().content {
+            ItemContent::Gallery { images } => {
+                assert_eq!(images.len(), 2);
+                assert_eq!(images[0].url, "https://i.redd.it/b.gif");
+                assert_eq!(images[0].alt.as_deref(), Some("Second"));
+                assert_eq!(images[1].url, "https://preview.redd.it/a.jpg?w=1&s=x");
+                assert_eq!(images[1].width, Some(640));
+            }
+            other => panic!("expected gallery, got {:?}", other),
+        }
+
+        let image = post(serde_json::json!({
+            "url": "https://i.redd.it/photo.png",
+            "post_hint": "image",
+            "preview": { "images": [{ "source": { "url": "https://preview.redd.it/photo.png", "width": 1200, "height": 900 } }] },
+        }));
+        match provider.post_to_item(image).unwrap().content {
+            ItemContent::Image {
+                url, width, height, ..
+            } => {
+                assert_eq!(url, "https://i.redd.it/photo.png");
+                assert_eq!((width, height), (Some(1200), Some(900)));
+            }
+            other => panic!("expected image, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_removed_posts_are_marked() {
+        let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
+        let post = |author: &str, removed_by: Option<&str>| -> RedditPost {
+            serde_json::from_value(serde_json::json!({
+                "id": "r1",
+                "name": "t3_r1",
+                "title": "Gone",
+                "selftext": "[removed]",
+                "author": author,
+                "subreddit": "rust",
+                "subreddit_name_prefixed": "r/rust",
+                "created_utc": 1700000000.0,
+                "permalink": "/r/rust/comments/r1/gone/",
+                "is_self": true,
+                "score": 1,
+                "num_comments": 0,
+                "over_18": false,
+                "removed_by_category": removed_by,
+            }))
+            .unwrap()
+        };
+
+        let removed = |post| {
+            provider
+                .post_to_item(post)
+                .unwrap()
+                .metadata
+                .remove(REMOVED_METADATA_KEY)
+        };
+        assert_eq!(removed(post("[deleted]", None)).as_deref(), Some("deleted"));
+        assert_eq!(
+            removed(post("someone", Some("moderator"))).as_deref(),
+            Some("moderator")
+        );
+        assert_eq!(removed(post("someone", None)), None);
+    }
+
     #[tokio::test]
     async fn test_available_actions() {
         let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
@@ -791,6 +1547,71 @@ mod tests {
         assert!(actions.iter().any(|a| a.kind == ActionKind::Save));
     }
 
+    #[test]
+    fn test_search_endpoint() {
+        let endpoint = |stream: Option<&str>, sort: Option<&str>, time: Option<&str>| {
+            let options = SearchOptions {
+                stream_id: stream.map(|s| StreamId(s.to_string())),
+                sort: sort.map(str::to_string),
+                time: time.map(str::to_string),
+                ..Default::default()
+            };
+            RedditProvider::search_endpoint("async & await", &options)
+        };
+
+        assert_eq!(
+            endpoint(None, None, None).unwrap(),
+            "/search?q=async%20%26%20await&type=link"
+        );
+        assert_eq!(
+            endpoint(Some("reddit:feed:rust"), Some("top"), Some("week")).unwrap(),
+            "/r/rust/search?q=async%20%26%20await&restrict_sr=on&type=link&sort=top&t=week"
+        );
+        assert_eq!(
+            endpoint(Some("reddit:feed:home"), Some("new"), None).unwrap(),
+            "/search?q=async%20%26%20await&type=link&sort=new"
+        );
+        assert!(endpoint(None, Some("best"), None).is_err());
+        assert!(endpoint(None, None, Some("decade")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notification_actions() {
+        let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
+        let message: RedditMessage = serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "name": "t1_c1",
+            "author": "helpful_user",
+            "subject": "post reply",
+            "body": "Nice post",
+            "created_utc": 1704117600.0,
+            "new": true,
+            "context": "/r/rust/comments/p1/post/c1/?context=3",
+            "subreddit": "rust",
+            "link_title": "My crate",
+            "type": "post_reply",
+        }))
+        .unwrap();
+        let mut item = RedditProvider::message_to_item(message, "t1", "post_replies").unwrap();
+        assert_eq!(item.id.as_str(), "reddit:t1_c1");
+        assert_eq!(item.metadata["notification_type"], "post_reply");
+
+        let actions = provider.available_actions(&item).await.unwrap();
+        assert!(actions.iter().any(|a| a.kind == ActionKind::MarkRead));
+        assert!(!actions.iter().any(|a| a.kind == ActionKind::Save));
+        let reply = actions.iter().find(|a| a.id == "reply").unwrap();
+
+        let result = provider.execute_action(&item, reply).await.unwrap();
+        assert!(result.success);
+        let data = result.data.unwrap();
+        assert_eq!(data["thing_id"], "t1_c1");
+        assert_eq!(data["requires_input"], true);
+
+        item.is_read = true;
+        let actions = provider.available_actions(&item).await.unwrap();
+        assert!(!actions.iter().any(|a| a.kind == ActionKind::MarkRead));
+    }
+
     #[tokio::test]
     async fn test_execute_action_open() {
         let provider = RedditProvider::new(mock_token_fetcher(), "test".to_string());
@@ -842,11 +1663,13 @@ mod tests {
             community_icon: None,
             subscribers: Some(100000),
             url: "/r/rust/".to_string(),
+            user_is_subscriber: Some(true),
         };
 
         let community = provider.subreddit_to_community(subreddit);
 
-        assert_eq!(community.id.0, "t5_2qh1i");
+        assert_eq!(community.id.0, "rust");
+        assert!(community.is_member);
         assert_eq!(community.name, "r/rust");
         assert_eq!(
             community.description,
@@ -0,0 +1,314 @@
+//! Mock-server tests for `provider-reddit`'s HTTP paths.
+
+use std::sync::Arc;
+
+use provider_reddit::RedditProvider;
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{
+    body_string_contains, method, path, query_param,
+};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::{fixture, MockApi};
+
+fn provider_for(api: &MockApi) -> RedditProvider {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "reddit".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    RedditProvider::with_base_url(Arc::new(tokens), "personal".to_string(), api.uri())
+}
+
+fn saved_options(limit: u32) -> SavedItemsOptions {
+    SavedItemsOptions {
+        limit: Some(limit),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn saved_items_follow_after_cursor() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/user/personal/saved",
+        "after",
+        vec![
+            (None, fixture("reddit/listing_page1.json")),
+            (Some("t3_p2"), fixture("reddit/listing_page2.json")),
+        ],
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_saved_items(saved_options(10))
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(ids, vec!["reddit:p1", "reddit:p2", "reddit:p3"]);
+    assert!(matches!(items[2].content, ItemContent::Gallery { .. }));
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn limit_stops_pagination() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/user/personal/saved",
+        "after",
+        vec![
+            (None, fixture("reddit/listing_page1.json")),
+            (Some("t3_p2"), fixture("reddit/listing_page2.json")),
+        ],
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_saved_items(saved_options(2))
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(api.request_count().await, 1);
+}
+
+#[tokio::test]
+async fn rejected_token_requires_auth() {
+    let api = MockApi::start().await;
+    api.mock_unauthorized("/user/personal/saved").await;
+
+    let err = provider_for(&api)
+        .get_saved_items(saved_options(10))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn too_many_requests_is_rate_limited() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/user/personal/saved", 30).await;
+
+    let err = provider_for(&api)
+        .get_saved_items(saved_options(10))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(30)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn unread_notifications_map_inbox_messages() {
+    let api = MockApi::start().await;
+    api.mock_json("GET", "/message/unread", fixture("reddit/inbox.json"))
+        .await;
+
+    let items = provider_for(&api)
+        .list_notifications(FeedOptions::default())
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(ids, vec!["reddit:t1_c1", "reddit:t1_c2", "reddit:t4_m1"]);
+
+    let reply = &items[0];
+    assert_eq!(reply.title, "Async question");
+    assert_eq!(reply.stream_id.as_str(), "reddit:feed:inbox");
+    assert!(!reply.is_read);
+    assert_eq!(
+        reply.url.as_deref(),
+        Some("https://reddit.com/r/rust/comments/p1/post/c1/?context=3")
+    );
+    match &reply.content {
+        ItemContent::Message {
+            text,
+            sender,
+            channel,
+            ..
+        } => {
+            assert_eq!(text, "Have you tried pinning the future?");
+            assert_eq!(sender, "helpful_user");
+            assert_eq!(channel.as_deref(), Some("r/rust"));
+        }
+        other => panic!("expected message, got {:?}", other),
+    }
+
+    let types: Vec<&str> = items
+        .iter()
+        .map(|item| item.metadata["notification_type"].as_str())
+        .collect();
+    assert_eq!(
+        types,
+        vec!["comment_reply", "username_mention", "private_message"]
+    );
+    assert!(items[1].is_read);
+    assert_eq!(items[2].title, "Meetup");
+    assert_eq!(
+        items[2].url.as_deref(),
+        Some("https://reddit.com/message/messages/m1")
+    );
+}
+
+#[tokio::test]
+async fn modmail_feed_is_tagged_as_modmail() {
+    let api = MockApi::start().await;
+    api.mock_json("GET", "/message/moderator", fixture("reddit/inbox.json"))
+        .await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&FeedId("modmail".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items[2].stream_id.as_str(), "reddit:feed:modmail");
+    assert_eq!(items[2].metadata["notification_type"], "modmail");
+}
+
+#[tokio::test]
+async fn mark_read_and_reply_send_the_fullname() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/read_message"))
+        .and(body_string_contains("id=t1_c1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/comment"))
+        .and(body_string_contains("thing_id=t4_m1"))
+        .and(body_string_contains("text=See+you+there"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "json": { "errors": [] } })),
+        )
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    provider
+        .mark_notification_read(&ItemId("reddit:t1_c1".to_string()))
+        .await
+        .unwrap();
+    provider
+        .reply_to_notification(&ItemId("reddit:t4_m1".to_string()), "See you there")
+        .await
+        .unwrap();
+
+    let err = provider
+        .mark_notification_read(&ItemId("reddit:p1".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::ItemNotFound(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn rejected_reply_is_an_error() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "POST",
+        "/api/comment",
+        serde_json::json!({
+            "json": { "errors": [["THREAD_LOCKED", "that comment is locked", "parent"]] }
+        }),
+    )
+    .await;
+
+    let err = provider_for(&api)
+        .reply_to_notification(&ItemId("reddit:t1_c1".to_string()), "Thanks!")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, StreamError::Provider(message) if message.contains("THREAD_LOCKED")),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn subreddit_search_returns_ranked_posts() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/r/rust/search"))
+        .and(query_param("q", "borrow checker"))
+        .and(query_param("restrict_sr", "on"))
+        .and(query_param("sort", "top"))
+        .and(query_param("t", "month"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("reddit/listing_page1.json")),
+        )
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let options = SearchOptions {
+        limit: Some(2),
+        stream_id: Some(StreamId("reddit:feed:rust".to_string())),
+        sort: Some("top".to_string()),
+        time: Some("month".to_string()),
+        low_bandwidth: false,
+    };
+    let items = provider_for(&api)
+        .search("borrow checker", options)
+        .await
+        .unwrap();
+
+    let ranked: Vec<(&str, &str)> = items
+        .iter()
+        .map(|item| (item.id.as_str(), item.metadata["search_rank"].as_str()))
+        .collect();
+    assert_eq!(ranked, vec![("reddit:p1", "1"), ("reddit:p2", "2")]);
+}
+
+#[tokio::test]
+async fn communities_browse_join_and_leave() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/r/rust/new"))
+        .and(query_param("limit", "2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("reddit/listing_page1.json")),
+        )
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/subscribe"))
+        .and(body_string_contains("action=sub&"))
+        .and(body_string_contains("sr_name=rust"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/subscribe"))
+        .and(body_string_contains("action=unsub"))
+        .and(body_string_contains("sr_name=rust"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let communities = provider.as_communities().unwrap();
+    let community = CommunityId("r/rust".to_string());
+    let options = FeedOptions {
+        limit: Some(2),
+        ..Default::default()
+    };
+    let items = communities
+        .get_community_items(&community, options)
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 2);
+
+    communities.join_community(&community).await.unwrap();
+    communities.leave_community(&community).await.unwrap();
+
+    let err = communities
+        .join_community(&CommunityId("../api".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::StreamNotFound(_)), "{:?}", err);
+}
@@ -0,0 +1,930 @@
+//! # provider-releases
+//!
+//! Package release tracker for Scryforge.
+//!
+//! This provider watches a configured list of packages on crates.io, GitHub
+//! releases, PyPI, and Docker Hub, and shows their new versions as items.
+//!
+//! ## Features
+//!
+//! - A feed per watched package, plus "All Releases" across every package
+//! - GitHub release notes as a changelog excerpt; the package description
+//!   for registries without release notes
+//! - A `copy_upgrade_command` action with the command that installs the
+//!   version: `cargo add`, `pip install`, `docker pull`, or
+//!   `gh release download`
+//! - Yanked versions, draft releases, and Docker's `latest` tag are skipped;
+//!   pre-releases only show when [`ReleasesSettings::include_prereleases`]
+//!   is set
+//!
+//! ## Authentication
+//!
+//! Every registry is read anonymously. A GitHub token, fetched via the
+//! Sigilforge client under the service name "github", raises GitHub's rate
+//! limit when there is one.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+// ============================================================================
+// Registry API Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct CrateResponse {
+    #[serde(rename = "crate")]
+    krate: CrateInfo,
+    #[serde(default)]
+    versions: Vec<CrateVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateInfo {
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateVersion {
+    num: String,
+    created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    html_url: String,
+    published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyPiResponse {
+    info: PyPiInfo,
+    #[serde(default)]
+    releases: HashMap<String, Vec<PyPiFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyPiInfo {
+    summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyPiFile {
+    upload_time_iso_8601: Option<DateTime<Utc>>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DockerTags {
+    #[serde(default)]
+    results: Vec<DockerTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DockerTag {
+    name: String,
+    last_updated: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Settings
+// ============================================================================
+
+const PROVIDER_ID: &str = "releases";
+
+/// Local ID of the feed of every watched package.
+const ALL_FEED: &str = "all";
+
+/// Most changelog lines shown in an item.
+const EXCERPT_LINES: usize = 12;
+
+/// Most changelog characters shown in an item.
+const EXCERPT_CHARS: usize = 800;
+
+/// Where a watched package is published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Registry {
+    /// crates.io; the name is the crate name
+    Crates,
+    /// GitHub releases; the name is `owner/repo`
+    GitHub,
+    /// PyPI; the name is the project name
+    PyPi,
+    /// Docker Hub; the name is `namespace/repo`, or `repo` for official images
+    Docker,
+}
+
+impl Registry {
+    /// Short name used in IDs and settings.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Registry::Crates => "crates",
+            Registry::GitHub => "github",
+            Registry::PyPi => "pypi",
+            Registry::Docker => "docker",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [
+            Registry::Crates,
+            Registry::GitHub,
+            Registry::PyPi,
+            Registry::Docker,
+        ]
+        .into_iter()
+        .find(|registry| registry.as_str() == name)
+    }
+
+    /// Display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Registry::Crates => "crates.io",
+            Registry::GitHub => "GitHub",
+            Registry::PyPi => "PyPI",
+            Registry::Docker => "Docker Hub",
+        }
+    }
+}
+
+/// A package to watch for new versions.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct WatchedPackage {
+    pub registry: Registry,
+    pub name: String,
+}
+
+impl WatchedPackage {
+    pub fn new(registry: Registry, name: impl Into<String>) -> Self {
+        Self {
+            registry,
+            name: name.into(),
+        }
+    }
+
+    /// Local ID of the package's feed, `<registry>:<name>`.
+    fn key(&self) -> String {
+        format!("{}:{}", self.registry.as_str(), self.name)
+    }
+}
+
+/// Provider settings, read from `[providers.releases.settings]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ReleasesSettings {
+    /// Packages to watch
+    pub packages: Vec<WatchedPackage>,
+    /// Newest versions shown per package when the caller sets no limit
+    pub releases_per_package: u32,
+    /// Show pre-release versions (`1.0.0-beta.1`, `2.0rc1`, GitHub
+    /// pre-releases)
+    pub include_prereleases: bool,
+}
+
+impl Default for ReleasesSettings {
+    fn default() -> Self {
+        Self {
+            packages: Vec::new(),
+            releases_per_package: 5,
+            include_prereleases: false,
+        }
+    }
+}
+
+/// Base URLs of the registry APIs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoints {
+    pub crates_io: String,
+    pub github: String,
+    pub pypi: String,
+    pub docker_hub: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            crates_io: "https://crates.io".to_string(),
+            github: "https://api.github.com".to_string(),
+            pypi: "https://pypi.org".to_string(),
+            docker_hub: "https://hub.docker.com".to_string(),
+        }
+    }
+}
+
+impl Endpoints {
+    /// Every registry at one base URL, such as a mock server in tests.
+    pub fn all_at(base_url: &str) -> Self {
+        Self {
+            crates_io: base_url.to_string(),
+            github: base_url.to_string(),
+            pypi: base_url.to_string(),
+            docker_hub: base_url.to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// Releases
+// ============================================================================
+
+/// One published version of a watched package.
+#[derive(Debug, Clone, PartialEq)]
+struct Release {
+    version: String,
+    /// Release title, when it says more than the version
+    title: Option<String>,
+    published: Option<DateTime<Utc>>,
+    url: String,
+    /// Release notes, Markdown
+    notes: Option<String>,
+    /// Package description, for registries without release notes
+    description: Option<String>,
+    prerelease: bool,
+}
+
+/// Whether a crates.io or PyPI version string names a pre-release.
+///
+/// SemVer marks them with a `-` suffix, PEP 440 with letters (`a1`, `b2`,
+/// `rc1`, `.dev0`).
+fn is_prerelease(registry: Registry, version: &str) -> bool {
+    match registry {
+        Registry::Crates => version.contains('-'),
+        Registry::PyPi => version.chars().any(|c| c.is_ascii_alphabetic()),
+        Registry::GitHub | Registry::Docker => false,
+    }
+}
+
+/// The first lines of release notes, cut at a line or character limit.
+fn excerpt(notes: &str) -> String {
+    let mut excerpt = notes
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| line.is_empty())
+        .take(EXCERPT_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut truncated = excerpt.lines().count() < notes.trim().lines().count();
+
+    if excerpt.chars().count() > EXCERPT_CHARS {
+        excerpt = excerpt.chars().take(EXCERPT_CHARS).collect();
+        truncated = true;
+    }
+    let mut excerpt = excerpt.trim_end().to_string();
+    if truncated {
+        excerpt.push_str("\n…");
+    }
+    excerpt
+}
+
+/// The command that installs `version` of `package`.
+fn upgrade_command(package: &WatchedPackage, version: &str) -> String {
+    match package.registry {
+        Registry::Crates => format!("cargo add {}@{}", package.name, version),
+        Registry::GitHub => format!("gh release download {} --repo {}", version, package.name),
+        Registry::PyPi => format!("pip install --upgrade '{}=={}'", package.name, version),
+        Registry::Docker => format!("docker pull {}:{}", package.name, version),
+    }
+}
+
+/// Docker Hub's `namespace/repo`, with official images under `library`.
+fn docker_repository(name: &str) -> String {
+    if name.contains('/') {
+        name.to_string()
+    } else {
+        format!("library/{}", name)
+    }
+}
+
+// ============================================================================
+// Provider
+// ============================================================================
+
+/// Package release tracker for Scryforge.
+pub struct ReleasesProvider {
+    token_fetcher: Arc<dyn TokenFetcher>,
+    account: String,
+    settings: ReleasesSettings,
+    endpoints: Endpoints,
+    client: Client,
+}
+
+impl ReleasesProvider {
+    /// Create a new release tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_fetcher` - Token fetcher for the optional GitHub token
+    /// * `account` - Account identifier for the token (e.g., "personal")
+    pub fn new(token_fetcher: Arc<dyn TokenFetcher>, account: String) -> Self {
+        Self::with_endpoints(token_fetcher, account, Endpoints::default())
+    }
+
+    /// Create a tracker that talks to different registry URLs, such as a mock
+    /// server in tests.
+    pub fn with_endpoints(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        account: String,
+        endpoints: Endpoints,
+    ) -> Self {
+        // crates.io rejects requests without a User-Agent
+        let client = Client::builder()
+            .user_agent("scryforge/0.1.0")
+            .build()
+            .unwrap();
+
+        Self {
+            token_fetcher,
+            account,
+            settings: ReleasesSettings::default(),
+            endpoints,
+            client,
+        }
+    }
+
+    /// Replace the provider settings.
+    pub fn with_settings(mut self, settings: ReleasesSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        label: &str,
+    ) -> Result<T> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok());
+            let body = response.text().await.unwrap_or_default();
+            // GitHub answers an exhausted anonymous quota with 403
+            if status.as_u16() == 403 && body.contains("rate limit") {
+                return Err(StreamError::RateLimited(retry_after.unwrap_or(60)));
+            }
+            if status.as_u16() == 404 {
+                return Err(StreamError::StreamNotFound(format!("{} not found", label)));
+            }
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                retry_after,
+                format!("{} API error ({}): {}", label, status, body),
+            ));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))
+    }
+
+    /// The newest releases of `package`, newest first.
+    async fn fetch_releases(&self, package: &WatchedPackage, limit: usize) -> Result<Vec<Release>> {
+        let mut releases = match package.registry {
+            Registry::Crates => self.fetch_crate(&package.name).await?,
+            Registry::GitHub => self.fetch_github(&package.name, limit).await?,
+            Registry::PyPi => self.fetch_pypi(&package.name).await?,
+            Registry::Docker => self.fetch_docker(&package.name, limit).await?,
+        };
+        if !self.settings.include_prereleases {
+            releases.retain(|release| !release.prerelease);
+        }
+        releases.sort_by(|a, b| b.published.cmp(&a.published));
+        releases.truncate(limit);
+        Ok(releases)
+    }
+
+    async fn fetch_crate(&self, name: &str) -> Result<Vec<Release>> {
+        let url = format!("{}/api/v1/crates/{}", self.endpoints.crates_io, name);
+        let response: CrateResponse = self.get_json(self.client.get(url), name).await?;
+        let description = response.krate.description.map(|d| d.trim().to_string());
+        Ok(response
+            .versions
+            .into_iter()
+            .filter(|version| !version.yanked)
+            .map(|version| Release {
+                url: format!("https://crates.io/crates/{}/{}", name, version.num),
+                prerelease: is_prerelease(Registry::Crates, &version.num),
+                version: version.num,
+                title: None,
+                published: version.created_at,
+                notes: None,
+                description: description.clone(),
+            })
+            .collect())
+    }
+
+    async fn fetch_github(&self, repo: &str, limit: usize) -> Result<Vec<Release>> {
+        let url = format!("{}/repos/{}/releases", self.endpoints.github, repo);
+        let mut request = self
+            .client
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .query(&[("per_page", limit.clamp(1, 100).to_string())]);
+        if let Ok(token) = self
+            .token_fetcher
+            .fetch_token("github", &self.account)
+            .await
+        {
+            request = request.bearer_auth(token);
+        }
+
+        let releases: Vec<GitHubRelease> = self.get_json(request, repo).await?;
+        Ok(releases
+            .into_iter()
+            .filter(|release| !release.draft)
+            .map(|release| Release {
+                title: release
+                    .name
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty() && *name != release.tag_name),
+                version: release.tag_name,
+                published: release.published_at,
+                url: release.html_url,
+                notes: release.body.filter(|body| !body.trim().is_empty()),
+                description: None,
+                prerelease: release.prerelease,
+            })
+            .collect())
+    }
+
+    async fn fetch_pypi(&self, name: &str) -> Result<Vec<Release>> {
+        let url = format!("{}/pypi/{}/json", self.endpoints.pypi, name);
+        let response: PyPiResponse = self.get_json(self.client.get(url), name).await?;
+        let description = response.info.summary.map(|s| s.trim().to_string());
+        Ok(response
+            .releases
+            .into_iter()
+            // Versions without files, or with only yanked ones, can't be
+            // installed
+            .filter(|(_, files)| files.iter().any(|file| !file.yanked))
+            .map(|(version, files)| Release {
+                url: format!("https://pypi.org/project/{}/{}/", name, version),
+                prerelease: is_prerelease(Registry::PyPi, &version),
+                published: files
+                    .iter()
+                    .filter_map(|file| file.upload_time_iso_8601)
+                    .min(),
+                version,
+                title: None,
+                notes: None,
+                description: description.clone(),
+            })
+            .collect())
+    }
+
+    async fn fetch_docker(&self, name: &str, limit: usize) -> Result<Vec<Release>> {
+        let repository = docker_repository(name);
+        let url = format!(
+            "{}/v2/repositories/{}/tags",
+            self.endpoints.docker_hub, repository
+        );
+        // One extra for `latest`, which is skipped
+        let request = self.client.get(url).query(&[
+            ("page_size", (limit + 1).clamp(1, 100).to_string()),
+            ("ordering", "last_updated".to_string()),
+        ]);
+        let tags: DockerTags = self.get_json(request, name).await?;
+        let page = match repository.strip_prefix("library/") {
+            Some(official) => format!("https://hub.docker.com/_/{}", official),
+            None => format!("https://hub.docker.com/r/{}", repository),
+        };
+        Ok(tags
+            .results
+            .into_iter()
+            .filter(|tag| tag.name != "latest")
+            .map(|tag| Release {
+                url: format!("{}/tags?name={}", page, tag.name),
+                version: tag.name,
+                title: None,
+                published: tag.last_updated,
+                notes: None,
+                description: None,
+                prerelease: false,
+            })
+            .collect())
+    }
+
+    fn release_to_item(&self, package: &WatchedPackage, release: &Release, stream: &str) -> Item {
+        let command = upgrade_command(package, &release.version);
+        let short_name = package.name.rsplit('/').next().unwrap_or(&package.name);
+        let title = match &release.title {
+            Some(title) => format!("{} {}: {}", short_name, release.version, title),
+            None => format!("{} {}", short_name, release.version),
+        };
+        let content = match (&release.notes, &release.description) {
+            (Some(notes), _) => ItemContent::Markdown(excerpt(notes)),
+            (None, Some(description)) => ItemContent::Text(description.clone()),
+            (None, None) => ItemContent::Text(String::new()),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "registry".to_string(),
+            package.registry.as_str().to_string(),
+        );
+        metadata.insert("package".to_string(), package.name.clone());
+        metadata.insert("version".to_string(), release.version.clone());
+        metadata.insert("upgrade_command".to_string(), command);
+        if release.prerelease {
+            metadata.insert("prerelease".to_string(), "true".to_string());
+        }
+
+        Item {
+            id: ItemId::new(
+                PROVIDER_ID,
+                &format!("{}@{}", package.key(), release.version),
+            ),
+            stream_id: StreamId::new(PROVIDER_ID, "feed", stream),
+            title,
+            content,
+            author: None,
+            published: release.published,
+            updated: None,
+            url: Some(release.url.clone()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![package.registry.as_str().to_string()],
+            metadata,
+        }
+    }
+
+    fn find_package(&self, key: &str) -> Option<&WatchedPackage> {
+        let (registry, name) = key.split_once(':')?;
+        let registry = Registry::parse(registry)?;
+        self.settings
+            .packages
+            .iter()
+            .find(|package| package.registry == registry && package.name == name)
+    }
+}
+
+#[async_trait]
+impl Provider for ReleasesProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Releases"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        Ok(ProviderHealth {
+            is_healthy: true,
+            message: Some(format!(
+                "Watching {} packages",
+                self.settings.packages.len()
+            )),
+            last_sync: None,
+            error_count: 0,
+        })
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = Instant::now();
+        let limit = self.settings.releases_per_package as usize;
+
+        // One unreachable registry shouldn't hide the other packages
+        let mut items_added = 0;
+        let mut errors = Vec::new();
+        for package in &self.settings.packages {
+            match self.fetch_releases(package, limit).await {
+                Ok(releases) => items_added += releases.len() as u32,
+                Err(e) => errors.push(format!("{}: {}", package.key(), e)),
+            }
+        }
+
+        Ok(SyncResult {
+            success: errors.is_empty(),
+            items_added,
+            items_updated: 0,
+            items_removed: 0,
+            errors,
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: false,
+            supports_pagination: false,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
+        Ok(vec![
+            Action {
+                id: "open_browser".to_string(),
+                name: "Open in Browser".to_string(),
+                description: "Open the release page in the default browser".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy the release page URL to the clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+            Action {
+                id: "copy_upgrade_command".to_string(),
+                name: "Copy Upgrade Command".to_string(),
+                description: "Copy the command that installs this version".to_string(),
+                kind: ActionKind::Custom("copy_upgrade_command".to_string()),
+                keyboard_shortcut: Some("y".to_string()),
+            },
+        ])
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match &action.kind {
+            ActionKind::OpenInBrowser | ActionKind::CopyLink => Ok(match &item.url {
+                Some(url) => ActionResult {
+                    success: true,
+                    message: Some(format!("Release page: {}", url)),
+                    data: Some(serde_json::json!({ "url": url })),
+                },
+                None => ActionResult {
+                    success: false,
+                    message: Some("Item has no URL".to_string()),
+                    data: None,
+                },
+            }),
+            ActionKind::Custom(name) if name == "copy_upgrade_command" => {
+                Ok(match item.metadata.get("upgrade_command") {
+                    Some(command) => ActionResult {
+                        success: true,
+                        message: Some(format!("Copied: {}", command)),
+                        data: Some(serde_json::json!({ "text": command })),
+                    },
+                    None => ActionResult {
+                        success: false,
+                        message: Some("Item has no upgrade command".to_string()),
+                        data: None,
+                    },
+                })
+            }
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some(format!("Unsupported action: {}", action.name)),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for ReleasesProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let mut feeds = vec![Feed {
+            id: FeedId(format!("{}:{}", PROVIDER_ID, ALL_FEED)),
+            name: "All Releases".to_string(),
+            description: Some("New versions of every watched package".to_string()),
+            icon: Some("📦".to_string()),
+            unread_count: None,
+            total_count: None,
+        }];
+        feeds.extend(self.settings.packages.iter().map(|package| Feed {
+            id: FeedId(format!("{}:{}", PROVIDER_ID, package.key())),
+            name: package.name.clone(),
+            description: Some(format!("Releases on {}", package.registry.label())),
+            icon: Some("📦".to_string()),
+            unread_count: None,
+            total_count: None,
+        }));
+        Ok(feeds)
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        let local = feed_id
+            .0
+            .strip_prefix(PROVIDER_ID)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+        let per_package = options.limit.unwrap_or(self.settings.releases_per_package) as usize;
+
+        let mut items = Vec::new();
+        if local == ALL_FEED {
+            for package in &self.settings.packages {
+                let releases = self.fetch_releases(package, per_package).await?;
+                items.extend(
+                    releases
+                        .iter()
+                        .map(|release| self.release_to_item(package, release, ALL_FEED)),
+                );
+            }
+            items.sort_by(|a, b| b.published.cmp(&a.published));
+        } else {
+            let package = self
+                .find_package(local)
+                .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
+            let releases = self.fetch_releases(package, per_package).await?;
+            items.extend(
+                releases
+                    .iter()
+                    .map(|release| self.release_to_item(package, release, local)),
+            );
+        }
+
+        if let Some(since) = options.since {
+            items.retain(|item| item.published.map_or(true, |published| published >= since));
+        }
+        if let Some(limit) = options.limit {
+            items.truncate(limit as usize);
+        }
+        Ok(items)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::auth::MockTokenFetcher;
+
+    fn provider() -> ReleasesProvider {
+        ReleasesProvider::new(Arc::new(MockTokenFetcher::empty()), "personal".to_string())
+            .with_settings(ReleasesSettings {
+                packages: vec![
+                    WatchedPackage::new(Registry::Crates, "serde"),
+                    WatchedPackage::new(Registry::GitHub, "rust-lang/rust"),
+                ],
+                ..Default::default()
+            })
+    }
+
+    #[test]
+    fn test_settings_deserialize() {
+        let settings: ReleasesSettings = serde_json::from_value(serde_json::json!({
+            "packages": [
+                { "registry": "crates", "name": "tokio" },
+                { "registry": "github", "name": "neovim/neovim" },
+                { "registry": "pypi", "name": "requests" },
+                { "registry": "docker", "name": "postgres" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(settings.packages.len(), 4);
+        assert_eq!(settings.packages[2].registry, Registry::PyPi);
+        assert_eq!(settings.releases_per_package, 5);
+        assert!(!settings.include_prereleases);
+    }
+
+    #[test]
+    fn test_prerelease_detection() {
+        assert!(is_prerelease(Registry::Crates, "1.0.0-beta.1"));
+        assert!(!is_prerelease(Registry::Crates, "1.0.0"));
+        assert!(is_prerelease(Registry::PyPi, "2.0rc1"));
+        assert!(is_prerelease(Registry::PyPi, "1.4.dev0"));
+        assert!(!is_prerelease(Registry::PyPi, "2.31.0"));
+    }
+
+    #[test]
+    fn test_upgrade_commands() {
+        let cases = [
+            (
+                Registry::Crates,
+                "serde",
+                "1.0.200",
+                "cargo add serde@1.0.200",
+            ),
+            (
+                Registry::GitHub,
+                "neovim/neovim",
+                "v0.10.0",
+                "gh release download v0.10.0 --repo neovim/neovim",
+            ),
+            (
+                Registry::PyPi,
+                "requests",
+                "2.31.0",
+                "pip install --upgrade 'requests==2.31.0'",
+            ),
+            (
+                Registry::Docker,
+                "postgres",
+                "16.3",
+                "docker pull postgres:16.3",
+            ),
+        ];
+        for (registry, name, version, command) in cases {
+            let package = WatchedPackage::new(registry, name);
+            assert_eq!(upgrade_command(&package, version), command);
+        }
+        assert_eq!(docker_repository("postgres"), "library/postgres");
+        assert_eq!(docker_repository("grafana/grafana"), "grafana/grafana");
+    }
+
+    #[test]
+    fn test_excerpt_cuts_long_notes() {
+        assert_eq!(excerpt("\n## Fixed\n- a bug\n"), "## Fixed\n- a bug");
+
+        let long: String = (1..=20).map(|n| format!("- change {}\n", n)).collect();
+        let cut = excerpt(&long);
+        assert_eq!(cut.lines().count(), EXCERPT_LINES + 1);
+        assert!(cut.ends_with("- change 12\n…"));
+
+        let wide = "x".repeat(EXCERPT_CHARS * 2);
+        assert_eq!(excerpt(&wide).chars().count(), EXCERPT_CHARS + 2);
+    }
+
+    #[test]
+    fn test_release_item() {
+        let provider = provider();
+        let package = WatchedPackage::new(Registry::GitHub, "rust-lang/rust");
+        let release = Release {
+            version: "1.80.0".to_string(),
+            title: Some("Rust 1.80.0".to_string()),
+            published: None,
+            url: "https://github.com/rust-lang/rust/releases/tag/1.80.0".to_string(),
+            notes: Some("## Language\n- LazyCell".to_string()),
+            description: None,
+            prerelease: false,
+        };
+        let item = provider.release_to_item(&package, &release, "github:rust-lang/rust");
+
+        assert_eq!(item.id.as_str(), "releases:github:rust-lang/rust@1.80.0");
+        assert_eq!(
+            item.stream_id.as_str(),
+            "releases:feed:github:rust-lang/rust"
+        );
+        assert_eq!(item.title, "rust 1.80.0: Rust 1.80.0");
+        assert!(matches!(item.content, ItemContent::Markdown(_)));
+        assert_eq!(
+            item.metadata["upgrade_command"],
+            "gh release download 1.80.0 --repo rust-lang/rust"
+        );
+        assert!(!item.metadata.contains_key("prerelease"));
+    }
+
+    #[tokio::test]
+    async fn test_feeds_list_every_package() {
+        let provider = provider();
+        let feeds = provider.list_feeds().await.unwrap();
+        let ids: Vec<&str> = feeds.iter().map(|f| f.id.0.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "releases:all",
+                "releases:crates:serde",
+                "releases:github:rust-lang/rust"
+            ]
+        );
+
+        let err = provider
+            .get_feed_items(
+                &FeedId("releases:pypi:requests".to_string()),
+                FeedOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StreamError::StreamNotFound(_)));
+    }
+}
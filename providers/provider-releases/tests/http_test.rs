@@ -0,0 +1,359 @@
+//! Mock-server tests for `provider-releases`'s HTTP paths.
+
+use std::sync::Arc;
+
+use provider_releases::{Endpoints, Registry, ReleasesProvider, ReleasesSettings, WatchedPackage};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{header, method, path, query_param};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::MockApi;
+use serde_json::json;
+
+fn provider_for(api: &MockApi, packages: Vec<WatchedPackage>) -> ReleasesProvider {
+    ReleasesProvider::with_endpoints(
+        Arc::new(MockTokenFetcher::empty()),
+        "personal".to_string(),
+        Endpoints::all_at(&api.uri()),
+    )
+    .with_settings(ReleasesSettings {
+        packages,
+        ..Default::default()
+    })
+}
+
+fn feed(id: &str) -> FeedId {
+    FeedId(format!("releases:{}", id))
+}
+
+async fn mock_crate(api: &MockApi) {
+    api.mock_json(
+        "GET",
+        "/api/v1/crates/serde",
+        json!({
+            "crate": { "name": "serde", "description": "A serialization framework" },
+            "versions": [
+                { "num": "1.0.201", "created_at": "2024-05-01T12:00:00Z", "yanked": true },
+                { "num": "1.0.200", "created_at": "2024-04-20T12:00:00Z", "yanked": false },
+                { "num": "2.0.0-alpha.1", "created_at": "2024-04-25T12:00:00Z", "yanked": false },
+                { "num": "1.0.199", "created_at": "2024-04-10T12:00:00Z", "yanked": false }
+            ]
+        }),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn crates_skip_yanked_and_prereleases() {
+    let api = MockApi::start().await;
+    mock_crate(&api).await;
+
+    let provider = provider_for(&api, vec![WatchedPackage::new(Registry::Crates, "serde")]);
+    let items = provider
+        .get_feed_items(&feed("crates:serde"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["serde 1.0.200", "serde 1.0.199"]);
+    let newest = &items[0];
+    assert_eq!(newest.id.as_str(), "releases:crates:serde@1.0.200");
+    assert_eq!(
+        newest.url.as_deref(),
+        Some("https://crates.io/crates/serde/1.0.200")
+    );
+    assert!(
+        matches!(&newest.content, ItemContent::Text(text) if text == "A serialization framework")
+    );
+    assert_eq!(
+        newest.metadata["upgrade_command"],
+        "cargo add serde@1.0.200"
+    );
+}
+
+#[tokio::test]
+async fn prereleases_show_when_enabled() {
+    let api = MockApi::start().await;
+    mock_crate(&api).await;
+
+    let provider = provider_for(&api, Vec::new()).with_settings(ReleasesSettings {
+        packages: vec![WatchedPackage::new(Registry::Crates, "serde")],
+        include_prereleases: true,
+        ..Default::default()
+    });
+    let items = provider
+        .get_feed_items(&feed("crates:serde"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].title, "serde 2.0.0-alpha.1");
+    assert_eq!(items[0].metadata["prerelease"], "true");
+}
+
+#[tokio::test]
+async fn github_releases_carry_changelog_excerpts() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/neovim/neovim/releases"))
+        .and(query_param("per_page", "5"))
+        .and(header("Accept", "application/vnd.github+json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "tag_name": "v0.11.0-dev",
+                "name": "Nightly",
+                "body": "Nightly build",
+                "html_url": "https://github.com/neovim/neovim/releases/tag/nightly",
+                "published_at": "2024-06-01T00:00:00Z",
+                "prerelease": true,
+                "draft": false
+            },
+            {
+                "tag_name": "v0.10.1",
+                "name": "Nvim 0.10.1",
+                "body": "## Bug fixes\n- treesitter: fix crash",
+                "html_url": "https://github.com/neovim/neovim/releases/tag/v0.10.1",
+                "published_at": "2024-05-20T00:00:00Z",
+                "prerelease": false,
+                "draft": false
+            },
+            {
+                "tag_name": "v0.10.2",
+                "name": null,
+                "body": null,
+                "html_url": "https://github.com/neovim/neovim/releases/tag/untagged-1",
+                "published_at": null,
+                "prerelease": false,
+                "draft": true
+            }
+        ])))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(
+        &api,
+        vec![WatchedPackage::new(Registry::GitHub, "neovim/neovim")],
+    );
+    let items = provider
+        .get_feed_items(&feed("github:neovim/neovim"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item.title, "neovim v0.10.1: Nvim 0.10.1");
+    assert!(
+        matches!(&item.content, ItemContent::Markdown(notes) if notes == "## Bug fixes\n- treesitter: fix crash")
+    );
+    assert_eq!(
+        item.metadata["upgrade_command"],
+        "gh release download v0.10.1 --repo neovim/neovim"
+    );
+}
+
+#[tokio::test]
+async fn github_token_is_sent_when_available() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/neovim/neovim/releases"))
+        .and(header("Authorization", "Bearer gh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let tokens = MockTokenFetcher::empty().with_token(
+        "github".to_string(),
+        "personal".to_string(),
+        "gh-token".to_string(),
+    );
+    let provider = ReleasesProvider::with_endpoints(
+        Arc::new(tokens),
+        "personal".to_string(),
+        Endpoints::all_at(&api.uri()),
+    )
+    .with_settings(ReleasesSettings {
+        packages: vec![WatchedPackage::new(Registry::GitHub, "neovim/neovim")],
+        ..Default::default()
+    });
+    let items = provider
+        .get_feed_items(&feed("github:neovim/neovim"), FeedOptions::default())
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+}
+
+#[tokio::test]
+async fn pypi_versions_need_installable_files() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/pypi/requests/json",
+        json!({
+            "info": { "name": "requests", "summary": "Python HTTP for Humans." },
+            "releases": {
+                "2.32.0": [
+                    { "upload_time_iso_8601": "2024-05-20T10:00:00.000000Z", "yanked": true }
+                ],
+                "2.31.0": [
+                    { "upload_time_iso_8601": "2023-05-22T15:12:44.000000Z", "yanked": false },
+                    { "upload_time_iso_8601": "2023-05-22T15:12:42.000000Z", "yanked": false }
+                ],
+                "2.30.0": [
+                    { "upload_time_iso_8601": "2023-05-03T15:30:13.000000Z", "yanked": false }
+                ],
+                "2.0.0": []
+            }
+        }),
+    )
+    .await;
+
+    let provider = provider_for(&api, vec![WatchedPackage::new(Registry::PyPi, "requests")]);
+    let items = provider
+        .get_feed_items(&feed("pypi:requests"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let versions: Vec<&str> = items
+        .iter()
+        .map(|item| item.metadata["version"].as_str())
+        .collect();
+    assert_eq!(versions, vec!["2.31.0", "2.30.0"]);
+    assert_eq!(
+        items[0].published.unwrap().to_rfc3339(),
+        "2023-05-22T15:12:42+00:00"
+    );
+    assert_eq!(
+        items[0].metadata["upgrade_command"],
+        "pip install --upgrade 'requests==2.31.0'"
+    );
+}
+
+#[tokio::test]
+async fn docker_official_images_use_the_library_namespace() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v2/repositories/library/postgres/tags"))
+        .and(query_param("ordering", "last_updated"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "count": 2,
+            "results": [
+                { "name": "latest", "last_updated": "2024-05-10T00:00:00Z" },
+                { "name": "16.3", "last_updated": "2024-05-10T00:00:00Z" }
+            ]
+        })))
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(
+        &api,
+        vec![WatchedPackage::new(Registry::Docker, "postgres")],
+    );
+    let items = provider
+        .get_feed_items(&feed("docker:postgres"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(
+        items[0].url.as_deref(),
+        Some("https://hub.docker.com/_/postgres/tags?name=16.3")
+    );
+
+    let action = provider
+        .available_actions(&items[0])
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|action| action.id == "copy_upgrade_command")
+        .unwrap();
+    let result = provider.execute_action(&items[0], &action).await.unwrap();
+    assert!(result.success);
+    assert_eq!(
+        result.data,
+        Some(json!({ "text": "docker pull postgres:16.3" }))
+    );
+}
+
+#[tokio::test]
+async fn all_feed_merges_packages_newest_first() {
+    let api = MockApi::start().await;
+    mock_crate(&api).await;
+    api.mock_json(
+        "GET",
+        "/pypi/requests/json",
+        json!({
+            "info": { "summary": "Python HTTP for Humans." },
+            "releases": {
+                "2.31.0": [{ "upload_time_iso_8601": "2024-04-15T00:00:00Z" }]
+            }
+        }),
+    )
+    .await;
+
+    let provider = provider_for(
+        &api,
+        vec![
+            WatchedPackage::new(Registry::Crates, "serde"),
+            WatchedPackage::new(Registry::PyPi, "requests"),
+        ],
+    );
+    let items = provider
+        .get_feed_items(&feed("all"), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "releases:crates:serde@1.0.200",
+            "releases:pypi:requests@2.31.0",
+            "releases:crates:serde@1.0.199"
+        ]
+    );
+    assert!(items
+        .iter()
+        .all(|item| item.stream_id.as_str() == "releases:feed:all"));
+}
+
+#[tokio::test]
+async fn sync_reports_failing_packages() {
+    let api = MockApi::start().await;
+    mock_crate(&api).await;
+    api.mock_status(
+        "GET",
+        "/api/v1/crates/no-such-crate",
+        404,
+        json!({ "errors": [{ "detail": "Not Found" }] }),
+    )
+    .await;
+
+    let provider = provider_for(
+        &api,
+        vec![
+            WatchedPackage::new(Registry::Crates, "serde"),
+            WatchedPackage::new(Registry::Crates, "no-such-crate"),
+        ],
+    );
+    let result = provider.sync().await.unwrap();
+
+    assert!(!result.success);
+    assert_eq!(result.items_added, 2);
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].starts_with("crates:no-such-crate"));
+}
+
+#[tokio::test]
+async fn rate_limit_carries_retry_after() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/pypi/requests/json", 30).await;
+
+    let provider = provider_for(&api, vec![WatchedPackage::new(Registry::PyPi, "requests")]);
+    let err = provider
+        .get_feed_items(&feed("pypi:requests"), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(30)), "{:?}", err);
+}
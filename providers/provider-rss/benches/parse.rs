@@ -0,0 +1,83 @@
+//! Benchmarks for converting RSS and Atom documents into items.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use provider_rss::{RssProvider, RssProviderConfig};
+use scryforge_provider_core::FeedId;
+
+const ENTRIES: usize = 500;
+
+fn rss_document() -> String {
+    let items: String = (0..ENTRIES)
+        .map(|n| {
+            format!(
+                "<item>\
+                 <title>Post {n}</title>\
+                 <link>https://example.com/posts/{n}</link>\
+                 <guid>https://example.com/posts/{n}</guid>\
+                 <pubDate>Mon, 01 Jan 2024 12:00:00 GMT</pubDate>\
+                 <author>writer@example.com (Writer)</author>\
+                 <category>rust</category>\
+                 <description><![CDATA[<p>Summary of post {n} with <b>markup</b>.</p>]]></description>\
+                 </item>"
+            )
+        })
+        .collect();
+    format!(
+        "<?xml version=\"1.0\"?><rss version=\"2.0\"><channel>\
+         <title>Bench</title><link>https://example.com</link>\
+         <description>Benchmark feed</description>{}</channel></rss>",
+        items
+    )
+}
+
+fn atom_document() -> String {
+    let body = "<p>Full article text.</p>".repeat(40);
+    let entries: String = (0..ENTRIES)
+        .map(|n| {
+            format!(
+                "<entry>\
+                 <title>Entry {n}</title>\
+                 <id>urn:uuid:entry-{n}</id>\
+                 <link rel=\"alternate\" href=\"https://example.com/entries/{n}\"/>\
+                 <updated>2024-01-01T12:00:00Z</updated>\
+                 <author><name>Writer</name></author>\
+                 <summary>Summary of entry {n}</summary>\
+                 <content type=\"html\">{body}</content>\
+                 </entry>",
+                body = body.replace('<', "&lt;").replace('>', "&gt;")
+            )
+        })
+        .collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\
+         <title>Bench</title><id>urn:uuid:bench</id>\
+         <updated>2024-01-01T12:00:00Z</updated>{}</feed>",
+        entries
+    )
+}
+
+fn bench_parse_items(c: &mut Criterion) {
+    let provider = RssProvider::new(RssProviderConfig::new(vec![]));
+    let feed_id = FeedId("rss:0".to_string());
+    let mut group = c.benchmark_group("parse_feed");
+    group.throughput(Throughput::Elements(ENTRIES as u64));
+
+    for (name, document) in [("rss", rss_document()), ("atom", atom_document())] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                provider
+                    .parse_items(
+                        &feed_id,
+                        "https://example.com/feed",
+                        black_box(document.as_bytes()),
+                    )
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse_items);
+criterion_main!(benches);
@@ -9,6 +9,8 @@
 //! - OPML import for bulk feed subscription
 //! - Both RSS and Atom formats via feed-rs
 //! - Article content extraction
+//! - Clean item links, with tracking parameters removed and, optionally, link
+//!   shorteners resolved
 //!
 //! ## Configuration
 //!
@@ -22,6 +24,7 @@
 //!         "https://example.com/feed.xml".to_string(),
 //!         "https://blog.example.com/atom.xml".to_string(),
 //!     ],
+//!     resolve_redirects: false,
 //! };
 //! let provider = RssProvider::new(config);
 //! ```
@@ -43,7 +46,9 @@ use async_trait::async_trait;
 use chrono::Utc;
 use feed_rs::parser;
 use reqwest::Client;
+use scryforge_provider_core::http::{check_reachable, client_builder, ProxyConfig};
 use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::{canonicalize_url, is_shortener};
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::collections::HashMap;
@@ -75,7 +80,10 @@ pub enum RssError {
 impl From<RssError> for StreamError {
     fn from(err: RssError) -> Self {
         match err {
-            RssError::Http(e) => StreamError::Network(e.to_string()),
+            RssError::Http(e) => match e.status() {
+                Some(status) => StreamError::from_status(status.as_u16(), None, e.to_string()),
+                None => StreamError::Network(e.to_string()),
+            },
             RssError::Parse(e) => StreamError::Provider(format!("Feed parsing error: {e}")),
             RssError::Opml(e) => StreamError::Provider(format!("OPML parsing error: {e}")),
             RssError::Io(e) => StreamError::Internal(format!("IO error: {e}")),
@@ -93,12 +101,20 @@ impl From<RssError> for StreamError {
 pub struct RssProviderConfig {
     /// List of feed URLs to fetch
     pub feeds: Vec<String>,
+    /// Follow item links that point at a link shortener, such as `t.co` or
+    /// FeedBurner's `feedproxy.google.com`, to store the page they lead to.
+    /// Costs a request per shortened link.
+    #[serde(default)]
+    pub resolve_redirects: bool,
 }
 
 impl RssProviderConfig {
     /// Create a new configuration with the given feed URLs.
     pub fn new(feeds: Vec<String>) -> Self {
-        Self { feeds }
+        Self {
+            feeds,
+            resolve_redirects: false,
+        }
     }
 
     /// Create a configuration from an OPML file.
@@ -116,7 +132,7 @@ impl RssProviderConfig {
         let mut feeds = Vec::new();
         Self::extract_feeds_from_outline(&document.body.outlines, &mut feeds);
 
-        Ok(Self { feeds })
+        Ok(Self::new(feeds))
     }
 
     /// Recursively extract feed URLs from OPML outlines.
@@ -145,28 +161,80 @@ impl RssProviderConfig {
 pub struct RssProvider {
     config: RssProviderConfig,
     client: Client,
+    proxy: Option<ProxyConfig>,
 }
 
 impl RssProvider {
     /// Create a new RSS provider with the given configuration.
     pub fn new(config: RssProviderConfig) -> Self {
-        let client = Client::builder()
-            .user_agent("Scryforge/0.1.0")
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
+        let client = client_builder(None)
+            .ok()
+            .and_then(|builder| builder.build().ok())
             .unwrap_or_default();
 
-        Self { config, client }
+        Self {
+            config,
+            client,
+            proxy: None,
+        }
     }
 
-    /// Fetch and parse a feed from a URL.
-    async fn fetch_feed(&self, url: &str) -> std::result::Result<feed_rs::model::Feed, RssError> {
+    /// Fetch feeds through `proxy`, which also makes `.onion` feeds
+    /// reachable when it is a Tor proxy.
+    pub fn with_proxy(mut self, proxy: &ProxyConfig) -> Result<Self> {
+        self.client = client_builder(Some(proxy))?
+            .build()
+            .map_err(|e| StreamError::Internal(format!("Failed to build HTTP client: {e}")))?;
+        self.proxy = Some(proxy.clone());
+        Ok(self)
+    }
+
+    /// Replace shortened item links with the canonical URL they redirect to.
+    /// Links that fail to resolve are kept as they are.
+    async fn resolve_redirects(&self, items: &mut [Item]) {
+        for item in items {
+            let Some(url) = item.url.as_deref().filter(|url| is_shortener(url)) else {
+                continue;
+            };
+            // The client follows redirects; the response URL is the last hop
+            if let Ok(response) = self.client.head(url).send().await {
+                item.url = Some(canonicalize_url(response.url().as_str()));
+            }
+        }
+    }
+
+    /// Fetch a feed document from a URL.
+    async fn fetch_content(&self, url: &str) -> std::result::Result<Vec<u8>, RssError> {
+        check_reachable(url, self.proxy.as_ref())
+            .map_err(|e| RssError::InvalidUrl(e.to_string()))?;
         let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
 
-        let content = response.bytes().await?;
+    /// Fetch and parse a feed from a URL.
+    async fn fetch_feed(&self, url: &str) -> std::result::Result<feed_rs::model::Feed, RssError> {
+        let content = self.fetch_content(url).await?;
         parser::parse(&content[..]).map_err(|e| RssError::Parse(e.to_string()))
     }
 
+    /// Parse an RSS or Atom document fetched from `feed_url` into items of
+    /// the feed `feed_id`, in document order.
+    pub fn parse_items(
+        &self,
+        feed_id: &FeedId,
+        feed_url: &str,
+        content: &[u8],
+    ) -> std::result::Result<Vec<Item>, RssError> {
+        let feed = parser::parse(content).map_err(|e| RssError::Parse(e.to_string()))?;
+        let stream_id = StreamId::new("rss", "feed", &feed_id.0);
+
+        Ok(feed
+            .entries
+            .iter()
+            .map(|entry| self.entry_to_item(entry, &stream_id, feed_url))
+            .collect())
+    }
+
     /// Convert a feed-rs entry to a Scryforge Item.
     fn entry_to_item(
         &self,
@@ -206,7 +274,7 @@ impl RssProvider {
             .iter()
             .find(|link| link.rel.as_deref() == Some("alternate"))
             .or_else(|| entry.links.first())
-            .map(|link| link.href.clone());
+            .map(|link| canonicalize_url(&link.href));
 
         // Extract thumbnail
         let thumbnail_url = entry.media.iter().find_map(|media| {
@@ -226,10 +294,29 @@ impl RssProvider {
             })
         });
 
-        // Build content
-        let content = ItemContent::Article {
-            summary,
-            full_content,
+        // Build content; entries that are only media:content images (with at
+        // most markup in the summary) become images instead of articles
+        let has_text = full_content.is_some()
+            || summary
+                .as_ref()
+                .and_then(|s| ItemContent::Html(s.clone()).plain_text())
+                .is_some();
+        let mut images = media_images(entry);
+        let content = if has_text || images.is_empty() {
+            ItemContent::Article {
+                summary,
+                full_content,
+            }
+        } else if images.len() == 1 {
+            let image = images.remove(0);
+            ItemContent::Image {
+                url: image.url,
+                alt: image.alt,
+                width: image.width,
+                height: image.height,
+            }
+        } else {
+            ItemContent::Gallery { images }
         };
 
         // Extract categories as tags
@@ -247,6 +334,9 @@ impl RssProvider {
                 metadata.insert("media_type".to_string(), mime.to_string());
             }
         }
+        if let Some(media_url) = media_enclosure(entry) {
+            metadata.insert("media_url".to_string(), media_url);
+        }
 
         Item {
             id: ItemId::new("rss", &entry_id),
@@ -266,6 +356,62 @@ impl RssProvider {
     }
 }
 
+/// Images attached to an entry with `media:content`.
+///
+/// feed-rs does not expose the `medium` attribute, so content is treated as an
+/// image when its MIME type is `image/*` or, without a type, when the URL
+/// looks like an image file.
+fn media_images(entry: &feed_rs::model::Entry) -> Vec<GalleryImage> {
+    const IMAGE_EXTENSIONS: &[&str] = &[".jpg", ".jpeg", ".png", ".gif", ".webp", ".avif"];
+
+    entry
+        .media
+        .iter()
+        .flat_map(|media| {
+            let alt = media
+                .description
+                .as_ref()
+                .or(media.title.as_ref())
+                .map(|text| text.content.trim().to_string())
+                .filter(|text| !text.is_empty());
+            media.content.iter().filter_map(move |content| {
+                let url = content.url.as_ref()?;
+                let is_image = match &content.content_type {
+                    Some(mime) => mime.to_string().starts_with("image/"),
+                    None => {
+                        let path = url.path().to_lowercase();
+                        IMAGE_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+                    }
+                };
+                is_image.then(|| GalleryImage {
+                    url: url.to_string(),
+                    alt: alt.clone(),
+                    width: content.width,
+                    height: content.height,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Link of the first audio or video attached to an entry, such as a podcast
+/// episode's `<enclosure>`, which the daemon's player prefers to the entry
+/// link.
+fn media_enclosure(entry: &feed_rs::model::Entry) -> Option<String> {
+    entry
+        .media
+        .iter()
+        .flat_map(|media| &media.content)
+        .find(|content| {
+            content.content_type.as_ref().is_some_and(|mime| {
+                let mime = mime.to_string();
+                mime.starts_with("audio/") || mime.starts_with("video/")
+            })
+        })
+        .and_then(|content| content.url.as_ref())
+        .map(|url| url.to_string())
+}
+
 #[async_trait]
 impl Provider for RssProvider {
     fn id(&self) -> &'static str {
@@ -305,30 +451,23 @@ impl Provider for RssProvider {
 
     async fn sync(&self) -> Result<SyncResult> {
         let start = Instant::now();
-        let mut items_added = 0;
-        let mut errors = Vec::new();
+        let mut streams = Vec::new();
 
-        for feed_url in &self.config.feeds {
-            match self.fetch_feed(feed_url).await {
-                Ok(feed) => {
-                    items_added += feed.entries.len() as u32;
-                }
-                Err(e) => {
-                    errors.push(format!("Failed to fetch {}: {}", feed_url, e));
-                }
-            }
+        for (idx, feed_url) in self.config.feeds.iter().enumerate() {
+            let stream_id = StreamId::new("rss", "feed", &format!("rss:{}", idx));
+            streams.push(match self.fetch_feed(feed_url).await {
+                Ok(feed) => StreamSyncResult::ok(stream_id, feed.entries.len() as u32, 0),
+                Err(e) => StreamSyncResult::failed(
+                    stream_id,
+                    format!("Failed to fetch {}: {}", feed_url, e),
+                ),
+            });
         }
 
-        let duration_ms = start.elapsed().as_millis() as u64;
-
-        Ok(SyncResult {
-            success: errors.is_empty(),
-            items_added,
-            items_updated: 0,
-            items_removed: 0,
-            errors,
-            duration_ms,
-        })
+        Ok(SyncResult::from_streams(
+            streams,
+            start.elapsed().as_millis() as u64,
+        ))
     }
 
     fn capabilities(&self) -> ProviderCapabilities {
@@ -337,6 +476,11 @@ impl Provider for RssProvider {
             has_collections: false,
             has_saved_items: false,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
@@ -423,6 +567,10 @@ impl Provider for RssProvider {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
 }
 
 #[async_trait]
@@ -486,18 +634,12 @@ impl HasFeeds for RssProvider {
             .get(feed_index)
             .ok_or_else(|| StreamError::StreamNotFound(feed_id.0.clone()))?;
 
-        // Fetch the feed
-        let feed = self.fetch_feed(feed_url).await?;
-
-        // Create stream ID
-        let stream_id = StreamId::new("rss", "feed", &feed_id.0);
-
-        // Convert entries to items
-        let mut items: Vec<Item> = feed
-            .entries
-            .iter()
-            .map(|entry| self.entry_to_item(entry, &stream_id, feed_url))
-            .collect();
+        // Fetch the feed and convert entries to items
+        let content = self.fetch_content(feed_url).await?;
+        let mut items = self.parse_items(feed_id, feed_url, &content)?;
+        if self.config.resolve_redirects {
+            self.resolve_redirects(&mut items).await;
+        }
 
         // Apply filtering based on options
         if !options.include_read {
@@ -650,6 +792,150 @@ mod tests {
         assert!(!item.is_saved);
     }
 
+    #[test]
+    fn test_entry_links_are_canonicalized() {
+        let rss = SAMPLE_RSS.replace(
+            "https://example.com/article1",
+            "https://example.com/article1?utm_source=rss&amp;utm_medium=feed",
+        );
+        let feed_id = FeedId("rss:0".to_string());
+        let provider = RssProvider::new(RssProviderConfig::default());
+
+        let items = provider
+            .parse_items(&feed_id, "https://example.com/rss", rss.as_bytes())
+            .unwrap();
+
+        assert_eq!(items[0].url.as_deref(), Some("https://example.com/article1"));
+    }
+
+    #[tokio::test]
+    async fn test_onion_feed_needs_tor_proxy() {
+        let onion = "http://example2abcdefgh.onion/feed.xml";
+        let provider = RssProvider::new(RssProviderConfig::default());
+        assert!(matches!(
+            provider.fetch_content(onion).await,
+            Err(RssError::InvalidUrl(_))
+        ));
+
+        let provider = provider
+            .with_proxy(&ProxyConfig::parse("tor").unwrap())
+            .unwrap();
+        assert!(check_reachable(onion, provider.proxy.as_ref()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sync_reports_each_feed() {
+        let provider = RssProvider::new(RssProviderConfig::new(vec![
+            "http://example2abcdefgh.onion/feed.xml".to_string(),
+        ]));
+
+        let result = provider.sync().await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.streams.len(), 1);
+        assert_eq!(result.streams[0].stream_id.as_str(), "rss:feed:rss:0");
+        assert!(result.streams[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("onion"));
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_media_content_images() {
+        const MEDIA_RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:media="http://search.yahoo.com/mrss/">
+  <channel>
+    <title>Photos</title>
+    <link>https://example.com</link>
+    <item>
+      <title>Sunset</title>
+      <link>https://example.com/sunset</link>
+      <description><![CDATA[<img src="https://example.com/sunset.jpg">]]></description>
+      <media:content url="https://example.com/sunset.jpg" type="image/jpeg" width="1024" height="768">
+        <media:description>Sun over the sea</media:description>
+      </media:content>
+    </item>
+    <item>
+      <title>Album</title>
+      <link>https://example.com/album</link>
+      <media:content url="https://example.com/1.png" width="800" height="600"/>
+      <media:content url="https://example.com/2.png"/>
+    </item>
+    <item>
+      <title>Podcast</title>
+      <link>https://example.com/episode</link>
+      <description>Episode notes</description>
+      <media:content url="https://example.com/cover.jpg" type="image/jpeg"/>
+    </item>
+  </channel>
+</rss>"#;
+
+        let feed = parser::parse(MEDIA_RSS.as_bytes()).unwrap();
+        let provider = RssProvider::new(RssProviderConfig::new(vec![]));
+        let stream_id = StreamId::new("rss", "feed", "rss:0");
+        let items: Vec<Item> = feed
+            .entries
+            .iter()
+            .map(|entry| provider.entry_to_item(entry, &stream_id, "https://example.com/rss"))
+            .collect();
+
+        match &items[0].content {
+            ItemContent::Image {
+                url,
+                alt,
+                width,
+                height,
+            } => {
+                assert_eq!(url, "https://example.com/sunset.jpg");
+                assert_eq!(alt.as_deref(), Some("Sun over the sea"));
+                assert_eq!((*width, *height), (Some(1024), Some(768)));
+            }
+            other => panic!("expected image, got {:?}", other),
+        }
+
+        match &items[1].content {
+            ItemContent::Gallery { images } => {
+                assert_eq!(images.len(), 2);
+                assert_eq!(images[0].width, Some(800));
+                assert_eq!(images[1].url, "https://example.com/2.png");
+            }
+            other => panic!("expected gallery, got {:?}", other),
+        }
+
+        // Entries with text stay articles
+        assert!(matches!(items[2].content, ItemContent::Article { .. }));
+        assert!(!items[2].metadata.contains_key("media_url"));
+    }
+
+    #[test]
+    fn test_podcast_enclosure_is_recorded() {
+        const PODCAST_RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>A Podcast</title>
+    <link>https://example.com</link>
+    <item>
+      <title>Episode 42</title>
+      <link>https://example.com/episodes/42</link>
+      <description>Show notes</description>
+      <enclosure url="https://cdn.example.com/42.mp3" length="1000" type="audio/mpeg"/>
+    </item>
+  </channel>
+</rss>"#;
+
+        let feed = parser::parse(PODCAST_RSS.as_bytes()).unwrap();
+        let provider = RssProvider::new(RssProviderConfig::new(vec![]));
+        let stream_id = StreamId::new("rss", "feed", "rss:0");
+        let item = provider.entry_to_item(&feed.entries[0], &stream_id, "https://example.com/rss");
+
+        assert_eq!(item.url.as_deref(), Some("https://example.com/episodes/42"));
+        assert_eq!(
+            item.metadata.get("media_url").map(String::as_str),
+            Some("https://cdn.example.com/42.mp3")
+        );
+    }
+
     #[test]
     fn test_atom_entry_with_author() {
         let feed = parser::parse(SAMPLE_ATOM.as_bytes()).unwrap();
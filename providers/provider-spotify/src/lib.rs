@@ -192,10 +192,11 @@ impl SpotifyProvider {
                 return Err(StreamError::RateLimited(60));
             }
 
-            return Err(StreamError::Provider(format!(
-                "Spotify API error ({}): {}",
-                status, error_body
-            )));
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                None,
+                format!("Spotify API error ({}): {}", status, error_body),
+            ));
         }
 
         response
@@ -316,6 +317,7 @@ impl Provider for SpotifyProvider {
                 items_removed: 0,
                 errors: vec![],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
             Err(e) => Ok(SyncResult {
                 success: false,
@@ -324,6 +326,7 @@ impl Provider for SpotifyProvider {
                 items_removed: 0,
                 errors: vec![format!("Sync failed: {}", e)],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -334,6 +337,11 @@ impl Provider for SpotifyProvider {
             has_collections: true,
             has_saved_items: true,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
@@ -389,6 +397,10 @@ impl Provider for SpotifyProvider {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        Some(self)
+    }
 }
 
 #[async_trait]
@@ -449,7 +461,7 @@ impl HasCollections for SpotifyProvider {
         _item_id: &ItemId,
     ) -> Result<()> {
         // TODO: Implement adding tracks to playlists (Phase 4 - write operations)
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Adding to collections is not yet implemented".to_string(),
         ))
     }
@@ -460,14 +472,14 @@ impl HasCollections for SpotifyProvider {
         _item_id: &ItemId,
     ) -> Result<()> {
         // TODO: Implement removing tracks from playlists (Phase 4 - write operations)
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Removing from collections is not yet implemented".to_string(),
         ))
     }
 
     async fn create_collection(&self, _name: &str) -> Result<Collection> {
         // TODO: Implement creating playlists (Phase 4 - write operations)
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Creating collections is not yet implemented".to_string(),
         ))
     }
@@ -509,14 +521,14 @@ impl HasSavedItems for SpotifyProvider {
 
     async fn save_item(&self, _item_id: &ItemId) -> Result<()> {
         // TODO: Implement saving/liking tracks (Phase 4 - write operations)
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Saving items is not yet implemented".to_string(),
         ))
     }
 
     async fn unsave_item(&self, _item_id: &ItemId) -> Result<()> {
         // TODO: Implement unsaving/unliking tracks (Phase 4 - write operations)
-        Err(StreamError::Provider(
+        Err(StreamError::NotSupported(
             "Unsaving items is not yet implemented".to_string(),
         ))
     }
@@ -0,0 +1,990 @@
+//! # provider-stackexchange
+//!
+//! Stack Overflow and Stack Exchange provider for Scryforge.
+//!
+//! This provider reads questions from the [Stack Exchange API] for one site of
+//! the network (Stack Overflow by default).
+//!
+//! ## Features
+//!
+//! - A feed of recently active questions for every followed tag
+//! - Bookmarked questions as saved items, with bookmarking written back
+//! - New answers to the user's own questions as inbox notifications
+//! - Score, answer count, view count, and accepted answer in item metadata
+//! - An `open_answer` action that opens the accepted or top-voted answer
+//!
+//! ## Tags
+//!
+//! The API has no method for reading a user's watched tags, so the tags to
+//! follow are listed in [`StackExchangeSettings::tags`]. Tag feeds work
+//! without a token; bookmarks and the inbox need one.
+//!
+//! ## Authentication
+//!
+//! Tokens are fetched via the Sigilforge client using the service name
+//! "stackexchange". Reading the inbox needs the `read_inbox` scope,
+//! bookmarking and replying need `write_access`. Write methods also require
+//! the registered app's [`StackExchangeSettings::key`], which raises the
+//! daily request quota for reads as well.
+//!
+//! [Stack Exchange API]: https://api.stackexchange.com/docs
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::decode_entities;
+use scryforge_provider_core::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// ============================================================================
+// Stack Exchange API Response Types
+// ============================================================================
+
+/// The envelope every API response is wrapped in.
+#[derive(Debug, Deserialize)]
+struct Wrapper<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// Body of an error response.
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    error_id: u32,
+    error_name: String,
+    error_message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeQuestion {
+    question_id: u64,
+    title: String,
+    link: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    score: i64,
+    answer_count: u32,
+    view_count: u64,
+    is_answered: bool,
+    accepted_answer_id: Option<u64>,
+    creation_date: i64,
+    last_activity_date: Option<i64>,
+    owner: Option<SeUser>,
+    /// Only sent with the `withbody` filter
+    body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeUser {
+    display_name: Option<String>,
+    link: Option<String>,
+    profile_image: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeAnswer {
+    answer_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeInboxItem {
+    item_type: String,
+    question_id: Option<u64>,
+    answer_id: Option<u64>,
+    title: String,
+    link: String,
+    body: Option<String>,
+    creation_date: i64,
+    is_unread: bool,
+}
+
+// ============================================================================
+// Item IDs
+// ============================================================================
+
+const PROVIDER_ID: &str = "stackexchange";
+
+/// Local ID of the feed of answers to the user's questions.
+const ANSWERS_FEED: &str = "answers";
+
+/// Inbox item type of a new answer to one of the user's questions.
+const NEW_ANSWER: &str = "new_answer";
+
+/// What a Stack Exchange item ID refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PostId {
+    /// `stackexchange:q:<id>`
+    Question(u64),
+    /// `stackexchange:a:<id>`, used for answer notifications
+    Answer(u64),
+}
+
+impl PostId {
+    fn item_id(self) -> ItemId {
+        match self {
+            Self::Question(id) => ItemId::new(PROVIDER_ID, &format!("q:{}", id)),
+            Self::Answer(id) => ItemId::new(PROVIDER_ID, &format!("a:{}", id)),
+        }
+    }
+
+    fn parse(id: &ItemId) -> Result<Self> {
+        let local = id
+            .as_str()
+            .strip_prefix("stackexchange:")
+            .ok_or_else(|| StreamError::ItemNotFound(id.as_str().to_string()))?;
+        let parsed = match local.split_once(':') {
+            Some(("q", num)) => num.parse().ok().map(Self::Question),
+            Some(("a", num)) => num.parse().ok().map(Self::Answer),
+            _ => None,
+        };
+        parsed.ok_or_else(|| StreamError::ItemNotFound(id.as_str().to_string()))
+    }
+}
+
+fn timestamp(secs: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
+}
+
+// ============================================================================
+// Stack Exchange Provider
+// ============================================================================
+
+/// Provider settings, read from `[providers.stackexchange.settings]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StackExchangeSettings {
+    /// API site parameter, e.g. `stackoverflow`, `superuser`, or `unix`
+    pub site: String,
+    /// Tags to show a feed of recently active questions for
+    pub tags: Vec<String>,
+    /// Key of the registered Stack Apps application
+    pub key: Option<String>,
+}
+
+impl Default for StackExchangeSettings {
+    fn default() -> Self {
+        Self {
+            site: "stackoverflow".to_string(),
+            tags: Vec::new(),
+            key: None,
+        }
+    }
+}
+
+/// Stack Exchange provider for Scryforge.
+pub struct StackExchangeProvider {
+    token_fetcher: Arc<dyn TokenFetcher>,
+    account: String,
+    settings: StackExchangeSettings,
+    client: Client,
+    base_url: String,
+}
+
+impl StackExchangeProvider {
+    const API_BASE: &'static str = "https://api.stackexchange.com/2.3";
+    /// Largest `pagesize` the API accepts.
+    const MAX_PAGE_SIZE: u32 = 100;
+    const DEFAULT_LIMIT: u32 = 30;
+
+    /// Create a new Stack Exchange provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_fetcher` - Token fetcher for OAuth authentication
+    /// * `account` - Account identifier for the token (e.g., "personal")
+    pub fn new(token_fetcher: Arc<dyn TokenFetcher>, account: String) -> Self {
+        Self::with_base_url(token_fetcher, account, Self::API_BASE.to_string())
+    }
+
+    /// Create a provider that talks to a different API base URL, such as a
+    /// mock server in tests.
+    pub fn with_base_url(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        account: String,
+        base_url: String,
+    ) -> Self {
+        let client = Client::builder()
+            .user_agent("scryforge/0.1.0")
+            .build()
+            .unwrap();
+
+        Self {
+            token_fetcher,
+            account,
+            settings: StackExchangeSettings::default(),
+            client,
+            base_url,
+        }
+    }
+
+    /// Replace the provider settings.
+    pub fn with_settings(mut self, settings: StackExchangeSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// The token, if the account has one; tag feeds work anonymously.
+    async fn token(&self) -> Option<String> {
+        self.token_fetcher
+            .fetch_token(PROVIDER_ID, &self.account)
+            .await
+            .ok()
+    }
+
+    async fn require_token(&self) -> Result<String> {
+        self.token_fetcher
+            .fetch_token(PROVIDER_ID, &self.account)
+            .await
+            .map_err(|e| StreamError::AuthRequired(format!("Failed to fetch token: {}", e)))
+    }
+
+    /// Parameters sent with every request.
+    fn common_params(&self, token: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut params = vec![("site", self.settings.site.clone())];
+        if let Some(key) = &self.settings.key {
+            params.push(("key", key.clone()));
+        }
+        if let Some(token) = token {
+            params.push(("access_token", token.to_string()));
+        }
+        params
+    }
+
+    /// Turn an error response into a [`StreamError`].
+    ///
+    /// The API reports errors in the body; `error_id` mirrors an HTTP status
+    /// but the response status itself is usually 400.
+    async fn check_response(response: reqwest::Response) -> Result<reqwest::Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let Ok(error) = serde_json::from_str::<ApiError>(&body) else {
+            return Err(StreamError::from_status(
+                status.as_u16(),
+                None,
+                format!("API error ({}): {}", status, body),
+            ));
+        };
+        match error.error_id {
+            401..=403 => Err(StreamError::AuthRequired(error.error_message)),
+            502 => {
+                // "too many requests from this IP, more requests available in N seconds"
+                let retry_after = error
+                    .error_message
+                    .split_whitespace()
+                    .rev()
+                    .find_map(|word| word.parse::<u64>().ok())
+                    .unwrap_or(60);
+                Err(StreamError::RateLimited(retry_after))
+            }
+            id => Err(StreamError::from_status(
+                u16::try_from(id).unwrap_or(400),
+                None,
+                format!(
+                    "{} ({}): {}",
+                    error.error_name, error.error_id, error.error_message
+                ),
+            )),
+        }
+    }
+
+    async fn api_get<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        token: Option<&str>,
+    ) -> Result<Wrapper<T>> {
+        let url = format!("{}{}", self.base_url, endpoint);
+        let response = self
+            .client
+            .get(&url)
+            .query(&self.common_params(token))
+            .query(params)
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        Self::check_response(response)
+            .await?
+            .json()
+            .await
+            .map_err(|e| StreamError::Provider(format!("Failed to parse response: {}", e)))
+    }
+
+    async fn api_post(&self, endpoint: &str, form: &[(&str, String)]) -> Result<()> {
+        let token = self.require_token().await?;
+        let url = format!("{}{}", self.base_url, endpoint);
+        let mut body = self.common_params(Some(&token));
+        body.extend(form.iter().map(|(name, value)| (*name, value.clone())));
+
+        let response = self
+            .client
+            .post(&url)
+            .form(&body)
+            .send()
+            .await
+            .map_err(|e| StreamError::Network(e.to_string()))?;
+
+        Self::check_response(response).await?;
+        Ok(())
+    }
+
+    /// Fetch up to `limit` items of a paged listing, starting at item `offset`.
+    async fn fetch_pages<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+        token: Option<&str>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<T>> {
+        let limit = limit.unwrap_or(Self::DEFAULT_LIMIT) as usize;
+        let offset = offset.unwrap_or(0) as usize;
+        let wanted = offset + limit;
+        // One page size for the whole listing keeps page numbers aligned
+        let page_size = (wanted as u32).clamp(1, Self::MAX_PAGE_SIZE);
+
+        let mut items = Vec::new();
+        let mut page = 1u32;
+        while items.len() < wanted {
+            let mut query = params.to_vec();
+            query.push(("pagesize", page_size.to_string()));
+            if page > 1 {
+                query.push(("page", page.to_string()));
+            }
+            let wrapper: Wrapper<T> = self.api_get(endpoint, &query, token).await?;
+            let has_more = wrapper.has_more && !wrapper.items.is_empty();
+            items.extend(wrapper.items);
+            if !has_more {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(items.into_iter().skip(offset).take(limit).collect())
+    }
+
+    fn question_to_item(&self, question: SeQuestion, stream_id: StreamId, saved: bool) -> Item {
+        let mut metadata = HashMap::new();
+        metadata.insert("question_id".to_string(), question.question_id.to_string());
+        metadata.insert("score".to_string(), question.score.to_string());
+        metadata.insert(
+            "answer_count".to_string(),
+            question.answer_count.to_string(),
+        );
+        metadata.insert("view_count".to_string(), question.view_count.to_string());
+        metadata.insert("is_answered".to_string(), question.is_answered.to_string());
+        if let Some(accepted) = question.accepted_answer_id {
+            metadata.insert("accepted_answer_id".to_string(), accepted.to_string());
+        }
+        metadata.insert("site".to_string(), self.settings.site.clone());
+
+        Item {
+            id: PostId::Question(question.question_id).item_id(),
+            stream_id,
+            title: decode_entities(&question.title),
+            content: ItemContent::Article {
+                summary: None,
+                full_content: question.body,
+            },
+            author: question.owner.and_then(|owner| {
+                Some(Author {
+                    name: decode_entities(&owner.display_name?),
+                    email: None,
+                    url: owner.link,
+                    avatar_url: owner.profile_image,
+                })
+            }),
+            published: timestamp(question.creation_date),
+            updated: question.last_activity_date.and_then(timestamp),
+            url: Some(question.link),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: saved,
+            tags: question.tags,
+            metadata,
+        }
+    }
+
+    /// Convert an inbox item, keeping only new answers.
+    fn inbox_to_item(&self, inbox: SeInboxItem) -> Option<Item> {
+        if inbox.item_type != NEW_ANSWER {
+            return None;
+        }
+        let answer_id = inbox.answer_id?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("answer_id".to_string(), answer_id.to_string());
+        if let Some(question_id) = inbox.question_id {
+            metadata.insert("question_id".to_string(), question_id.to_string());
+        }
+        metadata.insert("site".to_string(), self.settings.site.clone());
+
+        Some(Item {
+            id: PostId::Answer(answer_id).item_id(),
+            stream_id: StreamId::new(PROVIDER_ID, "feed", ANSWERS_FEED),
+            title: format!("New answer: {}", decode_entities(&inbox.title)),
+            content: ItemContent::Generic { body: inbox.body },
+            author: None,
+            published: timestamp(inbox.creation_date),
+            updated: None,
+            url: Some(inbox.link),
+            thumbnail_url: None,
+            is_read: !inbox.is_unread,
+            is_saved: false,
+            tags: vec![],
+            metadata,
+        })
+    }
+
+    async fn tag_questions(&self, tag: &str, options: &FeedOptions) -> Result<Vec<Item>> {
+        let mut params = vec![
+            ("tagged", tag.to_string()),
+            ("sort", "activity".to_string()),
+            ("order", "desc".to_string()),
+            ("filter", "withbody".to_string()),
+        ];
+        if let Some(since) = options.since {
+            // With `sort=activity`, `min` bounds the last activity date
+            params.push(("min", since.timestamp().to_string()));
+        }
+
+        let token = self.token().await;
+        let questions: Vec<SeQuestion> = self
+            .fetch_pages(
+                "/questions",
+                &params,
+                token.as_deref(),
+                options.limit,
+                options.offset,
+            )
+            .await?;
+
+        let stream_id = StreamId::new(PROVIDER_ID, "feed", &format!("tag:{}", tag));
+        Ok(questions
+            .into_iter()
+            .map(|question| self.question_to_item(question, stream_id.clone(), false))
+            .collect())
+    }
+
+    async fn bookmarks(&self, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<Item>> {
+        let token = self.require_token().await?;
+        let params = [
+            ("sort", "added".to_string()),
+            ("order", "desc".to_string()),
+            ("filter", "withbody".to_string()),
+        ];
+        let questions: Vec<SeQuestion> = self
+            .fetch_pages("/me/favorites", &params, Some(&token), limit, offset)
+            .await?;
+
+        let stream_id = StreamId::new(PROVIDER_ID, "saved", "bookmarks");
+        Ok(questions
+            .into_iter()
+            .map(|question| self.question_to_item(question, stream_id.clone(), true))
+            .collect())
+    }
+
+    async fn answer_notifications(&self, options: &FeedOptions) -> Result<Vec<Item>> {
+        let token = self.require_token().await?;
+        let endpoint = if options.include_read {
+            "/me/inbox"
+        } else {
+            "/me/inbox/unread"
+        };
+        let mut params = vec![("filter", "withbody".to_string())];
+        if let Some(since) = options.since {
+            params.push(("since", since.timestamp().to_string()));
+        }
+
+        // The inbox can't be filtered by type, so page through everything
+        // and keep the answers
+        let inbox: Vec<SeInboxItem> = self
+            .fetch_pages(
+                endpoint,
+                &params,
+                Some(&token),
+                Some(Self::MAX_PAGE_SIZE),
+                None,
+            )
+            .await?;
+
+        let limit = options.limit.unwrap_or(Self::DEFAULT_LIMIT) as usize;
+        Ok(inbox
+            .into_iter()
+            .filter_map(|item| self.inbox_to_item(item))
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(limit)
+            .collect())
+    }
+
+    /// Link to the accepted answer of a question, or its top-voted one.
+    ///
+    /// Returns `None` while the question has no answers.
+    pub async fn answer_url(&self, item: &Item) -> Result<Option<String>> {
+        let PostId::Question(question_id) = PostId::parse(&item.id)? else {
+            return Ok(item.url.clone());
+        };
+
+        let accepted = item
+            .metadata
+            .get("accepted_answer_id")
+            .and_then(|id| id.parse::<u64>().ok());
+        let answer_id = match accepted {
+            Some(id) => Some(id),
+            None => {
+                let token = self.token().await;
+                let params = [
+                    ("sort", "votes".to_string()),
+                    ("order", "desc".to_string()),
+                    ("pagesize", "1".to_string()),
+                ];
+                let answers: Wrapper<SeAnswer> = self
+                    .api_get(
+                        &format!("/questions/{}/answers", question_id),
+                        &params,
+                        token.as_deref(),
+                    )
+                    .await?;
+                answers.items.first().map(|answer| answer.answer_id)
+            }
+        };
+
+        // Answers live under the same host as their question
+        let site_root = item
+            .url
+            .as_deref()
+            .and_then(|url| url.split_once("/questions/"))
+            .map(|(root, _)| root.to_string())
+            .unwrap_or_else(|| format!("https://{}.com", self.settings.site));
+        Ok(answer_id.map(|id| format!("{}/a/{}", site_root, id)))
+    }
+
+    fn question_id(item_id: &ItemId) -> Result<u64> {
+        match PostId::parse(item_id)? {
+            PostId::Question(id) => Ok(id),
+            PostId::Answer(_) => Err(StreamError::NotSupported(
+                "Only questions can be bookmarked".to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for StackExchangeProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Stack Exchange"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        let token = self.token().await;
+        match self
+            .api_get::<serde_json::Value>("/info", &[], token.as_deref())
+            .await
+        {
+            Ok(_) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!("Connected to {}", self.settings.site)),
+                last_sync: Some(Utc::now()),
+                error_count: 0,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(format!("API error: {}", e)),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+
+        // Feeds are fetched on demand; sync only validates the connection
+        let health = self.health_check().await?;
+        Ok(SyncResult {
+            success: health.is_healthy,
+            items_added: 0,
+            items_updated: 0,
+            items_removed: 0,
+            errors: if health.is_healthy {
+                vec![]
+            } else {
+                vec![health.message.unwrap_or_default()]
+            },
+            duration_ms: start.elapsed().as_millis() as u64,
+            streams: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: true,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = vec![
+            Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                description: "Open in browser".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            },
+            Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy link to clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            },
+        ];
+
+        match PostId::parse(&item.id) {
+            Ok(PostId::Question(_)) => {
+                let answered = item
+                    .metadata
+                    .get("answer_count")
+                    .is_some_and(|count| count != "0");
+                if answered {
+                    actions.push(Action {
+                        id: "open_answer".to_string(),
+                        name: "Open Answer".to_string(),
+                        description: "Open the accepted or top-voted answer".to_string(),
+                        kind: ActionKind::Custom("open_answer".to_string()),
+                        keyboard_shortcut: Some("a".to_string()),
+                    });
+                }
+                if item.is_saved {
+                    actions.push(Action {
+                        id: "unsave".to_string(),
+                        name: "Remove Bookmark".to_string(),
+                        description: "Remove the question from bookmarks".to_string(),
+                        kind: ActionKind::Unsave,
+                        keyboard_shortcut: Some("u".to_string()),
+                    });
+                } else {
+                    actions.push(Action {
+                        id: "save".to_string(),
+                        name: "Bookmark".to_string(),
+                        description: "Bookmark the question".to_string(),
+                        kind: ActionKind::Save,
+                        keyboard_shortcut: Some("s".to_string()),
+                    });
+                }
+            }
+            Ok(PostId::Answer(_)) => actions.push(Action {
+                id: "reply".to_string(),
+                name: "Comment".to_string(),
+                description: "Comment on the answer".to_string(),
+                kind: ActionKind::Custom("reply".to_string()),
+                keyboard_shortcut: Some("R".to_string()),
+            }),
+            Err(_) => {}
+        }
+
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match &action.kind {
+            ActionKind::OpenInBrowser | ActionKind::Open | ActionKind::CopyLink => {
+                match &item.url {
+                    Some(url) => Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("Opening: {}", url)),
+                        data: Some(serde_json::json!({ "url": url })),
+                    }),
+                    None => Ok(ActionResult {
+                        success: false,
+                        message: Some("No URL available".to_string()),
+                        data: None,
+                    }),
+                }
+            }
+            ActionKind::Custom(custom) if custom == "open_answer" => {
+                match self.answer_url(item).await? {
+                    Some(url) => Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("Opening: {}", url)),
+                        data: Some(serde_json::json!({ "url": url })),
+                    }),
+                    None => Ok(ActionResult {
+                        success: false,
+                        message: Some("No answers yet".to_string()),
+                        data: None,
+                    }),
+                }
+            }
+            ActionKind::Custom(custom) if custom == "reply" => {
+                // The client prompts for the text and sends it with
+                // `reply_to_notification`
+                match PostId::parse(&item.id) {
+                    Ok(PostId::Answer(answer_id)) => Ok(ActionResult {
+                        success: true,
+                        message: Some("Enter your comment:".to_string()),
+                        data: Some(serde_json::json!({
+                            "answer_id": answer_id,
+                            "requires_input": true,
+                            "input_type": "text"
+                        })),
+                    }),
+                    _ => Ok(ActionResult {
+                        success: false,
+                        message: Some("Only answers can be commented on".to_string()),
+                        data: None,
+                    }),
+                }
+            }
+            ActionKind::Save => match self.save_item(&item.id).await {
+                Ok(()) => Ok(ActionResult {
+                    success: true,
+                    message: Some("Question bookmarked".to_string()),
+                    data: None,
+                }),
+                Err(e) => Ok(ActionResult {
+                    success: false,
+                    message: Some(format!("Failed to bookmark: {}", e)),
+                    data: None,
+                }),
+            },
+            ActionKind::Unsave => match self.unsave_item(&item.id).await {
+                Ok(()) => Ok(ActionResult {
+                    success: true,
+                    message: Some("Bookmark removed".to_string()),
+                    data: None,
+                }),
+                Err(e) => Ok(ActionResult {
+                    success: false,
+                    message: Some(format!("Failed to remove bookmark: {}", e)),
+                    data: None,
+                }),
+            },
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_notifications(&self) -> Option<&dyn HasNotifications> {
+        Some(self)
+    }
+}
+
+// ============================================================================
+// Capability Trait Implementations
+// ============================================================================
+
+#[async_trait]
+impl HasFeeds for StackExchangeProvider {
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let mut feeds: Vec<Feed> = self
+            .settings
+            .tags
+            .iter()
+            .map(|tag| Feed {
+                id: FeedId(format!("tag:{}", tag)),
+                name: format!("[{}]", tag),
+                description: Some(format!("Recently active questions tagged {}", tag)),
+                icon: Some("🏷".to_string()),
+                unread_count: None,
+                total_count: None,
+            })
+            .collect();
+        feeds.push(Feed {
+            id: FeedId(ANSWERS_FEED.to_string()),
+            name: "Answers".to_string(),
+            description: Some("New answers to your questions".to_string()),
+            icon: Some("📬".to_string()),
+            unread_count: None,
+            total_count: None,
+        });
+        Ok(feeds)
+    }
+
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        if feed_id.0 == ANSWERS_FEED {
+            return self.answer_notifications(&options).await;
+        }
+        match feed_id.0.strip_prefix("tag:") {
+            Some(tag) if !tag.is_empty() => self.tag_questions(tag, &options).await,
+            _ => Err(StreamError::StreamNotFound(feed_id.0.clone())),
+        }
+    }
+}
+
+#[async_trait]
+impl HasSavedItems for StackExchangeProvider {
+    async fn get_saved_items(&self, options: SavedItemsOptions) -> Result<Vec<Item>> {
+        self.bookmarks(options.limit, options.offset).await
+    }
+
+    async fn is_saved(&self, item_id: &ItemId) -> Result<bool> {
+        let id = Self::question_id(item_id)?.to_string();
+        let token = self.require_token().await?;
+        let questions: Vec<SeQuestion> = self
+            .fetch_pages("/me/favorites", &[], Some(&token), Some(1000), None)
+            .await?;
+        Ok(questions
+            .iter()
+            .any(|question| question.question_id.to_string() == id))
+    }
+
+    async fn save_item(&self, item_id: &ItemId) -> Result<()> {
+        let id = Self::question_id(item_id)?;
+        self.api_post(&format!("/questions/{}/favorite", id), &[])
+            .await
+    }
+
+    async fn unsave_item(&self, item_id: &ItemId) -> Result<()> {
+        let id = Self::question_id(item_id)?;
+        self.api_post(&format!("/questions/{}/favorite/undo", id), &[])
+            .await
+    }
+}
+
+#[async_trait]
+impl HasNotifications for StackExchangeProvider {
+    async fn list_notifications(&self, options: FeedOptions) -> Result<Vec<Item>> {
+        self.answer_notifications(&options).await
+    }
+
+    async fn mark_notification_read(&self, _item_id: &ItemId) -> Result<()> {
+        Err(StreamError::NotSupported(
+            "The Stack Exchange API can't mark inbox items read; open them on the site".to_string(),
+        ))
+    }
+
+    async fn reply_to_notification(&self, item_id: &ItemId, text: &str) -> Result<()> {
+        let PostId::Answer(answer_id) = PostId::parse(item_id)? else {
+            return Err(StreamError::NotSupported(
+                "Only answers can be commented on".to_string(),
+            ));
+        };
+        if text.trim().is_empty() {
+            return Err(StreamError::Provider("Comment is empty".to_string()));
+        }
+        self.api_post(
+            &format!("/posts/{}/comments/add", answer_id),
+            &[("body", text.to_string())],
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::auth::MockTokenFetcher;
+
+    fn provider() -> StackExchangeProvider {
+        StackExchangeProvider::new(Arc::new(MockTokenFetcher::empty()), "personal".to_string())
+    }
+
+    fn sample_question() -> SeQuestion {
+        serde_json::from_value(serde_json::json!({
+            "question_id": 11227809,
+            "title": "Why is processing a sorted array faster than an unsorted one?",
+            "link": "https://stackoverflow.com/questions/11227809/why-is-processing",
+            "tags": ["java", "performance"],
+            "score": 27000,
+            "answer_count": 26,
+            "view_count": 1900000,
+            "is_answered": true,
+            "accepted_answer_id": 11227902,
+            "creation_date": 1340805402,
+            "last_activity_date": 1700000000,
+            "owner": {"display_name": "GManNickG", "link": "https://stackoverflow.com/users/87234"},
+            "body": "<p>Here is a piece of C++ code</p>"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_post_id_round_trip() {
+        for post in [PostId::Question(42), PostId::Answer(7)] {
+            assert_eq!(PostId::parse(&post.item_id()).unwrap(), post);
+        }
+        assert!(PostId::parse(&ItemId::new("stackexchange", "c:1")).is_err());
+        assert!(PostId::parse(&ItemId::new("reddit", "q:1")).is_err());
+    }
+
+    #[test]
+    fn test_question_to_item() {
+        let item = provider().question_to_item(
+            sample_question(),
+            StreamId::new("stackexchange", "feed", "tag:java"),
+            false,
+        );
+
+        assert_eq!(item.id.as_str(), "stackexchange:q:11227809");
+        assert_eq!(item.author.unwrap().name, "GManNickG");
+        assert_eq!(item.tags, vec!["java", "performance"]);
+        assert_eq!(item.metadata["score"], "27000");
+        assert_eq!(item.metadata["answer_count"], "26");
+        assert_eq!(item.metadata["accepted_answer_id"], "11227902");
+        assert_eq!(item.metadata["site"], "stackoverflow");
+        assert!(matches!(
+            item.content,
+            ItemContent::Article {
+                full_content: Some(_),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_inbox_keeps_only_new_answers() {
+        let provider = provider();
+        let inbox = |item_type: &str| -> SeInboxItem {
+            serde_json::from_value(serde_json::json!({
+                "item_type": item_type,
+                "question_id": 1,
+                "answer_id": 2,
+                "title": "Borrowing &amp; lifetimes",
+                "link": "https://stackoverflow.com/a/2",
+                "creation_date": 1700000000,
+                "is_unread": true
+            }))
+            .unwrap()
+        };
+
+        let item = provider.inbox_to_item(inbox("new_answer")).unwrap();
+        assert_eq!(item.id.as_str(), "stackexchange:a:2");
+        assert_eq!(item.title, "New answer: Borrowing & lifetimes");
+        assert!(!item.is_read);
+        assert!(provider.inbox_to_item(inbox("comment")).is_none());
+    }
+}
@@ -0,0 +1,401 @@
+//! Mock-server tests for `provider-stackexchange`'s HTTP paths.
+
+use std::sync::Arc;
+
+use provider_stackexchange::{StackExchangeProvider, StackExchangeSettings};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{
+    body_string_contains, method, path, query_param, query_param_is_missing,
+};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::{fixture, MockApi};
+use serde_json::json;
+
+fn settings() -> StackExchangeSettings {
+    StackExchangeSettings {
+        tags: vec!["rust".to_string()],
+        key: Some("app-key".to_string()),
+        ..Default::default()
+    }
+}
+
+fn provider_for(api: &MockApi) -> StackExchangeProvider {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "stackexchange".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    StackExchangeProvider::with_base_url(Arc::new(tokens), "personal".to_string(), api.uri())
+        .with_settings(settings())
+}
+
+fn anonymous_provider_for(api: &MockApi) -> StackExchangeProvider {
+    StackExchangeProvider::with_base_url(
+        Arc::new(MockTokenFetcher::empty()),
+        "personal".to_string(),
+        api.uri(),
+    )
+    .with_settings(settings())
+}
+
+fn feed_options(limit: u32) -> FeedOptions {
+    FeedOptions {
+        limit: Some(limit),
+        ..Default::default()
+    }
+}
+
+fn rust_feed() -> FeedId {
+    FeedId("tag:rust".to_string())
+}
+
+fn custom_action(id: &str) -> Action {
+    Action {
+        id: id.to_string(),
+        name: id.to_string(),
+        description: String::new(),
+        kind: ActionKind::Custom(id.to_string()),
+        keyboard_shortcut: None,
+    }
+}
+
+#[tokio::test]
+async fn list_feeds_has_tags_and_answers() {
+    let api = MockApi::start().await;
+    let feeds = provider_for(&api).list_feeds().await.unwrap();
+
+    let ids: Vec<&str> = feeds.iter().map(|feed| feed.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["tag:rust", "answers"]);
+    assert_eq!(feeds[0].name, "[rust]");
+}
+
+#[tokio::test]
+async fn tag_feed_follows_pages() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/questions",
+        "page",
+        vec![
+            (None, fixture("stackexchange/questions_page1.json")),
+            (Some("2"), fixture("stackexchange/questions_page2.json")),
+        ],
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&rust_feed(), feed_options(10))
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "stackexchange:q:70000001",
+            "stackexchange:q:70000002",
+            "stackexchange:q:70000003"
+        ]
+    );
+    assert_eq!(api.request_count().await, 2);
+
+    let first = &items[0];
+    assert_eq!(first.title, "Why can't I borrow `self` mutably twice?");
+    assert_eq!(first.stream_id.as_str(), "stackexchange:feed:tag:rust");
+    assert_eq!(first.author.as_ref().unwrap().name, "Ferris & Friends");
+    assert_eq!(first.metadata["score"], "42");
+    assert_eq!(first.metadata["answer_count"], "2");
+    assert_eq!(first.metadata["accepted_answer_id"], "70000011");
+    assert_eq!(first.published.unwrap().timestamp(), 1714482000);
+}
+
+#[tokio::test]
+async fn limit_stops_pagination() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/questions",
+        "page",
+        vec![
+            (None, fixture("stackexchange/questions_page1.json")),
+            (Some("2"), fixture("stackexchange/questions_page2.json")),
+        ],
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&rust_feed(), feed_options(2))
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(api.request_count().await, 1);
+}
+
+#[tokio::test]
+async fn tag_feed_works_without_a_token() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/questions"))
+        .and(query_param("tagged", "rust"))
+        .and(query_param("site", "stackoverflow"))
+        .and(query_param("sort", "activity"))
+        .and(query_param("min", "1714500000"))
+        .and(query_param_is_missing("access_token"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("stackexchange/questions_page2.json")),
+        )
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let options = FeedOptions {
+        since: chrono::DateTime::from_timestamp(1714500000, 0),
+        ..Default::default()
+    };
+    let items = anonymous_provider_for(&api)
+        .get_feed_items(&rust_feed(), options)
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+}
+
+#[tokio::test]
+async fn bookmarks_need_a_token() {
+    let api = MockApi::start().await;
+    let err = anonymous_provider_for(&api)
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)));
+    assert_eq!(api.request_count().await, 0);
+}
+
+#[tokio::test]
+async fn bookmarks_are_saved_items() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/me/favorites"))
+        .and(query_param("access_token", "test-token"))
+        .and(query_param("key", "app-key"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(fixture("stackexchange/questions_page2.json")),
+        )
+        .mount(api.server())
+        .await;
+
+    let items = provider_for(&api)
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 1);
+    assert!(items[0].is_saved);
+    assert_eq!(items[0].stream_id.as_str(), "stackexchange:saved:bookmarks");
+}
+
+#[tokio::test]
+async fn bookmarking_posts_to_favorite() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/questions/70000002/favorite"))
+        .and(body_string_contains("access_token=test-token"))
+        .and(body_string_contains("key=app-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/questions/70000002/favorite/undo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let id = ItemId::new("stackexchange", "q:70000002");
+    provider.save_item(&id).await.unwrap();
+    provider.unsave_item(&id).await.unwrap();
+}
+
+#[tokio::test]
+async fn answer_notifications_skip_other_inbox_items() {
+    let api = MockApi::start().await;
+    api.mock_json("GET", "/me/inbox", fixture("stackexchange/inbox.json"))
+        .await;
+
+    let options = FeedOptions {
+        include_read: true,
+        ..Default::default()
+    };
+    let items = provider_for(&api)
+        .list_notifications(options)
+        .await
+        .unwrap();
+
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec!["stackexchange:a:70000150", "stackexchange:a:70000210"]
+    );
+    assert_eq!(
+        items[0].title,
+        "New answer: How do I parse \"key=value\" pairs?"
+    );
+    assert!(!items[0].is_read);
+    assert!(items[1].is_read);
+}
+
+#[tokio::test]
+async fn answers_feed_reads_unread_inbox() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/me/inbox/unread",
+        fixture("stackexchange/inbox.json"),
+    )
+    .await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&FeedId("answers".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].stream_id.as_str(), "stackexchange:feed:answers");
+}
+
+#[tokio::test]
+async fn reply_comments_on_the_answer() {
+    let api = MockApi::start().await;
+    Mock::given(method("POST"))
+        .and(path("/posts/70000150/comments/add"))
+        .and(body_string_contains("body=Thanks%21"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let id = ItemId::new("stackexchange", "a:70000150");
+    provider
+        .reply_to_notification(&id, "Thanks!")
+        .await
+        .unwrap();
+    assert!(provider.reply_to_notification(&id, " ").await.is_err());
+}
+
+#[tokio::test]
+async fn open_answer_prefers_accepted_then_top_voted() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/questions",
+        fixture("stackexchange/questions_page1.json"),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/questions/70000002/answers"))
+        .and(query_param("sort", "votes"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({ "items": [{ "answer_id": 70000020 }] })),
+        )
+        .expect(1)
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api);
+    let items = provider
+        .get_feed_items(&rust_feed(), feed_options(2))
+        .await
+        .unwrap();
+
+    let mut urls = Vec::new();
+    for item in &items {
+        let actions = provider.available_actions(item).await.unwrap();
+        assert!(actions.iter().any(|action| action.id == "open_answer"));
+        let result = provider
+            .execute_action(item, &custom_action("open_answer"))
+            .await
+            .unwrap();
+        assert!(result.success);
+        urls.push(result.data.unwrap()["url"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        urls,
+        vec![
+            "https://stackoverflow.com/a/70000011",
+            "https://stackoverflow.com/a/70000020"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn open_answer_without_answers_fails() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/questions",
+        fixture("stackexchange/questions_page2.json"),
+    )
+    .await;
+    api.mock_json(
+        "GET",
+        "/questions/70000003/answers",
+        json!({ "items": [], "has_more": false }),
+    )
+    .await;
+
+    let provider = provider_for(&api);
+    let items = provider
+        .get_feed_items(&rust_feed(), feed_options(1))
+        .await
+        .unwrap();
+
+    let actions = provider.available_actions(&items[0]).await.unwrap();
+    assert!(!actions.iter().any(|action| action.id == "open_answer"));
+    let result = provider
+        .execute_action(&items[0], &custom_action("open_answer"))
+        .await
+        .unwrap();
+    assert!(!result.success);
+}
+
+#[tokio::test]
+async fn throttle_violation_is_rate_limited() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/questions",
+        400,
+        fixture("stackexchange/error_throttle.json"),
+    )
+    .await;
+
+    let err = provider_for(&api)
+        .get_feed_items(&rust_feed(), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(42)));
+}
+
+#[tokio::test]
+async fn invalid_token_requires_auth() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/me/favorites",
+        400,
+        json!({
+            "error_id": 401,
+            "error_message": "No matching access_token found",
+            "error_name": "invalid_access_token"
+        }),
+    )
+    .await;
+
+    let err = provider_for(&api)
+        .get_saved_items(SavedItemsOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)));
+}
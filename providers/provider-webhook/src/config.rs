@@ -0,0 +1,67 @@
+//! Configuration for the webhook provider.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the [`WebhookProvider`](crate::WebhookProvider).
+///
+/// Items always live in `spool_dir`. Setting `bind_address` also serves the
+/// HTTP endpoint that writes there; `token` then protects it, and is
+/// required unless the address is loopback.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookProviderConfig {
+    /// Directory of pushed items, created on the first push.
+    pub spool_dir: PathBuf,
+    /// Address for the HTTP endpoint, e.g. `127.0.0.1:7780`. No endpoint
+    /// when unset.
+    #[serde(default)]
+    pub bind_address: Option<String>,
+    /// Bearer token pushes must present. Required for a `bind_address`
+    /// other than loopback.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl WebhookProviderConfig {
+    /// Configuration reading items dropped into `spool_dir`.
+    pub fn new(spool_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            spool_dir: spool_dir.into(),
+            bind_address: None,
+            token: None,
+        }
+    }
+
+    /// Also accept pushes over HTTP at `bind_address`.
+    pub fn with_endpoint(mut self, bind_address: impl Into<String>, token: Option<String>) -> Self {
+        self.bind_address = Some(bind_address.into());
+        self.token = token;
+        self
+    }
+
+    /// Check that the endpoint, if any, isn't open to the network without
+    /// a token.
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(bind_address) = &self.bind_address else {
+            return Ok(());
+        };
+        if self.token.as_ref().is_some_and(|t| !t.is_empty()) {
+            return Ok(());
+        }
+        let loopback = match bind_address.parse::<SocketAddr>() {
+            Ok(addr) => addr.ip().is_loopback(),
+            Err(_) => bind_address
+                .rsplit_once(':')
+                .is_some_and(|(host, _)| host.eq_ignore_ascii_case("localhost")),
+        };
+        if !loopback {
+            return Err(format!(
+                "webhook endpoint on {} requires a token; only loopback addresses may go without",
+                bind_address
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+//! # provider-webhook
+//!
+//! Generic inbox provider for Scryforge: anything that can write a JSON file
+//! or send an HTTP request can become a source.
+//!
+//! Items are kept in a spool directory ([`spool`]). Scripts can drop files
+//! there, and with a bind address configured the provider also serves a
+//! small HTTP endpoint ([`server`]) for webhooks from IFTTT, CI systems, or
+//! monitoring. Each item names the feed it belongs to; feeds appear as items
+//! arrive.
+//!
+//! ## Item format
+//!
+//! ```json
+//! {
+//!   "id": "build-1234",
+//!   "feed": "ci",
+//!   "title": "Build #1234 failed",
+//!   "body": "`cargo test` failed on **main**",
+//!   "format": "markdown",
+//!   "url": "https://ci.example.com/builds/1234",
+//!   "author": "CI",
+//!   "published": "2024-05-01T12:00:00Z",
+//!   "tags": ["ci", "failure"],
+//!   "metadata": {"branch": "main"}
+//! }
+//! ```
+//!
+//! Only `title` is required. `feed` defaults to `inbox` and `format` to
+//! `text` (`markdown` and `html` are also understood). Pushing an item with
+//! an existing `id` replaces it.
+//!
+//! ## Configuration
+//!
+//! ```no_run
+//! # async fn example() -> scryforge_provider_core::Result<()> {
+//! use provider_webhook::{WebhookProvider, WebhookProviderConfig};
+//!
+//! let config = WebhookProviderConfig::new("/home/me/.local/share/scryforge/webhook")
+//!     .with_endpoint("127.0.0.1:7780", Some("secret".to_string()));
+//! let provider = WebhookProvider::new(config);
+//! let server = provider.start_server().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```sh
+//! curl -H 'Authorization: Bearer secret' -d '{"title": "Backup done"}' \
+//!     http://127.0.0.1:7780/items
+//! ```
+
+pub mod config;
+pub mod server;
+pub mod spool;
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::url::canonicalize_url;
+use tokio::task::JoinHandle;
+
+pub use config::WebhookProviderConfig;
+pub use spool::{BodyFormat, PushedItem, Spool, SpoolContents, SpooledItem, DEFAULT_FEED};
+
+/// Provider id used as the namespace prefix for `ItemId` / `StreamId`.
+const PROVIDER_ID: &str = "webhook";
+
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Webhook and spool-directory provider for Scryforge.
+pub struct WebhookProvider {
+    config: WebhookProviderConfig,
+    spool: Spool,
+}
+
+impl WebhookProvider {
+    pub fn new(config: WebhookProviderConfig) -> Self {
+        let spool = Spool::new(config.spool_dir.clone());
+        Self { config, spool }
+    }
+
+    /// Serve the HTTP endpoint, if a bind address is configured.
+    ///
+    /// Fails if the configuration doesn't pass
+    /// [`WebhookProviderConfig::validate`].
+    ///
+    /// # Returns
+    ///
+    /// The task handle (abort it to stop the server) and the bound address,
+    /// or `None` without a bind address.
+    pub async fn start_server(&self) -> Result<Option<(JoinHandle<()>, SocketAddr)>> {
+        let Some(bind_address) = &self.config.bind_address else {
+            return Ok(None);
+        };
+        self.config.validate().map_err(StreamError::Provider)?;
+        server::start_server(self.spool.clone(), bind_address, self.config.token.clone())
+            .await
+            .map(Some)
+            .map_err(|e| {
+                StreamError::Provider(format!(
+                    "Failed to bind webhook endpoint to {}: {}",
+                    bind_address, e
+                ))
+            })
+    }
+
+    async fn read_spool(&self) -> Result<SpoolContents> {
+        let spool = self.spool.clone();
+        tokio::task::spawn_blocking(move || spool.read())
+            .await
+            .map_err(|e| StreamError::Internal(e.to_string()))?
+            .map_err(|e| {
+                StreamError::Provider(format!(
+                    "Failed to read spool {}: {}",
+                    self.spool.dir().display(),
+                    e
+                ))
+            })
+    }
+}
+
+/// Convert a spooled item into an [`Item`].
+fn spooled_to_item(spooled: &SpooledItem) -> Item {
+    let pushed = &spooled.item;
+    let content = match (&pushed.body, pushed.format) {
+        (None, _) => ItemContent::Generic { body: None },
+        (Some(body), BodyFormat::Text) => ItemContent::Text(body.clone()),
+        (Some(body), BodyFormat::Markdown) => ItemContent::Markdown(body.clone()),
+        (Some(body), BodyFormat::Html) => ItemContent::Html(body.clone()),
+    };
+
+    Item {
+        id: ItemId::new(PROVIDER_ID, &spooled.id),
+        stream_id: StreamId::new(PROVIDER_ID, "feed", pushed.feed()),
+        title: pushed.title.clone(),
+        content,
+        author: pushed.author.as_ref().map(|name| Author {
+            name: name.clone(),
+            email: None,
+            url: None,
+            avatar_url: None,
+        }),
+        published: Some(pushed.published.unwrap_or(spooled.received)),
+        updated: Some(spooled.received),
+        url: pushed.url.as_deref().map(canonicalize_url),
+        thumbnail_url: None,
+        is_read: false,
+        is_saved: false,
+        tags: pushed.tags.clone(),
+        metadata: pushed.metadata.clone(),
+    }
+}
+
+fn local_id(item_id: &ItemId) -> Result<&str> {
+    item_id
+        .as_str()
+        .strip_prefix("webhook:")
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| StreamError::ItemNotFound(item_id.as_str().to_string()))
+}
+
+#[async_trait]
+impl Provider for WebhookProvider {
+    fn id(&self) -> &'static str {
+        PROVIDER_ID
+    }
+
+    fn name(&self) -> &'static str {
+        "Webhook"
+    }
+
+    async fn health_check(&self) -> Result<ProviderHealth> {
+        match self.read_spool().await {
+            Ok(contents) => Ok(ProviderHealth {
+                is_healthy: true,
+                message: Some(format!(
+                    "{} items, {} unreadable files",
+                    contents.items.len(),
+                    contents.rejected.len()
+                )),
+                last_sync: Some(Utc::now()),
+                error_count: contents.rejected.len() as u32,
+            }),
+            Err(e) => Ok(ProviderHealth {
+                is_healthy: false,
+                message: Some(e.to_string()),
+                last_sync: None,
+                error_count: 1,
+            }),
+        }
+    }
+
+    async fn sync(&self) -> Result<SyncResult> {
+        let start = std::time::Instant::now();
+        match self.read_spool().await {
+            Ok(contents) => Ok(SyncResult {
+                success: true,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                // Bad files are reported but don't fail the sync
+                errors: contents
+                    .rejected
+                    .iter()
+                    .map(|(path, reason)| format!("{}: {}", path.display(), reason))
+                    .collect(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+            Err(e) => Ok(SyncResult {
+                success: false,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![e.to_string()],
+                duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
+            }),
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            has_feeds: true,
+            has_collections: false,
+            has_saved_items: false,
+            has_communities: false,
+            supports_search: false,
+            supports_write_actions: true,
+            supports_incremental_sync: true,
+            supports_pagination: true,
+            supports_notifications: false,
+        }
+    }
+
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = Vec::new();
+        if item.url.is_some() {
+            actions.push(Action {
+                id: "open".to_string(),
+                name: "Open".to_string(),
+                description: "Open in browser".to_string(),
+                kind: ActionKind::OpenInBrowser,
+                keyboard_shortcut: Some("o".to_string()),
+            });
+            actions.push(Action {
+                id: "copy_link".to_string(),
+                name: "Copy Link".to_string(),
+                description: "Copy link to clipboard".to_string(),
+                kind: ActionKind::CopyLink,
+                keyboard_shortcut: Some("c".to_string()),
+            });
+        }
+        actions.push(Action {
+            id: "dismiss".to_string(),
+            name: "Dismiss".to_string(),
+            description: "Remove the item from the spool".to_string(),
+            kind: ActionKind::Delete,
+            keyboard_shortcut: Some("d".to_string()),
+        });
+        Ok(actions)
+    }
+
+    async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
+        match action.kind {
+            ActionKind::OpenInBrowser | ActionKind::Open | ActionKind::CopyLink => {
+                match &item.url {
+                    Some(url) => Ok(ActionResult {
+                        success: true,
+                        message: Some(format!("Opening: {}", url)),
+                        data: Some(serde_json::json!({ "url": url })),
+                    }),
+                    None => Ok(ActionResult {
+                        success: false,
+                        message: Some("No URL available".to_string()),
+                        data: None,
+                    }),
+                }
+            }
+            ActionKind::Delete => {
+                let id = local_id(&item.id)?.to_string();
+                let spool = self.spool.clone();
+                let removed = tokio::task::spawn_blocking(move || spool.remove(&id))
+                    .await
+                    .map_err(|e| StreamError::Internal(e.to_string()))?
+                    .map_err(|e| StreamError::Provider(format!("Failed to dismiss: {}", e)))?;
+                Ok(ActionResult {
+                    success: removed,
+                    message: Some(if removed {
+                        "Dismissed".to_string()
+                    } else {
+                        "Already removed from the spool".to_string()
+                    }),
+                    data: None,
+                })
+            }
+            _ => Ok(ActionResult {
+                success: false,
+                message: Some("Action not supported".to_string()),
+                data: None,
+            }),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl HasFeeds for WebhookProvider {
+    /// One feed per feed name in the spool, plus `inbox`.
+    async fn list_feeds(&self) -> Result<Vec<Feed>> {
+        let contents = self.read_spool().await?;
+        let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+        counts.insert(DEFAULT_FEED.to_string(), 0);
+        for spooled in &contents.items {
+            *counts.entry(spooled.item.feed().to_string()).or_default() += 1;
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|(name, count)| Feed {
+                id: FeedId(name.clone()),
+                description: Some(format!("Items pushed to the `{}` feed", name)),
+                name,
+                icon: Some("📥".to_string()),
+                unread_count: None,
+                total_count: Some(count),
+            })
+            .collect())
+    }
+
+    /// Items of a feed, newest first. `since` selects items received since
+    /// then, including replaced ones.
+    async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
+        if !spool::valid_name(&feed_id.0) {
+            return Err(StreamError::StreamNotFound(feed_id.0.clone()));
+        }
+
+        let contents = self.read_spool().await?;
+        let mut items: Vec<Item> = contents
+            .items
+            .iter()
+            .filter(|spooled| spooled.item.feed() == feed_id.0)
+            .filter(|spooled| options.since.is_none_or(|since| spooled.received >= since))
+            .map(spooled_to_item)
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.published));
+
+        Ok(items
+            .into_iter()
+            .skip(options.offset.unwrap_or(0) as usize)
+            .take(options.limit.unwrap_or(DEFAULT_LIMIT) as usize)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spooled_to_item() {
+        let spooled = SpooledItem {
+            id: "build-42".to_string(),
+            item: serde_json::from_value(serde_json::json!({
+                "feed": "ci",
+                "title": "Build failed",
+                "body": "**red**",
+                "format": "markdown",
+                "author": "CI",
+                "tags": ["ci"],
+                "metadata": {"branch": "main"}
+            }))
+            .unwrap(),
+            path: "spool/1-build-42.json".into(),
+            received: "2024-05-01T12:00:00Z".parse().unwrap(),
+        };
+
+        let item = spooled_to_item(&spooled);
+        assert_eq!(item.id.as_str(), "webhook:build-42");
+        assert_eq!(item.stream_id.as_str(), "webhook:feed:ci");
+        assert!(matches!(&item.content, ItemContent::Markdown(body) if body == "**red**"));
+        assert_eq!(item.author.unwrap().name, "CI");
+        assert_eq!(item.published, Some(spooled.received));
+        assert_eq!(item.metadata["branch"], "main");
+    }
+}
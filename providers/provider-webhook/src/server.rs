@@ -0,0 +1,134 @@
+//! HTTP endpoint that writes pushed items into the spool.
+//!
+//! # Routes
+//!
+//! - `POST /items` - one item object or an array of them; answers
+//!   `202 Accepted` with the stored IDs
+//! - `GET /health` - liveness check, never authenticated
+//!
+//! With a token configured, `POST /items` requires
+//! `Authorization: Bearer <token>`. Without one the endpoint only listens
+//! on loopback addresses.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::spool::{Payload, Spool};
+
+struct ServerState {
+    spool: Spool,
+    token: Option<String>,
+}
+
+/// Build the endpoint's router.
+pub fn router(spool: Spool, token: Option<String>) -> Router {
+    let token = token.filter(|t| !t.is_empty());
+    let state = Arc::new(ServerState { spool, token });
+    Router::new()
+        .route("/items", post(push_items))
+        .route("/health", get(|| async { "ok" }))
+        .with_state(state)
+}
+
+/// Start the endpoint in a background task.
+///
+/// Without a `token`, an address that isn't loopback is refused.
+///
+/// # Returns
+///
+/// The task handle (abort it to stop the server) and the bound address.
+pub async fn start_server(
+    spool: Spool,
+    bind_address: &str,
+    token: Option<String>,
+) -> std::io::Result<(JoinHandle<()>, SocketAddr)> {
+    let listener = TcpListener::bind(bind_address).await?;
+    let addr = listener.local_addr()?;
+    if !addr.ip().is_loopback() && token.as_ref().is_none_or(|t| t.is_empty()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("refusing to serve {} without a token", addr),
+        ));
+    }
+    let app = router(spool, token);
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Webhook endpoint stopped: {}", e);
+        }
+    });
+
+    info!("Webhook endpoint listening on http://{}/items", addr);
+
+    Ok((handle, addr))
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+fn authorized(state: &ServerState, headers: &HeaderMap) -> bool {
+    let Some(token) = &state.token else {
+        return true;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| given == token)
+}
+
+async fn push_items(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Response {
+    if !authorized(&state, &headers) {
+        return error_response(StatusCode::UNAUTHORIZED, "missing or wrong bearer token");
+    }
+
+    let items = match serde_json::from_slice::<Payload>(&body) {
+        Ok(payload) => payload.into_items(),
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("expected an item object or an array of items: {e}"),
+            )
+        }
+    };
+    if items.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "no items");
+    }
+    for item in &items {
+        if let Err(reason) = item.validate() {
+            return error_response(StatusCode::UNPROCESSABLE_ENTITY, reason);
+        }
+    }
+
+    let spool = state.spool.clone();
+    match tokio::task::spawn_blocking(move || spool.write(items)).await {
+        Ok(Ok(ids)) => (
+            StatusCode::ACCEPTED,
+            Json(json!({ "accepted": ids.len(), "ids": ids })),
+        )
+            .into_response(),
+        Ok(Err(e)) => {
+            warn!("Failed to spool pushed items: {}", e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "failed to store items")
+        }
+        Err(e) => {
+            warn!("Spool writer panicked: {}", e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "failed to store items")
+        }
+    }
+}
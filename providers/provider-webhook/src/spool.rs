@@ -0,0 +1,379 @@
+//! The spool directory pushed items are kept in.
+//!
+//! Every `*.json` file in the directory holds one item object or an array of
+//! them. The HTTP endpoint writes one file per item; scripts can drop files
+//! in directly. Writers should create the file under a name starting with
+//! `.` and rename it into place, so a half-written file is never read.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Feed of items that don't name one.
+pub const DEFAULT_FEED: &str = "inbox";
+
+/// Longest accepted item ID or feed name.
+const MAX_NAME_LEN: usize = 128;
+
+/// How an item's `body` is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyFormat {
+    #[default]
+    Text,
+    Markdown,
+    Html,
+}
+
+/// An item as pushed by an external system.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PushedItem {
+    /// Stable ID; pushing the same ID again replaces the item. Generated
+    /// when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Feed to file the item under, `inbox` by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed: Option<String>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub format: BodyFormat,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// When the event happened; the time it was received otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Whether `name` can be an item ID or feed name: it ends up in file names.
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.')
+}
+
+impl PushedItem {
+    /// The feed the item belongs to.
+    pub fn feed(&self) -> &str {
+        self.feed.as_deref().unwrap_or(DEFAULT_FEED)
+    }
+
+    /// Check the item can be stored, explaining why not.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("title must not be empty".to_string());
+        }
+        if let Some(id) = &self.id {
+            if !valid_name(id) {
+                return Err(format!(
+                    "invalid id `{id}`: use up to {MAX_NAME_LEN} letters, digits, `-`, `_`, or `.`"
+                ));
+            }
+        }
+        if !valid_name(self.feed()) {
+            return Err(format!(
+                "invalid feed `{}`: use up to {MAX_NAME_LEN} letters, digits, `-`, `_`, or `.`",
+                self.feed()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Contents of one spool file, or of one push.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Payload {
+    One(Box<PushedItem>),
+    Many(Vec<PushedItem>),
+}
+
+impl Payload {
+    pub fn into_items(self) -> Vec<PushedItem> {
+        match self {
+            Self::One(item) => vec![*item],
+            Self::Many(items) => items,
+        }
+    }
+}
+
+/// An item read back from the spool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpooledItem {
+    /// The item's ID: its own, else derived from the file name.
+    pub id: String,
+    pub item: PushedItem,
+    /// File the item is stored in.
+    pub path: PathBuf,
+    /// When the file was written.
+    pub received: DateTime<Utc>,
+}
+
+/// Everything in the spool.
+#[derive(Debug, Default)]
+pub struct SpoolContents {
+    /// Items, newest first.
+    pub items: Vec<SpooledItem>,
+    /// Files that couldn't be read, with the reason.
+    pub rejected: Vec<(PathBuf, String)>,
+}
+
+/// A spool directory.
+#[derive(Debug, Clone)]
+pub struct Spool {
+    dir: PathBuf,
+}
+
+impl Spool {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Read every item. A missing directory is an empty spool.
+    ///
+    /// When several files carry the same item ID, the newest file wins.
+    pub fn read(&self) -> io::Result<SpoolContents> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(SpoolContents::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut contents = SpoolContents::default();
+        for entry in entries {
+            let path = entry?.path();
+            let is_json = path.extension().is_some_and(|ext| ext == "json");
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_none_or(|name| name.starts_with('.'));
+            if !is_json || hidden || !path.is_file() {
+                continue;
+            }
+            match Self::read_file(&path) {
+                Ok(items) => contents.items.extend(items),
+                Err(reason) => contents.rejected.push((path, reason)),
+            }
+        }
+
+        // Pushed file names start with the time, so they settle ties
+        contents
+            .items
+            .sort_by(|a, b| (b.received, &b.path).cmp(&(a.received, &a.path)));
+        let mut seen = std::collections::HashSet::new();
+        contents
+            .items
+            .retain(|spooled| seen.insert(spooled.id.clone()));
+        Ok(contents)
+    }
+
+    fn read_file(path: &Path) -> Result<Vec<SpooledItem>, String> {
+        let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let payload: Payload = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        let received = fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let (items, single) = match payload {
+            Payload::One(item) => (vec![*item], true),
+            Payload::Many(items) => (items, false),
+        };
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                item.validate()?;
+                let id = match &item.id {
+                    Some(id) => id.clone(),
+                    None if single => stem.clone(),
+                    None => format!("{stem}-{index}"),
+                };
+                Ok(SpooledItem {
+                    id,
+                    item,
+                    path: path.to_path_buf(),
+                    received,
+                })
+            })
+            .collect()
+    }
+
+    /// Store `items`, one file each, and return their IDs.
+    ///
+    /// Items are validated first; nothing is written if any is invalid.
+    pub fn write(&self, items: Vec<PushedItem>) -> io::Result<Vec<String>> {
+        for item in &items {
+            item.validate()
+                .map_err(|reason| io::Error::new(io::ErrorKind::InvalidInput, reason))?;
+        }
+        fs::create_dir_all(&self.dir)?;
+
+        let millis = Utc::now().timestamp_millis();
+        let mut ids = Vec::with_capacity(items.len());
+        for mut item in items {
+            let id = item
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+            item.id = Some(id.clone());
+
+            let name = format!("{millis}-{id}.json");
+            self.write_atomically(&name, &serde_json::to_vec_pretty(&item)?)?;
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    fn write_atomically(&self, name: &str, contents: &[u8]) -> io::Result<()> {
+        let temp = self.dir.join(format!(".{name}.tmp"));
+        fs::write(&temp, contents)?;
+        fs::rename(&temp, self.dir.join(name))
+    }
+
+    /// Remove the item with ID `id`, returning whether it was there.
+    ///
+    /// Files holding several items are rewritten without it; the others keep
+    /// the IDs they had.
+    pub fn remove(&self, id: &str) -> io::Result<bool> {
+        let contents = self.read()?;
+        let Some(target) = contents.items.iter().find(|spooled| spooled.id == id) else {
+            return Ok(false);
+        };
+
+        let siblings: Vec<PushedItem> = Self::read_file(&target.path)
+            .map_err(|reason| io::Error::new(io::ErrorKind::InvalidData, reason))?
+            .into_iter()
+            .filter(|spooled| spooled.id != id)
+            .map(|spooled| PushedItem {
+                id: Some(spooled.id),
+                ..spooled.item
+            })
+            .collect();
+
+        if siblings.is_empty() {
+            fs::remove_file(&target.path)?;
+        } else {
+            let name = target
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            self.write_atomically(&name, &serde_json::to_vec_pretty(&siblings)?)?;
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pushed(title: &str) -> PushedItem {
+        serde_json::from_value(serde_json::json!({ "title": title })).unwrap()
+    }
+
+    #[test]
+    fn write_read_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path().join("spool"));
+        assert!(spool.read().unwrap().items.is_empty());
+
+        let ids = spool
+            .write(vec![
+                pushed("first"),
+                PushedItem {
+                    id: Some("build-42".to_string()),
+                    feed: Some("ci".to_string()),
+                    ..pushed("second")
+                },
+            ])
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[1], "build-42");
+
+        let items = spool.read().unwrap().items;
+        assert_eq!(items.len(), 2);
+        let ci = items
+            .iter()
+            .find(|spooled| spooled.id == "build-42")
+            .unwrap();
+        assert_eq!(ci.item.feed(), "ci");
+
+        assert!(spool.remove("build-42").unwrap());
+        assert!(!spool.remove("build-42").unwrap());
+        assert_eq!(spool.read().unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn dropped_arrays_keep_ids_when_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = Spool::new(dir.path());
+        fs::write(
+            dir.path().join("alerts.json"),
+            r#"[{"title": "a"}, {"title": "b"}, {"title": "c"}]"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join(".partial.json"), "[{").unwrap();
+        fs::write(dir.path().join("broken.json"), "{").unwrap();
+        fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let contents = spool.read().unwrap();
+        let mut ids: Vec<&str> = contents.items.iter().map(|s| s.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["alerts-0", "alerts-1", "alerts-2"]);
+        assert_eq!(contents.rejected.len(), 1);
+
+        assert!(spool.remove("alerts-1").unwrap());
+        let mut ids: Vec<String> = spool
+            .read()
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["alerts-0", "alerts-2"]);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(pushed("ok").validate().is_ok());
+        assert!(pushed("  ").validate().is_err());
+        let bad_feed = PushedItem {
+            feed: Some("../etc".to_string()),
+            ..pushed("x")
+        };
+        assert!(bad_feed.validate().is_err());
+        let bad_id = PushedItem {
+            id: Some("a/b".to_string()),
+            ..pushed("x")
+        };
+        assert!(bad_id.validate().is_err());
+    }
+}
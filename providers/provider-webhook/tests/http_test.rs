@@ -0,0 +1,223 @@
+//! Pushes items through the HTTP endpoint and reads them back through the
+//! provider.
+
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use provider_webhook::server::{router, start_server};
+use provider_webhook::{Spool, WebhookProvider, WebhookProviderConfig};
+use scryforge_provider_core::prelude::*;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn push(spool: &Spool, token: Option<&str>, body: &str) -> (StatusCode, Value) {
+    let mut request = Request::post("/items").header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = router(spool.clone(), Some("secret".to_string()))
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn feed(name: &str) -> FeedId {
+    FeedId(name.to_string())
+}
+
+#[tokio::test]
+async fn push_requires_the_token() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = Spool::new(dir.path());
+
+    let (status, _) = push(&spool, None, r#"{"title": "no token"}"#).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = push(&spool, Some("wrong"), r#"{"title": "bad token"}"#).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(spool.read().unwrap().items.is_empty());
+
+    let (status, body) = push(&spool, Some("secret"), r#"{"title": "ok"}"#).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(body["accepted"], 1);
+}
+
+#[tokio::test]
+async fn bad_payloads_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = Spool::new(dir.path());
+
+    let (status, body) = push(&spool, Some("secret"), "{not json").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("expected an item"));
+    let (status, _) = push(&spool, Some("secret"), "[]").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // One invalid item rejects the whole batch
+    let (status, body) = push(
+        &spool,
+        Some("secret"),
+        r#"[{"title": "fine"}, {"title": " "}]"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "title must not be empty");
+    assert!(spool.read().unwrap().items.is_empty());
+}
+
+#[tokio::test]
+async fn pushed_items_show_up_in_feeds() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = Spool::new(dir.path());
+    let provider = WebhookProvider::new(WebhookProviderConfig::new(dir.path()));
+
+    let batch = json!([
+        {
+            "id": "build-1",
+            "feed": "ci",
+            "title": "Build #1 failed",
+            "url": "https://ci.example.com/builds/1",
+            "published": "2024-05-01T12:00:00Z"
+        },
+        {
+            "id": "build-2",
+            "feed": "ci",
+            "title": "Build #2 passed",
+            "published": "2024-05-01T13:00:00Z"
+        },
+        { "title": "Backup done" }
+    ]);
+    let (status, body) = push(&spool, Some("secret"), &batch.to_string()).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(body["ids"][0], "build-1");
+
+    let feeds = provider.list_feeds().await.unwrap();
+    let counts: Vec<(&str, Option<u32>)> = feeds
+        .iter()
+        .map(|feed| (feed.id.0.as_str(), feed.total_count))
+        .collect();
+    assert_eq!(counts, vec![("ci", Some(2)), ("inbox", Some(1))]);
+
+    let items = provider
+        .get_feed_items(&feed("ci"), FeedOptions::default())
+        .await
+        .unwrap();
+    let titles: Vec<&str> = items.iter().map(|item| item.title.as_str()).collect();
+    assert_eq!(titles, vec!["Build #2 passed", "Build #1 failed"]);
+
+    let page = provider
+        .get_feed_items(
+            &feed("ci"),
+            FeedOptions {
+                limit: Some(1),
+                offset: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(page[0].id.as_str(), "webhook:build-1");
+
+    // Replacing an item by ID keeps a single copy
+    let (status, _) = push(
+        &spool,
+        Some("secret"),
+        r#"{"id": "build-1", "feed": "ci", "title": "Build #1 fixed"}"#,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let items = provider
+        .get_feed_items(&feed("ci"), FeedOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().any(|item| item.title == "Build #1 fixed"));
+
+    let err = provider
+        .get_feed_items(&feed("../ci"), FeedOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StreamError::StreamNotFound(_)));
+}
+
+#[tokio::test]
+async fn dismiss_removes_from_the_spool() {
+    let dir = tempfile::tempdir().unwrap();
+    let spool = Spool::new(dir.path());
+    let provider = WebhookProvider::new(WebhookProviderConfig::new(dir.path()));
+    push(
+        &spool,
+        Some("secret"),
+        r#"{"title": "Disk almost full", "url": "https://grafana.example.com/d/1"}"#,
+    )
+    .await;
+
+    let item = provider
+        .get_feed_items(&feed("inbox"), FeedOptions::default())
+        .await
+        .unwrap()
+        .remove(0);
+    let actions = provider.available_actions(&item).await.unwrap();
+    let ids: Vec<&str> = actions.iter().map(|action| action.id.as_str()).collect();
+    assert_eq!(ids, vec!["open", "copy_link", "dismiss"]);
+
+    let result = provider.execute_action(&item, &actions[2]).await.unwrap();
+    assert!(result.success);
+    assert!(provider
+        .get_feed_items(&feed("inbox"), FeedOptions::default())
+        .await
+        .unwrap()
+        .is_empty());
+
+    let again = provider.execute_action(&item, &actions[2]).await.unwrap();
+    assert!(!again.success);
+}
+
+#[tokio::test]
+async fn endpoint_serves_over_tcp() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = WebhookProviderConfig::new(dir.path()).with_endpoint("127.0.0.1:0", None);
+    let provider = WebhookProvider::new(config);
+
+    let (handle, addr) = provider.start_server().await.unwrap().unwrap();
+    assert_ne!(addr.port(), 0);
+    handle.abort();
+
+    let no_endpoint = WebhookProvider::new(WebhookProviderConfig::new(dir.path()));
+    assert!(no_endpoint.start_server().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn public_endpoint_requires_a_token() {
+    let dir = tempfile::tempdir().unwrap();
+    let open = WebhookProviderConfig::new(dir.path()).with_endpoint("0.0.0.0:0", None);
+    assert!(open.validate().is_err());
+    let err = WebhookProvider::new(open).start_server().await.unwrap_err();
+    assert!(matches!(err, StreamError::Provider(_)));
+
+    // Checked again on the bound address, for callers skipping validation
+    let spool = Spool::new(dir.path());
+    assert!(start_server(spool.clone(), "0.0.0.0:0", None)
+        .await
+        .is_err());
+    assert!(start_server(spool, "0.0.0.0:0", Some(String::new()))
+        .await
+        .is_err());
+
+    let protected = WebhookProviderConfig::new(dir.path())
+        .with_endpoint("0.0.0.0:0", Some("secret".to_string()));
+    assert!(protected.validate().is_ok());
+    let (handle, _) = WebhookProvider::new(protected)
+        .start_server()
+        .await
+        .unwrap()
+        .unwrap();
+    handle.abort();
+
+    let local = WebhookProviderConfig::new(dir.path()).with_endpoint("localhost:7780", None);
+    assert!(local.validate().is_ok());
+}
@@ -15,19 +15,47 @@
 //! Sigilforge daemon. Ensure the daemon is running and configured with YouTube
 //! credentials.
 //!
+//! ## Channel RSS Feeds
+//!
+//! Every channel publishes its latest uploads as a public Atom feed that needs
+//! neither a token nor API quota. When the API rejects the token or the daily
+//! quota runs out, feeds fall back to the subscriptions already listed plus any
+//! [`YouTubeSettings::rss_channels`], and uploads are read from those feeds.
+//! Video details from the API are merged in whenever it still answers. With
+//! [`YouTubeSettings::rss_only`] uploads always come from the feeds, which
+//! saves the 100 quota units of every channel search.
+//!
+//! ## Chapters and SponsorBlock
+//!
+//! Chapter lists in video descriptions (`0:00 Intro`, one per line) are
+//! stored in the `chapters` metadata key as JSON and enable the
+//! `copy_chapter_link` action. With [`YouTubeSettings::sponsorblock`] the
+//! community-submitted [SponsorBlock](https://sponsor.ajay.app) segments of
+//! every listed video are stored under `sponsor_segments`.
+//!
+//! ## Transcripts
+//!
+//! [`YouTubeProvider::get_transcript`] reads a video's captions from the
+//! public timedtext endpoint, in the first of
+//! [`YouTubeSettings::transcript_languages`] that has a track. Videos it has
+//! no track for fall back to the Data API's captions endpoint, which only
+//! serves videos the account owns.
+//!
 //! ## API Reference
 //!
 //! - [YouTube Data API v3](https://developers.google.com/youtube/v3)
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use google_client::{GoogleClient, GoogleError, Method, Page};
 use reqwest::Client;
 use scryforge_provider_core::auth::TokenFetcher;
+use scryforge_provider_core::http::{client_builder, ProxyConfig};
 use scryforge_provider_core::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 // ============================================================================
@@ -42,9 +70,21 @@ pub enum YouTubeError {
     #[error("Failed to fetch auth token: {0}")]
     AuthError(String),
 
+    #[error("Rate limited: retry after {0} seconds")]
+    RateLimited(u64),
+
+    #[error("YouTube API quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("Channel not found: {0}")]
+    ChannelNotFound(String),
+
     #[error("YouTube API error: {0}")]
     ApiError(String),
 
+    #[error("YouTube API unavailable: {0}")]
+    Unavailable(String),
+
     #[error("Failed to parse response: {0}")]
     ParseError(String),
 }
@@ -54,33 +94,36 @@ impl From<YouTubeError> for StreamError {
         match err {
             YouTubeError::HttpError(e) => StreamError::Network(e.to_string()),
             YouTubeError::AuthError(e) => StreamError::AuthRequired(e),
+            YouTubeError::RateLimited(secs) => StreamError::RateLimited(secs),
+            YouTubeError::QuotaExceeded(e) => StreamError::Provider(e),
+            YouTubeError::ChannelNotFound(id) => {
+                StreamError::StreamNotFound(format!("Channel not found: {}", id))
+            }
             YouTubeError::ApiError(e) => StreamError::Provider(e),
+            YouTubeError::Unavailable(e) => StreamError::Transient(e),
             YouTubeError::ParseError(e) => StreamError::Internal(e),
         }
     }
 }
 
+impl From<GoogleError> for YouTubeError {
+    fn from(err: GoogleError) -> Self {
+        match err {
+            GoogleError::Http(e) => YouTubeError::HttpError(e),
+            GoogleError::Auth(e) => YouTubeError::AuthError(e),
+            GoogleError::RateLimited(secs) => YouTubeError::RateLimited(secs),
+            GoogleError::QuotaExceeded(e) => YouTubeError::QuotaExceeded(e),
+            GoogleError::ApiRequest(e) => YouTubeError::ApiError(e),
+            GoogleError::Unavailable(e) => YouTubeError::Unavailable(e),
+            GoogleError::Parse(e) => YouTubeError::ParseError(e),
+        }
+    }
+}
+
 // ============================================================================
 // YouTube API Response Types
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct YouTubeResponse<T> {
-    items: Vec<T>,
-    #[serde(rename = "nextPageToken")]
-    next_page_token: Option<String>,
-    #[serde(rename = "pageInfo")]
-    page_info: Option<PageInfo>,
-}
-
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct PageInfo {
-    #[serde(rename = "totalResults")]
-    total_results: u32,
-}
-
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct YouTubeSubscription {
@@ -221,6 +264,47 @@ struct ChannelSnippet {
     thumbnails: Option<Thumbnails>,
 }
 
+/// Captions in timedtext's `json3` format.
+#[derive(Debug, Deserialize)]
+struct TimedText {
+    #[serde(default)]
+    events: Vec<TimedTextEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimedTextEvent {
+    #[serde(rename = "tStartMs", default)]
+    start_ms: u64,
+    #[serde(rename = "dDurationMs", default)]
+    duration_ms: u64,
+    #[serde(default)]
+    segs: Vec<TimedTextSeg>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimedTextSeg {
+    #[serde(default)]
+    utf8: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct YouTubeCaption {
+    id: String,
+    snippet: CaptionSnippet,
+}
+
+#[derive(Debug, Deserialize)]
+struct CaptionSnippet {
+    language: String,
+}
+
+/// A segment from SponsorBlock's `skipSegments` endpoint.
+#[derive(Debug, Deserialize)]
+struct SkipSegment {
+    category: String,
+    segment: [f64; 2],
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct ChannelStatistics {
@@ -232,15 +316,115 @@ struct ChannelStatistics {
 // YouTube Provider Implementation
 // ============================================================================
 
+/// Provider settings, read from `[providers.youtube.settings]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct YouTubeSettings {
+    /// Channel IDs (`UC...`) to follow through their feeds, whether or not
+    /// the account is subscribed to them
+    pub rss_channels: Vec<String>,
+    /// Read uploads from the feeds even while the API is available
+    pub rss_only: bool,
+    /// Look up SponsorBlock segments for listed videos
+    pub sponsorblock: bool,
+    /// SponsorBlock categories to look up; empty means sponsor, selfpromo
+    /// and interaction
+    pub sponsorblock_categories: Vec<String>,
+    /// Caption languages to try for transcripts, in order; empty means `en`
+    pub transcript_languages: Vec<String>,
+}
+
+/// A channel's public uploads feed, from [`YouTubeProvider::channel_feeds`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelFeed {
+    pub channel_id: String,
+    /// Channel name
+    pub title: String,
+    /// Atom feed of the channel's latest uploads
+    pub url: String,
+}
+
+/// A video transcript, from [`YouTubeProvider::get_transcript`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub video_id: String,
+    /// Language code of the caption track
+    pub language: String,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// The transcript as plain text, one segment per line.
+    pub fn text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// One caption of a [`Transcript`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Start and duration in seconds
+    pub start: f64,
+    pub duration: f64,
+    pub text: String,
+}
+
+/// A chapter parsed from a video description.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Chapter {
+    /// Start time in seconds
+    start: u32,
+    title: String,
+}
+
+/// A SponsorBlock segment, stored in the `sponsor_segments` metadata key.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SponsorSegment {
+    category: String,
+    /// Start and end in seconds
+    start: f64,
+    end: f64,
+}
+
 /// YouTube provider that connects to YouTube Data API v3.
 pub struct YouTubeProvider {
+    /// Data API requests and their quota
+    google: GoogleClient,
+    /// Channel feeds, SponsorBlock, and timedtext, none of which need a token
     client: Client,
-    token_fetcher: Arc<dyn TokenFetcher>,
-    account_name: String,
+    rss_url: String,
+    sponsorblock_url: String,
+    timedtext_url: String,
+    settings: YouTubeSettings,
+    /// Subscriptions from the last successful listing, kept for when the API
+    /// stops answering
+    known_feeds: RwLock<Vec<Feed>>,
 }
 
 impl YouTubeProvider {
     const API_BASE: &'static str = "https://www.googleapis.com/youtube/v3";
+    /// Address of the public channel feeds, taking a `channel_id` query.
+    pub const RSS_URL: &'static str = "https://www.youtube.com/feeds/videos.xml";
+    const SPONSORBLOCK_URL: &'static str = "https://sponsor.ajay.app";
+    const TIMEDTEXT_URL: &'static str = "https://www.youtube.com/api/timedtext";
+    const DEFAULT_SPONSOR_CATEGORIES: [&'static str; 3] = ["sponsor", "selfpromo", "interaction"];
+
+    /// Daily quota of a Data API project, in units.
+    const DAILY_QUOTA: u32 = 10_000;
+    /// Quota cost of list requests.
+    const LIST_COST: u32 = 1;
+    /// Quota cost of inserts, updates, deletes, and ratings.
+    const WRITE_COST: u32 = 50;
+    /// Quota cost of a search.
+    const SEARCH_COST: u32 = 100;
+    /// Quota cost of listing a video's caption tracks.
+    const CAPTION_LIST_COST: u32 = 50;
+    /// Quota cost of downloading a caption track.
+    const CAPTION_DOWNLOAD_COST: u32 = 200;
 
     /// Create a new YouTube provider instance.
     ///
@@ -249,55 +433,77 @@ impl YouTubeProvider {
     /// * `token_fetcher` - Token fetcher for OAuth authentication
     /// * `account_name` - Account name for token lookup (e.g., "personal")
     pub fn new(token_fetcher: Arc<dyn TokenFetcher>, account_name: String) -> Self {
+        Self::with_base_url(token_fetcher, account_name, Self::API_BASE.to_string())
+    }
+
+    /// Create a provider that talks to a different API base URL, such as a
+    /// mock server in tests.
+    pub fn with_base_url(
+        token_fetcher: Arc<dyn TokenFetcher>,
+        account_name: String,
+        base_url: String,
+    ) -> Self {
         Self {
+            google: GoogleClient::new(token_fetcher, "youtube", account_name, base_url)
+                .with_daily_quota(Self::DAILY_QUOTA),
             client: Client::new(),
-            token_fetcher,
-            account_name,
+            rss_url: Self::RSS_URL.to_string(),
+            sponsorblock_url: Self::SPONSORBLOCK_URL.to_string(),
+            timedtext_url: Self::TIMEDTEXT_URL.to_string(),
+            settings: YouTubeSettings::default(),
+            known_feeds: RwLock::new(Vec::new()),
         }
     }
 
-    /// Fetch the OAuth access token from Sigilforge.
-    async fn get_access_token(&self) -> Result<String> {
-        self.token_fetcher
-            .fetch_token("youtube", &self.account_name)
-            .await
-            .map_err(|e| YouTubeError::AuthError(e.to_string()).into())
+    /// Configure channel feeds and SponsorBlock.
+    pub fn with_settings(mut self, settings: YouTubeSettings) -> Self {
+        self.settings = settings;
+        self
     }
 
-    /// Make an authenticated GET request to the YouTube API.
-    async fn api_get<T: for<'de> Deserialize<'de>>(
-        &self,
-        endpoint: &str,
-        params: &[(&str, &str)],
-    ) -> std::result::Result<T, YouTubeError> {
-        let token = self
-            .token_fetcher
-            .fetch_token("youtube", &self.account_name)
-            .await
-            .map_err(|e| YouTubeError::AuthError(e.to_string()))?;
+    /// Read channel feeds from a different URL, such as a mock server in tests.
+    pub fn with_rss_url(mut self, rss_url: String) -> Self {
+        self.rss_url = rss_url;
+        self
+    }
 
-        let url = format!("{}{}", Self::API_BASE, endpoint);
-        let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&token)
-            .query(params)
-            .send()
-            .await?;
+    /// Query a different SponsorBlock server.
+    pub fn with_sponsorblock_url(mut self, sponsorblock_url: String) -> Self {
+        self.sponsorblock_url = sponsorblock_url;
+        self
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(YouTubeError::ApiError(format!(
-                "API returned status {}: {}",
-                status, error_text
-            )));
-        }
+    /// Read captions from a different timedtext URL.
+    pub fn with_timedtext_url(mut self, timedtext_url: String) -> Self {
+        self.timedtext_url = timedtext_url;
+        self
+    }
 
-        response
-            .json::<T>()
-            .await
-            .map_err(|e| YouTubeError::ParseError(e.to_string()))
+    /// Send every request, to the Data API and the token-less endpoints
+    /// alike, through `proxy`.
+    pub fn with_proxy(mut self, proxy: &ProxyConfig) -> Result<Self> {
+        let client = client_builder(Some(proxy))?
+            .build()
+            .map_err(|e| StreamError::Internal(format!("Failed to build HTTP client: {e}")))?;
+        self.google = self.google.with_http_client(client.clone());
+        self.client = client;
+        Ok(self)
+    }
+
+    /// Fetch the OAuth access token from Sigilforge.
+    async fn get_access_token(&self) -> Result<String> {
+        self.google.token().await.map_err(StreamError::from)
+    }
+
+    /// Whether the channel feeds can stand in after `err`.
+    fn can_fall_back(err: &YouTubeError) -> bool {
+        matches!(
+            err,
+            YouTubeError::AuthError(_)
+                | YouTubeError::RateLimited(_)
+                | YouTubeError::QuotaExceeded(_)
+                | YouTubeError::Unavailable(_)
+        )
     }
 
     /// Parse ISO 8601 duration (PT1H30M15S) to seconds.
@@ -385,6 +591,92 @@ impl YouTubeProvider {
         }
     }
 
+    /// Parse a chapter timestamp: `M:SS`, `MM:SS` or `H:MM:SS`.
+    fn parse_chapter_timestamp(token: &str) -> Option<u32> {
+        let parts: Vec<&str> = token.split(':').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return None;
+        }
+
+        let mut seconds = 0;
+        for (i, part) in parts.iter().enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let value: u32 = part.parse().ok()?;
+            if i > 0 && (part.len() != 2 || value >= 60) {
+                return None;
+            }
+            seconds = seconds * 60 + value;
+        }
+        Some(seconds)
+    }
+
+    /// Parse the chapter list from a description: lines that start with a
+    /// timestamp, such as `0:00 Intro` or `(12:30) - Q&A`. Like YouTube, only
+    /// accepts lists of at least three chapters that start at 0:00 and run in
+    /// order; anything else is timestamps in prose, not chapters.
+    fn parse_chapters(description: &str) -> Vec<Chapter> {
+        let mut chapters: Vec<Chapter> = Vec::new();
+        for line in description.lines() {
+            let line = line.trim().trim_start_matches(['(', '[']);
+            let end = line
+                .find(|c: char| !(c.is_ascii_digit() || c == ':'))
+                .unwrap_or(line.len());
+            let Some(start) = Self::parse_chapter_timestamp(&line[..end]) else {
+                continue;
+            };
+            let title = line[end..]
+                .trim_start_matches([')', ']'])
+                .trim_start_matches(|c: char| c.is_whitespace() || "-–—:|".contains(c))
+                .trim_end();
+            if title.is_empty() {
+                continue;
+            }
+            if chapters.last().is_some_and(|last| start <= last.start) {
+                return Vec::new();
+            }
+            chapters.push(Chapter {
+                start,
+                title: title.to_string(),
+            });
+        }
+
+        if chapters.len() < 3 || chapters[0].start != 0 {
+            return Vec::new();
+        }
+        chapters
+    }
+
+    /// Store the chapters found in `description` under `chapters`.
+    fn insert_chapters(metadata: &mut HashMap<String, String>, description: &str) {
+        let chapters = Self::parse_chapters(description);
+        if chapters.is_empty() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(&chapters) {
+            metadata.insert("chapters".to_string(), json);
+        }
+    }
+
+    /// Chapters stored on an item by [`Self::insert_chapters`].
+    fn item_chapters(item: &Item) -> Vec<Chapter> {
+        item.metadata
+            .get("chapters")
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Format seconds the way YouTube shows chapter times: `5:30`, `1:02:03`.
+    fn format_chapter_timestamp(seconds: u32) -> String {
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            format!("{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
+    }
+
     /// Extract video ID from a YouTube URL or item.
     fn extract_video_id(item: &Item) -> Option<String> {
         // Try extracting from URL first (more reliable)
@@ -501,6 +793,7 @@ impl YouTubeProvider {
             .and_then(|cc| cc.parse::<u64>().ok());
 
         let mut metadata = HashMap::new();
+        Self::insert_chapters(&mut metadata, &video.snippet.description);
         if let Some(likes) = like_count {
             metadata.insert("like_count".to_string(), likes.to_string());
         }
@@ -546,50 +839,548 @@ impl YouTubeProvider {
             return Ok(vec![]);
         }
 
-        let ids = video_ids.join(",");
-        let response: YouTubeResponse<YouTubeVideo> = self
-            .api_get(
-                "/videos",
-                &[
-                    ("part", "snippet,contentDetails,statistics"),
-                    ("id", &ids),
-                    ("maxResults", "50"),
-                ],
+        let ids = video_ids.join(",");
+        let response: Page<YouTubeVideo> = self
+            .google
+            .get(
+                "/videos",
+                &[
+                    ("part", "snippet,contentDetails,statistics"),
+                    ("id", &ids),
+                    ("maxResults", "50"),
+                ],
+                Self::LIST_COST,
+                "fetch video details",
+            )
+            .await?;
+
+        Ok(response.items)
+    }
+
+    /// Fetch recent uploads of a channel through the API. Costs a channel
+    /// search, 100 quota units.
+    async fn api_channel_uploads(
+        &self,
+        channel_id: &str,
+        limit: u32,
+    ) -> std::result::Result<Vec<Item>, YouTubeError> {
+        let stream_id = StreamId::new("youtube", "feed", channel_id);
+
+        // Get the uploads playlist ID for the channel
+        let channel_response: Page<YouTubeChannel> = self
+            .google
+            .get(
+                "/channels",
+                &[("part", "contentDetails"), ("id", channel_id)],
+                Self::LIST_COST,
+                "fetch channel",
+            )
+            .await?;
+
+        if channel_response.items.is_empty() {
+            return Err(YouTubeError::ChannelNotFound(channel_id.to_string()));
+        }
+
+        // Get recent uploads from the channel
+        // YouTube channels have an "uploads" playlist we can query
+        let limit_str = limit.to_string();
+        let params = vec![
+            ("part", "snippet,contentDetails"),
+            ("channelId", channel_id),
+            ("maxResults", limit_str.as_str()),
+            ("order", "date"),
+            ("type", "video"),
+        ];
+
+        let search_response: Page<serde_json::Value> = self
+            .google
+            .get("/search", &params, Self::SEARCH_COST, "search channel uploads")
+            .await?;
+
+        // Extract video IDs from search results
+        let video_ids: Vec<String> = search_response
+            .items
+            .iter()
+            .filter_map(|item| {
+                item.get("id")
+                    .and_then(|id| id.get("videoId"))
+                    .and_then(|vid| vid.as_str())
+                    .map(|s| s.to_string())
+            })
+            .collect();
+
+        // Fetch full video details
+        let videos = self.fetch_video_details(&video_ids).await?;
+
+        Ok(videos
+            .into_iter()
+            .map(|video| self.video_to_item(video, stream_id.clone()))
+            .collect())
+    }
+
+    /// Fetch a channel's public uploads feed, returning the channel name and
+    /// its latest uploads (YouTube lists 15).
+    async fn fetch_channel_feed(
+        &self,
+        channel_id: &str,
+    ) -> std::result::Result<(String, Vec<Item>), YouTubeError> {
+        let response = self
+            .client
+            .get(&self.rss_url)
+            .query(&[("channel_id", channel_id)])
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(YouTubeError::ChannelNotFound(channel_id.to_string()));
+        }
+        if !response.status().is_success() {
+            return Err(YouTubeError::ApiError(format!(
+                "Channel feed returned status {}",
+                response.status()
+            )));
+        }
+
+        let body = response.bytes().await?;
+        let feed = feed_rs::parser::parse(&body[..])
+            .map_err(|e| YouTubeError::ParseError(e.to_string()))?;
+
+        let name = feed
+            .title
+            .map(|title| title.content)
+            .unwrap_or_else(|| channel_id.to_string());
+        let stream_id = StreamId::new("youtube", "feed", channel_id);
+        let items = feed
+            .entries
+            .into_iter()
+            .filter_map(|entry| Self::feed_entry_to_item(entry, channel_id, stream_id.clone()))
+            .collect();
+
+        Ok((name, items))
+    }
+
+    /// Convert a channel feed entry to an Item. Feeds carry no duration, so
+    /// `duration_seconds` stays empty until API details are merged in.
+    fn feed_entry_to_item(
+        entry: feed_rs::model::Entry,
+        channel_id: &str,
+        stream_id: StreamId,
+    ) -> Option<Item> {
+        let video_id = entry.id.strip_prefix("yt:video:")?.to_string();
+        let media = entry.media.into_iter().next();
+
+        let title = entry
+            .title
+            .map(|title| title.content)
+            .or_else(|| {
+                media
+                    .as_ref()
+                    .and_then(|m| m.title.as_ref())
+                    .map(|t| t.content.clone())
+            })
+            .unwrap_or_default();
+        let description = media
+            .as_ref()
+            .and_then(|m| m.description.as_ref())
+            .map(|d| d.content.clone())
+            .unwrap_or_default();
+        let view_count = media
+            .as_ref()
+            .and_then(|m| m.community.as_ref())
+            .and_then(|c| c.stats_views);
+        let thumbnail_url = media
+            .as_ref()
+            .and_then(|m| m.thumbnails.first())
+            .map(|thumb| thumb.image.uri.clone());
+
+        let author = entry.authors.into_iter().next().map(|person| Author {
+            name: person.name,
+            email: None,
+            url: Some(
+                person
+                    .uri
+                    .unwrap_or_else(|| format!("https://www.youtube.com/channel/{}", channel_id)),
+            ),
+            avatar_url: None,
+        });
+
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), "rss".to_string());
+        Self::insert_chapters(&mut metadata, &description);
+
+        Some(Item {
+            id: ItemId::new("youtube", &video_id),
+            stream_id,
+            title,
+            content: ItemContent::Video {
+                description,
+                duration_seconds: None,
+                view_count,
+            },
+            author,
+            published: entry.published,
+            updated: entry.updated,
+            url: Some(format!("https://www.youtube.com/watch?v={}", video_id)),
+            thumbnail_url,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata,
+        })
+    }
+
+    /// Fetch recent uploads of a channel through its feed, replacing entries
+    /// with full API details when the API still answers.
+    async fn rss_channel_uploads(
+        &self,
+        channel_id: &str,
+    ) -> std::result::Result<Vec<Item>, YouTubeError> {
+        let (_, mut items) = self.fetch_channel_feed(channel_id).await?;
+
+        let video_ids: Vec<String> = items.iter().filter_map(Self::extract_video_id).collect();
+        if let Ok(videos) = self.fetch_video_details(&video_ids).await {
+            let mut details: HashMap<String, YouTubeVideo> = videos
+                .into_iter()
+                .map(|video| (video.id.clone(), video))
+                .collect();
+            for item in &mut items {
+                let video = Self::extract_video_id(item).and_then(|id| details.remove(&id));
+                if let Some(video) = video {
+                    *item = self.video_to_item(video, item.stream_id.clone());
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Build the feed for a channel followed through `rss_channels`, named
+    /// after its feed title when the feed can be read.
+    async fn rss_channel_feed(&self, channel_id: &str) -> Feed {
+        let name = match self.fetch_channel_feed(channel_id).await {
+            Ok((name, _)) => name,
+            Err(_) => channel_id.to_string(),
+        };
+        Feed {
+            id: FeedId(channel_id.to_string()),
+            name,
+            description: None,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+        }
+    }
+
+    /// List the account's subscriptions through the API.
+    async fn subscription_feeds(&self) -> std::result::Result<Vec<Feed>, YouTubeError> {
+        // Subscriptions come 50 to a page
+        let subscriptions: Vec<YouTubeSubscription> = self
+            .google
+            .get_all(
+                "/subscriptions",
+                &[("part", "snippet"), ("mine", "true"), ("maxResults", "50")],
+                Self::LIST_COST,
+                "list subscriptions",
+            )
+            .await?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(|sub| Feed {
+                id: FeedId(sub.snippet.resource_id.channel_id.clone()),
+                name: sub.snippet.title,
+                description: Some(sub.snippet.description),
+                icon: Self::get_thumbnail_url(&sub.snippet.thumbnails),
+                unread_count: None,
+                total_count: None,
+            })
+            .collect())
+    }
+
+    /// List the account's subscriptions, plus any
+    /// [`YouTubeSettings::rss_channels`], as channel feeds that any RSS
+    /// reader can follow without a token or quota.
+    ///
+    /// Costs one quota unit per 50 subscriptions; fails when the API can't
+    /// be reached, since subscriptions can't be listed any other way.
+    pub async fn channel_feeds(&self) -> Result<Vec<ChannelFeed>> {
+        let mut feeds = self.subscription_feeds().await?;
+        for channel_id in &self.settings.rss_channels {
+            if !feeds.iter().any(|feed| &feed.id.0 == channel_id) {
+                feeds.push(self.rss_channel_feed(channel_id).await);
+            }
+        }
+
+        Ok(feeds
+            .into_iter()
+            .map(|feed| ChannelFeed {
+                url: self.channel_feed_url(&feed.id.0),
+                channel_id: feed.id.0,
+                title: feed.name,
+            })
+            .collect())
+    }
+
+    /// URL of a channel's uploads feed.
+    pub fn channel_feed_url(&self, channel_id: &str) -> String {
+        format!("{}?channel_id={}", self.rss_url, channel_id)
+    }
+
+    /// Fetch the SponsorBlock segments of a video; none if it has no
+    /// submissions.
+    async fn fetch_sponsor_segments(
+        &self,
+        video_id: &str,
+    ) -> std::result::Result<Vec<SponsorSegment>, YouTubeError> {
+        let categories: Vec<&str> = if self.settings.sponsorblock_categories.is_empty() {
+            Self::DEFAULT_SPONSOR_CATEGORIES.to_vec()
+        } else {
+            self.settings
+                .sponsorblock_categories
+                .iter()
+                .map(String::as_str)
+                .collect()
+        };
+        let categories = serde_json::to_string(&categories)
+            .map_err(|e| YouTubeError::ParseError(e.to_string()))?;
+
+        let url = format!("{}/api/skipSegments", self.sponsorblock_url);
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("videoID", video_id), ("categories", categories.as_str())])
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+        if !response.status().is_success() {
+            return Err(YouTubeError::ApiError(format!(
+                "SponsorBlock returned status {}",
+                response.status()
+            )));
+        }
+
+        let segments: Vec<SkipSegment> = response
+            .json()
+            .await
+            .map_err(|e| YouTubeError::ParseError(e.to_string()))?;
+        Ok(segments
+            .into_iter()
+            .map(|segment| SponsorSegment {
+                category: segment.category,
+                start: segment.segment[0],
+                end: segment.segment[1],
+            })
+            .collect())
+    }
+
+    /// Store SponsorBlock segments under `sponsor_segments` when enabled.
+    /// Lookups that fail leave the item without segments rather than failing
+    /// the listing.
+    async fn attach_sponsor_segments(&self, items: &mut [Item]) {
+        if !self.settings.sponsorblock {
+            return;
+        }
+
+        for item in items.iter_mut() {
+            let Some(video_id) = Self::extract_video_id(item) else {
+                continue;
+            };
+            let segments = match self.fetch_sponsor_segments(&video_id).await {
+                Ok(segments) if !segments.is_empty() => segments,
+                _ => continue,
+            };
+            if let Ok(json) = serde_json::to_string(&segments) {
+                item.metadata.insert("sponsor_segments".to_string(), json);
+            }
+        }
+    }
+
+    /// Fetch the transcript of a video.
+    ///
+    /// Tries each of the configured transcript languages on the public
+    /// timedtext endpoint, then the Data API's caption tracks.
+    pub async fn get_transcript(&self, video_id: &str) -> Result<Transcript> {
+        let languages: Vec<&str> = if self.settings.transcript_languages.is_empty() {
+            vec!["en"]
+        } else {
+            self.settings
+                .transcript_languages
+                .iter()
+                .map(String::as_str)
+                .collect()
+        };
+
+        for language in &languages {
+            let segments = self.fetch_timed_text(video_id, language).await?;
+            if !segments.is_empty() {
+                return Ok(Transcript {
+                    video_id: video_id.to_string(),
+                    language: language.to_string(),
+                    segments,
+                });
+            }
+        }
+
+        self.fetch_caption_track(video_id, &languages)
+            .await?
+            .ok_or_else(|| {
+                StreamError::ItemNotFound(format!("No transcript available for video {}", video_id))
+            })
+    }
+
+    /// Fetch captions in one language from the timedtext endpoint, which
+    /// answers with an empty body when there is no such track.
+    async fn fetch_timed_text(
+        &self,
+        video_id: &str,
+        language: &str,
+    ) -> std::result::Result<Vec<TranscriptSegment>, YouTubeError> {
+        let response = self
+            .client
+            .get(&self.timedtext_url)
+            .query(&[("v", video_id), ("lang", language), ("fmt", "json3")])
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+        if !response.status().is_success() {
+            return Err(YouTubeError::ApiError(format!(
+                "Timedtext returned status {}",
+                response.status()
+            )));
+        }
+
+        let body = response.text().await?;
+        if body.trim().is_empty() {
+            return Ok(vec![]);
+        }
+        let timed_text: TimedText =
+            serde_json::from_str(&body).map_err(|e| YouTubeError::ParseError(e.to_string()))?;
+
+        Ok(timed_text
+            .events
+            .into_iter()
+            .filter_map(|event| {
+                let text: String = event.segs.iter().map(|seg| seg.utf8.as_str()).collect();
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                (!text.is_empty()).then(|| TranscriptSegment {
+                    start: event.start_ms as f64 / 1000.0,
+                    duration: event.duration_ms as f64 / 1000.0,
+                    text,
+                })
+            })
+            .collect())
+    }
+
+    /// Download a caption track through the Data API, preferring the given
+    /// languages. Costs 250 quota units and only works for videos the
+    /// account owns.
+    async fn fetch_caption_track(
+        &self,
+        video_id: &str,
+        languages: &[&str],
+    ) -> std::result::Result<Option<Transcript>, YouTubeError> {
+        let tracks: Page<YouTubeCaption> = self
+            .google
+            .get(
+                "/captions",
+                &[("part", "snippet"), ("videoId", video_id)],
+                Self::CAPTION_LIST_COST,
+                "list caption tracks",
+            )
+            .await?;
+
+        let track = languages
+            .iter()
+            .find_map(|language| {
+                tracks
+                    .items
+                    .iter()
+                    .find(|track| track.snippet.language == *language)
+            })
+            .or_else(|| tracks.items.first());
+        let Some(track) = track else {
+            return Ok(None);
+        };
+
+        let endpoint = format!("/captions/{}", track.id);
+        let srt = self
+            .google
+            .send(
+                Method::GET,
+                &endpoint,
+                &[("tfmt", "srt")],
+                None::<&()>,
+                Self::CAPTION_DOWNLOAD_COST,
+                "download caption track",
             )
+            .await?
+            .text()
             .await?;
 
-        Ok(response.items)
+        Ok(Some(Transcript {
+            video_id: video_id.to_string(),
+            language: track.snippet.language.clone(),
+            segments: Self::parse_srt(&srt),
+        }))
     }
 
-    /// Rate a video (like, dislike, or none).
-    async fn rate_video(&self, video_id: &str, rating: &str) -> Result<()> {
-        let token = self.get_access_token().await?;
-        let url = format!("{}/videos/rate", Self::API_BASE);
-
-        let response = self.client
-            .post(&url)
-            .bearer_auth(&token)
-            .query(&[("id", video_id), ("rating", rating)])
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
+    /// Parse SubRip captions.
+    fn parse_srt(srt: &str) -> Vec<TranscriptSegment> {
+        let srt = srt.replace("\r\n", "\n");
+        srt.split("\n\n")
+            .filter_map(|block| {
+                let mut lines = block.trim().lines();
+                let mut timing = lines.next()?;
+                if !timing.contains("-->") {
+                    // Skip the cue number
+                    timing = lines.next()?;
+                }
+                let (start, end) = timing.split_once("-->")?;
+                let start = Self::parse_srt_time(start.trim())?;
+                let end = Self::parse_srt_time(end.trim())?;
+                let text = lines.collect::<Vec<_>>().join(" ");
+                (!text.is_empty()).then(|| TranscriptSegment {
+                    start,
+                    duration: (end - start).max(0.0),
+                    text,
+                })
+            })
+            .collect()
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to rate video: {} - {}", status, error_text
-            )));
+    /// Parse an SRT time (`00:01:02,500`) to seconds.
+    fn parse_srt_time(time: &str) -> Option<f64> {
+        let (clock, millis) = time.split_once(',')?;
+        let mut seconds = 0.0;
+        for part in clock.split(':') {
+            seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
         }
+        Some(seconds + millis.parse::<f64>().ok()? / 1000.0)
+    }
 
+    /// Rate a video (like, dislike, or none).
+    async fn rate_video(&self, video_id: &str, rating: &str) -> Result<()> {
+        self.google
+            .send(
+                Method::POST,
+                "/videos/rate",
+                &[("id", video_id), ("rating", rating)],
+                None::<&()>,
+                Self::WRITE_COST,
+                "rate video",
+            )
+            .await?;
         Ok(())
     }
 
     /// Subscribe to a channel.
     async fn subscribe_to_channel(&self, channel_id: &str) -> Result<()> {
-        let token = self.get_access_token().await?;
-        let url = format!("{}/subscriptions", Self::API_BASE);
-
         let body = serde_json::json!({
             "snippet": {
                 "resourceId": {
@@ -599,23 +1390,16 @@ impl YouTubeProvider {
             }
         });
 
-        let response = self.client
-            .post(&url)
-            .bearer_auth(&token)
-            .query(&[("part", "snippet")])
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to subscribe: {} - {}", status, error_text
-            )));
-        }
-
+        self.google
+            .send(
+                Method::POST,
+                "/subscriptions",
+                &[("part", "snippet")],
+                Some(&body),
+                Self::WRITE_COST,
+                "subscribe",
+            )
+            .await?;
         Ok(())
     }
 }
@@ -661,6 +1445,7 @@ impl Provider for YouTubeProvider {
                 items_removed: 0,
                 errors: vec![],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
             Err(e) => Ok(SyncResult {
                 success: false,
@@ -669,6 +1454,7 @@ impl Provider for YouTubeProvider {
                 items_removed: 0,
                 errors: vec![e.to_string()],
                 duration_ms: start.elapsed().as_millis() as u64,
+                streams: Vec::new(),
             }),
         }
     }
@@ -679,11 +1465,16 @@ impl Provider for YouTubeProvider {
             has_collections: true,
             has_saved_items: true,
             has_communities: false,
+            supports_search: false,
+            supports_write_actions: false,
+            supports_incremental_sync: false,
+            supports_pagination: true,
+            supports_notifications: false,
         }
     }
 
-    async fn available_actions(&self, _item: &Item) -> Result<Vec<Action>> {
-        Ok(vec![
+    async fn available_actions(&self, item: &Item) -> Result<Vec<Action>> {
+        let mut actions = vec![
             Action {
                 id: "open".to_string(),
                 name: "Open in YouTube".to_string(),
@@ -747,7 +1538,19 @@ impl Provider for YouTubeProvider {
                 kind: ActionKind::Custom("subscribe".to_string()),
                 keyboard_shortcut: Some("S".to_string()),
             },
-        ])
+        ];
+
+        if !Self::item_chapters(item).is_empty() {
+            actions.push(Action {
+                id: "copy_chapter_link".to_string(),
+                name: "Copy Chapter Link".to_string(),
+                description: "Copy a youtu.be link to the start of a chapter".to_string(),
+                kind: ActionKind::Custom("copy_chapter_link".to_string()),
+                keyboard_shortcut: Some("C".to_string()),
+            });
+        }
+
+        Ok(actions)
     }
 
     async fn execute_action(&self, item: &Item, action: &Action) -> Result<ActionResult> {
@@ -826,6 +1629,47 @@ impl Provider for YouTubeProvider {
                         })
                     }
                 }
+                "copy_chapter_link" => {
+                    // Like open_at_time, the client prompts for the chapter
+                    let chapters = Self::item_chapters(item);
+                    match Self::extract_video_id(item) {
+                        Some(video_id) if !chapters.is_empty() => {
+                            let short_url = Self::make_short_url(&video_id);
+                            let mut options = Vec::new();
+                            let mut lines = Vec::new();
+                            for chapter in &chapters {
+                                let timestamp = Self::format_chapter_timestamp(chapter.start);
+                                let url = format!("{}?t={}", short_url, chapter.start);
+                                lines.push(format!("{} {} {}", timestamp, chapter.title, url));
+                                options.push(serde_json::json!({
+                                    "title": chapter.title,
+                                    "start_seconds": chapter.start,
+                                    "timestamp": timestamp,
+                                    "url": url
+                                }));
+                            }
+                            Ok(ActionResult {
+                                success: true,
+                                message: Some(format!(
+                                    "Choose a chapter to copy ({} chapters)",
+                                    options.len()
+                                )),
+                                data: Some(serde_json::json!({
+                                    "video_id": video_id,
+                                    "chapters": options,
+                                    "text": lines.join("\n"),
+                                    "requires_input": true,
+                                    "input_type": "chapter"
+                                })),
+                            })
+                        }
+                        _ => Ok(ActionResult {
+                            success: false,
+                            message: Some("This video has no chapters".to_string()),
+                            data: None,
+                        }),
+                    }
+                }
                 "download" => {
                     if let Some(url) = &item.url {
                         match Self::find_download_tool() {
@@ -932,94 +1776,138 @@ impl Provider for YouTubeProvider {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn as_feeds(&self) -> Option<&dyn HasFeeds> {
+        Some(self)
+    }
+
+    fn as_collections(&self) -> Option<&dyn HasCollections> {
+        Some(self)
+    }
+
+    async fn diagnose(&self) -> Vec<DiagnosticCheck> {
+        let mut checks = Vec::new();
+
+        match self.get_access_token().await {
+            Ok(_) => {
+                checks.push(DiagnosticCheck::ok(
+                    "token",
+                    format!("Sigilforge has a token for account '{}'", self.google.account()),
+                ));
+
+                // One cheap request proves the token is accepted and the API reachable
+                let params = [("part", "id"), ("mine", "true")];
+                let check = match self
+                    .google
+                    .get::<serde_json::Value>("/channels", &params, Self::LIST_COST, "check the token")
+                    .await
+                {
+                    Ok(_) => {
+                        let quota = self.google.quota();
+                        DiagnosticCheck::ok(
+                            "api",
+                            format!(
+                                "YouTube Data API accepted the token ({} of {} quota units spent today)",
+                                quota.spent,
+                                Self::DAILY_QUOTA
+                            ),
+                        )
+                    }
+                    Err(GoogleError::RateLimited(secs)) => DiagnosticCheck::warning(
+                        "api",
+                        format!("Rate limited for {} seconds", secs),
+                        "Wait for the quota to reset, or sync less often",
+                    ),
+                    Err(GoogleError::QuotaExceeded(_)) => DiagnosticCheck::warning(
+                        "api",
+                        "The Data API quota for today is used up; feeds are read from channel RSS",
+                        "Wait for the quota to reset at midnight Pacific time, or set `rss_only`",
+                    ),
+                    Err(GoogleError::Http(e)) => DiagnosticCheck::failed(
+                        "api",
+                        format!("Could not reach the YouTube API: {}", e),
+                        "Check your network connection and proxy settings",
+                    ),
+                    Err(e) => DiagnosticCheck::failed(
+                        "api",
+                        e.to_string(),
+                        format!(
+                            "Re-authorize with `sigilforge add-account google {}` (see docs/YOUTUBE_SETUP.md)",
+                            self.google.account()
+                        ),
+                    ),
+                };
+                checks.push(check);
+            }
+            Err(e) => checks.push(DiagnosticCheck::failed(
+                "token",
+                e.to_string(),
+                format!(
+                    "Add the account with `sigilforge add-account google {}` (see docs/YOUTUBE_SETUP.md)",
+                    self.google.account()
+                ),
+            )),
+        }
+
+        checks.push(match Self::find_download_tool() {
+            Some("yt-dlp") => DiagnosticCheck::ok("yt-dlp", "yt-dlp is installed"),
+            Some(tool) => DiagnosticCheck::warning(
+                "yt-dlp",
+                format!("Only {} is installed; downloads may break on recent videos", tool),
+                "pip install yt-dlp",
+            ),
+            None => DiagnosticCheck::warning(
+                "yt-dlp",
+                "Neither yt-dlp nor youtube-dl is in PATH; the download action is unavailable",
+                "pip install yt-dlp",
+            ),
+        });
+
+        checks
+    }
 }
 
 #[async_trait]
 impl HasFeeds for YouTubeProvider {
     async fn list_feeds(&self) -> Result<Vec<Feed>> {
-        let response: YouTubeResponse<YouTubeSubscription> = self
-            .api_get(
-                "/subscriptions",
-                &[("part", "snippet"), ("mine", "true"), ("maxResults", "50")],
-            )
-            .await
-            .map_err(StreamError::from)?;
+        let mut feeds = match self.subscription_feeds().await {
+            Ok(feeds) => {
+                *self.known_feeds.write().unwrap() = feeds.clone();
+                feeds
+            }
+            Err(e) if Self::can_fall_back(&e) => {
+                let known = self.known_feeds.read().unwrap().clone();
+                if known.is_empty() && self.settings.rss_channels.is_empty() {
+                    return Err(e.into());
+                }
+                known
+            }
+            Err(e) => return Err(e.into()),
+        };
 
-        let feeds = response
-            .items
-            .into_iter()
-            .map(|sub| Feed {
-                id: FeedId(sub.snippet.resource_id.channel_id.clone()),
-                name: sub.snippet.title,
-                description: Some(sub.snippet.description),
-                icon: Self::get_thumbnail_url(&sub.snippet.thumbnails),
-                unread_count: None,
-                total_count: None,
-            })
-            .collect();
+        for channel_id in &self.settings.rss_channels {
+            if !feeds.iter().any(|feed| &feed.id.0 == channel_id) {
+                feeds.push(self.rss_channel_feed(channel_id).await);
+            }
+        }
 
         Ok(feeds)
     }
 
     async fn get_feed_items(&self, feed_id: &FeedId, options: FeedOptions) -> Result<Vec<Item>> {
         let channel_id = &feed_id.0;
-        let stream_id = StreamId::new("youtube", "feed", channel_id);
-
-        // Get the uploads playlist ID for the channel
-        let channel_response: YouTubeResponse<YouTubeChannel> = self
-            .api_get(
-                "/channels",
-                &[("part", "contentDetails"), ("id", channel_id)],
-            )
-            .await
-            .map_err(StreamError::from)?;
-
-        if channel_response.items.is_empty() {
-            return Err(StreamError::StreamNotFound(format!(
-                "Channel not found: {}",
-                channel_id
-            )));
-        }
-
-        // Get recent uploads from the channel
-        // YouTube channels have an "uploads" playlist we can query
         let limit = options.limit.unwrap_or(25).min(50);
-        let limit_str = limit.to_string();
-        let params = vec![
-            ("part", "snippet,contentDetails"),
-            ("channelId", channel_id),
-            ("maxResults", limit_str.as_str()),
-            ("order", "date"),
-            ("type", "video"),
-        ];
-
-        let search_response: YouTubeResponse<serde_json::Value> = self
-            .api_get("/search", &params)
-            .await
-            .map_err(StreamError::from)?;
-
-        // Extract video IDs from search results
-        let video_ids: Vec<String> = search_response
-            .items
-            .iter()
-            .filter_map(|item| {
-                item.get("id")
-                    .and_then(|id| id.get("videoId"))
-                    .and_then(|vid| vid.as_str())
-                    .map(|s| s.to_string())
-            })
-            .collect();
-
-        // Fetch full video details
-        let videos = self
-            .fetch_video_details(&video_ids)
-            .await
-            .map_err(StreamError::from)?;
 
-        let mut items: Vec<Item> = videos
-            .into_iter()
-            .map(|video| self.video_to_item(video, stream_id.clone()))
-            .collect();
+        let mut items = if self.settings.rss_only {
+            self.rss_channel_uploads(channel_id).await?
+        } else {
+            match self.api_channel_uploads(channel_id, limit).await {
+                Ok(items) => items,
+                Err(e) if Self::can_fall_back(&e) => self.rss_channel_uploads(channel_id).await?,
+                Err(e) => return Err(e.into()),
+            }
+        };
+        items.truncate(limit as usize);
 
         // Apply filters
         if let Some(since) = options.since {
@@ -1029,6 +1917,7 @@ impl HasFeeds for YouTubeProvider {
         // Apply offset and limit
         let offset = options.offset.unwrap_or(0) as usize;
         items = items.into_iter().skip(offset).collect();
+        self.attach_sponsor_segments(&mut items).await;
 
         Ok(items)
     }
@@ -1037,14 +1926,17 @@ impl HasFeeds for YouTubeProvider {
 #[async_trait]
 impl HasCollections for YouTubeProvider {
     async fn list_collections(&self) -> Result<Vec<Collection>> {
-        let response: YouTubeResponse<YouTubePlaylist> = self
-            .api_get(
+        let response: Page<YouTubePlaylist> = self
+            .google
+            .get(
                 "/playlists",
                 &[
                     ("part", "snippet,contentDetails"),
                     ("mine", "true"),
                     ("maxResults", "50"),
                 ],
+                Self::LIST_COST,
+                "list playlists",
             )
             .await
             .map_err(StreamError::from)?;
@@ -1073,14 +1965,17 @@ impl HasCollections for YouTubeProvider {
         let playlist_id = &collection_id.0;
         let stream_id = StreamId::new("youtube", "playlist", playlist_id);
 
-        let response: YouTubeResponse<YouTubePlaylistItem> = self
-            .api_get(
+        let response: Page<YouTubePlaylistItem> = self
+            .google
+            .get(
                 "/playlistItems",
                 &[
                     ("part", "snippet,contentDetails"),
                     ("playlistId", playlist_id),
                     ("maxResults", "50"),
                 ],
+                Self::LIST_COST,
+                "list playlist items",
             )
             .await
             .map_err(StreamError::from)?;
@@ -1099,10 +1994,11 @@ impl HasCollections for YouTubeProvider {
             .await
             .map_err(StreamError::from)?;
 
-        let items = videos
+        let mut items: Vec<Item> = videos
             .into_iter()
             .map(|video| self.video_to_item(video, stream_id.clone()))
             .collect();
+        self.attach_sponsor_segments(&mut items).await;
 
         Ok(items)
     }
@@ -1121,9 +2017,6 @@ impl HasCollections for YouTubeProvider {
         let playlist_id = &collection_id.0;
 
         // Add video to playlist
-        let token = self.get_access_token().await?;
-        let url = format!("{}/playlistItems", Self::API_BASE);
-
         let body = serde_json::json!({
             "snippet": {
                 "playlistId": playlist_id,
@@ -1134,25 +2027,16 @@ impl HasCollections for YouTubeProvider {
             }
         });
 
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&token)
-            .query(&[("part", "snippet")])
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to add to collection: {} - {}",
-                status, error_text
-            )));
-        }
-
+        self.google
+            .send(
+                Method::POST,
+                "/playlistItems",
+                &[("part", "snippet")],
+                Some(&body),
+                Self::WRITE_COST,
+                "add to collection",
+            )
+            .await?;
         Ok(())
     }
 
@@ -1170,14 +2054,17 @@ impl HasCollections for YouTubeProvider {
             .ok_or_else(|| StreamError::Provider("Invalid item ID format".to_string()))?;
 
         // Get playlist items to find the specific playlistItem ID
-        let response: YouTubeResponse<YouTubePlaylistItem> = self
-            .api_get(
+        let response: Page<YouTubePlaylistItem> = self
+            .google
+            .get(
                 "/playlistItems",
                 &[
                     ("part", "id,contentDetails"),
                     ("playlistId", playlist_id),
                     ("maxResults", "50"),
                 ],
+                Self::LIST_COST,
+                "list playlist items",
             )
             .await
             .map_err(StreamError::from)?;
@@ -1196,34 +2083,20 @@ impl HasCollections for YouTubeProvider {
             .ok_or_else(|| StreamError::ItemNotFound("Item not found in collection".to_string()))?;
 
         // Delete the playlist item
-        let token = self.get_access_token().await?;
-        let url = format!("{}/playlistItems", Self::API_BASE);
-
-        let response = self
-            .client
-            .delete(&url)
-            .bearer_auth(&token)
-            .query(&[("id", playlist_item_id.as_str())])
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to remove from collection: {} - {}",
-                status, error_text
-            )));
-        }
-
+        self.google
+            .send(
+                Method::DELETE,
+                "/playlistItems",
+                &[("id", playlist_item_id.as_str())],
+                None::<&()>,
+                Self::WRITE_COST,
+                "remove from collection",
+            )
+            .await?;
         Ok(())
     }
 
     async fn create_collection(&self, name: &str) -> Result<Collection> {
-        let token = self.get_access_token().await?;
-        let url = format!("{}/playlists", Self::API_BASE);
-
         let body = serde_json::json!({
             "snippet": {
                 "title": name,
@@ -1234,29 +2107,17 @@ impl HasCollections for YouTubeProvider {
             }
         });
 
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&token)
-            .query(&[("part", "snippet,status,contentDetails")])
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to create collection: {} - {}",
-                status, error_text
-            )));
-        }
-
-        let playlist: YouTubePlaylist = response
-            .json()
-            .await
-            .map_err(|e| StreamError::Internal(format!("Failed to parse response: {}", e)))?;
+        let playlist: YouTubePlaylist = self
+            .google
+            .send_json(
+                Method::POST,
+                "/playlists",
+                &[("part", "snippet,status,contentDetails")],
+                Some(&body),
+                Self::WRITE_COST,
+                "create collection",
+            )
+            .await?;
 
         Ok(Collection {
             id: CollectionId(playlist.id),
@@ -1285,13 +2146,16 @@ impl HasSavedItems for YouTubeProvider {
         // Fetch Watch Later
         if options.category.is_none() || options.category.as_deref() == Some("watch-later") {
             if let Ok(response) = self
-                .api_get::<YouTubeResponse<YouTubePlaylistItem>>(
+                .google
+                .get::<Page<YouTubePlaylistItem>>(
                     "/playlistItems",
                     &[
                         ("part", "snippet,contentDetails"),
                         ("playlistId", "WL"), // Watch Later playlist
                         ("maxResults", "50"),
                     ],
+                    Self::LIST_COST,
+                    "list Watch Later",
                 )
                 .await
             {
@@ -1311,13 +2175,16 @@ impl HasSavedItems for YouTubeProvider {
         // Fetch Liked Videos
         if options.category.is_none() || options.category.as_deref() == Some("liked") {
             if let Ok(response) = self
-                .api_get::<YouTubeResponse<YouTubePlaylistItem>>(
+                .google
+                .get::<Page<YouTubePlaylistItem>>(
                     "/playlistItems",
                     &[
                         ("part", "snippet,contentDetails"),
                         ("playlistId", "LL"), // Liked videos playlist
                         ("maxResults", "50"),
                     ],
+                    Self::LIST_COST,
+                    "list liked videos",
                 )
                 .await
             {
@@ -1347,6 +2214,7 @@ impl HasSavedItems for YouTubeProvider {
         if let Some(limit) = limit {
             items.truncate(limit);
         }
+        self.attach_sponsor_segments(&mut items).await;
 
         Ok(items)
     }
@@ -1364,9 +2232,6 @@ impl HasSavedItems for YouTubeProvider {
             .strip_prefix("youtube:")
             .ok_or_else(|| StreamError::Provider("Invalid item ID format".to_string()))?;
 
-        let token = self.get_access_token().await?;
-        let url = format!("{}/playlistItems", Self::API_BASE);
-
         let body = serde_json::json!({
             "snippet": {
                 "playlistId": "WL", // Watch Later playlist ID
@@ -1377,25 +2242,16 @@ impl HasSavedItems for YouTubeProvider {
             }
         });
 
-        let response = self
-            .client
-            .post(&url)
-            .bearer_auth(&token)
-            .query(&[("part", "snippet")])
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to save item: {} - {}",
-                status, error_text
-            )));
-        }
-
+        self.google
+            .send(
+                Method::POST,
+                "/playlistItems",
+                &[("part", "snippet")],
+                Some(&body),
+                Self::WRITE_COST,
+                "save item",
+            )
+            .await?;
         Ok(())
     }
 
@@ -1407,14 +2263,17 @@ impl HasSavedItems for YouTubeProvider {
             .ok_or_else(|| StreamError::Provider("Invalid item ID format".to_string()))?;
 
         // Get Watch Later playlist items to find the playlistItem ID
-        let response: YouTubeResponse<YouTubePlaylistItem> = self
-            .api_get(
+        let response: Page<YouTubePlaylistItem> = self
+            .google
+            .get(
                 "/playlistItems",
                 &[
                     ("part", "id,contentDetails"),
                     ("playlistId", "WL"),
                     ("maxResults", "50"),
                 ],
+                Self::LIST_COST,
+                "list Watch Later",
             )
             .await
             .map_err(StreamError::from)?;
@@ -1435,27 +2294,16 @@ impl HasSavedItems for YouTubeProvider {
             })?;
 
         // Delete the playlist item
-        let token = self.get_access_token().await?;
-        let url = format!("{}/playlistItems", Self::API_BASE);
-
-        let response = self
-            .client
-            .delete(&url)
-            .bearer_auth(&token)
-            .query(&[("id", playlist_item_id.as_str())])
-            .send()
-            .await
-            .map_err(|e| StreamError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StreamError::Provider(format!(
-                "Failed to unsave item: {} - {}",
-                status, error_text
-            )));
-        }
-
+        self.google
+            .send(
+                Method::DELETE,
+                "/playlistItems",
+                &[("id", playlist_item_id.as_str())],
+                None::<&()>,
+                Self::WRITE_COST,
+                "unsave item",
+            )
+            .await?;
         Ok(())
     }
 }
@@ -1491,6 +2339,21 @@ mod tests {
         assert!(!caps.has_communities);
     }
 
+    #[test]
+    fn test_channel_feed_url() {
+        let provider = create_test_provider();
+        assert_eq!(
+            provider.channel_feed_url("UCabc123"),
+            "https://www.youtube.com/feeds/videos.xml?channel_id=UCabc123"
+        );
+
+        let provider = provider.with_rss_url("http://127.0.0.1:9/feeds".to_string());
+        assert_eq!(
+            provider.channel_feed_url("UCabc123"),
+            "http://127.0.0.1:9/feeds?channel_id=UCabc123"
+        );
+    }
+
     #[test]
     fn test_parse_duration() {
         assert_eq!(YouTubeProvider::parse_duration("PT1H30M15S"), Some(5415));
@@ -1638,6 +2501,113 @@ mod tests {
         assert_eq!(item.metadata.get("comment_count"), Some(&"10".to_string()));
     }
 
+    #[test]
+    fn test_parse_chapters() {
+        let description = "Full walkthrough.\n\n\
+            0:00 Intro\n\
+            (1:30) - Setting up\n\
+            [12:05] Q&A: your questions\n\
+            1:02:03 | Outro\n\n\
+            Thanks to everyone who came at 5:00!";
+        let chapters = YouTubeProvider::parse_chapters(description);
+        let parsed: Vec<(u32, &str)> = chapters
+            .iter()
+            .map(|chapter| (chapter.start, chapter.title.as_str()))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (0, "Intro"),
+                (90, "Setting up"),
+                (725, "Q&A: your questions"),
+                (3723, "Outro"),
+            ]
+        );
+
+        // Not starting at 0:00, too short, or out of order
+        assert!(YouTubeProvider::parse_chapters("0:30 A\n1:00 B\n2:00 C").is_empty());
+        assert!(YouTubeProvider::parse_chapters("0:00 A\n1:00 B").is_empty());
+        assert!(YouTubeProvider::parse_chapters("0:00 A\n2:00 B\n1:00 C").is_empty());
+        // Malformed timestamps
+        assert_eq!(YouTubeProvider::parse_chapter_timestamp("1:5"), None);
+        assert_eq!(YouTubeProvider::parse_chapter_timestamp("1:75"), None);
+        assert_eq!(YouTubeProvider::parse_chapter_timestamp("90"), None);
+
+        assert_eq!(YouTubeProvider::format_chapter_timestamp(90), "1:30");
+        assert_eq!(YouTubeProvider::format_chapter_timestamp(3723), "1:02:03");
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:04,500\r\nWelcome back\r\nto the channel\r\n\r\n\
+                   2\r\n00:01:02,250 --> 00:01:03,000\r\nToday: lifetimes\r\n";
+        let segments = YouTubeProvider::parse_srt(srt);
+        assert_eq!(
+            segments,
+            vec![
+                TranscriptSegment {
+                    start: 1.0,
+                    duration: 3.5,
+                    text: "Welcome back to the channel".to_string(),
+                },
+                TranscriptSegment {
+                    start: 62.25,
+                    duration: 0.75,
+                    text: "Today: lifetimes".to_string(),
+                },
+            ]
+        );
+
+        let transcript = Transcript {
+            video_id: "test-video".to_string(),
+            language: "en".to_string(),
+            segments,
+        };
+        assert_eq!(
+            transcript.text(),
+            "Welcome back to the channel\nToday: lifetimes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_chapter_link() {
+        let provider = create_test_provider();
+        let mut item = create_test_video_item();
+        let action = Action {
+            id: "copy_chapter_link".to_string(),
+            name: "Copy Chapter Link".to_string(),
+            description: "Copy a youtu.be link to the start of a chapter".to_string(),
+            kind: ActionKind::Custom("copy_chapter_link".to_string()),
+            keyboard_shortcut: Some("C".to_string()),
+        };
+
+        assert_eq!(provider.available_actions(&item).await.unwrap().len(), 9);
+        let result = provider.execute_action(&item, &action).await.unwrap();
+        assert!(!result.success);
+
+        YouTubeProvider::insert_chapters(
+            &mut item.metadata,
+            "0:00 Intro\n4:10 Borrowing\n15:00 Lifetimes",
+        );
+        let actions = provider.available_actions(&item).await.unwrap();
+        assert_eq!(actions.len(), 10);
+        assert_eq!(actions[9].kind, action.kind);
+
+        let result = provider.execute_action(&item, &action).await.unwrap();
+        assert!(result.success);
+        let data = result.data.unwrap();
+        assert_eq!(data["requires_input"], true);
+        assert_eq!(data["chapters"][1]["title"], "Borrowing");
+        assert_eq!(data["chapters"][1]["timestamp"], "4:10");
+        assert_eq!(data["chapters"][1]["url"], "https://youtu.be/test-video?t=250");
+        assert_eq!(
+            data["text"],
+            "0:00 Intro https://youtu.be/test-video?t=0\n\
+             4:10 Borrowing https://youtu.be/test-video?t=250\n\
+             15:00 Lifetimes https://youtu.be/test-video?t=900"
+        );
+    }
+
     #[tokio::test]
     async fn test_health_check_with_mock() {
         let provider = create_test_provider();
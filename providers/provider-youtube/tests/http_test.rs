@@ -0,0 +1,488 @@
+//! Mock-server tests for `provider-youtube`'s HTTP paths.
+
+use std::sync::Arc;
+
+use provider_youtube::{YouTubeProvider, YouTubeSettings};
+use scryforge_provider_core::auth::MockTokenFetcher;
+use scryforge_provider_core::prelude::*;
+use scryforge_provider_core::testing::wiremock::matchers::{method, path, query_param};
+use scryforge_provider_core::testing::wiremock::{Mock, ResponseTemplate};
+use scryforge_provider_core::testing::{fixture, MockApi};
+
+const CHANNEL_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns:yt="http://www.youtube.com/xml/schemas/2015" xmlns:media="http://search.yahoo.com/mrss/" xmlns="http://www.w3.org/2005/Atom">
+  <id>yt:channel:UC_rust</id>
+  <yt:channelId>UC_rust</yt:channelId>
+  <title>Rust Talks</title>
+  <author>
+    <name>Rust Talks</name>
+    <uri>https://www.youtube.com/channel/UC_rust</uri>
+  </author>
+  <published>2020-01-01T00:00:00+00:00</published>
+  <entry>
+    <id>yt:video:vid_async</id>
+    <yt:videoId>vid_async</yt:videoId>
+    <yt:channelId>UC_rust</yt:channelId>
+    <title>Async in depth</title>
+    <link rel="alternate" href="https://www.youtube.com/watch?v=vid_async"/>
+    <author>
+      <name>Rust Talks</name>
+      <uri>https://www.youtube.com/channel/UC_rust</uri>
+    </author>
+    <published>2024-03-02T12:00:00+00:00</published>
+    <updated>2024-03-03T08:00:00+00:00</updated>
+    <media:group>
+      <media:title>Async in depth</media:title>
+      <media:content url="https://www.youtube.com/v/vid_async" type="application/x-shockwave-flash" width="640" height="390"/>
+      <media:thumbnail url="https://i.ytimg.com/vi/vid_async/hqdefault.jpg" width="480" height="360"/>
+      <media:description>Futures, wakers, and executors.</media:description>
+      <media:community>
+        <media:starRating count="120" average="5.00" min="1" max="5"/>
+        <media:statistics views="4521"/>
+      </media:community>
+    </media:group>
+  </entry>
+  <entry>
+    <id>yt:video:vid_macros</id>
+    <yt:videoId>vid_macros</yt:videoId>
+    <yt:channelId>UC_rust</yt:channelId>
+    <title>Declarative macros</title>
+    <link rel="alternate" href="https://www.youtube.com/watch?v=vid_macros"/>
+    <author>
+      <name>Rust Talks</name>
+      <uri>https://www.youtube.com/channel/UC_rust</uri>
+    </author>
+    <published>2024-02-20T12:00:00+00:00</published>
+    <updated>2024-02-21T08:00:00+00:00</updated>
+    <media:group>
+      <media:title>Declarative macros</media:title>
+      <media:thumbnail url="https://i.ytimg.com/vi/vid_macros/hqdefault.jpg" width="480" height="360"/>
+      <media:description>macro_rules! from the ground up.</media:description>
+    </media:group>
+  </entry>
+</feed>
+"#;
+
+fn provider_for(api: &MockApi) -> YouTubeProvider {
+    let tokens = MockTokenFetcher::empty().with_token(
+        "youtube".to_string(),
+        "personal".to_string(),
+        "test-token".to_string(),
+    );
+    YouTubeProvider::with_base_url(Arc::new(tokens), "personal".to_string(), api.uri())
+        .with_rss_url(format!("{}/feeds/videos.xml", api.uri()))
+}
+
+async fn mock_channel_feed(api: &MockApi, channel_id: &str) {
+    Mock::given(method("GET"))
+        .and(path("/feeds/videos.xml"))
+        .and(query_param("channel_id", channel_id))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CHANNEL_FEED))
+        .mount(api.server())
+        .await;
+}
+
+fn rss_channels(channels: &[&str]) -> YouTubeSettings {
+    YouTubeSettings {
+        rss_channels: channels.iter().map(|id| id.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn list_feeds_follows_page_tokens() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/subscriptions",
+        "pageToken",
+        vec![
+            (None, fixture("youtube/subscriptions_page1.json")),
+            (Some("PAGE2"), fixture("youtube/subscriptions_page2.json")),
+        ],
+    )
+    .await;
+
+    let feeds = provider_for(&api).list_feeds().await.unwrap();
+
+    let ids: Vec<&str> = feeds.iter().map(|feed| feed.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["UC_rust", "UC_terminal", "UC_synth"]);
+    assert_eq!(feeds[0].name, "Rust Talks");
+    assert_eq!(
+        feeds[0].icon.as_deref(),
+        Some("https://yt.example.com/rust.jpg")
+    );
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn rejected_token_requires_auth() {
+    let api = MockApi::start().await;
+    api.mock_unauthorized("/subscriptions").await;
+
+    let err = provider_for(&api).list_feeds().await.unwrap_err();
+    assert!(matches!(err, StreamError::AuthRequired(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn quota_exhaustion_is_rate_limited() {
+    let api = MockApi::start().await;
+    api.mock_rate_limited("/subscriptions", 120).await;
+
+    let err = provider_for(&api).list_feeds().await.unwrap_err();
+    assert!(matches!(err, StreamError::RateLimited(120)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn other_errors_are_provider_errors() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/subscriptions",
+        403,
+        fixture("youtube/error_quota.json"),
+    )
+    .await;
+
+    let err = provider_for(&api).list_feeds().await.unwrap_err();
+    assert!(matches!(err, StreamError::Provider(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn diagnose_checks_token_and_api() {
+    let api = MockApi::start().await;
+    api.mock_json("GET", "/channels", serde_json::json!({ "items": [] }))
+        .await;
+
+    let checks = provider_for(&api).diagnose().await;
+
+    let names: Vec<&str> = checks.iter().map(|check| check.name.as_str()).collect();
+    assert_eq!(names, vec!["token", "api", "yt-dlp"]);
+    assert_eq!(checks[0].status, CheckStatus::Ok);
+    assert_eq!(checks[1].status, CheckStatus::Ok);
+}
+
+#[tokio::test]
+async fn diagnose_suggests_reauthorizing_rejected_tokens() {
+    let api = MockApi::start().await;
+    api.mock_unauthorized("/channels").await;
+
+    let checks = provider_for(&api).diagnose().await;
+
+    assert_eq!(checks[1].name, "api");
+    assert_eq!(checks[1].status, CheckStatus::Failed);
+    assert!(checks[1].fix.as_deref().unwrap().contains("sigilforge"));
+}
+
+#[tokio::test]
+async fn diagnose_without_token_skips_api() {
+    let api = MockApi::start().await;
+    let provider = YouTubeProvider::with_base_url(
+        Arc::new(MockTokenFetcher::empty()),
+        "personal".to_string(),
+        api.uri(),
+    );
+
+    let checks = provider.diagnose().await;
+
+    assert_eq!(checks[0].name, "token");
+    assert_eq!(checks[0].status, CheckStatus::Failed);
+    assert!(checks.iter().all(|check| check.name != "api"));
+    assert_eq!(api.request_count().await, 0);
+}
+
+#[tokio::test]
+async fn diagnose_reports_spent_quota() {
+    let api = MockApi::start().await;
+    api.mock_status(
+        "GET",
+        "/subscriptions",
+        403,
+        fixture("youtube/error_daily_quota.json"),
+    )
+    .await;
+    let provider = provider_for(&api);
+    assert!(provider.list_feeds().await.is_err());
+
+    let checks = provider.diagnose().await;
+
+    assert_eq!(checks[1].name, "api");
+    assert_eq!(checks[1].status, CheckStatus::Warning);
+    assert!(checks[1].detail.contains("quota"));
+    // The spent quota is known, so the check doesn't ask the API again
+    assert_eq!(api.request_count().await, 1);
+}
+
+#[tokio::test]
+async fn daily_quota_falls_back_to_channel_feed() {
+    let api = MockApi::start().await;
+    let quota = fixture("youtube/error_daily_quota.json");
+    api.mock_status("GET", "/channels", 403, quota.clone())
+        .await;
+    api.mock_status("GET", "/videos", 403, quota).await;
+    mock_channel_feed(&api, "UC_rust").await;
+
+    let items = provider_for(&api)
+        .get_feed_items(&FeedId("UC_rust".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    let item = &items[0];
+    assert_eq!(item.id.0, "youtube:vid_async");
+    assert_eq!(item.title, "Async in depth");
+    assert_eq!(
+        item.url.as_deref(),
+        Some("https://www.youtube.com/watch?v=vid_async")
+    );
+    assert_eq!(
+        item.thumbnail_url.as_deref(),
+        Some("https://i.ytimg.com/vi/vid_async/hqdefault.jpg")
+    );
+    assert_eq!(item.author.as_ref().unwrap().name, "Rust Talks");
+    assert_eq!(item.metadata.get("source").map(String::as_str), Some("rss"));
+    match &item.content {
+        ItemContent::Video {
+            description,
+            duration_seconds,
+            view_count,
+        } => {
+            assert_eq!(description, "Futures, wakers, and executors.");
+            assert_eq!(*duration_seconds, None);
+            assert_eq!(*view_count, Some(4521));
+        }
+        other => panic!("expected video content, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn channel_feed_is_merged_with_api_details() {
+    let api = MockApi::start().await;
+    api.mock_json(
+        "GET",
+        "/videos",
+        serde_json::json!({
+            "items": [{
+                "id": "vid_async",
+                "snippet": {
+                    "publishedAt": "2024-03-02T12:00:00Z",
+                    "channelId": "UC_rust",
+                    "channelTitle": "Rust Talks",
+                    "title": "Async in depth",
+                    "description": "Futures, wakers, and executors, with the full show notes."
+                },
+                "contentDetails": { "duration": "PT42M10S" },
+                "statistics": { "viewCount": "4600", "likeCount": "310" }
+            }]
+        }),
+    )
+    .await;
+    mock_channel_feed(&api, "UC_rust").await;
+
+    let provider = provider_for(&api).with_settings(YouTubeSettings {
+        rss_only: true,
+        ..Default::default()
+    });
+    let items = provider
+        .get_feed_items(&FeedId("UC_rust".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(items.len(), 2);
+    assert!(!items[0].metadata.contains_key("source"));
+    assert_eq!(
+        items[0].metadata.get("like_count").map(String::as_str),
+        Some("310")
+    );
+    assert!(matches!(
+        items[0].content,
+        ItemContent::Video {
+            duration_seconds: Some(2530),
+            view_count: Some(4600),
+            ..
+        }
+    ));
+    // Not returned by the API, so still as read from the feed
+    assert_eq!(
+        items[1].metadata.get("source").map(String::as_str),
+        Some("rss")
+    );
+    // Feed and video details only; the quota-costly search is skipped
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn rejected_token_keeps_known_subscriptions() {
+    let api = MockApi::start().await;
+    api.mock_pages(
+        "/subscriptions",
+        "pageToken",
+        vec![
+            (None, fixture("youtube/subscriptions_page1.json")),
+            (Some("PAGE2"), fixture("youtube/subscriptions_page2.json")),
+        ],
+    )
+    .await;
+    let provider = provider_for(&api);
+    assert_eq!(provider.list_feeds().await.unwrap().len(), 3);
+
+    api.server().reset().await;
+    api.mock_unauthorized("/subscriptions").await;
+
+    let ids: Vec<String> = provider
+        .list_feeds()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|feed| feed.id.0)
+        .collect();
+    assert_eq!(ids, vec!["UC_rust", "UC_terminal", "UC_synth"]);
+}
+
+#[tokio::test]
+async fn rss_channels_are_listed_without_api_access() {
+    let api = MockApi::start().await;
+    api.mock_unauthorized("/subscriptions").await;
+    mock_channel_feed(&api, "UC_rust").await;
+
+    let feeds = provider_for(&api)
+        .with_settings(rss_channels(&["UC_rust", "UC_gone"]))
+        .list_feeds()
+        .await
+        .unwrap();
+
+    assert_eq!(feeds.len(), 2);
+    assert_eq!(feeds[0].id.0, "UC_rust");
+    assert_eq!(feeds[0].name, "Rust Talks");
+    // Unreadable feeds are still listed, under their channel ID
+    assert_eq!(feeds[1].name, "UC_gone");
+}
+
+#[tokio::test]
+async fn sponsorblock_segments_are_attached() {
+    let api = MockApi::start().await;
+    mock_channel_feed(&api, "UC_rust").await;
+    Mock::given(method("GET"))
+        .and(path("/api/skipSegments"))
+        .and(query_param("videoID", "vid_async"))
+        .and(query_param(
+            "categories",
+            r#"["sponsor","selfpromo","interaction"]"#,
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "category": "sponsor",
+                "segment": [12.5, 71.0],
+                "UUID": "a1",
+                "actionType": "skip"
+            }
+        ])))
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api)
+        .with_sponsorblock_url(api.uri())
+        .with_settings(YouTubeSettings {
+            rss_only: true,
+            sponsorblock: true,
+            ..Default::default()
+        });
+    let items = provider
+        .get_feed_items(&FeedId("UC_rust".to_string()), FeedOptions::default())
+        .await
+        .unwrap();
+
+    let segments: serde_json::Value =
+        serde_json::from_str(&items[0].metadata["sponsor_segments"]).unwrap();
+    assert_eq!(
+        segments,
+        serde_json::json!([{ "category": "sponsor", "start": 12.5, "end": 71.0 }])
+    );
+    // SponsorBlock answers 404 for videos without segments
+    assert!(!items[1].metadata.contains_key("sponsor_segments"));
+}
+
+#[tokio::test]
+async fn transcript_uses_first_available_language() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/timedtext"))
+        .and(query_param("lang", "de"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(""))
+        .mount(api.server())
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/timedtext"))
+        .and(query_param("v", "vid_async"))
+        .and(query_param("lang", "en"))
+        .and(query_param("fmt", "json3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "events": [
+                { "tStartMs": 0, "dDurationMs": 2400, "segs": [{ "utf8": "Futures are" }, { "utf8": " lazy" }] },
+                { "tStartMs": 2400, "dDurationMs": 10, "segs": [{ "utf8": "\n" }] },
+                { "tStartMs": 2410, "dDurationMs": 3000, "segs": [{ "utf8": "until polled." }] }
+            ]
+        })))
+        .mount(api.server())
+        .await;
+
+    let transcript = provider_for(&api)
+        .with_timedtext_url(format!("{}/api/timedtext", api.uri()))
+        .with_settings(YouTubeSettings {
+            transcript_languages: vec!["de".to_string(), "en".to_string()],
+            ..Default::default()
+        })
+        .get_transcript("vid_async")
+        .await
+        .unwrap();
+
+    assert_eq!(transcript.language, "en");
+    assert_eq!(transcript.segments.len(), 2);
+    assert_eq!(transcript.segments[1].start, 2.41);
+    assert_eq!(transcript.text(), "Futures are lazy\nuntil polled.");
+}
+
+#[tokio::test]
+async fn transcript_falls_back_to_caption_tracks() {
+    let api = MockApi::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/timedtext"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(""))
+        .mount(api.server())
+        .await;
+    api.mock_json(
+        "GET",
+        "/captions",
+        serde_json::json!({
+            "items": [
+                { "id": "cap_fr", "snippet": { "language": "fr" } },
+                { "id": "cap_en", "snippet": { "language": "en" } }
+            ]
+        }),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/captions/cap_en"))
+        .and(query_param("tfmt", "srt"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("1\n00:00:00,000 --> 00:00:02,000\nHello from my own upload\n"),
+        )
+        .mount(api.server())
+        .await;
+
+    let provider = provider_for(&api).with_timedtext_url(format!("{}/api/timedtext", api.uri()));
+    let transcript = provider.get_transcript("vid_own").await.unwrap();
+    assert_eq!(transcript.language, "en");
+    assert_eq!(transcript.text(), "Hello from my own upload");
+
+    api.server().reset().await;
+    Mock::given(method("GET"))
+        .and(path("/api/timedtext"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(""))
+        .mount(api.server())
+        .await;
+    api.mock_json("GET", "/captions", serde_json::json!({ "items": [] }))
+        .await;
+    let err = provider.get_transcript("vid_none").await.unwrap_err();
+    assert!(matches!(err, StreamError::ItemNotFound(_)), "{:?}", err);
+}
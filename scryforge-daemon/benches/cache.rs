@@ -0,0 +1,152 @@
+//! Benchmarks for the cache and the unified feed built on it.
+//!
+//! Run with `cargo bench -p scryforge-daemon`.
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use scryforge_daemon::cache::{Cache, SqliteCache};
+use scryforge_daemon::unified::{FeedSortOrder, UnifiedFeedOptions, UnifiedFeedsView};
+use scryforge_provider_core::{Author, Item, ItemContent, ItemId, Stream, StreamId, StreamType};
+use tempfile::TempDir;
+
+const PROVIDERS: &[&str] = &["rss", "reddit", "email-imap"];
+const STREAMS_PER_PROVIDER: usize = 10;
+
+fn streams() -> Vec<Stream> {
+    PROVIDERS
+        .iter()
+        .flat_map(|provider| {
+            (0..STREAMS_PER_PROVIDER).map(move |n| Stream {
+                id: StreamId::new(provider, "feed", &n.to_string()),
+                name: format!("{} feed {}", provider, n),
+                provider_id: provider.to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: Default::default(),
+            })
+        })
+        .collect()
+}
+
+/// `count` articles spread evenly over [`streams`], with varied dates and
+/// titles so sorting does real work.
+fn items(streams: &[Stream], count: usize) -> Vec<Item> {
+    let now = Utc::now();
+    (0..count)
+        .map(|n| {
+            let stream = &streams[n % streams.len()];
+            Item {
+                id: ItemId::new(&stream.provider_id, &format!("item-{}", n)),
+                stream_id: stream.id.clone(),
+                title: format!("Item {:x}", n.wrapping_mul(2_654_435_761) % 1_000_003),
+                content: ItemContent::Article {
+                    summary: Some(format!("Summary of item {}", n)),
+                    full_content: None,
+                },
+                author: Some(Author {
+                    name: format!("Author {}", n % 97),
+                    email: None,
+                    url: None,
+                    avatar_url: None,
+                }),
+                published: Some(now - Duration::minutes(((n * 7919) % 100_000) as i64)),
+                updated: None,
+                url: Some(format!("https://example.com/{}", n)),
+                thumbnail_url: None,
+                is_read: n % 3 == 0,
+                is_saved: n % 50 == 0,
+                tags: vec!["bench".to_string()],
+                metadata: Default::default(),
+            }
+        })
+        .collect()
+}
+
+fn open_cache(dir: &TempDir, streams: &[Stream]) -> SqliteCache {
+    let cache = SqliteCache::open_at(&dir.path().join("cache.db")).unwrap();
+    cache.upsert_streams(streams).unwrap();
+    cache
+}
+
+fn bench_upsert(c: &mut Criterion) {
+    let streams = streams();
+    let items = items(&streams, 100_000);
+
+    let mut group = c.benchmark_group("cache");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(items.len() as u64));
+
+    group.bench_function("upsert_100k_new", |b| {
+        b.iter_batched(
+            || {
+                let dir = TempDir::new().unwrap();
+                let cache = open_cache(&dir, &streams);
+                (dir, cache)
+            },
+            |(_dir, cache)| cache.upsert_items(&items).unwrap(),
+            BatchSize::PerIteration,
+        )
+    });
+
+    // Resyncing items that are already cached
+    let dir = TempDir::new().unwrap();
+    let cache = open_cache(&dir, &streams);
+    cache.upsert_items(&items).unwrap();
+    group.bench_function("upsert_100k_existing", |b| {
+        b.iter(|| cache.upsert_items(&items).unwrap())
+    });
+
+    group.finish();
+}
+
+fn bench_unified(c: &mut Criterion) {
+    let streams = streams();
+    let items = items(&streams, 30_000);
+    let dir = TempDir::new().unwrap();
+    let cache = open_cache(&dir, &streams);
+    cache.upsert_items(&items).unwrap();
+    let view = UnifiedFeedsView::new(Arc::new(cache));
+
+    let mut group = c.benchmark_group("unified");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(items.len() as u64));
+
+    for (name, sort) in [
+        ("newest_first", FeedSortOrder::NewestFirst),
+        ("alphabetical", FeedSortOrder::Alphabetical),
+        ("relevance", FeedSortOrder::Relevance),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                view.get_all_items(UnifiedFeedOptions {
+                    sort,
+                    limit: Some(100),
+                    ..Default::default()
+                })
+                .unwrap()
+            })
+        });
+    }
+
+    group.bench_function("dedupe_unread", |b| {
+        b.iter(|| {
+            view.get_all_items(UnifiedFeedOptions {
+                is_read: Some(false),
+                dedupe: true,
+                limit: Some(100),
+                ..Default::default()
+            })
+            .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_upsert, bench_unified);
+criterion_main!(benches);
@@ -0,0 +1,183 @@
+//! Saved search alerts.
+//!
+//! A search can be saved under a name through `alerts.create`, such as a
+//! project name to hear about any item that mentions it. After each sync
+//! that brings items, [`spawn_runner`] re-runs every saved search that is
+//! due. Items that match for the first time are listed in the "Alerts" feed
+//! (`alerts.items`), raise a desktop notification through the `[reminders]`
+//! command unless the search was saved quietly, and are published as
+//! [`DaemonEvent::AlertMatched`].
+//!
+//! Only items first cached after a search was saved count as matches, so
+//! saving a search doesn't flood the feed with what is already cached.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::cache::Cache;
+use crate::config::RemindersConfig;
+use crate::events::{DaemonEvent, EventBus};
+use crate::reminders::Notifier;
+use crate::search::SearchQuery;
+
+/// Run every saved search due at `now` and record the items that match
+/// for the first time.
+///
+/// A search whose query no longer parses is skipped with a warning.
+/// Returns the number of new matches.
+pub async fn run_due<C: Cache>(
+    cache: &C,
+    notifier: Option<&Notifier>,
+    events: &EventBus,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut matched = 0;
+    for search in cache.get_saved_searches()? {
+        if !search.is_due(now) {
+            continue;
+        }
+        let query = match SearchQuery::parse(&search.query) {
+            Ok(query) => query,
+            Err(e) => {
+                warn!("Skipping saved search {:?}: {:#}", search.name, e);
+                continue;
+            }
+        };
+
+        for item in cache.search(&query)? {
+            if !cache.record_alert_match(search.id, &item.id)? {
+                continue;
+            }
+
+            if let (true, Some(notifier)) = (search.notify, notifier) {
+                if let Err(e) = notifier.notify(&item).await {
+                    warn!("Failed to notify about {}: {:#}", item.id.as_str(), e);
+                }
+            }
+            events.publish(DaemonEvent::AlertMatched {
+                search_id: search.id,
+                name: search.name.clone(),
+                item_id: item.id.0.clone(),
+                title: item.title.clone(),
+                timestamp: now,
+            });
+            matched += 1;
+        }
+        cache.mark_saved_search_run(search.id, now)?;
+    }
+    Ok(matched)
+}
+
+/// Run due saved searches at startup and after every sync that brings
+/// items, until the task is aborted.
+pub fn spawn_runner<C: Cache + 'static>(
+    cache: Arc<C>,
+    config: RemindersConfig,
+    events: EventBus,
+) -> JoinHandle<()> {
+    let notifier = Notifier::from_config(&config);
+    let mut rx = events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match run_due(cache.as_ref(), notifier.as_ref(), &events, Utc::now()).await {
+                Ok(0) => debug!("No new alert matches"),
+                Ok(matched) => info!("Saved searches matched {} new item(s)", matched),
+                Err(e) => error!("Failed to run saved searches: {:#}", e),
+            }
+
+            // Wait for the next sync that brought items
+            loop {
+                match rx.recv().await {
+                    Ok(DaemonEvent::NewItems { .. }) => break,
+                    Ok(_) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => break,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use scryforge_provider_core::{Item, ItemContent, ItemId, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn item(id: &str, title: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:a".to_string()),
+            title: title.to_string(),
+            content: ItemContent::Text("Body text".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: Some(format!("https://example.com/{}", id)),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_due_records_new_matches_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SqliteCache::open_at(&temp_dir.path().join("test.db")).unwrap();
+        cache
+            .upsert_streams(&[Stream {
+                id: StreamId("rss:feed:a".to_string()),
+                name: "Feed".to_string(),
+                provider_id: "rss".to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])
+            .unwrap();
+
+        let search = cache
+            .create_saved_search("scryforge", "scryforge", false, None)
+            .unwrap();
+        cache
+            .upsert_items(&[
+                item("rss:1", "Scryforge 1.0 released"),
+                item("rss:2", "Unrelated news"),
+            ])
+            .unwrap();
+
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let now = Utc::now();
+
+        assert_eq!(run_due(&cache, None, &events, now).await.unwrap(), 1);
+        assert_eq!(run_due(&cache, None, &events, now).await.unwrap(), 0);
+
+        match rx.recv().await.unwrap() {
+            DaemonEvent::AlertMatched {
+                search_id, item_id, ..
+            } => {
+                assert_eq!(search_id, search.id);
+                assert_eq!(item_id, "rss:1");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        let alerts = cache.get_alert_items(None, 10).unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert!(cache.get_saved_searches().unwrap()[0].last_run_at.is_some());
+    }
+}
@@ -0,0 +1,185 @@
+//! Bearer-token authentication for the JSON-RPC server.
+//!
+//! The daemon can be configured with a list of named clients, each holding
+//! its own token. Every HTTP request and WebSocket handshake must carry an
+//! `Authorization: Bearer <token>` header matching one of them; anything else
+//! is rejected with `401 Unauthorized` before it reaches the RPC layer.
+//!
+//! When no clients are configured the layer lets every request through, which
+//! is the historical behavior for a loopback-only daemon.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use jsonrpsee::core::BoxError;
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use tower::{Layer, Service};
+use tracing::{debug, warn};
+
+use crate::config::ClientConfig;
+
+/// Tower layer that enforces bearer-token authentication.
+#[derive(Debug, Clone, Default)]
+pub struct AuthLayer {
+    clients: Arc<Vec<ClientConfig>>,
+}
+
+impl AuthLayer {
+    /// Create a layer accepting the given clients' tokens.
+    ///
+    /// An empty list disables authentication.
+    pub fn new(clients: Vec<ClientConfig>) -> Self {
+        Self {
+            clients: Arc::new(clients),
+        }
+    }
+
+    /// Whether this layer requires a token at all.
+    pub fn is_enabled(&self) -> bool {
+        !self.clients.is_empty()
+    }
+
+    /// Find the client whose token is presented in the `Authorization` header.
+    fn authorize(&self, headers: &http::HeaderMap) -> Option<&ClientConfig> {
+        let presented = headers
+            .get(http::header::AUTHORIZATION)?
+            .to_str()
+            .ok()?
+            .strip_prefix("Bearer ")?
+            .trim();
+
+        find_client(&self.clients, presented)
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service produced by [`AuthLayer`].
+#[derive(Debug, Clone)]
+pub struct Auth<S> {
+    inner: S,
+    layer: AuthLayer,
+}
+
+impl<S, B> Service<HttpRequest<B>> for Auth<S>
+where
+    S: Service<HttpRequest<B>, Response = HttpResponse, Error = BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<B>) -> Self::Future {
+        if self.layer.is_enabled() {
+            match self.layer.authorize(request.headers()) {
+                Some(client) => debug!("Authorized request from client '{}'", client.name),
+                None => {
+                    warn!("Rejected unauthenticated API request");
+                    return Box::pin(async { Ok(unauthorized()) });
+                }
+            }
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// Find the configured client owning `token`.
+pub(crate) fn find_client<'a>(
+    clients: &'a [ClientConfig],
+    token: &str,
+) -> Option<&'a ClientConfig> {
+    clients
+        .iter()
+        .find(|client| constant_time_eq(client.token.as_bytes(), token.as_bytes()))
+}
+
+/// Build a `401 Unauthorized` response.
+fn unauthorized() -> HttpResponse {
+    http::Response::builder()
+        .status(http::StatusCode::UNAUTHORIZED)
+        .header(http::header::WWW_AUTHENTICATE, "Bearer")
+        .header(http::header::CONTENT_TYPE, "text/plain")
+        .body(HttpBody::from("Missing or invalid bearer token\n"))
+        .expect("static response parts are valid")
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with(value: &str) -> http::HeaderMap {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::AUTHORIZATION, value.parse().unwrap());
+        headers
+    }
+
+    fn layer() -> AuthLayer {
+        AuthLayer::new(vec![
+            ClientConfig {
+                name: "laptop".to_string(),
+                token: "alpha".to_string(),
+            },
+            ClientConfig {
+                name: "phone".to_string(),
+                token: "bravo".to_string(),
+            },
+        ])
+    }
+
+    #[test]
+    fn test_authorize_matches_client() {
+        let layer = layer();
+        let client = layer.authorize(&headers_with("Bearer bravo")).unwrap();
+        assert_eq!(client.name, "phone");
+    }
+
+    #[test]
+    fn test_authorize_rejects_bad_tokens() {
+        let layer = layer();
+        assert!(layer.authorize(&headers_with("Bearer nope")).is_none());
+        assert!(layer.authorize(&headers_with("Basic alpha")).is_none());
+        assert!(layer.authorize(&http::HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_empty_layer_is_disabled() {
+        assert!(!AuthLayer::default().is_enabled());
+        assert!(layer().is_enabled());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"tok"));
+    }
+}
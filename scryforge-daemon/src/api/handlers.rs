@@ -3,23 +3,114 @@
 //! This module defines the RPC interface and provides implementations
 //! that return dummy data for now (Phase 2 will wire up actual providers).
 
-use chrono::Utc;
-use jsonrpsee::core::RpcResult;
+use chrono::{DateTime, NaiveDate, Utc};
+use jsonrpsee::core::{RpcResult, SubscriptionResult};
 use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::{PendingSubscriptionSink, SubscriptionMessage};
+use provider_bookmarks::{BookmarksProvider, DuplicateGroup};
 use scryforge_provider_core::{
-    Collection, CollectionId, Item, ItemContent, ItemId, Stream, StreamId, StreamType,
+    CalendarInvite, Collection, CollectionId, Community, CommunityId, FeedOptions, HasCommunities,
+    HasTags, HasTasks, Item, ItemContent, ItemId, Provider, ProviderCapabilities, SearchOptions,
+    SearchResult, Stream, StreamError, StreamId, StreamType, TagCount, TaskDraft, TaskUpdate,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::RwLock;
 
-use crate::cache::Cache;
-use crate::sync::{ProviderSyncState, SyncManager};
+use crate::audit::{self, AuditEntry};
+use crate::bandwidth::BandwidthStatus;
+use crate::cache::{
+    Cache, CacheStats, InteractionKind, ReadingStats, Reminder, SavedSearch, SpamVerdict,
+    StateConflict, Tombstone,
+};
+use crate::changes::ItemChange;
+use crate::config::{DigestConfig, DigestFormat, RankingConfig, SummarizerConfig};
+use crate::contacts::{Contact, Contacts};
+use crate::digest::{self, DigestBuilder};
+use crate::downloads::{Download, DownloadManager};
+use crate::events::{DaemonEvent, EventBus};
+use crate::export::{self, ExportFormat, ExportSource, ExportSummary};
+use crate::focus::{Focus, FocusReport, FocusSession, FocusStatus, DEFAULT_FOCUS_MINUTES};
+use crate::links::{self, ItemLink, LinkTitles};
+use crate::mute::MuteFilter;
+use crate::notes::NotesWriter;
+use crate::opener::{OpenedItem, Opener};
+use crate::outbox::{self, OutboxAction, OutboxEntry};
+use crate::player::{Playback, Player, PlayerStatus};
+use crate::queue::ReadingQueue;
+use crate::reading_time::reading_minutes;
+use crate::registry::ProviderRegistry;
+use crate::relations::RelatedItem;
+use crate::reminders;
+use crate::save_router::SaveRouter;
+use crate::search::SearchQuery;
+#[cfg(feature = "search-index")]
+use crate::search_index::SearchIndex;
+use crate::share::{ShareTargetInfo, SharedItem, Sharer};
+use crate::summarize::{self, Summarizer};
+use crate::sync::{ProviderSyncState, SyncManager, SyncOutcome, SyncPreview};
+use crate::triage::{Triage, TriageAction, TriageView};
+use crate::tts::{SpeechQueue, SpeechStatus};
+use crate::unified::{UnifiedFeedOptions, UnifiedFeedsView};
 
 // Re-export search types for use in TUI
 pub use serde_json::Value as JsonValue;
 
+/// Version of the JSON-RPC protocol exposed by the daemon.
+///
+/// Follows semver: a minor bump adds methods or fields, a major bump removes
+/// or changes them. Must match `info.version` in `schema/openrpc.json`.
+pub const API_VERSION: &str = "1.45.0";
+
+/// OpenRPC document describing every method, served by `rpc.discover`.
+pub const OPENRPC_SCHEMA: &str = include_str!("../../schema/openrpc.json");
+
+/// Most results `search.query` takes from each searchable provider.
+const PROVIDER_SEARCH_LIMIT: u32 = 25;
+
+/// Most results `search.query` takes from the full-text index.
+#[cfg(feature = "search-index")]
+const INDEX_SEARCH_LIMIT: usize = 100;
+
+/// Related items `items.related` lists when no limit is given.
+const DEFAULT_RELATED_LIMIT: u32 = 20;
+
+/// Changes `items.changes` lists when no limit is given.
+const DEFAULT_CHANGES_LIMIT: u32 = 20;
+
+/// Items `communities.items` fetches when no limit is given.
+const DEFAULT_COMMUNITY_LIMIT: u32 = 25;
+
+/// Days `stats.reading` covers when no window is given.
+const DEFAULT_STATS_DAYS: u32 = 30;
+
+/// Response for `rpc.version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiVersionInfo {
+    /// Protocol version (see [`API_VERSION`])
+    pub api_version: String,
+    /// Version of the running daemon binary
+    pub daemon_version: String,
+}
+
+/// Entry returned by `providers.list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    /// Provider identifier, the prefix of its item and stream IDs
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// What the provider supports
+    pub capabilities: ProviderCapabilities,
+    /// Set when the provider's last call panicked, with the panic message.
+    /// The daemon keeps running and retries the provider; clients can show it
+    /// as unhealthy until a call succeeds.
+    pub panic: Option<String>,
+}
+
 /// Response object for a saved item with provider metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedItemResponse {
@@ -61,6 +152,23 @@ pub trait ScryforgeApi {
     #[method(name = "items.list")]
     async fn list_items(&self, stream_id: String) -> RpcResult<Vec<Item>>;
 
+    /// List items from all feed streams in one inbox.
+    ///
+    /// Pass `{"sort": "Relevance"}` to rank by engagement, interaction
+    /// history, and age instead of date, or `"ShortestFirst"` and
+    /// `"LongestFirst"` to order by estimated reading time. Items hidden by
+    /// the mute list are left out; `{"muted_only": true}` lists just those
+    /// for review.
+    #[method(name = "items.unified")]
+    async fn unified_items(&self, options: Option<UnifiedFeedOptions>) -> RpcResult<Vec<Item>>;
+
+    /// List loaded providers with their capabilities.
+    ///
+    /// Clients use the capabilities to adapt up front, for example by noting
+    /// that actions on a read-only provider only change the local cache.
+    #[method(name = "providers.list")]
+    async fn list_providers(&self) -> RpcResult<Vec<ProviderInfo>>;
+
     /// Get sync status for all providers.
     #[method(name = "sync.status")]
     async fn sync_status(&self) -> RpcResult<HashMap<String, ProviderSyncState>>;
@@ -69,19 +177,78 @@ pub trait ScryforgeApi {
     #[method(name = "sync.trigger")]
     async fn sync_trigger(&self, provider_id: String) -> RpcResult<()>;
 
+    /// Sync all enabled providers concurrently and report each outcome.
+    ///
+    /// Returns once every provider has finished or hit its timeout, with
+    /// failures listed alongside the providers that synced.
+    #[method(name = "sync.trigger_all")]
+    async fn sync_trigger_all(&self) -> RpcResult<Vec<SyncOutcome>>;
+
+    /// List read/saved state conflicts resolved during merges, newest first.
+    ///
+    /// `limit` defaults to 50.
+    #[method(name = "sync.conflicts")]
+    async fn sync_conflicts(&self, limit: Option<u32>) -> RpcResult<Vec<StateConflict>>;
+
+    /// Fetch a provider's feeds and collections and report which items a
+    /// sync would add, update, or remove, without changing the cache.
+    #[method(name = "sync.dry_run")]
+    async fn sync_dry_run(&self, provider_id: String) -> RpcResult<SyncPreview>;
+
+    /// Get whether low-bandwidth mode is on and what it limits.
+    #[method(name = "bandwidth.status")]
+    async fn bandwidth_status(&self) -> RpcResult<BandwidthStatus>;
+
+    /// Switch low-bandwidth mode on or off, for metered connections.
+    ///
+    /// Applies from the next refresh, sync tick, and search.
+    #[method(name = "bandwidth.set")]
+    async fn set_bandwidth(&self, low_bandwidth: bool) -> RpcResult<BandwidthStatus>;
+
+    /// List mutations made on providers, newest first: actions sent, tasks
+    /// and tags changed, and items deleted, with whether they succeeded.
+    ///
+    /// `provider_id` narrows the log to one provider; `limit` defaults to
+    /// 100.
+    #[method(name = "audit.list")]
+    async fn audit_list(
+        &self,
+        provider_id: Option<String>,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<AuditEntry>>;
+
     /// Search items across all streams or within a specific stream.
     ///
     /// # Arguments
     ///
-    /// * `query` - The search query text
-    /// * `filters` - Optional JSON object with filters:
+    /// * `query` - The search query, in the [`search`](crate::search) query
+    ///   language: words, quoted phrases, and fields such as `provider:`,
+    ///   `tag:`, `is:`, `before:`, and `after:`
+    /// * `filters` - Optional JSON object with filters, applied on top of the
+    ///   query's fields:
     ///   - `stream_id`: Filter by specific stream
     ///   - `content_type`: Filter by content type (e.g., "article", "email")
     ///   - `is_read`: Filter by read status (boolean)
     ///   - `is_saved`: Filter by saved status (boolean)
+    ///   - `remote`: Also search providers' sources (boolean, default true)
+    ///   - `sort`, `time`: Provider-specific sort order and time window for
+    ///     the remote search
+    ///   - `min_minutes`, `max_minutes`: Bounds on the estimated reading time
+    ///
+    /// Cached matches come first, followed by remote results not already
+    /// cached, in each provider's ranking. Providers are only searched when
+    /// the query has words or phrases. A malformed field fails with -32602.
+    ///
+    /// Each result is the item with what matched: the byte ranges of its
+    /// title, and a snippet of its content around the first match with the
+    /// ranges of the snippet. With the full-text index on, cached matches are
+    /// ranked best first and carry their score.
     #[method(name = "search.query")]
-    async fn search_query(&self, query: String, filters: Option<JsonValue>)
-        -> RpcResult<Vec<Item>>;
+    async fn search_query(
+        &self,
+        query: String,
+        filters: Option<JsonValue>,
+    ) -> RpcResult<Vec<SearchResult>>;
 
     /// Mark an item as read.
     #[method(name = "items.mark_read")]
@@ -103,6 +270,290 @@ pub trait ScryforgeApi {
     #[method(name = "items.unsave")]
     async fn unsave_item(&self, item_id: String) -> RpcResult<()>;
 
+    /// Pin an item to the top of its stream.
+    ///
+    /// Pins are kept in the daemon's cache only and never sent to the
+    /// provider, so they work the same for every provider and leave its
+    /// saved state alone. Pinned items are not evicted.
+    #[method(name = "items.pin")]
+    async fn pin_item(&self, item_id: String) -> RpcResult<()>;
+
+    /// Unpin an item. Returns whether it was pinned.
+    #[method(name = "items.unpin")]
+    async fn unpin_item(&self, item_id: String) -> RpcResult<bool>;
+
+    /// List pinned items across all streams, most recently pinned first,
+    /// with the pin time in their `pinned_at` metadata.
+    #[method(name = "items.pinned")]
+    async fn pinned_items(&self) -> RpcResult<Vec<Item>>;
+
+    /// Mark an email as spam: archive it, record the verdict for training
+    /// local filters, and have the provider move it to the junk folder
+    /// through the outbox.
+    #[method(name = "items.mark_spam")]
+    async fn mark_spam(&self, item_id: String) -> RpcResult<()>;
+
+    /// Mark an email as not spam: unarchive it, record the verdict, and have
+    /// the provider move it back to the inbox through the outbox.
+    #[method(name = "items.mark_not_spam")]
+    async fn mark_not_spam(&self, item_id: String) -> RpcResult<()>;
+
+    /// List spam verdicts, newest first, with the sender and subject of
+    /// each message. `limit` defaults to 100.
+    #[method(name = "spam.verdicts")]
+    async fn spam_verdicts(&self, limit: Option<u32>) -> RpcResult<Vec<SpamVerdict>>;
+
+    /// Accept or decline the meeting invitation in an email.
+    ///
+    /// The item's provider sends the organizer an iCalendar reply. Returns
+    /// the provider's confirmation, such as which address the reply went to.
+    #[method(name = "items.respond_invite")]
+    async fn respond_invite(&self, item_id: String, accept: bool) -> RpcResult<String>;
+
+    /// Append an item to the configured notes file.
+    ///
+    /// Returns the path of the notes file that was written.
+    #[method(name = "items.send_to_notes")]
+    async fn send_to_notes(&self, item_id: String) -> RpcResult<String>;
+
+    /// List the share targets from the `[[share]]` config tables, in order.
+    #[method(name = "items.share_targets")]
+    async fn share_targets(&self) -> RpcResult<Vec<ShareTargetInfo>>;
+
+    /// Share an item to a configured target.
+    ///
+    /// Clipboard targets only format the text; the client copies it when
+    /// `copy` is set in the result.
+    #[method(name = "items.share")]
+    async fn share_item(&self, item_id: String, target: String) -> RpcResult<SharedItem>;
+
+    /// Summarize a long article, email, or video description.
+    ///
+    /// The summary is cached in the item's `summary` metadata, so repeated
+    /// calls return it without contacting the summarizer again.
+    #[method(name = "items.summarize")]
+    async fn summarize_item(&self, item_id: String) -> RpcResult<String>;
+
+    /// Fetch the transcript of a YouTube video, one caption per line.
+    ///
+    /// The transcript is cached in the item's `transcript` metadata, where
+    /// search and `items.summarize` pick it up.
+    #[method(name = "items.transcript")]
+    async fn get_transcript(&self, item_id: String) -> RpcResult<String>;
+
+    /// List cached items linked to an item by URL, from any provider: the
+    /// Reddit thread about an article, the video an RSS post embeds.
+    ///
+    /// Items pointing at the same page come first, then those the item
+    /// links to, then those linking to it. `limit` defaults to 20.
+    #[method(name = "items.related")]
+    async fn related_items(
+        &self,
+        item_id: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<RelatedItem>>;
+
+    /// List what changed in an item when syncs brought new versions of it,
+    /// newest first: edited titles and bodies, as word diffs, and moved due
+    /// dates. `limit` defaults to 20.
+    ///
+    /// Items with changes not yet seen carry a `changed_at` metadata stamp.
+    #[method(name = "items.changes")]
+    async fn item_changes(&self, item_id: String, limit: Option<u32>)
+        -> RpcResult<Vec<ItemChange>>;
+
+    /// Clear an item's `changed_at` stamp once its changes have been seen.
+    /// Returns whether it had one.
+    #[method(name = "items.acknowledge_changes")]
+    async fn acknowledge_changes(&self, item_id: String) -> RpcResult<bool>;
+
+    /// List items removed at their source, newest first: those deleted
+    /// through the daemon, and those their provider marks removed, whose
+    /// cached copy is kept with a `removed` metadata stamp.
+    ///
+    /// Syncs don't cache these items again. `limit` defaults to 100.
+    #[method(name = "items.tombstones")]
+    async fn tombstones(&self, limit: Option<u32>) -> RpcResult<Vec<Tombstone>>;
+
+    /// Forget that an item was removed, so the next sync that lists it
+    /// caches it again. Returns whether it had a tombstone.
+    #[method(name = "items.restore")]
+    async fn restore_item(&self, item_id: String) -> RpcResult<bool>;
+
+    /// Open an item's link with the command configured for its content kind
+    /// and provider, and mark it read.
+    ///
+    /// The command runs on the daemon's machine and is not waited for.
+    #[method(name = "items.open")]
+    async fn open_item(&self, item_id: String) -> RpcResult<OpenedItem>;
+
+    /// List the links in an item's body, in order, with the text each was
+    /// linked from. Titles of pages already fetched with `links.title` are
+    /// filled in.
+    #[method(name = "items.links")]
+    async fn item_links(&self, item_id: String) -> RpcResult<Vec<ItemLink>>;
+
+    /// Fetch the title of a page linked from a cached item, through the
+    /// proxy of the item's provider. The title is remembered, so
+    /// `items.links` lists it from then on; `null` when the page has none.
+    #[method(name = "links.title")]
+    async fn link_title(&self, item_id: String, url: String) -> RpcResult<Option<String>>;
+
+    /// Open a link found in an item with the command configured for its
+    /// content kind and the item's provider.
+    #[method(name = "items.open_link")]
+    async fn open_link(&self, item_id: String, url: String) -> RpcResult<OpenedItem>;
+
+    /// Bookmark a link found in an item, titled with the linked page's title
+    /// or else its link text. Returns the bookmark's item ID.
+    #[method(name = "items.bookmark_link")]
+    async fn bookmark_link(&self, item_id: String, url: String) -> RpcResult<String>;
+
+    /// Queue an item to be read aloud through the configured TTS command.
+    ///
+    /// Returns immediately with the number of items ahead of it in the queue.
+    #[method(name = "items.read_aloud")]
+    async fn read_aloud(&self, item_id: String) -> RpcResult<usize>;
+
+    /// Stop reading aloud and clear the queue.
+    ///
+    /// Returns the number of items that were stopped or dropped.
+    #[method(name = "tts.stop")]
+    async fn stop_reading(&self) -> RpcResult<usize>;
+
+    /// Get the item being read aloud and the items waiting.
+    #[method(name = "tts.status")]
+    async fn speech_status(&self) -> RpcResult<SpeechStatus>;
+
+    /// Play a video or podcast item in mpv, stopping whatever was playing.
+    ///
+    /// The player runs on the daemon's machine. Its position is recorded as
+    /// it plays and playing the item again resumes from there; a
+    /// `playback_finished` event reports when it exits.
+    #[method(name = "items.play")]
+    async fn play_item(&self, item_id: String) -> RpcResult<Playback>;
+
+    /// Stop the player. Returns whether anything was playing.
+    #[method(name = "player.stop")]
+    async fn stop_playback(&self) -> RpcResult<bool>;
+
+    /// Get the item playing and how the last playback ended.
+    #[method(name = "player.status")]
+    async fn player_status(&self) -> RpcResult<PlayerStatus>;
+
+    /// Queue an item's podcast episode, video, PDF, or image, and each of
+    /// its attachments, for download to the daemon's download directory.
+    ///
+    /// Returns the queued downloads; a `download_finished` event reports
+    /// when each is done.
+    #[method(name = "items.download")]
+    async fn download_item(&self, item_id: String) -> RpcResult<Vec<Download>>;
+
+    /// List queued, running, and finished downloads, oldest first.
+    #[method(name = "downloads.list")]
+    async fn list_downloads(&self) -> RpcResult<Vec<Download>>;
+
+    /// Cancel a queued or running download. What it has written is kept, so
+    /// retrying resumes it. Returns whether there was one to cancel.
+    #[method(name = "downloads.cancel")]
+    async fn cancel_download(&self, download_id: u64) -> RpcResult<bool>;
+
+    /// Queue a failed or cancelled download again, resuming where it stopped.
+    #[method(name = "downloads.retry")]
+    async fn retry_download(&self, download_id: u64) -> RpcResult<Download>;
+
+    /// Remove finished downloads from the list, keeping their files.
+    ///
+    /// Returns the number removed.
+    #[method(name = "downloads.clear")]
+    async fn clear_downloads(&self) -> RpcResult<usize>;
+
+    /// Remind the user of an item at `remind_at`, replacing any earlier reminder.
+    ///
+    /// When the time comes the daemon raises a desktop notification and the
+    /// item is listed by `reminders.due` until it is dismissed.
+    #[method(name = "items.remind")]
+    async fn remind_item(&self, item_id: String, remind_at: DateTime<Utc>) -> RpcResult<Reminder>;
+
+    /// List all reminders, due or not, soonest first.
+    #[method(name = "reminders.list")]
+    async fn list_reminders(&self) -> RpcResult<Vec<Reminder>>;
+
+    /// List the "Reminders due" feed: items whose reminder time has passed,
+    /// most overdue first, with the time in their `remind_at` metadata.
+    #[method(name = "reminders.due")]
+    async fn due_reminders(&self) -> RpcResult<Vec<Item>>;
+
+    /// Remove an item's reminder. Returns whether the item had one.
+    #[method(name = "reminders.dismiss")]
+    async fn dismiss_reminder(&self, item_id: String) -> RpcResult<bool>;
+
+    /// Save a search query as an alert under a unique `name`.
+    ///
+    /// The daemon re-runs it after every sync, or at most every
+    /// `interval_minutes`. Items first cached after it was saved that match
+    /// are listed by `alerts.items` and, if `notify` (the default) is set,
+    /// raise a desktop notification.
+    #[method(name = "alerts.create")]
+    async fn create_alert(
+        &self,
+        name: String,
+        query: String,
+        notify: Option<bool>,
+        interval_minutes: Option<u32>,
+    ) -> RpcResult<SavedSearch>;
+
+    /// List saved search alerts, oldest first.
+    #[method(name = "alerts.list")]
+    async fn list_alerts(&self) -> RpcResult<Vec<SavedSearch>>;
+
+    /// Delete a saved search alert and its matches. Returns whether it existed.
+    #[method(name = "alerts.delete")]
+    async fn delete_alert(&self, search_id: i64) -> RpcResult<bool>;
+
+    /// List the "Alerts" feed: items that matched saved searches, or one
+    /// saved search, most recent match first, with the search name in their
+    /// `alert_name` metadata and the match time in `alert_matched_at`.
+    #[method(name = "alerts.items")]
+    async fn alert_items(&self, search_id: Option<i64>, limit: Option<u32>)
+        -> RpcResult<Vec<Item>>;
+
+    /// Mark a cached task as completed at its provider.
+    ///
+    /// The task operations work with any provider that manages tasks, and
+    /// those that change a task return it as now cached.
+    #[method(name = "tasks.complete")]
+    async fn complete_task(&self, item_id: String) -> RpcResult<Item>;
+
+    /// Mark a cached task as not completed at its provider.
+    #[method(name = "tasks.uncomplete")]
+    async fn uncomplete_task(&self, item_id: String) -> RpcResult<Item>;
+
+    /// Create a task in one of a provider's task lists (its collections).
+    #[method(name = "tasks.create")]
+    async fn create_task(
+        &self,
+        provider_id: String,
+        list_id: String,
+        task: TaskDraft,
+    ) -> RpcResult<Item>;
+
+    /// Change a task's title or body.
+    #[method(name = "tasks.update")]
+    async fn update_task(&self, item_id: String, update: TaskUpdate) -> RpcResult<Item>;
+
+    /// Delete a task at its provider and drop it from the cache.
+    #[method(name = "tasks.delete")]
+    async fn delete_task(&self, item_id: String) -> RpcResult<()>;
+
+    /// Set a task's due date, or clear it with `null`.
+    #[method(name = "tasks.set_due_date")]
+    async fn set_task_due_date(
+        &self,
+        item_id: String,
+        due_date: Option<NaiveDate>,
+    ) -> RpcResult<Item>;
+
     /// List all collections across all providers.
     #[method(name = "collections.list")]
     async fn list_collections(&self) -> RpcResult<Vec<Collection>>;
@@ -123,6 +574,227 @@ pub trait ScryforgeApi {
     /// Create a new collection.
     #[method(name = "collections.create")]
     async fn create_collection(&self, name: String) -> RpcResult<Collection>;
+
+    /// List a provider's tags with how many items carry each, most used first.
+    #[method(name = "tags.list")]
+    async fn list_tags(&self, provider_id: String) -> RpcResult<Vec<TagCount>>;
+
+    /// Get a provider's items carrying `tag`.
+    #[method(name = "tags.items")]
+    async fn tagged_items(&self, provider_id: String, tag: String) -> RpcResult<Vec<Item>>;
+
+    /// Rename a tag on every item carrying it. Returns how many items changed.
+    ///
+    /// Renaming to a tag that already exists merges the two. The tag
+    /// operations change the provider's copy; cached items pick the change up
+    /// at their stream's next refresh.
+    #[method(name = "tags.rename")]
+    async fn rename_tag(&self, provider_id: String, from: String, to: String) -> RpcResult<u32>;
+
+    /// Replace each of the `sources` tags with `into`. Returns how many items
+    /// changed.
+    #[method(name = "tags.merge")]
+    async fn merge_tags(
+        &self,
+        provider_id: String,
+        sources: Vec<String>,
+        into: String,
+    ) -> RpcResult<u32>;
+
+    /// Remove a tag from every item carrying it. Returns how many items
+    /// changed.
+    #[method(name = "tags.delete")]
+    async fn delete_tag(&self, provider_id: String, tag: String) -> RpcResult<u32>;
+
+    /// List the communities a provider's account has joined, such as
+    /// subreddits.
+    #[method(name = "communities.list")]
+    async fn list_communities(&self, provider_id: String) -> RpcResult<Vec<Community>>;
+
+    /// Get a community's newest items, read live from the provider whether
+    /// or not it has been joined. They are not cached.
+    #[method(name = "communities.items")]
+    async fn community_items(
+        &self,
+        provider_id: String,
+        community_id: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<Item>>;
+
+    /// Join a community. Its items reach the provider's feeds from the next
+    /// sync.
+    #[method(name = "communities.join")]
+    async fn join_community(&self, provider_id: String, community_id: String) -> RpcResult<()>;
+
+    /// Leave a community.
+    #[method(name = "communities.leave")]
+    async fn leave_community(&self, provider_id: String, community_id: String) -> RpcResult<()>;
+
+    /// Find bookmarks that look like copies of each other: the same page
+    /// under equivalent URLs, or nearly the same title. Groups list their
+    /// bookmarks oldest first.
+    #[method(name = "bookmarks.duplicates")]
+    async fn bookmark_duplicates(&self) -> RpcResult<Vec<DuplicateGroup>>;
+
+    /// Fold the `duplicates` bookmarks into `keep`, which gains their tags,
+    /// and delete them. Returns the kept bookmark.
+    #[method(name = "bookmarks.merge")]
+    async fn merge_bookmarks(&self, keep: String, duplicates: Vec<String>) -> RpcResult<Item>;
+
+    /// Export a stream, collection, or search result to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - What to export (`{"kind": "stream" | "collection" | "search", ...}`)
+    /// * `format` - `markdown` (directory of files), `json`, `ndjson`, or, to
+    ///   publish the items, `rss`, `json_feed`, or `html`
+    /// * `path` - Destination directory or file, relative to the daemon's
+    ///   export directory
+    /// * `overwrite` - Replace an existing destination (default: false)
+    #[method(name = "export.items")]
+    async fn export_items(
+        &self,
+        source: ExportSource,
+        format: ExportFormat,
+        path: String,
+        overwrite: Option<bool>,
+    ) -> RpcResult<ExportSummary>;
+
+    /// Render a digest of the top unread items per provider.
+    ///
+    /// Covers the configured digest window (a day or a week) and defaults to
+    /// the configured format. The digest is returned, not written to disk.
+    #[method(name = "digest.generate")]
+    async fn generate_digest(&self, format: Option<DigestFormat>) -> RpcResult<String>;
+
+    /// Get the cache size and item counts, overall and per stream.
+    ///
+    /// `size_bytes` is the figure checked against the `max_size_mb` limit.
+    #[method(name = "cache.stats")]
+    async fn cache_stats(&self) -> RpcResult<CacheStats>;
+
+    /// Get the items received, read, and saved over the last `days` days
+    /// (default 30), per stream, per provider, and per day.
+    ///
+    /// Counts include items evicted since they arrived.
+    #[method(name = "stats.reading")]
+    async fn reading_stats(&self, days: Option<u32>) -> RpcResult<ReadingStats>;
+
+    /// Start a focus session on `stream_ids` lasting `minutes` (default 25).
+    ///
+    /// Fails if a session is already running. When it ends, a `focus_ended`
+    /// event reports what was read and saved in those streams.
+    #[method(name = "focus.start")]
+    async fn start_focus(
+        &self,
+        stream_ids: Vec<String>,
+        minutes: Option<u32>,
+    ) -> RpcResult<FocusSession>;
+
+    /// End the focus session early. Returns its report, or null if no
+    /// session was running.
+    #[method(name = "focus.stop")]
+    async fn stop_focus(&self) -> RpcResult<Option<FocusReport>>;
+
+    /// Get the running focus session and the report of the last one.
+    #[method(name = "focus.status")]
+    async fn focus_status(&self) -> RpcResult<FocusStatus>;
+
+    /// Begin a new triage session on the unread unified inbox and get its
+    /// first item.
+    ///
+    /// Items skipped in the previous session come back.
+    #[method(name = "triage.start")]
+    async fn start_triage(&self) -> RpcResult<TriageView>;
+
+    /// Get the item triage is on, without deciding anything.
+    #[method(name = "triage.next")]
+    async fn triage_next(&self) -> RpcResult<TriageView>;
+
+    /// Archive an item and move on. The archive reaches the provider through
+    /// the outbox.
+    #[method(name = "triage.archive")]
+    async fn triage_archive(&self, item_id: String) -> RpcResult<TriageView>;
+
+    /// Save an item, mark it read, and move on.
+    #[method(name = "triage.save")]
+    async fn triage_save(&self, item_id: String) -> RpcResult<TriageView>;
+
+    /// Hide an item until `until` (default in 24 hours) by setting a
+    /// reminder on it, and move on.
+    #[method(name = "triage.snooze")]
+    async fn triage_snooze(
+        &self,
+        item_id: String,
+        until: Option<DateTime<Utc>>,
+    ) -> RpcResult<TriageView>;
+
+    /// Pass over an item for the rest of the session, and move on.
+    #[method(name = "triage.skip")]
+    async fn triage_skip(&self, item_id: String) -> RpcResult<TriageView>;
+
+    /// Take back the latest triage decision and return to its item.
+    ///
+    /// Provider actions still in the outbox are cancelled; an archive the
+    /// provider already received stays archived there.
+    #[method(name = "triage.undo")]
+    async fn triage_undo(&self) -> RpcResult<TriageView>;
+
+    /// Get the reading queue: saved items from every provider in reading
+    /// order, with estimated reading times and the item to resume.
+    ///
+    /// Items saved since the last call join at the end; unsaved items leave.
+    #[method(name = "queue.list")]
+    async fn reading_queue(&self) -> RpcResult<ReadingQueue>;
+
+    /// Move a queued item to `position` (1-based), or to the end when
+    /// `position` is past it.
+    #[method(name = "queue.move")]
+    async fn move_queued_item(&self, item_id: String, position: u32) -> RpcResult<ReadingQueue>;
+
+    /// Record where the reader left off in a queued item, in the client's
+    /// own units. The item becomes the one to resume.
+    #[method(name = "queue.progress")]
+    async fn queue_progress(&self, item_id: String, offset: u32) -> RpcResult<()>;
+
+    /// Take a read item out of the queue. It stays saved.
+    #[method(name = "queue.finish")]
+    async fn finish_queued_item(&self, item_id: String) -> RpcResult<ReadingQueue>;
+
+    /// List provider actions waiting in the outbox, oldest first.
+    #[method(name = "outbox.list")]
+    async fn list_outbox(&self) -> RpcResult<Vec<OutboxEntry>>;
+
+    /// Suggest recipients whose address, name, or a word of their name
+    /// starts with `prefix`, from cached email headers and the imported
+    /// address book. Frequent correspondents come first. `limit` defaults
+    /// to 10.
+    #[method(name = "contacts.complete")]
+    async fn complete_contacts(
+        &self,
+        prefix: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<Contact>>;
+
+    /// Re-import the configured CardDAV address book, returning how many
+    /// addresses it holds.
+    #[method(name = "contacts.import")]
+    async fn import_contacts(&self) -> RpcResult<usize>;
+
+    /// Subscribe to live daemon events (new items, sync progress, completed actions).
+    ///
+    /// Notifications are delivered as `events.event` with a [`DaemonEvent`] payload.
+    /// Subscriptions require a persistent transport such as WebSocket.
+    #[subscription(name = "events.subscribe" => "events.event", unsubscribe = "events.unsubscribe", item = DaemonEvent)]
+    async fn subscribe_events(&self) -> SubscriptionResult;
+
+    /// Get the protocol version so clients can check compatibility.
+    #[method(name = "rpc.version")]
+    async fn api_version(&self) -> RpcResult<ApiVersionInfo>;
+
+    /// Get the OpenRPC schema describing this API.
+    #[method(name = "rpc.discover")]
+    async fn discover(&self) -> RpcResult<JsonValue>;
 }
 
 /// Implementation of the Scryforge API.
@@ -132,6 +804,26 @@ pub trait ScryforgeApi {
 pub struct ApiImpl<C: Cache + 'static> {
     sync_manager: Option<Arc<RwLock<SyncManager<C>>>>,
     cache: Option<Arc<C>>,
+    events: Option<EventBus>,
+    notes: Option<NotesWriter>,
+    save_router: Option<SaveRouter>,
+    ranking: RankingConfig,
+    mute: MuteFilter,
+    digest: DigestConfig,
+    summarizer: Option<Arc<dyn Summarizer>>,
+    summarizer_config: SummarizerConfig,
+    speech: Option<SpeechQueue>,
+    opener: Opener,
+    player: Option<Player>,
+    downloads: Option<DownloadManager>,
+    export_dir: Option<PathBuf>,
+    focus: Focus,
+    triage: Triage,
+    sharer: Sharer,
+    contacts: Contacts,
+    link_titles: LinkTitles,
+    #[cfg(feature = "search-index")]
+    search_index: Option<Arc<SearchIndex>>,
 }
 
 impl<C: Cache + 'static> Default for ApiImpl<C> {
@@ -139,6 +831,26 @@ impl<C: Cache + 'static> Default for ApiImpl<C> {
         Self {
             sync_manager: None,
             cache: None,
+            events: None,
+            notes: None,
+            save_router: None,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+            digest: DigestConfig::default(),
+            summarizer: None,
+            summarizer_config: SummarizerConfig::default(),
+            speech: None,
+            opener: Opener::default(),
+            player: None,
+            downloads: None,
+            export_dir: None,
+            focus: Focus::new(),
+            triage: Triage::new(),
+            sharer: Sharer::default(),
+            contacts: Contacts::default(),
+            link_titles: LinkTitles::default(),
+            #[cfg(feature = "search-index")]
+            search_index: None,
         }
     }
 }
@@ -152,6 +864,26 @@ impl<C: Cache + 'static> ApiImpl<C> {
         Self {
             sync_manager: Some(sync_manager),
             cache: None,
+            events: None,
+            notes: None,
+            save_router: None,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+            digest: DigestConfig::default(),
+            summarizer: None,
+            summarizer_config: SummarizerConfig::default(),
+            speech: None,
+            opener: Opener::default(),
+            player: None,
+            downloads: None,
+            export_dir: None,
+            focus: Focus::new(),
+            triage: Triage::new(),
+            sharer: Sharer::default(),
+            contacts: Contacts::default(),
+            link_titles: LinkTitles::default(),
+            #[cfg(feature = "search-index")]
+            search_index: None,
         }
     }
 
@@ -159,6 +891,26 @@ impl<C: Cache + 'static> ApiImpl<C> {
         Self {
             sync_manager: None,
             cache: Some(cache),
+            events: None,
+            notes: None,
+            save_router: None,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+            digest: DigestConfig::default(),
+            summarizer: None,
+            summarizer_config: SummarizerConfig::default(),
+            speech: None,
+            opener: Opener::default(),
+            player: None,
+            downloads: None,
+            export_dir: None,
+            focus: Focus::new(),
+            triage: Triage::new(),
+            sharer: Sharer::default(),
+            contacts: Contacts::default(),
+            link_titles: LinkTitles::default(),
+            #[cfg(feature = "search-index")]
+            search_index: None,
         }
     }
 
@@ -169,768 +921,5743 @@ impl<C: Cache + 'static> ApiImpl<C> {
         Self {
             sync_manager: Some(sync_manager),
             cache: Some(cache),
+            events: None,
+            notes: None,
+            save_router: None,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+            digest: DigestConfig::default(),
+            summarizer: None,
+            summarizer_config: SummarizerConfig::default(),
+            speech: None,
+            opener: Opener::default(),
+            player: None,
+            downloads: None,
+            export_dir: None,
+            focus: Focus::new(),
+            triage: Triage::new(),
+            sharer: Sharer::default(),
+            contacts: Contacts::default(),
+            link_titles: LinkTitles::default(),
+            #[cfg(feature = "search-index")]
+            search_index: None,
         }
     }
 
-    /// Extract provider ID from a collection ID string.
-    /// Format expected: "provider:collection-id"
-    fn extract_provider_id(id: &str) -> Option<&str> {
-        id.split(':').next()
+    /// Attach an event bus used for `events.subscribe` and action notifications.
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
     }
 
-    /// Generate dummy streams for testing.
-    fn generate_dummy_streams() -> Vec<Stream> {
-        vec![
-            Stream {
-                id: StreamId::new("email", "inbox", "gmail"),
-                name: "Gmail Inbox".to_string(),
-                provider_id: "email-imap".to_string(),
-                stream_type: StreamType::Feed,
-                icon: Some("📧".to_string()),
-                unread_count: Some(5),
-                total_count: Some(150),
-                last_updated: Some(Utc::now()),
-                metadata: HashMap::new(),
-            },
-            Stream {
-                id: StreamId::new("rss", "feed", "hackernews"),
-                name: "Hacker News".to_string(),
-                provider_id: "rss".to_string(),
-                stream_type: StreamType::Feed,
-                icon: Some("📰".to_string()),
-                unread_count: Some(42),
-                total_count: Some(100),
-                last_updated: Some(Utc::now()),
-                metadata: HashMap::new(),
-            },
-            Stream {
-                id: StreamId::new("reddit", "home", "default"),
-                name: "Reddit Home".to_string(),
-                provider_id: "reddit".to_string(),
-                stream_type: StreamType::Feed,
-                icon: Some("🔴".to_string()),
-                unread_count: None,
-                total_count: None,
-                last_updated: Some(Utc::now()),
-                metadata: HashMap::new(),
-            },
-            Stream {
-                id: StreamId::new("spotify", "collection", "liked"),
-                name: "Liked Songs".to_string(),
-                provider_id: "spotify".to_string(),
-                stream_type: StreamType::SavedItems,
-                icon: Some("💚".to_string()),
-                unread_count: None,
-                total_count: Some(523),
-                last_updated: Some(Utc::now()),
-                metadata: HashMap::new(),
-            },
-        ]
+    /// Enable `items.send_to_notes` with the given writer.
+    pub fn with_notes(mut self, notes: NotesWriter) -> Self {
+        self.notes = Some(notes);
+        self
     }
 
-    /// Generate dummy items for a stream.
-    fn generate_dummy_items(stream_id: &str) -> Vec<Item> {
-        // Return different items based on stream
-        if stream_id.starts_with("email:") {
-            vec![
-                Item {
-                    id: ItemId::new("email", "msg-001"),
-                    stream_id: StreamId(stream_id.to_string()),
-                    title: "Meeting tomorrow at 10am".to_string(),
-                    content: ItemContent::Email {
-                        subject: "Meeting tomorrow at 10am".to_string(),
-                        body_text: Some(
-                            "Hi,\n\nJust a reminder about our meeting tomorrow.\n\nBest,\nJohn"
-                                .to_string(),
-                        ),
-                        body_html: None,
-                        snippet: "Just a reminder about our meeting...".to_string(),
-                    },
-                    author: Some(scryforge_provider_core::Author {
-                        name: "John Doe".to_string(),
-                        email: Some("john@example.com".to_string()),
-                        url: None,
-                        avatar_url: None,
-                    }),
-                    published: Some(Utc::now()),
-                    updated: None,
-                    url: None,
-                    thumbnail_url: None,
-                    is_read: false,
-                    is_saved: false,
-                    tags: vec![],
-                    metadata: HashMap::new(),
-                },
-                Item {
-                    id: ItemId::new("email", "msg-002"),
-                    stream_id: StreamId(stream_id.to_string()),
-                    title: "Your order has shipped".to_string(),
-                    content: ItemContent::Email {
-                        subject: "Your order has shipped".to_string(),
-                        body_text: Some(
-                            "Your order #12345 has shipped and will arrive by Friday.".to_string(),
-                        ),
-                        body_html: None,
-                        snippet: "Your order #12345 has shipped...".to_string(),
-                    },
-                    author: Some(scryforge_provider_core::Author {
-                        name: "Shop Support".to_string(),
-                        email: Some("support@shop.com".to_string()),
-                        url: None,
-                        avatar_url: None,
-                    }),
-                    published: Some(Utc::now()),
-                    updated: None,
-                    url: None,
-                    thumbnail_url: None,
-                    is_read: true,
-                    is_saved: false,
-                    tags: vec![],
-                    metadata: HashMap::new(),
-                },
-            ]
-        } else if stream_id.starts_with("rss:") {
-            vec![
-                Item {
-                    id: ItemId::new("rss", "article-001"),
-                    stream_id: StreamId(stream_id.to_string()),
-                    title: "Show HN: A new Rust TUI framework".to_string(),
-                    content: ItemContent::Article {
-                        summary: Some(
-                            "I've been working on a new TUI framework in Rust...".to_string(),
-                        ),
-                        full_content: None,
-                    },
-                    author: Some(scryforge_provider_core::Author {
-                        name: "rustdev".to_string(),
-                        email: None,
-                        url: None,
-                        avatar_url: None,
-                    }),
-                    published: Some(Utc::now()),
-                    updated: None,
-                    url: Some("https://news.ycombinator.com/item?id=123".to_string()),
-                    thumbnail_url: None,
-                    is_read: false,
-                    is_saved: false,
-                    tags: vec![],
-                    metadata: HashMap::new(),
-                },
-                Item {
-                    id: ItemId::new("rss", "article-002"),
-                    stream_id: StreamId(stream_id.to_string()),
-                    title: "Rust 1.75 Released".to_string(),
-                    content: ItemContent::Article {
-                        summary: Some(
-                            "The Rust team is happy to announce a new version of Rust..."
-                                .to_string(),
-                        ),
-                        full_content: None,
-                    },
-                    author: Some(scryforge_provider_core::Author {
-                        name: "Rust Blog".to_string(),
-                        email: None,
-                        url: Some("https://blog.rust-lang.org".to_string()),
-                        avatar_url: None,
-                    }),
-                    published: Some(Utc::now()),
-                    updated: None,
-                    url: Some("https://blog.rust-lang.org/2023/12/28/Rust-1.75.0.html".to_string()),
-                    thumbnail_url: None,
-                    is_read: false,
-                    is_saved: false,
-                    tags: vec![],
-                    metadata: HashMap::new(),
-                },
-            ]
-        } else if stream_id.starts_with("spotify:") {
-            vec![Item {
-                id: ItemId::new("spotify", "track-001"),
-                stream_id: StreamId(stream_id.to_string()),
-                title: "Example Song".to_string(),
-                content: ItemContent::Track {
-                    album: Some("Example Album".to_string()),
-                    duration_ms: Some(210000),
-                    artists: vec!["Example Artist".to_string()],
-                },
-                author: Some(scryforge_provider_core::Author {
-                    name: "Example Artist".to_string(),
-                    email: None,
-                    url: None,
-                    avatar_url: None,
-                }),
-                published: None,
-                updated: None,
-                url: Some("https://open.spotify.com/track/example".to_string()),
-                thumbnail_url: None,
-                is_read: false,
-                is_saved: true,
-                tags: vec![],
-                metadata: HashMap::new(),
-            }]
-        } else {
-            // Generic items for other streams
-            vec![Item {
-                id: ItemId::new("generic", "item-001"),
-                stream_id: StreamId(stream_id.to_string()),
-                title: "Example Item".to_string(),
-                content: ItemContent::Generic {
-                    body: Some("This is a generic item from the daemon API.".to_string()),
-                },
-                author: None,
-                published: Some(Utc::now()),
-                updated: None,
-                url: None,
-                thumbnail_url: None,
-                is_read: false,
-                is_saved: false,
-                tags: vec![],
-                metadata: HashMap::new(),
-            }]
-        }
+    /// Route `items.save` to read-later providers as configured.
+    pub fn with_save_router(mut self, router: SaveRouter) -> Self {
+        self.save_router = Some(router);
+        self
     }
-}
 
-#[jsonrpsee::core::async_trait]
-impl<C: Cache + 'static> ScryforgeApiServer for ApiImpl<C> {
-    async fn list_streams(&self) -> RpcResult<Vec<Stream>> {
-        Ok(Self::generate_dummy_streams())
+    /// Use the given weights when `items.unified` sorts by relevance.
+    pub fn with_ranking_config(mut self, ranking: RankingConfig) -> Self {
+        self.ranking = ranking;
+        self
     }
 
-    async fn list_items(&self, stream_id: String) -> RpcResult<Vec<Item>> {
-        Ok(Self::generate_dummy_items(&stream_id))
+    /// Hide items matched by the mute list from `items.unified`.
+    pub fn with_mute_filter(mut self, mute: MuteFilter) -> Self {
+        self.mute = mute;
+        self
     }
 
-    async fn sync_status(&self) -> RpcResult<HashMap<String, ProviderSyncState>> {
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let states = manager.get_sync_states().await;
-            Ok(states)
-        } else {
-            // If sync manager is not available, return empty status
-            Ok(HashMap::new())
-        }
+    /// Use the given window, format, and item limit for `digest.generate`.
+    pub fn with_digest_config(mut self, digest: DigestConfig) -> Self {
+        self.digest = digest;
+        self
     }
 
-    async fn sync_trigger(&self, provider_id: String) -> RpcResult<()> {
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            manager.trigger_sync(&provider_id).await.map_err(|e| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32000,
-                    format!("Failed to trigger sync: {}", e),
-                    None::<()>,
-                )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                -32001,
-                "Sync manager not available".to_string(),
-                None::<()>,
-            ))
-        }
+    /// Enable `items.summarize` with the given backend and length limits.
+    pub fn with_summarizer(
+        mut self,
+        summarizer: Arc<dyn Summarizer>,
+        config: SummarizerConfig,
+    ) -> Self {
+        self.summarizer = Some(summarizer);
+        self.summarizer_config = config;
+        self
     }
 
-    async fn search_query(
-        &self,
-        query: String,
-        filters: Option<JsonValue>,
-    ) -> RpcResult<Vec<Item>> {
-        // If cache is available, use it for search
-        if let Some(ref cache) = self.cache {
-            // Parse filters from JSON
-            let mut stream_id: Option<String> = None;
-            let mut content_type: Option<String> = None;
-            let mut is_read: Option<bool> = None;
-            let mut is_saved: Option<bool> = None;
-
-            if let Some(filter_obj) = filters {
-                if let Some(stream) = filter_obj.get("stream_id").and_then(|v| v.as_str()) {
-                    stream_id = Some(stream.to_string());
-                }
-                if let Some(ctype) = filter_obj.get("content_type").and_then(|v| v.as_str()) {
-                    content_type = Some(ctype.to_string());
-                }
-                if let Some(read) = filter_obj.get("is_read").and_then(|v| v.as_bool()) {
-                    is_read = Some(read);
-                }
-                if let Some(saved) = filter_obj.get("is_saved").and_then(|v| v.as_bool()) {
-                    is_saved = Some(saved);
-                }
-            }
+    /// Enable `items.read_aloud` and the `tts.*` methods with the given queue.
+    pub fn with_speech_queue(mut self, speech: SpeechQueue) -> Self {
+        self.speech = Some(speech);
+        self
+    }
 
-            cache
-                .search_items(
-                    &query,
-                    stream_id.as_deref(),
-                    content_type.as_deref(),
-                    is_read,
-                    is_saved,
-                )
-                .map_err(|e| {
-                    jsonrpsee::types::ErrorObjectOwned::owned(
-                        -32000,
-                        format!("Search failed: {}", e),
-                        None::<()>,
-                    )
-                })
-        } else {
-            // If no cache available, return empty results
-            Ok(Vec::new())
-        }
+    /// Open items with the given commands instead of the system default.
+    pub fn with_opener(mut self, opener: Opener) -> Self {
+        self.opener = opener;
+        self
     }
 
-    async fn mark_item_read(&self, item_id: String) -> RpcResult<()> {
-        if let Some(ref cache) = self.cache {
-            let id = ItemId(item_id);
-            cache.mark_read(&id, true).map_err(|e| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32000,
-                    format!("Failed to mark item as read: {}", e),
-                    None::<()>,
-                )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+    /// Enable `items.play` and the `player.*` methods with the given player.
+    pub fn with_player(mut self, player: Player) -> Self {
+        self.player = Some(player);
+        self
+    }
+
+    /// Enable `items.download` and the `downloads.*` methods with the given
+    /// manager.
+    pub fn with_download_manager(mut self, downloads: DownloadManager) -> Self {
+        self.downloads = Some(downloads);
+        self
+    }
+
+    /// Enable `export.items`, writing into the given directory.
+    pub fn with_export_dir(mut self, dir: PathBuf) -> Self {
+        self.export_dir = Some(dir);
+        self
+    }
+
+    /// Offer the given share targets through `items.share`.
+    pub fn with_sharer(mut self, sharer: Sharer) -> Self {
+        self.sharer = sharer;
+        self
+    }
+
+    /// Fetch link titles with the given clients, such as ones going through
+    /// provider proxies.
+    pub fn with_link_titles(mut self, link_titles: LinkTitles) -> Self {
+        self.link_titles = link_titles;
+        self
+    }
+
+    /// Suggest and import contacts per the `[contacts]` config.
+    pub fn with_contacts(mut self, contacts: Contacts) -> Self {
+        self.contacts = contacts;
+        self
+    }
+
+    /// Answer `search.query` from a full-text index rather than the cache.
+    #[cfg(feature = "search-index")]
+    pub fn with_search_index(mut self, index: Arc<SearchIndex>) -> Self {
+        self.search_index = Some(index);
+        self
+    }
+
+    fn speech_queue(&self) -> RpcResult<&SpeechQueue> {
+        self.speech.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
-                "Cache not available".to_string(),
+                "Read aloud not configured".to_string(),
                 None::<()>,
-            ))
-        }
+            )
+        })
     }
 
-    async fn mark_item_unread(&self, item_id: String) -> RpcResult<()> {
-        if let Some(ref cache) = self.cache {
-            let id = ItemId(item_id);
-            cache.mark_read(&id, false).map_err(|e| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32000,
-                    format!("Failed to mark item as unread: {}", e),
-                    None::<()>,
-                )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+    fn player(&self) -> RpcResult<&Player> {
+        self.player.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
-                "Cache not available".to_string(),
+                "Player not configured".to_string(),
                 None::<()>,
-            ))
-        }
+            )
+        })
     }
 
-    async fn archive_item(&self, item_id: String) -> RpcResult<()> {
-        if let Some(ref cache) = self.cache {
-            let id = ItemId(item_id);
-            cache.mark_archived(&id, true).map_err(|e| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32000,
-                    format!("Failed to archive item: {}", e),
-                    None::<()>,
-                )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+    fn download_manager(&self) -> RpcResult<&DownloadManager> {
+        self.downloads.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
-                "Cache not available".to_string(),
+                "Downloads not configured".to_string(),
                 None::<()>,
-            ))
+            )
+        })
+    }
+
+    /// Log an interaction for relevance ranking; failures only warn, since
+    /// the action itself has already succeeded.
+    fn record_interaction(&self, cache: &C, id: &ItemId, kind: InteractionKind) {
+        if let Err(e) = cache.record_interaction(id, kind) {
+            tracing::warn!("Failed to record interaction for {}: {}", id.as_str(), e);
         }
     }
 
-    async fn save_item(&self, item_id: String) -> RpcResult<()> {
-        if let Some(ref cache) = self.cache {
-            let id = ItemId(item_id);
-            cache.mark_starred(&id, true).map_err(|e| {
+    /// Hand a saved item off to its read-later target, if one is configured.
+    async fn route_save(&self, cache: &C, id: &ItemId) -> RpcResult<()> {
+        let (Some(router), Some(sync_manager)) = (&self.save_router, &self.sync_manager) else {
+            return Ok(());
+        };
+
+        let item = match cache.get_item(id) {
+            Ok(Some(item)) => item,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                ))
+            }
+        };
+
+        let manager = sync_manager.read().await;
+        router
+            .route(manager.get_registry(), &item)
+            .await
+            .map_err(|e| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
                     -32000,
-                    format!("Failed to save item: {}", e),
+                    format!("Saved locally, but handoff failed: {}", e),
                     None::<()>,
                 )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                -32001,
-                "Cache not available".to_string(),
-                None::<()>,
-            ))
+            })?;
+        Ok(())
+    }
+
+    /// The video ID of a YouTube video item.
+    fn youtube_video_id(item: &Item) -> Option<&str> {
+        match item.content {
+            ItemContent::Video { .. } => item.id.as_str().strip_prefix("youtube:"),
+            _ => None,
         }
     }
 
-    async fn unsave_item(&self, item_id: String) -> RpcResult<()> {
-        if let Some(ref cache) = self.cache {
-            let id = ItemId(item_id);
-            cache.mark_starred(&id, false).map_err(|e| {
+    /// The cached transcript of `item`, fetching and caching it from the
+    /// YouTube provider if needed.
+    async fn load_transcript(&self, cache: &C, item: &Item) -> RpcResult<String> {
+        if let Some(transcript) = item.metadata.get(summarize::TRANSCRIPT_KEY) {
+            return Ok(transcript.clone());
+        }
+
+        let video_id = Self::youtube_video_id(item).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Item '{}' is not a YouTube video", item.id.as_str()),
+                None::<()>,
+            )
+        })?;
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let manager = sync_manager.read().await;
+        let registry = manager.get_registry();
+        let provider = registry.get("youtube");
+        let youtube = provider
+            .as_ref()
+            .and_then(|provider| {
+                provider
+                    .as_any()
+                    .downcast_ref::<provider_youtube::YouTubeProvider>()
+            })
+            .ok_or_else(|| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32000,
-                    format!("Failed to unsave item: {}", e),
+                    -32001,
+                    "YouTube provider not available".to_string(),
                     None::<()>,
                 )
-            })
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                -32001,
-                "Cache not available".to_string(),
-                None::<()>,
-            ))
+            })?;
+
+        let transcript = registry
+            .guard("youtube", youtube.get_transcript(video_id))
+            .await
+            .map_err(|e| match e {
+                StreamError::ItemNotFound(message) => {
+                    jsonrpsee::types::ErrorObjectOwned::owned(-32003, message, None::<()>)
+                }
+                e => jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to fetch transcript: {}", e),
+                    None::<()>,
+                ),
+            })?
+            .text();
+
+        if let Err(e) = cache.set_item_metadata(&item.id, summarize::TRANSCRIPT_KEY, &transcript) {
+            tracing::warn!("Failed to cache transcript for {}: {}", item.id.as_str(), e);
         }
+        Ok(transcript)
     }
 
-    async fn list_collections(&self) -> RpcResult<Vec<Collection>> {
-        use scryforge_provider_core::HasCollections;
+    /// Search the cache for `query`: through the full-text index, best match
+    /// first, when there is one and the query has words to look for, and
+    /// the cache's own search otherwise.
+    fn search_cache(&self, cache: &C, query: &SearchQuery) -> anyhow::Result<Vec<SearchResult>> {
+        #[cfg(feature = "search-index")]
+        if let Some(ref index) = self.search_index {
+            if query.needles().next().is_some() {
+                return index.search(cache, query, INDEX_SEARCH_LIMIT);
+            }
+        }
+        let needles: Vec<&str> = query.needles().collect();
+        Ok(cache
+            .search(query)?
+            .into_iter()
+            .map(|item| SearchResult::new(item, &needles))
+            .collect())
+    }
 
+    /// Strip items sent to a client as the bandwidth mode asks. The cache
+    /// keeps everything, so what is stripped comes back once the mode is off.
+    async fn strip_for_bandwidth<'a>(&self, items: impl IntoIterator<Item = &'a mut Item> + Send) {
         if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let registry = manager.get_registry();
+            sync_manager.read().await.bandwidth().strip(items);
+        }
+    }
 
-            let mut all_collections = Vec::new();
+    /// Search every provider that implements `HasSearch` and that `query`
+    /// allows, such as only the one owning `options.stream_id` when that is
+    /// set. A failing provider is logged and skipped so one outage doesn't
+    /// fail the whole search.
+    async fn search_providers(&self, query: &SearchQuery, mut options: SearchOptions) -> Vec<Item> {
+        let Some(sync_manager) = self.sync_manager.as_ref() else {
+            return Vec::new();
+        };
+        let manager = sync_manager.read().await;
+        let registry = manager.get_registry();
+        let bandwidth = manager.bandwidth();
+        options.low_bandwidth = bandwidth.is_low();
+        if let Some(page_size) = bandwidth.page_size() {
+            options.limit = Some(
+                options
+                    .limit
+                    .map_or(page_size, |limit| limit.min(page_size)),
+            );
+        }
 
-            // Iterate through all providers and collect their collections
-            for provider_id in registry.list() {
-                if let Some(provider) = registry.get(provider_id) {
-                    // Check if provider supports collections
-                    if provider.capabilities().has_collections {
-                        // Downcast to HasCollections trait
-                        if let Some(collections_provider) = provider
-                            .as_any()
-                            .downcast_ref::<provider_dummy::DummyProvider>(
-                        ) {
-                            match collections_provider.list_collections().await {
-                                Ok(collections) => all_collections.extend(collections),
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "Failed to list collections from provider {}: {}",
-                                        provider_id,
-                                        e
-                                    );
-                                }
-                            }
-                        }
-                    }
+        let mut provider_ids = registry.list();
+        provider_ids.sort_unstable();
+
+        let text = query.text();
+        let mut items = Vec::new();
+        for provider_id in provider_ids {
+            if let Some(ref stream_id) = options.stream_id {
+                if !stream_id.as_str().starts_with(&format!("{}:", provider_id)) {
+                    continue;
                 }
             }
+            if !query.allows_provider(provider_id) {
+                continue;
+            }
+            let Some(provider) = registry.get(provider_id) else {
+                continue;
+            };
+            let Some(searcher) = provider.as_search() else {
+                continue;
+            };
+            if !provider.capabilities().supports_search {
+                continue;
+            }
 
-            Ok(all_collections)
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                -32001,
-                "Sync manager not available".to_string(),
-                None::<()>,
-            ))
+            match registry
+                .guard(provider_id, searcher.search(&text, options.clone()))
+                .await
+            {
+                Ok(found) => {
+                    registry.budgets().record_items(provider_id, &found);
+                    items.extend(found);
+                }
+                Err(e) => tracing::warn!("Search in provider '{}' failed: {}", provider_id, e),
+            }
         }
+        items
     }
 
-    async fn get_collection_items(&self, collection_id: String) -> RpcResult<Vec<Item>> {
-        use scryforge_provider_core::HasCollections;
+    /// Record the outcome of a mutation on a provider in the audit log,
+    /// passing the result through.
+    fn audited<T>(
+        &self,
+        provider_id: &str,
+        item_id: Option<&ItemId>,
+        action: &str,
+        result: Result<T, StreamError>,
+    ) -> Result<T, StreamError> {
+        let error = result.as_ref().err().map(ToString::to_string);
+        self.record_audit(provider_id, item_id, action, error.as_deref());
+        result
+    }
 
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let registry = manager.get_registry();
+    fn record_audit(
+        &self,
+        provider_id: &str,
+        item_id: Option<&ItemId>,
+        action: &str,
+        error: Option<&str>,
+    ) {
+        if let Some(ref cache) = self.cache {
+            audit::record(cache.as_ref(), provider_id, item_id, action, error);
+        }
+    }
 
-            // Extract provider ID from collection ID
-            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+    /// Publish an `ActionCompleted` event if an event bus is attached.
+    fn publish_action(&self, item_id: &str, action: &str) {
+        if let Some(ref events) = self.events {
+            events.publish(DaemonEvent::ActionCompleted {
+                item_id: item_id.to_string(),
+                action: action.to_string(),
+                timestamp: Utc::now(),
+            });
+        }
+    }
+
+    /// The next triage item out of the unread unified feed.
+    fn triage_view(&self) -> RpcResult<TriageView> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let inbox = UnifiedFeedsView::new(cache.clone())
+            .with_ranking_config(self.ranking.clone())
+            .with_mute_filter(self.mute.clone())
+            .get_all_items(UnifiedFeedOptions {
+                is_read: Some(false),
+                ..UnifiedFeedOptions::default()
+            })
+            .map_err(|e| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32002,
-                    "Invalid collection ID format".to_string(),
+                    -32000,
+                    format!("Failed to load inbox: {}", e),
                     None::<()>,
                 )
             })?;
+        self.triage.view(cache.as_ref(), inbox).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load inbox: {}", e),
+                None::<()>,
+            )
+        })
+    }
 
-            let provider = registry.get(provider_id).ok_or_else(|| {
+    /// Take a triage decision on an item and return the next one.
+    fn triage_apply(
+        &self,
+        item_id: String,
+        action: TriageAction,
+        snooze_until: Option<DateTime<Utc>>,
+    ) -> RpcResult<TriageView> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        if !matches!(cache.get_item(&id), Ok(Some(_))) {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item not found: {}", id.0),
+                None::<()>,
+            ));
+        }
+        let step = self
+            .triage
+            .apply(cache.as_ref(), &id, action, snooze_until)
+            .map_err(|e| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32003,
-                    format!("Provider '{}' not found", provider_id),
+                    -32000,
+                    format!("Failed to triage item: {}", e),
                     None::<()>,
                 )
             })?;
+        let name = match step.action {
+            TriageAction::Archive => "archive",
+            TriageAction::Save => "save",
+            TriageAction::Snooze => "snooze",
+            TriageAction::Skip => "skip",
+        };
+        self.publish_action(&id.0, name);
+        self.triage_view()
+    }
 
-            // Check if provider supports collections
-            if !provider.capabilities().has_collections {
-                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32004,
-                    format!("Provider '{}' does not support collections", provider_id),
-                    None::<()>,
-                ));
-            }
+    /// The reading queue as it stands.
+    fn load_reading_queue(&self) -> RpcResult<ReadingQueue> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
 
-            // Downcast to HasCollections trait
-            if let Some(collections_provider) = provider
-                .as_any()
-                .downcast_ref::<provider_dummy::DummyProvider>()
-            {
-                collections_provider
-                    .get_collection_items(&CollectionId(collection_id))
-                    .await
-                    .map_err(|e| {
-                        jsonrpsee::types::ErrorObjectOwned::owned(
-                            -32000,
-                            format!("Failed to get collection items: {}", e),
-                            None::<()>,
-                        )
-                    })
-            } else {
-                Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32005,
-                    format!(
-                        "Provider '{}' does not implement HasCollections",
-                        provider_id
-                    ),
-                    None::<()>,
-                ))
-            }
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+        ReadingQueue::load(cache.as_ref()).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load reading queue: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    /// Apply `change` to a queued item, failing with not-found when the item
+    /// isn't in the queue.
+    fn change_queued_item(
+        &self,
+        item_id: &str,
+        change: impl FnOnce(&C, &ItemId) -> anyhow::Result<bool>,
+    ) -> RpcResult<()> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
-                "Sync manager not available".to_string(),
+                "Cache not available".to_string(),
                 None::<()>,
-            ))
+            )
+        })?;
+
+        let id = ItemId(item_id.to_string());
+        let queued = change(cache.as_ref(), &id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to update reading queue: {}", e),
+                None::<()>,
+            )
+        })?;
+        if !queued {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item not in reading queue: {}", id.0),
+                None::<()>,
+            ));
         }
+        Ok(())
     }
 
-    async fn add_to_collection(&self, collection_id: String, item_id: String) -> RpcResult<()> {
-        use scryforge_provider_core::HasCollections;
+    /// Record a spam verdict on an item, file it away or back, and queue the
+    /// move for its provider.
+    fn judge_spam(&self, item_id: String, spam: bool) -> RpcResult<()> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
 
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let registry = manager.get_registry();
+        let id = ItemId(item_id);
+        let failed = |e: anyhow::Error| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to mark item: {}", e),
+                None::<()>,
+            )
+        };
+        if !cache.record_spam_verdict(&id, spam).map_err(failed)? {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item not found: {}", id.0),
+                None::<()>,
+            ));
+        }
+        cache.mark_archived(&id, spam).map_err(failed)?;
+        let action = if spam {
+            OutboxAction::MarkSpam
+        } else {
+            OutboxAction::MarkNotSpam
+        };
+        outbox::queue(cache.as_ref(), &id, action).map_err(failed)?;
+        self.publish_action(&id.0, &action.to_string());
+        Ok(())
+    }
 
-            // Extract provider ID from collection ID
-            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+    /// Load a cached task along with the registry and the provider that owns it.
+    async fn task_with_provider(
+        &self,
+        item_id: String,
+    ) -> RpcResult<(Item, Arc<ProviderRegistry>, Arc<dyn Provider>)> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let task = cache
+            .get_item(&id)
+            .map_err(|e| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32002,
-                    "Invalid collection ID format".to_string(),
+                    -32000,
+                    format!("Failed to load item: {}", e),
                     None::<()>,
                 )
-            })?;
-
-            let provider = registry.get(provider_id).ok_or_else(|| {
+            })?
+            .ok_or_else(|| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32003,
-                    format!("Provider '{}' not found", provider_id),
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
                     None::<()>,
                 )
             })?;
 
-            // Check if provider supports collections
-            if !provider.capabilities().has_collections {
-                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32004,
-                    format!("Provider '{}' does not support collections", provider_id),
-                    None::<()>,
-                ));
-            }
+        let provider_id = Self::extract_provider_id(task.id.as_str()).unwrap_or_default();
+        let (registry, provider) = self.task_provider(provider_id).await?;
+        Ok((task, registry, provider))
+    }
 
-            // Downcast to HasCollections trait
-            if let Some(collections_provider) = provider
-                .as_any()
-                .downcast_ref::<provider_dummy::DummyProvider>()
-            {
-                collections_provider
-                    .add_to_collection(&CollectionId(collection_id), &ItemId(item_id))
-                    .await
-                    .map_err(|e| {
-                        jsonrpsee::types::ErrorObjectOwned::owned(
-                            -32000,
-                            format!("Failed to add item to collection: {}", e),
-                            None::<()>,
-                        )
-                    })
-            } else {
-                Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32005,
-                    format!(
-                        "Provider '{}' does not implement HasCollections",
-                        provider_id
-                    ),
-                    None::<()>,
-                ))
-            }
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+    /// Look up a provider that manages tasks.
+    async fn task_provider(
+        &self,
+        provider_id: &str,
+    ) -> RpcResult<(Arc<ProviderRegistry>, Arc<dyn Provider>)> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
                 "Sync manager not available".to_string(),
                 None::<()>,
-            ))
-        }
+            )
+        })?;
+        let registry = sync_manager.read().await.get_registry().clone();
+
+        let provider = registry.get(provider_id).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32003,
+                format!("Provider '{}' not found", provider_id),
+                None::<()>,
+            )
+        })?;
+        Self::tasks_of(provider.as_ref())?;
+        Ok((registry, provider))
     }
 
-    async fn remove_from_collection(
+    /// The task operations of `provider`, or an error if it has none.
+    fn tasks_of(provider: &dyn Provider) -> RpcResult<&dyn HasTasks> {
+        provider.as_tasks().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Provider '{}' does not support tasks", provider.id()),
+                None::<()>,
+            )
+        })
+    }
+
+    /// The name and items of a collection, read from its provider.
+    async fn provider_collection(
         &self,
-        collection_id: String,
-        item_id: String,
-    ) -> RpcResult<()> {
-        use scryforge_provider_core::HasCollections;
+        provider_id: &str,
+        collection_id: &str,
+    ) -> RpcResult<(String, Vec<Item>)> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let registry = sync_manager.read().await.get_registry().clone();
 
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let registry = manager.get_registry();
+        let provider = registry.get(provider_id).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32003,
+                format!("Provider '{}' not found", provider_id),
+                None::<()>,
+            )
+        })?;
+        let collections = provider.as_collections().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Provider '{}' does not support collections", provider_id),
+                None::<()>,
+            )
+        })?;
 
-            // Extract provider ID from collection ID
-            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+        let failed = |e: StreamError| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to get collection items: {}", e),
+                None::<()>,
+            )
+        };
+        let name = registry
+            .guard(provider.id(), collections.list_collections())
+            .await
+            .map_err(failed)?
+            .into_iter()
+            .find(|collection| collection.id.0 == collection_id)
+            .map(|collection| collection.name)
+            .ok_or_else(|| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
                     -32002,
-                    "Invalid collection ID format".to_string(),
+                    format!("Collection '{}' not found", collection_id),
                     None::<()>,
                 )
             })?;
+        let items = registry
+            .guard(
+                provider.id(),
+                collections.get_collection_items(&CollectionId(collection_id.to_string())),
+            )
+            .await
+            .map_err(failed)?;
+        registry.budgets().record_items(provider.id(), &items);
+        Ok((name, items))
+    }
 
-            let provider = registry.get(provider_id).ok_or_else(|| {
+    /// Look up a provider that manages tags.
+    async fn tag_provider(
+        &self,
+        provider_id: &str,
+    ) -> RpcResult<(Arc<ProviderRegistry>, Arc<dyn Provider>)> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let registry = sync_manager.read().await.get_registry().clone();
+
+        let provider = registry.get(provider_id).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32003,
+                format!("Provider '{}' not found", provider_id),
+                None::<()>,
+            )
+        })?;
+        Self::tags_of(provider.as_ref())?;
+        Ok((registry, provider))
+    }
+
+    /// Look up the bookmarks provider, for the operations only it offers.
+    async fn bookmarks_provider(&self) -> RpcResult<Arc<dyn Provider>> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let provider = sync_manager.read().await.get_registry().get("bookmarks");
+        provider
+            .filter(|provider| provider.as_any().is::<BookmarksProvider>())
+            .ok_or_else(|| {
                 jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32003,
-                    format!("Provider '{}' not found", provider_id),
+                    -32001,
+                    "Bookmarks provider not available".to_string(),
                     None::<()>,
                 )
-            })?;
+            })
+    }
 
-            // Check if provider supports collections
-            if !provider.capabilities().has_collections {
-                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32004,
-                    format!("Provider '{}' does not support collections", provider_id),
+    /// A cached item and the link to `url` in its body. Only links the item
+    /// holds are acted on, so clients can't open arbitrary URLs through it.
+    fn item_link(&self, item_id: String, url: &str) -> RpcResult<(Item, ItemLink)> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
                     None::<()>,
-                ));
-            }
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
 
-            // Downcast to HasCollections trait
-            if let Some(collections_provider) = provider
-                .as_any()
-                .downcast_ref::<provider_dummy::DummyProvider>()
-            {
-                collections_provider
-                    .remove_from_collection(&CollectionId(collection_id), &ItemId(item_id))
-                    .await
-                    .map_err(|e| {
-                        jsonrpsee::types::ErrorObjectOwned::owned(
-                            -32000,
-                            format!("Failed to remove item from collection: {}", e),
-                            None::<()>,
-                        )
-                    })
-            } else {
-                Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32005,
-                    format!(
-                        "Provider '{}' does not implement HasCollections",
-                        provider_id
-                    ),
+        let link = links::extract(&item)
+            .into_iter()
+            .find(|link| link.url == url)
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32602,
+                    format!("Item '{}' doesn't link to {}", id.as_str(), url),
                     None::<()>,
-                ))
-            }
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                )
+            })?;
+        Ok((item, link))
+    }
+
+    /// The local bookmark ID in a bookmarks item ID.
+    fn bookmark_id(item_id: &str) -> RpcResult<&str> {
+        item_id.strip_prefix("bookmarks:").ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Item '{}' is not a bookmark", item_id),
+                None::<()>,
+            )
+        })
+    }
+
+    /// The tag operations of `provider`, or an error if it has none.
+    /// Look up a provider that has communities.
+    async fn community_provider(
+        &self,
+        provider_id: &str,
+    ) -> RpcResult<(Arc<ProviderRegistry>, Arc<dyn Provider>)> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
                 -32001,
                 "Sync manager not available".to_string(),
                 None::<()>,
-            ))
+            )
+        })?;
+        let registry = sync_manager.read().await.get_registry().clone();
+
+        let provider = registry.get(provider_id).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32003,
+                format!("Provider '{}' not found", provider_id),
+                None::<()>,
+            )
+        })?;
+        Self::communities_of(provider.as_ref())?;
+        Ok((registry, provider))
+    }
+
+    /// The communities of `provider`, or an error if it has none.
+    fn communities_of(provider: &dyn Provider) -> RpcResult<&dyn HasCommunities> {
+        provider.as_communities().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Provider '{}' does not have communities", provider.id()),
+                None::<()>,
+            )
+        })
+    }
+
+    fn tags_of(provider: &dyn Provider) -> RpcResult<&dyn HasTags> {
+        provider.as_tags().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Provider '{}' does not support tags", provider.id()),
+                None::<()>,
+            )
+        })
+    }
+
+    /// Replace the cached copy of a task the provider has changed.
+    ///
+    /// The change has already been made at the source, so a cache failure is
+    /// only logged; the provider's copy is returned in that case.
+    fn store_task(&self, task: Item) -> Item {
+        let Some(ref cache) = self.cache else {
+            return task;
+        };
+        match cache
+            .upsert_items(std::slice::from_ref(&task))
+            .and_then(|_| cache.get_item(&task.id))
+        {
+            Ok(Some(cached)) => cached,
+            Ok(None) => task,
+            Err(e) => {
+                tracing::warn!("Failed to cache task {}: {:#}", task.id.as_str(), e);
+                task
+            }
+        }
+    }
+
+    /// Mark a task completed or not, at its provider and in the cache.
+    async fn set_task_completed(&self, item_id: String, completed: bool) -> RpcResult<Item> {
+        let (mut task, registry, provider) = self.task_with_provider(item_id).await?;
+        let tasks = Self::tasks_of(provider.as_ref())?;
+
+        let result = if completed {
+            registry
+                .guard(provider.id(), tasks.complete_task(&task))
+                .await
+        } else {
+            registry
+                .guard(provider.id(), tasks.uncomplete_task(&task))
+                .await
+        };
+        let action = if completed { "complete" } else { "uncomplete" };
+        self.audited(provider.id(), Some(&task.id), action, result)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to update task: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        if let ItemContent::Task { is_completed, .. } = &mut task.content {
+            *is_completed = completed;
+        }
+        let task = self.store_task(task);
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.mark_read(&task.id, completed) {
+                tracing::warn!("Failed to mark task {} read: {:#}", task.id.as_str(), e);
+            }
+        }
+
+        self.publish_action(&task.id.0, action);
+        Ok(Item {
+            is_read: completed,
+            ..task
+        })
+    }
+
+    /// Extract provider ID from a collection ID string.
+    /// Format expected: "provider:collection-id"
+    fn extract_provider_id(id: &str) -> Option<&str> {
+        id.split(':').next()
+    }
+
+    /// Generate dummy streams for testing.
+    fn generate_dummy_streams() -> Vec<Stream> {
+        vec![
+            Stream {
+                id: StreamId::new("email", "inbox", "gmail"),
+                name: "Gmail Inbox".to_string(),
+                provider_id: "email-imap".to_string(),
+                stream_type: StreamType::Feed,
+                icon: Some("📧".to_string()),
+                unread_count: Some(5),
+                total_count: Some(150),
+                last_updated: Some(Utc::now()),
+                metadata: HashMap::new(),
+            },
+            Stream {
+                id: StreamId::new("rss", "feed", "hackernews"),
+                name: "Hacker News".to_string(),
+                provider_id: "rss".to_string(),
+                stream_type: StreamType::Feed,
+                icon: Some("📰".to_string()),
+                unread_count: Some(42),
+                total_count: Some(100),
+                last_updated: Some(Utc::now()),
+                metadata: HashMap::new(),
+            },
+            Stream {
+                id: StreamId::new("reddit", "home", "default"),
+                name: "Reddit Home".to_string(),
+                provider_id: "reddit".to_string(),
+                stream_type: StreamType::Feed,
+                icon: Some("🔴".to_string()),
+                unread_count: None,
+                total_count: None,
+                last_updated: Some(Utc::now()),
+                metadata: HashMap::new(),
+            },
+            Stream {
+                id: StreamId::new("spotify", "collection", "liked"),
+                name: "Liked Songs".to_string(),
+                provider_id: "spotify".to_string(),
+                stream_type: StreamType::SavedItems,
+                icon: Some("💚".to_string()),
+                unread_count: None,
+                total_count: Some(523),
+                last_updated: Some(Utc::now()),
+                metadata: HashMap::new(),
+            },
+        ]
+    }
+
+    /// Generate dummy items for a stream.
+    fn generate_dummy_items(stream_id: &str) -> Vec<Item> {
+        // Return different items based on stream
+        if stream_id.starts_with("email:") {
+            vec![
+                Item {
+                    id: ItemId::new("email", "msg-001"),
+                    stream_id: StreamId(stream_id.to_string()),
+                    title: "Meeting tomorrow at 10am".to_string(),
+                    content: ItemContent::Email {
+                        subject: "Meeting tomorrow at 10am".to_string(),
+                        body_text: Some(
+                            "Hi,\n\nJust a reminder about our meeting tomorrow.\n\nBest,\nJohn"
+                                .to_string(),
+                        ),
+                        body_html: None,
+                        snippet: "Just a reminder about our meeting...".to_string(),
+                    },
+                    author: Some(scryforge_provider_core::Author {
+                        name: "John Doe".to_string(),
+                        email: Some("john@example.com".to_string()),
+                        url: None,
+                        avatar_url: None,
+                    }),
+                    published: Some(Utc::now()),
+                    updated: None,
+                    url: None,
+                    thumbnail_url: None,
+                    is_read: false,
+                    is_saved: false,
+                    tags: vec![],
+                    metadata: HashMap::new(),
+                },
+                Item {
+                    id: ItemId::new("email", "msg-002"),
+                    stream_id: StreamId(stream_id.to_string()),
+                    title: "Your order has shipped".to_string(),
+                    content: ItemContent::Email {
+                        subject: "Your order has shipped".to_string(),
+                        body_text: Some(
+                            "Your order #12345 has shipped and will arrive by Friday.".to_string(),
+                        ),
+                        body_html: None,
+                        snippet: "Your order #12345 has shipped...".to_string(),
+                    },
+                    author: Some(scryforge_provider_core::Author {
+                        name: "Shop Support".to_string(),
+                        email: Some("support@shop.com".to_string()),
+                        url: None,
+                        avatar_url: None,
+                    }),
+                    published: Some(Utc::now()),
+                    updated: None,
+                    url: None,
+                    thumbnail_url: None,
+                    is_read: true,
+                    is_saved: false,
+                    tags: vec![],
+                    metadata: HashMap::new(),
+                },
+            ]
+        } else if stream_id.starts_with("rss:") {
+            vec![
+                Item {
+                    id: ItemId::new("rss", "article-001"),
+                    stream_id: StreamId(stream_id.to_string()),
+                    title: "Show HN: A new Rust TUI framework".to_string(),
+                    content: ItemContent::Article {
+                        summary: Some(
+                            "I've been working on a new TUI framework in Rust...".to_string(),
+                        ),
+                        full_content: None,
+                    },
+                    author: Some(scryforge_provider_core::Author {
+                        name: "rustdev".to_string(),
+                        email: None,
+                        url: None,
+                        avatar_url: None,
+                    }),
+                    published: Some(Utc::now()),
+                    updated: None,
+                    url: Some("https://news.ycombinator.com/item?id=123".to_string()),
+                    thumbnail_url: None,
+                    is_read: false,
+                    is_saved: false,
+                    tags: vec![],
+                    metadata: HashMap::new(),
+                },
+                Item {
+                    id: ItemId::new("rss", "article-002"),
+                    stream_id: StreamId(stream_id.to_string()),
+                    title: "Rust 1.75 Released".to_string(),
+                    content: ItemContent::Article {
+                        summary: Some(
+                            "The Rust team is happy to announce a new version of Rust..."
+                                .to_string(),
+                        ),
+                        full_content: None,
+                    },
+                    author: Some(scryforge_provider_core::Author {
+                        name: "Rust Blog".to_string(),
+                        email: None,
+                        url: Some("https://blog.rust-lang.org".to_string()),
+                        avatar_url: None,
+                    }),
+                    published: Some(Utc::now()),
+                    updated: None,
+                    url: Some("https://blog.rust-lang.org/2023/12/28/Rust-1.75.0.html".to_string()),
+                    thumbnail_url: None,
+                    is_read: false,
+                    is_saved: false,
+                    tags: vec![],
+                    metadata: HashMap::new(),
+                },
+            ]
+        } else if stream_id.starts_with("spotify:") {
+            vec![Item {
+                id: ItemId::new("spotify", "track-001"),
+                stream_id: StreamId(stream_id.to_string()),
+                title: "Example Song".to_string(),
+                content: ItemContent::Track {
+                    album: Some("Example Album".to_string()),
+                    duration_ms: Some(210000),
+                    artists: vec!["Example Artist".to_string()],
+                },
+                author: Some(scryforge_provider_core::Author {
+                    name: "Example Artist".to_string(),
+                    email: None,
+                    url: None,
+                    avatar_url: None,
+                }),
+                published: None,
+                updated: None,
+                url: Some("https://open.spotify.com/track/example".to_string()),
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: true,
+                tags: vec![],
+                metadata: HashMap::new(),
+            }]
+        } else {
+            // Generic items for other streams
+            vec![Item {
+                id: ItemId::new("generic", "item-001"),
+                stream_id: StreamId(stream_id.to_string()),
+                title: "Example Item".to_string(),
+                content: ItemContent::Generic {
+                    body: Some("This is a generic item from the daemon API.".to_string()),
+                },
+                author: None,
+                published: Some(Utc::now()),
+                updated: None,
+                url: None,
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: false,
+                tags: vec![],
+                metadata: HashMap::new(),
+            }]
+        }
+    }
+}
+
+#[jsonrpsee::core::async_trait]
+impl<C: Cache + 'static> ScryforgeApiServer for ApiImpl<C> {
+    async fn list_streams(&self) -> RpcResult<Vec<Stream>> {
+        Ok(Self::generate_dummy_streams())
+    }
+
+    async fn list_items(&self, stream_id: String) -> RpcResult<Vec<Item>> {
+        Ok(Self::generate_dummy_items(&stream_id))
+    }
+
+    async fn unified_items(&self, options: Option<UnifiedFeedOptions>) -> RpcResult<Vec<Item>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let mut items = UnifiedFeedsView::new(cache.clone())
+            .with_ranking_config(self.ranking.clone())
+            .with_mute_filter(self.mute.clone())
+            .get_all_items(options.unwrap_or_default())
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load unified feed: {}", e),
+                    None::<()>,
+                )
+            })?;
+        self.strip_for_bandwidth(&mut items).await;
+        Ok(items)
+    }
+
+    async fn list_providers(&self) -> RpcResult<Vec<ProviderInfo>> {
+        let Some(ref sync_manager) = self.sync_manager else {
+            return Ok(Vec::new());
+        };
+
+        let manager = sync_manager.read().await;
+        let registry = manager.get_registry();
+        let mut providers: Vec<ProviderInfo> = registry
+            .list()
+            .into_iter()
+            .filter_map(|id| registry.get(id))
+            .map(|provider| ProviderInfo {
+                id: provider.id().to_string(),
+                name: provider.name().to_string(),
+                capabilities: provider.capabilities(),
+                panic: registry.panic_message(provider.id()),
+            })
+            .collect();
+        providers.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(providers)
+    }
+
+    async fn sync_status(&self) -> RpcResult<HashMap<String, ProviderSyncState>> {
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let states = manager.get_sync_states().await;
+            Ok(states)
+        } else {
+            // If sync manager is not available, return empty status
+            Ok(HashMap::new())
+        }
+    }
+
+    async fn sync_trigger(&self, provider_id: String) -> RpcResult<()> {
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            manager.trigger_sync(&provider_id).await.map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to trigger sync: {}", e),
+                    None::<()>,
+                )
+            })
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn sync_trigger_all(&self) -> RpcResult<Vec<SyncOutcome>> {
+        if let Some(ref sync_manager) = self.sync_manager {
+            // Let go of the manager while the syncs run
+            let sync = sync_manager.read().await.sync_all();
+            Ok(sync.await)
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn sync_conflicts(&self, limit: Option<u32>) -> RpcResult<Vec<StateConflict>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_conflicts(limit.unwrap_or(50)).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to list conflicts: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn sync_dry_run(&self, provider_id: String) -> RpcResult<SyncPreview> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let manager = sync_manager.read().await;
+        manager.dry_run(&provider_id).await.map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to preview sync: {:#}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn bandwidth_status(&self) -> RpcResult<BandwidthStatus> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        Ok(sync_manager.read().await.bandwidth().status())
+    }
+
+    async fn set_bandwidth(&self, low_bandwidth: bool) -> RpcResult<BandwidthStatus> {
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let manager = sync_manager.read().await;
+        manager.bandwidth().set_low(low_bandwidth);
+        Ok(manager.bandwidth().status())
+    }
+
+    async fn audit_list(
+        &self,
+        provider_id: Option<String>,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<AuditEntry>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache
+            .get_audit_log(provider_id.as_deref(), limit.unwrap_or(100))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to list audit log: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn search_query(
+        &self,
+        query: String,
+        filters: Option<JsonValue>,
+    ) -> RpcResult<Vec<SearchResult>> {
+        let mut search = SearchQuery::parse(&query).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Invalid search query: {}", e),
+                None::<()>,
+            )
+        })?;
+
+        // Filters apply on top of the fields in the query
+        let mut remote = true;
+        let mut sort: Option<String> = None;
+        let mut time: Option<String> = None;
+        let mut min_minutes: Option<u32> = None;
+        let mut max_minutes: Option<u32> = None;
+
+        if let Some(filter_obj) = filters {
+            if let Some(stream) = filter_obj.get("stream_id").and_then(|v| v.as_str()) {
+                search.stream_id = Some(stream.to_string());
+            }
+            if let Some(ctype) = filter_obj.get("content_type").and_then(|v| v.as_str()) {
+                search.content_type = Some(ctype.to_lowercase());
+            }
+            if let Some(read) = filter_obj.get("is_read").and_then(|v| v.as_bool()) {
+                search.is_read = Some(read);
+            }
+            if let Some(saved) = filter_obj.get("is_saved").and_then(|v| v.as_bool()) {
+                search.is_saved = Some(saved);
+            }
+            if let Some(search_remote) = filter_obj.get("remote").and_then(|v| v.as_bool()) {
+                remote = search_remote;
+            }
+            if let Some(order) = filter_obj.get("sort").and_then(|v| v.as_str()) {
+                sort = Some(order.to_string());
+            }
+            if let Some(window) = filter_obj.get("time").and_then(|v| v.as_str()) {
+                time = Some(window.to_string());
+            }
+            if let Some(min) = filter_obj.get("min_minutes").and_then(|v| v.as_u64()) {
+                min_minutes = Some(min as u32);
+            }
+            if let Some(max) = filter_obj.get("max_minutes").and_then(|v| v.as_u64()) {
+                max_minutes = Some(max as u32);
+            }
+        }
+
+        // Without a cache only the providers are searched
+        let mut results = match self.cache {
+            Some(ref cache) => self.search_cache(cache, &search).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Search failed: {}", e),
+                    None::<()>,
+                )
+            })?,
+            None => Vec::new(),
+        };
+
+        // Providers need something to search for; a query of fields alone
+        // only searches the cache
+        if remote && search.needles().next().is_some() {
+            let defaults = search.search_options();
+            let options = SearchOptions {
+                limit: Some(PROVIDER_SEARCH_LIMIT),
+                sort,
+                time: time.or(defaults.time),
+                ..defaults
+            };
+            let cached: std::collections::HashSet<String> = results
+                .iter()
+                .map(|result| result.item.id.0.clone())
+                .collect();
+            let found = self.search_providers(&search, options).await;
+            let needles: Vec<&str> = search.needles().collect();
+            results.extend(
+                found
+                    .into_iter()
+                    .filter(|item| !cached.contains(item.id.as_str()) && search.matches(item))
+                    .map(|item| SearchResult::new(item, &needles)),
+            );
+        }
+
+        if min_minutes.is_some() || max_minutes.is_some() {
+            results.retain(|result| {
+                let minutes = reading_minutes(&result.item);
+                min_minutes.is_none_or(|min| minutes >= min)
+                    && max_minutes.is_none_or(|max| minutes <= max)
+            });
+        }
+
+        self.strip_for_bandwidth(results.iter_mut().map(|result| &mut result.item))
+            .await;
+        Ok(results)
+    }
+
+    async fn mark_item_read(&self, item_id: String) -> RpcResult<()> {
+        if let Some(ref cache) = self.cache {
+            let id = ItemId(item_id);
+            cache.mark_read(&id, true).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to mark item as read: {}", e),
+                    None::<()>,
+                )
+            })?;
+            self.record_interaction(cache, &id, InteractionKind::Opened);
+            self.publish_action(&id.0, "mark_read");
+            Ok(())
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn mark_item_unread(&self, item_id: String) -> RpcResult<()> {
+        if let Some(ref cache) = self.cache {
+            let id = ItemId(item_id);
+            cache.mark_read(&id, false).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to mark item as unread: {}", e),
+                    None::<()>,
+                )
+            })?;
+            self.publish_action(&id.0, "mark_unread");
+            Ok(())
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn archive_item(&self, item_id: String) -> RpcResult<()> {
+        if let Some(ref cache) = self.cache {
+            let id = ItemId(item_id);
+            // Archiving without reading counts against the stream and author
+            let unread = matches!(cache.get_item(&id), Ok(Some(ref item)) if !item.is_read);
+            cache.mark_archived(&id, true).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to archive item: {}", e),
+                    None::<()>,
+                )
+            })?;
+            if unread {
+                self.record_interaction(cache, &id, InteractionKind::Skipped);
+            }
+            self.publish_action(&id.0, "archive");
+            Ok(())
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn save_item(&self, item_id: String) -> RpcResult<()> {
+        if let Some(ref cache) = self.cache {
+            let id = ItemId(item_id);
+            cache.mark_starred(&id, true).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to save item: {}", e),
+                    None::<()>,
+                )
+            })?;
+            self.record_interaction(cache, &id, InteractionKind::Saved);
+            self.route_save(cache, &id).await?;
+            self.publish_action(&id.0, "save");
+            Ok(())
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn unsave_item(&self, item_id: String) -> RpcResult<()> {
+        if let Some(ref cache) = self.cache {
+            let id = ItemId(item_id);
+            cache.mark_starred(&id, false).map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to unsave item: {}", e),
+                    None::<()>,
+                )
+            })?;
+            self.publish_action(&id.0, "unsave");
+            Ok(())
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn pin_item(&self, item_id: String) -> RpcResult<()> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let exists = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .is_some();
+        if !exists {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item '{}' not found", id.as_str()),
+                None::<()>,
+            ));
+        }
+
+        cache.pin_item(&id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to pin item: {:#}", e),
+                None::<()>,
+            )
+        })?;
+        self.publish_action(&id.0, "pin");
+        Ok(())
+    }
+
+    async fn unpin_item(&self, item_id: String) -> RpcResult<bool> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let removed = cache.unpin_item(&id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to unpin item: {}", e),
+                None::<()>,
+            )
+        })?;
+        if removed {
+            self.publish_action(&id.0, "unpin");
+        }
+        Ok(removed)
+    }
+
+    async fn pinned_items(&self) -> RpcResult<Vec<Item>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let mut items = cache.get_pinned_items().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load pinned items: {}", e),
+                None::<()>,
+            )
+        })?;
+        self.strip_for_bandwidth(&mut items).await;
+        Ok(items)
+    }
+
+    async fn mark_spam(&self, item_id: String) -> RpcResult<()> {
+        self.judge_spam(item_id, true)
+    }
+
+    async fn mark_not_spam(&self, item_id: String) -> RpcResult<()> {
+        self.judge_spam(item_id, false)
+    }
+
+    async fn spam_verdicts(&self, limit: Option<u32>) -> RpcResult<Vec<SpamVerdict>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_spam_verdicts(limit.unwrap_or(100)).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load spam verdicts: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn respond_invite(&self, item_id: String, accept: bool) -> RpcResult<String> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+        if !CalendarInvite::from_item(&item).is_some_and(|invite| invite.is_request()) {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Item '{}' has no invitation to answer", id.as_str()),
+                None::<()>,
+            ));
+        }
+
+        let sync_manager = self.sync_manager.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let registry = sync_manager.read().await.get_registry().clone();
+        let provider_id = Self::extract_provider_id(id.as_str()).unwrap_or_default();
+        let provider = registry.get(provider_id).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32003,
+                format!("Provider '{}' not found", provider_id),
+                None::<()>,
+            )
+        })?;
+
+        let failed = |e: StreamError| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to answer invitation: {}", e),
+                None::<()>,
+            )
+        };
+        let action_id = if accept {
+            "accept_invite"
+        } else {
+            "decline_invite"
+        };
+        let action = registry
+            .guard(provider_id, provider.available_actions(&item))
+            .await
+            .map_err(failed)?
+            .into_iter()
+            .find(|action| action.id == action_id)
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Provider '{}' cannot answer invitations", provider_id),
+                    None::<()>,
+                )
+            })?;
+        let result = registry
+            .guard(provider_id, provider.execute_action(&item, &action))
+            .await;
+        let error = match &result {
+            Ok(result) if result.success => None,
+            Ok(result) => Some(
+                result
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| "Failed to answer invitation".to_string()),
+            ),
+            Err(e) => Some(e.to_string()),
+        };
+        self.record_audit(provider_id, Some(&id), action_id, error.as_deref());
+        let result = result.map_err(failed)?;
+        if !result.success {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                error.unwrap_or_default(),
+                None::<()>,
+            ));
+        }
+
+        self.publish_action(&id.0, action_id);
+        Ok(result.message.unwrap_or_default())
+    }
+
+    async fn send_to_notes(&self, item_id: String) -> RpcResult<String> {
+        let notes = self.notes.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Notes path not configured".to_string(),
+                None::<()>,
+            )
+        })?;
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let path = notes.append(&item).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to send to notes: {:#}", e),
+                None::<()>,
+            )
+        })?;
+        self.publish_action(&id.0, "send_to_notes");
+        Ok(path.display().to_string())
+    }
+
+    async fn share_targets(&self) -> RpcResult<Vec<ShareTargetInfo>> {
+        Ok(self.sharer.targets())
+    }
+
+    async fn share_item(&self, item_id: String, target: String) -> RpcResult<SharedItem> {
+        let share_target = self.sharer.get(&target).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Unknown share target '{}'", target),
+                None::<()>,
+            )
+        })?;
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let registry = match &self.sync_manager {
+            Some(sync_manager) => Some(sync_manager.read().await.get_registry().clone()),
+            None => None,
+        };
+        let shared = self
+            .sharer
+            .share(share_target, &item, registry.as_deref())
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to share to '{}': {:#}", target, e),
+                    None::<()>,
+                )
+            })?;
+        self.publish_action(&id.0, "share");
+        Ok(shared)
+    }
+
+    async fn summarize_item(&self, item_id: String) -> RpcResult<String> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let mut item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        if let Some(summary) = item.metadata.get(summarize::SUMMARY_KEY) {
+            return Ok(summary.clone());
+        }
+
+        let summarizer = self.summarizer.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Summarizer not configured".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        // Videos are summarized from their transcript when one is available
+        if Self::youtube_video_id(&item).is_some() {
+            match self.load_transcript(cache.as_ref(), &item).await {
+                Ok(transcript) => {
+                    item.metadata
+                        .insert(summarize::TRANSCRIPT_KEY.to_string(), transcript);
+                }
+                Err(e) => tracing::debug!(
+                    "Summarizing {} from its description: {}",
+                    id.as_str(),
+                    e.message()
+                ),
+            }
+        }
+
+        let config = &self.summarizer_config;
+        let text = summarize::summarizable_text(&item, config.min_words, config.max_input_chars)
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!(
+                        "Item '{}' is not a long article, email, or video description",
+                        id.as_str()
+                    ),
+                    None::<()>,
+                )
+            })?;
+
+        let summary = summarizer
+            .summarize(&item.title, &text)
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Summarization failed ({}): {:#}", summarizer.name(), e),
+                    None::<()>,
+                )
+            })?;
+
+        if let Err(e) = cache.set_item_metadata(&id, summarize::SUMMARY_KEY, &summary) {
+            tracing::warn!("Failed to cache summary for {}: {}", id.as_str(), e);
+        }
+        self.publish_action(&id.0, "summarize");
+        Ok(summary)
+    }
+
+    async fn get_transcript(&self, item_id: String) -> RpcResult<String> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        self.load_transcript(cache.as_ref(), &item).await
+    }
+
+    async fn open_item(&self, item_id: String) -> RpcResult<OpenedItem> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let opened = self
+            .opener
+            .open(&item)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(-32000, format!("{:#}", e), None::<()>)
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Item '{}' has no link to open", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        if let Err(e) = cache.mark_read(&id, true) {
+            tracing::warn!(
+                "Failed to mark opened item '{}' as read: {}",
+                id.as_str(),
+                e
+            );
+        }
+        self.record_interaction(cache, &id, InteractionKind::Opened);
+        self.publish_action(&id.0, "open");
+        Ok(opened)
+    }
+
+    async fn item_links(&self, item_id: String) -> RpcResult<Vec<ItemLink>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        Ok(links::extract(&item)
+            .into_iter()
+            .map(|link| ItemLink {
+                title: self.link_titles.cached(&link.url),
+                ..link
+            })
+            .collect())
+    }
+
+    async fn link_title(&self, item_id: String, url: String) -> RpcResult<Option<String>> {
+        let (item, link) = self.item_link(item_id, &url)?;
+        if !(link.url.starts_with("https://") || link.url.starts_with("http://")) {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Not an http or https URL: {}", link.url),
+                None::<()>,
+            ));
+        }
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        Ok(self.link_titles.title(provider_id, &link.url).await)
+    }
+
+    async fn open_link(&self, item_id: String, url: String) -> RpcResult<OpenedItem> {
+        let (item, link) = self.item_link(item_id, &url)?;
+        let opened = self.opener.open_link(&item, &link.url).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(-32000, format!("{:#}", e), None::<()>)
+        })?;
+
+        if let Some(ref cache) = self.cache {
+            self.record_interaction(cache, &item.id, InteractionKind::Opened);
+        }
+        self.publish_action(&item.id.0, "open_link");
+        Ok(opened)
+    }
+
+    async fn bookmark_link(&self, item_id: String, url: String) -> RpcResult<String> {
+        let (item, link) = self.item_link(item_id, &url)?;
+        let provider = self.bookmarks_provider().await?;
+        let bookmarks = provider
+            .as_any()
+            .downcast_ref::<BookmarksProvider>()
+            .expect("checked by bookmarks_provider");
+
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        let title = match self.link_titles.title(provider_id, &link.url).await {
+            Some(title) => title,
+            None => link.text.unwrap_or_else(|| link.url.clone()),
+        };
+        let description = Some(format!("Linked from \"{}\"", item.title));
+        let (bookmark, _) = bookmarks
+            .capture(title, link.url, None, description, Vec::new())
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to bookmark link: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        let bookmark_item = bookmarks.bookmark_to_item(&bookmark);
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.upsert_items(std::slice::from_ref(&bookmark_item)) {
+                tracing::warn!(
+                    "Failed to cache bookmark {}: {:#}",
+                    bookmark_item.id.as_str(),
+                    e
+                );
+            }
+        }
+        self.publish_action(&item.id.0, "bookmark_link");
+        Ok(bookmark_item.id.0)
+    }
+
+    async fn read_aloud(&self, item_id: String) -> RpcResult<usize> {
+        let speech = self.speech_queue()?;
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let text = speech.speech_text(&item).ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Item '{}' has no text to read", id.as_str()),
+                None::<()>,
+            )
+        })?;
+        let ahead = speech.enqueue(id.as_str(), text).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(-32000, e.to_string(), None::<()>)
+        })?;
+
+        self.publish_action(&id.0, "read_aloud");
+        Ok(ahead)
+    }
+
+    async fn stop_reading(&self) -> RpcResult<usize> {
+        Ok(self.speech_queue()?.stop())
+    }
+
+    async fn speech_status(&self) -> RpcResult<SpeechStatus> {
+        Ok(self.speech_queue()?.status())
+    }
+
+    async fn play_item(&self, item_id: String) -> RpcResult<Playback> {
+        let player = self.player()?;
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let playback = player
+            .play(&item, Arc::clone(cache), self.events.clone())
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(-32000, format!("{:#}", e), None::<()>)
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Item '{}' has no video or audio to play", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        self.record_interaction(cache, &id, InteractionKind::Opened);
+        self.publish_action(&id.0, "play");
+        Ok(playback)
+    }
+
+    async fn stop_playback(&self) -> RpcResult<bool> {
+        Ok(self.player()?.stop())
+    }
+
+    async fn player_status(&self) -> RpcResult<PlayerStatus> {
+        Ok(self.player()?.status())
+    }
+
+    async fn download_item(&self, item_id: String) -> RpcResult<Vec<Download>> {
+        let downloads = self.download_manager()?;
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let item = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Item '{}' not found", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        let mut queued = Vec::new();
+        if let Some(sync_manager) = &self.sync_manager {
+            let registry = sync_manager.read().await.get_registry().clone();
+            let provider_id = id.as_str().split(':').next().unwrap_or_default();
+            let provider = registry.get(provider_id);
+            if let Some(attachments) = provider.as_ref().and_then(|p| p.as_attachments()) {
+                let listed = registry
+                    .guard(provider_id, attachments.list_attachments(&item))
+                    .await
+                    .map_err(|e| {
+                        jsonrpsee::types::ErrorObjectOwned::owned(
+                            -32000,
+                            format!("Failed to list attachments: {}", e),
+                            None::<()>,
+                        )
+                    })?;
+                for attachment in &listed {
+                    queued.push(downloads.queue_attachment(
+                        &item,
+                        attachment,
+                        Arc::clone(&registry),
+                        self.events.clone(),
+                    ));
+                }
+            }
+        }
+        queued.extend(downloads.queue_link(&item, self.events.clone()));
+
+        if queued.is_empty() {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32004,
+                format!("Item '{}' has nothing to download", id.as_str()),
+                None::<()>,
+            ));
+        }
+        self.publish_action(&id.0, "download");
+        Ok(queued)
+    }
+
+    async fn list_downloads(&self) -> RpcResult<Vec<Download>> {
+        Ok(self.download_manager()?.list())
+    }
+
+    async fn cancel_download(&self, download_id: u64) -> RpcResult<bool> {
+        Ok(self.download_manager()?.cancel(download_id))
+    }
+
+    async fn retry_download(&self, download_id: u64) -> RpcResult<Download> {
+        self.download_manager()?
+            .retry(download_id, self.events.clone())
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Download {} not found", download_id),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn clear_downloads(&self) -> RpcResult<usize> {
+        Ok(self.download_manager()?.clear_finished())
+    }
+
+    async fn related_items(
+        &self,
+        item_id: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<RelatedItem>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let exists = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .is_some();
+        if !exists {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item '{}' not found", id.as_str()),
+                None::<()>,
+            ));
+        }
+
+        cache
+            .get_related(&id, limit.unwrap_or(DEFAULT_RELATED_LIMIT))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load related items: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn item_changes(
+        &self,
+        item_id: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<ItemChange>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache
+            .get_item_changes(&ItemId(item_id), limit.unwrap_or(DEFAULT_CHANGES_LIMIT))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item changes: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn acknowledge_changes(&self, item_id: String) -> RpcResult<bool> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let acknowledged = cache.acknowledge_changes(&id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to acknowledge item changes: {}", e),
+                None::<()>,
+            )
+        })?;
+        if acknowledged {
+            self.publish_action(&id.0, "acknowledge_changes");
+        }
+        Ok(acknowledged)
+    }
+
+    async fn tombstones(&self, limit: Option<u32>) -> RpcResult<Vec<Tombstone>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_tombstones(limit.unwrap_or(100)).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to list tombstones: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn restore_item(&self, item_id: String) -> RpcResult<bool> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let restored = cache.restore_item(&id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to restore item: {}", e),
+                None::<()>,
+            )
+        })?;
+        if restored {
+            self.publish_action(&id.0, "restore");
+        }
+        Ok(restored)
+    }
+
+    async fn remind_item(&self, item_id: String, remind_at: DateTime<Utc>) -> RpcResult<Reminder> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        if remind_at <= Utc::now() {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Reminder time {} is in the past", remind_at.to_rfc3339()),
+                None::<()>,
+            ));
+        }
+
+        let id = ItemId(item_id);
+        let exists = cache
+            .get_item(&id)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load item: {}", e),
+                    None::<()>,
+                )
+            })?
+            .is_some();
+        if !exists {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32002,
+                format!("Item '{}' not found", id.as_str()),
+                None::<()>,
+            ));
+        }
+
+        let reminder = cache
+            .set_reminder(&id, remind_at)
+            .and_then(|_| cache.get_reminders())
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to set reminder: {:#}", e),
+                    None::<()>,
+                )
+            })?
+            .into_iter()
+            .find(|reminder| reminder.item_id == id)
+            .ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Reminder on '{}' was not stored", id.as_str()),
+                    None::<()>,
+                )
+            })?;
+
+        self.publish_action(&id.0, "remind");
+        Ok(reminder)
+    }
+
+    async fn list_reminders(&self) -> RpcResult<Vec<Reminder>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_reminders().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load reminders: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn due_reminders(&self) -> RpcResult<Vec<Item>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        reminders::due_items(cache.as_ref(), Utc::now()).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load due reminders: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn dismiss_reminder(&self, item_id: String) -> RpcResult<bool> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let id = ItemId(item_id);
+        let removed = cache.remove_reminder(&id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to dismiss reminder: {}", e),
+                None::<()>,
+            )
+        })?;
+        if removed {
+            self.publish_action(&id.0, "dismiss_reminder");
+        }
+        Ok(removed)
+    }
+
+    async fn create_alert(
+        &self,
+        name: String,
+        query: String,
+        notify: Option<bool>,
+        interval_minutes: Option<u32>,
+    ) -> RpcResult<SavedSearch> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "Alert name is empty".to_string(),
+                None::<()>,
+            ));
+        }
+        SearchQuery::parse(&query).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Invalid search query: {}", e),
+                None::<()>,
+            )
+        })?;
+
+        let searches = cache.get_saved_searches().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load alerts: {}", e),
+                None::<()>,
+            )
+        })?;
+        if searches.iter().any(|search| search.name == name) {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                format!("Alert '{}' already exists", name),
+                None::<()>,
+            ));
+        }
+
+        cache
+            .create_saved_search(name, &query, notify.unwrap_or(true), interval_minutes)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to save alert: {:#}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn list_alerts(&self) -> RpcResult<Vec<SavedSearch>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_saved_searches().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load alerts: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn delete_alert(&self, search_id: i64) -> RpcResult<bool> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.delete_saved_search(search_id).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to delete alert: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn alert_items(
+        &self,
+        search_id: Option<i64>,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<Item>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let mut items = cache
+            .get_alert_items(search_id, limit.unwrap_or(100))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to load alert items: {}", e),
+                    None::<()>,
+                )
+            })?;
+        self.strip_for_bandwidth(&mut items).await;
+        Ok(items)
+    }
+
+    async fn complete_task(&self, item_id: String) -> RpcResult<Item> {
+        self.set_task_completed(item_id, true).await
+    }
+
+    async fn uncomplete_task(&self, item_id: String) -> RpcResult<Item> {
+        self.set_task_completed(item_id, false).await
+    }
+
+    async fn create_task(
+        &self,
+        provider_id: String,
+        list_id: String,
+        task: TaskDraft,
+    ) -> RpcResult<Item> {
+        let (registry, provider) = self.task_provider(&provider_id).await?;
+        let tasks = Self::tasks_of(provider.as_ref())?;
+
+        let created = registry
+            .guard(
+                provider.id(),
+                tasks.create_task(&CollectionId(list_id), task),
+            )
+            .await;
+        let item_id = created.as_ref().ok().map(|created| created.id.clone());
+        let created = self
+            .audited(provider.id(), item_id.as_ref(), "create_task", created)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to create task: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        let created = self.store_task(created);
+        self.publish_action(&created.id.0, "create_task");
+        Ok(created)
+    }
+
+    async fn update_task(&self, item_id: String, update: TaskUpdate) -> RpcResult<Item> {
+        let (task, registry, provider) = self.task_with_provider(item_id).await?;
+        let tasks = Self::tasks_of(provider.as_ref())?;
+
+        let updated = registry
+            .guard(provider.id(), tasks.update_task(&task, update))
+            .await;
+        let updated = self
+            .audited(provider.id(), Some(&task.id), "update_task", updated)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to update task: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        let updated = self.store_task(updated);
+        self.publish_action(&updated.id.0, "update_task");
+        Ok(updated)
+    }
+
+    async fn delete_task(&self, item_id: String) -> RpcResult<()> {
+        let (task, registry, provider) = self.task_with_provider(item_id).await?;
+        let tasks = Self::tasks_of(provider.as_ref())?;
+
+        let deleted = registry
+            .guard(provider.id(), tasks.delete_task(&task))
+            .await;
+        self.audited(provider.id(), Some(&task.id), "delete_task", deleted)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to delete task: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        if let Some(ref cache) = self.cache {
+            if let Err(e) = cache.delete_item(&task.id) {
+                tracing::warn!(
+                    "Failed to drop task {} from cache: {:#}",
+                    task.id.as_str(),
+                    e
+                );
+            }
+        }
+        self.publish_action(&task.id.0, "delete_task");
+        Ok(())
+    }
+
+    async fn set_task_due_date(
+        &self,
+        item_id: String,
+        due_date: Option<NaiveDate>,
+    ) -> RpcResult<Item> {
+        let (task, registry, provider) = self.task_with_provider(item_id).await?;
+        let tasks = Self::tasks_of(provider.as_ref())?;
+
+        let updated = registry
+            .guard(provider.id(), tasks.set_due_date(&task, due_date))
+            .await;
+        let updated = self
+            .audited(provider.id(), Some(&task.id), "set_due_date", updated)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to set due date: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        let updated = self.store_task(updated);
+        self.publish_action(&updated.id.0, "set_due_date");
+        Ok(updated)
+    }
+
+    async fn list_collections(&self) -> RpcResult<Vec<Collection>> {
+        use scryforge_provider_core::HasCollections;
+
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let registry = manager.get_registry();
+
+            let mut all_collections = Vec::new();
+
+            // Iterate through all providers and collect their collections
+            for provider_id in registry.list() {
+                if let Some(provider) = registry.get(provider_id) {
+                    // Check if provider supports collections
+                    if provider.capabilities().has_collections {
+                        // Downcast to HasCollections trait
+                        if let Some(collections_provider) = provider
+                            .as_any()
+                            .downcast_ref::<provider_dummy::DummyProvider>(
+                        ) {
+                            match registry
+                                .guard(provider_id, collections_provider.list_collections())
+                                .await
+                            {
+                                Ok(collections) => all_collections.extend(collections),
+                                Err(e) => {
+                                    tracing::warn!(
+                                        "Failed to list collections from provider {}: {}",
+                                        provider_id,
+                                        e
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            Ok(all_collections)
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn get_collection_items(&self, collection_id: String) -> RpcResult<Vec<Item>> {
+        use scryforge_provider_core::HasCollections;
+
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let registry = manager.get_registry();
+
+            // Extract provider ID from collection ID
+            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    "Invalid collection ID format".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+            let provider = registry.get(provider_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32003,
+                    format!("Provider '{}' not found", provider_id),
+                    None::<()>,
+                )
+            })?;
+
+            // Check if provider supports collections
+            if !provider.capabilities().has_collections {
+                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Provider '{}' does not support collections", provider_id),
+                    None::<()>,
+                ));
+            }
+
+            // Downcast to HasCollections trait
+            if let Some(collections_provider) = provider
+                .as_any()
+                .downcast_ref::<provider_dummy::DummyProvider>()
+            {
+                registry
+                    .guard(
+                        provider.id(),
+                        collections_provider.get_collection_items(&CollectionId(collection_id)),
+                    )
+                    .await
+                    .inspect(|items| registry.budgets().record_items(provider.id(), items))
+                    .map_err(|e| {
+                        jsonrpsee::types::ErrorObjectOwned::owned(
+                            -32000,
+                            format!("Failed to get collection items: {}", e),
+                            None::<()>,
+                        )
+                    })
+            } else {
+                Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32005,
+                    format!(
+                        "Provider '{}' does not implement HasCollections",
+                        provider_id
+                    ),
+                    None::<()>,
+                ))
+            }
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn add_to_collection(&self, collection_id: String, item_id: String) -> RpcResult<()> {
+        use scryforge_provider_core::HasCollections;
+
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let registry = manager.get_registry();
+
+            // Extract provider ID from collection ID
+            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    "Invalid collection ID format".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+            let provider = registry.get(provider_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32003,
+                    format!("Provider '{}' not found", provider_id),
+                    None::<()>,
+                )
+            })?;
+
+            // Check if provider supports collections
+            if !provider.capabilities().has_collections {
+                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Provider '{}' does not support collections", provider_id),
+                    None::<()>,
+                ));
+            }
+
+            // Downcast to HasCollections trait
+            if let Some(collections_provider) = provider
+                .as_any()
+                .downcast_ref::<provider_dummy::DummyProvider>()
+            {
+                let item = ItemId(item_id.clone());
+                let added = registry
+                    .guard(
+                        provider.id(),
+                        collections_provider.add_to_collection(&CollectionId(collection_id), &item),
+                    )
+                    .await;
+                self.audited(provider.id(), Some(&item), "add_to_collection", added)
+                    .map_err(|e| {
+                        jsonrpsee::types::ErrorObjectOwned::owned(
+                            -32000,
+                            format!("Failed to add item to collection: {}", e),
+                            None::<()>,
+                        )
+                    })?;
+                self.publish_action(&item_id, "add_to_collection");
+                Ok(())
+            } else {
+                Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32005,
+                    format!(
+                        "Provider '{}' does not implement HasCollections",
+                        provider_id
+                    ),
+                    None::<()>,
+                ))
+            }
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn remove_from_collection(
+        &self,
+        collection_id: String,
+        item_id: String,
+    ) -> RpcResult<()> {
+        use scryforge_provider_core::HasCollections;
+
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let registry = manager.get_registry();
+
+            // Extract provider ID from collection ID
+            let provider_id = Self::extract_provider_id(&collection_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    "Invalid collection ID format".to_string(),
+                    None::<()>,
+                )
+            })?;
+
+            let provider = registry.get(provider_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32003,
+                    format!("Provider '{}' not found", provider_id),
+                    None::<()>,
+                )
+            })?;
+
+            // Check if provider supports collections
+            if !provider.capabilities().has_collections {
+                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Provider '{}' does not support collections", provider_id),
+                    None::<()>,
+                ));
+            }
+
+            // Downcast to HasCollections trait
+            if let Some(collections_provider) = provider
+                .as_any()
+                .downcast_ref::<provider_dummy::DummyProvider>()
+            {
+                let item = ItemId(item_id.clone());
+                let removed = registry
+                    .guard(
+                        provider.id(),
+                        collections_provider
+                            .remove_from_collection(&CollectionId(collection_id), &item),
+                    )
+                    .await;
+                self.audited(
+                    provider.id(),
+                    Some(&item),
+                    "remove_from_collection",
+                    removed,
+                )
+                .map_err(|e| {
+                    jsonrpsee::types::ErrorObjectOwned::owned(
+                        -32000,
+                        format!("Failed to remove item from collection: {}", e),
+                        None::<()>,
+                    )
+                })?;
+                self.publish_action(&item_id, "remove_from_collection");
+                Ok(())
+            } else {
+                Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32005,
+                    format!(
+                        "Provider '{}' does not implement HasCollections",
+                        provider_id
+                    ),
+                    None::<()>,
+                ))
+            }
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn create_collection(&self, name: String) -> RpcResult<Collection> {
+        use scryforge_provider_core::HasCollections;
+
+        if let Some(ref sync_manager) = self.sync_manager {
+            let manager = sync_manager.read().await;
+            let registry = manager.get_registry();
+
+            // For now, create collection in the dummy provider
+            // In the future, this should accept a provider_id parameter
+            let provider_id = "dummy";
+
+            let provider = registry.get(provider_id).ok_or_else(|| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32003,
+                    format!("Provider '{}' not found", provider_id),
+                    None::<()>,
+                )
+            })?;
+
+            // Check if provider supports collections
+            if !provider.capabilities().has_collections {
+                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32004,
+                    format!("Provider '{}' does not support collections", provider_id),
+                    None::<()>,
+                ));
+            }
+
+            // Downcast to HasCollections trait
+            if let Some(collections_provider) = provider
+                .as_any()
+                .downcast_ref::<provider_dummy::DummyProvider>()
+            {
+                registry
+                    .guard(provider_id, collections_provider.create_collection(&name))
+                    .await
+                    .map_err(|e| {
+                        jsonrpsee::types::ErrorObjectOwned::owned(
+                            -32000,
+                            format!("Failed to create collection: {}", e),
+                            None::<()>,
+                        )
+                    })
+            } else {
+                Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32005,
+                    format!(
+                        "Provider '{}' does not implement HasCollections",
+                        provider_id
+                    ),
+                    None::<()>,
+                ))
+            }
+        } else {
+            Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Sync manager not available".to_string(),
+                None::<()>,
+            ))
+        }
+    }
+
+    async fn list_tags(&self, provider_id: String) -> RpcResult<Vec<TagCount>> {
+        let (registry, provider) = self.tag_provider(&provider_id).await?;
+        let tags = Self::tags_of(provider.as_ref())?;
+
+        registry
+            .guard(provider.id(), tags.list_tags())
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to list tags: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn tagged_items(&self, provider_id: String, tag: String) -> RpcResult<Vec<Item>> {
+        let (registry, provider) = self.tag_provider(&provider_id).await?;
+        let tags = Self::tags_of(provider.as_ref())?;
+
+        let mut items = registry
+            .guard(provider.id(), tags.tagged_items(&tag))
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to get items tagged '{}': {}", tag, e),
+                    None::<()>,
+                )
+            })?;
+        self.strip_for_bandwidth(&mut items).await;
+        Ok(items)
+    }
+
+    async fn rename_tag(&self, provider_id: String, from: String, to: String) -> RpcResult<u32> {
+        let (registry, provider) = self.tag_provider(&provider_id).await?;
+        let tags = Self::tags_of(provider.as_ref())?;
+
+        let renamed = registry
+            .guard(provider.id(), tags.rename_tag(&from, &to))
+            .await;
+        self.audited(provider.id(), None, "rename_tag", renamed)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to rename tag '{}': {}", from, e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn merge_tags(
+        &self,
+        provider_id: String,
+        sources: Vec<String>,
+        into: String,
+    ) -> RpcResult<u32> {
+        if sources.is_empty() {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "No tags to merge".to_string(),
+                None::<()>,
+            ));
+        }
+        let (registry, provider) = self.tag_provider(&provider_id).await?;
+        let tags = Self::tags_of(provider.as_ref())?;
+
+        let merged = registry
+            .guard(provider.id(), tags.merge_tags(&sources, &into))
+            .await;
+        self.audited(provider.id(), None, "merge_tags", merged)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to merge tags: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn delete_tag(&self, provider_id: String, tag: String) -> RpcResult<u32> {
+        let (registry, provider) = self.tag_provider(&provider_id).await?;
+        let tags = Self::tags_of(provider.as_ref())?;
+
+        let deleted = registry.guard(provider.id(), tags.delete_tag(&tag)).await;
+        self.audited(provider.id(), None, "delete_tag", deleted)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to delete tag '{}': {}", tag, e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn list_communities(&self, provider_id: String) -> RpcResult<Vec<Community>> {
+        let (registry, provider) = self.community_provider(&provider_id).await?;
+        let communities = Self::communities_of(provider.as_ref())?;
+
+        registry
+            .guard(provider.id(), communities.list_communities())
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to list communities: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn community_items(
+        &self,
+        provider_id: String,
+        community_id: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<Item>> {
+        let (registry, provider) = self.community_provider(&provider_id).await?;
+        let communities = Self::communities_of(provider.as_ref())?;
+
+        let low_bandwidth = match self.sync_manager {
+            Some(ref sync_manager) => sync_manager.read().await.bandwidth().is_low(),
+            None => false,
+        };
+        let options = FeedOptions {
+            limit: Some(limit.unwrap_or(DEFAULT_COMMUNITY_LIMIT)),
+            low_bandwidth,
+            ..Default::default()
+        };
+        let mut items = registry
+            .guard(
+                provider.id(),
+                communities.get_community_items(&CommunityId(community_id.clone()), options),
+            )
+            .await
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to get items of '{}': {}", community_id, e),
+                    None::<()>,
+                )
+            })?;
+        self.strip_for_bandwidth(&mut items).await;
+        Ok(items)
+    }
+
+    async fn join_community(&self, provider_id: String, community_id: String) -> RpcResult<()> {
+        let (registry, provider) = self.community_provider(&provider_id).await?;
+        let communities = Self::communities_of(provider.as_ref())?;
+
+        let id = CommunityId(community_id);
+        let joined = registry
+            .guard(provider.id(), communities.join_community(&id))
+            .await;
+        self.audited(provider.id(), None, "join_community", joined)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to join '{}': {}", id.0, e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn leave_community(&self, provider_id: String, community_id: String) -> RpcResult<()> {
+        let (registry, provider) = self.community_provider(&provider_id).await?;
+        let communities = Self::communities_of(provider.as_ref())?;
+
+        let id = CommunityId(community_id);
+        let left = registry
+            .guard(provider.id(), communities.leave_community(&id))
+            .await;
+        self.audited(provider.id(), None, "leave_community", left)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to leave '{}': {}", id.0, e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn bookmark_duplicates(&self) -> RpcResult<Vec<DuplicateGroup>> {
+        let provider = self.bookmarks_provider().await?;
+        let bookmarks = provider
+            .as_any()
+            .downcast_ref::<BookmarksProvider>()
+            .expect("checked by bookmarks_provider");
+        Ok(bookmarks.find_duplicates())
+    }
+
+    async fn merge_bookmarks(&self, keep: String, duplicates: Vec<String>) -> RpcResult<Item> {
+        let keep_id = Self::bookmark_id(&keep)?;
+        let duplicate_ids = duplicates
+            .iter()
+            .map(|id| Self::bookmark_id(id).map(str::to_string))
+            .collect::<RpcResult<Vec<String>>>()?;
+
+        let provider = self.bookmarks_provider().await?;
+        let bookmarks = provider
+            .as_any()
+            .downcast_ref::<BookmarksProvider>()
+            .expect("checked by bookmarks_provider");
+        let merged = bookmarks.merge_bookmarks(keep_id, &duplicate_ids);
+        let keep_item = ItemId(keep.clone());
+        let merged = self
+            .audited(provider.id(), Some(&keep_item), "merge_bookmarks", merged)
+            .map_err(|e| match e {
+                StreamError::ItemNotFound(id) => jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32002,
+                    format!("Bookmark '{}' not found", id),
+                    None::<()>,
+                ),
+                e => jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to merge bookmarks: {}", e),
+                    None::<()>,
+                ),
+            })?;
+        let item = bookmarks.bookmark_to_item(&merged);
+
+        // Drop the merged copies from the cache now rather than at the next sync
+        if let Some(ref cache) = self.cache {
+            for id in duplicates.iter().filter(|id| **id != keep) {
+                let id = ItemId(id.clone());
+                self.record_audit(provider.id(), Some(&id), "delete", None);
+                if let Err(e) = cache.delete_item(&id) {
+                    tracing::warn!("Failed to remove merged bookmark {}: {:#}", id.as_str(), e);
+                }
+            }
+            if let Err(e) = cache.upsert_items(std::slice::from_ref(&item)) {
+                tracing::warn!("Failed to cache bookmark {}: {:#}", item.id.as_str(), e);
+            }
+        }
+        Ok(item)
+    }
+
+    async fn api_version(&self) -> RpcResult<ApiVersionInfo> {
+        Ok(ApiVersionInfo {
+            api_version: API_VERSION.to_string(),
+            daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    async fn export_items(
+        &self,
+        source: ExportSource,
+        format: ExportFormat,
+        path: String,
+        overwrite: Option<bool>,
+    ) -> RpcResult<ExportSummary> {
+        let export_dir = self.export_dir.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Export not available".to_string(),
+                None::<()>,
+            )
+        })?;
+        let dest = export::resolve_destination(export_dir, &path, overwrite.unwrap_or(false))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(-32602, format!("{:#}", e), None::<()>)
+            })?;
+
+        let (title, items) = match source {
+            ExportSource::Stream { stream_id } => {
+                let cache = self.cache.as_ref().ok_or_else(|| {
+                    jsonrpsee::types::ErrorObjectOwned::owned(
+                        -32001,
+                        "Cache not available".to_string(),
+                        None::<()>,
+                    )
+                })?;
+                let items = cache
+                    .get_items(&StreamId(stream_id.clone()), None)
+                    .map_err(|e| {
+                        jsonrpsee::types::ErrorObjectOwned::owned(
+                            -32000,
+                            format!("Failed to load stream items: {}", e),
+                            None::<()>,
+                        )
+                    })?;
+                let title = cache
+                    .get_streams(None)
+                    .ok()
+                    .and_then(|streams| streams.into_iter().find(|s| s.id.0 == stream_id))
+                    .map_or(stream_id, |stream| stream.name);
+                (title, items)
+            }
+            ExportSource::Collection {
+                collection_id,
+                provider_id: Some(provider_id),
+            } => {
+                self.provider_collection(&provider_id, &collection_id)
+                    .await?
+            }
+            ExportSource::Collection {
+                collection_id,
+                provider_id: None,
+            } => {
+                let items =
+                    ScryforgeApiServer::get_collection_items(self, collection_id.clone()).await?;
+                (collection_id, items)
+            }
+            ExportSource::Search { query, filters } => {
+                let results = self.search_query(query.clone(), filters).await?;
+                let items = results.into_iter().map(|result| result.item).collect();
+                (format!("Search: {}", query), items)
+            }
+        };
+
+        export::export_items(&items, &title, format, &dest).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Export failed: {:#}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn generate_digest(&self, format: Option<DigestFormat>) -> RpcResult<String> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let since = Utc::now() - digest::window(self.digest.schedule);
+        let digest = DigestBuilder::new(cache.clone(), self.digest.items_per_provider)
+            .with_ranking_config(self.ranking.clone())
+            .with_mute_filter(self.mute.clone())
+            .build(since)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to build digest: {}", e),
+                    None::<()>,
+                )
+            })?;
+
+        Ok(digest.render(format.unwrap_or(self.digest.format)))
+    }
+
+    async fn cache_stats(&self) -> RpcResult<CacheStats> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_cache_stats().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to read cache stats: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn reading_stats(&self, days: Option<u32>) -> RpcResult<ReadingStats> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let days = days.unwrap_or(DEFAULT_STATS_DAYS);
+        if days == 0 {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "days must be at least 1".to_string(),
+                None::<()>,
+            ));
+        }
+
+        let since = Utc::now() - chrono::Duration::days(i64::from(days));
+        cache.get_reading_stats(since).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to read reading stats: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn start_focus(
+        &self,
+        stream_ids: Vec<String>,
+        minutes: Option<u32>,
+    ) -> RpcResult<FocusSession> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let minutes = minutes.unwrap_or(DEFAULT_FOCUS_MINUTES);
+        if stream_ids.is_empty() || minutes == 0 {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "A focus session needs at least one stream and one minute".to_string(),
+                None::<()>,
+            ));
+        }
+
+        self.focus
+            .start(stream_ids, minutes, Arc::clone(cache), self.events.clone())
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(-32000, e.to_string(), None::<()>)
+            })
+    }
+
+    async fn stop_focus(&self) -> RpcResult<Option<FocusReport>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        Ok(self.focus.stop(cache.as_ref(), self.events.as_ref()))
+    }
+
+    async fn focus_status(&self) -> RpcResult<FocusStatus> {
+        Ok(self.focus.status())
+    }
+
+    async fn start_triage(&self) -> RpcResult<TriageView> {
+        self.triage.reset();
+        self.triage_view()
+    }
+
+    async fn triage_next(&self) -> RpcResult<TriageView> {
+        self.triage_view()
+    }
+
+    async fn triage_archive(&self, item_id: String) -> RpcResult<TriageView> {
+        self.triage_apply(item_id, TriageAction::Archive, None)
+    }
+
+    async fn triage_save(&self, item_id: String) -> RpcResult<TriageView> {
+        self.triage_apply(item_id, TriageAction::Save, None)
+    }
+
+    async fn triage_snooze(
+        &self,
+        item_id: String,
+        until: Option<DateTime<Utc>>,
+    ) -> RpcResult<TriageView> {
+        if until.is_some_and(|until| until <= Utc::now()) {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "Snooze time must be in the future".to_string(),
+                None::<()>,
+            ));
+        }
+        self.triage_apply(item_id, TriageAction::Snooze, until)
+    }
+
+    async fn triage_skip(&self, item_id: String) -> RpcResult<TriageView> {
+        self.triage_apply(item_id, TriageAction::Skip, None)
+    }
+
+    async fn triage_undo(&self) -> RpcResult<TriageView> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        let step = self.triage.undo(cache.as_ref()).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to undo triage: {}", e),
+                None::<()>,
+            )
+        })?;
+        if let Some(step) = step {
+            self.publish_action(&step.item_id.0, "undo");
+        }
+        self.triage_view()
+    }
+
+    async fn reading_queue(&self) -> RpcResult<ReadingQueue> {
+        self.load_reading_queue()
+    }
+
+    async fn move_queued_item(&self, item_id: String, position: u32) -> RpcResult<ReadingQueue> {
+        if position == 0 {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32602,
+                "Queue positions start at 1".to_string(),
+                None::<()>,
+            ));
+        }
+        // Bring newly saved items in first so the position counts them
+        self.load_reading_queue()?;
+        self.change_queued_item(&item_id, |cache, id| {
+            cache.move_queued_item(id, position - 1)
+        })?;
+        self.load_reading_queue()
+    }
+
+    async fn queue_progress(&self, item_id: String, offset: u32) -> RpcResult<()> {
+        self.change_queued_item(&item_id, |cache, id| cache.set_queue_offset(id, offset))
+    }
+
+    async fn finish_queued_item(&self, item_id: String) -> RpcResult<ReadingQueue> {
+        self.change_queued_item(&item_id, |cache, id| cache.finish_queued_item(id))?;
+        self.publish_action(&item_id, "finish");
+        self.load_reading_queue()
+    }
+
+    async fn list_outbox(&self) -> RpcResult<Vec<OutboxEntry>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        cache.get_outbox().map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to list outbox: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn complete_contacts(
+        &self,
+        prefix: String,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<Contact>> {
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        self.contacts
+            .complete(cache.as_ref(), &prefix, limit.unwrap_or(10))
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32000,
+                    format!("Failed to complete contacts: {}", e),
+                    None::<()>,
+                )
+            })
+    }
+
+    async fn import_contacts(&self) -> RpcResult<usize> {
+        if !self.contacts.can_import() {
+            return Err(jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "No CardDAV address book configured".to_string(),
+                None::<()>,
+            ));
+        }
+        let cache = self.cache.as_ref().ok_or_else(|| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32001,
+                "Cache not available".to_string(),
+                None::<()>,
+            )
+        })?;
+
+        self.contacts.import(cache.as_ref()).await.map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to import contacts: {:#}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn discover(&self) -> RpcResult<JsonValue> {
+        serde_json::from_str(OPENRPC_SCHEMA).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                -32000,
+                format!("Failed to load API schema: {}", e),
+                None::<()>,
+            )
+        })
+    }
+
+    async fn subscribe_events(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let Some(ref events) = self.events else {
+            pending
+                .reject(jsonrpsee::types::ErrorObjectOwned::owned(
+                    -32001,
+                    "Event bus not available".to_string(),
+                    None::<()>,
+                ))
+                .await;
+            return Ok(());
+        };
+
+        // Subscribe before accepting so no events are missed in between
+        let mut rx = events.subscribe();
+        let sink = pending.accept().await?;
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => break,
+                event = rx.recv() => match event {
+                    Ok(event) => {
+                        let msg = SubscriptionMessage::from_json(&event)?;
+                        if sink.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event subscriber lagged, skipped {} event(s)", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_cache() -> anyhow::Result<SqliteCache> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("test.db");
+        let cache = SqliteCache::open_at(&path)?;
+        std::mem::forget(temp_dir);
+        Ok(cache)
+    }
+
+    fn create_test_item(id: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("test:stream:1".to_string()),
+            title: "Test Item".to_string(),
+            content: ItemContent::Text("Test content".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Task provider that records which operations it was asked to perform.
+    #[derive(Default)]
+    struct TaskProvider {
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl TaskProvider {
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl scryforge_provider_core::Provider for TaskProvider {
+        fn id(&self) -> &'static str {
+            "todo"
+        }
+
+        fn name(&self) -> &'static str {
+            "Todo"
+        }
+
+        async fn health_check(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ProviderHealth> {
+            unimplemented!()
+        }
+
+        async fn sync(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::SyncResult> {
+            unimplemented!()
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                has_collections: true,
+                ..Default::default()
+            }
+        }
+
+        async fn available_actions(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<scryforge_provider_core::Action>> {
+            Ok(vec![])
+        }
+
+        async fn execute_action(
+            &self,
+            _item: &Item,
+            _action: &scryforge_provider_core::Action,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ActionResult> {
+            unimplemented!()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_tasks(&self) -> Option<&dyn HasTasks> {
+            Some(self)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HasTasks for TaskProvider {
+        async fn complete_task(&self, task: &Item) -> scryforge_provider_core::Result<()> {
+            self.record(format!("complete {}", task.id.as_str()));
+            Ok(())
+        }
+
+        async fn uncomplete_task(&self, task: &Item) -> scryforge_provider_core::Result<()> {
+            self.record(format!("uncomplete {}", task.id.as_str()));
+            Ok(())
+        }
+
+        async fn create_task(
+            &self,
+            list_id: &CollectionId,
+            task: TaskDraft,
+        ) -> scryforge_provider_core::Result<Item> {
+            self.record(format!("create {} in {}", task.title, list_id.0));
+            let mut item = create_test_item("todo:new");
+            item.title = task.title;
+            item.content = ItemContent::Task {
+                body: task.body,
+                due_date: task.due_date,
+                is_completed: false,
+            };
+            Ok(item)
+        }
+
+        async fn update_task(
+            &self,
+            task: &Item,
+            update: TaskUpdate,
+        ) -> scryforge_provider_core::Result<Item> {
+            self.record(format!("update {}", task.id.as_str()));
+            let mut item = task.clone();
+            if let Some(title) = update.title {
+                item.title = title;
+            }
+            Ok(item)
+        }
+
+        async fn delete_task(&self, task: &Item) -> scryforge_provider_core::Result<()> {
+            self.record(format!("delete {}", task.id.as_str()));
+            Ok(())
+        }
+
+        async fn set_due_date(
+            &self,
+            task: &Item,
+            due_date: Option<NaiveDate>,
+        ) -> scryforge_provider_core::Result<Item> {
+            self.record(format!("due {}", task.id.as_str()));
+            let mut item = task.clone();
+            if let ItemContent::Task { due_date: due, .. } = &mut item.content {
+                *due = due_date;
+            }
+            Ok(item)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_task_methods() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut task = create_test_item("todo:task:1");
+        task.content = ItemContent::Task {
+            body: None,
+            due_date: None,
+            is_completed: false,
+        };
+        cache.upsert_items(&[task, create_test_item("web:item:1")])?;
+
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(TaskProvider::default());
+        registry.register(SearchableProvider);
+        let registry = Arc::new(registry);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            registry.clone(),
+            cache.clone(),
+        );
+        let api =
+            ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache.clone());
+
+        let completed = ScryforgeApiServer::complete_task(&api, "todo:task:1".to_string()).await?;
+        assert!(completed.is_read);
+        assert!(matches!(
+            completed.content,
+            ItemContent::Task {
+                is_completed: true,
+                ..
+            }
+        ));
+
+        let due = NaiveDate::from_ymd_opt(2026, 3, 1);
+        let scheduled =
+            ScryforgeApiServer::set_task_due_date(&api, "todo:task:1".to_string(), due).await?;
+        assert!(matches!(
+            scheduled.content,
+            ItemContent::Task { due_date, .. } if due_date == due
+        ));
+        // Local state survives the provider's copy replacing the cached one
+        assert!(scheduled.is_read);
+
+        let update = TaskUpdate {
+            title: Some("Renamed".to_string()),
+            body: None,
+        };
+        let renamed =
+            ScryforgeApiServer::update_task(&api, "todo:task:1".to_string(), update).await?;
+        assert_eq!(renamed.title, "Renamed");
+
+        let draft = TaskDraft {
+            title: "New".to_string(),
+            ..TaskDraft::default()
+        };
+        let created =
+            ScryforgeApiServer::create_task(&api, "todo".to_string(), "list-1".to_string(), draft)
+                .await?;
+        assert!(cache.get_item(&created.id)?.is_some());
+
+        ScryforgeApiServer::delete_task(&api, "todo:task:1".to_string()).await?;
+        assert!(cache
+            .get_item(&ItemId("todo:task:1".to_string()))?
+            .is_none());
+
+        let provider = registry.get("todo").unwrap();
+        let provider = provider.as_any().downcast_ref::<TaskProvider>().unwrap();
+        assert_eq!(
+            *provider.calls.lock().unwrap(),
+            vec![
+                "complete todo:task:1",
+                "due todo:task:1",
+                "update todo:task:1",
+                "create New in list-1",
+                "delete todo:task:1",
+            ]
+        );
+
+        // Every change made on the provider is in the audit log, newest first
+        let log = ScryforgeApiServer::audit_list(&api, Some("todo".to_string()), None).await?;
+        let logged: Vec<(&str, &str)> = log
+            .iter()
+            .map(|entry| {
+                let item_id = entry.item_id.as_ref().map_or("", |id| id.as_str());
+                (item_id, entry.action.as_str())
+            })
+            .collect();
+        assert_eq!(
+            logged,
+            vec![
+                ("todo:task:1", "delete_task"),
+                (created.id.as_str(), "create_task"),
+                ("todo:task:1", "update_task"),
+                ("todo:task:1", "set_due_date"),
+                ("todo:task:1", "complete"),
+            ]
+        );
+        assert!(log.iter().all(|entry| entry.success));
+        assert!(
+            ScryforgeApiServer::audit_list(&api, Some("web".to_string()), None)
+                .await?
+                .is_empty()
+        );
+
+        // Providers without task operations are refused
+        let err = ScryforgeApiServer::complete_task(&api, "web:item:1".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32004);
+        let err = ScryforgeApiServer::complete_task(&api, "todo:missing".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32002);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_community_methods_refuse_other_providers() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(TaskProvider::default());
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let err = ScryforgeApiServer::list_communities(&api, "todo".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32004);
+        let err =
+            ScryforgeApiServer::join_community(&api, "reddit".to_string(), "rust".to_string())
+                .await
+                .unwrap_err();
+        assert_eq!(err.code(), -32003);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tag_methods() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bookmarks =
+            provider_bookmarks::BookmarksProvider::with_path(temp_dir.path().join("b.json"))?;
+        for (title, url, tags) in [
+            ("Rust", "https://rust-lang.org", vec!["rust", "rustlang"]),
+            ("Cargo", "https://crates.io", vec!["rustlang", "tools"]),
+        ] {
+            bookmarks.add_bookmark(
+                title.to_string(),
+                url.to_string(),
+                None,
+                None,
+                tags.into_iter().map(String::from).collect(),
+            )?;
+        }
+
+        let cache = Arc::new(create_test_cache()?);
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(bookmarks);
+        registry.register(TaskProvider::default());
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let merged = ScryforgeApiServer::merge_tags(
+            &api,
+            "bookmarks".to_string(),
+            vec!["rustlang".to_string()],
+            "rust".to_string(),
+        )
+        .await?;
+        assert_eq!(merged, 2);
+        let renamed = ScryforgeApiServer::rename_tag(
+            &api,
+            "bookmarks".to_string(),
+            "tools".to_string(),
+            "tooling".to_string(),
+        )
+        .await?;
+        assert_eq!(renamed, 1);
+
+        let tags = ScryforgeApiServer::list_tags(&api, "bookmarks".to_string()).await?;
+        let names: Vec<(&str, u32)> = tags.iter().map(|t| (t.name.as_str(), t.count)).collect();
+        assert_eq!(names, vec![("rust", 2), ("tooling", 1)]);
+        let items =
+            ScryforgeApiServer::tagged_items(&api, "bookmarks".to_string(), "tooling".to_string())
+                .await?;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Cargo");
+
+        let deleted =
+            ScryforgeApiServer::delete_tag(&api, "bookmarks".to_string(), "rust".to_string())
+                .await?;
+        assert_eq!(deleted, 2);
+
+        // Providers without tags, and empty merges, are refused
+        let err = ScryforgeApiServer::list_tags(&api, "todo".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32004);
+        let err = ScryforgeApiServer::merge_tags(
+            &api,
+            "bookmarks".to_string(),
+            vec![],
+            "rust".to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bookmark_duplicates_and_merge() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bookmarks =
+            provider_bookmarks::BookmarksProvider::with_path(temp_dir.path().join("b.json"))?;
+        let mut ids = Vec::new();
+        for (title, url, tags) in [
+            ("Rust", "https://www.rust-lang.org/", vec!["rust"]),
+            (
+                "Rust Programming Language",
+                "http://rust-lang.org",
+                vec!["lang"],
+            ),
+            ("Cargo", "https://crates.io", vec![]),
+        ] {
+            let bookmark = bookmarks.add_bookmark(
+                title.to_string(),
+                url.to_string(),
+                None,
+                None,
+                tags.into_iter().map(String::from).collect(),
+            )?;
+            ids.push(format!("bookmarks:{}", bookmark.id));
+        }
+
+        let cache = Arc::new(create_test_cache()?);
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(bookmarks);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let groups = ScryforgeApiServer::bookmark_duplicates(&api).await?;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].reason,
+            provider_bookmarks::DuplicateReason::SameUrl
+        );
+        let group: Vec<&str> = groups[0].items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(group, vec![ids[0].as_str(), ids[1].as_str()]);
+
+        let merged =
+            ScryforgeApiServer::merge_bookmarks(&api, ids[0].clone(), vec![ids[1].clone()]).await?;
+        assert_eq!(merged.title, "Rust");
+        assert_eq!(merged.tags, vec!["rust".to_string(), "lang".to_string()]);
+        assert!(ScryforgeApiServer::bookmark_duplicates(&api)
+            .await?
+            .is_empty());
+
+        let err = ScryforgeApiServer::merge_bookmarks(&api, ids[0].clone(), vec![ids[1].clone()])
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32002);
+        let err =
+            ScryforgeApiServer::merge_bookmarks(&api, "rss:1".to_string(), vec![ids[2].clone()])
+                .await
+                .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        Ok(())
+    }
+
+    /// Provider whose search returns one new item and one already cached.
+    struct SearchableProvider;
+
+    #[async_trait::async_trait]
+    impl scryforge_provider_core::Provider for SearchableProvider {
+        fn id(&self) -> &'static str {
+            "web"
+        }
+
+        fn name(&self) -> &'static str {
+            "Web"
+        }
+
+        async fn health_check(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ProviderHealth> {
+            unimplemented!()
+        }
+
+        async fn sync(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::SyncResult> {
+            unimplemented!()
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                supports_search: true,
+                ..Default::default()
+            }
+        }
+
+        async fn available_actions(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<scryforge_provider_core::Action>> {
+            Ok(vec![])
+        }
+
+        async fn execute_action(
+            &self,
+            _item: &Item,
+            _action: &scryforge_provider_core::Action,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ActionResult> {
+            unimplemented!()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_search(&self) -> Option<&dyn scryforge_provider_core::HasSearch> {
+            Some(self)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl scryforge_provider_core::HasSearch for SearchableProvider {
+        async fn search(
+            &self,
+            _query: &str,
+            _options: SearchOptions,
+        ) -> scryforge_provider_core::Result<Vec<Item>> {
+            let mut remote = create_test_item("web:hit");
+            remote.stream_id = StreamId("web:feed:all".to_string());
+            Ok(vec![create_test_item("test:item:1"), remote])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_query_includes_provider_results() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(SearchableProvider);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let ids = |results: Vec<SearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.item.id.0).collect()
+        };
+
+        let items = ScryforgeApiServer::search_query(&api, "Test".to_string(), None).await?;
+        assert_eq!(items[0].title_highlights, vec![0..4]);
+        assert_eq!(items[0].snippet.as_deref(), Some("Test content"));
+        assert_eq!(ids(items), vec!["test:item:1", "web:hit"]);
+
+        let local_only = serde_json::json!({ "remote": false });
+        let items =
+            ScryforgeApiServer::search_query(&api, "Test".to_string(), Some(local_only)).await?;
+        assert_eq!(ids(items), vec!["test:item:1"]);
+
+        // A stream filter only searches the provider that owns the stream
+        let other_stream = serde_json::json!({ "stream_id": "test:stream:1" });
+        let items =
+            ScryforgeApiServer::search_query(&api, "Test".to_string(), Some(other_stream)).await?;
+        assert_eq!(ids(items), vec!["test:item:1"]);
+
+        let saved = serde_json::json!({ "is_saved": true });
+        let items = ScryforgeApiServer::search_query(&api, "Test".to_string(), Some(saved)).await?;
+        assert!(items.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_save_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        // Create stream first (required for foreign key constraint)
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+
+        // Create and insert a test item
+        let item = create_test_item("test:item:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        // Verify item is not saved initially
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(!items[0].is_saved);
+
+        // Save the item via RPC
+        let result = ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await;
+        assert!(result.is_ok());
+
+        // Verify item is now saved
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_saved);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reading_queue_methods() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut items: Vec<Item> = ["q:1", "q:2", "q:3"].map(create_test_item).into();
+        items[1].content = ItemContent::Text("word ".repeat(1000));
+        cache.upsert_items(&items)?;
+        for item in &items[..2] {
+            cache.mark_starred(&item.id, true)?;
+        }
+
+        let queue_ids = |queue: &ReadingQueue| -> Vec<String> {
+            queue.entries.iter().map(|e| e.item.id.0.clone()).collect()
+        };
+
+        let queue = ScryforgeApiServer::reading_queue(&api).await?;
+        assert_eq!(queue_ids(&queue), vec!["q:1", "q:2"]);
+        assert_eq!(queue.entries[1].minutes, 5);
+        assert_eq!(queue.total_minutes, 6);
+        assert_eq!(queue.resume, Some(ItemId("q:1".to_string())));
+
+        // A newly saved item can be moved before the listing has seen it
+        cache.mark_starred(&items[2].id, true)?;
+        let queue = ScryforgeApiServer::move_queued_item(&api, "q:3".to_string(), 1).await?;
+        assert_eq!(queue_ids(&queue), vec!["q:3", "q:1", "q:2"]);
+        assert_eq!(queue.entries[0].position, 1);
+
+        ScryforgeApiServer::queue_progress(&api, "q:2".to_string(), 12).await?;
+        let queue = ScryforgeApiServer::reading_queue(&api).await?;
+        assert_eq!(queue.resume, Some(ItemId("q:2".to_string())));
+        assert_eq!(queue.entries[2].offset, 12);
+
+        let queue = ScryforgeApiServer::finish_queued_item(&api, "q:3".to_string()).await?;
+        assert_eq!(queue_ids(&queue), vec!["q:1", "q:2"]);
+        assert!(cache.get_item(&items[2].id)?.unwrap().is_saved);
+
+        let err = ScryforgeApiServer::queue_progress(&api, "q:3".to_string(), 1)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32002);
+        let err = ScryforgeApiServer::move_queued_item(&api, "q:1".to_string(), 0)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsave_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        // Create stream first (required for foreign key constraint)
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+
+        // Create and insert a test item that's already saved
+        let mut item = create_test_item("test:item:1");
+        item.is_saved = true;
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        // Verify item is saved initially
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_saved);
+
+        // Unsave the item via RPC
+        let result = ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await;
+        assert!(result.is_ok());
+
+        // Verify item is now unsaved
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(!items[0].is_saved);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_save_item_publishes_action_event() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let api = ApiImpl::with_cache(cache.clone()).with_event_bus(events);
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await?;
+
+        match rx.try_recv()? {
+            DaemonEvent::ActionCompleted {
+                item_id, action, ..
+            } => {
+                assert_eq!(item_id, "test:item:1");
+                assert_eq!(action, "save");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_openrpc_schema_matches_registered_methods() {
+        let schema: JsonValue = serde_json::from_str(OPENRPC_SCHEMA).unwrap();
+        assert_eq!(schema["info"]["version"], API_VERSION);
+
+        let mut documented: Vec<String> = schema["methods"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["name"].as_str().unwrap().to_string())
+            .collect();
+        documented.sort();
+
+        let module = ApiImpl::<SqliteCache>::new().into_rpc();
+        let mut registered: Vec<String> = module.method_names().map(String::from).collect();
+        registered.sort();
+
+        assert_eq!(documented, registered);
+    }
+
+    #[tokio::test]
+    async fn test_api_version() {
+        let api = ApiImpl::<SqliteCache>::new();
+        let info = ScryforgeApiServer::api_version(&api).await.unwrap();
+        assert_eq!(info.api_version, API_VERSION);
+    }
+
+    #[tokio::test]
+    async fn test_save_item_without_cache() {
+        let api = ApiImpl::<SqliteCache>::new();
+
+        // Try to save without cache configured
+        let result = ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsave_item_without_cache() {
+        let api = ApiImpl::<SqliteCache>::new();
+
+        // Try to unsave without cache configured
+        let result = ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_methods() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(crate::registry::ProviderRegistry::new()),
+            cache.clone(),
+        );
+        let bandwidth = manager.bandwidth().clone();
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let status = ScryforgeApiServer::bandwidth_status(&api).await.unwrap();
+        assert!(!status.low_bandwidth);
+        assert_eq!(status.page_size, None);
+
+        let status = ScryforgeApiServer::set_bandwidth(&api, true).await.unwrap();
+        assert!(status.low_bandwidth);
+        assert!(!status.thumbnails);
+        assert_eq!(status.sync_interval_factor, 4);
+        // The sync tasks follow the same switch
+        assert!(bandwidth.is_low());
+
+        let api = ApiImpl::<SqliteCache>::new();
+        let err = ScryforgeApiServer::bandwidth_status(&api)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32001);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_conflicts_lists_resolved_conflicts() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut item = create_test_item("test:item:1");
+        cache.merge_items(std::slice::from_ref(&item), Default::default())?;
+
+        cache.mark_starred(&item.id, true)?;
+        cache.mark_starred(&item.id, false)?;
+        item.is_saved = true;
+        cache.merge_items(
+            std::slice::from_ref(&item),
+            crate::config::ConflictStrategy::RemoteWins,
+        )?;
+
+        let conflicts = ScryforgeApiServer::sync_conflicts(&api, None)
+            .await
+            .unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].item_id, item.id);
+        assert_eq!(conflicts[0].field, crate::cache::StateField::Saved);
+
+        let api = ApiImpl::<SqliteCache>::new();
+        assert!(ScryforgeApiServer::sync_conflicts(&api, Some(10))
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cache_stats() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        cache.upsert_items(&[
+            create_test_item("test:item:1"),
+            create_test_item("test:item:2"),
+        ])?;
+        cache.mark_starred(&ItemId("test:item:1".to_string()), true)?;
+
+        let stats = ScryforgeApiServer::cache_stats(&api).await.unwrap();
+        assert_eq!(stats.total_items, 2);
+        assert_eq!(stats.saved_items, 1);
+        assert_eq!(stats.streams.len(), 1);
+        assert!(stats.size_bytes > 0);
+
+        let api = ApiImpl::<SqliteCache>::new();
+        assert!(ScryforgeApiServer::cache_stats(&api).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reading_stats() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        cache.upsert_items(&[
+            create_test_item("test:item:1"),
+            create_test_item("test:item:2"),
+        ])?;
+        cache.mark_read(&ItemId("test:item:1".to_string()), true)?;
+
+        let stats = ScryforgeApiServer::reading_stats(&api, None).await.unwrap();
+        assert_eq!(stats.streams.len(), 1);
+        assert_eq!(stats.streams[0].received, 2);
+        assert_eq!(stats.streams[0].read, 1);
+        assert_eq!(stats.providers[0].provider_id, "test");
+
+        let err = ScryforgeApiServer::reading_stats(&api, Some(0))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        let api = ApiImpl::<SqliteCache>::new();
+        assert!(ScryforgeApiServer::reading_stats(&api, None).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_focus_session() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let err = ScryforgeApiServer::start_focus(&api, vec![], None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        let session =
+            ScryforgeApiServer::start_focus(&api, vec!["test:stream:1".to_string()], None)
+                .await
+                .unwrap();
+        assert_eq!(session.minutes, 25);
+        let err = ScryforgeApiServer::start_focus(&api, vec!["test:stream:1".to_string()], None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32000);
+        let status = ScryforgeApiServer::focus_status(&api).await.unwrap();
+        assert_eq!(status.session, Some(session));
+
+        let report = ScryforgeApiServer::stop_focus(&api).await.unwrap().unwrap();
+        assert!(!report.completed);
+        assert!(ScryforgeApiServer::stop_focus(&api)
+            .await
+            .unwrap()
+            .is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_triage() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        cache.upsert_items(&[
+            create_test_item("test:item:1"),
+            create_test_item("test:item:2"),
+        ])?;
+
+        let view = ScryforgeApiServer::start_triage(&api).await.unwrap();
+        assert_eq!(view.remaining, 2);
+        let first = view.item.unwrap().id.0;
+
+        let view = ScryforgeApiServer::triage_archive(&api, first.clone())
+            .await
+            .unwrap();
+        assert_eq!(view.remaining, 1);
+        assert_eq!(view.undo.unwrap().action, TriageAction::Archive);
+        let outbox = ScryforgeApiServer::list_outbox(&api).await.unwrap();
+        assert_eq!(outbox.len(), 2);
+        assert!(outbox.iter().all(|entry| entry.item_id.0 == first));
+
+        let view = ScryforgeApiServer::triage_undo(&api).await.unwrap();
+        assert_eq!(view.item.unwrap().id.0, first);
+        assert!(ScryforgeApiServer::list_outbox(&api)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let err = ScryforgeApiServer::triage_skip(&api, "test:item:404".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32002);
+        let past = Utc::now() - chrono::Duration::hours(1);
+        let err = ScryforgeApiServer::triage_snooze(&api, first, Some(past))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32602);
+
+        let api = ApiImpl::<SqliteCache>::new();
+        assert!(ScryforgeApiServer::triage_next(&api).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_toggle_save_multiple_times() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        // Create stream first (required for foreign key constraint)
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+
+        // Create and insert a test item
+        let item = create_test_item("test:item:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        // Save
+        ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await?;
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert!(items[0].is_saved);
+
+        // Unsave
+        ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await?;
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert!(!items[0].is_saved);
+
+        // Save again
+        ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await?;
+        let items = cache.get_items(&item.stream_id, None)?;
+        assert!(items[0].is_saved);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_stream_items() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let temp_dir = TempDir::new()?;
+        let api = ApiImpl::with_cache(cache.clone()).with_export_dir(temp_dir.path().to_path_buf());
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[
+            create_test_item("test:item:1"),
+            create_test_item("test:item:2"),
+        ])?;
+
+        let export = |path: &str, overwrite: Option<bool>| {
+            ScryforgeApiServer::export_items(
+                &api,
+                ExportSource::Stream {
+                    stream_id: "test:stream:1".to_string(),
+                },
+                ExportFormat::Ndjson,
+                path.to_string(),
+                overwrite,
+            )
+        };
+        let summary = export("export.ndjson", None).await?;
+
+        assert_eq!(summary.items_exported, 2);
+        let dest = temp_dir.path().join("export.ndjson");
+        assert_eq!(std::fs::read_to_string(&dest)?.lines().count(), 2);
+
+        // Paths stay inside the export directory, and files are kept unless
+        // replacing them is asked for
+        let outside = temp_dir.path().with_extension("ndjson");
+        let err = export(&outside.to_string_lossy(), Some(true))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32602);
+        assert!(!outside.exists());
+        let err = export("../escape.ndjson", Some(true)).await.unwrap_err();
+        assert_eq!(err.code(), -32602);
+        let err = export("export.ndjson", None).await.unwrap_err();
+        assert_eq!(err.code(), -32602);
+        export("export.ndjson", Some(true)).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_publish_bookmark_folder() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bookmarks =
+            provider_bookmarks::BookmarksProvider::with_path(temp_dir.path().join("b.json"))?;
+        let folder = bookmarks.add_folder("Rust Links".to_string(), None, None)?;
+        bookmarks.add_bookmark(
+            "The Book".to_string(),
+            "https://doc.rust-lang.org/book/".to_string(),
+            Some(folder.id.clone()),
+            Some("Learn Rust".to_string()),
+            vec!["rust".to_string()],
+        )?;
+
+        let cache = Arc::new(create_test_cache()?);
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(bookmarks);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache)
+            .with_export_dir(temp_dir.path().join("exports"));
+
+        let summary = ScryforgeApiServer::export_items(
+            &api,
+            ExportSource::Collection {
+                collection_id: folder.id.clone(),
+                provider_id: Some("bookmarks".to_string()),
+            },
+            ExportFormat::Rss,
+            "public/rust.xml".to_string(),
+            None,
+        )
+        .await?;
+        let dest = temp_dir.path().join("exports/public/rust.xml");
+        assert_eq!(summary.items_exported, 1);
+        let rss = std::fs::read_to_string(&dest)?;
+        assert!(rss.contains("<title>Rust Links</title>"));
+        assert!(rss.contains("<link>https://doc.rust-lang.org/book/</link>"));
+
+        let err = ScryforgeApiServer::export_items(
+            &api,
+            ExportSource::Collection {
+                collection_id: "missing".to_string(),
+                provider_id: Some("bookmarks".to_string()),
+            },
+            ExportFormat::Html,
+            "x.html".to_string(),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), -32002);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_stream_without_cache() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let api: ApiImpl<SqliteCache> =
+            ApiImpl::new().with_export_dir(temp_dir.path().to_path_buf());
+        let result = ScryforgeApiServer::export_items(
+            &api,
+            ExportSource::Stream {
+                stream_id: "test:stream:1".to_string(),
+            },
+            ExportFormat::Json,
+            "unused.json".to_string(),
+            None,
+        )
+        .await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_to_notes() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let temp_dir = TempDir::new()?;
+        let notes_path = temp_dir.path().join("inbox.md");
+        let notes = NotesWriter::from_config(&crate::config::NotesConfig {
+            path: Some(notes_path.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+        let api = ApiImpl::with_cache(cache.clone()).with_notes(notes);
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        let written = ScryforgeApiServer::send_to_notes(&api, "test:item:1".to_string()).await?;
+        assert_eq!(written, notes_path.display().to_string());
+        assert!(std::fs::read_to_string(&notes_path)?.contains("- Test Item\n"));
+
+        let missing = ScryforgeApiServer::send_to_notes(&api, "test:item:404".to_string()).await;
+        assert!(missing.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_share_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let temp_dir = TempDir::new()?;
+        let log_path = temp_dir.path().join("reading.md");
+        let sharer = Sharer::new(vec![
+            crate::config::ShareTarget {
+                id: "markdown".to_string(),
+                name: Some("Copy Markdown link".to_string()),
+                format: None,
+                kind: crate::config::ShareKind::Clipboard,
+            },
+            crate::config::ShareTarget {
+                id: "log".to_string(),
+                name: None,
+                format: Some("* {title}".to_string()),
+                kind: crate::config::ShareKind::File {
+                    path: log_path.clone(),
+                },
+            },
+        ]);
+        let api = ApiImpl::with_cache(cache.clone()).with_sharer(sharer);
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        let targets = ScryforgeApiServer::share_targets(&api).await?;
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].name, "Copy Markdown link");
+        assert_eq!(targets[1].kind, "file");
+
+        let copied =
+            ScryforgeApiServer::share_item(&api, "test:item:1".to_string(), "markdown".to_string())
+                .await?;
+        assert!(copied.copy);
+        assert!(copied.text.starts_with("[Test Item]("));
+
+        let logged =
+            ScryforgeApiServer::share_item(&api, "test:item:1".to_string(), "log".to_string())
+                .await?;
+        assert!(!logged.copy);
+        assert_eq!(std::fs::read_to_string(&log_path)?, "* Test Item\n");
+
+        let unknown =
+            ScryforgeApiServer::share_item(&api, "test:item:1".to_string(), "fax".to_string())
+                .await;
+        assert_eq!(unknown.unwrap_err().code(), -32602);
+        let missing =
+            ScryforgeApiServer::share_item(&api, "test:item:404".to_string(), "log".to_string())
+                .await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mark_spam() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        ScryforgeApiServer::mark_spam(&api, "test:item:1".to_string()).await?;
+        let outbox = cache.get_outbox()?;
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].action, OutboxAction::MarkSpam);
+        let verdicts = ScryforgeApiServer::spam_verdicts(&api, None).await?;
+        assert_eq!(verdicts.len(), 1);
+        assert!(verdicts[0].spam);
+        assert_eq!(verdicts[0].subject, "Test Item");
+
+        // Changing your mind before the move is sent cancels it
+        ScryforgeApiServer::mark_not_spam(&api, "test:item:1".to_string()).await?;
+        assert!(cache.get_outbox()?.is_empty());
+        assert!(!ScryforgeApiServer::spam_verdicts(&api, None).await?[0].spam);
+
+        let missing = ScryforgeApiServer::mark_spam(&api, "test:item:404".to_string()).await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        Ok(())
+    }
+
+    /// Mail provider that answers invitations, recording each answer.
+    #[derive(Default)]
+    struct InviteProvider {
+        answers: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl scryforge_provider_core::Provider for InviteProvider {
+        fn id(&self) -> &'static str {
+            "mail"
+        }
+
+        fn name(&self) -> &'static str {
+            "Mail"
+        }
+
+        async fn health_check(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ProviderHealth> {
+            unimplemented!()
+        }
+
+        async fn sync(
+            &self,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::SyncResult> {
+            unimplemented!()
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities::default()
+        }
+
+        async fn available_actions(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<scryforge_provider_core::Action>> {
+            let action = |id: &str| scryforge_provider_core::Action {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: String::new(),
+                kind: scryforge_provider_core::ActionKind::Custom(id.to_string()),
+                keyboard_shortcut: None,
+            };
+            Ok(vec![action("accept_invite"), action("decline_invite")])
+        }
+
+        async fn execute_action(
+            &self,
+            item: &Item,
+            action: &scryforge_provider_core::Action,
+        ) -> scryforge_provider_core::Result<scryforge_provider_core::ActionResult> {
+            self.answers
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", action.id, item.id.as_str()));
+            Ok(scryforge_provider_core::ActionResult {
+                success: true,
+                message: Some("Reply sent".to_string()),
+                data: None,
+            })
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_respond_invite() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        let invite = CalendarInvite {
+            method: "REQUEST".to_string(),
+            summary: "Planning".to_string(),
+            start: Some("2024-06-03 14:00 UTC".to_string()),
+            end: None,
+            location: None,
+            organizer: Some("alice@example.com".to_string()),
+            attendees: vec![],
+        };
+        let mut invitation = create_test_item("mail:invite:1");
+        invitation.metadata.insert(
+            scryforge_provider_core::INVITE_METADATA_KEY.to_string(),
+            serde_json::to_string(&invite)?,
+        );
+        let cancelled = CalendarInvite {
+            method: "CANCEL".to_string(),
+            ..invite
+        };
+        let mut cancellation = create_test_item("mail:invite:2");
+        cancellation.metadata.insert(
+            scryforge_provider_core::INVITE_METADATA_KEY.to_string(),
+            serde_json::to_string(&cancelled)?,
+        );
+        cache.upsert_items(&[invitation, cancellation, create_test_item("mail:plain:1")])?;
+
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(InviteProvider::default());
+        let registry = Arc::new(registry);
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            registry.clone(),
+            cache.clone(),
+        );
+        let api = ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache);
+
+        let message =
+            ScryforgeApiServer::respond_invite(&api, "mail:invite:1".to_string(), false).await?;
+        assert_eq!(message, "Reply sent");
+
+        // Only invitations that ask for a reply can be answered
+        for id in ["mail:invite:2", "mail:plain:1"] {
+            let err = ScryforgeApiServer::respond_invite(&api, id.to_string(), true)
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), -32004);
+        }
+        let missing =
+            ScryforgeApiServer::respond_invite(&api, "mail:invite:404".to_string(), true).await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        let provider = registry.get("mail").unwrap();
+        let provider = provider.as_any().downcast_ref::<InviteProvider>().unwrap();
+        assert_eq!(
+            *provider.answers.lock().unwrap(),
+            vec!["decline_invite mail:invite:1"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_complete_contacts() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let contacts = Contacts::from_config(&crate::config::ContactsConfig {
+            ignore: vec!["Me@Example.com".to_string()],
+            ..Default::default()
+        });
+        let api = ApiImpl::with_cache(cache.clone()).with_contacts(contacts);
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        let mut item = create_test_item("test:item:1");
+        item.content = ItemContent::Email {
+            subject: "Test Item".to_string(),
+            body_text: None,
+            body_html: None,
+            snippet: String::new(),
+        };
+        item.metadata.insert(
+            "to".to_string(),
+            "me@example.com, Mia Wong <mia@example.com>".to_string(),
+        );
+        cache.upsert_items(&[item])?;
+
+        let found = ScryforgeApiServer::complete_contacts(&api, "m".to_string(), None).await?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name.as_deref(), Some("Mia Wong"));
+        assert!(
+            ScryforgeApiServer::complete_contacts(&api, "wong".to_string(), Some(0))
+                .await?
+                .is_empty()
+        );
+
+        let import = ScryforgeApiServer::import_contacts(&api).await;
+        assert_eq!(import.unwrap_err().code(), -32001);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reminders() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        let remind_at = Utc::now() + chrono::Duration::hours(1);
+        let reminder =
+            ScryforgeApiServer::remind_item(&api, "test:item:1".to_string(), remind_at).await?;
+        assert_eq!(reminder.item_id.as_str(), "test:item:1");
+        assert_eq!(reminder.notified_at, None);
+        assert_eq!(ScryforgeApiServer::list_reminders(&api).await?.len(), 1);
+        assert!(ScryforgeApiServer::due_reminders(&api).await?.is_empty());
+
+        let past = ScryforgeApiServer::remind_item(
+            &api,
+            "test:item:1".to_string(),
+            Utc::now() - chrono::Duration::minutes(1),
+        )
+        .await;
+        assert_eq!(past.unwrap_err().code(), -32602);
+        let missing =
+            ScryforgeApiServer::remind_item(&api, "test:item:404".to_string(), remind_at).await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        // Once the time passes the item shows up in the due feed
+        cache.set_reminder(&reminder.item_id, Utc::now() - chrono::Duration::minutes(1))?;
+        let due = ScryforgeApiServer::due_reminders(&api).await?;
+        assert_eq!(due.len(), 1);
+        assert!(due[0].metadata.contains_key(reminders::REMIND_AT_KEY));
+
+        assert!(ScryforgeApiServer::dismiss_reminder(&api, "test:item:1".to_string()).await?);
+        assert!(!ScryforgeApiServer::dismiss_reminder(&api, "test:item:1".to_string()).await?);
+        assert!(ScryforgeApiServer::due_reminders(&api).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pinned_items() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        cache.upsert_items(&[create_test_item("test:item:1")])?;
+
+        ScryforgeApiServer::pin_item(&api, "test:item:1".to_string()).await?;
+        let pinned = ScryforgeApiServer::pinned_items(&api).await?;
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].id.as_str(), "test:item:1");
+        assert!(!pinned[0].is_saved);
+
+        let missing = ScryforgeApiServer::pin_item(&api, "test:item:404".to_string()).await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        assert!(ScryforgeApiServer::unpin_item(&api, "test:item:1".to_string()).await?);
+        assert!(!ScryforgeApiServer::unpin_item(&api, "test:item:1".to_string()).await?);
+        assert!(ScryforgeApiServer::pinned_items(&api).await?.is_empty());
+
+        let no_cache: ApiImpl<SqliteCache> = ApiImpl::new();
+        let err = ScryforgeApiServer::pinned_items(&no_cache)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), -32001);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_related_items() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let stream = scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream])?;
+        let mut article = create_test_item("test:item:1");
+        article.url = Some("https://example.com/article".to_string());
+        let mut thread = create_test_item("test:item:2");
+        thread.content = ItemContent::Text("Thoughts on https://example.com/article".to_string());
+        cache.upsert_items(&[article, thread, create_test_item("test:item:3")])?;
+
+        let related =
+            ScryforgeApiServer::related_items(&api, "test:item:1".to_string(), None).await?;
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].item.id.as_str(), "test:item:2");
+        assert_eq!(related[0].relation, crate::relations::Relation::LinkedFrom);
+        assert!(
+            ScryforgeApiServer::related_items(&api, "test:item:3".to_string(), None)
+                .await?
+                .is_empty()
+        );
+
+        let missing =
+            ScryforgeApiServer::related_items(&api, "test:item:404".to_string(), None).await;
+        assert_eq!(missing.unwrap_err().code(), -32002);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_to_notes_not_configured() {
+        let api: ApiImpl<SqliteCache> = ApiImpl::with_cache(Arc::new(create_test_cache().unwrap()));
+        let result = ScryforgeApiServer::send_to_notes(&api, "test:item:1".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unified_items_relevance_uses_interactions() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+
+        let streams: Vec<_> = ["test:stream:1", "test:stream:2"]
+            .iter()
+            .map(|id| scryforge_provider_core::Stream {
+                id: StreamId(id.to_string()),
+                name: id.to_string(),
+                provider_id: "test".to_string(),
+                stream_type: scryforge_provider_core::StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            })
+            .collect();
+        cache.upsert_streams(&streams)?;
+
+        let published = Utc::now();
+        let mut first = create_test_item("test:item:1");
+        first.published = Some(published);
+        let mut second = create_test_item("test:item:2");
+        second.stream_id = StreamId("test:stream:2".to_string());
+        second.published = Some(published);
+        cache.upsert_items(&[first, second])?;
+
+        // Saving an item from the second stream makes that stream preferred
+        ScryforgeApiServer::save_item(&api, "test:item:2".to_string()).await?;
+        ScryforgeApiServer::archive_item(&api, "test:item:1".to_string()).await?;
+
+        let stats = cache.get_interaction_stats()?;
+        assert_eq!(stats.by_stream["test:stream:1"].skipped, 1);
+        assert_eq!(stats.by_stream["test:stream:2"].saved, 1);
+
+        let options = UnifiedFeedOptions {
+            sort: crate::unified::FeedSortOrder::Relevance,
+            ..UnifiedFeedOptions::default()
+        };
+        let items = ScryforgeApiServer::unified_items(&api, Some(options)).await?;
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["test:item:2", "test:item:1"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unified_items_hides_muted() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let mute = MuteFilter::from_config(&crate::config::MuteConfig {
+            rules: crate::config::MuteRules {
+                keywords: vec!["spoiler".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let api = ApiImpl::with_cache(cache.clone()).with_mute_filter(mute);
+
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut muted = create_test_item("test:item:1");
+        muted.title = "Finale spoilers".to_string();
+        cache.upsert_items(&[muted, create_test_item("test:item:2")])?;
+
+        let items = ScryforgeApiServer::unified_items(&api, None).await?;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id.as_str(), "test:item:2");
+
+        let options = UnifiedFeedOptions {
+            muted_only: true,
+            ..UnifiedFeedOptions::default()
+        };
+        let items = ScryforgeApiServer::unified_items(&api, Some(options)).await?;
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0]
+                .metadata
+                .get(crate::mute::MUTED_REASON_KEY)
+                .unwrap(),
+            "keyword \"spoiler\""
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_items_by_reading_time() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        let api = ApiImpl::with_cache(cache.clone());
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut items: Vec<Item> = ["test:item:1", "test:item:2", "test:item:3"]
+            .map(create_test_item)
+            .into();
+        for (item, words) in items.iter_mut().zip([2000, 100, 700]) {
+            item.title = "Reading time".to_string();
+            item.content = ItemContent::Text("word ".repeat(words));
+        }
+        cache.upsert_items(&items)?;
+
+        let ids = |items: &[Item]| -> Vec<String> {
+            items.iter().map(|item| item.id.0.clone()).collect()
+        };
+
+        let options = UnifiedFeedOptions {
+            sort: crate::unified::FeedSortOrder::ShortestFirst,
+            ..UnifiedFeedOptions::default()
+        };
+        let unified = ScryforgeApiServer::unified_items(&api, Some(options)).await?;
+        assert_eq!(
+            ids(&unified),
+            vec!["test:item:2", "test:item:3", "test:item:1"]
+        );
+        assert_eq!(
+            unified[0]
+                .metadata
+                .get(crate::reading_time::READING_MINUTES_KEY)
+                .unwrap(),
+            "1"
+        );
+
+        let options = UnifiedFeedOptions {
+            sort: crate::unified::FeedSortOrder::LongestFirst,
+            max_minutes: Some(5),
+            ..UnifiedFeedOptions::default()
+        };
+        let unified = ScryforgeApiServer::unified_items(&api, Some(options)).await?;
+        assert_eq!(ids(&unified), vec!["test:item:3", "test:item:2"]);
+
+        let filters = serde_json::json!({ "remote": false, "min_minutes": 4 });
+        let found =
+            ScryforgeApiServer::search_query(&api, "Reading".to_string(), Some(filters)).await?;
+        let mut found: Vec<Item> = found.into_iter().map(|result| result.item).collect();
+        found.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+        assert_eq!(ids(&found), vec!["test:item:1", "test:item:3"]);
+
+        Ok(())
+    }
+
+    struct StubSummarizer;
+
+    #[async_trait::async_trait]
+    impl Summarizer for StubSummarizer {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        async fn summarize(&self, title: &str, _text: &str) -> anyhow::Result<String> {
+            Ok(format!("Summary of {}", title))
         }
     }
 
-    async fn create_collection(&self, name: String) -> RpcResult<Collection> {
-        use scryforge_provider_core::HasCollections;
+    #[tokio::test]
+    async fn test_summarize_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut article = create_test_item("test:item:1");
+        article.content = ItemContent::Article {
+            summary: None,
+            full_content: Some("word ".repeat(200)),
+        };
+        cache.upsert_items(&[article, create_test_item("test:item:2")])?;
 
-        if let Some(ref sync_manager) = self.sync_manager {
-            let manager = sync_manager.read().await;
-            let registry = manager.get_registry();
+        // Without a backend nothing can be summarized
+        let api = ApiImpl::with_cache(cache.clone());
+        let result = ScryforgeApiServer::summarize_item(&api, "test:item:1".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32001);
 
-            // For now, create collection in the dummy provider
-            // In the future, this should accept a provider_id parameter
-            let provider_id = "dummy";
+        let api = ApiImpl::with_cache(cache.clone())
+            .with_summarizer(Arc::new(StubSummarizer), SummarizerConfig::default());
+        let summary = ScryforgeApiServer::summarize_item(&api, "test:item:1".to_string()).await?;
+        assert_eq!(summary, "Summary of Test Item");
 
-            let provider = registry.get(provider_id).ok_or_else(|| {
-                jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32003,
-                    format!("Provider '{}' not found", provider_id),
-                    None::<()>,
-                )
-            })?;
+        let stored = cache.get_item(&ItemId("test:item:1".to_string()))?.unwrap();
+        assert_eq!(
+            stored.metadata.get(summarize::SUMMARY_KEY).unwrap(),
+            "Summary of Test Item"
+        );
 
-            // Check if provider supports collections
-            if !provider.capabilities().has_collections {
-                return Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32004,
-                    format!("Provider '{}' does not support collections", provider_id),
-                    None::<()>,
-                ));
-            }
+        // Short plain-text items are rejected
+        let result = ScryforgeApiServer::summarize_item(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
 
-            // Downcast to HasCollections trait
-            if let Some(collections_provider) = provider
-                .as_any()
-                .downcast_ref::<provider_dummy::DummyProvider>()
-            {
-                collections_provider
-                    .create_collection(&name)
-                    .await
-                    .map_err(|e| {
-                        jsonrpsee::types::ErrorObjectOwned::owned(
-                            -32000,
-                            format!("Failed to create collection: {}", e),
-                            None::<()>,
-                        )
-                    })
-            } else {
-                Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                    -32005,
-                    format!(
-                        "Provider '{}' does not implement HasCollections",
-                        provider_id
-                    ),
-                    None::<()>,
-                ))
-            }
-        } else {
-            Err(jsonrpsee::types::ErrorObjectOwned::owned(
-                -32001,
-                "Sync manager not available".to_string(),
-                None::<()>,
-            ))
-        }
+        // Cached summaries are returned even once the backend is gone
+        let api = ApiImpl::with_cache(cache.clone());
+        let summary = ScryforgeApiServer::summarize_item(&api, "test:item:1".to_string()).await?;
+        assert_eq!(summary, "Summary of Test Item");
+        Ok(())
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[tokio::test]
+    async fn test_get_transcript() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "youtube".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut video = create_test_item("youtube:vid1");
+        video.content = ItemContent::Video {
+            description: "Links below".to_string(),
+            duration_seconds: Some(600),
+            view_count: None,
+        };
+        cache.upsert_items(&[video, create_test_item("test:item:2")])?;
+        let api = ApiImpl::with_cache(cache.clone());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cache::SqliteCache;
-    use std::collections::HashMap;
-    use tempfile::TempDir;
+        let result = ScryforgeApiServer::get_transcript(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
+        let result = ScryforgeApiServer::get_transcript(&api, "missing".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32002);
 
-    fn create_test_cache() -> anyhow::Result<SqliteCache> {
-        let temp_dir = TempDir::new()?;
-        let path = temp_dir.path().join("test.db");
-        let cache = SqliteCache::open_at(&path)?;
-        std::mem::forget(temp_dir);
-        Ok(cache)
+        // Fetching needs the YouTube provider
+        let result = ScryforgeApiServer::get_transcript(&api, "youtube:vid1".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32001);
+
+        let id = ItemId("youtube:vid1".to_string());
+        cache.set_item_metadata(&id, summarize::TRANSCRIPT_KEY, "Hello\nand welcome")?;
+        let transcript =
+            ScryforgeApiServer::get_transcript(&api, "youtube:vid1".to_string()).await?;
+        assert_eq!(transcript, "Hello\nand welcome");
+        Ok(())
     }
 
-    fn create_test_item(id: &str) -> Item {
-        Item {
-            id: ItemId(id.to_string()),
-            stream_id: StreamId("test:stream:1".to_string()),
-            title: "Test Item".to_string(),
-            content: ItemContent::Text("Test content".to_string()),
-            author: None,
-            published: None,
-            updated: None,
-            url: None,
-            thumbnail_url: None,
-            is_read: false,
-            is_saved: false,
-            tags: vec![],
+    #[tokio::test]
+    async fn test_open_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
             metadata: HashMap::new(),
-        }
+        }])?;
+        let mut linked = create_test_item("test:item:1");
+        linked.url = Some("https://example.com/paper.pdf".to_string());
+        cache.upsert_items(&[linked, create_test_item("test:item:2")])?;
+
+        let opener = Opener::from_config(&crate::config::OpenerConfig {
+            commands: crate::config::OpenerCommands {
+                pdf: Some("true".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let api = ApiImpl::with_cache(cache.clone()).with_opener(opener);
+
+        let opened = ScryforgeApiServer::open_item(&api, "test:item:1".to_string()).await?;
+        assert_eq!(opened.kind, crate::opener::ContentKind::Pdf);
+        assert_eq!(opened.command, "true");
+        assert!(
+            cache
+                .get_item(&ItemId("test:item:1".to_string()))?
+                .unwrap()
+                .is_read
+        );
+
+        let result = ScryforgeApiServer::open_item(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
+        let result = ScryforgeApiServer::open_item(&api, "test:missing".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32002);
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_save_item() -> anyhow::Result<()> {
-        let cache = Arc::new(create_test_cache()?);
-        let api = ApiImpl::with_cache(cache.clone());
+    async fn test_item_links() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bookmarks =
+            provider_bookmarks::BookmarksProvider::with_path(temp_dir.path().join("b.json"))?;
+        let mut registry = crate::registry::ProviderRegistry::new();
+        registry.register(bookmarks);
 
-        // Create stream first (required for foreign key constraint)
-        let stream = scryforge_provider_core::Stream {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
             id: StreamId("test:stream:1".to_string()),
             name: "Test Stream".to_string(),
             provider_id: "test".to_string(),
@@ -940,37 +6667,144 @@ mod tests {
             total_count: None,
             last_updated: None,
             metadata: HashMap::new(),
-        };
-        cache.upsert_streams(&[stream])?;
-
-        // Create and insert a test item
-        let item = create_test_item("test:item:1");
-        cache.upsert_items(std::slice::from_ref(&item))?;
+        }])?;
+        // Nothing listens on port 9, so fetching titles fails fast
+        let mut newsletter = create_test_item("test:item:1");
+        newsletter.title = "Weekly".to_string();
+        newsletter.content = ItemContent::Html(
+            "<a href=\"http://127.0.0.1:9/paper.pdf\">The paper</a> and http://127.0.0.1:9/notes"
+                .to_string(),
+        );
+        cache.upsert_items(&[newsletter])?;
 
-        // Verify item is not saved initially
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(!items[0].is_saved);
+        let opener = Opener::from_config(&crate::config::OpenerConfig {
+            commands: crate::config::OpenerCommands {
+                pdf: Some("true".to_string()),
+                default: Some("true".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let manager = SyncManager::new(
+            crate::config::Config::default(),
+            Arc::new(registry),
+            cache.clone(),
+        );
+        let api =
+            ApiImpl::with_sync_manager_and_cache(Arc::new(RwLock::new(manager)), cache.clone())
+                .with_opener(opener);
 
-        // Save the item via RPC
-        let result = ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await;
-        assert!(result.is_ok());
+        let links = ScryforgeApiServer::item_links(&api, "test:item:1".to_string()).await?;
+        let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["http://127.0.0.1:9/paper.pdf", "http://127.0.0.1:9/notes"]
+        );
+        assert_eq!(links[0].text.as_deref(), Some("The paper"));
+        assert_eq!(
+            ScryforgeApiServer::link_title(
+                &api,
+                "test:item:1".to_string(),
+                "http://127.0.0.1:9/notes".to_string()
+            )
+            .await?,
+            None
+        );
+        // Only links in the item are fetched
+        let result = ScryforgeApiServer::link_title(
+            &api,
+            "test:item:1".to_string(),
+            "http://169.254.169.254/latest/meta-data/".to_string(),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), -32602);
 
-        // Verify item is now saved
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(items[0].is_saved);
+        let opened = ScryforgeApiServer::open_link(
+            &api,
+            "test:item:1".to_string(),
+            "http://127.0.0.1:9/paper.pdf".to_string(),
+        )
+        .await?;
+        assert_eq!(opened.kind, crate::opener::ContentKind::Pdf);
+        // Only links in the item are opened
+        let result = ScryforgeApiServer::open_link(
+            &api,
+            "test:item:1".to_string(),
+            "https://elsewhere.example.com/".to_string(),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().code(), -32602);
 
+        // Without a fetched title, the link text names the bookmark
+        let bookmark_id = ScryforgeApiServer::bookmark_link(
+            &api,
+            "test:item:1".to_string(),
+            "http://127.0.0.1:9/paper.pdf".to_string(),
+        )
+        .await?;
+        assert!(bookmark_id.starts_with("bookmarks:"));
+        let stored = std::fs::read_to_string(temp_dir.path().join("b.json"))?;
+        assert!(stored.contains("\"The paper\""));
+        assert!(stored.contains("Linked from \\\"Weekly\\\""));
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_unsave_item() -> anyhow::Result<()> {
+    async fn test_play_item() -> anyhow::Result<()> {
         let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut video = create_test_item("test:item:1");
+        video.url = Some("https://youtu.be/abc".to_string());
+        cache.upsert_items(&[video, create_test_item("test:item:2")])?;
+
         let api = ApiImpl::with_cache(cache.clone());
+        let result = ScryforgeApiServer::play_item(&api, "test:item:1".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32001);
 
-        // Create stream first (required for foreign key constraint)
-        let stream = scryforge_provider_core::Stream {
+        let player = Player::from_config(&crate::config::PlayerConfig {
+            command: "true".to_string(),
+            ..Default::default()
+        });
+        let api = api.with_player(player);
+        let playback = ScryforgeApiServer::play_item(&api, "test:item:1".to_string()).await?;
+        assert_eq!(playback.kind, crate::opener::ContentKind::Video);
+        assert_eq!(playback.start_secs, 0);
+
+        let result = ScryforgeApiServer::play_item(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
+        let result = ScryforgeApiServer::play_item(&api, "test:missing".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32002);
+
+        for _ in 0..200 {
+            if ScryforgeApiServer::player_status(&api)
+                .await?
+                .playing
+                .is_none()
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let status = ScryforgeApiServer::player_status(&api).await?;
+        assert_eq!(status.last.unwrap().exit_code, Some(0));
+        assert!(!ScryforgeApiServer::stop_playback(&api).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_item() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
             id: StreamId("test:stream:1".to_string()),
             name: "Test Stream".to_string(),
             provider_id: "test".to_string(),
@@ -980,56 +6814,103 @@ mod tests {
             total_count: None,
             last_updated: None,
             metadata: HashMap::new(),
-        };
-        cache.upsert_streams(&[stream])?;
+        }])?;
+        let mut video = create_test_item("test:item:1");
+        video.url = Some("https://youtu.be/abc".to_string());
+        cache.upsert_items(&[video, create_test_item("test:item:2")])?;
 
-        // Create and insert a test item that's already saved
-        let mut item = create_test_item("test:item:1");
-        item.is_saved = true;
-        cache.upsert_items(std::slice::from_ref(&item))?;
+        let api = ApiImpl::with_cache(cache.clone());
+        let result = ScryforgeApiServer::download_item(&api, "test:item:1".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32001);
 
-        // Verify item is saved initially
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(items[0].is_saved);
+        let temp_dir = tempfile::TempDir::new()?;
+        let downloads = DownloadManager::from_config(
+            &crate::config::DownloadsConfig {
+                ytdl_command: "false".to_string(),
+                ..Default::default()
+            },
+            temp_dir.path().to_path_buf(),
+        );
+        let api = api.with_download_manager(downloads);
+        let queued = ScryforgeApiServer::download_item(&api, "test:item:1".to_string()).await?;
+        assert_eq!(queued.len(), 1);
+        assert_eq!(
+            queued[0].source,
+            crate::downloads::DownloadSource::Ytdlp {
+                url: "https://youtu.be/abc".to_string()
+            }
+        );
 
-        // Unsave the item via RPC
-        let result = ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await;
-        assert!(result.is_ok());
+        let result = ScryforgeApiServer::download_item(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
+        let result = ScryforgeApiServer::download_item(&api, "test:missing".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32002);
 
-        // Verify item is now unsaved
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(!items[0].is_saved);
+        let id = queued[0].id;
+        for _ in 0..200 {
+            if ScryforgeApiServer::list_downloads(&api).await?[0]
+                .state
+                .is_finished()
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let listed = ScryforgeApiServer::list_downloads(&api).await?;
+        assert_eq!(listed[0].state, crate::downloads::DownloadState::Failed);
+        assert!(!ScryforgeApiServer::cancel_download(&api, id).await?);
 
+        let retried = ScryforgeApiServer::retry_download(&api, id).await?;
+        assert_eq!(retried.state, crate::downloads::DownloadState::Queued);
+        let result = ScryforgeApiServer::retry_download(&api, 999).await;
+        assert_eq!(result.unwrap_err().code(), -32002);
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_save_item_without_cache() {
-        let api = ApiImpl::<SqliteCache>::new();
+    async fn test_read_aloud() -> anyhow::Result<()> {
+        let cache = Arc::new(create_test_cache()?);
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
+            id: StreamId("test:stream:1".to_string()),
+            name: "Test Stream".to_string(),
+            provider_id: "test".to_string(),
+            stream_type: scryforge_provider_core::StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let mut empty = create_test_item("test:item:2");
+        empty.content = ItemContent::Generic { body: None };
+        cache.upsert_items(&[create_test_item("test:item:1"), empty])?;
 
-        // Try to save without cache configured
-        let result = ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await;
-        assert!(result.is_err());
-    }
+        let api = ApiImpl::with_cache(cache.clone());
+        let result = ScryforgeApiServer::read_aloud(&api, "test:item:1".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32001);
 
-    #[tokio::test]
-    async fn test_unsave_item_without_cache() {
-        let api = ApiImpl::<SqliteCache>::new();
+        let speech = SpeechQueue::from_config(&crate::config::TtsConfig {
+            command: Some("sleep 30".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let api = ApiImpl::with_cache(cache.clone()).with_speech_queue(speech);
 
-        // Try to unsave without cache configured
-        let result = ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await;
-        assert!(result.is_err());
+        let ahead = ScryforgeApiServer::read_aloud(&api, "test:item:1".to_string()).await?;
+        assert_eq!(ahead, 0);
+        let result = ScryforgeApiServer::read_aloud(&api, "test:item:2".to_string()).await;
+        assert_eq!(result.unwrap_err().code(), -32004);
+
+        assert_eq!(ScryforgeApiServer::stop_reading(&api).await?, 1);
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_toggle_save_multiple_times() -> anyhow::Result<()> {
+    async fn test_generate_digest() -> anyhow::Result<()> {
         let cache = Arc::new(create_test_cache()?);
         let api = ApiImpl::with_cache(cache.clone());
 
-        // Create stream first (required for foreign key constraint)
-        let stream = scryforge_provider_core::Stream {
+        cache.upsert_streams(&[scryforge_provider_core::Stream {
             id: StreamId("test:stream:1".to_string()),
             name: "Test Stream".to_string(),
             provider_id: "test".to_string(),
@@ -1039,28 +6920,17 @@ mod tests {
             total_count: None,
             last_updated: None,
             metadata: HashMap::new(),
-        };
-        cache.upsert_streams(&[stream])?;
-
-        // Create and insert a test item
-        let item = create_test_item("test:item:1");
-        cache.upsert_items(std::slice::from_ref(&item))?;
-
-        // Save
-        ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await?;
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert!(items[0].is_saved);
-
-        // Unsave
-        ScryforgeApiServer::unsave_item(&api, "test:item:1".to_string()).await?;
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert!(!items[0].is_saved);
+        }])?;
+        let mut item = create_test_item("test:item:1");
+        item.published = Some(Utc::now());
+        cache.upsert_items(&[item])?;
 
-        // Save again
-        ScryforgeApiServer::save_item(&api, "test:item:1".to_string()).await?;
-        let items = cache.get_items(&item.stream_id, None)?;
-        assert!(items[0].is_saved);
+        let markdown = ScryforgeApiServer::generate_digest(&api, None).await?;
+        assert!(markdown.contains("## test (1 unread)"));
+        assert!(markdown.contains("- Test Item — Test Stream"));
 
+        let html = ScryforgeApiServer::generate_digest(&api, Some(DigestFormat::Html)).await?;
+        assert!(html.starts_with("<!DOCTYPE html>"));
         Ok(())
     }
 }
@@ -3,8 +3,9 @@
 //! This module exposes the daemon's functionality to clients (TUI, web, etc.)
 //! over a JSON-RPC interface via TCP.
 
+pub mod auth;
 pub mod handlers;
 pub mod saved_items;
 pub mod server;
 
-pub use server::start_server;
+pub use server::{start_server, start_server_with};
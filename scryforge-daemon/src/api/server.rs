@@ -1,14 +1,30 @@
 //! JSON-RPC server implementation for scryforge-daemon.
 //!
-//! This module provides the server that listens on TCP localhost and handles
-//! incoming JSON-RPC requests from clients.
+//! This module provides the server that listens on TCP (localhost by default)
+//! and handles incoming JSON-RPC requests from clients. Requests pass through
+//! the [`AuthLayer`](super::auth::AuthLayer) when client tokens are configured,
+//! and the listener can optionally be wrapped in TLS for remote access.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use jsonrpsee::server::{Server, ServerHandle};
-use tracing::info;
+use jsonrpsee::server::{
+    serve_with_graceful_shutdown, stop_channel, Methods, Server, ServerHandle,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, info, warn};
 
+use super::auth::AuthLayer;
 use super::handlers::{ApiImpl, ScryforgeApiServer};
 use crate::cache::SqliteCache;
+use crate::config::{DaemonConfig, TlsConfig};
 
 /// Start the JSON-RPC API server on TCP localhost.
 ///
@@ -19,15 +35,41 @@ use crate::cache::SqliteCache;
 ///
 /// A tuple of (ServerHandle, SocketAddr) - the handle keeps the server running,
 /// and the address shows where it's listening
-pub async fn start_server() -> Result<(ServerHandle, std::net::SocketAddr)> {
-    info!("Starting JSON-RPC server on 127.0.0.1:3030");
-
+pub async fn start_server() -> Result<(ServerHandle, SocketAddr)> {
     // Create API implementation with SqliteCache type
     let api: ApiImpl<SqliteCache> = ApiImpl::new();
+    start_server_with(api, &DaemonConfig::default()).await
+}
+
+/// Start the JSON-RPC API server with a preconfigured API and daemon settings.
+///
+/// The server listens on `config.bind_address` and accepts both HTTP requests
+/// and WebSocket connections on the same port; WebSocket clients can use
+/// `events.subscribe` to receive live events. If `config.clients` is non-empty
+/// every request must present one of the configured bearer tokens, and if
+/// `config.tls` is set the listener only speaks TLS.
+pub async fn start_server_with(
+    api: ApiImpl<SqliteCache>,
+    config: &DaemonConfig,
+) -> Result<(ServerHandle, SocketAddr)> {
+    info!("Starting JSON-RPC server on {}", config.bind_address);
+
+    let auth = AuthLayer::new(config.clients.clone());
+    if auth.is_enabled() {
+        info!(
+            "API authentication enabled for {} client(s)",
+            config.clients.len()
+        );
+    }
+
+    if let Some(ref tls) = config.tls {
+        return start_tls_server(api, config, tls, auth).await;
+    }
 
-    // Build the server on localhost
+    // Build the server
     let server = Server::builder()
-        .build("127.0.0.1:3030")
+        .set_http_middleware(tower::ServiceBuilder::new().layer(auth))
+        .build(config.bind_address.as_str())
         .await
         .context("Failed to build JSON-RPC server")?;
 
@@ -45,6 +87,112 @@ pub async fn start_server() -> Result<(ServerHandle, std::net::SocketAddr)> {
     Ok((handle, addr))
 }
 
+/// Serve the API over TLS using a hand-rolled accept loop.
+///
+/// jsonrpsee's built-in server only handles plain TCP, so each accepted
+/// connection is wrapped by the TLS acceptor and then handed to a jsonrpsee
+/// tower service sharing the same methods and stop handle.
+async fn start_tls_server(
+    api: ApiImpl<SqliteCache>,
+    config: &DaemonConfig,
+    tls: &TlsConfig,
+    auth: AuthLayer,
+) -> Result<(ServerHandle, SocketAddr)> {
+    let acceptor = load_tls_acceptor(tls)?;
+
+    let listener = TcpListener::bind(config.bind_address.as_str())
+        .await
+        .with_context(|| format!("Failed to bind {}", config.bind_address))?;
+    let addr = listener
+        .local_addr()
+        .context("Failed to get server address")?;
+
+    let methods: Methods = api.into_rpc().into();
+    let (stop_handle, server_handle) = stop_channel();
+    let service_builder = Server::builder()
+        .set_http_middleware(tower::ServiceBuilder::new().layer(auth))
+        .to_service_builder();
+
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _peer)) => stream,
+                    Err(e) => {
+                        warn!("Failed to accept connection: {}", e);
+                        continue;
+                    }
+                },
+                _ = stop_handle.clone().shutdown() => break,
+            };
+
+            let acceptor = acceptor.clone();
+            let service = service_builder
+                .clone()
+                .build(methods.clone(), stop_handle.clone());
+            let stop_handle = stop_handle.clone();
+
+            tokio::spawn(async move {
+                let stream = match acceptor.accept(stream).await {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        debug!("TLS handshake failed: {}", e);
+                        return;
+                    }
+                };
+
+                if let Err(e) =
+                    serve_with_graceful_shutdown(stream, service, stop_handle.shutdown()).await
+                {
+                    debug!("TLS connection closed with error: {}", e);
+                }
+            });
+        }
+
+        info!("TLS listener stopped");
+    });
+
+    info!("JSON-RPC server listening on {} (TLS)", addr);
+
+    Ok((server_handle, addr))
+}
+
+/// Load the certificate chain and private key and build a TLS acceptor.
+fn load_tls_acceptor(tls: &TlsConfig) -> Result<TlsAcceptor> {
+    let cert_file = File::open(&tls.cert_path).with_context(|| {
+        format!(
+            "Failed to open TLS certificate: {}",
+            tls.cert_path.display()
+        )
+    })?;
+    let certs = CertificateDer::pem_reader_iter(BufReader::new(cert_file))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| {
+            format!(
+                "Failed to parse TLS certificate: {}",
+                tls.cert_path.display()
+            )
+        })?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificates found in {}", tls.cert_path.display());
+    }
+
+    let key_file = File::open(&tls.key_path)
+        .with_context(|| format!("Failed to open TLS key: {}", tls.key_path.display()))?;
+    let key = PrivateKeyDer::from_pem_reader(BufReader::new(key_file))
+        .with_context(|| format!("Failed to parse TLS key: {}", tls.key_path.display()))?;
+
+    let server_config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .context("Failed to configure TLS protocol versions")?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .context("Invalid TLS certificate or key")?;
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,4 +207,19 @@ mod tests {
         }
         // Don't assert - port may be in use in CI
     }
+
+    #[tokio::test]
+    async fn test_tls_requires_readable_certificate() {
+        let config = DaemonConfig {
+            bind_address: "127.0.0.1:0".to_string(),
+            tls: Some(TlsConfig {
+                cert_path: "/nonexistent/cert.pem".into(),
+                key_path: "/nonexistent/key.pem".into(),
+            }),
+            ..Default::default()
+        };
+
+        let result = start_server_with(ApiImpl::new(), &config).await;
+        assert!(result.is_err());
+    }
 }
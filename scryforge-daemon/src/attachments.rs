@@ -0,0 +1,635 @@
+//! Attachment text for search.
+//!
+//! With `[attachments] index = true`, each stream refresh reads the PDF and
+//! Office attachments of the items it caches for the first time and records
+//! their text in the cache, where [`Cache::search_items`] matches it along
+//! with the item's own text, so "that invoice from March" turns up the email
+//! the invoice came with.
+//!
+//! Word, Excel, PowerPoint, and OpenDocument files are ZIP archives of XML
+//! and are read here. PDFs go through the configured `pdf_command`, run with
+//! `sh -c` with the file on stdin, `pdftotext` by default. Only items whose
+//! provider reports attachments under [`ATTACHMENTS_METADATA_KEY`] are
+//! listed, since listing can mean fetching the whole message.
+
+use std::io::{Cursor, Read};
+use std::process::Stdio;
+
+use anyhow::{bail, Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use scryforge_provider_core::{Attachment, Item, ATTACHMENTS_METADATA_KEY};
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+use zip::ZipArchive;
+
+use crate::cache::Cache;
+use crate::config::AttachmentsConfig;
+use crate::registry::ProviderRegistry;
+
+/// A kind of document whose text can be extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    Pdf,
+    /// `.docx`
+    Word,
+    /// `.xlsx`
+    Spreadsheet,
+    /// `.pptx`
+    Presentation,
+    /// `.odt`, `.ods`, and `.odp`
+    OpenDocument,
+}
+
+impl DocumentKind {
+    /// The kind of `attachment`, by file extension or else MIME type.
+    pub fn of(attachment: &Attachment) -> Option<Self> {
+        let extension = attachment
+            .file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        let by_extension = match extension.as_deref() {
+            Some("pdf") => Some(Self::Pdf),
+            Some("docx") => Some(Self::Word),
+            Some("xlsx") => Some(Self::Spreadsheet),
+            Some("pptx") => Some(Self::Presentation),
+            Some("odt" | "ods" | "odp") => Some(Self::OpenDocument),
+            _ => None,
+        };
+        by_extension.or_else(|| {
+            let mime = attachment.mime_type.as_deref()?.to_ascii_lowercase();
+            match mime.as_str() {
+                "application/pdf" => Some(Self::Pdf),
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                    Some(Self::Word)
+                }
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => {
+                    Some(Self::Spreadsheet)
+                }
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation" => {
+                    Some(Self::Presentation)
+                }
+                _ if mime.starts_with("application/vnd.oasis.opendocument.") => {
+                    Some(Self::OpenDocument)
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Whether the archive entry `name` holds text of this kind of document.
+    fn is_text_part(self, name: &str) -> bool {
+        match self {
+            Self::Pdf => false,
+            Self::Word => name == "word/document.xml",
+            Self::Spreadsheet => name == "xl/sharedStrings.xml",
+            Self::Presentation => name.starts_with("ppt/slides/slide") && name.ends_with(".xml"),
+            Self::OpenDocument => name == "content.xml",
+        }
+    }
+}
+
+/// Extracts and records the text of attachments.
+#[derive(Debug, Clone)]
+pub struct AttachmentIndexer {
+    max_bytes: u64,
+    max_chars: usize,
+    pdf_command: String,
+}
+
+impl AttachmentIndexer {
+    /// Build an indexer, or `None` if indexing is off.
+    pub fn from_config(config: &AttachmentsConfig) -> Option<Self> {
+        config.index.then(|| Self {
+            max_bytes: config.max_bytes,
+            max_chars: config.max_chars,
+            pdf_command: config.pdf_command.clone(),
+        })
+    }
+
+    /// Record the text of the attachments of `item`, a cached item, and
+    /// return how many were recorded.
+    ///
+    /// Attachments that are too large, of other kinds, or whose text can't
+    /// be extracted are skipped.
+    pub async fn index_item<C: Cache + ?Sized>(
+        &self,
+        registry: &ProviderRegistry,
+        cache: &C,
+        item: &Item,
+    ) -> Result<usize> {
+        let has_attachments = item
+            .metadata
+            .get(ATTACHMENTS_METADATA_KEY)
+            .and_then(|count| count.parse::<u32>().ok())
+            .is_some_and(|count| count > 0);
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        let provider = registry.get(provider_id);
+        let Some(attachments) = provider
+            .as_ref()
+            .and_then(|p| p.as_attachments())
+            .filter(|_| has_attachments)
+        else {
+            return Ok(0);
+        };
+
+        let listed = registry
+            .guard(provider_id, attachments.list_attachments(item))
+            .await
+            .context("Failed to list attachments")?;
+        let mut indexed = 0;
+        for attachment in &listed {
+            let Some(kind) = DocumentKind::of(attachment) else {
+                continue;
+            };
+            if attachment.size.is_some_and(|size| size > self.max_bytes) {
+                debug!("Not indexing {}: too large", attachment.file_name);
+                continue;
+            }
+
+            let data = registry
+                .guard(
+                    provider_id,
+                    attachments.fetch_attachment(item, &attachment.id),
+                )
+                .await
+                .with_context(|| format!("Failed to fetch {}", attachment.file_name))?;
+            if data.len() as u64 > self.max_bytes {
+                debug!("Not indexing {}: too large", attachment.file_name);
+                continue;
+            }
+            let text = match self.extract(kind, &data).await {
+                Ok(text) if !text.is_empty() => text,
+                Ok(_) => continue,
+                Err(e) => {
+                    debug!("No text extracted from {}: {:#}", attachment.file_name, e);
+                    continue;
+                }
+            };
+            if cache.set_attachment_text(&item.id, &attachment.id, &attachment.file_name, &text)? {
+                indexed += 1;
+            }
+        }
+
+        if indexed > 0 {
+            debug!("Indexed {} attachments of {}", indexed, item.id.as_str());
+        }
+        Ok(indexed)
+    }
+
+    /// The text of `data`, a document of `kind`, cut to `max_chars`.
+    pub async fn extract(&self, kind: DocumentKind, data: &[u8]) -> Result<String> {
+        let text = match kind {
+            DocumentKind::Pdf => run_extractor(&self.pdf_command, data.to_vec()).await?,
+            _ => {
+                let mut text = String::new();
+                for (_, xml) in zip_entries(data, self.max_bytes, |name| kind.is_text_part(name))? {
+                    text.push_str(&xml_text(&String::from_utf8_lossy(&xml))?);
+                    text.push('\n');
+                }
+                text
+            }
+        };
+
+        let text = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(match text.char_indices().nth(self.max_chars) {
+            Some((end, _)) => text[..end].to_string(),
+            None => text,
+        })
+    }
+}
+
+/// Run `command` with `data` on stdin and return what it writes to stdout.
+async fn run_extractor(command: &str, data: Vec<u8>) -> Result<String> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start extractor: {}", command))?;
+
+    // Write stdin while stdout is read, so large output can't deadlock
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = tokio::spawn(async move {
+        let _ = stdin.write_all(&data).await;
+    });
+    let output = child.wait_with_output().await?;
+    writer.abort();
+    if !output.status.success() {
+        bail!("Extractor exited with {}", output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The entries of the ZIP archive `data` whose names pass `wanted`, in
+/// archive order, each decompressed to at most `limit` bytes.
+fn zip_entries(
+    data: &[u8],
+    limit: u64,
+    wanted: impl Fn(&str) -> bool,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).context("Not a ZIP archive")?;
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index).context("Corrupt ZIP archive")?;
+        if !wanted(entry.name()) {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let mut contents = Vec::new();
+        entry
+            .take(limit)
+            .read_to_end(&mut contents)
+            .with_context(|| format!("Failed to read {}", name))?;
+        entries.push((name, contents));
+    }
+    Ok(entries)
+}
+
+/// The text content of an Office XML part, one paragraph, cell, or shared
+/// string per line.
+fn xml_text(xml: &str) -> Result<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut text = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Text(content) => {
+                // An entity XML doesn't define shouldn't lose the document
+                let content = content
+                    .unescape()
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&content).into_owned().into());
+                text.push_str(&content);
+            }
+            Event::CData(data) => text.push_str(&String::from_utf8_lossy(&data)),
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"w:tab" | b"a:tab" | b"text:tab" | b"text:s" => text.push(' '),
+                b"w:br" | b"a:br" | b"text:line-break" => text.push('\n'),
+                _ => {}
+            },
+            Event::End(element) => {
+                if let b"w:p" | b"a:p" | b"text:p" | b"text:h" | b"si" = element.name().as_ref() {
+                    text.push('\n');
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(text)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use async_trait::async_trait;
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use scryforge_provider_core::{
+        Action, ActionResult, HasAttachments, ItemContent, ItemId, Provider, ProviderCapabilities,
+        ProviderHealth, Stream, StreamId, StreamType, SyncResult,
+    };
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Mail provider whose messages all carry the same attachments.
+    struct MailProvider {
+        attachments: Vec<(Attachment, Vec<u8>)>,
+        fetched: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Provider for MailProvider {
+        fn id(&self) -> &'static str {
+            "mail"
+        }
+
+        fn name(&self) -> &'static str {
+            "Mail"
+        }
+
+        async fn health_check(&self) -> scryforge_provider_core::Result<ProviderHealth> {
+            unimplemented!()
+        }
+
+        async fn sync(&self) -> scryforge_provider_core::Result<SyncResult> {
+            unimplemented!()
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities::default()
+        }
+
+        async fn available_actions(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<Action>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute_action(
+            &self,
+            _item: &Item,
+            _action: &Action,
+        ) -> scryforge_provider_core::Result<ActionResult> {
+            unimplemented!()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_attachments(&self) -> Option<&dyn HasAttachments> {
+            Some(self)
+        }
+    }
+
+    #[async_trait]
+    impl HasAttachments for MailProvider {
+        async fn list_attachments(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<Attachment>> {
+            Ok(self.attachments.iter().map(|(a, _)| a.clone()).collect())
+        }
+
+        async fn fetch_attachment(
+            &self,
+            _item: &Item,
+            attachment_id: &str,
+        ) -> scryforge_provider_core::Result<Vec<u8>> {
+            self.fetched.lock().unwrap().push(attachment_id.to_string());
+            let (_, data) = self
+                .attachments
+                .iter()
+                .find(|(a, _)| a.id == attachment_id)
+                .unwrap();
+            Ok(data.clone())
+        }
+    }
+
+    fn message(id: &str, attachments: Option<&str>) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("mail:feed:inbox".to_string()),
+            title: "Your statement".to_string(),
+            content: ItemContent::Text("See attached.".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: attachments
+                .map(|count| HashMap::from([(ATTACHMENTS_METADATA_KEY.to_string(), count.into())]))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// A ZIP archive of `entries`, the first stored and the rest deflated.
+    fn zip(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut directory = Vec::new();
+        for (index, (name, contents)) in entries.iter().enumerate() {
+            let (method, data) = if index == 0 {
+                (0u16, contents.as_bytes().to_vec())
+            } else {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(contents.as_bytes()).unwrap();
+                (8, encoder.finish().unwrap())
+            };
+            let offset = archive.len() as u32;
+            let sizes = [data.len() as u32, contents.len() as u32];
+
+            archive.extend_from_slice(b"PK\x03\x04");
+            archive.extend_from_slice(&[20, 0, 0, 0]);
+            archive.extend_from_slice(&method.to_le_bytes());
+            archive.extend_from_slice(&[0; 8]);
+            for size in sizes {
+                archive.extend_from_slice(&size.to_le_bytes());
+            }
+            archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            archive.extend_from_slice(&[0, 0]);
+            archive.extend_from_slice(name.as_bytes());
+            archive.extend_from_slice(&data);
+
+            directory.extend_from_slice(b"PK\x01\x02");
+            directory.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+            directory.extend_from_slice(&method.to_le_bytes());
+            directory.extend_from_slice(&[0; 8]);
+            for size in sizes {
+                directory.extend_from_slice(&size.to_le_bytes());
+            }
+            directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&offset.to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let directory_offset = archive.len() as u32;
+        archive.extend_from_slice(&directory);
+        archive.extend_from_slice(b"PK\x05\x06");
+        archive.extend_from_slice(&[0; 4]);
+        for _ in 0..2 {
+            archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        }
+        archive.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&directory_offset.to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+        archive
+    }
+
+    fn indexer(max_chars: usize) -> AttachmentIndexer {
+        AttachmentIndexer::from_config(&AttachmentsConfig {
+            index: true,
+            max_chars,
+            pdf_command: "tr a-z A-Z".to_string(),
+            ..AttachmentsConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn attachment(file_name: &str, mime_type: Option<&str>) -> Attachment {
+        Attachment {
+            id: file_name.to_string(),
+            file_name: file_name.to_string(),
+            mime_type: mime_type.map(str::to_string),
+            size: None,
+        }
+    }
+
+    #[test]
+    fn test_document_kind() {
+        assert_eq!(
+            DocumentKind::of(&attachment("Invoice.PDF", None)),
+            Some(DocumentKind::Pdf)
+        );
+        assert_eq!(
+            DocumentKind::of(&attachment("report.docx", None)),
+            Some(DocumentKind::Word)
+        );
+        assert_eq!(
+            DocumentKind::of(&attachment(
+                "attachment-2",
+                Some("application/vnd.oasis.opendocument.text")
+            )),
+            Some(DocumentKind::OpenDocument)
+        );
+        assert_eq!(
+            DocumentKind::of(&attachment("photo.jpg", Some("image/jpeg"))),
+            None
+        );
+        assert!(AttachmentIndexer::from_config(&AttachmentsConfig::default()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_extract_office_documents() -> Result<()> {
+        let docx = zip(&[
+            ("[Content_Types].xml", "<Types/>"),
+            (
+                "word/document.xml",
+                r#"<w:document><w:body><w:p><w:r><w:t>Invoice</w:t></w:r><w:r><w:tab/><w:t xml:space="preserve">March &amp; April</w:t></w:r></w:p><w:p><w:r><w:t>Total: &#8364;42</w:t></w:r></w:p></w:body></w:document>"#,
+            ),
+        ]);
+        let text = indexer(1000).extract(DocumentKind::Word, &docx).await?;
+        assert_eq!(text, "Invoice March & April\nTotal: €42");
+
+        let pptx = zip(&[
+            (
+                "ppt/slides/slide1.xml",
+                "<p:sld><a:p><a:r><a:t>Roadmap</a:t></a:r></a:p></p:sld>",
+            ),
+            ("ppt/slides/_rels/slide1.xml.rels", "<Relationships/>"),
+            (
+                "ppt/slides/slide2.xml",
+                "<p:sld><a:p><a:r><a:t>Q3 launch</a:t></a:r></a:p></p:sld>",
+            ),
+        ]);
+        let text = indexer(1000)
+            .extract(DocumentKind::Presentation, &pptx)
+            .await?;
+        assert_eq!(text, "Roadmap\nQ3 launch");
+
+        // Text is cut to max_chars
+        let text = indexer(6).extract(DocumentKind::Word, &docx).await?;
+        assert_eq!(text, "Invoic");
+
+        assert!(indexer(1000)
+            .extract(DocumentKind::Spreadsheet, b"not a zip")
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extract_pdf_runs_command() -> Result<()> {
+        let text = indexer(1000)
+            .extract(DocumentKind::Pdf, b"  invoice\n\n march  \n")
+            .await?;
+        assert_eq!(text, "INVOICE\nMARCH");
+
+        let failing = AttachmentIndexer {
+            pdf_command: "exit 1".to_string(),
+            ..indexer(1000)
+        };
+        assert!(failing.extract(DocumentKind::Pdf, b"%PDF").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_index_item_records_document_text() -> Result<()> {
+        let dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&dir.path().join("cache.db"))?;
+        cache.upsert_streams(&[Stream {
+            id: StreamId("mail:feed:inbox".to_string()),
+            name: "Inbox".to_string(),
+            provider_id: "mail".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let statement = message("mail:msg:1", Some("3"));
+        let plain = message("mail:msg:2", None);
+        cache.upsert_items(&[statement.clone(), plain.clone()])?;
+
+        let mut oversized = attachment("scan.pdf", None);
+        oversized.size = Some(1 << 40);
+        let provider = MailProvider {
+            attachments: vec![
+                (attachment("march.pdf", None), b"invoice for march".to_vec()),
+                (
+                    attachment("photo.jpg", Some("image/jpeg")),
+                    vec![0xFF, 0xD8],
+                ),
+                (oversized, Vec::new()),
+            ],
+            fetched: Mutex::new(Vec::new()),
+        };
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider);
+
+        let indexer = indexer(1000);
+        assert_eq!(indexer.index_item(&registry, &cache, &statement).await?, 1);
+        assert_eq!(indexer.index_item(&registry, &cache, &plain).await?, 0);
+
+        // Only the PDF small enough was fetched
+        let provider = registry.get("mail").unwrap();
+        let provider = provider.as_any().downcast_ref::<MailProvider>().unwrap();
+        assert_eq!(*provider.fetched.lock().unwrap(), vec!["march.pdf"]);
+
+        let found = cache.search_items("INVOICE FOR", None, None, None, None)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, statement.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extract_zip64_document() -> Result<()> {
+        // ZIP64 sizes and offsets throughout, with a ZIP64 end of central
+        // directory, as written by tools that stream archives
+        let docx = include_bytes!("../fixtures/attachments/zip64.docx");
+        let text = indexer(1000).extract(DocumentKind::Word, docx).await?;
+        assert_eq!(text, "Quarterly report\nRevenue grew 12% & costs fell");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extract_truncated_archive_fails() {
+        let docx = include_bytes!("../fixtures/attachments/zip64.docx");
+        for end in [docx.len() / 2, docx.len() - 10] {
+            assert!(indexer(1000)
+                .extract(DocumentKind::Word, &docx[..end])
+                .await
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_xml_text() -> Result<()> {
+        assert_eq!(
+            xml_text("<si><t>a &lt;b&gt; &#x41;&#66;</t></si><si><t>c</t></si>")?,
+            "a <b> AB\nc\n"
+        );
+        assert!(xml_text("<si><t>unclosed</si>").is_err());
+        Ok(())
+    }
+}
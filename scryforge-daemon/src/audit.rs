@@ -0,0 +1,52 @@
+//! Audit log of provider mutations.
+//!
+//! Every change the daemon makes on a provider's side, such as an outbox
+//! action sent, a task created or deleted, or an invitation answered, and
+//! every item it deletes, is recorded in the cache's `audit_log` table with
+//! its outcome. The log is listed by `audit.list`, newest first, and keeps
+//! the latest [`MAX_AUDIT_ENTRIES`].
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::ItemId;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::cache::Cache;
+
+/// Entries kept in the audit log; older ones are dropped as new ones come.
+pub const MAX_AUDIT_ENTRIES: u32 = 10_000;
+
+/// One provider mutation and how it went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub provider_id: String,
+    /// The item acted on, if any
+    pub item_id: Option<ItemId>,
+    /// Action ID, such as `mark_read`, `delete_task`, or `delete`
+    pub action: String,
+    pub success: bool,
+    /// Why the mutation failed, if it did
+    pub error: Option<String>,
+}
+
+/// Record a mutation on a provider, of `item_id` if it concerned an item;
+/// `error` is set when it failed.
+///
+/// A failure to write the log is only logged, so it never fails the
+/// mutation itself.
+pub fn record<C: Cache + ?Sized>(
+    cache: &C,
+    provider_id: &str,
+    item_id: Option<&ItemId>,
+    action: &str,
+    error: Option<&str>,
+) {
+    if let Err(e) = cache.record_audit(provider_id, item_id, action, error) {
+        warn!(
+            "Failed to record {} on {} in the audit log: {:#}",
+            action, provider_id, e
+        );
+    }
+}
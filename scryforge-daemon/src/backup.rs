@@ -0,0 +1,609 @@
+//! Backup and restore of the complete application state.
+//!
+//! A backup is a gzipped tar archive holding everything needed to move
+//! Scryforge to another machine:
+//!
+//! - `manifest.json` - format version, creation time, and what was redacted
+//! - `config.toml` - the daemon config with secrets removed
+//! - `cache.db` - a consistent snapshot of the cache, including read and
+//!   saved state and interaction history
+//! - `bookmarks.json` - the bookmarks provider's storage
+//! - `notes.md` or `notes/` - the send-to-notes file or daily directory
+//!
+//! Client bearer tokens and secret-looking provider settings are not
+//! written. Restoring lists them so they can be re-added by hand.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::config::{Config, NotesLayout};
+
+/// Version of the archive layout written by this build.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const CONFIG: &str = "config.toml";
+const CACHE: &str = "cache.db";
+const BOOKMARKS: &str = "bookmarks.json";
+const NOTES_FILE: &str = "notes.md";
+const NOTES_DIR: &str = "notes";
+
+/// Provider setting names treated as secrets, matched as a suffix of the key.
+const SECRET_KEYS: &[&str] = &["password", "secret", "token", "api_key"];
+
+/// Describes the contents of a backup archive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of Scryforge that wrote the archive
+    pub scryforge_version: String,
+    /// Archive entries, in the order they were written
+    pub entries: Vec<String>,
+    /// Dotted config keys left out of the archived config
+    pub redacted: Vec<String>,
+}
+
+/// Where each piece of state lives on a machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatePaths {
+    pub config: PathBuf,
+    pub cache: PathBuf,
+    pub bookmarks: PathBuf,
+    pub notes: Option<(PathBuf, NotesLayout)>,
+}
+
+impl StatePaths {
+    /// Resolve the locations used by `config`, which was loaded from `config_path`.
+    pub fn resolve(config_path: PathBuf, config: &Config) -> Result<Self> {
+        let bookmarks = provider_bookmarks::BookmarksProvider::default_storage_path()
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        Ok(Self {
+            config: config_path,
+            cache: config.cache_path()?,
+            bookmarks,
+            notes: config
+                .notes
+                .path
+                .clone()
+                .map(|path| (path, config.notes.layout)),
+        })
+    }
+}
+
+/// Result of a restore.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoreSummary {
+    pub manifest: Manifest,
+    /// Files written, in the order they were restored
+    pub restored: Vec<PathBuf>,
+}
+
+/// Write a backup of the state at `paths` to `dest`.
+///
+/// State that does not exist yet, such as a cache that was never created,
+/// is skipped. The cache is snapshotted with `VACUUM INTO`, so the daemon
+/// may keep running.
+pub fn create_backup(paths: &StatePaths, dest: &Path) -> Result<Manifest> {
+    let mut entries = Vec::new();
+    let mut redacted = Vec::new();
+
+    let config = if paths.config.exists() {
+        let content = fs::read_to_string(&paths.config)
+            .with_context(|| format!("Failed to read {}", paths.config.display()))?;
+        let (content, keys) = redact_config(&content)?;
+        redacted = keys;
+        entries.push(CONFIG.to_string());
+        Some(content)
+    } else {
+        None
+    };
+
+    let snapshot = if paths.cache.exists() {
+        entries.push(CACHE.to_string());
+        Some(snapshot_cache(&paths.cache)?)
+    } else {
+        None
+    };
+
+    if paths.bookmarks.exists() {
+        entries.push(BOOKMARKS.to_string());
+    }
+
+    let notes = paths.notes.as_ref().filter(|(path, _)| path.exists());
+    if let Some((_, layout)) = notes {
+        entries.push(match layout {
+            NotesLayout::File => NOTES_FILE.to_string(),
+            NotesLayout::Daily => NOTES_DIR.to_string(),
+        });
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        created_at: Utc::now(),
+        scryforge_version: env!("CARGO_PKG_VERSION").to_string(),
+        entries,
+        redacted,
+    };
+
+    let result = write_archive(dest, &manifest, config, snapshot.as_deref(), paths, notes);
+    if let Some(ref snapshot) = snapshot {
+        let _ = fs::remove_file(snapshot);
+    }
+    result?;
+
+    Ok(manifest)
+}
+
+fn write_archive(
+    dest: &Path,
+    manifest: &Manifest,
+    config: Option<String>,
+    snapshot: Option<&Path>,
+    paths: &StatePaths,
+    notes: Option<&(PathBuf, NotesLayout)>,
+) -> Result<()> {
+    let file = File::create(dest)
+        .with_context(|| format!("Failed to create backup file: {}", dest.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    append_bytes(
+        &mut archive,
+        MANIFEST,
+        &serde_json::to_vec_pretty(manifest)?,
+    )?;
+    if let Some(config) = config {
+        append_bytes(&mut archive, CONFIG, config.as_bytes())?;
+    }
+    if let Some(snapshot) = snapshot {
+        archive.append_path_with_name(snapshot, CACHE)?;
+    }
+    if manifest.entries.iter().any(|e| e == BOOKMARKS) {
+        archive.append_path_with_name(&paths.bookmarks, BOOKMARKS)?;
+    }
+    match notes {
+        Some((path, NotesLayout::File)) => archive.append_path_with_name(path, NOTES_FILE)?,
+        Some((path, NotesLayout::Daily)) => archive.append_dir_all(NOTES_DIR, path)?,
+        None => {}
+    }
+
+    archive
+        .into_inner()?
+        .finish()
+        .with_context(|| format!("Failed to write backup file: {}", dest.display()))?;
+    Ok(())
+}
+
+fn append_bytes<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    name: &str,
+    data: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(Utc::now().timestamp() as u64);
+    header.set_cksum();
+    archive.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// Copy the cache database to a temporary file without blocking writers.
+fn snapshot_cache(cache: &Path) -> Result<PathBuf> {
+    let snapshot = scratch_path("backup");
+
+    let conn = rusqlite::Connection::open(cache)
+        .with_context(|| format!("Failed to open cache database: {}", cache.display()))?;
+    conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy()])
+        .context("Failed to snapshot cache database")?;
+
+    Ok(snapshot)
+}
+
+/// A fresh path in the temp directory, unique within and across processes.
+fn scratch_path(kind: &str) -> PathBuf {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "scryforge-{}-{}-{}",
+        kind,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&path);
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Remove secrets from a config file, returning the new content and the
+/// dotted keys that were removed.
+///
+/// Content without secrets is returned unchanged so its comments survive.
+pub fn redact_config(content: &str) -> Result<(String, Vec<String>)> {
+    let mut value: toml::Table = toml::from_str(content).context("Failed to parse config")?;
+    let mut redacted = Vec::new();
+
+    if let Some(daemon) = value.get_mut("daemon").and_then(|d| d.as_table_mut()) {
+        if daemon.remove("clients").is_some() {
+            redacted.push("daemon.clients".to_string());
+        }
+    }
+    if let Some(providers) = value.get_mut("providers").and_then(|p| p.as_table_mut()) {
+        for (id, provider) in providers.iter_mut() {
+            if let Some(settings) = provider.get_mut("settings") {
+                redact_secrets(
+                    settings,
+                    &format!("providers.{}.settings", id),
+                    &mut redacted,
+                );
+            }
+        }
+    }
+
+    if redacted.is_empty() {
+        return Ok((content.to_string(), redacted));
+    }
+    Ok((toml::to_string_pretty(&value)?, redacted))
+}
+
+fn redact_secrets(value: &mut toml::Value, prefix: &str, redacted: &mut Vec<String>) {
+    let Some(table) = value.as_table_mut() else {
+        return;
+    };
+
+    table.retain(|key, _| {
+        let secret = SECRET_KEYS.iter().any(|s| key.to_lowercase().ends_with(s));
+        if secret {
+            redacted.push(format!("{}.{}", prefix, key));
+        }
+        !secret
+    });
+    for (key, child) in table.iter_mut() {
+        redact_secrets(child, &format!("{}.{}", prefix, key), redacted);
+    }
+}
+
+/// Restore the backup at `archive`.
+///
+/// `resolve` maps the restored config (or the default config, if the
+/// backup has none) to the paths to restore into. Existing files are only
+/// replaced when `force` is set. The daemon should be stopped first.
+pub fn restore_backup(
+    archive: &Path,
+    force: bool,
+    resolve: impl FnOnce(&Config) -> Result<StatePaths>,
+) -> Result<RestoreSummary> {
+    let staging = scratch_path("restore");
+    fs::create_dir_all(&staging)?;
+
+    let result =
+        unpack(archive, &staging).and_then(|manifest| install(&staging, manifest, force, resolve));
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn unpack(archive: &Path, staging: &Path) -> Result<Manifest> {
+    let file = File::open(archive)
+        .with_context(|| format!("Failed to open backup file: {}", archive.display()))?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(staging)
+        .with_context(|| format!("{} is not a Scryforge backup", archive.display()))?;
+
+    let manifest = fs::read(staging.join(MANIFEST))
+        .with_context(|| format!("{} has no {}", archive.display(), MANIFEST))?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)?;
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "Backup format {} is newer than this build supports ({}); upgrade Scryforge first",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+fn install(
+    staging: &Path,
+    manifest: Manifest,
+    force: bool,
+    resolve: impl FnOnce(&Config) -> Result<StatePaths>,
+) -> Result<RestoreSummary> {
+    let config = match fs::read_to_string(staging.join(CONFIG)) {
+        Ok(content) => toml::from_str(&content).context("Backup contains an invalid config")?,
+        Err(_) => Config::default(),
+    };
+    let paths = resolve(&config)?;
+
+    let mut files: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for entry in &manifest.entries {
+        match entry.as_str() {
+            CONFIG => files.push((staging.join(CONFIG), paths.config.clone())),
+            CACHE => files.push((staging.join(CACHE), paths.cache.clone())),
+            BOOKMARKS => files.push((staging.join(BOOKMARKS), paths.bookmarks.clone())),
+            NOTES_FILE | NOTES_DIR => {
+                let Some((ref target, _)) = paths.notes else {
+                    bail!("Backup contains notes but the restored config has no notes.path");
+                };
+                if entry == NOTES_FILE {
+                    files.push((staging.join(NOTES_FILE), target.clone()));
+                } else {
+                    for file in fs::read_dir(staging.join(NOTES_DIR))? {
+                        let file = file?;
+                        if !file.file_type()?.is_file() {
+                            continue;
+                        }
+                        files.push((file.path(), target.join(file.file_name())));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let existing: Vec<String> = files
+        .iter()
+        .filter(|(_, target)| target.exists())
+        .map(|(_, target)| target.display().to_string())
+        .collect();
+    if !force && !existing.is_empty() {
+        bail!(
+            "Refusing to overwrite existing files (use --force): {}",
+            existing.join(", ")
+        );
+    }
+
+    let mut restored = Vec::new();
+    for (source, target) in files {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        if target == paths.cache {
+            remove_sqlite_sidecars(&target)?;
+        }
+        replace_file(&source, &target)
+            .with_context(|| format!("Failed to restore {}", target.display()))?;
+        restored.push(target);
+    }
+
+    Ok(RestoreSummary { manifest, restored })
+}
+
+/// Copy `source` over `target` through a temporary file in the same
+/// directory, so `target` is never left half written.
+fn replace_file(source: &Path, target: &Path) -> Result<()> {
+    let temp = sibling(target, ".restoring");
+    fs::copy(source, &temp)?;
+    if let Err(e) = fs::rename(&temp, target) {
+        let _ = fs::remove_file(&temp);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Remove the rollback journal and WAL files of the database at `path`,
+/// which SQLite would otherwise apply to the database restored over it.
+fn remove_sqlite_sidecars(path: &Path) -> Result<()> {
+    for suffix in ["-journal", "-wal", "-shm"] {
+        let sidecar = sibling(path, suffix);
+        match fs::remove_file(&sidecar) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to remove {}", sidecar.display()))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `path` with `suffix` appended to its file name.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CONFIG_WITH_SECRETS: &str = r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[[daemon.clients]]
+name = "laptop"
+token = "hunter2"
+
+[cache]
+max_items_per_stream = 500
+
+[providers.email]
+enabled = true
+sync_interval_minutes = 5
+
+[providers.email.settings]
+username = "me@example.com"
+password = "hunter2"
+
+[providers.email.settings.smtp]
+auth_token = "hunter2"
+"#;
+
+    fn state_paths(root: &Path) -> StatePaths {
+        StatePaths {
+            config: root.join("config/config.toml"),
+            cache: root.join("data/cache.db"),
+            bookmarks: root.join("data/bookmarks.json"),
+            notes: Some((root.join("notes"), NotesLayout::Daily)),
+        }
+    }
+
+    fn write_state(paths: &StatePaths, notes_path: &Path) {
+        fs::create_dir_all(paths.config.parent().unwrap()).unwrap();
+        fs::create_dir_all(paths.cache.parent().unwrap()).unwrap();
+        fs::create_dir_all(notes_path).unwrap();
+
+        let config = format!(
+            "{}\n[notes]\npath = {:?}\nlayout = \"daily\"\n",
+            CONFIG_WITH_SECRETS,
+            notes_path.display().to_string()
+        );
+        fs::write(&paths.config, config).unwrap();
+
+        let conn = rusqlite::Connection::open(&paths.cache).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (id TEXT PRIMARY KEY, is_read INTEGER);
+             INSERT INTO items VALUES ('rss:1', 1);",
+        )
+        .unwrap();
+
+        fs::write(&paths.bookmarks, r#"{"bookmarks":[]}"#).unwrap();
+        fs::write(
+            notes_path.join("2025-06-01.md"),
+            "- [Post](https://example.com)\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_redact_config() {
+        let (content, redacted) = redact_config(CONFIG_WITH_SECRETS).unwrap();
+
+        assert!(!content.contains("hunter2"));
+        assert!(content.contains("me@example.com"));
+        assert_eq!(
+            redacted,
+            vec![
+                "daemon.clients",
+                "providers.email.settings.password",
+                "providers.email.settings.smtp.auth_token",
+            ]
+        );
+        toml::from_str::<Config>(&content).unwrap();
+    }
+
+    #[test]
+    fn test_redact_config_keeps_comments_without_secrets() {
+        let content = Config::default_config_content();
+        let (redacted_content, redacted) = redact_config(&content).unwrap();
+
+        assert!(redacted.is_empty());
+        assert_eq!(redacted_content, content);
+    }
+
+    #[test]
+    fn test_backup_restore_round_trip() {
+        let source = TempDir::new().unwrap();
+        let source_paths = state_paths(source.path());
+        write_state(&source_paths, &source.path().join("notes"));
+
+        let archive = source.path().join("scryforge.tar.gz");
+        let manifest = create_backup(&source_paths, &archive).unwrap();
+        assert_eq!(manifest.entries, vec![CONFIG, CACHE, BOOKMARKS, NOTES_DIR]);
+
+        let target = TempDir::new().unwrap();
+        let summary = restore_backup(&archive, false, |config| {
+            let mut paths = state_paths(target.path());
+            paths.notes = config
+                .notes
+                .path
+                .as_ref()
+                .map(|_| (target.path().join("notes"), config.notes.layout));
+            Ok(paths)
+        })
+        .unwrap();
+        assert_eq!(summary.manifest, manifest);
+        assert_eq!(summary.restored.len(), 4);
+
+        let target_paths = state_paths(target.path());
+        let config = fs::read_to_string(&target_paths.config).unwrap();
+        assert!(!config.contains("hunter2"));
+
+        let conn = rusqlite::Connection::open(&target_paths.cache).unwrap();
+        let is_read: i64 = conn
+            .query_row("SELECT is_read FROM items WHERE id = 'rss:1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(is_read, 1);
+
+        assert_eq!(
+            fs::read(&target_paths.bookmarks).unwrap(),
+            fs::read(&source_paths.bookmarks).unwrap()
+        );
+        assert!(target.path().join("notes/2025-06-01.md").exists());
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite_without_force() {
+        let root = TempDir::new().unwrap();
+        let paths = state_paths(root.path());
+        write_state(&paths, &root.path().join("notes"));
+
+        let archive = root.path().join("scryforge.tar.gz");
+        create_backup(&paths, &archive).unwrap();
+
+        let resolve = |_: &Config| Ok(state_paths(root.path()));
+        let err = restore_backup(&archive, false, resolve).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+
+        fs::write(&paths.bookmarks, "{}").unwrap();
+        let journal = sibling(&paths.cache, "-journal");
+        fs::write(&journal, "stale").unwrap();
+        restore_backup(&archive, true, resolve).unwrap();
+        assert_eq!(
+            fs::read_to_string(&paths.bookmarks).unwrap(),
+            r#"{"bookmarks":[]}"#
+        );
+        // A journal left by the replaced database is not replayed into the
+        // restored one
+        assert!(!journal.exists());
+        assert!(!sibling(&paths.cache, ".restoring").exists());
+    }
+
+    #[test]
+    fn test_backup_skips_missing_state() {
+        let root = TempDir::new().unwrap();
+        let paths = state_paths(root.path());
+
+        let manifest = create_backup(&paths, &root.path().join("empty.tar.gz")).unwrap();
+        assert!(manifest.entries.is_empty());
+    }
+
+    #[test]
+    fn test_restore_rejects_newer_format() {
+        let root = TempDir::new().unwrap();
+        let archive = root.path().join("future.tar.gz");
+        let manifest = Manifest {
+            format_version: FORMAT_VERSION + 1,
+            created_at: Utc::now(),
+            scryforge_version: "99.0.0".to_string(),
+            entries: Vec::new(),
+            redacted: Vec::new(),
+        };
+
+        let file = File::create(&archive).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        append_bytes(
+            &mut builder,
+            MANIFEST,
+            &serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = restore_backup(&archive, false, |_| Ok(state_paths(root.path()))).unwrap_err();
+        assert!(err.to_string().contains("upgrade Scryforge"));
+    }
+}
@@ -0,0 +1,157 @@
+//! Low-bandwidth mode for metered connections.
+//!
+//! While the mode is on, the daemon saves data wherever it decides what to
+//! fetch:
+//!
+//! - Providers are asked to fetch as little as they can, through the
+//!   `low_bandwidth` flag of their feed and search options; IMAP lists
+//!   headers and fetches a body only when the message is opened
+//! - Thumbnails are dropped from items sent to clients, so they don't load
+//!   them. The cache keeps them, and they are sent again once the mode is off
+//! - Attachments of new items aren't downloaded for search
+//! - Feed refreshes and provider searches fetch at most `page_size` items
+//! - Sync intervals and stream refreshes are `sync_interval_factor` times
+//!   longer
+//!
+//! The mode starts as `[bandwidth] low_bandwidth` says and is switched with
+//! the `bandwidth.set` method. The [`Bandwidth`] handle is shared by the sync
+//! tasks and the API, so a switch applies from their next fetch.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use scryforge_provider_core::Item;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::config::BandwidthConfig;
+
+/// Whether low-bandwidth mode is on, and what it limits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthStatus {
+    pub low_bandwidth: bool,
+    /// Whether items sent to clients keep their thumbnails
+    pub thumbnails: bool,
+    /// Whether attachments of new items are downloaded for search
+    pub attachments: bool,
+    /// Items fetched per feed refresh or search, if limited
+    pub page_size: Option<u32>,
+    /// How many times longer sync intervals are
+    pub sync_interval_factor: u32,
+}
+
+/// Shared switch for low-bandwidth mode.
+#[derive(Debug, Clone)]
+pub struct Bandwidth {
+    config: BandwidthConfig,
+    low: Arc<AtomicBool>,
+}
+
+impl Bandwidth {
+    /// A switch starting in the configured mode.
+    pub fn new(config: &BandwidthConfig) -> Self {
+        Self {
+            config: config.clone(),
+            low: Arc::new(AtomicBool::new(config.low_bandwidth)),
+        }
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.low.load(Ordering::Relaxed)
+    }
+
+    /// Switch low-bandwidth mode on or off.
+    pub fn set_low(&self, low: bool) {
+        if self.low.swap(low, Ordering::Relaxed) != low {
+            info!(
+                "Low-bandwidth mode {}",
+                if low { "enabled" } else { "disabled" }
+            );
+        }
+    }
+
+    pub fn status(&self) -> BandwidthStatus {
+        let low = self.is_low();
+        BandwidthStatus {
+            low_bandwidth: low,
+            thumbnails: !low,
+            attachments: !low,
+            page_size: self.page_size(),
+            sync_interval_factor: if low {
+                self.config.sync_interval_factor
+            } else {
+                1
+            },
+        }
+    }
+
+    /// Most items to fetch at once, if limited.
+    pub fn page_size(&self) -> Option<u32> {
+        self.is_low().then_some(self.config.page_size)
+    }
+
+    /// A sync or refresh interval, lengthened in low-bandwidth mode.
+    pub fn stretch(&self, every: Duration) -> Duration {
+        if self.is_low() {
+            every.saturating_mul(self.config.sync_interval_factor)
+        } else {
+            every
+        }
+    }
+
+    /// Whether attachments of new items may be downloaded.
+    pub fn fetch_attachments(&self) -> bool {
+        !self.is_low()
+    }
+
+    /// Drop what items sent to clients shouldn't carry in the current mode.
+    /// Only responses are stripped, never what is cached.
+    pub fn strip<'a>(&self, items: impl IntoIterator<Item = &'a mut Item>) {
+        if self.is_low() {
+            for item in items {
+                item.thumbnail_url = None;
+            }
+        }
+    }
+}
+
+impl Default for Bandwidth {
+    fn default() -> Self {
+        Self::new(&BandwidthConfig::default())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_bandwidth_limits() {
+        let bandwidth = Bandwidth::default();
+        let every = Duration::from_secs(900);
+        assert!(!bandwidth.is_low());
+        assert_eq!(bandwidth.page_size(), None);
+        assert_eq!(bandwidth.stretch(every), every);
+        assert!(bandwidth.fetch_attachments());
+
+        // Clones share the switch
+        bandwidth.clone().set_low(true);
+        assert_eq!(
+            bandwidth.status(),
+            BandwidthStatus {
+                low_bandwidth: true,
+                thumbnails: false,
+                attachments: false,
+                page_size: Some(20),
+                sync_interval_factor: 4,
+            }
+        );
+        assert_eq!(bandwidth.stretch(every), every * 4);
+        assert!(!bandwidth.fetch_attachments());
+    }
+}
@@ -0,0 +1,344 @@
+//! # scry
+//!
+//! Command-line utilities for a Scryforge installation.
+//!
+//! ```text
+//! scry doctor [--json]             Check the config, sockets, credentials and providers
+//! scry backup <archive>            Bundle the cache, bookmarks, notes and config
+//! scry restore <archive> [--force] Restore a backup onto this machine
+//! scry keygen                      Print a new key for encrypt_at_rest providers
+//! scry copy [text]                 Copy text, or stdin, to the clipboard
+//! scry youtube-to-rss [--disable-youtube]
+//!                                  Follow YouTube subscriptions through RSS
+//! scry youtube-to-rss --opml <file>
+//!                                  Write YouTube subscriptions as OPML
+//! scry takeout <archive> [--subscriptions-to youtube|rss]
+//!                                  Import a Google Takeout archive
+//! ```
+
+use anyhow::{bail, Result};
+use scryforge_clipboard::Clipboard;
+use scryforge_daemon::backup::{self, StatePaths};
+use scryforge_daemon::cache::SqliteCache;
+use scryforge_daemon::config::Config;
+use scryforge_daemon::doctor;
+use scryforge_daemon::encryption;
+use scryforge_daemon::registry::{self, YOUTUBE_ACCOUNT};
+use scryforge_daemon::takeout::{self, Takeout};
+use scryforge_daemon::youtube_rss;
+use scryforge_provider_core::crypto::StoreCipher;
+use scryforge_provider_core::CheckStatus;
+use std::io::Read;
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "Usage:
+  scry doctor [--json]
+  scry backup <archive>
+  scry restore <archive> [--force]
+  scry keygen
+  scry copy [text]
+  scry youtube-to-rss [--disable-youtube]
+  scry youtube-to-rss --opml <file>
+  scry takeout <archive> [--subscriptions-to youtube|rss]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["doctor"] => return doctor(false).await,
+        ["doctor", "--json"] => return doctor(true).await,
+        ["backup", archive] => run_backup(Path::new(archive)),
+        ["restore", archive] => run_restore(Path::new(archive), false),
+        ["restore", archive, "--force"] => run_restore(Path::new(archive), true),
+        ["copy"] => run_copy(None),
+        ["copy", text] => run_copy(Some(text)),
+        ["youtube-to-rss"] => run_youtube_to_rss(false).await,
+        ["youtube-to-rss", "--disable-youtube"] => run_youtube_to_rss(true).await,
+        ["youtube-to-rss", "--opml", file] => run_youtube_opml(Path::new(file)).await,
+        ["takeout", archive] => run_takeout(Path::new(archive), false).await,
+        ["takeout", archive, "--subscriptions-to", "youtube"] => {
+            run_takeout(Path::new(archive), false).await
+        }
+        ["takeout", archive, "--subscriptions-to", "rss"] => {
+            run_takeout(Path::new(archive), true).await
+        }
+        ["keygen"] => {
+            println!("{}", StoreCipher::generate_key());
+            return ExitCode::SUCCESS;
+        }
+        ["--help"] | ["-h"] => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn doctor(json: bool) -> ExitCode {
+    let checks = doctor::run().await;
+    if json {
+        match serde_json::to_string_pretty(&checks) {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print!("{}", doctor::render(&checks));
+    }
+
+    if checks.iter().any(|c| c.status == CheckStatus::Failed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_backup(archive: &Path) -> Result<()> {
+    let (config_path, config) = current_config()?;
+    let paths = StatePaths::resolve(config_path, &config)?;
+
+    let manifest = backup::create_backup(&paths, archive)?;
+    println!(
+        "Wrote {} ({})",
+        archive.display(),
+        manifest.entries.join(", ")
+    );
+    if !manifest.redacted.is_empty() {
+        println!("Left out secrets: {}", manifest.redacted.join(", "));
+    }
+    Ok(())
+}
+
+fn run_restore(archive: &Path, force: bool) -> Result<()> {
+    let (config_path, config) = current_config()?;
+    if daemon_running(&config) {
+        bail!(
+            "scryforge-daemon is running on {}; stop it before restoring",
+            config.daemon.bind_address
+        );
+    }
+
+    let summary = backup::restore_backup(archive, force, |restored| {
+        StatePaths::resolve(config_path, restored)
+    })?;
+    for path in &summary.restored {
+        println!("Restored {}", path.display());
+    }
+    if !summary.manifest.redacted.is_empty() {
+        println!(
+            "Re-add these secrets by hand: {}",
+            summary.manifest.redacted.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Copy `text`, or stdin without its final newline, so a link an action
+/// printed can be piped in.
+fn run_copy(text: Option<&str>) -> Result<()> {
+    let text = match text {
+        Some(text) => text.to_string(),
+        None => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            input.strip_suffix('\n').unwrap_or(&input).to_string()
+        }
+    };
+    if text.is_empty() {
+        bail!("nothing to copy");
+    }
+
+    let mut clipboard = Clipboard::new();
+    // Report before waiting: on Linux the text is served until replaced
+    eprintln!("Copying {} bytes via {}", text.len(), clipboard.backend());
+    clipboard.copy_and_wait(&text)?;
+    Ok(())
+}
+
+/// Follow the YouTube account's subscriptions through the RSS provider,
+/// keeping the previous config next to it since comments aren't carried
+/// over.
+async fn run_youtube_to_rss(disable_youtube: bool) -> Result<()> {
+    let config = Config::load_default()?;
+    let feeds = youtube_channel_feeds(&config).await?;
+
+    let (previous, import) =
+        rewrite_config(|content| youtube_rss::import_feeds(content, &feeds, disable_youtube))?;
+
+    println!(
+        "Added {} channel feeds to the RSS provider ({} already followed)",
+        import.added, import.already_followed
+    );
+    if import.youtube_disabled {
+        println!("Disabled the YouTube provider");
+    }
+    println!("Previous config kept at {}", previous.display());
+    if daemon_running(&config) {
+        println!("Restart scryforge-daemon to load the feeds");
+    }
+    Ok(())
+}
+
+/// Write the YouTube account's subscriptions as OPML for another reader.
+async fn run_youtube_opml(file: &Path) -> Result<()> {
+    let (_, config) = current_config()?;
+    let feeds = youtube_channel_feeds(&config).await?;
+    std::fs::write(file, youtube_rss::render_opml(&feeds))?;
+    println!("Wrote {} channel feeds to {}", feeds.len(), file.display());
+    Ok(())
+}
+
+/// The subscriptions and configured channels of the YouTube account.
+async fn youtube_channel_feeds(config: &Config) -> Result<Vec<provider_youtube::ChannelFeed>> {
+    let settings = config
+        .providers
+        .get("youtube")
+        .map(|youtube| youtube.settings.clone().try_into())
+        .transpose()?
+        .unwrap_or_default();
+    let youtube = provider_youtube::YouTubeProvider::new(
+        registry::default_token_fetcher(),
+        YOUTUBE_ACCOUNT.to_string(),
+    )
+    .with_settings(settings);
+
+    let feeds = youtube.channel_feeds().await?;
+    if feeds.is_empty() {
+        bail!("the YouTube account has no subscriptions");
+    }
+    Ok(feeds)
+}
+
+/// Import a Google Takeout archive: watch history and playlists onto cached
+/// videos, Chrome bookmarks into the bookmarks provider, and subscriptions
+/// into the YouTube provider's `rss_channels` or, with `to_rss`, the RSS
+/// provider's feeds.
+async fn run_takeout(archive: &Path, to_rss: bool) -> Result<()> {
+    let config = Config::load_default()?;
+    if daemon_running(&config) {
+        bail!(
+            "scryforge-daemon is running on {}; stop it before importing",
+            config.daemon.bind_address
+        );
+    }
+    let takeout = Takeout::open(archive)?;
+    let cipher = encryption::from_config(&config).await?;
+
+    let history = takeout.watch_history()?;
+    let playlists = takeout.playlists();
+    if !history.is_empty() || !playlists.is_empty() {
+        let mut cache = SqliteCache::open_at(&config.cache_path()?)?;
+        if let Some(cipher) = cipher.clone() {
+            cache = cache.with_encryption(cipher, encryption::encrypted_providers(&config));
+        }
+        let import = takeout::annotate_youtube(&cache, &history, &playlists)?;
+        println!(
+            "Marked {} cached videos watched and {} with their playlists ({} not cached)",
+            import.watched, import.playlisted, import.not_cached
+        );
+    }
+
+    if let Some(html) = takeout.chrome_bookmarks() {
+        let bookmarks = match &cipher {
+            Some(cipher) => {
+                let encrypt = config
+                    .providers
+                    .get("bookmarks")
+                    .is_some_and(|bookmarks| bookmarks.encrypt_at_rest);
+                provider_bookmarks::BookmarksProvider::with_cipher(
+                    provider_bookmarks::BookmarksProvider::default_storage_path()?,
+                    cipher.clone(),
+                    encrypt,
+                )?
+            }
+            None => provider_bookmarks::BookmarksProvider::new()?,
+        };
+        let (count, errors) = bookmarks.import_html(html);
+        println!("Imported {} Chrome bookmarks", count);
+        for error in errors {
+            eprintln!("{}", error);
+        }
+    }
+
+    let subscriptions = takeout.subscriptions();
+    if !subscriptions.is_empty() {
+        let (added, provider) = if to_rss {
+            let feeds: Vec<provider_youtube::ChannelFeed> = subscriptions
+                .into_iter()
+                .map(|subscription| provider_youtube::ChannelFeed {
+                    url: format!(
+                        "{}?channel_id={}",
+                        provider_youtube::YouTubeProvider::RSS_URL,
+                        subscription.channel_id
+                    ),
+                    channel_id: subscription.channel_id,
+                    title: subscription.title,
+                })
+                .collect();
+            let (_, import) =
+                rewrite_config(|content| youtube_rss::import_feeds(content, &feeds, false))?;
+            (import.added, "RSS")
+        } else {
+            let ids: Vec<String> = subscriptions
+                .into_iter()
+                .map(|subscription| subscription.channel_id)
+                .collect();
+            let (_, added) = rewrite_config(|content| youtube_rss::import_channels(content, &ids))?;
+            (added, "YouTube")
+        };
+        println!("Added {} subscriptions to the {} provider", added, provider);
+    }
+    Ok(())
+}
+
+/// Rewrite the config file through `edit`, keeping the previous file next
+/// to it since comments aren't carried over. Returns the previous file's
+/// path and what `edit` reported.
+fn rewrite_config<T>(edit: impl FnOnce(&str) -> Result<(String, T)>) -> Result<(PathBuf, T)> {
+    let config_path = Config::default_config_path()?;
+    let content = std::fs::read_to_string(&config_path)?;
+    let (updated, outcome) = edit(&content)?;
+    toml::from_str::<Config>(&updated)?.validate()?;
+
+    let previous = config_path.with_extension("toml.bak");
+    std::fs::copy(&config_path, &previous)?;
+    std::fs::write(&config_path, updated)?;
+    Ok((previous, outcome))
+}
+
+/// The config in effect on this machine, or the defaults if there is none.
+fn current_config() -> Result<(PathBuf, Config)> {
+    let path = Config::default_config_path()?;
+    let config = if path.exists() {
+        Config::load(&path)?
+    } else {
+        Config::default()
+    };
+    Ok((path, config))
+}
+
+fn daemon_running(config: &Config) -> bool {
+    config
+        .daemon
+        .bind_address
+        .parse::<SocketAddr>()
+        .is_ok_and(|address| TcpStream::connect_timeout(&address, Duration::from_secs(1)).is_ok())
+}
@@ -0,0 +1,296 @@
+//! Daily budgets on the daemon's use of each provider.
+//!
+//! A provider's `[providers.<id>.budget]` caps the operations the daemon
+//! runs on it and the megabytes of items it gets from it per UTC day. An
+//! operation is one guarded call into the provider, such as a sync, a feed
+//! fetch, a search, or an action, however many HTTP requests the provider
+//! makes to serve it. Item megabytes are the items' size as JSON, not the
+//! bytes on the wire, which the daemon doesn't see.
+//!
+//! The [`ProviderRegistry`](crate::registry::ProviderRegistry) counts every
+//! guarded call and refuses calls once a limit is reached, with
+//! [`StreamError::RateLimited`] until midnight, so syncs retry at the reset
+//! like any other throttled provider.
+//!
+//! Before that point, sync intervals are stretched ([`Budgets::stretch`]) as
+//! usage passes [`STRETCH_FROM`] of the budget, so a provider's budget lasts
+//! longer into the day. Usage is kept in memory and starts over when the
+//! daemon restarts.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use scryforge_provider_core::{Item, StreamError};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::{BudgetConfig, Config};
+
+/// Share of a budget after which sync intervals are stretched.
+pub const STRETCH_FROM: f64 = 0.5;
+
+/// Longest a sync interval is stretched, as a multiple of the configured one.
+const MAX_STRETCH: f64 = 24.0;
+
+/// A provider's usage on one UTC day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BudgetUsage {
+    pub day: NaiveDate,
+    pub operations: u32,
+    pub item_bytes: u64,
+}
+
+/// Budget limits and today's usage of every provider.
+#[derive(Debug, Default)]
+pub struct Budgets {
+    limits: HashMap<String, BudgetConfig>,
+    usage: Mutex<HashMap<String, BudgetUsage>>,
+}
+
+impl Budgets {
+    /// Budgets with no limits; usage is still counted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The budgets of the configured providers.
+    pub fn from_config(config: &Config) -> Self {
+        let mut budgets = Self::new();
+        for (provider_id, provider_config) in &config.providers {
+            budgets.set_limit(provider_id, provider_config.budget.clone());
+        }
+        budgets
+    }
+
+    /// Set the limits of a provider.
+    pub fn set_limit(&mut self, provider_id: &str, budget: BudgetConfig) {
+        if budget.is_unlimited() {
+            self.limits.remove(provider_id);
+        } else {
+            self.limits.insert(provider_id.to_string(), budget);
+        }
+    }
+
+    /// A provider's usage today.
+    pub fn usage(&self, provider_id: &str) -> BudgetUsage {
+        self.usage_at(provider_id, Utc::now())
+    }
+
+    /// Count an operation on a provider, or refuse it if the provider's budget
+    /// for the day is used up.
+    pub fn spend_operation(&self, provider_id: &str) -> Result<(), StreamError> {
+        self.spend_operation_at(provider_id, Utc::now())
+    }
+
+    /// Count `bytes` of items got from a provider.
+    pub fn record_item_bytes(&self, provider_id: &str, bytes: u64) {
+        let now = Utc::now();
+        let mut usage = self.usage_map();
+        let entry = Self::today(&mut usage, provider_id, now);
+        entry.item_bytes = entry.item_bytes.saturating_add(bytes);
+    }
+
+    /// Count the items got from a provider, by their size as JSON.
+    pub fn record_items(&self, provider_id: &str, items: &[Item]) {
+        self.record_item_bytes(provider_id, item_size(items));
+    }
+
+    /// A sync interval, lengthened in proportion to how much of its budget
+    /// the provider has used today.
+    ///
+    /// Intervals are unchanged until [`STRETCH_FROM`] of the budget is
+    /// used, doubled at three quarters, and grow up to 24 times as the
+    /// budget runs out.
+    pub fn stretch(&self, provider_id: &str, every: Duration) -> Duration {
+        self.stretch_at(provider_id, every, Utc::now())
+    }
+
+    fn usage_at(&self, provider_id: &str, now: DateTime<Utc>) -> BudgetUsage {
+        let mut usage = self.usage_map();
+        *Self::today(&mut usage, provider_id, now)
+    }
+
+    fn spend_operation_at(&self, provider_id: &str, now: DateTime<Utc>) -> Result<(), StreamError> {
+        let mut usage = self.usage_map();
+        let entry = Self::today(&mut usage, provider_id, now);
+        if let Some(limit) = self.limits.get(provider_id) {
+            let operations_left = limit
+                .max_operations_per_day
+                .is_none_or(|max| entry.operations < max);
+            let item_bytes_left = limit
+                .max_item_megabytes_per_day
+                .is_none_or(|max| entry.item_bytes < max.saturating_mul(1024 * 1024));
+            if !(operations_left && item_bytes_left) {
+                let retry_after = seconds_until_reset(now);
+                warn!(
+                    "Provider '{}' has used its daily budget, pausing for {}s",
+                    provider_id, retry_after
+                );
+                return Err(StreamError::RateLimited(retry_after));
+            }
+        }
+        entry.operations += 1;
+        Ok(())
+    }
+
+    fn stretch_at(&self, provider_id: &str, every: Duration, now: DateTime<Utc>) -> Duration {
+        let Some(limit) = self.limits.get(provider_id) else {
+            return every;
+        };
+        let usage = self.usage_at(provider_id, now);
+        let used = [
+            limit
+                .max_operations_per_day
+                .map(|max| f64::from(usage.operations) / f64::from(max.max(1))),
+            limit
+                .max_item_megabytes_per_day
+                .map(|max| usage.item_bytes as f64 / (max.max(1) as f64 * 1024.0 * 1024.0)),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(0.0, f64::max);
+        if used <= STRETCH_FROM {
+            return every;
+        }
+        let factor = ((1.0 - STRETCH_FROM) / (1.0 - used).max(f64::EPSILON)).min(MAX_STRETCH);
+        every.mul_f64(factor)
+    }
+
+    /// The usage entry of a provider for the day of `now`, reset if it is
+    /// from an earlier day.
+    fn today<'a>(
+        usage: &'a mut HashMap<String, BudgetUsage>,
+        provider_id: &str,
+        now: DateTime<Utc>,
+    ) -> &'a mut BudgetUsage {
+        let day = now.date_naive();
+        let entry = usage.entry(provider_id.to_string()).or_default();
+        if entry.day != day {
+            *entry = BudgetUsage {
+                day,
+                ..BudgetUsage::default()
+            };
+        }
+        entry
+    }
+
+    fn usage_map(&self) -> std::sync::MutexGuard<'_, HashMap<String, BudgetUsage>> {
+        // Counts in a poisoned map are still usable
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Size of items, as their JSON encoding.
+pub fn item_size(items: &[Item]) -> u64 {
+    items
+        .iter()
+        .map(|item| serde_json::to_vec(item).map_or(0, |json| json.len() as u64))
+        .sum()
+}
+
+/// Seconds from `now` until the next midnight UTC, when budgets reset.
+fn seconds_until_reset(now: DateTime<Utc>) -> u64 {
+    let midnight = (now.date_naive() + chrono::Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+    (midnight - now).num_seconds().max(1) as u64
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn budgets(operations: Option<u32>, megabytes: Option<u64>) -> Budgets {
+        let mut budgets = Budgets::new();
+        budgets.set_limit(
+            "mail",
+            BudgetConfig {
+                max_operations_per_day: operations,
+                max_item_megabytes_per_day: megabytes,
+            },
+        );
+        budgets
+    }
+
+    #[test]
+    fn test_operations_refused_until_midnight() {
+        let budgets = budgets(Some(2), None);
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 23, 0, 0).unwrap();
+
+        assert!(budgets.spend_operation_at("mail", now).is_ok());
+        assert!(budgets.spend_operation_at("mail", now).is_ok());
+        assert!(matches!(
+            budgets.spend_operation_at("mail", now),
+            Err(StreamError::RateLimited(3600))
+        ));
+        assert_eq!(budgets.usage_at("mail", now).operations, 2);
+
+        // Other providers and the next day are unaffected
+        assert!(budgets.spend_operation_at("rss", now).is_ok());
+        let tomorrow = now + chrono::Duration::hours(2);
+        assert!(budgets.spend_operation_at("mail", tomorrow).is_ok());
+        assert_eq!(budgets.usage_at("mail", tomorrow).operations, 1);
+    }
+
+    #[test]
+    fn test_item_size_limit() {
+        let budgets = budgets(None, Some(1));
+        assert!(budgets.spend_operation("mail").is_ok());
+        budgets.record_item_bytes("mail", 1024 * 1024);
+        assert!(budgets.spend_operation("mail").is_err());
+        assert_eq!(budgets.usage("mail").item_bytes, 1024 * 1024);
+    }
+
+    #[test]
+    fn test_stretch_grows_as_budget_runs_out() {
+        let budgets = budgets(Some(100), None);
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let every = Duration::from_secs(600);
+
+        for _ in 0..50 {
+            budgets.spend_operation_at("mail", now).unwrap();
+        }
+        assert_eq!(budgets.stretch_at("mail", every, now), every);
+        for _ in 0..25 {
+            budgets.spend_operation_at("mail", now).unwrap();
+        }
+        assert_eq!(budgets.stretch_at("mail", every, now), every * 2);
+        for _ in 0..25 {
+            budgets.spend_operation_at("mail", now).unwrap();
+        }
+        assert_eq!(budgets.stretch_at("mail", every, now), every * 24);
+
+        // Providers without a budget keep their interval
+        assert_eq!(budgets.stretch_at("rss", every, now), every);
+    }
+
+    #[test]
+    fn test_item_size() {
+        let item = Item {
+            id: scryforge_provider_core::ItemId("mail:1".to_string()),
+            stream_id: scryforge_provider_core::StreamId("mail:feed:inbox".to_string()),
+            title: "Hello".to_string(),
+            content: scryforge_provider_core::ItemContent::Text("Body".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        let size = item_size(std::slice::from_ref(&item));
+        assert_eq!(size, serde_json::to_vec(&item).unwrap().len() as u64);
+        assert_eq!(item_size(&[item.clone(), item]), size * 2);
+    }
+}
@@ -6,10 +6,50 @@
 //! # Database Schema
 //!
 //! - `streams`: Cached stream metadata
-//! - `items`: Cached items from providers
+//! - `items`: Cached items from providers, their metadata stamped with an
+//!   estimated reading time, see [`crate::reading_time`]
 //! - `sync_state`: Tracks last sync timestamps per provider
+//! - `interactions`: Log of opens, saves, and skips used for relevance ranking
+//! - `state_conflicts`: Read/saved changes made on both sides and how they were resolved
+//! - `reminders`: "Remind me at" times set on items
+//! - `pins`: Items pinned to the top of their stream
+//! - `reading_queue`: Order of saved items in the reading queue and where
+//!   the reader left off in each
+//! - `outbox`: Provider actions waiting to be sent, see [`crate::outbox`]
+//! - `audit_log`: Mutations made on providers and their outcome, see
+//!   [`crate::audit`]
+//! - `playback`: How far into a video or podcast episode the user got
+//! - `item_links`: Normalized URLs of items and of the links in their
+//!   content, for [`Cache::get_related`]
+//! - `item_contacts`: Addresses in the headers of cached email, kept after
+//!   the email is evicted, for [`Cache::complete_contacts`]
+//! - `address_book`: Contacts imported from CardDAV, see [`crate::contacts`]
+//! - `spam_verdicts`: Messages marked spam or not spam, kept for training
+//!   local filters
+//! - `attachment_text`: Text extracted from attachments, searched with the
+//!   item, see [`crate::attachments`]
+//! - `saved_searches`: Search queries saved as alerts, see [`crate::alerts`]
+//! - `alert_matches`: Items that matched a saved search after it was saved
 //! - `schema_version`: Migration tracking
 //!
+//! # Retention
+//!
+//! [`Cache::evict`] applies the `[cache]` limits: items per stream, item
+//! age, and database size. A stream's `[streams]` policy overrides the first
+//! two and can have old unread items marked read. Saved items, pinned items,
+//! items with a reminder or a pending outbox action, items partway through
+//! playback, and items carrying locally added metadata, such as a generated
+//! summary, are never evicted.
+//! [`spawn_eviction`] runs it in the background.
+//!
+//! # Encryption
+//!
+//! With [`SqliteCache::with_encryption`], items of `encrypt_at_rest`
+//! providers are written with their title, content, author, and metadata
+//! sealed by a [`StoreCipher`]. Sealed columns are opened transparently on
+//! read. Search matches them after decryption, and they are left out of the
+//! per-author interaction counts.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -27,14 +67,470 @@
 //! ```
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use directories::ProjectDirs;
-use rusqlite::{params, Connection, OptionalExtension};
-use scryforge_provider_core::{Item, ItemId, Stream, StreamId};
-use std::collections::HashMap;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use scryforge_provider_core::crypto::{StoreCipher, SEALED_PREFIX};
+use scryforge_provider_core::{
+    Item, ItemId, Stream, StreamId, LAZY_BODY_METADATA_KEY, REMOVED_METADATA_KEY,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::Mutex;
-use tracing::{debug, info, warn};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::audit::{AuditEntry, MAX_AUDIT_ENTRIES};
+use crate::changes::{diff_items, ItemChange, CHANGED_AT_KEY, MAX_CHANGES_PER_ITEM};
+use crate::config::{CacheConfig, ConflictStrategy, EvictionPolicy, StreamPolicy};
+use crate::contacts::{self, Contact, SeenAddress};
+use crate::outbox::{OutboxAction, OutboxEntry};
+use crate::reading_time::{estimated_minutes, READING_MINUTES_KEY};
+use crate::relations::{ItemLinks, RelatedItem, Relation};
+use crate::search::SearchQuery;
+
+// ============================================================================
+// Interaction Types
+// ============================================================================
+
+/// A user interaction with an item, recorded for relevance ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionKind {
+    /// The item was opened or read
+    Opened,
+    /// The item was saved
+    Saved,
+    /// The item was dismissed without being read
+    Skipped,
+}
+
+impl InteractionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InteractionKind::Opened => "opened",
+            InteractionKind::Saved => "saved",
+            InteractionKind::Skipped => "skipped",
+        }
+    }
+}
+
+/// Interaction totals for one stream or author.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InteractionCounts {
+    pub opened: u32,
+    pub saved: u32,
+    pub skipped: u32,
+}
+
+impl InteractionCounts {
+    fn add(&mut self, kind: &str, count: u32) {
+        match kind {
+            "opened" => self.opened += count,
+            "saved" => self.saved += count,
+            "skipped" => self.skipped += count,
+            _ => {}
+        }
+    }
+}
+
+/// Interaction history aggregated per stream and per author name.
+#[derive(Debug, Clone, Default)]
+pub struct InteractionStats {
+    pub by_stream: HashMap<String, InteractionCounts>,
+    pub by_author: HashMap<String, InteractionCounts>,
+}
+
+// ============================================================================
+// Conflict Types
+// ============================================================================
+
+/// An item field that can change both in Scryforge and at the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateField {
+    Read,
+    Saved,
+}
+
+/// The side whose value was kept when a conflict was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictWinner {
+    Local,
+    Remote,
+}
+
+/// A field that changed locally and remotely since the last merge, with
+/// the value each side had and which one was kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateConflict {
+    pub item_id: ItemId,
+    pub field: StateField,
+    pub local: bool,
+    pub remote: bool,
+    pub winner: ConflictWinner,
+    pub strategy: ConflictStrategy,
+    pub resolved_at: DateTime<Utc>,
+}
+
+/// Merge one bidirectional field, returning the value to keep and, if both
+/// sides changed it to different values, which side won.
+///
+/// A local change counts if it was made after the last merge; a remote
+/// change counts if the provider reports a different value than it did at
+/// the last merge.
+pub fn resolve_field(
+    strategy: ConflictStrategy,
+    local: bool,
+    local_changed_at: Option<DateTime<Utc>>,
+    last_remote: Option<bool>,
+    merged_at: Option<DateTime<Utc>>,
+    remote: bool,
+    remote_updated: Option<DateTime<Utc>>,
+) -> (bool, Option<ConflictWinner>) {
+    if remote == local || remote == last_remote.unwrap_or(local) {
+        return (local, None);
+    }
+
+    let local_changed = match (local_changed_at, merged_at) {
+        (Some(changed), Some(merged)) => changed > merged,
+        (changed, _) => changed.is_some(),
+    };
+    if !local_changed {
+        return (remote, None);
+    }
+
+    let winner = match strategy {
+        ConflictStrategy::RemoteWins => ConflictWinner::Remote,
+        ConflictStrategy::LocalWins => ConflictWinner::Local,
+        ConflictStrategy::LatestTimestamp => match (local_changed_at, remote_updated) {
+            (Some(local_at), Some(remote_at)) if local_at > remote_at => ConflictWinner::Local,
+            _ => ConflictWinner::Remote,
+        },
+    };
+    let value = match winner {
+        ConflictWinner::Local => local,
+        ConflictWinner::Remote => remote,
+    };
+    (value, Some(winner))
+}
+
+// ============================================================================
+// Reminder Types
+// ============================================================================
+
+/// A time at which the user asked to be reminded of an item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub item_id: ItemId,
+    pub remind_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    /// When the desktop notification went out; `None` until the reminder fires
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+impl Reminder {
+    /// Whether the reminder time has passed at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.remind_at <= now
+    }
+}
+
+// ============================================================================
+// Saved Search Types
+// ============================================================================
+
+/// Metadata key holding the saved search name on items from
+/// [`Cache::get_alert_items`].
+pub const ALERT_NAME_KEY: &str = "alert_name";
+
+/// Metadata key holding when an item from [`Cache::get_alert_items`] matched.
+pub const ALERT_MATCHED_AT_KEY: &str = "alert_matched_at";
+
+/// A search query saved as an alert, re-run after syncs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: i64,
+    /// Unique name, shown with its matches
+    pub name: String,
+    /// Query in the syntax of [`crate::search`]
+    pub query: String,
+    /// Whether new matches raise a desktop notification
+    pub notify: bool,
+    /// Least time between runs, in minutes; `None` runs after every sync
+    pub interval_minutes: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+impl SavedSearch {
+    /// Whether the search should run again at `now`.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        match (self.interval_minutes, self.last_run_at) {
+            (Some(minutes), Some(last_run_at)) => {
+                now - last_run_at >= chrono::Duration::minutes(minutes as i64)
+            }
+            _ => true,
+        }
+    }
+}
+
+// ============================================================================
+// Pin Types
+// ============================================================================
+
+/// Metadata key holding the pin time on items from [`Cache::get_pinned_items`].
+pub const PINNED_AT_KEY: &str = "pinned_at";
+
+// ============================================================================
+// Annotation Types
+// ============================================================================
+
+/// Metadata keys the daemon writes with [`Cache::set_item_metadata`], kept
+/// when a sync replaces the rest of an item's metadata.
+pub const LOCAL_METADATA_KEYS: &[&str] = &[
+    crate::summarize::SUMMARY_KEY,
+    crate::summarize::TRANSCRIPT_KEY,
+    crate::takeout::WATCHED_AT_KEY,
+    crate::takeout::PLAYLISTS_KEY,
+];
+
+// ============================================================================
+// Tombstone Types
+// ============================================================================
+
+/// Days a tombstone is kept after its item was removed.
+pub const TOMBSTONE_DAYS: i64 = 90;
+
+/// An item removed at its source, remembered so a later sync doesn't bring
+/// it back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub item_id: ItemId,
+    pub stream_id: StreamId,
+    /// `deleted` for items deleted through the daemon, otherwise the reason
+    /// the provider gave in [`REMOVED_METADATA_KEY`] metadata
+    pub reason: String,
+    pub removed_at: DateTime<Utc>,
+    /// Whether the cached copy is kept, stamped with the reason
+    pub kept: bool,
+}
+
+/// A place in the order items changed in the cache, as
+/// [`Cache::changed_items`] returns them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeCursor {
+    /// When the item's row changed, in SQLite `datetime` format
+    pub changed_at: String,
+    pub item_id: String,
+}
+
+// ============================================================================
+// Reading Queue Types
+// ============================================================================
+
+/// A saved item in the reading queue, from [`Cache::get_reading_queue`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedItem {
+    pub item: Item,
+    /// Where the reader left off, in the units of the client that recorded
+    /// it; the TUI counts preview lines
+    pub offset: u32,
+    /// When the reader last recorded an offset
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+// ============================================================================
+// Spam Types
+// ============================================================================
+
+/// A message the user marked as spam or as not spam.
+///
+/// Verdicts outlive their items, so a local filter can be trained on them
+/// long after the messages are evicted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpamVerdict {
+    pub item_id: ItemId,
+    pub spam: bool,
+    /// Sender address, if the message had one
+    pub sender: Option<String>,
+    pub subject: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Playback Types
+// ============================================================================
+
+/// How far into an item's video or audio the user got.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackProgress {
+    pub item_id: ItemId,
+    /// Position playback stopped at, in seconds
+    pub position_secs: u64,
+    pub duration_secs: Option<u64>,
+    /// Whether the item was played to the end
+    pub finished: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Retention Types
+// ============================================================================
+
+/// How often [`spawn_eviction`] applies the retention limits.
+pub const EVICTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Items are deleted this many at a time until the database fits `max_size_mb`.
+const SIZE_EVICTION_BATCH: u32 = 200;
+
+/// Items that eviction may delete.
+const EVICTABLE: &str = "is_saved = 0 AND is_annotated = 0
+    AND id NOT IN (SELECT item_id FROM reminders)
+    AND id NOT IN (SELECT item_id FROM pins)
+    AND id NOT IN (SELECT item_id FROM outbox)
+    AND id NOT IN (SELECT item_id FROM playback WHERE finished = 0)";
+
+/// The item columns read by `SqliteCache::row_to_item`, in order.
+const ITEM_COLUMNS: &str = "id, stream_id, title, content_type, content_data,
+    author_name, author_email, author_url, author_avatar_url,
+    published, updated, url, thumbnail_url, is_read, is_saved,
+    tags, metadata";
+
+/// Items removed by one eviction pass, by the limit that removed them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvictionSummary {
+    pub too_old: usize,
+    pub over_stream_limit: usize,
+    pub over_size: usize,
+    /// Unread items marked read by a `mark_read_after_days` policy; not
+    /// counted in [`total`](Self::total)
+    pub marked_read: usize,
+}
+
+impl EvictionSummary {
+    /// Total items removed.
+    pub fn total(&self) -> usize {
+        self.too_old + self.over_stream_limit + self.over_size
+    }
+}
+
+/// Number of cached items in one stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamItemCount {
+    pub stream_id: String,
+    pub items: u64,
+}
+
+/// Size and contents of the cache.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// Bytes in use by the database; counted against `max_size_mb`
+    pub size_bytes: u64,
+    /// Size of the database file, including free pages
+    pub file_bytes: u64,
+    pub total_items: u64,
+    pub unread_items: u64,
+    pub saved_items: u64,
+    /// Items with locally added metadata, which are never evicted
+    pub annotated_items: u64,
+    /// Item counts per stream, largest first
+    pub streams: Vec<StreamItemCount>,
+}
+
+/// Reading activity in one stream over a reporting window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamReadingStats {
+    pub stream_id: String,
+    /// Stream name, if the stream is still cached
+    pub name: Option<String>,
+    pub provider_id: String,
+    /// Items that arrived in the window
+    pub received: u64,
+    /// Items first read in the window, whenever they arrived
+    pub read: u64,
+    /// Items first saved in the window
+    pub saved: u64,
+    /// Mean seconds from arrival to first read of the items read
+    pub avg_secs_to_read: Option<u64>,
+    /// Unread items in the cache now
+    pub unread: u64,
+}
+
+/// Reading activity summed over the streams of one provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderReadingStats {
+    pub provider_id: String,
+    pub received: u64,
+    pub read: u64,
+    pub saved: u64,
+    pub avg_secs_to_read: Option<u64>,
+    pub unread: u64,
+}
+
+/// Items received and read on one day (UTC).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyReadingStats {
+    pub date: NaiveDate,
+    pub received: u64,
+    pub read: u64,
+}
+
+/// Items received, read, and saved since a point in time.
+///
+/// Counts come from a ledger kept apart from the items, so they include
+/// items evicted since. Items marked read by a `mark_read_after_days`
+/// policy are not counted as read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadingStats {
+    pub since: DateTime<Utc>,
+    /// Streams with activity in the window, busiest first
+    pub streams: Vec<StreamReadingStats>,
+    /// Per-provider totals, busiest first
+    pub providers: Vec<ProviderReadingStats>,
+    /// One entry per day with activity, oldest first
+    pub days: Vec<DailyReadingStats>,
+}
+
+/// Apply `retention` and the per-stream `streams` policies now and then
+/// every [`EVICTION_INTERVAL`] until the task is aborted.
+pub fn spawn_eviction<C: Cache + 'static>(
+    cache: Arc<C>,
+    retention: CacheConfig,
+    streams: HashMap<String, StreamPolicy>,
+) -> JoinHandle<()> {
+    let (retention, streams) = (Arc::new(retention), Arc::new(streams));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(EVICTION_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let (cache, retention, streams) = (
+                Arc::clone(&cache),
+                Arc::clone(&retention),
+                Arc::clone(&streams),
+            );
+            let evicted = tokio::task::spawn_blocking(move || cache.evict(&retention, &streams))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|result| result);
+            match evicted {
+                Ok(summary) if summary.marked_read > 0 || summary.total() > 0 => info!(
+                    "Evicted {} cached items ({} too old, {} over the per-stream limit, {} over the size limit), marked {} read",
+                    summary.total(),
+                    summary.too_old,
+                    summary.over_stream_limit,
+                    summary.over_size,
+                    summary.marked_read
+                ),
+                Ok(_) => debug!("Cache is within its retention limits"),
+                Err(e) => error!("Cache eviction failed: {:#}", e),
+            }
+        }
+    })
+}
 
 // ============================================================================
 // Cache Trait
@@ -45,15 +541,31 @@ pub trait Cache: Send + Sync {
     /// Get all streams, optionally filtered by provider ID.
     fn get_streams(&self, provider_id: Option<&str>) -> Result<Vec<Stream>>;
 
-    /// Get items for a specific stream.
+    /// Get items for a specific stream, pinned items first, then newest
+    /// first.
     fn get_items(&self, stream_id: &StreamId, limit: Option<u32>) -> Result<Vec<Item>>;
 
+    /// Get a single item by ID.
+    fn get_item(&self, item_id: &ItemId) -> Result<Option<Item>>;
+
     /// Insert or update multiple streams in the cache.
     fn upsert_streams(&self, streams: &[Stream]) -> Result<()>;
 
     /// Insert or update multiple items in the cache.
+    ///
+    /// Existing items keep their cached read and saved state.
     fn upsert_items(&self, items: &[Item]) -> Result<()>;
 
+    /// Insert or update items fetched from a provider, merging their read
+    /// and saved state with changes made locally since the last merge.
+    ///
+    /// Returns the conflicts resolved with `strategy`, which are also logged.
+    fn merge_items(&self, items: &[Item], strategy: ConflictStrategy)
+        -> Result<Vec<StateConflict>>;
+
+    /// Get the most recently resolved conflicts, newest first.
+    fn get_conflicts(&self, limit: u32) -> Result<Vec<StateConflict>>;
+
     /// Mark an item as read or unread.
     fn mark_read(&self, item_id: &ItemId, is_read: bool) -> Result<()>;
 
@@ -63,17 +575,216 @@ pub trait Cache: Send + Sync {
     /// Mark an item as archived or unarchived.
     fn mark_archived(&self, item_id: &ItemId, is_archived: bool) -> Result<()>;
 
+    /// Remove an item, and any reminder on it, from the cache, leaving a
+    /// tombstone so a sync that still lists it doesn't bring it back.
+    ///
+    /// For items deleted at the source. Returns whether the item was cached.
+    fn delete_item(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Set a single metadata entry on a cached item, keeping the others.
+    ///
+    /// The item is marked as annotated and is no longer evicted. `key` should
+    /// be one of [`LOCAL_METADATA_KEYS`], or the next sync drops it.
+    fn set_item_metadata(&self, item_id: &ItemId, key: &str, value: &str) -> Result<()>;
+
     /// Get the last sync timestamp for a provider.
     fn get_sync_state(&self, provider_id: &str) -> Result<Option<DateTime<Utc>>>;
 
     /// Update the last sync timestamp for a provider.
     fn update_sync_state(&self, provider_id: &str, last_sync: DateTime<Utc>) -> Result<()>;
 
+    /// Record that the user interacted with an item.
+    fn record_interaction(&self, item_id: &ItemId, kind: InteractionKind) -> Result<()>;
+
+    /// Get interaction history aggregated per stream and per author.
+    fn get_interaction_stats(&self) -> Result<InteractionStats>;
+
+    /// Set a reminder on a cached item, replacing any earlier one.
+    fn set_reminder(&self, item_id: &ItemId, remind_at: DateTime<Utc>) -> Result<()>;
+
+    /// Remove an item's reminder. Returns whether there was one.
+    fn remove_reminder(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Get all reminders, soonest first.
+    fn get_reminders(&self) -> Result<Vec<Reminder>>;
+
+    /// Record that the notification for an item's reminder was sent.
+    fn mark_reminder_notified(&self, item_id: &ItemId, notified_at: DateTime<Utc>) -> Result<()>;
+
+    /// Save a search as an alert. Fails if the name is taken.
+    fn create_saved_search(
+        &self,
+        name: &str,
+        query: &str,
+        notify: bool,
+        interval_minutes: Option<u32>,
+    ) -> Result<SavedSearch>;
+
+    /// Get all saved searches, oldest first.
+    fn get_saved_searches(&self) -> Result<Vec<SavedSearch>>;
+
+    /// Delete a saved search and its matches. Returns whether it existed.
+    fn delete_saved_search(&self, id: i64) -> Result<bool>;
+
+    /// Record that a saved search ran at `ran_at`.
+    fn mark_saved_search_run(&self, id: i64, ran_at: DateTime<Utc>) -> Result<()>;
+
+    /// Record that an item matched a saved search. Items first cached before
+    /// the search was saved are not recorded.
+    ///
+    /// Returns whether this is a new match.
+    fn record_alert_match(&self, search_id: i64, item_id: &ItemId) -> Result<bool>;
+
+    /// Get up to `limit` items that matched saved searches, or one saved
+    /// search, most recent match first, each with the search name in
+    /// [`ALERT_NAME_KEY`] and the match time in [`ALERT_MATCHED_AT_KEY`]
+    /// metadata.
+    fn get_alert_items(&self, search_id: Option<i64>, limit: u32) -> Result<Vec<Item>>;
+
+    /// Pin a cached item to the top of its stream. Pinning it again keeps
+    /// the original pin time.
+    fn pin_item(&self, item_id: &ItemId) -> Result<()>;
+
+    /// Unpin an item. Returns whether it was pinned.
+    fn unpin_item(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Get pinned items across all streams, most recently pinned first, each
+    /// with its pin time in [`PINNED_AT_KEY`] metadata.
+    fn get_pinned_items(&self) -> Result<Vec<Item>>;
+
+    /// Get the reading queue: saved items from every provider that haven't
+    /// been finished, in the order the user arranged them.
+    ///
+    /// Items saved since the last call join at the end, oldest first, and
+    /// items no longer saved leave the queue.
+    fn get_reading_queue(&self) -> Result<Vec<QueuedItem>>;
+
+    /// Move a queued item to `index` (0-based) in the queue, or to the end
+    /// when `index` is past it. Returns whether the item is queued.
+    fn move_queued_item(&self, item_id: &ItemId, index: u32) -> Result<bool>;
+
+    /// Record where the reader left off in a queued item. Returns whether the
+    /// item is queued.
+    fn set_queue_offset(&self, item_id: &ItemId, offset: u32) -> Result<bool>;
+
+    /// Take a read item out of the reading queue; it stays saved. Returns
+    /// whether it was queued.
+    fn finish_queued_item(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Queue a provider action on a cached item in the outbox.
+    fn queue_outbox(&self, item_id: &ItemId, action: OutboxAction) -> Result<()>;
+
+    /// Remove an entry from the outbox. Returns whether it was there.
+    fn remove_outbox(&self, id: i64) -> Result<bool>;
+
+    /// Get the outbox, oldest entry first.
+    fn get_outbox(&self) -> Result<Vec<OutboxEntry>>;
+
+    /// Record a failed attempt to send an outbox entry.
+    fn record_outbox_failure(&self, id: i64, error: &str) -> Result<()>;
+
+    /// Record a mutation made on a provider in the audit log; `error` is
+    /// set when it failed. The oldest entries beyond [`MAX_AUDIT_ENTRIES`]
+    /// are dropped.
+    fn record_audit(
+        &self,
+        provider_id: &str,
+        item_id: Option<&ItemId>,
+        action: &str,
+        error: Option<&str>,
+    ) -> Result<()>;
+
+    /// Get up to `limit` audit log entries, newest first, optionally for one
+    /// provider.
+    fn get_audit_log(&self, provider_id: Option<&str>, limit: u32) -> Result<Vec<AuditEntry>>;
+
+    /// Get up to `limit` recorded changes to an item, newest first.
+    fn get_item_changes(&self, item_id: &ItemId, limit: u32) -> Result<Vec<ItemChange>>;
+
+    /// Clear an item's [`CHANGED_AT_KEY`] stamp once its changes have been
+    /// seen; the changes stay recorded. Returns whether it was stamped.
+    fn acknowledge_changes(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Get up to `limit` tombstones of items removed at their source,
+    /// newest first. Tombstones are kept for [`TOMBSTONE_DAYS`].
+    fn get_tombstones(&self, limit: u32) -> Result<Vec<Tombstone>>;
+
+    /// Whether an item was removed at its source and must not be cached
+    /// again.
+    fn is_tombstoned(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Forget that an item was removed, so the next sync that lists it
+    /// caches it again. Returns whether it had a tombstone.
+    fn restore_item(&self, item_id: &ItemId) -> Result<bool>;
+
+    /// Record that a cached item was marked spam, or not spam, replacing an
+    /// earlier verdict on it. Returns whether the item exists.
+    fn record_spam_verdict(&self, item_id: &ItemId, spam: bool) -> Result<bool>;
+
+    /// Get up to `limit` spam verdicts, newest first.
+    fn get_spam_verdicts(&self, limit: u32) -> Result<Vec<SpamVerdict>>;
+
+    /// Record the text of an attachment of a cached item, replacing earlier
+    /// text for it, so [`Cache::search_items`] matches the item by it.
+    ///
+    /// The text is stored in the clear, so nothing is recorded for encrypted
+    /// items. Returns whether it was recorded.
+    fn set_attachment_text(
+        &self,
+        item_id: &ItemId,
+        attachment_id: &str,
+        file_name: &str,
+        text: &str,
+    ) -> Result<bool>;
+
+    /// Record playback progress on a cached item, replacing the earlier one.
+    ///
+    /// Items partway through are not evicted until they are finished.
+    fn set_playback_progress(&self, progress: &PlaybackProgress) -> Result<()>;
+
+    /// Get the recorded playback progress of an item, if it was ever played.
+    fn get_playback_progress(&self, item_id: &ItemId) -> Result<Option<PlaybackProgress>>;
+
+    /// Delete items beyond the retention limits in `retention`, or in the
+    /// policy of their stream in `streams`, never touching saved, pinned,
+    /// annotated, reminded, or partly played items. Also marks items read as the
+    /// policies' `mark_read_after_days` ask, and drops tombstones older than
+    /// [`TOMBSTONE_DAYS`]. The interactions and state conflicts of deleted
+    /// items go with them.
+    ///
+    /// Blocks on SQLite, including a `VACUUM` after size eviction.
+    fn evict(
+        &self,
+        retention: &CacheConfig,
+        streams: &HashMap<String, StreamPolicy>,
+    ) -> Result<EvictionSummary>;
+
+    /// Get the size of the cache and the number of items it holds.
+    fn get_cache_stats(&self) -> Result<CacheStats>;
+
+    /// Get the items received, read, and saved since `since`, per stream,
+    /// per provider, and per day.
+    fn get_reading_stats(&self, since: DateTime<Utc>) -> Result<ReadingStats>;
+
+    /// Get up to `limit` items linked to an item by URL: those pointing at
+    /// the same page first, then those it links to, then those linking to
+    /// it. Newest first within each relation.
+    fn get_related(&self, item_id: &ItemId, limit: u32) -> Result<Vec<RelatedItem>>;
+
+    /// Get up to `limit` contacts whose address, name, or a word of their
+    /// name starts with `prefix`, case-insensitively. Those seen in the most
+    /// messages come first, then the most recently seen.
+    fn complete_contacts(&self, prefix: &str, limit: u32) -> Result<Vec<Contact>>;
+
+    /// Replace the imported address book with `contacts`, returning how many
+    /// distinct addresses it now holds.
+    fn import_contacts(&self, contacts: &[SeenAddress]) -> Result<usize>;
+
     /// Search for items matching a query and optional filters.
     ///
     /// # Arguments
     ///
-    /// * `query` - The search text (searched in title, content)
+    /// * `query` - The search text (searched in title, content), as one phrase
     /// * `stream_id` - Optional stream ID to filter by
     /// * `content_type` - Optional content type to filter by
     /// * `is_read` - Optional read status filter
@@ -86,6 +797,21 @@ pub trait Cache: Send + Sync {
         is_read: Option<bool>,
         is_saved: Option<bool>,
     ) -> Result<Vec<Item>>;
+
+    /// Search for items matching a parsed query, newest first.
+    fn search(&self, query: &SearchQuery) -> Result<Vec<Item>>;
+
+    /// Get up to `limit` items whose rows changed after `after`, in the
+    /// order they changed, each with its [`ChangeCursor`]. Pass the last
+    /// cursor back to get the next page.
+    ///
+    /// Encrypted items are left out, so their text is never copied out of
+    /// the cache.
+    fn changed_items(
+        &self,
+        after: Option<&ChangeCursor>,
+        limit: u32,
+    ) -> Result<Vec<(Item, ChangeCursor)>>;
 }
 
 // ============================================================================
@@ -98,6 +824,8 @@ pub trait Cache: Send + Sync {
 /// and to satisfy the `Sync` trait requirement.
 pub struct SqliteCache {
     conn: Mutex<Connection>,
+    cipher: Option<StoreCipher>,
+    encrypted_providers: HashSet<String>,
 }
 
 impl SqliteCache {
@@ -131,12 +859,106 @@ impl SqliteCache {
 
         let cache = Self {
             conn: Mutex::new(conn),
+            cipher: None,
+            encrypted_providers: HashSet::new(),
         };
         cache.run_migrations()?;
 
         Ok(cache)
     }
 
+    /// Open sealed items with `cipher`, and seal the items of `providers`
+    /// when they are written.
+    pub fn with_encryption(
+        mut self,
+        cipher: StoreCipher,
+        providers: impl IntoIterator<Item = String>,
+    ) -> Self {
+        self.cipher = Some(cipher);
+        self.encrypted_providers = providers.into_iter().collect();
+        self
+    }
+
+    /// Rewrite cached items whose encryption no longer matches their
+    /// provider's setting, returning how many were rewritten.
+    ///
+    /// Sealed items can only be opened while a cipher is configured; any
+    /// left sealed are reported and rewritten at their next sync.
+    pub fn apply_encryption(&self) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let mismatched: Vec<(String, bool)> = tx
+            .prepare(
+                "SELECT items.id, streams.provider_id, items.title
+                 FROM items JOIN streams ON streams.id = items.stream_id",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .filter_map(|row| match row {
+                Ok((id, provider_id, title)) => {
+                    let seal = self.encrypted_providers.contains(&provider_id);
+                    (StoreCipher::is_sealed(&title) != seal).then_some(Ok((id, seal)))
+                }
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<rusqlite::Result<_>>()?;
+
+        let cipher = self.cipher.as_ref();
+        if cipher.is_none() && !mismatched.is_empty() {
+            warn!(
+                "{} cached items are encrypted but no encryption key is loaded",
+                mismatched.len()
+            );
+            return Ok(0);
+        }
+
+        for (id, seal) in &mismatched {
+            let item = tx.query_row(
+                &format!("SELECT {} FROM items WHERE id = ?", ITEM_COLUMNS),
+                params![id],
+                |row| Self::row_to_item(row, cipher),
+            )?;
+            Self::write_item(&tx, &item, cipher.filter(|_| *seal))?;
+        }
+
+        tx.commit()?;
+        if !mismatched.is_empty() {
+            info!("Updated encryption of {} cached items", mismatched.len());
+        }
+        Ok(mismatched.len())
+    }
+
+    /// The cipher to seal items of `stream_id` with, if its provider is encrypted.
+    fn sealing_cipher(
+        &self,
+        conn: &Connection,
+        stream_id: &StreamId,
+    ) -> Result<Option<&StoreCipher>> {
+        let Some(cipher) = self.cipher.as_ref() else {
+            return Ok(None);
+        };
+        if self.encrypted_providers.is_empty() {
+            return Ok(None);
+        }
+
+        let provider_id: Option<String> = conn
+            .query_row(
+                "SELECT provider_id FROM streams WHERE id = ?",
+                params![stream_id.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(provider_id
+            .filter(|id| self.encrypted_providers.contains(id))
+            .map(|_| cipher))
+    }
+
     /// Get the default database path using XDG directories.
     fn default_db_path() -> Result<PathBuf> {
         let project_dirs = ProjectDirs::from("com", "raibid-labs", "scryforge")
@@ -181,6 +1003,78 @@ impl SqliteCache {
             self.migrate_to_v2()?;
         }
 
+        if current_version < 3 {
+            self.migrate_to_v3()?;
+        }
+
+        if current_version < 4 {
+            self.migrate_to_v4()?;
+        }
+
+        if current_version < 5 {
+            self.migrate_to_v5()?;
+        }
+
+        if current_version < 6 {
+            self.migrate_to_v6()?;
+        }
+
+        if current_version < 7 {
+            self.migrate_to_v7()?;
+        }
+
+        if current_version < 8 {
+            self.migrate_to_v8()?;
+        }
+
+        if current_version < 9 {
+            self.migrate_to_v9()?;
+        }
+
+        if current_version < 10 {
+            self.migrate_to_v10()?;
+        }
+
+        if current_version < 11 {
+            self.migrate_to_v11()?;
+        }
+
+        if current_version < 12 {
+            self.migrate_to_v12()?;
+        }
+
+        if current_version < 13 {
+            self.migrate_to_v13()?;
+        }
+
+        if current_version < 14 {
+            self.migrate_to_v14()?;
+        }
+
+        if current_version < 15 {
+            self.migrate_to_v15()?;
+        }
+
+        if current_version < 16 {
+            self.migrate_to_v16()?;
+        }
+
+        if current_version < 17 {
+            self.migrate_to_v17()?;
+        }
+
+        if current_version < 18 {
+            self.migrate_to_v18()?;
+        }
+
+        if current_version < 19 {
+            self.migrate_to_v19()?;
+        }
+
+        if current_version < 20 {
+            self.migrate_to_v20()?;
+        }
+
         Ok(())
     }
 
@@ -321,546 +1215,3135 @@ impl SqliteCache {
         Ok(())
     }
 
-    /// Serialize metadata HashMap to JSON string.
-    fn serialize_metadata(metadata: &HashMap<String, String>) -> Result<String> {
-        serde_json::to_string(metadata).context("Failed to serialize metadata")
-    }
+    /// Migration to version 3: Interaction log for relevance ranking.
+    ///
+    /// Rows copy the stream and author from the item so ranking doesn't need
+    /// to join on items. Eviction deletes them with their item.
+    fn migrate_to_v3(&self) -> Result<()> {
+        info!("Running migration to schema version 3");
 
-    /// Deserialize metadata from JSON string.
-    fn deserialize_metadata(json: &str) -> Result<HashMap<String, String>> {
-        serde_json::from_str(json).context("Failed to deserialize metadata")
-    }
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-    /// Serialize tags Vec to JSON string.
-    fn serialize_tags(tags: &[String]) -> Result<String> {
-        serde_json::to_string(tags).context("Failed to serialize tags")
-    }
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS interactions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                stream_id TEXT NOT NULL,
+                author_name TEXT,
+                kind TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )
+        .context("Failed to create interactions table")?;
 
-    /// Deserialize tags from JSON string.
-    fn deserialize_tags(json: &str) -> Result<Vec<String>> {
-        serde_json::from_str(json).context("Failed to deserialize tags")
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_interactions_stream
+             ON interactions(stream_id)",
+            [],
+        )?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (3)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 3");
+        Ok(())
     }
-}
 
-impl Cache for SqliteCache {
-    fn get_streams(&self, provider_id: Option<&str>) -> Result<Vec<Stream>> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 4: Bookkeeping for merging read/saved state.
+    ///
+    /// `remote_is_read` and `remote_is_saved` hold the provider's values at
+    /// the last merge, and the `*_changed_at` columns the time of the last
+    /// local change, so a merge can tell which side changed.
+    fn migrate_to_v4(&self) -> Result<()> {
+        info!("Running migration to schema version 4");
 
-        let streams = if let Some(provider) = provider_id {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, provider_id, stream_type, icon, unread_count,
-                        total_count, last_updated, metadata
-                 FROM streams
-                 WHERE provider_id = ?
-                 ORDER BY name",
-            )?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-            let result = stmt
-                .query_map([provider], |row| {
-                    let id: String = row.get(0)?;
-                    let name: String = row.get(1)?;
-                    let provider_id: String = row.get(2)?;
-                    let stream_type_str: String = row.get(3)?;
-                    let icon: Option<String> = row.get(4)?;
-                    let unread_count: Option<u32> = row.get(5)?;
-                    let total_count: Option<u32> = row.get(6)?;
-                    let last_updated: Option<String> = row.get(7)?;
-                    let metadata_json: String = row.get(8)?;
+        for column in [
+            "remote_is_read INTEGER",
+            "remote_is_saved INTEGER",
+            "read_changed_at TEXT",
+            "saved_changed_at TEXT",
+            "merged_at TEXT",
+        ] {
+            tx.execute(&format!("ALTER TABLE items ADD COLUMN {}", column), [])
+                .with_context(|| format!("Failed to add column {}", column))?;
+        }
 
-                    let stream_type = match stream_type_str.as_str() {
-                        "Feed" => scryforge_provider_core::StreamType::Feed,
-                        "Collection" => scryforge_provider_core::StreamType::Collection,
-                        "SavedItems" => scryforge_provider_core::StreamType::SavedItems,
-                        "Community" => scryforge_provider_core::StreamType::Community,
-                        other => scryforge_provider_core::StreamType::Custom(other.to_string()),
-                    };
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS state_conflicts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                field TEXT NOT NULL,
+                local_value INTEGER NOT NULL,
+                remote_value INTEGER NOT NULL,
+                winner TEXT NOT NULL,
+                strategy TEXT NOT NULL,
+                resolved_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create state_conflicts table")?;
 
-                    let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
-                        rusqlite::Error::FromSqlConversionFailure(
-                            8,
-                            rusqlite::types::Type::Text,
-                            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
-                        )
-                    })?;
+        tx.execute("INSERT INTO schema_version (version) VALUES (4)", [])
+            .context("Failed to update schema version")?;
 
-                    let last_updated = last_updated
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc));
+        tx.commit()?;
 
-                    Ok(Stream {
-                        id: StreamId(id),
-                        name,
-                        provider_id,
-                        stream_type,
-                        icon,
-                        unread_count,
-                        total_count,
-                        last_updated,
-                        metadata,
-                    })
-                })?
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            result
-        } else {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, provider_id, stream_type, icon, unread_count,
-                        total_count, last_updated, metadata
-                 FROM streams
-                 ORDER BY provider_id, name",
-            )?;
+        info!("Successfully migrated to schema version 4");
+        Ok(())
+    }
 
-            let result = stmt
-                .query_map([], |row| {
-                    let id: String = row.get(0)?;
-                    let name: String = row.get(1)?;
-                    let provider_id: String = row.get(2)?;
-                    let stream_type_str: String = row.get(3)?;
-                    let icon: Option<String> = row.get(4)?;
-                    let unread_count: Option<u32> = row.get(5)?;
-                    let total_count: Option<u32> = row.get(6)?;
-                    let last_updated: Option<String> = row.get(7)?;
-                    let metadata_json: String = row.get(8)?;
+    /// Migration to version 5: Access times and annotation flags for eviction.
+    ///
+    /// Items that already carry a generated summary count as annotated.
+    fn migrate_to_v5(&self) -> Result<()> {
+        info!("Running migration to schema version 5");
 
-                    let stream_type = match stream_type_str.as_str() {
-                        "Feed" => scryforge_provider_core::StreamType::Feed,
-                        "Collection" => scryforge_provider_core::StreamType::Collection,
-                        "SavedItems" => scryforge_provider_core::StreamType::SavedItems,
-                        "Community" => scryforge_provider_core::StreamType::Community,
-                        other => scryforge_provider_core::StreamType::Custom(other.to_string()),
-                    };
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-                    let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
-                        rusqlite::Error::FromSqlConversionFailure(
-                            8,
-                            rusqlite::types::Type::Text,
-                            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
-                        )
-                    })?;
+        tx.execute("ALTER TABLE items ADD COLUMN accessed_at TEXT", [])
+            .context("Failed to add accessed_at column")?;
+        tx.execute(
+            "ALTER TABLE items ADD COLUMN is_annotated INTEGER NOT NULL DEFAULT 0",
+            [],
+        )
+        .context("Failed to add is_annotated column")?;
+        tx.execute(
+            "UPDATE items SET is_annotated = 1 WHERE json_extract(metadata, ?) IS NOT NULL",
+            [format!("$.{}", crate::summarize::SUMMARY_KEY)],
+        )?;
 
-                    let last_updated = last_updated
-                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                        .map(|dt| dt.with_timezone(&Utc));
+        tx.execute("INSERT INTO schema_version (version) VALUES (5)", [])
+            .context("Failed to update schema version")?;
 
-                    Ok(Stream {
-                        id: StreamId(id),
-                        name,
-                        provider_id,
-                        stream_type,
-                        icon,
-                        unread_count,
-                        total_count,
-                        last_updated,
-                        metadata,
-                    })
-                })?
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            result
-        };
+        tx.commit()?;
 
-        Ok(streams)
+        info!("Successfully migrated to schema version 5");
+        Ok(())
     }
 
-    fn get_items(&self, stream_id: &StreamId, limit: Option<u32>) -> Result<Vec<Item>> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 6: Item reminders.
+    ///
+    /// Reminders go with their item when it is deleted, though eviction
+    /// leaves reminded items alone.
+    fn migrate_to_v6(&self) -> Result<()> {
+        info!("Running migration to schema version 6");
 
-        let query = if limit.is_some() {
-            "SELECT id, stream_id, title, content_type, content_data,
-                    author_name, author_email, author_url, author_avatar_url,
-                    published, updated, url, thumbnail_url, is_read, is_saved,
-                    tags, metadata
-             FROM items
-             WHERE stream_id = ?
-             ORDER BY published DESC, created_at DESC
-             LIMIT ?"
-        } else {
-            "SELECT id, stream_id, title, content_type, content_data,
-                    author_name, author_email, author_url, author_avatar_url,
-                    published, updated, url, thumbnail_url, is_read, is_saved,
-                    tags, metadata
-             FROM items
-             WHERE stream_id = ?
-             ORDER BY published DESC, created_at DESC"
-        };
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        let mut stmt = conn.prepare(query)?;
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS reminders (
+                item_id TEXT PRIMARY KEY,
+                remind_at TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                notified_at TEXT,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create reminders table")?;
 
-        let items = if let Some(lim) = limit {
-            stmt.query_map(params![stream_id.as_str(), lim], Self::row_to_item)?
-        } else {
-            stmt.query_map(params![stream_id.as_str()], Self::row_to_item)?
-        };
+        tx.execute("INSERT INTO schema_version (version) VALUES (6)", [])
+            .context("Failed to update schema version")?;
 
-        items
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .context("Failed to fetch items from cache")
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 6");
+        Ok(())
     }
 
-    fn upsert_streams(&self, streams: &[Stream]) -> Result<()> {
+    /// Migration to version 7: Item links.
+    ///
+    /// Links are recorded for cached items here; sealed content is skipped
+    /// as it is when items are written.
+    fn migrate_to_v7(&self) -> Result<()> {
+        info!("Running migration to schema version 7");
+
         let mut conn = self.conn.lock().unwrap();
         let tx = conn.transaction()?;
 
-        for stream in streams {
-            let stream_type_str = match &stream.stream_type {
-                scryforge_provider_core::StreamType::Feed => "Feed",
-                scryforge_provider_core::StreamType::Collection => "Collection",
-                scryforge_provider_core::StreamType::SavedItems => "SavedItems",
-                scryforge_provider_core::StreamType::Community => "Community",
-                scryforge_provider_core::StreamType::Custom(s) => s.as_str(),
-            };
-
-            let metadata_json = Self::serialize_metadata(&stream.metadata)?;
-            let last_updated = stream.last_updated.map(|dt| dt.to_rfc3339());
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS item_links (
+                item_id TEXT NOT NULL,
+                url_key TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                PRIMARY KEY (item_id, url_key, kind),
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create item_links table")?;
 
-            tx.execute(
-                "INSERT INTO streams
-                    (id, name, provider_id, stream_type, icon, unread_count,
-                     total_count, last_updated, metadata, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, datetime('now'))
-                 ON CONFLICT(id) DO UPDATE SET
-                    name = excluded.name,
-                    stream_type = excluded.stream_type,
-                    icon = excluded.icon,
-                    unread_count = excluded.unread_count,
-                    total_count = excluded.total_count,
-                    last_updated = excluded.last_updated,
-                    metadata = excluded.metadata,
-                    updated_at = datetime('now')",
-                params![
-                    stream.id.as_str(),
-                    &stream.name,
-                    &stream.provider_id,
-                    stream_type_str,
-                    &stream.icon,
-                    stream.unread_count,
-                    stream.total_count,
-                    last_updated,
-                    metadata_json,
-                ],
-            )?;
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_item_links_url_key
+             ON item_links(url_key)",
+            [],
+        )
+        .context("Failed to create item_links index")?;
+
+        let items: Vec<(String, Option<String>, String)> = tx
+            .prepare("SELECT id, url, content_data FROM items")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (id, url, content_data) in &items {
+            let content_data = Some(content_data.as_str()).filter(|c| !StoreCipher::is_sealed(c));
+            Self::write_links(&tx, id, &ItemLinks::of(url.as_deref(), content_data))?;
         }
 
+        tx.execute("INSERT INTO schema_version (version) VALUES (7)", [])
+            .context("Failed to update schema version")?;
+
         tx.commit()?;
-        debug!("Upserted {} streams", streams.len());
+
+        info!("Successfully migrated to schema version 7");
         Ok(())
     }
 
-    fn upsert_items(&self, items: &[Item]) -> Result<()> {
+    /// Migration to version 8: Playback progress.
+    fn migrate_to_v8(&self) -> Result<()> {
+        info!("Running migration to schema version 8");
+
         let mut conn = self.conn.lock().unwrap();
         let tx = conn.transaction()?;
 
-        for item in items {
-            let (content_type, content_data) = Self::serialize_content(&item.content)?;
-            let tags_json = Self::serialize_tags(&item.tags)?;
-            let metadata_json = Self::serialize_metadata(&item.metadata)?;
-
-            let author_name = item.author.as_ref().map(|a| &a.name);
-            let author_email = item.author.as_ref().and_then(|a| a.email.as_ref());
-            let author_url = item.author.as_ref().and_then(|a| a.url.as_ref());
-            let author_avatar_url = item.author.as_ref().and_then(|a| a.avatar_url.as_ref());
-
-            let published = item.published.map(|dt| dt.to_rfc3339());
-            let updated = item.updated.map(|dt| dt.to_rfc3339());
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS playback (
+                item_id TEXT PRIMARY KEY,
+                position_secs INTEGER NOT NULL,
+                duration_secs INTEGER,
+                finished INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create playback table")?;
 
-            tx.execute(
-                "INSERT INTO items
-                    (id, stream_id, title, content_type, content_data,
-                     author_name, author_email, author_url, author_avatar_url,
-                     published, updated, url, thumbnail_url, is_read, is_saved,
-                     tags, metadata, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, datetime('now'))
-                 ON CONFLICT(id) DO UPDATE SET
-                    title = excluded.title,
-                    content_type = excluded.content_type,
-                    content_data = excluded.content_data,
-                    author_name = excluded.author_name,
-                    author_email = excluded.author_email,
-                    author_url = excluded.author_url,
-                    author_avatar_url = excluded.author_avatar_url,
-                    published = excluded.published,
-                    updated = excluded.updated,
-                    url = excluded.url,
-                    thumbnail_url = excluded.thumbnail_url,
-                    tags = excluded.tags,
-                    metadata = excluded.metadata,
-                    updated_at = datetime('now')",
-                params![
-                    item.id.as_str(),
-                    item.stream_id.as_str(),
-                    &item.title,
-                    content_type,
-                    content_data,
-                    author_name,
-                    author_email,
-                    author_url,
-                    author_avatar_url,
-                    published,
-                    updated,
-                    &item.url,
-                    &item.thumbnail_url,
-                    item.is_read as i32,
-                    item.is_saved as i32,
-                    tags_json,
-                    metadata_json,
-                ],
-            )?;
-        }
+        tx.execute("INSERT INTO schema_version (version) VALUES (8)", [])
+            .context("Failed to update schema version")?;
 
         tx.commit()?;
-        debug!("Upserted {} items", items.len());
+
+        info!("Successfully migrated to schema version 8");
         Ok(())
     }
 
-    fn mark_read(&self, item_id: &ItemId, is_read: bool) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 9: Reading statistics.
+    ///
+    /// `item_stats` has no foreign key so the history outlives eviction.
+    /// Items already cached are backfilled from their change timestamps.
+    fn migrate_to_v9(&self) -> Result<()> {
+        info!("Running migration to schema version 9");
 
-        let rows = conn.execute(
-            "UPDATE items SET is_read = ?, updated_at = datetime('now') WHERE id = ?",
-            params![is_read as i32, item_id.as_str()],
-        )?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        if rows == 0 {
-            warn!(
-                "Attempted to mark non-existent item as read: {}",
-                item_id.as_str()
-            );
-        }
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS item_stats (
+                item_id TEXT PRIMARY KEY,
+                stream_id TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                received_at TEXT NOT NULL,
+                read_at TEXT,
+                saved_at TEXT
+            )",
+            [],
+        )
+        .context("Failed to create item_stats table")?;
 
-        Ok(())
-    }
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_item_stats_stream
+             ON item_stats(stream_id)",
+            [],
+        )?;
 
-    fn mark_starred(&self, item_id: &ItemId, is_starred: bool) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        tx.execute(
+            "INSERT OR IGNORE INTO item_stats
+                (item_id, stream_id, provider_id, received_at, read_at, saved_at)
+             SELECT i.id, i.stream_id, s.provider_id,
+                    strftime('%Y-%m-%dT%H:%M:%S+00:00', i.created_at),
+                    CASE WHEN i.is_read THEN i.read_changed_at END,
+                    CASE WHEN i.is_saved THEN i.saved_changed_at END
+             FROM items i JOIN streams s ON s.id = i.stream_id",
+            [],
+        )
+        .context("Failed to backfill item_stats")?;
 
-        let rows = conn.execute(
-            "UPDATE items SET is_saved = ?, updated_at = datetime('now') WHERE id = ?",
-            params![is_starred as i32, item_id.as_str()],
-        )?;
+        tx.execute("INSERT INTO schema_version (version) VALUES (9)", [])
+            .context("Failed to update schema version")?;
 
-        if rows == 0 {
-            warn!(
-                "Attempted to mark non-existent item as starred: {}",
-                item_id.as_str()
-            );
-        }
+        tx.commit()?;
 
+        info!("Successfully migrated to schema version 9");
         Ok(())
     }
 
-    fn mark_archived(&self, item_id: &ItemId, is_archived: bool) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 10: Pinned items.
+    ///
+    /// Pins are local only; they never reach the provider, unlike saving.
+    fn migrate_to_v10(&self) -> Result<()> {
+        info!("Running migration to schema version 10");
 
-        let rows = conn.execute(
-            "UPDATE items SET is_archived = ?, updated_at = datetime('now') WHERE id = ?",
-            params![is_archived as i32, item_id.as_str()],
-        )?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        if rows == 0 {
-            warn!(
-                "Attempted to mark non-existent item as archived: {}",
-                item_id.as_str()
-            );
-        }
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS pins (
+                item_id TEXT PRIMARY KEY,
+                pinned_at TEXT NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create pins table")?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (10)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
 
+        info!("Successfully migrated to schema version 10");
         Ok(())
     }
 
-    fn get_sync_state(&self, provider_id: &str) -> Result<Option<DateTime<Utc>>> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 11: Outbox of provider actions.
+    fn migrate_to_v11(&self) -> Result<()> {
+        info!("Running migration to schema version 11");
 
-        let result: Option<String> = conn
-            .query_row(
-                "SELECT last_sync FROM sync_state WHERE provider_id = ?",
-                params![provider_id],
-                |row| row.get(0),
-            )
-            .optional()?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        Ok(result
-            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-            .map(|dt| dt.with_timezone(&Utc)))
-    }
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS outbox (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                action TEXT NOT NULL,
+                queued_at TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create outbox table")?;
 
-    fn update_sync_state(&self, provider_id: &str, last_sync: DateTime<Utc>) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        tx.execute("INSERT INTO schema_version (version) VALUES (11)", [])
+            .context("Failed to update schema version")?;
 
-        conn.execute(
-            "INSERT INTO sync_state (provider_id, last_sync, updated_at)
-             VALUES (?1, ?2, datetime('now'))
-             ON CONFLICT(provider_id) DO UPDATE SET
-                last_sync = excluded.last_sync,
-                updated_at = datetime('now')",
-            params![provider_id, last_sync.to_rfc3339()],
-        )?;
+        tx.commit()?;
 
+        info!("Successfully migrated to schema version 11");
         Ok(())
     }
 
-    fn search_items(
-        &self,
-        query: &str,
-        stream_id: Option<&str>,
-        content_type: Option<&str>,
-        is_read: Option<bool>,
-        is_saved: Option<bool>,
-    ) -> Result<Vec<Item>> {
-        let conn = self.conn.lock().unwrap();
+    /// Migration to version 12: Contacts from email headers and CardDAV.
+    fn migrate_to_v12(&self) -> Result<()> {
+        info!("Running migration to schema version 12");
 
-        // Build the query dynamically based on filters
-        let mut sql = String::from(
-            "SELECT id, stream_id, title, content_type, content_data,
-                    author_name, author_email, author_url, author_avatar_url,
-                    published, updated, url, thumbnail_url, is_read, is_saved,
-                    tags, metadata
-             FROM items
-             WHERE 1=1",
-        );
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        // No foreign key: contacts outlive the email they were seen in
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS item_contacts (
+                item_id TEXT NOT NULL,
+                email TEXT NOT NULL,
+                name TEXT,
+                seen_at TEXT NOT NULL,
+                PRIMARY KEY (item_id, email)
+            )",
+            [],
+        )
+        .context("Failed to create item_contacts table")?;
 
-        // Add search query filter (search in title and serialized content)
-        if !query.is_empty() {
-            sql.push_str(" AND (title LIKE ? OR content_data LIKE ?)");
-            let search_pattern = format!("%{}%", query);
-            params_vec.push(Box::new(search_pattern.clone()));
-            params_vec.push(Box::new(search_pattern));
-        }
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_item_contacts_email
+             ON item_contacts(email)",
+            [],
+        )
+        .context("Failed to create item_contacts index")?;
 
-        // Add stream filter
-        if let Some(stream) = stream_id {
-            sql.push_str(" AND stream_id = ?");
-            params_vec.push(Box::new(stream.to_string()));
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS address_book (
+                email TEXT PRIMARY KEY,
+                name TEXT,
+                imported_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create address_book table")?;
+
+        // id, author name and address, metadata, and published date
+        type EmailRow = (
+            String,
+            Option<String>,
+            Option<String>,
+            String,
+            Option<String>,
+        );
+        let emails: Vec<EmailRow> = tx
+            .prepare(
+                "SELECT id, author_name, author_email, metadata, published
+                 FROM items WHERE content_type = 'Email'",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        for (id, author_name, author_email, metadata_json, published) in &emails {
+            if StoreCipher::is_sealed(metadata_json) {
+                continue;
+            }
+            let metadata = Self::deserialize_metadata(metadata_json)?;
+            let from = author_email
+                .as_deref()
+                .map(|email| (author_name.as_deref().unwrap_or_default(), email));
+            let seen_at = parse_timestamp(published.clone()).unwrap_or_else(Utc::now);
+            Self::write_contacts(
+                &tx,
+                id,
+                &contacts::header_addresses(from, &metadata),
+                seen_at,
+            )?;
         }
 
-        // Add content type filter
-        if let Some(ctype) = content_type {
-            sql.push_str(" AND content_type = ?");
-            params_vec.push(Box::new(ctype.to_string()));
-        }
+        tx.execute("INSERT INTO schema_version (version) VALUES (12)", [])
+            .context("Failed to update schema version")?;
 
-        // Add is_read filter
-        if let Some(read_status) = is_read {
-            sql.push_str(" AND is_read = ?");
-            params_vec.push(Box::new(read_status as i32));
-        }
+        tx.commit()?;
 
-        // Add is_saved filter
-        if let Some(saved_status) = is_saved {
-            sql.push_str(" AND is_saved = ?");
-            params_vec.push(Box::new(saved_status as i32));
-        }
+        info!("Successfully migrated to schema version 12");
+        Ok(())
+    }
 
-        // Order by published date, newest first
-        sql.push_str(" ORDER BY published DESC, created_at DESC LIMIT 100");
+    /// Migration to version 13: Spam verdicts.
+    fn migrate_to_v13(&self) -> Result<()> {
+        info!("Running migration to schema version 13");
 
-        let mut stmt = conn.prepare(&sql)?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        // Convert params to references for query_map
-        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec
-            .iter()
-            .map(|p| p.as_ref() as &dyn rusqlite::ToSql)
-            .collect();
+        // No foreign key: verdicts are training data that outlive the item
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS spam_verdicts (
+                item_id TEXT PRIMARY KEY,
+                spam INTEGER NOT NULL,
+                sender TEXT,
+                subject TEXT NOT NULL,
+                recorded_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create spam_verdicts table")?;
 
-        let items = stmt.query_map(params_refs.as_slice(), Self::row_to_item)?;
+        tx.execute("INSERT INTO schema_version (version) VALUES (13)", [])
+            .context("Failed to update schema version")?;
 
-        items
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .context("Failed to search items from cache")
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 13");
+        Ok(())
     }
-}
 
-// Helper methods for SqliteCache
-impl SqliteCache {
-    /// Convert a database row to an Item.
-    fn row_to_item(row: &rusqlite::Row) -> rusqlite::Result<Item> {
-        let id: String = row.get(0)?;
-        let stream_id: String = row.get(1)?;
-        let title: String = row.get(2)?;
-        let content_type: String = row.get(3)?;
-        let content_data: String = row.get(4)?;
-        let author_name: Option<String> = row.get(5)?;
-        let author_email: Option<String> = row.get(6)?;
-        let author_url: Option<String> = row.get(7)?;
-        let author_avatar_url: Option<String> = row.get(8)?;
-        let published: Option<String> = row.get(9)?;
-        let updated: Option<String> = row.get(10)?;
-        let url: Option<String> = row.get(11)?;
-        let thumbnail_url: Option<String> = row.get(12)?;
-        let is_read: i32 = row.get(13)?;
-        let is_saved: i32 = row.get(14)?;
-        let tags_json: String = row.get(15)?;
-        let metadata_json: String = row.get(16)?;
+    /// Migration to version 14: Attachment text.
+    fn migrate_to_v14(&self) -> Result<()> {
+        info!("Running migration to schema version 14");
 
-        let content = Self::deserialize_content(&content_type, &content_data).map_err(|e| {
-            rusqlite::Error::FromSqlConversionFailure(
-                4,
-                rusqlite::types::Type::Text,
-                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
-            )
-        })?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        let author = author_name.map(|name| scryforge_provider_core::Author {
-            name,
-            email: author_email,
-            url: author_url,
-            avatar_url: author_avatar_url,
-        });
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS attachment_text (
+                item_id TEXT NOT NULL,
+                attachment_id TEXT NOT NULL,
+                file_name TEXT NOT NULL,
+                text TEXT NOT NULL,
+                indexed_at TEXT NOT NULL,
+                PRIMARY KEY (item_id, attachment_id),
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create attachment_text table")?;
 
-        let published = published
-            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-            .map(|dt| dt.with_timezone(&Utc));
+        tx.execute("INSERT INTO schema_version (version) VALUES (14)", [])
+            .context("Failed to update schema version")?;
 
-        let updated = updated
-            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-            .map(|dt| dt.with_timezone(&Utc));
+        tx.commit()?;
 
-        let tags = Self::deserialize_tags(&tags_json).map_err(|e| {
-            rusqlite::Error::FromSqlConversionFailure(
-                15,
-                rusqlite::types::Type::Text,
-                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
-            )
-        })?;
+        info!("Successfully migrated to schema version 14");
+        Ok(())
+    }
 
-        let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
-            rusqlite::Error::FromSqlConversionFailure(
-                16,
-                rusqlite::types::Type::Text,
-                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
-            )
-        })?;
+    /// Migration to version 15: Reading queue.
+    ///
+    /// Finished items keep their row, so they don't rejoin the queue while
+    /// they stay saved.
+    fn migrate_to_v15(&self) -> Result<()> {
+        info!("Running migration to schema version 15");
 
-        Ok(Item {
-            id: ItemId(id),
-            stream_id: StreamId(stream_id),
-            title,
-            content,
-            author,
-            published,
-            updated,
-            url,
-            thumbnail_url,
-            is_read: is_read != 0,
-            is_saved: is_saved != 0,
-            tags,
-            metadata,
-        })
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS reading_queue (
+                item_id TEXT PRIMARY KEY,
+                rank INTEGER NOT NULL,
+                read_offset INTEGER NOT NULL DEFAULT 0,
+                opened_at TEXT,
+                finished_at TEXT,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create reading_queue table")?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (15)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 15");
+        Ok(())
     }
 
-    /// Serialize item content to type and JSON data.
-    fn serialize_content(
-        content: &scryforge_provider_core::ItemContent,
-    ) -> Result<(String, String)> {
-        use scryforge_provider_core::ItemContent;
+    /// Migration to version 16: Audit log of provider mutations.
+    ///
+    /// Entries outlive their items, so deletions stay on record.
+    fn migrate_to_v16(&self) -> Result<()> {
+        info!("Running migration to schema version 16");
 
-        let (content_type, data) = match content {
-            ItemContent::Text(text) => ("Text", serde_json::json!({"text": text})),
-            ItemContent::Markdown(md) => ("Markdown", serde_json::json!({"markdown": md})),
-            ItemContent::Html(html) => ("Html", serde_json::json!({"html": html})),
-            ItemContent::Email {
-                subject,
-                body_text,
-                body_html,
-                snippet,
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                provider_id TEXT NOT NULL,
+                item_id TEXT,
+                action TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                error TEXT
+            )",
+            [],
+        )
+        .context("Failed to create audit_log table")?;
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_provider ON audit_log(provider_id)",
+            [],
+        )?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (16)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 16");
+        Ok(())
+    }
+
+    /// Migration to version 17: What changed when items were updated.
+    fn migrate_to_v17(&self) -> Result<()> {
+        info!("Running migration to schema version 17");
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS item_changes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                item_id TEXT NOT NULL,
+                changed_at TEXT NOT NULL,
+                changes TEXT NOT NULL,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create item_changes table")?;
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_item_changes_item ON item_changes(item_id)",
+            [],
+        )?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (17)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 17");
+        Ok(())
+    }
+
+    /// Migration to version 18: Tombstones of items removed at their source.
+    ///
+    /// Tombstones outlive their items, so deleted items stay deleted.
+    fn migrate_to_v18(&self) -> Result<()> {
+        info!("Running migration to schema version 18");
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS tombstones (
+                item_id TEXT PRIMARY KEY,
+                stream_id TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                removed_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .context("Failed to create tombstones table")?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (18)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 18");
+        Ok(())
+    }
+
+    /// Migration to version 19: Index of items by when their row changed.
+    ///
+    /// Lets the full-text index pick up only what changed since it last
+    /// looked.
+    fn migrate_to_v19(&self) -> Result<()> {
+        info!("Running migration to schema version 19");
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_items_updated_at ON items(updated_at, id)",
+            [],
+        )
+        .context("Failed to create updated_at index")?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (19)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 19");
+        Ok(())
+    }
+
+    /// Migration to version 20: Saved search alerts.
+    fn migrate_to_v20(&self) -> Result<()> {
+        info!("Running migration to schema version 20");
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS saved_searches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                query TEXT NOT NULL,
+                notify INTEGER NOT NULL DEFAULT 1,
+                interval_minutes INTEGER,
+                created_at TEXT NOT NULL,
+                last_run_at TEXT
+            )",
+            [],
+        )
+        .context("Failed to create saved_searches table")?;
+
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS alert_matches (
+                search_id INTEGER NOT NULL,
+                item_id TEXT NOT NULL,
+                matched_at TEXT NOT NULL,
+                PRIMARY KEY (search_id, item_id),
+                FOREIGN KEY (search_id) REFERENCES saved_searches(id) ON DELETE CASCADE,
+                FOREIGN KEY (item_id) REFERENCES items(id) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .context("Failed to create alert_matches table")?;
+
+        tx.execute(
+            "CREATE INDEX IF NOT EXISTS idx_alert_matches_matched_at
+             ON alert_matches(matched_at DESC)",
+            [],
+        )
+        .context("Failed to create alert_matches index")?;
+
+        tx.execute("INSERT INTO schema_version (version) VALUES (20)", [])
+            .context("Failed to update schema version")?;
+
+        tx.commit()?;
+
+        info!("Successfully migrated to schema version 20");
+        Ok(())
+    }
+
+    /// Delete the interactions and state conflicts of items no longer in
+    /// the cache, which have no foreign key to remove them with the item.
+    fn delete_orphaned_history(conn: &Connection) -> Result<()> {
+        conn.execute(
+            "DELETE FROM interactions WHERE item_id NOT IN (SELECT id FROM items)",
+            [],
+        )?;
+        conn.execute(
+            "DELETE FROM state_conflicts WHERE item_id NOT IN (SELECT id FROM items)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Bytes of database pages in use, excluding the free list.
+    fn used_bytes(conn: &Connection) -> Result<u64> {
+        let (page_count, freelist, page_size): (u64, u64, u64) = (
+            conn.query_row("PRAGMA page_count", [], |row| row.get(0))?,
+            conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?,
+            conn.query_row("PRAGMA page_size", [], |row| row.get(0))?,
+        );
+        Ok(page_count.saturating_sub(freelist) * page_size)
+    }
+
+    /// Insert an item, or update everything but its state columns.
+    ///
+    /// With a `cipher`, the title, content, author, and metadata are sealed.
+    fn write_item(conn: &Connection, item: &Item, cipher: Option<&StoreCipher>) -> Result<()> {
+        let seal = |value: String| match cipher {
+            Some(cipher) => cipher.seal_str(&value),
+            None => value,
+        };
+
+        let title = seal(item.title.clone());
+        let (content_type, content_data) = Self::serialize_content(&item.content)?;
+        let links = ItemLinks::of(
+            item.url.as_deref(),
+            Some(content_data.as_str()).filter(|_| cipher.is_none()),
+        );
+        let content_data = seal(content_data);
+        let tags_json = Self::serialize_tags(&item.tags)?;
+        let mut metadata = item.metadata.clone();
+        metadata.insert(
+            READING_MINUTES_KEY.to_string(),
+            estimated_minutes(item).to_string(),
+        );
+        let metadata_json = seal(Self::serialize_metadata(&metadata)?);
+
+        let author_name = item.author.as_ref().map(|a| seal(a.name.clone()));
+        let author_email = item.author.as_ref().and_then(|a| a.email.clone()).map(seal);
+        let author_url = item.author.as_ref().and_then(|a| a.url.as_ref());
+        let author_avatar_url = item.author.as_ref().and_then(|a| a.avatar_url.as_ref());
+
+        let published = item.published.map(|dt| dt.to_rfc3339());
+        let updated = item.updated.map(|dt| dt.to_rfc3339());
+
+        conn.execute(
+            "INSERT INTO items
+                (id, stream_id, title, content_type, content_data,
+                 author_name, author_email, author_url, author_avatar_url,
+                 published, updated, url, thumbnail_url, is_read, is_saved,
+                 tags, metadata, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, datetime('now'))
+             ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                content_type = excluded.content_type,
+                content_data = excluded.content_data,
+                author_name = excluded.author_name,
+                author_email = excluded.author_email,
+                author_url = excluded.author_url,
+                author_avatar_url = excluded.author_avatar_url,
+                published = excluded.published,
+                updated = excluded.updated,
+                url = excluded.url,
+                thumbnail_url = excluded.thumbnail_url,
+                tags = excluded.tags,
+                metadata = excluded.metadata,
+                updated_at = datetime('now')",
+            params![
+                item.id.as_str(),
+                item.stream_id.as_str(),
+                title,
+                content_type,
+                content_data,
+                author_name,
+                author_email,
+                author_url,
+                author_avatar_url,
+                published,
+                updated,
+                &item.url,
+                &item.thumbnail_url,
+                item.is_read as i32,
+                item.is_saved as i32,
+                tags_json,
+                metadata_json,
+            ],
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO item_stats (item_id, stream_id, provider_id, received_at)
+             SELECT ?1, id, provider_id, ?3 FROM streams WHERE id = ?2",
+            params![
+                item.id.as_str(),
+                item.stream_id.as_str(),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Self::write_links(conn, item.id.as_str(), &links)?;
+
+        // The contacts table is stored in the clear, like item_links
+        if cipher.is_none() {
+            Self::write_contacts(
+                conn,
+                item.id.as_str(),
+                &contacts::item_addresses(item),
+                item.published.unwrap_or_else(Utc::now),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Stamp the first time an item is seen read or saved in `item_stats`.
+    fn record_item_state(conn: &Connection, item_id: &str) -> Result<()> {
+        conn.execute(
+            "UPDATE item_stats SET
+                read_at = COALESCE(read_at,
+                    CASE WHEN (SELECT is_read FROM items WHERE id = ?1) THEN ?2 END),
+                saved_at = COALESCE(saved_at,
+                    CASE WHEN (SELECT is_saved FROM items WHERE id = ?1) THEN ?2 END)
+             WHERE item_id = ?1",
+            params![item_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// The version of `item` to write over the stored one.
+    ///
+    /// If [`diff_items`] finds changes, they are recorded in `item_changes`,
+    /// sealed like the item, and the item is stamped with
+    /// [`CHANGED_AT_KEY`]; a stamp not yet acknowledged is carried over, as
+    /// are the [`LOCAL_METADATA_KEYS`] the provider doesn't know about.
+    fn track_changes(
+        conn: &Connection,
+        item: &Item,
+        cipher: Option<&StoreCipher>,
+        opener: Option<&StoreCipher>,
+    ) -> Result<Item> {
+        let mut item = item.clone();
+        let stored = conn
+            .query_row(
+                &format!("SELECT {} FROM items WHERE id = ?", ITEM_COLUMNS),
+                params![item.id.as_str()],
+                |row| Self::row_to_item(row, opener),
+            )
+            .optional();
+        let stored = match stored {
+            Ok(Some(stored)) => stored,
+            Ok(None) => return Ok(item),
+            // Such as a sealed item with no key loaded: it is replaced as is
+            Err(e) => {
+                warn!(
+                    "Failed to read cached item '{}' to compare: {}",
+                    item.id.as_str(),
+                    e
+                );
+                return Ok(item);
+            }
+        };
+
+        // A body fetched earlier outlives listings that skip bodies, such as
+        // in low-bandwidth mode
+        if item.metadata.contains_key(LAZY_BODY_METADATA_KEY)
+            && !stored.metadata.contains_key(LAZY_BODY_METADATA_KEY)
+        {
+            item.content = stored.content.clone();
+            item.metadata.remove(LAZY_BODY_METADATA_KEY);
+        }
+        for key in LOCAL_METADATA_KEYS.iter().chain([&CHANGED_AT_KEY]) {
+            if let Some(value) = stored.metadata.get(*key) {
+                item.metadata
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        let changes = diff_items(&stored, &item);
+        if changes.is_empty() {
+            return Ok(item);
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut changes_json = serde_json::to_string(&changes)?;
+        if let Some(cipher) = cipher {
+            changes_json = cipher.seal_str(&changes_json);
+        }
+        conn.execute(
+            "INSERT INTO item_changes (item_id, changed_at, changes) VALUES (?, ?, ?)",
+            params![item.id.as_str(), now, changes_json],
+        )?;
+        conn.execute(
+            "DELETE FROM item_changes WHERE item_id = ?1 AND id NOT IN
+                (SELECT id FROM item_changes WHERE item_id = ?1 ORDER BY id DESC LIMIT ?2)",
+            params![item.id.as_str(), MAX_CHANGES_PER_ITEM],
+        )?;
+        debug!(
+            "Item '{}' changed: {} field(s)",
+            item.id.as_str(),
+            changes.len()
+        );
+        item.metadata.insert(CHANGED_AT_KEY.to_string(), now);
+        Ok(item)
+    }
+
+    /// Rewrite the metadata of a cached item with `edit`, keeping it sealed
+    /// if it was. Returns whether the item is cached and `edit` changed it.
+    fn edit_metadata(
+        conn: &Connection,
+        cipher: Option<&StoreCipher>,
+        item_id: &str,
+        edit: impl FnOnce(&mut HashMap<String, String>) -> bool,
+    ) -> Result<bool> {
+        let metadata: Option<String> = conn
+            .query_row(
+                "SELECT metadata FROM items WHERE id = ?",
+                params![item_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(metadata) = metadata else {
+            return Ok(false);
+        };
+
+        let sealed = StoreCipher::is_sealed(&metadata);
+        let mut metadata = Self::deserialize_metadata(
+            &Self::open_column(cipher, metadata).map_err(|e| anyhow::anyhow!(e))?,
+        )?;
+        if !edit(&mut metadata) {
+            return Ok(false);
+        }
+        let mut metadata_json = Self::serialize_metadata(&metadata)?;
+        if let Some(cipher) = cipher.filter(|_| sealed) {
+            metadata_json = cipher.seal_str(&metadata_json);
+        }
+        conn.execute(
+            "UPDATE items SET metadata = ?, updated_at = datetime('now') WHERE id = ?",
+            params![metadata_json, item_id],
+        )?;
+        Ok(true)
+    }
+
+    /// Remember that an item was removed at its source, unless it already
+    /// is.
+    fn write_tombstone(
+        conn: &Connection,
+        item_id: &ItemId,
+        stream_id: &StreamId,
+        reason: &str,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT OR IGNORE INTO tombstones (item_id, stream_id, reason, removed_at)
+             VALUES (?, ?, ?, ?)",
+            params![
+                item_id.as_str(),
+                stream_id.as_str(),
+                reason,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn has_tombstone(conn: &Connection, item_id: &ItemId) -> Result<bool> {
+        Ok(conn
+            .query_row(
+                "SELECT 1 FROM tombstones WHERE item_id = ?",
+                params![item_id.as_str()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    /// Whether a synced item must be left out of the cache: it has a
+    /// tombstone, or its provider marks it removed.
+    ///
+    /// A removed item gets a tombstone, and its cached copy, if any, is kept
+    /// as it was, stamped with the reason in [`REMOVED_METADATA_KEY`].
+    fn bury(conn: &Connection, cipher: Option<&StoreCipher>, item: &Item) -> Result<bool> {
+        if Self::has_tombstone(conn, &item.id)? {
+            return Ok(true);
+        }
+        let Some(reason) = item.metadata.get(REMOVED_METADATA_KEY) else {
+            return Ok(false);
+        };
+
+        Self::write_tombstone(conn, &item.id, &item.stream_id, reason)?;
+        Self::edit_metadata(conn, cipher, item.id.as_str(), |metadata| {
+            metadata.insert(REMOVED_METADATA_KEY.to_string(), reason.clone());
+            true
+        })?;
+        debug!("Item '{}' was removed ({})", item.id.as_str(), reason);
+        Ok(true)
+    }
+
+    /// Replace the recorded links of an item.
+    fn write_links(conn: &Connection, item_id: &str, links: &ItemLinks) -> Result<()> {
+        conn.execute("DELETE FROM item_links WHERE item_id = ?", params![item_id])?;
+
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR IGNORE INTO item_links (item_id, url_key, kind) VALUES (?, ?, ?)",
+        )?;
+        if let Some(ref own) = links.own {
+            stmt.execute(params![item_id, own, "own"])?;
+        }
+        for mentioned in &links.mentioned {
+            stmt.execute(params![item_id, mentioned, "mention"])?;
+        }
+        Ok(())
+    }
+
+    /// Replace the addresses recorded for an item.
+    fn write_contacts(
+        conn: &Connection,
+        item_id: &str,
+        addresses: &[SeenAddress],
+        seen_at: DateTime<Utc>,
+    ) -> Result<()> {
+        conn.execute(
+            "DELETE FROM item_contacts WHERE item_id = ?",
+            params![item_id],
+        )?;
+
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR IGNORE INTO item_contacts (item_id, email, name, seen_at)
+             VALUES (?, ?, ?, ?)",
+        )?;
+        let seen_at = seen_at.to_rfc3339();
+        for address in addresses {
+            stmt.execute(params![item_id, address.email, address.name, seen_at])?;
+        }
+        Ok(())
+    }
+
+    /// Serialize metadata HashMap to JSON string.
+    fn serialize_metadata(metadata: &HashMap<String, String>) -> Result<String> {
+        serde_json::to_string(metadata).context("Failed to serialize metadata")
+    }
+
+    /// Deserialize metadata from JSON string.
+    fn deserialize_metadata(json: &str) -> Result<HashMap<String, String>> {
+        serde_json::from_str(json).context("Failed to deserialize metadata")
+    }
+
+    /// Serialize tags Vec to JSON string.
+    fn serialize_tags(tags: &[String]) -> Result<String> {
+        serde_json::to_string(tags).context("Failed to serialize tags")
+    }
+
+    /// Deserialize tags from JSON string.
+    fn deserialize_tags(json: &str) -> Result<Vec<String>> {
+        serde_json::from_str(json).context("Failed to deserialize tags")
+    }
+}
+
+/// Mean of `total_secs` over `count` reads, rounded to whole seconds.
+fn mean_secs(total_secs: f64, count: u64) -> Option<u64> {
+    (count > 0).then(|| (total_secs / count as f64).round() as u64)
+}
+
+/// Parse an RFC 3339 timestamp column, treating bad values as unset.
+fn parse_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// The serde name of a unit enum variant, for storing in a TEXT column.
+fn enum_name<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => anyhow::bail!("Expected a unit variant, got {}", other),
+    }
+}
+
+/// Parse a value stored with [`enum_name`].
+fn parse_enum<T: serde::de::DeserializeOwned>(name: &str) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .with_context(|| format!("Unknown value in cache: {}", name))
+}
+
+impl Cache for SqliteCache {
+    fn get_streams(&self, provider_id: Option<&str>) -> Result<Vec<Stream>> {
+        let conn = self.conn.lock().unwrap();
+
+        let streams = if let Some(provider) = provider_id {
+            let mut stmt = conn.prepare(
+                "SELECT id, name, provider_id, stream_type, icon, unread_count,
+                        total_count, last_updated, metadata
+                 FROM streams
+                 WHERE provider_id = ?
+                 ORDER BY name",
+            )?;
+
+            let result = stmt
+                .query_map([provider], |row| {
+                    let id: String = row.get(0)?;
+                    let name: String = row.get(1)?;
+                    let provider_id: String = row.get(2)?;
+                    let stream_type_str: String = row.get(3)?;
+                    let icon: Option<String> = row.get(4)?;
+                    let unread_count: Option<u32> = row.get(5)?;
+                    let total_count: Option<u32> = row.get(6)?;
+                    let last_updated: Option<String> = row.get(7)?;
+                    let metadata_json: String = row.get(8)?;
+
+                    let stream_type = match stream_type_str.as_str() {
+                        "Feed" => scryforge_provider_core::StreamType::Feed,
+                        "Collection" => scryforge_provider_core::StreamType::Collection,
+                        "SavedItems" => scryforge_provider_core::StreamType::SavedItems,
+                        "Community" => scryforge_provider_core::StreamType::Community,
+                        other => scryforge_provider_core::StreamType::Custom(other.to_string()),
+                    };
+
+                    let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            8,
+                            rusqlite::types::Type::Text,
+                            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+                        )
+                    })?;
+
+                    let last_updated = last_updated
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+
+                    Ok(Stream {
+                        id: StreamId(id),
+                        name,
+                        provider_id,
+                        stream_type,
+                        icon,
+                        unread_count,
+                        total_count,
+                        last_updated,
+                        metadata,
+                    })
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            result
+        } else {
+            let mut stmt = conn.prepare(
+                "SELECT id, name, provider_id, stream_type, icon, unread_count,
+                        total_count, last_updated, metadata
+                 FROM streams
+                 ORDER BY provider_id, name",
+            )?;
+
+            let result = stmt
+                .query_map([], |row| {
+                    let id: String = row.get(0)?;
+                    let name: String = row.get(1)?;
+                    let provider_id: String = row.get(2)?;
+                    let stream_type_str: String = row.get(3)?;
+                    let icon: Option<String> = row.get(4)?;
+                    let unread_count: Option<u32> = row.get(5)?;
+                    let total_count: Option<u32> = row.get(6)?;
+                    let last_updated: Option<String> = row.get(7)?;
+                    let metadata_json: String = row.get(8)?;
+
+                    let stream_type = match stream_type_str.as_str() {
+                        "Feed" => scryforge_provider_core::StreamType::Feed,
+                        "Collection" => scryforge_provider_core::StreamType::Collection,
+                        "SavedItems" => scryforge_provider_core::StreamType::SavedItems,
+                        "Community" => scryforge_provider_core::StreamType::Community,
+                        other => scryforge_provider_core::StreamType::Custom(other.to_string()),
+                    };
+
+                    let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(
+                            8,
+                            rusqlite::types::Type::Text,
+                            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+                        )
+                    })?;
+
+                    let last_updated = last_updated
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+
+                    Ok(Stream {
+                        id: StreamId(id),
+                        name,
+                        provider_id,
+                        stream_type,
+                        icon,
+                        unread_count,
+                        total_count,
+                        last_updated,
+                        metadata,
+                    })
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            result
+        };
+
+        Ok(streams)
+    }
+
+    fn get_items(&self, stream_id: &StreamId, limit: Option<u32>) -> Result<Vec<Item>> {
+        let conn = self.conn.lock().unwrap();
+
+        let query = if limit.is_some() {
+            "SELECT id, stream_id, title, content_type, content_data,
+                    author_name, author_email, author_url, author_avatar_url,
+                    published, updated, url, thumbnail_url, is_read, is_saved,
+                    tags, metadata
+             FROM items
+             WHERE stream_id = ?
+             ORDER BY id NOT IN (SELECT item_id FROM pins), published DESC, created_at DESC
+             LIMIT ?"
+        } else {
+            "SELECT id, stream_id, title, content_type, content_data,
+                    author_name, author_email, author_url, author_avatar_url,
+                    published, updated, url, thumbnail_url, is_read, is_saved,
+                    tags, metadata
+             FROM items
+             WHERE stream_id = ?
+             ORDER BY id NOT IN (SELECT item_id FROM pins), published DESC, created_at DESC"
+        };
+
+        let mut stmt = conn.prepare(query)?;
+
+        let row_to_item = |row: &rusqlite::Row| Self::row_to_item(row, self.cipher.as_ref());
+        let items = if let Some(lim) = limit {
+            stmt.query_map(params![stream_id.as_str(), lim], row_to_item)?
+        } else {
+            stmt.query_map(params![stream_id.as_str()], row_to_item)?
+        };
+
+        items
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to fetch items from cache")
+    }
+
+    fn get_item(&self, item_id: &ItemId) -> Result<Option<Item>> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.query_row(
+            "SELECT id, stream_id, title, content_type, content_data,
+                    author_name, author_email, author_url, author_avatar_url,
+                    published, updated, url, thumbnail_url, is_read, is_saved,
+                    tags, metadata
+             FROM items
+             WHERE id = ?",
+            params![item_id.as_str()],
+            |row| Self::row_to_item(row, self.cipher.as_ref()),
+        )
+        .optional()
+        .context("Failed to fetch item from cache")
+    }
+
+    fn upsert_streams(&self, streams: &[Stream]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for stream in streams {
+            let stream_type_str = match &stream.stream_type {
+                scryforge_provider_core::StreamType::Feed => "Feed",
+                scryforge_provider_core::StreamType::Collection => "Collection",
+                scryforge_provider_core::StreamType::SavedItems => "SavedItems",
+                scryforge_provider_core::StreamType::Community => "Community",
+                scryforge_provider_core::StreamType::Custom(s) => s.as_str(),
+            };
+
+            let metadata_json = Self::serialize_metadata(&stream.metadata)?;
+            let last_updated = stream.last_updated.map(|dt| dt.to_rfc3339());
+
+            tx.execute(
+                "INSERT INTO streams
+                    (id, name, provider_id, stream_type, icon, unread_count,
+                     total_count, last_updated, metadata, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, datetime('now'))
+                 ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    stream_type = excluded.stream_type,
+                    icon = excluded.icon,
+                    unread_count = excluded.unread_count,
+                    total_count = excluded.total_count,
+                    last_updated = excluded.last_updated,
+                    metadata = excluded.metadata,
+                    updated_at = datetime('now')",
+                params![
+                    stream.id.as_str(),
+                    &stream.name,
+                    &stream.provider_id,
+                    stream_type_str,
+                    &stream.icon,
+                    stream.unread_count,
+                    stream.total_count,
+                    last_updated,
+                    metadata_json,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        debug!("Upserted {} streams", streams.len());
+        Ok(())
+    }
+
+    fn upsert_items(&self, items: &[Item]) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        for item in items {
+            let cipher = self.sealing_cipher(&tx, &item.stream_id)?;
+            let item = Self::track_changes(&tx, item, cipher, self.cipher.as_ref())?;
+            Self::write_item(&tx, &item, cipher)?;
+            Self::record_item_state(&tx, item.id.as_str())?;
+        }
+
+        tx.commit()?;
+        debug!("Upserted {} items", items.len());
+        Ok(())
+    }
+
+    fn merge_items(
+        &self,
+        items: &[Item],
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<StateConflict>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = Utc::now();
+        let mut conflicts = Vec::new();
+        let mut buried = 0;
+
+        for item in items {
+            if Self::bury(&tx, self.cipher.as_ref(), item)? {
+                buried += 1;
+                continue;
+            }
+
+            let existing = tx
+                .query_row(
+                    "SELECT is_read, is_saved, remote_is_read, remote_is_saved,
+                            read_changed_at, saved_changed_at, merged_at
+                     FROM items WHERE id = ?",
+                    params![item.id.as_str()],
+                    |row| {
+                        Ok((
+                            row.get::<_, bool>(0)?,
+                            row.get::<_, bool>(1)?,
+                            row.get::<_, Option<bool>>(2)?,
+                            row.get::<_, Option<bool>>(3)?,
+                            parse_timestamp(row.get(4)?),
+                            parse_timestamp(row.get(5)?),
+                            parse_timestamp(row.get(6)?),
+                        ))
+                    },
+                )
+                .optional()?;
+
+            let cipher = self.sealing_cipher(&tx, &item.stream_id)?;
+            let tracked = Self::track_changes(&tx, item, cipher, self.cipher.as_ref())?;
+            Self::write_item(&tx, &tracked, cipher)?;
+
+            let (is_read, is_saved) = match existing {
+                None => (item.is_read, item.is_saved),
+                Some((read, saved, remote_read, remote_saved, read_at, saved_at, merged_at)) => {
+                    let fields = [
+                        (StateField::Read, read, read_at, remote_read, item.is_read),
+                        (
+                            StateField::Saved,
+                            saved,
+                            saved_at,
+                            remote_saved,
+                            item.is_saved,
+                        ),
+                    ];
+                    let mut values = [read, saved];
+                    for (value, (field, local, changed_at, last_remote, remote)) in
+                        values.iter_mut().zip(fields)
+                    {
+                        let (kept, winner) = resolve_field(
+                            strategy,
+                            local,
+                            changed_at,
+                            last_remote,
+                            merged_at,
+                            remote,
+                            item.updated,
+                        );
+                        *value = kept;
+                        if let Some(winner) = winner {
+                            conflicts.push(StateConflict {
+                                item_id: item.id.clone(),
+                                field,
+                                local,
+                                remote,
+                                winner,
+                                strategy,
+                                resolved_at: now,
+                            });
+                        }
+                    }
+                    (values[0], values[1])
+                }
+            };
+
+            tx.execute(
+                "UPDATE items
+                 SET is_read = ?1, is_saved = ?2, remote_is_read = ?3, remote_is_saved = ?4,
+                     merged_at = ?5
+                 WHERE id = ?6",
+                params![
+                    is_read,
+                    is_saved,
+                    item.is_read,
+                    item.is_saved,
+                    now.to_rfc3339(),
+                    item.id.as_str()
+                ],
+            )?;
+            Self::record_item_state(&tx, item.id.as_str())?;
+        }
+
+        for conflict in &conflicts {
+            info!(
+                "Resolved {:?} conflict on '{}' ({:?}): local {}, remote {}, kept {:?}",
+                conflict.field,
+                conflict.item_id.as_str(),
+                conflict.strategy,
+                conflict.local,
+                conflict.remote,
+                conflict.winner
+            );
+            tx.execute(
+                "INSERT INTO state_conflicts
+                    (item_id, field, local_value, remote_value, winner, strategy, resolved_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    conflict.item_id.as_str(),
+                    enum_name(&conflict.field)?,
+                    conflict.local,
+                    conflict.remote,
+                    enum_name(&conflict.winner)?,
+                    enum_name(&conflict.strategy)?,
+                    conflict.resolved_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        debug!(
+            "Merged {} items with {} conflict(s), {} removed",
+            items.len() - buried,
+            conflicts.len(),
+            buried
+        );
+        Ok(conflicts)
+    }
+
+    fn get_conflicts(&self, limit: u32) -> Result<Vec<StateConflict>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT item_id, field, local_value, remote_value, winner, strategy, resolved_at
+             FROM state_conflicts
+             ORDER BY id DESC
+             LIMIT ?",
+        )?;
+        let rows = stmt
+            .query_map([limit], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, bool>(2)?,
+                    row.get::<_, bool>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(
+                |(item_id, field, local, remote, winner, strategy, resolved_at)| {
+                    Ok(StateConflict {
+                        item_id: ItemId(item_id),
+                        field: parse_enum(&field)?,
+                        local,
+                        remote,
+                        winner: parse_enum(&winner)?,
+                        strategy: parse_enum(&strategy)?,
+                        resolved_at: DateTime::parse_from_rfc3339(&resolved_at)?
+                            .with_timezone(&Utc),
+                    })
+                },
+            )
+            .collect()
+    }
+
+    fn mark_read(&self, item_id: &ItemId, is_read: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "UPDATE items
+             SET is_read = ?, read_changed_at = ?, accessed_at = datetime('now'),
+                 updated_at = datetime('now')
+             WHERE id = ?",
+            params![is_read as i32, Utc::now().to_rfc3339(), item_id.as_str()],
+        )?;
+        Self::record_item_state(&conn, item_id.as_str())?;
+
+        if rows == 0 {
+            warn!(
+                "Attempted to mark non-existent item as read: {}",
+                item_id.as_str()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn mark_starred(&self, item_id: &ItemId, is_starred: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "UPDATE items SET is_saved = ?, saved_changed_at = ?, updated_at = datetime('now')
+             WHERE id = ?",
+            params![is_starred as i32, Utc::now().to_rfc3339(), item_id.as_str()],
+        )?;
+        Self::record_item_state(&conn, item_id.as_str())?;
+
+        if rows == 0 {
+            warn!(
+                "Attempted to mark non-existent item as starred: {}",
+                item_id.as_str()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn mark_archived(&self, item_id: &ItemId, is_archived: bool) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "UPDATE items SET is_archived = ?, updated_at = datetime('now') WHERE id = ?",
+            params![is_archived as i32, item_id.as_str()],
+        )?;
+
+        if rows == 0 {
+            warn!(
+                "Attempted to mark non-existent item as archived: {}",
+                item_id.as_str()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn delete_item(&self, item_id: &ItemId) -> Result<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let stream_id: Option<String> = tx
+            .query_row(
+                "SELECT stream_id FROM items WHERE id = ?",
+                params![item_id.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(stream_id) = stream_id else {
+            return Ok(false);
+        };
+        Self::write_tombstone(&tx, item_id, &StreamId(stream_id), "deleted")?;
+        tx.execute("DELETE FROM items WHERE id = ?", params![item_id.as_str()])?;
+
+        tx.commit()?;
+        Ok(true)
+    }
+
+    fn set_item_metadata(&self, item_id: &ItemId, key: &str, value: &str) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let metadata: Option<String> = tx
+            .query_row(
+                "SELECT metadata FROM items WHERE id = ?",
+                params![item_id.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(metadata) = metadata else {
+            warn!(
+                "Attempted to set metadata on non-existent item: {}",
+                item_id.as_str()
+            );
+            return Ok(());
+        };
+
+        let sealed = StoreCipher::is_sealed(&metadata);
+        let cipher = self.cipher.as_ref();
+        let mut metadata = Self::deserialize_metadata(
+            &Self::open_column(cipher, metadata).map_err(|e| anyhow::anyhow!(e))?,
+        )?;
+        metadata.insert(key.to_string(), value.to_string());
+        let mut metadata_json = Self::serialize_metadata(&metadata)?;
+        if let Some(cipher) = cipher.filter(|_| sealed) {
+            metadata_json = cipher.seal_str(&metadata_json);
+        }
+        tx.execute(
+            "UPDATE items SET metadata = ?, is_annotated = 1, updated_at = datetime('now')
+             WHERE id = ?",
+            params![metadata_json, item_id.as_str()],
+        )?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_sync_state(&self, provider_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.conn.lock().unwrap();
+
+        let result: Option<String> = conn
+            .query_row(
+                "SELECT last_sync FROM sync_state WHERE provider_id = ?",
+                params![provider_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(result
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc)))
+    }
+
+    fn update_sync_state(&self, provider_id: &str, last_sync: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO sync_state (provider_id, last_sync, updated_at)
+             VALUES (?1, ?2, datetime('now'))
+             ON CONFLICT(provider_id) DO UPDATE SET
+                last_sync = excluded.last_sync,
+                updated_at = datetime('now')",
+            params![provider_id, last_sync.to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    fn record_interaction(&self, item_id: &ItemId, kind: InteractionKind) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "INSERT INTO interactions (item_id, stream_id, author_name, kind)
+             SELECT id, stream_id,
+                    CASE WHEN author_name LIKE ?3 THEN NULL ELSE author_name END, ?2
+             FROM items WHERE id = ?1",
+            params![
+                item_id.as_str(),
+                kind.as_str(),
+                format!("{}%", SEALED_PREFIX)
+            ],
+        )?;
+        if kind != InteractionKind::Skipped {
+            conn.execute(
+                "UPDATE items SET accessed_at = datetime('now') WHERE id = ?",
+                params![item_id.as_str()],
+            )?;
+        }
+
+        if rows == 0 {
+            warn!(
+                "Attempted to record interaction for non-existent item: {}",
+                item_id.as_str()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn get_interaction_stats(&self) -> Result<InteractionStats> {
+        let conn = self.conn.lock().unwrap();
+        let mut stats = InteractionStats::default();
+
+        let mut stmt = conn.prepare(
+            "SELECT stream_id, author_name, kind, COUNT(*)
+             FROM interactions
+             GROUP BY stream_id, author_name, kind",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, u32>(3)?,
+            ))
+        })?;
+
+        for row in rows {
+            let (stream_id, author, kind, count) = row?;
+            stats
+                .by_stream
+                .entry(stream_id)
+                .or_default()
+                .add(&kind, count);
+            if let Some(author) = author {
+                stats.by_author.entry(author).or_default().add(&kind, count);
+            }
+        }
+
+        Ok(stats)
+    }
+
+    fn set_reminder(&self, item_id: &ItemId, remind_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO reminders (item_id, remind_at, created_at) VALUES (?, ?, ?)
+             ON CONFLICT(item_id) DO UPDATE SET
+                remind_at = excluded.remind_at,
+                created_at = excluded.created_at,
+                notified_at = NULL",
+            params![
+                item_id.as_str(),
+                remind_at.to_rfc3339(),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .with_context(|| format!("Failed to set reminder on {}", item_id.as_str()))?;
+
+        Ok(())
+    }
+
+    fn remove_reminder(&self, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "DELETE FROM reminders WHERE item_id = ?",
+            params![item_id.as_str()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn get_reminders(&self) -> Result<Vec<Reminder>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT item_id, remind_at, created_at, notified_at
+             FROM reminders
+             ORDER BY remind_at ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(item_id, remind_at, created_at, notified_at)| {
+                Ok(Reminder {
+                    item_id: ItemId(item_id),
+                    remind_at: DateTime::parse_from_rfc3339(&remind_at)?.with_timezone(&Utc),
+                    created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+                    notified_at: parse_timestamp(notified_at),
+                })
+            })
+            .collect()
+    }
+
+    fn mark_reminder_notified(&self, item_id: &ItemId, notified_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE reminders SET notified_at = ? WHERE item_id = ?",
+            params![notified_at.to_rfc3339(), item_id.as_str()],
+        )?;
+        Ok(())
+    }
+
+    fn create_saved_search(
+        &self,
+        name: &str,
+        query: &str,
+        notify: bool,
+        interval_minutes: Option<u32>,
+    ) -> Result<SavedSearch> {
+        let conn = self.conn.lock().unwrap();
+
+        let created_at = Utc::now();
+        conn.execute(
+            "INSERT INTO saved_searches (name, query, notify, interval_minutes, created_at)
+             VALUES (?, ?, ?, ?, ?)",
+            params![
+                name,
+                query,
+                notify,
+                interval_minutes,
+                created_at.to_rfc3339()
+            ],
+        )
+        .with_context(|| format!("Failed to save search {:?}", name))?;
+
+        Ok(SavedSearch {
+            id: conn.last_insert_rowid(),
+            name: name.to_string(),
+            query: query.to_string(),
+            notify,
+            interval_minutes,
+            created_at,
+            last_run_at: None,
+        })
+    }
+
+    fn get_saved_searches(&self) -> Result<Vec<SavedSearch>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, query, notify, interval_minutes, created_at, last_run_at
+             FROM saved_searches
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, bool>(3)?,
+                    row.get::<_, Option<u32>>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(
+                |(id, name, query, notify, interval_minutes, created_at, last_run_at)| {
+                    Ok(SavedSearch {
+                        id,
+                        name,
+                        query,
+                        notify,
+                        interval_minutes,
+                        created_at: DateTime::parse_from_rfc3339(&created_at)?.with_timezone(&Utc),
+                        last_run_at: parse_timestamp(last_run_at),
+                    })
+                },
+            )
+            .collect()
+    }
+
+    fn delete_saved_search(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute("DELETE FROM saved_searches WHERE id = ?", params![id])?;
+        Ok(rows > 0)
+    }
+
+    fn mark_saved_search_run(&self, id: i64, ran_at: DateTime<Utc>) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE saved_searches SET last_run_at = ? WHERE id = ?",
+            params![ran_at.to_rfc3339(), id],
+        )?;
+        Ok(())
+    }
+
+    fn record_alert_match(&self, search_id: i64, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        // Items' created_at is in SQLite `datetime` format, the search's is
+        // RFC 3339, so compare through `datetime`
+        let rows = conn
+            .execute(
+                "INSERT OR IGNORE INTO alert_matches (search_id, item_id, matched_at)
+                 SELECT saved_searches.id, items.id, ?
+                 FROM saved_searches, items
+                 WHERE saved_searches.id = ? AND items.id = ?
+                   AND items.created_at >= datetime(saved_searches.created_at)",
+                params![Utc::now().to_rfc3339(), search_id, item_id.as_str()],
+            )
+            .with_context(|| format!("Failed to record alert match for {}", item_id.as_str()))?;
+        Ok(rows > 0)
+    }
+
+    fn get_alert_items(&self, search_id: Option<i64>, limit: u32) -> Result<Vec<Item>> {
+        let conn = self.conn.lock().unwrap();
+
+        // An item matching several searches is listed once, under the one it
+        // matched last; SQLite takes bare columns from the row with the max
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, alerts.alert_name, alerts.alert_matched_at
+             FROM items
+             JOIN (SELECT alert_matches.item_id AS alert_item_id,
+                          saved_searches.name AS alert_name,
+                          MAX(alert_matches.matched_at) AS alert_matched_at
+                   FROM alert_matches
+                   JOIN saved_searches ON saved_searches.id = alert_matches.search_id
+                   WHERE ?1 IS NULL OR alert_matches.search_id = ?1
+                   GROUP BY alert_matches.item_id) AS alerts
+               ON alerts.alert_item_id = items.id
+             ORDER BY alerts.alert_matched_at DESC, published DESC
+             LIMIT ?2",
+            ITEM_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map(params![search_id, limit], |row| {
+                let mut item = Self::row_to_item(row, self.cipher.as_ref())?;
+                item.metadata
+                    .insert(ALERT_NAME_KEY.to_string(), row.get(17)?);
+                item.metadata
+                    .insert(ALERT_MATCHED_AT_KEY.to_string(), row.get(18)?);
+                Ok(item)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to fetch alert items from cache")?;
+
+        Ok(items)
+    }
+
+    fn pin_item(&self, item_id: &ItemId) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR IGNORE INTO pins (item_id, pinned_at) VALUES (?, ?)",
+            params![item_id.as_str(), Utc::now().to_rfc3339()],
+        )
+        .with_context(|| format!("Failed to pin {}", item_id.as_str()))?;
+
+        Ok(())
+    }
+
+    fn unpin_item(&self, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "DELETE FROM pins WHERE item_id = ?",
+            params![item_id.as_str()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn get_pinned_items(&self) -> Result<Vec<Item>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, pins.pinned_at
+             FROM items JOIN pins ON pins.item_id = items.id
+             ORDER BY pins.pinned_at DESC",
+            ITEM_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map([], |row| {
+                let mut item = Self::row_to_item(row, self.cipher.as_ref())?;
+                item.metadata
+                    .insert(PINNED_AT_KEY.to_string(), row.get(17)?);
+                Ok(item)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to fetch pinned items from cache")?;
+
+        Ok(items)
+    }
+
+    fn get_reading_queue(&self) -> Result<Vec<QueuedItem>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM reading_queue
+             WHERE item_id NOT IN (SELECT id FROM items WHERE is_saved = 1)",
+            [],
+        )?;
+        tx.execute(
+            "INSERT INTO reading_queue (item_id, rank)
+             SELECT id,
+                    (SELECT COALESCE(MAX(rank), 0) FROM reading_queue)
+                        + ROW_NUMBER() OVER (ORDER BY COALESCE(published, created_at), id)
+             FROM items
+             WHERE is_saved = 1 AND id NOT IN (SELECT item_id FROM reading_queue)",
+            [],
+        )?;
+
+        let queue = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT {}, reading_queue.read_offset, reading_queue.opened_at
+                 FROM items JOIN reading_queue ON reading_queue.item_id = items.id
+                 WHERE reading_queue.finished_at IS NULL
+                 ORDER BY reading_queue.rank",
+                ITEM_COLUMNS
+            ))?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    Self::row_to_item(row, self.cipher.as_ref())?,
+                    row.get::<_, u32>(17)?,
+                    row.get::<_, Option<String>>(18)?,
+                ))
+            })?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()
+                .context("Failed to fetch the reading queue from cache")?
+        };
+        tx.commit()?;
+
+        queue
+            .into_iter()
+            .map(|(item, offset, opened_at)| {
+                let opened_at = opened_at
+                    .map(|at| DateTime::parse_from_rfc3339(&at).map(|at| at.with_timezone(&Utc)))
+                    .transpose()?;
+                Ok(QueuedItem {
+                    item,
+                    offset,
+                    opened_at,
+                })
+            })
+            .collect()
+    }
+
+    fn move_queued_item(&self, item_id: &ItemId, index: u32) -> Result<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let mut order = {
+            let mut stmt = tx.prepare(
+                "SELECT item_id FROM reading_queue WHERE finished_at IS NULL ORDER BY rank",
+            )?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+            rows.collect::<rusqlite::Result<Vec<_>>>()?
+        };
+        let Some(from) = order.iter().position(|id| id == item_id.as_str()) else {
+            return Ok(false);
+        };
+        let id = order.remove(from);
+        order.insert((index as usize).min(order.len()), id);
+
+        for (rank, id) in order.iter().enumerate() {
+            tx.execute(
+                "UPDATE reading_queue SET rank = ? WHERE item_id = ?",
+                params![rank as i64 + 1, id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    fn set_queue_offset(&self, item_id: &ItemId, offset: u32) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn
+            .execute(
+                "UPDATE reading_queue SET read_offset = ?, opened_at = ?
+                 WHERE item_id = ? AND finished_at IS NULL",
+                params![offset, Utc::now().to_rfc3339(), item_id.as_str()],
+            )
+            .with_context(|| format!("Failed to record reading offset on {}", item_id.as_str()))?;
+        Ok(rows > 0)
+    }
+
+    fn finish_queued_item(&self, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            "UPDATE reading_queue SET finished_at = ?
+             WHERE item_id = ? AND finished_at IS NULL",
+            params![Utc::now().to_rfc3339(), item_id.as_str()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn queue_outbox(&self, item_id: &ItemId, action: OutboxAction) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO outbox (item_id, action, queued_at) VALUES (?, ?, ?)",
+            params![
+                item_id.as_str(),
+                enum_name(&action)?,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .with_context(|| format!("Failed to queue {} for {}", action, item_id.as_str()))?;
+
+        Ok(())
+    }
+
+    fn remove_outbox(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute("DELETE FROM outbox WHERE id = ?", params![id])?;
+        Ok(rows > 0)
+    }
+
+    fn get_outbox(&self) -> Result<Vec<OutboxEntry>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, item_id, action, queued_at, attempts, last_error
+             FROM outbox
+             ORDER BY id ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, u32>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter()
+            .map(|(id, item_id, action, queued_at, attempts, last_error)| {
+                Ok(OutboxEntry {
+                    id,
+                    item_id: ItemId(item_id),
+                    action: parse_enum(&action)?,
+                    queued_at: DateTime::parse_from_rfc3339(&queued_at)?.with_timezone(&Utc),
+                    attempts,
+                    last_error,
+                })
+            })
+            .collect()
+    }
+
+    fn record_outbox_failure(&self, id: i64, error: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "UPDATE outbox SET attempts = attempts + 1, last_error = ? WHERE id = ?",
+            params![error, id],
+        )?;
+        Ok(())
+    }
+
+    fn record_audit(
+        &self,
+        provider_id: &str,
+        item_id: Option<&ItemId>,
+        action: &str,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO audit_log (timestamp, provider_id, item_id, action, success, error)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                Utc::now().to_rfc3339(),
+                provider_id,
+                item_id.map(ItemId::as_str),
+                action,
+                error.is_none(),
+                error
+            ],
+        )
+        .context("Failed to record audit entry")?;
+        conn.execute(
+            "DELETE FROM audit_log WHERE id <= (SELECT MAX(id) FROM audit_log) - ?",
+            params![MAX_AUDIT_ENTRIES],
+        )?;
+        Ok(())
+    }
+
+    fn get_audit_log(&self, provider_id: Option<&str>, limit: u32) -> Result<Vec<AuditEntry>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, provider_id, item_id, action, success, error
+             FROM audit_log
+             WHERE ?1 IS NULL OR provider_id = ?1
+             ORDER BY id DESC
+             LIMIT ?2",
+        )?;
+        let entries = stmt
+            .query_map(params![provider_id, limit], |row| {
+                Ok(AuditEntry {
+                    id: row.get(0)?,
+                    timestamp: parse_timestamp(row.get(1)?).unwrap_or_else(Utc::now),
+                    provider_id: row.get(2)?,
+                    item_id: row.get::<_, Option<String>>(3)?.map(ItemId),
+                    action: row.get(4)?,
+                    success: row.get(5)?,
+                    error: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()
+            .context("Failed to fetch audit log from cache")?;
+
+        Ok(entries)
+    }
+
+    fn get_item_changes(&self, item_id: &ItemId, limit: u32) -> Result<Vec<ItemChange>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, changed_at, changes
+             FROM item_changes
+             WHERE item_id = ?
+             ORDER BY id DESC
+             LIMIT ?",
+        )?;
+        let rows = stmt
+            .query_map(params![item_id.as_str(), limit], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to fetch item changes from cache")?;
+
+        rows.into_iter()
+            .map(|(id, changed_at, changes)| {
+                let changes = Self::open_column(self.cipher.as_ref(), changes)
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                Ok(ItemChange {
+                    id,
+                    item_id: item_id.clone(),
+                    changed_at: parse_timestamp(Some(changed_at)).unwrap_or_else(Utc::now),
+                    changes: serde_json::from_str(&changes)?,
+                })
+            })
+            .collect()
+    }
+
+    fn acknowledge_changes(&self, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        Self::edit_metadata(&conn, self.cipher.as_ref(), item_id.as_str(), |metadata| {
+            metadata.remove(CHANGED_AT_KEY).is_some()
+        })
+    }
+
+    fn get_tombstones(&self, limit: u32) -> Result<Vec<Tombstone>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT t.item_id, t.stream_id, t.reason, t.removed_at,
+                    EXISTS (SELECT 1 FROM items WHERE id = t.item_id)
+             FROM tombstones t
+             ORDER BY t.removed_at DESC
+             LIMIT ?",
+        )?;
+        let tombstones = stmt
+            .query_map([limit], |row| {
+                Ok(Tombstone {
+                    item_id: ItemId(row.get(0)?),
+                    stream_id: StreamId(row.get(1)?),
+                    reason: row.get(2)?,
+                    removed_at: parse_timestamp(row.get(3)?).unwrap_or_else(Utc::now),
+                    kept: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()
+            .context("Failed to fetch tombstones from cache")?;
+
+        Ok(tombstones)
+    }
+
+    fn is_tombstoned(&self, item_id: &ItemId) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        Self::has_tombstone(&conn, item_id)
+    }
+
+    fn restore_item(&self, item_id: &ItemId) -> Result<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let rows = tx.execute(
+            "DELETE FROM tombstones WHERE item_id = ?",
+            params![item_id.as_str()],
+        )?;
+        Self::edit_metadata(&tx, self.cipher.as_ref(), item_id.as_str(), |metadata| {
+            metadata.remove(REMOVED_METADATA_KEY).is_some()
+        })?;
+
+        tx.commit()?;
+        Ok(rows > 0)
+    }
+
+    fn record_spam_verdict(&self, item_id: &ItemId, spam: bool) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        // Sealed columns are copied as they are, so they stay sealed
+        let rows = conn.execute(
+            "INSERT INTO spam_verdicts (item_id, spam, sender, subject, recorded_at)
+             SELECT id, ?2, author_email, title, ?3 FROM items WHERE id = ?1
+             ON CONFLICT(item_id) DO UPDATE SET
+                spam = excluded.spam,
+                sender = excluded.sender,
+                subject = excluded.subject,
+                recorded_at = excluded.recorded_at",
+            params![item_id.as_str(), spam as i32, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn get_spam_verdicts(&self, limit: u32) -> Result<Vec<SpamVerdict>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT item_id, spam, sender, subject, recorded_at
+             FROM spam_verdicts
+             ORDER BY recorded_at DESC
+             LIMIT ?",
+        )?;
+        let cipher = self.cipher.as_ref();
+        let verdicts = stmt
+            .query_map(params![limit], |row| {
+                Ok(SpamVerdict {
+                    item_id: ItemId(row.get(0)?),
+                    spam: row.get::<_, i32>(1)? != 0,
+                    sender: Self::get_opened(row, 2, cipher)?,
+                    subject: Self::get_opened(row, 3, cipher)?.unwrap_or_default(),
+                    recorded_at: parse_timestamp(row.get(4)?).unwrap_or_else(Utc::now),
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()
+            .context("Failed to fetch spam verdicts from cache")?;
+
+        Ok(verdicts)
+    }
+
+    fn set_attachment_text(
+        &self,
+        item_id: &ItemId,
+        attachment_id: &str,
+        file_name: &str,
+        text: &str,
+    ) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+
+        let rows = conn.execute(
+            &format!(
+                "INSERT INTO attachment_text
+                    (item_id, attachment_id, file_name, text, indexed_at)
+                 SELECT id, ?2, ?3, ?4, ?5 FROM items
+                 WHERE id = ?1 AND title NOT LIKE '{}%'
+                 ON CONFLICT(item_id, attachment_id) DO UPDATE SET
+                    file_name = excluded.file_name,
+                    text = excluded.text,
+                    indexed_at = excluded.indexed_at",
+                SEALED_PREFIX
+            ),
+            params![
+                item_id.as_str(),
+                attachment_id,
+                file_name,
+                text,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(rows > 0)
+    }
+
+    fn set_playback_progress(&self, progress: &PlaybackProgress) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO playback (item_id, position_secs, duration_secs, finished, updated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(item_id) DO UPDATE SET
+                position_secs = excluded.position_secs,
+                duration_secs = excluded.duration_secs,
+                finished = excluded.finished,
+                updated_at = excluded.updated_at",
+            params![
+                progress.item_id.as_str(),
+                progress.position_secs,
+                progress.duration_secs,
+                progress.finished,
+                progress.updated_at.to_rfc3339()
+            ],
+        )
+        .with_context(|| {
+            format!(
+                "Failed to record playback progress on {}",
+                progress.item_id.as_str()
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn get_playback_progress(&self, item_id: &ItemId) -> Result<Option<PlaybackProgress>> {
+        let conn = self.conn.lock().unwrap();
+
+        let row = conn
+            .query_row(
+                "SELECT position_secs, duration_secs, finished, updated_at
+                 FROM playback WHERE item_id = ?",
+                params![item_id.as_str()],
+                |row| {
+                    Ok((
+                        row.get::<_, u64>(0)?,
+                        row.get::<_, Option<u64>>(1)?,
+                        row.get::<_, bool>(2)?,
+                        row.get::<_, String>(3)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        row.map(|(position_secs, duration_secs, finished, updated_at)| {
+            Ok(PlaybackProgress {
+                item_id: item_id.clone(),
+                position_secs,
+                duration_secs,
+                finished,
+                updated_at: DateTime::parse_from_rfc3339(&updated_at)?.with_timezone(&Utc),
+            })
+        })
+        .transpose()
+    }
+
+    fn evict(
+        &self,
+        retention: &CacheConfig,
+        streams: &HashMap<String, StreamPolicy>,
+    ) -> Result<EvictionSummary> {
+        let mut conn = self.conn.lock().unwrap();
+        let order = match retention.eviction {
+            EvictionPolicy::Age => "COALESCE(datetime(published), created_at)",
+            EvictionPolicy::Lru => "COALESCE(accessed_at, created_at)",
+        };
+        let mut summary = EvictionSummary::default();
+
+        let tx = conn.transaction()?;
+        for (stream_id, policy) in streams {
+            if let Some(days) = policy.mark_read_after_days {
+                summary.marked_read += tx.execute(
+                    "UPDATE items SET is_read = 1, read_changed_at = ?, updated_at = datetime('now')
+                     WHERE stream_id = ? AND is_read = 0
+                       AND COALESCE(datetime(published), created_at) < datetime('now', ?)",
+                    params![Utc::now().to_rfc3339(), stream_id, format!("-{} days", days)],
+                )?;
+            }
+        }
+
+        // Streams with an age limit of their own are left to the loop below
+        let own_age: Vec<&String> = streams
+            .iter()
+            .filter(|(_, policy)| policy.max_age_days.is_some())
+            .map(|(stream_id, _)| stream_id)
+            .collect();
+        if let Some(days) = retention.max_age_days {
+            summary.too_old = tx.execute(
+                &format!(
+                    "DELETE FROM items
+                     WHERE {} AND COALESCE(datetime(published), created_at) < datetime('now', ?)
+                       AND stream_id NOT IN ({})",
+                    EVICTABLE,
+                    vec!["?"; own_age.len()].join(", ")
+                ),
+                params_from_iter(
+                    std::iter::once(format!("-{} days", days))
+                        .chain(own_age.iter().map(|stream_id| stream_id.to_string())),
+                ),
+            )?;
+        }
+        for (stream_id, policy) in streams {
+            if let Some(days) = policy.max_age_days {
+                summary.too_old += tx.execute(
+                    &format!(
+                        "DELETE FROM items
+                         WHERE {} AND stream_id = ?
+                           AND COALESCE(datetime(published), created_at) < datetime('now', ?)",
+                        EVICTABLE
+                    ),
+                    params![stream_id, format!("-{} days", days)],
+                )?;
+            }
+        }
+
+        let max_items = |stream_id: &str| {
+            streams
+                .get(stream_id)
+                .and_then(|policy| policy.max_items)
+                .unwrap_or(retention.max_items_per_stream) as i64
+        };
+        let smallest_limit = streams
+            .values()
+            .filter_map(|policy| policy.max_items)
+            .fold(retention.max_items_per_stream, usize::min) as i64;
+        let over_limit: Vec<(String, i64)> = tx
+            .prepare(
+                "SELECT stream_id, COUNT(*) FROM items GROUP BY stream_id HAVING COUNT(*) > ?",
+            )?
+            .query_map([smallest_limit], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (stream_id, count) in over_limit {
+            let max_items = max_items(&stream_id);
+            if count <= max_items {
+                continue;
+            }
+            summary.over_stream_limit += tx.execute(
+                &format!(
+                    "DELETE FROM items WHERE id IN (
+                        SELECT id FROM items WHERE stream_id = ? AND {}
+                        ORDER BY {} ASC LIMIT ?)",
+                    EVICTABLE, order
+                ),
+                params![stream_id, count - max_items],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM tombstones WHERE removed_at < ?",
+            params![(Utc::now() - chrono::Duration::days(TOMBSTONE_DAYS)).to_rfc3339()],
+        )?;
+        Self::delete_orphaned_history(&tx)?;
+        tx.commit()?;
+
+        if let Some(max_size_mb) = retention.max_size_mb {
+            let limit = max_size_mb * 1024 * 1024;
+            while Self::used_bytes(&conn)? > limit {
+                let tx = conn.transaction()?;
+                let removed = tx.execute(
+                    &format!(
+                        "DELETE FROM items WHERE id IN (
+                            SELECT id FROM items WHERE {} ORDER BY {} ASC LIMIT ?)",
+                        EVICTABLE, order
+                    ),
+                    [SIZE_EVICTION_BATCH],
+                )?;
+                Self::delete_orphaned_history(&tx)?;
+                tx.commit()?;
+                if removed == 0 {
+                    warn!(
+                        "Cache is over {} MB but only saved or annotated items remain",
+                        max_size_mb
+                    );
+                    break;
+                }
+                summary.over_size += removed;
+            }
+            if summary.over_size > 0 {
+                conn.execute_batch("VACUUM")
+                    .context("Failed to compact cache database")?;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    fn get_cache_stats(&self) -> Result<CacheStats> {
+        let conn = self.conn.lock().unwrap();
+
+        let (total_items, unread_items, saved_items, annotated_items) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(is_read = 0), 0), COALESCE(SUM(is_saved), 0),
+                    COALESCE(SUM(is_annotated), 0)
+             FROM items",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        let streams = conn
+            .prepare(
+                "SELECT stream_id, COUNT(*) AS items FROM items
+                 GROUP BY stream_id ORDER BY items DESC, stream_id",
+            )?
+            .query_map([], |row| {
+                Ok(StreamItemCount {
+                    stream_id: row.get(0)?,
+                    items: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let page_count: u64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: u64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+
+        Ok(CacheStats {
+            size_bytes: Self::used_bytes(&conn)?,
+            file_bytes: page_count * page_size,
+            total_items,
+            unread_items,
+            saved_items,
+            annotated_items,
+            streams,
+        })
+    }
+
+    fn get_reading_stats(&self, since: DateTime<Utc>) -> Result<ReadingStats> {
+        let conn = self.conn.lock().unwrap();
+        let since_str = since.to_rfc3339();
+
+        // Seconds to read are summed, not averaged, so provider totals can
+        // be weighted by the number of reads
+        let rows = conn
+            .prepare(
+                "SELECT l.stream_id, s.name, l.provider_id,
+                    COUNT(CASE WHEN julianday(l.received_at) >= julianday(?1) THEN 1 END),
+                    COUNT(CASE WHEN julianday(l.read_at) >= julianday(?1) THEN 1 END),
+                    COUNT(CASE WHEN julianday(l.saved_at) >= julianday(?1) THEN 1 END),
+                    TOTAL(CASE WHEN julianday(l.read_at) >= julianday(?1)
+                        THEN MAX(julianday(l.read_at) - julianday(l.received_at), 0) * 86400
+                    END),
+                    (SELECT COUNT(*) FROM items i
+                     WHERE i.stream_id = l.stream_id AND i.is_read = 0)
+                 FROM item_stats l LEFT JOIN streams s ON s.id = l.stream_id
+                 GROUP BY l.stream_id",
+            )?
+            .query_map(params![since_str], |row| {
+                Ok((
+                    StreamReadingStats {
+                        stream_id: row.get(0)?,
+                        name: row.get(1)?,
+                        provider_id: row.get(2)?,
+                        received: row.get(3)?,
+                        read: row.get(4)?,
+                        saved: row.get(5)?,
+                        avg_secs_to_read: None,
+                        unread: row.get(7)?,
+                    },
+                    row.get::<_, f64>(6)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut streams = Vec::new();
+        let mut providers: HashMap<String, (ProviderReadingStats, f64)> = HashMap::new();
+        for (mut stream, secs_to_read) in rows {
+            if stream.received + stream.read + stream.saved == 0 {
+                continue;
+            }
+            let (provider, provider_secs) = providers
+                .entry(stream.provider_id.clone())
+                .or_insert_with(|| {
+                    (
+                        ProviderReadingStats {
+                            provider_id: stream.provider_id.clone(),
+                            received: 0,
+                            read: 0,
+                            saved: 0,
+                            avg_secs_to_read: None,
+                            unread: 0,
+                        },
+                        0.0,
+                    )
+                });
+            provider.received += stream.received;
+            provider.read += stream.read;
+            provider.saved += stream.saved;
+            provider.unread += stream.unread;
+            *provider_secs += secs_to_read;
+
+            stream.avg_secs_to_read = mean_secs(secs_to_read, stream.read);
+            streams.push(stream);
+        }
+        streams.sort_by(|a, b| {
+            b.received
+                .cmp(&a.received)
+                .then_with(|| a.stream_id.cmp(&b.stream_id))
+        });
+
+        let mut providers: Vec<ProviderReadingStats> = providers
+            .into_values()
+            .map(|(mut provider, secs_to_read)| {
+                provider.avg_secs_to_read = mean_secs(secs_to_read, provider.read);
+                provider
+            })
+            .collect();
+        providers.sort_by(|a, b| {
+            b.received
+                .cmp(&a.received)
+                .then_with(|| a.provider_id.cmp(&b.provider_id))
+        });
+
+        let mut days: BTreeMap<NaiveDate, DailyReadingStats> = BTreeMap::new();
+        for (column, is_read) in [("received_at", false), ("read_at", true)] {
+            let counts = conn
+                .prepare(&format!(
+                    "SELECT date({0}), COUNT(*) FROM item_stats
+                     WHERE julianday({0}) >= julianday(?1)
+                     GROUP BY date({0})",
+                    column
+                ))?
+                .query_map(params![since_str], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            for (date, count) in counts {
+                let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")?;
+                let day = days.entry(date).or_insert(DailyReadingStats {
+                    date,
+                    received: 0,
+                    read: 0,
+                });
+                if is_read {
+                    day.read += count;
+                } else {
+                    day.received += count;
+                }
+            }
+        }
+
+        Ok(ReadingStats {
+            since,
+            streams,
+            providers,
+            days: days.into_values().collect(),
+        })
+    }
+
+    fn get_related(&self, item_id: &ItemId, limit: u32) -> Result<Vec<RelatedItem>> {
+        let conn = self.conn.lock().unwrap();
+
+        // Two items that only mention the same page aren't related
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, MIN(CASE
+                        WHEN mine.kind = 'mention' THEN 1
+                        WHEN other.kind = 'mention' THEN 2
+                        ELSE 0
+                    END) AS relation
+             FROM item_links mine
+             JOIN item_links other
+                ON other.url_key = mine.url_key AND other.item_id != mine.item_id
+             JOIN items ON items.id = other.item_id
+             WHERE mine.item_id = ?1 AND (mine.kind = 'own' OR other.kind = 'own')
+             GROUP BY items.id
+             ORDER BY relation, published DESC, created_at DESC
+             LIMIT ?2",
+            ITEM_COLUMNS
+        ))?;
+        let related = stmt
+            .query_map(params![item_id.as_str(), limit], |row| {
+                let relation = match row.get::<_, i64>(17)? {
+                    0 => Relation::SameLink,
+                    1 => Relation::LinksTo,
+                    _ => Relation::LinkedFrom,
+                };
+                Ok(RelatedItem {
+                    relation,
+                    item: Self::row_to_item(row, self.cipher.as_ref())?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()
+            .context("Failed to fetch related items from cache")?;
+
+        Ok(related)
+    }
+
+    fn complete_contacts(&self, prefix: &str, limit: u32) -> Result<Vec<Contact>> {
+        let conn = self.conn.lock().unwrap();
+
+        let escaped = prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let mut stmt = conn.prepare(
+            "WITH seen AS (
+                SELECT email, COUNT(*) AS messages, MAX(seen_at) AS last_seen
+                FROM item_contacts GROUP BY email
+             ),
+             contacts AS (
+                SELECT known.email,
+                       COALESCE(address_book.name,
+                           (SELECT name FROM item_contacts
+                            WHERE email = known.email AND name IS NOT NULL
+                            ORDER BY seen_at DESC LIMIT 1)) AS name,
+                       COALESCE(seen.messages, 0) AS messages,
+                       seen.last_seen,
+                       address_book.email IS NOT NULL AS in_address_book
+                FROM (SELECT email FROM seen UNION SELECT email FROM address_book) known
+                LEFT JOIN seen ON seen.email = known.email
+                LEFT JOIN address_book ON address_book.email = known.email
+             )
+             SELECT email, name, messages, last_seen, in_address_book FROM contacts
+             WHERE email LIKE ?1 ESCAPE '\\' OR name LIKE ?1 ESCAPE '\\'
+                OR name LIKE ?2 ESCAPE '\\'
+             ORDER BY messages DESC, last_seen DESC, email
+             LIMIT ?3",
+        )?;
+        let contacts = stmt
+            .query_map(
+                params![format!("{}%", escaped), format!("% {}%", escaped), limit],
+                |row| {
+                    Ok(Contact {
+                        email: row.get(0)?,
+                        name: row.get(1)?,
+                        messages: row.get(2)?,
+                        last_seen: parse_timestamp(row.get(3)?),
+                        in_address_book: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<_>>()
+            .context("Failed to fetch contacts from cache")?;
+
+        Ok(contacts)
+    }
+
+    fn import_contacts(&self, contacts: &[SeenAddress]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute("DELETE FROM address_book", [])?;
+        let now = Utc::now().to_rfc3339();
+        {
+            // A contact listed twice keeps its first name
+            let mut stmt = tx.prepare(
+                "INSERT INTO address_book (email, name, imported_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(email) DO UPDATE SET name = COALESCE(name, excluded.name)",
+            )?;
+            for contact in contacts {
+                stmt.execute(params![contact.email, contact.name, now])?;
+            }
+        }
+        let count: usize =
+            tx.query_row("SELECT COUNT(*) FROM address_book", [], |row| row.get(0))?;
+
+        tx.commit()?;
+        Ok(count)
+    }
+
+    fn search_items(
+        &self,
+        query: &str,
+        stream_id: Option<&str>,
+        content_type: Option<&str>,
+        is_read: Option<bool>,
+        is_saved: Option<bool>,
+    ) -> Result<Vec<Item>> {
+        self.search(&SearchQuery {
+            stream_id: stream_id.map(str::to_string),
+            content_type: content_type.map(str::to_lowercase),
+            is_read,
+            is_saved,
+            ..SearchQuery::phrase(query)
+        })
+    }
+
+    fn search(&self, query: &SearchQuery) -> Result<Vec<Item>> {
+        let conn = self.conn.lock().unwrap();
+
+        // Build the query dynamically based on filters. The last column marks
+        // sealed items, whose text can only be searched once decrypted.
+        let mut sql = format!(
+            "SELECT {}, title LIKE '{}%'
+             FROM items
+             WHERE 1=1",
+            ITEM_COLUMNS, SEALED_PREFIX
+        );
+
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        // Every word and phrase must appear in the title, serialized content,
+        // any cached transcript or the text and names of indexed attachments.
+        // Sealed items all pass and are matched below; their metadata is not
+        // JSON until decrypted.
+        let needles: Vec<&str> = query.needles().collect();
+        let search_sealed = !needles.is_empty() && self.cipher.is_some();
+        for needle in &needles {
+            sql.push_str(&format!(
+                " AND (title LIKE ? OR content_data LIKE ?
+                       OR CASE WHEN json_valid(metadata)
+                          THEN json_extract(metadata, '$.{}') END LIKE ?
+                       OR id IN (SELECT item_id FROM attachment_text
+                                 WHERE text LIKE ? OR file_name LIKE ?)",
+                crate::summarize::TRANSCRIPT_KEY
+            ));
+            if search_sealed {
+                sql.push_str(&format!(" OR title LIKE '{}%'", SEALED_PREFIX));
+            }
+            sql.push(')');
+            let search_pattern = format!("%{}%", needle);
+            for _ in 0..5 {
+                params_vec.push(Box::new(search_pattern.clone()));
+            }
+        }
+
+        // Add provider filters
+        if !query.providers.is_empty() {
+            sql.push_str(&format!(
+                " AND stream_id IN (SELECT id FROM streams WHERE provider_id IN ({}))",
+                vec!["?"; query.providers.len()].join(", ")
+            ));
+            for provider in &query.providers {
+                params_vec.push(Box::new(provider.clone()));
+            }
+        }
+        for provider in &query.excluded_providers {
+            sql.push_str(" AND stream_id NOT IN (SELECT id FROM streams WHERE provider_id = ?)");
+            params_vec.push(Box::new(provider.clone()));
+        }
+
+        // Add stream filter
+        if let Some(ref stream) = query.stream_id {
+            sql.push_str(" AND stream_id = ?");
+            params_vec.push(Box::new(stream.clone()));
+        }
+
+        // Add tag filters; tags are a JSON array and never sealed
+        for tag in &query.tags {
+            sql.push_str(
+                " AND EXISTS (SELECT 1 FROM json_each(items.tags)
+                              WHERE lower(value) = lower(?))",
+            );
+            params_vec.push(Box::new(tag.clone()));
+        }
+
+        // Add content type filter
+        if let Some(ref ctype) = query.content_type {
+            sql.push_str(" AND lower(content_type) = lower(?)");
+            params_vec.push(Box::new(ctype.clone()));
+        }
+
+        // Add is_read filter
+        if let Some(read_status) = query.is_read {
+            sql.push_str(" AND is_read = ?");
+            params_vec.push(Box::new(read_status as i32));
+        }
+
+        // Add is_saved filter
+        if let Some(saved_status) = query.is_saved {
+            sql.push_str(" AND is_saved = ?");
+            params_vec.push(Box::new(saved_status as i32));
+        }
+
+        // Add date filters; published dates are RFC 3339, so they compare
+        // with a bare date as text
+        if let Some(before) = query.before {
+            sql.push_str(" AND published < ?");
+            params_vec.push(Box::new(before.to_string()));
+        }
+        if let Some(after) = query.after {
+            sql.push_str(" AND published >= ?");
+            params_vec.push(Box::new(after.to_string()));
+        }
+
+        // Order by published date, newest first
+        sql.push_str(" ORDER BY published DESC, created_at DESC");
+        if !search_sealed {
+            sql.push_str(" LIMIT 100");
+        }
+
+        let mut stmt = conn.prepare(&sql)?;
+
+        // Convert params to references for query_map
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec
+            .iter()
+            .map(|p| p.as_ref() as &dyn rusqlite::ToSql)
+            .collect();
+
+        let items = stmt.query_map(params_refs.as_slice(), |row| {
+            Ok((
+                Self::row_to_item(row, self.cipher.as_ref())?,
+                row.get::<_, bool>(17)?,
+            ))
+        })?;
+
+        let needles: Vec<String> = needles.iter().map(|n| n.to_lowercase()).collect();
+        let mut matched = Vec::new();
+        'rows: for row in items {
+            let (item, sealed) = row.context("Failed to search items from cache")?;
+            if sealed {
+                for needle in &needles {
+                    if !Self::item_matches(&item, needle)? {
+                        continue 'rows;
+                    }
+                }
+            }
+            matched.push(item);
+            if matched.len() == 100 {
+                break;
+            }
+        }
+        Ok(matched)
+    }
+
+    fn changed_items(
+        &self,
+        after: Option<&ChangeCursor>,
+        limit: u32,
+    ) -> Result<Vec<(Item, ChangeCursor)>> {
+        let conn = self.conn.lock().unwrap();
+
+        let (changed_at, item_id) = after
+            .map(|cursor| (cursor.changed_at.as_str(), cursor.item_id.as_str()))
+            .unwrap_or_default();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, updated_at
+             FROM items
+             WHERE (updated_at, id) > (?1, ?2) AND title NOT LIKE ?3
+             ORDER BY updated_at, id
+             LIMIT ?4",
+            ITEM_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map(
+                params![changed_at, item_id, format!("{}%", SEALED_PREFIX), limit],
+                |row| {
+                    let item = Self::row_to_item(row, self.cipher.as_ref())?;
+                    let cursor = ChangeCursor {
+                        changed_at: row.get(17)?,
+                        item_id: item.id.0.clone(),
+                    };
+                    Ok((item, cursor))
+                },
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list changed items from cache")?;
+
+        Ok(items)
+    }
+}
+
+// Helper methods for SqliteCache
+impl SqliteCache {
+    /// Whether the title, serialized content or transcript of `item`
+    /// contains `needle`, ignoring case like SQL `LIKE`.
+    fn item_matches(item: &Item, needle: &str) -> Result<bool> {
+        let (_, content_data) = Self::serialize_content(&item.content)?;
+        let transcript = item.metadata.get(crate::summarize::TRANSCRIPT_KEY);
+        Ok(item.title.to_lowercase().contains(needle)
+            || content_data.to_lowercase().contains(needle)
+            || transcript.is_some_and(|text| text.to_lowercase().contains(needle)))
+    }
+
+    /// Decrypt a column value if it is sealed.
+    fn open_column(
+        cipher: Option<&StoreCipher>,
+        value: String,
+    ) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if !StoreCipher::is_sealed(&value) {
+            return Ok(value);
+        }
+        let cipher = cipher.ok_or("Cached item is encrypted but no encryption key is loaded")?;
+        Ok(cipher.open_str(&value)?)
+    }
+
+    /// Read a text column, decrypting it if it is sealed.
+    fn get_opened(
+        row: &rusqlite::Row,
+        idx: usize,
+        cipher: Option<&StoreCipher>,
+    ) -> rusqlite::Result<Option<String>> {
+        row.get::<_, Option<String>>(idx)?
+            .map(|value| Self::open_column(cipher, value))
+            .transpose()
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, e)
+            })
+    }
+
+    /// Convert a database row to an Item, opening sealed columns with `cipher`.
+    fn row_to_item(row: &rusqlite::Row, cipher: Option<&StoreCipher>) -> rusqlite::Result<Item> {
+        let id: String = row.get(0)?;
+        let stream_id: String = row.get(1)?;
+        let title: String = Self::get_opened(row, 2, cipher)?.unwrap_or_default();
+        let content_type: String = row.get(3)?;
+        let content_data: String = Self::get_opened(row, 4, cipher)?.unwrap_or_default();
+        let author_name: Option<String> = Self::get_opened(row, 5, cipher)?;
+        let author_email: Option<String> = Self::get_opened(row, 6, cipher)?;
+        let author_url: Option<String> = row.get(7)?;
+        let author_avatar_url: Option<String> = row.get(8)?;
+        let published: Option<String> = row.get(9)?;
+        let updated: Option<String> = row.get(10)?;
+        let url: Option<String> = row.get(11)?;
+        let thumbnail_url: Option<String> = row.get(12)?;
+        let is_read: i32 = row.get(13)?;
+        let is_saved: i32 = row.get(14)?;
+        let tags_json: String = row.get(15)?;
+        let metadata_json: String = Self::get_opened(row, 16, cipher)?.unwrap_or_default();
+
+        let content = Self::deserialize_content(&content_type, &content_data).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                4,
+                rusqlite::types::Type::Text,
+                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            )
+        })?;
+
+        let author = author_name.map(|name| scryforge_provider_core::Author {
+            name,
+            email: author_email,
+            url: author_url,
+            avatar_url: author_avatar_url,
+        });
+
+        let published = published
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let updated = updated
+            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let tags = Self::deserialize_tags(&tags_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                15,
+                rusqlite::types::Type::Text,
+                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            )
+        })?;
+
+        let metadata = Self::deserialize_metadata(&metadata_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                16,
+                rusqlite::types::Type::Text,
+                Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+            )
+        })?;
+
+        Ok(Item {
+            id: ItemId(id),
+            stream_id: StreamId(stream_id),
+            title,
+            content,
+            author,
+            published,
+            updated,
+            url,
+            thumbnail_url,
+            is_read: is_read != 0,
+            is_saved: is_saved != 0,
+            tags,
+            metadata,
+        })
+    }
+
+    /// Serialize item content to type and JSON data.
+    fn serialize_content(
+        content: &scryforge_provider_core::ItemContent,
+    ) -> Result<(String, String)> {
+        use scryforge_provider_core::ItemContent;
+
+        let (content_type, data) = match content {
+            ItemContent::Text(text) => ("Text", serde_json::json!({"text": text})),
+            ItemContent::Markdown(md) => ("Markdown", serde_json::json!({"markdown": md})),
+            ItemContent::Html(html) => ("Html", serde_json::json!({"html": html})),
+            ItemContent::Email {
+                subject,
+                body_text,
+                body_html,
+                snippet,
             } => (
                 "Email",
                 serde_json::json!({
@@ -938,6 +4421,58 @@ impl SqliteCache {
                     "description": description,
                 }),
             ),
+            ItemContent::Image {
+                url,
+                alt,
+                width,
+                height,
+            } => (
+                "Image",
+                serde_json::json!({
+                    "url": url,
+                    "alt": alt,
+                    "width": width,
+                    "height": height,
+                }),
+            ),
+            ItemContent::Gallery { images } => (
+                "Gallery",
+                serde_json::json!({
+                    "images": images,
+                }),
+            ),
+            ItemContent::Message {
+                text,
+                sender,
+                channel,
+                thread_id,
+                reactions,
+            } => (
+                "Message",
+                serde_json::json!({
+                    "text": text,
+                    "sender": sender,
+                    "channel": channel,
+                    "thread_id": thread_id,
+                    "reactions": reactions,
+                }),
+            ),
+            ItemContent::Patch {
+                description,
+                diff,
+                files_changed,
+                additions,
+                deletions,
+            } => (
+                "Patch",
+                serde_json::json!({
+                    "description": description,
+                    "diff": diff,
+                    "files_changed": files_changed,
+                    "additions": additions,
+                    "deletions": deletions,
+                }),
+            ),
             ItemContent::Generic { body } => (
                 "Generic",
                 serde_json::json!({
@@ -962,492 +4497,1985 @@ impl SqliteCache {
             "Text" => ItemContent::Text(
                 data["text"]
                     .as_str()
-                    .context("Missing text field")?
-                    .to_string(),
-            ),
-            "Markdown" => ItemContent::Markdown(
-                data["markdown"]
+                    .context("Missing text field")?
+                    .to_string(),
+            ),
+            "Markdown" => ItemContent::Markdown(
+                data["markdown"]
+                    .as_str()
+                    .context("Missing markdown field")?
+                    .to_string(),
+            ),
+            "Html" => ItemContent::Html(
+                data["html"]
+                    .as_str()
+                    .context("Missing html field")?
+                    .to_string(),
+            ),
+            "Email" => ItemContent::Email {
+                subject: data["subject"]
+                    .as_str()
+                    .context("Missing subject field")?
+                    .to_string(),
+                body_text: data["body_text"].as_str().map(|s| s.to_string()),
+                body_html: data["body_html"].as_str().map(|s| s.to_string()),
+                snippet: data["snippet"]
+                    .as_str()
+                    .context("Missing snippet field")?
+                    .to_string(),
+            },
+            "Article" => ItemContent::Article {
+                summary: data["summary"].as_str().map(|s| s.to_string()),
+                full_content: data["full_content"].as_str().map(|s| s.to_string()),
+            },
+            "Video" => ItemContent::Video {
+                description: data["description"]
+                    .as_str()
+                    .context("Missing description field")?
+                    .to_string(),
+                duration_seconds: data["duration_seconds"].as_u64().map(|v| v as u32),
+                view_count: data["view_count"].as_u64(),
+            },
+            "Track" => ItemContent::Track {
+                album: data["album"].as_str().map(|s| s.to_string()),
+                duration_ms: data["duration_ms"].as_u64().map(|v| v as u32),
+                artists: data["artists"]
+                    .as_array()
+                    .context("Missing artists field")?
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect(),
+            },
+            "Task" => ItemContent::Task {
+                body: data["body"].as_str().map(|s| s.to_string()),
+                due_date: data["due_date"]
+                    .as_str()
+                    .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()),
+                is_completed: data["is_completed"].as_bool().unwrap_or(false),
+            },
+            "Event" => ItemContent::Event {
+                description: data["description"].as_str().map(|s| s.to_string()),
+                start: data["start"]
                     .as_str()
-                    .context("Missing markdown field")?
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .context("Missing or invalid start field")?,
+                end: data["end"]
+                    .as_str()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .context("Missing or invalid end field")?,
+                location: data["location"].as_str().map(|s| s.to_string()),
+                is_all_day: data["is_all_day"].as_bool().unwrap_or(false),
+            },
+            "Bookmark" => ItemContent::Bookmark {
+                description: data["description"].as_str().map(|s| s.to_string()),
+            },
+            "Image" => ItemContent::Image {
+                url: data["url"]
+                    .as_str()
+                    .context("Missing url field")?
                     .to_string(),
-            ),
-            "Html" => ItemContent::Html(
-                data["html"]
+                alt: data["alt"].as_str().map(|s| s.to_string()),
+                width: data["width"].as_u64().map(|v| v as u32),
+                height: data["height"].as_u64().map(|v| v as u32),
+            },
+            "Gallery" => ItemContent::Gallery {
+                images: serde_json::from_value(data["images"].clone())
+                    .context("Missing or invalid images field")?,
+            },
+            "Message" => ItemContent::Message {
+                text: data["text"]
                     .as_str()
-                    .context("Missing html field")?
+                    .context("Missing text field")?
                     .to_string(),
-            ),
-            "Email" => ItemContent::Email {
-                subject: data["subject"]
+                sender: data["sender"]
                     .as_str()
-                    .context("Missing subject field")?
+                    .context("Missing sender field")?
                     .to_string(),
-                body_text: data["body_text"].as_str().map(|s| s.to_string()),
-                body_html: data["body_html"].as_str().map(|s| s.to_string()),
-                snippet: data["snippet"]
+                channel: data["channel"].as_str().map(|s| s.to_string()),
+                thread_id: data["thread_id"].as_str().map(|s| s.to_string()),
+                reactions: serde_json::from_value(data["reactions"].clone()).unwrap_or_default(),
+            },
+            "Patch" => ItemContent::Patch {
+                description: data["description"].as_str().map(|s| s.to_string()),
+                diff: data["diff"]
                     .as_str()
-                    .context("Missing snippet field")?
+                    .context("Missing diff field")?
                     .to_string(),
+                files_changed: serde_json::from_value(data["files_changed"].clone())
+                    .unwrap_or_default(),
+                additions: data["additions"].as_u64().unwrap_or(0) as u32,
+                deletions: data["deletions"].as_u64().unwrap_or(0) as u32,
+            },
+            "Generic" => ItemContent::Generic {
+                body: data["body"].as_str().map(|s| s.to_string()),
+            },
+            _ => ItemContent::Generic {
+                body: Some(format!("Unknown content type: {}", content_type)),
+            },
+        };
+
+        Ok(content)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changes::ChangedField;
+    use scryforge_provider_core::{GalleryImage, ItemContent, Reaction, StreamType};
+    use tempfile::TempDir;
+
+    fn create_test_cache() -> Result<SqliteCache> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("test.db");
+        let cache = SqliteCache::open_at(&path)?;
+        // Keep tempdir alive by leaking it - tests are short-lived anyway
+        std::mem::forget(temp_dir);
+        Ok(cache)
+    }
+
+    fn create_test_stream(id: &str, provider_id: &str) -> Stream {
+        Stream {
+            id: StreamId(id.to_string()),
+            name: format!("Test Stream {}", id),
+            provider_id: provider_id.to_string(),
+            stream_type: StreamType::Feed,
+            icon: Some("icon.png".to_string()),
+            unread_count: Some(5),
+            total_count: Some(10),
+            last_updated: Some(Utc::now()),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn create_test_item(id: &str, stream_id: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream_id.to_string()),
+            title: format!("Test Item {}", id),
+            content: ItemContent::Text("Test content".to_string()),
+            author: Some(scryforge_provider_core::Author {
+                name: "Test Author".to_string(),
+                email: Some("test@example.com".to_string()),
+                url: None,
+                avatar_url: None,
+            }),
+            published: Some(Utc::now()),
+            updated: None,
+            url: Some("https://example.com".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec!["test".to_string()],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_create_cache() -> Result<()> {
+        let cache = create_test_cache()?;
+        assert!(cache.get_streams(None)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_and_get_streams() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream1 = create_test_stream("test:feed:1", "test-provider");
+        let stream2 = create_test_stream("test:feed:2", "test-provider");
+
+        cache.upsert_streams(&[stream1.clone(), stream2.clone()])?;
+
+        let streams = cache.get_streams(None)?;
+        assert_eq!(streams.len(), 2);
+
+        let streams = cache.get_streams(Some("test-provider"))?;
+        assert_eq!(streams.len(), 2);
+
+        let streams = cache.get_streams(Some("other-provider"))?;
+        assert_eq!(streams.len(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_stream_updates_existing() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let mut stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        stream.name = "Updated Stream".to_string();
+        stream.unread_count = Some(15);
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let streams = cache.get_streams(None)?;
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].name, "Updated Stream");
+        assert_eq!(streams[0].unread_count, Some(15));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_and_get_items() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item1 = create_test_item("test:item:1", "test:feed:1");
+        let item2 = create_test_item("test:item:2", "test:feed:1");
+
+        cache.upsert_items(&[item1.clone(), item2.clone()])?;
+
+        let items = cache.get_items(&StreamId("test:feed:1".to_string()), None)?;
+        assert_eq!(items.len(), 2);
+
+        let items = cache.get_items(&StreamId("test:feed:1".to_string()), Some(1))?;
+        assert_eq!(items.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_item() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        let fetched = cache.get_item(&item.id)?.expect("item should exist");
+        assert_eq!(fetched.title, item.title);
+
+        assert!(cache
+            .get_item(&ItemId("test:item:missing".to_string()))?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mark_read() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        cache.mark_read(&item.id, true)?;
+
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_read);
+
+        cache.mark_read(&item.id, false)?;
+
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(!items[0].is_read);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mark_starred() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        cache.mark_starred(&item.id, true)?;
+
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_saved);
+
+        cache.mark_starred(&item.id, false)?;
+
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), 1);
+        assert!(!items[0].is_saved);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_state() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let provider_id = "test-provider";
+
+        let state = cache.get_sync_state(provider_id)?;
+        assert!(state.is_none());
+
+        let now = Utc::now();
+        cache.update_sync_state(provider_id, now)?;
+
+        let state = cache.get_sync_state(provider_id)?;
+        assert!(state.is_some());
+
+        // Allow for minor timestamp differences due to serialization
+        let diff = (state.unwrap() - now).num_seconds().abs();
+        assert!(diff < 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_different_content_types() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let content_types = vec![
+            ItemContent::Text("Plain text".to_string()),
+            ItemContent::Markdown("# Markdown".to_string()),
+            ItemContent::Html("<p>HTML</p>".to_string()),
+            ItemContent::Email {
+                subject: "Test Email".to_string(),
+                body_text: Some("Body".to_string()),
+                body_html: None,
+                snippet: "Snippet".to_string(),
             },
-            "Article" => ItemContent::Article {
-                summary: data["summary"].as_str().map(|s| s.to_string()),
-                full_content: data["full_content"].as_str().map(|s| s.to_string()),
+            ItemContent::Article {
+                summary: Some("Summary".to_string()),
+                full_content: None,
             },
-            "Video" => ItemContent::Video {
-                description: data["description"]
-                    .as_str()
-                    .context("Missing description field")?
-                    .to_string(),
-                duration_seconds: data["duration_seconds"].as_u64().map(|v| v as u32),
-                view_count: data["view_count"].as_u64(),
+            ItemContent::Video {
+                description: "Video description".to_string(),
+                duration_seconds: Some(120),
+                view_count: Some(1000),
             },
-            "Track" => ItemContent::Track {
-                album: data["album"].as_str().map(|s| s.to_string()),
-                duration_ms: data["duration_ms"].as_u64().map(|v| v as u32),
-                artists: data["artists"]
-                    .as_array()
-                    .context("Missing artists field")?
-                    .iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect(),
+            ItemContent::Track {
+                album: Some("Album".to_string()),
+                duration_ms: Some(180000),
+                artists: vec!["Artist 1".to_string(), "Artist 2".to_string()],
             },
-            "Task" => ItemContent::Task {
-                body: data["body"].as_str().map(|s| s.to_string()),
-                due_date: data["due_date"]
-                    .as_str()
-                    .and_then(|s| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()),
-                is_completed: data["is_completed"].as_bool().unwrap_or(false),
+            ItemContent::Bookmark {
+                description: Some("Bookmark".to_string()),
             },
-            "Event" => ItemContent::Event {
-                description: data["description"].as_str().map(|s| s.to_string()),
-                start: data["start"]
-                    .as_str()
-                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .context("Missing or invalid start field")?,
-                end: data["end"]
-                    .as_str()
-                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .context("Missing or invalid end field")?,
-                location: data["location"].as_str().map(|s| s.to_string()),
-                is_all_day: data["is_all_day"].as_bool().unwrap_or(false),
+            ItemContent::Image {
+                url: "https://example.com/photo.jpg".to_string(),
+                alt: Some("A photo".to_string()),
+                width: Some(640),
+                height: None,
             },
-            "Bookmark" => ItemContent::Bookmark {
-                description: data["description"].as_str().map(|s| s.to_string()),
+            ItemContent::Gallery {
+                images: vec![GalleryImage {
+                    url: "https://example.com/1.jpg".to_string(),
+                    alt: None,
+                    width: Some(800),
+                    height: Some(600),
+                }],
             },
-            "Generic" => ItemContent::Generic {
-                body: data["body"].as_str().map(|s| s.to_string()),
+            ItemContent::Message {
+                text: "Hello".to_string(),
+                sender: "@alice:example.org".to_string(),
+                channel: Some("#rust".to_string()),
+                thread_id: Some("$root".to_string()),
+                reactions: vec![Reaction {
+                    emoji: "👍".to_string(),
+                    count: 2,
+                }],
             },
-            _ => ItemContent::Generic {
-                body: Some(format!("Unknown content type: {}", content_type)),
+            ItemContent::patch(
+                Some("Fix the parser".to_string()),
+                "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n".to_string(),
+            ),
+            ItemContent::Generic {
+                body: Some("Generic content".to_string()),
             },
+        ];
+
+        for (i, content) in content_types.iter().enumerate() {
+            let mut item = create_test_item(&format!("test:item:{}", i), "test:feed:1");
+            item.content = content.clone();
+            cache.upsert_items(&[item])?;
+        }
+
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), content_types.len());
+
+        let gallery = items
+            .iter()
+            .find_map(|item| match &item.content {
+                ItemContent::Gallery { images } => Some(images.clone()),
+                _ => None,
+            })
+            .expect("gallery round-trips");
+        assert_eq!(gallery[0].width, Some(800));
+        assert_eq!(gallery[0].url, "https://example.com/1.jpg");
+
+        let reactions = items
+            .iter()
+            .find_map(|item| match &item.content {
+                ItemContent::Message { reactions, .. } => Some(reactions.clone()),
+                _ => None,
+            })
+            .expect("message round-trips");
+        assert_eq!(reactions[0].count, 2);
+
+        let files_changed = items
+            .iter()
+            .find_map(|item| match &item.content {
+                ItemContent::Patch {
+                    files_changed,
+                    additions: 1,
+                    deletions: 1,
+                    ..
+                } => Some(files_changed.clone()),
+                _ => None,
+            })
+            .expect("patch round-trips");
+        assert_eq!(files_changed, vec!["src/lib.rs"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_foreign_key_cascade() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        // Delete the stream
+        let conn = cache.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM streams WHERE id = ?",
+            params![stream.id.as_str()],
+        )?;
+        drop(conn);
+
+        // Items should also be deleted due to CASCADE
+        let items = cache.get_items(&stream.id, None)?;
+        assert_eq!(items.len(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_items_by_text() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let mut item1 = create_test_item("test:item:1", "test:feed:1");
+        item1.title = "Rust programming tutorial".to_string();
+
+        let mut item2 = create_test_item("test:item:2", "test:feed:1");
+        item2.title = "Python machine learning".to_string();
+
+        let mut item3 = create_test_item("test:item:3", "test:feed:1");
+        item3.title = "Advanced Rust patterns".to_string();
+
+        cache.upsert_items(&[item1, item2, item3])?;
+
+        // Search for "Rust"
+        let results = cache.search_items("Rust", None, None, None, None)?;
+        assert_eq!(results.len(), 2);
+
+        // Search for "Python"
+        let results = cache.search_items("Python", None, None, None, None)?;
+        assert_eq!(results.len(), 1);
+
+        // Search for non-existent term
+        let results = cache.search_items("JavaScript", None, None, None, None)?;
+        assert_eq!(results.len(), 0);
+
+        // Cached transcripts are searched too
+        cache.set_item_metadata(
+            &ItemId("test:item:2".to_string()),
+            crate::summarize::TRANSCRIPT_KEY,
+            "Today we train a JavaScript model",
+        )?;
+        let results = cache.search_items("javascript", None, None, None, None)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id.as_str(), "test:item:2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_items_with_filters() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream1 = create_test_stream("test:feed:1", "test-provider");
+        let stream2 = create_test_stream("test:feed:2", "test-provider");
+        cache.upsert_streams(&[stream1.clone(), stream2.clone()])?;
+
+        let mut item1 = create_test_item("test:item:1", "test:feed:1");
+        item1.title = "Test article".to_string();
+        item1.is_read = false;
+        item1.is_saved = false;
+
+        let mut item2 = create_test_item("test:item:2", "test:feed:1");
+        item2.title = "Another test".to_string();
+        item2.is_read = true;
+        item2.is_saved = false;
+
+        let mut item3 = create_test_item("test:item:3", "test:feed:2");
+        item3.title = "Test item".to_string();
+        item3.is_read = false;
+        item3.is_saved = true;
+
+        cache.upsert_items(&[item1, item2, item3])?;
+
+        // Search for unread items
+        let results = cache.search_items("test", None, None, Some(false), None)?;
+        assert_eq!(results.len(), 2);
+
+        // Search for read items
+        let results = cache.search_items("test", None, None, Some(true), None)?;
+        assert_eq!(results.len(), 1);
+
+        // Search for saved items
+        let results = cache.search_items("test", None, None, None, Some(true))?;
+        assert_eq!(results.len(), 1);
+
+        // Search within specific stream
+        let results = cache.search_items("test", Some("test:feed:1"), None, None, None)?;
+        assert_eq!(results.len(), 2);
+
+        let results = cache.search_items("test", Some("test:feed:2"), None, None, None)?;
+        assert_eq!(results.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_query_fields() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("reddit:feed:rust", "reddit"),
+            create_test_stream("rss:feed:blog", "rss"),
+        ])?;
+
+        let mut old = create_test_item("reddit:1", "reddit:feed:rust");
+        old.title = "Async closures are stable".to_string();
+        old.tags = vec!["Rust".to_string()];
+        old.published = Some("2024-05-20T10:00:00Z".parse().unwrap());
+        let mut new = create_test_item("reddit:2", "reddit:feed:rust");
+        new.title = "Async drop, stable soon?".to_string();
+        new.tags = vec!["rust".to_string(), "news".to_string()];
+        new.is_read = true;
+        let mut blog = create_test_item("rss:1", "rss:feed:blog");
+        blog.title = "Async closures in depth".to_string();
+        cache.upsert_items(&[old, new, blog])?;
+
+        let ids = |query: &str| -> Result<Vec<String>> {
+            let mut ids: Vec<String> = cache
+                .search(&SearchQuery::parse(query)?)?
+                .into_iter()
+                .map(|item| item.id.0)
+                .collect();
+            ids.sort();
+            Ok(ids)
         };
 
-        Ok(content)
+        assert_eq!(ids("async stable")?, vec!["reddit:1", "reddit:2"]);
+        assert_eq!(ids("\"async closures\"")?, vec!["reddit:1", "rss:1"]);
+        assert_eq!(ids("provider:reddit closures")?, vec!["reddit:1"]);
+        assert_eq!(ids("-provider:reddit async")?, vec!["rss:1"]);
+        assert_eq!(ids("tag:rust tag:news")?, vec!["reddit:2"]);
+        assert_eq!(ids("is:unread tag:RUST")?, vec!["reddit:1"]);
+        assert_eq!(ids("before:2024-06-01")?, vec!["reddit:1"]);
+        assert_eq!(ids("after:2024-06-01 provider:reddit")?, vec!["reddit:2"]);
+        assert_eq!(ids("type:text in:rss:feed:blog")?, vec!["rss:1"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_changed_items_pages_in_order() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        cache.upsert_items(&[
+            create_test_item("test:item:1", "test:feed:1"),
+            create_test_item("test:item:2", "test:feed:1"),
+            create_test_item("test:item:3", "test:feed:1"),
+        ])?;
+
+        let first = cache.changed_items(None, 2)?;
+        assert_eq!(first.len(), 2);
+        let rest = cache.changed_items(Some(&first[1].1), 2)?;
+        let ids: Vec<&str> = first
+            .iter()
+            .chain(&rest)
+            .map(|(item, _)| item.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["test:item:1", "test:item:2", "test:item:3"]);
+        assert!(cache.changed_items(Some(&rest[0].1), 2)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_items_empty_query() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item1 = create_test_item("test:item:1", "test:feed:1");
+        let item2 = create_test_item("test:item:2", "test:feed:1");
+
+        cache.upsert_items(&[item1, item2])?;
+
+        // Empty query should return all items (up to limit)
+        let results = cache.search_items("", None, None, None, None)?;
+        assert_eq!(results.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mark_archived() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        // Mark item as archived
+        cache.mark_archived(&item.id, true)?;
+
+        // Verify the operation succeeded (no error)
+        // Note: The Item struct doesn't have is_archived field yet,
+        // but the database column is there and the operation should succeed
+
+        // Unarchive the item
+        cache.mark_archived(&item.id, false)?;
+
+        Ok(())
     }
-}
 
-// ============================================================================
-// Tests
-// ============================================================================
+    #[test]
+    fn test_mark_archived_nonexistent_item() -> Result<()> {
+        let cache = create_test_cache()?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use scryforge_provider_core::{ItemContent, StreamType};
-    use tempfile::TempDir;
+        // Marking non-existent item should not fail, but log a warning
+        cache.mark_archived(&ItemId("nonexistent".to_string()), true)?;
 
-    fn create_test_cache() -> Result<SqliteCache> {
-        let temp_dir = TempDir::new()?;
-        let path = temp_dir.path().join("test.db");
-        let cache = SqliteCache::open_at(&path)?;
-        // Keep tempdir alive by leaking it - tests are short-lived anyway
-        std::mem::forget(temp_dir);
-        Ok(cache)
+        Ok(())
     }
 
-    fn create_test_stream(id: &str, provider_id: &str) -> Stream {
-        Stream {
-            id: StreamId(id.to_string()),
-            name: format!("Test Stream {}", id),
-            provider_id: provider_id.to_string(),
-            stream_type: StreamType::Feed,
-            icon: Some("icon.png".to_string()),
-            unread_count: Some(5),
-            total_count: Some(10),
-            last_updated: Some(Utc::now()),
-            metadata: HashMap::new(),
-        }
+    #[test]
+    fn test_set_item_metadata() -> Result<()> {
+        let cache = create_test_cache()?;
+
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        item.metadata.insert("views".to_string(), "100".to_string());
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        cache.set_item_metadata(&item.id, "summary", "Short version")?;
+
+        let stored = cache.get_item(&item.id)?.unwrap();
+        assert_eq!(stored.metadata.get("summary").unwrap(), "Short version");
+        assert_eq!(stored.metadata.get("views").unwrap(), "100");
+
+        // Unknown items are ignored
+        cache.set_item_metadata(&ItemId("nonexistent".to_string()), "summary", "x")?;
+
+        Ok(())
     }
 
-    fn create_test_item(id: &str, stream_id: &str) -> Item {
-        Item {
-            id: ItemId(id.to_string()),
-            stream_id: StreamId(stream_id.to_string()),
-            title: format!("Test Item {}", id),
-            content: ItemContent::Text("Test content".to_string()),
-            author: Some(scryforge_provider_core::Author {
-                name: "Test Author".to_string(),
-                email: Some("test@example.com".to_string()),
-                url: None,
-                avatar_url: None,
-            }),
-            published: Some(Utc::now()),
-            updated: None,
-            url: Some("https://example.com".to_string()),
-            thumbnail_url: None,
-            is_read: false,
-            is_saved: false,
-            tags: vec!["test".to_string()],
-            metadata: HashMap::new(),
-        }
+    #[test]
+    fn test_item_metadata_survives_sync() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        item.metadata.insert("views".to_string(), "100".to_string());
+        cache.upsert_items(std::slice::from_ref(&item))?;
+        cache.set_item_metadata(&item.id, crate::summarize::SUMMARY_KEY, "Short version")?;
+
+        item.metadata.insert("views".to_string(), "250".to_string());
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::RemoteWins)?;
+        cache.upsert_items(std::slice::from_ref(&item))?;
+
+        let stored = cache.get_item(&item.id)?.unwrap();
+        assert_eq!(
+            stored.metadata.get(crate::summarize::SUMMARY_KEY).unwrap(),
+            "Short version"
+        );
+        assert_eq!(stored.metadata.get("views").unwrap(), "250");
+
+        Ok(())
     }
 
     #[test]
-    fn test_create_cache() -> Result<()> {
+    fn test_items_stamped_with_reading_time() -> Result<()> {
         let cache = create_test_cache()?;
-        assert!(cache.get_streams(None)?.is_empty());
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        item.content = ItemContent::Text("word ".repeat(700));
+        cache.upsert_items(std::slice::from_ref(&item))?;
+        let stored = cache.get_item(&item.id)?.unwrap();
+        assert_eq!(stored.metadata.get(READING_MINUTES_KEY).unwrap(), "4");
+
+        // A provider's update re-estimates it
+        item.content = ItemContent::Text("word ".repeat(100));
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::default())?;
+        let stored = cache.get_item(&item.id)?.unwrap();
+        assert_eq!(stored.metadata.get(READING_MINUTES_KEY).unwrap(), "1");
+
         Ok(())
     }
 
     #[test]
-    fn test_upsert_and_get_streams() -> Result<()> {
+    fn test_interaction_stats() -> Result<()> {
         let cache = create_test_cache()?;
 
-        let stream1 = create_test_stream("test:feed:1", "test-provider");
-        let stream2 = create_test_stream("test:feed:2", "test-provider");
+        let stream = create_test_stream("test:feed:1", "test-provider");
+        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        let item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
 
-        cache.upsert_streams(&[stream1.clone(), stream2.clone()])?;
+        cache.record_interaction(&item.id, InteractionKind::Opened)?;
+        cache.record_interaction(&item.id, InteractionKind::Opened)?;
+        cache.record_interaction(&item.id, InteractionKind::Saved)?;
+        // Unknown items are ignored
+        cache.record_interaction(&ItemId("nonexistent".to_string()), InteractionKind::Skipped)?;
+
+        let stats = cache.get_interaction_stats()?;
+        let expected = InteractionCounts {
+            opened: 2,
+            saved: 1,
+            skipped: 0,
+        };
+        assert_eq!(stats.by_stream.len(), 1);
+        assert_eq!(stats.by_stream["test:feed:1"], expected);
+        assert_eq!(stats.by_author["Test Author"], expected);
 
-        let streams = cache.get_streams(None)?;
-        assert_eq!(streams.len(), 2);
+        Ok(())
+    }
 
-        let streams = cache.get_streams(Some("test-provider"))?;
-        assert_eq!(streams.len(), 2);
+    #[test]
+    fn test_resolve_field() {
+        let t = |secs| Some(DateTime::from_timestamp(secs, 0).unwrap());
+        let resolve = |strategy, local, local_at, remote, remote_at| {
+            resolve_field(
+                strategy,
+                local,
+                local_at,
+                Some(false),
+                t(100),
+                remote,
+                remote_at,
+            )
+        };
 
-        let streams = cache.get_streams(Some("other-provider"))?;
-        assert_eq!(streams.len(), 0);
+        // Unchanged remote keeps local; untouched local takes remote
+        assert_eq!(
+            resolve(ConflictStrategy::RemoteWins, true, t(150), false, None),
+            (true, None)
+        );
+        assert_eq!(
+            resolve(ConflictStrategy::LocalWins, false, t(50), true, None),
+            (true, None)
+        );
+
+        // Both changed to different values
+        assert_eq!(
+            resolve(ConflictStrategy::RemoteWins, false, t(150), true, None),
+            (true, Some(ConflictWinner::Remote))
+        );
+        assert_eq!(
+            resolve(ConflictStrategy::LocalWins, false, t(150), true, None),
+            (false, Some(ConflictWinner::Local))
+        );
+        assert_eq!(
+            resolve(
+                ConflictStrategy::LatestTimestamp,
+                false,
+                t(150),
+                true,
+                t(120)
+            ),
+            (false, Some(ConflictWinner::Local))
+        );
+        assert_eq!(
+            resolve(
+                ConflictStrategy::LatestTimestamp,
+                false,
+                t(150),
+                true,
+                t(180)
+            ),
+            (true, Some(ConflictWinner::Remote))
+        );
+        assert_eq!(
+            resolve(ConflictStrategy::LatestTimestamp, false, t(150), true, None),
+            (true, Some(ConflictWinner::Remote))
+        );
+    }
+
+    #[test]
+    fn test_merge_items_applies_one_sided_changes() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::RemoteWins)?;
+
+        // Read remotely: taken
+        item.is_read = true;
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::LocalWins)?;
+        assert!(cache.get_item(&item.id)?.unwrap().is_read);
+
+        // Saved locally, provider unchanged: kept
+        cache.mark_starred(&item.id, true)?;
+        let conflicts =
+            cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::RemoteWins)?;
+        assert!(conflicts.is_empty());
+        assert!(cache.get_item(&item.id)?.unwrap().is_saved);
 
         Ok(())
     }
 
     #[test]
-    fn test_upsert_stream_updates_existing() -> Result<()> {
+    fn test_merge_items_resolves_and_logs_conflicts() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::default())?;
 
-        let mut stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        // Read in webmail, but deliberately kept unread in Scryforge
+        cache.mark_read(&item.id, true)?;
+        cache.mark_read(&item.id, false)?;
+        item.is_read = true;
 
-        stream.name = "Updated Stream".to_string();
-        stream.unread_count = Some(15);
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        let conflicts =
+            cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::LocalWins)?;
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, StateField::Read);
+        assert_eq!(conflicts[0].winner, ConflictWinner::Local);
+        assert!(!cache.get_item(&item.id)?.unwrap().is_read);
 
-        let streams = cache.get_streams(None)?;
-        assert_eq!(streams.len(), 1);
-        assert_eq!(streams[0].name, "Updated Stream");
-        assert_eq!(streams[0].unread_count, Some(15));
+        // The conflict is settled: the next merge sees no change on either side
+        assert!(cache
+            .merge_items(std::slice::from_ref(&item), ConflictStrategy::LocalWins)?
+            .is_empty());
+
+        // Another local change, now resolved for the provider
+        cache.mark_starred(&item.id, true)?;
+        item.is_read = false;
+        item.is_saved = false;
+        cache.mark_read(&item.id, true)?;
+        let conflicts =
+            cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::RemoteWins)?;
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].winner, ConflictWinner::Remote);
+        assert!(!cache.get_item(&item.id)?.unwrap().is_read);
+        assert!(cache.get_item(&item.id)?.unwrap().is_saved);
+
+        let logged = cache.get_conflicts(10)?;
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].strategy, ConflictStrategy::RemoteWins);
+        assert_eq!(logged[1].strategy, ConflictStrategy::LocalWins);
+        assert_eq!(cache.get_conflicts(1)?.len(), 1);
 
         Ok(())
     }
 
+    fn retention(max_items_per_stream: usize) -> CacheConfig {
+        CacheConfig {
+            max_items_per_stream,
+            ..CacheConfig::default()
+        }
+    }
+
+    /// Insert `count` items into `stream_id`, published a day apart with the
+    /// first one oldest and the last one half a day old.
+    fn insert_aged_items(cache: &SqliteCache, stream_id: &str, count: usize) -> Result<Vec<Item>> {
+        let items: Vec<Item> = (0..count)
+            .map(|i| {
+                let mut item = create_test_item(&format!("{}:item:{}", stream_id, i), stream_id);
+                item.published =
+                    Some(Utc::now() - chrono::Duration::hours((count - i) as i64 * 24 - 12));
+                item
+            })
+            .collect();
+        cache.upsert_items(&items)?;
+        Ok(items)
+    }
+
+    fn cached_ids(cache: &SqliteCache, stream_id: &str) -> Result<Vec<String>> {
+        let mut ids: Vec<String> = cache
+            .get_items(&StreamId(stream_id.to_string()), None)?
+            .into_iter()
+            .map(|item| item.id.0)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
     #[test]
-    fn test_upsert_and_get_items() -> Result<()> {
+    fn test_evict_per_stream_limit_keeps_saved_and_annotated() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "test-provider"),
+            create_test_stream("feed:b", "test-provider"),
+        ])?;
+        let items = insert_aged_items(&cache, "feed:a", 5)?;
+        insert_aged_items(&cache, "feed:b", 2)?;
+        cache.mark_starred(&items[0].id, true)?;
+        cache.set_item_metadata(&items[1].id, "summary", "Short version")?;
+
+        let summary = cache.evict(&retention(3), &HashMap::new())?;
+
+        assert_eq!(summary.over_stream_limit, 2);
+        assert_eq!(
+            cached_ids(&cache, "feed:a")?,
+            vec!["feed:a:item:0", "feed:a:item:1", "feed:a:item:4"]
+        );
+        assert_eq!(cached_ids(&cache, "feed:b")?.len(), 2);
+        assert_eq!(cache.evict(&retention(3), &HashMap::new())?.total(), 0);
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        Ok(())
+    }
 
-        let item1 = create_test_item("test:item:1", "test:feed:1");
-        let item2 = create_test_item("test:item:2", "test:feed:1");
+    #[test]
+    fn test_reminders() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 4)?;
+        let now = Utc::now();
 
-        cache.upsert_items(&[item1.clone(), item2.clone()])?;
+        cache.set_reminder(&items[2].id, now + chrono::Duration::hours(2))?;
+        cache.set_reminder(&items[0].id, now - chrono::Duration::minutes(5))?;
+        cache.mark_reminder_notified(&items[0].id, now)?;
+
+        let reminders = cache.get_reminders()?;
+        assert_eq!(reminders.len(), 2);
+        assert_eq!(reminders[0].item_id, items[0].id);
+        assert!(reminders[0].is_due(now));
+        assert!(reminders[0].notified_at.is_some());
+        assert!(!reminders[1].is_due(now));
+
+        // Setting a new time re-arms the notification
+        cache.set_reminder(&items[0].id, now + chrono::Duration::hours(1))?;
+        let reminders = cache.get_reminders()?;
+        assert_eq!(reminders[0].item_id, items[0].id);
+        assert_eq!(reminders[0].notified_at, None);
+
+        // Reminded items survive eviction
+        cache.evict(&retention(1), &HashMap::new())?;
+        assert_eq!(
+            cached_ids(&cache, "feed:a")?,
+            vec!["feed:a:item:0", "feed:a:item:2"]
+        );
 
-        let items = cache.get_items(&StreamId("test:feed:1".to_string()), None)?;
-        assert_eq!(items.len(), 2);
+        assert!(cache.remove_reminder(&items[0].id)?);
+        assert!(!cache.remove_reminder(&items[0].id)?);
+        assert_eq!(cache.get_reminders()?.len(), 1);
 
-        let items = cache.get_items(&StreamId("test:feed:1".to_string()), Some(1))?;
-        assert_eq!(items.len(), 1);
+        // Unknown items cannot get a reminder
+        assert!(cache
+            .set_reminder(&ItemId("missing".to_string()), now)
+            .is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_mark_read() -> Result<()> {
+    fn test_saved_search_alerts() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 3)?;
+        {
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE items SET created_at = datetime('now', '-1 day') WHERE id = ?",
+                params![items[0].id.as_str()],
+            )?;
+        }
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        let search = cache.create_saved_search("scryforge", "scryforge", true, Some(30))?;
+        let other = cache.create_saved_search("rust", "rust", false, None)?;
+        assert!(cache
+            .create_saved_search("scryforge", "other", true, None)
+            .is_err());
+        assert_eq!(
+            cache.get_saved_searches()?,
+            vec![search.clone(), other.clone()]
+        );
 
-        let item = create_test_item("test:item:1", "test:feed:1");
-        cache.upsert_items(std::slice::from_ref(&item))?;
+        // Items cached before the search was saved are not new matches
+        assert!(!cache.record_alert_match(search.id, &items[0].id)?);
+        assert!(cache.record_alert_match(search.id, &items[1].id)?);
+        assert!(!cache.record_alert_match(search.id, &items[1].id)?);
+        assert!(cache.record_alert_match(other.id, &items[1].id)?);
+        assert!(cache.record_alert_match(other.id, &items[2].id)?);
+        assert!(!cache.record_alert_match(search.id, &ItemId("missing".to_string()))?);
+
+        // Each item is listed once
+        let alerts = cache.get_alert_items(None, 10)?;
+        assert_eq!(alerts.len(), 2);
+        assert!(alerts
+            .iter()
+            .all(|item| item.metadata.contains_key(ALERT_MATCHED_AT_KEY)));
+        let alerts = cache.get_alert_items(Some(search.id), 10)?;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].metadata.get(ALERT_NAME_KEY).unwrap(), "scryforge");
 
-        cache.mark_read(&item.id, true)?;
+        let now = Utc::now();
+        assert!(search.is_due(now));
+        cache.mark_saved_search_run(search.id, now)?;
+        let searches = cache.get_saved_searches()?;
+        assert!(!searches[0].is_due(now + chrono::Duration::minutes(10)));
+        assert!(searches[0].is_due(now + chrono::Duration::minutes(30)));
 
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(items[0].is_read);
+        assert!(cache.delete_saved_search(other.id)?);
+        assert!(!cache.delete_saved_search(other.id)?);
+        assert_eq!(cache.get_alert_items(None, 10)?.len(), 1);
 
-        cache.mark_read(&item.id, false)?;
+        Ok(())
+    }
 
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(!items[0].is_read);
+    #[test]
+    fn test_pinned_items() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "test-provider"),
+            create_test_stream("feed:b", "test-provider"),
+        ])?;
+        let items = insert_aged_items(&cache, "feed:a", 4)?;
+        let other = insert_aged_items(&cache, "feed:b", 1)?;
+
+        cache.pin_item(&items[0].id)?;
+        cache.pin_item(&other[0].id)?;
+        // Pinning is local: the provider's saved flag is left alone
+        assert!(!cache.get_item(&items[0].id)?.unwrap().is_saved);
+
+        // The oldest item now leads its stream
+        let ids: Vec<String> = cache
+            .get_items(&StreamId("feed:a".to_string()), Some(2))?
+            .into_iter()
+            .map(|item| item.id.0)
+            .collect();
+        assert_eq!(ids, vec!["feed:a:item:0", "feed:a:item:3"]);
+
+        let pinned = cache.get_pinned_items()?;
+        assert_eq!(pinned.len(), 2);
+        assert!(pinned
+            .iter()
+            .all(|item| item.metadata.contains_key(PINNED_AT_KEY)));
+
+        // Pinned items survive eviction
+        cache.evict(&retention(1), &HashMap::new())?;
+        assert_eq!(cached_ids(&cache, "feed:a")?, vec!["feed:a:item:0"]);
+
+        assert!(cache.unpin_item(&items[0].id)?);
+        assert!(!cache.unpin_item(&items[0].id)?);
+        assert_eq!(cache.get_pinned_items()?.len(), 1);
+
+        assert!(cache.pin_item(&ItemId("missing".to_string())).is_err());
 
         Ok(())
     }
 
     #[test]
-    fn test_mark_starred() -> Result<()> {
+    fn test_reading_queue() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "test-provider"),
+            create_test_stream("feed:b", "other-provider"),
+        ])?;
+        let items = insert_aged_items(&cache, "feed:a", 3)?;
+        let other = insert_aged_items(&cache, "feed:b", 1)?;
+        for item in [&items[0], &items[2], &other[0]] {
+            cache.mark_starred(&item.id, true)?;
+        }
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        let queue_ids = |cache: &SqliteCache| -> Result<Vec<String>> {
+            Ok(cache
+                .get_reading_queue()?
+                .into_iter()
+                .map(|queued| queued.item.id.0)
+                .collect())
+        };
 
-        let item = create_test_item("test:item:1", "test:feed:1");
-        cache.upsert_items(std::slice::from_ref(&item))?;
+        // Saved items from every provider join oldest first
+        assert_eq!(
+            queue_ids(&cache)?,
+            vec!["feed:a:item:0", "feed:a:item:2", "feed:b:item:0"]
+        );
 
-        cache.mark_starred(&item.id, true)?;
+        assert!(cache.move_queued_item(&items[2].id, 0)?);
+        assert!(cache.move_queued_item(&items[0].id, 10)?);
+        assert!(!cache.move_queued_item(&items[1].id, 0)?);
+        assert_eq!(
+            queue_ids(&cache)?,
+            vec!["feed:a:item:2", "feed:b:item:0", "feed:a:item:0"]
+        );
 
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(items[0].is_saved);
+        assert!(cache.set_queue_offset(&other[0].id, 42)?);
+        assert!(!cache.set_queue_offset(&items[1].id, 1)?);
+        let queue = cache.get_reading_queue()?;
+        assert_eq!(queue[1].offset, 42);
+        assert!(queue[1].opened_at.is_some());
+        assert!(queue[0].opened_at.is_none());
+
+        // Newly saved items join at the end and unsaved ones leave
+        cache.mark_starred(&items[1].id, true)?;
+        cache.mark_starred(&items[2].id, false)?;
+        assert_eq!(
+            queue_ids(&cache)?,
+            vec!["feed:b:item:0", "feed:a:item:0", "feed:a:item:1"]
+        );
 
-        cache.mark_starred(&item.id, false)?;
+        // Finished items stay saved but don't come back
+        assert!(cache.finish_queued_item(&other[0].id)?);
+        assert!(!cache.finish_queued_item(&other[0].id)?);
+        assert!(cache.get_item(&other[0].id)?.unwrap().is_saved);
+        assert_eq!(queue_ids(&cache)?, vec!["feed:a:item:0", "feed:a:item:1"]);
 
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), 1);
-        assert!(!items[0].is_saved);
+        Ok(())
+    }
+
+    #[test]
+    fn test_playback_progress() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 4)?;
+        let progress = |i: usize, position_secs: u64, finished: bool| PlaybackProgress {
+            item_id: items[i].id.clone(),
+            position_secs,
+            duration_secs: Some(600),
+            finished,
+            updated_at: Utc::now(),
+        };
+
+        assert_eq!(cache.get_playback_progress(&items[0].id)?, None);
+        cache.set_playback_progress(&progress(0, 120, false))?;
+        cache.set_playback_progress(&progress(0, 300, false))?;
+        cache.set_playback_progress(&progress(1, 600, true))?;
+        let recorded = cache.get_playback_progress(&items[0].id)?.unwrap();
+        assert_eq!(recorded.position_secs, 300);
+        assert!(!recorded.finished);
+
+        // Progress survives a re-sync of the item
+        cache.upsert_items(&items[..1])?;
+        assert!(cache.get_playback_progress(&items[0].id)?.is_some());
+
+        // Items partway through survive eviction; finished ones do not
+        cache.evict(&retention(1), &HashMap::new())?;
+        assert_eq!(cached_ids(&cache, "feed:a")?, vec!["feed:a:item:0"]);
+        assert_eq!(cache.get_playback_progress(&items[1].id)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_item() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 2)?;
+        cache.set_reminder(&items[0].id, Utc::now())?;
+
+        assert!(cache.delete_item(&items[0].id)?);
+        assert!(!cache.delete_item(&items[0].id)?);
+        assert_eq!(cached_ids(&cache, "feed:a")?, vec!["feed:a:item:1"]);
+        assert!(cache.get_reminders()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_related_links_items_by_url() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("rss:feed:blog", "rss"),
+            create_test_stream("youtube:feed:subs", "youtube"),
+            create_test_stream("reddit:feed:rust", "reddit"),
+            create_test_stream("hn:feed:front", "hn"),
+        ])?;
+        let linked = |id: &str, stream: &str, url: &str, content: &str| {
+            let mut item = create_test_item(id, stream);
+            item.url = Some(url.to_string());
+            item.content = ItemContent::Html(content.to_string());
+            item
+        };
+        let post = linked(
+            "rss:post",
+            "rss:feed:blog",
+            "https://blog.example.com/post",
+            r#"<iframe src="https://www.youtube.com/embed/abc123"></iframe>"#,
+        );
+        cache.upsert_items(&[
+            post.clone(),
+            linked(
+                "youtube:abc123",
+                "youtube:feed:subs",
+                "https://www.youtube.com/watch?v=abc123",
+                "",
+            ),
+            linked(
+                "reddit:t3",
+                "reddit:feed:rust",
+                "https://blog.example.com/post/?utm_source=reddit",
+                "",
+            ),
+            linked(
+                "hn:1",
+                "hn:feed:front",
+                "https://news.ycombinator.com/item?id=1",
+                "Discussed: https://blog.example.com/post.",
+            ),
+            linked(
+                "hn:2",
+                "hn:feed:front",
+                "https://news.ycombinator.com/item?id=2",
+                "Mentions https://www.youtube.com/watch?v=abc123 too",
+            ),
+        ])?;
+
+        let related = cache.get_related(&post.id, 10)?;
+        let related: Vec<(&str, Relation)> = related
+            .iter()
+            .map(|r| (r.item.id.as_str(), r.relation))
+            .collect();
+        assert_eq!(
+            related,
+            vec![
+                ("reddit:t3", Relation::SameLink),
+                ("youtube:abc123", Relation::LinksTo),
+                ("hn:1", Relation::LinkedFrom),
+            ]
+        );
+        assert_eq!(cache.get_related(&post.id, 1)?.len(), 1);
+
+        // Mentioning the same video doesn't relate the post and hn:2
+        let video = ItemId("youtube:abc123".to_string());
+        let ids: Vec<String> = cache
+            .get_related(&video, 10)?
+            .into_iter()
+            .map(|r| r.item.id.0)
+            .collect();
+        assert_eq!(ids, vec!["hn:2", "rss:post"]);
+
+        cache.delete_item(&post.id)?;
+        assert_eq!(cache.get_related(&video, 10)?.len(), 1);
 
         Ok(())
     }
 
     #[test]
-    fn test_sync_state() -> Result<()> {
+    fn test_complete_contacts() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("mail:inbox", "mail")])?;
+        let email = |id: &str, from: &str, to: &str, days_ago: i64| {
+            let mut item = create_test_item(id, "mail:inbox");
+            item.content = ItemContent::Email {
+                subject: "Hello".to_string(),
+                body_text: None,
+                body_html: None,
+                snippet: String::new(),
+            };
+            let (name, address) = from.split_once(" <").unwrap();
+            item.author = Some(scryforge_provider_core::Author {
+                name: name.to_string(),
+                email: Some(address.trim_end_matches('>').to_string()),
+                url: None,
+                avatar_url: None,
+            });
+            item.metadata.insert("to".to_string(), to.to_string());
+            item.published = Some(Utc::now() - chrono::Duration::days(days_ago));
+            item
+        };
+        cache.upsert_items(&[
+            email("mail:1", "Ann Lee <ann@example.com>", "me@example.com", 3),
+            email("mail:2", "Ann Lee <ann@example.com>", "me@example.com", 2),
+            email(
+                "mail:3",
+                "Bob Annis <bob@example.com>",
+                "Me <me@example.com>",
+                0,
+            ),
+        ])?;
 
-        let provider_id = "test-provider";
-
-        let state = cache.get_sync_state(provider_id)?;
-        assert!(state.is_none());
-
-        let now = Utc::now();
-        cache.update_sync_state(provider_id, now)?;
+        let emails = |contacts: Vec<Contact>| -> Vec<String> {
+            contacts.into_iter().map(|c| c.email).collect()
+        };
+        assert_eq!(
+            emails(cache.complete_contacts("", 10)?),
+            vec!["me@example.com", "ann@example.com", "bob@example.com"]
+        );
+        let ann = cache.complete_contacts("ANN", 10)?;
+        assert_eq!(
+            emails(ann.clone()),
+            vec!["ann@example.com", "bob@example.com"]
+        );
+        assert_eq!(ann[0].name.as_deref(), Some("Ann Lee"));
+        assert_eq!(ann[0].messages, 2);
+        assert_eq!(
+            emails(cache.complete_contacts("lee", 10)?),
+            vec!["ann@example.com"]
+        );
+        assert!(cache.complete_contacts("%", 10)?.is_empty());
+
+        // Re-syncing doesn't count a message twice, and eviction forgets nothing
+        cache.upsert_items(&[email("mail:3", "Bob Annis <bob@example.com>", "", 1)])?;
+        cache.delete_item(&ItemId("mail:1".to_string()))?;
+        let me = cache.complete_contacts("me@", 10)?;
+        assert_eq!(me[0].messages, 2);
+        assert_eq!(me[0].name.as_deref(), None);
+        assert_eq!(cache.complete_contacts("ann", 1)?[0].messages, 2);
+
+        let imported = cache.import_contacts(&[
+            SeenAddress {
+                email: "bob@example.com".to_string(),
+                name: Some("Robert Annis".to_string()),
+            },
+            SeenAddress {
+                email: "carol@example.com".to_string(),
+                name: Some("Carol".to_string()),
+            },
+        ])?;
+        assert_eq!(imported, 2);
+        let bob = cache.complete_contacts("rob", 10)?;
+        assert_eq!(bob[0].email, "bob@example.com");
+        assert!(bob[0].in_address_book);
+        let carol = cache.complete_contacts("carol", 10)?;
+        assert_eq!((carol[0].messages, carol[0].last_seen), (0, None));
 
-        let state = cache.get_sync_state(provider_id)?;
-        assert!(state.is_some());
+        Ok(())
+    }
 
-        // Allow for minor timestamp differences due to serialization
-        let diff = (state.unwrap() - now).num_seconds().abs();
-        assert!(diff < 2);
+    #[test]
+    fn test_spam_verdicts() -> Result<()> {
+        let cache =
+            create_test_cache()?.with_encryption(StoreCipher::new(&[1; 32]), ["mail".to_string()]);
+        cache.upsert_streams(&[
+            create_test_stream("mail:inbox", "mail"),
+            create_test_stream("feed:a", "test-provider"),
+        ])?;
+        let prize = create_test_item("mail:msg:1", "mail:inbox");
+        let post = create_test_item("feed:item:1", "feed:a");
+        cache.upsert_items(&[prize.clone(), post.clone()])?;
+
+        assert!(cache.record_spam_verdict(&post.id, true)?);
+        assert!(cache.record_spam_verdict(&prize.id, true)?);
+        assert!(!cache.record_spam_verdict(&ItemId("missing".to_string()), true)?);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.record_spam_verdict(&post.id, false)?);
+
+        // Verdicts survive the item, and sealed columns stay sealed
+        cache.delete_item(&prize.id)?;
+        let verdicts = cache.get_spam_verdicts(10)?;
+        assert_eq!(verdicts.len(), 2);
+        assert_eq!(verdicts[0].item_id, post.id);
+        assert!(!verdicts[0].spam);
+        assert!(verdicts[1].spam);
+        assert_eq!(verdicts[1].subject, "Test Item mail:msg:1");
+        assert_eq!(verdicts[1].sender.as_deref(), Some("test@example.com"));
+        {
+            let conn = cache.conn.lock().unwrap();
+            let subject: String = conn.query_row(
+                "SELECT subject FROM spam_verdicts WHERE item_id = 'mail:msg:1'",
+                [],
+                |row| row.get(0),
+            )?;
+            assert!(StoreCipher::is_sealed(&subject));
+        }
+        assert_eq!(cache.get_spam_verdicts(1)?.len(), 1);
 
         Ok(())
     }
 
     #[test]
-    fn test_different_content_types() -> Result<()> {
+    fn test_audit_log() -> Result<()> {
         let cache = create_test_cache()?;
-
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
-
-        let content_types = vec![
-            ItemContent::Text("Plain text".to_string()),
-            ItemContent::Markdown("# Markdown".to_string()),
-            ItemContent::Html("<p>HTML</p>".to_string()),
-            ItemContent::Email {
-                subject: "Test Email".to_string(),
-                body_text: Some("Body".to_string()),
-                body_html: None,
-                snippet: "Snippet".to_string(),
-            },
-            ItemContent::Article {
-                summary: Some("Summary".to_string()),
-                full_content: None,
-            },
-            ItemContent::Video {
-                description: "Video description".to_string(),
-                duration_seconds: Some(120),
-                view_count: Some(1000),
-            },
-            ItemContent::Track {
-                album: Some("Album".to_string()),
-                duration_ms: Some(180000),
-                artists: vec!["Artist 1".to_string(), "Artist 2".to_string()],
-            },
-            ItemContent::Bookmark {
-                description: Some("Bookmark".to_string()),
-            },
-            ItemContent::Generic {
-                body: Some("Generic content".to_string()),
-            },
-        ];
-
-        for (i, content) in content_types.iter().enumerate() {
-            let mut item = create_test_item(&format!("test:item:{}", i), "test:feed:1");
-            item.content = content.clone();
-            cache.upsert_items(&[item])?;
+        let task = ItemId("tasks:1".to_string());
+        cache.record_audit("tasks", Some(&task), "delete_task", None)?;
+        cache.record_audit(
+            "mail",
+            Some(&ItemId("mail:2".to_string())),
+            "archive",
+            Some("Server unavailable"),
+        )?;
+        cache.record_audit("tasks", None, "sync", None)?;
+
+        let log = cache.get_audit_log(None, 10)?;
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].action, "sync");
+        assert_eq!(log[0].item_id, None);
+        assert!(!log[1].success);
+        assert_eq!(log[1].error.as_deref(), Some("Server unavailable"));
+        assert_eq!(log[2].item_id, Some(task));
+        assert!(log[2].success);
+
+        let tasks = cache.get_audit_log(Some("tasks"), 10)?;
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks.iter().all(|entry| entry.provider_id == "tasks"));
+        assert_eq!(cache.get_audit_log(None, 1)?.len(), 1);
+
+        // Only the latest entries are kept
+        {
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE audit_log SET id = id - ? WHERE id < 3",
+                params![MAX_AUDIT_ENTRIES],
+            )?;
         }
-
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), content_types.len());
+        cache.record_audit("tasks", None, "sync", None)?;
+        assert_eq!(cache.get_audit_log(None, 10)?.len(), 2);
 
         Ok(())
     }
 
     #[test]
-    fn test_foreign_key_cascade() -> Result<()> {
+    fn test_item_changes_are_recorded() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let mut item = create_test_item("test:item:1", "test:feed:1");
+        cache.upsert_items(std::slice::from_ref(&item))?;
+        let stamped = |id: &ItemId| -> Result<bool> {
+            let item = cache.get_item(id)?.unwrap();
+            Ok(item.metadata.contains_key(CHANGED_AT_KEY))
+        };
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
+        // Unchanged or only read elsewhere: nothing recorded
+        item.is_read = true;
+        cache.merge_items(std::slice::from_ref(&item), ConflictStrategy::RemoteWins)?;
+        assert!(cache.get_item_changes(&item.id, 10)?.is_empty());
+        assert!(!stamped(&item.id)?);
 
-        let item = create_test_item("test:item:1", "test:feed:1");
+        item.content = ItemContent::Text("Test content, edited".to_string());
         cache.upsert_items(std::slice::from_ref(&item))?;
+        let changes = cache.get_item_changes(&item.id, 10)?;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].changes[0].field, ChangedField::Body);
+        assert_eq!(
+            changes[0].changes[0].diff.as_deref(),
+            Some("Test [-content-] {+content, edited+}")
+        );
 
-        // Delete the stream
-        let conn = cache.conn.lock().unwrap();
-        conn.execute(
-            "DELETE FROM streams WHERE id = ?",
-            params![stream.id.as_str()],
-        )?;
-        drop(conn);
-
-        // Items should also be deleted due to CASCADE
-        let items = cache.get_items(&stream.id, None)?;
-        assert_eq!(items.len(), 0);
+        // The stamp outlives later syncs until it is acknowledged
+        cache.upsert_items(std::slice::from_ref(&item))?;
+        assert!(stamped(&item.id)?);
+        assert!(cache.acknowledge_changes(&item.id)?);
+        assert!(!cache.acknowledge_changes(&item.id)?);
+        assert!(!stamped(&item.id)?);
+        assert_eq!(cache.get_item_changes(&item.id, 10)?.len(), 1);
+
+        // Only the latest changes are kept
+        for n in 0..MAX_CHANGES_PER_ITEM + 2 {
+            item.title = format!("Title {}", n);
+            cache.upsert_items(std::slice::from_ref(&item))?;
+        }
+        let changes = cache.get_item_changes(&item.id, 100)?;
+        assert_eq!(changes.len(), MAX_CHANGES_PER_ITEM as usize);
+        assert_eq!(
+            changes[0].changes[0].after.as_deref(),
+            Some(format!("Title {}", MAX_CHANGES_PER_ITEM + 1).as_str())
+        );
 
         Ok(())
     }
 
     #[test]
-    fn test_search_items_by_text() -> Result<()> {
+    fn test_lazy_listing_keeps_cached_body() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let strategy = ConflictStrategy::RemoteWins;
+        let item = create_test_item("test:mail", "test:feed:1");
+        cache.merge_items(std::slice::from_ref(&item), strategy)?;
+
+        let mut lazy = item.clone();
+        lazy.content = ItemContent::Text(String::new());
+        lazy.is_read = true;
+        lazy.metadata
+            .insert(LAZY_BODY_METADATA_KEY.to_string(), "true".to_string());
+        cache.merge_items(std::slice::from_ref(&lazy), strategy)?;
+
+        let kept = cache.get_item(&item.id)?.unwrap();
+        assert!(matches!(kept.content, ItemContent::Text(ref text) if text == "Test content"));
+        assert!(!kept.metadata.contains_key(LAZY_BODY_METADATA_KEY));
+        assert!(kept.is_read);
+        assert!(cache.get_item_changes(&item.id, 10)?.is_empty());
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
-
-        let mut item1 = create_test_item("test:item:1", "test:feed:1");
-        item1.title = "Rust programming tutorial".to_string();
-
-        let mut item2 = create_test_item("test:item:2", "test:feed:1");
-        item2.title = "Python machine learning".to_string();
+        Ok(())
+    }
 
-        let mut item3 = create_test_item("test:item:3", "test:feed:1");
-        item3.title = "Advanced Rust patterns".to_string();
+    #[test]
+    fn test_removed_items_stay_removed() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("test:feed:1", "test-provider")])?;
+        let strategy = ConflictStrategy::RemoteWins;
+        let mut post = create_test_item("test:post", "test:feed:1");
+        let task = create_test_item("test:task", "test:feed:1");
+        cache.merge_items(&[post.clone(), task.clone()], strategy)?;
+
+        // Deleted through the daemon: a sync still listing it doesn't bring it back
+        assert!(cache.delete_item(&task.id)?);
+        cache.merge_items(std::slice::from_ref(&task), strategy)?;
+        assert!(cache.get_item(&task.id)?.is_none());
+        assert!(cache.is_tombstoned(&task.id)?);
+
+        // Removed by the provider: the cached copy is kept as it was
+        post.title = "[deleted]".to_string();
+        post.metadata
+            .insert(REMOVED_METADATA_KEY.to_string(), "moderator".to_string());
+        cache.merge_items(std::slice::from_ref(&post), strategy)?;
+        let kept = cache.get_item(&post.id)?.unwrap();
+        assert_eq!(kept.title, "Test Item test:post");
+        assert_eq!(
+            kept.metadata.get(REMOVED_METADATA_KEY).map(String::as_str),
+            Some("moderator")
+        );
 
-        cache.upsert_items(&[item1, item2, item3])?;
+        let tombstones = cache.get_tombstones(10)?;
+        assert_eq!(tombstones.len(), 2);
+        let reasons: HashMap<&str, (&str, bool)> = tombstones
+            .iter()
+            .map(|t| (t.item_id.as_str(), (t.reason.as_str(), t.kept)))
+            .collect();
+        assert_eq!(reasons["test:task"], ("deleted", false));
+        assert_eq!(reasons["test:post"], ("moderator", true));
+
+        // Restored items are cached again by the next sync
+        assert!(cache.restore_item(&task.id)?);
+        assert!(!cache.restore_item(&task.id)?);
+        cache.merge_items(std::slice::from_ref(&task), strategy)?;
+        assert!(cache.get_item(&task.id)?.is_some());
+
+        // Old tombstones are dropped by eviction
+        {
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE tombstones SET removed_at = ?",
+                params![(Utc::now() - chrono::Duration::days(TOMBSTONE_DAYS + 1)).to_rfc3339()],
+            )?;
+        }
+        cache.evict(&CacheConfig::default(), &HashMap::new())?;
+        assert!(cache.get_tombstones(10)?.is_empty());
 
-        // Search for "Rust"
-        let results = cache.search_items("Rust", None, None, None, None)?;
-        assert_eq!(results.len(), 2);
+        Ok(())
+    }
 
-        // Search for "Python"
-        let results = cache.search_items("Python", None, None, None, None)?;
+    #[test]
+    fn test_attachment_text_is_searched() -> Result<()> {
+        let cache =
+            create_test_cache()?.with_encryption(StoreCipher::new(&[1; 32]), ["mail".to_string()]);
+        cache.upsert_streams(&[
+            create_test_stream("imap:inbox", "imap"),
+            create_test_stream("mail:inbox", "mail"),
+        ])?;
+        let invoice = create_test_item("imap:msg:1", "imap:inbox");
+        let sealed = create_test_item("mail:msg:1", "mail:inbox");
+        cache.upsert_items(&[invoice.clone(), sealed.clone()])?;
+
+        assert!(cache.set_attachment_text(
+            &invoice.id,
+            "2",
+            "march.pdf",
+            "Invoice total: 42 EUR"
+        )?);
+        assert!(!cache.set_attachment_text(&sealed.id, "2", "march.pdf", "Invoice")?);
+        assert!(!cache.set_attachment_text(&ItemId("missing".to_string()), "2", "a.pdf", "")?);
+
+        let results = cache.search_items("invoice", None, None, None, None)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, invoice.id);
+        let results = cache.search_items("march.pdf", None, None, None, None)?;
         assert_eq!(results.len(), 1);
 
-        // Search for non-existent term
-        let results = cache.search_items("JavaScript", None, None, None, None)?;
-        assert_eq!(results.len(), 0);
+        // Refreshing the item keeps its attachment text; deleting it does not
+        cache.upsert_items(std::slice::from_ref(&invoice))?;
+        let results = cache.search_items("42 EUR", None, None, None, None)?;
+        assert_eq!(results.len(), 1);
+        cache.delete_item(&invoice.id)?;
+        let conn = cache.conn.lock().unwrap();
+        let rows: u32 =
+            conn.query_row("SELECT COUNT(*) FROM attachment_text", [], |row| row.get(0))?;
+        assert_eq!(rows, 0);
 
         Ok(())
     }
 
     #[test]
-    fn test_search_items_with_filters() -> Result<()> {
+    fn test_evict_max_age() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 10)?;
+        cache.mark_starred(&items[0].id, true)?;
+        for item in &items[..2] {
+            cache.record_interaction(&item.id, InteractionKind::Opened)?;
+            cache.conn.lock().unwrap().execute(
+                "INSERT INTO state_conflicts
+                    (item_id, field, local_value, remote_value, winner, strategy, resolved_at)
+                 VALUES (?, 'read', 1, 0, 'local', 'newest', datetime('now'))",
+                [item.id.as_str()],
+            )?;
+        }
 
-        let stream1 = create_test_stream("test:feed:1", "test-provider");
-        let stream2 = create_test_stream("test:feed:2", "test-provider");
-        cache.upsert_streams(&[stream1.clone(), stream2.clone()])?;
+        let summary = cache.evict(
+            &CacheConfig {
+                max_age_days: Some(5),
+                ..CacheConfig::default()
+            },
+            &HashMap::new(),
+        )?;
 
-        let mut item1 = create_test_item("test:item:1", "test:feed:1");
-        item1.title = "Test article".to_string();
-        item1.is_read = false;
-        item1.is_saved = false;
+        // Items 0 to 4 are more than five days old; the saved one stays
+        assert_eq!(summary.too_old, 4);
+        assert_eq!(cached_ids(&cache, "feed:a")?.len(), 6);
+        assert!(cache.get_item(&items[0].id)?.is_some());
 
-        let mut item2 = create_test_item("test:item:2", "test:feed:1");
-        item2.title = "Another test".to_string();
-        item2.is_read = true;
-        item2.is_saved = false;
+        // The history of evicted items goes with them
+        let conn = cache.conn.lock().unwrap();
+        for table in ["interactions", "state_conflicts"] {
+            let item_ids: Vec<String> = conn
+                .prepare(&format!("SELECT item_id FROM {}", table))?
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            assert_eq!(item_ids, [items[0].id.0.clone()], "{}", table);
+        }
 
-        let mut item3 = create_test_item("test:item:3", "test:feed:2");
-        item3.title = "Test item".to_string();
-        item3.is_read = false;
-        item3.is_saved = true;
+        Ok(())
+    }
 
-        cache.upsert_items(&[item1, item2, item3])?;
+    #[test]
+    fn test_evict_stream_policies_override_cache_limits() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "test-provider"),
+            create_test_stream("feed:b", "test-provider"),
+            create_test_stream("feed:c", "test-provider"),
+        ])?;
+        let items = insert_aged_items(&cache, "feed:a", 10)?;
+        insert_aged_items(&cache, "feed:b", 4)?;
+        insert_aged_items(&cache, "feed:c", 10)?;
+        let streams = HashMap::from([
+            (
+                "feed:a".to_string(),
+                StreamPolicy {
+                    max_items: Some(3),
+                    mark_read_after_days: Some(1),
+                    ..StreamPolicy::default()
+                },
+            ),
+            (
+                "feed:b".to_string(),
+                StreamPolicy {
+                    max_age_days: Some(2),
+                    ..StreamPolicy::default()
+                },
+            ),
+            (
+                "feed:c".to_string(),
+                StreamPolicy {
+                    max_items: Some(20),
+                    max_age_days: Some(30),
+                    ..StreamPolicy::default()
+                },
+            ),
+        ]);
 
-        // Search for unread items
-        let results = cache.search_items("test", None, None, Some(false), None)?;
-        assert_eq!(results.len(), 2);
+        let summary = cache.evict(
+            &CacheConfig {
+                max_age_days: Some(8),
+                ..retention(6)
+            },
+            &streams,
+        )?;
 
-        // Search for read items
-        let results = cache.search_items("test", None, None, Some(true), None)?;
-        assert_eq!(results.len(), 1);
+        // feed:a loses two items to the global age limit and five to its own
+        // item limit; feed:b two to its own age limit; feed:c keeps all ten
+        assert_eq!(
+            summary,
+            EvictionSummary {
+                too_old: 4,
+                over_stream_limit: 5,
+                over_size: 0,
+                marked_read: 9,
+            }
+        );
+        assert_eq!(cached_ids(&cache, "feed:a")?.len(), 3);
+        assert_eq!(cached_ids(&cache, "feed:b")?.len(), 2);
+        assert_eq!(cached_ids(&cache, "feed:c")?.len(), 10);
+        assert!(cache.get_item(&items[8].id)?.unwrap().is_read);
+        assert!(!cache.get_item(&items[9].id)?.unwrap().is_read);
 
-        // Search for saved items
-        let results = cache.search_items("test", None, None, None, Some(true))?;
-        assert_eq!(results.len(), 1);
+        Ok(())
+    }
 
-        // Search within specific stream
-        let results = cache.search_items("test", Some("test:feed:1"), None, None, None)?;
-        assert_eq!(results.len(), 2);
+    #[test]
+    fn test_evict_lru_keeps_recently_opened() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items = insert_aged_items(&cache, "feed:a", 3)?;
+        {
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE items SET created_at = datetime('now', '-1 day')",
+                [],
+            )?;
+        }
+        // Opening the oldest item makes it the most recently used
+        cache.mark_read(&items[0].id, true)?;
 
-        let results = cache.search_items("test", Some("test:feed:2"), None, None, None)?;
-        assert_eq!(results.len(), 1);
+        let summary = cache.evict(
+            &CacheConfig {
+                eviction: EvictionPolicy::Lru,
+                ..retention(1)
+            },
+            &HashMap::new(),
+        )?;
+
+        assert_eq!(summary.over_stream_limit, 2);
+        assert_eq!(cached_ids(&cache, "feed:a")?, vec!["feed:a:item:0"]);
 
         Ok(())
     }
 
     #[test]
-    fn test_search_items_empty_query() -> Result<()> {
+    fn test_evict_max_size() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[create_test_stream("feed:a", "test-provider")])?;
+        let items: Vec<Item> = (0..600)
+            .map(|i| {
+                let mut item = create_test_item(&format!("feed:a:item:{}", i), "feed:a");
+                item.content = ItemContent::Text("x".repeat(4096));
+                item
+            })
+            .collect();
+        cache.upsert_items(&items)?;
+        cache.mark_starred(&items[0].id, true)?;
+        assert!(cache.get_cache_stats()?.size_bytes > 2 * 1024 * 1024);
+
+        let summary = cache.evict(
+            &CacheConfig {
+                max_size_mb: Some(1),
+                ..retention(10_000)
+            },
+            &HashMap::new(),
+        )?;
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
-
-        let item1 = create_test_item("test:item:1", "test:feed:1");
-        let item2 = create_test_item("test:item:2", "test:feed:1");
+        let stats = cache.get_cache_stats()?;
+        assert!(summary.over_size > 0);
+        assert!(stats.size_bytes <= 1024 * 1024);
+        assert!(stats.file_bytes <= 1024 * 1024);
+        assert_eq!(stats.saved_items, 1);
 
-        cache.upsert_items(&[item1, item2])?;
+        Ok(())
+    }
 
-        // Empty query should return all items (up to limit)
-        let results = cache.search_items("", None, None, None, None)?;
-        assert_eq!(results.len(), 2);
+    #[test]
+    fn test_cache_stats() -> Result<()> {
+        let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "test-provider"),
+            create_test_stream("feed:b", "test-provider"),
+        ])?;
+        let items = insert_aged_items(&cache, "feed:a", 3)?;
+        insert_aged_items(&cache, "feed:b", 1)?;
+        cache.mark_read(&items[0].id, true)?;
+        cache.mark_starred(&items[1].id, true)?;
+        cache.set_item_metadata(&items[2].id, "summary", "Short version")?;
+
+        let stats = cache.get_cache_stats()?;
+
+        assert_eq!(stats.total_items, 4);
+        assert_eq!(stats.unread_items, 3);
+        assert_eq!(stats.saved_items, 1);
+        assert_eq!(stats.annotated_items, 1);
+        assert!(stats.size_bytes > 0 && stats.size_bytes <= stats.file_bytes);
+        assert_eq!(
+            stats.streams,
+            vec![
+                StreamItemCount {
+                    stream_id: "feed:a".to_string(),
+                    items: 3
+                },
+                StreamItemCount {
+                    stream_id: "feed:b".to_string(),
+                    items: 1
+                },
+            ]
+        );
 
         Ok(())
     }
 
     #[test]
-    fn test_mark_archived() -> Result<()> {
+    fn test_reading_stats() -> Result<()> {
         let cache = create_test_cache()?;
+        cache.upsert_streams(&[
+            create_test_stream("feed:a", "rss"),
+            create_test_stream("feed:b", "rss"),
+            create_test_stream("feed:c", "reddit"),
+        ])?;
+        cache.upsert_items(&[
+            create_test_item("a1", "feed:a"),
+            create_test_item("a2", "feed:a"),
+            create_test_item("a3", "feed:a"),
+            create_test_item("b1", "feed:b"),
+            create_test_item("c1", "feed:c"),
+        ])?;
+        {
+            // a1 arrived an hour ago, c1 before the window
+            let conn = cache.conn.lock().unwrap();
+            conn.execute(
+                "UPDATE item_stats SET received_at = ? WHERE item_id = 'a1'",
+                [(Utc::now() - chrono::Duration::hours(1)).to_rfc3339()],
+            )?;
+            conn.execute(
+                "UPDATE item_stats SET received_at = ? WHERE item_id = 'c1'",
+                [(Utc::now() - chrono::Duration::days(60)).to_rfc3339()],
+            )?;
+        }
+        cache.mark_read(&ItemId("a1".to_string()), true)?;
+        cache.mark_read(&ItemId("a1".to_string()), false)?;
+        cache.mark_read(&ItemId("a1".to_string()), true)?;
+        cache.mark_starred(&ItemId("a2".to_string()), true)?;
+        cache.mark_read(&ItemId("c1".to_string()), true)?;
+        // Evicted items stay counted
+        cache.delete_item(&ItemId("b1".to_string()))?;
+
+        let stats = cache.get_reading_stats(Utc::now() - chrono::Duration::days(30))?;
+
+        let ids: Vec<&str> = stats.streams.iter().map(|s| s.stream_id.as_str()).collect();
+        assert_eq!(ids, vec!["feed:a", "feed:b", "feed:c"]);
+        let a = &stats.streams[0];
+        assert_eq!(a.name.as_deref(), Some("Test Stream feed:a"));
+        assert_eq!((a.received, a.read, a.saved, a.unread), (3, 1, 1, 2));
+        let secs = a.avg_secs_to_read.unwrap();
+        assert!((3590..=3610).contains(&secs), "{}", secs);
+        assert_eq!(stats.streams[1].received, 1);
+        let c = &stats.streams[2];
+        assert_eq!((c.received, c.read), (0, 1));
+
+        let providers: Vec<(&str, u64, u64)> = stats
+            .providers
+            .iter()
+            .map(|p| (p.provider_id.as_str(), p.received, p.read))
+            .collect();
+        assert_eq!(providers, vec![("rss", 4, 1), ("reddit", 0, 1)]);
 
-        let stream = create_test_stream("test:feed:1", "test-provider");
-        cache.upsert_streams(std::slice::from_ref(&stream))?;
-
-        let item = create_test_item("test:item:1", "test:feed:1");
-        cache.upsert_items(std::slice::from_ref(&item))?;
+        assert_eq!(stats.days.last().unwrap().date, Utc::now().date_naive());
+        let received: u64 = stats.days.iter().map(|d| d.received).sum();
+        let read: u64 = stats.days.iter().map(|d| d.read).sum();
+        assert_eq!((received, read), (4, 2));
 
-        // Mark item as archived
-        cache.mark_archived(&item.id, true)?;
+        Ok(())
+    }
 
-        // Verify the operation succeeded (no error)
-        // Note: The Item struct doesn't have is_archived field yet,
-        // but the database column is there and the operation should succeed
+    fn raw_title(cache: &SqliteCache, item_id: &str) -> Result<String> {
+        let conn = cache.conn.lock().unwrap();
+        Ok(
+            conn.query_row("SELECT title FROM items WHERE id = ?", [item_id], |row| {
+                row.get(0)
+            })?,
+        )
+    }
 
-        // Unarchive the item
-        cache.mark_archived(&item.id, false)?;
+    #[test]
+    fn test_encrypted_provider_items_are_sealed() -> Result<()> {
+        let cache =
+            create_test_cache()?.with_encryption(StoreCipher::new(&[1; 32]), ["mail".to_string()]);
+        cache.upsert_streams(&[
+            create_test_stream("mail:inbox", "mail"),
+            create_test_stream("feed:a", "test-provider"),
+        ])?;
+        let mut mail = create_test_item("mail:msg:1", "mail:inbox");
+        mail.title = "Offer letter".to_string();
+        cache.upsert_items(&[mail.clone(), create_test_item("feed:item:1", "feed:a")])?;
+
+        let stored = raw_title(&cache, "mail:msg:1")?;
+        assert!(StoreCipher::is_sealed(&stored));
+        assert!(!stored.contains("Offer"));
+        assert_eq!(raw_title(&cache, "feed:item:1")?, "Test Item feed:item:1");
+
+        let fetched = cache.get_item(&mail.id)?.unwrap();
+        assert_eq!(fetched.title, "Offer letter");
+        assert_eq!(fetched.author.unwrap().email.unwrap(), "test@example.com");
+
+        let found = cache.search_items("offer", None, None, None, None)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, mail.id);
+        assert_eq!(cache.search_items("", None, None, None, None)?.len(), 2);
+
+        cache.set_item_metadata(&mail.id, "summary", "A job offer")?;
+        assert_eq!(
+            cache.get_item(&mail.id)?.unwrap().metadata["summary"],
+            "A job offer"
+        );
+        cache.set_item_metadata(
+            &mail.id,
+            crate::summarize::TRANSCRIPT_KEY,
+            "Starting salary attached",
+        )?;
+        let found = cache.search_items("salary", None, None, None, None)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, mail.id);
+        {
+            let conn = cache.conn.lock().unwrap();
+            let metadata: String = conn.query_row(
+                "SELECT metadata FROM items WHERE id = 'mail:msg:1'",
+                [],
+                |row| row.get(0),
+            )?;
+            assert!(StoreCipher::is_sealed(&metadata));
+        }
 
         Ok(())
     }
 
     #[test]
-    fn test_mark_archived_nonexistent_item() -> Result<()> {
-        let cache = create_test_cache()?;
+    fn test_apply_encryption_follows_provider_setting() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join("test.db");
+        let cipher = StoreCipher::new(&[2; 32]);
 
-        // Marking non-existent item should not fail, but log a warning
-        cache.mark_archived(&ItemId("nonexistent".to_string()), true)?;
+        let cache = SqliteCache::open_at(&path)?;
+        cache.upsert_streams(&[create_test_stream("mail:inbox", "mail")])?;
+        cache.upsert_items(&[create_test_item("mail:msg:1", "mail:inbox")])?;
+
+        // Turned on: existing items are sealed
+        let cache = cache.with_encryption(cipher.clone(), ["mail".to_string()]);
+        assert_eq!(cache.apply_encryption()?, 1);
+        assert!(StoreCipher::is_sealed(&raw_title(&cache, "mail:msg:1")?));
+        assert_eq!(cache.apply_encryption()?, 0);
+        drop(cache);
+
+        // Without the key, sealed items cannot be read or converted
+        let cache = SqliteCache::open_at(&path)?;
+        assert_eq!(cache.apply_encryption()?, 0);
+        assert!(cache.get_item(&ItemId("mail:msg:1".to_string())).is_err());
+        drop(cache);
+
+        // Turned off with the key loaded: items are opened again
+        let cache = SqliteCache::open_at(&path)?.with_encryption(cipher, Vec::new());
+        assert_eq!(cache.apply_encryption()?, 1);
+        assert_eq!(raw_title(&cache, "mail:msg:1")?, "Test Item mail:msg:1");
 
         Ok(())
     }
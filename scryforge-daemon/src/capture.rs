@@ -0,0 +1,376 @@
+//! Bookmark capture endpoint for browser extensions and bookmarklets.
+//!
+//! A small HTTP server (axum) on a loopback address that saves pages into
+//! the local bookmarks provider, so a browser can bookmark the current page
+//! with one click.
+//!
+//! # Routes
+//!
+//! - `POST /bookmarks` - save a page, given as a JSON body:
+//!
+//! ```json
+//! {"url": "https://example.com", "title": "Example", "description": "...",
+//!  "tags": ["to-read"], "folder": "Inbox", "token": "..."}
+//! ```
+//!
+//! Only `url` is required. The body is read as JSON whatever its content
+//! type, so a bookmarklet can post it as `text/plain` without a CORS
+//! preflight. The token goes in an `Authorization: Bearer` header or the
+//! `token` field; it is `capture.token` or any `[[daemon.clients]]` token.
+//!
+//! A page bookmarked before is answered with `200 OK` and its existing
+//! bookmark, which gains any new tags; a new bookmark gets `201 Created`.
+//! Either way the reply is `{"id": "bookmarks:<id>", "created": bool}`.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use provider_bookmarks::BookmarksProvider;
+use scryforge_provider_core::{ItemId, StreamError};
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::api::auth::find_client;
+use crate::config::{CaptureConfig, ClientConfig};
+use crate::registry::ProviderRegistry;
+
+/// Shared state for the capture handler.
+struct CaptureState {
+    registry: Arc<ProviderRegistry>,
+    /// Tokens accepted from pages: `capture.token` and the daemon's clients
+    clients: Vec<ClientConfig>,
+    folder: Option<String>,
+}
+
+/// A page posted by a browser.
+#[derive(Debug, Deserialize)]
+struct CapturedPage {
+    url: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    folder: Option<String>,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Start the capture server in a background task.
+///
+/// # Returns
+///
+/// The task handle (abort it to stop the server) and the bound address.
+pub async fn start_capture_server(
+    registry: Arc<ProviderRegistry>,
+    config: &CaptureConfig,
+    clients: Vec<ClientConfig>,
+) -> Result<(JoinHandle<()>, SocketAddr)> {
+    let app = router(registry, config, clients);
+
+    let listener = TcpListener::bind(config.bind_address.as_str())
+        .await
+        .with_context(|| format!("Failed to bind bookmark capture to {}", config.bind_address))?;
+    let addr = listener
+        .local_addr()
+        .context("Failed to get bookmark capture address")?;
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Bookmark capture stopped: {}", e);
+        }
+    });
+
+    info!("Bookmark capture listening on http://{}/bookmarks", addr);
+
+    Ok((handle, addr))
+}
+
+fn router(
+    registry: Arc<ProviderRegistry>,
+    config: &CaptureConfig,
+    mut clients: Vec<ClientConfig>,
+) -> Router {
+    if let Some(token) = config.token.as_ref().filter(|t| !t.is_empty()) {
+        clients.push(ClientConfig {
+            name: "capture".to_string(),
+            token: token.clone(),
+        });
+    }
+    let state = Arc::new(CaptureState {
+        registry,
+        clients,
+        folder: config.folder.clone(),
+    });
+
+    Router::new()
+        .route("/bookmarks", post(add_bookmark).options(preflight))
+        .with_state(state)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn add_bookmark(
+    State(state): State<Arc<CaptureState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let page: CapturedPage = match serde_json::from_slice(&body) {
+        Ok(page) => page,
+        Err(e) => return reply(StatusCode::BAD_REQUEST, &format!("Invalid page: {}", e)),
+    };
+
+    let token = bearer_token(&headers).or(page.token.as_deref());
+    if token.is_none_or(|token| find_client(&state.clients, token).is_none()) {
+        warn!("Rejected bookmark capture without a valid token");
+        return reply(StatusCode::UNAUTHORIZED, "Missing or invalid token");
+    }
+
+    let url = page.url.trim().to_string();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return reply(
+            StatusCode::BAD_REQUEST,
+            "Only http and https pages can be bookmarked",
+        );
+    }
+
+    let provider = state.registry.get("bookmarks");
+    let Some(bookmarks) = provider
+        .as_ref()
+        .and_then(|p| p.as_any().downcast_ref::<BookmarksProvider>())
+    else {
+        return reply(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Bookmarks provider not loaded",
+        );
+    };
+
+    let title = page
+        .title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| url.clone());
+    let description = page
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    let mut tags: Vec<String> = Vec::new();
+    for tag in page.tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    let folder = page.folder.or_else(|| state.folder.clone());
+
+    match bookmarks.capture(title, url, folder.as_deref(), description, tags) {
+        Ok((bookmark, created)) => {
+            let id = ItemId::new("bookmarks", &bookmark.id);
+            info!("Captured bookmark {} ({})", bookmark.url, id.as_str());
+            let status = if created {
+                StatusCode::CREATED
+            } else {
+                StatusCode::OK
+            };
+            let body = serde_json::json!({ "id": id.as_str(), "created": created });
+            (status, cors(), Json(body)).into_response()
+        }
+        Err(StreamError::StreamNotFound(folder)) => reply(
+            StatusCode::BAD_REQUEST,
+            &format!("Folder not found: {}", folder),
+        ),
+        Err(e) => {
+            error!("Failed to capture bookmark: {}", e);
+            reply(StatusCode::INTERNAL_SERVER_ERROR, "Failed to save bookmark")
+        }
+    }
+}
+
+/// Answer a CORS preflight, for extensions posting JSON with a bearer token.
+async fn preflight() -> Response {
+    (
+        StatusCode::NO_CONTENT,
+        cors(),
+        [
+            (header::ACCESS_CONTROL_ALLOW_METHODS, "POST, OPTIONS"),
+            (
+                header::ACCESS_CONTROL_ALLOW_HEADERS,
+                "authorization, content-type",
+            ),
+        ],
+    )
+        .into_response()
+}
+
+/// Extract the token from a `Bearer` authorization header.
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Pages post from any site's origin; the token is what keeps them out.
+fn cors() -> [(header::HeaderName, HeaderValue); 1] {
+    [(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    )]
+}
+
+fn reply(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        cors(),
+        Json(serde_json::json!({ "error": message })),
+    )
+        .into_response()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn create_test_router(temp_dir: &TempDir) -> Router {
+        let bookmarks =
+            BookmarksProvider::with_path(temp_dir.path().join("bookmarks.json")).unwrap();
+        bookmarks
+            .add_folder("Inbox".to_string(), None, None)
+            .unwrap();
+        let mut registry = ProviderRegistry::new();
+        registry.register(bookmarks);
+
+        let config = CaptureConfig {
+            enabled: true,
+            token: Some("secret".to_string()),
+            folder: Some("Inbox".to_string()),
+            ..Default::default()
+        };
+        let clients = vec![ClientConfig {
+            name: "tui".to_string(),
+            token: "client-token".to_string(),
+        }];
+        router(Arc::new(registry), &config, clients)
+    }
+
+    async fn post(app: Router, body: &str, auth: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/bookmarks")
+            .header(header::CONTENT_TYPE, "text/plain");
+        if let Some(auth) = auth {
+            request = request.header(header::AUTHORIZATION, auth);
+        }
+        let response = app
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_capture_saves_page_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let app = create_test_router(&temp_dir);
+        let page = r#"{"url": "https://example.com/post", "title": " Post ", "tags": ["read", "read"], "token": "secret"}"#;
+
+        let (status, body) = post(app.clone(), page, None).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["created"], true);
+        let id = body["id"].as_str().unwrap().to_string();
+        assert!(id.starts_with("bookmarks:"));
+
+        // Posting the page again finds the same bookmark
+        let again = r#"{"url": "https://example.com/post"}"#;
+        let (status, body) = post(app, again, Some("Bearer client-token")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["created"], false);
+        assert_eq!(body["id"], id);
+
+        let saved = BookmarksProvider::with_path(temp_dir.path().join("bookmarks.json"))
+            .unwrap()
+            .capture(
+                String::new(),
+                "https://example.com/post".to_string(),
+                None,
+                None,
+                vec![],
+            )
+            .unwrap()
+            .0;
+        assert_eq!(saved.title, "Post");
+        assert_eq!(saved.tags, vec!["read".to_string()]);
+        assert!(saved.folder_id.is_some_and(|id| id != "default"));
+    }
+
+    #[tokio::test]
+    async fn test_capture_rejects_bad_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let app = create_test_router(&temp_dir);
+
+        let page = r#"{"url": "https://example.com"}"#;
+        let (status, _) = post(app.clone(), page, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = post(app.clone(), page, Some("Bearer wrong")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = post(app.clone(), "not json", Some("Bearer secret")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let script = r#"{"url": "javascript:alert(1)"}"#;
+        let (status, _) = post(app.clone(), script, Some("Bearer secret")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let unknown = r#"{"url": "https://example.com", "folder": "Nowhere"}"#;
+        let (status, body) = post(app, unknown, Some("Bearer secret")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Folder not found: Nowhere");
+    }
+
+    #[tokio::test]
+    async fn test_capture_preflight() {
+        let temp_dir = TempDir::new().unwrap();
+        let response = create_test_router(&temp_dir)
+            .oneshot(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/bookmarks")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS],
+            "authorization, content-type"
+        );
+    }
+}
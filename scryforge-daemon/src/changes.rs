@@ -0,0 +1,372 @@
+//! What changed when a cached item was updated.
+//!
+//! When a sync brings a new version of a cached item, the cache compares it
+//! with the stored one ([`diff_items`]) and, if a field a reader would notice
+//! changed, such as an edited post body or a moved due date, records the
+//! changes in its `item_changes` table and stamps the item with
+//! [`CHANGED_AT_KEY`] metadata. Clients show that key as an "updated" badge,
+//! list the changes with `items.changes`, and clear the badge with
+//! `items.acknowledge_changes` once they've been seen.
+//!
+//! Bodies are compared word by word, so an edit shows as a [`word_diff`]
+//! rather than the whole text twice.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::{Item, ItemContent, ItemId};
+use serde::{Deserialize, Serialize};
+
+/// Metadata key holding when an item last changed, until the change is
+/// acknowledged.
+pub const CHANGED_AT_KEY: &str = "changed_at";
+
+/// Changes kept per item; older ones are dropped as new ones come.
+pub const MAX_CHANGES_PER_ITEM: u32 = 20;
+
+/// Unchanged words kept around each edit in a [`word_diff`].
+const CONTEXT_WORDS: usize = 8;
+
+/// Largest word grid compared exactly; longer edits show as a replacement.
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
+/// A field of an item compared between versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangedField {
+    Title,
+    Body,
+    Url,
+    Author,
+    DueDate,
+    Completed,
+    Start,
+    End,
+    Location,
+}
+
+/// One field that changed, with its values before and after.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: ChangedField,
+    /// Old value, if it had one; not kept for bodies
+    pub before: Option<String>,
+    /// New value, if it has one; not kept for bodies
+    pub after: Option<String>,
+    /// Word diff of a body, with `[-removed-]` and `{+added+}` words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// The changes one update made to an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub id: i64,
+    pub item_id: ItemId,
+    pub changed_at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+/// The fields that differ between the stored version of an item and a new
+/// one, in [`ChangedField`] order. Empty if nothing a reader would notice
+/// changed; read and saved state, tags, and metadata are not compared.
+pub fn diff_items(old: &Item, new: &Item) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut compare = |field, before: Option<String>, after: Option<String>| {
+        if before != after {
+            changes.push(FieldChange {
+                field,
+                before,
+                after,
+                diff: None,
+            });
+        }
+    };
+
+    compare(
+        ChangedField::Title,
+        Some(old.title.clone()),
+        Some(new.title.clone()),
+    );
+
+    let (old_body, new_body) = (old.content.full_text(), new.content.full_text());
+    let body_change = (old_body != new_body).then(|| FieldChange {
+        field: ChangedField::Body,
+        before: None,
+        after: None,
+        diff: Some(word_diff(
+            old_body.as_deref().unwrap_or_default(),
+            new_body.as_deref().unwrap_or_default(),
+        )),
+    });
+
+    compare(ChangedField::Url, old.url.clone(), new.url.clone());
+    compare(
+        ChangedField::Author,
+        old.author.as_ref().map(|a| a.name.clone()),
+        new.author.as_ref().map(|a| a.name.clone()),
+    );
+
+    match (&old.content, &new.content) {
+        (
+            ItemContent::Task {
+                due_date: old_due,
+                is_completed: old_done,
+                ..
+            },
+            ItemContent::Task {
+                due_date: new_due,
+                is_completed: new_done,
+                ..
+            },
+        ) => {
+            compare(
+                ChangedField::DueDate,
+                old_due.map(|d| d.to_string()),
+                new_due.map(|d| d.to_string()),
+            );
+            compare(
+                ChangedField::Completed,
+                Some(old_done.to_string()),
+                Some(new_done.to_string()),
+            );
+        }
+        (
+            ItemContent::Event {
+                start: old_start,
+                end: old_end,
+                location: old_location,
+                ..
+            },
+            ItemContent::Event {
+                start: new_start,
+                end: new_end,
+                location: new_location,
+                ..
+            },
+        ) => {
+            compare(
+                ChangedField::Start,
+                Some(old_start.to_rfc3339()),
+                Some(new_start.to_rfc3339()),
+            );
+            compare(
+                ChangedField::End,
+                Some(old_end.to_rfc3339()),
+                Some(new_end.to_rfc3339()),
+            );
+            compare(
+                ChangedField::Location,
+                old_location.clone(),
+                new_location.clone(),
+            );
+        }
+        _ => {}
+    }
+
+    changes.extend(body_change);
+    changes.sort_by_key(|change| change.field as u8);
+    changes
+}
+
+/// Compare two texts word by word.
+///
+/// Removed runs are marked `[-…-]` and added runs `{+…+}`, as `git diff
+/// --word-diff` does. Unchanged runs longer than a few words around the
+/// edits are cut to `…`.
+pub fn word_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.split_whitespace().collect();
+    let new: Vec<&str> = new.split_whitespace().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_mid, new_mid) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let mut ops = vec![Op::Same; prefix];
+    ops.extend(diff_words(old_mid, new_mid));
+    ops.extend(vec![Op::Same; suffix]);
+
+    render(&ops, &old, &new)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Edit script between two word lists, by longest common subsequence.
+fn diff_words(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let (n, m) = (old.len(), new.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        let mut ops = vec![Op::Removed; n];
+        ops.extend(vec![Op::Added; m]);
+        return ops;
+    }
+
+    // lcs[i][j]: common words of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(n + m);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            ops.push(Op::Same);
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Removed);
+            i += 1;
+        } else {
+            ops.push(Op::Added);
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// Write out an edit script, marking edits and eliding long unchanged runs.
+fn render(ops: &[Op], old: &[&str], new: &[&str]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut k = 0;
+    while k < ops.len() {
+        let op = ops[k];
+        let run = ops[k..].iter().take_while(|o| **o == op).count();
+        match op {
+            Op::Same => {
+                let words = &old[i..i + run];
+                let first = k == 0;
+                let last = k + run == ops.len();
+                let keep_head = if first { 0 } else { CONTEXT_WORDS };
+                let keep_tail = if last { 0 } else { CONTEXT_WORDS };
+                if words.len() > keep_head + keep_tail {
+                    out.extend(words[..keep_head].iter().map(|w| w.to_string()));
+                    out.push("…".to_string());
+                    out.extend(
+                        words[words.len() - keep_tail..]
+                            .iter()
+                            .map(|w| w.to_string()),
+                    );
+                } else {
+                    out.extend(words.iter().map(|w| w.to_string()));
+                }
+                i += run;
+                j += run;
+            }
+            Op::Removed => {
+                out.push(format!("[-{}-]", old[i..i + run].join(" ")));
+                i += run;
+            }
+            Op::Added => {
+                out.push(format!("{{+{}+}}", new[j..j + run].join(" ")));
+                j += run;
+            }
+        }
+        k += run;
+    }
+    out.join(" ")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use scryforge_provider_core::StreamId;
+    use std::collections::HashMap;
+
+    fn task(title: &str, body: &str, due: Option<NaiveDate>) -> Item {
+        Item {
+            id: ItemId("todo:1".to_string()),
+            stream_id: StreamId("todo:list:inbox".to_string()),
+            title: title.to_string(),
+            content: ItemContent::Task {
+                body: Some(body.to_string()),
+                due_date: due,
+                is_completed: false,
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_word_diff() {
+        assert_eq!(
+            word_diff("the quick brown fox", "the slow brown fox jumps"),
+            "the [-quick-] {+slow+} brown fox {+jumps+}"
+        );
+        assert_eq!(word_diff("same text", "same text"), "same text");
+        assert_eq!(word_diff("", "new"), "{+new+}");
+
+        // Long unchanged runs are cut down to the words around the edit
+        let old = (0..40).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let new = old.replace(" 20 ", " twenty ");
+        assert_eq!(
+            word_diff(&old, &new),
+            "… 12 13 14 15 16 17 18 19 [-20-] {+twenty+} 21 22 23 24 25 26 27 28 …"
+        );
+    }
+
+    #[test]
+    fn test_diff_items() {
+        let due = NaiveDate::from_ymd_opt(2026, 3, 1);
+        let old = task("Pay rent", "Transfer before the first", due);
+        assert!(diff_items(&old, &old.clone()).is_empty());
+
+        // State and metadata don't count as changes
+        let mut read = old.clone();
+        read.is_read = true;
+        read.metadata.insert("k".to_string(), "v".to_string());
+        assert!(diff_items(&old, &read).is_empty());
+
+        let new = task(
+            "Pay rent",
+            "Transfer before the third",
+            NaiveDate::from_ymd_opt(2026, 3, 3),
+        );
+        let changes = diff_items(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, ChangedField::Body);
+        assert_eq!(
+            changes[0].diff.as_deref(),
+            Some("Transfer before the [-first-] {+third+}")
+        );
+        assert_eq!(
+            changes[1],
+            FieldChange {
+                field: ChangedField::DueDate,
+                before: Some("2026-03-01".to_string()),
+                after: Some("2026-03-03".to_string()),
+                diff: None,
+            }
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! exist, a default configuration is created with documented comments.
 
 use anyhow::{Context, Result};
+use scryforge_provider_core::http::ProxyConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -17,9 +18,79 @@ pub struct Config {
     pub daemon: DaemonConfig,
     /// Cache configuration
     pub cache: CacheConfig,
+    /// Read-only web dashboard configuration
+    #[serde(default)]
+    pub web: WebConfig,
+    /// Endpoint a browser extension or bookmarklet saves pages through
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    /// Send-to-notes configuration
+    #[serde(default)]
+    pub notes: NotesConfig,
+    /// Cross-provider save routing
+    #[serde(default)]
+    pub save: SaveConfig,
+    /// Weights for the relevance sort of the unified feed
+    #[serde(default)]
+    pub ranking: RankingConfig,
+    /// Keywords, authors, domains, and communities hidden from aggregated views
+    #[serde(default)]
+    pub mute: MuteConfig,
+    /// Scheduled digest of top unread items
+    #[serde(default)]
+    pub digest: DigestConfig,
+    /// LLM backend for the "Summarize" action
+    #[serde(default)]
+    pub summarizer: SummarizerConfig,
+    /// Text-to-speech command for the "read aloud" action
+    #[serde(default)]
+    pub tts: TtsConfig,
+    /// Desktop notifications for item reminders
+    #[serde(default)]
+    pub reminders: RemindersConfig,
+    /// Commands that open item links, by content kind
+    #[serde(default)]
+    pub opener: OpenerConfig,
+    /// mpv settings for the "Play" action
+    #[serde(default)]
+    pub player: PlayerConfig,
+    /// Where and how many items the download manager saves at once
+    #[serde(default)]
+    pub downloads: DownloadsConfig,
+    /// Directory `export.items` writes into
+    #[serde(default)]
+    pub export: ExportConfig,
+    /// Places items can be shared to, offered as actions on every item
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub share: Vec<ShareTarget>,
+    /// Address book built from email headers, with an optional CardDAV import
+    #[serde(default)]
+    pub contacts: ContactsConfig,
+    /// Text extraction from PDF and Office attachments, for search
+    #[serde(default)]
+    pub attachments: AttachmentsConfig,
+    /// Splitting newsletters into one item per story
+    #[serde(default)]
+    pub newsletters: NewslettersConfig,
+    /// Full-text index for fast, ranked search of large caches
+    #[serde(default)]
+    pub search: SearchConfig,
+    /// Limits on concurrent provider syncs
+    #[serde(default)]
+    pub sync: SyncConfig,
+    /// Low-bandwidth mode for metered connections
+    #[serde(default)]
+    pub bandwidth: BandwidthConfig,
+    /// Where the key for `encrypt_at_rest` providers comes from
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
     /// Provider-specific configurations
     #[serde(default)]
     pub providers: HashMap<String, ProviderConfig>,
+    /// Refresh and retention overrides for single feeds and collections,
+    /// keyed by stream ID
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub streams: HashMap<String, StreamPolicy>,
 }
 
 /// Daemon server configuration
@@ -31,6 +102,31 @@ pub struct DaemonConfig {
     /// Log level (trace, debug, info, warn, error)
     /// Default: "info"
     pub log_level: String,
+    /// Clients allowed to connect, each with its own bearer token.
+    /// When empty, the API is unauthenticated (only allowed on loopback).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clients: Vec<ClientConfig>,
+    /// TLS settings; when set, the server only accepts TLS connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+}
+
+/// A client permitted to access the daemon API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClientConfig {
+    /// Human-readable client name, used in logs
+    pub name: String,
+    /// Bearer token the client sends in the `Authorization` header
+    pub token: String,
+}
+
+/// TLS certificate configuration for the API server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TlsConfig {
+    /// Path to the PEM-encoded certificate chain
+    pub cert_path: PathBuf,
+    /// Path to the PEM-encoded private key
+    pub key_path: PathBuf,
 }
 
 /// Cache configuration
@@ -43,6 +139,616 @@ pub struct CacheConfig {
     /// Maximum number of items to keep per stream
     /// Default: 1000
     pub max_items_per_stream: usize,
+    /// Evict items published more than this many days ago
+    /// If None, items are kept regardless of age
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+    /// Evict items until the database holds at most this many megabytes
+    /// If None, the database may grow without limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+    /// Which items go first when a stream or the database is over its limit
+    /// Default: age
+    #[serde(default)]
+    pub eviction: EvictionPolicy,
+}
+
+/// Order in which items are evicted when the cache is over a limit.
+///
+/// Saved items and items with locally added metadata are never evicted.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Oldest published first
+    #[default]
+    Age,
+    /// Least recently opened or read first
+    Lru,
+}
+
+/// Overrides for one feed or collection, set under `[streams."<stream id>"]`.
+///
+/// Each unset field falls back to the provider's sync interval or the
+/// `[cache]` limit.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StreamPolicy {
+    /// Fetch the stream's items this often, in minutes, on top of its
+    /// provider's sync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_minutes: Option<u64>,
+    /// Keep at most this many items, instead of `cache.max_items_per_stream`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// Evict items published more than this many days ago, instead of
+    /// `cache.max_age_days`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+    /// Mark unread items read once they were published this many days ago
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_read_after_days: Option<u32>,
+}
+
+impl StreamPolicy {
+    /// The feed or collection a stream ID names: its provider, whether it is
+    /// a collection, and its ID within the provider.
+    pub fn target(stream_id: &str) -> Option<(&str, bool, &str)> {
+        let mut parts = stream_id.splitn(3, ':');
+        let provider_id = parts.next().filter(|p| !p.is_empty())?;
+        let is_collection = match parts.next()? {
+            "feed" => false,
+            "collection" => true,
+            _ => return None,
+        };
+        let local_id = parts.next().filter(|id| !id.is_empty())?;
+        Some((provider_id, is_collection, local_id))
+    }
+}
+
+/// Read-only web dashboard configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebConfig {
+    /// Whether to serve the dashboard
+    /// Default: false
+    pub enabled: bool,
+    /// Bind address for the dashboard HTTP server
+    /// Default: "127.0.0.1:3031"
+    pub bind_address: String,
+    /// Maximum number of items shown per page
+    /// Default: 100
+    pub items_per_page: u32,
+}
+
+/// Bookmark capture endpoint configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CaptureConfig {
+    /// Whether to accept pages posted by a browser extension or bookmarklet
+    /// Default: false
+    pub enabled: bool,
+    /// Loopback address for the capture HTTP server
+    /// Default: "127.0.0.1:3032"
+    pub bind_address: String,
+    /// Token posted pages must carry. Any `[[daemon.clients]]` token is
+    /// accepted as well.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Folder, by ID or name, new bookmarks go into when the page doesn't
+    /// name one. If None, they stay unsorted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+/// Send-to-notes configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotesConfig {
+    /// Markdown file (`file` layout) or directory (`daily` layout) that
+    /// entries are appended to. If None, the send-to-notes action is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// How entries are laid out under `path`
+    /// Default: file
+    pub layout: NotesLayout,
+    /// strftime format for daily note file names (without `.md`)
+    /// Default: "%Y-%m-%d"
+    pub daily_format: String,
+}
+
+/// Where send-to-notes entries are written.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotesLayout {
+    /// Append every entry to the single file at `path`
+    #[default]
+    File,
+    /// Append to a per-day file inside the directory at `path`
+    Daily,
+}
+
+/// Cross-provider save routing configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SaveConfig {
+    /// Provider that receives saves from sources without an explicit route
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_target: Option<String>,
+    /// Source provider ID -> read-later provider ID
+    pub routes: HashMap<String, String>,
+}
+
+/// Relevance ranking configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RankingConfig {
+    /// Age at which an item's score has halved
+    /// Default: 24.0
+    pub half_life_hours: f64,
+    /// Weight of engagement metadata (score, comments, likes, views)
+    /// Default: 1.0
+    pub engagement_weight: f64,
+    /// Weight of past interactions with the item's stream and author
+    /// Default: 1.0
+    pub affinity_weight: f64,
+}
+
+/// Mute list configuration
+///
+/// The top-level rules apply to every provider; `[mute.providers.<id>]`
+/// tables add to them (or replace them, with `inherit = false`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MuteConfig {
+    /// Rules applied to all providers
+    #[serde(flatten)]
+    pub rules: MuteRules,
+    /// Provider ID -> provider-specific rules
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub providers: HashMap<String, ProviderMuteRules>,
+}
+
+/// A set of mute rules. Matching is case-insensitive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MuteRules {
+    /// Words or phrases matched against titles and content
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Author names or email addresses
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    /// Link domains; subdomains are muted too
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<String>,
+    /// Subreddit names, with or without the `r/` prefix
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subreddits: Vec<String>,
+    /// Hide items flagged as NSFW
+    /// Default: false (inherited from the global rules in provider overrides)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_nsfw: Option<bool>,
+}
+
+/// Mute rules for a single provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProviderMuteRules {
+    /// Rules specific to this provider
+    #[serde(flatten)]
+    pub rules: MuteRules,
+    /// Whether the global rules also apply to this provider
+    /// Default: true
+    pub inherit: bool,
+}
+
+/// Scheduled digest configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Whether to generate digests on a schedule
+    /// Default: false
+    pub enabled: bool,
+    /// How often a digest is generated; also the time window it covers
+    /// Default: daily
+    pub schedule: DigestSchedule,
+    /// Local hour (0-23) at which digests are generated
+    /// Default: 7
+    pub hour: u32,
+    /// Document format
+    /// Default: markdown
+    pub format: DigestFormat,
+    /// Directory digests are written to
+    /// If None, uses XDG_DATA_HOME/scryforge/digests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<PathBuf>,
+    /// Maximum number of items listed per provider
+    /// Default: 10
+    pub items_per_provider: usize,
+}
+
+/// How often digests are generated.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestSchedule {
+    /// Every day, covering the last 24 hours
+    #[default]
+    Daily,
+    /// Every Monday, covering the last 7 days
+    Weekly,
+}
+
+/// Format of a generated digest.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+/// Text-to-speech configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TtsConfig {
+    /// Shell command that reads text on stdin and speaks it, e.g. "espeak-ng"
+    /// or a piper pipeline. If None, the read-aloud action is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Longer text is cut before it is spoken
+    /// Default: 20000
+    pub max_chars: usize,
+    /// Maximum number of items waiting to be read
+    /// Default: 20
+    pub max_queue: usize,
+}
+
+/// Item reminder configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RemindersConfig {
+    /// Whether due reminders raise a desktop notification. Due items are
+    /// listed in `reminders.due` either way.
+    /// Default: true
+    pub notify: bool,
+    /// Command run with `sh -c` for each notification; the item title and
+    /// link are appended as its last two arguments
+    /// Default: "notify-send --app-name=Scryforge"
+    pub notify_command: String,
+    /// How often due reminders are checked for
+    /// Default: 30
+    pub check_interval_secs: u64,
+}
+
+/// Item opener configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OpenerConfig {
+    /// Commands used for every provider
+    #[serde(flatten)]
+    pub commands: OpenerCommands,
+    /// Per-provider overrides, keyed by provider ID
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub providers: HashMap<String, OpenerCommands>,
+}
+
+/// Shell commands that open links, each receiving the link as its last
+/// argument. Unset kinds fall back to `default`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OpenerCommands {
+    /// Command for links of any other kind
+    /// Default: "xdg-open" ("open" on macOS)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Videos and video sites, e.g. "mpv"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    /// Tracks and audio files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    /// Images and galleries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// PDF documents, e.g. "zathura"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf: Option<String>,
+    /// Articles, e.g. "w3m" or a browser
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub article: Option<String>,
+}
+
+/// Media player configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PlayerConfig {
+    /// mpv executable; streaming sites are resolved by its yt-dlp hook
+    /// Default: "mpv"
+    pub command: String,
+    /// Extra arguments passed to mpv before the link, e.g. ["--fs"]
+    /// Default: []
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// yt-dlp format selector, passed as `--ytdl-format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ytdl_format: Option<String>,
+    /// yt-dlp executable for mpv's hook, if not found on PATH
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ytdl_path: Option<String>,
+    /// Start where the item was last stopped
+    /// Default: true
+    pub resume: bool,
+    /// How often the playback position is read from mpv and recorded
+    /// Default: 15
+    pub progress_interval_secs: u64,
+}
+
+/// Download manager configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DownloadsConfig {
+    /// Directory downloads are saved to. If None, a `scryforge` directory in
+    /// the user's download directory is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+    /// Save each provider's downloads in a subdirectory named after it
+    /// Default: true
+    pub per_provider: bool,
+    /// Maximum number of downloads running at the same time
+    /// Default: 2
+    pub max_concurrent: usize,
+    /// yt-dlp executable used for videos and streaming sites
+    /// Default: "yt-dlp"
+    pub ytdl_command: String,
+    /// yt-dlp format selector, passed as `--format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ytdl_format: Option<String>,
+}
+
+/// Export configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Directory exports are written into; clients name paths relative to it
+    /// If None, uses XDG_DATA_HOME/scryforge/exports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+}
+
+/// Contacts index configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ContactsConfig {
+    /// Addresses never suggested, such as your own
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+    /// CardDAV address book whose contacts are imported at startup and by
+    /// `contacts.import`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carddav_url: Option<String>,
+    /// CardDAV username
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carddav_username: Option<String>,
+    /// Environment variable holding the CardDAV password
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carddav_password_env: Option<String>,
+}
+
+/// Attachment indexing configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AttachmentsConfig {
+    /// Extract the text of PDF and Office attachments on newly synced items
+    /// so search finds it
+    /// Default: false
+    pub index: bool,
+    /// Attachments larger than this many bytes are not indexed
+    /// Default: 10485760 (10 MiB)
+    pub max_bytes: u64,
+    /// Characters of text kept per attachment
+    /// Default: 100000
+    pub max_chars: usize,
+    /// Command run with `sh -c` that reads a PDF on stdin and writes its
+    /// text to stdout
+    /// Default: "pdftotext -q - -"
+    pub pdf_command: String,
+}
+
+/// Newsletter unbundling configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NewslettersConfig {
+    /// Split newsletter emails and feed entries into one item per story
+    /// when streams are refreshed
+    /// Default: false
+    pub unbundle: bool,
+    /// Fewest stories an item needs to be split
+    /// Default: 3
+    pub min_stories: usize,
+    /// Stream IDs whose items are split; all streams when empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub streams: Vec<String>,
+}
+
+/// Full-text search index configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Keep a Tantivy index of cached items, so search ranks results by
+    /// relevance and returns snippets; needs a daemon built with the
+    /// `search-index` feature
+    /// Default: false
+    pub index: bool,
+    /// Directory of the index
+    /// If None, uses XDG_DATA_HOME/scryforge/search-index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_path: Option<PathBuf>,
+}
+
+/// A share target, configured as a `[[share]]` table
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShareTarget {
+    /// Name passed to `items.share`, unique among targets
+    pub id: String,
+    /// Label shown by clients
+    /// Default: the id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Template of the shared text, with placeholders such as `{title}` and
+    /// `{url}` replaced from the item
+    /// Default: depends on the kind
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Where the text goes
+    #[serde(flatten)]
+    pub kind: ShareKind,
+}
+
+/// Where a share target sends the text, selected by its `kind` key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ShareKind {
+    /// Return the text for the client to copy to its clipboard
+    Clipboard,
+    /// Send the text as an email from a provider that can send mail
+    Email {
+        /// Provider ID, e.g. "outlook"
+        provider: String,
+        /// Recipient addresses
+        to: Vec<String>,
+        /// Template of the subject line
+        /// Default: "{title}"
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        subject: Option<String>,
+    },
+    /// Post the text as a Mastodon status
+    Mastodon {
+        /// Base URL of the instance, e.g. "https://mastodon.social"
+        instance: String,
+        /// Environment variable holding an access token with `write:statuses`
+        token_env: String,
+        /// "public", "unlisted", "private", or "direct"
+        /// Default: the account's default
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility: Option<String>,
+    },
+    /// Append the text to a file
+    File {
+        /// File to append to; created if missing
+        path: PathBuf,
+    },
+}
+
+impl OpenerCommands {
+    /// Every configured command.
+    fn iter(&self) -> impl Iterator<Item = &String> {
+        [
+            &self.default,
+            &self.video,
+            &self.audio,
+            &self.image,
+            &self.pdf,
+            &self.article,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Sync scheduling configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Maximum number of providers syncing at the same time
+    /// Default: 4
+    pub max_concurrent: usize,
+    /// A provider sync running longer than this is abandoned and counted as
+    /// an error. Providers can override it with `sync_timeout_secs`.
+    /// Default: 120
+    pub timeout_secs: u64,
+}
+
+/// Low-bandwidth mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct BandwidthConfig {
+    /// Start in low-bandwidth mode. It can be switched at runtime with
+    /// `bandwidth.set`.
+    /// Default: false
+    pub low_bandwidth: bool,
+    /// In low-bandwidth mode, sync intervals and stream refreshes are this
+    /// many times longer
+    /// Default: 4
+    pub sync_interval_factor: u32,
+    /// In low-bandwidth mode, items fetched per feed refresh or search
+    /// Default: 20
+    pub page_size: u32,
+}
+
+/// At-rest encryption configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    /// Sigilforge reference to the base64-encoded 32-byte key, kept in the
+    /// system keyring. The `SCRYFORGE_ENCRYPTION_KEY` environment variable
+    /// takes precedence.
+    /// Default: "auth://scryforge/encryption/key"
+    pub key: String,
+}
+
+/// Summarizer configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SummarizerConfig {
+    /// Backend to send text to. If None, summarization is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<SummarizerBackend>,
+    /// Base URL of the backend
+    /// Default: "http://127.0.0.1:8080" (llama_cpp), "https://api.openai.com/v1" (openai)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Model name sent to OpenAI-compatible endpoints
+    /// Default: "gpt-4o-mini"
+    pub model: String,
+    /// Environment variable holding the API key, if the endpoint needs one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Items with fewer words than this are not offered for summarization
+    /// Default: 150
+    pub min_words: usize,
+    /// Longer text is truncated before it is sent
+    /// Default: 12000
+    pub max_input_chars: usize,
+    /// Maximum length of the generated summary, in tokens
+    /// Default: 256
+    pub max_tokens: u32,
+    /// Request timeout in seconds
+    /// Default: 60
+    pub timeout_secs: u64,
+}
+
+/// Summarizer backend type.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SummarizerBackend {
+    /// A llama.cpp server's native `/completion` endpoint
+    LlamaCpp,
+    /// Any OpenAI-compatible `/chat/completions` endpoint
+    Openai,
+}
+
+/// Which side wins when an item's read or saved state changed both in
+/// Scryforge and at the provider since the last sync.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Keep the provider's value
+    RemoteWins,
+    /// Keep the value set in Scryforge
+    LocalWins,
+    /// Keep whichever change is newer, comparing the local change time with
+    /// the item's `updated` time; items without one keep the provider's value
+    #[default]
+    LatestTimestamp,
 }
 
 /// Per-provider configuration
@@ -52,11 +758,72 @@ pub struct ProviderConfig {
     pub enabled: bool,
     /// Sync interval in minutes
     pub sync_interval_minutes: u64,
+    /// Sync timeout in seconds, overriding `sync.timeout_secs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_timeout_secs: Option<u64>,
+    /// How read/saved state changed both locally and remotely is merged
+    #[serde(default)]
+    pub conflict_strategy: ConflictStrategy,
+    /// Encrypt this provider's cached items and local store on disk
+    #[serde(default)]
+    pub encrypt_at_rest: bool,
+    /// Daily limits on the provider's calls and downloads
+    #[serde(default, skip_serializing_if = "BudgetConfig::is_unlimited")]
+    pub budget: BudgetConfig,
+    /// Proxy for the provider's HTTP requests: an `http://`, `https://`,
+    /// `socks5://` or `socks5h://` URL, or `tor`. Only the providers in
+    /// [`PROXY_PROVIDERS`] accept one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
     /// Provider-specific settings as arbitrary TOML value
     #[serde(default = "default_settings")]
     pub settings: toml::Value,
 }
 
+/// Providers whose requests go through their `proxy` setting. Any other
+/// provider with a proxy fails validation instead of connecting directly.
+pub const PROXY_PROVIDERS: &[&str] = &["rss", "youtube"];
+
+impl ProviderConfig {
+    /// How long a sync of this provider may run.
+    pub fn sync_timeout(&self, sync: &SyncConfig) -> std::time::Duration {
+        std::time::Duration::from_secs(self.sync_timeout_secs.unwrap_or(sync.timeout_secs))
+    }
+
+    /// The proxy this provider's requests go through, if one is set.
+    pub fn proxy_config(&self) -> Result<Option<ProxyConfig>> {
+        self.proxy
+            .as_deref()
+            .map(ProxyConfig::parse)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+/// Daily limits on the daemon's use of a provider, under
+/// `[providers.<id>.budget]`. Both default to no limit.
+///
+/// Syncs are spaced further apart as either limit nears, and once one is
+/// reached the provider is not called again until midnight UTC.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BudgetConfig {
+    /// Operations run on the provider per day: guarded calls such as syncs,
+    /// fetches, searches, and actions, not HTTP requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_operations_per_day: Option<u32>,
+    /// Megabytes of items got from the provider per day, measured by their
+    /// size as JSON rather than bytes transferred
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_item_megabytes_per_day: Option<u64>,
+}
+
+impl BudgetConfig {
+    /// Whether neither limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_operations_per_day.is_none() && self.max_item_megabytes_per_day.is_none()
+    }
+}
+
 fn default_settings() -> toml::Value {
     toml::Value::Table(toml::map::Map::new())
 }
@@ -66,230 +833,1731 @@ impl Default for DaemonConfig {
         Self {
             bind_address: "127.0.0.1:3030".to_string(),
             log_level: "info".to_string(),
+            clients: Vec::new(),
+            tls: None,
         }
     }
 }
 
-impl Default for CacheConfig {
+impl Default for WebConfig {
     fn default() -> Self {
         Self {
-            path: None,
-            max_items_per_stream: 1000,
+            enabled: false,
+            bind_address: "127.0.0.1:3031".to_string(),
+            items_per_page: 100,
         }
     }
 }
 
-impl Default for ProviderConfig {
+impl Default for CaptureConfig {
     fn default() -> Self {
         Self {
-            enabled: true,
-            sync_interval_minutes: 15,
-            settings: toml::Value::Table(toml::map::Map::new()),
+            enabled: false,
+            bind_address: "127.0.0.1:3032".to_string(),
+            token: None,
+            folder: None,
         }
     }
 }
 
-impl Config {
-    /// Load configuration from the specified path
-    ///
-    /// # Arguments
-    /// * `path` - Path to the configuration file
-    ///
-    /// # Returns
-    /// The parsed configuration or an error if loading/parsing fails
-    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref();
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            layout: NotesLayout::File,
+            daily_format: "%Y-%m-%d".to_string(),
+        }
+    }
+}
 
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            half_life_hours: 24.0,
+            engagement_weight: 1.0,
+            affinity_weight: 1.0,
+        }
+    }
+}
 
-        config.validate()?;
-        Ok(config)
+impl Default for ProviderMuteRules {
+    fn default() -> Self {
+        Self {
+            rules: MuteRules::default(),
+            inherit: true,
+        }
     }
+}
 
-    /// Load configuration from the default XDG config location
-    ///
-    /// If the configuration file doesn't exist, creates a default configuration
-    /// file with documented comments.
-    ///
-    /// # Returns
-    /// The parsed configuration or an error if loading/parsing fails
-    pub fn load_default() -> Result<Self> {
-        let config_path = Self::default_config_path()?;
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: DigestSchedule::Daily,
+            hour: 7,
+            format: DigestFormat::Markdown,
+            output_dir: None,
+            items_per_provider: 10,
+        }
+    }
+}
 
-        if !config_path.exists() {
-            Self::create_default_file(&config_path)?;
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            timeout_secs: 120,
         }
+    }
+}
 
-        Self::load(&config_path)
+impl Default for BandwidthConfig {
+    fn default() -> Self {
+        Self {
+            low_bandwidth: false,
+            sync_interval_factor: 4,
+            page_size: 20,
+        }
     }
+}
 
-    /// Get the default configuration file path
-    ///
-    /// Returns `$XDG_CONFIG_HOME/scryforge/config.toml`
-    pub fn default_config_path() -> Result<PathBuf> {
-        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
-            .context("Failed to determine project directories")?;
+impl Default for EncryptionConfig {
+    fn default() -> Self {
+        Self {
+            key: "auth://scryforge/encryption/key".to_string(),
+        }
+    }
+}
 
-        Ok(dirs.config_dir().join("config.toml"))
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            max_chars: 20000,
+            max_queue: 20,
+        }
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            command: "mpv".to_string(),
+            args: Vec::new(),
+            ytdl_format: None,
+            ytdl_path: None,
+            resume: true,
+            progress_interval_secs: 15,
+        }
+    }
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            per_provider: true,
+            max_concurrent: 2,
+            ytdl_command: "yt-dlp".to_string(),
+            ytdl_format: None,
+        }
+    }
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            index: false,
+            max_bytes: 10 * 1024 * 1024,
+            max_chars: 100_000,
+            pdf_command: "pdftotext -q - -".to_string(),
+        }
+    }
+}
+
+impl Default for NewslettersConfig {
+    fn default() -> Self {
+        Self {
+            unbundle: false,
+            min_stories: 3,
+            streams: Vec::new(),
+        }
+    }
+}
+
+impl Default for RemindersConfig {
+    fn default() -> Self {
+        Self {
+            notify: true,
+            notify_command: "notify-send --app-name=Scryforge".to_string(),
+            check_interval_secs: 30,
+        }
+    }
+}
+
+impl Default for SummarizerConfig {
+    fn default() -> Self {
+        Self {
+            backend: None,
+            endpoint: None,
+            model: "gpt-4o-mini".to_string(),
+            api_key_env: None,
+            min_words: 150,
+            max_input_chars: 12000,
+            max_tokens: 256,
+            timeout_secs: 60,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_items_per_stream: 1000,
+            max_age_days: None,
+            max_size_mb: None,
+            eviction: EvictionPolicy::default(),
+        }
+    }
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sync_interval_minutes: 15,
+            sync_timeout_secs: None,
+            conflict_strategy: ConflictStrategy::default(),
+            encrypt_at_rest: false,
+            budget: BudgetConfig::default(),
+            proxy: None,
+            settings: toml::Value::Table(toml::map::Map::new()),
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration from the specified path
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file
+    ///
+    /// # Returns
+    /// The parsed configuration or an error if loading/parsing fails
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load configuration from the default XDG config location
+    ///
+    /// If the configuration file doesn't exist, creates a default configuration
+    /// file with documented comments.
+    ///
+    /// # Returns
+    /// The parsed configuration or an error if loading/parsing fails
+    pub fn load_default() -> Result<Self> {
+        let config_path = Self::default_config_path()?;
+
+        if !config_path.exists() {
+            Self::create_default_file(&config_path)?;
+        }
+
+        Self::load(&config_path)
+    }
+
+    /// Get the default configuration file path
+    ///
+    /// Returns `$XDG_CONFIG_HOME/scryforge/config.toml`
+    pub fn default_config_path() -> Result<PathBuf> {
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.config_dir().join("config.toml"))
+    }
+
+    /// Create a default configuration file with documented comments
+    fn create_default_file(path: &Path) -> Result<()> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory: {}", parent.display())
+            })?;
+        }
+
+        let default_config = Self::default_config_content();
+        fs::write(path, default_config)
+            .with_context(|| format!("Failed to write default config file: {}", path.display()))?;
+
+        tracing::info!("Created default configuration file at: {}", path.display());
+        Ok(())
+    }
+
+    /// Generate the default configuration file content with comments
+    pub(crate) fn default_config_content() -> String {
+        r#"# Scryforge Daemon Configuration
+# This file configures the scryforge-daemon behavior.
+
+[daemon]
+# Bind address for the JSON-RPC API server
+# Default: "127.0.0.1:3030"
+bind_address = "127.0.0.1:3030"
+
+# Log level: trace, debug, info, warn, error
+# Default: "info"
+log_level = "info"
+
+# Clients allowed to access the API. Each client sends its token as
+# "Authorization: Bearer <token>". Required when bind_address is not loopback.
+# [[daemon.clients]]
+# name = "laptop"
+# token = "change-me"
+
+# Serve the API over TLS (recommended when listening on a network interface)
+# [daemon.tls]
+# cert_path = "/path/to/cert.pem"
+# key_path = "/path/to/key.pem"
+
+[cache]
+# Path to the SQLite cache database
+# If not specified, defaults to $XDG_DATA_HOME/scryforge/cache.db
+# path = "/path/to/cache.db"
+
+# Maximum number of items to keep per stream
+# Default: 1000
+max_items_per_stream = 1000
+
+# Evict items published more than this many days ago
+# max_age_days = 90
+
+# Evict items until the database is at most this many megabytes
+# max_size_mb = 500
+
+# Which items go first when a stream or the database is over its limit:
+# "age" (oldest published) or "lru" (least recently opened or read).
+# Saved and annotated items are never evicted.
+# Default: "age"
+eviction = "age"
+
+[web]
+# Serve a read-only web dashboard of the unified inbox and feeds
+# Default: false
+enabled = false
+
+# Bind address for the dashboard. Binding beyond loopback requires
+# [[daemon.clients]]; browsers log in with any username and a client token.
+# Default: "127.0.0.1:3031"
+bind_address = "127.0.0.1:3031"
+
+# Maximum number of items shown per page
+# Default: 100
+items_per_page = 100
+
+[capture]
+# Accept "add bookmark" POSTs from a browser extension or bookmarklet at
+# http://<bind_address>/bookmarks, saving pages into the bookmarks provider
+# Default: false
+enabled = false
+
+# Loopback address for the capture endpoint
+# Default: "127.0.0.1:3032"
+bind_address = "127.0.0.1:3032"
+
+# Token posted pages must carry, as "Authorization: Bearer <token>" or a
+# "token" field; [[daemon.clients]] tokens work too. Required when enabled
+# unless clients are configured.
+# token = "a-long-random-string"
+
+# Folder, by ID or name, for pages that don't name one
+# folder = "Inbox"
+
+[notes]
+# Markdown file or directory that "send to notes" appends entries to
+# (title, URL, excerpt, tags). Unset disables the action.
+# path = "/home/user/notes/inbox.md"
+
+# "file" appends to the file at path; "daily" appends to path/<date>.md,
+# matching Obsidian daily notes or a Logseq journals directory.
+# Default: "file"
+layout = "file"
+
+# File name format for daily notes; Logseq journals use "%Y_%m_%d"
+# Default: "%Y-%m-%d"
+daily_format = "%Y-%m-%d"
+
+[save]
+# Hand "Save" off to a read-later provider (e.g. "bookmarks") in addition to
+# starring the item locally. Routes map source provider IDs to targets.
+# default_target = "bookmarks"
+
+[save.routes]
+# rss = "bookmarks"
+# reddit = "bookmarks"
+
+[ranking]
+# Relevance sort for the unified feed. Scores combine engagement (score,
+# comments, likes, views) with how often you open, save, or skip items from
+# the same stream and author, then decay with age.
+
+# Hours after which an item's score has halved
+# Default: 24.0
+half_life_hours = 24.0
+
+# Set a weight to 0 to ignore that signal
+# Default: 1.0
+engagement_weight = 1.0
+affinity_weight = 1.0
+
+[mute]
+# Items matching these rules are hidden from the unified feed and listed in
+# the muted items review instead. Matching is case-insensitive.
+# keywords = ["spoiler", "sponsored"]
+# authors = ["AutoModerator"]
+# domains = ["example.com"]
+# subreddits = ["r/politics"]
+# hide_nsfw = true
+
+# Provider overrides add to the rules above; set inherit = false to use only
+# the provider's own rules.
+# [mute.providers.reddit]
+# keywords = ["meme"]
+# inherit = true
+
+[digest]
+# Write a summary of the top unread items per provider to disk on a schedule
+# Default: false
+enabled = false
+
+# "daily" covers the last 24 hours; "weekly" runs on Mondays and covers 7 days
+# Default: "daily"
+schedule = "daily"
+
+# Local hour (0-23) at which the digest is generated
+# Default: 7
+hour = 7
+
+# "markdown" or "html"
+# Default: "markdown"
+format = "markdown"
+
+# Directory digests are written to
+# If not specified, defaults to $XDG_DATA_HOME/scryforge/digests
+# output_dir = "/path/to/digests"
+
+# Maximum number of items listed per provider
+# Default: 10
+items_per_provider = 10
+
+[summarizer]
+# LLM used by the "Summarize" action on long articles, emails, and video
+# descriptions. "llama_cpp" talks to a local llama.cpp server; "openai" to any
+# OpenAI-compatible endpoint (OpenAI, Ollama, vLLM, llama.cpp's /v1).
+# Unset disables summarization.
+# backend = "llama_cpp"
+# endpoint = "http://127.0.0.1:8080"
+
+# Model name for OpenAI-compatible endpoints
+# Default: "gpt-4o-mini"
+model = "gpt-4o-mini"
+
+# Environment variable holding the API key, if the endpoint needs one
+# api_key_env = "OPENAI_API_KEY"
+
+# Only items with at least this many words can be summarized
+# Default: 150
+min_words = 150
+
+# Text beyond this many characters is cut before sending
+# Default: 12000
+max_input_chars = 12000
+
+# Maximum summary length in tokens
+# Default: 256
+max_tokens = 256
+
+# Request timeout in seconds
+# Default: 60
+timeout_secs = 60
+
+[tts]
+# Command used by the "read aloud" action. It is run with `sh -c` and receives
+# the item text on stdin; items are read one at a time, in order.
+# Unset disables read aloud.
+# command = "espeak-ng -s 170"
+# command = "piper --model ~/voices/en_US-amy-medium.onnx --output-raw | aplay -r 22050 -f S16_LE -t raw -"
+
+# Text beyond this many characters is not read
+# Default: 20000
+max_chars = 20000
+
+# Maximum number of items waiting to be read
+# Default: 20
+max_queue = 20
+
+[reminders]
+# Items given a "remind me at" time stay in the cache and appear in the
+# "Reminders due" feed once the time has passed, until they are dismissed.
+
+# Raise a desktop notification when a reminder is due
+# Default: true
+notify = true
+
+# Notification command, run with `sh -c`; the item title and link are
+# appended as its last two arguments
+# Default: "notify-send --app-name=Scryforge"
+notify_command = "notify-send --app-name=Scryforge"
+
+# Seconds between checks for due reminders
+# Default: 30
+check_interval_secs = 30
+
+[player]
+# Videos and podcast episodes are played in mpv, which streams YouTube and
+# other sites through yt-dlp. The position is recorded as they play.
+
+# mpv executable
+# Default: "mpv"
+command = "mpv"
+
+# Extra arguments passed to mpv
+# args = ["--fs"]
+
+# yt-dlp format selector
+# ytdl_format = "bestvideo[height<=1080]+bestaudio/best"
+
+# Start where the item was last stopped
+# Default: true
+resume = true
+
+# Seconds between recordings of the playback position
+# Default: 15
+progress_interval_secs = 15
+
+[downloads]
+# Podcast episodes, videos, PDFs, and email attachments are saved here.
+# Interrupted downloads resume when the item is downloaded again.
+# Default: "scryforge" in your download directory
+# directory = "/path/to/downloads"
+
+# Save each provider's downloads in a subdirectory named after it
+# Default: true
+per_provider = true
+
+# Maximum number of downloads running at the same time
+# Default: 2
+max_concurrent = 2
+
+# yt-dlp executable, used for videos and streaming sites
+# Default: "yt-dlp"
+ytdl_command = "yt-dlp"
+
+# yt-dlp format selector
+# ytdl_format = "bestvideo[height<=720]+bestaudio/best"
+
+[export]
+# Exported and published files are written here. Clients name a path
+# inside it; absolute paths and paths leaving it are refused.
+# Default: XDG_DATA_HOME/scryforge/exports
+# directory = "/srv/www/scryforge"
+
+# Share targets are offered as actions on every item. Each has an id, a kind
+# (clipboard, email, mastodon, or file), and a format template using
+# {title}, {url}, {author}, {excerpt}, {stream}, {provider}, {tags}, and
+# {date}. {url} is empty for items without a link.
+# [[share]]
+# id = "markdown"
+# name = "Copy Markdown link"
+# kind = "clipboard"
+# format = "[{title}]({url})"
+#
+# [[share]]
+# id = "mail-team"
+# name = "Email the team"
+# kind = "email"
+# provider = "outlook"
+# to = ["team@example.com"]
+# subject = "FYI: {title}"
+#
+# [[share]]
+# id = "toot"
+# kind = "mastodon"
+# instance = "https://mastodon.social"
+# token_env = "MASTODON_TOKEN"
+# format = "{title} {url}"
+#
+# [[share]]
+# id = "reading-log"
+# kind = "file"
+# path = "/home/user/notes/reading.md"
+# format = "- {date} [{title}]({url})"
+
+[contacts]
+# Contacts are learned from the From, To, and Cc headers of cached email and
+# suggested by how often they appear. Addresses listed here are never
+# suggested, such as your own.
+# ignore = ["me@example.com"]
+
+# Import a CardDAV address book at startup and on contacts.import
+# carddav_url = "https://dav.example.com/addressbooks/me/contacts/"
+# carddav_username = "me"
+# carddav_password_env = "CARDDAV_PASSWORD"
+
+[attachments]
+# Extract the text of PDF, Word, Excel, PowerPoint, and OpenDocument
+# attachments when new items are synced, so search finds "that invoice from
+# March". Office documents are read directly; PDFs need pdf_command.
+# Default: false
+index = false
+
+# Attachments larger than this many bytes are skipped
+# Default: 10485760
+max_bytes = 10485760
+
+# Characters of text kept per attachment
+# Default: 100000
+max_chars = 100000
+
+# Reads a PDF on stdin and writes its text to stdout, run with sh -c
+# Default: "pdftotext -q - -"
+pdf_command = "pdftotext -q - -"
+
+[newsletters]
+# Split newsletters into one item per story when streams are refreshed, so
+# each story can be saved or dismissed on its own. Stories are found by the
+# headings or linked list items of an email or feed entry, and are kept next
+# to the newsletter in its stream.
+# Default: false
+unbundle = false
+
+# Fewest stories an item needs to count as a newsletter
+# Default: 3
+min_stories = 3
+
+# Only split items of these streams; all streams when unset
+# streams = ["email-imap:feed:imap:Newsletters", "rss:feed:weekly"]
+
+[search]
+# Keep a Tantivy full-text index of the cache, updated as syncs bring new
+# items, so search stays fast on hundreds of thousands of items, ranks
+# results by relevance, and shows a snippet of each. Items of encrypted
+# providers are left out of it. Needs a daemon built with the search-index
+# feature.
+# Default: false
+index = false
+
+# Directory of the index
+# Default: $XDG_DATA_HOME/scryforge/search-index
+# index_path = "/path/to/search-index"
+
+[sync]
+# Providers sync concurrently, at most this many at once
+# Default: 4
+max_concurrent = 4
+
+# A sync running longer than this many seconds is abandoned and retried with
+# backoff, so one slow provider cannot hold up the others
+# Default: 120
+timeout_secs = 120
+
+[bandwidth]
+# Low-bandwidth mode for metered connections: thumbnails are dropped,
+# attachments aren't downloaded for search, feed refreshes and searches
+# fetch fewer items, and syncs are spaced further apart. The bandwidth.set
+# API method switches it at runtime.
+# Default: false
+low_bandwidth = false
+
+# Sync intervals and stream refreshes are this many times longer
+# Default: 4
+sync_interval_factor = 4
+
+# Items fetched per feed refresh or search
+# Default: 20
+page_size = 20
+
+[encryption]
+# Providers with encrypt_at_rest = true have their cached items and local
+# files encrypted with this key, fetched from the keyring through Sigilforge.
+# Create one with `scry keygen`. SCRYFORGE_ENCRYPTION_KEY overrides it.
+# Default: "auth://scryforge/encryption/key"
+key = "auth://scryforge/encryption/key"
+
+# Provider-specific configurations
+# Each provider can be configured with:
+# - enabled: Whether the provider is enabled (default: true)
+# - sync_interval_minutes: How often to sync data (default: 15)
+# - sync_timeout_secs: Override sync.timeout_secs for this provider
+# - conflict_strategy: remote_wins, local_wins or latest_timestamp
+#   (default: latest_timestamp) for read/saved state changed on both sides
+# - encrypt_at_rest: Encrypt cached items and local files (default: false)
+# - budget: Daily limits, max_operations_per_day (syncs, fetches, searches
+#   and actions, not HTTP requests) and max_item_megabytes_per_day (items'
+#   size as JSON, not bytes transferred) (default: none). Syncs slow down as
+#   a limit nears and stop until midnight UTC once it is reached.
+# - proxy: Send the provider's requests through an http://, https://,
+#   socks5:// or socks5h:// proxy, or "tor" for socks5h://127.0.0.1:9050.
+#   .onion feeds need "tor" or a socks5h:// proxy. Downloads and playback of
+#   the provider's items use it too; mpv needs an http:// proxy. Only the rss
+#   and youtube providers accept a proxy (default: none)
+# - settings: Provider-specific settings (varies by provider)
+
+# Per-stream overrides, keyed by stream ID ("<provider>:feed:<id>" or
+# "<provider>:collection:<id>", as returned by streams.list). Each field
+# is optional:
+# - refresh_minutes: Fetch this feed or collection this often, on top of
+#   its provider's sync
+# - max_items: Keep this many items instead of cache.max_items_per_stream
+# - max_age_days: Evict items older than this instead of cache.max_age_days
+# - mark_read_after_days: Mark unread items read once this many days old
+#
+# [streams."rss:feed:hn"]
+# refresh_minutes = 5
+# max_items = 50
+# mark_read_after_days = 7
+
+# Example: Dummy provider configuration
+[providers.dummy]
+enabled = true
+sync_interval_minutes = 15
+
+# Provider-specific settings are defined here
+[providers.dummy.settings]
+# The dummy provider needs no settings. These inject faults for testing
+# retries and loading states; all default to 0 (off).
+# latency_ms = 500          # delay before every call returns
+# fail_every = 3            # every Nth call fails with a network error
+# rate_limit_every = 5      # every Nth call is rate limited...
+# retry_after_secs = 30     # ...with this retry-after
+# giant_feed_items = 10000  # add a "Giant Feed" with this many items
+# item_delay_ms = 10        # extra delay per returned item (slow stream)
+# scenario = "demo.toml"    # replay a scripted timeline of syncs (TOML/JSON)
+
+# Example: Future RSS provider configuration
+# [providers.rss]
+# enabled = true
+# sync_interval_minutes = 30
+#
+# [providers.rss.settings]
+# feeds = [
+#     "https://example.com/feed.xml",
+#     "https://another.example.com/rss",
+# ]
+
+# Example: Future email provider configuration
+# [providers.email]
+# enabled = true
+# sync_interval_minutes = 5
+#
+# [providers.email.settings]
+# imap_server = "imap.example.com"
+# imap_port = 993
+# use_tls = true
+"#
+        .to_string()
+    }
+
+    /// Validate the configuration
+    ///
+    /// Ensures all configuration values are valid and within acceptable ranges.
+    pub fn validate(&self) -> Result<()> {
+        // Validate bind address format
+        let bind_address = self
+            .daemon
+            .bind_address
+            .parse::<std::net::SocketAddr>()
+            .with_context(|| format!("Invalid bind_address: {}", self.daemon.bind_address))?;
+
+        // Refuse to expose an unauthenticated API beyond this machine
+        if !bind_address.ip().is_loopback() && self.daemon.clients.is_empty() {
+            anyhow::bail!(
+                "bind_address {} is not a loopback address; configure at least one [[daemon.clients]] token",
+                bind_address
+            );
+        }
+
+        for client in &self.daemon.clients {
+            if client.token.trim().is_empty() {
+                anyhow::bail!("Client '{}': token must not be empty", client.name);
+            }
+        }
+
+        // Validate log level
+        let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
+        if !valid_log_levels.contains(&self.daemon.log_level.as_str()) {
+            anyhow::bail!(
+                "Invalid log_level: {}. Must be one of: {}",
+                self.daemon.log_level,
+                valid_log_levels.join(", ")
+            );
+        }
+
+        // Validate web dashboard settings
+        let web_address = self
+            .web
+            .bind_address
+            .parse::<std::net::SocketAddr>()
+            .with_context(|| format!("Invalid web.bind_address: {}", self.web.bind_address))?;
+
+        if self.web.enabled && !web_address.ip().is_loopback() && self.daemon.clients.is_empty() {
+            anyhow::bail!(
+                "web.bind_address {} is not a loopback address; configure at least one [[daemon.clients]] token",
+                web_address
+            );
+        }
+
+        if self.web.items_per_page == 0 {
+            anyhow::bail!("web.items_per_page must be greater than 0");
+        }
+
+        // Validate bookmark capture settings
+        if self.capture.enabled {
+            let capture_address = self
+                .capture
+                .bind_address
+                .parse::<std::net::SocketAddr>()
+                .with_context(|| {
+                    format!(
+                        "Invalid capture.bind_address: {}",
+                        self.capture.bind_address
+                    )
+                })?;
+            if !capture_address.ip().is_loopback() {
+                anyhow::bail!(
+                    "capture.bind_address {} must be a loopback address",
+                    capture_address
+                );
+            }
+            let has_token = self.capture.token.as_ref().is_some_and(|t| !t.is_empty());
+            if !has_token && self.daemon.clients.is_empty() {
+                anyhow::bail!(
+                    "capture requires capture.token or at least one [[daemon.clients]] token"
+                );
+            }
+        }
+
+        // Validate send-to-notes settings
+        if self.notes.layout == NotesLayout::Daily
+            && (self.notes.daily_format.is_empty() || self.notes.daily_format.contains('/'))
+        {
+            anyhow::bail!("notes.daily_format must be a non-empty file name format");
+        }
+
+        // Validate save routing
+        for (source, target) in &self.save.routes {
+            if source.is_empty() || target.is_empty() {
+                anyhow::bail!("save.routes entries must name both a source and a target provider");
+            }
+        }
+        if self.save.default_target.as_deref() == Some("") {
+            anyhow::bail!("save.default_target must not be empty");
+        }
+
+        // Validate ranking weights
+        if !self.ranking.half_life_hours.is_finite() || self.ranking.half_life_hours <= 0.0 {
+            anyhow::bail!("ranking.half_life_hours must be greater than 0");
+        }
+        if self.ranking.engagement_weight < 0.0 || self.ranking.affinity_weight < 0.0 {
+            anyhow::bail!("ranking weights must not be negative");
+        }
+
+        // Validate mute rules
+        let mute_rule_sets =
+            std::iter::once(&self.mute.rules).chain(self.mute.providers.values().map(|p| &p.rules));
+        for rules in mute_rule_sets {
+            let has_empty_entry = rules
+                .keywords
+                .iter()
+                .chain(&rules.authors)
+                .chain(&rules.domains)
+                .chain(&rules.subreddits)
+                .any(|entry| entry.trim().is_empty());
+            if has_empty_entry {
+                anyhow::bail!("mute rules must not contain empty entries");
+            }
+        }
+
+        // Validate digest settings
+        if self.digest.hour > 23 {
+            anyhow::bail!("digest.hour must be between 0 and 23");
+        }
+        if self.digest.items_per_provider == 0 {
+            anyhow::bail!("digest.items_per_provider must be greater than 0");
+        }
+
+        // Validate summarizer settings
+        if let Some(ref endpoint) = self.summarizer.endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                anyhow::bail!("summarizer.endpoint must be an http(s) URL: {}", endpoint);
+            }
+        }
+        if self.summarizer.max_input_chars == 0 || self.summarizer.max_tokens == 0 {
+            anyhow::bail!("summarizer.max_input_chars and max_tokens must be greater than 0");
+        }
+        if self.summarizer.timeout_secs == 0 {
+            anyhow::bail!("summarizer.timeout_secs must be greater than 0");
+        }
+
+        // Validate TTS settings
+        if let Some(ref command) = self.tts.command {
+            if command.trim().is_empty() {
+                anyhow::bail!("tts.command must not be empty");
+            }
+        }
+        if self.tts.max_chars == 0 || self.tts.max_queue == 0 {
+            anyhow::bail!("tts.max_chars and tts.max_queue must be greater than 0");
+        }
+
+        // Validate reminder settings
+        if self.reminders.notify && self.reminders.notify_command.trim().is_empty() {
+            anyhow::bail!("reminders.notify_command must not be empty");
+        }
+        if self.reminders.check_interval_secs == 0 {
+            anyhow::bail!("reminders.check_interval_secs must be greater than 0");
+        }
+
+        // Validate opener commands
+        let opener_commands = std::iter::once(&self.opener.commands)
+            .chain(self.opener.providers.values())
+            .flat_map(OpenerCommands::iter);
+        for command in opener_commands {
+            if command.trim().is_empty() {
+                anyhow::bail!("opener commands must not be empty");
+            }
+        }
+
+        // Validate player settings
+        if self.player.command.trim().is_empty() {
+            anyhow::bail!("player.command must not be empty");
+        }
+        if self.player.progress_interval_secs == 0 {
+            anyhow::bail!("player.progress_interval_secs must be greater than 0");
+        }
+
+        // Validate share targets
+        let mut share_ids = std::collections::HashSet::new();
+        for target in &self.share {
+            if target.id.trim().is_empty() {
+                anyhow::bail!("share targets must have an id");
+            }
+            if !share_ids.insert(target.id.as_str()) {
+                anyhow::bail!("share target '{}' is defined twice", target.id);
+            }
+            let mut templates = vec![target.format.as_deref()];
+            match &target.kind {
+                ShareKind::Email {
+                    provider,
+                    to,
+                    subject,
+                } => {
+                    if provider.is_empty() || to.is_empty() {
+                        anyhow::bail!(
+                            "share target '{}' needs a provider and at least one recipient",
+                            target.id
+                        );
+                    }
+                    templates.push(subject.as_deref());
+                }
+                ShareKind::Mastodon {
+                    instance,
+                    token_env,
+                    ..
+                } => {
+                    if !instance.starts_with("https://") && !instance.starts_with("http://") {
+                        anyhow::bail!(
+                            "share target '{}': instance must be an http(s) URL",
+                            target.id
+                        );
+                    }
+                    if token_env.is_empty() {
+                        anyhow::bail!("share target '{}' needs a token_env", target.id);
+                    }
+                }
+                ShareKind::Clipboard | ShareKind::File { .. } => {}
+            }
+            for template in templates.into_iter().flatten() {
+                crate::share::check_template(template)
+                    .with_context(|| format!("Invalid template in share target '{}'", target.id))?;
+            }
+        }
+
+        // Validate contacts settings
+        if let Some(url) = &self.contacts.carddav_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("contacts.carddav_url must start with http:// or https://");
+            }
+        }
+        if self
+            .contacts
+            .ignore
+            .iter()
+            .any(|address| !address.contains('@'))
+        {
+            anyhow::bail!("contacts.ignore entries must be email addresses");
+        }
+
+        // Validate attachment indexing settings
+        if self.attachments.index {
+            if self.attachments.max_bytes == 0 || self.attachments.max_chars == 0 {
+                anyhow::bail!(
+                    "attachments.max_bytes and attachments.max_chars must be greater than 0"
+                );
+            }
+            if self.attachments.pdf_command.trim().is_empty() {
+                anyhow::bail!("attachments.pdf_command must not be empty");
+            }
+        }
+
+        // Validate newsletter unbundling settings
+        if self.newsletters.unbundle && self.newsletters.min_stories < 2 {
+            anyhow::bail!("newsletters.min_stories must be at least 2");
+        }
+
+        // Validate download settings
+        if self.downloads.max_concurrent == 0 {
+            anyhow::bail!("downloads.max_concurrent must be greater than 0");
+        }
+        if self.downloads.ytdl_command.trim().is_empty() {
+            anyhow::bail!("downloads.ytdl_command must not be empty");
+        }
+
+        // Validate sync settings
+        if self.sync.max_concurrent == 0 || self.sync.timeout_secs == 0 {
+            anyhow::bail!("sync.max_concurrent and sync.timeout_secs must be greater than 0");
+        }
+        if self.bandwidth.sync_interval_factor == 0 || self.bandwidth.page_size == 0 {
+            anyhow::bail!(
+                "bandwidth.sync_interval_factor and bandwidth.page_size must be greater than 0"
+            );
+        }
+
+        // Validate cache settings
+        if self.cache.max_items_per_stream == 0 {
+            anyhow::bail!("cache.max_items_per_stream must be greater than 0");
+        }
+        if self.cache.max_age_days == Some(0) || self.cache.max_size_mb == Some(0) {
+            anyhow::bail!("cache.max_age_days and cache.max_size_mb must be greater than 0");
+        }
+
+        // Validate provider configurations
+        for (provider_id, provider_config) in &self.providers {
+            if provider_config.sync_interval_minutes == 0 {
+                anyhow::bail!(
+                    "Provider '{}': sync_interval_minutes must be greater than 0",
+                    provider_id
+                );
+            }
+            if provider_config.encrypt_at_rest && self.encryption.key.trim().is_empty() {
+                anyhow::bail!(
+                    "Provider '{}': encrypt_at_rest needs an encryption.key reference",
+                    provider_id
+                );
+            }
+            if provider_config.sync_timeout_secs == Some(0) {
+                anyhow::bail!(
+                    "Provider '{}': sync_timeout_secs must be greater than 0",
+                    provider_id
+                );
+            }
+            if provider_config.proxy.is_some() && !PROXY_PROVIDERS.contains(&provider_id.as_str()) {
+                anyhow::bail!(
+                    "Provider '{}': proxy is only supported for {}",
+                    provider_id,
+                    PROXY_PROVIDERS.join(", ")
+                );
+            }
+            provider_config
+                .proxy_config()
+                .with_context(|| format!("Provider '{}': invalid proxy", provider_id))?;
+        }
+
+        // Validate stream overrides
+        for (stream_id, policy) in &self.streams {
+            if policy.refresh_minutes == Some(0)
+                || policy.max_items == Some(0)
+                || policy.max_age_days == Some(0)
+                || policy.mark_read_after_days == Some(0)
+            {
+                anyhow::bail!(
+                    "Stream '{}': refresh_minutes, max_items, max_age_days and mark_read_after_days must be greater than 0",
+                    stream_id
+                );
+            }
+            if policy.refresh_minutes.is_some() && StreamPolicy::target(stream_id).is_none() {
+                anyhow::bail!(
+                    "Stream '{}': refresh_minutes needs a feed or collection stream ID such as \"rss:feed:<id>\"",
+                    stream_id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the cache database path
+    ///
+    /// Returns the configured cache path or the default XDG data directory path
+    pub fn cache_path(&self) -> Result<PathBuf> {
+        if let Some(ref path) = self.cache.path {
+            return Ok(path.clone());
+        }
+
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.data_dir().join("cache.db"))
+    }
+
+    /// Get the directory of the full-text search index
+    ///
+    /// Returns the configured path or the default XDG data directory path
+    pub fn search_index_path(&self) -> Result<PathBuf> {
+        if let Some(ref path) = self.search.index_path {
+            return Ok(path.clone());
+        }
+
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.data_dir().join("search-index"))
+    }
+
+    /// Get the directory scheduled digests are written to
+    ///
+    /// Returns the configured output directory or the default XDG data directory path
+    pub fn digest_dir(&self) -> Result<PathBuf> {
+        if let Some(ref dir) = self.digest.output_dir {
+            return Ok(dir.clone());
+        }
+
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.data_dir().join("digests"))
+    }
+
+    /// Get the directory downloads are saved to
+    ///
+    /// Returns the configured directory, a `scryforge` directory in the
+    /// user's download directory, or the XDG data directory if there is none
+    pub fn download_dir(&self) -> Result<PathBuf> {
+        if let Some(ref dir) = self.downloads.directory {
+            return Ok(dir.clone());
+        }
+
+        if let Some(downloads) = directories::UserDirs::new()
+            .as_ref()
+            .and_then(|dirs| dirs.download_dir())
+        {
+            return Ok(downloads.join("scryforge"));
+        }
+
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.data_dir().join("downloads"))
+    }
+
+    /// The proxies of the providers that have one, by provider ID
+    pub fn provider_proxies(&self) -> Result<HashMap<String, ProxyConfig>> {
+        let mut proxies = HashMap::new();
+        for (provider_id, provider) in &self.providers {
+            if let Some(proxy) = provider.proxy_config()? {
+                proxies.insert(provider_id.clone(), proxy);
+            }
+        }
+        Ok(proxies)
+    }
+
+    /// Get the directory exports are written into
+    ///
+    /// Returns the configured directory or the default XDG data directory path
+    pub fn export_dir(&self) -> Result<PathBuf> {
+        if let Some(ref dir) = self.export.directory {
+            return Ok(dir.clone());
+        }
+
+        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
+            .context("Failed to determine project directories")?;
+
+        Ok(dirs.data_dir().join("exports"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.daemon.bind_address, "127.0.0.1:3030");
+        assert_eq!(config.daemon.log_level, "info");
+        assert_eq!(config.cache.max_items_per_stream, 1000);
+        assert!(config.cache.path.is_none());
+        assert!(config.providers.is_empty());
+    }
+
+    #[test]
+    fn test_default_daemon_config() {
+        let config = DaemonConfig::default();
+        assert_eq!(config.bind_address, "127.0.0.1:3030");
+        assert_eq!(config.log_level, "info");
+    }
+
+    #[test]
+    fn test_default_web_config() {
+        let config = WebConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.bind_address, "127.0.0.1:3031");
+        assert_eq!(config.items_per_page, 100);
+    }
+
+    #[test]
+    fn test_validate_capture_config() {
+        let mut config = Config::default();
+        config.capture.enabled = true;
+        assert!(config.validate().is_err());
+
+        config.capture.token = Some("secret".to_string());
+        assert!(config.validate().is_ok());
+
+        config.capture.bind_address = "0.0.0.0:3032".to_string();
+        assert!(config.validate().is_err());
+
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[capture]
+enabled = true
+folder = "Inbox"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.capture.bind_address, "127.0.0.1:3032");
+        assert_eq!(config.capture.folder.as_deref(), Some("Inbox"));
+    }
+
+    #[test]
+    fn test_notes_config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[notes]
+path = "/tmp/journals"
+layout = "daily"
+daily_format = "%Y_%m_%d"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.notes.path, Some(PathBuf::from("/tmp/journals")));
+        assert_eq!(config.notes.layout, NotesLayout::Daily);
+        assert!(config.validate().is_ok());
+
+        assert_eq!(Config::default().notes.path, None);
+    }
+
+    #[test]
+    fn test_save_routes_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[save.routes]
+rss = "bookmarks"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.save.routes.get("rss").map(String::as_str),
+            Some("bookmarks")
+        );
+        assert_eq!(config.save.default_target, None);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mute_config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[mute]
+keywords = ["spoiler"]
+hide_nsfw = true
+
+[mute.providers.reddit]
+subreddits = ["r/politics"]
+inherit = false
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.mute.rules.keywords, vec!["spoiler".to_string()]);
+        assert_eq!(config.mute.rules.hide_nsfw, Some(true));
+        let reddit = &config.mute.providers["reddit"];
+        assert_eq!(reddit.rules.subreddits, vec!["r/politics".to_string()]);
+        assert!(!reddit.inherit);
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.mute.rules.authors.push(" ".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_digest_config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[digest]
+enabled = true
+schedule = "weekly"
+format = "html"
+output_dir = "/tmp/digests"
+"#,
+        )
+        .unwrap();
+
+        assert!(config.digest.enabled);
+        assert_eq!(config.digest.schedule, DigestSchedule::Weekly);
+        assert_eq!(config.digest.format, DigestFormat::Html);
+        assert_eq!(config.digest.hour, 7);
+        assert_eq!(config.digest_dir().unwrap(), PathBuf::from("/tmp/digests"));
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.digest.hour = 24;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_summarizer_config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[summarizer]
+backend = "openai"
+endpoint = "http://localhost:11434/v1"
+model = "llama3.2"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.summarizer.backend, Some(SummarizerBackend::Openai));
+        assert_eq!(config.summarizer.model, "llama3.2");
+        assert_eq!(config.summarizer.min_words, 150);
+        assert!(config.validate().is_ok());
+        assert_eq!(Config::default().summarizer.backend, None);
+
+        let mut config = config;
+        config.summarizer.endpoint = Some("localhost:8080".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tts_config_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[tts]
+command = "espeak-ng -s 170"
+max_queue = 5
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tts.command.as_deref(), Some("espeak-ng -s 170"));
+        assert_eq!(config.tts.max_queue, 5);
+        assert_eq!(config.tts.max_chars, 20000);
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.tts.command = Some("  ".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_opener_config() {
+        let config: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[opener]
+video = "mpv"
+pdf = "zathura"
+
+[opener.providers.reddit]
+article = "w3m"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.opener.commands.default, None);
+        assert_eq!(config.opener.commands.video.as_deref(), Some("mpv"));
+        assert_eq!(config.opener.commands.pdf.as_deref(), Some("zathura"));
+        assert_eq!(
+            config.opener.providers["reddit"].article.as_deref(),
+            Some("w3m")
+        );
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.opener.providers.get_mut("reddit").unwrap().video = Some(" ".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_player_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.player, PlayerConfig::default());
+        assert!(config.validate().is_ok());
+
+        let config_str = r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[player]
+args = ["--fs", "--volume=60"]
+ytdl_format = "bestaudio"
+resume = false
+"#;
+        let parsed: Config = toml::from_str(config_str).unwrap();
+        assert_eq!(parsed.player.command, "mpv");
+        assert_eq!(parsed.player.args, vec!["--fs", "--volume=60"]);
+        assert_eq!(parsed.player.ytdl_format.as_deref(), Some("bestaudio"));
+        assert!(!parsed.player.resume);
+
+        config.player.progress_interval_secs = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_downloads_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.downloads, DownloadsConfig::default());
+        assert!(config.validate().is_ok());
+
+        let config_str = r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[downloads]
+directory = "/tmp/scryforge-downloads"
+max_concurrent = 4
+"#;
+        let parsed: Config = toml::from_str(config_str).unwrap();
+        assert_eq!(
+            parsed.download_dir().unwrap(),
+            PathBuf::from("/tmp/scryforge-downloads")
+        );
+        assert_eq!(parsed.downloads.max_concurrent, 4);
+        assert_eq!(parsed.downloads.ytdl_command, "yt-dlp");
+
+        config.downloads.max_concurrent = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_export_config() {
+        let config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.export, ExportConfig::default());
+        assert!(config.export_dir().unwrap().ends_with("exports"));
+
+        let parsed: Config = toml::from_str(
+            r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
+
+[cache]
+max_items_per_stream = 1000
+
+[export]
+directory = "/srv/www/scryforge"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.export_dir().unwrap(),
+            PathBuf::from("/srv/www/scryforge")
+        );
+    }
+
+    #[test]
+    fn test_attachments_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.attachments, AttachmentsConfig::default());
+        assert!(!config.attachments.index);
+
+        config.attachments.max_chars = 0;
+        assert!(config.validate().is_ok());
+        config.attachments.index = true;
+        assert!(config.validate().is_err());
+        config.attachments.max_chars = 1000;
+        assert!(config.validate().is_ok());
+        config.attachments.pdf_command = " ".to_string();
+        assert!(config.validate().is_err());
     }
 
-    /// Create a default configuration file with documented comments
-    fn create_default_file(path: &Path) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).with_context(|| {
-                format!("Failed to create config directory: {}", parent.display())
-            })?;
-        }
+    #[test]
+    fn test_newsletters_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.newsletters, NewslettersConfig::default());
 
-        let default_config = Self::default_config_content();
-        fs::write(path, default_config)
-            .with_context(|| format!("Failed to write default config file: {}", path.display()))?;
+        config.newsletters.min_stories = 1;
+        assert!(config.validate().is_ok());
+        config.newsletters.unbundle = true;
+        assert!(config.validate().is_err());
 
-        tracing::info!("Created default configuration file at: {}", path.display());
-        Ok(())
+        let parsed: NewslettersConfig =
+            toml::from_str("unbundle = true\nstreams = [\"rss:feed:weekly\"]").unwrap();
+        assert_eq!(parsed.min_stories, 3);
+        assert_eq!(parsed.streams, vec!["rss:feed:weekly"]);
     }
 
-    /// Generate the default configuration file content with comments
-    fn default_config_content() -> String {
-        r#"# Scryforge Daemon Configuration
-# This file configures the scryforge-daemon behavior.
-
+    #[test]
+    fn test_stream_policies() {
+        let config_str = r#"
 [daemon]
-# Bind address for the JSON-RPC API server
-# Default: "127.0.0.1:3030"
 bind_address = "127.0.0.1:3030"
-
-# Log level: trace, debug, info, warn, error
-# Default: "info"
 log_level = "info"
 
 [cache]
-# Path to the SQLite cache database
-# If not specified, defaults to $XDG_DATA_HOME/scryforge/cache.db
-# path = "/path/to/cache.db"
-
-# Maximum number of items to keep per stream
-# Default: 1000
 max_items_per_stream = 1000
 
-# Provider-specific configurations
-# Each provider can be configured with:
-# - enabled: Whether the provider is enabled (default: true)
-# - sync_interval_minutes: How often to sync data (default: 15)
-# - settings: Provider-specific settings (varies by provider)
-
-# Example: Dummy provider configuration
-[providers.dummy]
-enabled = true
-sync_interval_minutes = 15
+[streams."rss:feed:hn"]
+refresh_minutes = 5
+max_items = 50
+mark_read_after_days = 7
 
-# Provider-specific settings are defined here
-[providers.dummy.settings]
-# Add provider-specific settings as needed
-# For the dummy provider, no special settings are required
+[streams."bookmarks:collection:papers"]
+max_items = 5000
+"#;
+        let mut config: Config = toml::from_str(config_str).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.streams["rss:feed:hn"],
+            StreamPolicy {
+                refresh_minutes: Some(5),
+                max_items: Some(50),
+                max_age_days: None,
+                mark_read_after_days: Some(7),
+            }
+        );
+        assert_eq!(
+            config.streams["bookmarks:collection:papers"].max_items,
+            Some(5000)
+        );
 
-# Example: Future RSS provider configuration
-# [providers.rss]
-# enabled = true
-# sync_interval_minutes = 30
-#
-# [providers.rss.settings]
-# feeds = [
-#     "https://example.com/feed.xml",
-#     "https://another.example.com/rss",
-# ]
+        assert_eq!(
+            StreamPolicy::target("rss:feed:rss:0"),
+            Some(("rss", false, "rss:0"))
+        );
+        assert_eq!(
+            StreamPolicy::target("bookmarks:collection:papers"),
+            Some(("bookmarks", true, "papers"))
+        );
+        assert_eq!(StreamPolicy::target("rss:saved:all"), None);
 
-# Example: Future email provider configuration
-# [providers.email]
-# enabled = true
-# sync_interval_minutes = 5
-#
-# [providers.email.settings]
-# imap_server = "imap.example.com"
-# imap_port = 993
-# use_tls = true
-"#
-        .to_string()
+        config.streams.insert(
+            "rss:saved:all".to_string(),
+            StreamPolicy {
+                refresh_minutes: Some(5),
+                ..StreamPolicy::default()
+            },
+        );
+        assert!(config.validate().is_err());
+        config
+            .streams
+            .get_mut("rss:saved:all")
+            .unwrap()
+            .refresh_minutes = None;
+        config.streams.get_mut("rss:saved:all").unwrap().max_items = Some(10);
+        assert!(config.validate().is_ok());
+        config.streams.get_mut("rss:feed:hn").unwrap().max_items = Some(0);
+        assert!(config.validate().is_err());
     }
 
-    /// Validate the configuration
-    ///
-    /// Ensures all configuration values are valid and within acceptable ranges.
-    pub fn validate(&self) -> Result<()> {
-        // Validate bind address format
-        self.daemon
-            .bind_address
-            .parse::<std::net::SocketAddr>()
-            .with_context(|| format!("Invalid bind_address: {}", self.daemon.bind_address))?;
+    #[test]
+    fn test_reminders_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.reminders, RemindersConfig::default());
+        assert!(config.validate().is_ok());
 
-        // Validate log level
-        let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
-        if !valid_log_levels.contains(&self.daemon.log_level.as_str()) {
-            anyhow::bail!(
-                "Invalid log_level: {}. Must be one of: {}",
-                self.daemon.log_level,
-                valid_log_levels.join(", ")
-            );
-        }
+        config.reminders.notify_command = String::new();
+        assert!(config.validate().is_err());
+        config.reminders.notify = false;
+        assert!(config.validate().is_ok());
 
-        // Validate cache settings
-        if self.cache.max_items_per_stream == 0 {
-            anyhow::bail!("cache.max_items_per_stream must be greater than 0");
-        }
+        config.reminders.check_interval_secs = 0;
+        assert!(config.validate().is_err());
+    }
 
-        // Validate provider configurations
-        for (provider_id, provider_config) in &self.providers {
-            if provider_config.sync_interval_minutes == 0 {
-                anyhow::bail!(
-                    "Provider '{}': sync_interval_minutes must be greater than 0",
-                    provider_id
-                );
-            }
-        }
+    #[test]
+    fn test_contacts_config() {
+        let toml_str = r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
 
-        Ok(())
+[cache]
+max_items_per_stream = 1000
+
+[contacts]
+ignore = ["me@example.com"]
+carddav_url = "https://dav.example.com/addressbooks/me/"
+carddav_password_env = "CARDDAV_PASSWORD"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.contacts.ignore, vec!["me@example.com"]);
+        assert!(config.contacts.carddav_username.is_none());
+        assert!(config.validate().is_ok());
+
+        config.contacts.carddav_url = Some("dav.example.com".to_string());
+        assert!(config.validate().is_err());
+        config.contacts.carddav_url = None;
+        config.contacts.ignore.push("me".to_string());
+        assert!(config.validate().is_err());
     }
 
-    /// Get the cache database path
-    ///
-    /// Returns the configured cache path or the default XDG data directory path
-    pub fn cache_path(&self) -> Result<PathBuf> {
-        if let Some(ref path) = self.cache.path {
-            return Ok(path.clone());
-        }
+    #[test]
+    fn test_share_targets() {
+        let toml_str = r#"
+[daemon]
+bind_address = "127.0.0.1:3030"
+log_level = "info"
 
-        let dirs = directories::ProjectDirs::from("", "raibid-labs", "scryforge")
-            .context("Failed to determine project directories")?;
+[cache]
+max_items_per_stream = 1000
 
-        Ok(dirs.data_dir().join("cache.db"))
-    }
-}
+[[share]]
+id = "markdown"
+kind = "clipboard"
+format = "[{title}]({url})"
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
+[[share]]
+id = "mail-team"
+kind = "email"
+provider = "outlook"
+to = ["team@example.com"]
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.share.len(), 2);
+        assert_eq!(config.share[0].kind, ShareKind::Clipboard);
+        assert!(matches!(
+            &config.share[1].kind,
+            ShareKind::Email { provider, subject: None, .. } if provider == "outlook"
+        ));
+        assert!(config.validate().is_ok());
+
+        config.share[1].id = "markdown".to_string();
+        assert!(config.validate().is_err());
+        config.share[1].id = "mail-team".to_string();
+        config.share[0].format = Some("{title} {link}".to_string());
+        assert!(config.validate().is_err());
+
+        // The commented examples in the default file parse and validate
+        let examples: String = Config::default_config_content()
+            .lines()
+            .skip_while(|line| !line.starts_with("# [[share]]"))
+            .take_while(|line| line.starts_with('#'))
+            .map(|line| line.trim_start_matches('#').trim_start())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let header = &toml_str[..toml_str.find("[[share]]").unwrap()];
+        let config: Config = toml::from_str(&format!("{}{}", header, examples)).unwrap();
+        assert_eq!(config.share.len(), 4);
+        assert!(config.validate().is_ok());
+    }
 
     #[test]
-    fn test_default_config() {
-        let config = Config::default();
-        assert_eq!(config.daemon.bind_address, "127.0.0.1:3030");
-        assert_eq!(config.daemon.log_level, "info");
-        assert_eq!(config.cache.max_items_per_stream, 1000);
-        assert!(config.cache.path.is_none());
-        assert!(config.providers.is_empty());
+    fn test_validate_ranking_config() {
+        let mut config = Config::default();
+        assert_eq!(config.ranking.half_life_hours, 24.0);
+
+        config.ranking.half_life_hours = 0.0;
+        assert!(config.validate().is_err());
+
+        config.ranking.half_life_hours = 12.0;
+        config.ranking.affinity_weight = -1.0;
+        assert!(config.validate().is_err());
     }
 
     #[test]
-    fn test_default_daemon_config() {
-        let config = DaemonConfig::default();
-        assert_eq!(config.bind_address, "127.0.0.1:3030");
-        assert_eq!(config.log_level, "info");
+    fn test_validate_public_web_requires_clients() {
+        let mut config = Config::default();
+        config.web.enabled = true;
+        config.web.bind_address = "0.0.0.0:3031".to_string();
+        assert!(config.validate().is_err());
+
+        // A disabled dashboard is not checked
+        config.web.enabled = false;
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -313,6 +2581,10 @@ mod tests {
 bind_address = "0.0.0.0:8080"
 log_level = "debug"
 
+[[daemon.clients]]
+name = "test"
+token = "secret"
+
 [cache]
 max_items_per_stream = 500
 
@@ -370,6 +2642,58 @@ max_items_per_stream = 1000
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_public_bind_requires_clients() {
+        let mut config = Config::default();
+        config.daemon.bind_address = "0.0.0.0:3030".to_string();
+        assert!(config.validate().is_err());
+
+        config.daemon.clients.push(ClientConfig {
+            name: "laptop".to_string(),
+            token: "secret".to_string(),
+        });
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_empty_client_token() {
+        let mut config = Config::default();
+        config.daemon.clients.push(ClientConfig {
+            name: "laptop".to_string(),
+            token: " ".to_string(),
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_load_clients_and_tls() {
+        let config_content = r#"
+[daemon]
+bind_address = "0.0.0.0:3030"
+log_level = "info"
+
+[[daemon.clients]]
+name = "laptop"
+token = "abc123"
+
+[daemon.tls]
+cert_path = "/etc/scryforge/cert.pem"
+key_path = "/etc/scryforge/key.pem"
+
+[cache]
+max_items_per_stream = 1000
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(config_content.as_bytes()).unwrap();
+
+        let config = Config::load(temp_file.path()).unwrap();
+        assert_eq!(config.daemon.clients.len(), 1);
+        assert_eq!(config.daemon.clients[0].name, "laptop");
+        let tls = config.daemon.tls.unwrap();
+        assert_eq!(tls.cert_path, PathBuf::from("/etc/scryforge/cert.pem"));
+    }
+
     #[test]
     fn test_validate_invalid_log_level() {
         let mut config = Config::default();
@@ -384,6 +2708,29 @@ max_items_per_stream = 1000
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_cache_retention_config() {
+        let config: CacheConfig = toml::from_str(
+            r#"
+max_items_per_stream = 200
+max_age_days = 30
+max_size_mb = 250
+eviction = "lru"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_age_days, Some(30));
+        assert_eq!(config.max_size_mb, Some(250));
+        assert_eq!(config.eviction, EvictionPolicy::Lru);
+
+        let mut config = Config::default();
+        config.cache.max_age_days = Some(0);
+        assert!(config.validate().is_err());
+        config.cache.max_age_days = None;
+        config.cache.max_size_mb = Some(0);
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_validate_zero_sync_interval() {
         let mut config = Config::default();
@@ -392,12 +2739,46 @@ max_items_per_stream = 1000
             ProviderConfig {
                 enabled: true,
                 sync_interval_minutes: 0,
+                sync_timeout_secs: None,
+                conflict_strategy: Default::default(),
+                encrypt_at_rest: false,
+                budget: Default::default(),
+                proxy: None,
                 settings: toml::Value::Table(toml::map::Map::new()),
             },
         );
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_zero_sync_limits() {
+        let mut config = Config::default();
+        config.sync.max_concurrent = 0;
+        assert!(config.validate().is_err());
+
+        let mut config = Config::default();
+        config.providers.insert(
+            "test".to_string(),
+            ProviderConfig {
+                sync_timeout_secs: Some(0),
+                conflict_strategy: Default::default(),
+                encrypt_at_rest: false,
+                ..Default::default()
+            },
+        );
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_provider_sync_timeout_override() {
+        let sync = SyncConfig::default();
+        let mut provider_config = ProviderConfig::default();
+        assert_eq!(provider_config.sync_timeout(&sync).as_secs(), 120);
+
+        provider_config.sync_timeout_secs = Some(300);
+        assert_eq!(provider_config.sync_timeout(&sync).as_secs(), 300);
+    }
+
     #[test]
     fn test_cache_path_default() {
         let config = Config::default();
@@ -414,6 +2795,69 @@ max_items_per_stream = 1000
         assert_eq!(config.cache_path().unwrap(), custom_path);
     }
 
+    #[test]
+    fn test_conflict_strategy_parsing() {
+        let config: ProviderConfig = toml::from_str(
+            r#"
+enabled = true
+sync_interval_minutes = 5
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.conflict_strategy, ConflictStrategy::LatestTimestamp);
+
+        let config: ProviderConfig = toml::from_str(
+            r#"
+enabled = true
+sync_interval_minutes = 5
+conflict_strategy = "remote_wins"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.conflict_strategy, ConflictStrategy::RemoteWins);
+
+        assert!(toml::from_str::<ProviderConfig>(
+            "enabled = true\nsync_interval_minutes = 5\nconflict_strategy = \"newest\"\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_encryption_config() {
+        let mut config: Config = toml::from_str(&Config::default_config_content()).unwrap();
+        assert_eq!(config.encryption.key, "auth://scryforge/encryption/key");
+        assert!(config.providers.values().all(|p| !p.encrypt_at_rest));
+
+        config.providers.get_mut("dummy").unwrap().encrypt_at_rest = true;
+        assert!(config.validate().is_ok());
+        config.encryption.key = " ".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_provider_proxy() {
+        let mut config = Config::default();
+        let provider = ProviderConfig {
+            proxy: Some("tor".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            provider.proxy_config().unwrap().unwrap().url(),
+            "socks5h://127.0.0.1:9050"
+        );
+        assert!(ProviderConfig::default().proxy_config().unwrap().is_none());
+
+        config.providers.insert("rss".to_string(), provider.clone());
+        assert!(config.validate().is_ok());
+        config.providers.get_mut("rss").unwrap().proxy = Some("gopher://proxy:70".to_string());
+        assert!(config.validate().is_err());
+
+        // Providers that don't route requests through a proxy refuse one
+        config.providers.remove("rss");
+        config.providers.insert("bookmarks".to_string(), provider);
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_provider_config_serialization() {
         let mut settings = toml::map::Map::new();
@@ -422,6 +2866,14 @@ max_items_per_stream = 1000
         let provider_config = ProviderConfig {
             enabled: false,
             sync_interval_minutes: 30,
+            sync_timeout_secs: Some(300),
+            conflict_strategy: ConflictStrategy::LocalWins,
+            encrypt_at_rest: true,
+            budget: BudgetConfig {
+                max_operations_per_day: Some(500),
+                max_item_megabytes_per_day: None,
+            },
+            proxy: Some("tor".to_string()),
             settings: toml::Value::Table(settings),
         };
 
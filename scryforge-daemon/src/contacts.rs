@@ -0,0 +1,429 @@
+//! Contacts for composing email.
+//!
+//! The cache records every address in the From, To, and Cc headers of cached
+//! email, so `contacts.complete` can suggest recipients ranked by how many
+//! messages they appeared in, then by how recently. The record outlives the
+//! messages: evicting an item does not forget who sent it. Automated senders
+//! such as `noreply@` are never recorded, nor are the addresses of encrypted
+//! items, since the contacts table is stored in the clear.
+//!
+//! A CardDAV address book can be imported as well, at startup and with
+//! `contacts.import`. Its contacts are suggested even if they never appeared
+//! in a cached message, and their names take precedence over header names.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::{
+    decode_entities, Item, ItemContent, CC_METADATA_KEY, TO_METADATA_KEY,
+};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::cache::Cache;
+use crate::config::ContactsConfig;
+
+/// Request body asking a CardDAV server for every vCard in an address book.
+const ADDRESSBOOK_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<C:addressbook-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:carddav">
+  <D:prop>
+    <D:getetag/>
+    <C:address-data/>
+  </D:prop>
+</C:addressbook-query>"#;
+
+/// A suggested recipient.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contact {
+    /// Lowercased address
+    pub email: String,
+    pub name: Option<String>,
+    /// Cached messages the address appeared in
+    pub messages: u64,
+    /// Date of the latest of those messages
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Whether the contact came from the imported address book
+    pub in_address_book: bool,
+}
+
+/// An address read from a header or a vCard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenAddress {
+    /// Lowercased address
+    pub email: String,
+    pub name: Option<String>,
+}
+
+impl SeenAddress {
+    fn new(name: Option<&str>, email: &str) -> Option<Self> {
+        let email = email.trim().to_lowercase();
+        if !email.contains('@') || email.contains(char::is_whitespace) {
+            return None;
+        }
+        let name = name
+            .map(|name| name.trim().trim_matches('"').trim())
+            .filter(|name| !name.is_empty() && !name.eq_ignore_ascii_case(&email))
+            .map(str::to_string);
+        Some(Self { email, name })
+    }
+}
+
+/// The addresses of an email item: its sender and its To and Cc recipients.
+/// Other items have none.
+pub fn item_addresses(item: &Item) -> Vec<SeenAddress> {
+    if !matches!(item.content, ItemContent::Email { .. }) {
+        return Vec::new();
+    }
+    let from = item
+        .author
+        .as_ref()
+        .and_then(|author| Some((author.name.as_str(), author.email.as_deref()?)));
+    header_addresses(from, &item.metadata)
+}
+
+/// Addresses from a sender's name and address and the recipient lists in
+/// `metadata`, without automated senders or duplicates.
+pub fn header_addresses(
+    from: Option<(&str, &str)>,
+    metadata: &HashMap<String, String>,
+) -> Vec<SeenAddress> {
+    let from = from.and_then(|(name, email)| SeenAddress::new(Some(name), email));
+    let recipients = [TO_METADATA_KEY, CC_METADATA_KEY]
+        .into_iter()
+        .filter_map(|key| metadata.get(key))
+        .flat_map(|list| parse_mailboxes(list));
+
+    let mut addresses: Vec<SeenAddress> = Vec::new();
+    for address in from.into_iter().chain(recipients) {
+        if !is_automated(&address.email) && !addresses.iter().any(|a| a.email == address.email) {
+            addresses.push(address);
+        }
+    }
+    addresses
+}
+
+/// Parse a comma-separated list of mailboxes, as written by
+/// [`format_mailbox`](scryforge_provider_core::format_mailbox):
+/// `Name <address>`, `"Last, First" <address>`, or a bare address.
+pub fn parse_mailboxes(list: &str) -> Vec<SeenAddress> {
+    let mut mailboxes = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut angle = false;
+    for (i, c) in list.char_indices() {
+        match c {
+            '"' if !angle => quoted = !quoted,
+            '<' if !quoted => angle = true,
+            '>' if !quoted => angle = false,
+            ',' if !quoted && !angle => {
+                mailboxes.extend(parse_mailbox(&list[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    mailboxes.extend(parse_mailbox(&list[start..]));
+    mailboxes
+}
+
+fn parse_mailbox(mailbox: &str) -> Option<SeenAddress> {
+    let mailbox = mailbox.trim();
+    match (mailbox.rfind('<'), mailbox.ends_with('>')) {
+        (Some(open), true) => {
+            let name = mailbox[..open].replace("\\\"", "\"");
+            SeenAddress::new(Some(&name), &mailbox[open + 1..mailbox.len() - 1])
+        }
+        _ => SeenAddress::new(None, mailbox),
+    }
+}
+
+/// Whether an address belongs to a mailer rather than a person, like
+/// `noreply@`, `do-not-reply@`, or `mailer-daemon@`.
+fn is_automated(email: &str) -> bool {
+    let local: String = email
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.'))
+        .collect();
+    local.contains("noreply")
+        || local.contains("donotreply")
+        || local.starts_with("mailerdaemon")
+        || local == "postmaster"
+}
+
+/// Read the names and addresses from the vCards in `text`, such as a CardDAV
+/// multistatus response. A contact with several addresses yields one entry
+/// per address.
+pub fn parse_vcards(text: &str) -> Vec<SeenAddress> {
+    let text = decode_entities(text);
+
+    let mut addresses = Vec::new();
+    let mut rest = text.as_str();
+    while let Some(begin) = rest.find("BEGIN:VCARD") {
+        let (card, next) = rest[begin..]
+            .split_once("END:VCARD")
+            .unwrap_or((&rest[begin..], ""));
+        addresses.extend(parse_vcard(card));
+        rest = next;
+    }
+    addresses
+}
+
+fn parse_vcard(card: &str) -> Vec<SeenAddress> {
+    // Lines starting with whitespace continue the previous one
+    let mut lines: Vec<String> = Vec::new();
+    for line in card.lines().map(|line| line.trim_end_matches('\r')) {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut name = None;
+    let mut emails = Vec::new();
+    for line in &lines {
+        let Some((property, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop parameters like `;TYPE=work` and groups like `item1.`
+        let property = property.split(';').next().unwrap_or_default();
+        let property = property.rsplit('.').next().unwrap_or_default();
+        if property.eq_ignore_ascii_case("FN") {
+            name = Some(unescape_value(value));
+        } else if property.eq_ignore_ascii_case("EMAIL") {
+            let value = value.trim();
+            let value = value.strip_prefix("mailto:").unwrap_or(value).to_string();
+            emails.push(value);
+        }
+    }
+
+    emails
+        .iter()
+        .filter_map(|email| SeenAddress::new(name.as_deref(), email))
+        .collect()
+}
+
+fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push(' '),
+            Some(escaped) => unescaped.push(escaped),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// A CardDAV address book to import.
+#[derive(Debug, Clone)]
+struct CardDav {
+    url: String,
+    username: Option<String>,
+    password_env: Option<String>,
+}
+
+/// Contact suggestions and the CardDAV import, per the `[contacts]` config.
+#[derive(Debug, Clone, Default)]
+pub struct Contacts {
+    ignore: HashSet<String>,
+    carddav: Option<CardDav>,
+    http: reqwest::Client,
+}
+
+impl Contacts {
+    pub fn from_config(config: &ContactsConfig) -> Self {
+        Self {
+            ignore: config
+                .ignore
+                .iter()
+                .map(|address| address.trim().to_lowercase())
+                .collect(),
+            carddav: config.carddav_url.clone().map(|url| CardDav {
+                url,
+                username: config.carddav_username.clone(),
+                password_env: config.carddav_password_env.clone(),
+            }),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Up to `limit` contacts whose address, name, or a word of their name
+    /// starts with `prefix`, leaving out ignored addresses.
+    pub fn complete<C: Cache>(&self, cache: &C, prefix: &str, limit: u32) -> Result<Vec<Contact>> {
+        let mut contacts = cache.complete_contacts(
+            prefix.trim(),
+            limit.saturating_add(self.ignore.len() as u32),
+        )?;
+        contacts.retain(|contact| !self.ignore.contains(&contact.email));
+        contacts.truncate(limit as usize);
+        Ok(contacts)
+    }
+
+    /// Whether a CardDAV address book is configured.
+    pub fn can_import(&self) -> bool {
+        self.carddav.is_some()
+    }
+
+    /// Replace the cached address book with the CardDAV one, returning how
+    /// many addresses it holds.
+    pub async fn import<C: Cache>(&self, cache: &C) -> Result<usize> {
+        let Some(carddav) = &self.carddav else {
+            bail!("No CardDAV address book is configured");
+        };
+
+        let method = reqwest::Method::from_bytes(b"REPORT").expect("REPORT is a valid method");
+        let mut request = self
+            .http
+            .request(method, &carddav.url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(ADDRESSBOOK_QUERY);
+        if let Some(username) = &carddav.username {
+            let password = match &carddav.password_env {
+                Some(var) => Some(
+                    std::env::var(var)
+                        .with_context(|| format!("CardDAV password variable {} is not set", var))?,
+                ),
+                None => None,
+            };
+            request = request.basic_auth(username, password);
+        }
+
+        let body = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", carddav.url))?
+            .error_for_status()?
+            .text()
+            .await?;
+        let addresses = parse_vcards(&body);
+        debug!(
+            "Read {} addresses from CardDAV address book {}",
+            addresses.len(),
+            carddav.url
+        );
+        cache.import_contacts(&addresses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{Author, ItemId, StreamId};
+
+    fn address(name: Option<&str>, email: &str) -> SeenAddress {
+        SeenAddress {
+            email: email.to_string(),
+            name: name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_mailboxes() {
+        let list = r#"Ann Lee <Ann@Example.com>, "Doe, John" <john@example.com>, bob@example.com, not an address"#;
+        assert_eq!(
+            parse_mailboxes(list),
+            vec![
+                address(Some("Ann Lee"), "ann@example.com"),
+                address(Some("Doe, John"), "john@example.com"),
+                address(None, "bob@example.com"),
+            ]
+        );
+        assert!(parse_mailboxes("").is_empty());
+    }
+
+    #[test]
+    fn test_item_addresses() {
+        let mut item = Item {
+            id: ItemId::new("email", "1"),
+            stream_id: StreamId::new("email", "inbox", "INBOX"),
+            title: "Lunch".to_string(),
+            content: ItemContent::Email {
+                subject: "Lunch".to_string(),
+                body_text: None,
+                body_html: None,
+                snippet: String::new(),
+            },
+            author: Some(Author {
+                name: "Ann Lee".to_string(),
+                email: Some("ann@example.com".to_string()),
+                url: None,
+                avatar_url: None,
+            }),
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        };
+        item.metadata.insert(
+            TO_METADATA_KEY.to_string(),
+            "me@example.com, ann@example.com".to_string(),
+        );
+        item.metadata.insert(
+            CC_METADATA_KEY.to_string(),
+            "No Reply <no-reply@shop.example.com>".to_string(),
+        );
+
+        assert_eq!(
+            item_addresses(&item),
+            vec![
+                address(Some("Ann Lee"), "ann@example.com"),
+                address(None, "me@example.com"),
+            ]
+        );
+
+        item.content = ItemContent::Text("Lunch".to_string());
+        assert!(item_addresses(&item).is_empty());
+    }
+
+    #[test]
+    fn test_parse_vcards() {
+        let response = "<d:multistatus xmlns:d=\"DAV:\">\
+            <d:response><card:address-data>BEGIN:VCARD&#13;\n\
+            VERSION:3.0&#13;\n\
+            FN:Lee\\, Ann&#13;\n\
+            item1.EMAIL;TYPE=work:ann@example.com&#13;\n\
+            EMAIL;TYPE=home:ann.lee@\n home.example&#13;\n\
+            END:VCARD&#13;\n</card:address-data></d:response>\
+            <d:response><card:address-data>BEGIN:VCARD\n\
+            FN:R&amp;D &amp;lt;Labs&amp;gt;\n\
+            EMAIL:rd@example.com\n\
+            END:VCARD\n</card:address-data></d:response>\
+            <d:response><card:address-data>BEGIN:VCARD\n\
+            FN:No Email\n\
+            END:VCARD\n</card:address-data></d:response></d:multistatus>";
+
+        assert_eq!(
+            parse_vcards(response),
+            vec![
+                address(Some("Lee, Ann"), "ann@example.com"),
+                address(Some("Lee, Ann"), "ann.lee@home.example"),
+                // Entities are decoded once: the name was `R&D &lt;Labs&gt;`
+                address(Some("R&D &lt;Labs&gt;"), "rd@example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_automated_senders() {
+        assert!(is_automated("noreply@example.com"));
+        assert!(is_automated("do-not-reply@example.com"));
+        assert!(is_automated("no_reply.orders@example.com"));
+        assert!(is_automated("mailer-daemon@example.com"));
+        assert!(!is_automated("ann@example.com"));
+        assert!(!is_automated("replies@example.com"));
+    }
+}
@@ -0,0 +1,252 @@
+//! Deduplication of cross-posted items.
+//!
+//! The same story often arrives several times: from the publisher's RSS feed,
+//! as a Reddit link post, and on Hacker News. [`dedupe_items`] groups such
+//! items into one, keeping the earliest-published copy and recording every
+//! source on it so frontends can show source badges.
+//!
+//! Two items are considered the same when their URLs match after light
+//! normalization, or when their titles are identical after normalization and
+//! they come from different streams.
+
+use std::collections::HashMap;
+
+use scryforge_provider_core::url::url_key;
+use scryforge_provider_core::Item;
+
+/// Metadata key listing the provider IDs an item was seen on, comma-separated.
+pub const SOURCES_KEY: &str = "sources";
+
+/// Metadata key listing the IDs of items merged into this one, comma-separated.
+pub const DUPLICATE_IDS_KEY: &str = "duplicate_ids";
+
+/// Titles with fewer words than this are too generic to match on.
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Group cross-posted items, returning one item per group.
+///
+/// Groups keep the position of their first member. The representative is the
+/// earliest-published member; it is marked saved if any member is saved, and
+/// gains [`SOURCES_KEY`] and [`DUPLICATE_IDS_KEY`] metadata when the group
+/// has more than one member.
+pub fn dedupe_items(items: Vec<Item>) -> Vec<Item> {
+    let mut groups: Vec<Vec<Item>> = Vec::new();
+    let mut by_url: HashMap<String, usize> = HashMap::new();
+    let mut by_title: HashMap<String, usize> = HashMap::new();
+
+    for item in items {
+        let url_key = item.url.as_deref().and_then(url_key);
+        let title_key = title_key(&item.title);
+
+        let existing = url_key
+            .as_ref()
+            .and_then(|key| by_url.get(key))
+            .or_else(|| {
+                title_key
+                    .as_ref()
+                    .and_then(|key| by_title.get(key))
+                    .filter(|&&idx| groups[idx].iter().all(|g| g.stream_id != item.stream_id))
+            })
+            .copied();
+
+        let idx = match existing {
+            Some(idx) => idx,
+            None => {
+                groups.push(Vec::new());
+                groups.len() - 1
+            }
+        };
+
+        if let Some(key) = url_key {
+            by_url.entry(key).or_insert(idx);
+        }
+        if let Some(key) = title_key {
+            by_title.entry(key).or_insert(idx);
+        }
+        groups[idx].push(item);
+    }
+
+    groups.into_iter().map(merge_group).collect()
+}
+
+/// Collapse a group into its representative item.
+fn merge_group(mut group: Vec<Item>) -> Item {
+    if group.len() == 1 {
+        return group.pop().expect("group is non-empty");
+    }
+
+    // Earliest published first; undated items last
+    let primary_idx = group
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, item)| (item.published.is_none(), item.published))
+        .map(|(idx, _)| idx)
+        .unwrap_or(0);
+    let mut primary = group.remove(primary_idx);
+
+    let mut sources = vec![source_of(&primary)];
+    let mut duplicate_ids = Vec::new();
+    for item in &group {
+        let source = source_of(item);
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+        duplicate_ids.push(item.id.as_str().to_string());
+        primary.is_saved |= item.is_saved;
+    }
+
+    primary
+        .metadata
+        .insert(SOURCES_KEY.to_string(), sources.join(","));
+    primary
+        .metadata
+        .insert(DUPLICATE_IDS_KEY.to_string(), duplicate_ids.join(","));
+    primary
+}
+
+/// Provider an item came from, preferring the unified view's metadata.
+fn source_of(item: &Item) -> String {
+    item.metadata
+        .get("provider_id")
+        .cloned()
+        .unwrap_or_else(|| {
+            item.id
+                .as_str()
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+/// Comparison key for a title: lowercase words with punctuation removed.
+fn title_key(title: &str) -> Option<String> {
+    let words: Vec<String> = title
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    if words.len() < MIN_TITLE_WORDS {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use scryforge_provider_core::{ItemContent, ItemId, StreamId};
+
+    fn item(id: &str, stream: &str, title: &str, url: Option<&str>, hour: u32) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream.to_string()),
+            title: title.to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: Some(Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap()),
+            updated: None,
+            url: url.map(str::to_string),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_groups_by_normalized_url() {
+        let items = vec![
+            item(
+                "reddit:abc",
+                "reddit:sub:rust",
+                "Rust 1.80 is out",
+                Some("https://www.blog.rust-lang.org/2024/release/?utm_source=reddit"),
+                12,
+            ),
+            item(
+                "rss:1",
+                "rss:feed:rust-blog",
+                "Announcing Rust 1.80",
+                Some("http://blog.rust-lang.org/2024/release"),
+                9,
+            ),
+            item(
+                "rss:2",
+                "rss:feed:rust-blog",
+                "Other post",
+                Some("https://blog.rust-lang.org/other"),
+                8,
+            ),
+        ];
+
+        let deduped = dedupe_items(items);
+        assert_eq!(deduped.len(), 2);
+
+        // Earliest copy represents the group, at the group's first position
+        let merged = &deduped[0];
+        assert_eq!(merged.id.as_str(), "rss:1");
+        assert_eq!(merged.metadata.get(SOURCES_KEY).unwrap(), "rss,reddit");
+        assert_eq!(
+            merged.metadata.get(DUPLICATE_IDS_KEY).unwrap(),
+            "reddit:abc"
+        );
+        assert!(!deduped[1].metadata.contains_key(SOURCES_KEY));
+    }
+
+    #[test]
+    fn test_groups_by_title_across_streams_only() {
+        let mut saved = item(
+            "hn:1",
+            "hn:feed:front",
+            "Show HN: A tiny Rust database!",
+            Some("https://news.ycombinator.com/item?id=1"),
+            10,
+        );
+        saved.is_saved = true;
+
+        let items = vec![
+            item(
+                "rss:1",
+                "rss:feed:a",
+                "Show HN -- a tiny rust database",
+                None,
+                11,
+            ),
+            saved,
+        ];
+        let deduped = dedupe_items(items);
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].id.as_str(), "hn:1");
+        assert!(deduped[0].is_saved);
+
+        // Same title within one stream is a recurring post, not a cross-post
+        let items = vec![
+            item("rss:1", "rss:feed:a", "This week in Rust issue", None, 11),
+            item("rss:2", "rss:feed:a", "This week in Rust issue", None, 10),
+        ];
+        assert_eq!(dedupe_items(items).len(), 2);
+    }
+
+    #[test]
+    fn test_short_titles_do_not_match() {
+        let items = vec![
+            item("rss:1", "rss:feed:a", "Update", None, 11),
+            item("rss:2", "rss:feed:b", "Update", None, 10),
+        ];
+        assert_eq!(dedupe_items(items).len(), 2);
+    }
+}
@@ -0,0 +1,488 @@
+//! Daily and weekly digests.
+//!
+//! A digest lists the top unread items per provider from a time window in a
+//! single Markdown or HTML document. Items are picked from the unified feed,
+//! so the mute list applies, cross-posts are grouped, and "top" means the
+//! relevance order from [`crate::ranking`].
+//!
+//! [`spawn_scheduler`] writes a digest to disk every day or every Monday at
+//! the configured hour; `digest.generate` builds one on demand.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use scryforge_provider_core::Item;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::cache::Cache;
+use crate::config::{DigestConfig, DigestFormat, DigestSchedule, RankingConfig};
+use crate::markup::escape_html;
+use crate::mute::MuteFilter;
+use crate::unified::{FeedSortOrder, UnifiedFeedOptions, UnifiedFeedsView};
+
+/// Maximum characters of content included in an item's excerpt.
+const EXCERPT_CHARS: usize = 200;
+
+/// Top unread items from one provider.
+#[derive(Debug, Clone)]
+pub struct DigestSection {
+    pub provider_id: String,
+    /// Unread items from this provider in the window, including unlisted ones
+    pub unread_count: usize,
+    /// The highest-ranked unread items
+    pub items: Vec<Item>,
+}
+
+/// A compiled digest, ready to render.
+#[derive(Debug, Clone)]
+pub struct Digest {
+    /// Start of the covered window
+    pub since: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    /// Sections ordered by provider ID
+    pub sections: Vec<DigestSection>,
+}
+
+impl Digest {
+    /// Total unread items in the window across all providers.
+    pub fn unread_count(&self) -> usize {
+        self.sections.iter().map(|s| s.unread_count).sum()
+    }
+
+    /// Render the digest in the given format.
+    pub fn render(&self, format: DigestFormat) -> String {
+        match format {
+            DigestFormat::Markdown => render_markdown(self),
+            DigestFormat::Html => render_html(self),
+        }
+    }
+}
+
+/// Builds digests from the cache.
+pub struct DigestBuilder<C: Cache> {
+    cache: Arc<C>,
+    items_per_provider: usize,
+    ranking: RankingConfig,
+    mute: MuteFilter,
+}
+
+impl<C: Cache> DigestBuilder<C> {
+    pub fn new(cache: Arc<C>, items_per_provider: usize) -> Self {
+        Self {
+            cache,
+            items_per_provider,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+        }
+    }
+
+    /// Rank items with the given weights.
+    pub fn with_ranking_config(mut self, ranking: RankingConfig) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Leave out items matched by the mute list.
+    pub fn with_mute_filter(mut self, mute: MuteFilter) -> Self {
+        self.mute = mute;
+        self
+    }
+
+    /// Compile a digest of unread items published since `since`.
+    pub fn build(&self, since: DateTime<Utc>) -> Result<Digest> {
+        let view = UnifiedFeedsView::new(Arc::clone(&self.cache))
+            .with_ranking_config(self.ranking.clone())
+            .with_mute_filter(self.mute.clone());
+        let items = view
+            .get_all_items(UnifiedFeedOptions {
+                sort: FeedSortOrder::Relevance,
+                date_from: Some(since),
+                is_read: Some(false),
+                dedupe: true,
+                ..Default::default()
+            })
+            .map_err(|e| anyhow!("Failed to load unified feed: {}", e))?;
+
+        let mut sections: HashMap<String, DigestSection> = HashMap::new();
+        for item in items {
+            // Undated items cannot be placed in the window
+            if item.published.is_none() {
+                continue;
+            }
+            let provider_id = item
+                .metadata
+                .get("provider_id")
+                .cloned()
+                .unwrap_or_default();
+            let section = sections
+                .entry(provider_id.clone())
+                .or_insert_with(|| DigestSection {
+                    provider_id,
+                    unread_count: 0,
+                    items: Vec::new(),
+                });
+            section.unread_count += 1;
+            if section.items.len() < self.items_per_provider {
+                section.items.push(item);
+            }
+        }
+
+        let mut sections: Vec<DigestSection> = sections.into_values().collect();
+        sections.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+
+        Ok(Digest {
+            since,
+            generated_at: Utc::now(),
+            sections,
+        })
+    }
+}
+
+/// Length of the window a schedule covers.
+pub fn window(schedule: DigestSchedule) -> Duration {
+    match schedule {
+        DigestSchedule::Daily => Duration::days(1),
+        DigestSchedule::Weekly => Duration::days(7),
+    }
+}
+
+/// Next time after `now` that a digest is due.
+pub fn next_run(now: DateTime<Local>, schedule: DigestSchedule, hour: u32) -> DateTime<Local> {
+    let hour = hour.min(23);
+    let today = now.date_naive();
+    let first = match schedule {
+        DigestSchedule::Daily => today,
+        DigestSchedule::Weekly => {
+            let days_to_monday = (7 - today.weekday().num_days_from_monday()) % 7;
+            today + Duration::days(days_to_monday as i64)
+        }
+    };
+
+    let mut date = first;
+    loop {
+        if let Some(run) = local_at(date, hour).filter(|run| *run > now) {
+            return run;
+        }
+        date += window(schedule);
+    }
+}
+
+/// `hour` o'clock local time on `date`, if that time exists.
+fn local_at(date: NaiveDate, hour: u32) -> Option<DateTime<Local>> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+        .earliest()
+}
+
+/// Write a digest into `dir`, returning the file written.
+pub fn write_digest(digest: &Digest, format: DigestFormat, dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create digest directory: {}", dir.display()))?;
+
+    let extension = match format {
+        DigestFormat::Markdown => "md",
+        DigestFormat::Html => "html",
+    };
+    let path = dir.join(format!(
+        "digest-{}.{}",
+        digest.generated_at.with_timezone(&Local).format("%Y-%m-%d"),
+        extension
+    ));
+
+    fs::write(&path, digest.render(format))
+        .with_context(|| format!("Failed to write digest: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Generate digests on the configured schedule until the task is aborted.
+pub fn spawn_scheduler<C: Cache + 'static>(
+    builder: DigestBuilder<C>,
+    config: DigestConfig,
+    dir: PathBuf,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let now = Local::now();
+            let run_at = next_run(now, config.schedule, config.hour);
+            info!(
+                "Next digest scheduled for {}",
+                run_at.format("%Y-%m-%d %H:%M")
+            );
+
+            let wait = (run_at - now).to_std().unwrap_or_default();
+            tokio::time::sleep(wait).await;
+
+            let since = Utc::now() - window(config.schedule);
+            match builder
+                .build(since)
+                .and_then(|digest| write_digest(&digest, config.format, &dir))
+            {
+                Ok(path) => info!("Wrote digest to {}", path.display()),
+                Err(e) => error!("Failed to generate digest: {:#}", e),
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+fn render_markdown(digest: &Digest) -> String {
+    let mut doc = format!(
+        "# Scryforge digest, {}\n\n{} unread items since {}.\n",
+        local_date(digest.generated_at),
+        digest.unread_count(),
+        digest.since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+
+    for section in &digest.sections {
+        doc.push_str(&format!(
+            "\n## {} ({} unread)\n\n",
+            section.provider_id, section.unread_count
+        ));
+        for item in &section.items {
+            let title = item.title.replace(['[', ']'], "");
+            match item.url {
+                Some(ref url) => doc.push_str(&format!("- [{}]({})", title, url)),
+                None => doc.push_str(&format!("- {}", title)),
+            }
+            let byline = byline(item);
+            if !byline.is_empty() {
+                doc.push_str(&format!(" — {}", byline));
+            }
+            doc.push('\n');
+            if let Some(excerpt) = excerpt(item) {
+                doc.push_str(&format!("  > {}\n", excerpt));
+            }
+        }
+    }
+
+    doc
+}
+
+fn render_html(digest: &Digest) -> String {
+    let title = format!("Scryforge digest, {}", local_date(digest.generated_at));
+    let mut doc = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title></head><body>\
+<h1>{}</h1><p>{} unread items since {}.</p>",
+        title,
+        title,
+        digest.unread_count(),
+        digest.since.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    );
+
+    for section in &digest.sections {
+        doc.push_str(&format!(
+            "<h2>{} ({} unread)</h2><ul>",
+            escape_html(&section.provider_id),
+            section.unread_count
+        ));
+        for item in &section.items {
+            let title = escape_html(&item.title);
+            doc.push_str("<li>");
+            match item.url {
+                Some(ref url) if url.starts_with("https://") || url.starts_with("http://") => {
+                    doc.push_str(&format!("<a href=\"{}\">{}</a>", escape_html(url), title))
+                }
+                _ => doc.push_str(&title),
+            }
+            let byline = byline(item);
+            if !byline.is_empty() {
+                doc.push_str(&format!(" <small>{}</small>", escape_html(&byline)));
+            }
+            if let Some(excerpt) = excerpt(item) {
+                doc.push_str(&format!("<p>{}</p>", escape_html(&excerpt)));
+            }
+            doc.push_str("</li>");
+        }
+        doc.push_str("</ul>");
+    }
+
+    doc.push_str("</body></html>\n");
+    doc
+}
+
+fn local_date(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+/// Stream name and author, e.g. "r/rust · ferris".
+fn byline(item: &Item) -> String {
+    let parts: Vec<&str> = [
+        item.metadata.get("stream_name").map(String::as_str),
+        item.author.as_ref().map(|author| author.name.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|part| !part.is_empty())
+    .collect();
+    parts.join(" · ")
+}
+
+fn excerpt(item: &Item) -> Option<String> {
+    let text = item.content.plain_text()?;
+    if text.chars().count() > EXCERPT_CHARS {
+        let truncated: String = text.chars().take(EXCERPT_CHARS).collect();
+        Some(format!("{}…", truncated.trim_end()))
+    } else {
+        Some(text)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use chrono::{Timelike, Weekday};
+    use scryforge_provider_core::{ItemContent, ItemId, Stream, StreamId, StreamType};
+    use tempfile::TempDir;
+
+    fn create_test_cache() -> Arc<SqliteCache> {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SqliteCache::open_at(&temp_dir.path().join("test.db")).unwrap();
+        std::mem::forget(temp_dir);
+        Arc::new(cache)
+    }
+
+    fn stream(id: &str, provider_id: &str) -> Stream {
+        Stream {
+            id: StreamId(id.to_string()),
+            name: format!("{} stream", provider_id),
+            provider_id: provider_id.to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn item(id: &str, stream: &str, hours_old: i64, is_read: bool) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream.to_string()),
+            title: format!("Item <{}>", id),
+            content: ItemContent::Text("Body text".to_string()),
+            author: None,
+            published: Some(Utc::now() - Duration::hours(hours_old)),
+            updated: None,
+            url: Some(format!("https://example.com/{}", id)),
+            thumbnail_url: None,
+            is_read,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn seeded_cache() -> Arc<SqliteCache> {
+        let cache = create_test_cache();
+        cache
+            .upsert_streams(&[
+                stream("rss:feed:a", "rss"),
+                stream("reddit:feed:b", "reddit"),
+            ])
+            .unwrap();
+        cache
+            .upsert_items(&[
+                item("rss:1", "rss:feed:a", 1, false),
+                item("rss:2", "rss:feed:a", 2, false),
+                item("rss:3", "rss:feed:a", 3, false),
+                item("rss:old", "rss:feed:a", 48, false),
+                item("rss:read", "rss:feed:a", 1, true),
+                item("reddit:1", "reddit:feed:b", 5, false),
+            ])
+            .unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_build_groups_top_unread_by_provider() {
+        let digest = DigestBuilder::new(seeded_cache(), 2)
+            .build(Utc::now() - Duration::days(1))
+            .unwrap();
+
+        let providers: Vec<&str> = digest
+            .sections
+            .iter()
+            .map(|s| s.provider_id.as_str())
+            .collect();
+        assert_eq!(providers, vec!["reddit", "rss"]);
+
+        let rss = &digest.sections[1];
+        assert_eq!(rss.unread_count, 3);
+        let ids: Vec<&str> = rss.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["rss:1", "rss:2"]);
+        assert_eq!(digest.unread_count(), 4);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let digest = DigestBuilder::new(seeded_cache(), 1)
+            .build(Utc::now() - Duration::days(1))
+            .unwrap();
+
+        let markdown = digest.render(DigestFormat::Markdown);
+        assert!(markdown.starts_with("# Scryforge digest, "));
+        assert!(markdown.contains("4 unread items since"));
+        assert!(markdown.contains("## rss (3 unread)"));
+        assert!(markdown.contains("- [Item <rss:1>](https://example.com/rss:1) — rss stream\n"));
+        assert!(markdown.contains("  > Body text\n"));
+
+        let html = digest.render(DigestFormat::Html);
+        assert!(html.contains("<h2>rss (3 unread)</h2>"));
+        assert!(html.contains("<a href=\"https://example.com/rss:1\">Item &lt;rss:1&gt;</a>"));
+    }
+
+    #[test]
+    fn test_write_digest() {
+        let dir = TempDir::new().unwrap();
+        let digest = DigestBuilder::new(seeded_cache(), 5)
+            .build(Utc::now() - Duration::days(1))
+            .unwrap();
+
+        let path = write_digest(&digest, DigestFormat::Html, &dir.path().join("out")).unwrap();
+        assert_eq!(path.extension().unwrap(), "html");
+        assert!(fs::read_to_string(path).unwrap().contains("<h1>"));
+    }
+
+    #[test]
+    fn test_next_run() {
+        // Wednesday 2024-03-06
+        let now = Local.with_ymd_and_hms(2024, 3, 6, 9, 0, 0).unwrap();
+
+        let daily = next_run(now, DigestSchedule::Daily, 7);
+        assert_eq!(
+            daily.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 3, 7).unwrap()
+        );
+        assert_eq!(daily.hour(), 7);
+        let later_today = next_run(now, DigestSchedule::Daily, 18);
+        assert_eq!(later_today.date_naive(), now.date_naive());
+
+        let weekly = next_run(now, DigestSchedule::Weekly, 7);
+        assert_eq!(weekly.weekday(), Weekday::Mon);
+        assert_eq!(
+            weekly.date_naive(),
+            NaiveDate::from_ymd_opt(2024, 3, 11).unwrap()
+        );
+
+        // A Monday before the hour runs the same day
+        let monday = Local.with_ymd_and_hms(2024, 3, 11, 6, 0, 0).unwrap();
+        assert_eq!(
+            next_run(monday, DigestSchedule::Weekly, 7).date_naive(),
+            monday.date_naive()
+        );
+    }
+}
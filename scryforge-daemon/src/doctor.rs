@@ -0,0 +1,619 @@
+//! Environment diagnostics behind `scry doctor`.
+//!
+//! The checks cover what usually goes wrong on a new machine: a config file
+//! that does not parse or validate, a daemon address that cannot be bound, a
+//! Sigilforge socket that is missing or not ours, credentials that Sigilforge
+//! cannot read or that have expired, an encryption key that cannot be
+//! loaded, providers that fail their health check, and missing tools such
+//! as yt-dlp. Providers add their own probes through
+//! [`Provider::diagnose`].
+//!
+//! Every warning and failure carries a suggested fix, so the report doubles
+//! as a setup checklist.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::crypto::StoreCipher;
+use scryforge_provider_core::prelude::*;
+#[cfg(unix)]
+use scryforge_sigilforge_client::{SigilforgeClient, SigilforgeError};
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::config::Config;
+#[cfg(unix)]
+use crate::encryption;
+use crate::plugin::PluginManager;
+use crate::registry::ProviderRegistry;
+#[cfg(unix)]
+use crate::registry::YOUTUBE_ACCOUNT;
+
+/// How long a provider health check may take before it counts as failed.
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long to wait for the daemon to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Run every check against the default config path and Sigilforge socket.
+pub async fn run() -> Vec<DiagnosticCheck> {
+    let mut checks = Vec::new();
+
+    let config = match Config::default_config_path() {
+        Ok(path) => {
+            let (check, config) = check_config(&path);
+            checks.push(check);
+            if let (Some(config), Some(check)) = (&config, check_config_permissions(&path)) {
+                if !config.daemon.clients.is_empty() {
+                    checks.push(check);
+                }
+            }
+            config.unwrap_or_default()
+        }
+        Err(e) => {
+            checks.push(DiagnosticCheck::failed(
+                "config",
+                format!("Cannot locate the config directory: {:#}", e),
+                "Set HOME or XDG_CONFIG_HOME",
+            ));
+            Config::default()
+        }
+    };
+
+    checks.push(check_api(&config).await);
+    checks.extend(check_tls(&config));
+
+    // Sigilforge is reached over a Unix socket
+    #[cfg(unix)]
+    let cipher = {
+        let sigilforge = SigilforgeClient::with_default_path();
+        let sigilforge_check = check_sigilforge(&sigilforge).await;
+        let sigilforge_ok = sigilforge_check.status == CheckStatus::Ok;
+        checks.push(sigilforge_check);
+        if sigilforge_ok {
+            checks.push(check_token(&sigilforge, "youtube", YOUTUBE_ACCOUNT).await);
+        }
+
+        let (encryption_check, cipher) = check_encryption(&config, &sigilforge).await;
+        checks.extend(encryption_check);
+        cipher
+    };
+    #[cfg(not(unix))]
+    let cipher: Option<StoreCipher> = None;
+
+    let token_fetcher = crate::registry::default_token_fetcher();
+    let mut registry =
+        ProviderRegistry::with_builtin_providers(&config, token_fetcher, cipher.as_ref());
+    let mut plugin_manager = PluginManager::new();
+    if plugin_manager.discover_and_load().is_ok() {
+        plugin_manager.register_providers(&mut registry);
+    }
+    checks.extend(check_providers(&registry).await);
+
+    checks
+}
+
+/// Whether the config file parses and validates.
+///
+/// Returns the config for the remaining checks, or `None` if it is unusable.
+pub fn check_config(path: &Path) -> (DiagnosticCheck, Option<Config>) {
+    if !path.exists() {
+        return (
+            DiagnosticCheck::warning(
+                "config",
+                format!("No config file at {}; defaults are used", path.display()),
+                "Start scryforge-daemon once to write a commented default config",
+            ),
+            Some(Config::default()),
+        );
+    }
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return (
+                DiagnosticCheck::failed(
+                    "config",
+                    format!("Cannot read {}: {}", path.display(), e),
+                    format!("Check the ownership and permissions of {}", path.display()),
+                ),
+                None,
+            )
+        }
+    };
+
+    let config: Config = match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            return (
+                DiagnosticCheck::failed(
+                    "config",
+                    format!("{} is not valid: {}", path.display(), e.message()),
+                    "Fix the reported key, or move the file aside to regenerate the defaults",
+                ),
+                None,
+            )
+        }
+    };
+
+    match config.validate() {
+        Ok(()) => (
+            DiagnosticCheck::ok("config", format!("{} is valid", path.display())),
+            Some(config),
+        ),
+        Err(e) => (
+            DiagnosticCheck::failed(
+                "config",
+                format!("{:#}", e),
+                "See \"Validation Rules\" in docs/CONFIGURATION.md",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Warn when a config file holding API tokens is readable by other users.
+#[cfg(unix)]
+pub fn check_config_permissions(path: &Path) -> Option<DiagnosticCheck> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path).ok()?.permissions().mode();
+    Some(if mode & 0o077 != 0 {
+        DiagnosticCheck::warning(
+            "config permissions",
+            format!(
+                "{} holds client tokens but has mode {:o}",
+                path.display(),
+                mode & 0o777
+            ),
+            format!("chmod 600 {}", path.display()),
+        )
+    } else {
+        DiagnosticCheck::ok(
+            "config permissions",
+            "Client tokens are readable only by you",
+        )
+    })
+}
+
+#[cfg(not(unix))]
+pub fn check_config_permissions(_path: &Path) -> Option<DiagnosticCheck> {
+    None
+}
+
+/// Whether the daemon is listening, or could listen, on its bind address.
+pub async fn check_api(config: &Config) -> DiagnosticCheck {
+    let address: SocketAddr = match config.daemon.bind_address.parse() {
+        Ok(address) => address,
+        Err(_) => {
+            return DiagnosticCheck::failed(
+                "api",
+                format!("Invalid bind_address {}", config.daemon.bind_address),
+                "Set daemon.bind_address to an address like 127.0.0.1:3030",
+            )
+        }
+    };
+
+    if let Ok(Ok(_)) = timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(address)).await {
+        return DiagnosticCheck::ok("api", format!("Daemon is listening on {}", address));
+    }
+
+    match TcpListener::bind(address) {
+        Ok(_) => DiagnosticCheck::warning(
+            "api",
+            format!("Daemon is not running; {} is free", address),
+            "Start scryforge-daemon",
+        ),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => DiagnosticCheck::failed(
+            "api",
+            format!("Not allowed to listen on {}", address),
+            "Use a port above 1024 in daemon.bind_address",
+        ),
+        Err(e) if e.kind() == ErrorKind::AddrInUse => DiagnosticCheck::failed(
+            "api",
+            format!("{} is in use but not answering", address),
+            "Stop the program holding the port, or change daemon.bind_address",
+        ),
+        Err(e) => DiagnosticCheck::failed(
+            "api",
+            format!("Cannot listen on {}: {}", address, e),
+            "Change daemon.bind_address",
+        ),
+    }
+}
+
+/// Whether the configured TLS certificate and key are readable.
+pub fn check_tls(config: &Config) -> Option<DiagnosticCheck> {
+    let tls = config.daemon.tls.as_ref()?;
+
+    for path in [&tls.cert_path, &tls.key_path] {
+        if let Err(e) = fs::File::open(path) {
+            return Some(DiagnosticCheck::failed(
+                "tls",
+                format!("Cannot read {}: {}", path.display(), e),
+                "Fix daemon.tls.cert_path and key_path, or their permissions",
+            ));
+        }
+    }
+    Some(DiagnosticCheck::ok(
+        "tls",
+        "Certificate and key are readable",
+    ))
+}
+
+/// Whether the Sigilforge socket exists and accepts our connections.
+#[cfg(unix)]
+pub async fn check_sigilforge(client: &SigilforgeClient) -> DiagnosticCheck {
+    let socket = client.socket_path();
+    if !client.is_available() {
+        return DiagnosticCheck::warning(
+            "sigilforge",
+            format!(
+                "No socket at {}; OAuth providers use mock tokens",
+                socket.display()
+            ),
+            "Start the sigilforge daemon to use YouTube and other OAuth providers",
+        );
+    }
+
+    match tokio::net::UnixStream::connect(socket).await {
+        Ok(_) => DiagnosticCheck::ok("sigilforge", format!("Connected to {}", socket.display())),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => DiagnosticCheck::failed(
+            "sigilforge",
+            format!("Permission denied opening {}", socket.display()),
+            "Run scry as the user that owns the socket, or fix its permissions",
+        ),
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => DiagnosticCheck::failed(
+            "sigilforge",
+            format!("{} exists but nothing is listening", socket.display()),
+            "Restart the sigilforge daemon",
+        ),
+        Err(e) => DiagnosticCheck::failed(
+            "sigilforge",
+            format!("Cannot connect to {}: {}", socket.display(), e),
+            "Restart the sigilforge daemon",
+        ),
+    }
+}
+
+/// Whether the encryption key for `encrypt_at_rest` providers can be loaded.
+///
+/// Returns no check when no provider encrypts its data, and the key when
+/// it loads so encrypted stores can be opened for the provider checks.
+#[cfg(unix)]
+pub async fn check_encryption(
+    config: &Config,
+    client: &SigilforgeClient,
+) -> (Option<DiagnosticCheck>, Option<StoreCipher>) {
+    let providers = encryption::encrypted_providers(config);
+    if providers.is_empty() {
+        return (None, None);
+    }
+
+    match encryption::load_key(config, client).await {
+        Ok(cipher) => (
+            Some(DiagnosticCheck::ok(
+                "encryption",
+                format!("Key loaded for {}", providers.join(", ")),
+            )),
+            Some(cipher),
+        ),
+        Err(e) => (
+            Some(DiagnosticCheck::failed(
+                "encryption",
+                format!("{:#}", e),
+                format!(
+                    "Create a key with `scry keygen` and store it in Sigilforge as {}, or set {}",
+                    config.encryption.key,
+                    encryption::KEY_ENV
+                ),
+            )),
+            None,
+        ),
+    }
+}
+
+/// Whether Sigilforge can read a token from its keyring, and whether the
+/// token is still valid.
+#[cfg(unix)]
+pub async fn check_token(
+    client: &SigilforgeClient,
+    service: &str,
+    account: &str,
+) -> DiagnosticCheck {
+    let name = format!("token {}/{}", service, account);
+    let add_account = format!(
+        "Add the account with `sigilforge add-account google {}` (see docs/YOUTUBE_SETUP.md)",
+        account
+    );
+
+    match client.get_token_info(service, account).await {
+        Ok(info) => token_expiry_check(name, info.expires_at.as_deref(), Utc::now(), account),
+        Err(SigilforgeError::TokenNotFound { .. }) => {
+            DiagnosticCheck::failed(name, "Sigilforge has no such account", add_account)
+        }
+        Err(SigilforgeError::Rpc(message)) => DiagnosticCheck::failed(
+            name,
+            format!("Sigilforge could not read the credential: {}", message),
+            "Unlock your system keyring, then check `sigilforge list-accounts`",
+        ),
+        Err(e) => DiagnosticCheck::failed(name, e.to_string(), "Restart the sigilforge daemon"),
+    }
+}
+
+/// Classify a token by its reported expiry.
+///
+/// Sigilforge refreshes tokens on request, so an expired token means the
+/// refresh failed and the account must be authorized again.
+pub fn token_expiry_check(
+    name: String,
+    expires_at: Option<&str>,
+    now: DateTime<Utc>,
+    account: &str,
+) -> DiagnosticCheck {
+    let Some(expires_at) = expires_at else {
+        return DiagnosticCheck::ok(name, "Token available (no expiry reported)");
+    };
+    let Ok(expiry) = DateTime::parse_from_rfc3339(expires_at) else {
+        return DiagnosticCheck::ok(
+            name,
+            format!("Token available (unrecognized expiry {:?})", expires_at),
+        );
+    };
+
+    let remaining = expiry.with_timezone(&Utc) - now;
+    if remaining <= chrono::Duration::zero() {
+        DiagnosticCheck::failed(
+            name,
+            format!("Token expired at {}", expiry.to_rfc3339()),
+            format!(
+                "Re-authorize with `sigilforge add-account google {}`",
+                account
+            ),
+        )
+    } else {
+        DiagnosticCheck::ok(
+            name,
+            format!("Token valid for {} more minutes", remaining.num_minutes()),
+        )
+    }
+}
+
+/// Health check and deeper probes for every registered provider.
+pub async fn check_providers(registry: &ProviderRegistry) -> Vec<DiagnosticCheck> {
+    let mut ids = registry.list();
+    ids.sort();
+
+    let mut checks = Vec::new();
+    for id in ids {
+        let Some(provider) = registry.get(id) else {
+            continue;
+        };
+
+        let health = timeout(
+            PROVIDER_TIMEOUT,
+            registry.guard(id, provider.health_check()),
+        )
+        .await;
+        checks.push(match health {
+            Ok(Ok(health)) if health.is_healthy => {
+                DiagnosticCheck::ok(id, health.message.unwrap_or_else(|| "Healthy".to_string()))
+            }
+            Ok(Ok(health)) => DiagnosticCheck::failed(
+                id,
+                health
+                    .message
+                    .unwrap_or_else(|| "Reported unhealthy".to_string()),
+                format!("Check the settings under [providers.{}]", id),
+            ),
+            Ok(Err(e)) => DiagnosticCheck::failed(
+                id,
+                e.to_string(),
+                format!("Check the settings under [providers.{}]", id),
+            ),
+            Err(_) => DiagnosticCheck::failed(
+                id,
+                format!("No response within {} seconds", PROVIDER_TIMEOUT.as_secs()),
+                "Check your network connection to the provider's server",
+            ),
+        });
+
+        let probes = registry
+            .guard(id, async { Ok(provider.diagnose().await) })
+            .await
+            .unwrap_or_else(|e| {
+                vec![DiagnosticCheck::failed(
+                    "diagnose",
+                    e.to_string(),
+                    "Report this as a bug in the provider",
+                )]
+            });
+        checks.extend(probes.into_iter().map(|mut check| {
+            check.name = format!("{}/{}", id, check.name);
+            check
+        }));
+    }
+
+    checks
+}
+
+/// Human-readable report, one check per line with fixes indented below.
+pub fn render(checks: &[DiagnosticCheck]) -> String {
+    let mut out = String::new();
+    for check in checks {
+        let label = match check.status {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warn",
+            CheckStatus::Failed => "FAIL",
+        };
+        out.push_str(&format!(
+            "[{:<4}] {}: {}\n",
+            label, check.name, check.detail
+        ));
+        if let Some(ref fix) = check.fix {
+            out.push_str(&format!("       fix: {}\n", fix));
+        }
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    out.push_str(&format!(
+        "\n{} ok, {} warning(s), {} failure(s)\n",
+        count(CheckStatus::Ok),
+        count(CheckStatus::Warning),
+        count(CheckStatus::Failed)
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_config_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let (check, config) = check_config(&temp_dir.path().join("config.toml"));
+
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert!(check.fix.unwrap().contains("scryforge-daemon"));
+        assert_eq!(config, Some(Config::default()));
+    }
+
+    #[test]
+    fn test_config_syntax_and_validation_errors_fail() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+
+        fs::write(&path, "[daemon\nbind_address = 1").unwrap();
+        let (check, config) = check_config(&path);
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(config.is_none());
+
+        let content = Config::default_config_content()
+            .replace("log_level = \"info\"", "log_level = \"loud\"");
+        fs::write(&path, content).unwrap();
+        let (check, _) = check_config(&path);
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(check.detail.contains("log_level"));
+        assert!(check.fix.unwrap().contains("CONFIGURATION.md"));
+    }
+
+    #[test]
+    fn test_valid_config_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, Config::default_config_content()).unwrap();
+
+        let (check, config) = check_config(&path);
+        assert_eq!(check.status, CheckStatus::Ok, "{}", check.detail);
+        assert!(config.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_world_readable_config_warns() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "").unwrap();
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let check = check_config_permissions(&path).unwrap();
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert!(check.fix.unwrap().starts_with("chmod 600"));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        let check = check_config_permissions(&path).unwrap();
+        assert_eq!(check.status, CheckStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_api_reports_listening_daemon() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut config = Config::default();
+        config.daemon.bind_address = listener.local_addr().unwrap().to_string();
+
+        let check = check_api(&config).await;
+        assert_eq!(check.status, CheckStatus::Ok, "{}", check.detail);
+
+        drop(listener);
+        let check = check_api(&config).await;
+        assert_eq!(check.status, CheckStatus::Warning);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_missing_sigilforge_socket_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let client = SigilforgeClient::new(temp_dir.path().join("sigilforge.sock"));
+
+        let check = check_sigilforge(&client).await;
+        assert_eq!(check.status, CheckStatus::Warning);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_encryption_key_checked_only_when_needed() {
+        let temp_dir = TempDir::new().unwrap();
+        let client = SigilforgeClient::new(temp_dir.path().join("sigilforge.sock"));
+        let mut config = Config::default();
+
+        let (check, cipher) = check_encryption(&config, &client).await;
+        assert!(check.is_none() && cipher.is_none());
+
+        config.providers.insert(
+            "email".to_string(),
+            crate::config::ProviderConfig {
+                encrypt_at_rest: true,
+                ..Default::default()
+            },
+        );
+        let (check, cipher) = check_encryption(&config, &client).await;
+        let check = check.unwrap();
+        assert_eq!(check.status, CheckStatus::Failed);
+        assert!(check.fix.unwrap().contains("scry keygen"));
+        assert!(cipher.is_none());
+    }
+
+    #[test]
+    fn test_token_expiry() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let check = |expiry| token_expiry_check("token".into(), expiry, now, "personal");
+
+        assert_eq!(check(None).status, CheckStatus::Ok);
+        assert_eq!(check(Some("2025-06-01T13:00:00Z")).status, CheckStatus::Ok);
+        assert_eq!(check(Some("soon")).status, CheckStatus::Ok);
+
+        let expired = check(Some("2025-06-01T11:00:00Z"));
+        assert_eq!(expired.status, CheckStatus::Failed);
+        assert!(expired.fix.unwrap().contains("add-account google personal"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_probes_are_namespaced() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider_dummy::DummyProvider::new());
+
+        let checks = check_providers(&registry).await;
+        assert_eq!(checks[0].name, "dummy");
+        assert_eq!(checks[0].status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_render_lists_fixes_and_totals() {
+        let report = render(&[
+            DiagnosticCheck::ok("config", "valid"),
+            DiagnosticCheck::warning("yt-dlp", "missing", "pip install yt-dlp"),
+        ]);
+
+        assert!(report.contains("[ok  ] config: valid"));
+        assert!(report.contains("[warn] yt-dlp: missing\n       fix: pip install yt-dlp"));
+        assert!(report.contains("1 ok, 1 warning(s), 0 failure(s)"));
+    }
+}
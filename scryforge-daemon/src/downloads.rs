@@ -0,0 +1,1150 @@
+//! Downloading podcasts, videos, documents, and attachments to disk.
+//!
+//! `items.download` queues whatever an item has worth keeping: a podcast
+//! episode's enclosure, a video (fetched with yt-dlp), a linked PDF or image,
+//! or the attachments of an email. The [`DownloadManager`] runs at most
+//! `max_concurrent` of them at once; the rest wait their turn.
+//!
+//! Files are written next to their destination with a `.part` suffix and
+//! renamed once complete. A download that fails, is cancelled, or is cut off
+//! by a daemon restart leaves that file behind, and downloading the same file
+//! again resumes from it, with an HTTP range request or yt-dlp's
+//! `--continue`. While they run, downloads publish
+//! [`DaemonEvent::DownloadProgress`] events at most once a second.
+//!
+//! Downloads of a provider with a proxy go through it, yt-dlp's included.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use reqwest::StatusCode;
+use scryforge_provider_core::http::{check_reachable, ProxyConfig};
+use scryforge_provider_core::{Attachment, Item};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{oneshot, Semaphore};
+use tracing::{debug, warn};
+
+use crate::config::DownloadsConfig;
+use crate::events::{DaemonEvent, EventBus};
+use crate::opener::{ContentKind, Opener};
+use crate::player::MEDIA_URL_KEY;
+use crate::registry::ProviderRegistry;
+
+/// Minimum time between progress events of one download.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Finished downloads kept in the list; older ones are dropped first.
+const MAX_FINISHED: usize = 100;
+
+/// Longest file name the manager writes, in characters.
+const MAX_FILE_NAME: usize = 150;
+
+/// Prefixes marking the lines yt-dlp prints for the manager.
+const YTDL_PROGRESS: &str = "scryforge-progress";
+const YTDL_FILE: &str = "scryforge-file";
+
+/// Where a download comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DownloadSource {
+    /// A file fetched over HTTP
+    Http { url: String },
+    /// A video or audio page fetched with yt-dlp
+    Ytdlp { url: String },
+    /// A file attached to the item, fetched from its provider
+    Attachment { attachment_id: String },
+}
+
+/// Where a download is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    Queued,
+    Downloading,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl DownloadState {
+    /// Whether the download is over, one way or another.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// A queued, running, or finished download.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Download {
+    pub id: u64,
+    pub item_id: String,
+    /// Name of the file written; for yt-dlp, the item title until the file
+    /// is known
+    pub file_name: String,
+    pub source: DownloadSource,
+    pub state: DownloadState,
+    /// Where the file was saved, once completed
+    pub path: Option<String>,
+    /// Bytes written so far, including any resumed from an earlier attempt
+    pub bytes: u64,
+    pub total_bytes: Option<u64>,
+    /// Why the download failed
+    pub error: Option<String>,
+}
+
+/// How to fetch a download, kept so it can be retried.
+#[derive(Clone)]
+enum Job {
+    Http {
+        url: String,
+        dest: PathBuf,
+        client: reqwest::Client,
+        proxy: Option<ProxyConfig>,
+    },
+    Ytdlp {
+        url: String,
+        dir: PathBuf,
+        proxy: Option<ProxyConfig>,
+    },
+    Attachment {
+        registry: Arc<ProviderRegistry>,
+        item: Box<Item>,
+        attachment_id: String,
+        dest: PathBuf,
+    },
+}
+
+impl Job {
+    /// The file this job writes, if known before it runs.
+    fn dest(&self) -> Option<&Path> {
+        match self {
+            Job::Http { dest, .. } | Job::Attachment { dest, .. } => Some(dest),
+            Job::Ytdlp { .. } => None,
+        }
+    }
+}
+
+struct Entry {
+    download: Download,
+    job: Job,
+    /// Incremented on retry, so a cancelled attempt that is still winding
+    /// down can't overwrite the state of the new one
+    attempt: u64,
+    cancel: Option<oneshot::Sender<()>>,
+}
+
+/// Downloads items to disk on background tasks, a few at a time.
+///
+/// Dropping the manager cancels every download.
+pub struct DownloadManager {
+    config: DownloadsConfig,
+    dir: PathBuf,
+    client: reqwest::Client,
+    /// Clients and proxies of the providers with a proxy, by provider ID
+    proxied: HashMap<String, (reqwest::Client, ProxyConfig)>,
+    permits: Arc<Semaphore>,
+    entries: Arc<Mutex<Vec<Entry>>>,
+    next_id: AtomicU64,
+}
+
+impl DownloadManager {
+    /// A manager saving to `dir`, usually [`Config::download_dir`].
+    ///
+    /// [`Config::download_dir`]: crate::config::Config::download_dir
+    pub fn from_config(config: &DownloadsConfig, dir: PathBuf) -> Self {
+        Self {
+            config: config.clone(),
+            dir,
+            client: reqwest::Client::new(),
+            proxied: HashMap::new(),
+            permits: Arc::new(Semaphore::new(config.max_concurrent)),
+            entries: Arc::default(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Download the links of each provider in `proxies` through its proxy.
+    pub fn with_proxies(mut self, proxies: &HashMap<String, ProxyConfig>) -> Result<Self> {
+        for (provider_id, proxy) in proxies {
+            let client = reqwest::Client::builder()
+                .proxy(reqwest::Proxy::all(proxy.url())?)
+                .build()?;
+            self.proxied
+                .insert(provider_id.clone(), (client, proxy.clone()));
+        }
+        Ok(self)
+    }
+
+    /// What `item` links to that can be downloaded: its enclosure, or its
+    /// own link if that is a video, audio, PDF, or image. Attachments are
+    /// listed by the item's provider instead.
+    pub fn source(item: &Item) -> Option<DownloadSource> {
+        if let Some(url) = item.metadata.get(MEDIA_URL_KEY) {
+            return Some(DownloadSource::Http { url: url.clone() });
+        }
+        let url = Opener::link(item)?.to_string();
+        match ContentKind::of(item, &url) {
+            ContentKind::Video | ContentKind::Audio => Some(DownloadSource::Ytdlp { url }),
+            ContentKind::Pdf | ContentKind::Image => Some(DownloadSource::Http { url }),
+            ContentKind::Article | ContentKind::Other => None,
+        }
+    }
+
+    /// Queue the download of `item`'s [`source`](Self::source), or return
+    /// `None` if it has nothing to download.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn queue_link(&self, item: &Item, events: Option<EventBus>) -> Option<Download> {
+        let source = Self::source(item)?;
+        let dir = self.item_dir(item);
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        let (client, proxy) = match self.proxied.get(provider_id) {
+            Some((client, proxy)) => (client.clone(), Some(proxy.clone())),
+            None => (self.client.clone(), None),
+        };
+        let (file_name, job) = match &source {
+            DownloadSource::Http { url } => {
+                let file_name = file_name_for(url, &item.title);
+                let dest = dir.join(&file_name);
+                (
+                    file_name,
+                    Job::Http {
+                        url: url.clone(),
+                        dest,
+                        client,
+                        proxy,
+                    },
+                )
+            }
+            DownloadSource::Ytdlp { url } => (
+                item.title.clone(),
+                Job::Ytdlp {
+                    url: url.clone(),
+                    dir,
+                    proxy,
+                },
+            ),
+            DownloadSource::Attachment { .. } => unreachable!("links are never attachments"),
+        };
+        Some(self.queue(item, source, file_name, job, events))
+    }
+
+    /// Queue the download of one of `item`'s attachments, fetched from its
+    /// provider in `registry`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn queue_attachment(
+        &self,
+        item: &Item,
+        attachment: &Attachment,
+        registry: Arc<ProviderRegistry>,
+        events: Option<EventBus>,
+    ) -> Download {
+        let file_name = sanitize_file_name(&attachment.file_name);
+        let job = Job::Attachment {
+            registry,
+            item: Box::new(item.clone()),
+            attachment_id: attachment.id.clone(),
+            dest: self.item_dir(item).join(&file_name),
+        };
+        let source = DownloadSource::Attachment {
+            attachment_id: attachment.id.clone(),
+        };
+        self.queue(item, source, file_name, job, events)
+    }
+
+    /// Every download, oldest first.
+    pub fn list(&self) -> Vec<Download> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().map(|e| e.download.clone()).collect()
+    }
+
+    /// Cancel a queued or running download, keeping what it has written so
+    /// far. Returns whether there was one to cancel.
+    pub fn cancel(&self, id: u64) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        let Some(entry) = entries.iter_mut().find(|e| e.download.id == id) else {
+            return false;
+        };
+        match entry.cancel.take() {
+            Some(cancel) if !entry.download.state.is_finished() => {
+                let _ = cancel.send(());
+                entry.download.state = DownloadState::Cancelled;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue a failed or cancelled download again, resuming from what it
+    /// wrote. Downloads that are still going are returned unchanged; `None`
+    /// means there is no download `id`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn retry(&self, id: u64, events: Option<EventBus>) -> Option<Download> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.iter_mut().find(|e| e.download.id == id)?;
+        if matches!(
+            entry.download.state,
+            DownloadState::Failed | DownloadState::Cancelled
+        ) {
+            entry.download.state = DownloadState::Queued;
+            entry.download.error = None;
+            entry.attempt += 1;
+            entry.cancel = Some(self.spawn(id, entry.attempt, entry.job.clone(), events));
+        }
+        Some(entry.download.clone())
+    }
+
+    /// Remove finished downloads from the list, returning how many there were.
+    /// Downloaded files are kept.
+    pub fn clear_finished(&self) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|e| !e.download.state.is_finished());
+        before - entries.len()
+    }
+
+    fn item_dir(&self, item: &Item) -> PathBuf {
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        if self.config.per_provider && !provider_id.is_empty() {
+            self.dir.join(sanitize_file_name(provider_id))
+        } else {
+            self.dir.clone()
+        }
+    }
+
+    fn queue(
+        &self,
+        item: &Item,
+        source: DownloadSource,
+        file_name: String,
+        job: Job,
+        events: Option<EventBus>,
+    ) -> Download {
+        let mut entries = self.entries.lock().unwrap();
+
+        // Downloading the same thing twice at once would write one file twice
+        if let Some(entry) = entries.iter().find(|e| {
+            !e.download.state.is_finished()
+                && e.download.item_id == item.id.0
+                && e.download.source == source
+        }) {
+            return entry.download.clone();
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut download = Download {
+            id,
+            item_id: item.id.0.clone(),
+            file_name,
+            source,
+            state: DownloadState::Queued,
+            path: None,
+            bytes: 0,
+            total_bytes: None,
+            error: None,
+        };
+
+        let existing = job
+            .dest()
+            .filter(|dest| dest.is_file())
+            .map(Path::to_path_buf);
+        let cancel = match existing {
+            Some(dest) => {
+                debug!("{} is already downloaded", dest.display());
+                let len = std::fs::metadata(&dest).map(|m| m.len()).ok();
+                download.state = DownloadState::Completed;
+                download.path = Some(dest.display().to_string());
+                download.bytes = len.unwrap_or_default();
+                download.total_bytes = len;
+                None
+            }
+            None => Some(self.spawn(id, 0, job.clone(), events)),
+        };
+
+        entries.push(Entry {
+            download: download.clone(),
+            job,
+            attempt: 0,
+            cancel,
+        });
+        prune(&mut entries);
+        download
+    }
+
+    /// Run `job` on a background task once a permit is free, returning the
+    /// sender that cancels it.
+    fn spawn(
+        &self,
+        id: u64,
+        attempt: u64,
+        job: Job,
+        events: Option<EventBus>,
+    ) -> oneshot::Sender<()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let worker = Worker {
+            id,
+            attempt,
+            entries: Arc::clone(&self.entries),
+            events,
+            reported: Mutex::new(None),
+        };
+        let permits = Arc::clone(&self.permits);
+        let config = self.config.clone();
+
+        tokio::spawn(async move {
+            let download = async {
+                let _permit = permits.acquire_owned().await?;
+                worker.start();
+                match &job {
+                    Job::Http {
+                        url,
+                        dest,
+                        client,
+                        proxy,
+                    } => fetch_http(client, proxy.as_ref(), url, dest, &worker).await,
+                    Job::Ytdlp { url, dir, proxy } => {
+                        fetch_ytdl(&config, proxy.as_ref(), url, dir, &worker).await
+                    }
+                    Job::Attachment {
+                        registry,
+                        item,
+                        attachment_id,
+                        dest,
+                    } => fetch_attachment(registry, item, attachment_id, dest, &worker).await,
+                }
+            };
+            // Also resolves if the manager is gone
+            let result = tokio::select! {
+                result = download => Some(result),
+                _ = cancel_rx => None,
+            };
+            worker.finish(result);
+        });
+        cancel_tx
+    }
+}
+
+impl Drop for DownloadManager {
+    fn drop(&mut self) {
+        for entry in self.entries.lock().unwrap().iter_mut() {
+            if let Some(cancel) = entry.cancel.take() {
+                let _ = cancel.send(());
+            }
+        }
+    }
+}
+
+/// Drop the oldest finished downloads beyond [`MAX_FINISHED`].
+fn prune(entries: &mut Vec<Entry>) {
+    let mut excess = entries
+        .iter()
+        .filter(|e| e.download.state.is_finished())
+        .count()
+        .saturating_sub(MAX_FINISHED);
+    entries.retain(|e| {
+        if excess > 0 && e.download.state.is_finished() {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+/// Reports on one download from its background task.
+struct Worker {
+    id: u64,
+    attempt: u64,
+    entries: Arc<Mutex<Vec<Entry>>>,
+    events: Option<EventBus>,
+    /// When progress was last published
+    reported: Mutex<Option<Instant>>,
+}
+
+impl Worker {
+    fn update(&self, f: impl FnOnce(&mut Download)) -> Option<Download> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.download.id == self.id && e.attempt == self.attempt)?;
+        f(&mut entry.download);
+        Some(entry.download.clone())
+    }
+
+    fn start(&self) {
+        self.update(|d| d.state = DownloadState::Downloading);
+    }
+
+    fn progress(&self, bytes: u64, total_bytes: Option<u64>) {
+        let Some(download) = self.update(|d| {
+            d.bytes = bytes;
+            d.total_bytes = total_bytes;
+        }) else {
+            return;
+        };
+
+        let mut reported = self.reported.lock().unwrap();
+        if reported.is_some_and(|at| at.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+        *reported = Some(Instant::now());
+        if let Some(events) = &self.events {
+            events.publish(DaemonEvent::DownloadProgress {
+                download_id: download.id,
+                item_id: download.item_id,
+                bytes,
+                total_bytes,
+                timestamp: Utc::now(),
+            });
+        }
+    }
+
+    /// Record the outcome: a path, an error, or `None` if cancelled.
+    fn finish(&self, result: Option<Result<PathBuf>>) {
+        let download = self.update(|d| match result {
+            Some(Ok(path)) => {
+                d.state = DownloadState::Completed;
+                if let Some(name) = path.file_name() {
+                    d.file_name = name.to_string_lossy().into_owned();
+                }
+                d.path = Some(path.display().to_string());
+                d.total_bytes = d.total_bytes.or(Some(d.bytes));
+            }
+            Some(Err(e)) => {
+                warn!("Download of {} failed: {:#}", d.item_id, e);
+                d.state = DownloadState::Failed;
+                d.error = Some(format!("{:#}", e));
+            }
+            None => d.state = DownloadState::Cancelled,
+        });
+
+        if let (Some(download), Some(events)) = (download, &self.events) {
+            events.publish(DaemonEvent::DownloadFinished {
+                download_id: download.id,
+                item_id: download.item_id,
+                state: download.state,
+                path: download.path,
+                error: download.error,
+                timestamp: Utc::now(),
+            });
+        }
+    }
+}
+
+/// The `.part` file `dest` is written to until it is complete.
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Fetch `url` to `dest` with `client`, which goes through `proxy`,
+/// resuming a partial download.
+async fn fetch_http(
+    client: &reqwest::Client,
+    proxy: Option<&ProxyConfig>,
+    url: &str,
+    dest: &Path,
+    worker: &Worker,
+) -> Result<PathBuf> {
+    check_reachable(url, proxy)?;
+    if let Some(dir) = dest.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let part = part_path(dest);
+    let offset = tokio::fs::metadata(&part)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+
+    // The part file already holds everything
+    if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        tokio::fs::rename(&part, dest).await?;
+        worker.progress(offset, Some(offset));
+        return Ok(dest.to_path_buf());
+    }
+    let mut response = response
+        .error_for_status()
+        .with_context(|| format!("Failed to fetch {}", url))?;
+
+    // Servers that ignore the range send the whole file again
+    let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    let mut bytes = if resumed { offset } else { 0 };
+    let total_bytes = response.content_length().map(|len| len + bytes);
+    if resumed {
+        debug!("Resuming {} at {} bytes", dest.display(), offset);
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part)
+        .await
+        .with_context(|| format!("Failed to open {}", part.display()))?;
+    worker.progress(bytes, total_bytes);
+    while let Some(chunk) = response.chunk().await.context("Download interrupted")? {
+        file.write_all(&chunk).await?;
+        bytes += chunk.len() as u64;
+        worker.progress(bytes, total_bytes);
+    }
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&part, dest)
+        .await
+        .with_context(|| format!("Failed to move download to {}", dest.display()))?;
+    Ok(dest.to_path_buf())
+}
+
+/// Fetch a video or audio page into `dir` with yt-dlp, which continues
+/// partial downloads itself, through `proxy` if given.
+async fn fetch_ytdl(
+    config: &DownloadsConfig,
+    proxy: Option<&ProxyConfig>,
+    url: &str,
+    dir: &Path,
+    worker: &Worker,
+) -> Result<PathBuf> {
+    check_reachable(url, proxy)?;
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut command = tokio::process::Command::new(&config.ytdl_command);
+    command
+        .arg("--newline")
+        .arg("--progress")
+        .arg("--continue")
+        .arg("--no-playlist")
+        .arg("--progress-template")
+        .arg(format!(
+            "download:{} %(progress.downloaded_bytes)s %(progress.total_bytes)s %(progress.total_bytes_estimate)s",
+            YTDL_PROGRESS
+        ))
+        .arg("--print")
+        .arg(format!("after_move:{} %(filepath)s", YTDL_FILE))
+        .arg("--output")
+        .arg(dir.join("%(title)s [%(id)s].%(ext)s"));
+    if let Some(format) = &config.ytdl_format {
+        command.arg("--format").arg(format);
+    }
+    if let Some(proxy) = proxy {
+        command.arg("--proxy").arg(proxy.url());
+    }
+    let mut child = command
+        .arg("--")
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to run {}", config.ytdl_command))?;
+
+    let mut stdout = child.stdout.take().map(|s| BufReader::new(s).lines());
+    let mut stderr = child.stderr.take().map(|s| BufReader::new(s).lines());
+    let mut path = None;
+    let mut last_error = None;
+    while stdout.is_some() || stderr.is_some() {
+        let line = tokio::select! {
+            line = next_line(&mut stdout) => line,
+            line = next_line(&mut stderr) => line,
+        };
+        let Some(line) = line else { continue };
+        let line = line.trim();
+        if let Some((bytes, total_bytes)) = parse_ytdl_progress(line) {
+            worker.progress(bytes, total_bytes);
+        } else if let Some(file) = line.strip_prefix(YTDL_FILE) {
+            path = Some(PathBuf::from(file.trim_start()));
+        } else if !line.is_empty() {
+            last_error = Some(line.to_string());
+        }
+    }
+
+    let status = child.wait().await?;
+    if !status.success() {
+        bail!(
+            "{} exited with {}: {}",
+            config.ytdl_command,
+            status,
+            last_error.unwrap_or_default()
+        );
+    }
+    path.with_context(|| format!("{} did not report a file", config.ytdl_command))
+}
+
+/// The next line of an output stream, clearing it at the end. Never
+/// resolves once cleared.
+async fn next_line<R: tokio::io::AsyncBufRead + Unpin>(
+    lines: &mut Option<tokio::io::Lines<R>>,
+) -> Option<String> {
+    match lines {
+        Some(stream) => match stream.next_line().await {
+            Ok(Some(line)) => Some(line),
+            _ => {
+                *lines = None;
+                None
+            }
+        },
+        None => std::future::pending().await,
+    }
+}
+
+/// Bytes downloaded and the total, if known, from a progress line printed
+/// with the manager's template. yt-dlp prints `NA` for unknown values and
+/// estimates as decimals.
+fn parse_ytdl_progress(line: &str) -> Option<(u64, Option<u64>)> {
+    let mut fields = line.strip_prefix(YTDL_PROGRESS)?.split_whitespace();
+    let mut number = || {
+        fields
+            .next()
+            .and_then(|field| field.parse::<f64>().ok())
+            .map(|n| n as u64)
+    };
+    let bytes = number()?;
+    let total = number();
+    let estimate = number();
+    Some((bytes, total.or(estimate)))
+}
+
+/// Fetch an attachment from the item's provider and write it to `dest`.
+async fn fetch_attachment(
+    registry: &ProviderRegistry,
+    item: &Item,
+    attachment_id: &str,
+    dest: &Path,
+    worker: &Worker,
+) -> Result<PathBuf> {
+    let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+    let provider = registry
+        .get(provider_id)
+        .with_context(|| format!("Provider '{}' not found", provider_id))?;
+    let attachments = provider
+        .as_attachments()
+        .with_context(|| format!("Provider '{}' has no attachments", provider_id))?;
+    let data = registry
+        .guard(
+            provider_id,
+            attachments.fetch_attachment(item, attachment_id),
+        )
+        .await?;
+
+    if let Some(dir) = dest.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let part = part_path(dest);
+    tokio::fs::write(&part, &data)
+        .await
+        .with_context(|| format!("Failed to write {}", part.display()))?;
+    tokio::fs::rename(&part, dest).await?;
+    worker.progress(data.len() as u64, Some(data.len() as u64));
+    Ok(dest.to_path_buf())
+}
+
+/// File name for a download of `url`: the last segment of its path if that
+/// has an extension, otherwise `title`.
+fn file_name_for(url: &str, title: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    match path
+        .split_once('/')
+        .map(|(_, path)| path.rsplit('/').next())
+    {
+        Some(Some(segment)) if segment.contains('.') => sanitize_file_name(segment),
+        _ => sanitize_file_name(title),
+    }
+}
+
+/// `name` with characters that are not allowed in file names replaced, and
+/// cut to [`MAX_FILE_NAME`] characters.
+fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_FILE_NAME)
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemContent, ItemId, StreamId};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn item(id: &str, url: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:1".to_string()),
+            title: "Episode 12: Downloads".to_string(),
+            content: ItemContent::Article {
+                summary: None,
+                full_content: None,
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: Some(url.to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn manager(dir: &Path, config: DownloadsConfig) -> DownloadManager {
+        DownloadManager::from_config(&config, dir.to_path_buf())
+    }
+
+    async fn wait_until_finished(manager: &DownloadManager, id: u64) -> Download {
+        for _ in 0..200 {
+            let download = manager.list().into_iter().find(|d| d.id == id).unwrap();
+            if download.state.is_finished() {
+                return download;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("download {} did not finish", id);
+    }
+
+    /// Serve `body` once per connection, honoring `Range: bytes=N-`, and
+    /// report the range each request asked for.
+    async fn serve(body: &'static [u8]) -> (String, tokio::sync::mpsc::UnboundedReceiver<u64>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (ranges_tx, ranges_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let start = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes="))
+                    .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok());
+                let _ = ranges_tx.send(start.unwrap_or(0) as u64);
+
+                let response = match start {
+                    Some(start) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                        body.len() - start,
+                        start,
+                        body.len() - 1,
+                        body.len()
+                    ),
+                    None => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    ),
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.write_all(&body[start.unwrap_or(0)..]).await.unwrap();
+            }
+        });
+        (format!("http://{}", addr), ranges_rx)
+    }
+
+    #[test]
+    fn test_source_and_file_name() {
+        let mut episode = item("rss:1", "https://example.com/episodes/12");
+        assert_eq!(DownloadManager::source(&episode), None);
+        episode.metadata.insert(
+            MEDIA_URL_KEY.to_string(),
+            "https://cdn.example.com/ep12.mp3?token=abc".to_string(),
+        );
+        assert_eq!(
+            DownloadManager::source(&episode),
+            Some(DownloadSource::Http {
+                url: "https://cdn.example.com/ep12.mp3?token=abc".to_string()
+            })
+        );
+
+        let video = item("rss:2", "https://www.youtube.com/watch?v=abc");
+        assert!(matches!(
+            DownloadManager::source(&video),
+            Some(DownloadSource::Ytdlp { .. })
+        ));
+        let paper = item("rss:3", "https://example.com/paper.pdf");
+        assert!(matches!(
+            DownloadManager::source(&paper),
+            Some(DownloadSource::Http { .. })
+        ));
+
+        assert_eq!(
+            file_name_for("https://cdn.example.com/ep12.mp3?token=abc", "Ep 12"),
+            "ep12.mp3"
+        );
+        assert_eq!(
+            file_name_for("https://example.com/watch", "A/B: the talk"),
+            "A_B_ the talk"
+        );
+        assert_eq!(sanitize_file_name("../.."), "_..");
+        assert_eq!(sanitize_file_name(" ..hidden"), "hidden");
+        assert_eq!(sanitize_file_name(""), "download");
+    }
+
+    #[test]
+    fn test_parse_ytdl_progress() {
+        assert_eq!(
+            parse_ytdl_progress("scryforge-progress 1024 4096 NA"),
+            Some((1024, Some(4096)))
+        );
+        assert_eq!(
+            parse_ytdl_progress("scryforge-progress 1024 NA 5000.5"),
+            Some((1024, Some(5000)))
+        );
+        assert_eq!(
+            parse_ytdl_progress("scryforge-progress 10 NA NA"),
+            Some((10, None))
+        );
+        assert_eq!(parse_ytdl_progress("[download] 10.0% of 1MiB"), None);
+    }
+
+    #[tokio::test]
+    async fn test_http_download_resumes_part_file() {
+        let body: &'static [u8] = b"0123456789abcdefghij";
+        let (base, mut ranges) = serve(body).await;
+        let temp_dir = TempDir::new().unwrap();
+        let manager = manager(temp_dir.path(), DownloadsConfig::default());
+
+        let mut episode = item("rss:1", "https://example.com/episodes/12");
+        episode
+            .metadata
+            .insert(MEDIA_URL_KEY.to_string(), format!("{}/ep12.mp3", base));
+        let dest = temp_dir.path().join("rss").join("ep12.mp3");
+        std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        std::fs::write(part_path(&dest), &body[..8]).unwrap();
+
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let queued = manager.queue_link(&episode, Some(events)).unwrap();
+        assert_eq!(queued.file_name, "ep12.mp3");
+
+        let download = wait_until_finished(&manager, queued.id).await;
+        assert_eq!(download.state, DownloadState::Completed);
+        assert_eq!(download.path, Some(dest.display().to_string()));
+        assert_eq!((download.bytes, download.total_bytes), (20, Some(20)));
+        assert_eq!(ranges.recv().await, Some(8));
+        assert_eq!(std::fs::read(&dest).unwrap(), body);
+        assert!(!part_path(&dest).exists());
+
+        let mut finished = None;
+        while let Ok(event) = rx.try_recv() {
+            if let DaemonEvent::DownloadFinished { state, .. } = event {
+                finished = Some(state);
+            }
+        }
+        assert_eq!(finished, Some(DownloadState::Completed));
+
+        // The file is there, so downloading it again does nothing
+        let again = manager.queue_link(&episode, None).unwrap();
+        assert_eq!(again.state, DownloadState::Completed);
+        assert_ne!(again.id, queued.id);
+        assert!(ranges.try_recv().is_err());
+        assert_eq!(manager.clear_finished(), 2);
+        assert!(manager.list().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ytdl_download_reports_progress_and_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("fake-yt-dlp");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             echo 'scryforge-progress 512 NA 1024.0' >&2\n\
+             echo 'scryforge-progress 1024 1024 NA' >&2\n\
+             echo \"scryforge-file /downloads/talk [abc].webm\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let failing = temp_dir.path().join("failing-yt-dlp");
+        std::fs::write(
+            &failing,
+            "#!/bin/sh\necho 'ERROR: Video unavailable' >&2\nexit 1\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&failing, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let video = item("youtube:abc", "https://www.youtube.com/watch?v=abc");
+        let manager = manager(
+            &temp_dir.path().join("downloads"),
+            DownloadsConfig {
+                ytdl_command: script.display().to_string(),
+                ..DownloadsConfig::default()
+            },
+        );
+        let queued = manager.queue_link(&video, None).unwrap();
+        assert_eq!(queued.file_name, video.title);
+        let download = wait_until_finished(&manager, queued.id).await;
+        assert_eq!(download.state, DownloadState::Completed);
+        assert_eq!(download.file_name, "talk [abc].webm");
+        assert_eq!((download.bytes, download.total_bytes), (1024, Some(1024)));
+        assert!(temp_dir.path().join("downloads").join("youtube").is_dir());
+
+        let manager = DownloadManager::from_config(
+            &DownloadsConfig {
+                ytdl_command: failing.display().to_string(),
+                ..DownloadsConfig::default()
+            },
+            temp_dir.path().to_path_buf(),
+        );
+        let queued = manager.queue_link(&video, None).unwrap();
+        let download = wait_until_finished(&manager, queued.id).await;
+        assert_eq!(download.state, DownloadState::Failed);
+        assert!(download
+            .error
+            .unwrap()
+            .ends_with("ERROR: Video unavailable"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ytdl_download_uses_provider_proxy() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("fake-yt-dlp");
+        std::fs::write(
+            &script,
+            "#!/bin/sh
+\
+             case \"$*\" in *'--proxy socks5h://127.0.0.1:9050'*) ;; *) exit 1 ;; esac\n\
+             echo \"scryforge-file /downloads/talk.webm\"\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let proxies = HashMap::from([("youtube".to_string(), ProxyConfig::parse("tor").unwrap())]);
+        let manager = manager(
+            &temp_dir.path().join("downloads"),
+            DownloadsConfig {
+                ytdl_command: script.display().to_string(),
+                ..DownloadsConfig::default()
+            },
+        )
+        .with_proxies(&proxies)
+        .unwrap();
+        let video = item("youtube:abc", "https://www.youtube.com/watch?v=abc");
+        let queued = manager.queue_link(&video, None).unwrap();
+        let download = wait_until_finished(&manager, queued.id).await;
+        assert_eq!(download.state, DownloadState::Completed);
+
+        // Without a proxy an onion link fails instead of leaking the lookup
+        let manager = manager(
+            &temp_dir.path().join("downloads"),
+            DownloadsConfig::default(),
+        );
+        let hidden = item("rss:onion", "http://example.onion/slides.pdf");
+        let queued = manager.queue_link(&hidden, None).unwrap();
+        let download = wait_until_finished(&manager, queued.id).await;
+        assert_eq!(download.state, DownloadState::Failed);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_and_retry() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("slow-yt-dlp");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\n\
+             [ -e \"$0.fast\" ] || sleep 30\n\
+             echo 'scryforge-file /downloads/talk.webm'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let manager = manager(
+            temp_dir.path(),
+            DownloadsConfig {
+                ytdl_command: script.display().to_string(),
+                max_concurrent: 1,
+                ..DownloadsConfig::default()
+            },
+        );
+        let first = manager.queue_link(&item("youtube:a", "https://youtu.be/a"), None);
+        let second = manager.queue_link(&item("youtube:b", "https://youtu.be/b"), None);
+        let (first, second) = (first.unwrap(), second.unwrap());
+
+        // Only one runs at a time
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let states: Vec<DownloadState> = manager.list().iter().map(|d| d.state).collect();
+        assert_eq!(
+            states,
+            vec![DownloadState::Downloading, DownloadState::Queued]
+        );
+        // Queueing a running download again returns it
+        let duplicate = manager.queue_link(&item("youtube:a", "https://youtu.be/a"), None);
+        assert_eq!(duplicate.unwrap().id, first.id);
+
+        assert!(manager.cancel(first.id));
+        assert!(manager.cancel(second.id));
+        assert!(!manager.cancel(second.id));
+        let download = wait_until_finished(&manager, first.id).await;
+        assert_eq!(download.state, DownloadState::Cancelled);
+
+        std::fs::write(temp_dir.path().join("slow-yt-dlp.fast"), "").unwrap();
+        let retried = manager.retry(first.id, None).unwrap();
+        assert_eq!(retried.state, DownloadState::Queued);
+        let download = wait_until_finished(&manager, first.id).await;
+        assert_eq!(download.state, DownloadState::Completed);
+        assert_eq!(
+            manager.retry(first.id, None).unwrap().state,
+            DownloadState::Completed
+        );
+        assert!(manager.retry(999, None).is_none());
+    }
+}
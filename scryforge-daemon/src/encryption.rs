@@ -0,0 +1,67 @@
+//! Encryption key loading for `encrypt_at_rest` providers.
+//!
+//! Providers opt in with `encrypt_at_rest = true`. Their cached items and
+//! local stores, such as the bookmarks file, are then sealed with a
+//! [`StoreCipher`] whose key is fetched at startup: from the
+//! [`KEY_ENV`] environment variable if set, otherwise from Sigilforge, which
+//! keeps it in the system keyring. The key is never written to the config
+//! or next to the data.
+//!
+//! Turning `encrypt_at_rest` off decrypts that provider's data at the next
+//! start, but only while the key is loaded. With no provider left encrypting,
+//! set [`KEY_ENV`] for that one start.
+
+use anyhow::{bail, Context, Result};
+use scryforge_provider_core::crypto::StoreCipher;
+use scryforge_sigilforge_client::SigilforgeClient;
+
+use crate::config::Config;
+
+/// Environment variable holding a base64-encoded key, for systems without a
+/// keyring. Takes precedence over `encryption.key`.
+pub const KEY_ENV: &str = "SCRYFORGE_ENCRYPTION_KEY";
+
+/// IDs of the providers with `encrypt_at_rest` enabled, sorted.
+pub fn encrypted_providers(config: &Config) -> Vec<String> {
+    let mut providers: Vec<String> = config
+        .providers
+        .iter()
+        .filter(|(_, provider)| provider.encrypt_at_rest)
+        .map(|(id, _)| id.clone())
+        .collect();
+    providers.sort();
+    providers
+}
+
+/// Load the encryption key, or `None` if no provider needs one and
+/// [`KEY_ENV`] is not set.
+pub async fn from_config(config: &Config) -> Result<Option<StoreCipher>> {
+    if encrypted_providers(config).is_empty() && std::env::var_os(KEY_ENV).is_none() {
+        return Ok(None);
+    }
+    load_key(config, &SigilforgeClient::with_default_path())
+        .await
+        .map(Some)
+}
+
+/// Load the key from [`KEY_ENV`] or resolve `encryption.key` through `client`.
+pub async fn load_key(config: &Config, client: &SigilforgeClient) -> Result<StoreCipher> {
+    if let Ok(encoded) = std::env::var(KEY_ENV) {
+        return StoreCipher::from_base64(&encoded).with_context(|| format!("Invalid {}", KEY_ENV));
+    }
+
+    let reference = &config.encryption.key;
+    if !client.is_available() {
+        bail!(
+            "Sigilforge is not running, so the encryption key {} cannot be read; start it or set {}",
+            reference,
+            KEY_ENV
+        );
+    }
+    let encoded = client
+        .resolve(reference)
+        .await
+        .with_context(|| format!("Failed to read encryption key {}", reference))?;
+    StoreCipher::from_base64(&encoded)
+        .with_context(|| format!("Invalid encryption key {}", reference))
+}
@@ -0,0 +1,209 @@
+//! Daemon event bus for live client updates.
+//!
+//! Components inside the daemon (the sync manager, API handlers) publish
+//! [`DaemonEvent`]s onto a shared [`EventBus`]. The JSON-RPC server exposes
+//! the bus through the `events.subscribe` subscription so the TUI and other
+//! clients can react to changes as they happen instead of polling.
+//!
+//! The bus is a thin wrapper around a tokio `broadcast` channel: publishing
+//! never blocks, and slow subscribers simply miss the oldest events.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tracing::trace;
+
+use crate::downloads::DownloadState;
+
+/// Default number of events buffered per subscriber before lagging.
+pub const DEFAULT_EVENT_CAPACITY: usize = 256;
+
+/// An event published by the daemon.
+///
+/// Serialized with an internal `type` tag so clients can dispatch on it
+/// without knowing every variant up front.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonEvent {
+    /// A provider sync discovered new or updated items.
+    NewItems {
+        provider_id: String,
+        items_added: u32,
+        items_updated: u32,
+        timestamp: DateTime<Utc>,
+    },
+    /// A provider sync cycle started.
+    SyncStarted {
+        provider_id: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A provider sync cycle finished, successfully or not.
+    SyncFinished {
+        provider_id: String,
+        success: bool,
+        error: Option<String>,
+        /// Streams that failed to sync, including while the rest succeeded
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        failed_streams: Vec<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// An item action (mark read, archive, save, ...) completed.
+    ActionCompleted {
+        item_id: String,
+        action: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// The reminder set on an item is due.
+    ReminderDue {
+        item_id: String,
+        title: String,
+        remind_at: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+    /// An item first matched a saved search.
+    AlertMatched {
+        search_id: i64,
+        /// Name of the saved search
+        name: String,
+        item_id: String,
+        title: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// The player started by `items.play` exited.
+    PlaybackFinished {
+        item_id: String,
+        /// mpv's exit code; `None` if it was killed by a signal
+        exit_code: Option<i32>,
+        /// Position playback stopped at, in seconds, if mpv reported one
+        position_secs: Option<u64>,
+        /// Whether the item was played to the end
+        finished: bool,
+        timestamp: DateTime<Utc>,
+    },
+    /// A download wrote more of its file; published at most once a second
+    /// per download.
+    DownloadProgress {
+        download_id: u64,
+        item_id: String,
+        bytes: u64,
+        total_bytes: Option<u64>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A download completed, failed, or was cancelled.
+    DownloadFinished {
+        download_id: u64,
+        item_id: String,
+        state: DownloadState,
+        /// Where the file was saved, if it completed
+        path: Option<String>,
+        error: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A focus session ended, on time or stopped early.
+    FocusEnded {
+        stream_ids: Vec<String>,
+        /// Whether the session ran its full length
+        completed: bool,
+        /// Items in the session's streams read during it
+        read: u64,
+        /// Items in the session's streams saved during it
+        saved: u64,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Broadcast channel shared by everything that publishes daemon events.
+///
+/// Cloning an `EventBus` yields another handle to the same channel.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<DaemonEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Create a new event bus buffering up to `capacity` events per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Publish an event to all current subscribers.
+    ///
+    /// Events published while nobody is subscribed are dropped.
+    pub fn publish(&self, event: DaemonEvent) {
+        if self.tx.send(event).is_err() {
+            trace!("Dropped daemon event, no subscribers");
+        }
+    }
+
+    /// Subscribe to events published after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<DaemonEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Number of active subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_and_receive() {
+        let bus = EventBus::default();
+        let mut rx = bus.subscribe();
+
+        bus.publish(DaemonEvent::SyncStarted {
+            provider_id: "dummy".to_string(),
+            timestamp: Utc::now(),
+        });
+
+        match rx.recv().await.unwrap() {
+            DaemonEvent::SyncStarted { provider_id, .. } => assert_eq!(provider_id, "dummy"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        let bus = EventBus::new(4);
+        assert_eq!(bus.subscriber_count(), 0);
+
+        // Must not panic or block
+        bus.publish(DaemonEvent::ActionCompleted {
+            item_id: "dummy:1".to_string(),
+            action: "mark_read".to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    #[test]
+    fn test_event_serialization_is_tagged() {
+        let event = DaemonEvent::NewItems {
+            provider_id: "rss".to_string(),
+            items_added: 3,
+            items_updated: 1,
+            timestamp: Utc::now(),
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "new_items");
+        assert_eq!(json["items_added"], 3);
+
+        let parsed: DaemonEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
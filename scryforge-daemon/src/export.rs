@@ -0,0 +1,780 @@
+//! Export items to Markdown files, JSON archives, or publishable feeds.
+//!
+//! Any set of items (a feed, a collection, a search result) can be written
+//! out in one of these formats:
+//!
+//! - [`ExportFormat::Markdown`] - a directory with one `.md` file per item,
+//!   each starting with YAML frontmatter. Suited to note-taking tools.
+//! - [`ExportFormat::Json`] - a single pretty-printed JSON array.
+//! - [`ExportFormat::Ndjson`] - a single file with one JSON item per line,
+//!   convenient for streaming into other tools or appending backups.
+//! - [`ExportFormat::Rss`], [`ExportFormat::JsonFeed`], and
+//!   [`ExportFormat::Html`] - an RSS 2.0 feed, a JSON Feed 1.1, or a static
+//!   page listing the items' links. These are meant to be hosted, so a
+//!   curated folder of bookmarks can be shared; they carry only titles,
+//!   links, descriptions, and tags, never read or saved state.
+//!
+//! Markdown file names are derived from item IDs, so exporting the same
+//! source again overwrites earlier files instead of duplicating them.
+//!
+//! Clients pick the destination, so [`resolve_destination`] keeps it inside
+//! the configured export directory.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::{Item, ItemContent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::markup::escape_html;
+
+/// Output format for an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One Markdown file with frontmatter per item, written into a directory.
+    Markdown,
+    /// A single JSON array of items.
+    Json,
+    /// Newline-delimited JSON, one item per line.
+    Ndjson,
+    /// An RSS 2.0 feed.
+    Rss,
+    /// A JSON Feed 1.1 document.
+    JsonFeed,
+    /// A static HTML page of links.
+    Html,
+}
+
+/// Where the exported items come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportSource {
+    /// All cached items of a stream.
+    Stream { stream_id: String },
+    /// Items of a provider collection.
+    ///
+    /// With `provider_id`, `collection_id` is the provider's own ID for the
+    /// collection, as in its `collection` stream IDs; a bookmarks folder is
+    /// `{"provider_id": "bookmarks", "collection_id": "<folder id>"}`.
+    Collection {
+        collection_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provider_id: Option<String>,
+    },
+    /// Results of a search, using the same filters as `search.query`.
+    Search {
+        query: String,
+        #[serde(default)]
+        filters: Option<JsonValue>,
+    },
+}
+
+/// Outcome of a completed export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportSummary {
+    /// Format that was written
+    pub format: ExportFormat,
+    /// Directory (Markdown) or file (all other formats) that was written
+    pub path: PathBuf,
+    /// Number of items exported
+    pub items_exported: usize,
+}
+
+/// Write `items` to `dest` in the given format.
+///
+/// For [`ExportFormat::Markdown`] `dest` is a directory, created if needed.
+/// For the other formats it is the output file; parent directories are
+/// created and an existing file is replaced. `title` names the feed or page
+/// in the publishable formats and is ignored by the others.
+pub fn export_items(
+    items: &[Item],
+    title: &str,
+    format: ExportFormat,
+    dest: &Path,
+) -> Result<ExportSummary> {
+    match format {
+        ExportFormat::Markdown => write_markdown(items, dest)?,
+        ExportFormat::Json => {
+            let mut writer = create_file(dest)?;
+            serde_json::to_writer_pretty(&mut writer, items)
+                .context("Failed to serialize items")?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        ExportFormat::Ndjson => {
+            let mut writer = create_file(dest)?;
+            for item in items {
+                serde_json::to_writer(&mut writer, item).context("Failed to serialize item")?;
+                writeln!(writer)?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Rss => write_file(dest, &render_rss(title, items))?,
+        ExportFormat::JsonFeed => {
+            let mut writer = create_file(dest)?;
+            serde_json::to_writer_pretty(&mut writer, &render_json_feed(title, items))
+                .context("Failed to serialize feed")?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        ExportFormat::Html => write_file(dest, &render_html(title, items))?,
+    }
+
+    Ok(ExportSummary {
+        format,
+        path: dest.to_path_buf(),
+        items_exported: items.len(),
+    })
+}
+
+/// Resolve `path`, relative to the export directory `root`, to the
+/// destination to pass to [`export_items`].
+///
+/// `path` must be relative and must not leave `root`, whether through `..`
+/// or a symlink already in it. An existing destination is only replaced
+/// with `overwrite`.
+pub fn resolve_destination(root: &Path, path: &str, overwrite: bool) -> Result<PathBuf> {
+    let relative = Path::new(path);
+    let mut components = relative.components().peekable();
+    if components.peek().is_none()
+        || !components.all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        || relative.file_name().is_none()
+    {
+        bail!(
+            "Export path must name a file or directory inside the export directory: {}",
+            path
+        );
+    }
+
+    fs::create_dir_all(root)
+        .with_context(|| format!("Failed to create export directory: {}", root.display()))?;
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve export directory: {}", root.display()))?;
+    let dest = root.join(relative);
+
+    // The deepest part of the path that exists decides where it really leads
+    let existing = dest
+        .ancestors()
+        .find(|p| fs::symlink_metadata(p).is_ok())
+        .unwrap_or(&root);
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", existing.display()))?;
+    if !resolved.starts_with(&root) {
+        bail!("Export path leads outside the export directory: {}", path);
+    }
+    if existing == dest.as_path() && !overwrite {
+        bail!(
+            "{} already exists; export with overwrite to replace it",
+            path
+        );
+    }
+
+    Ok(dest)
+}
+
+fn write_markdown(items: &[Item], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create export directory: {}", dir.display()))?;
+
+    for item in items {
+        let path = dir.join(markdown_file_name(item));
+        fs::write(&path, render_markdown(item))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    let mut writer = create_file(path)?;
+    writer.write_all(contents.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn create_file(path: &Path) -> Result<BufWriter<File>> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create export file: {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+/// File name for an item's Markdown export, derived from its ID.
+pub fn markdown_file_name(item: &Item) -> String {
+    let mut name = String::with_capacity(item.id.as_str().len() + 3);
+    let mut last_dash = false;
+    for c in item.id.as_str().chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            name.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            name.push('-');
+            last_dash = true;
+        }
+    }
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "item.md".to_string()
+    } else {
+        format!("{}.md", name)
+    }
+}
+
+/// Render a single item as Markdown with YAML frontmatter.
+pub fn render_markdown(item: &Item) -> String {
+    let mut out = String::from("---\n");
+
+    frontmatter_field(&mut out, "id", item.id.as_str());
+    frontmatter_field(&mut out, "stream_id", item.stream_id.as_str());
+    frontmatter_field(&mut out, "title", &item.title);
+    if let Some(ref url) = item.url {
+        frontmatter_field(&mut out, "url", url);
+    }
+    if let Some(ref author) = item.author {
+        frontmatter_field(&mut out, "author", &author.name);
+    }
+    if let Some(published) = item.published {
+        frontmatter_field(&mut out, "published", &published.to_rfc3339());
+    }
+    if let Some(updated) = item.updated {
+        frontmatter_field(&mut out, "updated", &updated.to_rfc3339());
+    }
+    out.push_str(&format!("tags: {}\n", yaml_string_list(&item.tags)));
+    out.push_str(&format!("is_read: {}\n", item.is_read));
+    out.push_str(&format!("is_saved: {}\n", item.is_saved));
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n", item.title));
+
+    let body = content_markdown(&item.content);
+    if !body.trim().is_empty() {
+        out.push('\n');
+        out.push_str(body.trim_end());
+        out.push('\n');
+    }
+
+    if let Some(ref url) = item.url {
+        out.push_str(&format!("\n[Source]({})\n", url));
+    }
+
+    out
+}
+
+/// Markdown body for an item's content.
+fn content_markdown(content: &ItemContent) -> String {
+    match content {
+        ItemContent::Text(text) | ItemContent::Markdown(text) | ItemContent::Html(text) => {
+            text.clone()
+        }
+        ItemContent::Email {
+            body_text,
+            body_html,
+            snippet,
+            ..
+        } => body_text
+            .clone()
+            .or_else(|| body_html.clone())
+            .unwrap_or_else(|| snippet.clone()),
+        ItemContent::Article {
+            summary,
+            full_content,
+        } => full_content
+            .clone()
+            .or_else(|| summary.clone())
+            .unwrap_or_default(),
+        ItemContent::Video {
+            description,
+            duration_seconds,
+            view_count,
+        } => {
+            let mut body = description.clone();
+            if let Some(secs) = duration_seconds {
+                body.push_str(&format!("\n\nDuration: {}:{:02}", secs / 60, secs % 60));
+            }
+            if let Some(views) = view_count {
+                body.push_str(&format!("\n\nViews: {}", views));
+            }
+            body
+        }
+        ItemContent::Track { album, artists, .. } => {
+            let mut body = format!("Artists: {}", artists.join(", "));
+            if let Some(album) = album {
+                body.push_str(&format!("\n\nAlbum: {}", album));
+            }
+            body
+        }
+        ItemContent::Task {
+            body,
+            due_date,
+            is_completed,
+        } => {
+            let mut out = format!("- [{}] Task", if *is_completed { "x" } else { " " });
+            if let Some(due) = due_date {
+                out.push_str(&format!(" (due {})", due));
+            }
+            if let Some(body) = body {
+                out.push_str(&format!("\n\n{}", body));
+            }
+            out
+        }
+        ItemContent::Event {
+            description,
+            start,
+            end,
+            location,
+            ..
+        } => {
+            let mut out = format!("When: {} - {}", start.to_rfc3339(), end.to_rfc3339());
+            if let Some(location) = location {
+                out.push_str(&format!("\n\nWhere: {}", location));
+            }
+            if let Some(description) = description {
+                out.push_str(&format!("\n\n{}", description));
+            }
+            out
+        }
+        ItemContent::Image { url, alt, .. } => markdown_image(url, alt.as_deref()),
+        ItemContent::Gallery { images } => images
+            .iter()
+            .map(|image| markdown_image(&image.url, image.alt.as_deref()))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        ItemContent::Message {
+            text,
+            sender,
+            channel,
+            reactions,
+            ..
+        } => {
+            let mut out = match channel {
+                Some(channel) => format!("**{}** in {}:\n\n{}", sender, channel, text),
+                None => format!("**{}**:\n\n{}", sender, text),
+            };
+            if !reactions.is_empty() {
+                let reactions: Vec<String> = reactions
+                    .iter()
+                    .map(|r| format!("{} {}", r.emoji, r.count))
+                    .collect();
+                out.push_str(&format!("\n\nReactions: {}", reactions.join(", ")));
+            }
+            out
+        }
+        ItemContent::Patch {
+            description, diff, ..
+        } => match description {
+            Some(description) => format!("{}\n\n```diff\n{}\n```", description, diff.trim_end()),
+            None => format!("```diff\n{}\n```", diff.trim_end()),
+        },
+        ItemContent::Bookmark { description } | ItemContent::Generic { body: description } => {
+            description.clone().unwrap_or_default()
+        }
+    }
+}
+
+/// Render items as an RSS 2.0 feed.
+///
+/// Items without a link get their ID as a non-permalink `guid`. The build
+/// date is that of the newest item, so re-publishing unchanged items
+/// produces the same file.
+pub fn render_rss(title: &str, items: &[Item]) -> String {
+    let mut doc = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    doc.push_str("<rss version=\"2.0\">\n<channel>\n");
+    doc.push_str(&format!("  <title>{}</title>\n", escape_html(title)));
+    doc.push_str(&format!(
+        "  <description>{}</description>\n",
+        escape_html(title)
+    ));
+    if let Some(built) = newest(items) {
+        doc.push_str(&format!(
+            "  <lastBuildDate>{}</lastBuildDate>\n",
+            built.to_rfc2822()
+        ));
+    }
+    doc.push_str("  <generator>Scryforge</generator>\n");
+
+    for item in items {
+        doc.push_str("  <item>\n");
+        doc.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_html(&item.title)
+        ));
+        match item.url {
+            Some(ref url) => {
+                doc.push_str(&format!("    <link>{}</link>\n", escape_html(url)));
+                doc.push_str(&format!("    <guid>{}</guid>\n", escape_html(url)));
+            }
+            None => doc.push_str(&format!(
+                "    <guid isPermaLink=\"false\">{}</guid>\n",
+                escape_html(item.id.as_str())
+            )),
+        }
+        if let Some(description) = description(item) {
+            doc.push_str(&format!(
+                "    <description>{}</description>\n",
+                escape_html(&description)
+            ));
+        }
+        if let Some(ref author) = item.author {
+            doc.push_str(&format!(
+                "    <dc:creator xmlns:dc=\"http://purl.org/dc/elements/1.1/\">{}</dc:creator>\n",
+                escape_html(&author.name)
+            ));
+        }
+        if let Some(published) = item.published {
+            doc.push_str(&format!(
+                "    <pubDate>{}</pubDate>\n",
+                published.to_rfc2822()
+            ));
+        }
+        for tag in &item.tags {
+            doc.push_str(&format!("    <category>{}</category>\n", escape_html(tag)));
+        }
+        doc.push_str("  </item>\n");
+    }
+
+    doc.push_str("</channel>\n</rss>\n");
+    doc
+}
+
+/// Render items as a JSON Feed 1.1 document.
+pub fn render_json_feed(title: &str, items: &[Item]) -> JsonValue {
+    let items: Vec<JsonValue> = items
+        .iter()
+        .map(|item| {
+            let mut entry = serde_json::json!({
+                "id": item.url.as_deref().unwrap_or(item.id.as_str()),
+                "title": item.title,
+                "content_text": description(item).unwrap_or_default(),
+            });
+            if let Some(ref url) = item.url {
+                entry["url"] = url.clone().into();
+            }
+            if let Some(published) = item.published {
+                entry["date_published"] = published.to_rfc3339().into();
+            }
+            if let Some(updated) = item.updated {
+                entry["date_modified"] = updated.to_rfc3339().into();
+            }
+            if let Some(ref author) = item.author {
+                entry["authors"] = serde_json::json!([{ "name": author.name }]);
+            }
+            if !item.tags.is_empty() {
+                entry["tags"] = item.tags.clone().into();
+            }
+            entry
+        })
+        .collect();
+
+    serde_json::json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": title,
+        "items": items,
+    })
+}
+
+/// Render items as a self-contained HTML page of links.
+pub fn render_html(title: &str, items: &[Item]) -> String {
+    let title = escape_html(title);
+    let mut doc = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<ul>\n",
+        title, title
+    );
+
+    for item in items {
+        let item_title = escape_html(&item.title);
+        doc.push_str("<li>");
+        match item.url {
+            Some(ref url) if url.starts_with("https://") || url.starts_with("http://") => doc
+                .push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(url),
+                    item_title
+                )),
+            _ => doc.push_str(&item_title),
+        }
+        if !item.tags.is_empty() {
+            let tags: Vec<String> = item
+                .tags
+                .iter()
+                .map(|tag| format!("#{}", escape_html(tag)))
+                .collect();
+            doc.push_str(&format!(" <small>{}</small>", tags.join(" ")));
+        }
+        if let Some(description) = description(item) {
+            doc.push_str(&format!("<p>{}</p>", escape_html(&description)));
+        }
+        doc.push_str("</li>\n");
+    }
+
+    doc.push_str(&format!(
+        "</ul>\n<p><small>{} links</small></p>\n</body>\n</html>\n",
+        items.len()
+    ));
+    doc
+}
+
+/// Plain-text description of an item for the publishable formats.
+fn description(item: &Item) -> Option<String> {
+    item.content
+        .plain_text()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// When the newest of `items` was published or updated.
+fn newest(items: &[Item]) -> Option<DateTime<Utc>> {
+    items
+        .iter()
+        .filter_map(|item| item.updated.max(item.published))
+        .max()
+}
+
+fn markdown_image(url: &str, alt: Option<&str>) -> String {
+    format!("![{}]({})", alt.unwrap_or_default(), url)
+}
+
+fn frontmatter_field(out: &mut String, key: &str, value: &str) {
+    out.push_str(&format!("{}: {}\n", key, yaml_string(value)));
+}
+
+/// Quote a string for YAML. JSON string literals are valid YAML scalars.
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn yaml_string_list(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|v| yaml_string(v)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use scryforge_provider_core::{Author, ItemId, StreamId};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_item(id: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:news".to_string()),
+            title: "A \"quoted\" title".to_string(),
+            content: ItemContent::Article {
+                summary: Some("Short summary".to_string()),
+                full_content: None,
+            },
+            author: Some(Author {
+                name: "Jane".to_string(),
+                email: None,
+                url: None,
+                avatar_url: None,
+            }),
+            published: Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()),
+            updated: None,
+            url: Some("https://example.com/post".to_string()),
+            thumbnail_url: None,
+            is_read: true,
+            is_saved: false,
+            tags: vec!["rust".to_string(), "news".to_string()],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_markdown_frontmatter() {
+        let md = render_markdown(&create_test_item("rss:1"));
+
+        assert!(md.starts_with("---\n"));
+        assert!(md.contains("id: \"rss:1\"\n"));
+        assert!(md.contains("title: \"A \\\"quoted\\\" title\"\n"));
+        assert!(md.contains("author: \"Jane\"\n"));
+        assert!(md.contains("published: \"2024-03-01T12:00:00+00:00\"\n"));
+        assert!(md.contains("tags: [\"rust\", \"news\"]\n"));
+        assert!(md.contains("is_read: true\n"));
+        assert!(md.contains("# A \"quoted\" title\n\nShort summary\n"));
+        assert!(md.ends_with("[Source](https://example.com/post)\n"));
+    }
+
+    #[test]
+    fn test_markdown_file_name() {
+        assert_eq!(
+            markdown_file_name(&create_test_item("email:INBOX/42")),
+            "email-INBOX-42.md"
+        );
+        assert_eq!(markdown_file_name(&create_test_item("::")), "item.md");
+    }
+
+    #[test]
+    fn test_export_markdown_directory() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dest = temp_dir.path().join("notes");
+        let items = vec![create_test_item("rss:1"), create_test_item("rss:2")];
+
+        let summary = export_items(&items, "Export", ExportFormat::Markdown, &dest)?;
+        assert_eq!(summary.items_exported, 2);
+        assert!(dest.join("rss-1.md").exists());
+        assert!(dest.join("rss-2.md").exists());
+
+        // Re-exporting overwrites instead of duplicating
+        export_items(&items, "Export", ExportFormat::Markdown, &dest)?;
+        assert_eq!(fs::read_dir(&dest)?.count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_destination() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().join("exports");
+
+        let dest = resolve_destination(&root, "public/links.xml", false)?;
+        assert_eq!(dest, root.canonicalize()?.join("public/links.xml"));
+        write_file(&dest, "<rss/>")?;
+
+        for path in ["", ".", "/etc/passwd", "../outside.xml", "public/../../x"] {
+            assert!(resolve_destination(&root, path, true).is_err(), "{}", path);
+        }
+
+        // Existing files are replaced only when asked
+        assert!(resolve_destination(&root, "public/links.xml", false).is_err());
+        assert_eq!(resolve_destination(&root, "public/links.xml", true)?, dest);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(temp_dir.path(), root.join("escape"))?;
+            assert!(resolve_destination(&root, "escape/x.xml", true).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_json_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dest = temp_dir.path().join("archive/items.json");
+        let items = vec![create_test_item("rss:1"), create_test_item("rss:2")];
+
+        export_items(&items, "Export", ExportFormat::Json, &dest)?;
+        let parsed: Vec<Item> = serde_json::from_str(&fs::read_to_string(&dest)?)?;
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].id.as_str(), "rss:2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_ndjson_one_item_per_line() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dest = temp_dir.path().join("items.ndjson");
+        let items = vec![create_test_item("rss:1"), create_test_item("rss:2")];
+
+        export_items(&items, "Export", ExportFormat::Ndjson, &dest)?;
+        let contents = fs::read_to_string(&dest)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: Item = serde_json::from_str(lines[0])?;
+        assert_eq!(first.id.as_str(), "rss:1");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let mut bookmark = create_test_item("bookmarks:1");
+        bookmark.url = None;
+        bookmark.content = ItemContent::Bookmark {
+            description: Some("Fish & <chips>".to_string()),
+        };
+        let rss = render_rss("Reading & Links", &[create_test_item("rss:1"), bookmark]);
+
+        assert!(
+            rss.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<rss version=\"2.0\">")
+        );
+        assert!(rss.contains("<title>Reading &amp; Links</title>"));
+        assert!(rss.contains("<lastBuildDate>Fri, 1 Mar 2024 12:00:00 +0000</lastBuildDate>"));
+        assert!(rss.contains("<title>A &quot;quoted&quot; title</title>"));
+        assert!(rss.contains("<link>https://example.com/post</link>"));
+        assert!(rss.contains("<guid isPermaLink=\"false\">bookmarks:1</guid>"));
+        assert!(rss.contains("<description>Fish &amp; &lt;chips&gt;</description>"));
+        assert!(rss.contains("<category>rust</category>"));
+        assert!(rss.ends_with("</channel>\n</rss>\n"));
+    }
+
+    #[test]
+    fn test_render_json_feed() {
+        let feed = render_json_feed("Links", &[create_test_item("rss:1")]);
+
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(feed["title"], "Links");
+        let item = &feed["items"][0];
+        assert_eq!(item["id"], "https://example.com/post");
+        assert_eq!(item["url"], "https://example.com/post");
+        assert_eq!(item["content_text"], "Short summary");
+        assert_eq!(item["date_published"], "2024-03-01T12:00:00+00:00");
+        assert_eq!(item["authors"][0]["name"], "Jane");
+        assert_eq!(item["tags"], serde_json::json!(["rust", "news"]));
+        // Local state stays private
+        assert!(item.get("is_read").is_none());
+    }
+
+    #[test]
+    fn test_export_html_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dest = temp_dir.path().join("site/index.html");
+        let mut unsafe_link = create_test_item("rss:2");
+        unsafe_link.url = Some("javascript:alert(1)".to_string());
+
+        export_items(
+            &[create_test_item("rss:1"), unsafe_link],
+            "My <Links>",
+            ExportFormat::Html,
+            &dest,
+        )?;
+        let html = fs::read_to_string(&dest)?;
+        assert!(html.contains("<title>My &lt;Links&gt;</title>"));
+        assert!(html.contains(
+            "<a href=\"https://example.com/post\">A &quot;quoted&quot; title</a> <small>#rust #news</small>"
+        ));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("<p><small>2 links</small></p>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_source_serialization() {
+        let source: ExportSource =
+            serde_json::from_str(r#"{"kind":"search","query":"rust"}"#).unwrap();
+        assert_eq!(
+            source,
+            ExportSource::Search {
+                query: "rust".to_string(),
+                filters: None
+            }
+        );
+
+        let source: ExportSource = serde_json::from_str(
+            r#"{"kind":"collection","collection_id":"f1","provider_id":"bookmarks"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            source,
+            ExportSource::Collection {
+                collection_id: "f1".to_string(),
+                provider_id: Some("bookmarks".to_string()),
+            }
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Time-boxed focus sessions.
+//!
+//! `focus.start` begins a session on a handful of streams, 25 minutes long
+//! unless asked otherwise. The daemon only keeps the session's state; while
+//! it runs, clients show the session's streams and hide unread counts
+//! everywhere else. When the time is up, or on `focus.stop`, the session
+//! ends with a [`FocusReport`] of what was read and saved in its streams,
+//! taken from the cache's reading statistics, and a
+//! [`DaemonEvent::FocusEnded`] event.
+//!
+//! Only one session runs at a time and none survive a daemon restart.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::task::AbortHandle;
+use tracing::{info, warn};
+
+use crate::cache::Cache;
+use crate::events::{DaemonEvent, EventBus};
+
+/// Length of a session when `focus.start` is given none.
+pub const DEFAULT_FOCUS_MINUTES: u32 = 25;
+
+/// A running focus session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusSession {
+    /// Streams the session is limited to
+    pub stream_ids: Vec<String>,
+    pub minutes: u32,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// What was processed during a focus session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusReport {
+    pub stream_ids: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// Whether the session ran its full length rather than being stopped
+    pub completed: bool,
+    /// Items in the session's streams first read during it
+    pub read: u64,
+    /// Items in the session's streams first saved during it
+    pub saved: u64,
+}
+
+/// The running session and how the last one went.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusStatus {
+    pub session: Option<FocusSession>,
+    pub last: Option<FocusReport>,
+}
+
+struct Running {
+    id: u64,
+    session: FocusSession,
+    timer: AbortHandle,
+}
+
+#[derive(Default)]
+struct FocusState {
+    current: Option<Running>,
+    last: Option<FocusReport>,
+}
+
+/// Runs focus sessions, one at a time, ending each when its time is up.
+///
+/// Dropping the value cancels the timer of a running session without
+/// reporting on it.
+#[derive(Default)]
+pub struct Focus {
+    state: Arc<Mutex<FocusState>>,
+    sessions: AtomicU64,
+}
+
+impl Focus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a session on `stream_ids` lasting `minutes`, failing if one is
+    /// already running.
+    ///
+    /// The session is ended on a background task, which reports from
+    /// `cache` and publishes on `events`. Must be called within a Tokio
+    /// runtime.
+    pub fn start<C: Cache + 'static>(
+        &self,
+        stream_ids: Vec<String>,
+        minutes: u32,
+        cache: Arc<C>,
+        events: Option<EventBus>,
+    ) -> Result<FocusSession> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(running) = &state.current {
+            bail!(
+                "A focus session is already running until {}",
+                running.session.ends_at.to_rfc3339()
+            );
+        }
+
+        let started_at = Utc::now();
+        let session = FocusSession {
+            stream_ids,
+            minutes,
+            started_at,
+            ends_at: started_at + Duration::minutes(i64::from(minutes)),
+        };
+        let id = self.sessions.fetch_add(1, Ordering::Relaxed);
+
+        let timer_state = Arc::clone(&self.state);
+        let length = std::time::Duration::from_secs(u64::from(minutes) * 60);
+        let timer = tokio::spawn(async move {
+            tokio::time::sleep(length).await;
+            let running = {
+                let mut state = timer_state.lock().unwrap();
+                match &state.current {
+                    Some(running) if running.id == id => state.current.take(),
+                    _ => None,
+                }
+            };
+            if let Some(running) = running {
+                finish(
+                    &timer_state,
+                    cache.as_ref(),
+                    events.as_ref(),
+                    running.session,
+                    true,
+                );
+            }
+        })
+        .abort_handle();
+
+        info!(
+            "Focus session started on {} stream(s) for {} minutes",
+            session.stream_ids.len(),
+            minutes
+        );
+        state.current = Some(Running {
+            id,
+            session: session.clone(),
+            timer,
+        });
+        Ok(session)
+    }
+
+    /// End the running session early, returning its report, or `None` if
+    /// no session was running.
+    pub fn stop<C: Cache>(&self, cache: &C, events: Option<&EventBus>) -> Option<FocusReport> {
+        let running = self.state.lock().unwrap().current.take()?;
+        running.timer.abort();
+        Some(finish(&self.state, cache, events, running.session, false))
+    }
+
+    /// The running session and the report of the last one.
+    pub fn status(&self) -> FocusStatus {
+        let state = self.state.lock().unwrap();
+        FocusStatus {
+            session: state.current.as_ref().map(|r| r.session.clone()),
+            last: state.last.clone(),
+        }
+    }
+}
+
+impl Drop for Focus {
+    fn drop(&mut self) {
+        if let Some(running) = self.state.lock().unwrap().current.take() {
+            running.timer.abort();
+        }
+    }
+}
+
+/// Report on an ended session, publish the report, and keep it as the
+/// last one.
+fn finish<C: Cache + ?Sized>(
+    state: &Mutex<FocusState>,
+    cache: &C,
+    events: Option<&EventBus>,
+    session: FocusSession,
+    completed: bool,
+) -> FocusReport {
+    let mut report = FocusReport {
+        stream_ids: session.stream_ids,
+        started_at: session.started_at,
+        ended_at: Utc::now(),
+        completed,
+        read: 0,
+        saved: 0,
+    };
+    match cache.get_reading_stats(session.started_at) {
+        Ok(stats) => {
+            for stream in stats
+                .streams
+                .iter()
+                .filter(|s| report.stream_ids.contains(&s.stream_id))
+            {
+                report.read += stream.read;
+                report.saved += stream.saved;
+            }
+        }
+        Err(e) => warn!("Failed to count items processed in focus session: {}", e),
+    }
+
+    info!(
+        "Focus session ended: {} read, {} saved",
+        report.read, report.saved
+    );
+    if let Some(events) = events {
+        events.publish(DaemonEvent::FocusEnded {
+            stream_ids: report.stream_ids.clone(),
+            completed,
+            read: report.read,
+            saved: report.saved,
+            timestamp: report.ended_at,
+        });
+    }
+    state.lock().unwrap().last = Some(report.clone());
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use scryforge_provider_core::{Item, ItemContent, ItemId, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn cache_with_items() -> Result<(TempDir, Arc<SqliteCache>)> {
+        let dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&dir.path().join("cache.db"))?;
+        let streams = ["rss:feed:a", "rss:feed:b"].map(|id| Stream {
+            id: StreamId(id.to_string()),
+            name: id.to_string(),
+            provider_id: "rss".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        });
+        cache.upsert_streams(&streams)?;
+        let items: Vec<Item> = [
+            ("a1", "rss:feed:a"),
+            ("a2", "rss:feed:a"),
+            ("b1", "rss:feed:b"),
+        ]
+        .into_iter()
+        .map(|(id, stream_id)| Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream_id.to_string()),
+            title: id.to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        })
+        .collect();
+        cache.upsert_items(&items)?;
+        Ok((dir, Arc::new(cache)))
+    }
+
+    #[tokio::test]
+    async fn test_stop_reports_items_processed_in_session_streams() -> Result<()> {
+        let (_dir, cache) = cache_with_items()?;
+        let focus = Focus::new();
+
+        let session = focus.start(
+            vec!["rss:feed:a".to_string()],
+            DEFAULT_FOCUS_MINUTES,
+            Arc::clone(&cache),
+            None,
+        )?;
+        assert_eq!(session.ends_at - session.started_at, Duration::minutes(25));
+        assert!(focus
+            .start(vec!["rss:feed:b".to_string()], 5, Arc::clone(&cache), None)
+            .is_err());
+
+        cache.mark_read(&ItemId("a1".to_string()), true)?;
+        cache.mark_starred(&ItemId("a2".to_string()), true)?;
+        cache.mark_read(&ItemId("b1".to_string()), true)?;
+
+        let report = focus.stop(cache.as_ref(), None).unwrap();
+        assert!(!report.completed);
+        assert_eq!((report.read, report.saved), (1, 1));
+
+        let status = focus.status();
+        assert!(status.session.is_none());
+        assert_eq!(status.last, Some(report));
+        assert!(focus.stop(cache.as_ref(), None).is_none());
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_session_ends_when_time_is_up() -> Result<()> {
+        let (_dir, cache) = cache_with_items()?;
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let focus = Focus::new();
+
+        focus.start(
+            vec!["rss:feed:b".to_string()],
+            1,
+            Arc::clone(&cache),
+            Some(events),
+        )?;
+        cache.mark_read(&ItemId("b1".to_string()), true)?;
+        tokio::time::sleep(std::time::Duration::from_secs(61)).await;
+
+        match rx.recv().await? {
+            DaemonEvent::FocusEnded {
+                completed, read, ..
+            } => {
+                assert!(completed);
+                assert_eq!(read, 1);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert!(focus.status().session.is_none());
+        assert!(focus.status().last.unwrap().completed);
+        Ok(())
+    }
+}
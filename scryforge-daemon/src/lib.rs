@@ -2,10 +2,53 @@
 //!
 //! This module exports the internal components of the daemon for testing purposes.
 
+pub mod alerts;
 pub mod api;
+pub mod attachments;
+pub mod audit;
+pub mod backup;
+pub mod bandwidth;
+pub mod budget;
 pub mod cache;
+#[cfg(feature = "web")]
+pub mod capture;
+pub mod changes;
 pub mod config;
+pub mod contacts;
+pub mod dedup;
+pub mod digest;
+pub mod doctor;
+pub mod downloads;
+pub mod encryption;
+pub mod events;
+pub mod export;
+pub mod focus;
+pub mod links;
+pub mod markup;
+pub mod mute;
+pub mod newsletters;
+pub mod notes;
+pub mod opener;
+pub mod outbox;
+pub mod player;
 pub mod plugin;
+pub mod queue;
+pub mod ranking;
+pub mod reading_time;
 pub mod registry;
+pub mod relations;
+pub mod reminders;
+pub mod save_router;
+pub mod search;
+#[cfg(feature = "search-index")]
+pub mod search_index;
+pub mod share;
+pub mod summarize;
 pub mod sync;
+pub mod takeout;
+pub mod triage;
+pub mod tts;
 pub mod unified;
+#[cfg(feature = "web")]
+pub mod web;
+pub mod youtube_rss;
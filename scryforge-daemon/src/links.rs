@@ -0,0 +1,478 @@
+//! Links found in an item's body, each to be followed on its own.
+//!
+//! Newsletters are mostly links. [`extract`] lists the URLs in an item's
+//! content in the order they appear, with the text they were linked from
+//! (an HTML anchor's text or a Markdown link's label). Clients act on each
+//! one with `items.open_link` and `items.bookmark_link`.
+//!
+//! Page titles cost a request per link, so they are fetched only when a
+//! client asks for one with `links.title`, and [`LinkTitles`] remembers them
+//! for the next listing. Only links of cached items are fetched, through the
+//! proxy of the item's provider.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use scryforge_provider_core::http::{check_reachable, client_builder, ProxyConfig};
+use scryforge_provider_core::{decode_entities, strip_tags, Item, ItemContent};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::relations::{find_urls, link_key};
+
+/// Bytes of a page read looking for its title.
+const TITLE_SCAN_BYTES: usize = 64 * 1024;
+
+/// Characters kept of a page title.
+const MAX_TITLE_CHARS: usize = 200;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Titles [`LinkTitles`] remembers; the oldest are forgotten first.
+const MAX_TITLES: usize = 2000;
+
+/// A link in an item's body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemLink {
+    pub url: String,
+    /// The text the link was made from, if it wasn't a bare URL
+    pub text: Option<String>,
+    /// The linked page's title, once fetched
+    pub title: Option<String>,
+}
+
+/// The links in `item`'s body, in order. Each page is listed once, however
+/// often it's linked; the item's own URL and embedded images are left out.
+pub fn extract(item: &Item) -> Vec<ItemLink> {
+    let own = item.url.as_deref().and_then(link_key);
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+
+    for body in bodies(&item.content) {
+        let texts = link_texts(body);
+        for url in find_urls(body) {
+            // `find_urls` yields slices of `body`
+            let offset = url.as_ptr() as usize - body.as_ptr() as usize;
+            if is_image_source(&body[..offset]) {
+                continue;
+            }
+            let url = url.replace("&amp;", "&");
+            let key = link_key(&url).unwrap_or_else(|| url.clone());
+            if own.as_ref() == Some(&key) || !seen.insert(key) {
+                continue;
+            }
+            links.push(ItemLink {
+                text: texts.get(&url).cloned(),
+                url,
+                title: None,
+            });
+        }
+    }
+    links
+}
+
+/// The raw bodies of `content` that may hold links, HTML left as is.
+fn bodies(content: &ItemContent) -> Vec<&str> {
+    match content {
+        ItemContent::Text(text) | ItemContent::Markdown(text) | ItemContent::Html(text) => {
+            vec![text.as_str()]
+        }
+        ItemContent::Email {
+            body_text,
+            body_html,
+            ..
+        } => body_html
+            .iter()
+            .chain(body_text)
+            .map(String::as_str)
+            .collect(),
+        ItemContent::Article {
+            summary,
+            full_content,
+        } => full_content
+            .iter()
+            .chain(summary)
+            .map(String::as_str)
+            .collect(),
+        ItemContent::Video { description, .. } => vec![description.as_str()],
+        ItemContent::Message { text, .. } => vec![text.as_str()],
+        ItemContent::Task { body, .. } | ItemContent::Generic { body } => {
+            body.iter().map(String::as_str).collect()
+        }
+        ItemContent::Event { description, .. }
+        | ItemContent::Bookmark { description }
+        | ItemContent::Patch { description, .. } => {
+            description.iter().map(String::as_str).collect()
+        }
+        ItemContent::Track { .. } | ItemContent::Image { .. } | ItemContent::Gallery { .. } => {
+            Vec::new()
+        }
+    }
+}
+
+/// Whether a URL preceded by `before` is an `<img src>` or similar, which
+/// shows in the body rather than linking out of it.
+fn is_image_source(before: &str) -> bool {
+    let before = before.trim_end_matches(['"', '\'']).trim_end();
+    let Some(before) = before.strip_suffix('=') else {
+        return false;
+    };
+    let before = before.trim_end().to_ascii_lowercase();
+    before.ends_with("src") || before.ends_with("srcset")
+}
+
+/// The text of each HTML anchor and Markdown link in `body`, by URL. A URL
+/// linked more than once keeps its first text.
+pub(crate) fn link_texts(body: &str) -> HashMap<String, String> {
+    let mut texts = HashMap::new();
+
+    // ASCII lowercasing keeps byte offsets
+    let lower = body.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(at) = lower[from..].find("<a ") {
+        let start = from + at;
+        let Some(tag_end) = lower[start..].find('>').map(|i| start + i) else {
+            break;
+        };
+        let Some(close) = lower[tag_end..].find("</a>").map(|i| tag_end + i) else {
+            break;
+        };
+        if let Some(href) = attribute(&body[start..tag_end], "href") {
+            let text = collapse(&decode_entities(&strip_tags(&body[tag_end + 1..close], "")));
+            if !text.is_empty() {
+                texts.entry(decode_entities(href)).or_insert(text);
+            }
+        }
+        from = close;
+    }
+
+    for (at, _) in body.match_indices("](") {
+        let Some(open) = body[..at].rfind('[') else {
+            continue;
+        };
+        let label = body[open + 1..at].trim_matches(['*', '_', '`', ' ']);
+        let url = body[at + 2..]
+            .split([')', ' ', '\n'])
+            .next()
+            .unwrap_or_default();
+        if !label.is_empty() && !label.contains('\n') && url.starts_with("http") {
+            texts
+                .entry(url.to_string())
+                .or_insert_with(|| collapse(label));
+        }
+    }
+
+    texts
+}
+
+/// The value of attribute `name` in an HTML start tag.
+pub(crate) fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lower = tag.to_ascii_lowercase();
+    let at = lower.match_indices(name).map(|(at, _)| at).find(|at| {
+        lower[..*at].ends_with(char::is_whitespace)
+            && lower[at + name.len()..].trim_start().starts_with('=')
+    })?;
+    let value = tag[at + name.len()..].trim_start()[1..].trim_start();
+    match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next(),
+        _ => value.split(|c: char| c.is_whitespace() || c == '>').next(),
+    }
+}
+
+pub(crate) fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The title of an HTML page: its `<title>`, or else its `og:title`.
+pub fn page_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let cleaned =
+        |title: &str| Some(collapse(&decode_entities(title))).filter(|title| !title.is_empty());
+
+    let title = lower
+        .find("<title")
+        .and_then(|at| Some(at + lower[at..].find('>')? + 1))
+        .and_then(|start| cleaned(&html[start..start + lower[start..].find("</title")?]))
+        .or_else(|| {
+            let mut from = 0;
+            while let Some(at) = lower[from..].find("<meta") {
+                let start = from + at;
+                let end = lower[start..].find('>').map_or(lower.len(), |i| start + i);
+                let tag = &html[start..end];
+                if attribute(tag, "property").is_some_and(|p| p.eq_ignore_ascii_case("og:title")) {
+                    return attribute(tag, "content").and_then(cleaned);
+                }
+                from = end;
+            }
+            None
+        })?;
+
+    Some(match title.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((end, _)) => format!("{}…", &title[..end]),
+        None => title,
+    })
+}
+
+/// Titles of linked pages, fetched on first request and remembered.
+#[derive(Debug)]
+pub struct LinkTitles {
+    http: reqwest::Client,
+    /// Clients of the providers with a proxy, by provider ID
+    proxied: HashMap<String, (reqwest::Client, ProxyConfig)>,
+    titles: Mutex<RememberedTitles>,
+}
+
+#[derive(Debug, Default)]
+struct RememberedTitles {
+    /// `None` for pages fetched without finding a title
+    by_url: HashMap<String, Option<String>>,
+    /// URLs in the order they were first fetched
+    order: VecDeque<String>,
+}
+
+impl RememberedTitles {
+    fn insert(&mut self, url: &str, title: Option<String>) {
+        if self.by_url.insert(url.to_string(), title).is_some() {
+            return;
+        }
+        self.order.push_back(url.to_string());
+        while self.order.len() > MAX_TITLES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_url.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl Default for LinkTitles {
+    fn default() -> Self {
+        Self {
+            http: title_client(None).unwrap_or_default(),
+            proxied: HashMap::new(),
+            titles: Mutex::default(),
+        }
+    }
+}
+
+impl LinkTitles {
+    /// Fetch the links of each provider in `proxies` through its proxy, and
+    /// other links directly.
+    pub fn with_proxies(proxies: &HashMap<String, ProxyConfig>) -> anyhow::Result<Self> {
+        let mut titles = Self::default();
+        for (provider_id, proxy) in proxies {
+            titles.proxied.insert(
+                provider_id.clone(),
+                (title_client(Some(proxy))?, proxy.clone()),
+            );
+        }
+        Ok(titles)
+    }
+
+    /// The title already fetched for `url`, without fetching it.
+    pub fn cached(&self, url: &str) -> Option<String> {
+        self.titles
+            .lock()
+            .unwrap()
+            .by_url
+            .get(url)
+            .cloned()
+            .flatten()
+    }
+
+    /// The title of the page at `url`, linked from an item of `provider_id`,
+    /// fetched the first time it's asked for. `None` when the page has none;
+    /// pages that fail to load are tried again next time.
+    pub async fn title(&self, provider_id: &str, url: &str) -> Option<String> {
+        if let Some(title) = self.titles.lock().unwrap().by_url.get(url) {
+            return title.clone();
+        }
+        match self.fetch(provider_id, url).await {
+            Ok(title) => {
+                self.titles.lock().unwrap().insert(url, title.clone());
+                title
+            }
+            Err(e) => {
+                debug!("Failed to fetch title of {}: {:#}", url, e);
+                None
+            }
+        }
+    }
+
+    async fn fetch(&self, provider_id: &str, url: &str) -> anyhow::Result<Option<String>> {
+        let (http, proxy) = match self.proxied.get(provider_id) {
+            Some((http, proxy)) => (http, Some(proxy)),
+            None => (&self.http, None),
+        };
+        check_reachable(url, proxy)?;
+
+        let mut response = http.get(url).send().await?.error_for_status()?;
+        let is_html = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_none_or(|value| value.contains("html"));
+        if !is_html {
+            return Ok(None);
+        }
+
+        let mut head = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            head.extend_from_slice(&chunk);
+            if head.len() >= TITLE_SCAN_BYTES || contains(&head, b"</title") {
+                break;
+            }
+        }
+        Ok(page_title(&String::from_utf8_lossy(&head)))
+    }
+}
+
+fn title_client(proxy: Option<&ProxyConfig>) -> anyhow::Result<reqwest::Client> {
+    Ok(client_builder(proxy)?.timeout(FETCH_TIMEOUT).build()?)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window.eq_ignore_ascii_case(needle))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemId, StreamId};
+
+    fn item(url: Option<&str>, content: ItemContent) -> Item {
+        Item {
+            id: ItemId("email:msg:1".to_string()),
+            stream_id: StreamId("email:folder:inbox".to_string()),
+            title: "Weekly".to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: url.map(str::to_string),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_html_newsletter() {
+        let html = r#"<p>This week:</p>
+<img src="https://cdn.example.com/banner.png">
+<a href="https://blog.example.com/post?a=1&amp;b=2"><b>A post</b>
+  about &lt;things&gt;</a>,
+<a class="x" href='https://example.org/paper.pdf'>the paper</a>,
+again <a href="https://blog.example.com/post?a=1&amp;b=2">here</a>,
+and https://news.example.net/story.
+<a href="https://example.com/weekly/42">View online</a>"#;
+        let links = extract(&item(
+            Some("https://example.com/weekly/42"),
+            ItemContent::Html(html.to_string()),
+        ));
+
+        assert_eq!(
+            links,
+            vec![
+                ItemLink {
+                    url: "https://blog.example.com/post?a=1&b=2".to_string(),
+                    text: Some("A post about <things>".to_string()),
+                    title: None,
+                },
+                ItemLink {
+                    url: "https://example.org/paper.pdf".to_string(),
+                    text: Some("the paper".to_string()),
+                    title: None,
+                },
+                ItemLink {
+                    url: "https://news.example.net/story".to_string(),
+                    text: None,
+                    title: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_markdown_and_email() {
+        let markdown = "See [**the docs**](https://docs.example.com/guide \"Guide\") and \
+                        <https://example.com/a>.";
+        let urls: Vec<(String, Option<String>)> =
+            extract(&item(None, ItemContent::Markdown(markdown.to_string())))
+                .into_iter()
+                .map(|link| (link.url, link.text))
+                .collect();
+        assert_eq!(
+            urls,
+            vec![
+                (
+                    "https://docs.example.com/guide".to_string(),
+                    Some("the docs".to_string())
+                ),
+                ("https://example.com/a".to_string(), None),
+            ]
+        );
+
+        // Both bodies of an email are read, the HTML first
+        let email = ItemContent::Email {
+            subject: "Weekly".to_string(),
+            body_text: Some("Read https://example.com/a and https://example.com/b".to_string()),
+            body_html: Some(r#"<a href="https://example.com/a">A</a>"#.to_string()),
+            snippet: String::new(),
+        };
+        let urls: Vec<String> = extract(&item(None, email))
+            .into_iter()
+            .map(|link| link.url)
+            .collect();
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+
+    #[test]
+    fn test_page_title() {
+        assert_eq!(
+            page_title("<html><head><TITLE>\n  Tom &amp; Jerry\n</TITLE>").as_deref(),
+            Some("Tom & Jerry")
+        );
+        // An empty <title> falls back to og:title
+        assert_eq!(
+            page_title(r#"<meta content="Shared" property="og:title"><title> </title>"#).as_deref(),
+            Some("Shared")
+        );
+        assert_eq!(page_title("<p>no title</p>"), None);
+    }
+
+    #[test]
+    fn test_remembered_titles_are_bounded() {
+        let mut titles = RememberedTitles::default();
+        for i in 0..MAX_TITLES + 10 {
+            titles.insert(&format!("https://example.com/{}", i), None);
+        }
+        titles.insert("https://example.com/20", Some("Again".to_string()));
+
+        assert_eq!(titles.by_url.len(), MAX_TITLES);
+        assert_eq!(titles.order.len(), MAX_TITLES);
+        assert!(!titles.by_url.contains_key("https://example.com/9"));
+        assert_eq!(
+            titles.by_url["https://example.com/20"].as_deref(),
+            Some("Again")
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        assert_eq!(
+            attribute(r#"<a data-href="x" HREF = "y">"#, "href"),
+            Some("y")
+        );
+        assert_eq!(attribute("<a href=z>", "href"), Some("z"));
+        assert_eq!(attribute("<a title=href>", "href"), None);
+    }
+}
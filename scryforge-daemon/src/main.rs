@@ -49,21 +49,39 @@
 //! RUST_LOG=debug cargo run --bin scryforge-daemon
 //! ```
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::sync::Arc;
-use tracing::{info, Level};
+use tokio::sync::RwLock;
+use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
 // Use modules from the library crate
+use scryforge_daemon::alerts;
 use scryforge_daemon::api;
-use scryforge_daemon::cache::SqliteCache;
+use scryforge_daemon::api::handlers::ApiImpl;
+use scryforge_daemon::cache::{spawn_eviction, SqliteCache};
 use scryforge_daemon::config::Config;
+use scryforge_daemon::contacts::Contacts;
+use scryforge_daemon::digest::{self, DigestBuilder};
+use scryforge_daemon::downloads::DownloadManager;
+use scryforge_daemon::encryption;
+use scryforge_daemon::events::EventBus;
+use scryforge_daemon::links::LinkTitles;
+use scryforge_daemon::mute::MuteFilter;
+use scryforge_daemon::notes::NotesWriter;
+use scryforge_daemon::opener::Opener;
+use scryforge_daemon::outbox;
+use scryforge_daemon::player::Player;
 use scryforge_daemon::plugin::PluginManager;
-use scryforge_daemon::registry::ProviderRegistry;
+use scryforge_daemon::registry::{self, ProviderRegistry};
+use scryforge_daemon::reminders;
+use scryforge_daemon::save_router::SaveRouter;
+#[cfg(feature = "search-index")]
+use scryforge_daemon::search_index::{self, SearchIndex};
+use scryforge_daemon::share::Sharer;
+use scryforge_daemon::summarize;
 use scryforge_daemon::sync::SyncManager;
-
-// Sigilforge client for OAuth token fetching
-use scryforge_sigilforge_client::{MockTokenFetcher, SigilforgeClient, TokenFetcher};
+use scryforge_daemon::tts::SpeechQueue;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -105,32 +123,18 @@ async fn main() -> Result<()> {
         );
     }
 
-    // Initialize provider registry
-    let mut registry = ProviderRegistry::new();
-
-    // Load dummy provider for testing (provides demo data)
-    info!("Loading dummy provider...");
-    registry.register(provider_dummy::DummyProvider::new());
-
-    // Initialize Sigilforge client for OAuth token fetching
-    let token_fetcher: Arc<dyn TokenFetcher + Send + Sync> = {
-        let client = SigilforgeClient::with_default_path();
-        if client.is_available() {
-            info!("Sigilforge daemon available - OAuth providers enabled");
-            Arc::new(client)
-        } else {
-            info!("Sigilforge not available - YouTube provider will use mock tokens");
-            Arc::new(MockTokenFetcher::empty())
-        }
-    };
+    // Load the at-rest encryption key before anything touches stored data
+    let cipher = encryption::from_config(&config)
+        .await
+        .context("Providers have encrypt_at_rest set but the encryption key is unavailable")?;
 
-    // Load YouTube provider
-    info!("Loading YouTube provider...");
-    let youtube_provider = provider_youtube::YouTubeProvider::new(
-        token_fetcher.clone(),
-        "personal".to_string(),
+    // Initialize provider registry with the built-in providers, using
+    // Sigilforge for OAuth tokens when it is running
+    let mut registry = ProviderRegistry::with_builtin_providers(
+        &config,
+        registry::default_token_fetcher(),
+        cipher.as_ref(),
     );
-    registry.register(youtube_provider);
 
     // Register plugin-based providers
     plugin_manager.register_providers(&mut registry);
@@ -148,7 +152,7 @@ async fn main() -> Result<()> {
         info!("Dummy provider loaded: {}", provider.name());
 
         // Perform health check
-        match provider.health_check().await {
+        match registry.guard("dummy", provider.health_check()).await {
             Ok(health) => {
                 info!(
                     "Provider health check: healthy={}, message={:?}",
@@ -167,26 +171,179 @@ async fn main() -> Result<()> {
     let cache = match SqliteCache::open_at(&cache_path) {
         Ok(c) => {
             info!("Cache initialized successfully");
-            Arc::new(c)
+            c
         }
         Err(e) => {
             info!("Failed to initialize cache: {}", e);
             return Err(e);
         }
     };
+    let cache = match cipher {
+        Some(cipher) => {
+            let providers = encryption::encrypted_providers(&config);
+            info!("Encrypting cached items of: {}", providers.join(", "));
+            cache.with_encryption(cipher, providers)
+        }
+        None => cache,
+    };
+    cache.apply_encryption()?;
+    let cache = Arc::new(cache);
+
+    // Apply the cache retention limits and stream policies now and then hourly
+    let eviction_handle = spawn_eviction(
+        Arc::clone(&cache),
+        config.cache.clone(),
+        config.streams.clone(),
+    );
 
     // Wrap registry in Arc for sharing with sync manager and API
     let registry = Arc::new(registry);
 
+    // Shared event bus for live client notifications
+    let events = EventBus::default();
+
     // Start sync manager with background sync tasks
-    let mut sync_manager = SyncManager::new(config.clone(), Arc::clone(&registry), Arc::clone(&cache));
+    let mut sync_manager =
+        SyncManager::new(config.clone(), Arc::clone(&registry), Arc::clone(&cache))
+            .with_event_bus(events.clone());
     match sync_manager.start().await {
         Ok(_) => info!("Sync manager started successfully"),
         Err(e) => info!("Sync manager startup: {}", e),
     }
+    let sync_manager = Arc::new(RwLock::new(sync_manager));
 
     // Start the JSON-RPC API server
-    let (server_handle, addr) = api::start_server().await?;
+    let mut api =
+        ApiImpl::with_sync_manager_and_cache(Arc::clone(&sync_manager), Arc::clone(&cache))
+            .with_event_bus(events.clone())
+            .with_ranking_config(config.ranking.clone())
+            .with_mute_filter(MuteFilter::from_config(&config.mute))
+            .with_digest_config(config.digest.clone());
+    if let Some(notes) = NotesWriter::from_config(&config.notes) {
+        api = api.with_notes(notes);
+    }
+    let save_router = SaveRouter::from_config(&config.save);
+    if !save_router.is_empty() {
+        api = api.with_save_router(save_router);
+    }
+    match summarize::from_config(&config.summarizer) {
+        Ok(Some(summarizer)) => {
+            info!("Summarization enabled ({})", summarizer.name());
+            api = api.with_summarizer(summarizer, config.summarizer.clone());
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Summarization disabled: {:#}", e),
+    }
+    if let Some(speech) = SpeechQueue::from_config(&config.tts) {
+        api = api.with_speech_queue(speech);
+    }
+    api = api.with_opener(Opener::from_config(&config.opener));
+    let proxies = config.provider_proxies()?;
+    api = api.with_player(Player::from_config(&config.player).with_proxies(&proxies));
+    api = api.with_download_manager(
+        DownloadManager::from_config(&config.downloads, config.download_dir()?)
+            .with_proxies(&proxies)?,
+    );
+    api = api.with_export_dir(config.export_dir()?);
+    api = api.with_link_titles(LinkTitles::with_proxies(&proxies)?);
+    api = api.with_sharer(Sharer::new(config.share.clone()));
+
+    // Refresh the CardDAV address book in the background
+    let contacts = Contacts::from_config(&config.contacts);
+    if contacts.can_import() {
+        let contacts = contacts.clone();
+        let cache = Arc::clone(&cache);
+        tokio::spawn(async move {
+            match contacts.import(cache.as_ref()).await {
+                Ok(count) => info!("Imported {} contacts from CardDAV", count),
+                Err(e) => warn!("CardDAV import failed: {:#}", e),
+            }
+        });
+    }
+    api = api.with_contacts(contacts);
+
+    // Keep a full-text index of the cache for search, if enabled
+    #[cfg(feature = "search-index")]
+    let index_handle = if config.search.index {
+        let path = config.search_index_path()?;
+        match SearchIndex::open(&path) {
+            Ok(index) => {
+                info!("Search index at {}", path.display());
+                let index = Arc::new(index);
+                api = api.with_search_index(Arc::clone(&index));
+                Some(search_index::spawn_indexer(
+                    index,
+                    Arc::clone(&cache),
+                    events.clone(),
+                ))
+            }
+            Err(e) => {
+                warn!("Search index disabled: {:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    #[cfg(not(feature = "search-index"))]
+    if config.search.index {
+        warn!("Search index disabled: the daemon was built without the search-index feature");
+    }
+
+    let (server_handle, addr) = api::start_server_with(api, &config.daemon).await?;
+
+    // Start the read-only web dashboard, if enabled
+    #[cfg(feature = "web")]
+    let web_handle = if config.web.enabled {
+        let (handle, _) = scryforge_daemon::web::start_web_server(
+            Arc::clone(&cache),
+            &config.web,
+            config.daemon.clients.clone(),
+            MuteFilter::from_config(&config.mute),
+        )
+        .await?;
+        Some(handle)
+    } else {
+        None
+    };
+
+    // Accept pages saved from the browser, if enabled
+    #[cfg(feature = "web")]
+    let capture_handle = if config.capture.enabled {
+        let (handle, _) = scryforge_daemon::capture::start_capture_server(
+            Arc::clone(&registry),
+            &config.capture,
+            config.daemon.clients.clone(),
+        )
+        .await?;
+        Some(handle)
+    } else {
+        None
+    };
+
+    // Generate digests on a schedule, if enabled
+    let digest_handle = if config.digest.enabled {
+        let builder = DigestBuilder::new(Arc::clone(&cache), config.digest.items_per_provider)
+            .with_ranking_config(config.ranking.clone())
+            .with_mute_filter(MuteFilter::from_config(&config.mute));
+        Some(digest::spawn_scheduler(
+            builder,
+            config.digest.clone(),
+            config.digest_dir()?,
+        ))
+    } else {
+        None
+    };
+
+    // Notify about item reminders as they come due
+    let reminders_handle =
+        reminders::spawn_scheduler(Arc::clone(&cache), config.reminders.clone(), events.clone());
+
+    // Re-run saved searches after syncs and alert on new matches
+    let alerts_handle = alerts::spawn_runner(Arc::clone(&cache), config.reminders.clone(), events);
+
+    // Send queued provider actions once their undo window has passed
+    let outbox_handle = outbox::spawn_flusher(Arc::clone(&cache), Arc::clone(&registry));
 
     info!("Daemon startup complete");
     info!("Listening on: {}", addr);
@@ -198,11 +355,35 @@ async fn main() -> Result<()> {
     info!("Shutting down...");
 
     // Shutdown sync manager gracefully
-    sync_manager.shutdown().await;
+    sync_manager.write().await.shutdown().await;
 
     // Stop the server gracefully
     server_handle.stop()?;
 
+    #[cfg(feature = "web")]
+    if let Some(handle) = web_handle {
+        handle.abort();
+    }
+
+    #[cfg(feature = "web")]
+    if let Some(handle) = capture_handle {
+        handle.abort();
+    }
+
+    if let Some(handle) = digest_handle {
+        handle.abort();
+    }
+
+    #[cfg(feature = "search-index")]
+    if let Some(handle) = index_handle {
+        handle.abort();
+    }
+
+    reminders_handle.abort();
+    alerts_handle.abort();
+    outbox_handle.abort();
+    eviction_handle.abort();
+
     info!("Daemon stopped");
     Ok(())
 }
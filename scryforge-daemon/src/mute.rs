@@ -0,0 +1,369 @@
+//! Keyword muting and content filters.
+//!
+//! A [`MuteFilter`] is built from the `[mute]` config section and decides
+//! whether an item should be hidden from aggregated views. Items can be muted
+//! by keyword (title or content), author, link domain, subreddit, or NSFW
+//! flag. Providers can extend or replace the global rules.
+//!
+//! Muted items stay in the cache; the unified feed hides them, and can list
+//! only the muted ones (with [`MUTED_REASON_KEY`] metadata) for review.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use scryforge_provider_core::url::url_host;
+use scryforge_provider_core::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{MuteConfig, MuteRules};
+
+/// Metadata key describing why an item was muted, set in the review view.
+pub const MUTED_REASON_KEY: &str = "muted_reason";
+
+/// Why an item was muted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", content = "value", rename_all = "snake_case")]
+pub enum MuteReason {
+    Keyword(String),
+    Author(String),
+    Domain(String),
+    Subreddit(String),
+    Nsfw,
+}
+
+impl fmt::Display for MuteReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MuteReason::Keyword(keyword) => write!(f, "keyword \"{}\"", keyword),
+            MuteReason::Author(author) => write!(f, "author {}", author),
+            MuteReason::Domain(domain) => write!(f, "domain {}", domain),
+            MuteReason::Subreddit(subreddit) => write!(f, "r/{}", subreddit),
+            MuteReason::Nsfw => write!(f, "NSFW"),
+        }
+    }
+}
+
+/// Mute rules with entries normalized for matching.
+#[derive(Debug, Clone, Default)]
+struct CompiledRules {
+    keywords: Vec<String>,
+    authors: Vec<String>,
+    domains: Vec<String>,
+    subreddits: Vec<String>,
+    hide_nsfw: bool,
+}
+
+impl CompiledRules {
+    fn new(rules: &MuteRules) -> Self {
+        let mut compiled = Self::default();
+        compiled.extend(rules);
+        compiled.hide_nsfw = rules.hide_nsfw.unwrap_or(false);
+        compiled
+    }
+
+    fn extend(&mut self, rules: &MuteRules) {
+        let lower = |entries: &[String]| {
+            entries
+                .iter()
+                .map(|entry| entry.trim().to_lowercase())
+                .collect::<Vec<_>>()
+        };
+        self.keywords.extend(lower(&rules.keywords));
+        self.authors.extend(lower(&rules.authors));
+        self.domains.extend(
+            lower(&rules.domains)
+                .into_iter()
+                .map(|domain| domain.trim_start_matches("www.").to_string()),
+        );
+        self.subreddits.extend(
+            lower(&rules.subreddits)
+                .into_iter()
+                .map(|name| strip_subreddit_prefix(&name).to_string()),
+        );
+    }
+
+    fn is_empty(&self) -> bool {
+        self.keywords.is_empty()
+            && self.authors.is_empty()
+            && self.domains.is_empty()
+            && self.subreddits.is_empty()
+            && !self.hide_nsfw
+    }
+
+    fn check(&self, item: &Item) -> Option<MuteReason> {
+        if self.hide_nsfw && is_nsfw(item) {
+            return Some(MuteReason::Nsfw);
+        }
+
+        if let Some(ref author) = item.author {
+            let names = [Some(&author.name), author.email.as_ref()];
+            for name in names.into_iter().flatten() {
+                let name = name.to_lowercase();
+                if let Some(muted) = self.authors.iter().find(|muted| **muted == name) {
+                    return Some(MuteReason::Author(muted.clone()));
+                }
+            }
+        }
+
+        if let Some(subreddit) = item.metadata.get("subreddit") {
+            let subreddit = subreddit.to_lowercase();
+            if let Some(muted) = self.subreddits.iter().find(|muted| **muted == subreddit) {
+                return Some(MuteReason::Subreddit(muted.clone()));
+            }
+        }
+
+        if let Some(host) = item.url.as_deref().and_then(url_host) {
+            if let Some(muted) = self
+                .domains
+                .iter()
+                .find(|muted| host == **muted || host.ends_with(&format!(".{}", muted)))
+            {
+                return Some(MuteReason::Domain(muted.clone()));
+            }
+        }
+
+        if !self.keywords.is_empty() {
+            let mut text = item.title.to_lowercase();
+            if let Some(body) = item.content.plain_text() {
+                text.push('\n');
+                text.push_str(&body.to_lowercase());
+            }
+            if let Some(keyword) = self.keywords.iter().find(|k| text.contains(k.as_str())) {
+                return Some(MuteReason::Keyword(keyword.clone()));
+            }
+        }
+
+        None
+    }
+}
+
+/// Decides which items are hidden by the mute list.
+#[derive(Debug, Clone, Default)]
+pub struct MuteFilter {
+    global: CompiledRules,
+    providers: HashMap<String, CompiledRules>,
+}
+
+impl MuteFilter {
+    /// Build a filter from the `[mute]` config section.
+    pub fn from_config(config: &MuteConfig) -> Self {
+        let global = CompiledRules::new(&config.rules);
+
+        let providers = config
+            .providers
+            .iter()
+            .map(|(provider_id, overrides)| {
+                let rules = if overrides.inherit {
+                    let mut rules = global.clone();
+                    rules.extend(&overrides.rules);
+                    rules.hide_nsfw = overrides.rules.hide_nsfw.unwrap_or(global.hide_nsfw);
+                    rules
+                } else {
+                    CompiledRules::new(&overrides.rules)
+                };
+                (provider_id.clone(), rules)
+            })
+            .collect();
+
+        Self { global, providers }
+    }
+
+    /// Whether the filter mutes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.providers.values().all(CompiledRules::is_empty)
+    }
+
+    /// Why `item` is muted, or `None` if it should be shown.
+    ///
+    /// The provider is taken from the item's `provider_id` metadata, falling
+    /// back to the item ID prefix.
+    pub fn check(&self, item: &Item) -> Option<MuteReason> {
+        let provider_id = item
+            .metadata
+            .get("provider_id")
+            .map(String::as_str)
+            .unwrap_or_else(|| item.id.as_str().split(':').next().unwrap_or_default());
+
+        self.providers
+            .get(provider_id)
+            .unwrap_or(&self.global)
+            .check(item)
+    }
+}
+
+/// Whether a provider flagged the item as NSFW.
+fn is_nsfw(item: &Item) -> bool {
+    ["over_18", "nsfw"]
+        .iter()
+        .any(|key| item.metadata.get(*key).map(String::as_str) == Some("true"))
+        || item.tags.iter().any(|tag| tag.eq_ignore_ascii_case("nsfw"))
+}
+
+fn strip_subreddit_prefix(name: &str) -> &str {
+    let name = name.trim_start_matches('/');
+    name.strip_prefix("r/").unwrap_or(name)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderMuteRules;
+    use scryforge_provider_core::{Author, ItemContent, ItemId, StreamId};
+
+    fn item(id: &str, title: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("test:feed:1".to_string()),
+            title: title.to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn filter(rules: MuteRules, providers: &[(&str, ProviderMuteRules)]) -> MuteFilter {
+        MuteFilter::from_config(&MuteConfig {
+            rules,
+            providers: providers
+                .iter()
+                .map(|(id, rules)| (id.to_string(), rules.clone()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_global_rules() {
+        let filter = filter(
+            MuteRules {
+                keywords: vec!["Episode Recap".to_string(), "spoiler".to_string()],
+                authors: vec!["AutoModerator".to_string()],
+                domains: vec!["www.example.com".to_string()],
+                ..MuteRules::default()
+            },
+            &[],
+        );
+        assert!(!filter.is_empty());
+
+        assert_eq!(
+            filter.check(&item("rss:1", "No SPOILERS here")),
+            Some(MuteReason::Keyword("spoiler".to_string()))
+        );
+
+        let mut by_author = item("reddit:1", "Daily thread");
+        by_author.author = Some(Author {
+            name: "automoderator".to_string(),
+            email: None,
+            url: None,
+            avatar_url: None,
+        });
+        assert_eq!(
+            filter.check(&by_author),
+            Some(MuteReason::Author("automoderator".to_string()))
+        );
+
+        let mut linked = item("rss:2", "Interesting");
+        linked.url = Some("https://news.Example.com/story".to_string());
+        assert_eq!(
+            filter.check(&linked),
+            Some(MuteReason::Domain("example.com".to_string()))
+        );
+        linked.url = Some("https://notexample.com/story".to_string());
+        assert_eq!(filter.check(&linked), None);
+
+        // Keywords match content too
+        let mut recap = item("rss:3", "Weekly thread");
+        recap.content = ItemContent::Html("<p>Full <b>episode</b> recap</p>".to_string());
+        assert_eq!(
+            filter.check(&recap),
+            Some(MuteReason::Keyword("episode recap".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_subreddit_and_nsfw() {
+        let filter = filter(
+            MuteRules {
+                subreddits: vec!["r/Politics".to_string()],
+                hide_nsfw: Some(true),
+                ..MuteRules::default()
+            },
+            &[],
+        );
+
+        let mut post = item("reddit:1", "Post");
+        post.metadata
+            .insert("subreddit".to_string(), "politics".to_string());
+        assert_eq!(
+            filter.check(&post),
+            Some(MuteReason::Subreddit("politics".to_string()))
+        );
+
+        let mut nsfw = item("reddit:2", "Post");
+        nsfw.metadata
+            .insert("over_18".to_string(), "true".to_string());
+        assert_eq!(filter.check(&nsfw), Some(MuteReason::Nsfw));
+        assert_eq!(MuteReason::Nsfw.to_string(), "NSFW");
+    }
+
+    #[test]
+    fn test_provider_overrides() {
+        let global = MuteRules {
+            keywords: vec!["crypto".to_string()],
+            ..MuteRules::default()
+        };
+        let filter = filter(
+            global,
+            &[
+                (
+                    "reddit",
+                    ProviderMuteRules {
+                        rules: MuteRules {
+                            keywords: vec!["meme".to_string()],
+                            ..MuteRules::default()
+                        },
+                        inherit: true,
+                    },
+                ),
+                (
+                    "email",
+                    ProviderMuteRules {
+                        rules: MuteRules::default(),
+                        inherit: false,
+                    },
+                ),
+            ],
+        );
+
+        assert!(filter.check(&item("reddit:1", "Crypto news")).is_some());
+        assert!(filter.check(&item("reddit:2", "Best meme")).is_some());
+        assert!(filter.check(&item("rss:1", "Best meme")).is_none());
+        assert!(filter.check(&item("rss:2", "Crypto news")).is_some());
+        // Email opted out of the global rules
+        assert!(filter.check(&item("email:1", "Crypto news")).is_none());
+
+        // The unified view's provider metadata wins over the ID prefix
+        let mut tagged = item("x:1", "Best meme");
+        tagged
+            .metadata
+            .insert("provider_id".to_string(), "reddit".to_string());
+        assert!(filter.check(&tagged).is_some());
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        let filter = MuteFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.check(&item("rss:1", "Anything")).is_none());
+    }
+}
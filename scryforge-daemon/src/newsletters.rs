@@ -0,0 +1,616 @@
+//! Newsletter unbundling.
+//!
+//! A newsletter bundles many stories into one email or feed entry, so it can
+//! only be saved or dismissed as a whole. With `[newsletters] unbundle =
+//! true`, stream refreshes look for newsletters among the items they fetch
+//! and cache each of their stories as an item of its own, in the same
+//! stream, next to the newsletter itself.
+//!
+//! [`stories`] finds the stories of an HTML body by its `<h1>` to `<h4>`
+//! headings, or failing that by its list items that link somewhere; plain
+//! text and Markdown bodies are read the same way by their `#` headings and
+//! `-`, `*`, or numbered list lines. Each story needs a link, and links to
+//! unsubscribe, view the email in a browser, or manage preferences never
+//! count. Items with fewer than `min_stories` stories are left whole.
+//!
+//! Stories exist only in the cache. Their IDs are the newsletter's followed
+//! by `#story-<n>`, which the newsletter's provider doesn't know, so actions
+//! queued on them are kept local (see [`crate::outbox`]) and their read and
+//! saved state is never overwritten by a refresh.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use scryforge_provider_core::{
+    decode_entities, strip_tags, Item, ItemContent, ItemId, REMOVED_METADATA_KEY,
+};
+
+use crate::config::NewslettersConfig;
+use crate::links::{attribute, collapse, link_texts};
+use crate::relations::{find_urls, link_key};
+
+/// Metadata key holding the title of the newsletter a story came from.
+pub const NEWSLETTER_METADATA_KEY: &str = "newsletter";
+
+/// Metadata key holding the ID of the newsletter a story came from.
+pub const NEWSLETTER_ID_METADATA_KEY: &str = "newsletter_id";
+
+/// Metadata key holding how many stories a newsletter was split into.
+pub const STORIES_METADATA_KEY: &str = "stories";
+
+/// Separates a newsletter's ID from the number of one of its stories.
+const STORY_ID_SEPARATOR: &str = "#story-";
+
+/// Headings a story may be titled by, in HTML.
+const HEADINGS: [&str; 4] = ["h1", "h2", "h3", "h4"];
+
+/// Link texts of a newsletter's footer rather than its stories.
+const FOOTER_TEXTS: [&str; 9] = [
+    "unsubscribe",
+    "view in browser",
+    "view in your browser",
+    "view online",
+    "view this email",
+    "manage preferences",
+    "update your preferences",
+    "email preferences",
+    "forward to a friend",
+];
+
+/// Fragments of footer link URLs.
+const FOOTER_URLS: [&str; 4] = ["unsubscribe", "optout", "opt-out", "/preferences"];
+
+/// A story of a newsletter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Story {
+    pub title: String,
+    /// Where the story links to
+    pub url: String,
+    /// The story's section of the newsletter, in the newsletter's format
+    pub body: String,
+}
+
+/// The format of a newsletter's body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Html,
+    /// Plain text, read as Markdown
+    Text,
+}
+
+/// The stories of `item`, if it is an email or feed entry with at least
+/// `min_stories` of them, and none otherwise.
+pub fn stories(item: &Item, min_stories: usize) -> Vec<Story> {
+    let Some((body, format)) = body(&item.content) else {
+        return Vec::new();
+    };
+    let sections = match format {
+        Format::Html => [html_sections(body), html_list_items(body)],
+        Format::Text => [text_sections(body), text_list_items(body)],
+    };
+
+    // Stories under headings, or else list items, whichever are enough
+    let own = item.url.as_deref().and_then(link_key);
+    for candidates in sections {
+        let mut seen = HashSet::new();
+        let stories: Vec<Story> = candidates
+            .into_iter()
+            .filter(|story| !story.title.is_empty() && !is_footer(story))
+            .filter(|story| {
+                let key = link_key(&story.url).unwrap_or_else(|| story.url.clone());
+                own.as_ref() != Some(&key) && seen.insert(key)
+            })
+            .collect();
+        if stories.len() >= min_stories {
+            return stories;
+        }
+    }
+    Vec::new()
+}
+
+/// Whether `item` is a story split out of a newsletter.
+pub fn is_story(item: &Item) -> bool {
+    item.metadata.contains_key(NEWSLETTER_ID_METADATA_KEY)
+}
+
+/// The newsletter a story was split out of.
+pub fn newsletter_id(item: &Item) -> Option<ItemId> {
+    item.metadata
+        .get(NEWSLETTER_ID_METADATA_KEY)
+        .map(|id| ItemId(id.clone()))
+}
+
+/// The body of an email or feed entry, and its format.
+fn body(content: &ItemContent) -> Option<(&str, Format)> {
+    match content {
+        ItemContent::Email {
+            body_html: Some(html),
+            ..
+        } => Some((html, Format::Html)),
+        ItemContent::Email {
+            body_text: Some(text),
+            ..
+        } => Some((text, Format::Text)),
+        ItemContent::Article {
+            summary,
+            full_content,
+        } => {
+            let body = full_content.as_deref().or(summary.as_deref())?;
+            // Feeds publish HTML, but not always
+            let format = if body.contains("</") {
+                Format::Html
+            } else {
+                Format::Text
+            };
+            Some((body, format))
+        }
+        _ => None,
+    }
+}
+
+fn is_footer(story: &Story) -> bool {
+    let title = story.title.to_lowercase();
+    let url = story.url.to_ascii_lowercase();
+    FOOTER_TEXTS.iter().any(|text| title.contains(text))
+        || FOOTER_URLS.iter().any(|fragment| url.contains(fragment))
+}
+
+/// An HTML element found in a body.
+struct Element {
+    /// Where the start tag starts
+    start: usize,
+    /// Between the start and end tags
+    inner: Range<usize>,
+    /// Where the end tag ends
+    end: usize,
+}
+
+/// The elements named one of `names` in `html`, in order. An element inside
+/// another of the same names is skipped.
+fn elements(html: &str, names: &[&str]) -> Vec<Element> {
+    // ASCII lowercasing keeps byte offsets
+    let lower = html.to_ascii_lowercase();
+    let mut elements = Vec::new();
+    let mut from = 0;
+    while let Some(at) = lower[from..].find('<') {
+        let start = from + at;
+        from = start + 1;
+        let Some(name) = names.iter().find(|name| {
+            lower[start + 1..].starts_with(*name)
+                && lower[start + 1 + name.len()..]
+                    .starts_with(|c: char| c == '>' || c.is_whitespace())
+        }) else {
+            continue;
+        };
+        let Some(inner_start) = lower[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let close = format!("</{}", name);
+        let Some(inner_end) = lower[inner_start..].find(&close).map(|i| inner_start + i) else {
+            break;
+        };
+        let end = lower[inner_end..]
+            .find('>')
+            .map_or(html.len(), |i| inner_end + i + 1);
+        elements.push(Element {
+            start,
+            inner: inner_start..inner_end,
+            end,
+        });
+        from = end;
+    }
+    elements
+}
+
+/// The first `http` link in `html`, with its text.
+fn first_anchor(html: &str) -> Option<(String, String)> {
+    elements(html, &["a"]).into_iter().find_map(|anchor| {
+        let url = decode_entities(attribute(&html[anchor.start..anchor.inner.start], "href")?);
+        url.starts_with("http")
+            .then(|| (url, html_text(&html[anchor.inner])))
+    })
+}
+
+fn html_text(html: &str) -> String {
+    collapse(&decode_entities(&strip_tags(html, "")))
+}
+
+/// Sections of `html` under headings, each running to the next heading.
+fn html_sections(html: &str) -> Vec<Story> {
+    let headings = elements(html, &HEADINGS);
+    headings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, heading)| {
+            let end = headings.get(i + 1).map_or(html.len(), |next| next.start);
+            let (url, _) = first_anchor(&html[heading.inner.clone()])
+                .or_else(|| first_anchor(&html[heading.end..end]))?;
+            Some(Story {
+                title: html_text(&html[heading.inner.clone()]),
+                url,
+                body: html[heading.start..end].trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// List items of `html` with a link, titled by the link's text.
+fn html_list_items(html: &str) -> Vec<Story> {
+    elements(html, &["li"])
+        .into_iter()
+        .filter_map(|item| {
+            let inner = &html[item.inner.clone()];
+            let (url, text) = first_anchor(inner)?;
+            let title = if text.is_empty() {
+                html_text(inner)
+            } else {
+                text
+            };
+            Some(Story {
+                title,
+                url,
+                body: html[item.start..item.end].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The text of a `#` heading line, up to `####`.
+fn heading(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.len() - trimmed.trim_start_matches('#').len();
+    let text = trimmed[level..].strip_prefix([' ', '\t'])?;
+    (1..=4)
+        .contains(&level)
+        .then(|| text.trim().trim_end_matches('#').trim_end())
+}
+
+/// The text of a `-`, `*`, `+`, or numbered list line.
+fn list_item(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(text) = trimmed
+        .strip_prefix(['-', '*', '+', '•'])
+        .and_then(|text| text.strip_prefix(' '))
+    {
+        return Some(text);
+    }
+    let digits = trimmed.len()
+        - trimmed
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .len();
+    if digits == 0 {
+        return None;
+    }
+    trimmed[digits..]
+        .strip_prefix(['.', ')'])?
+        .strip_prefix(' ')
+}
+
+/// Markdown with its links replaced by their labels and emphasis removed.
+fn unlink(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find("](").map(|i| open + i) else {
+            break;
+        };
+        let Some(end) = rest[close..].find(')').map(|i| close + i) else {
+            break;
+        };
+        text.push_str(&rest[..open]);
+        text.push_str(&rest[open + 1..close]);
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    collapse(&text.replace("**", "").replace("__", "").replace('`', ""))
+}
+
+fn first_url(text: &str) -> Option<String> {
+    find_urls(text).next().map(str::to_string)
+}
+
+/// Sections of plain text or Markdown under `#` headings.
+fn text_sections(text: &str) -> Vec<Story> {
+    let mut headings: Vec<(usize, usize, &str)> = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if let Some(title) = heading(line) {
+            headings.push((offset, offset + line.len(), title));
+        }
+        offset += line.len();
+    }
+
+    headings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &(start, line_end, title))| {
+            let end = headings.get(i + 1).map_or(text.len(), |next| next.0);
+            let url = first_url(title).or_else(|| first_url(&text[line_end..end]))?;
+            Some(Story {
+                title: unlink(title),
+                url,
+                body: text[start..end].trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// List lines of plain text or Markdown with a link, titled by the link's
+/// label or else the rest of the line.
+fn text_list_items(text: &str) -> Vec<Story> {
+    text.lines()
+        .filter_map(|line| {
+            let item = list_item(line)?;
+            let url = first_url(item)?;
+            let title = match link_texts(item).remove(&url) {
+                Some(label) => label,
+                None => {
+                    let rest = unlink(&item.replace(&url, ""));
+                    rest.trim_matches(|c: char| {
+                        c.is_whitespace()
+                            || matches!(c, ':' | '-' | '–' | '—' | '(' | ')' | '<' | '>')
+                    })
+                    .to_string()
+                }
+            };
+            Some(Story {
+                title: if title.is_empty() { url.clone() } else { title },
+                url,
+                body: line.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Splits the newsletters of refreshed streams into their stories.
+#[derive(Debug, Clone)]
+pub struct Unbundler {
+    min_stories: usize,
+    streams: Vec<String>,
+}
+
+impl Unbundler {
+    /// An unbundler for `config`, or `None` when unbundling is off.
+    pub fn from_config(config: &NewslettersConfig) -> Option<Self> {
+        config.unbundle.then(|| Self {
+            min_stories: config.min_stories,
+            streams: config.streams.clone(),
+        })
+    }
+
+    /// The stories of the newsletters among `items`, fetched from
+    /// `stream_id`, as items of their own. Each newsletter is marked with
+    /// how many stories it was split into.
+    pub fn unbundle(&self, stream_id: &str, items: &mut [Item]) -> Vec<Item> {
+        if !self.streams.is_empty() && !self.streams.iter().any(|id| id == stream_id) {
+            return Vec::new();
+        }
+
+        let mut children = Vec::new();
+        for item in items.iter_mut() {
+            if item.metadata.contains_key(REMOVED_METADATA_KEY) {
+                continue;
+            }
+            let stories = stories(item, self.min_stories);
+            if stories.is_empty() {
+                continue;
+            }
+            item.metadata
+                .insert(STORIES_METADATA_KEY.to_string(), stories.len().to_string());
+            children.extend(
+                stories
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, story)| story_item(item, i + 1, story)),
+            );
+        }
+        children
+    }
+}
+
+/// Story `number` of `newsletter` as an item.
+fn story_item(newsletter: &Item, number: usize, story: Story) -> Item {
+    let content = match body(&newsletter.content).map(|(_, format)| format) {
+        Some(Format::Html) => ItemContent::Html(story.body),
+        _ => ItemContent::Text(story.body),
+    };
+    let metadata = HashMap::from([
+        (
+            NEWSLETTER_METADATA_KEY.to_string(),
+            newsletter.title.clone(),
+        ),
+        (
+            NEWSLETTER_ID_METADATA_KEY.to_string(),
+            newsletter.id.as_str().to_string(),
+        ),
+    ]);
+
+    Item {
+        id: ItemId(format!(
+            "{}{}{}",
+            newsletter.id.as_str(),
+            STORY_ID_SEPARATOR,
+            number
+        )),
+        stream_id: newsletter.stream_id.clone(),
+        title: story.title,
+        content,
+        author: newsletter.author.clone(),
+        published: newsletter.published,
+        updated: newsletter.updated,
+        url: Some(story.url),
+        thumbnail_url: None,
+        // A newsletter read before it was split doesn't bring back its stories
+        is_read: newsletter.is_read,
+        is_saved: false,
+        tags: newsletter.tags.clone(),
+        metadata,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::StreamId;
+
+    fn email(html: Option<&str>, text: Option<&str>) -> Item {
+        Item {
+            id: ItemId("email:msg:7".to_string()),
+            stream_id: StreamId("email:folder:inbox".to_string()),
+            title: "The Weekly #42".to_string(),
+            content: ItemContent::Email {
+                subject: "The Weekly #42".to_string(),
+                body_text: text.map(str::to_string),
+                body_html: html.map(str::to_string),
+                snippet: String::new(),
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: true,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn titles(stories: &[Story]) -> Vec<(&str, &str)> {
+        stories
+            .iter()
+            .map(|story| (story.title.as_str(), story.url.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_html_stories_by_heading() {
+        let html = r#"<p><a href="https://example.com/weekly/42">View in browser</a></p>
+<h1>The Weekly</h1><p>Welcome back.</p>
+<h2><a href="https://blog.example.com/a?x=1&amp;y=2">Rust &amp; you</a></h2><p>First story.</p>
+<H3 class="story">Faster builds</H3><p>Read <a href="https://example.org/builds">more</a>.</p>
+<h2>Paper</h2><p><a href='https://example.net/paper.pdf'>PDF</a></p>
+<h2>Goodbye</h2><p><a href="https://example.com/unsubscribe?u=1">Unsubscribe</a></p>"#;
+        let stories = stories(&email(Some(html), None), 3);
+        assert_eq!(
+            titles(&stories),
+            vec![
+                ("Rust & you", "https://blog.example.com/a?x=1&y=2"),
+                ("Faster builds", "https://example.org/builds"),
+                ("Paper", "https://example.net/paper.pdf"),
+            ]
+        );
+        assert_eq!(
+            stories[1].body,
+            r#"<H3 class="story">Faster builds</H3><p>Read <a href="https://example.org/builds">more</a>.</p>"#
+        );
+    }
+
+    #[test]
+    fn test_html_stories_by_list_item() {
+        let html = r#"<ul>
+<li><a href="https://a.example.com/1">One</a> - why it matters</li>
+<li>Two: <a href="https://b.example.com/2"><b>Two</b></a></li>
+<li>No link here</li>
+<li><a href="https://a.example.com/1">One again</a></li>
+<li><a href="https://c.example.com/3">Three</a></li>
+</ul>"#;
+        assert_eq!(
+            titles(&stories(&email(Some(html), None), 3)),
+            vec![
+                ("One", "https://a.example.com/1"),
+                ("Two", "https://b.example.com/2"),
+                ("Three", "https://c.example.com/3"),
+            ]
+        );
+        // Too few stories leave the email whole
+        assert!(stories(&email(Some(html), None), 4).is_empty());
+    }
+
+    #[test]
+    fn test_text_stories() {
+        let text = "Hi all,\n\n\
+                    ## [First](https://example.com/1)\nSome words.\n\n\
+                    ## Second story ##\nSee https://example.com/2.\n\n\
+                    ### Third\n<https://example.com/3>\n";
+        assert_eq!(
+            titles(&stories(&email(None, Some(text)), 3)),
+            vec![
+                ("First", "https://example.com/1"),
+                ("Second story", "https://example.com/2"),
+                ("Third", "https://example.com/3"),
+            ]
+        );
+
+        let text = "Links this week:\n\
+                    - [**Tokio 2.0**](https://tokio.rs/blog) released\n\
+                    * Async traits: https://example.com/traits\n\
+                    1. https://example.com/bare\n\
+                    - Unsubscribe: https://example.com/unsubscribe\n";
+        assert_eq!(
+            titles(&stories(&email(None, Some(text)), 3)),
+            vec![
+                ("Tokio 2.0", "https://tokio.rs/blog"),
+                ("Async traits", "https://example.com/traits"),
+                ("https://example.com/bare", "https://example.com/bare"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unbundle() {
+        let html = "<h2><a href=\"https://example.com/1\">One</a></h2>\
+                    <h2><a href=\"https://example.com/2\">Two</a></h2>";
+        let mut items = vec![email(Some(html), None), email(None, Some("Just a note"))];
+        items[1].id = ItemId("email:msg:8".to_string());
+
+        let unbundler = Unbundler::from_config(&NewslettersConfig {
+            unbundle: true,
+            min_stories: 2,
+            streams: Vec::new(),
+        })
+        .unwrap();
+        let children = unbundler.unbundle("email:folder:inbox", &mut items);
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].id.as_str(), "email:msg:7#story-1");
+        assert_eq!(children[1].url.as_deref(), Some("https://example.com/2"));
+        assert!(matches!(children[1].content, ItemContent::Html(_)));
+        assert!(children[0].is_read && is_story(&children[0]));
+        assert_eq!(newsletter_id(&children[0]), Some(items[0].id.clone()));
+        assert_eq!(
+            children[0]
+                .metadata
+                .get(NEWSLETTER_METADATA_KEY)
+                .map(String::as_str),
+            Some("The Weekly #42")
+        );
+        assert_eq!(
+            items[0]
+                .metadata
+                .get(STORIES_METADATA_KEY)
+                .map(String::as_str),
+            Some("2")
+        );
+        assert!(!is_story(&items[0]));
+        assert!(!items[1].metadata.contains_key(STORIES_METADATA_KEY));
+
+        // Only the listed streams are unbundled
+        let unbundler = Unbundler::from_config(&NewslettersConfig {
+            unbundle: true,
+            min_stories: 2,
+            streams: vec!["rss:feed:weekly".to_string()],
+        })
+        .unwrap();
+        assert!(unbundler
+            .unbundle("email:folder:inbox", &mut items)
+            .is_empty());
+        assert!(Unbundler::from_config(&NewslettersConfig::default()).is_none());
+    }
+}
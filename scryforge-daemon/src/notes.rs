@@ -0,0 +1,220 @@
+//! Send-to-notes integration.
+//!
+//! Appends a short Markdown entry for an item (title, link, excerpt, tags)
+//! to a notes file, so items can be collected into an Obsidian vault or a
+//! Logseq graph without leaving the TUI. Entries are written as top-level
+//! list items, which both tools render as blocks.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use scryforge_provider_core::Item;
+
+use crate::config::{NotesConfig, NotesLayout};
+
+/// Maximum characters of content included in an entry's excerpt.
+const EXCERPT_CHARS: usize = 280;
+
+/// Appends item entries to the configured notes location.
+#[derive(Debug, Clone)]
+pub struct NotesWriter {
+    path: PathBuf,
+    layout: NotesLayout,
+    daily_format: String,
+}
+
+impl NotesWriter {
+    /// Create a writer from config, or `None` if no notes path is configured.
+    pub fn from_config(config: &NotesConfig) -> Option<Self> {
+        Some(Self {
+            path: config.path.clone()?,
+            layout: config.layout,
+            daily_format: config.daily_format.clone(),
+        })
+    }
+
+    /// File that an entry written at `now` goes to.
+    pub fn target_path(&self, now: DateTime<Local>) -> PathBuf {
+        match self.layout {
+            NotesLayout::File => self.path.clone(),
+            NotesLayout::Daily => self
+                .path
+                .join(format!("{}.md", now.format(&self.daily_format))),
+        }
+    }
+
+    /// Append an entry for `item`, returning the file written to.
+    pub fn append(&self, item: &Item) -> Result<PathBuf> {
+        let now = Local::now();
+        let path = self.target_path(now);
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create notes directory: {}", parent.display())
+            })?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open notes file: {}", path.display()))?;
+
+        // Keep entries on their own lines even if the file lacks a trailing newline
+        if !ends_with_newline(&path)? {
+            writeln!(file)?;
+        }
+
+        file.write_all(format_entry(item, now).as_bytes())
+            .with_context(|| format!("Failed to write notes file: {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+/// Format a notes entry for an item.
+pub fn format_entry(item: &Item, added: DateTime<Local>) -> String {
+    let title = item.title.replace(['[', ']'], "");
+    let mut entry = match item.url {
+        Some(ref url) => format!("- [{}]({})\n", title, url),
+        None => format!("- {}\n", title),
+    };
+
+    if let Some(excerpt) = item.content.plain_text() {
+        let excerpt = if excerpt.chars().count() > EXCERPT_CHARS {
+            let truncated: String = excerpt.chars().take(EXCERPT_CHARS).collect();
+            format!("{}…", truncated.trim_end())
+        } else {
+            excerpt
+        };
+        entry.push_str(&format!("  > {}\n", excerpt));
+    }
+
+    let tags: Vec<String> = item
+        .tags
+        .iter()
+        .map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("-"))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("#{}", tag))
+        .collect();
+    if !tags.is_empty() {
+        entry.push_str(&format!("  {}\n", tags.join(" ")));
+    }
+
+    entry.push_str(&format!(
+        "  added {} from `{}`\n",
+        added.format("%Y-%m-%d %H:%M"),
+        item.stream_id.as_str()
+    ));
+
+    entry
+}
+
+/// Whether the file is empty or its last byte is a newline.
+fn ends_with_newline(path: &Path) -> Result<bool> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8; 1];
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use scryforge_provider_core::{ItemContent, ItemId, StreamId};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_item() -> Item {
+        Item {
+            id: ItemId("rss:1".to_string()),
+            stream_id: StreamId("rss:feed:news".to_string()),
+            title: "Rust [1.80] released".to_string(),
+            content: ItemContent::Html("<p>The   Rust team is happy</p>".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("https://example.com/rust".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec!["rust".to_string(), "release notes".to_string()],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn writer(dir: &TempDir, layout: NotesLayout) -> NotesWriter {
+        NotesWriter::from_config(&NotesConfig {
+            path: Some(match layout {
+                NotesLayout::File => dir.path().join("inbox.md"),
+                NotesLayout::Daily => dir.path().join("journals"),
+            }),
+            layout,
+            daily_format: "%Y_%m_%d".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_format_entry() {
+        let added = Local.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+        let entry = format_entry(&create_test_item(), added);
+
+        assert_eq!(
+            entry,
+            "- [Rust 1.80 released](https://example.com/rust)\n\
+             \x20 > The Rust team is happy\n\
+             \x20 #rust #release-notes\n\
+             \x20 added 2024-03-01 09:30 from `rss:feed:news`\n"
+        );
+    }
+
+    #[test]
+    fn test_disabled_without_path() {
+        assert!(NotesWriter::from_config(&NotesConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_append_to_file() -> Result<()> {
+        let dir = TempDir::new()?;
+        let writer = writer(&dir, NotesLayout::File);
+        fs::write(dir.path().join("inbox.md"), "# Inbox")?;
+
+        let path = writer.append(&create_test_item())?;
+        writer.append(&create_test_item())?;
+
+        let contents = fs::read_to_string(path)?;
+        assert!(contents.starts_with("# Inbox\n- [Rust 1.80 released]"));
+        assert_eq!(contents.matches("- [Rust").count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_daily_layout_path() -> Result<()> {
+        let dir = TempDir::new()?;
+        let writer = writer(&dir, NotesLayout::Daily);
+
+        let now = Local.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+        assert_eq!(
+            writer.target_path(now),
+            dir.path().join("journals").join("2024_03_01.md")
+        );
+
+        let path = writer.append(&create_test_item())?;
+        assert!(path.starts_with(dir.path().join("journals")));
+        assert!(path.exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,336 @@
+//! Opening items in external programs.
+//!
+//! `items.open` hands an item's link to a command chosen by what the link
+//! points at: videos to a player such as `mpv`, PDFs to a document viewer,
+//! articles to a browser or `w3m`. The `[opener]` section maps each
+//! [`ContentKind`] to a command, and providers can override any of them.
+//!
+//! Commands are run with `sh -c` and the link appended as their last
+//! argument. They are not waited for, since a player or browser keeps
+//! running long after the request returns.
+
+use std::process::Stdio;
+
+use anyhow::{Context, Result};
+use scryforge_provider_core::{Item, ItemContent};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{OpenerCommands, OpenerConfig};
+
+/// File extensions by the kind of content they hold.
+const EXTENSIONS: &[(ContentKind, &[&str])] = &[
+    (ContentKind::Pdf, &["pdf"]),
+    (
+        ContentKind::Video,
+        &["mp4", "mkv", "webm", "mov", "avi", "m4v", "m3u8"],
+    ),
+    (
+        ContentKind::Audio,
+        &["mp3", "ogg", "opus", "flac", "m4a", "wav", "aac"],
+    ),
+    (
+        ContentKind::Image,
+        &["jpg", "jpeg", "png", "gif", "webp", "avif", "svg"],
+    ),
+];
+
+/// Command for links without a configured one.
+#[cfg(target_os = "macos")]
+const DEFAULT_COMMAND: &str = "open";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_COMMAND: &str = "xdg-open";
+
+/// Hosts whose pages are videos.
+const VIDEO_HOSTS: &[&str] = &["youtube.com", "youtu.be", "vimeo.com", "twitch.tv"];
+
+/// What an item's link points at, which decides the command that opens it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Video,
+    Audio,
+    Image,
+    Pdf,
+    Article,
+    /// Anything else; opened with the default command
+    Other,
+}
+
+impl ContentKind {
+    /// Kind of `item`'s link, judged by its file extension, then its host,
+    /// then the item's content.
+    pub fn of(item: &Item, url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let after_scheme = path.split_once("://").map_or(path, |(_, rest)| rest);
+        let (host, path) = after_scheme.split_once('/').unwrap_or((after_scheme, ""));
+        let host = host.to_ascii_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+
+        if let Some((_, extension)) = path.rsplit('/').next().unwrap_or_default().rsplit_once('.') {
+            let extension = extension.to_ascii_lowercase();
+            if let Some(&(kind, _)) = EXTENSIONS
+                .iter()
+                .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+            {
+                return kind;
+            }
+        }
+        if VIDEO_HOSTS
+            .iter()
+            .any(|h| host == *h || host.ends_with(&format!(".{}", h)))
+        {
+            return ContentKind::Video;
+        }
+
+        match item.content {
+            ItemContent::Video { .. } => ContentKind::Video,
+            ItemContent::Track { .. } => ContentKind::Audio,
+            ItemContent::Image { .. } | ItemContent::Gallery { .. } => ContentKind::Image,
+            ItemContent::Article { .. } | ItemContent::Html(_) | ItemContent::Markdown(_) => {
+                ContentKind::Article
+            }
+            _ => ContentKind::Other,
+        }
+    }
+}
+
+/// How an item was opened.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenedItem {
+    pub url: String,
+    pub kind: ContentKind,
+    /// Command the link was passed to
+    pub command: String,
+}
+
+/// Opens item links with the commands configured per content kind.
+#[derive(Debug, Clone, Default)]
+pub struct Opener {
+    config: OpenerConfig,
+}
+
+impl Opener {
+    pub fn from_config(config: &OpenerConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// The command for `kind` links from `provider_id`: the provider's
+    /// command for the kind, its default, the global command for the kind,
+    /// then the global default.
+    pub fn command_for(&self, provider_id: &str, kind: ContentKind) -> &str {
+        let provider = self.config.providers.get(provider_id);
+        provider
+            .and_then(|commands| commands.for_kind(kind))
+            .or_else(|| provider.and_then(|commands| commands.default.as_deref()))
+            .or_else(|| self.config.commands.for_kind(kind))
+            .or(self.config.commands.default.as_deref())
+            .unwrap_or(DEFAULT_COMMAND)
+    }
+
+    /// The link `item` is opened at: its URL, or the image it shows.
+    pub fn link(item: &Item) -> Option<&str> {
+        item.url.as_deref().or(match &item.content {
+            ItemContent::Image { url, .. } => Some(url.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Open `item`, or `Ok(None)` if it has no link.
+    pub fn open(&self, item: &Item) -> Result<Option<OpenedItem>> {
+        match Self::link(item) {
+            Some(url) => self.open_link(item, url).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Open `url`, a link found in `item`, with the command for the kind of
+    /// content it points at.
+    pub fn open_link(&self, item: &Item, url: &str) -> Result<OpenedItem> {
+        let kind = ContentKind::of(item, url);
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        let command = self.command_for(provider_id, kind);
+
+        // The link is a positional argument, so it needs no shell quoting.
+        // Dropping the child neither kills nor waits for it.
+        tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$1\"", command))
+            .arg("sh")
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to run open command: {}", command))?;
+
+        Ok(OpenedItem {
+            url: url.to_string(),
+            kind,
+            command: command.to_string(),
+        })
+    }
+}
+
+impl OpenerCommands {
+    fn for_kind(&self, kind: ContentKind) -> Option<&str> {
+        match kind {
+            ContentKind::Video => self.video.as_deref(),
+            ContentKind::Audio => self.audio.as_deref(),
+            ContentKind::Image => self.image.as_deref(),
+            ContentKind::Pdf => self.pdf.as_deref(),
+            ContentKind::Article => self.article.as_deref(),
+            ContentKind::Other => None,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemId, StreamId};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn item(id: &str, url: Option<&str>, content: ItemContent) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:1".to_string()),
+            title: "Item".to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: url.map(str::to_string),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn article() -> ItemContent {
+        ItemContent::Article {
+            summary: None,
+            full_content: None,
+        }
+    }
+
+    #[test]
+    fn test_kind_follows_extension_host_then_content() {
+        let post = item("rss:1", None, article());
+        let video = item(
+            "youtube:1",
+            None,
+            ItemContent::Video {
+                description: String::new(),
+                duration_seconds: None,
+                view_count: None,
+            },
+        );
+
+        let cases = [
+            (
+                &post,
+                "https://example.com/paper.PDF?dl=1",
+                ContentKind::Pdf,
+            ),
+            (&post, "https://example.com/clip.webm", ContentKind::Video),
+            (
+                &post,
+                "https://www.youtube.com/watch?v=abc",
+                ContentKind::Video,
+            ),
+            (&post, "https://m.twitch.tv/somebody", ContentKind::Video),
+            (&post, "https://example.com/post", ContentKind::Article),
+            (&post, "https://example.com/v1.2/post", ContentKind::Article),
+            (&video, "https://example.com/watch", ContentKind::Video),
+        ];
+        for (item, url, kind) in cases {
+            assert_eq!(ContentKind::of(item, url), kind, "{}", url);
+        }
+
+        let note = item("mstodo:1", None, ItemContent::Generic { body: None });
+        assert_eq!(
+            ContentKind::of(&note, "https://example.com"),
+            ContentKind::Other
+        );
+    }
+
+    #[test]
+    fn test_command_falls_back_from_provider_to_global() {
+        let config = OpenerConfig {
+            commands: OpenerCommands {
+                video: Some("mpv".to_string()),
+                pdf: Some("zathura".to_string()),
+                ..OpenerCommands::default()
+            },
+            providers: HashMap::from([(
+                "reddit".to_string(),
+                OpenerCommands {
+                    default: Some("firefox".to_string()),
+                    video: Some("mpv --ytdl-format=worst".to_string()),
+                    ..OpenerCommands::default()
+                },
+            )]),
+        };
+        let opener = Opener::from_config(&config);
+
+        assert_eq!(opener.command_for("rss", ContentKind::Video), "mpv");
+        assert_eq!(
+            opener.command_for("rss", ContentKind::Article),
+            DEFAULT_COMMAND
+        );
+        assert_eq!(
+            opener.command_for("reddit", ContentKind::Video),
+            "mpv --ytdl-format=worst"
+        );
+        assert_eq!(opener.command_for("reddit", ContentKind::Pdf), "firefox");
+        assert_eq!(opener.command_for("reddit", ContentKind::Other), "firefox");
+    }
+
+    #[tokio::test]
+    async fn test_open_passes_link_to_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("opened");
+        let opener = Opener::from_config(&OpenerConfig {
+            commands: OpenerCommands {
+                default: Some(format!("printf '%s\\n' >> '{}'", log.display())),
+                ..OpenerCommands::default()
+            },
+            ..OpenerConfig::default()
+        });
+
+        let opened = opener
+            .open(&item(
+                "rss:1",
+                Some("https://example.com/a?b=1&c=2"),
+                article(),
+            ))
+            .unwrap()
+            .unwrap();
+        assert_eq!(opened.kind, ContentKind::Article);
+        assert!(opener
+            .open(&item("rss:2", None, article()))
+            .unwrap()
+            .is_none());
+
+        // The command runs detached; wait for it to write
+        for _ in 0..100 {
+            if std::fs::read_to_string(&log).is_ok_and(|s| s.ends_with('\n')) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "https://example.com/a?b=1&c=2\n"
+        );
+    }
+}
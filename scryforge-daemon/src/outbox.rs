@@ -0,0 +1,568 @@
+//! Outbox of provider actions.
+//!
+//! Actions taken on items locally, such as archiving during triage, are
+//! applied to the cache at once and queued here for the item's provider.
+//! [`spawn_flusher`] sends them in batches, one provider at a time, once they
+//! have waited [`OUTBOX_DELAY`]; until then they can still be taken back, so
+//! an undo never has to reach the provider. Queuing the opposite of an action
+//! still waiting, such as unsaving an item whose save has not been sent,
+//! cancels both instead.
+//!
+//! Each action is sent as the matching entry of the provider's
+//! `available_actions` for the item. Providers that offer none keep the
+//! change local, and the entry is dropped, as are actions on stories split
+//! out of newsletters (see [`crate::newsletters`]), which their provider has
+//! never seen. Entries that keep failing are given up after
+//! [`MAX_ATTEMPTS`]. Every attempt to send an action is recorded in the
+//! [`audit`] log.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::{ActionKind, ItemId};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::audit;
+use crate::cache::Cache;
+use crate::newsletters;
+use crate::registry::ProviderRegistry;
+
+/// How long an action waits in the outbox before it is sent.
+pub const OUTBOX_DELAY: Duration = Duration::from_secs(30);
+
+/// How often [`spawn_flusher`] sends the actions that are due.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Failed attempts after which an action is given up.
+pub const MAX_ATTEMPTS: u32 = 5;
+
+/// A provider action waiting in the outbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxAction {
+    MarkRead,
+    MarkUnread,
+    Save,
+    Unsave,
+    Archive,
+    MarkSpam,
+    MarkNotSpam,
+}
+
+impl OutboxAction {
+    /// The provider action kind this is sent as. Spam actions are custom
+    /// actions that only mail providers offer.
+    pub fn kind(self) -> ActionKind {
+        match self {
+            Self::MarkRead => ActionKind::MarkRead,
+            Self::MarkUnread => ActionKind::MarkUnread,
+            Self::Save => ActionKind::Save,
+            Self::Unsave => ActionKind::Unsave,
+            Self::Archive => ActionKind::Archive,
+            Self::MarkSpam => ActionKind::Custom("mark_spam".to_string()),
+            Self::MarkNotSpam => ActionKind::Custom("not_spam".to_string()),
+        }
+    }
+
+    /// The action that undoes this one, if providers have one.
+    pub fn inverse(self) -> Option<Self> {
+        match self {
+            Self::MarkRead => Some(Self::MarkUnread),
+            Self::MarkUnread => Some(Self::MarkRead),
+            Self::Save => Some(Self::Unsave),
+            Self::Unsave => Some(Self::Save),
+            Self::Archive => None,
+            Self::MarkSpam => Some(Self::MarkNotSpam),
+            Self::MarkNotSpam => Some(Self::MarkSpam),
+        }
+    }
+}
+
+impl fmt::Display for OutboxAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MarkRead => "mark_read",
+            Self::MarkUnread => "mark_unread",
+            Self::Save => "save",
+            Self::Unsave => "unsave",
+            Self::Archive => "archive",
+            Self::MarkSpam => "mark_spam",
+            Self::MarkNotSpam => "mark_not_spam",
+        })
+    }
+}
+
+/// An action in the outbox and how sending it has gone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: i64,
+    pub item_id: ItemId,
+    pub action: OutboxAction,
+    pub queued_at: DateTime<Utc>,
+    /// Failed attempts to send it so far
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+/// What one [`flush`] did with the due actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlushSummary {
+    pub sent: usize,
+    /// Actions the provider doesn't offer for the item, kept local
+    pub unsupported: usize,
+    /// Actions that failed and stay queued for another attempt
+    pub failed: usize,
+    /// Actions given up after [`MAX_ATTEMPTS`], or whose item is gone
+    pub dropped: usize,
+}
+
+/// Queue `action` on an item, or cancel a waiting opposite action instead.
+///
+/// Returns whether the action was queued.
+pub fn queue<C: Cache + ?Sized>(cache: &C, item_id: &ItemId, action: OutboxAction) -> Result<bool> {
+    if let Some(inverse) = action.inverse() {
+        if cancel(cache, item_id, inverse)? {
+            debug!(
+                "{} on {} cancelled a waiting {}",
+                action,
+                item_id.as_str(),
+                inverse
+            );
+            return Ok(false);
+        }
+    }
+    cache.queue_outbox(item_id, action)?;
+    Ok(true)
+}
+
+/// Take the latest waiting `action` on an item out of the outbox.
+///
+/// Returns whether there was one; `false` means it was already sent, or
+/// never queued.
+pub fn cancel<C: Cache + ?Sized>(
+    cache: &C,
+    item_id: &ItemId,
+    action: OutboxAction,
+) -> Result<bool> {
+    let entry = cache
+        .get_outbox()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.item_id == *item_id && entry.action == action);
+    match entry {
+        Some(entry) => cache.remove_outbox(entry.id),
+        None => Ok(false),
+    }
+}
+
+/// Send every action queued at or before `due`, grouped by provider.
+pub async fn flush<C: Cache + ?Sized>(
+    cache: &C,
+    registry: &ProviderRegistry,
+    due: DateTime<Utc>,
+) -> Result<FlushSummary> {
+    let mut batches: BTreeMap<String, Vec<OutboxEntry>> = BTreeMap::new();
+    for entry in cache.get_outbox()? {
+        if entry.queued_at <= due {
+            let provider_id = entry.item_id.as_str().split(':').next().unwrap_or_default();
+            batches
+                .entry(provider_id.to_string())
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let mut summary = FlushSummary::default();
+    for (provider_id, entries) in batches {
+        let Some(provider) = registry.get(&provider_id) else {
+            for entry in &entries {
+                fail(cache, entry, "Provider not loaded", &mut summary)?;
+            }
+            continue;
+        };
+
+        debug!("Sending {} action(s) to {}", entries.len(), provider_id);
+        for entry in entries {
+            let Some(item) = cache.get_item(&entry.item_id)? else {
+                cache.remove_outbox(entry.id)?;
+                summary.dropped += 1;
+                continue;
+            };
+            if newsletters::is_story(&item) {
+                debug!(
+                    "{} is a newsletter story; keeping {} local",
+                    item.id.as_str(),
+                    entry.action
+                );
+                cache.remove_outbox(entry.id)?;
+                summary.unsupported += 1;
+                continue;
+            }
+
+            let kind = entry.action.kind();
+            let offered = registry
+                .guard(&provider_id, provider.available_actions(&item))
+                .await
+                .map(|actions| actions.into_iter().find(|action| action.kind == kind));
+            let action = match offered {
+                Ok(Some(action)) => action,
+                Ok(None) => {
+                    debug!(
+                        "{} doesn't offer {} for {}; keeping it local",
+                        provider_id,
+                        entry.action,
+                        item.id.as_str()
+                    );
+                    cache.remove_outbox(entry.id)?;
+                    summary.unsupported += 1;
+                    continue;
+                }
+                Err(e) => {
+                    fail(cache, &entry, &e.to_string(), &mut summary)?;
+                    continue;
+                }
+            };
+
+            let action_id = entry.action.to_string();
+            match registry
+                .guard(&provider_id, provider.execute_action(&item, &action))
+                .await
+            {
+                Ok(result) if result.success => {
+                    audit::record(cache, &provider_id, Some(&item.id), &action_id, None);
+                    cache.remove_outbox(entry.id)?;
+                    summary.sent += 1;
+                }
+                Ok(result) => {
+                    let message = result
+                        .message
+                        .unwrap_or_else(|| "Action failed".to_string());
+                    audit::record(
+                        cache,
+                        &provider_id,
+                        Some(&item.id),
+                        &action_id,
+                        Some(&message),
+                    );
+                    fail(cache, &entry, &message, &mut summary)?;
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    audit::record(
+                        cache,
+                        &provider_id,
+                        Some(&item.id),
+                        &action_id,
+                        Some(&message),
+                    );
+                    fail(cache, &entry, &message, &mut summary)?;
+                }
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Count a failed attempt, giving the entry up after [`MAX_ATTEMPTS`].
+fn fail<C: Cache + ?Sized>(
+    cache: &C,
+    entry: &OutboxEntry,
+    error: &str,
+    summary: &mut FlushSummary,
+) -> Result<()> {
+    if entry.attempts + 1 >= MAX_ATTEMPTS {
+        warn!(
+            "Giving up on {} for {} after {} attempts: {}",
+            entry.action,
+            entry.item_id.as_str(),
+            MAX_ATTEMPTS,
+            error
+        );
+        cache.remove_outbox(entry.id)?;
+        summary.dropped += 1;
+    } else {
+        cache.record_outbox_failure(entry.id, error)?;
+        summary.failed += 1;
+    }
+    Ok(())
+}
+
+/// Send due outbox actions every [`FLUSH_INTERVAL`] until the task is
+/// aborted.
+pub fn spawn_flusher<C: Cache + 'static>(
+    cache: Arc<C>,
+    registry: Arc<ProviderRegistry>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let due = Utc::now() - chrono::Duration::from_std(OUTBOX_DELAY).unwrap_or_default();
+            match flush(cache.as_ref(), &registry, due).await {
+                Ok(summary) if summary == FlushSummary::default() => {}
+                Ok(summary) => info!(
+                    "Outbox: sent {}, kept {} local, {} to retry, {} dropped",
+                    summary.sent, summary.unsupported, summary.failed, summary.dropped
+                ),
+                Err(e) => error!("Failed to flush the outbox: {:#}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use async_trait::async_trait;
+    use scryforge_provider_core::{
+        Action, ActionResult, Item, ItemContent, Provider, ProviderCapabilities, ProviderHealth,
+        Stream, StreamId, StreamType, SyncResult,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Offers marking read and archiving, and records what it was sent.
+    struct MailProvider {
+        sent: Arc<Mutex<Vec<(String, ActionKind)>>>,
+    }
+
+    #[async_trait]
+    impl Provider for MailProvider {
+        fn id(&self) -> &'static str {
+            "mail"
+        }
+
+        fn name(&self) -> &'static str {
+            "Mail"
+        }
+
+        async fn health_check(&self) -> scryforge_provider_core::Result<ProviderHealth> {
+            Ok(ProviderHealth {
+                is_healthy: true,
+                message: None,
+                last_sync: None,
+                error_count: 0,
+            })
+        }
+
+        async fn sync(&self) -> scryforge_provider_core::Result<SyncResult> {
+            Ok(SyncResult {
+                success: true,
+                items_added: 0,
+                items_updated: 0,
+                items_removed: 0,
+                errors: vec![],
+                duration_ms: 0,
+                streams: Vec::new(),
+            })
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities::default()
+        }
+
+        async fn available_actions(
+            &self,
+            _item: &Item,
+        ) -> scryforge_provider_core::Result<Vec<Action>> {
+            Ok([ActionKind::MarkRead, ActionKind::Archive]
+                .into_iter()
+                .map(|kind| Action {
+                    id: format!("{:?}", kind),
+                    name: format!("{:?}", kind),
+                    description: String::new(),
+                    kind,
+                    keyboard_shortcut: None,
+                })
+                .collect())
+        }
+
+        async fn execute_action(
+            &self,
+            item: &Item,
+            action: &Action,
+        ) -> scryforge_provider_core::Result<ActionResult> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push((item.id.0.clone(), action.kind.clone()));
+            Ok(ActionResult {
+                success: true,
+                message: None,
+                data: None,
+            })
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn cache_with_items(ids: &[&str]) -> Result<(TempDir, SqliteCache)> {
+        let dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&dir.path().join("cache.db"))?;
+        cache.upsert_streams(&[Stream {
+            id: StreamId("mail:inbox".to_string()),
+            name: "Inbox".to_string(),
+            provider_id: "mail".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let items: Vec<Item> = ids
+            .iter()
+            .map(|id| Item {
+                id: ItemId(id.to_string()),
+                stream_id: StreamId("mail:inbox".to_string()),
+                title: id.to_string(),
+                content: ItemContent::Text(String::new()),
+                author: None,
+                published: None,
+                updated: None,
+                url: None,
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: false,
+                tags: vec![],
+                metadata: HashMap::new(),
+            })
+            .collect();
+        cache.upsert_items(&items)?;
+        Ok((dir, cache))
+    }
+
+    #[test]
+    fn test_opposite_actions_cancel_out() -> Result<()> {
+        let (_dir, cache) = cache_with_items(&["mail:1"])?;
+        let id = ItemId("mail:1".to_string());
+
+        assert!(queue(&cache, &id, OutboxAction::Save)?);
+        assert!(queue(&cache, &id, OutboxAction::Archive)?);
+        assert!(!queue(&cache, &id, OutboxAction::Unsave)?);
+
+        let outbox = cache.get_outbox()?;
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].action, OutboxAction::Archive);
+
+        assert!(cancel(&cache, &id, OutboxAction::Archive)?);
+        assert!(!cancel(&cache, &id, OutboxAction::Archive)?);
+        assert!(cache.get_outbox()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_spam_actions() -> Result<()> {
+        let (_dir, cache) = cache_with_items(&["mail:1"])?;
+        let id = ItemId("mail:1".to_string());
+
+        assert_eq!(
+            OutboxAction::MarkSpam.kind(),
+            ActionKind::Custom("mark_spam".to_string())
+        );
+        assert!(queue(&cache, &id, OutboxAction::MarkSpam)?);
+        assert!(!queue(&cache, &id, OutboxAction::MarkNotSpam)?);
+        assert!(cache.get_outbox()?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flush_sends_due_actions_the_provider_offers() -> Result<()> {
+        let (_dir, cache) = cache_with_items(&["mail:1", "mail:2", "other:1"])?;
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = ProviderRegistry::new();
+        registry.register(MailProvider {
+            sent: Arc::clone(&sent),
+        });
+
+        queue(&cache, &ItemId("mail:1".to_string()), OutboxAction::Archive)?;
+        queue(
+            &cache,
+            &ItemId("mail:2".to_string()),
+            OutboxAction::MarkRead,
+        )?;
+        queue(&cache, &ItemId("mail:2".to_string()), OutboxAction::Save)?;
+        queue(
+            &cache,
+            &ItemId("other:1".to_string()),
+            OutboxAction::Archive,
+        )?;
+
+        // Nothing has waited long enough yet
+        let early = flush(&cache, &registry, Utc::now() - chrono::Duration::minutes(1)).await?;
+        assert_eq!(early, FlushSummary::default());
+
+        let summary = flush(&cache, &registry, Utc::now()).await?;
+        assert_eq!(
+            summary,
+            FlushSummary {
+                sent: 2,
+                unsupported: 1,
+                failed: 1,
+                dropped: 0,
+            }
+        );
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                ("mail:1".to_string(), ActionKind::Archive),
+                ("mail:2".to_string(), ActionKind::MarkRead),
+            ]
+        );
+
+        // Only the action for the unloaded provider is left, with its error
+        let outbox = cache.get_outbox()?;
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].item_id.as_str(), "other:1");
+        assert_eq!(outbox[0].attempts, 1);
+        assert!(outbox[0].last_error.is_some());
+
+        // Sent actions are on record; the unloaded provider never got one
+        let log = cache.get_audit_log(None, 10)?;
+        let logged: Vec<(&str, &str, bool)> = log
+            .iter()
+            .map(|entry| {
+                let item_id = entry.item_id.as_ref().map_or("", |id| id.as_str());
+                (item_id, entry.action.as_str(), entry.success)
+            })
+            .collect();
+        assert_eq!(
+            logged,
+            vec![("mail:2", "mark_read", true), ("mail:1", "archive", true)]
+        );
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_flush_keeps_story_actions_local() -> Result<()> {
+        let (_dir, cache) = cache_with_items(&["mail:1"])?;
+        let mut story = cache.get_item(&ItemId("mail:1".to_string()))?.unwrap();
+        story.id = ItemId("mail:1#story-1".to_string());
+        story.metadata.insert(
+            newsletters::NEWSLETTER_ID_METADATA_KEY.to_string(),
+            "mail:1".to_string(),
+        );
+        cache.upsert_items(&[story.clone()])?;
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = ProviderRegistry::new();
+        registry.register(MailProvider {
+            sent: Arc::clone(&sent),
+        });
+
+        queue(&cache, &story.id, OutboxAction::Archive)?;
+        let summary = flush(&cache, &registry, Utc::now()).await?;
+        assert_eq!(summary.unsupported, 1);
+        assert!(sent.lock().unwrap().is_empty());
+        assert!(cache.get_outbox()?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,797 @@
+//! Playing videos and podcasts in mpv.
+//!
+//! `items.play` starts mpv on an item's video or audio. Pages on YouTube and
+//! other streaming sites are resolved by mpv's yt-dlp hook, so the item's own
+//! link is enough; podcast episodes are played from the enclosure providers
+//! record in [`MEDIA_URL_KEY`] metadata.
+//!
+//! Unlike `items.open`, the [`Player`] keeps hold of the process. While mpv
+//! runs, the position is read over its JSON IPC socket and recorded with
+//! [`Cache::set_playback_progress`]; playing the item again resumes from
+//! there. Items played to the end are marked read. When mpv exits, its exit
+//! code and final position are reported by `player.status` and a
+//! [`DaemonEvent::PlaybackFinished`] event.
+//!
+//! Items of a provider with a proxy are streamed through it. mpv only speaks
+//! HTTP proxies, so playing them behind a SOCKS proxy is refused rather than
+//! sent direct.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use scryforge_provider_core::http::{check_reachable, ProxyConfig};
+use scryforge_provider_core::{Item, ItemId};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixStream;
+use tokio::process::Child;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+use crate::cache::{Cache, PlaybackProgress};
+use crate::config::PlayerConfig;
+use crate::events::{DaemonEvent, EventBus};
+use crate::opener::ContentKind;
+
+/// Metadata key holding the audio or video enclosure of a feed entry.
+pub const MEDIA_URL_KEY: &str = "media_url";
+
+/// Playback past this fraction of the duration counts as finished, so
+/// quitting during the credits still marks the item watched.
+const FINISHED_FRACTION: f64 = 0.95;
+
+/// IPC request IDs of the properties read from mpv.
+const POSITION_REQUEST: u64 = 1;
+const DURATION_REQUEST: u64 = 2;
+
+/// An item being played.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Playback {
+    pub item_id: String,
+    pub url: String,
+    /// `video` or `audio`
+    pub kind: ContentKind,
+    /// Position playback started from, in seconds
+    pub start_secs: u64,
+    /// Last position read from mpv, in seconds
+    pub position_secs: Option<u64>,
+    pub duration_secs: Option<u64>,
+}
+
+/// How a playback ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaybackResult {
+    pub item_id: String,
+    /// mpv's exit code; `None` if it was killed, as `player.stop` does
+    pub exit_code: Option<i32>,
+    /// Position playback stopped at, in seconds, if mpv reported one
+    pub position_secs: Option<u64>,
+    /// Whether the item was played to the end
+    pub finished: bool,
+}
+
+/// What the player is doing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerStatus {
+    pub playing: Option<Playback>,
+    /// The playback that ended most recently
+    pub last: Option<PlaybackResult>,
+}
+
+struct Session {
+    id: u64,
+    playback: Playback,
+    stop: Option<oneshot::Sender<()>>,
+}
+
+#[derive(Default)]
+struct PlayerState {
+    current: Option<Session>,
+    last: Option<PlaybackResult>,
+}
+
+/// Plays items in mpv, one at a time, and records how far they got.
+///
+/// Dropping the player stops playback.
+pub struct Player {
+    config: PlayerConfig,
+    /// Proxies of the providers that have one, by provider ID
+    proxies: HashMap<String, ProxyConfig>,
+    state: Arc<Mutex<PlayerState>>,
+    sessions: AtomicU64,
+}
+
+impl Player {
+    pub fn from_config(config: &PlayerConfig) -> Self {
+        Self {
+            config: config.clone(),
+            proxies: HashMap::new(),
+            state: Arc::default(),
+            sessions: AtomicU64::new(0),
+        }
+    }
+
+    /// Stream the items of each provider in `proxies` through its proxy.
+    pub fn with_proxies(mut self, proxies: &HashMap<String, ProxyConfig>) -> Self {
+        self.proxies = proxies.clone();
+        self
+    }
+
+    /// The link `item` is played from and whether it is video or audio, or
+    /// `None` if it has neither. An enclosure wins over the item's own link,
+    /// which for a podcast episode is usually its web page.
+    pub fn media(item: &Item) -> Option<(&str, ContentKind)> {
+        if let Some(url) = item.metadata.get(MEDIA_URL_KEY) {
+            let video = ContentKind::of(item, url) == ContentKind::Video
+                || item
+                    .metadata
+                    .get("media_type")
+                    .is_some_and(|mime| mime.starts_with("video/"));
+            let kind = if video {
+                ContentKind::Video
+            } else {
+                ContentKind::Audio
+            };
+            return Some((url, kind));
+        }
+
+        let url = item.url.as_deref()?;
+        let kind = ContentKind::of(item, url);
+        matches!(kind, ContentKind::Video | ContentKind::Audio).then_some((url, kind))
+    }
+
+    /// Play `item`, stopping whatever was playing, or return `Ok(None)` if
+    /// it has nothing to play.
+    ///
+    /// mpv is supervised on a background task, which records progress in
+    /// `cache` and publishes on `events` when it exits. Must be called within
+    /// a Tokio runtime. Fails if the item's provider has a proxy mpv can't
+    /// use.
+    pub fn play<C: Cache + 'static>(
+        &self,
+        item: &Item,
+        cache: Arc<C>,
+        events: Option<EventBus>,
+    ) -> Result<Option<Playback>> {
+        let Some((url, kind)) = Self::media(item) else {
+            return Ok(None);
+        };
+        let provider_id = item.id.as_str().split(':').next().unwrap_or_default();
+        let proxy = self.proxies.get(provider_id);
+        if let Some(proxy) = proxy {
+            if !proxy.url().starts_with("http://") {
+                bail!(
+                    "{} is behind {}, but mpv can only use an http:// proxy",
+                    provider_id,
+                    proxy.url()
+                );
+            }
+        }
+        check_reachable(url, proxy)?;
+        let start_secs = match cache.get_playback_progress(&item.id)? {
+            Some(progress) if self.config.resume && !progress.finished => progress.position_secs,
+            _ => 0,
+        };
+
+        let id = self.sessions.fetch_add(1, Ordering::Relaxed);
+        let socket =
+            std::env::temp_dir().join(format!("scryforge-mpv-{}-{}.sock", std::process::id(), id));
+        let child = self
+            .command(item, url, proxy, &socket, start_secs)
+            .spawn()
+            .with_context(|| format!("Failed to start player: {}", self.config.command))?;
+        debug!("Playing {} from {}s", item.id.as_str(), start_secs);
+
+        let playback = Playback {
+            item_id: item.id.0.clone(),
+            url: url.to_string(),
+            kind,
+            start_secs,
+            position_secs: None,
+            duration_secs: None,
+        };
+        let (stop_tx, stop_rx) = oneshot::channel();
+        {
+            let mut state = self.state.lock().unwrap();
+            if let Some(stop) = state.current.as_mut().and_then(|s| s.stop.take()) {
+                let _ = stop.send(());
+            }
+            state.current = Some(Session {
+                id,
+                playback: playback.clone(),
+                stop: Some(stop_tx),
+            });
+        }
+
+        let supervisor = Supervisor {
+            session: id,
+            item_id: item.id.clone(),
+            socket,
+            interval: Duration::from_secs(self.config.progress_interval_secs),
+            state: Arc::clone(&self.state),
+            cache,
+            events,
+        };
+        tokio::spawn(supervisor.run(child, stop_rx));
+
+        Ok(Some(playback))
+    }
+
+    /// Stop playback, returning whether anything was playing.
+    pub fn stop(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.current.as_mut().and_then(|s| s.stop.take()) {
+            Some(stop) => stop.send(()).is_ok(),
+            None => false,
+        }
+    }
+
+    /// The item playing and how the last playback ended.
+    pub fn status(&self) -> PlayerStatus {
+        let state = self.state.lock().unwrap();
+        PlayerStatus {
+            playing: state.current.as_ref().map(|s| s.playback.clone()),
+            last: state.last.clone(),
+        }
+    }
+
+    fn command(
+        &self,
+        item: &Item,
+        url: &str,
+        proxy: Option<&ProxyConfig>,
+        socket: &std::path::Path,
+        start_secs: u64,
+    ) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(&self.config.command);
+        command
+            .arg("--no-terminal")
+            .arg("--ytdl")
+            .arg(format!("--input-ipc-server={}", socket.display()))
+            .arg(format!("--force-media-title={}", item.title));
+        if start_secs > 0 {
+            command.arg(format!("--start={}", start_secs));
+        }
+        if let Some(format) = &self.config.ytdl_format {
+            command.arg(format!("--ytdl-format={}", format));
+        }
+        if let Some(path) = &self.config.ytdl_path {
+            command.arg(format!("--script-opts=ytdl_hook-ytdl_path={}", path));
+        }
+        if let Some(proxy) = proxy {
+            command
+                .arg(format!("--http-proxy={}", proxy.url()))
+                .arg(format!("--ytdl-raw-options-append=proxy={}", proxy.url()));
+        }
+        command
+            .args(&self.config.args)
+            .arg("--")
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        command
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Position and duration of the playing item as reported by mpv.
+#[derive(Debug, Default)]
+struct Progress {
+    position: Option<f64>,
+    duration: Option<f64>,
+    /// mpv reached the end of the file
+    ended: bool,
+}
+
+impl Progress {
+    /// Apply one line of mpv's IPC output: a property reply or an event.
+    fn apply(&mut self, line: &str) {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        if message["event"] == "end-file" {
+            self.ended |= message["reason"] == "eof";
+            return;
+        }
+        // Unavailable properties, such as the position before the file has
+        // loaded, come back with an error and no data
+        let Some(value) = message["data"].as_f64() else {
+            return;
+        };
+        match message["request_id"].as_u64() {
+            Some(POSITION_REQUEST) => self.position = Some(value),
+            Some(DURATION_REQUEST) => self.duration = Some(value),
+            _ => {}
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.ended
+            || matches!(
+                (self.position, self.duration),
+                (Some(position), Some(duration)) if duration > 0.0 && position >= duration * FINISHED_FRACTION
+            )
+    }
+
+    fn position_secs(&self) -> Option<u64> {
+        if self.ended {
+            return self.duration.or(self.position).map(|secs| secs as u64);
+        }
+        self.position.map(|secs| secs as u64)
+    }
+
+    fn duration_secs(&self) -> Option<u64> {
+        self.duration.map(|secs| secs as u64)
+    }
+}
+
+/// A connection to mpv's JSON IPC socket.
+struct Ipc {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl Ipc {
+    async fn connect(socket: &std::path::Path) -> std::io::Result<Self> {
+        let (reader, writer) = UnixStream::connect(socket).await?.into_split();
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+        })
+    }
+
+    /// Ask for the position and duration; the replies arrive as lines.
+    async fn request_progress(&mut self) -> std::io::Result<()> {
+        for (request_id, property) in [
+            (POSITION_REQUEST, "time-pos"),
+            (DURATION_REQUEST, "duration"),
+        ] {
+            let request =
+                json!({ "command": ["get_property", property], "request_id": request_id });
+            self.writer
+                .write_all(format!("{}\n", request).as_bytes())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// The next line from mpv, or `None` once the connection closes. Never
+/// resolves while disconnected.
+async fn next_line(ipc: &mut Option<Ipc>) -> Option<String> {
+    match ipc {
+        Some(ipc) => ipc.lines.next_line().await.ok().flatten(),
+        None => std::future::pending().await,
+    }
+}
+
+/// Background task owning one mpv process.
+struct Supervisor<C> {
+    session: u64,
+    item_id: ItemId,
+    socket: PathBuf,
+    interval: Duration,
+    state: Arc<Mutex<PlayerState>>,
+    cache: Arc<C>,
+    events: Option<EventBus>,
+}
+
+impl<C: Cache> Supervisor<C> {
+    async fn run(self, mut child: Child, mut stop_rx: oneshot::Receiver<()>) {
+        let mut progress = Progress::default();
+        let mut ipc: Option<Ipc> = None;
+        let mut ticker = tokio::time::interval(self.interval);
+
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => break status,
+                // Also resolves if the player is gone
+                _ = &mut stop_rx => {
+                    let _ = child.start_kill();
+                    break child.wait().await;
+                }
+                line = next_line(&mut ipc) => match line {
+                    Some(line) => progress.apply(&line),
+                    None => ipc = None,
+                },
+                _ = ticker.tick() => {
+                    // mpv creates the socket some time after it starts
+                    if ipc.is_none() {
+                        ipc = Ipc::connect(&self.socket).await.ok();
+                    }
+                    if let Some(conn) = ipc.as_mut() {
+                        if conn.request_progress().await.is_err() {
+                            ipc = None;
+                        }
+                    }
+                    self.record(&progress);
+                }
+            }
+        };
+
+        // Replies and the end-of-file event may still be buffered
+        if let Some(mut conn) = ipc {
+            while let Ok(Ok(Some(line))) =
+                tokio::time::timeout(Duration::from_millis(100), conn.lines.next_line()).await
+            {
+                progress.apply(&line);
+            }
+        }
+        let _ = std::fs::remove_file(&self.socket);
+
+        self.finish(status, &progress);
+    }
+
+    /// Record `progress` in the cache and the player status.
+    fn record(&self, progress: &Progress) {
+        if let Some(session) = self.state.lock().unwrap().current.as_mut() {
+            if session.id == self.session {
+                session.playback.position_secs = progress.position_secs();
+                session.playback.duration_secs = progress.duration_secs();
+            }
+        }
+
+        let finished = progress.finished();
+        let Some(position_secs) = progress.position_secs().or(finished.then_some(0)) else {
+            return;
+        };
+        let recorded = self.cache.set_playback_progress(&PlaybackProgress {
+            item_id: self.item_id.clone(),
+            position_secs,
+            duration_secs: progress.duration_secs(),
+            finished,
+            updated_at: Utc::now(),
+        });
+        if let Err(e) = recorded {
+            warn!(
+                "Failed to record playback progress of {}: {:#}",
+                self.item_id.as_str(),
+                e
+            );
+        }
+    }
+
+    fn finish(&self, status: std::io::Result<ExitStatus>, progress: &Progress) {
+        let exit_code = match status {
+            Ok(status) => status.code(),
+            Err(e) => {
+                warn!(
+                    "Failed to wait for the player of {}: {}",
+                    self.item_id.as_str(),
+                    e
+                );
+                None
+            }
+        };
+        match exit_code {
+            Some(0) | None => {}
+            Some(code) => warn!(
+                "Player exited with code {} playing {}",
+                code,
+                self.item_id.as_str()
+            ),
+        }
+
+        self.record(progress);
+        let finished = progress.finished();
+        if finished {
+            if let Err(e) = self.cache.mark_read(&self.item_id, true) {
+                warn!(
+                    "Failed to mark played item '{}' as read: {}",
+                    self.item_id.as_str(),
+                    e
+                );
+            }
+        }
+
+        let result = PlaybackResult {
+            item_id: self.item_id.0.clone(),
+            exit_code,
+            position_secs: progress.position_secs(),
+            finished,
+        };
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.current.as_ref().is_some_and(|s| s.id == self.session) {
+                state.current = None;
+            }
+            state.last = Some(result.clone());
+        }
+
+        if let Some(events) = &self.events {
+            events.publish(DaemonEvent::PlaybackFinished {
+                item_id: result.item_id,
+                exit_code: result.exit_code,
+                position_secs: result.position_secs,
+                finished: result.finished,
+                timestamp: Utc::now(),
+            });
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use scryforge_provider_core::{ItemContent, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use tokio::net::UnixListener;
+
+    fn item(url: &str) -> Item {
+        Item {
+            id: ItemId("youtube:abc".to_string()),
+            stream_id: StreamId("youtube:subscriptions".to_string()),
+            title: "A talk".to_string(),
+            content: ItemContent::Video {
+                description: String::new(),
+                duration_seconds: Some(600),
+                view_count: None,
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: Some(url.to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn cache_with(temp_dir: &TempDir, item: &Item) -> Arc<SqliteCache> {
+        let cache = SqliteCache::open_at(&temp_dir.path().join("cache.db")).unwrap();
+        cache
+            .upsert_streams(&[Stream {
+                id: item.stream_id.clone(),
+                name: "Subscriptions".to_string(),
+                provider_id: "youtube".to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])
+            .unwrap();
+        cache.upsert_items(std::slice::from_ref(item)).unwrap();
+        Arc::new(cache)
+    }
+
+    /// A stand-in for mpv that logs its arguments, one per line, then exits
+    /// with `exit_code` after `sleep` seconds.
+    fn fake_mpv(temp_dir: &TempDir, sleep: u32, exit_code: i32) -> (PlayerConfig, PathBuf) {
+        let script = temp_dir.path().join("mpv");
+        let log = temp_dir.path().join("args");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\nsleep {}\nexit {}\n",
+                log.display(),
+                sleep,
+                exit_code
+            ),
+        )
+        .unwrap();
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = PlayerConfig {
+            command: script.display().to_string(),
+            progress_interval_secs: 1,
+            ..PlayerConfig::default()
+        };
+        (config, log)
+    }
+
+    async fn wait_idle(player: &Player) -> PlayerStatus {
+        for _ in 0..500 {
+            let status = player.status();
+            if status.playing.is_none() && status.last.is_some() {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("player did not exit: {:?}", player.status());
+    }
+
+    #[test]
+    fn test_media_prefers_enclosures_and_skips_articles() {
+        let video = item("https://www.youtube.com/watch?v=abc");
+        assert_eq!(
+            Player::media(&video),
+            Some(("https://www.youtube.com/watch?v=abc", ContentKind::Video))
+        );
+
+        let mut episode = item("https://example.com/episodes/42");
+        episode.content = ItemContent::Article {
+            summary: None,
+            full_content: None,
+        };
+        assert_eq!(Player::media(&episode), None);
+
+        episode.metadata.insert(
+            MEDIA_URL_KEY.to_string(),
+            "https://cdn.example.com/42?token=x".to_string(),
+        );
+        assert_eq!(
+            Player::media(&episode),
+            Some(("https://cdn.example.com/42?token=x", ContentKind::Audio))
+        );
+    }
+
+    #[test]
+    fn test_progress_from_ipc_messages() {
+        let mut progress = Progress::default();
+        progress.apply(r#"{"request_id":1,"error":"property unavailable"}"#);
+        progress.apply(r#"{"data":120.6,"request_id":1,"error":"success"}"#);
+        progress.apply(r#"{"data":600.0,"request_id":2,"error":"success"}"#);
+        progress.apply(r#"{"event":"playback-restart"}"#);
+        assert_eq!(progress.position_secs(), Some(120));
+        assert_eq!(progress.duration_secs(), Some(600));
+        assert!(!progress.finished());
+
+        // The credits count as the end
+        progress.apply(r#"{"data":585.0,"request_id":1,"error":"success"}"#);
+        assert!(progress.finished());
+
+        let mut progress = Progress::default();
+        progress.apply(r#"{"data":30.0,"request_id":1,"error":"success"}"#);
+        progress.apply(r#"{"event":"end-file","reason":"quit"}"#);
+        assert!(!progress.finished());
+        progress.apply(r#"{"event":"end-file","reason":"eof"}"#);
+        assert!(progress.finished());
+    }
+
+    #[tokio::test]
+    async fn test_play_resumes_and_reports_exit_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let video = item("https://www.youtube.com/watch?v=abc");
+        let cache = cache_with(&temp_dir, &video);
+        cache
+            .set_playback_progress(&PlaybackProgress {
+                item_id: video.id.clone(),
+                position_secs: 120,
+                duration_secs: Some(600),
+                finished: false,
+                updated_at: Utc::now(),
+            })
+            .unwrap();
+        let (config, log) = fake_mpv(&temp_dir, 0, 2);
+        let player = Player::from_config(&config);
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+
+        let playback = player
+            .play(&video, Arc::clone(&cache), Some(events))
+            .unwrap()
+            .unwrap();
+        assert_eq!(playback.start_secs, 120);
+        assert_eq!(playback.kind, ContentKind::Video);
+
+        let status = wait_idle(&player).await;
+        let last = status.last.unwrap();
+        assert_eq!(last.exit_code, Some(2));
+        assert!(!last.finished);
+        match rx.recv().await.unwrap() {
+            DaemonEvent::PlaybackFinished { exit_code, .. } => assert_eq!(exit_code, Some(2)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let args = std::fs::read_to_string(&log).unwrap();
+        let args: Vec<&str> = args.lines().collect();
+        assert!(args.contains(&"--start=120"));
+        assert!(args.contains(&"--ytdl"));
+        assert_eq!(
+            &args[args.len() - 2..],
+            ["--", "https://www.youtube.com/watch?v=abc"]
+        );
+        // A failed start leaves the recorded position alone
+        assert_eq!(
+            cache
+                .get_playback_progress(&video.id)
+                .unwrap()
+                .unwrap()
+                .position_secs,
+            120
+        );
+    }
+
+    #[tokio::test]
+    async fn test_play_goes_through_provider_proxy() {
+        let temp_dir = TempDir::new().unwrap();
+        let video = item("https://www.youtube.com/watch?v=abc");
+        let cache = cache_with(&temp_dir, &video);
+        let (config, log) = fake_mpv(&temp_dir, 0, 0);
+
+        let http = HashMap::from([(
+            "youtube".to_string(),
+            ProxyConfig::parse("http://127.0.0.1:3128").unwrap(),
+        )]);
+        let player = Player::from_config(&config).with_proxies(&http);
+        player.play(&video, Arc::clone(&cache), None).unwrap();
+        wait_idle(&player).await;
+        let args = std::fs::read_to_string(&log).unwrap();
+        let args: Vec<&str> = args.lines().collect();
+        assert!(args.contains(&"--http-proxy=http://127.0.0.1:3128"));
+        assert!(args.contains(&"--ytdl-raw-options-append=proxy=http://127.0.0.1:3128"));
+
+        let tor = HashMap::from([("youtube".to_string(), ProxyConfig::parse("tor").unwrap())]);
+        let player = Player::from_config(&config).with_proxies(&tor);
+        assert!(player.play(&video, cache, None).is_err());
+        assert!(player.status().last.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_play_records_position_from_mpv() {
+        let temp_dir = TempDir::new().unwrap();
+        let video = item("https://www.youtube.com/watch?v=abc");
+        let cache = cache_with(&temp_dir, &video);
+        let (config, log) = fake_mpv(&temp_dir, 2, 0);
+        let player = Player::from_config(&config);
+
+        player.play(&video, Arc::clone(&cache), None).unwrap();
+
+        // Serve the IPC socket the player was told to create
+        let socket = loop {
+            let args = std::fs::read_to_string(&log).unwrap_or_default();
+            if let Some(socket) = args
+                .lines()
+                .find_map(|arg| arg.strip_prefix("--input-ipc-server="))
+            {
+                break PathBuf::from(socket);
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        let listener = UnixListener::bind(&socket).unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut requests = BufReader::new(reader).lines();
+        for _ in 0..2 {
+            let request: Value =
+                serde_json::from_str(&requests.next_line().await.unwrap().unwrap()).unwrap();
+            let data = match request["command"][1].as_str() {
+                Some("time-pos") => 300.0,
+                _ => 600.0,
+            };
+            let reply =
+                json!({ "data": data, "request_id": request["request_id"], "error": "success" });
+            writer
+                .write_all(format!("{}\n", reply).as_bytes())
+                .await
+                .unwrap();
+        }
+
+        let status = wait_idle(&player).await;
+        let last = status.last.unwrap();
+        assert_eq!(last.exit_code, Some(0));
+        assert_eq!(last.position_secs, Some(300));
+        assert!(!last.finished);
+
+        let progress = cache.get_playback_progress(&video.id).unwrap().unwrap();
+        assert_eq!(progress.position_secs, 300);
+        assert_eq!(progress.duration_secs, Some(600));
+        assert!(!cache.get_item(&video.id).unwrap().unwrap().is_read);
+    }
+}
@@ -0,0 +1,146 @@
+//! The reading queue: saved items from every provider, in the order the
+//! reader means to get to them.
+//!
+//! The cache keeps the order and where the reader left off in each item
+//! (see [`Cache::get_reading_queue`]); this module adds what a client shows
+//! alongside them: how long each item takes to get through and which item
+//! to resume.
+
+use scryforge_provider_core::{Item, ItemId};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{Cache, QueuedItem};
+use crate::reading_time::reading_minutes;
+
+/// A queued item with its place in the queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub item: Item,
+    /// 1-based place in the queue
+    pub position: u32,
+    /// Estimated minutes to read, watch, or listen to the item
+    pub minutes: u32,
+    /// Where the reader left off, in the units of the client that recorded it
+    pub offset: u32,
+}
+
+/// The reading queue, in order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadingQueue {
+    pub entries: Vec<QueueEntry>,
+    /// The item opened most recently, else the first in the queue; `None`
+    /// when the queue is empty
+    pub resume: Option<ItemId>,
+    /// Sum of the entries' `minutes`
+    pub total_minutes: u32,
+}
+
+impl ReadingQueue {
+    /// Build the queue from the cache's queued items.
+    pub fn new(queued: Vec<QueuedItem>) -> Self {
+        let resume = queued
+            .iter()
+            .filter_map(|q| q.opened_at.map(|at| (at, &q.item.id)))
+            .max_by_key(|(at, _)| *at)
+            .map(|(_, id)| id.clone())
+            .or_else(|| queued.first().map(|q| q.item.id.clone()));
+
+        let entries: Vec<QueueEntry> = queued
+            .into_iter()
+            .enumerate()
+            .map(|(i, q)| QueueEntry {
+                minutes: reading_minutes(&q.item),
+                item: q.item,
+                position: i as u32 + 1,
+                offset: q.offset,
+            })
+            .collect();
+        let total_minutes = entries.iter().map(|entry| entry.minutes).sum();
+
+        Self {
+            entries,
+            resume,
+            total_minutes,
+        }
+    }
+
+    /// Load the queue from `cache`.
+    pub fn load<C: Cache + ?Sized>(cache: &C) -> anyhow::Result<Self> {
+        Ok(Self::new(cache.get_reading_queue()?))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use scryforge_provider_core::{ItemContent, StreamId};
+
+    fn item(id: &str, content: ItemContent) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:a".to_string()),
+            title: id.to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: true,
+            tags: vec![],
+            metadata: Default::default(),
+        }
+    }
+
+    fn article(id: &str, words: usize) -> Item {
+        item(
+            id,
+            ItemContent::Article {
+                summary: Some("A short summary".to_string()),
+                full_content: Some(format!("<p>{}</p>", "word ".repeat(words))),
+            },
+        )
+    }
+
+    fn queued(item: Item, opened_hours_ago: Option<i64>) -> QueuedItem {
+        QueuedItem {
+            item,
+            offset: 0,
+            opened_at: opened_hours_ago.map(|hours| Utc::now() - Duration::hours(hours)),
+        }
+    }
+
+    #[test]
+    fn test_reading_queue() {
+        let queue = ReadingQueue::new(vec![
+            queued(article("a", 460), None),
+            queued(article("b", 230), Some(5)),
+            queued(article("c", 690), Some(1)),
+        ]);
+        let positions: Vec<(&str, u32, u32)> = queue
+            .entries
+            .iter()
+            .map(|e| (e.item.id.as_str(), e.position, e.minutes))
+            .collect();
+        assert_eq!(positions, vec![("a", 1, 2), ("b", 2, 1), ("c", 3, 3)]);
+        assert_eq!(queue.total_minutes, 6);
+        // The most recently opened item is where reading resumes
+        assert_eq!(queue.resume, Some(ItemId("c".to_string())));
+
+        let unopened = ReadingQueue::new(vec![
+            queued(article("a", 10), None),
+            queued(article("b", 10), None),
+        ]);
+        assert_eq!(unopened.resume, Some(ItemId("a".to_string())));
+
+        let empty = ReadingQueue::new(vec![]);
+        assert!(empty.resume.is_none());
+        assert_eq!(empty.total_minutes, 0);
+    }
+}
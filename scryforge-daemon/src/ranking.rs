@@ -0,0 +1,257 @@
+//! Relevance ranking for the unified feed.
+//!
+//! The [`Ranker`] scores items so the inbox can be sorted by "interesting"
+//! rather than strictly by date. A score combines three signals:
+//!
+//! - **Engagement**: provider metadata such as Reddit score, comment and like
+//!   counts, and video views, log-scaled so viral posts don't drown out
+//!   everything else.
+//! - **Affinity**: how often the user has opened, saved, or skipped items
+//!   from the same stream and author, taken from the cache's interaction log.
+//! - **Recency**: the combined score halves every `half_life_hours`.
+
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::{Item, ItemContent};
+
+use crate::cache::{InteractionCounts, InteractionStats};
+use crate::config::RankingConfig;
+
+/// Weight of one view; views are far more common than votes or comments.
+const VIEW_WEIGHT: f64 = 0.01;
+
+/// Metadata keys holding engagement counts, with the weight of one unit.
+const ENGAGEMENT_KEYS: &[(&str, f64)] = &[
+    ("score", 1.0),
+    ("num_comments", 1.0),
+    ("comment_count", 1.0),
+    ("like_count", 1.0),
+    ("view_count", VIEW_WEIGHT),
+];
+
+/// Interactions assumed before any are recorded, so a single open does not
+/// swing a stream's affinity to the maximum.
+const AFFINITY_PRIOR: f64 = 5.0;
+
+/// A save counts this many times as much as an open.
+const SAVE_WEIGHT: f64 = 3.0;
+
+/// Scores items using engagement, interaction history, and age.
+#[derive(Debug, Clone)]
+pub struct Ranker {
+    config: RankingConfig,
+    stats: InteractionStats,
+    now: DateTime<Utc>,
+}
+
+impl Ranker {
+    /// Create a ranker that scores items as of `now`.
+    pub fn new(config: RankingConfig, stats: InteractionStats, now: DateTime<Utc>) -> Self {
+        Self { config, stats, now }
+    }
+
+    /// Relevance score for an item; higher is more interesting.
+    pub fn score(&self, item: &Item) -> f64 {
+        let stream_affinity = affinity(self.stats.by_stream.get(item.stream_id.as_str()));
+        let author_affinity = affinity(
+            item.author
+                .as_ref()
+                .and_then(|author| self.stats.by_author.get(&author.name)),
+        );
+
+        let base = 1.0
+            + self.config.engagement_weight * engagement(item)
+            + self.config.affinity_weight * (stream_affinity + author_affinity);
+
+        base.max(0.0) * self.decay(item)
+    }
+
+    /// Sort items by descending score.
+    pub fn rank(&self, items: &mut [Item]) {
+        let mut scored: Vec<(f64, Item)> = items
+            .iter()
+            .map(|item| (self.score(item), item.clone()))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        for (slot, (_, item)) in items.iter_mut().zip(scored) {
+            *slot = item;
+        }
+    }
+
+    /// Exponential decay by age; undated items are treated as one half-life old.
+    fn decay(&self, item: &Item) -> f64 {
+        let age_hours = match item.published.or(item.updated) {
+            Some(published) => ((self.now - published).num_seconds().max(0) as f64) / 3600.0,
+            None => self.config.half_life_hours,
+        };
+        0.5_f64.powf(age_hours / self.config.half_life_hours)
+    }
+}
+
+/// Log-scaled engagement from item metadata and video view counts.
+pub fn engagement(item: &Item) -> f64 {
+    let mut total: f64 = ENGAGEMENT_KEYS
+        .iter()
+        .filter_map(|(key, weight)| {
+            let value = item.metadata.get(*key)?.parse::<f64>().ok()?;
+            Some(value.max(0.0) * weight)
+        })
+        .sum();
+
+    if !item.metadata.contains_key("view_count") {
+        if let ItemContent::Video {
+            view_count: Some(views),
+            ..
+        } = item.content
+        {
+            total += views as f64 * VIEW_WEIGHT;
+        }
+    }
+
+    total.ln_1p()
+}
+
+/// Smoothed preference in `[-1, SAVE_WEIGHT)`; zero without history.
+fn affinity(counts: Option<&InteractionCounts>) -> f64 {
+    let Some(counts) = counts else {
+        return 0.0;
+    };
+    let opened = counts.opened as f64;
+    let saved = counts.saved as f64;
+    let skipped = counts.skipped as f64;
+
+    (opened + SAVE_WEIGHT * saved - skipped) / (opened + saved + skipped + AFFINITY_PRIOR)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use scryforge_provider_core::{Author, ItemId, StreamId};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn item(id: &str, stream: &str, hours_old: i64, metadata: &[(&str, &str)]) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream.to_string()),
+            title: id.to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: Some(now() - Duration::hours(hours_old)),
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn ranker(stats: InteractionStats) -> Ranker {
+        Ranker::new(RankingConfig::default(), stats, now())
+    }
+
+    #[test]
+    fn test_engagement() {
+        assert_eq!(engagement(&item("a", "s", 0, &[])), 0.0);
+
+        let popular = item("b", "s", 0, &[("score", "500"), ("num_comments", "120")]);
+        let quiet = item(
+            "c",
+            "s",
+            0,
+            &[("score", "3"), ("num_comments", "not a number")],
+        );
+        assert!(engagement(&popular) > engagement(&quiet));
+
+        let mut video = item("d", "s", 0, &[]);
+        video.content = ItemContent::Video {
+            description: String::new(),
+            duration_seconds: None,
+            view_count: Some(10_000),
+        };
+        assert!((engagement(&video) - 101f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recency_decay() {
+        let ranker = ranker(InteractionStats::default());
+        let fresh = ranker.score(&item("a", "s", 0, &[]));
+        let day_old = ranker.score(&item("b", "s", 24, &[]));
+
+        assert!((fresh - 1.0).abs() < 1e-9);
+        assert!((day_old - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_affinity_reorders_items() {
+        let mut stats = InteractionStats::default();
+        stats.by_stream.insert(
+            "rss:feed:loved".to_string(),
+            InteractionCounts {
+                opened: 10,
+                saved: 4,
+                skipped: 0,
+            },
+        );
+        stats.by_stream.insert(
+            "rss:feed:ignored".to_string(),
+            InteractionCounts {
+                opened: 0,
+                saved: 0,
+                skipped: 20,
+            },
+        );
+        stats.by_author.insert(
+            "Favourite".to_string(),
+            InteractionCounts {
+                opened: 0,
+                saved: 2,
+                skipped: 0,
+            },
+        );
+
+        let mut by_author = item("author", "rss:feed:other", 6, &[]);
+        by_author.author = Some(Author {
+            name: "Favourite".to_string(),
+            email: None,
+            url: None,
+            avatar_url: None,
+        });
+
+        let mut items = vec![
+            item("ignored", "rss:feed:ignored", 0, &[]),
+            item("loved", "rss:feed:loved", 6, &[]),
+            item("plain", "rss:feed:other", 0, &[]),
+            by_author,
+        ];
+        ranker(stats).rank(&mut items);
+
+        let order: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(order, vec!["loved", "author", "plain", "ignored"]);
+    }
+
+    #[test]
+    fn test_weights_can_disable_signals() {
+        let config = RankingConfig {
+            engagement_weight: 0.0,
+            ..RankingConfig::default()
+        };
+        let ranker = Ranker::new(config, InteractionStats::default(), now());
+        let popular = item("a", "s", 0, &[("score", "1000")]);
+        assert!((ranker.score(&popular) - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,139 @@
+//! Estimated time to read, watch, or listen to an item.
+//!
+//! The cache stamps every item it stores with its estimate under
+//! [`READING_MINUTES_KEY`], so clients can show it and the unified feed and
+//! search can sort and filter on it without re-counting words.
+
+use scryforge_provider_core::{Item, ItemContent};
+
+/// Reading speed the estimates assume.
+pub const WORDS_PER_MINUTE: usize = 230;
+
+/// Metadata key holding an item's estimated minutes.
+pub const READING_MINUTES_KEY: &str = "reading_minutes";
+
+/// Estimated minutes to get through an item, at least one.
+///
+/// Videos and tracks take their running time; everything else, articles and
+/// emails included, is timed by the words of its full text at
+/// [`WORDS_PER_MINUTE`].
+pub fn estimated_minutes(item: &Item) -> u32 {
+    let seconds = match &item.content {
+        ItemContent::Video {
+            duration_seconds: Some(seconds),
+            ..
+        } => Some(*seconds),
+        ItemContent::Track {
+            duration_ms: Some(ms),
+            ..
+        } => Some(ms / 1000),
+        _ => None,
+    };
+    let minutes = match seconds {
+        Some(seconds) => seconds.div_ceil(60) as usize,
+        None => {
+            let words = item
+                .content
+                .full_text()
+                .map_or(0, |text| text.split_whitespace().count());
+            words.div_ceil(WORDS_PER_MINUTE)
+        }
+    };
+    minutes.max(1) as u32
+}
+
+/// An item's minutes: the estimate stored in its metadata, or a fresh one
+/// for items that haven't been through the cache, such as remote search
+/// results.
+pub fn reading_minutes(item: &Item) -> u32 {
+    item.metadata
+        .get(READING_MINUTES_KEY)
+        .and_then(|minutes| minutes.parse().ok())
+        .unwrap_or_else(|| estimated_minutes(item))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemId, StreamId};
+
+    fn item(id: &str, content: ItemContent) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:a".to_string()),
+            title: id.to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: Default::default(),
+        }
+    }
+
+    fn article(id: &str, words: usize) -> Item {
+        item(
+            id,
+            ItemContent::Article {
+                summary: Some("A short summary".to_string()),
+                full_content: Some(format!("<p>{}</p>", "word ".repeat(words))),
+            },
+        )
+    }
+
+    #[test]
+    fn test_estimated_minutes() {
+        assert_eq!(estimated_minutes(&article("long", 2300)), 10);
+        assert_eq!(estimated_minutes(&article("odd", 231)), 2);
+        assert_eq!(estimated_minutes(&article("empty", 0)), 1);
+        assert_eq!(
+            estimated_minutes(&item("note", ItemContent::Text("one two".to_string()))),
+            1
+        );
+        assert_eq!(
+            estimated_minutes(&item(
+                "video",
+                ItemContent::Video {
+                    description: "word ".repeat(5000),
+                    duration_seconds: Some(61 * 60 + 5),
+                    view_count: None,
+                }
+            )),
+            62
+        );
+        assert_eq!(
+            estimated_minutes(&item(
+                "track",
+                ItemContent::Track {
+                    album: None,
+                    duration_ms: Some(3 * 60 * 1000),
+                    artists: vec![],
+                }
+            )),
+            3
+        );
+    }
+
+    #[test]
+    fn test_reading_minutes_prefers_stored_estimate() {
+        let mut stored = article("stored", 2300);
+        stored
+            .metadata
+            .insert(READING_MINUTES_KEY.to_string(), "4".to_string());
+        assert_eq!(reading_minutes(&stored), 4);
+
+        let mut garbled = article("garbled", 2300);
+        garbled
+            .metadata
+            .insert(READING_MINUTES_KEY.to_string(), "soon".to_string());
+        assert_eq!(reading_minutes(&garbled), 10);
+    }
+}
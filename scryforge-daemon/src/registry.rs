@@ -5,10 +5,31 @@
 //! The registry stores providers by their ID and allows retrieval by ID or listing
 //! all available providers. Providers are stored as trait objects to enable runtime
 //! polymorphism.
-
+//!
+//! Calls into providers go through [`ProviderRegistry::guard`], which turns a
+//! panic inside a provider (an `unwrap` on malformed API data, say) into an
+//! error and marks the provider unhealthy, instead of unwinding into the sync
+//! task or API connection that made the call. The guard also counts the call
+//! against the provider's daily [`Budgets`], and refuses it once they are
+//! used up.
+
+use futures::FutureExt;
+use scryforge_provider_core::crypto::StoreCipher;
+use scryforge_provider_core::http::ProxyConfig;
 use scryforge_provider_core::prelude::*;
+use scryforge_sigilforge_client::{MockTokenFetcher, SigilforgeClient, TokenFetcher};
+use std::any::Any;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use tracing::{error, info, warn};
+
+use crate::budget::Budgets;
+use crate::config::Config;
+
+/// Sigilforge account the YouTube provider fetches tokens for.
+pub const YOUTUBE_ACCOUNT: &str = "personal";
 
 /// Registry for managing loaded providers.
 ///
@@ -29,6 +50,9 @@ use std::sync::Arc;
 /// ```
 pub struct ProviderRegistry {
     providers: HashMap<String, Arc<dyn Provider>>,
+    /// Panic message of each provider whose last guarded call panicked
+    panics: Mutex<HashMap<String, String>>,
+    budgets: Budgets,
 }
 
 impl ProviderRegistry {
@@ -36,9 +60,192 @@ impl ProviderRegistry {
     pub fn new() -> Self {
         Self {
             providers: HashMap::new(),
+            panics: Mutex::new(HashMap::new()),
+            budgets: Budgets::new(),
         }
     }
 
+    /// Limit the operations run on providers and the items got from them
+    /// to `budgets`.
+    pub fn with_budgets(mut self, budgets: Budgets) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    /// The providers' budgets and today's usage.
+    pub fn budgets(&self) -> &Budgets {
+        &self.budgets
+    }
+
+    /// Create a registry with the built-in providers: dummy (with any faults
+    /// or scenario configured under `[providers.dummy.settings]`), YouTube
+    /// (with channel feeds and SponsorBlock under
+    /// `[providers.youtube.settings]`), local bookmarks, and RSS when
+    /// `[providers.rss]` is enabled with feeds under its settings.
+    ///
+    /// `cipher` opens an encrypted bookmarks file, and encrypts it when the
+    /// bookmarks provider has `encrypt_at_rest` set. Each provider's
+    /// `budget` is enforced by [`ProviderRegistry::guard`].
+    pub fn with_builtin_providers(
+        config: &Config,
+        token_fetcher: Arc<dyn TokenFetcher + Send + Sync>,
+        cipher: Option<&StoreCipher>,
+    ) -> Self {
+        let mut registry = Self::new().with_budgets(Budgets::from_config(config));
+
+        info!("Loading dummy provider...");
+        let faults = config
+            .providers
+            .get("dummy")
+            .map(|dummy| {
+                dummy
+                    .settings
+                    .clone()
+                    .try_into::<provider_dummy::FaultConfig>()
+            })
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Ignoring invalid dummy provider settings: {}", e);
+                None
+            })
+            .unwrap_or_default();
+        if faults.is_active() {
+            info!("Dummy provider injecting faults: {:?}", faults);
+        }
+        let mut dummy = provider_dummy::DummyProvider::with_faults(faults);
+        let scenario_path = config
+            .providers
+            .get("dummy")
+            .and_then(|dummy| dummy.settings.get("scenario"))
+            .and_then(|path| path.as_str());
+        if let Some(path) = scenario_path {
+            match provider_dummy::Scenario::load(std::path::Path::new(path)) {
+                Ok(scenario) => {
+                    info!(
+                        "Dummy provider replaying scenario {:?} ({} steps)",
+                        scenario.name,
+                        scenario.steps.len()
+                    );
+                    dummy = dummy.with_scenario(scenario);
+                }
+                Err(e) => warn!("Ignoring dummy provider scenario: {}", e),
+            }
+        }
+        registry.register(dummy);
+
+        info!("Loading YouTube provider...");
+        let youtube_settings = config
+            .providers
+            .get("youtube")
+            .map(|youtube| {
+                youtube
+                    .settings
+                    .clone()
+                    .try_into::<provider_youtube::YouTubeSettings>()
+            })
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Ignoring invalid YouTube provider settings: {}", e);
+                None
+            })
+            .unwrap_or_default();
+        let youtube =
+            provider_youtube::YouTubeProvider::new(token_fetcher, YOUTUBE_ACCOUNT.to_string())
+                .with_settings(youtube_settings);
+        match with_proxy(config, "youtube", youtube, |youtube, proxy| {
+            youtube.with_proxy(proxy)
+        }) {
+            Ok(youtube) => registry.register(youtube),
+            Err(e) => warn!("YouTube provider not loaded: {}", e),
+        }
+
+        // Feeds only load when configured, e.g. by `scry youtube-to-rss`
+        if let Some(rss) = config.providers.get("rss").filter(|rss| rss.enabled) {
+            match rss
+                .settings
+                .clone()
+                .try_into::<provider_rss::RssProviderConfig>()
+            {
+                Ok(settings) => {
+                    info!("Loading RSS provider ({} feeds)...", settings.feeds.len());
+                    let provider = provider_rss::RssProvider::new(settings);
+                    match with_proxy(config, "rss", provider, |rss, proxy| rss.with_proxy(proxy)) {
+                        Ok(provider) => registry.register(provider),
+                        Err(e) => warn!("RSS provider not loaded: {}", e),
+                    }
+                }
+                Err(e) => warn!("Ignoring invalid RSS provider settings: {}", e),
+            }
+        }
+
+        // Local bookmarks are also a read-later target for save routing
+        info!("Loading bookmarks provider...");
+        let bookmarks = match cipher {
+            Some(cipher) => {
+                let encrypt = config
+                    .providers
+                    .get("bookmarks")
+                    .is_some_and(|bookmarks| bookmarks.encrypt_at_rest);
+                provider_bookmarks::BookmarksProvider::default_storage_path().and_then(|path| {
+                    provider_bookmarks::BookmarksProvider::with_cipher(
+                        path,
+                        cipher.clone(),
+                        encrypt,
+                    )
+                })
+            }
+            None => provider_bookmarks::BookmarksProvider::new(),
+        };
+        match bookmarks {
+            Ok(provider) => registry.register(provider),
+            Err(e) => info!("Bookmarks provider unavailable: {}", e),
+        }
+
+        registry
+    }
+
+    /// Run a call into a provider, catching panics.
+    ///
+    /// A panic is logged and returned as [`StreamError::Internal`], and the
+    /// provider is reported unhealthy by [`ProviderRegistry::panic_message`]
+    /// until a later guarded call succeeds. A provider whose daily budget is
+    /// used up isn't called, and [`StreamError::RateLimited`] is returned
+    /// until the budget resets.
+    pub async fn guard<T, F>(&self, provider_id: &str, call: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.budgets.spend_operation(provider_id)?;
+        match AssertUnwindSafe(call).catch_unwind().await {
+            Ok(result) => {
+                if result.is_ok() && self.panics().remove(provider_id).is_some() {
+                    info!("Provider '{}' recovered after a panic", provider_id);
+                }
+                result
+            }
+            Err(payload) => {
+                let message = panic_payload_message(payload.as_ref());
+                error!("Provider '{}' panicked: {}", provider_id, message);
+                self.panics()
+                    .insert(provider_id.to_string(), message.clone());
+                Err(StreamError::Internal(format!(
+                    "Provider '{}' panicked: {}",
+                    provider_id, message
+                )))
+            }
+        }
+    }
+
+    /// Message of the panic that marked a provider unhealthy, if any.
+    pub fn panic_message(&self, id: &str) -> Option<String> {
+        self.panics().get(id).cloned()
+    }
+
+    fn panics(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        // A poisoned map still holds valid messages
+        self.panics.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Register a provider with the registry.
     ///
     /// The provider's ID will be used as the key. If a provider with the same ID
@@ -114,6 +321,52 @@ impl Default for ProviderRegistry {
     }
 }
 
+/// Token source for OAuth providers: the Sigilforge daemon if it is running,
+/// otherwise mock tokens that make those providers report auth errors.
+pub fn default_token_fetcher() -> Arc<dyn TokenFetcher + Send + Sync> {
+    let client = SigilforgeClient::with_default_path();
+    if client.is_available() {
+        info!("Sigilforge daemon available - OAuth providers enabled");
+        Arc::new(client)
+    } else {
+        info!("Sigilforge not available - YouTube provider will use mock tokens");
+        Arc::new(MockTokenFetcher::empty())
+    }
+}
+
+/// Route a provider's requests through its configured proxy, if any.
+///
+/// A provider whose proxy can't be set up isn't loaded rather than left to
+/// connect directly.
+fn with_proxy<P>(
+    config: &Config,
+    provider_id: &str,
+    provider: P,
+    apply: impl FnOnce(P, &ProxyConfig) -> Result<P>,
+) -> anyhow::Result<P> {
+    let proxy = match config.providers.get(provider_id) {
+        Some(provider_config) => provider_config.proxy_config()?,
+        None => None,
+    };
+    match proxy {
+        Some(proxy) => {
+            info!("Provider '{}' using proxy {}", provider_id, proxy.url());
+            Ok(apply(provider, &proxy)?)
+        }
+        None => Ok(provider),
+    }
+}
+
+/// Text of a panic payload, which is a `&str` or `String` for `panic!` and
+/// `unwrap` failures.
+fn panic_payload_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +410,7 @@ mod tests {
                 items_removed: 0,
                 errors: vec![],
                 duration_ms: 0,
+                streams: Vec::new(),
             })
         }
 
@@ -264,4 +518,67 @@ mod tests {
         // Should still have only one provider
         assert_eq!(registry.count(), 1);
     }
+
+    #[tokio::test]
+    async fn test_guard_catches_panics() {
+        let registry = ProviderRegistry::new();
+
+        let result: Result<()> = registry.guard("test", async { panic!("bad regex") }).await;
+        match result {
+            Err(StreamError::Internal(message)) => assert!(message.contains("bad regex")),
+            other => panic!("expected an internal error, got {:?}", other),
+        }
+        assert_eq!(registry.panic_message("test").as_deref(), Some("bad regex"));
+        assert!(registry.panic_message("other").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_guard_success_clears_panic() {
+        let registry = ProviderRegistry::new();
+        let _: Result<()> = registry
+            .guard("test", async { panic!("missing field {}", "published") })
+            .await;
+        assert_eq!(
+            registry.panic_message("test").as_deref(),
+            Some("missing field published")
+        );
+
+        // Errors keep the mark; only a success clears it
+        let _: Result<()> = registry
+            .guard("test", async { Err(StreamError::Network("down".into())) })
+            .await;
+        assert!(registry.panic_message("test").is_some());
+
+        let value = registry.guard("test", async { Ok(42) }).await.unwrap();
+        assert_eq!(value, 42);
+        assert!(registry.panic_message("test").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_guard_enforces_budget() {
+        let mut budgets = Budgets::new();
+        budgets.set_limit(
+            "test",
+            crate::config::BudgetConfig {
+                max_operations_per_day: Some(1),
+                max_item_megabytes_per_day: None,
+            },
+        );
+        let registry = ProviderRegistry::new().with_budgets(budgets);
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let call = || async {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok::<_, StreamError>(())
+        };
+
+        assert!(registry.guard("test", call()).await.is_ok());
+        let refused = registry.guard("test", call()).await;
+        assert!(matches!(refused, Err(StreamError::RateLimited(_))));
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(registry.budgets().usage("test").operations, 1);
+
+        // Providers without a budget are only counted
+        assert!(registry.guard("other", call()).await.is_ok());
+        assert_eq!(registry.budgets().usage("other").operations, 1);
+    }
 }
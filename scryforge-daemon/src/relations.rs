@@ -0,0 +1,185 @@
+//! Links between items across providers.
+//!
+//! An RSS post often embeds a YouTube video, and a Reddit thread or a Hacker
+//! News story discusses an article that is also in a feed. The cache records
+//! each item's own URL and the URLs found in its content (see
+//! [`ItemLinks`]), and relates items whose URLs match after normalization:
+//! one links to the other, or both point at the same page.
+//!
+//! Links found in the content of encrypted items are not recorded, since the
+//! link table is stored in the clear.
+
+use scryforge_provider_core::url::url_key;
+use scryforge_provider_core::Item;
+use serde::{Deserialize, Serialize};
+
+/// Characters that end a URL found in free text, HTML, or serialized JSON.
+const URL_TERMINATORS: &[char] = &['"', '\'', '<', '>', '(', ')', '[', ']', '{', '}', '\\', '`'];
+
+/// How a related item is linked to the item it was looked up for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// Both items point at the same page, like a Reddit link post and the
+    /// article it links to
+    SameLink,
+    /// The item links to the related item, like a post embedding a video
+    LinksTo,
+    /// The related item links to the item
+    LinkedFrom,
+}
+
+/// An item related to another, and how.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedItem {
+    pub relation: Relation,
+    pub item: Item,
+}
+
+/// The normalized URLs of an item, as stored in the cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemLinks {
+    /// The item's own URL
+    pub own: Option<String>,
+    /// URLs mentioned in the content, excluding the item's own
+    pub mentioned: Vec<String>,
+}
+
+impl ItemLinks {
+    /// Links of an item at `url`, with mentions scanned from `content_data`,
+    /// its serialized content. Pass `None` to record only the item's own URL.
+    pub fn of(url: Option<&str>, content_data: Option<&str>) -> Self {
+        let own = url.and_then(link_key);
+
+        let mut mentioned: Vec<String> = Vec::new();
+        for url in content_data.into_iter().flat_map(find_urls) {
+            if let Some(key) = link_key(url) {
+                if Some(&key) != own.as_ref() && !mentioned.contains(&key) {
+                    mentioned.push(key);
+                }
+            }
+        }
+
+        Self { own, mentioned }
+    }
+}
+
+/// Comparison key for a linked URL, or `None` for a bare site root, which
+/// says nothing about what an item is about.
+///
+/// YouTube's short and embed links are rewritten to `watch` links first, and
+/// their other parameters, such as the start time, are dropped.
+pub fn link_key(url: &str) -> Option<String> {
+    let key = match youtube_video_id(url) {
+        Some(id) => format!("youtube.com/watch?v={}", id),
+        None => url_key(url)?,
+    };
+    let (_, path) = key.split_once(['/', '?'])?;
+    (!path.is_empty()).then_some(key)
+}
+
+/// The video ID of a YouTube video URL.
+fn youtube_video_id(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (host, path) = rest.split_once('/')?;
+    let host = host.to_ascii_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(&host);
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+
+    let id = match host {
+        "youtu.be" => path,
+        "youtube.com" | "youtube-nocookie.com" => match path.split_once('/') {
+            Some(("embed" | "shorts" | "live", id)) => id,
+            _ if path == "watch" => query
+                .split('&')
+                .find_map(|param| param.strip_prefix("v="))?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let id = id.trim_end_matches('/');
+    (!id.is_empty() && !id.contains('/')).then(|| id.to_string())
+}
+
+/// `http` and `https` URLs in `text`, with trailing punctuation removed. A
+/// URL embedded in another's query string is found as well.
+pub(crate) fn find_urls(text: &str) -> impl Iterator<Item = &str> {
+    text.match_indices("http").filter_map(move |(start, _)| {
+        let candidate = &text[start..];
+        if !(candidate.starts_with("http://") || candidate.starts_with("https://")) {
+            return None;
+        }
+        let end = candidate
+            .find(|c: char| c.is_whitespace() || URL_TERMINATORS.contains(&c))
+            .unwrap_or(candidate.len());
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        // Escaped HTML attributes end at the entity
+        let url = url.split("&quot;").next().unwrap_or(url);
+        (url.len() > "https://".len()).then_some(url)
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_youtube_links_share_a_key() {
+        let expected = Some("youtube.com/watch?v=dQw4w9WgXcQ".to_string());
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://m.youtube.com/watch?feature=share&v=dQw4w9WgXcQ",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ",
+        ] {
+            assert_eq!(link_key(url), expected, "{}", url);
+        }
+        assert_eq!(
+            link_key("https://www.youtube.com/@rustlang").as_deref(),
+            Some("youtube.com/@rustlang")
+        );
+    }
+
+    #[test]
+    fn test_site_roots_are_not_links() {
+        assert_eq!(link_key("https://www.reddit.com/"), None);
+        assert_eq!(link_key("https://example.com"), None);
+        assert!(link_key("https://example.com/post").is_some());
+    }
+
+    #[test]
+    fn test_mentions_come_from_html_and_text() {
+        let content = r#"<p>Watch <a href="https://youtu.be/abc123">the talk</a>,
+            then read https://blog.example.com/post. Or (https://example.org/faq)!
+            Also https://blog.example.com/post?utm_source=x again.</p>
+            <a href="https://www.example.com/self">permalink</a>"#;
+
+        let links = ItemLinks::of(Some("https://example.com/self"), Some(content));
+
+        assert_eq!(links.own.as_deref(), Some("example.com/self"));
+        assert_eq!(
+            links.mentioned,
+            vec![
+                "youtube.com/watch?v=abc123",
+                "blog.example.com/post",
+                "example.org/faq"
+            ]
+        );
+        assert_eq!(ItemLinks::of(None, None), ItemLinks::default());
+    }
+
+    #[test]
+    fn test_urls_end_at_json_escapes() {
+        let content = r#"{"summary":"See https://example.com/a\nand \"https://example.com/b\""}"#;
+        let urls: Vec<&str> = find_urls(content).collect();
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+}
@@ -0,0 +1,271 @@
+//! Item reminders.
+//!
+//! Any cached item can be given a "remind me at" time through
+//! `items.remind`. Reminders live in the cache next to the item, so they
+//! survive restarts and keep the item from being evicted. Once the time has
+//! passed the item is listed in the "Reminders due" feed (`reminders.due`)
+//! until the reminder is dismissed.
+//!
+//! [`spawn_scheduler`] checks for reminders that have come due, raises a
+//! desktop notification through the configured command, and publishes a
+//! [`DaemonEvent::ReminderDue`] so connected clients can react too.
+
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use scryforge_provider_core::Item;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+use crate::cache::Cache;
+use crate::config::RemindersConfig;
+use crate::events::{DaemonEvent, EventBus};
+
+/// Metadata key holding the reminder time on items in the due feed.
+pub const REMIND_AT_KEY: &str = "remind_at";
+
+/// Raises desktop notifications by running a shell command.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    command: String,
+}
+
+impl Notifier {
+    /// Create a notifier from config, or `None` if notifications are off.
+    pub fn from_config(config: &RemindersConfig) -> Option<Self> {
+        config.notify.then(|| Self {
+            command: config.notify_command.clone(),
+        })
+    }
+
+    /// Notify the user that the reminder on `item` is due.
+    ///
+    /// The title and link are passed as positional arguments rather than
+    /// spliced into the command, so they need no shell quoting.
+    pub async fn notify(&self, item: &Item) -> Result<()> {
+        let status = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$1\" \"$2\"", self.command))
+            .arg("sh")
+            .arg(&item.title)
+            .arg(item.url.as_deref().unwrap_or_default())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .with_context(|| format!("Failed to run notification command: {}", self.command))?;
+
+        if !status.success() {
+            anyhow::bail!("Notification command exited with {}", status);
+        }
+        Ok(())
+    }
+}
+
+/// Items whose reminder time has passed at `now`, most overdue first.
+///
+/// Each item carries its reminder time in [`REMIND_AT_KEY`] metadata.
+pub fn due_items<C: Cache>(cache: &C, now: DateTime<Utc>) -> Result<Vec<Item>> {
+    let mut items = Vec::new();
+    for reminder in cache.get_reminders()? {
+        if !reminder.is_due(now) {
+            break;
+        }
+        if let Some(mut item) = cache.get_item(&reminder.item_id)? {
+            item.metadata
+                .insert(REMIND_AT_KEY.to_string(), reminder.remind_at.to_rfc3339());
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Notify about every reminder due at `now` that has not been notified yet.
+///
+/// A reminder counts as notified even if the notification command fails, so
+/// a broken command is reported once per reminder instead of on every check.
+/// Returns the number of reminders that fired.
+pub async fn fire_due<C: Cache>(
+    cache: &C,
+    notifier: Option<&Notifier>,
+    events: &EventBus,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let mut fired = 0;
+    for reminder in cache.get_reminders()? {
+        if !reminder.is_due(now) {
+            break;
+        }
+        if reminder.notified_at.is_some() {
+            continue;
+        }
+        let Some(item) = cache.get_item(&reminder.item_id)? else {
+            continue;
+        };
+
+        if let Some(notifier) = notifier {
+            if let Err(e) = notifier.notify(&item).await {
+                warn!("Failed to notify about {}: {:#}", item.id.as_str(), e);
+            }
+        }
+        cache.mark_reminder_notified(&item.id, now)?;
+        events.publish(DaemonEvent::ReminderDue {
+            item_id: item.id.0.clone(),
+            title: item.title.clone(),
+            remind_at: reminder.remind_at,
+            timestamp: now,
+        });
+        fired += 1;
+    }
+    Ok(fired)
+}
+
+/// Fire due reminders every `check_interval_secs` until the task is aborted.
+pub fn spawn_scheduler<C: Cache + 'static>(
+    cache: Arc<C>,
+    config: RemindersConfig,
+    events: EventBus,
+) -> JoinHandle<()> {
+    let notifier = Notifier::from_config(&config);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(config.check_interval_secs));
+        loop {
+            ticker.tick().await;
+            match fire_due(cache.as_ref(), notifier.as_ref(), &events, Utc::now()).await {
+                Ok(0) => debug!("No reminders due"),
+                Ok(fired) => info!("Fired {} reminder(s)", fired),
+                Err(e) => error!("Failed to check reminders: {:#}", e),
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use chrono::Duration;
+    use scryforge_provider_core::{ItemContent, ItemId, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn seeded_cache() -> SqliteCache {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SqliteCache::open_at(&temp_dir.path().join("test.db")).unwrap();
+        std::mem::forget(temp_dir);
+
+        cache
+            .upsert_streams(&[Stream {
+                id: StreamId("rss:feed:a".to_string()),
+                name: "Feed".to_string(),
+                provider_id: "rss".to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])
+            .unwrap();
+        let items: Vec<Item> = (1..=3)
+            .map(|i| Item {
+                id: ItemId(format!("rss:{}", i)),
+                stream_id: StreamId("rss:feed:a".to_string()),
+                title: format!("Item {}", i),
+                content: ItemContent::Text("Body text".to_string()),
+                author: None,
+                published: None,
+                updated: None,
+                url: Some(format!("https://example.com/{}", i)),
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: false,
+                tags: vec![],
+                metadata: HashMap::new(),
+            })
+            .collect();
+        cache.upsert_items(&items).unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_due_items_lists_passed_reminders() {
+        let cache = seeded_cache();
+        let now = Utc::now();
+        cache
+            .set_reminder(&ItemId("rss:1".to_string()), now - Duration::minutes(1))
+            .unwrap();
+        cache
+            .set_reminder(&ItemId("rss:2".to_string()), now - Duration::hours(1))
+            .unwrap();
+        cache
+            .set_reminder(&ItemId("rss:3".to_string()), now + Duration::hours(1))
+            .unwrap();
+
+        let items = due_items(&cache, now).unwrap();
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["rss:2", "rss:1"]);
+        assert!(items[0].metadata.contains_key(REMIND_AT_KEY));
+    }
+
+    #[tokio::test]
+    async fn test_fire_due_notifies_once() {
+        let cache = seeded_cache();
+        let now = Utc::now();
+        cache
+            .set_reminder(&ItemId("rss:1".to_string()), now - Duration::minutes(1))
+            .unwrap();
+        cache
+            .set_reminder(&ItemId("rss:3".to_string()), now + Duration::hours(1))
+            .unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("notifications");
+        let notifier = Notifier::from_config(&RemindersConfig {
+            notify_command: format!("printf '%s|%s\\n' >> '{}'", log.display()),
+            ..RemindersConfig::default()
+        })
+        .unwrap();
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+
+        assert_eq!(
+            fire_due(&cache, Some(&notifier), &events, now)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            fire_due(&cache, Some(&notifier), &events, now)
+                .await
+                .unwrap(),
+            0
+        );
+
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "Item 1|https://example.com/1\n"
+        );
+        match rx.recv().await.unwrap() {
+            DaemonEvent::ReminderDue { item_id, .. } => assert_eq!(item_id, "rss:1"),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_notifier_disabled() {
+        let config = RemindersConfig {
+            notify: false,
+            ..RemindersConfig::default()
+        };
+        assert!(Notifier::from_config(&config).is_none());
+    }
+}
@@ -0,0 +1,201 @@
+//! Cross-provider save routing.
+//!
+//! By default "Save" only stars an item in the cache. The [`SaveRouter`] can
+//! additionally hand saved items off to a read-later provider, so saving an
+//! RSS or Reddit item creates a bookmark (or a Wallabag entry) instead of
+//! relying on the source provider's native save.
+//!
+//! Routes are keyed by the source provider ID, taken from the item ID prefix
+//! (`rss:...`, `reddit:...`). Targets must implement
+//! [`HasReadLater`](scryforge_provider_core::HasReadLater).
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use scryforge_provider_core::{Item, ItemId};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::config::SaveConfig;
+use crate::registry::ProviderRegistry;
+
+/// Where a saved item was handed off to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveHandoff {
+    /// Provider that stored the item
+    pub target_provider: String,
+    /// ID of the entry created in the target provider
+    pub saved_id: ItemId,
+}
+
+/// Routes saves from source providers to read-later providers.
+#[derive(Debug, Clone, Default)]
+pub struct SaveRouter {
+    routes: HashMap<String, String>,
+    default_target: Option<String>,
+}
+
+impl SaveRouter {
+    /// Build a router from the `[save]` config section.
+    pub fn from_config(config: &SaveConfig) -> Self {
+        Self {
+            routes: config.routes.clone(),
+            default_target: config.default_target.clone(),
+        }
+    }
+
+    /// Whether any routing is configured.
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty() && self.default_target.is_none()
+    }
+
+    /// Target provider for items from `source`, if any.
+    ///
+    /// Items are never routed back to the provider they came from.
+    pub fn target_for(&self, source: &str) -> Option<&str> {
+        self.routes
+            .get(source)
+            .or(self.default_target.as_ref())
+            .map(String::as_str)
+            .filter(|target| *target != source)
+    }
+
+    /// Hand `item` off to its configured target provider.
+    ///
+    /// Returns `Ok(None)` when no route applies to the item.
+    pub async fn route(
+        &self,
+        registry: &ProviderRegistry,
+        item: &Item,
+    ) -> Result<Option<SaveHandoff>> {
+        let source = item.id.as_str().split(':').next().unwrap_or_default();
+        let Some(target_id) = self.target_for(source) else {
+            return Ok(None);
+        };
+
+        let provider = registry
+            .get(target_id)
+            .ok_or_else(|| anyhow!("Save target provider '{}' is not loaded", target_id))?;
+        let reader = provider.as_read_later().ok_or_else(|| {
+            anyhow!(
+                "Provider '{}' cannot store items from other providers",
+                target_id
+            )
+        })?;
+
+        let saved_id = registry
+            .guard(target_id, reader.save_for_later(item))
+            .await?;
+        debug!(
+            "Routed save of {} to {} as {}",
+            item.id.as_str(),
+            target_id,
+            saved_id.as_str()
+        );
+
+        Ok(Some(SaveHandoff {
+            target_provider: target_id.to_string(),
+            saved_id,
+        }))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use provider_bookmarks::BookmarksProvider;
+    use scryforge_provider_core::{HasSavedItems, ItemContent, SavedItemsOptions, StreamId};
+    use tempfile::TempDir;
+
+    fn router(routes: &[(&str, &str)], default_target: Option<&str>) -> SaveRouter {
+        SaveRouter::from_config(&SaveConfig {
+            routes: routes
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+            default_target: default_target.map(str::to_string),
+        })
+    }
+
+    fn create_test_item(id: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId("rss:feed:news".to_string()),
+            title: "Saved post".to_string(),
+            content: ItemContent::Text("Body".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("https://example.com/saved".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_target_for() {
+        let router = router(&[("rss", "bookmarks")], Some("wallabag"));
+        assert_eq!(router.target_for("rss"), Some("bookmarks"));
+        assert_eq!(router.target_for("reddit"), Some("wallabag"));
+        assert_eq!(router.target_for("wallabag"), None);
+
+        assert!(SaveRouter::default().is_empty());
+        assert_eq!(SaveRouter::default().target_for("rss"), None);
+    }
+
+    #[tokio::test]
+    async fn test_route_creates_bookmark() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let bookmarks = BookmarksProvider::with_path(temp_dir.path().join("bookmarks.json"))?;
+        let mut registry = ProviderRegistry::new();
+        registry.register(bookmarks);
+
+        let router = router(&[("rss", "bookmarks")], None);
+        let handoff = router
+            .route(&registry, &create_test_item("rss:1"))
+            .await?
+            .expect("rss items should be routed");
+        assert_eq!(handoff.target_provider, "bookmarks");
+
+        let provider = registry.get("bookmarks").unwrap();
+        let bookmarks = provider
+            .as_any()
+            .downcast_ref::<BookmarksProvider>()
+            .unwrap();
+        let saved = bookmarks
+            .get_saved_items(SavedItemsOptions::default())
+            .await?;
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].id, handoff.saved_id);
+
+        // Unrouted providers are left alone
+        assert!(router
+            .route(&registry, &create_test_item("reddit:1"))
+            .await?
+            .is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_route_rejects_unsupported_target() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider_dummy::DummyProvider::new());
+
+        let router = router(&[("rss", "dummy"), ("reddit", "missing")], None);
+        assert!(router
+            .route(&registry, &create_test_item("rss:1"))
+            .await
+            .is_err());
+        assert!(router
+            .route(&registry, &create_test_item("reddit:1"))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! The search query language.
+//!
+//! `search.query` takes a query such as
+//!
+//! ```text
+//! provider:reddit tag:rust is:unread before:2024-06-01 "exact phrase" async
+//! ```
+//!
+//! Plain words and quoted phrases must all appear in an item's text, as
+//! before; fields narrow the results down by everything else:
+//!
+//! - `provider:<id>`, `-provider:<id>`: from, or not from, a provider
+//! - `stream:<id>` (or `in:<id>`): from one stream
+//! - `tag:<tag>`: carrying a tag, ignoring case; repeated tags must all match
+//! - `type:<type>`: with a kind of content, such as `article` or `email`
+//! - `is:read`, `is:unread`, `is:saved` (or `is:starred`), `is:unsaved`
+//! - `before:<date>`, `after:<date>`: published before the day, or on or
+//!   after it, as `YYYY-MM-DD` in UTC
+//! - `since:<n>d`, `since:<n>w`: published in the last days or weeks
+//!
+//! A field value with spaces is quoted (`tag:"machine learning"`). Anything
+//! with a colon that isn't a field, such as a URL, is searched as text.
+//!
+//! The cache turns a [`SearchQuery`] into SQL. Providers that search their
+//! source get its [`text`](SearchQuery::text) and
+//! [`search_options`](SearchQuery::search_options), and what they return is
+//! checked against the fields with [`SearchQuery::matches`], since most
+//! can't filter on them.
+
+use anyhow::{bail, Context, Result};
+use chrono::{Days, NaiveDate, Utc};
+use scryforge_provider_core::{Item, ItemContent, SearchOptions, StreamId};
+
+/// A parsed search query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Words that must all appear in the text
+    pub words: Vec<String>,
+    /// Phrases that must all appear in the text as they are
+    pub phrases: Vec<String>,
+    /// Providers an item may come from; any if empty
+    pub providers: Vec<String>,
+    /// Providers an item may not come from
+    pub excluded_providers: Vec<String>,
+    pub stream_id: Option<String>,
+    /// Tags an item must all carry
+    pub tags: Vec<String>,
+    /// Content type name, lowercase
+    pub content_type: Option<String>,
+    pub is_read: Option<bool>,
+    pub is_saved: Option<bool>,
+    /// Published before the start of this day
+    pub before: Option<NaiveDate>,
+    /// Published on or after this day
+    pub after: Option<NaiveDate>,
+}
+
+impl SearchQuery {
+    /// Parse a query in the syntax described in the [module docs](self).
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_at(input, Utc::now().date_naive())
+    }
+
+    /// A query matching `text` as a single phrase, as searches did before
+    /// the query language.
+    pub fn phrase(text: &str) -> Self {
+        Self {
+            phrases: Some(text.to_string())
+                .filter(|text| !text.is_empty())
+                .into_iter()
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    fn parse_at(input: &str, today: NaiveDate) -> Result<Self> {
+        let mut query = Self::default();
+        for (token, quoted) in tokens(input) {
+            if quoted {
+                query.phrases.push(token);
+                continue;
+            }
+            let Some((key, value)) = token.split_once(':').filter(|(_, value)| !value.is_empty())
+            else {
+                query.words.push(token);
+                continue;
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "provider" => query.providers.push(value.to_ascii_lowercase()),
+                "-provider" => query.excluded_providers.push(value.to_ascii_lowercase()),
+                "stream" | "in" => query.stream_id = Some(value.to_string()),
+                "tag" => query.tags.push(value.to_string()),
+                "type" => query.content_type = Some(value.to_ascii_lowercase()),
+                "is" => match value.to_ascii_lowercase().as_str() {
+                    "read" => query.is_read = Some(true),
+                    "unread" => query.is_read = Some(false),
+                    "saved" | "starred" => query.is_saved = Some(true),
+                    "unsaved" => query.is_saved = Some(false),
+                    other => bail!(
+                        "Unknown filter is:{}; expected read, unread, saved, or unsaved",
+                        other
+                    ),
+                },
+                "before" => query.before = Some(parse_date(value)?),
+                "after" => query.after = Some(parse_date(value)?),
+                "since" => query.after = Some(days_ago(value, today)?),
+                _ => query.words.push(token.clone()),
+            }
+        }
+        Ok(query)
+    }
+
+    /// The words and phrases, each of which must appear in the text.
+    pub fn needles(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().chain(&self.phrases).map(String::as_str)
+    }
+
+    /// The text to search providers' sources for, with phrases quoted.
+    pub fn text(&self) -> String {
+        self.words
+            .iter()
+            .cloned()
+            .chain(self.phrases.iter().map(|phrase| format!("\"{}\"", phrase)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether results from `provider_id` can match.
+    pub fn allows_provider(&self, provider_id: &str) -> bool {
+        (self.providers.is_empty() || self.providers.iter().any(|p| p == provider_id))
+            && !self.excluded_providers.iter().any(|p| p == provider_id)
+            && self
+                .stream_id
+                .as_ref()
+                .is_none_or(|stream| stream.starts_with(&format!("{}:", provider_id)))
+    }
+
+    /// Provider search options for the query: its stream, and the smallest
+    /// common time window (`day`, `week`, `month`, `year`) covering
+    /// `after:`.
+    pub fn search_options(&self) -> SearchOptions {
+        self.search_options_at(Utc::now().date_naive())
+    }
+
+    fn search_options_at(&self, today: NaiveDate) -> SearchOptions {
+        let time = self.after.map(|after| {
+            match (today - after).num_days() {
+                ..=1 => "day",
+                2..=7 => "week",
+                8..=31 => "month",
+                32..=366 => "year",
+                _ => "all",
+            }
+            .to_string()
+        });
+        SearchOptions {
+            stream_id: self.stream_id.clone().map(StreamId),
+            time,
+            ..SearchOptions::default()
+        }
+    }
+
+    /// Whether `item` passes the fields of the query. Its text isn't
+    /// checked, since providers match text in their own way.
+    pub fn matches(&self, item: &Item) -> bool {
+        let stream = item.stream_id.as_str();
+        let provider = stream.split(':').next().unwrap_or_default();
+        let published = item.published.map(|published| published.date_naive());
+
+        self.allows_provider(provider)
+            && self.stream_id.as_ref().is_none_or(|id| id == stream)
+            && self
+                .tags
+                .iter()
+                .all(|tag| item.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.content_type.as_ref().is_none_or(|ctype| {
+                content_type_name(&item.content)
+                    .is_some_and(|name| name.eq_ignore_ascii_case(ctype))
+            })
+            && self.is_read.is_none_or(|read| item.is_read == read)
+            && self.is_saved.is_none_or(|saved| item.is_saved == saved)
+            && self
+                .before
+                .is_none_or(|before| published.is_some_and(|day| day < before))
+            && self
+                .after
+                .is_none_or(|after| published.is_some_and(|day| day >= after))
+    }
+}
+
+/// Name of an item's content variant, as stored in the cache's
+/// `content_type` column.
+pub(crate) fn content_type_name(content: &ItemContent) -> Option<String> {
+    serde_json::to_value(content)
+        .ok()?
+        .as_object()?
+        .keys()
+        .next()
+        .cloned()
+}
+
+/// Split a query at whitespace outside quotes. Each token comes with
+/// whether it was quoted as a whole; quotes after a `field:` only keep the
+/// value together.
+fn tokens(input: &str) -> Vec<(String, bool)> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&first) = chars.peek() {
+        if first.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() && !in_quotes {
+                break;
+            }
+            if c == '"' {
+                in_quotes = !in_quotes;
+            } else {
+                token.push(c);
+            }
+            chars.next();
+        }
+        if !token.is_empty() {
+            tokens.push((token, first == '"'));
+        }
+    }
+    tokens
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date '{}'; expected YYYY-MM-DD", value))
+}
+
+/// The day `value` (such as `30d` or `2w`) before `today`.
+fn days_ago(value: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let days = match (value.strip_suffix('d'), value.strip_suffix('w')) {
+        (Some(days), _) => days.parse::<u64>().ok(),
+        (_, Some(weeks)) => weeks
+            .parse::<u64>()
+            .ok()
+            .and_then(|weeks| weeks.checked_mul(7)),
+        _ => None,
+    };
+    let Some(days) = days else {
+        bail!(
+            "Invalid period '{}'; expected days or weeks, like 30d or 2w",
+            value
+        );
+    };
+    today
+        .checked_sub_days(Days::new(days))
+        .with_context(|| format!("Period '{}' is too long", value))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = SearchQuery::parse_at(
+            r#"provider:Reddit tag:rust is:unread before:2024-06-01 "exact phrase" async https://example.com"#,
+            day(2024, 6, 10),
+        )
+        .unwrap();
+        assert_eq!(
+            query,
+            SearchQuery {
+                words: vec!["async".to_string(), "https://example.com".to_string()],
+                phrases: vec!["exact phrase".to_string()],
+                providers: vec!["reddit".to_string()],
+                tags: vec!["rust".to_string()],
+                is_read: Some(false),
+                before: Some(day(2024, 6, 1)),
+                ..SearchQuery::default()
+            }
+        );
+        assert_eq!(query.text(), r#"async https://example.com "exact phrase""#);
+
+        let query = SearchQuery::parse_at(
+            r#"tag:"machine learning" -provider:hackernews in:rss:feed:blog since:2w"#,
+            day(2024, 6, 15),
+        )
+        .unwrap();
+        assert_eq!(query.tags, vec!["machine learning"]);
+        assert_eq!(query.excluded_providers, vec!["hackernews"]);
+        assert_eq!(query.stream_id.as_deref(), Some("rss:feed:blog"));
+        assert_eq!(query.after, Some(day(2024, 6, 1)));
+        assert!(query.needles().next().is_none());
+
+        assert!(SearchQuery::parse("is:maybe").is_err());
+        assert!(SearchQuery::parse("before:June").is_err());
+        assert!(SearchQuery::parse("since:forever").is_err());
+    }
+
+    #[test]
+    fn test_provider_options_and_matching() {
+        let query = SearchQuery::parse_at(
+            "tag:Rust type:article after:2024-06-05 async",
+            day(2024, 6, 10),
+        )
+        .unwrap();
+        let options = query.search_options_at(day(2024, 6, 10));
+        assert_eq!(options.time.as_deref(), Some("week"));
+        assert!(query.allows_provider("reddit"));
+
+        let mut item = Item {
+            id: scryforge_provider_core::ItemId("reddit:1".to_string()),
+            stream_id: StreamId("reddit:feed:rust".to_string()),
+            title: "Async Rust".to_string(),
+            content: ItemContent::Article {
+                summary: None,
+                full_content: None,
+            },
+            author: None,
+            published: Some(Utc.with_ymd_and_hms(2024, 6, 8, 12, 0, 0).unwrap()),
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec!["rust".to_string()],
+            metadata: HashMap::new(),
+        };
+        assert!(query.matches(&item));
+
+        item.published = Some(Utc.with_ymd_and_hms(2024, 6, 4, 23, 0, 0).unwrap());
+        assert!(!query.matches(&item));
+
+        let query = SearchQuery::parse("-provider:reddit async").unwrap();
+        assert!(!query.allows_provider("reddit"));
+        assert!(!query.matches(&item));
+    }
+}
@@ -0,0 +1,548 @@
+//! Tantivy full-text index of the cache.
+//!
+//! The cache's own search scans every row with `LIKE`, which slows down as
+//! it grows. With `[search] index = true` and the `search-index` feature,
+//! the daemon keeps a [`SearchIndex`] of cached items beside it: titles,
+//! text, authors, and transcripts, plus the fields the
+//! [query language](crate::search) filters on. `search.query` then asks
+//! the index, which ranks matches by relevance and cuts the snippet of each
+//! [`SearchResult`] from the text it holds, transcripts included.
+//!
+//! The index follows the cache rather than its writers. It remembers how far
+//! it got in the order items changed ([`Cache::changed_items`]) and picks up
+//! the rest when a sync reports new items, before each search, and in full
+//! the first time it's opened. Items later evicted from the cache are
+//! dropped from the index when a search comes across them. Items of
+//! encrypted providers are never indexed, so their text stays sealed; only
+//! the cache's own search finds them.
+
+use std::fs;
+use std::ops::Bound;
+use std::path::Path;
+use std::sync::{Arc, Mutex, TryLockError};
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use scryforge_provider_core::search::SNIPPET_CHARS;
+use scryforge_provider_core::{Item, ItemId, SearchResult};
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, BoostQuery, Occur, PhraseQuery, Query, RangeQuery, TermQuery};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, Value, FAST, INDEXED, STORED, STRING, TEXT,
+};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::tokenizer::TokenStream;
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, TantivyError, Term};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::cache::{Cache, ChangeCursor};
+use crate::events::{DaemonEvent, EventBus};
+use crate::search::{content_type_name, SearchQuery};
+use crate::summarize::TRANSCRIPT_KEY;
+
+/// Memory the index writer buffers documents in before flushing them.
+const WRITER_MEMORY: usize = 50_000_000;
+
+/// Changed items read from the cache at a time.
+const BATCH_SIZE: u32 = 500;
+
+/// Index matches considered per search, before the fields the index doesn't
+/// hold, such as read state, are checked.
+const CANDIDATES: usize = 1000;
+
+/// Extra weight of matches in titles.
+const TITLE_BOOST: f32 = 2.0;
+
+/// The indexed fields.
+struct Fields {
+    id: Field,
+    title: Field,
+    body: Field,
+    author: Field,
+    provider: Field,
+    stream: Field,
+    tag: Field,
+    kind: Field,
+    published: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Self) {
+        let mut builder = Schema::builder();
+        let fields = Self {
+            id: builder.add_text_field("id", STRING | STORED),
+            title: builder.add_text_field("title", TEXT | STORED),
+            body: builder.add_text_field("body", TEXT | STORED),
+            author: builder.add_text_field("author", TEXT),
+            provider: builder.add_text_field("provider", STRING),
+            stream: builder.add_text_field("stream", STRING),
+            tag: builder.add_text_field("tag", STRING),
+            kind: builder.add_text_field("kind", STRING),
+            published: builder.add_i64_field("published", INDEXED | FAST),
+        };
+        (builder.build(), fields)
+    }
+}
+
+/// A full-text index of cached items.
+pub struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    fields: Fields,
+}
+
+impl SearchIndex {
+    /// Open the index in `path`, creating it if needed. An index written
+    /// with other fields is started over.
+    pub fn open(path: &Path) -> Result<Self> {
+        let (schema, fields) = Fields::schema();
+        fs::create_dir_all(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let index = match Index::open_or_create(MmapDirectory::open(path)?, schema.clone()) {
+            Err(TantivyError::SchemaError(e)) => {
+                info!("Rebuilding search index with a new schema ({})", e);
+                fs::remove_dir_all(path)?;
+                fs::create_dir_all(path)?;
+                Index::create_in_dir(path, schema)?
+            }
+            index => index?,
+        };
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let writer = index.writer(WRITER_MEMORY)?;
+        Ok(Self {
+            index,
+            reader,
+            writer: Mutex::new(writer),
+            fields,
+        })
+    }
+
+    /// Items in the index.
+    pub fn len(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Index the items that changed in `cache` since the last call. Returns
+    /// how many were indexed.
+    pub fn catch_up<C: Cache + ?Sized>(&self, cache: &C) -> Result<usize> {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        self.catch_up_with(&mut writer, cache)
+    }
+
+    /// Like [`Self::catch_up`], unless another catch-up is running.
+    fn try_catch_up<C: Cache + ?Sized>(&self, cache: &C) -> Result<usize> {
+        match self.writer.try_lock() {
+            Ok(mut writer) => self.catch_up_with(&mut writer, cache),
+            Err(TryLockError::Poisoned(e)) => self.catch_up_with(&mut e.into_inner(), cache),
+            Err(TryLockError::WouldBlock) => Ok(0),
+        }
+    }
+
+    fn catch_up_with<C: Cache + ?Sized>(
+        &self,
+        writer: &mut IndexWriter,
+        cache: &C,
+    ) -> Result<usize> {
+        // How far indexing got is kept as the commit payload. Change times
+        // only have seconds, so an item can still change in the second of
+        // the last one indexed, and sort before it; that second is indexed
+        // again until it's over.
+        let mut cursor = self.mark()?;
+
+        let mut indexed = 0;
+        loop {
+            let batch = cache.changed_items(cursor.as_ref(), BATCH_SIZE)?;
+            let done = batch.len() < BATCH_SIZE as usize;
+            for (item, changed) in batch {
+                writer.delete_term(Term::from_field_text(self.fields.id, item.id.as_str()));
+                writer.add_document(self.document(&item))?;
+                cursor = Some(changed);
+                indexed += 1;
+            }
+            if done {
+                break;
+            }
+        }
+
+        if indexed > 0 {
+            let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            let mark = cursor.map(|mut cursor| {
+                if cursor.changed_at >= now {
+                    cursor.item_id.clear();
+                }
+                cursor
+            });
+            self.commit(writer, mark.as_ref())?;
+            debug!("Indexed {} changed item(s)", indexed);
+        }
+        Ok(indexed)
+    }
+
+    /// The last change committed to the index.
+    fn mark(&self) -> Result<Option<ChangeCursor>> {
+        match self.index.load_metas()?.payload {
+            Some(mark) => Ok(Some(serde_json::from_str(&mark)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Commit pending changes with `mark` as the last change indexed.
+    fn commit(&self, writer: &mut IndexWriter, mark: Option<&ChangeCursor>) -> Result<()> {
+        let mut commit = writer.prepare_commit()?;
+        if let Some(mark) = mark {
+            commit.set_payload(&serde_json::to_string(mark)?);
+        }
+        commit.commit()?;
+        self.reader.reload()?;
+        Ok(())
+    }
+
+    fn document(&self, item: &Item) -> TantivyDocument {
+        let fields = &self.fields;
+        let mut doc = TantivyDocument::default();
+        doc.add_text(fields.id, item.id.as_str());
+        doc.add_text(fields.title, &item.title);
+        if let Some(text) = item.content.full_text() {
+            doc.add_text(fields.body, text);
+        }
+        if let Some(transcript) = item.metadata.get(TRANSCRIPT_KEY) {
+            doc.add_text(fields.body, transcript);
+        }
+        if let Some(ref author) = item.author {
+            doc.add_text(fields.author, &author.name);
+        }
+        let stream = item.stream_id.as_str();
+        doc.add_text(
+            fields.provider,
+            stream.split(':').next().unwrap_or_default(),
+        );
+        doc.add_text(fields.stream, stream);
+        for tag in &item.tags {
+            doc.add_text(fields.tag, tag.to_lowercase());
+        }
+        if let Some(kind) = content_type_name(&item.content) {
+            doc.add_text(fields.kind, kind.to_lowercase());
+        }
+        if let Some(published) = item.published {
+            doc.add_i64(fields.published, published.timestamp());
+        }
+        doc
+    }
+
+    /// Search the index for up to `limit` items matching `query`, best
+    /// match first, with their scores and what matched.
+    ///
+    /// Items come from `cache`, so they carry their current state, and
+    /// changes to the cache are indexed first. A query without words or
+    /// phrases matches nothing.
+    pub fn search<C: Cache + ?Sized>(
+        &self,
+        cache: &C,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        if let Err(e) = self.try_catch_up(cache) {
+            warn!("Failed to update the search index: {:#}", e);
+        }
+        let Some(index_query) = self.build_query(query)? else {
+            return Ok(Vec::new());
+        };
+
+        let searcher = self.reader.searcher();
+        let top = searcher.search(&index_query, &TopDocs::with_limit(CANDIDATES))?;
+        let mut snippets = SnippetGenerator::create(&searcher, &index_query, self.fields.body)?;
+        snippets.set_max_num_chars(SNIPPET_CHARS);
+        let needles: Vec<&str> = query.needles().collect();
+
+        let mut results = Vec::new();
+        let mut evicted = Vec::new();
+        for (score, address) in top {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let Some(id) = doc.get_first(self.fields.id).and_then(|id| id.as_str()) else {
+                continue;
+            };
+            let Some(item) = cache.get_item(&ItemId(id.to_string()))? else {
+                evicted.push(id.to_string());
+                continue;
+            };
+            if !query.matches(&item) {
+                continue;
+            }
+            let mut result = SearchResult::new(item, &needles).with_score(score);
+            let snippet = snippets.snippet_from_doc(&doc);
+            if !snippet.is_empty() {
+                // Line breaks and tabs are single bytes, like the spaces
+                // that replace them, so the highlights still line up
+                let text = snippet.fragment().replace(['\n', '\r', '\t'], " ");
+                result = result.with_snippet(text, snippet.highlighted().to_vec());
+            }
+            results.push(result);
+            if results.len() == limit {
+                break;
+            }
+        }
+
+        if !evicted.is_empty() {
+            self.forget(&evicted);
+        }
+        Ok(results)
+    }
+
+    /// Drop items no longer in the cache from the index, unless a catch-up
+    /// is running; the next search will find them again.
+    fn forget(&self, item_ids: &[String]) {
+        let Ok(mut writer) = self.writer.try_lock() else {
+            return;
+        };
+        for item_id in item_ids {
+            writer.delete_term(Term::from_field_text(self.fields.id, item_id));
+        }
+        let forgotten = self
+            .mark()
+            .and_then(|mark| self.commit(&mut writer, mark.as_ref()));
+        match forgotten {
+            Ok(()) => debug!("Dropped {} evicted item(s) from the index", item_ids.len()),
+            Err(e) => warn!("Failed to drop evicted items from the index: {:#}", e),
+        }
+    }
+
+    /// The index query for `query`: every word and phrase in the title,
+    /// text, or author, and the fields the index holds. `None` if the query
+    /// has no words to look for.
+    fn build_query(&self, query: &SearchQuery) -> Result<Option<BooleanQuery>> {
+        let fields = &self.fields;
+        let mut analyzer = self.index.tokenizer_for_field(fields.title)?;
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+        for needle in query.needles() {
+            let mut words = Vec::new();
+            let mut tokens = analyzer.token_stream(needle);
+            while tokens.advance() {
+                words.push(tokens.token().text.clone());
+            }
+            if words.is_empty() {
+                continue;
+            }
+            let anywhere = [
+                (fields.title, TITLE_BOOST),
+                (fields.body, 1.0),
+                (fields.author, 1.0),
+            ]
+            .into_iter()
+            .map(|(field, boost)| {
+                let terms: Vec<Term> = words
+                    .iter()
+                    .map(|word| Term::from_field_text(field, word))
+                    .collect();
+                let matched: Box<dyn Query> = match terms.as_slice() {
+                    [term] => Box::new(TermQuery::new(term.clone(), IndexRecordOption::WithFreqs)),
+                    _ => Box::new(PhraseQuery::new(terms)),
+                };
+                (
+                    Occur::Should,
+                    Box::new(BoostQuery::new(matched, boost)) as Box<dyn Query>,
+                )
+            })
+            .collect();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(anywhere))));
+        }
+        if clauses.is_empty() {
+            return Ok(None);
+        }
+
+        let term = |field: Field, value: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(field, value),
+                IndexRecordOption::Basic,
+            ))
+        };
+        if !query.providers.is_empty() {
+            let any = query
+                .providers
+                .iter()
+                .map(|provider| (Occur::Should, term(fields.provider, provider)))
+                .collect();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(any))));
+        }
+        for provider in &query.excluded_providers {
+            clauses.push((Occur::MustNot, term(fields.provider, provider)));
+        }
+        if let Some(ref stream) = query.stream_id {
+            clauses.push((Occur::Must, term(fields.stream, stream)));
+        }
+        for tag in &query.tags {
+            clauses.push((Occur::Must, term(fields.tag, &tag.to_lowercase())));
+        }
+        if let Some(ref kind) = query.content_type {
+            clauses.push((Occur::Must, term(fields.kind, kind)));
+        }
+        if query.before.is_some() || query.after.is_some() {
+            let start_of = |day: NaiveDate| day.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp();
+            clauses.push((
+                Occur::Must,
+                Box::new(RangeQuery::new_i64_bounds(
+                    "published".to_string(),
+                    query
+                        .after
+                        .map_or(Bound::Unbounded, |day| Bound::Included(start_of(day))),
+                    query
+                        .before
+                        .map_or(Bound::Unbounded, |day| Bound::Excluded(start_of(day))),
+                )),
+            ));
+        }
+
+        Ok(Some(BooleanQuery::new(clauses)))
+    }
+}
+
+/// Keep `index` up to date with `cache` in the background: in full now,
+/// then whenever a sync reports new or updated items.
+pub fn spawn_indexer<C: Cache + 'static>(
+    index: Arc<SearchIndex>,
+    cache: Arc<C>,
+    events: EventBus,
+) -> JoinHandle<()> {
+    let mut events = events.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let (index, cache) = (Arc::clone(&index), Arc::clone(&cache));
+            match tokio::task::spawn_blocking(move || index.catch_up(cache.as_ref())).await {
+                Ok(Ok(0)) => {}
+                Ok(Ok(indexed)) => info!("Search index updated with {} item(s)", indexed),
+                Ok(Err(e)) => warn!("Failed to update the search index: {:#}", e),
+                Err(e) => warn!("Search indexer failed: {}", e),
+            }
+
+            // Wait for the next sync that brought items
+            loop {
+                match events.recv().await {
+                    Ok(DaemonEvent::NewItems { .. }) => break,
+                    Ok(_) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => break,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use scryforge_provider_core::{ItemContent, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn item(id: &str, stream: &str, title: &str, body: &str) -> Item {
+        Item {
+            id: ItemId(id.to_string()),
+            stream_id: StreamId(stream.to_string()),
+            title: title.to_string(),
+            content: ItemContent::Text(body.to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_index_follows_cache() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&temp_dir.path().join("cache.db"))?;
+        for (id, provider) in [("rss:feed:blog", "rss"), ("reddit:feed:rust", "reddit")] {
+            cache.upsert_streams(&[Stream {
+                id: StreamId(id.to_string()),
+                name: id.to_string(),
+                provider_id: provider.to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])?;
+        }
+        cache.upsert_items(&[
+            item(
+                "rss:1",
+                "rss:feed:blog",
+                "Release notes",
+                "This release makes async closures stable, among other things.",
+            ),
+            item(
+                "reddit:1",
+                "reddit:feed:rust",
+                "Async closures are stable",
+                "Finally.",
+            ),
+            item(
+                "reddit:2",
+                "reddit:feed:rust",
+                "Weekly thread",
+                "Ask anything.",
+            ),
+        ])?;
+
+        let index = SearchIndex::open(&temp_dir.path().join("index"))?;
+        assert_eq!(index.catch_up(&cache)?, 3);
+        index.catch_up(&cache)?;
+        assert_eq!(index.len(), 3);
+
+        // Title matches rank first; text matches come with a snippet
+        let found = index.search(&cache, &SearchQuery::parse("\"async closures\"")?, 10)?;
+        let ids: Vec<&str> = found.iter().map(|found| found.item.id.as_str()).collect();
+        assert_eq!(ids, vec!["reddit:1", "rss:1"]);
+        assert!(found[0].score > found[1].score);
+        assert_eq!(found[0].title_parts()[0], ("Async closures", true));
+        let matched: Vec<&str> = found[1]
+            .snippet_parts()
+            .into_iter()
+            .filter_map(|(text, matched)| matched.then_some(text))
+            .collect();
+        assert_eq!(matched, vec!["async", "closures"]);
+
+        let found = index.search(&cache, &SearchQuery::parse("provider:rss stable")?, 10)?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].item.id.as_str(), "rss:1");
+
+        // Read state comes from the cache
+        cache.mark_read(&ItemId("reddit:1".to_string()), true)?;
+        let found = index.search(&cache, &SearchQuery::parse("is:unread closures")?, 10)?;
+        assert_eq!(found.len(), 1);
+
+        // Reopening keeps what was indexed
+        drop(index);
+        let index = SearchIndex::open(&temp_dir.path().join("index"))?;
+        assert_eq!(index.len(), 3);
+
+        // Items gone from the cache are dropped when found
+        cache.delete_item(&ItemId("rss:1".to_string()))?;
+        assert!(index
+            .search(&cache, &SearchQuery::parse("release")?, 10)?
+            .is_empty());
+        assert_eq!(index.len(), 2);
+
+        Ok(())
+    }
+}
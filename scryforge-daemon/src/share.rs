@@ -0,0 +1,459 @@
+//! Sharing items to configured targets.
+//!
+//! Each `[[share]]` table in the config names a target: the clipboard, an
+//! email sent from a provider that implements
+//! [`HasCompose`](scryforge_provider_core::HasCompose), a Mastodon status,
+//! or a file. Clients list the targets with `items.share_targets` and offer
+//! each as an action on every item.
+//!
+//! The shared text comes from the target's `format` template, in which
+//! `{title}`, `{url}`, `{author}`, `{excerpt}`, `{stream}`, `{provider}`,
+//! `{tags}`, and `{date}` are replaced from the item; `{{` and `}}` stand for
+//! literal braces. Clipboard targets only format the text and hand it back,
+//! since the clipboard that matters is the client's, which may be on another
+//! machine.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Local;
+use scryforge_provider_core::{Item, OutgoingMessage};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::config::{ShareKind, ShareTarget};
+use crate::registry::ProviderRegistry;
+
+/// Placeholders a template may use.
+pub const PLACEHOLDERS: [&str; 8] = [
+    "title", "url", "author", "excerpt", "stream", "provider", "tags", "date",
+];
+
+/// Maximum characters of content in `{excerpt}`.
+const EXCERPT_CHARS: usize = 280;
+
+/// A share target as clients list it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareTargetInfo {
+    pub id: String,
+    pub name: String,
+    /// "clipboard", "email", "mastodon", or "file"
+    pub kind: String,
+}
+
+/// What sharing an item did.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedItem {
+    pub target: String,
+    /// The formatted text that was shared
+    pub text: String,
+    /// Whether the client should copy `text` to its clipboard
+    pub copy: bool,
+    /// Where the text went, for display
+    pub message: String,
+}
+
+/// The configured share targets.
+#[derive(Debug, Clone, Default)]
+pub struct Sharer {
+    targets: Vec<ShareTarget>,
+    http: reqwest::Client,
+}
+
+impl Sharer {
+    pub fn new(targets: Vec<ShareTarget>) -> Self {
+        Self {
+            targets,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// The targets in config order.
+    pub fn targets(&self) -> Vec<ShareTargetInfo> {
+        self.targets
+            .iter()
+            .map(|target| ShareTargetInfo {
+                id: target.id.clone(),
+                name: target.name.clone().unwrap_or_else(|| target.id.clone()),
+                kind: kind_name(&target.kind).to_string(),
+            })
+            .collect()
+    }
+
+    /// The target with ID `id`, if there is one.
+    pub fn get(&self, id: &str) -> Option<&ShareTarget> {
+        self.targets.iter().find(|target| target.id == id)
+    }
+
+    /// Share `item` to `target`. Email targets send through their provider in
+    /// `registry`.
+    pub async fn share(
+        &self,
+        target: &ShareTarget,
+        item: &Item,
+        registry: Option<&ProviderRegistry>,
+    ) -> Result<SharedItem> {
+        let text = render(
+            target
+                .format
+                .as_deref()
+                .unwrap_or(default_format(&target.kind)),
+            item,
+        );
+        let (copy, message) = match &target.kind {
+            ShareKind::Clipboard => (true, "Copied".to_string()),
+            ShareKind::Email {
+                provider,
+                to,
+                subject,
+            } => {
+                let registry = registry.ok_or_else(|| anyhow!("Providers are not loaded"))?;
+                let compose = registry
+                    .get(provider)
+                    .ok_or_else(|| anyhow!("Provider '{}' is not loaded", provider))?;
+                let compose = compose
+                    .as_compose()
+                    .ok_or_else(|| anyhow!("Provider '{}' cannot send mail", provider))?;
+                let message = OutgoingMessage {
+                    to: to.clone(),
+                    subject: render(subject.as_deref().unwrap_or("{title}"), item),
+                    body: text.clone(),
+                };
+                registry
+                    .guard(provider, compose.send_message(&message))
+                    .await?;
+                (false, format!("Emailed to {}", to.join(", ")))
+            }
+            ShareKind::Mastodon {
+                instance,
+                token_env,
+                visibility,
+            } => {
+                let url = self
+                    .post_status(instance, token_env, visibility.as_deref(), &text)
+                    .await?;
+                (
+                    false,
+                    format!("Posted {}", url.as_deref().unwrap_or(instance)),
+                )
+            }
+            ShareKind::File { path } => {
+                append_line(path, &text)?;
+                (false, format!("Appended to {}", path.display()))
+            }
+        };
+
+        debug!("Shared {} to {}", item.id.as_str(), target.id);
+        Ok(SharedItem {
+            target: target.id.clone(),
+            text,
+            copy,
+            message,
+        })
+    }
+
+    /// Post `text` as a status, returning its URL.
+    async fn post_status(
+        &self,
+        instance: &str,
+        token_env: &str,
+        visibility: Option<&str>,
+        text: &str,
+    ) -> Result<Option<String>> {
+        let token = std::env::var(token_env)
+            .with_context(|| format!("Mastodon token variable {} is not set", token_env))?;
+        let mut body = json!({ "status": text });
+        if let Some(visibility) = visibility {
+            body["visibility"] = json!(visibility);
+        }
+
+        let response: Value = self
+            .http
+            .post(format!(
+                "{}/api/v1/statuses",
+                instance.trim_end_matches('/')
+            ))
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", instance))?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response["url"].as_str().map(str::to_string))
+    }
+}
+
+fn kind_name(kind: &ShareKind) -> &'static str {
+    match kind {
+        ShareKind::Clipboard => "clipboard",
+        ShareKind::Email { .. } => "email",
+        ShareKind::Mastodon { .. } => "mastodon",
+        ShareKind::File { .. } => "file",
+    }
+}
+
+/// The template used when a target sets no `format`.
+fn default_format(kind: &ShareKind) -> &'static str {
+    match kind {
+        ShareKind::Clipboard => "[{title}]({url})",
+        ShareKind::Email { .. } => "{title}\n{url}\n\n{excerpt}",
+        ShareKind::Mastodon { .. } => "{title} {url}",
+        ShareKind::File { .. } => "- [{title}]({url})",
+    }
+}
+
+/// Template pieces: literal text and placeholder names.
+enum Piece<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Piece<'_>>> {
+    let mut pieces = Vec::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        pieces.push(Piece::Text(&rest[..pos]));
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            pieces.push(Piece::Text(&tail[..1]));
+            rest = &tail[2..];
+        } else if tail.starts_with('}') {
+            bail!("Unmatched '}}' in template: {}", template);
+        } else {
+            let end = tail
+                .find('}')
+                .ok_or_else(|| anyhow!("Unclosed '{{' in template: {}", template))?;
+            pieces.push(Piece::Placeholder(&tail[1..end]));
+            rest = &tail[end + 1..];
+        }
+    }
+    pieces.push(Piece::Text(rest));
+    Ok(pieces)
+}
+
+/// Check that `template` is well formed and uses only known placeholders.
+pub fn check_template(template: &str) -> Result<()> {
+    for piece in parse(template)? {
+        if let Piece::Placeholder(name) = piece {
+            if !PLACEHOLDERS.contains(&name) {
+                bail!(
+                    "Unknown placeholder {{{}}}; expected one of {}",
+                    name,
+                    PLACEHOLDERS.join(", ")
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fill in `template` from `item`. Templates are checked when the config is
+/// loaded; a malformed one is used as it is.
+pub fn render(template: &str, item: &Item) -> String {
+    let Ok(pieces) = parse(template) else {
+        return template.to_string();
+    };
+    pieces
+        .into_iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => text.to_string(),
+            Piece::Placeholder(name) => {
+                value(name, item).unwrap_or_else(|| format!("{{{}}}", name))
+            }
+        })
+        .collect()
+}
+
+fn value(name: &str, item: &Item) -> Option<String> {
+    Some(match name {
+        "title" => item.title.clone(),
+        "url" => item.url.clone().unwrap_or_default(),
+        "author" => item
+            .author
+            .as_ref()
+            .map(|author| author.name.clone())
+            .unwrap_or_default(),
+        "excerpt" => {
+            let text = item.content.plain_text().unwrap_or_default();
+            if text.chars().count() > EXCERPT_CHARS {
+                let truncated: String = text.chars().take(EXCERPT_CHARS).collect();
+                format!("{}…", truncated.trim_end())
+            } else {
+                text
+            }
+        }
+        "stream" => item.stream_id.as_str().to_string(),
+        "provider" => item
+            .id
+            .as_str()
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        "tags" => item
+            .tags
+            .iter()
+            .map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("-"))
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| format!("#{}", tag))
+            .collect::<Vec<_>>()
+            .join(" "),
+        "date" => Local::now().format("%Y-%m-%d").to_string(),
+        _ => return None,
+    })
+}
+
+/// Append `text` to `path` as a line of its own.
+fn append_line(path: &Path, text: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let needs_newline = fs::read(path)
+        .map(|contents| contents.last().is_some_and(|&last| last != b'\n'))
+        .unwrap_or(false);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    if needs_newline {
+        writeln!(file)?;
+    }
+    writeln!(file, "{}", text).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{Author, ItemContent, ItemId, StreamId};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn create_test_item() -> Item {
+        Item {
+            id: ItemId("rss:1".to_string()),
+            stream_id: StreamId("rss:feed:news".to_string()),
+            title: "Rust 1.80 released".to_string(),
+            content: ItemContent::Text("The Rust team is happy".to_string()),
+            author: Some(Author {
+                name: "Rust Team".to_string(),
+                email: None,
+                url: None,
+                avatar_url: None,
+            }),
+            published: None,
+            updated: None,
+            url: Some("https://example.com/rust".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec!["rust".to_string(), "release notes".to_string()],
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn target(id: &str, format: Option<&str>, kind: ShareKind) -> ShareTarget {
+        ShareTarget {
+            id: id.to_string(),
+            name: None,
+            format: format.map(str::to_string),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let item = create_test_item();
+        assert_eq!(
+            render("[{title}]({url}) by {author} {tags}", &item),
+            "[Rust 1.80 released](https://example.com/rust) by Rust Team #rust #release-notes"
+        );
+        assert_eq!(
+            render("{{{provider}}} {excerpt}", &item),
+            "{rss} The Rust team is happy"
+        );
+
+        assert!(check_template("{title} {{literal}}").is_ok());
+        assert!(check_template("{link}").is_err());
+        assert!(check_template("{title").is_err());
+        assert!(check_template("title}").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_and_file_targets() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("log").join("reading.md");
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, "# Reading")?;
+        let sharer = Sharer::new(vec![
+            target("markdown", None, ShareKind::Clipboard),
+            target(
+                "log",
+                Some("- {title}"),
+                ShareKind::File { path: path.clone() },
+            ),
+        ]);
+        let item = create_test_item();
+
+        let listed = sharer.targets();
+        assert_eq!(listed[0].name, "markdown");
+        assert_eq!(listed[1].kind, "file");
+
+        let shared = sharer
+            .share(sharer.get("markdown").unwrap(), &item, None)
+            .await?;
+        assert!(shared.copy);
+        assert_eq!(
+            shared.text,
+            "[Rust 1.80 released](https://example.com/rust)"
+        );
+
+        let log = sharer.get("log").unwrap();
+        sharer.share(log, &item, None).await?;
+        let shared = sharer.share(log, &item, None).await?;
+        assert!(!shared.copy);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "# Reading\n- Rust 1.80 released\n- Rust 1.80 released\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_email_needs_a_compose_provider() {
+        let sharer = Sharer::new(vec![target(
+            "mail",
+            None,
+            ShareKind::Email {
+                provider: "dummy".to_string(),
+                to: vec!["team@example.com".to_string()],
+                subject: None,
+            },
+        )]);
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider_dummy::DummyProvider::new());
+        let target = sharer.get("mail").unwrap();
+
+        let err = sharer
+            .share(target, &create_test_item(), Some(&registry))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cannot send mail"));
+        assert!(sharer
+            .share(target, &create_test_item(), None)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! LLM summarization of long items.
+//!
+//! A [`Summarizer`] turns the text of a long article, email, or video
+//! description into a short summary. Two backends are provided, selected by
+//! the `[summarizer]` config section:
+//!
+//! - [`LlamaCppSummarizer`] talks to a local llama.cpp server's native
+//!   `/completion` endpoint.
+//! - [`OpenAiSummarizer`] talks to any OpenAI-compatible `/chat/completions`
+//!   endpoint (OpenAI itself, Ollama, vLLM, or llama.cpp's `/v1`).
+//!
+//! Summaries are stored in the item's metadata under [`SUMMARY_KEY`] so each
+//! item is only summarized once. Videos with a transcript under
+//! [`TRANSCRIPT_KEY`] are summarized from it rather than their description.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use scryforge_provider_core::{Item, ItemContent};
+use serde_json::{json, Value};
+
+use crate::config::{SummarizerBackend, SummarizerConfig};
+
+/// Metadata key holding a cached summary.
+pub const SUMMARY_KEY: &str = "summary";
+
+/// Metadata key holding a cached video transcript.
+pub const TRANSCRIPT_KEY: &str = "transcript";
+
+const DEFAULT_LLAMA_CPP_ENDPOINT: &str = "http://127.0.0.1:8080";
+const DEFAULT_OPENAI_ENDPOINT: &str = "https://api.openai.com/v1";
+
+const SYSTEM_PROMPT: &str = "You summarize articles, emails, and video descriptions for a \
+busy reader. Reply with a summary of at most five sentences in the language of the text. \
+Do not add commentary or a preamble.";
+
+/// A backend that can summarize text.
+#[async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Short backend name for logs.
+    fn name(&self) -> &str;
+
+    /// Summarize `text`, which has the given title.
+    async fn summarize(&self, title: &str, text: &str) -> Result<String>;
+}
+
+/// Build the configured summarizer, or `None` if summarization is disabled.
+pub fn from_config(config: &SummarizerConfig) -> Result<Option<Arc<dyn Summarizer>>> {
+    let Some(backend) = config.backend else {
+        return Ok(None);
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .build()
+        .context("Failed to create HTTP client for summarizer")?;
+
+    let summarizer: Arc<dyn Summarizer> = match backend {
+        SummarizerBackend::LlamaCpp => Arc::new(LlamaCppSummarizer {
+            client,
+            endpoint: endpoint(config, DEFAULT_LLAMA_CPP_ENDPOINT),
+            max_tokens: config.max_tokens,
+        }),
+        SummarizerBackend::Openai => {
+            let api_key = config
+                .api_key_env
+                .as_ref()
+                .map(|var| {
+                    std::env::var(var)
+                        .with_context(|| format!("Summarizer API key variable {} is not set", var))
+                })
+                .transpose()?;
+            Arc::new(OpenAiSummarizer {
+                client,
+                endpoint: endpoint(config, DEFAULT_OPENAI_ENDPOINT),
+                model: config.model.clone(),
+                api_key,
+                max_tokens: config.max_tokens,
+            })
+        }
+    };
+
+    Ok(Some(summarizer))
+}
+
+fn endpoint(config: &SummarizerConfig, default: &str) -> String {
+    config
+        .endpoint
+        .as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Text to summarize for `item`, or `None` if it is the wrong kind of item
+/// or too short to be worth summarizing.
+///
+/// Text longer than `max_chars` is truncated at a character boundary.
+pub fn summarizable_text(item: &Item, min_words: usize, max_chars: usize) -> Option<String> {
+    let text = match item.content {
+        ItemContent::Video { .. } if item.metadata.contains_key(TRANSCRIPT_KEY) => {
+            item.metadata[TRANSCRIPT_KEY].clone()
+        }
+        ItemContent::Article { .. } | ItemContent::Email { .. } | ItemContent::Video { .. } => {
+            item.content.full_text()?
+        }
+        _ => return None,
+    };
+
+    if text.split_whitespace().count() < min_words {
+        return None;
+    }
+
+    Some(match text.char_indices().nth(max_chars) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text,
+    })
+}
+
+fn user_prompt(title: &str, text: &str) -> String {
+    format!("Title: {}\n\n{}", title, text)
+}
+
+/// Summarizer backed by a llama.cpp server.
+pub struct LlamaCppSummarizer {
+    client: reqwest::Client,
+    endpoint: String,
+    max_tokens: u32,
+}
+
+impl LlamaCppSummarizer {
+    fn request_body(&self, title: &str, text: &str) -> Value {
+        json!({
+            "prompt": format!(
+                "{}\n\n{}\n\nSummary:",
+                SYSTEM_PROMPT,
+                user_prompt(title, text)
+            ),
+            "n_predict": self.max_tokens,
+            "temperature": 0.2,
+            "stream": false,
+        })
+    }
+}
+
+#[async_trait]
+impl Summarizer for LlamaCppSummarizer {
+    fn name(&self) -> &str {
+        "llama_cpp"
+    }
+
+    async fn summarize(&self, title: &str, text: &str) -> Result<String> {
+        let response: Value = self
+            .client
+            .post(format!("{}/completion", self.endpoint))
+            .json(&self.request_body(title, text))
+            .send()
+            .await
+            .context("Failed to reach llama.cpp server")?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        parse_completion(&response)
+    }
+}
+
+/// Summarizer backed by an OpenAI-compatible chat completions endpoint.
+pub struct OpenAiSummarizer {
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    max_tokens: u32,
+}
+
+impl OpenAiSummarizer {
+    fn request_body(&self, title: &str, text: &str) -> Value {
+        json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": user_prompt(title, text) },
+            ],
+            "max_tokens": self.max_tokens,
+            "temperature": 0.2,
+        })
+    }
+}
+
+#[async_trait]
+impl Summarizer for OpenAiSummarizer {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn summarize(&self, title: &str, text: &str) -> Result<String> {
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", self.endpoint))
+            .json(&self.request_body(title, text));
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response: Value = request
+            .send()
+            .await
+            .context("Failed to reach summarization endpoint")?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        parse_chat_completion(&response)
+    }
+}
+
+/// Extract the summary from a llama.cpp `/completion` response.
+fn parse_completion(response: &Value) -> Result<String> {
+    non_empty(response.get("content").and_then(Value::as_str))
+}
+
+/// Extract the summary from a `/chat/completions` response.
+fn parse_chat_completion(response: &Value) -> Result<String> {
+    non_empty(
+        response
+            .pointer("/choices/0/message/content")
+            .and_then(Value::as_str),
+    )
+}
+
+fn non_empty(summary: Option<&str>) -> Result<String> {
+    summary
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Summarizer returned no text"))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemId, StreamId};
+    use std::collections::HashMap;
+
+    fn item(content: ItemContent) -> Item {
+        Item {
+            id: ItemId("rss:1".to_string()),
+            stream_id: StreamId("rss:feed:1".to_string()),
+            title: "Long read".to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarizable_text() {
+        let article = item(ItemContent::Article {
+            summary: Some("Teaser".to_string()),
+            full_content: Some("<p>one two</p> <p>three four five</p>".to_string()),
+        });
+        assert_eq!(
+            summarizable_text(&article, 5, 1000).as_deref(),
+            Some("one two three four five")
+        );
+        assert_eq!(summarizable_text(&article, 6, 1000), None);
+        assert_eq!(
+            summarizable_text(&article, 1, 7).as_deref(),
+            Some("one two")
+        );
+
+        let email = item(ItemContent::Email {
+            subject: "Hi".to_string(),
+            body_text: None,
+            body_html: Some("<div>Hello <b>there</b></div>".to_string()),
+            snippet: "Hello".to_string(),
+        });
+        assert_eq!(
+            summarizable_text(&email, 1, 1000).as_deref(),
+            Some("Hello there")
+        );
+
+        // Short-form content is not summarized
+        let text = item(ItemContent::Text("plenty of words here".to_string()));
+        assert_eq!(summarizable_text(&text, 1, 1000), None);
+
+        // Transcripts stand in for video descriptions
+        let mut video = item(ItemContent::Video {
+            description: "Links below".to_string(),
+            duration_seconds: Some(600),
+            view_count: None,
+        });
+        assert_eq!(summarizable_text(&video, 3, 1000), None);
+        video.metadata.insert(
+            TRANSCRIPT_KEY.to_string(),
+            "Today we look at\nborrowing".to_string(),
+        );
+        assert_eq!(
+            summarizable_text(&video, 3, 1000).as_deref(),
+            Some("Today we look at\nborrowing")
+        );
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let completion = json!({ "content": "  A short summary.\n" });
+        assert_eq!(parse_completion(&completion).unwrap(), "A short summary.");
+
+        let chat = json!({
+            "choices": [{ "message": { "role": "assistant", "content": "Summary" } }]
+        });
+        assert_eq!(parse_chat_completion(&chat).unwrap(), "Summary");
+
+        assert!(parse_chat_completion(&json!({ "choices": [] })).is_err());
+        assert!(parse_completion(&json!({ "content": " " })).is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert!(from_config(&SummarizerConfig::default()).unwrap().is_none());
+
+        let config = SummarizerConfig {
+            backend: Some(SummarizerBackend::LlamaCpp),
+            ..SummarizerConfig::default()
+        };
+        let summarizer = from_config(&config).unwrap().unwrap();
+        assert_eq!(summarizer.name(), "llama_cpp");
+
+        let config = SummarizerConfig {
+            backend: Some(SummarizerBackend::Openai),
+            api_key_env: Some("SCRYFORGE_TEST_UNSET_SUMMARIZER_KEY".to_string()),
+            ..SummarizerConfig::default()
+        };
+        assert!(from_config(&config).is_err());
+    }
+}
@@ -4,10 +4,19 @@
 //! synchronization of data from all enabled providers. It handles:
 //!
 //! - Per-provider sync scheduling based on configured intervals
+//! - Refreshing single feeds and collections on the schedule of their
+//!   `[streams]` policy
+//! - A global cap on concurrent syncs and a timeout per provider sync
 //! - Tracking sync state (last sync time, status, error count)
-//! - Exponential backoff on provider errors
+//! - Retrying failed syncs according to the [`ErrorCategory`] of the error,
+//!   including provider panics
 //! - Graceful shutdown signaling
 //! - Event emission for new items
+//! - Per-stream results for providers that sync feeds one by one, so a
+//!   broken feed is reported on its own without failing the provider
+//! - Publishing sync lifecycle events on the daemon [`EventBus`]
+//! - Previewing what a sync would change without writing anything
+//!   ([`SyncManager::dry_run`])
 //!
 //! # Architecture
 //!
@@ -15,6 +24,42 @@
 //! Each task runs its own sync loop with the configured interval, fetching
 //! new data and storing it in the cache.
 //!
+//! Tasks share a semaphore sized by `sync.max_concurrent`, so a burst of due
+//! providers syncs a few at a time. Each sync is cut off after the provider's
+//! timeout; a slow IMAP server then costs one backoff step instead of holding
+//! a permit indefinitely. [`SyncManager::sync_all`] syncs every provider at
+//! once and reports each outcome, including those that failed or timed out.
+//!
+//! A provider with a daily `budget` (see [`crate::budget`]) has its sync
+//! and refresh ticks skipped as the budget runs low, which stretches its
+//! interval, and items fetched by refreshes count against its downloads.
+//! Low-bandwidth mode (see [`crate::bandwidth`]) stretches every interval
+//! the same way and trims what refreshes fetch.
+//!
+//! A feed or collection with a `refresh_minutes` policy gets a task of its
+//! own, which fetches just that stream's items and merges them into the
+//! cache, in between its provider's syncs. It shares the semaphore but not
+//! the provider's backoff: a failed refresh is logged and tried again at the
+//! next tick. With newsletter unbundling on (see [`crate::newsletters`]), a
+//! refresh also caches the stories of the newsletters it fetches.
+//!
+//! # Retries
+//!
+//! After a failed sync the task schedules the next attempt from the error's
+//! category alone (see [`retry_delay`]): transient failures and timeouts
+//! back off exponentially, throttling waits as long as the provider asked,
+//! expired credentials and permanent errors retry hourly, and operations the
+//! provider doesn't support aren't retried until a manual trigger or a
+//! successful sync. A retry may come before the next interval tick.
+//!
+//! Backoff delays are spread by up to [`RETRY_JITTER`] either way, so
+//! providers that failed together, say when the network dropped, don't all
+//! retry at the same moment; waits a throttled provider asked for are kept
+//! exact. A retry first probes the provider with its health check and only
+//! runs a full sync once the probe passes. A failed probe counts as another
+//! error and backs off further, and the error count is reset by the first
+//! successful sync.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -42,14 +87,20 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
 use scryforge_provider_core::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc, RwLock};
-use tokio::time::{interval, sleep};
+use tokio::sync::{broadcast, mpsc, RwLock, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{interval, sleep, timeout};
 use tracing::{debug, error, info, warn};
 
-use crate::cache::Cache;
-use crate::config::{Config, ProviderConfig};
+use crate::attachments::AttachmentIndexer;
+use crate::bandwidth::Bandwidth;
+use crate::cache::{Cache, StateConflict};
+use crate::config::{Config, ConflictStrategy, ProviderConfig, StreamPolicy};
+use crate::events::{DaemonEvent, EventBus};
+use crate::newsletters::{self, Unbundler};
 use crate::registry::ProviderRegistry;
 
 // ============================================================================
@@ -80,6 +131,11 @@ pub struct ProviderSyncState {
     pub error_count: u32,
     /// Scheduled time for next sync (considering backoff)
     pub next_sync: Option<DateTime<Utc>>,
+    /// Category of the last error, cleared by a successful sync
+    pub error_category: Option<ErrorCategory>,
+    /// Per-stream results of the last sync, for providers that report them
+    #[serde(default)]
+    pub streams: Vec<StreamSyncResult>,
 }
 
 impl ProviderSyncState {
@@ -90,8 +146,61 @@ impl ProviderSyncState {
             status: SyncStatus::Idle,
             error_count: 0,
             next_sync: Some(Utc::now()),
+            error_category: None,
+            streams: Vec::new(),
         }
     }
+
+    /// Whether an interval tick should sync the provider now.
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        match self.next_sync {
+            Some(next) => next <= now,
+            None => self.error_category != Some(ErrorCategory::NotSupported),
+        }
+    }
+}
+
+/// Longest wait between retries of a failing provider, in minutes.
+const MAX_BACKOFF_MINUTES: i64 = 60;
+
+/// Share of a retry delay by which retries are spread, either way.
+pub const RETRY_JITTER: f64 = 0.2;
+
+/// How long to wait before retrying a provider after `error_count`
+/// consecutive failures, the last of them in `category`. `None` means no
+/// retry is scheduled.
+pub fn retry_delay(category: ErrorCategory, error_count: u32) -> Option<Duration> {
+    match category {
+        // 2^error_count minutes, max 60 minutes
+        ErrorCategory::Transient => Some(Duration::minutes(
+            (2_i64.pow(error_count.min(6))).min(MAX_BACKOFF_MINUTES),
+        )),
+        // Waits beyond a day are taken as a day
+        ErrorCategory::RateLimited { retry_after } => {
+            Some(Duration::seconds(retry_after.min(86_400) as i64))
+        }
+        ErrorCategory::AuthExpired | ErrorCategory::PermanentProviderError => {
+            Some(Duration::minutes(MAX_BACKOFF_MINUTES))
+        }
+        ErrorCategory::NotSupported => None,
+    }
+}
+
+/// [`retry_delay`], spread by [`RETRY_JITTER`] unless the provider asked
+/// for the wait.
+fn jittered_retry_delay(category: ErrorCategory, error_count: u32) -> Option<Duration> {
+    let delay = retry_delay(category, error_count)?;
+    Some(match category {
+        ErrorCategory::RateLimited { .. } => delay,
+        _ => with_jitter(delay, rand::random()),
+    })
+}
+
+/// `delay` moved by up to [`RETRY_JITTER`] of itself, earlier for `roll`
+/// below 0.5 and later above it.
+fn with_jitter(delay: Duration, roll: f64) -> Duration {
+    let factor = 1.0 + RETRY_JITTER * (2.0 * roll.clamp(0.0, 1.0) - 1.0);
+    Duration::milliseconds((delay.num_milliseconds() as f64 * factor).round() as i64)
 }
 
 /// Event emitted when new items are discovered during sync.
@@ -103,10 +212,159 @@ pub struct SyncEvent {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Result of one provider sync cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncOutcome {
+    pub provider_id: String,
+    pub success: bool,
+    pub items_added: u32,
+    pub items_updated: u32,
+    /// Wall-clock time of the sync, excluding time spent waiting for a slot
+    pub duration_ms: u64,
+    /// Why the sync failed, if it did
+    pub error: Option<String>,
+    /// The sync was abandoned after the provider's timeout
+    pub timed_out: bool,
+    /// Category of the error, if the sync failed
+    pub error_category: Option<ErrorCategory>,
+    /// Per-stream results, for providers that report them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub streams: Vec<StreamSyncResult>,
+}
+
+impl SyncOutcome {
+    fn failed(
+        provider_id: &str,
+        error: String,
+        category: ErrorCategory,
+        duration_ms: u64,
+        timed_out: bool,
+    ) -> Self {
+        Self {
+            provider_id: provider_id.to_string(),
+            success: false,
+            items_added: 0,
+            items_updated: 0,
+            duration_ms,
+            error: Some(error),
+            timed_out,
+            error_category: Some(category),
+            streams: Vec::new(),
+        }
+    }
+}
+
+/// What syncing a provider would change in the cache, from
+/// [`SyncManager::dry_run`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncPreview {
+    pub provider_id: String,
+    /// Items the provider lists that aren't cached yet
+    pub added: Vec<PreviewItem>,
+    /// Cached items whose title, link, content, tags, or read and saved
+    /// state differ from the provider's
+    pub updated: Vec<PreviewItem>,
+    /// Cached items of the provider's feeds and collections that it no
+    /// longer lists; the cache keeps them until they are evicted
+    pub removed: Vec<PreviewItem>,
+}
+
+/// An item in a [`SyncPreview`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewItem {
+    pub item_id: ItemId,
+    pub stream_id: StreamId,
+    pub title: String,
+}
+
+impl PreviewItem {
+    fn of(item: &Item) -> Self {
+        Self {
+            item_id: item.id.clone(),
+            stream_id: item.stream_id.clone(),
+            title: item.title.clone(),
+        }
+    }
+}
+
+/// Whether a fetched item differs from its cached copy in anything a sync
+/// would write.
+fn item_changed(cached: &Item, fetched: &Item) -> bool {
+    cached.title != fetched.title
+        || cached.url != fetched.url
+        || cached.updated != fetched.updated
+        || cached.is_read != fetched.is_read
+        || cached.is_saved != fetched.is_saved
+        || cached.tags != fetched.tags
+        || serde_json::to_value(&cached.content).ok() != serde_json::to_value(&fetched.content).ok()
+}
+
 // ============================================================================
 // SyncManager
 // ============================================================================
 
+/// State shared by every sync task.
+struct SyncShared<C: Cache + 'static> {
+    registry: Arc<ProviderRegistry>,
+    cache: Arc<C>,
+    state: Arc<RwLock<HashMap<String, ProviderSyncState>>>,
+    /// Providers with a sync cycle running, see [`InFlight`]
+    in_flight: Arc<std::sync::Mutex<HashSet<String>>>,
+    event_tx: mpsc::Sender<SyncEvent>,
+    events: EventBus,
+    permits: Arc<Semaphore>,
+    attachments: Option<Arc<AttachmentIndexer>>,
+    unbundler: Option<Arc<Unbundler>>,
+    bandwidth: Bandwidth,
+}
+
+impl<C: Cache + 'static> Clone for SyncShared<C> {
+    fn clone(&self) -> Self {
+        Self {
+            registry: Arc::clone(&self.registry),
+            cache: Arc::clone(&self.cache),
+            state: Arc::clone(&self.state),
+            in_flight: Arc::clone(&self.in_flight),
+            event_tx: self.event_tx.clone(),
+            events: self.events.clone(),
+            permits: Arc::clone(&self.permits),
+            attachments: self.attachments.clone(),
+            unbundler: self.unbundler.clone(),
+            bandwidth: self.bandwidth.clone(),
+        }
+    }
+}
+
+/// Marks a provider as syncing until dropped, so a manual sync and the
+/// provider's background task never run at the same time.
+struct InFlight {
+    providers: Arc<std::sync::Mutex<HashSet<String>>>,
+    provider_id: String,
+}
+
+impl InFlight {
+    /// Mark `provider_id` as syncing, or `None` if it already is.
+    fn start(
+        providers: &Arc<std::sync::Mutex<HashSet<String>>>,
+        provider_id: &str,
+    ) -> Option<Self> {
+        providers
+            .lock()
+            .unwrap()
+            .insert(provider_id.to_string())
+            .then(|| Self {
+                providers: Arc::clone(providers),
+                provider_id: provider_id.to_string(),
+            })
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.providers.lock().unwrap().remove(&self.provider_id);
+    }
+}
+
 /// Manages background sync operations for all providers.
 ///
 /// The sync manager spawns a task for each enabled provider and coordinates
@@ -115,10 +373,8 @@ pub struct SyncEvent {
 pub struct SyncManager<C: Cache + 'static> {
     config: Config,
     registry: Arc<ProviderRegistry>,
-    cache: Arc<C>,
-    state: Arc<RwLock<HashMap<String, ProviderSyncState>>>,
+    shared: SyncShared<C>,
     shutdown_tx: Option<broadcast::Sender<()>>,
-    event_tx: mpsc::Sender<SyncEvent>,
     event_rx: Option<mpsc::Receiver<SyncEvent>>,
 }
 
@@ -132,18 +388,49 @@ impl<C: Cache + 'static> SyncManager<C> {
     /// * `cache` - Cache implementation for storing synced data
     pub fn new(config: Config, registry: Arc<ProviderRegistry>, cache: Arc<C>) -> Self {
         let (event_tx, event_rx) = mpsc::channel(100);
+        let permits = Arc::new(Semaphore::new(config.sync.max_concurrent.max(1)));
+        let attachments = AttachmentIndexer::from_config(&config.attachments).map(Arc::new);
+        let unbundler = Unbundler::from_config(&config.newsletters).map(Arc::new);
+        let bandwidth = Bandwidth::new(&config.bandwidth);
 
         Self {
             config,
-            registry,
-            cache,
-            state: Arc::new(RwLock::new(HashMap::new())),
+            registry: Arc::clone(&registry),
+            shared: SyncShared {
+                registry,
+                cache,
+                state: Arc::new(RwLock::new(HashMap::new())),
+                in_flight: Arc::default(),
+                event_tx,
+                events: EventBus::default(),
+                permits,
+                attachments,
+                unbundler,
+                bandwidth,
+            },
             shutdown_tx: None,
-            event_tx,
             event_rx: Some(event_rx),
         }
     }
 
+    /// Publish sync events on the given bus instead of a private one.
+    ///
+    /// Use this to share one bus between the sync manager and the API server.
+    pub fn with_event_bus(mut self, events: EventBus) -> Self {
+        self.shared.events = events;
+        self
+    }
+
+    /// Get a handle to the event bus this manager publishes on.
+    pub fn event_bus(&self) -> EventBus {
+        self.shared.events.clone()
+    }
+
+    /// Get the low-bandwidth mode switch the sync tasks follow.
+    pub fn bandwidth(&self) -> &Bandwidth {
+        &self.shared.bandwidth
+    }
+
     /// Start the sync manager and spawn background tasks for all enabled providers.
     ///
     /// This method spawns a tokio task for each enabled provider configured in
@@ -159,7 +446,7 @@ impl<C: Cache + 'static> SyncManager<C> {
         self.shutdown_tx = Some(shutdown_tx.clone());
 
         // Initialize state for all configured providers
-        let mut state = self.state.write().await;
+        let mut state = self.shared.state.write().await;
         for (provider_id, provider_config) in &self.config.providers {
             if provider_config.enabled {
                 state.insert(
@@ -189,9 +476,10 @@ impl<C: Cache + 'static> SyncManager<C> {
                 }
             };
 
+            let sync_timeout = provider_config.sync_timeout(&self.config.sync);
             info!(
-                "Starting sync task for provider '{}' with interval {} minutes",
-                provider_id, provider_config.sync_interval_minutes
+                "Starting sync task for provider '{}' with interval {} minutes, timeout {:?}",
+                provider_id, provider_config.sync_interval_minutes, sync_timeout
             );
 
             let task_shutdown_rx = shutdown_tx.subscribe();
@@ -205,6 +493,47 @@ impl<C: Cache + 'static> SyncManager<C> {
             task_count += 1;
         }
 
+        for (stream_id, policy) in &self.config.streams {
+            let Some(minutes) = policy.refresh_minutes else {
+                continue;
+            };
+            let Some((provider_id, _, _)) = StreamPolicy::target(stream_id) else {
+                continue;
+            };
+            let Some(provider_config) = self
+                .config
+                .providers
+                .get(provider_id)
+                .filter(|config| config.enabled)
+            else {
+                debug!(
+                    "Skipping refresh of '{}', provider '{}' is not enabled",
+                    stream_id, provider_id
+                );
+                continue;
+            };
+            let Some(provider) = self.registry.get(provider_id) else {
+                warn!(
+                    "Stream '{}' has a refresh policy but provider '{}' is not registered, skipping",
+                    stream_id, provider_id
+                );
+                continue;
+            };
+
+            info!(
+                "Starting refresh task for stream '{}' with interval {} minutes",
+                stream_id, minutes
+            );
+            self.spawn_refresh_task(
+                stream_id.clone(),
+                provider,
+                provider_config.clone(),
+                std::time::Duration::from_secs(minutes * 60),
+                shutdown_tx.subscribe(),
+            );
+            task_count += 1;
+        }
+
         if task_count == 0 {
             warn!("No enabled providers found, sync manager has no work to do");
         } else {
@@ -222,25 +551,28 @@ impl<C: Cache + 'static> SyncManager<C> {
         config: ProviderConfig,
         mut shutdown_rx: broadcast::Receiver<()>,
     ) {
-        let cache = Arc::clone(&self.cache);
-        let state = Arc::clone(&self.state);
-        let event_tx = self.event_tx.clone();
+        let shared = self.shared.clone();
+        let sync_timeout = config.sync_timeout(&self.config.sync);
+        let every = std::time::Duration::from_secs(config.sync_interval_minutes * 60);
 
         tokio::spawn(async move {
-            let mut sync_interval = interval(std::time::Duration::from_secs(
-                config.sync_interval_minutes * 60,
-            ));
+            let mut sync_interval = interval(every);
 
             loop {
                 tokio::select! {
                     _ = sync_interval.tick() => {
-                        Self::run_sync_cycle(
-                            &provider_id,
-                            &provider,
-                            &cache,
-                            &state,
-                            &event_tx,
-                        ).await;
+                        if !Self::is_due(&provider_id, &shared).await {
+                            debug!("Provider '{}' is backing off, skipping this tick", provider_id);
+                        } else if !Self::interval_elapsed(&provider_id, &shared, every).await {
+                            debug!("Provider '{}' is on a stretched interval, skipping this tick", provider_id);
+                        } else {
+                            Self::run_sync_cycle(&provider_id, &provider, &shared, sync_timeout).await;
+                        }
+                    }
+                    _ = Self::wait_for_retry(&provider_id, &shared) => {
+                        if Self::probe_recovery(&provider_id, &provider, &shared, sync_timeout).await {
+                            Self::run_sync_cycle(&provider_id, &provider, &shared, sync_timeout).await;
+                        }
                     }
                     _ = shutdown_rx.recv() => {
                         info!("Sync task for '{}' received shutdown signal", provider_id);
@@ -253,16 +585,379 @@ impl<C: Cache + 'static> SyncManager<C> {
         });
     }
 
+    /// Spawn a background task refreshing a single feed or collection every
+    /// `every`.
+    fn spawn_refresh_task(
+        &self,
+        stream_id: String,
+        provider: Arc<dyn Provider>,
+        config: ProviderConfig,
+        every: std::time::Duration,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let shared = self.shared.clone();
+        let sync_timeout = config.sync_timeout(&self.config.sync);
+
+        tokio::spawn(async move {
+            let mut refresh_interval = interval(every);
+            let mut last_refresh: Option<std::time::Instant> = None;
+
+            loop {
+                tokio::select! {
+                    _ = refresh_interval.tick() => {
+                        let stretched = Self::stretched_interval(provider.id(), &shared, every);
+                        if last_refresh.is_some_and(|last| last.elapsed() + every / 2 < stretched) {
+                            debug!(
+                                "Provider '{}' is on a stretched interval, skipping refresh of '{}'",
+                                provider.id(),
+                                stream_id
+                            );
+                            continue;
+                        }
+                        last_refresh = Some(std::time::Instant::now());
+                        let refresh = Self::refresh_stream(
+                            &stream_id,
+                            &provider,
+                            &shared,
+                            config.conflict_strategy,
+                            sync_timeout,
+                        );
+                        match refresh.await {
+                            Ok(Some(_)) => {}
+                            Ok(None) => {
+                                warn!(
+                                    "Provider '{}' can't fetch stream '{}' on its own, stopping its refresh",
+                                    provider.id(),
+                                    stream_id
+                                );
+                                break;
+                            }
+                            Err(e) => warn!("Failed to refresh stream '{}': {:#}", stream_id, e),
+                        }
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+
+            debug!("Refresh task for '{}' stopped", stream_id);
+        });
+    }
+
+    /// Fetch the items of one feed or collection and merge them into the
+    /// cache, returning how many were new.
+    ///
+    /// Returns `Ok(None)` if the provider has no such feeds or collections.
+    async fn refresh_stream(
+        stream_id: &str,
+        provider: &Arc<dyn Provider>,
+        shared: &SyncShared<C>,
+        strategy: ConflictStrategy,
+        sync_timeout: std::time::Duration,
+    ) -> Result<Option<u32>> {
+        let Some((provider_id, is_collection, local_id)) = StreamPolicy::target(stream_id) else {
+            return Ok(None);
+        };
+
+        // Items can only be cached under a cached stream
+        let cached = shared
+            .cache
+            .get_streams(Some(provider_id))?
+            .iter()
+            .any(|stream| stream.id.as_str() == stream_id);
+
+        let _permit = shared.permits.acquire().await.ok();
+        let fetch = async {
+            if is_collection {
+                let Some(collections) = provider.as_collections() else {
+                    return Ok(None);
+                };
+                let name = if cached {
+                    None
+                } else {
+                    collections
+                        .list_collections()
+                        .await?
+                        .into_iter()
+                        .find(|collection| collection.id.0 == local_id)
+                        .map(|collection| collection.name)
+                };
+                let items = collections
+                    .get_collection_items(&CollectionId(local_id.to_string()))
+                    .await?;
+                Ok(Some((StreamType::Collection, name, items)))
+            } else {
+                let Some(feeds) = provider.as_feeds() else {
+                    return Ok(None);
+                };
+                let name = if cached {
+                    None
+                } else {
+                    feeds
+                        .list_feeds()
+                        .await?
+                        .into_iter()
+                        .find(|feed| feed.id.0 == local_id)
+                        .map(|feed| feed.name)
+                };
+                let options = FeedOptions {
+                    limit: shared.bandwidth.page_size(),
+                    include_read: true,
+                    low_bandwidth: shared.bandwidth.is_low(),
+                    ..FeedOptions::default()
+                };
+                let items = feeds
+                    .get_feed_items(&FeedId(local_id.to_string()), options)
+                    .await?;
+                Ok(Some((StreamType::Feed, name, items)))
+            }
+        };
+        let fetched = timeout(sync_timeout, shared.registry.guard(provider_id, fetch))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}s", sync_timeout.as_secs()))??;
+        let Some((stream_type, name, mut items)) = fetched else {
+            return Ok(None);
+        };
+        shared.registry.budgets().record_items(provider_id, &items);
+
+        if !cached {
+            shared.cache.upsert_streams(&[Stream {
+                id: StreamId(stream_id.to_string()),
+                name: name.unwrap_or_else(|| local_id.to_string()),
+                provider_id: provider_id.to_string(),
+                stream_type,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: Some(Utc::now()),
+                metadata: HashMap::new(),
+            }])?;
+        }
+
+        // Newsletters are kept whole, with each of their stories next to them
+        let mut stories = Vec::new();
+        if let Some(unbundler) = &shared.unbundler {
+            for story in unbundler.unbundle(stream_id, &mut items) {
+                let removed = match newsletters::newsletter_id(&story) {
+                    Some(newsletter) => shared.cache.is_tombstoned(&newsletter)?,
+                    None => false,
+                };
+                if !removed && !shared.cache.is_tombstoned(&story.id)? {
+                    stories.push(story);
+                }
+            }
+        }
+
+        // Items removed at their source stay out of the cache
+        let mut new_items = Vec::new();
+        for item in &items {
+            if shared.cache.get_item(&item.id)?.is_none()
+                && !shared.cache.is_tombstoned(&item.id)?
+            {
+                new_items.push(item);
+            }
+        }
+        let mut new_stories = 0;
+        for story in &stories {
+            if shared.cache.get_item(&story.id)?.is_none() {
+                new_stories += 1;
+            }
+        }
+        let items_added = (new_items.len() + new_stories) as u32;
+        shared.cache.merge_items(&items, strategy)?;
+        // Stories are unknown to the provider, so their read and saved state
+        // is only ever changed locally
+        shared.cache.upsert_items(&stories)?;
+        debug!(
+            "Refreshed stream '{}': {} items, {} stories, {} new",
+            stream_id,
+            items.len(),
+            stories.len(),
+            items_added
+        );
+
+        // Attachment text is only read once, when an item is first cached,
+        // and not at all in low-bandwidth mode
+        let indexer = shared
+            .attachments
+            .as_ref()
+            .filter(|_| shared.bandwidth.fetch_attachments());
+        if let Some(indexer) = indexer {
+            for item in new_items {
+                let index = indexer.index_item(&shared.registry, shared.cache.as_ref(), item);
+                if let Err(e) = timeout(sync_timeout, index)
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out")))
+                {
+                    warn!(
+                        "Failed to index attachments of '{}': {:#}",
+                        item.id.as_str(),
+                        e
+                    );
+                }
+            }
+        }
+
+        if items_added > 0 {
+            shared.events.publish(DaemonEvent::NewItems {
+                provider_id: provider_id.to_string(),
+                items_added,
+                items_updated: (items.len() + stories.len()) as u32 - items_added,
+                timestamp: Utc::now(),
+            });
+        }
+        Ok(Some(items_added))
+    }
+
+    async fn is_due(provider_id: &str, shared: &SyncShared<C>) -> bool {
+        let state = shared.state.read().await;
+        state
+            .get(provider_id)
+            .is_none_or(|provider_state| provider_state.is_due(Utc::now()))
+    }
+
+    /// A provider's interval `every`, stretched by its daily budget and by
+    /// low-bandwidth mode.
+    fn stretched_interval(
+        provider_id: &str,
+        shared: &SyncShared<C>,
+        every: std::time::Duration,
+    ) -> std::time::Duration {
+        let every = shared.registry.budgets().stretch(provider_id, every);
+        shared.bandwidth.stretch(every)
+    }
+
+    /// Whether the provider's last sync is far enough back for its interval
+    /// `every`, as stretched by [`Self::stretched_interval`].
+    async fn interval_elapsed(
+        provider_id: &str,
+        shared: &SyncShared<C>,
+        every: std::time::Duration,
+    ) -> bool {
+        let stretched = Self::stretched_interval(provider_id, shared, every);
+        if stretched <= every {
+            return true;
+        }
+        let state = shared.state.read().await;
+        // Ticks come every `every`, so the sync runs on the first tick
+        // after the stretched interval
+        state
+            .get(provider_id)
+            .and_then(|provider_state| provider_state.last_sync)
+            .is_none_or(|last| {
+                (Utc::now() - last).to_std().unwrap_or_default() + every / 2 >= stretched
+            })
+    }
+
+    /// Wait until the scheduled retry of a failed sync, or forever if none
+    /// is scheduled.
+    async fn wait_for_retry(provider_id: &str, shared: &SyncShared<C>) {
+        let retry_at = {
+            let state = shared.state.read().await;
+            state
+                .get(provider_id)
+                .filter(|provider_state| provider_state.error_category.is_some())
+                .and_then(|provider_state| provider_state.next_sync)
+        };
+        match retry_at {
+            Some(retry_at) => {
+                let wait = (retry_at - Utc::now()).to_std().unwrap_or_default();
+                sleep(wait).await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Ask a failing provider's health check whether it has recovered,
+    /// before its retry runs a full sync.
+    ///
+    /// A failed probe is recorded like a failed sync, in the category of
+    /// the probe's error or, if the provider just reports itself unhealthy,
+    /// that of the last error, which schedules the next retry further out.
+    async fn probe_recovery(
+        provider_id: &str,
+        provider: &Arc<dyn Provider>,
+        shared: &SyncShared<C>,
+        sync_timeout: std::time::Duration,
+    ) -> bool {
+        let probe = timeout(
+            sync_timeout,
+            shared.registry.guard(provider_id, provider.health_check()),
+        )
+        .await;
+        let (message, category) = match probe {
+            Ok(Ok(health)) if health.is_healthy => {
+                info!(
+                    "Provider '{}' passed its recovery probe, retrying sync",
+                    provider_id
+                );
+                return true;
+            }
+            Ok(Ok(health)) => {
+                let last_category = shared
+                    .state
+                    .read()
+                    .await
+                    .get(provider_id)
+                    .and_then(|provider_state| provider_state.error_category)
+                    .unwrap_or(ErrorCategory::Transient);
+                let reason = health
+                    .message
+                    .unwrap_or_else(|| "provider reports itself unhealthy".to_string());
+                (format!("Recovery probe failed: {}", reason), last_category)
+            }
+            Ok(Err(e)) => (format!("Recovery probe failed: {}", e), e.category()),
+            Err(_) => (
+                format!("Recovery probe timed out after {}s", sync_timeout.as_secs()),
+                ErrorCategory::Transient,
+            ),
+        };
+        warn!("Provider '{}': {}", provider_id, message);
+        Self::handle_sync_error(provider_id, &shared.state, &message, category).await;
+        false
+    }
+
     /// Run a single sync cycle for a provider.
+    ///
+    /// Waits for a free sync slot first, then gives the provider at most
+    /// `sync_timeout` to finish. Fails at once if the provider is already
+    /// syncing.
     async fn run_sync_cycle(
         provider_id: &str,
         provider: &Arc<dyn Provider>,
-        cache: &Arc<C>,
-        state: &Arc<RwLock<HashMap<String, ProviderSyncState>>>,
-        event_tx: &mpsc::Sender<SyncEvent>,
-    ) {
+        shared: &SyncShared<C>,
+        sync_timeout: std::time::Duration,
+    ) -> SyncOutcome {
+        let SyncShared {
+            registry,
+            cache,
+            state,
+            in_flight,
+            event_tx,
+            events,
+            permits,
+            ..
+        } = shared;
+
+        let Some(_in_flight) = InFlight::start(in_flight, provider_id) else {
+            debug!("Provider '{}' is already syncing", provider_id);
+            return SyncOutcome::failed(
+                provider_id,
+                "Sync already in progress".to_string(),
+                ErrorCategory::Transient,
+                0,
+                false,
+            );
+        };
+
+        // The semaphore is never closed, so acquiring only waits
+        let _permit = permits.acquire().await.ok();
         debug!("Starting sync cycle for provider '{}'", provider_id);
 
+        events.publish(DaemonEvent::SyncStarted {
+            provider_id: provider_id.to_string(),
+            timestamp: Utc::now(),
+        });
+
         // Update state to syncing
         {
             let mut state_lock = state.write().await;
@@ -273,11 +968,28 @@ impl<C: Cache + 'static> SyncManager<C> {
 
         // Execute the sync
         let sync_start = std::time::Instant::now();
-        let sync_result = provider.sync().await;
+        let sync_result = timeout(sync_timeout, registry.guard(provider_id, provider.sync())).await;
         let duration = sync_start.elapsed();
+        let duration_ms = duration.as_millis() as u64;
+
+        let outcome = match sync_result {
+            Ok(Ok(result)) => {
+                let failed_streams: Vec<String> = result
+                    .failed_streams()
+                    .map(|stream| stream.stream_id.0.clone())
+                    .collect();
+                for stream in result.failed_streams() {
+                    warn!(
+                        "Provider '{}' stream '{}' failed to sync: {}",
+                        provider_id,
+                        stream.stream_id.as_str(),
+                        stream.error.as_deref().unwrap_or_default()
+                    );
+                }
+                if let Some(provider_state) = state.write().await.get_mut(provider_id) {
+                    provider_state.streams = result.streams.clone();
+                }
 
-        match sync_result {
-            Ok(result) => {
                 if result.success {
                     info!(
                         "Provider '{}' sync completed successfully: +{} items, ~{} items, duration: {}ms",
@@ -298,6 +1010,7 @@ impl<C: Cache + 'static> SyncManager<C> {
                             provider_state.last_sync = Some(now);
                             provider_state.error_count = 0;
                             provider_state.next_sync = None;
+                            provider_state.error_category = None;
                         }
                     }
 
@@ -313,63 +1026,134 @@ impl<C: Cache + 'static> SyncManager<C> {
                         if let Err(e) = event_tx.send(event).await {
                             warn!("Failed to send sync event: {}", e);
                         }
+
+                        events.publish(DaemonEvent::NewItems {
+                            provider_id: provider_id.to_string(),
+                            items_added: result.items_added,
+                            items_updated: result.items_updated,
+                            timestamp: now,
+                        });
+                    }
+
+                    events.publish(DaemonEvent::SyncFinished {
+                        provider_id: provider_id.to_string(),
+                        success: true,
+                        error: None,
+                        failed_streams,
+                        timestamp: now,
+                    });
+
+                    SyncOutcome {
+                        provider_id: provider_id.to_string(),
+                        success: true,
+                        items_added: result.items_added,
+                        items_updated: result.items_updated,
+                        duration_ms,
+                        error: None,
+                        timed_out: false,
+                        error_category: None,
+                        streams: result.streams,
                     }
                 } else {
                     warn!(
                         "Provider '{}' sync completed with errors: {:?}",
                         provider_id, result.errors
                     );
-                    Self::handle_sync_error(
+                    // A partial failure reports its errors as text only
+                    let category = ErrorCategory::Transient;
+                    let message = format!("Sync failed: {:?}", result.errors);
+                    Self::handle_sync_error(provider_id, state, &message, category).await;
+                    Self::publish_sync_failure(
+                        events,
                         provider_id,
-                        state,
-                        &format!("Sync failed: {:?}", result.errors),
-                    )
-                    .await;
+                        message.clone(),
+                        failed_streams,
+                    );
+                    SyncOutcome {
+                        streams: result.streams,
+                        ..SyncOutcome::failed(provider_id, message, category, duration_ms, false)
+                    }
                 }
             }
-            Err(e) => {
+            Ok(Err(e)) => {
                 error!("Provider '{}' sync failed: {}", provider_id, e);
-                Self::handle_sync_error(provider_id, state, &e.to_string()).await;
+                let category = e.category();
+                Self::handle_sync_error(provider_id, state, &e.to_string(), category).await;
+                Self::publish_sync_failure(events, provider_id, e.to_string(), Vec::new());
+                SyncOutcome::failed(provider_id, e.to_string(), category, duration_ms, false)
             }
-        }
+            Err(_) => {
+                let category = ErrorCategory::Transient;
+                let message = format!("Sync timed out after {}s", sync_timeout.as_secs());
+                error!("Provider '{}': {}", provider_id, message);
+                Self::handle_sync_error(provider_id, state, &message, category).await;
+                Self::publish_sync_failure(events, provider_id, message.clone(), Vec::new());
+                SyncOutcome::failed(provider_id, message, category, duration_ms, true)
+            }
+        };
 
         debug!(
             "Sync cycle for provider '{}' completed in {:?}",
             provider_id, duration
         );
+        outcome
     }
 
-    /// Handle a sync error with exponential backoff.
+    /// Publish a `SyncFinished` event for a failed sync.
+    fn publish_sync_failure(
+        events: &EventBus,
+        provider_id: &str,
+        message: String,
+        failed_streams: Vec<String>,
+    ) {
+        events.publish(DaemonEvent::SyncFinished {
+            provider_id: provider_id.to_string(),
+            success: false,
+            error: Some(message),
+            failed_streams,
+            timestamp: Utc::now(),
+        });
+    }
+
+    /// Record a sync error and schedule the retry its category calls for.
     async fn handle_sync_error(
         provider_id: &str,
         state: &Arc<RwLock<HashMap<String, ProviderSyncState>>>,
         error_message: &str,
+        category: ErrorCategory,
     ) {
         let mut state_lock = state.write().await;
         if let Some(provider_state) = state_lock.get_mut(provider_id) {
             provider_state.status = SyncStatus::Error(error_message.to_string());
             provider_state.error_count += 1;
-
-            // Calculate exponential backoff: 2^error_count minutes, max 60 minutes
-            let backoff_minutes = (2_u32.pow(provider_state.error_count.min(6))).min(60);
-            let backoff_duration = Duration::minutes(backoff_minutes as i64);
-            provider_state.next_sync = Some(Utc::now() + backoff_duration);
-
-            warn!(
-                "Provider '{}' error count: {}, next retry in {} minutes",
-                provider_id, provider_state.error_count, backoff_minutes
-            );
+            provider_state.error_category = Some(category);
+
+            let delay = jittered_retry_delay(category, provider_state.error_count);
+            provider_state.next_sync = delay.map(|delay| Utc::now() + delay);
+
+            match delay {
+                Some(delay) => warn!(
+                    "Provider '{}' error count: {}, next retry in {}s",
+                    provider_id,
+                    provider_state.error_count,
+                    delay.num_seconds()
+                ),
+                None => warn!(
+                    "Provider '{}' error count: {}, not retrying until triggered",
+                    provider_id, provider_state.error_count
+                ),
+            }
         }
     }
 
     /// Get the current sync state for all providers.
     pub async fn get_sync_states(&self) -> HashMap<String, ProviderSyncState> {
-        self.state.read().await.clone()
+        self.shared.state.read().await.clone()
     }
 
     /// Get the sync state for a specific provider.
     pub async fn get_provider_state(&self, provider_id: &str) -> Option<ProviderSyncState> {
-        self.state.read().await.get(provider_id).cloned()
+        self.shared.state.read().await.get(provider_id).cloned()
     }
 
     /// Get the provider registry.
@@ -410,18 +1194,156 @@ impl<C: Cache + 'static> SyncManager<C> {
             .context("Provider not registered")?;
 
         // Run sync cycle immediately
-        Self::run_sync_cycle(
-            provider_id,
-            &provider,
-            &self.cache,
-            &self.state,
-            &self.event_tx,
-        )
-        .await;
+        let sync_timeout = provider_config.sync_timeout(&self.config.sync);
+        Self::run_sync_cycle(provider_id, &provider, &self.shared, sync_timeout).await;
 
         Ok(())
     }
 
+    /// Sync every enabled provider now, concurrently.
+    ///
+    /// Providers still share the `sync.max_concurrent` limit with the
+    /// background tasks, and one already syncing fails instead of syncing
+    /// twice. Returns one outcome per provider, sorted by provider ID, once
+    /// all have finished or timed out; a failing provider does not hide the
+    /// results of the others.
+    ///
+    /// The syncs start right away, and the returned future doesn't borrow
+    /// the manager, so a lock on it can be released while waiting. Must be
+    /// called within a Tokio runtime.
+    pub fn sync_all(&self) -> impl Future<Output = Vec<SyncOutcome>> + Send + 'static {
+        let mut tasks = JoinSet::new();
+
+        for (provider_id, provider_config) in &self.config.providers {
+            if !provider_config.enabled {
+                continue;
+            }
+            let Some(provider) = self.registry.get(provider_id) else {
+                continue;
+            };
+
+            let provider_id = provider_id.clone();
+            let shared = self.shared.clone();
+            let sync_timeout = provider_config.sync_timeout(&self.config.sync);
+            tasks.spawn(async move {
+                Self::run_sync_cycle(&provider_id, &provider, &shared, sync_timeout).await
+            });
+        }
+
+        async move {
+            let mut outcomes = Vec::new();
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(outcome) => outcomes.push(outcome),
+                    Err(e) => error!("Sync task panicked: {}", e),
+                }
+            }
+            outcomes.sort_by(|a, b| a.provider_id.cmp(&b.provider_id));
+
+            let failed = outcomes.iter().filter(|o| !o.success).count();
+            info!(
+                "Synced {} provider(s), {} failed",
+                outcomes.len() - failed,
+                failed
+            );
+            outcomes
+        }
+    }
+
+    /// Fetch every feed and collection of a provider and compare them with
+    /// the cache, without writing anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider is not configured or registered, or
+    /// if fetching fails or exceeds the provider's timeout.
+    pub async fn dry_run(&self, provider_id: &str) -> Result<SyncPreview> {
+        let provider_config = self
+            .config
+            .providers
+            .get(provider_id)
+            .context("Provider not configured")?;
+        let provider = self
+            .registry
+            .get(provider_id)
+            .context("Provider not registered")?;
+
+        let fetch = async {
+            let mut streams = Vec::new();
+            if let Some(feeds) = provider.as_feeds() {
+                for feed in feeds.list_feeds().await? {
+                    let options = FeedOptions {
+                        include_read: true,
+                        ..FeedOptions::default()
+                    };
+                    let items = feeds.get_feed_items(&feed.id, options).await?;
+                    streams.push((StreamId::new(provider_id, "feed", &feed.id.0), items));
+                }
+            }
+            if let Some(collections) = provider.as_collections() {
+                for collection in collections.list_collections().await? {
+                    let items = collections.get_collection_items(&collection.id).await?;
+                    streams.push((
+                        StreamId::new(provider_id, "collection", &collection.id.0),
+                        items,
+                    ));
+                }
+            }
+            Ok(streams)
+        };
+        let sync_timeout = provider_config.sync_timeout(&self.config.sync);
+        let streams = timeout(sync_timeout, self.registry.guard(provider_id, fetch))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}s", sync_timeout.as_secs()))??;
+        for (_, items) in &streams {
+            self.registry.budgets().record_items(provider_id, items);
+        }
+
+        let mut preview = SyncPreview {
+            provider_id: provider_id.to_string(),
+            ..SyncPreview::default()
+        };
+        let mut listed = HashSet::new();
+        for (_, items) in &streams {
+            for item in items {
+                if !listed.insert(item.id.clone()) {
+                    continue;
+                }
+                match self.shared.cache.get_item(&item.id)? {
+                    None if self.shared.cache.is_tombstoned(&item.id)? => {}
+                    None => preview.added.push(PreviewItem::of(item)),
+                    Some(cached) if item_changed(&cached, item) => {
+                        preview.updated.push(PreviewItem::of(item))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        for (stream_id, _) in &streams {
+            for cached in self.shared.cache.get_items(stream_id, None)? {
+                // Stories of newsletters are never listed by the provider
+                if !listed.contains(&cached.id) && !newsletters::is_story(&cached) {
+                    preview.removed.push(PreviewItem::of(&cached));
+                }
+            }
+        }
+        Ok(preview)
+    }
+
+    /// Store items fetched from a provider, merging their read and saved
+    /// state with local changes using the provider's `conflict_strategy`.
+    ///
+    /// Returns the conflicts that were resolved.
+    pub fn merge_items(&self, provider_id: &str, items: &[Item]) -> Result<Vec<StateConflict>> {
+        let strategy = self
+            .config
+            .providers
+            .get(provider_id)
+            .map(|p| p.conflict_strategy)
+            .unwrap_or_default();
+        self.shared.cache.merge_items(items, strategy)
+    }
+
     /// Gracefully shutdown all sync tasks.
     ///
     /// This sends a shutdown signal to all background sync tasks and waits
@@ -457,28 +1379,70 @@ mod tests {
     use super::*;
     use crate::cache::SqliteCache;
     use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::TempDir;
 
     // Mock provider for testing
     struct MockProvider {
         id: &'static str,
-        should_fail: bool,
+        failure: Option<fn() -> StreamError>,
+        unhealthy: bool,
+        should_panic: bool,
+        delay: std::time::Duration,
+        active: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+        syncs: Arc<AtomicUsize>,
+        stream_results: Vec<StreamSyncResult>,
     }
 
     impl MockProvider {
         fn new(id: &'static str) -> Self {
             Self {
                 id,
-                should_fail: false,
+                failure: None,
+                unhealthy: false,
+                should_panic: false,
+                delay: std::time::Duration::ZERO,
+                active: Arc::default(),
+                peak: Arc::default(),
+                syncs: Arc::default(),
+                stream_results: Vec::new(),
             }
         }
 
         fn new_failing(id: &'static str) -> Self {
+            Self::failing_with(id, || {
+                StreamError::Provider("Mock sync failure".to_string())
+            })
+        }
+
+        fn failing_with(id: &'static str, failure: fn() -> StreamError) -> Self {
             Self {
-                id,
-                should_fail: true,
+                failure: Some(failure),
+                ..Self::new(id)
             }
         }
+
+        fn new_panicking(id: &'static str) -> Self {
+            Self {
+                should_panic: true,
+                ..Self::new(id)
+            }
+        }
+
+        fn new_slow(id: &'static str, delay: std::time::Duration) -> Self {
+            Self {
+                delay,
+                ..Self::new(id)
+            }
+        }
+
+        /// Count concurrent syncs in shared counters.
+        fn tracked(mut self, active: &Arc<AtomicUsize>, peak: &Arc<AtomicUsize>) -> Self {
+            self.active = Arc::clone(active);
+            self.peak = Arc::clone(peak);
+            self
+        }
     }
 
     #[async_trait]
@@ -493,16 +1457,27 @@ mod tests {
 
         async fn health_check(&self) -> scryforge_provider_core::Result<ProviderHealth> {
             Ok(ProviderHealth {
-                is_healthy: true,
-                message: None,
+                is_healthy: !self.unhealthy,
+                message: self.unhealthy.then(|| "Mock endpoint down".to_string()),
                 last_sync: None,
                 error_count: 0,
             })
         }
 
         async fn sync(&self) -> scryforge_provider_core::Result<SyncResult> {
-            if self.should_fail {
-                Err(StreamError::Provider("Mock sync failure".to_string()))
+            self.syncs.fetch_add(1, Ordering::SeqCst);
+            let running = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            sleep(self.delay).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            if self.should_panic {
+                panic!("malformed API response");
+            }
+            if let Some(failure) = self.failure {
+                Err(failure())
+            } else if !self.stream_results.is_empty() {
+                Ok(SyncResult::from_streams(self.stream_results.clone(), 100))
             } else {
                 Ok(SyncResult {
                     success: true,
@@ -511,6 +1486,7 @@ mod tests {
                     items_removed: 0,
                     errors: vec![],
                     duration_ms: 100,
+                    streams: Vec::new(),
                 })
             }
         }
@@ -558,6 +1534,11 @@ mod tests {
             ProviderConfig {
                 enabled: true,
                 sync_interval_minutes: 1,
+                sync_timeout_secs: None,
+                conflict_strategy: Default::default(),
+                encrypt_at_rest: false,
+                budget: Default::default(),
+                proxy: None,
                 settings: toml::Value::Table(toml::map::Map::new()),
             },
         );
@@ -658,6 +1639,11 @@ mod tests {
             ProviderConfig {
                 enabled: true,
                 sync_interval_minutes: 60, // Long interval to avoid additional automatic syncs
+                sync_timeout_secs: None,
+                conflict_strategy: Default::default(),
+                encrypt_at_rest: false,
+                budget: Default::default(),
+                proxy: None,
                 settings: toml::Value::Table(toml::map::Map::new()),
             },
         );
@@ -685,6 +1671,398 @@ mod tests {
         sync_manager.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn test_sync_publishes_lifecycle_events() {
+        let config = create_test_config();
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::new("mock"));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let events = EventBus::default();
+        let mut rx = events.subscribe();
+        let sync_manager = SyncManager::new(config, registry, cache).with_event_bus(events);
+
+        sync_manager.trigger_sync("mock").await.unwrap();
+
+        let mut received = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            received.push(event);
+        }
+
+        assert_eq!(received.len(), 3);
+        assert!(matches!(received[0], DaemonEvent::SyncStarted { .. }));
+        assert!(matches!(
+            received[1],
+            DaemonEvent::NewItems {
+                items_added: 5,
+                items_updated: 2,
+                ..
+            }
+        ));
+        assert!(matches!(
+            received[2],
+            DaemonEvent::SyncFinished { success: true, .. }
+        ));
+    }
+
+    fn insert_provider(config: &mut Config, id: &str, sync_timeout_secs: Option<u64>) {
+        config.providers.insert(
+            id.to_string(),
+            ProviderConfig {
+                sync_interval_minutes: 60,
+                sync_timeout_secs,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_timeout_counts_as_error() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "slow", Some(5));
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::new_slow(
+            "slow",
+            std::time::Duration::from_secs(60),
+        ));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, registry, cache);
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_secs(10)).await;
+
+        let state = sync_manager.get_provider_state("slow").await.unwrap();
+        assert_eq!(state.error_count, 1);
+        assert_eq!(
+            state.status,
+            SyncStatus::Error("Sync timed out after 5s".to_string())
+        );
+
+        sync_manager.shutdown().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_all_skips_providers_already_syncing() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "slow", Some(5));
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::new_slow(
+            "slow",
+            std::time::Duration::from_secs(60),
+        ));
+        let sync_manager = SyncManager::new(config, Arc::new(registry), create_test_cache());
+
+        let first = sync_manager.sync_all();
+        tokio::task::yield_now().await;
+        let second = sync_manager.sync_all().await;
+        assert!(!second[0].success);
+        assert_eq!(second[0].error.as_deref(), Some("Sync already in progress"));
+
+        // The first sync ran its course, and the provider is free again
+        assert!(first.await[0].timed_out);
+        assert!(sync_manager.sync_all().await[0].timed_out);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sync_all_reports_partial_results() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "mock", None);
+        insert_provider(&mut config, "failing", None);
+        insert_provider(&mut config, "slow", Some(5));
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::new("mock"));
+        registry.register(MockProvider::new_failing("failing"));
+        registry.register(MockProvider::new_slow(
+            "slow",
+            std::time::Duration::from_secs(60),
+        ));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let sync_manager = SyncManager::new(config, registry, cache);
+        let outcomes = sync_manager.sync_all().await;
+
+        let ids: Vec<&str> = outcomes.iter().map(|o| o.provider_id.as_str()).collect();
+        assert_eq!(ids, vec!["failing", "mock", "slow"]);
+
+        assert!(!outcomes[0].success);
+        assert!(!outcomes[0].timed_out);
+        assert!(outcomes[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Mock sync failure"));
+
+        assert!(outcomes[1].success);
+        assert_eq!(outcomes[1].items_added, 5);
+        assert!(outcomes[1].error.is_none());
+
+        assert!(!outcomes[2].success);
+        assert!(outcomes[2].timed_out);
+    }
+
+    #[tokio::test]
+    async fn test_failed_stream_does_not_fail_sync() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "feeds", None);
+
+        let broken = StreamId("feeds:feed:broken".to_string());
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider {
+            stream_results: vec![
+                StreamSyncResult::ok(StreamId("feeds:feed:news".to_string()), 3, 1),
+                StreamSyncResult::failed(broken.clone(), "HTTP 404"),
+            ],
+            ..MockProvider::new("feeds")
+        });
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, registry, cache);
+        let mut events = sync_manager.event_bus().subscribe();
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_millis(200)).await;
+
+        let state = sync_manager.get_provider_state("feeds").await.unwrap();
+        assert_eq!(state.status, SyncStatus::Idle);
+        assert_eq!(state.error_count, 0);
+        assert_eq!(state.streams[1].error.as_deref(), Some("HTTP 404"));
+
+        let finished = std::iter::from_fn(|| events.try_recv().ok())
+            .find(|event| matches!(event, DaemonEvent::SyncFinished { .. }))
+            .unwrap();
+        assert!(matches!(
+            finished,
+            DaemonEvent::SyncFinished { success: true, failed_streams, .. }
+                if failed_streams == vec![broken.0.clone()]
+        ));
+
+        let outcomes = sync_manager.sync_all().await;
+        assert!(outcomes[0].success);
+        assert_eq!(outcomes[0].items_added, 3);
+        assert_eq!(outcomes[0].streams.len(), 2);
+
+        sync_manager.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_panicking_provider_is_isolated() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "mock", None);
+        insert_provider(&mut config, "panicky", None);
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::new("mock"));
+        registry.register(MockProvider::new_panicking("panicky"));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, Arc::clone(&registry), cache);
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_millis(200)).await;
+
+        // The background task survives the panic and keeps serving triggers
+        sync_manager.trigger_sync("panicky").await.unwrap();
+        let state = sync_manager.get_provider_state("panicky").await.unwrap();
+        assert_eq!(state.error_count, 2);
+        assert!(matches!(state.status, SyncStatus::Error(ref e) if e.contains("panicked")));
+        assert_eq!(
+            registry.panic_message("panicky").as_deref(),
+            Some("malformed API response")
+        );
+
+        let outcomes = sync_manager.sync_all().await;
+        assert!(outcomes[0].success);
+        assert!(outcomes[1].error.as_deref().unwrap().contains("panicked"));
+        assert!(registry.panic_message("mock").is_none());
+
+        sync_manager.shutdown().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_max_concurrent_limits_syncs() {
+        let mut config = Config::default();
+        config.sync.max_concurrent = 2;
+        let ids = ["a", "b", "c", "d", "e"];
+        for id in ids {
+            insert_provider(&mut config, id, None);
+        }
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut registry = ProviderRegistry::new();
+        for id in ids {
+            registry.register(
+                MockProvider::new_slow(id, std::time::Duration::from_secs(1))
+                    .tracked(&active, &peak),
+            );
+        }
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let sync_manager = SyncManager::new(config, registry, cache);
+        let outcomes = sync_manager.sync_all().await;
+
+        assert_eq!(outcomes.len(), 5);
+        assert!(outcomes.iter().all(|o| o.success));
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limited_sync_retries_before_next_tick() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "throttled", None);
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::failing_with("throttled", || {
+            StreamError::RateLimited(30)
+        }));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, registry, cache);
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_secs(10)).await;
+
+        let state = sync_manager.get_provider_state("throttled").await.unwrap();
+        assert_eq!(state.error_count, 1);
+        assert_eq!(
+            state.error_category,
+            Some(ErrorCategory::RateLimited { retry_after: 30 })
+        );
+
+        // Retried after 30 seconds, well before the hourly tick
+        sleep(std::time::Duration::from_secs(30)).await;
+        let state = sync_manager.get_provider_state("throttled").await.unwrap();
+        assert_eq!(state.error_count, 2);
+
+        sync_manager.shutdown().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unsupported_sync_is_not_retried() {
+        let mut config = Config::default();
+        config.providers.insert(
+            "unsupported".to_string(),
+            ProviderConfig {
+                sync_interval_minutes: 1,
+                ..Default::default()
+            },
+        );
+
+        let mut registry = ProviderRegistry::new();
+        registry.register(MockProvider::failing_with("unsupported", || {
+            StreamError::NotSupported("sync".to_string())
+        }));
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, registry, cache);
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_secs(5 * 60)).await;
+
+        let state = sync_manager
+            .get_provider_state("unsupported")
+            .await
+            .unwrap();
+        assert_eq!(state.error_count, 1);
+        assert_eq!(state.error_category, Some(ErrorCategory::NotSupported));
+        assert!(state.next_sync.is_none());
+
+        // A manual trigger still runs it
+        sync_manager.trigger_sync("unsupported").await.unwrap();
+        let state = sync_manager
+            .get_provider_state("unsupported")
+            .await
+            .unwrap();
+        assert_eq!(state.error_count, 2);
+
+        sync_manager.shutdown().await;
+    }
+
+    #[test]
+    fn test_retry_delay_follows_category() {
+        assert_eq!(
+            retry_delay(ErrorCategory::Transient, 1),
+            Some(Duration::minutes(2))
+        );
+        assert_eq!(
+            retry_delay(ErrorCategory::Transient, 10),
+            Some(Duration::minutes(60))
+        );
+        assert_eq!(
+            retry_delay(ErrorCategory::RateLimited { retry_after: 90 }, 5),
+            Some(Duration::seconds(90))
+        );
+        assert_eq!(
+            retry_delay(ErrorCategory::AuthExpired, 1),
+            Some(Duration::minutes(60))
+        );
+        assert_eq!(
+            retry_delay(ErrorCategory::PermanentProviderError, 1),
+            Some(Duration::minutes(60))
+        );
+        assert_eq!(retry_delay(ErrorCategory::NotSupported, 1), None);
+    }
+
+    #[test]
+    fn test_retry_jitter() {
+        let delay = Duration::minutes(60);
+        assert_eq!(with_jitter(delay, 0.0), Duration::minutes(48));
+        assert_eq!(with_jitter(delay, 0.5), delay);
+        assert_eq!(with_jitter(delay, 1.0), Duration::minutes(72));
+
+        for _ in 0..20 {
+            let delay = jittered_retry_delay(ErrorCategory::Transient, 1).unwrap();
+            assert!(delay >= Duration::seconds(96) && delay <= Duration::seconds(144));
+        }
+        assert_eq!(
+            jittered_retry_delay(ErrorCategory::RateLimited { retry_after: 30 }, 3),
+            Some(Duration::seconds(30))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_recovery_probe_backs_off() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "down", None);
+
+        let provider = MockProvider {
+            unhealthy: true,
+            ..MockProvider::failing_with("down", || StreamError::Network("refused".to_string()))
+        };
+        let syncs = Arc::clone(&provider.syncs);
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider);
+        let registry = Arc::new(registry);
+        let cache = create_test_cache();
+
+        let mut sync_manager = SyncManager::new(config, registry, cache);
+        sync_manager.start().await.unwrap();
+        sleep(std::time::Duration::from_secs(10)).await;
+        assert_eq!(syncs.load(Ordering::SeqCst), 1);
+
+        // The retry, at most 2.4 minutes later, probes without syncing
+        sleep(std::time::Duration::from_secs(150)).await;
+        let state = sync_manager.get_provider_state("down").await.unwrap();
+        assert_eq!(state.error_count, 2);
+        assert_eq!(
+            state.status,
+            SyncStatus::Error("Recovery probe failed: Mock endpoint down".to_string())
+        );
+        assert_eq!(state.error_category, Some(ErrorCategory::Transient));
+        assert_eq!(syncs.load(Ordering::SeqCst), 1);
+        let wait = state.next_sync.unwrap() - Utc::now();
+        assert!(wait > Duration::minutes(3) && wait <= Duration::seconds(288));
+
+        sync_manager.shutdown().await;
+    }
+
     #[test]
     fn test_sync_status_serialization() {
         let status_idle = SyncStatus::Idle;
@@ -709,4 +2087,172 @@ mod tests {
         assert_eq!(state.error_count, 0);
         assert!(state.next_sync.is_some());
     }
+
+    #[tokio::test]
+    async fn test_stream_policy_refreshes_single_feed() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "dummy", None);
+        config.streams.insert(
+            "dummy:feed:dummy:subscriptions".to_string(),
+            StreamPolicy {
+                refresh_minutes: Some(60),
+                ..StreamPolicy::default()
+            },
+        );
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider_dummy::DummyProvider::new());
+        let cache = create_test_cache();
+        let mut sync_manager = SyncManager::new(config, Arc::new(registry), cache.clone());
+        let mut events = sync_manager.event_bus().subscribe();
+
+        sync_manager.start().await.unwrap();
+
+        // The first tick fetches the feed into the cache, stream included
+        let stream_id = StreamId("dummy:feed:dummy:subscriptions".to_string());
+        for _ in 0..100 {
+            if !cache.get_items(&stream_id, None).unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(!cache.get_items(&stream_id, None).unwrap().is_empty());
+        let streams = cache.get_streams(Some("dummy")).unwrap();
+        assert_eq!(streams.len(), 1);
+        assert_eq!(streams[0].name, "Subscriptions");
+
+        let mut new_items = None;
+        while let Ok(event) = events.try_recv() {
+            if let DaemonEvent::NewItems { items_added, .. } = event {
+                new_items = Some(items_added);
+            }
+        }
+        assert!(new_items.is_some_and(|added| added > 0));
+
+        sync_manager.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_merge_items_uses_provider_strategy() {
+        let mut config = create_test_config();
+        config.providers.get_mut("mock").unwrap().conflict_strategy = ConflictStrategy::LocalWins;
+        let cache = create_test_cache();
+        let sync_manager =
+            SyncManager::new(config, Arc::new(ProviderRegistry::new()), cache.clone());
+
+        cache
+            .upsert_streams(&[Stream {
+                id: StreamId("mock:feed".to_string()),
+                name: "Feed".to_string(),
+                provider_id: "mock".to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])
+            .unwrap();
+        let mut item = Item {
+            id: ItemId("mock:1".to_string()),
+            stream_id: StreamId("mock:feed".to_string()),
+            title: "Item".to_string(),
+            content: ItemContent::Text("Body".to_string()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: Vec::new(),
+            metadata: HashMap::new(),
+        };
+        sync_manager
+            .merge_items("mock", std::slice::from_ref(&item))
+            .unwrap();
+
+        cache.mark_read(&item.id, true).unwrap();
+        cache.mark_read(&item.id, false).unwrap();
+        item.is_read = true;
+
+        let conflicts = sync_manager
+            .merge_items("mock", std::slice::from_ref(&item))
+            .unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].strategy, ConflictStrategy::LocalWins);
+        assert!(!cache.get_item(&item.id).unwrap().unwrap().is_read);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_nothing() {
+        let mut config = Config::default();
+        insert_provider(&mut config, "dummy", None);
+        let provider = provider_dummy::DummyProvider::new();
+        let feed_items = provider
+            .get_feed_items(
+                &FeedId("dummy:subscriptions".to_string()),
+                FeedOptions {
+                    include_read: true,
+                    ..FeedOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+        let mut registry = ProviderRegistry::new();
+        registry.register(provider);
+        let cache = create_test_cache();
+        let sync_manager = SyncManager::new(config, Arc::new(registry), cache.clone());
+
+        // Nothing cached: everything is new, and stays uncached
+        let preview = sync_manager.dry_run("dummy").await.unwrap();
+        assert!(preview.added.len() >= feed_items.len());
+        assert!(preview.updated.is_empty());
+        assert!(preview.removed.is_empty());
+        assert!(cache.get_streams(Some("dummy")).unwrap().is_empty());
+
+        // One item cached as is, one renamed, and one the feed dropped
+        let stream_id = feed_items[0].stream_id.clone();
+        cache
+            .upsert_streams(&[Stream {
+                id: stream_id.clone(),
+                name: "Subscriptions".to_string(),
+                provider_id: "dummy".to_string(),
+                stream_type: StreamType::Feed,
+                icon: None,
+                unread_count: None,
+                total_count: None,
+                last_updated: None,
+                metadata: HashMap::new(),
+            }])
+            .unwrap();
+        let mut renamed = feed_items[1].clone();
+        renamed.title = "Old title".to_string();
+        let mut dropped = feed_items[0].clone();
+        dropped.id = ItemId("dummy:gone".to_string());
+        cache
+            .upsert_items(&[feed_items[0].clone(), renamed, dropped])
+            .unwrap();
+
+        let preview = sync_manager.dry_run("dummy").await.unwrap();
+        assert_eq!(preview.provider_id, "dummy");
+        assert!(preview
+            .added
+            .iter()
+            .all(|item| item.item_id != feed_items[0].id && item.item_id != feed_items[1].id));
+        let updated: Vec<&ItemId> = preview.updated.iter().map(|item| &item.item_id).collect();
+        assert_eq!(updated, vec![&feed_items[1].id]);
+        assert_eq!(preview.updated[0].title, feed_items[1].title);
+        let removed: Vec<&str> = preview
+            .removed
+            .iter()
+            .map(|item| item.item_id.as_str())
+            .collect();
+        assert_eq!(removed, vec!["dummy:gone"]);
+        assert_eq!(
+            cache.get_item(&feed_items[1].id).unwrap().unwrap().title,
+            "Old title"
+        );
+
+        assert!(sync_manager.dry_run("missing").await.is_err());
+    }
 }
@@ -0,0 +1,524 @@
+//! Importing Google Takeout archives.
+//!
+//! `scry takeout <archive>` reads an archive exported from
+//! takeout.google.com, either as downloaded (`.tgz`) or extracted into a
+//! directory, and brings in:
+//!
+//! - YouTube watch history (exported as JSON), which marks cached videos
+//!   read and stamps them with [`WATCHED_AT_KEY`]
+//! - YouTube playlists, listed on their cached videos under
+//!   [`PLAYLISTS_KEY`]
+//! - Chrome bookmarks (`Chrome/Bookmarks.html`), added to the bookmarks
+//!   provider
+//! - YouTube subscriptions, followed through their channel feeds by either
+//!   the YouTube provider (`rss_channels`) or the RSS provider
+//!
+//! Files are found by name, so the archive's top-level folder names, which
+//! Takeout translates, don't matter. History and playlists only annotate
+//! videos already in the cache; the rest are counted as not cached.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use scryforge_provider_core::ItemId;
+use serde::Deserialize;
+
+use crate::cache::Cache;
+
+/// Metadata key holding when a video was last watched, per Takeout.
+pub const WATCHED_AT_KEY: &str = "watched_at";
+
+/// Metadata key listing the Takeout playlists a video is in, comma-separated.
+pub const PLAYLISTS_KEY: &str = "playlists";
+
+/// The files of a Takeout archive the importers read, by path.
+#[derive(Debug, Default)]
+pub struct Takeout {
+    files: BTreeMap<String, String>,
+}
+
+/// A video from the watch history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedVideo {
+    pub video_id: String,
+    pub title: String,
+    pub channel: Option<String>,
+    /// RFC 3339 timestamp
+    pub watched_at: String,
+}
+
+/// A playlist and the IDs of its videos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playlist {
+    pub name: String,
+    pub video_ids: Vec<String>,
+}
+
+/// A subscribed channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub channel_id: String,
+    pub title: String,
+}
+
+/// Outcome of [`annotate_youtube`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YouTubeImport {
+    /// Cached videos marked watched
+    pub watched: usize,
+    /// Cached videos listed with their playlists
+    pub playlisted: usize,
+    /// Videos from the history or playlists that aren't cached
+    pub not_cached: usize,
+}
+
+/// An entry of `watch-history.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryEntry {
+    title: String,
+    /// Missing for ads and removed videos
+    title_url: Option<String>,
+    #[serde(default)]
+    subtitles: Vec<HistorySubtitle>,
+    time: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistorySubtitle {
+    name: String,
+}
+
+impl Takeout {
+    /// Read an archive: a directory, or a `.tgz` / `.tar.gz` file.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut takeout = Self::default();
+        if path.is_dir() {
+            takeout.read_dir(path, path)?;
+        } else {
+            let name = path.to_string_lossy().to_ascii_lowercase();
+            if name.ends_with(".zip") {
+                bail!(
+                    "{} is a zip archive; extract it and import the directory",
+                    path.display()
+                );
+            }
+            let file =
+                File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+            let mut archive = tar::Archive::new(GzDecoder::new(file));
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().into_owned();
+                if is_wanted(&name) {
+                    let mut content = String::new();
+                    entry
+                        .read_to_string(&mut content)
+                        .with_context(|| format!("Failed to read {}", name))?;
+                    takeout.files.insert(name, content);
+                }
+            }
+        }
+
+        if takeout.files.is_empty() {
+            bail!(
+                "{} has no YouTube history, playlists, subscriptions or Chrome bookmarks",
+                path.display()
+            );
+        }
+        Ok(takeout)
+    }
+
+    fn read_dir(&mut self, root: &Path, dir: &Path) -> Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.read_dir(root, &path)?;
+                continue;
+            }
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if is_wanted(&name) {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                self.files.insert(name, content);
+            }
+        }
+        Ok(())
+    }
+
+    /// The watch history, most recent first, with each video once.
+    ///
+    /// Takeout exports the history as HTML unless JSON is chosen under
+    /// "Multiple formats"; only JSON can be read.
+    pub fn watch_history(&self) -> Result<Vec<WatchedVideo>> {
+        let Some(content) = self.file(|name| name.ends_with("/watch-history.json")) else {
+            if self
+                .file(|name| name.ends_with("/watch-history.html"))
+                .is_some()
+            {
+                bail!("the watch history was exported as HTML; export it again as JSON");
+            }
+            return Ok(Vec::new());
+        };
+        let entries: Vec<HistoryEntry> =
+            serde_json::from_str(content).context("Failed to parse watch-history.json")?;
+
+        let mut seen = HashSet::new();
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let video_id = video_id(entry.title_url.as_deref()?)?;
+                seen.insert(video_id.clone()).then(|| WatchedVideo {
+                    video_id,
+                    title: entry
+                        .title
+                        .strip_prefix("Watched ")
+                        .unwrap_or(&entry.title)
+                        .to_string(),
+                    channel: entry.subtitles.into_iter().next().map(|s| s.name),
+                    watched_at: entry.time,
+                })
+            })
+            .collect())
+    }
+
+    /// The playlists, each from its own CSV file.
+    pub fn playlists(&self) -> Vec<Playlist> {
+        self.files
+            .iter()
+            .filter(|(name, _)| is_playlist(name))
+            .filter_map(|(name, content)| {
+                let stem = name.rsplit('/').next()?.strip_suffix(".csv")?;
+                let video_ids: Vec<String> = content
+                    .lines()
+                    .filter_map(|line| csv_fields(line).into_iter().next())
+                    .filter(|field| is_video_id(field))
+                    .collect();
+                Some(Playlist {
+                    name: stem.strip_suffix("-videos").unwrap_or(stem).to_string(),
+                    video_ids,
+                })
+            })
+            .filter(|playlist| !playlist.video_ids.is_empty())
+            .collect()
+    }
+
+    /// The subscribed channels.
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        let Some(content) = self.file(|name| name.ends_with("/subscriptions.csv")) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| {
+                let fields = csv_fields(line);
+                let channel_id = fields.first()?;
+                channel_id.starts_with("UC").then(|| Subscription {
+                    channel_id: channel_id.clone(),
+                    title: fields.get(2).cloned().unwrap_or_else(|| channel_id.clone()),
+                })
+            })
+            .collect()
+    }
+
+    /// The contents of the Chrome bookmark file, if exported.
+    pub fn chrome_bookmarks(&self) -> Option<&str> {
+        self.file(|name| name.ends_with("/Bookmarks.html"))
+    }
+
+    fn file(&self, matches: impl Fn(&str) -> bool) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|(name, _)| matches(name))
+            .map(|(_, content)| content.as_str())
+    }
+}
+
+/// Whether an archive path is one of the files the importers read.
+fn is_wanted(name: &str) -> bool {
+    name.ends_with("/watch-history.json")
+        || name.ends_with("/watch-history.html")
+        || name.ends_with("/subscriptions.csv")
+        || name.ends_with("/Bookmarks.html")
+        || is_playlist(name)
+}
+
+/// Playlist files sit in a `playlists` folder beside `playlists.csv`, the
+/// index of their names.
+fn is_playlist(name: &str) -> bool {
+    let mut parts = name.rsplit('/');
+    let file = parts.next().unwrap_or_default();
+    parts.next() == Some("playlists") && file.ends_with(".csv") && file != "playlists.csv"
+}
+
+/// The `v` parameter of a watch URL.
+fn video_id(url: &str) -> Option<String> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("v="))
+        .filter(|id| is_video_id(id))
+        .map(str::to_string)
+}
+
+fn is_video_id(text: &str) -> bool {
+    text.len() == 11
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Fields of a CSV line, with quoted fields unquoted.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// Annotate cached YouTube videos from the watch history and playlists.
+///
+/// Watched videos are marked read. Annotations are kept in item metadata,
+/// which the next sync of a video replaces, so importing again after a
+/// sync restores them.
+pub fn annotate_youtube<C: Cache + ?Sized>(
+    cache: &C,
+    history: &[WatchedVideo],
+    playlists: &[Playlist],
+) -> Result<YouTubeImport> {
+    let mut import = YouTubeImport::default();
+    let mut missing = HashSet::new();
+
+    for video in history {
+        let item_id = ItemId::new("youtube", &video.video_id);
+        if cache.get_item(&item_id)?.is_none() {
+            missing.insert(video.video_id.as_str());
+            continue;
+        }
+        cache.mark_read(&item_id, true)?;
+        cache.set_item_metadata(&item_id, WATCHED_AT_KEY, &video.watched_at)?;
+        import.watched += 1;
+    }
+
+    let mut memberships: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for playlist in playlists {
+        for video_id in &playlist.video_ids {
+            let names = memberships.entry(video_id.as_str()).or_default();
+            if !names.contains(&playlist.name.as_str()) {
+                names.push(&playlist.name);
+            }
+        }
+    }
+    for (video_id, names) in memberships {
+        let item_id = ItemId::new("youtube", video_id);
+        if cache.get_item(&item_id)?.is_none() {
+            missing.insert(video_id);
+            continue;
+        }
+        cache.set_item_metadata(&item_id, PLAYLISTS_KEY, &names.join(", "))?;
+        import.playlisted += 1;
+    }
+
+    import.not_cached = missing.len();
+    Ok(import)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use chrono::Utc;
+    use scryforge_provider_core::{Item, ItemContent, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const HISTORY: &str = r#"[
+  {
+    "header": "YouTube",
+    "title": "Watched Parsing in Rust",
+    "titleUrl": "https://www.youtube.com/watch?v=aaaaaaaaaaa",
+    "subtitles": [{"name": "Rust Channel", "url": "https://www.youtube.com/channel/UCa"}],
+    "time": "2024-05-02T10:00:00.000Z"
+  },
+  {
+    "header": "YouTube",
+    "title": "Visited an ad",
+    "time": "2024-05-01T12:00:00.000Z",
+    "details": [{"name": "From Google Ads"}]
+  },
+  {
+    "header": "YouTube",
+    "title": "Watched Parsing in Rust",
+    "titleUrl": "https://www.youtube.com/watch?v=aaaaaaaaaaa",
+    "time": "2024-05-01T10:00:00.000Z"
+  },
+  {
+    "header": "YouTube",
+    "title": "Watched Something Else",
+    "titleUrl": "https://www.youtube.com/watch?v=bbbbbbbbbbb&t=10s",
+    "time": "2024-04-30T10:00:00.000Z"
+  }
+]"#;
+
+    fn write(root: &Path, name: &str, content: &str) {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn sample_takeout() -> (TempDir, Takeout) {
+        let dir = TempDir::new().unwrap();
+        let youtube = "Takeout/YouTube and YouTube Music";
+        write(
+            dir.path(),
+            &format!("{youtube}/history/watch-history.json"),
+            HISTORY,
+        );
+        write(
+            dir.path(),
+            &format!("{youtube}/playlists/playlists.csv"),
+            "Playlist ID,Playlist Title (Original)\nPLxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx,Talks\n",
+        );
+        write(
+            dir.path(),
+            &format!("{youtube}/playlists/Talks-videos.csv"),
+            "Video ID,Playlist Video Creation Timestamp\naaaaaaaaaaa,2024-01-01T00:00:00+00:00\nccccccccccc,2024-01-02T00:00:00+00:00\n",
+        );
+        write(
+            dir.path(),
+            &format!("{youtube}/subscriptions/subscriptions.csv"),
+            "Channel Id,Channel Url,Channel Title\nUCa,http://www.youtube.com/channel/UCa,\"Rust, the Channel\"\n",
+        );
+        write(dir.path(), "Takeout/Chrome/Bookmarks.html", "<DL></DL>");
+        write(dir.path(), "Takeout/Chrome/BrowserHistory.json", "{}");
+        let takeout = Takeout::open(dir.path()).unwrap();
+        (dir, takeout)
+    }
+
+    #[test]
+    fn test_parse_takeout() {
+        let (_dir, takeout) = sample_takeout();
+        assert_eq!(takeout.files.len(), 4);
+
+        let history = takeout.watch_history().unwrap();
+        assert_eq!(
+            history,
+            vec![
+                WatchedVideo {
+                    video_id: "aaaaaaaaaaa".to_string(),
+                    title: "Parsing in Rust".to_string(),
+                    channel: Some("Rust Channel".to_string()),
+                    watched_at: "2024-05-02T10:00:00.000Z".to_string(),
+                },
+                WatchedVideo {
+                    video_id: "bbbbbbbbbbb".to_string(),
+                    title: "Something Else".to_string(),
+                    channel: None,
+                    watched_at: "2024-04-30T10:00:00.000Z".to_string(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            takeout.playlists(),
+            vec![Playlist {
+                name: "Talks".to_string(),
+                video_ids: vec!["aaaaaaaaaaa".to_string(), "ccccccccccc".to_string()],
+            }]
+        );
+        assert_eq!(
+            takeout.subscriptions(),
+            vec![Subscription {
+                channel_id: "UCa".to_string(),
+                title: "Rust, the Channel".to_string(),
+            }]
+        );
+        assert_eq!(takeout.chrome_bookmarks(), Some("<DL></DL>"));
+    }
+
+    #[test]
+    fn test_open_rejects_unusable_archives() {
+        let dir = TempDir::new().unwrap();
+        assert!(Takeout::open(dir.path()).is_err());
+        write(dir.path(), "takeout.zip", "");
+        assert!(Takeout::open(&dir.path().join("takeout.zip")).is_err());
+    }
+
+    #[test]
+    fn test_annotate_youtube() -> Result<()> {
+        let (_dir, takeout) = sample_takeout();
+        let cache_dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&cache_dir.path().join("cache.db"))?;
+
+        let stream_id = StreamId::new("youtube", "feed", "UCa");
+        cache.upsert_streams(&[Stream {
+            id: stream_id.clone(),
+            name: "Rust Channel".to_string(),
+            provider_id: "youtube".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: Some(Utc::now()),
+            metadata: HashMap::new(),
+        }])?;
+        cache.upsert_items(&[Item {
+            id: ItemId::new("youtube", "aaaaaaaaaaa"),
+            stream_id,
+            title: "Parsing in Rust".to_string(),
+            content: ItemContent::Text(String::new()),
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }])?;
+
+        let import = annotate_youtube(&cache, &takeout.watch_history()?, &takeout.playlists())?;
+        assert_eq!(
+            import,
+            YouTubeImport {
+                watched: 1,
+                playlisted: 1,
+                not_cached: 2,
+            }
+        );
+
+        let item = cache
+            .get_item(&ItemId::new("youtube", "aaaaaaaaaaa"))?
+            .unwrap();
+        assert!(item.is_read);
+        assert_eq!(item.metadata[WATCHED_AT_KEY], "2024-05-02T10:00:00.000Z");
+        assert_eq!(item.metadata[PLAYLISTS_KEY], "Talks");
+
+        Ok(())
+    }
+}
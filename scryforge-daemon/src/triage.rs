@@ -0,0 +1,356 @@
+//! Keyboard-driven triage of the unified inbox.
+//!
+//! Triage walks the unread items of the unified feed one at a time, newest
+//! first, and each item gets one decision: archive, save, snooze, or skip.
+//! Decisions apply to the cache at once; what the provider should learn of
+//! them, such as an archived email, is queued in the [`crate::outbox`] and
+//! sent shortly after, so [`Triage::undo`] can usually take a decision back
+//! before it leaves the daemon.
+//!
+//! Snoozing sets a reminder and hides the item until it is due. Skipping
+//! only hides it for the rest of the session; `triage.start` begins a new
+//! session with skipped items back in the inbox. Sessions, and their undo
+//! history, do not survive a daemon restart.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use scryforge_provider_core::{Item, ItemId};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::cache::{Cache, InteractionKind};
+use crate::outbox::{self, OutboxAction};
+
+/// How long items are snoozed when `triage.snooze` is given no time.
+pub const DEFAULT_SNOOZE_HOURS: i64 = 24;
+
+/// Decisions kept for undo; older ones can no longer be taken back.
+const MAX_UNDO: usize = 100;
+
+/// A triage decision on an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageAction {
+    Archive,
+    Save,
+    Snooze,
+    Skip,
+}
+
+/// A decision taken during triage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriageStep {
+    pub item_id: ItemId,
+    pub action: TriageAction,
+    /// When a snoozed item returns to the inbox
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
+/// Where triage stands: the item to decide on next and what undo would revert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriageView {
+    /// `None` once the inbox is empty
+    pub item: Option<Item>,
+    /// Items left in the inbox, including `item`
+    pub remaining: usize,
+    /// Decisions taken this session
+    pub processed: usize,
+    /// The decision `triage.undo` would take back
+    pub undo: Option<TriageStep>,
+}
+
+/// A decision and the item state it replaced.
+struct Applied {
+    step: TriageStep,
+    was_read: bool,
+    was_saved: bool,
+    reminder: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct TriageState {
+    skipped: HashSet<ItemId>,
+    history: Vec<Applied>,
+    processed: usize,
+    /// Item to show first, set when a decision on it is undone
+    front: Option<ItemId>,
+}
+
+/// The triage session of the daemon.
+#[derive(Default)]
+pub struct Triage {
+    state: Mutex<TriageState>,
+}
+
+impl Triage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin a new session, forgetting skipped items and the undo history.
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = TriageState::default();
+    }
+
+    /// The next item to decide on out of `inbox`, the unread unified feed.
+    pub fn view<C: Cache + ?Sized>(&self, cache: &C, inbox: Vec<Item>) -> Result<TriageView> {
+        let now = Utc::now();
+        let snoozed: HashSet<ItemId> = cache
+            .get_reminders()?
+            .into_iter()
+            .filter(|reminder| !reminder.is_due(now))
+            .map(|reminder| reminder.item_id)
+            .collect();
+
+        let state = self.state.lock().unwrap();
+        let mut inbox: Vec<Item> = inbox
+            .into_iter()
+            .filter(|item| !item.is_read)
+            .filter(|item| !state.skipped.contains(&item.id) && !snoozed.contains(&item.id))
+            .collect();
+        if let Some(front) = &state.front {
+            if let Some(pos) = inbox.iter().position(|item| item.id == *front) {
+                let item = inbox.remove(pos);
+                inbox.insert(0, item);
+            }
+        }
+
+        Ok(TriageView {
+            remaining: inbox.len(),
+            item: inbox.into_iter().next(),
+            processed: state.processed,
+            undo: state.history.last().map(|applied| applied.step.clone()),
+        })
+    }
+
+    /// Take `action` on an item, snoozing until `snooze_until` or for
+    /// [`DEFAULT_SNOOZE_HOURS`].
+    pub fn apply<C: Cache + ?Sized>(
+        &self,
+        cache: &C,
+        item_id: &ItemId,
+        action: TriageAction,
+        snooze_until: Option<DateTime<Utc>>,
+    ) -> Result<TriageStep> {
+        let item = cache
+            .get_item(item_id)?
+            .ok_or_else(|| anyhow!("Item not found: {}", item_id.as_str()))?;
+        let reminder = cache
+            .get_reminders()?
+            .into_iter()
+            .find(|reminder| reminder.item_id == *item_id)
+            .map(|reminder| reminder.remind_at);
+
+        let mut step = TriageStep {
+            item_id: item_id.clone(),
+            action,
+            snoozed_until: None,
+        };
+        match action {
+            TriageAction::Archive => {
+                cache.mark_archived(item_id, true)?;
+                cache.mark_read(item_id, true)?;
+                if !item.is_read {
+                    cache.record_interaction(item_id, InteractionKind::Skipped)?;
+                    outbox::queue(cache, item_id, OutboxAction::MarkRead)?;
+                }
+                outbox::queue(cache, item_id, OutboxAction::Archive)?;
+            }
+            TriageAction::Save => {
+                cache.mark_starred(item_id, true)?;
+                cache.mark_read(item_id, true)?;
+                cache.record_interaction(item_id, InteractionKind::Saved)?;
+                if !item.is_saved {
+                    outbox::queue(cache, item_id, OutboxAction::Save)?;
+                }
+                if !item.is_read {
+                    outbox::queue(cache, item_id, OutboxAction::MarkRead)?;
+                }
+            }
+            TriageAction::Snooze => {
+                let until = snooze_until
+                    .unwrap_or_else(|| Utc::now() + Duration::hours(DEFAULT_SNOOZE_HOURS));
+                cache.set_reminder(item_id, until)?;
+                step.snoozed_until = Some(until);
+            }
+            TriageAction::Skip => {}
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if action == TriageAction::Skip {
+            state.skipped.insert(item_id.clone());
+        }
+        if state.front.as_ref() == Some(item_id) {
+            state.front = None;
+        }
+        state.processed += 1;
+        state.history.push(Applied {
+            step: step.clone(),
+            was_read: item.is_read,
+            was_saved: item.is_saved,
+            reminder,
+        });
+        if state.history.len() > MAX_UNDO {
+            state.history.remove(0);
+        }
+        Ok(step)
+    }
+
+    /// Take back the latest decision, returning it, or `None` if there is
+    /// nothing to undo.
+    ///
+    /// Provider actions still waiting in the outbox are cancelled. An
+    /// archive that was already sent stays archived on the provider.
+    pub fn undo<C: Cache + ?Sized>(&self, cache: &C) -> Result<Option<TriageStep>> {
+        let Some(applied) = self.state.lock().unwrap().history.pop() else {
+            return Ok(None);
+        };
+        let item_id = &applied.step.item_id;
+
+        match applied.step.action {
+            TriageAction::Archive => {
+                cache.mark_archived(item_id, false)?;
+                if !outbox::cancel(cache, item_id, OutboxAction::Archive)? {
+                    warn!(
+                        "Archive of {} was already sent; it stays archived on its provider",
+                        item_id.as_str()
+                    );
+                }
+                if !applied.was_read {
+                    cache.mark_read(item_id, false)?;
+                    outbox::queue(cache, item_id, OutboxAction::MarkUnread)?;
+                }
+            }
+            TriageAction::Save => {
+                if !applied.was_saved {
+                    cache.mark_starred(item_id, false)?;
+                    outbox::queue(cache, item_id, OutboxAction::Unsave)?;
+                }
+                if !applied.was_read {
+                    cache.mark_read(item_id, false)?;
+                    outbox::queue(cache, item_id, OutboxAction::MarkUnread)?;
+                }
+            }
+            TriageAction::Snooze => match applied.reminder {
+                Some(remind_at) => cache.set_reminder(item_id, remind_at)?,
+                None => {
+                    cache.remove_reminder(item_id)?;
+                }
+            },
+            TriageAction::Skip => {}
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.skipped.remove(item_id);
+        state.processed = state.processed.saturating_sub(1);
+        state.front = Some(item_id.clone());
+        Ok(Some(applied.step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use scryforge_provider_core::{ItemContent, Stream, StreamId, StreamType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn cache_with_items() -> Result<(TempDir, SqliteCache)> {
+        let dir = TempDir::new()?;
+        let cache = SqliteCache::open_at(&dir.path().join("cache.db"))?;
+        cache.upsert_streams(&[Stream {
+            id: StreamId("mail:inbox".to_string()),
+            name: "Inbox".to_string(),
+            provider_id: "mail".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        }])?;
+        let items: Vec<Item> = ["mail:1", "mail:2", "mail:3"]
+            .into_iter()
+            .map(|id| Item {
+                id: ItemId(id.to_string()),
+                stream_id: StreamId("mail:inbox".to_string()),
+                title: id.to_string(),
+                content: ItemContent::Text(String::new()),
+                author: None,
+                published: None,
+                updated: None,
+                url: None,
+                thumbnail_url: None,
+                is_read: false,
+                is_saved: false,
+                tags: vec![],
+                metadata: HashMap::new(),
+            })
+            .collect();
+        cache.upsert_items(&items)?;
+        Ok((dir, cache))
+    }
+
+    fn inbox(cache: &SqliteCache) -> Result<Vec<Item>> {
+        cache.get_items(&StreamId("mail:inbox".to_string()), None)
+    }
+
+    fn next_id(triage: &Triage, cache: &SqliteCache) -> Result<Option<String>> {
+        let view = triage.view(cache, inbox(cache)?)?;
+        Ok(view.item.map(|item| item.id.0))
+    }
+
+    #[test]
+    fn test_decisions_empty_the_inbox() -> Result<()> {
+        let (_dir, cache) = cache_with_items()?;
+        let triage = Triage::new();
+        let [a, b, c] = ["mail:1", "mail:2", "mail:3"].map(|id| ItemId(id.to_string()));
+
+        assert_eq!(triage.view(&cache, inbox(&cache)?)?.remaining, 3);
+        triage.apply(&cache, &a, TriageAction::Archive, None)?;
+        let snooze = triage.apply(&cache, &b, TriageAction::Snooze, None)?;
+        assert!(snooze.snoozed_until.unwrap() > Utc::now() + Duration::hours(23));
+        triage.apply(&cache, &c, TriageAction::Skip, None)?;
+
+        let view = triage.view(&cache, inbox(&cache)?)?;
+        assert!(view.item.is_none());
+        assert_eq!((view.remaining, view.processed), (0, 3));
+        assert_eq!(view.undo.unwrap().action, TriageAction::Skip);
+
+        let queued: Vec<OutboxAction> = cache.get_outbox()?.into_iter().map(|e| e.action).collect();
+        assert_eq!(queued, vec![OutboxAction::MarkRead, OutboxAction::Archive]);
+
+        // A new session brings skipped items back
+        triage.reset();
+        assert_eq!(next_id(&triage, &cache)?.as_deref(), Some("mail:3"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_restores_item_and_cancels_outbox() -> Result<()> {
+        let (_dir, cache) = cache_with_items()?;
+        let triage = Triage::new();
+        let a = ItemId("mail:1".to_string());
+        let b = ItemId("mail:2".to_string());
+
+        triage.apply(&cache, &a, TriageAction::Save, None)?;
+        triage.apply(&cache, &b, TriageAction::Archive, None)?;
+        assert_eq!(cache.get_outbox()?.len(), 4);
+
+        assert_eq!(triage.undo(&cache)?.unwrap().item_id, b);
+        assert_eq!(triage.undo(&cache)?.unwrap().item_id, a);
+        assert!(triage.undo(&cache)?.is_none());
+
+        assert!(cache.get_outbox()?.is_empty());
+        let item = cache.get_item(&a)?.unwrap();
+        assert!(!item.is_read && !item.is_saved);
+        // The item last undone comes up next
+        assert_eq!(next_id(&triage, &cache)?.as_deref(), Some("mail:1"));
+        assert_eq!(triage.view(&cache, inbox(&cache)?)?.remaining, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+//! Read-aloud queue.
+//!
+//! The [`SpeechQueue`] pipes item text through a configurable text-to-speech
+//! command, one item at a time, on a background task. Clients only enqueue
+//! items and return immediately, so the TUI keeps updating while an article
+//! or email is read.
+//!
+//! The command is run with `sh -c` and receives the text on stdin, which
+//! suits both `espeak-ng` and piper pipelines such as
+//! `piper --model voice.onnx --output-raw | aplay -r 22050 -f S16_LE -t raw -`.
+
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context, Result};
+use scryforge_provider_core::Item;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::config::TtsConfig;
+
+/// What the read-aloud queue is doing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpeechStatus {
+    /// Item currently being read
+    pub speaking: Option<String>,
+    /// Items waiting to be read, in order
+    pub queued: Vec<String>,
+}
+
+struct Utterance {
+    item_id: String,
+    text: String,
+}
+
+#[derive(Default)]
+struct QueueState {
+    speaking: Option<String>,
+    pending: VecDeque<Utterance>,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<QueueState>,
+    wake: Notify,
+}
+
+/// Reads items aloud in order through the configured TTS command.
+///
+/// Dropping the queue stops the background task and any running command.
+pub struct SpeechQueue {
+    shared: Arc<Shared>,
+    stop_tx: watch::Sender<u64>,
+    worker: JoinHandle<()>,
+    max_chars: usize,
+    max_queue: usize,
+}
+
+impl SpeechQueue {
+    /// Start a queue from config, or `None` if no TTS command is configured.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn from_config(config: &TtsConfig) -> Option<Self> {
+        let command = config.command.clone()?;
+        let shared = Arc::new(Shared::default());
+        let (stop_tx, stop_rx) = watch::channel(0);
+        let worker = tokio::spawn(run(command, Arc::clone(&shared), stop_rx));
+
+        Some(Self {
+            shared,
+            stop_tx,
+            worker,
+            max_chars: config.max_chars,
+            max_queue: config.max_queue,
+        })
+    }
+
+    /// Text that would be read for `item`: its title followed by the full
+    /// body, or `None` if the item has no body.
+    pub fn speech_text(&self, item: &Item) -> Option<String> {
+        let body = item.content.full_text()?;
+        let text = format!("{}.\n\n{}", item.title.trim_end_matches('.'), body);
+        Some(match text.char_indices().nth(self.max_chars) {
+            Some((end, _)) => text[..end].to_string(),
+            None => text,
+        })
+    }
+
+    /// Queue `text` for `item_id`, returning how many items are ahead of it.
+    pub fn enqueue(&self, item_id: &str, text: String) -> Result<usize> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.pending.len() >= self.max_queue {
+            bail!("Read-aloud queue is full ({} items)", self.max_queue);
+        }
+
+        let ahead = state.pending.len() + usize::from(state.speaking.is_some());
+        state.pending.push_back(Utterance {
+            item_id: item_id.to_string(),
+            text,
+        });
+        drop(state);
+
+        self.shared.wake.notify_one();
+        Ok(ahead)
+    }
+
+    /// Stop reading and clear the queue, returning how many items were dropped.
+    pub fn stop(&self) -> usize {
+        let mut state = self.shared.state.lock().unwrap();
+        let dropped = state.pending.len() + usize::from(state.speaking.is_some());
+        state.pending.clear();
+        drop(state);
+
+        self.stop_tx.send_modify(|generation| *generation += 1);
+        dropped
+    }
+
+    /// Current item and pending items.
+    pub fn status(&self) -> SpeechStatus {
+        let state = self.shared.state.lock().unwrap();
+        SpeechStatus {
+            speaking: state.speaking.clone(),
+            queued: state.pending.iter().map(|u| u.item_id.clone()).collect(),
+        }
+    }
+}
+
+impl Drop for SpeechQueue {
+    fn drop(&mut self) {
+        self.worker.abort();
+    }
+}
+
+/// Background task reading queued items one after another.
+async fn run(command: String, shared: Arc<Shared>, mut stop_rx: watch::Receiver<u64>) {
+    loop {
+        let next = {
+            let mut state = shared.state.lock().unwrap();
+            let next = state.pending.pop_front();
+            state.speaking = next.as_ref().map(|u| u.item_id.clone());
+            next
+        };
+        let Some(utterance) = next else {
+            shared.wake.notified().await;
+            continue;
+        };
+
+        // Only stops requested from now on apply to this item
+        stop_rx.borrow_and_update();
+        debug!("Reading {} aloud", utterance.item_id);
+        if let Err(e) = speak(&command, utterance.text, &mut stop_rx).await {
+            warn!("Failed to read {} aloud: {:#}", utterance.item_id, e);
+        }
+
+        shared.state.lock().unwrap().speaking = None;
+    }
+}
+
+/// Run the TTS command on `text`, killing it if a stop is requested.
+async fn speak(command: &str, text: String, stop_rx: &mut watch::Receiver<u64>) -> Result<()> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start TTS command: {}", command))?;
+
+    // Feed stdin separately so a slow reader can still be stopped; dropping
+    // the handle closes the pipe
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let writer = tokio::spawn(async move {
+        let _ = stdin.write_all(text.as_bytes()).await;
+    });
+
+    let result = tokio::select! {
+        status = child.wait() => match status {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(anyhow::anyhow!("TTS command exited with {}", status)),
+            Err(e) => Err(e.into()),
+        },
+        _ = stop_rx.changed() => child.kill().await.context("Failed to stop TTS command"),
+    };
+    writer.abort();
+    result
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scryforge_provider_core::{ItemContent, ItemId, StreamId};
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn queue(command: &str) -> SpeechQueue {
+        SpeechQueue::from_config(&TtsConfig {
+            command: Some(command.to_string()),
+            max_chars: 100,
+            max_queue: 2,
+        })
+        .unwrap()
+    }
+
+    fn item(content: ItemContent) -> Item {
+        Item {
+            id: ItemId("email:1".to_string()),
+            stream_id: StreamId("email:inbox".to_string()),
+            title: "Meeting notes.".to_string(),
+            content,
+            author: None,
+            published: None,
+            updated: None,
+            url: None,
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    async fn wait_idle(queue: &SpeechQueue) {
+        for _ in 0..200 {
+            if queue.status() == SpeechStatus::default() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("queue did not finish: {:?}", queue.status());
+    }
+
+    #[tokio::test]
+    async fn test_speech_text() {
+        let queue = queue("true");
+        let email = item(ItemContent::Email {
+            subject: "Meeting notes".to_string(),
+            body_text: Some("See you at ten.".to_string()),
+            body_html: None,
+            snippet: String::new(),
+        });
+        assert_eq!(
+            queue.speech_text(&email).as_deref(),
+            Some("Meeting notes.\n\nSee you at ten.")
+        );
+
+        let long = item(ItemContent::Text("word ".repeat(100)));
+        assert_eq!(queue.speech_text(&long).unwrap().chars().count(), 100);
+
+        assert_eq!(
+            queue.speech_text(&item(ItemContent::Generic { body: None })),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_items_are_read_in_order() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let out = temp_dir.path().join("spoken.txt");
+        let queue = queue(&format!(
+            "cat >> '{}'; echo >> '{}'",
+            out.display(),
+            out.display()
+        ));
+
+        queue.enqueue("a", "first".to_string())?;
+        queue.enqueue("b", "second".to_string())?;
+        wait_idle(&queue).await;
+
+        assert_eq!(std::fs::read_to_string(&out)?, "first\nsecond\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stop_clears_queue() -> Result<()> {
+        let queue = queue("sleep 30");
+
+        assert_eq!(queue.enqueue("a", "first".to_string())?, 0);
+        for _ in 0..200 {
+            if queue.status().speaking.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(queue.enqueue("b", "second".to_string())?, 1);
+        queue.enqueue("c", "third".to_string())?;
+        assert!(queue.enqueue("d", "fourth".to_string()).is_err());
+
+        assert_eq!(queue.stop(), 3);
+        wait_idle(&queue).await;
+        Ok(())
+    }
+}
@@ -14,7 +14,12 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error};
 
-use crate::cache::Cache;
+use crate::cache::{Cache, InteractionStats};
+use crate::config::RankingConfig;
+use crate::dedup::dedupe_items;
+use crate::mute::{MuteFilter, MUTED_REASON_KEY};
+use crate::ranking::Ranker;
+use crate::reading_time::reading_minutes;
 use crate::registry::ProviderRegistry;
 /// A saved item with tracking of which providers it's saved on.
 #[derive(Debug, Clone)]
@@ -199,6 +204,9 @@ impl UnifiedSavedView {
             ItemContent::Task { .. } => "task",
             ItemContent::Event { .. } => "event",
             ItemContent::Bookmark { .. } => "bookmark",
+            ItemContent::Image { .. } | ItemContent::Gallery { .. } => "image",
+            ItemContent::Message { .. } => "message",
+            ItemContent::Patch { .. } => "patch",
             ItemContent::Generic { .. } => "generic",
         };
 
@@ -240,6 +248,7 @@ impl UnifiedSavedView {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UnifiedFeedOptions {
     /// Sort order for items (default: newest first)
+    #[serde(default)]
     pub sort: FeedSortOrder,
     /// Maximum number of items to return
     pub limit: Option<u32>,
@@ -257,6 +266,16 @@ pub struct UnifiedFeedOptions {
     pub is_read: Option<bool>,
     /// Filter by saved status
     pub is_saved: Option<bool>,
+    /// Only items estimated to take at least this many minutes
+    pub min_minutes: Option<u32>,
+    /// Only items estimated to take at most this many minutes
+    pub max_minutes: Option<u32>,
+    /// Group cross-posted items (same URL or title) into one
+    #[serde(default)]
+    pub dedupe: bool,
+    /// Return only items hidden by the mute list, for review
+    #[serde(default)]
+    pub muted_only: bool,
 }
 
 /// Sort order for unified feeds.
@@ -269,6 +288,12 @@ pub enum FeedSortOrder {
     OldestFirst,
     /// Alphabetically by title
     Alphabetical,
+    /// Most interesting first, by engagement, interaction history, and age
+    Relevance,
+    /// Quickest to read, watch, or listen to first
+    ShortestFirst,
+    /// Longest to read, watch, or listen to first
+    LongestFirst,
 }
 
 /// A unified view that aggregates feed items from all providers.
@@ -277,12 +302,30 @@ pub enum FeedSortOrder {
 /// providers' feeds into a single, chronologically-sorted view.
 pub struct UnifiedFeedsView<C: Cache> {
     cache: Arc<C>,
+    ranking: RankingConfig,
+    mute: MuteFilter,
 }
 
 impl<C: Cache> UnifiedFeedsView<C> {
     /// Create a new unified feeds view with the given cache.
     pub fn new(cache: Arc<C>) -> Self {
-        Self { cache }
+        Self {
+            cache,
+            ranking: RankingConfig::default(),
+            mute: MuteFilter::default(),
+        }
+    }
+
+    /// Hide items matched by the given mute list.
+    pub fn with_mute_filter(mut self, mute: MuteFilter) -> Self {
+        self.mute = mute;
+        self
+    }
+
+    /// Use the given weights for [`FeedSortOrder::Relevance`].
+    pub fn with_ranking_config(mut self, ranking: RankingConfig) -> Self {
+        self.ranking = ranking;
+        self
     }
 
     /// Get all feed items from all providers, merged and sorted.
@@ -291,7 +334,7 @@ impl<C: Cache> UnifiedFeedsView<C> {
     /// 1. Fetches all streams from the cache
     /// 2. For each feed stream, fetches its items
     /// 3. Merges all items into a single list
-    /// 4. Applies filters and sorting
+    /// 4. Applies filters, optional deduplication, and sorting
     /// 5. Adds provider metadata to each item
     ///
     /// # Arguments
@@ -357,6 +400,12 @@ impl<C: Cache> UnifiedFeedsView<C> {
 
         // Apply filters
         all_items = self.apply_filters(all_items, &options);
+        all_items = self.apply_mute(all_items, options.muted_only);
+
+        // Group cross-posts before sorting so each group sorts by its representative
+        if options.dedupe {
+            all_items = dedupe_items(all_items);
+        }
 
         // Sort items
         self.sort_items(&mut all_items, options.sort);
@@ -394,6 +443,10 @@ impl<C: Cache> UnifiedFeedsView<C> {
                         ItemContent::Task { .. } => "Task",
                         ItemContent::Event { .. } => "Event",
                         ItemContent::Bookmark { .. } => "Bookmark",
+                        ItemContent::Image { .. } => "Image",
+                        ItemContent::Gallery { .. } => "Gallery",
+                        ItemContent::Message { .. } => "Message",
+                        ItemContent::Patch { .. } => "Patch",
                         ItemContent::Text(_) => "Text",
                         ItemContent::Markdown(_) => "Markdown",
                         ItemContent::Html(_) => "Html",
@@ -432,11 +485,42 @@ impl<C: Cache> UnifiedFeedsView<C> {
                     }
                 }
 
+                // Filter by estimated reading time
+                if options.min_minutes.is_some() || options.max_minutes.is_some() {
+                    let minutes = reading_minutes(item);
+                    if options.min_minutes.is_some_and(|min| minutes < min)
+                        || options.max_minutes.is_some_and(|max| minutes > max)
+                    {
+                        return false;
+                    }
+                }
+
                 true
             })
             .collect()
     }
 
+    /// Drop muted items, or with `muted_only` keep just those, annotated
+    /// with the reason they were muted.
+    fn apply_mute(&self, items: Vec<Item>, muted_only: bool) -> Vec<Item> {
+        if self.mute.is_empty() {
+            return if muted_only { Vec::new() } else { items };
+        }
+
+        items
+            .into_iter()
+            .filter_map(|mut item| match (self.mute.check(&item), muted_only) {
+                (Some(reason), true) => {
+                    item.metadata
+                        .insert(MUTED_REASON_KEY.to_string(), reason.to_string());
+                    Some(item)
+                }
+                (None, false) => Some(item),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Sort items according to the specified sort order.
     fn sort_items(&self, items: &mut [Item], sort: FeedSortOrder) {
         match sort {
@@ -467,6 +551,20 @@ impl<C: Cache> UnifiedFeedsView<C> {
             FeedSortOrder::Alphabetical => {
                 items.sort_by(|a, b| a.title.cmp(&b.title));
             }
+            FeedSortOrder::Relevance => {
+                // Without history, rank on engagement and recency alone
+                let stats = self.cache.get_interaction_stats().unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load interaction history: {}", e);
+                    InteractionStats::default()
+                });
+                Ranker::new(self.ranking.clone(), stats, Utc::now()).rank(items);
+            }
+            FeedSortOrder::ShortestFirst => {
+                items.sort_by_key(reading_minutes);
+            }
+            FeedSortOrder::LongestFirst => {
+                items.sort_by_key(|item| std::cmp::Reverse(reading_minutes(item)));
+            }
         }
     }
 
@@ -860,6 +958,7 @@ mod tests {
                 items_removed: 0,
                 errors: vec![],
                 duration_ms: 0,
+                streams: Vec::new(),
             })
         }
 
@@ -869,6 +968,7 @@ mod tests {
                 has_collections: false,
                 has_saved_items: true,
                 has_communities: false,
+                ..ProviderCapabilities::default()
             }
         }
 
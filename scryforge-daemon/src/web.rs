@@ -0,0 +1,538 @@
+//! Read-only web dashboard.
+//!
+//! A small embedded HTTP server (axum) that renders the unified inbox and
+//! per-feed item lists straight from the daemon cache, for quick access from
+//! a phone browser. Nothing here mutates state.
+//!
+//! # Routes
+//!
+//! - `GET /` - unified inbox across all feed streams
+//! - `GET /feed?stream=<stream id>` - items of a single stream
+//! - `GET /muted` - items hidden from the inbox by the mute list
+//!
+//! When `[[daemon.clients]]` are configured the dashboard requires HTTP Basic
+//! auth: any username, with a client token as the password. Browsers prompt
+//! for it natively.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::{Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use base64::Engine;
+use scryforge_provider_core::{Item, ItemContent, Stream, StreamId, StreamType};
+use serde::Deserialize;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::api::auth::find_client;
+use crate::cache::Cache;
+use crate::config::{ClientConfig, WebConfig};
+use crate::dedup::SOURCES_KEY;
+use crate::markup::escape_html;
+use crate::mute::{MuteFilter, MUTED_REASON_KEY};
+use crate::unified::{UnifiedFeedOptions, UnifiedFeedsView};
+
+/// Maximum characters of item content shown in a list entry.
+const PREVIEW_CHARS: usize = 200;
+
+/// Shared state for dashboard handlers.
+struct WebState<C: Cache> {
+    cache: Arc<C>,
+    clients: Vec<ClientConfig>,
+    items_per_page: u32,
+    mute: MuteFilter,
+}
+
+/// Start the dashboard server in a background task.
+///
+/// # Returns
+///
+/// The task handle (abort it to stop the server) and the bound address.
+pub async fn start_web_server<C: Cache + 'static>(
+    cache: Arc<C>,
+    config: &WebConfig,
+    clients: Vec<ClientConfig>,
+    mute: MuteFilter,
+) -> Result<(JoinHandle<()>, SocketAddr)> {
+    let app = router(cache, config, clients, mute);
+
+    let listener = TcpListener::bind(config.bind_address.as_str())
+        .await
+        .with_context(|| format!("Failed to bind web dashboard to {}", config.bind_address))?;
+    let addr = listener
+        .local_addr()
+        .context("Failed to get web dashboard address")?;
+
+    let handle = tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            error!("Web dashboard stopped: {}", e);
+        }
+    });
+
+    info!("Web dashboard listening on http://{}", addr);
+
+    Ok((handle, addr))
+}
+
+/// Build the dashboard router.
+fn router<C: Cache + 'static>(
+    cache: Arc<C>,
+    config: &WebConfig,
+    clients: Vec<ClientConfig>,
+    mute: MuteFilter,
+) -> Router {
+    let state = Arc::new(WebState {
+        cache,
+        clients,
+        items_per_page: config.items_per_page,
+        mute,
+    });
+
+    Router::new()
+        .route("/", get(inbox::<C>))
+        .route("/feed", get(feed::<C>))
+        .route("/muted", get(muted::<C>))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            require_auth::<C>,
+        ))
+        .with_state(state)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn inbox<C: Cache + 'static>(State(state): State<Arc<WebState<C>>>) -> Response {
+    unified_page(&state, "Inbox", false)
+}
+
+async fn muted<C: Cache + 'static>(State(state): State<Arc<WebState<C>>>) -> Response {
+    unified_page(&state, "Muted", true)
+}
+
+/// Render the unified feed, or only its muted items.
+fn unified_page<C: Cache + 'static>(
+    state: &WebState<C>,
+    title: &str,
+    muted_only: bool,
+) -> Response {
+    let view = UnifiedFeedsView::new(Arc::clone(&state.cache)).with_mute_filter(state.mute.clone());
+    let options = UnifiedFeedOptions {
+        limit: Some(state.items_per_page),
+        dedupe: true,
+        muted_only,
+        ..Default::default()
+    };
+
+    let items = match view.get_all_items(options) {
+        Ok(items) => items,
+        Err(e) => return server_error(&e.to_string()),
+    };
+    let streams = match state.cache.get_streams(None) {
+        Ok(streams) => streams,
+        Err(e) => return server_error(&e.to_string()),
+    };
+
+    Html(render_page(title, &streams, &items, true)).into_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedParams {
+    stream: String,
+}
+
+async fn feed<C: Cache + 'static>(
+    State(state): State<Arc<WebState<C>>>,
+    Query(params): Query<FeedParams>,
+) -> Response {
+    let streams = match state.cache.get_streams(None) {
+        Ok(streams) => streams,
+        Err(e) => return server_error(&e.to_string()),
+    };
+
+    let Some(stream) = streams.iter().find(|s| s.id.as_str() == params.stream) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html(render_message("Stream not found")),
+        )
+            .into_response();
+    };
+
+    let items = match state
+        .cache
+        .get_items(&StreamId(params.stream.clone()), Some(state.items_per_page))
+    {
+        Ok(items) => items,
+        Err(e) => return server_error(&e.to_string()),
+    };
+
+    Html(render_page(&stream.name, &streams, &items, false)).into_response()
+}
+
+/// Enforce HTTP Basic auth when client tokens are configured.
+async fn require_auth<C: Cache + 'static>(
+    State(state): State<Arc<WebState<C>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.clients.is_empty()
+        || basic_auth_token(request.headers())
+            .is_some_and(|token| find_client(&state.clients, &token).is_some())
+    {
+        return next.run(request).await;
+    }
+
+    warn!("Rejected unauthenticated dashboard request");
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Basic realm=\"scryforge\"")],
+        "Unauthorized\n",
+    )
+        .into_response()
+}
+
+/// Extract the password from a `Basic` authorization header.
+fn basic_auth_token(headers: &HeaderMap) -> Option<String> {
+    let encoded = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    let credentials = String::from_utf8(decoded).ok()?;
+    let (_user, password) = credentials.split_once(':')?;
+    Some(password.to_string())
+}
+
+fn server_error(message: &str) -> Response {
+    error!("Web dashboard error: {}", message);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Html(render_message("Failed to load items from the cache")),
+    )
+        .into_response()
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:0 auto;max-width:48rem;padding:0 1rem;color:#222}\
+nav{display:flex;flex-wrap:wrap;gap:.5rem;padding:.75rem 0;border-bottom:1px solid #ddd}\
+nav a{text-decoration:none;color:#36c}\
+ul{list-style:none;padding:0}\
+li{padding:.6rem 0;border-bottom:1px solid #eee}\
+.unread .title{font-weight:bold}\
+.meta{color:#777;font-size:.85rem}\
+.preview{color:#444;font-size:.9rem;margin:.25rem 0 0}";
+
+fn render_page(title: &str, streams: &[Stream], items: &[Item], show_stream: bool) -> String {
+    let mut html = page_header(title);
+
+    html.push_str("<nav><a href=\"/\">Inbox</a><a href=\"/muted\">Muted</a>");
+    for stream in streams
+        .iter()
+        .filter(|s| matches!(s.stream_type, StreamType::Feed))
+    {
+        html.push_str(&format!(
+            "<a href=\"/feed?stream={}\">{}</a>",
+            percent_encode(stream.id.as_str()),
+            escape_html(&stream.name)
+        ));
+    }
+    html.push_str("</nav>");
+
+    html.push_str(&format!("<h1>{}</h1>", escape_html(title)));
+
+    if items.is_empty() {
+        html.push_str("<p>No items.</p>");
+    } else {
+        html.push_str("<ul>");
+        for item in items {
+            html.push_str(&render_item(item, streams, show_stream));
+        }
+        html.push_str("</ul>");
+    }
+
+    html.push_str("</body></html>");
+    html
+}
+
+fn render_item(item: &Item, streams: &[Stream], show_stream: bool) -> String {
+    let title = escape_html(&item.title);
+    let title = match item.url {
+        Some(ref url) if is_http_url(url) => {
+            format!("<a href=\"{}\">{}</a>", escape_html(url), title)
+        }
+        _ => title,
+    };
+
+    let mut meta = Vec::new();
+    if show_stream {
+        let stream_name = streams
+            .iter()
+            .find(|s| s.id == item.stream_id)
+            .map(|s| s.name.as_str())
+            .unwrap_or(item.stream_id.as_str());
+        meta.push(escape_html(stream_name));
+    }
+    if let Some(reason) = item.metadata.get(MUTED_REASON_KEY) {
+        meta.push(format!("muted: {}", escape_html(reason)));
+    }
+    if let Some(sources) = item.metadata.get(SOURCES_KEY) {
+        meta.push(format!("via {}", escape_html(&sources.replace(',', ", "))));
+    }
+    if let Some(ref author) = item.author {
+        meta.push(escape_html(&author.name));
+    }
+    if let Some(published) = item.published {
+        meta.push(published.format("%Y-%m-%d %H:%M").to_string());
+    }
+
+    let preview = content_preview(&item.content)
+        .map(|text| format!("<p class=\"preview\">{}</p>", escape_html(&text)))
+        .unwrap_or_default();
+
+    format!(
+        "<li class=\"{}\"><div class=\"title\">{}</div><div class=\"meta\">{}</div>{}</li>",
+        if item.is_read { "read" } else { "unread" },
+        title,
+        meta.join(" · "),
+        preview
+    )
+}
+
+fn render_message(message: &str) -> String {
+    let mut html = page_header(message);
+    html.push_str(&format!(
+        "<p>{}</p><p><a href=\"/\">Back to inbox</a></p></body></html>",
+        escape_html(message)
+    ));
+    html
+}
+
+fn page_header(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+<title>{} - Scryforge</title><style>{}</style></head><body>",
+        escape_html(title),
+        STYLE
+    )
+}
+
+/// Short plain-text preview of an item's content.
+fn content_preview(content: &ItemContent) -> Option<String> {
+    let text = content.plain_text()?;
+
+    if text.chars().count() > PREVIEW_CHARS {
+        let truncated: String = text.chars().take(PREVIEW_CHARS).collect();
+        Some(format!("{}…", truncated.trim_end()))
+    } else {
+        Some(text)
+    }
+}
+
+/// Percent-encode a value for use in a query string.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Only link out to http(s) URLs, never `javascript:` and friends.
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::SqliteCache;
+    use axum::body::Body;
+    use scryforge_provider_core::ItemId;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn create_test_cache() -> Arc<SqliteCache> {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.db");
+        let cache = SqliteCache::open_at(&path).unwrap();
+        std::mem::forget(temp_dir);
+        Arc::new(cache)
+    }
+
+    fn seed(cache: &SqliteCache) {
+        let stream = Stream {
+            id: StreamId("rss:feed:news".to_string()),
+            name: "News".to_string(),
+            provider_id: "rss".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: None,
+            total_count: None,
+            last_updated: None,
+            metadata: HashMap::new(),
+        };
+        cache.upsert_streams(&[stream]).unwrap();
+
+        let item = Item {
+            id: ItemId("rss:1".to_string()),
+            stream_id: StreamId("rss:feed:news".to_string()),
+            title: "Hello <world>".to_string(),
+            content: ItemContent::Article {
+                summary: Some("<p>Summary text</p>".to_string()),
+                full_content: None,
+            },
+            author: None,
+            published: None,
+            updated: None,
+            url: Some("javascript:alert(1)".to_string()),
+            thumbnail_url: None,
+            is_read: false,
+            is_saved: false,
+            tags: vec![],
+            metadata: HashMap::new(),
+        };
+        cache.upsert_items(&[item]).unwrap();
+    }
+
+    async fn get(app: Router, uri: &str, auth: Option<&str>) -> (StatusCode, String) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(auth) = auth {
+            request = request.header(header::AUTHORIZATION, auth);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_inbox_lists_items_escaped() {
+        let cache = create_test_cache();
+        seed(&cache);
+        let app = router(cache, &WebConfig::default(), vec![], MuteFilter::default());
+
+        let (status, body) = get(app, "/", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Hello &lt;world&gt;"));
+        assert!(body.contains("Summary text"));
+        assert!(!body.contains("javascript:"));
+        assert!(body.contains("/feed?stream=rss:feed:news"));
+    }
+
+    #[tokio::test]
+    async fn test_feed_view_and_missing_stream() {
+        let cache = create_test_cache();
+        seed(&cache);
+
+        let app = router(
+            Arc::clone(&cache),
+            &WebConfig::default(),
+            vec![],
+            MuteFilter::default(),
+        );
+        let (status, body) = get(app, "/feed?stream=rss:feed:news", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("<h1>News</h1>"));
+
+        let app = router(cache, &WebConfig::default(), vec![], MuteFilter::default());
+        let (status, _) = get(app, "/feed?stream=nope", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_muted_items_move_to_review_page() {
+        let cache = create_test_cache();
+        seed(&cache);
+        let mute = MuteFilter::from_config(&crate::config::MuteConfig {
+            rules: crate::config::MuteRules {
+                keywords: vec!["hello".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let app = router(
+            Arc::clone(&cache),
+            &WebConfig::default(),
+            vec![],
+            mute.clone(),
+        );
+        let (_, body) = get(app, "/", None).await;
+        assert!(!body.contains("Hello &lt;world&gt;"));
+
+        let app = router(cache, &WebConfig::default(), vec![], mute);
+        let (status, body) = get(app, "/muted", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("Hello &lt;world&gt;"));
+        assert!(body.contains("muted: keyword &quot;hello&quot;"));
+    }
+
+    #[tokio::test]
+    async fn test_basic_auth_required_with_clients() {
+        let cache = create_test_cache();
+        let clients = vec![ClientConfig {
+            name: "phone".to_string(),
+            token: "secret".to_string(),
+        }];
+
+        let app = router(
+            Arc::clone(&cache),
+            &WebConfig::default(),
+            clients.clone(),
+            MuteFilter::default(),
+        );
+        let (status, _) = get(app, "/", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // "any:secret"
+        let app = router(cache, &WebConfig::default(), clients, MuteFilter::default());
+        let (status, _) = get(app, "/", Some("Basic YW55OnNlY3JldA==")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_content_preview_truncates() {
+        let long = "word ".repeat(100);
+        let preview = content_preview(&ItemContent::Text(long)).unwrap();
+        assert!(preview.chars().count() <= PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+
+        assert!(content_preview(&ItemContent::Generic { body: None }).is_none());
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("imap:INBOX/Work"), "imap:INBOX%2FWork");
+        assert_eq!(percent_encode("a b&c"), "a%20b%26c");
+    }
+}
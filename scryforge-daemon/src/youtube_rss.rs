@@ -0,0 +1,262 @@
+//! Moving YouTube subscriptions over to the RSS provider.
+//!
+//! Every channel publishes its uploads as a public feed, so following
+//! channels needs neither the YouTube Data API nor its daily quota. `scry
+//! youtube-to-rss` lists the account's subscriptions once through the API
+//! ([`YouTubeProvider::channel_feeds`]) and then either adds their feeds to
+//! `[providers.rss.settings]` in the config ([`import_feeds`]) or writes them
+//! out as OPML for another reader ([`render_opml`]). With the YouTube
+//! provider disabled afterwards, the API isn't called again.
+//!
+//! Channels can also stay with the YouTube provider as `rss_channels`
+//! ([`import_channels`]), as `scry takeout` does for subscriptions exported
+//! from Google Takeout.
+//!
+//! [`YouTubeProvider::channel_feeds`]: provider_youtube::YouTubeProvider::channel_feeds
+
+use anyhow::{bail, Context, Result};
+use provider_youtube::ChannelFeed;
+
+use crate::markup::escape_html;
+
+/// Sync interval of the provider sections the import has to add.
+const SYNC_INTERVAL_MINUTES: i64 = 30;
+
+/// Outcome of [`import_feeds`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedImport {
+    /// Feeds added to the RSS provider
+    pub added: usize,
+    /// Feeds the RSS provider already followed
+    pub already_followed: usize,
+    /// Whether the YouTube provider was disabled
+    pub youtube_disabled: bool,
+}
+
+/// Add channel feeds to `[providers.rss.settings] feeds` in a config file,
+/// returning the new content.
+///
+/// The RSS provider is added and enabled if needed, and feeds it already
+/// follows are skipped. With `disable_youtube`, the YouTube provider is
+/// disabled so its API is no longer used. Comments in the file are not kept.
+pub fn import_feeds(
+    content: &str,
+    feeds: &[ChannelFeed],
+    disable_youtube: bool,
+) -> Result<(String, FeedImport)> {
+    let mut config: toml::Table = toml::from_str(content).context("Failed to parse config")?;
+    let mut import = FeedImport::default();
+
+    let providers = table_entry(&mut config, "providers")?;
+    let rss = table_entry(providers, "rss")?;
+    rss.insert("enabled".to_string(), toml::Value::Boolean(true));
+    rss.entry("sync_interval_minutes")
+        .or_insert(toml::Value::Integer(SYNC_INTERVAL_MINUTES));
+    let settings = table_entry(rss, "settings")?;
+    let Some(followed) = settings
+        .entry("feeds")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+    else {
+        bail!("providers.rss.settings.feeds is not a list");
+    };
+    for feed in feeds {
+        if followed.iter().any(|url| url.as_str() == Some(&feed.url)) {
+            import.already_followed += 1;
+        } else {
+            followed.push(toml::Value::String(feed.url.clone()));
+            import.added += 1;
+        }
+    }
+
+    if disable_youtube {
+        let youtube = table_entry(providers, "youtube")?;
+        youtube.insert("enabled".to_string(), toml::Value::Boolean(false));
+        youtube
+            .entry("sync_interval_minutes")
+            .or_insert(toml::Value::Integer(SYNC_INTERVAL_MINUTES));
+        import.youtube_disabled = true;
+    }
+
+    Ok((toml::to_string_pretty(&config)?, import))
+}
+
+/// Add channel IDs to `[providers.youtube.settings] rss_channels` in a
+/// config file, returning the new content and how many were added.
+///
+/// The YouTube provider then follows the channels through their feeds
+/// whether or not the account is subscribed to them.
+pub fn import_channels(content: &str, channel_ids: &[String]) -> Result<(String, usize)> {
+    let mut config: toml::Table = toml::from_str(content).context("Failed to parse config")?;
+
+    let providers = table_entry(&mut config, "providers")?;
+    let youtube = table_entry(providers, "youtube")?;
+    youtube
+        .entry("enabled")
+        .or_insert(toml::Value::Boolean(true));
+    youtube
+        .entry("sync_interval_minutes")
+        .or_insert(toml::Value::Integer(SYNC_INTERVAL_MINUTES));
+    let settings = table_entry(youtube, "settings")?;
+    let Some(channels) = settings
+        .entry("rss_channels")
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+    else {
+        bail!("providers.youtube.settings.rss_channels is not a list");
+    };
+    let mut added = 0;
+    for channel_id in channel_ids {
+        if !channels.iter().any(|id| id.as_str() == Some(channel_id)) {
+            channels.push(toml::Value::String(channel_id.clone()));
+            added += 1;
+        }
+    }
+
+    Ok((toml::to_string_pretty(&config)?, added))
+}
+
+/// The table under `key`, created if missing.
+fn table_entry<'a>(table: &'a mut toml::Table, key: &str) -> Result<&'a mut toml::Table> {
+    table
+        .entry(key)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .with_context(|| format!("{} is not a table", key))
+}
+
+/// Render channel feeds as an OPML 2.0 subscription list.
+pub fn render_opml(feeds: &[ChannelFeed]) -> String {
+    let mut doc = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    doc.push_str("<opml version=\"2.0\">\n");
+    doc.push_str("  <head>\n    <title>YouTube subscriptions</title>\n  </head>\n");
+    doc.push_str("  <body>\n    <outline text=\"YouTube\" title=\"YouTube\">\n");
+    for feed in feeds {
+        let title = escape_html(&feed.title);
+        doc.push_str(&format!(
+            "      <outline type=\"rss\" text=\"{}\" title=\"{}\" xmlUrl=\"{}\" htmlUrl=\"https://www.youtube.com/channel/{}\"/>\n",
+            title,
+            title,
+            escape_html(&feed.url),
+            escape_html(&feed.channel_id)
+        ));
+    }
+    doc.push_str("    </outline>\n  </body>\n</opml>\n");
+    doc
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+
+    /// The `[providers.<id>]` section of a config file.
+    fn provider(content: &str, id: &str) -> Option<ProviderConfig> {
+        let config: toml::Table = toml::from_str(content).unwrap();
+        config["providers"]
+            .get(id)
+            .map(|section| section.clone().try_into().unwrap())
+    }
+
+    fn channel(id: &str, title: &str) -> ChannelFeed {
+        ChannelFeed {
+            channel_id: id.to_string(),
+            title: title.to_string(),
+            url: format!("https://www.youtube.com/feeds/videos.xml?channel_id={}", id),
+        }
+    }
+
+    #[test]
+    fn test_import_feeds_adds_rss_provider() -> Result<()> {
+        let content = "[providers.youtube]\nenabled = true\nsync_interval_minutes = 15\n";
+        let feeds = [channel("UCa", "Alpha"), channel("UCb", "Beta")];
+
+        let (content, import) = import_feeds(content, &feeds, true)?;
+        assert_eq!(
+            import,
+            FeedImport {
+                added: 2,
+                already_followed: 0,
+                youtube_disabled: true,
+            }
+        );
+
+        let rss = provider(&content, "rss").unwrap();
+        assert!(rss.enabled);
+        assert_eq!(rss.sync_interval_minutes, 30);
+        let urls: Vec<&str> = rss.settings["feeds"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|url| url.as_str())
+            .collect();
+        assert_eq!(urls, vec![feeds[0].url.as_str(), feeds[1].url.as_str()]);
+        let youtube = provider(&content, "youtube").unwrap();
+        assert!(!youtube.enabled);
+        assert_eq!(youtube.sync_interval_minutes, 15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_feeds_skips_followed_feeds() -> Result<()> {
+        let content = r#"
+[providers.rss]
+enabled = false
+sync_interval_minutes = 60
+
+[providers.rss.settings]
+feeds = ["https://www.youtube.com/feeds/videos.xml?channel_id=UCa", "https://example.com/feed.xml"]
+"#;
+        let feeds = [channel("UCa", "Alpha"), channel("UCb", "Beta")];
+
+        let (content, import) = import_feeds(content, &feeds, false)?;
+        assert_eq!(import.added, 1);
+        assert_eq!(import.already_followed, 1);
+        assert!(!import.youtube_disabled);
+
+        let rss = provider(&content, "rss").unwrap();
+        assert!(rss.enabled);
+        assert_eq!(rss.sync_interval_minutes, 60);
+        assert_eq!(rss.settings["feeds"].as_array().unwrap().len(), 3);
+        assert!(provider(&content, "youtube").is_none());
+
+        let err = import_feeds("[providers.rss.settings]\nfeeds = 3\n", &feeds, false);
+        assert!(err.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_channels() -> Result<()> {
+        let content = "[providers.youtube.settings]\nrss_channels = [\"UCa\"]\n";
+        let ids = ["UCa".to_string(), "UCb".to_string()];
+
+        let (content, added) = import_channels(content, &ids)?;
+        assert_eq!(added, 1);
+        let youtube = provider(&content, "youtube").unwrap();
+        assert!(youtube.enabled);
+        assert_eq!(
+            youtube.settings["rss_channels"],
+            toml::Value::try_from(["UCa", "UCb"])?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_opml() {
+        let opml = render_opml(&[channel("UCa", "Tom & Jerry")]);
+        assert!(opml.starts_with("<?xml"));
+        assert!(opml.contains(
+            "<outline type=\"rss\" text=\"Tom &amp; Jerry\" title=\"Tom &amp; Jerry\" \
+             xmlUrl=\"https://www.youtube.com/feeds/videos.xml?channel_id=UCa\" \
+             htmlUrl=\"https://www.youtube.com/channel/UCa\"/>"
+        ));
+        assert!(opml.trim_end().ends_with("</opml>"));
+    }
+}
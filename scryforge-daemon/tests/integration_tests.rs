@@ -47,6 +47,11 @@ fn create_test_config() -> Config {
         ProviderConfig {
             enabled: true,
             sync_interval_minutes: 1,
+            sync_timeout_secs: None,
+            conflict_strategy: Default::default(),
+            encrypt_at_rest: false,
+            budget: Default::default(),
+            proxy: None,
             settings: toml::Value::Table(toml::map::Map::new()),
         },
     );
@@ -364,6 +369,11 @@ async fn test_sync_manager_multiple_providers() -> Result<()> {
         ProviderConfig {
             enabled: true,
             sync_interval_minutes: 60,
+            sync_timeout_secs: None,
+            conflict_strategy: Default::default(),
+            encrypt_at_rest: false,
+            budget: Default::default(),
+            proxy: None,
             settings: toml::Value::Table(toml::map::Map::new()),
         },
     );
@@ -435,6 +445,29 @@ async fn test_jsonrpc_streams_list() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_jsonrpc_providers_list() -> Result<()> {
+    let (cache, sync_manager, _temp_dir) = {
+        let (_, cache, sync_manager, temp_dir) = setup_test_environment().await?;
+        (cache, sync_manager, temp_dir)
+    };
+
+    let (url, handle) = start_test_server(cache, sync_manager).await?;
+
+    let client = HttpClientBuilder::default().build(&url)?;
+
+    let result: Vec<scryforge_daemon::api::handlers::ProviderInfo> =
+        client.request("providers.list", rpc_params![]).await?;
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].id, "dummy");
+    assert!(result[0].capabilities.supports_pagination);
+    assert!(!result[0].capabilities.supports_write_actions);
+
+    handle.abort();
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_jsonrpc_items_list() -> Result<()> {
     let (cache, sync_manager, _temp_dir) = {
@@ -482,11 +515,12 @@ async fn test_jsonrpc_search_query() -> Result<()> {
     let client = HttpClientBuilder::default().build(&url)?;
 
     // Call search.query
-    let result: Vec<scryforge_provider_core::Item> = client
+    let result: Vec<scryforge_provider_core::SearchResult> = client
         .request("search.query", rpc_params!["Test Item", json!(null)])
         .await?;
 
     assert_eq!(result.len(), 5);
+    assert_eq!(result[0].title_parts()[0], ("Test", true));
 
     // Search with filters
     let filters = json!({
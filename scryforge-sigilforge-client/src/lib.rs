@@ -91,6 +91,15 @@ struct GetTokenResponse {
     expires_at: Option<String>,
 }
 
+/// An access token returned by Sigilforge.
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct TokenInfo {
+    pub token: String,
+    /// RFC 3339 expiry time, if Sigilforge reported one
+    pub expires_at: Option<String>,
+}
+
 #[cfg(unix)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ResolveResponse {
@@ -172,6 +181,15 @@ impl SigilforgeClient {
     /// - The account is not found
     /// - The RPC call fails
     pub async fn get_token(&self, service: &str, account: &str) -> Result<String> {
+        Ok(self.get_token_info(service, account).await?.token)
+    }
+
+    /// Get a fresh access token together with its expiry.
+    ///
+    /// # Errors
+    ///
+    /// Fails in the same cases as [`SigilforgeClient::get_token`].
+    pub async fn get_token_info(&self, service: &str, account: &str) -> Result<TokenInfo> {
         if !self.is_available() {
             return Err(SigilforgeError::Unavailable(format!(
                 "Socket not found at {:?}",
@@ -193,7 +211,10 @@ impl SigilforgeClient {
                 }
             })?;
 
-        Ok(response.token)
+        Ok(TokenInfo {
+            token: response.token,
+            expires_at: response.expires_at,
+        })
     }
 
     /// Resolve a credential reference to its actual value.
@@ -163,6 +163,26 @@ async fn test_client_fetch_token_success() {
     assert_eq!(token, "test_access_token_123");
 }
 
+#[tokio::test]
+async fn test_client_token_info_includes_expiry() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("test.sock");
+
+    let mut tokens = HashMap::new();
+    tokens.insert(
+        ("youtube".to_string(), "personal".to_string()),
+        "yt_token".to_string(),
+    );
+
+    let _server = start_mock_daemon(socket_path.clone(), tokens).await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let client = SigilforgeClient::new(socket_path);
+    let info = client.get_token_info("youtube", "personal").await.unwrap();
+    assert_eq!(info.token, "yt_token");
+    assert_eq!(info.expires_at.as_deref(), Some("2025-12-07T00:00:00Z"));
+}
+
 #[tokio::test]
 async fn test_client_token_not_found() {
     let temp_dir = TempDir::new().unwrap();
@@ -16,6 +16,18 @@
 //! - `:plugin list` - List all loaded plugins
 //! - `:plugin enable <id>` - Enable a plugin
 //! - `:plugin disable <id>` - Disable a plugin
+//! - `:focus [minutes]` - Start a focus session on the marked streams
+//! - `:focus stop` - End the focus session early
+//! - `:tag rename <old> <new>` - Rename a bookmark tag
+//! - `:tag merge <tag>... <into>` - Merge bookmark tags into one
+//! - `:tag delete <tag>` - Remove a tag from every bookmark
+//! - `:duplicates` - List bookmarks that look like copies of each other
+//! - `:duplicates merge` - Merge the selected bookmark's copies into it
+//! - `:queue` - Show the reading queue of saved items
+//! - `:queue <up|down|top|bottom|n>` - Move the selected item in the queue
+//! - `:queue done` - Take the selected item out of the queue
+//! - `:publish <rss|json|html> <path>` - Write the selected stream or folder
+//!   out as a feed or web page for sharing
 //! - Any text without `:` prefix is treated as a search query
 
 use crate::search::{parse_search_query, SearchQuery};
@@ -37,6 +49,18 @@ pub enum Command {
     Plugin(PluginCommand),
     /// Theme management commands
     Theme(ThemeCommand),
+    /// Focus session commands
+    Focus(FocusCommand),
+    /// Bookmark tag commands
+    Tag(TagCommand),
+    /// Duplicate bookmark commands
+    Duplicates(DuplicateCommand),
+    /// Reading queue commands
+    Queue(QueueCommand),
+    /// Publish the selected stream to a file in the daemon's export directory
+    Publish { format: PublishFormat, path: String },
+    /// Save a search query as an alert under a name
+    Alert { name: String, query: String },
 }
 
 /// Plugin management subcommands.
@@ -63,6 +87,76 @@ pub enum ThemeCommand {
     Set(String),
 }
 
+/// Focus session subcommands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusCommand {
+    /// Start a session, for the daemon's default length unless given
+    Start(Option<u32>),
+    /// End the running session
+    Stop,
+}
+
+/// Bookmark tag subcommands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagCommand {
+    /// Rename a tag
+    Rename { from: String, to: String },
+    /// Replace the source tags with the last one
+    Merge { sources: Vec<String>, into: String },
+    /// Remove a tag from every bookmark
+    Delete(String),
+}
+
+/// Duplicate bookmark subcommands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateCommand {
+    /// List the groups of duplicate bookmarks
+    Find,
+    /// Merge the other bookmarks in the selected one's group into it
+    Merge,
+}
+
+/// Reading queue subcommands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueCommand {
+    /// Show the reading queue
+    Show,
+    /// Move the selected item one place earlier
+    Up,
+    /// Move the selected item one place later
+    Down,
+    /// Move the selected item to the front
+    Top,
+    /// Move the selected item to the end
+    Bottom,
+    /// Move the selected item to a 1-based position
+    MoveTo(u32),
+    /// Take the selected item out of the queue
+    Done,
+}
+
+/// Formats the selected stream can be published in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishFormat {
+    /// An RSS 2.0 feed
+    Rss,
+    /// A JSON Feed
+    JsonFeed,
+    /// A static HTML page of links
+    Html,
+}
+
+impl PublishFormat {
+    /// The daemon's `export.items` name for the format.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PublishFormat::Rss => "rss",
+            PublishFormat::JsonFeed => "json_feed",
+            PublishFormat::Html => "html",
+        }
+    }
+}
+
 /// Parse a command or search query from omnibar input.
 ///
 /// If the input starts with `:`, it's parsed as a command.
@@ -135,10 +229,48 @@ fn parse_command_string(cmd_str: &str) -> Option<Command> {
         "help" | "h" => Some(Command::Help),
         "plugin" | "plugins" => parse_plugin_command(args),
         "theme" | "themes" => parse_theme_command(args),
+        "focus" => parse_focus_command(args),
+        "tag" | "tags" => parse_tag_command(args),
+        "duplicates" | "dupes" => match args {
+            [] => Some(Command::Duplicates(DuplicateCommand::Find)),
+            [arg] if arg.eq_ignore_ascii_case("merge") => {
+                Some(Command::Duplicates(DuplicateCommand::Merge))
+            }
+            _ => None,
+        },
+        "queue" => parse_queue_command(args),
+        "publish" => parse_publish_command(args),
+        "alert" => match args {
+            [name, query @ ..] if !query.is_empty() => Some(Command::Alert {
+                name: name.to_string(),
+                query: query.join(" "),
+            }),
+            _ => None,
+        },
         _ => None, // Unknown command
     }
 }
 
+/// Parse reading queue subcommands.
+fn parse_queue_command(args: &[&str]) -> Option<Command> {
+    let command = match args {
+        [] => QueueCommand::Show,
+        [arg] => match arg.to_lowercase().as_str() {
+            "up" => QueueCommand::Up,
+            "down" => QueueCommand::Down,
+            "top" => QueueCommand::Top,
+            "bottom" => QueueCommand::Bottom,
+            "done" => QueueCommand::Done,
+            position => match position.parse() {
+                Ok(n @ 1..) => QueueCommand::MoveTo(n),
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+    Some(Command::Queue(command))
+}
+
 /// Parse plugin subcommands.
 fn parse_plugin_command(args: &[&str]) -> Option<Command> {
     if args.is_empty() {
@@ -196,6 +328,55 @@ fn parse_theme_command(args: &[&str]) -> Option<Command> {
     }
 }
 
+/// Parse focus subcommands.
+fn parse_focus_command(args: &[&str]) -> Option<Command> {
+    match args {
+        [] => Some(Command::Focus(FocusCommand::Start(None))),
+        [arg] if arg.eq_ignore_ascii_case("stop") => Some(Command::Focus(FocusCommand::Stop)),
+        [minutes] => match minutes.parse() {
+            Ok(0) | Err(_) => None,
+            Ok(minutes) => Some(Command::Focus(FocusCommand::Start(Some(minutes)))),
+        },
+        _ => None,
+    }
+}
+
+/// Parse tag subcommands. Tag names keep their case.
+fn parse_tag_command(args: &[&str]) -> Option<Command> {
+    let (subcommand, subargs) = args.split_first()?;
+    let tag = match (subcommand.to_lowercase().as_str(), subargs) {
+        ("rename" | "mv", [from, to]) => TagCommand::Rename {
+            from: from.to_string(),
+            to: to.to_string(),
+        },
+        ("merge", [sources @ .., into]) if !sources.is_empty() => TagCommand::Merge {
+            sources: sources.iter().map(|s| s.to_string()).collect(),
+            into: into.to_string(),
+        },
+        ("delete" | "rm", [tag]) => TagCommand::Delete(tag.to_string()),
+        _ => return None,
+    };
+    Some(Command::Tag(tag))
+}
+
+/// Parse `:publish <format> <path>`; the path may contain spaces.
+fn parse_publish_command(args: &[&str]) -> Option<Command> {
+    let (format, path) = args.split_first()?;
+    let format = match format.to_lowercase().as_str() {
+        "rss" | "xml" => PublishFormat::Rss,
+        "json" | "jsonfeed" | "json_feed" => PublishFormat::JsonFeed,
+        "html" => PublishFormat::Html,
+        _ => return None,
+    };
+    if path.is_empty() {
+        return None;
+    }
+    Some(Command::Publish {
+        format,
+        path: path.join(" "),
+    })
+}
+
 /// Get help text for available commands.
 pub fn get_help_text() -> &'static str {
     "Available Commands:\n\
@@ -213,6 +394,32 @@ pub fn get_help_text() -> &'static str {
      :plugin info <id>   - Show plugin details\n\
      :plugin reload      - Reload plugins from disk\n\
      \n\
+     Focus Commands:\n\
+     :focus [minutes]    - Focus on the marked streams (default 25 minutes)\n\
+     :focus stop         - End the focus session early\n\
+     \n\
+     Tag Commands:\n\
+     :tag rename <old> <new> - Rename a bookmark tag\n\
+     :tag merge <tag>... <into> - Merge bookmark tags into one\n\
+     :tag delete <tag>   - Remove a tag from every bookmark\n\
+     \n\
+     Duplicate Bookmarks:\n\
+     :duplicates         - List bookmarks that look like copies\n\
+     :duplicates merge   - Merge the selected bookmark's copies into it\n\
+     \n\
+     Reading Queue:\n\
+     :queue              - Show saved items in reading order\n\
+     :queue up/down      - Move the selected item one place\n\
+     :queue top/bottom   - Move the selected item to the front or end\n\
+     :queue <n>          - Move the selected item to place n\n\
+     :queue done         - Take the selected item out of the queue\n\
+     \n\
+     Publishing:\n\
+     :publish <rss|json|html> <path> - Write the selected stream or folder as a feed or page\n\
+     \n\
+     Alerts:\n\
+     :alert <name> <query> - Alert on new items matching a search\n\
+     \n\
      Search Syntax:\n\
      \"exact phrase\"      - Search for exact phrase\n\
      title:keyword       - Search in title field\n\
@@ -227,7 +434,7 @@ pub fn get_help_text() -> &'static str {
      \n\
      Navigation:\n\
      h/l, Tab            - Move between panes\n\
-     j/k, ↑/↓            - Navigate lists\n\
+     j/k, ↑/↓            - Navigate lists, or scroll the focused preview\n\
      g/G                 - Jump to first/last\n\
      Enter               - Open item\n\
      /                   - Search\n\
@@ -270,6 +477,37 @@ pub fn get_command_suggestions(partial: &str) -> Vec<String> {
         (":plugin disable <id>", "Disable a plugin"),
         (":plugin info <id>", "Show plugin details"),
         (":plugin reload", "Reload plugins"),
+        (":focus", "Start a focus session"),
+        (":focus <minutes>", "Focus for this many minutes"),
+        (":focus stop", "End the focus session"),
+        (":tag rename <old> <new>", "Rename a bookmark tag"),
+        (":tag merge <tag>... <into>", "Merge bookmark tags"),
+        (":tag delete <tag>", "Delete a bookmark tag"),
+        (":duplicates", "List duplicate bookmarks"),
+        (
+            ":duplicates merge",
+            "Merge the selected bookmark's copies into it",
+        ),
+        (":queue", "Show the reading queue"),
+        (":queue up", "Move the selected item earlier in the queue"),
+        (":queue down", "Move the selected item later in the queue"),
+        (":queue top", "Move the selected item to the front"),
+        (":queue bottom", "Move the selected item to the end"),
+        (":queue <n>", "Move the selected item to place n"),
+        (":queue done", "Take the selected item out of the queue"),
+        (":publish rss <path>", "Publish the selected stream as RSS"),
+        (
+            ":publish json <path>",
+            "Publish the selected stream as a JSON Feed",
+        ),
+        (
+            ":publish html <path>",
+            "Publish the selected stream as a web page",
+        ),
+        (
+            ":alert <name> <query>",
+            "Alert on new items matching a search",
+        ),
     ];
 
     for (cmd, desc) in &commands {
@@ -340,6 +578,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_focus_commands() {
+        assert_eq!(
+            parse_command(":focus"),
+            Some(Command::Focus(FocusCommand::Start(None)))
+        );
+        assert_eq!(
+            parse_command(":focus 50"),
+            Some(Command::Focus(FocusCommand::Start(Some(50))))
+        );
+        assert_eq!(
+            parse_command(":focus stop"),
+            Some(Command::Focus(FocusCommand::Stop))
+        );
+        assert_eq!(parse_command(":focus 0"), None);
+        assert_eq!(parse_command(":focus soon"), None);
+    }
+
+    #[test]
+    fn test_parse_tag_commands() {
+        assert_eq!(
+            parse_command(":tag rename RustLang rust"),
+            Some(Command::Tag(TagCommand::Rename {
+                from: "RustLang".to_string(),
+                to: "rust".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_command(":tag merge rs rustlang rust"),
+            Some(Command::Tag(TagCommand::Merge {
+                sources: vec!["rs".to_string(), "rustlang".to_string()],
+                into: "rust".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_command(":tags rm old"),
+            Some(Command::Tag(TagCommand::Delete("old".to_string())))
+        );
+        assert_eq!(parse_command(":tag"), None);
+        assert_eq!(parse_command(":tag merge rust"), None);
+        assert_eq!(parse_command(":tag rename rust"), None);
+    }
+
+    #[test]
+    fn test_parse_duplicates_command() {
+        assert_eq!(
+            parse_command(":duplicates"),
+            Some(Command::Duplicates(DuplicateCommand::Find))
+        );
+        assert_eq!(
+            parse_command(":dupes Merge"),
+            Some(Command::Duplicates(DuplicateCommand::Merge))
+        );
+        assert_eq!(parse_command(":duplicates purge"), None);
+    }
+
+    #[test]
+    fn test_parse_queue_command() {
+        assert_eq!(
+            parse_command(":queue"),
+            Some(Command::Queue(QueueCommand::Show))
+        );
+        assert_eq!(
+            parse_command(":queue Top"),
+            Some(Command::Queue(QueueCommand::Top))
+        );
+        assert_eq!(
+            parse_command(":queue 3"),
+            Some(Command::Queue(QueueCommand::MoveTo(3)))
+        );
+        assert_eq!(
+            parse_command(":queue done"),
+            Some(Command::Queue(QueueCommand::Done))
+        );
+        assert_eq!(parse_command(":queue 0"), None);
+        assert_eq!(parse_command(":queue up 2"), None);
+        assert_eq!(parse_command(":queue later"), None);
+    }
+
+    #[test]
+    fn test_parse_publish_command() {
+        assert_eq!(
+            parse_command(":publish rss public/links.xml"),
+            Some(Command::Publish {
+                format: PublishFormat::Rss,
+                path: "public/links.xml".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_command(":publish JSON my links.json"),
+            Some(Command::Publish {
+                format: PublishFormat::JsonFeed,
+                path: "my links.json".to_string(),
+            })
+        );
+        assert_eq!(parse_command(":publish html"), None);
+        assert_eq!(parse_command(":publish atom /tmp/feed.xml"), None);
+    }
+
+    #[test]
+    fn test_parse_alert_command() {
+        assert_eq!(
+            parse_command(":alert scryforge scryforge -provider:github"),
+            Some(Command::Alert {
+                name: "scryforge".to_string(),
+                query: "scryforge -provider:github".to_string(),
+            })
+        );
+        assert_eq!(parse_command(":alert scryforge"), None);
+    }
+
     #[test]
     fn test_parse_unknown_command() {
         assert_eq!(parse_command(":unknown"), None);
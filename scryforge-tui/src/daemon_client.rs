@@ -1,13 +1,19 @@
 //! Client for communicating with the scryforge-daemon.
 //!
 //! This module provides an async client for fetching streams and items
-//! from the daemon via JSON-RPC over HTTP.
+//! from the daemon via JSON-RPC over HTTP, plus a WebSocket subscription
+//! for live daemon events.
 
 use anyhow::{Context, Result};
-use jsonrpsee::core::client::ClientT;
-use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use chrono::{DateTime, Utc};
+use jsonrpsee::core::client::{ClientT, SubscriptionClientT};
+use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
-use scryforge_provider_core::{Collection, Item, Stream};
+use jsonrpsee::ws_client::WsClientBuilder;
+use scryforge_provider_core::{
+    Collection, Item, ProviderCapabilities, SearchResult, Stream, TagCount,
+};
+use serde::Deserialize;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info};
 
@@ -17,18 +23,97 @@ use tracing::{debug, error, info};
 pub enum Command {
     /// Fetch all available streams
     FetchStreams,
+    /// Fetch loaded providers and their capabilities
+    FetchProviders,
     /// Fetch items for a specific stream
     FetchItems(String),
+    /// Search cached items and searchable providers
+    Search(String),
     /// Mark an item as read
     MarkItemRead(String),
     /// Mark an item as unread
     MarkItemUnread(String),
     /// Archive an item
     ArchiveItem(String),
+    /// Report an email as spam
+    MarkSpam(String),
+    /// Report an email as not spam
+    MarkNotSpam(String),
     /// Save an item
     SaveItem(String),
     /// Unsave an item
     UnsaveItem(String),
+    /// Pin an item to the top of its stream
+    PinItem(String),
+    /// Unpin an item
+    UnpinItem(String),
+    /// Fetch pinned items across all streams
+    FetchPinned,
+    /// Fetch items that matched saved search alerts
+    FetchAlerts,
+    /// Append an item to the configured notes file
+    SendToNotes(String),
+    /// Fetch the share targets configured in the daemon
+    FetchShareTargets,
+    /// Share an item to a configured target
+    ShareItem { item_id: String, target: String },
+    /// Accept or decline the meeting invitation in an email
+    RespondInvite { item_id: String, accept: bool },
+    /// Summarize a long item with the daemon's configured backend
+    SummarizeItem(String),
+    /// Fetch the transcript of a YouTube video
+    FetchTranscript(String),
+    /// Fetch items linked to an item by URL
+    FetchRelated(String),
+    /// Fetch what changed in an updated item, and mark the changes seen
+    FetchChanges(String),
+    /// Open an item's link with the daemon's configured opener
+    OpenItem(String),
+    /// Fetch the links in an item's body
+    FetchLinks(String),
+    /// Fetch the title of a page linked from an item
+    FetchLinkTitle { item_id: String, url: String },
+    /// Open a link found in an item with the daemon's configured opener
+    OpenLink { item_id: String, url: String },
+    /// Bookmark a link found in an item
+    BookmarkLink { item_id: String, url: String },
+    /// Queue an item to be read aloud
+    ReadAloud(String),
+    /// Stop reading aloud and clear the queue
+    StopReading,
+    /// Play a video or podcast item in the daemon's player
+    PlayItem(String),
+    /// Stop the player
+    StopPlayback,
+    /// Download an item's media, documents, or attachments
+    DownloadItem(String),
+    /// Fetch the daemon's downloads
+    FetchDownloads,
+    /// Cancel a download
+    CancelDownload(u64),
+    /// Retry a failed or cancelled download
+    RetryDownload(u64),
+    /// Fetch reading statistics for the last this many days
+    FetchReadingStats(u32),
+    /// Start a focus session on these streams, for the daemon's default
+    /// length unless minutes are given
+    StartFocus {
+        stream_ids: Vec<String>,
+        minutes: Option<u32>,
+    },
+    /// End the focus session early
+    StopFocus,
+    /// Fetch the running focus session, if any
+    FetchFocus,
+    /// Begin a new triage session on the unread unified inbox
+    StartTriage,
+    /// Take a triage decision on an item
+    TriageItem {
+        item_id: String,
+        action: TriageAction,
+    },
+    /// Take back the latest triage decision
+    UndoTriage,
     /// Fetch all collections
     FetchCollections,
     /// Add item to collection
@@ -43,10 +128,373 @@ pub enum Command {
     },
     /// Create a new collection
     CreateCollection(String),
+    /// Fetch a provider's tags with their counts
+    FetchTags(String),
+    /// Fetch a provider's items carrying a tag
+    FetchTagItems { provider_id: String, tag: String },
+    /// Rename a tag on every item carrying it
+    RenameTag {
+        provider_id: String,
+        from: String,
+        to: String,
+    },
+    /// Replace several tags with one
+    MergeTags {
+        provider_id: String,
+        sources: Vec<String>,
+        into: String,
+    },
+    /// Remove a tag from every item carrying it
+    DeleteTag { provider_id: String, tag: String },
+    /// Export a stream, or the collection behind it, as a feed or page
+    PublishStream {
+        stream_id: String,
+        format: &'static str,
+        path: String,
+    },
+    /// Save a search query as an alert
+    CreateAlert { name: String, query: String },
+    /// Find bookmarks that look like copies of each other
+    FetchDuplicates,
+    /// Fold duplicate bookmarks into the one to keep
+    MergeBookmarks {
+        keep: String,
+        duplicates: Vec<String>,
+    },
+    /// Fetch the reading queue
+    FetchQueue,
+    /// Move a queued item to a 1-based position
+    MoveQueued { item_id: String, position: u32 },
+    /// Record how far the preview of a queued item was scrolled
+    QueueProgress { item_id: String, offset: u32 },
+    /// Take a read item out of the reading queue
+    FinishQueued(String),
     /// Shutdown the client
     Shutdown,
 }
 
+/// Major version of the daemon API this client understands.
+const SUPPORTED_API_MAJOR: u64 = 1;
+
+/// Daemon version information returned by `rpc.version`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiVersionInfo {
+    pub api_version: String,
+    pub daemon_version: String,
+}
+
+impl ApiVersionInfo {
+    /// Whether this client can talk to a daemon reporting this version.
+    fn is_compatible(&self) -> bool {
+        self.api_version
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u64>().ok())
+            == Some(SUPPORTED_API_MAJOR)
+    }
+}
+
+/// Provider entry returned by `providers.list`; the display name is ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProviderInfo {
+    pub id: String,
+    pub capabilities: ProviderCapabilities,
+}
+
+/// How a related item is linked to the selected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// Both point at the same page
+    SameLink,
+    /// The selected item links to it
+    LinksTo,
+    /// It links to the selected item
+    LinkedFrom,
+    /// A relation this client doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// Entry returned by `items.related`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelatedItem {
+    pub relation: Relation,
+    pub item: Item,
+}
+
+/// One field that changed in an `ItemChange`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldChange {
+    /// Field name, such as "title", "body", or "due_date"
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// Word diff of a body, with `[-removed-]` and `{+added+}` words
+    #[serde(default)]
+    pub diff: Option<String>,
+}
+
+/// Entry returned by `items.changes`: what one update changed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemChange {
+    pub changed_at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Result of `items.open`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenedItem {
+    /// Content kind the command was chosen by, such as "video" or "pdf"
+    pub kind: String,
+    pub command: String,
+}
+
+/// A link in an item's body, from `items.links`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemLink {
+    pub url: String,
+    /// Anchor text or Markdown label; `None` for a bare URL
+    pub text: Option<String>,
+    /// The page's title, once fetched with `links.title`
+    pub title: Option<String>,
+}
+
+/// Result of `items.play`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Playback {
+    /// "video" or "audio"
+    pub kind: String,
+    /// Position playback resumed from, in seconds
+    pub start_secs: u64,
+}
+
+/// Where a download is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    Queued,
+    Downloading,
+    Completed,
+    Failed,
+    Cancelled,
+    /// A state this client doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// Entry returned by `downloads.list` and `items.download`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Download {
+    pub id: u64,
+    pub file_name: String,
+    pub state: DownloadState,
+    pub path: Option<String>,
+    pub bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Reading activity in one stream, from `stats.reading`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StreamReadingStats {
+    pub stream_id: String,
+    pub name: Option<String>,
+    pub received: u64,
+    pub read: u64,
+    pub saved: u64,
+    pub avg_secs_to_read: Option<u64>,
+    pub unread: u64,
+}
+
+/// Items received and read on one day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DailyReadingStats {
+    pub date: String,
+    pub received: u64,
+    pub read: u64,
+}
+
+/// Result of `stats.reading`; per-provider totals are ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReadingStats {
+    pub streams: Vec<StreamReadingStats>,
+    pub days: Vec<DailyReadingStats>,
+}
+
+/// A running focus session, from `focus.start` and `focus.status`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FocusSession {
+    pub stream_ids: Vec<String>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// What was processed in an ended focus session, from `focus.stop`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FocusReport {
+    pub completed: bool,
+    pub read: u64,
+    pub saved: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FocusStatus {
+    session: Option<FocusSession>,
+}
+
+/// A triage decision on an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageAction {
+    Archive,
+    Save,
+    /// Hide the item for the daemon's default snooze time
+    Snooze,
+    Skip,
+}
+
+impl TriageAction {
+    fn method(self) -> &'static str {
+        match self {
+            TriageAction::Archive => "triage.archive",
+            TriageAction::Save => "triage.save",
+            TriageAction::Snooze => "triage.snooze",
+            TriageAction::Skip => "triage.skip",
+        }
+    }
+}
+
+/// A triage decision the daemon can undo; which item it was on is ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriageStep {
+    pub action: TriageAction,
+}
+
+/// Where triage stands, returned by every `triage.*` call.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TriageView {
+    /// `None` once the inbox is empty
+    pub item: Option<Item>,
+    pub remaining: usize,
+    pub undo: Option<TriageStep>,
+}
+
+/// A configured share target, from `items.share_targets`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShareTarget {
+    pub id: String,
+    pub name: String,
+}
+
+/// What an export wrote, from `export.items`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub items_exported: usize,
+}
+
+/// Bookmarks that look like copies of each other, oldest first, from
+/// `bookmarks.duplicates`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DuplicateGroup {
+    /// `same_url` or `similar_title`
+    pub reason: String,
+    pub items: Vec<Item>,
+}
+
+/// The reading queue from `queue.list`: saved items in reading order.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReadingQueue {
+    pub entries: Vec<QueueEntry>,
+    /// The item to resume reading at; `None` when the queue is empty
+    pub resume: Option<String>,
+    pub total_minutes: u32,
+}
+
+/// A saved item in the [`ReadingQueue`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueueEntry {
+    pub item: Item,
+    /// 1-based place in the queue
+    pub position: u32,
+    /// Estimated reading time
+    pub minutes: u32,
+    /// Preview lines scrolled when the item was last left
+    pub offset: u32,
+}
+
+/// What sharing an item did, from `items.share`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharedItem {
+    pub text: String,
+    /// Whether the TUI should copy `text` to the clipboard
+    pub copy: bool,
+    pub message: String,
+}
+
+/// Live event pushed by the daemon over the `events.subscribe` subscription.
+///
+/// Mirrors the daemon's event payload; fields the TUI doesn't use are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonEvent {
+    /// A provider sync discovered new or updated items
+    NewItems {
+        provider_id: String,
+        items_added: u32,
+        items_updated: u32,
+    },
+    /// A provider sync started
+    SyncStarted { provider_id: String },
+    /// A provider sync finished
+    SyncFinished {
+        provider_id: String,
+        success: bool,
+        error: Option<String>,
+        /// Streams that failed, including while the rest synced
+        #[serde(default)]
+        failed_streams: Vec<String>,
+    },
+    /// An item action completed
+    ActionCompleted { item_id: String, action: String },
+    /// The player started by `items.play` exited
+    PlaybackFinished {
+        item_id: String,
+        exit_code: Option<i32>,
+        position_secs: Option<u64>,
+        finished: bool,
+    },
+    /// A running download made progress
+    DownloadProgress {
+        download_id: u64,
+        bytes: u64,
+        total_bytes: Option<u64>,
+    },
+    /// A download completed, failed, or was cancelled
+    DownloadFinished {
+        download_id: u64,
+        state: DownloadState,
+        path: Option<String>,
+        error: Option<String>,
+    },
+    /// A focus session ran out or was stopped
+    FocusEnded {
+        completed: bool,
+        read: u64,
+        saved: u64,
+    },
+    /// An item matched a saved search alert for the first time
+    AlertMatched {
+        search_id: i64,
+        name: String,
+        item_id: String,
+        title: String,
+    },
+    /// An event type this client doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
 /// Messages sent from the async client thread to the UI thread.
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -54,6 +502,17 @@ pub enum Message {
     StreamsLoaded(Vec<Stream>),
     /// Items were loaded successfully
     ItemsLoaded(Vec<Item>),
+    /// Results of a search, with what matched in each
+    SearchLoaded {
+        query: String,
+        results: Vec<SearchResult>,
+    },
+    /// Pinned items were loaded, most recently pinned first
+    PinnedLoaded(Vec<Item>),
+    /// Items that matched saved search alerts were loaded, most recent match first
+    AlertsLoaded(Vec<Item>),
+    /// Providers and their capabilities were loaded
+    ProvidersLoaded(Vec<ProviderInfo>),
     /// Collections were loaded successfully
     CollectionsLoaded(Vec<Collection>),
     /// Collection created successfully
@@ -62,6 +521,84 @@ pub enum Message {
     ItemAddedToCollection,
     /// Item removed from collection
     ItemRemovedFromCollection,
+    /// A provider's tags were loaded, most used first
+    TagsLoaded {
+        provider_id: String,
+        tags: Vec<TagCount>,
+    },
+    /// A tag was renamed, merged, or deleted on this many items
+    TagsEdited { provider_id: String, changed: u32 },
+    /// A stream was published
+    Published(ExportSummary),
+    /// A search was saved as an alert under this name
+    AlertCreated(String),
+    /// Duplicate bookmarks were found
+    DuplicatesLoaded(Vec<DuplicateGroup>),
+    /// This many bookmarks were merged into the one titled `kept`
+    BookmarksMerged { kept: String, merged: usize },
+    /// The reading queue was loaded or changed
+    QueueLoaded(ReadingQueue),
+    /// Item appended to the notes file at this path
+    SentToNotes(String),
+    /// The daemon's share targets were loaded
+    ShareTargetsLoaded(Vec<ShareTarget>),
+    /// An item was shared
+    ItemShared(SharedItem),
+    /// A meeting invitation was answered, with the provider's confirmation
+    InviteAnswered(String),
+    /// An item summary was generated (or loaded from the cache)
+    ItemSummarized { item_id: String, summary: String },
+    /// A video transcript was fetched (or loaded from the cache)
+    TranscriptFetched { item_id: String, transcript: String },
+    /// Items linked to an item were loaded
+    RelatedLoaded {
+        item_id: String,
+        related: Vec<RelatedItem>,
+    },
+    /// What changed in an updated item was loaded
+    ChangesLoaded {
+        item_id: String,
+        changes: Vec<ItemChange>,
+    },
+    /// An item's link was handed to an opener command
+    ItemOpened { item_id: String, opened: OpenedItem },
+    /// The links in an item's body were loaded
+    LinksLoaded {
+        item_id: String,
+        links: Vec<ItemLink>,
+    },
+    /// A linked page's title was fetched; `None` when it has none
+    LinkTitleFetched { url: String, title: Option<String> },
+    /// A link in an item was handed to an opener command
+    LinkOpened(OpenedItem),
+    /// A link was bookmarked under this bookmark item ID
+    LinkBookmarked(String),
+    /// Item queued for reading aloud behind this many others
+    ReadAloudQueued(usize),
+    /// Read aloud stopped
+    ReadingStopped,
+    /// The player started on an item
+    PlaybackStarted(Playback),
+    /// The player was stopped; false if nothing was playing
+    PlaybackStopped(bool),
+    /// Downloads were queued for an item
+    DownloadsQueued(Vec<Download>),
+    /// The daemon's downloads were loaded
+    DownloadsLoaded(Vec<Download>),
+    /// A download was retried
+    DownloadRetried(Download),
+    /// Reading statistics were loaded for the last this many days
+    ReadingStatsLoaded { days: u32, stats: ReadingStats },
+    /// A focus session started
+    FocusStarted(FocusSession),
+    /// The running focus session was loaded; `None` if there is none
+    FocusLoaded(Option<FocusSession>),
+    /// The focus session was stopped; `None` if none was running
+    FocusStopped(Option<FocusReport>),
+    /// Triage moved on to another item
+    TriageLoaded(Box<TriageView>),
+    /// A live event arrived from the daemon
+    Event(DaemonEvent),
     /// An error occurred
     Error(String),
     /// Client is ready
@@ -82,6 +619,7 @@ impl DaemonClient {
 
         // Build HTTP client
         let client = HttpClientBuilder::default()
+            .set_headers(auth_headers()?)
             .build(url)
             .context("Failed to build HTTP client")?;
 
@@ -90,6 +628,14 @@ impl DaemonClient {
         Ok(Self { client })
     }
 
+    /// Get the daemon's API version.
+    pub async fn api_version(&self) -> Result<ApiVersionInfo> {
+        self.client
+            .request("rpc.version", rpc_params![])
+            .await
+            .context("Failed to get daemon API version")
+    }
+
     /// List all available streams.
     pub async fn list_streams(&self) -> Result<Vec<Stream>> {
         debug!("Fetching streams from daemon");
@@ -104,6 +650,18 @@ impl DaemonClient {
         Ok(streams)
     }
 
+    /// List loaded providers with their capabilities.
+    pub async fn list_providers(&self) -> Result<Vec<ProviderInfo>> {
+        let providers: Vec<ProviderInfo> = self
+            .client
+            .request("providers.list", rpc_params![])
+            .await
+            .context("Failed to fetch providers")?;
+
+        debug!("Fetched {} providers", providers.len());
+        Ok(providers)
+    }
+
     /// List items for a specific stream.
     pub async fn list_items(&self, stream_id: &str) -> Result<Vec<Item>> {
         debug!("Fetching items for stream: {}", stream_id);
@@ -131,206 +689,1171 @@ impl DaemonClient {
         Ok(())
     }
 
-    /// Unsave an item.
-    pub async fn unsave_item(&self, item_id: &str) -> Result<()> {
-        debug!("Unsaving item: {}", item_id);
+    /// Append an item to the daemon's configured notes file.
+    ///
+    /// Returns the path of the notes file that was written.
+    pub async fn send_to_notes(&self, item_id: &str) -> Result<String> {
+        debug!("Sending item to notes: {}", item_id);
 
-        self.client
-            .request::<(), _>("items.unsave", rpc_params![item_id])
+        let path: String = self
+            .client
+            .request("items.send_to_notes", rpc_params![item_id])
             .await
-            .context("Failed to unsave item")?;
+            .context("Failed to send item to notes")?;
 
-        debug!("Unsaved item {}", item_id);
-        Ok(())
+        debug!("Sent item {} to {}", item_id, path);
+        Ok(path)
     }
 
-    /// Mark an item as read.
-    pub async fn mark_item_read(&self, item_id: &str) -> Result<()> {
-        debug!("Marking item as read: {}", item_id);
+    /// Accept or decline the meeting invitation in an email. Returns the
+    /// provider's confirmation.
+    pub async fn respond_invite(&self, item_id: &str, accept: bool) -> Result<String> {
+        debug!("Answering invitation {} (accept: {})", item_id, accept);
 
-        self.client
-            .request::<(), _>("items.mark_read", rpc_params![item_id])
+        let message: String = self
+            .client
+            .request("items.respond_invite", rpc_params![item_id, accept])
             .await
-            .context("Failed to mark item as read")?;
+            .context("Failed to answer invitation")?;
 
-        debug!("Marked item {} as read", item_id);
-        Ok(())
+        debug!("Answered invitation {}", item_id);
+        Ok(message)
     }
 
-    /// Mark an item as unread.
-    pub async fn mark_item_unread(&self, item_id: &str) -> Result<()> {
-        debug!("Marking item as unread: {}", item_id);
+    /// List the share targets configured in the daemon.
+    pub async fn list_share_targets(&self) -> Result<Vec<ShareTarget>> {
+        let targets: Vec<ShareTarget> = self
+            .client
+            .request("items.share_targets", rpc_params![])
+            .await
+            .context("Failed to list share targets")?;
 
-        self.client
-            .request::<(), _>("items.mark_unread", rpc_params![item_id])
+        debug!("Received {} share targets", targets.len());
+        Ok(targets)
+    }
+
+    /// Share an item to a configured target.
+    pub async fn share_item(&self, item_id: &str, target: &str) -> Result<SharedItem> {
+        debug!("Sharing item {} to {}", item_id, target);
+
+        let shared: SharedItem = self
+            .client
+            .request("items.share", rpc_params![item_id, target])
             .await
-            .context("Failed to mark item as unread")?;
+            .context("Failed to share item")?;
 
-        debug!("Marked item {} as unread", item_id);
-        Ok(())
+        Ok(shared)
     }
 
-    /// Archive an item.
-    pub async fn archive_item(&self, item_id: &str) -> Result<()> {
-        debug!("Archiving item: {}", item_id);
+    /// Summarize a long article, email, or video description.
+    pub async fn summarize_item(&self, item_id: &str) -> Result<String> {
+        debug!("Summarizing item: {}", item_id);
 
-        self.client
-            .request::<(), _>("items.archive", rpc_params![item_id])
+        let summary: String = self
+            .client
+            .request("items.summarize", rpc_params![item_id])
             .await
-            .context("Failed to archive item")?;
+            .context("Failed to summarize item")?;
 
-        debug!("Archived item {}", item_id);
-        Ok(())
+        Ok(summary)
     }
 
-    /// List all collections.
-    pub async fn list_collections(&self) -> Result<Vec<Collection>> {
-        debug!("Fetching collections from daemon");
+    /// Fetch the transcript of a YouTube video.
+    pub async fn get_transcript(&self, item_id: &str) -> Result<String> {
+        debug!("Fetching transcript: {}", item_id);
 
-        let collections: Vec<Collection> = self
+        let transcript: String = self
             .client
-            .request("collections.list", rpc_params![])
+            .request("items.transcript", rpc_params![item_id])
             .await
-            .context("Failed to fetch collections")?;
+            .context("Failed to fetch transcript")?;
 
-        debug!("Fetched {} collections", collections.len());
-        Ok(collections)
+        Ok(transcript)
     }
 
-    /// Add an item to a collection.
-    pub async fn add_to_collection(&self, collection_id: &str, item_id: &str) -> Result<()> {
-        debug!("Adding item {} to collection {}", item_id, collection_id);
+    /// Fetch cached items from any provider linked to an item by URL.
+    pub async fn related_items(&self, item_id: &str) -> Result<Vec<RelatedItem>> {
+        debug!("Fetching related items: {}", item_id);
 
-        self.client
-            .request::<(), _>("collections.add_item", rpc_params![collection_id, item_id])
+        let related: Vec<RelatedItem> = self
+            .client
+            .request("items.related", rpc_params![item_id])
             .await
-            .context("Failed to add item to collection")?;
+            .context("Failed to fetch related items")?;
 
-        debug!("Added item {} to collection {}", item_id, collection_id);
-        Ok(())
+        Ok(related)
     }
 
-    /// Remove an item from a collection.
-    pub async fn remove_from_collection(&self, collection_id: &str, item_id: &str) -> Result<()> {
-        debug!(
-            "Removing item {} from collection {}",
-            item_id, collection_id
-        );
+    /// List what changed in an item when syncs updated it, newest first.
+    pub async fn item_changes(&self, item_id: &str) -> Result<Vec<ItemChange>> {
+        debug!("Fetching item changes: {}", item_id);
 
-        self.client
-            .request::<(), _>(
-                "collections.remove_item",
-                rpc_params![collection_id, item_id],
-            )
+        let changes: Vec<ItemChange> = self
+            .client
+            .request("items.changes", rpc_params![item_id])
             .await
-            .context("Failed to remove item from collection")?;
+            .context("Failed to fetch item changes")?;
 
-        debug!("Removed item {} from collection {}", item_id, collection_id);
-        Ok(())
+        Ok(changes)
     }
 
-    /// Create a new collection.
-    pub async fn create_collection(&self, name: &str) -> Result<Collection> {
-        debug!("Creating collection: {}", name);
+    /// Clear an item's "updated" badge. Returns whether it had one.
+    pub async fn acknowledge_changes(&self, item_id: &str) -> Result<bool> {
+        let acknowledged: bool = self
+            .client
+            .request("items.acknowledge_changes", rpc_params![item_id])
+            .await
+            .context("Failed to acknowledge item changes")?;
 
-        let collection: Collection = self
+        Ok(acknowledged)
+    }
+
+    /// Open an item with the command configured for its content kind.
+    pub async fn open_item(&self, item_id: &str) -> Result<OpenedItem> {
+        debug!("Opening item: {}", item_id);
+
+        let opened: OpenedItem = self
             .client
-            .request("collections.create", rpc_params![name])
+            .request("items.open", rpc_params![item_id])
             .await
-            .context("Failed to create collection")?;
+            .context("Failed to open item")?;
 
-        debug!("Created collection: {} ({})", name, collection.id.0);
-        Ok(collection)
+        Ok(opened)
     }
-}
 
-/// Spawn the daemon client task.
-///
-/// This function spawns a background task that handles communication with the daemon.
-/// It receives commands from the UI thread via `cmd_rx` and sends responses back
-/// via `msg_tx`.
-///
-/// # Arguments
-///
-/// * `url` - URL of the daemon's HTTP endpoint (e.g., "http://127.0.0.1:3030")
-/// * `cmd_rx` - Receiver for commands from the UI thread
-/// * `msg_tx` - Sender for messages to the UI thread
-///
-/// # Returns
-///
-/// A join handle for the spawned task
-pub fn spawn_client_task(
-    url: String,
-    mut cmd_rx: mpsc::UnboundedReceiver<Command>,
-    msg_tx: mpsc::UnboundedSender<Message>,
-) -> tokio::task::JoinHandle<()> {
-    tokio::spawn(async move {
-        // Try to connect to the daemon
-        let client = match DaemonClient::connect(&url).await {
-            Ok(client) => {
-                let _ = msg_tx.send(Message::Ready);
-                client
-            }
-            Err(e) => {
-                error!("Failed to connect to daemon: {}", e);
-                let _ = msg_tx.send(Message::Error(format!(
-                    "Failed to connect to daemon: {}",
-                    e
-                )));
-                let _ = msg_tx.send(Message::Disconnected);
-                return;
-            }
-        };
+    /// List the links in an item's body.
+    pub async fn item_links(&self, item_id: &str) -> Result<Vec<ItemLink>> {
+        let links: Vec<ItemLink> = self
+            .client
+            .request("items.links", rpc_params![item_id])
+            .await
+            .context("Failed to list links")?;
 
-        // Process commands
-        while let Some(cmd) = cmd_rx.recv().await {
-            match cmd {
-                Command::FetchStreams => match client.list_streams().await {
-                    Ok(streams) => {
-                        let _ = msg_tx.send(Message::StreamsLoaded(streams));
-                    }
-                    Err(e) => {
-                        error!("Failed to fetch streams: {}", e);
-                        let _ =
-                            msg_tx.send(Message::Error(format!("Failed to fetch streams: {}", e)));
-                    }
-                },
-                Command::FetchItems(stream_id) => match client.list_items(&stream_id).await {
-                    Ok(items) => {
-                        let _ = msg_tx.send(Message::ItemsLoaded(items));
-                    }
-                    Err(e) => {
-                        error!("Failed to fetch items: {}", e);
-                        let _ =
-                            msg_tx.send(Message::Error(format!("Failed to fetch items: {}", e)));
-                    }
-                },
-                Command::SaveItem(item_id) => {
-                    match client.save_item(&item_id).await {
-                        Ok(()) => {
-                            // Success - no message needed, just log
-                            debug!("Successfully saved item {}", item_id);
-                        }
-                        Err(e) => {
-                            error!("Failed to save item: {}", e);
-                            let _ =
-                                msg_tx.send(Message::Error(format!("Failed to save item: {}", e)));
-                        }
+        debug!("Received {} links in {}", links.len(), item_id);
+        Ok(links)
+    }
+
+    /// Fetch the title of a page linked from an item.
+    pub async fn link_title(&self, item_id: &str, url: &str) -> Result<Option<String>> {
+        let title: Option<String> = self
+            .client
+            .request("links.title", rpc_params![item_id, url])
+            .await
+            .context("Failed to fetch link title")?;
+
+        Ok(title)
+    }
+
+    /// Open a link found in an item with the command for its content kind.
+    pub async fn open_link(&self, item_id: &str, url: &str) -> Result<OpenedItem> {
+        debug!("Opening link {} in {}", url, item_id);
+
+        let opened: OpenedItem = self
+            .client
+            .request("items.open_link", rpc_params![item_id, url])
+            .await
+            .context("Failed to open link")?;
+
+        Ok(opened)
+    }
+
+    /// Bookmark a link found in an item. Returns the bookmark's item ID.
+    pub async fn bookmark_link(&self, item_id: &str, url: &str) -> Result<String> {
+        debug!("Bookmarking link {} in {}", url, item_id);
+
+        let bookmark_id: String = self
+            .client
+            .request("items.bookmark_link", rpc_params![item_id, url])
+            .await
+            .context("Failed to bookmark link")?;
+
+        Ok(bookmark_id)
+    }
+
+    /// Queue an item to be read aloud by the daemon's TTS command.
+    ///
+    /// Returns the number of items ahead of it in the queue.
+    pub async fn read_aloud(&self, item_id: &str) -> Result<usize> {
+        debug!("Queueing item for read aloud: {}", item_id);
+
+        let ahead: usize = self
+            .client
+            .request("items.read_aloud", rpc_params![item_id])
+            .await
+            .context("Failed to read item aloud")?;
+
+        Ok(ahead)
+    }
+
+    /// Stop reading aloud and clear the queue.
+    pub async fn stop_reading(&self) -> Result<()> {
+        let _: usize = self
+            .client
+            .request("tts.stop", rpc_params![])
+            .await
+            .context("Failed to stop reading aloud")?;
+
+        Ok(())
+    }
+
+    /// Play a video or podcast item in the daemon's player, resuming where
+    /// it was last stopped.
+    pub async fn play_item(&self, item_id: &str) -> Result<Playback> {
+        debug!("Playing item: {}", item_id);
+
+        let playback: Playback = self
+            .client
+            .request("items.play", rpc_params![item_id])
+            .await
+            .context("Failed to play item")?;
+
+        Ok(playback)
+    }
+
+    /// Stop the player, returning whether anything was playing.
+    pub async fn stop_playback(&self) -> Result<bool> {
+        let stopped: bool = self
+            .client
+            .request("player.stop", rpc_params![])
+            .await
+            .context("Failed to stop playback")?;
+
+        Ok(stopped)
+    }
+
+    /// Download whatever an item has worth keeping, returning the queued
+    /// downloads.
+    pub async fn download_item(&self, item_id: &str) -> Result<Vec<Download>> {
+        debug!("Downloading item: {}", item_id);
+
+        let downloads: Vec<Download> = self
+            .client
+            .request("items.download", rpc_params![item_id])
+            .await
+            .context("Failed to download item")?;
+
+        Ok(downloads)
+    }
+
+    /// List queued, running, and finished downloads.
+    pub async fn list_downloads(&self) -> Result<Vec<Download>> {
+        let downloads: Vec<Download> = self
+            .client
+            .request("downloads.list", rpc_params![])
+            .await
+            .context("Failed to fetch downloads")?;
+
+        debug!("Fetched {} downloads", downloads.len());
+        Ok(downloads)
+    }
+
+    /// Cancel a download, returning whether it was still running.
+    pub async fn cancel_download(&self, download_id: u64) -> Result<bool> {
+        let cancelled: bool = self
+            .client
+            .request("downloads.cancel", rpc_params![download_id])
+            .await
+            .context("Failed to cancel download")?;
+
+        Ok(cancelled)
+    }
+
+    /// Retry a failed or cancelled download.
+    pub async fn retry_download(&self, download_id: u64) -> Result<Download> {
+        let download: Download = self
+            .client
+            .request("downloads.retry", rpc_params![download_id])
+            .await
+            .context("Failed to retry download")?;
+
+        Ok(download)
+    }
+
+    /// Get items received, read, and saved per stream over the last `days` days.
+    pub async fn reading_stats(&self, days: u32) -> Result<ReadingStats> {
+        let stats: ReadingStats = self
+            .client
+            .request("stats.reading", rpc_params![days])
+            .await
+            .context("Failed to fetch reading stats")?;
+
+        debug!("Fetched reading stats for {} streams", stats.streams.len());
+        Ok(stats)
+    }
+
+    /// Start a focus session on `stream_ids`.
+    pub async fn start_focus(
+        &self,
+        stream_ids: &[String],
+        minutes: Option<u32>,
+    ) -> Result<FocusSession> {
+        let session: FocusSession = self
+            .client
+            .request("focus.start", rpc_params![stream_ids, minutes])
+            .await
+            .context("Failed to start focus session")?;
+
+        Ok(session)
+    }
+
+    /// End the focus session early, returning its report if one was running.
+    pub async fn stop_focus(&self) -> Result<Option<FocusReport>> {
+        let report: Option<FocusReport> = self
+            .client
+            .request("focus.stop", rpc_params![])
+            .await
+            .context("Failed to stop focus session")?;
+
+        Ok(report)
+    }
+
+    /// Get the running focus session, if any.
+    pub async fn focus_session(&self) -> Result<Option<FocusSession>> {
+        let status: FocusStatus = self
+            .client
+            .request("focus.status", rpc_params![])
+            .await
+            .context("Failed to fetch focus session")?;
+
+        Ok(status.session)
+    }
+
+    /// Begin a new triage session and get its first item.
+    pub async fn start_triage(&self) -> Result<TriageView> {
+        let view: TriageView = self
+            .client
+            .request("triage.start", rpc_params![])
+            .await
+            .context("Failed to start triage")?;
+
+        debug!("Triage started with {} items", view.remaining);
+        Ok(view)
+    }
+
+    /// Take a triage decision on an item and get the next one.
+    pub async fn triage_item(&self, item_id: &str, action: TriageAction) -> Result<TriageView> {
+        let view: TriageView = self
+            .client
+            .request(action.method(), rpc_params![item_id])
+            .await
+            .context("Failed to triage item")?;
+
+        Ok(view)
+    }
+
+    /// Take back the latest triage decision and return to its item.
+    pub async fn undo_triage(&self) -> Result<TriageView> {
+        let view: TriageView = self
+            .client
+            .request("triage.undo", rpc_params![])
+            .await
+            .context("Failed to undo triage")?;
+
+        Ok(view)
+    }
+
+    /// Unsave an item.
+    pub async fn unsave_item(&self, item_id: &str) -> Result<()> {
+        debug!("Unsaving item: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.unsave", rpc_params![item_id])
+            .await
+            .context("Failed to unsave item")?;
+
+        debug!("Unsaved item {}", item_id);
+        Ok(())
+    }
+
+    /// Pin an item to the top of its stream.
+    pub async fn pin_item(&self, item_id: &str) -> Result<()> {
+        debug!("Pinning item: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.pin", rpc_params![item_id])
+            .await
+            .context("Failed to pin item")?;
+
+        debug!("Pinned item {}", item_id);
+        Ok(())
+    }
+
+    /// Unpin an item. Returns whether it was pinned.
+    pub async fn unpin_item(&self, item_id: &str) -> Result<bool> {
+        debug!("Unpinning item: {}", item_id);
+
+        let was_pinned: bool = self
+            .client
+            .request("items.unpin", rpc_params![item_id])
+            .await
+            .context("Failed to unpin item")?;
+
+        debug!("Unpinned item {}", item_id);
+        Ok(was_pinned)
+    }
+
+    /// Search cached items and searchable providers with the daemon's query
+    /// language.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let results: Vec<SearchResult> = self
+            .client
+            .request("search.query", rpc_params![query, None::<()>])
+            .await
+            .context("Failed to search")?;
+
+        debug!("Found {} results for {}", results.len(), query);
+        Ok(results)
+    }
+
+    /// List pinned items across all streams, most recently pinned first.
+    pub async fn pinned_items(&self) -> Result<Vec<Item>> {
+        let items: Vec<Item> = self
+            .client
+            .request("items.pinned", rpc_params![])
+            .await
+            .context("Failed to fetch pinned items")?;
+
+        debug!("Fetched {} pinned items", items.len());
+        Ok(items)
+    }
+
+    /// Save a search query as an alert under `name`, notifying on new matches.
+    pub async fn create_alert(&self, name: &str, query: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .client
+            .request(
+                "alerts.create",
+                rpc_params![name, query, None::<bool>, None::<u32>],
+            )
+            .await
+            .context("Failed to save alert")?;
+
+        debug!("Saved alert {}", name);
+        Ok(())
+    }
+
+    /// List items that matched saved search alerts, most recent match first.
+    pub async fn alert_items(&self) -> Result<Vec<Item>> {
+        let items: Vec<Item> = self
+            .client
+            .request("alerts.items", rpc_params![None::<i64>, None::<u32>])
+            .await
+            .context("Failed to fetch alert items")?;
+
+        debug!("Fetched {} alert items", items.len());
+        Ok(items)
+    }
+
+    /// Mark an item as read.
+    pub async fn mark_item_read(&self, item_id: &str) -> Result<()> {
+        debug!("Marking item as read: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.mark_read", rpc_params![item_id])
+            .await
+            .context("Failed to mark item as read")?;
+
+        debug!("Marked item {} as read", item_id);
+        Ok(())
+    }
+
+    /// Mark an item as unread.
+    pub async fn mark_item_unread(&self, item_id: &str) -> Result<()> {
+        debug!("Marking item as unread: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.mark_unread", rpc_params![item_id])
+            .await
+            .context("Failed to mark item as unread")?;
+
+        debug!("Marked item {} as unread", item_id);
+        Ok(())
+    }
+
+    /// Archive an item.
+    pub async fn archive_item(&self, item_id: &str) -> Result<()> {
+        debug!("Archiving item: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.archive", rpc_params![item_id])
+            .await
+            .context("Failed to archive item")?;
+
+        debug!("Archived item {}", item_id);
+        Ok(())
+    }
+
+    /// Report an email as spam, moving it to the account's Junk folder.
+    pub async fn mark_spam(&self, item_id: &str) -> Result<()> {
+        debug!("Marking item as spam: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.mark_spam", rpc_params![item_id])
+            .await
+            .context("Failed to mark item as spam")?;
+
+        debug!("Marked item {} as spam", item_id);
+        Ok(())
+    }
+
+    /// Report an email as not spam, moving it back to the inbox.
+    pub async fn mark_not_spam(&self, item_id: &str) -> Result<()> {
+        debug!("Marking item as not spam: {}", item_id);
+
+        self.client
+            .request::<(), _>("items.mark_not_spam", rpc_params![item_id])
+            .await
+            .context("Failed to mark item as not spam")?;
+
+        debug!("Marked item {} as not spam", item_id);
+        Ok(())
+    }
+
+    /// List all collections.
+    pub async fn list_collections(&self) -> Result<Vec<Collection>> {
+        debug!("Fetching collections from daemon");
+
+        let collections: Vec<Collection> = self
+            .client
+            .request("collections.list", rpc_params![])
+            .await
+            .context("Failed to fetch collections")?;
+
+        debug!("Fetched {} collections", collections.len());
+        Ok(collections)
+    }
+
+    /// Add an item to a collection.
+    pub async fn add_to_collection(&self, collection_id: &str, item_id: &str) -> Result<()> {
+        debug!("Adding item {} to collection {}", item_id, collection_id);
+
+        self.client
+            .request::<(), _>("collections.add_item", rpc_params![collection_id, item_id])
+            .await
+            .context("Failed to add item to collection")?;
+
+        debug!("Added item {} to collection {}", item_id, collection_id);
+        Ok(())
+    }
+
+    /// Remove an item from a collection.
+    pub async fn remove_from_collection(&self, collection_id: &str, item_id: &str) -> Result<()> {
+        debug!(
+            "Removing item {} from collection {}",
+            item_id, collection_id
+        );
+
+        self.client
+            .request::<(), _>(
+                "collections.remove_item",
+                rpc_params![collection_id, item_id],
+            )
+            .await
+            .context("Failed to remove item from collection")?;
+
+        debug!("Removed item {} from collection {}", item_id, collection_id);
+        Ok(())
+    }
+
+    /// Create a new collection.
+    pub async fn create_collection(&self, name: &str) -> Result<Collection> {
+        debug!("Creating collection: {}", name);
+
+        let collection: Collection = self
+            .client
+            .request("collections.create", rpc_params![name])
+            .await
+            .context("Failed to create collection")?;
+
+        debug!("Created collection: {} ({})", name, collection.id.0);
+        Ok(collection)
+    }
+
+    /// List a provider's tags, most used first.
+    pub async fn list_tags(&self, provider_id: &str) -> Result<Vec<TagCount>> {
+        let tags: Vec<TagCount> = self
+            .client
+            .request("tags.list", rpc_params![provider_id])
+            .await
+            .context("Failed to fetch tags")?;
+
+        debug!("Fetched {} tags from {}", tags.len(), provider_id);
+        Ok(tags)
+    }
+
+    /// Get a provider's items carrying a tag.
+    pub async fn tagged_items(&self, provider_id: &str, tag: &str) -> Result<Vec<Item>> {
+        self.client
+            .request("tags.items", rpc_params![provider_id, tag])
+            .await
+            .context("Failed to fetch tagged items")
+    }
+
+    /// Rename a tag. Returns how many items changed.
+    pub async fn rename_tag(&self, provider_id: &str, from: &str, to: &str) -> Result<u32> {
+        self.client
+            .request("tags.rename", rpc_params![provider_id, from, to])
+            .await
+            .context("Failed to rename tag")
+    }
+
+    /// Replace `sources` with `into`. Returns how many items changed.
+    pub async fn merge_tags(
+        &self,
+        provider_id: &str,
+        sources: &[String],
+        into: &str,
+    ) -> Result<u32> {
+        self.client
+            .request("tags.merge", rpc_params![provider_id, sources, into])
+            .await
+            .context("Failed to merge tags")
+    }
+
+    /// Export a stream as `format`, reading a collection stream such as
+    /// `bookmarks:collection:<folder>` from its provider. `path` is inside
+    /// the daemon's export directory; publishing again replaces the file.
+    pub async fn publish_stream(
+        &self,
+        stream_id: &str,
+        format: &str,
+        path: &str,
+    ) -> Result<ExportSummary> {
+        let source = match stream_id.split_once(":collection:") {
+            Some((provider_id, collection_id)) => serde_json::json!({
+                "kind": "collection",
+                "provider_id": provider_id,
+                "collection_id": collection_id,
+            }),
+            None => serde_json::json!({ "kind": "stream", "stream_id": stream_id }),
+        };
+        self.client
+            .request("export.items", rpc_params![source, format, path, true])
+            .await
+            .context("Failed to publish stream")
+    }
+
+    /// Delete a tag. Returns how many items changed.
+    pub async fn delete_tag(&self, provider_id: &str, tag: &str) -> Result<u32> {
+        self.client
+            .request("tags.delete", rpc_params![provider_id, tag])
+            .await
+            .context("Failed to delete tag")
+    }
+
+    /// Find bookmarks that look like copies of each other.
+    pub async fn bookmark_duplicates(&self) -> Result<Vec<DuplicateGroup>> {
+        self.client
+            .request("bookmarks.duplicates", rpc_params![])
+            .await
+            .context("Failed to find duplicate bookmarks")
+    }
+
+    /// Fold `duplicates` into the bookmark `keep`. Returns the kept bookmark.
+    pub async fn merge_bookmarks(&self, keep: &str, duplicates: &[String]) -> Result<Item> {
+        self.client
+            .request("bookmarks.merge", rpc_params![keep, duplicates])
+            .await
+            .context("Failed to merge bookmarks")
+    }
+
+    /// Get the reading queue.
+    pub async fn reading_queue(&self) -> Result<ReadingQueue> {
+        self.client
+            .request("queue.list", rpc_params![])
+            .await
+            .context("Failed to fetch reading queue")
+    }
+
+    /// Move a queued item to `position` (1-based). Returns the new queue.
+    pub async fn move_queued(&self, item_id: &str, position: u32) -> Result<ReadingQueue> {
+        self.client
+            .request("queue.move", rpc_params![item_id, position])
+            .await
+            .context("Failed to move item in reading queue")
+    }
+
+    /// Record where the reader left off in a queued item.
+    pub async fn queue_progress(&self, item_id: &str, offset: u32) -> Result<()> {
+        self.client
+            .request::<(), _>("queue.progress", rpc_params![item_id, offset])
+            .await
+            .context("Failed to record reading position")
+    }
+
+    /// Take an item out of the reading queue. Returns the new queue.
+    pub async fn finish_queued(&self, item_id: &str) -> Result<ReadingQueue> {
+        self.client
+            .request("queue.finish", rpc_params![item_id])
+            .await
+            .context("Failed to finish queued item")
+    }
+}
+
+/// Spawn the daemon client task.
+///
+/// This function spawns a background task that handles communication with the daemon.
+/// It receives commands from the UI thread via `cmd_rx` and sends responses back
+/// via `msg_tx`.
+///
+/// # Arguments
+///
+/// * `url` - URL of the daemon's HTTP endpoint (e.g., "http://127.0.0.1:3030")
+/// * `cmd_rx` - Receiver for commands from the UI thread
+/// * `msg_tx` - Sender for messages to the UI thread
+///
+/// # Returns
+///
+/// A join handle for the spawned task
+pub fn spawn_client_task(
+    url: String,
+    mut cmd_rx: mpsc::UnboundedReceiver<Command>,
+    msg_tx: mpsc::UnboundedSender<Message>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        // Try to connect to the daemon
+        let client = match DaemonClient::connect(&url).await {
+            Ok(client) => {
+                match client.api_version().await {
+                    Ok(version) if !version.is_compatible() => {
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Daemon API {} (daemon v{}) is not supported by this client",
+                            version.api_version, version.daemon_version
+                        )));
+                    }
+                    Ok(version) => debug!("Daemon API version {}", version.api_version),
+                    Err(e) => debug!("Could not determine daemon API version: {}", e),
+                }
+                let _ = msg_tx.send(Message::Ready);
+                client
+            }
+            Err(e) => {
+                error!("Failed to connect to daemon: {}", e);
+                let _ = msg_tx.send(Message::Error(format!(
+                    "Failed to connect to daemon: {}",
+                    e
+                )));
+                let _ = msg_tx.send(Message::Disconnected);
+                return;
+            }
+        };
+
+        // Process commands
+        while let Some(cmd) = cmd_rx.recv().await {
+            match cmd {
+                Command::FetchStreams => match client.list_streams().await {
+                    Ok(streams) => {
+                        let _ = msg_tx.send(Message::StreamsLoaded(streams));
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch streams: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to fetch streams: {}", e)));
+                    }
+                },
+                Command::FetchProviders => match client.list_providers().await {
+                    Ok(providers) => {
+                        let _ = msg_tx.send(Message::ProvidersLoaded(providers));
+                    }
+                    Err(e) => {
+                        // Older daemons lack providers.list; capabilities are only hints
+                        debug!("Failed to fetch providers: {}", e);
+                    }
+                },
+                Command::FetchItems(stream_id) => match client.list_items(&stream_id).await {
+                    Ok(items) => {
+                        let _ = msg_tx.send(Message::ItemsLoaded(items));
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch items: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to fetch items: {}", e)));
+                    }
+                },
+                Command::SaveItem(item_id) => {
+                    match client.save_item(&item_id).await {
+                        Ok(()) => {
+                            // Success - no message needed, just log
+                            debug!("Successfully saved item {}", item_id);
+                        }
+                        Err(e) => {
+                            error!("Failed to save item: {}", e);
+                            let _ =
+                                msg_tx.send(Message::Error(format!("Failed to save item: {}", e)));
+                        }
+                    }
+                }
+                Command::UnsaveItem(item_id) => {
+                    match client.unsave_item(&item_id).await {
+                        Ok(()) => {
+                            // Success - no message needed, just log
+                            debug!("Successfully unsaved item {}", item_id);
+                        }
+                        Err(e) => {
+                            error!("Failed to unsave item: {}", e);
+                            let _ = msg_tx
+                                .send(Message::Error(format!("Failed to unsave item: {}", e)));
+                        }
+                    }
+                }
+                Command::PinItem(item_id) => {
+                    if let Err(e) = client.pin_item(&item_id).await {
+                        error!("Failed to pin item: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Failed to pin item: {}", e)));
+                    }
+                    // Reload either way so the UI shows what the daemon kept
+                    if let Ok(items) = client.pinned_items().await {
+                        let _ = msg_tx.send(Message::PinnedLoaded(items));
+                    }
+                }
+                Command::UnpinItem(item_id) => {
+                    if let Err(e) = client.unpin_item(&item_id).await {
+                        error!("Failed to unpin item: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Failed to unpin item: {}", e)));
+                    }
+                    if let Ok(items) = client.pinned_items().await {
+                        let _ = msg_tx.send(Message::PinnedLoaded(items));
+                    }
+                }
+                Command::Search(query) => match client.search(&query).await {
+                    Ok(results) => {
+                        let _ = msg_tx.send(Message::SearchLoaded { query, results });
+                    }
+                    Err(e) => {
+                        error!("Search failed: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Search failed: {}", e)));
+                    }
+                },
+                Command::FetchPinned => match client.pinned_items().await {
+                    Ok(items) => {
+                        let _ = msg_tx.send(Message::PinnedLoaded(items));
+                    }
+                    Err(e) => {
+                        // Daemons older than pins don't have items.pinned
+                        debug!("Failed to fetch pinned items: {}", e);
+                    }
+                },
+                Command::FetchAlerts => match client.alert_items().await {
+                    Ok(items) => {
+                        let _ = msg_tx.send(Message::AlertsLoaded(items));
+                    }
+                    Err(e) => {
+                        // Daemons older than alerts don't have alerts.items
+                        debug!("Failed to fetch alert items: {}", e);
+                    }
+                },
+                Command::SendToNotes(item_id) => match client.send_to_notes(&item_id).await {
+                    Ok(path) => {
+                        let _ = msg_tx.send(Message::SentToNotes(path));
+                    }
+                    Err(e) => {
+                        error!("Failed to send item to notes: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to send item to notes: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::FetchShareTargets => match client.list_share_targets().await {
+                    Ok(targets) => {
+                        let _ = msg_tx.send(Message::ShareTargetsLoaded(targets));
+                    }
+                    Err(e) => {
+                        error!("Failed to list share targets: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to list share targets: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::ShareItem { item_id, target } => {
+                    match client.share_item(&item_id, &target).await {
+                        Ok(shared) => {
+                            let _ = msg_tx.send(Message::ItemShared(shared));
+                        }
+                        Err(e) => {
+                            error!("Failed to share item: {}", e);
+                            let _ =
+                                msg_tx.send(Message::Error(format!("Failed to share item: {}", e)));
+                        }
+                    }
+                }
+                Command::RespondInvite { item_id, accept } => {
+                    match client.respond_invite(&item_id, accept).await {
+                        Ok(message) => {
+                            let _ = msg_tx.send(Message::InviteAnswered(message));
+                        }
+                        Err(e) => {
+                            error!("Failed to answer invitation: {}", e);
+                            let _ = msg_tx.send(Message::Error(format!(
+                                "Failed to answer invitation: {}",
+                                e
+                            )));
+                        }
+                    }
+                }
+                Command::SummarizeItem(item_id) => match client.summarize_item(&item_id).await {
+                    Ok(summary) => {
+                        let _ = msg_tx.send(Message::ItemSummarized { item_id, summary });
+                    }
+                    Err(e) => {
+                        error!("Failed to summarize item: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to summarize item: {}", e)));
+                    }
+                },
+                Command::FetchTranscript(item_id) => match client.get_transcript(&item_id).await {
+                    Ok(transcript) => {
+                        let _ = msg_tx.send(Message::TranscriptFetched {
+                            item_id,
+                            transcript,
+                        });
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch transcript: {}", e);
+                        let _ = msg_tx
+                            .send(Message::Error(format!("Failed to fetch transcript: {}", e)));
+                    }
+                },
+                Command::FetchRelated(item_id) => match client.related_items(&item_id).await {
+                    Ok(related) => {
+                        let _ = msg_tx.send(Message::RelatedLoaded { item_id, related });
+                    }
+                    Err(e) => {
+                        // Older daemons lack items.related; the section just stays empty
+                        debug!("Failed to fetch related items: {}", e);
+                    }
+                },
+                Command::FetchChanges(item_id) => match client.item_changes(&item_id).await {
+                    Ok(changes) => {
+                        // Shown in the preview from here on, so they're seen
+                        if let Err(e) = client.acknowledge_changes(&item_id).await {
+                            debug!("Failed to acknowledge item changes: {}", e);
+                        }
+                        let _ = msg_tx.send(Message::ChangesLoaded { item_id, changes });
+                    }
+                    Err(e) => {
+                        debug!("Failed to fetch item changes: {}", e);
+                    }
+                },
+                Command::OpenItem(item_id) => match client.open_item(&item_id).await {
+                    Ok(opened) => {
+                        let _ = msg_tx.send(Message::ItemOpened { item_id, opened });
+                    }
+                    Err(e) => {
+                        error!("Failed to open item: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Failed to open item: {}", e)));
+                    }
+                },
+                Command::FetchLinks(item_id) => match client.item_links(&item_id).await {
+                    Ok(links) => {
+                        let _ = msg_tx.send(Message::LinksLoaded { item_id, links });
+                    }
+                    Err(e) => {
+                        error!("Failed to list links: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Failed to list links: {}", e)));
+                    }
+                },
+                Command::FetchLinkTitle { item_id, url } => {
+                    match client.link_title(&item_id, &url).await {
+                        Ok(title) => {
+                            let _ = msg_tx.send(Message::LinkTitleFetched { url, title });
+                        }
+                        Err(e) => {
+                            debug!("Failed to fetch link title: {}", e);
+                        }
                     }
                 }
-                Command::UnsaveItem(item_id) => {
-                    match client.unsave_item(&item_id).await {
-                        Ok(()) => {
-                            // Success - no message needed, just log
-                            debug!("Successfully unsaved item {}", item_id);
+                Command::OpenLink { item_id, url } => {
+                    match client.open_link(&item_id, &url).await {
+                        Ok(opened) => {
+                            let _ = msg_tx.send(Message::LinkOpened(opened));
                         }
                         Err(e) => {
-                            error!("Failed to unsave item: {}", e);
+                            error!("Failed to open link: {}", e);
+                            let _ =
+                                msg_tx.send(Message::Error(format!("Failed to open link: {}", e)));
+                        }
+                    }
+                }
+                Command::BookmarkLink { item_id, url } => {
+                    match client.bookmark_link(&item_id, &url).await {
+                        Ok(bookmark_id) => {
+                            let _ = msg_tx.send(Message::LinkBookmarked(bookmark_id));
+                        }
+                        Err(e) => {
+                            error!("Failed to bookmark link: {}", e);
                             let _ = msg_tx
-                                .send(Message::Error(format!("Failed to unsave item: {}", e)));
+                                .send(Message::Error(format!("Failed to bookmark link: {}", e)));
+                        }
+                    }
+                }
+                Command::ReadAloud(item_id) => match client.read_aloud(&item_id).await {
+                    Ok(ahead) => {
+                        let _ = msg_tx.send(Message::ReadAloudQueued(ahead));
+                    }
+                    Err(e) => {
+                        error!("Failed to read item aloud: {}", e);
+                        let _ = msg_tx
+                            .send(Message::Error(format!("Failed to read item aloud: {}", e)));
+                    }
+                },
+                Command::StopReading => match client.stop_reading().await {
+                    Ok(()) => {
+                        let _ = msg_tx.send(Message::ReadingStopped);
+                    }
+                    Err(e) => {
+                        error!("Failed to stop reading aloud: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to stop reading aloud: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::PlayItem(item_id) => match client.play_item(&item_id).await {
+                    Ok(playback) => {
+                        let _ = msg_tx.send(Message::PlaybackStarted(playback));
+                    }
+                    Err(e) => {
+                        error!("Failed to play item: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!("Failed to play item: {}", e)));
+                    }
+                },
+                Command::StopPlayback => match client.stop_playback().await {
+                    Ok(stopped) => {
+                        let _ = msg_tx.send(Message::PlaybackStopped(stopped));
+                    }
+                    Err(e) => {
+                        error!("Failed to stop playback: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to stop playback: {}", e)));
+                    }
+                },
+                Command::DownloadItem(item_id) => match client.download_item(&item_id).await {
+                    Ok(downloads) => {
+                        let _ = msg_tx.send(Message::DownloadsQueued(downloads));
+                    }
+                    Err(e) => {
+                        error!("Failed to download item: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to download item: {}", e)));
+                    }
+                },
+                Command::FetchDownloads => match client.list_downloads().await {
+                    Ok(downloads) => {
+                        let _ = msg_tx.send(Message::DownloadsLoaded(downloads));
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch downloads: {}", e);
+                        let _ = msg_tx
+                            .send(Message::Error(format!("Failed to fetch downloads: {}", e)));
+                    }
+                },
+                Command::CancelDownload(download_id) => {
+                    match client.cancel_download(download_id).await {
+                        Ok(_) => {
+                            // The download_finished event updates the list
+                            debug!("Cancelled download {}", download_id);
+                        }
+                        Err(e) => {
+                            error!("Failed to cancel download: {}", e);
+                            let _ = msg_tx
+                                .send(Message::Error(format!("Failed to cancel download: {}", e)));
                         }
                     }
                 }
+                Command::RetryDownload(download_id) => {
+                    match client.retry_download(download_id).await {
+                        Ok(download) => {
+                            let _ = msg_tx.send(Message::DownloadRetried(download));
+                        }
+                        Err(e) => {
+                            error!("Failed to retry download: {}", e);
+                            let _ = msg_tx
+                                .send(Message::Error(format!("Failed to retry download: {}", e)));
+                        }
+                    }
+                }
+                Command::FetchReadingStats(days) => match client.reading_stats(days).await {
+                    Ok(stats) => {
+                        let _ = msg_tx.send(Message::ReadingStatsLoaded { days, stats });
+                    }
+                    Err(e) => {
+                        error!("Failed to fetch reading stats: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to fetch reading stats: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::StartFocus {
+                    stream_ids,
+                    minutes,
+                } => match client.start_focus(&stream_ids, minutes).await {
+                    Ok(session) => {
+                        let _ = msg_tx.send(Message::FocusStarted(session));
+                    }
+                    Err(e) => {
+                        error!("Failed to start focus session: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to start focus session: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::StopFocus => match client.stop_focus().await {
+                    Ok(report) => {
+                        let _ = msg_tx.send(Message::FocusStopped(report));
+                    }
+                    Err(e) => {
+                        error!("Failed to stop focus session: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to stop focus session: {}",
+                            e
+                        )));
+                    }
+                },
+                Command::FetchFocus => match client.focus_session().await {
+                    Ok(session) => {
+                        let _ = msg_tx.send(Message::FocusLoaded(session));
+                    }
+                    Err(e) => {
+                        // Daemons older than focus mode don't have focus.status
+                        debug!("Failed to fetch focus session: {}", e);
+                    }
+                },
+                Command::StartTriage => match client.start_triage().await {
+                    Ok(view) => {
+                        let _ = msg_tx.send(Message::TriageLoaded(Box::new(view)));
+                    }
+                    Err(e) => {
+                        error!("Failed to start triage: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to start triage: {}", e)));
+                    }
+                },
+                Command::TriageItem { item_id, action } => {
+                    match client.triage_item(&item_id, action).await {
+                        Ok(view) => {
+                            let _ = msg_tx.send(Message::TriageLoaded(Box::new(view)));
+                        }
+                        Err(e) => {
+                            error!("Failed to triage item: {}", e);
+                            let _ = msg_tx
+                                .send(Message::Error(format!("Failed to triage item: {}", e)));
+                        }
+                    }
+                }
+                Command::UndoTriage => match client.undo_triage().await {
+                    Ok(view) => {
+                        let _ = msg_tx.send(Message::TriageLoaded(Box::new(view)));
+                    }
+                    Err(e) => {
+                        error!("Failed to undo triage: {}", e);
+                        let _ =
+                            msg_tx.send(Message::Error(format!("Failed to undo triage: {}", e)));
+                    }
+                },
                 Command::MarkItemRead(item_id) => match client.mark_item_read(&item_id).await {
                     Ok(()) => {
                         debug!("Successfully marked item {} as read", item_id);
@@ -365,6 +1888,24 @@ pub fn spawn_client_task(
                             msg_tx.send(Message::Error(format!("Failed to archive item: {}", e)));
                     }
                 },
+                Command::MarkSpam(item_id) => {
+                    if let Err(e) = client.mark_spam(&item_id).await {
+                        error!("Failed to mark item as spam: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to mark item as spam: {}",
+                            e
+                        )));
+                    }
+                }
+                Command::MarkNotSpam(item_id) => {
+                    if let Err(e) = client.mark_not_spam(&item_id).await {
+                        error!("Failed to mark item as not spam: {}", e);
+                        let _ = msg_tx.send(Message::Error(format!(
+                            "Failed to mark item as not spam: {}",
+                            e
+                        )));
+                    }
+                }
                 Command::FetchCollections => match client.list_collections().await {
                     Ok(collections) => {
                         let _ = msg_tx.send(Message::CollectionsLoaded(collections));
@@ -433,6 +1974,130 @@ pub fn spawn_client_task(
                         )));
                     }
                 },
+                Command::FetchTags(provider_id) => match client.list_tags(&provider_id).await {
+                    Ok(tags) => {
+                        let _ = msg_tx.send(Message::TagsLoaded { provider_id, tags });
+                    }
+                    Err(e) => {
+                        // Daemons older than tag management don't have tags.list
+                        debug!("Failed to fetch tags: {}", e);
+                    }
+                },
+                Command::FetchTagItems { provider_id, tag } => {
+                    match client.tagged_items(&provider_id, &tag).await {
+                        Ok(items) => {
+                            let _ = msg_tx.send(Message::ItemsLoaded(items));
+                        }
+                        Err(e) => {
+                            error!("Failed to fetch items tagged {}: {}", tag, e);
+                            let _ = msg_tx.send(Message::Error(format!(
+                                "Failed to fetch items tagged {}: {}",
+                                tag, e
+                            )));
+                        }
+                    }
+                }
+                Command::RenameTag {
+                    provider_id,
+                    from,
+                    to,
+                } => {
+                    let result = client.rename_tag(&provider_id, &from, &to).await;
+                    send_tag_edit(&msg_tx, provider_id, result);
+                }
+                Command::MergeTags {
+                    provider_id,
+                    sources,
+                    into,
+                } => {
+                    let result = client.merge_tags(&provider_id, &sources, &into).await;
+                    send_tag_edit(&msg_tx, provider_id, result);
+                }
+                Command::DeleteTag { provider_id, tag } => {
+                    let result = client.delete_tag(&provider_id, &tag).await;
+                    send_tag_edit(&msg_tx, provider_id, result);
+                }
+                Command::PublishStream {
+                    stream_id,
+                    format,
+                    path,
+                } => match client.publish_stream(&stream_id, format, &path).await {
+                    Ok(summary) => {
+                        let _ = msg_tx.send(Message::Published(summary));
+                    }
+                    Err(e) => {
+                        error!("{:#}", e);
+                        let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                    }
+                },
+                Command::CreateAlert { name, query } => {
+                    match client.create_alert(&name, &query).await {
+                        Ok(()) => {
+                            let _ = msg_tx.send(Message::AlertCreated(name));
+                        }
+                        Err(e) => {
+                            error!("{:#}", e);
+                            let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                        }
+                    }
+                }
+                Command::FetchDuplicates => match client.bookmark_duplicates().await {
+                    Ok(groups) => {
+                        let _ = msg_tx.send(Message::DuplicatesLoaded(groups));
+                    }
+                    Err(e) => {
+                        error!("{:#}", e);
+                        let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                    }
+                },
+                Command::MergeBookmarks { keep, duplicates } => {
+                    match client.merge_bookmarks(&keep, &duplicates).await {
+                        Ok(kept) => {
+                            let _ = msg_tx.send(Message::BookmarksMerged {
+                                kept: kept.title,
+                                merged: duplicates.len(),
+                            });
+                        }
+                        Err(e) => {
+                            error!("{:#}", e);
+                            let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                        }
+                    }
+                }
+                Command::FetchQueue => match client.reading_queue().await {
+                    Ok(queue) => {
+                        let _ = msg_tx.send(Message::QueueLoaded(queue));
+                    }
+                    Err(e) => {
+                        // Daemons older than the reading queue don't have queue.list
+                        debug!("Failed to fetch reading queue: {}", e);
+                    }
+                },
+                Command::MoveQueued { item_id, position } => {
+                    match client.move_queued(&item_id, position).await {
+                        Ok(queue) => {
+                            let _ = msg_tx.send(Message::QueueLoaded(queue));
+                        }
+                        Err(e) => {
+                            error!("{:#}", e);
+                            let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                        }
+                    }
+                }
+                Command::QueueProgress { item_id, offset } => {
+                    if let Err(e) = client.queue_progress(&item_id, offset).await {
+                        debug!("Failed to record position in {}: {:#}", item_id, e);
+                    }
+                }
+                Command::FinishQueued(item_id) => match client.finish_queued(&item_id).await {
+                    Ok(queue) => {
+                        let _ = msg_tx.send(Message::QueueLoaded(queue));
+                    }
+                    Err(e) => {
+                        error!("{:#}", e);
+                        let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+                    }
+                },
                 Command::Shutdown => {
                     info!("Shutting down daemon client");
                     break;
@@ -444,7 +2109,262 @@ pub fn spawn_client_task(
     })
 }
 
-/// Get the default daemon URL.
+/// Report the outcome of a tag rename, merge, or delete to the UI thread.
+fn send_tag_edit(
+    msg_tx: &mpsc::UnboundedSender<Message>,
+    provider_id: String,
+    result: Result<u32>,
+) {
+    match result {
+        Ok(changed) => {
+            let _ = msg_tx.send(Message::TagsEdited {
+                provider_id,
+                changed,
+            });
+        }
+        Err(e) => {
+            error!("{:#}", e);
+            let _ = msg_tx.send(Message::Error(format!("{:#}", e)));
+        }
+    }
+}
+
+/// Spawn the daemon event subscription task.
+///
+/// Connects to the daemon over WebSocket, subscribes to `events.subscribe`,
+/// and forwards every event to the UI thread as [`Message::Event`]. If the
+/// daemon can't be reached the task exits quietly; the request/response
+/// client keeps working without live updates.
+///
+/// # Arguments
+///
+/// * `url` - URL of the daemon's HTTP endpoint (e.g., "http://127.0.0.1:3030")
+/// * `msg_tx` - Sender for messages to the UI thread
+pub fn spawn_event_task(
+    url: String,
+    msg_tx: mpsc::UnboundedSender<Message>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let ws_url = to_ws_url(&url);
+
+        let headers = match auth_headers() {
+            Ok(headers) => headers,
+            Err(e) => {
+                debug!("Live events unavailable: {}", e);
+                return;
+            }
+        };
+
+        let client = match WsClientBuilder::default()
+            .set_headers(headers)
+            .build(&ws_url)
+            .await
+        {
+            Ok(client) => client,
+            Err(e) => {
+                debug!(
+                    "Live events unavailable, could not connect to {}: {}",
+                    ws_url, e
+                );
+                return;
+            }
+        };
+
+        let mut subscription = match client
+            .subscribe::<DaemonEvent, _>("events.subscribe", rpc_params![], "events.unsubscribe")
+            .await
+        {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                debug!("Live events unavailable, subscription failed: {}", e);
+                return;
+            }
+        };
+
+        info!("Subscribed to daemon events");
+
+        while let Some(event) = subscription.next().await {
+            match event {
+                Ok(event) => {
+                    if msg_tx.send(Message::Event(event)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to decode daemon event: {}", e);
+                }
+            }
+        }
+
+        debug!("Daemon event subscription closed");
+    })
+}
+
+/// Convert the daemon's HTTP URL into the matching WebSocket URL.
+fn to_ws_url(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        url.to_string()
+    }
+}
+
+/// Build the `Authorization` header from `SCRYFORGE_TOKEN`, if set.
+fn auth_headers() -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    if let Ok(token) = std::env::var("SCRYFORGE_TOKEN") {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+            .context("SCRYFORGE_TOKEN contains invalid header characters")?;
+        headers.insert("Authorization", value);
+    }
+    Ok(headers)
+}
+
+/// Get the daemon URL.
+///
+/// Uses `SCRYFORGE_DAEMON_URL` when set (e.g. "https://homeserver:3030"),
+/// otherwise the local default.
 pub fn get_daemon_url() -> String {
-    "http://127.0.0.1:3030".to_string()
+    std::env::var("SCRYFORGE_DAEMON_URL").unwrap_or_else(|_| "http://127.0.0.1:3030".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_ws_url() {
+        assert_eq!(to_ws_url("http://127.0.0.1:3030"), "ws://127.0.0.1:3030");
+        assert_eq!(to_ws_url("https://example.com"), "wss://example.com");
+        assert_eq!(to_ws_url("ws://127.0.0.1:3030"), "ws://127.0.0.1:3030");
+    }
+
+    #[test]
+    fn test_api_version_compatibility() {
+        let version = |v: &str| ApiVersionInfo {
+            api_version: v.to_string(),
+            daemon_version: "0.1.0".to_string(),
+        };
+        assert!(version("1.0.0").is_compatible());
+        assert!(version("1.4.2").is_compatible());
+        assert!(!version("2.0.0").is_compatible());
+        assert!(!version("garbage").is_compatible());
+    }
+
+    #[test]
+    fn test_daemon_event_deserialization() {
+        let json = r#"{"type":"sync_finished","provider_id":"rss","success":false,"error":"boom","timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            event,
+            DaemonEvent::SyncFinished {
+                provider_id: "rss".to_string(),
+                success: false,
+                error: Some("boom".to_string()),
+                failed_streams: Vec::new(),
+            }
+        );
+
+        let json = r#"{"type":"sync_finished","provider_id":"rss","success":true,"error":null,"failed_streams":["rss:feed:rss:2"],"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            event,
+            DaemonEvent::SyncFinished { success: true, failed_streams, .. }
+                if failed_streams == ["rss:feed:rss:2"]
+        ));
+
+        let json = r#"{"type":"playback_finished","item_id":"youtube:abc","exit_code":null,"position_secs":95,"finished":false,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            event,
+            DaemonEvent::PlaybackFinished {
+                item_id: "youtube:abc".to_string(),
+                exit_code: None,
+                position_secs: Some(95),
+                finished: false,
+            }
+        );
+
+        let json = r#"{"type":"download_finished","download_id":3,"item_id":"rss:1","state":"failed","path":null,"error":"HTTP 404","timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            event,
+            DaemonEvent::DownloadFinished {
+                download_id: 3,
+                state: DownloadState::Failed,
+                path: None,
+                error: Some("HTTP 404".to_string()),
+            }
+        );
+
+        let json = r#"{"type":"focus_ended","stream_ids":["rss:feed:hn"],"completed":true,"read":7,"saved":1,"timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            event,
+            DaemonEvent::FocusEnded {
+                completed: true,
+                read: 7,
+                saved: 1,
+            }
+        );
+
+        let json = r#"{"type":"alert_matched","search_id":2,"name":"scryforge","item_id":"rss:9","title":"Scryforge 1.0","timestamp":"2024-01-01T00:00:00Z"}"#;
+        let event: DaemonEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            event,
+            DaemonEvent::AlertMatched {
+                search_id: 2,
+                name: "scryforge".to_string(),
+                item_id: "rss:9".to_string(),
+                title: "Scryforge 1.0".to_string(),
+            }
+        );
+
+        let unknown: DaemonEvent = serde_json::from_str(r#"{"type":"something_new"}"#).unwrap();
+        assert_eq!(unknown, DaemonEvent::Unknown);
+    }
+
+    #[test]
+    fn test_relation_deserialization() {
+        let relation: Relation = serde_json::from_str(r#""linked_from""#).unwrap();
+        assert_eq!(relation, Relation::LinkedFrom);
+        let unknown: Relation = serde_json::from_str(r#""quoted_by""#).unwrap();
+        assert_eq!(unknown, Relation::Unknown);
+    }
+
+    #[test]
+    fn test_triage_view_deserialization() {
+        let json = r#"{"item":null,"remaining":0,"processed":4,"undo":{"item_id":"email:1","action":"snooze","snoozed_until":"2024-01-02T00:00:00Z"}}"#;
+        let view: TriageView = serde_json::from_str(json).unwrap();
+        assert!(view.item.is_none());
+        assert_eq!(view.undo.unwrap().action, TriageAction::Snooze);
+    }
+
+    #[test]
+    fn test_shared_item_deserialization() {
+        let json = r#"{"target":"markdown","text":"[Title](https://example.com)","copy":true,"message":"Copied"}"#;
+        let shared: SharedItem = serde_json::from_str(json).unwrap();
+        assert!(shared.copy);
+        assert_eq!(shared.text, "[Title](https://example.com)");
+    }
+
+    #[test]
+    fn test_item_link_deserialization() {
+        let json = r#"[{"url":"https://example.com/a","text":"A post","title":null},{"url":"https://example.com/b","text":null,"title":"B"}]"#;
+        let links: Vec<ItemLink> = serde_json::from_str(json).unwrap();
+        assert_eq!(links[0].text.as_deref(), Some("A post"));
+        assert!(links[0].title.is_none());
+        assert_eq!(links[1].title.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn test_item_change_deserialization() {
+        let json = r#"{"id":3,"item_id":"todo:1","changed_at":"2024-01-02T00:00:00Z","changes":[{"field":"body","before":null,"after":null,"diff":"a [-b-] {+c+}"},{"field":"due_date","before":"2024-01-01","after":"2024-01-03"}]}"#;
+        let change: ItemChange = serde_json::from_str(json).unwrap();
+        assert_eq!(change.changes.len(), 2);
+        assert_eq!(change.changes[0].diff.as_deref(), Some("a [-b-] {+c+}"));
+        assert!(change.changes[1].diff.is_none());
+    }
 }
@@ -0,0 +1,334 @@
+//! Syntax highlighting for code blocks in the preview.
+//!
+//! Bodies are split into prose and code ([`blocks`]): Markdown fences
+//! (` ```rust ` or `~~~`) as Reddit posts and issues write them, and HTML
+//! `<pre>` blocks as feeds publish them, with the language taken from a
+//! `language-*` class. Code is highlighted with syntect's bundled grammars,
+//! in colors drawn from the TUI [`Theme`] so code matches the rest of the
+//! interface whichever theme is chosen.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use fusabi_tui_core::style::{Color, Style};
+use fusabi_tui_widgets::text::{Line, Span};
+use scryforge_provider_core::{decode_entities, strip_tags};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{self, ScopeSelectors, StyleModifier, ThemeItem, ThemeSettings};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+use crate::theme::Theme;
+
+/// Indent of code under the prose around it.
+const CODE_INDENT: &str = "  ";
+
+/// A run of a body: text, or a code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block<'a> {
+    Prose(&'a str),
+    Code {
+        /// The fence's info string or the `language-*` class, if given
+        language: Option<String>,
+        code: String,
+    },
+}
+
+/// Split `text` into prose and code blocks.
+pub fn blocks(text: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    while let Some(pre) = next_pre(rest) {
+        fenced_blocks(&rest[..pre.start], &mut blocks);
+        blocks.push(Block::Code {
+            language: pre.language,
+            code: pre.code,
+        });
+        rest = &rest[pre.end..];
+    }
+    fenced_blocks(rest, &mut blocks);
+    blocks
+}
+
+/// Split Markdown into prose and fenced code blocks. A fence left open runs
+/// to the end of the text.
+fn fenced_blocks<'a>(text: &'a str, blocks: &mut Vec<Block<'a>>) {
+    let mut prose_start = 0;
+    let mut offset = 0;
+    // The open fence's character, length, and language, and where its code starts
+    let mut open: Option<(char, usize, Option<String>, usize)> = None;
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let Some((fence_char, length, info)) = fence(line) else {
+            continue;
+        };
+
+        if let Some((open_char, open_length, language, code_start)) = open.take() {
+            if fence_char == open_char && length >= open_length && info.is_empty() {
+                blocks.push(Block::Code {
+                    language,
+                    code: text[code_start..line_start].to_string(),
+                });
+                prose_start = offset;
+            } else {
+                open = Some((open_char, open_length, language, code_start));
+            }
+        } else if fence_char == '~' || !info.contains('`') {
+            if line_start > prose_start {
+                blocks.push(Block::Prose(&text[prose_start..line_start]));
+            }
+            let language = info.split_whitespace().next().map(|language| {
+                language
+                    .trim_matches(|c| c == '{' || c == '}' || c == '.')
+                    .to_string()
+            });
+            open = Some((fence_char, length, language, offset));
+        }
+    }
+
+    match open {
+        Some((_, _, language, code_start)) => blocks.push(Block::Code {
+            language,
+            code: text[code_start..].to_string(),
+        }),
+        None if prose_start < text.len() => blocks.push(Block::Prose(&text[prose_start..])),
+        None => {}
+    }
+}
+
+/// A fence line's character, length, and info string.
+fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.len() - trimmed.trim_start_matches(fence_char).len();
+    if length < 3 {
+        return None;
+    }
+    Some((fence_char, length, trimmed[length..].trim()))
+}
+
+/// An HTML `<pre>` block found in a body.
+struct Pre {
+    /// Where the opening tag starts
+    start: usize,
+    /// Where the closing tag ends
+    end: usize,
+    language: Option<String>,
+    code: String,
+}
+
+/// The first complete `<pre>` block in `html`.
+fn next_pre(html: &str) -> Option<Pre> {
+    // ASCII lowercasing keeps byte offsets
+    let lower = html.to_ascii_lowercase();
+    let mut from = 0;
+    let start = loop {
+        let at = from + lower[from..].find("<pre")?;
+        match lower.as_bytes().get(at + 4) {
+            Some(b'>') | Some(b' ') | Some(b'\t') | Some(b'\n') => break at,
+            _ => from = at + 4,
+        }
+    };
+    let body_start = start + lower[start..].find('>')? + 1;
+    let body_end = body_start + lower[body_start..].find("</pre>")?;
+
+    let mut tags = lower[start..body_start].to_string();
+    let mut body = &html[body_start..body_end];
+    if body.trim_start().to_ascii_lowercase().starts_with("<code") {
+        let code_tag_end = body.find('>')? + 1;
+        tags.push_str(&lower[body_start..body_start + code_tag_end]);
+        body = &body[code_tag_end..];
+    }
+
+    let language = ["language-", "lang-"].iter().find_map(|prefix| {
+        let at = tags.find(prefix)? + prefix.len();
+        let language: String = tags[at..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '#' | '-' | '_'))
+            .collect();
+        Some(language).filter(|language| !language.is_empty())
+    });
+
+    Some(Pre {
+        start,
+        end: body_end + "</pre>".len(),
+        language,
+        code: decode_entities(&strip_tags(body, "")),
+    })
+}
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// The grammar for a fence's language, or the code's `#!` line.
+fn find_syntax<'a>(
+    syntaxes: &'a SyntaxSet,
+    language: Option<&str>,
+    code: &str,
+) -> &'a SyntaxReference {
+    let by_language = language.and_then(|language| {
+        let language = language.to_ascii_lowercase();
+        // Names syntect's bundled grammars don't answer to
+        let token = match language.as_str() {
+            "shell" | "console" | "zsh" | "sh" => "bash",
+            "ts" | "typescript" | "jsx" | "tsx" => "js",
+            "golang" => "go",
+            "c++" => "cpp",
+            "py3" => "py",
+            other => other,
+        };
+        syntaxes.find_syntax_by_token(token)
+    });
+    by_language
+        .or_else(|| syntaxes.find_syntax_by_first_line(code))
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text())
+}
+
+/// A syntect theme in the colors of `theme`.
+fn syntax_theme(theme: &Theme) -> highlighting::Theme {
+    let scopes = [
+        ("comment", theme.muted),
+        ("string, constant.character", theme.success),
+        ("constant.numeric, constant.language", theme.warning),
+        ("keyword, storage", theme.unread),
+        ("entity.name.function, support.function", theme.accent),
+        (
+            "entity.name.type, entity.name.class, support.type, support.class",
+            theme.border_focused,
+        ),
+        ("invalid", theme.error),
+    ];
+    highlighting::Theme {
+        settings: ThemeSettings {
+            foreground: Some(syntect_color(theme.foreground)),
+            ..Default::default()
+        },
+        scopes: scopes
+            .into_iter()
+            .filter_map(|(selector, color)| {
+                Some(ThemeItem {
+                    scope: ScopeSelectors::from_str(selector).ok()?,
+                    style: StyleModifier {
+                        foreground: Some(syntect_color(color)),
+                        background: None,
+                        font_style: None,
+                    },
+                })
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn syntect_color(color: Color) -> highlighting::Color {
+    match color {
+        Color::Rgb(r, g, b) => highlighting::Color { r, g, b, a: 0xff },
+        _ => highlighting::Color::WHITE,
+    }
+}
+
+/// Lines of `code`, indented and highlighted as `language` in the colors of
+/// `theme`.
+pub fn highlight(code: &str, language: Option<&str>, theme: &Theme) -> Vec<Line<'static>> {
+    let syntaxes = syntaxes();
+    let syntax_theme = syntax_theme(theme);
+    let mut highlighter = HighlightLines::new(find_syntax(syntaxes, language, code), &syntax_theme);
+
+    LinesWithEndings::from(code)
+        .map(|line| {
+            let mut spans = vec![Span::raw(CODE_INDENT)];
+            match highlighter.highlight_line(line, syntaxes) {
+                Ok(ranges) => {
+                    for (style, text) in ranges {
+                        let text = text.trim_end_matches(['\n', '\r']);
+                        if !text.is_empty() {
+                            let fg = style.foreground;
+                            spans.push(Span::styled(
+                                text.to_string(),
+                                Style::new().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                            ));
+                        }
+                    }
+                }
+                Err(_) => spans.push(Span::raw(line.trim_end_matches(['\n', '\r']).to_string())),
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(language: Option<&str>, code: &str) -> Block<'static> {
+        Block::Code {
+            language: language.map(str::to_string),
+            code: code.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_markdown_fences() {
+        let text = "Try this:\n\n```rust\nfn main() {}\n```\nor\n~~~~ {.py}\n```\nprint(1)\n~~~~\n";
+        assert_eq!(
+            blocks(text),
+            vec![
+                Block::Prose("Try this:\n\n"),
+                code(Some("rust"), "fn main() {}\n"),
+                Block::Prose("or\n"),
+                code(Some("py"), "```\nprint(1)\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_fence_runs_to_end() {
+        assert_eq!(
+            blocks("a\n```\nx = 1\n"),
+            vec![Block::Prose("a\n"), code(None, "x = 1\n")]
+        );
+        // Inline code isn't a fence
+        assert_eq!(blocks("use ``` here"), vec![Block::Prose("use ``` here")]);
+    }
+
+    #[test]
+    fn test_html_pre_blocks() {
+        let html = "<p>Run</p><pre><code class=\"language-rust\">let x = &amp;<span>y</span>;\n</code></pre><p>done</p>";
+        assert_eq!(
+            blocks(html),
+            vec![
+                Block::Prose("<p>Run</p>"),
+                code(Some("rust"), "let x = &y;\n"),
+                Block::Prose("<p>done</p>"),
+            ]
+        );
+        assert_eq!(
+            blocks("<PRE>a &lt; b</PRE><preview>"),
+            vec![code(None, "a < b"), Block::Prose("<preview>")]
+        );
+    }
+
+    #[test]
+    fn test_highlight_uses_theme_colors() {
+        let theme = Theme::dracula();
+        let lines = highlight("// note\nfn main() {}\n", Some("rust"), &theme);
+        assert_eq!(lines.len(), 2);
+
+        let color_of = |line: &Line<'_>, text: &str| {
+            line.spans
+                .iter()
+                .find(|span| span.content.contains(text))
+                .and_then(|span| span.style.fg)
+        };
+        assert_eq!(color_of(&lines[0], "note"), Some(theme.muted));
+        assert_eq!(color_of(&lines[1], "fn"), Some(theme.unread));
+        assert_eq!(lines[1].spans[0].content, CODE_INDENT);
+    }
+}
@@ -41,7 +41,8 @@
 //! |-----|--------|
 //! | `h/l` or `Tab` | Move focus between panes |
 //! | `j/k` or `↑/↓` | Navigate within list |
-//! | `Enter` | Open selected item |
+//! | `Enter` | Open selected item with the daemon's opener |
+//! | `y` | Copy the selected item's link |
 //! | `/` | Focus omnibar for search |
 //! | `:` | Focus omnibar for commands |
 //! | `q` | Quit |
@@ -58,25 +59,57 @@
 //! ```
 
 use anyhow::Result;
-use fusabi_tui_core::{buffer::Buffer, layout::{Constraint, Direction, Layout, Rect}};
+use fusabi_tui_core::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+};
 use fusabi_tui_render::prelude::*;
-use scryforge_provider_core::{Collection, Item, Stream};
-use std::collections::HashMap;
+use scryforge_clipboard::Clipboard;
+use scryforge_provider_core::{
+    CalendarInvite, Collection, Item, ProviderCapabilities, SearchResult, Stream, StreamId,
+    StreamType, TagCount,
+};
+use std::collections::{HashMap, HashSet};
 use std::io::stdout;
 use tokio::sync::mpsc;
 
 pub mod command;
 mod daemon_client;
+pub mod highlight;
+pub mod markdown;
 pub mod search;
 pub mod theme;
 pub mod time;
 pub mod widgets;
 
-use daemon_client::{get_daemon_url, spawn_client_task};
-use daemon_client::{Command as DaemonCommand, Message};
+use daemon_client::{get_daemon_url, spawn_client_task, spawn_event_task};
+use daemon_client::{
+    Command as DaemonCommand, DaemonEvent, Download, DownloadState, DuplicateGroup, FocusReport,
+    FocusSession, ItemChange, ItemLink, Message, QueueEntry, ReadingQueue, ReadingStats,
+    RelatedItem, ShareTarget, TriageAction, TriageView,
+};
 use theme::Theme;
 use widgets::*;
 
+/// Windows, in days, the analytics view cycles through with `t`.
+const STATS_WINDOWS: [u32; 3] = [7, 30, 90];
+
+/// ID of the "Pinned" entry listed after the daemon's streams.
+const PINNED_STREAM_ID: &str = "local:pinned";
+
+/// ID of the "Reading Queue" entry listed after "Pinned".
+const QUEUE_STREAM_ID: &str = "local:queue";
+
+/// ID of the "Alerts" entry listed after "Reading Queue".
+const ALERTS_STREAM_ID: &str = "local:alerts";
+
+/// Provider whose tags are listed after "Pinned" and edited with `:tag`.
+const TAG_PROVIDER_ID: &str = "bookmarks";
+
+/// ID prefix of the tag entries, matching the bookmarks provider's
+/// `tag:<name>` collections.
+const TAG_STREAM_PREFIX: &str = "bookmarks:collection:tag:";
+
 fn main() -> Result<()> {
     // Initialize logging to file
     // TODO: Set up file-based logging properly
@@ -96,7 +129,8 @@ async fn async_main() -> Result<()> {
 
     // Spawn the daemon client task
     let daemon_url = get_daemon_url();
-    let _client_handle = spawn_client_task(daemon_url, cmd_rx, msg_tx);
+    let client_handle = spawn_client_task(daemon_url.clone(), cmd_rx, msg_tx.clone());
+    let _event_handle = spawn_event_task(daemon_url, msg_tx);
 
     // Initialize terminal
     use crossterm::{
@@ -117,6 +151,12 @@ async fn async_main() -> Result<()> {
 
     // Request initial data from daemon
     let _ = cmd_tx.send(DaemonCommand::FetchStreams);
+    let _ = cmd_tx.send(DaemonCommand::FetchProviders);
+    let _ = cmd_tx.send(DaemonCommand::FetchFocus);
+    let _ = cmd_tx.send(DaemonCommand::FetchPinned);
+    let _ = cmd_tx.send(DaemonCommand::FetchQueue);
+    let _ = cmd_tx.send(DaemonCommand::FetchAlerts);
+    let _ = cmd_tx.send(DaemonCommand::FetchTags(TAG_PROVIDER_ID.to_string()));
 
     // Main event loop
     loop {
@@ -144,8 +184,10 @@ async fn async_main() -> Result<()> {
         }
     }
 
-    // Send shutdown command to daemon client
+    // Send shutdown command to daemon client, once the reading position is saved
+    app.record_preview_position();
     let _ = cmd_tx.send(DaemonCommand::Shutdown);
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(1), client_handle).await;
 
     // Cleanup terminal
     renderer.show_cursor(true)?;
@@ -314,8 +356,61 @@ struct App {
     cmd_tx: mpsc::UnboundedSender<DaemonCommand>,
     daemon_connected: bool,
     provider_statuses: HashMap<String, ProviderSyncStatus>,
+    /// Streams that failed in their provider's last sync
+    failed_streams: HashSet<String>,
+    provider_capabilities: HashMap<String, ProviderCapabilities>,
     toasts: Vec<Toast>,
     active_search_filter: Option<String>,
+    /// What matched in each item of the last search, while its results are listed
+    search_results: Vec<SearchResult>,
+    /// Items linked to the selected item, keyed by its ID
+    related: Option<(String, Vec<RelatedItem>)>,
+    /// What changed in the selected item, if it was updated, keyed by its ID
+    changes: Option<(String, Vec<ItemChange>)>,
+    /// Opened on first copy; holds copied text on X11 and Wayland
+    clipboard: Option<Clipboard>,
+    downloads: Vec<Download>,
+    download_state: ListState,
+    /// Whether the downloads list replaces the preview pane
+    downloads_view_active: bool,
+    /// Links in the item the links view was opened on, keyed by its ID
+    links: Option<(String, Vec<ItemLink>)>,
+    link_state: ListState,
+    /// Links whose titles were asked for, so each page is fetched once
+    requested_link_titles: HashSet<String>,
+    /// Whether the selected item's links replace the preview pane
+    links_view_active: bool,
+    reading_stats: Option<ReadingStats>,
+    stats_state: ListState,
+    /// Days the analytics view covers, cycled with `t`
+    stats_days: u32,
+    /// Whether the reading analytics replace the preview pane
+    analytics_view_active: bool,
+    focus_session: Option<FocusSession>,
+    /// Streams picked with `f` for the next focus session
+    focus_marked: Vec<String>,
+    /// Pinned items from every stream, most recently pinned first
+    pinned: Vec<Item>,
+    /// Saved items from every provider in reading order
+    queue: ReadingQueue,
+    /// Items that matched saved search alerts, most recent match first
+    alerts: Vec<Item>,
+    /// Bookmark tags listed after "Reading Queue", most used first
+    tags: Vec<TagCount>,
+    /// Duplicate bookmark groups from the last `:duplicates`
+    duplicates: Vec<DuplicateGroup>,
+    /// Lines scrolled off the top of the preview
+    preview_scroll: u16,
+    /// Item the preview was scrolled on
+    preview_item: Option<String>,
+    /// Whether triage of the unread inbox replaces the preview pane
+    triage_view_active: bool,
+    /// Where triage stands; `None` until the daemon answers
+    triage: Option<TriageView>,
+    /// Targets from `items.share_targets`; `None` until loaded
+    share_targets: Option<Vec<ShareTarget>>,
+    /// Whether the next digit key picks a share target for the selected item
+    share_picker_active: bool,
 }
 
 impl App {
@@ -338,8 +433,38 @@ impl App {
             cmd_tx,
             daemon_connected: false,
             provider_statuses: HashMap::new(),
+            failed_streams: HashSet::new(),
+            provider_capabilities: HashMap::new(),
             toasts: Vec::new(),
             active_search_filter: None,
+            search_results: Vec::new(),
+            related: None,
+            changes: None,
+            clipboard: None,
+            downloads: Vec::new(),
+            download_state: ListState::new(0),
+            downloads_view_active: false,
+            links: None,
+            link_state: ListState::new(0),
+            requested_link_titles: HashSet::new(),
+            links_view_active: false,
+            reading_stats: None,
+            stats_state: ListState::new(0),
+            stats_days: STATS_WINDOWS[1],
+            analytics_view_active: false,
+            focus_session: None,
+            focus_marked: Vec::new(),
+            pinned: Vec::new(),
+            queue: ReadingQueue::default(),
+            alerts: Vec::new(),
+            tags: Vec::new(),
+            duplicates: Vec::new(),
+            preview_scroll: 0,
+            preview_item: None,
+            triage_view_active: false,
+            triage: None,
+            share_targets: None,
+            share_picker_active: false,
         }
     }
 
@@ -349,7 +474,17 @@ impl App {
                 self.daemon_connected = true;
                 self.status_message = "Connected to daemon - Press ? for help".to_string();
             }
-            Message::StreamsLoaded(streams) => {
+            Message::ProvidersLoaded(providers) => {
+                self.provider_capabilities = providers
+                    .into_iter()
+                    .map(|provider| (provider.id, provider.capabilities))
+                    .collect();
+            }
+            Message::StreamsLoaded(mut streams) => {
+                streams.push(self.pinned_stream());
+                streams.push(self.queue_stream());
+                streams.push(self.alerts_stream());
+                streams.extend(self.tag_streams());
                 let count = streams.len();
                 self.streams = streams;
                 self.stream_state = ListState::new(count);
@@ -364,17 +499,182 @@ impl App {
                 }
                 self.status_message = format!("Loaded {} streams", count);
             }
-            Message::ItemsLoaded(items) => {
+            Message::ItemsLoaded(mut items) => {
                 let count = items.len();
+                items.sort_by_key(|item| !self.is_pinned(item));
                 self.items = items;
+                self.search_results.clear();
                 self.item_state = ListState::new(count);
                 if count > 0 {
                     self.item_state.select_first();
                     // Auto-mark first item as read when items are loaded
-                    self.auto_mark_selected_as_read();
+                    self.on_item_selected();
                 }
                 self.status_message = format!("Loaded {} items", count);
             }
+            Message::SearchLoaded { query, results } => {
+                let count = results.len();
+                self.items = results.iter().map(|result| result.item.clone()).collect();
+                self.search_results = results;
+                self.item_state = ListState::new(count);
+                if count > 0 {
+                    self.item_state.select_first();
+                    self.focused = FocusedPane::ItemList;
+                }
+                self.status_message = format!("{} result(s) for {}", count, query);
+            }
+            Message::PinnedLoaded(pinned) => {
+                self.pinned = pinned;
+                let stream = self.pinned_stream();
+                let entry = self.streams.iter_mut().find(|s| s.id.0 == PINNED_STREAM_ID);
+                if let Some(entry) = entry {
+                    *entry = stream;
+                }
+
+                let selected_id = self
+                    .item_state
+                    .selected
+                    .and_then(|idx| self.items.get(idx))
+                    .map(|item| item.id.clone());
+                if self.viewing_pinned() {
+                    self.items = self.pinned.clone();
+                } else {
+                    let mut items = std::mem::take(&mut self.items);
+                    items.sort_by_key(|item| !self.is_pinned(item));
+                    self.items = items;
+                }
+                // Keep the cursor on the same item as it moves
+                self.item_state.update_len(self.items.len());
+                let position =
+                    selected_id.and_then(|id| self.items.iter().position(|i| i.id == id));
+                if let Some(pos) = position {
+                    self.item_state.selected = Some(pos);
+                }
+            }
+            Message::AlertsLoaded(alerts) => {
+                self.alerts = alerts;
+                let stream = self.alerts_stream();
+                if let Some(entry) = self.streams.iter_mut().find(|s| s.id.0 == ALERTS_STREAM_ID) {
+                    *entry = stream;
+                }
+                if self.viewing_alerts() {
+                    // Stay on the selected item as new matches come in above it
+                    let selected_id = self
+                        .item_state
+                        .selected
+                        .and_then(|idx| self.items.get(idx))
+                        .map(|item| item.id.clone());
+                    self.items = self.alerts.clone();
+                    self.item_state.update_len(self.items.len());
+                    let position =
+                        selected_id.and_then(|id| self.items.iter().position(|i| i.id == id));
+                    if let Some(pos) = position {
+                        self.item_state.selected = Some(pos);
+                    }
+                }
+            }
+            Message::QueueLoaded(queue) => {
+                self.queue = queue;
+                let stream = self.queue_stream();
+                if let Some(entry) = self.streams.iter_mut().find(|s| s.id.0 == QUEUE_STREAM_ID) {
+                    *entry = stream;
+                }
+                if self.viewing_queue() {
+                    // Stay on the selected item as it moves
+                    let selected_id = self
+                        .item_state
+                        .selected
+                        .and_then(|idx| self.items.get(idx))
+                        .map(|item| item.id.as_str().to_string());
+                    self.show_queue(selected_id);
+                    self.on_item_selected();
+                    self.show_queue_position();
+                    if self.queue.entries.is_empty() {
+                        self.status_message =
+                            "Reading queue is empty - save items to add them".to_string();
+                    }
+                }
+            }
+            Message::TagsLoaded { provider_id, tags } if provider_id == TAG_PROVIDER_ID => {
+                self.tags = tags;
+                let selected_id = self
+                    .stream_state
+                    .selected
+                    .and_then(|idx| self.streams.get(idx))
+                    .map(|s| s.id.clone());
+                self.streams
+                    .retain(|s| !s.id.0.starts_with(TAG_STREAM_PREFIX));
+                let tag_streams = self.tag_streams();
+                self.streams.extend(tag_streams);
+
+                // Keep the cursor on the same stream; a deleted tag moves it to the last entry
+                self.stream_state.update_len(self.streams.len());
+                let position =
+                    selected_id.and_then(|id| self.streams.iter().position(|s| s.id == id));
+                if let Some(pos) = position {
+                    self.stream_state.selected = Some(pos);
+                }
+            }
+            Message::TagsLoaded { .. } => {}
+            Message::TagsEdited {
+                provider_id,
+                changed,
+            } => {
+                self.status_message = format!("Retagged {} bookmark(s)", changed);
+                self.add_toast(Toast::success(format!("Retagged {} bookmark(s)", changed)));
+                let _ = self.cmd_tx.send(DaemonCommand::FetchTags(provider_id));
+                if self.viewing_tag().is_some() {
+                    self.fetch_items_for_selected_stream();
+                }
+            }
+            Message::DuplicatesLoaded(groups) => {
+                // List the groups back to back, each oldest first
+                self.items = groups
+                    .iter()
+                    .flat_map(|g| g.items.iter().cloned())
+                    .collect();
+                self.duplicates = groups;
+                self.item_state = ListState::new(self.items.len());
+                if self.duplicates.is_empty() {
+                    self.status_message = "No duplicate bookmarks".to_string();
+                    self.add_toast(Toast::success("No duplicate bookmarks"));
+                } else {
+                    self.item_state.select_first();
+                    self.focused = FocusedPane::ItemList;
+                    self.on_item_selected();
+                    let by_url = self
+                        .duplicates
+                        .iter()
+                        .filter(|g| g.reason == "same_url")
+                        .count();
+                    self.status_message = format!(
+                        "{} group(s) of duplicate bookmarks ({} by URL, {} by title) - select the one to keep and :duplicates merge",
+                        self.duplicates.len(),
+                        by_url,
+                        self.duplicates.len() - by_url
+                    );
+                }
+            }
+            Message::BookmarksMerged { kept, merged } => {
+                self.status_message = format!("Merged {} bookmark(s) into {}", merged, kept);
+                self.add_toast(Toast::success(format!("Merged {} bookmark(s)", merged)));
+                let _ = self.cmd_tx.send(DaemonCommand::FetchDuplicates);
+                let _ = self
+                    .cmd_tx
+                    .send(DaemonCommand::FetchTags(TAG_PROVIDER_ID.to_string()));
+            }
+            Message::AlertCreated(name) => {
+                self.status_message =
+                    format!("Alert {} saved - new matches appear under Alerts", name);
+                self.add_toast(Toast::success(format!("Alert {} saved", name)));
+            }
+            Message::Published(summary) => {
+                self.status_message = format!(
+                    "Published {} item(s) to {}",
+                    summary.items_exported, summary.path
+                );
+                self.add_toast(Toast::success("Published"));
+            }
             Message::Error(err) => {
                 self.status_message = format!("Error: {}", err);
                 self.daemon_connected = false;
@@ -409,6 +709,330 @@ impl App {
                 self.status_message = "Item removed from collection".to_string();
                 self.add_toast(Toast::success("Removed from collection"));
             }
+            Message::SentToNotes(path) => {
+                self.status_message = format!("Sent to notes: {}", path);
+                self.add_toast(Toast::success("Sent to notes"));
+            }
+            Message::InviteAnswered(message) => {
+                self.status_message = message;
+                self.add_toast(Toast::success("Reply sent"));
+            }
+            Message::ShareTargetsLoaded(targets) => {
+                self.share_targets = Some(targets);
+                if self.share_picker_active {
+                    self.prompt_share_target();
+                }
+            }
+            Message::ItemShared(shared) => {
+                if shared.copy {
+                    match self
+                        .clipboard
+                        .get_or_insert_with(Clipboard::new)
+                        .copy(&shared.text)
+                    {
+                        Ok(backend) => {
+                            self.add_toast(Toast::success(format!("Copied via {}", backend)))
+                        }
+                        Err(e) => self.add_toast(Toast::error(format!("Copy failed: {}", e))),
+                    }
+                } else {
+                    self.add_toast(Toast::success(shared.message.clone()));
+                }
+                self.status_message = shared.message;
+            }
+            Message::ItemSummarized { item_id, summary } => {
+                if let Some(item) = self.items.iter_mut().find(|i| i.id.as_str() == item_id) {
+                    item.metadata.insert("summary".to_string(), summary);
+                }
+                self.status_message = "Summary ready".to_string();
+            }
+            Message::TranscriptFetched {
+                item_id,
+                transcript,
+            } => {
+                if let Some(item) = self.items.iter_mut().find(|i| i.id.as_str() == item_id) {
+                    item.metadata.insert("transcript".to_string(), transcript);
+                }
+                self.status_message = "Transcript ready".to_string();
+            }
+            Message::RelatedLoaded { item_id, related } => {
+                self.related = Some((item_id, related));
+            }
+            Message::ChangesLoaded { item_id, changes } => {
+                // The daemon has cleared the badge; the preview shows the changes
+                if let Some(item) = self.items.iter_mut().find(|i| i.id.as_str() == item_id) {
+                    item.metadata.remove("changed_at");
+                }
+                self.changes = Some((item_id, changes));
+            }
+            Message::ItemOpened { item_id, opened } => {
+                if let Some(item) = self.items.iter_mut().find(|i| i.id.as_str() == item_id) {
+                    item.is_read = true;
+                }
+                self.status_message = format!("Opened {} with {}", opened.kind, opened.command);
+            }
+            Message::LinksLoaded { item_id, links } => {
+                if self.links_view_active {
+                    self.status_message = match links.len() {
+                        0 => "No links in this item".to_string(),
+                        1 => "1 link".to_string(),
+                        n => format!("{} links", n),
+                    };
+                    self.link_state = ListState::new(links.len());
+                    self.links = Some((item_id, links));
+                    self.fetch_selected_link_title();
+                }
+            }
+            Message::LinkTitleFetched { url, title } => {
+                if let Some((_, links)) = &mut self.links {
+                    for link in links.iter_mut().filter(|link| link.url == url) {
+                        link.title = title.clone();
+                    }
+                }
+            }
+            Message::LinkOpened(opened) => {
+                self.status_message =
+                    format!("Opened {} link with {}", opened.kind, opened.command);
+            }
+            Message::LinkBookmarked(_) => {
+                self.status_message = "Bookmarked link".to_string();
+                self.add_toast(Toast::success("Bookmarked link"));
+            }
+            Message::ReadAloudQueued(ahead) => {
+                self.status_message = match ahead {
+                    0 => "Reading aloud".to_string(),
+                    n => format!("Queued for reading aloud ({} ahead)", n),
+                };
+            }
+            Message::ReadingStopped => {
+                self.status_message = "Stopped reading aloud".to_string();
+            }
+            Message::PlaybackStarted(playback) => {
+                self.status_message = match playback.start_secs {
+                    0 => format!("Playing {}", playback.kind),
+                    secs => format!(
+                        "Resuming {} at {}",
+                        playback.kind,
+                        time::format_duration(secs)
+                    ),
+                };
+            }
+            Message::PlaybackStopped(stopped) => {
+                self.status_message = if stopped {
+                    "Stopped playback".to_string()
+                } else {
+                    "Nothing is playing".to_string()
+                };
+            }
+            Message::DownloadsQueued(downloads) => {
+                self.status_message = match downloads.as_slice() {
+                    [download] => format!("Downloading {}", download.file_name),
+                    downloads => format!("Downloading {} files", downloads.len()),
+                };
+                if self.downloads_view_active {
+                    let _ = self.cmd_tx.send(DaemonCommand::FetchDownloads);
+                }
+            }
+            Message::DownloadsLoaded(downloads) => {
+                self.download_state.update_len(downloads.len());
+                self.downloads = downloads;
+            }
+            Message::ReadingStatsLoaded { days, stats } => {
+                // Drop answers for a window the user has since moved off
+                if days == self.stats_days {
+                    self.stats_state.update_len(stats.streams.len());
+                    self.reading_stats = Some(stats);
+                }
+            }
+            Message::FocusStarted(session) => {
+                self.status_message = format!(
+                    "Focusing on {} stream(s) until {}",
+                    session.stream_ids.len(),
+                    session
+                        .ends_at
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M")
+                );
+                self.focus_marked.clear();
+                self.focus_session = Some(session);
+            }
+            Message::FocusLoaded(session) => {
+                self.focus_session = session;
+            }
+            Message::FocusStopped(report) => match report {
+                Some(report) => self.end_focus(&report),
+                None => {
+                    self.focus_session = None;
+                    self.status_message = "No focus session is running".to_string();
+                }
+            },
+            Message::TriageLoaded(view) => {
+                // Drop answers that arrive after triage was left
+                if self.triage_view_active {
+                    let last = match view.undo.as_ref().map(|step| step.action) {
+                        Some(TriageAction::Archive) => "Archived. ",
+                        Some(TriageAction::Save) => "Saved. ",
+                        Some(TriageAction::Snooze) => "Snoozed. ",
+                        Some(TriageAction::Skip) => "Skipped. ",
+                        None => "",
+                    };
+                    self.status_message = match &view.item {
+                        Some(_) => format!(
+                            "{}Triage: {} left - e:archive s:save z:snooze Space:skip u:undo Esc:done",
+                            last, view.remaining
+                        ),
+                        None => format!("{}Inbox is empty - u:undo Esc:done", last),
+                    };
+                    self.triage = Some(*view);
+                }
+            }
+            Message::DownloadRetried(download) => {
+                self.status_message = format!("Retrying {}", download.file_name);
+                if let Some(entry) = self.downloads.iter_mut().find(|d| d.id == download.id) {
+                    *entry = download;
+                }
+            }
+            Message::Event(event) => self.handle_daemon_event(event),
+        }
+    }
+
+    fn handle_daemon_event(&mut self, event: DaemonEvent) {
+        match event {
+            DaemonEvent::SyncStarted { provider_id } => {
+                self.provider_statuses
+                    .insert(provider_id, ProviderSyncStatus::Syncing);
+            }
+            DaemonEvent::SyncFinished {
+                provider_id,
+                success,
+                error,
+                failed_streams,
+            } => {
+                let prefix = format!("{}:", provider_id);
+                self.failed_streams.retain(|id| !id.starts_with(&prefix));
+                if success && !failed_streams.is_empty() {
+                    self.add_toast(Toast::error(format!(
+                        "{}: {} stream(s) failed to sync",
+                        provider_id,
+                        failed_streams.len()
+                    )));
+                }
+                self.failed_streams.extend(failed_streams);
+                if success {
+                    self.provider_statuses
+                        .insert(provider_id, ProviderSyncStatus::Synced);
+                } else {
+                    self.add_toast(Toast::error(format!(
+                        "{} sync failed: {}",
+                        provider_id,
+                        error.unwrap_or_default()
+                    )));
+                    self.provider_statuses
+                        .insert(provider_id, ProviderSyncStatus::Error);
+                }
+            }
+            DaemonEvent::NewItems {
+                provider_id,
+                items_added,
+                ..
+            } => {
+                if items_added > 0 {
+                    self.add_toast(Toast::info(format!(
+                        "{} new item(s) from {}",
+                        items_added, provider_id
+                    )));
+                }
+
+                // Reload the visible list if it belongs to the provider that changed
+                let selected = self
+                    .stream_state
+                    .selected
+                    .and_then(|idx| self.streams.get(idx));
+                if let Some(stream) = selected {
+                    if stream.provider_id == provider_id {
+                        let _ = self
+                            .cmd_tx
+                            .send(DaemonCommand::FetchItems(stream.id.as_str().to_string()));
+                    }
+                }
+            }
+            DaemonEvent::PlaybackFinished {
+                item_id,
+                exit_code,
+                position_secs,
+                finished,
+            } => {
+                if finished {
+                    if let Some(item) = self.items.iter_mut().find(|i| i.id.as_str() == item_id) {
+                        item.is_read = true;
+                    }
+                    self.status_message = "Finished playing".to_string();
+                } else if let Some(code @ 1..) = exit_code {
+                    self.add_toast(Toast::error(format!("Player exited with code {}", code)));
+                } else if let Some(secs) = position_secs {
+                    self.status_message =
+                        format!("Stopped playing at {}", time::format_duration(secs));
+                }
+            }
+            DaemonEvent::DownloadProgress {
+                download_id,
+                bytes,
+                total_bytes,
+            } => {
+                if let Some(download) = self.downloads.iter_mut().find(|d| d.id == download_id) {
+                    download.state = DownloadState::Downloading;
+                    download.bytes = bytes;
+                    download.total_bytes = total_bytes;
+                }
+            }
+            DaemonEvent::DownloadFinished {
+                download_id,
+                state,
+                path,
+                error,
+            } => {
+                let name = match self.downloads.iter_mut().find(|d| d.id == download_id) {
+                    Some(download) => {
+                        download.state = state;
+                        download.path.clone_from(&path);
+                        download.error.clone_from(&error);
+                        download.file_name.clone()
+                    }
+                    None => "Download".to_string(),
+                };
+                if self.downloads_view_active {
+                    // Completed sizes and yt-dlp file names are only in the list
+                    let _ = self.cmd_tx.send(DaemonCommand::FetchDownloads);
+                }
+                match state {
+                    DownloadState::Completed => self.add_toast(Toast::success(format!(
+                        "Downloaded {}",
+                        path.as_deref().unwrap_or(&name)
+                    ))),
+                    DownloadState::Failed => self.add_toast(Toast::error(format!(
+                        "{} failed: {}",
+                        name,
+                        error.unwrap_or_default()
+                    ))),
+                    _ => {}
+                }
+            }
+            DaemonEvent::FocusEnded {
+                completed,
+                read,
+                saved,
+            } => {
+                self.end_focus(&FocusReport {
+                    completed,
+                    read,
+                    saved,
+                });
+            }
+            DaemonEvent::AlertMatched { name, title, .. } => {
+                self.add_toast(Toast::info(format!("Alert {}: {}", name, title)));
+                let _ = self.cmd_tx.send(DaemonCommand::FetchAlerts);
+            }
+            DaemonEvent::ActionCompleted { .. } | DaemonEvent::Unknown => {}
         }
     }
 
@@ -439,20 +1063,68 @@ impl App {
             .split(main_chunks[0]);
 
         // Render streams
+        let focus_streams = self.focus_session.as_ref().map(|s| s.stream_ids.as_slice());
         StreamListWidget::new(&self.streams, self.stream_state.selected, &self.theme)
             .focused(self.focused == FocusedPane::StreamList)
+            .focus_streams(focus_streams)
+            .marked(&self.focus_marked)
+            .failed(&self.failed_streams)
             .render(content_chunks[0], buffer);
 
         // Render items
         ItemListWidget::new(&self.items, self.item_state.selected, &self.theme)
             .focused(self.focused == FocusedPane::ItemList)
+            .pinned(&self.pinned)
+            .search_results(&self.search_results)
             .render(content_chunks[1], buffer);
 
         // Render preview
         let selected_item = self.item_state.selected.and_then(|i| self.items.get(i));
-        PreviewWidget::new(selected_item, &self.theme)
-            .focused(self.focused == FocusedPane::Preview)
+        let related = match (&self.related, selected_item) {
+            (Some((item_id, related)), Some(item)) if item.id.as_str() == item_id => {
+                related.as_slice()
+            }
+            _ => &[],
+        };
+        let changes = match (&self.changes, selected_item) {
+            (Some((item_id, changes)), Some(item)) if item.id.as_str() == item_id => {
+                changes.as_slice()
+            }
+            _ => &[],
+        };
+        if self.triage_view_active {
+            let item = self.triage.as_ref().and_then(|view| view.item.as_ref());
+            PreviewWidget::new(item, &self.theme)
+                .focused(true)
+                .render(content_chunks[2], buffer);
+        } else if self.analytics_view_active {
+            AnalyticsWidget::new(
+                self.reading_stats.as_ref(),
+                self.stats_days,
+                self.stats_state.selected,
+                &self.theme,
+            )
             .render(content_chunks[2], buffer);
+        } else if self.downloads_view_active {
+            DownloadsWidget::new(&self.downloads, self.download_state.selected, &self.theme)
+                .focused(true)
+                .render(content_chunks[2], buffer);
+        } else if self.links_view_active {
+            let links = self
+                .links
+                .as_ref()
+                .map_or(&[][..], |(_, links)| links.as_slice());
+            LinksWidget::new(links, self.link_state.selected, &self.theme)
+                .focused(true)
+                .render(content_chunks[2], buffer);
+        } else {
+            PreviewWidget::new(selected_item, &self.theme)
+                .related(related)
+                .changes(changes)
+                .scroll(self.preview_scroll)
+                .focused(self.focused == FocusedPane::Preview)
+                .render(content_chunks[2], buffer);
+        }
 
         // Render omnibar
         OmnibarWidget::new(&self.omnibar_input, &self.theme)
@@ -480,17 +1152,29 @@ impl App {
             })
             .collect();
 
-        // Calculate total unread count
+        // Calculate total unread count, only over the focus session's streams while one runs;
+        // pinned, queued and alerted items are already counted in their own streams
         let unread_count: u32 = self
             .streams
             .iter()
+            .filter(|s| {
+                s.id.0 != PINNED_STREAM_ID
+                    && s.id.0 != QUEUE_STREAM_ID
+                    && s.id.0 != ALERTS_STREAM_ID
+            })
+            .filter(|s| focus_streams.is_none_or(|ids| ids.contains(&s.id.0)))
             .map(|s| s.unread_count.unwrap_or(0))
             .sum();
+        let focus_remaining = self
+            .focus_session
+            .as_ref()
+            .map(|s| (s.ends_at - chrono::Utc::now()).num_seconds().max(0) as u64);
 
         StatusBarWidget::new(&self.status_message, connection_status, &self.theme)
             .provider_statuses(&provider_statuses)
             .unread_count(unread_count)
             .search_filter(self.active_search_filter.as_deref())
+            .focus_remaining(focus_remaining)
             .render(main_chunks[2], buffer);
 
         // Render toasts (overlay on top-right)
@@ -502,8 +1186,16 @@ impl App {
 
     /// Get unique provider IDs from streams
     fn get_unique_providers(&self) -> Vec<String> {
-        let mut providers: Vec<String> =
-            self.streams.iter().map(|s| s.provider_id.clone()).collect();
+        let mut providers: Vec<String> = self
+            .streams
+            .iter()
+            .filter(|s| {
+                s.id.0 != PINNED_STREAM_ID
+                    && s.id.0 != QUEUE_STREAM_ID
+                    && s.id.0 != ALERTS_STREAM_ID
+            })
+            .map(|s| s.provider_id.clone())
+            .collect();
         providers.sort();
         providers.dedup();
         providers
@@ -562,6 +1254,158 @@ impl App {
                     return true;
                 }
 
+                // Handle the share picker when active; a digit picks a target
+                if self.share_picker_active {
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.share_picker_active = false;
+                            self.status_message = "Share cancelled".to_string();
+                        }
+                        KeyCode::Char(c @ '1'..='9') => {
+                            self.share_selected_item(c as usize - '1' as usize);
+                        }
+                        _ => {}
+                    }
+                    return true;
+                }
+
+                // Handle triage when active; every key is a single decision
+                if self.triage_view_active {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('I') => {
+                            self.triage_view_active = false;
+                            self.triage = None;
+                            self.status_message = "Left triage".to_string();
+                            // Triage changed read and saved state behind the lists
+                            self.fetch_items_for_selected_stream();
+                        }
+                        KeyCode::Char('e') => self.triage_current(TriageAction::Archive),
+                        KeyCode::Char('s') => self.triage_current(TriageAction::Save),
+                        KeyCode::Char('z') => self.triage_current(TriageAction::Snooze),
+                        KeyCode::Char(' ') | KeyCode::Char('j') | KeyCode::Down => {
+                            self.triage_current(TriageAction::Skip);
+                        }
+                        KeyCode::Char('u') => {
+                            let _ = self.cmd_tx.send(DaemonCommand::UndoTriage);
+                        }
+                        KeyCode::Char('q') => {
+                            self.quit = true;
+                            return false;
+                        }
+                        _ => {}
+                    }
+                    return true;
+                }
+
+                // Handle the downloads view when active
+                if self.downloads_view_active {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('D') => {
+                            self.downloads_view_active = false;
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.download_state.select_next();
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.download_state.select_prev();
+                        }
+                        KeyCode::Char('x') => {
+                            if let Some(download) = self.selected_download() {
+                                let _ =
+                                    self.cmd_tx.send(DaemonCommand::CancelDownload(download.id));
+                            }
+                        }
+                        KeyCode::Char('R') => {
+                            if let Some(download) = self.selected_download() {
+                                let _ = self.cmd_tx.send(DaemonCommand::RetryDownload(download.id));
+                            }
+                        }
+                        KeyCode::Char('q') => {
+                            self.quit = true;
+                            return false;
+                        }
+                        _ => {}
+                    }
+                    return true;
+                }
+
+                // Handle the links view when active
+                if self.links_view_active {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('L') => {
+                            self.links_view_active = false;
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.link_state.select_next();
+                            self.fetch_selected_link_title();
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.link_state.select_prev();
+                            self.fetch_selected_link_title();
+                        }
+                        KeyCode::Enter => {
+                            if let Some((item_id, url)) = self.selected_link() {
+                                let _ = self.cmd_tx.send(DaemonCommand::OpenLink { item_id, url });
+                            }
+                        }
+                        KeyCode::Char('b') => {
+                            if let Some((item_id, url)) = self.selected_link() {
+                                let _ = self
+                                    .cmd_tx
+                                    .send(DaemonCommand::BookmarkLink { item_id, url });
+                                self.status_message = "Bookmarking link...".to_string();
+                            }
+                        }
+                        KeyCode::Char('y') => {
+                            if let Some((_, url)) = self.selected_link() {
+                                match self.clipboard.get_or_insert_with(Clipboard::new).copy(&url) {
+                                    Ok(backend) => self.add_toast(Toast::success(format!(
+                                        "Copied link via {}",
+                                        backend
+                                    ))),
+                                    Err(e) => {
+                                        self.add_toast(Toast::error(format!("Copy failed: {}", e)))
+                                    }
+                                }
+                            }
+                        }
+                        KeyCode::Char('q') => {
+                            self.quit = true;
+                            return false;
+                        }
+                        _ => {}
+                    }
+                    return true;
+                }
+
+                // Handle the analytics view when active
+                if self.analytics_view_active {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('A') => {
+                            self.analytics_view_active = false;
+                        }
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            self.stats_state.select_next();
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            self.stats_state.select_prev();
+                        }
+                        KeyCode::Char('t') => {
+                            let next = STATS_WINDOWS
+                                .iter()
+                                .position(|&days| days == self.stats_days)
+                                .map_or(0, |i| (i + 1) % STATS_WINDOWS.len());
+                            self.open_analytics(STATS_WINDOWS[next]);
+                        }
+                        KeyCode::Char('q') => {
+                            self.quit = true;
+                            return false;
+                        }
+                        _ => {}
+                    }
+                    return true;
+                }
+
                 // Handle omnibar input when active
                 if self.omnibar_active {
                     match key.code {
@@ -621,27 +1465,94 @@ impl App {
                         self.navigate_last();
                     }
                     KeyCode::Enter => {
-                        // TODO: Open selected item
-                        self.status_message = "Open item (not implemented)".to_string();
+                        self.open_selected_item();
                     }
                     KeyCode::Char('s') => {
                         self.toggle_save_item();
                     }
+                    KeyCode::Char('b') => {
+                        self.toggle_pin_item();
+                    }
                     KeyCode::Char('r') => {
                         self.toggle_read_status();
                     }
                     KeyCode::Char('e') => {
                         self.archive_selected_item();
                     }
+                    KeyCode::Char('J') => {
+                        self.toggle_spam_selected_item();
+                    }
                     KeyCode::Char('a') => {
                         self.show_collection_picker();
                     }
                     KeyCode::Char('d') => {
                         self.remove_item_from_current_collection();
                     }
+                    KeyCode::Char('n') => {
+                        self.send_selected_to_notes();
+                    }
+                    KeyCode::Char('Y') => {
+                        self.respond_to_invite(true);
+                    }
+                    KeyCode::Char('N') => {
+                        self.respond_to_invite(false);
+                    }
+                    KeyCode::Char('y') => {
+                        self.copy_selected_link();
+                    }
+                    KeyCode::Char('m') => {
+                        self.show_share_picker();
+                    }
+                    KeyCode::Char('S') => {
+                        self.summarize_selected_item();
+                    }
+                    KeyCode::Char('T') => {
+                        self.fetch_selected_transcript();
+                    }
+                    KeyCode::Char('p') => {
+                        self.read_selected_aloud();
+                    }
+                    KeyCode::Char('P') => {
+                        let _ = self.cmd_tx.send(DaemonCommand::StopReading);
+                    }
+                    KeyCode::Char('v') => {
+                        self.play_selected_item();
+                    }
+                    KeyCode::Char('V') => {
+                        let _ = self.cmd_tx.send(DaemonCommand::StopPlayback);
+                    }
+                    KeyCode::Char('w') => {
+                        self.download_selected_item();
+                    }
+                    KeyCode::Char('D') => {
+                        self.downloads_view_active = true;
+                        let _ = self.cmd_tx.send(DaemonCommand::FetchDownloads);
+                    }
+                    KeyCode::Char('L') => {
+                        self.open_links_view();
+                    }
+                    KeyCode::Char('A') => {
+                        self.open_analytics(self.stats_days);
+                    }
+                    KeyCode::Char('I') => {
+                        self.triage_view_active = true;
+                        self.triage = None;
+                        self.status_message = "Starting triage...".to_string();
+                        let _ = self.cmd_tx.send(DaemonCommand::StartTriage);
+                    }
+                    KeyCode::Char('f') => {
+                        self.toggle_focus_mark();
+                    }
+                    KeyCode::Char('F') => {
+                        if self.focus_session.is_some() {
+                            let _ = self.cmd_tx.send(DaemonCommand::StopFocus);
+                        } else {
+                            self.start_focus(None);
+                        }
+                    }
                     KeyCode::Char('?') => {
                         self.status_message =
-                            "h/l:panes j/k:nav /:search r:read/unread e:archive s:save a:add-to-collection d:remove-from-collection q:quit"
+                            "h/l:panes j/k:nav /:search r:read/unread e:archive J:spam s:save b:pin y:copy-link m:share n:send-to-notes Y/N:accept/decline-invite S:summarize T:transcript p/P:read-aloud/stop v/V:play/stop w:download D:downloads L:links A:analytics I:triage f/F:mark/focus a:add-to-collection d:remove-from-collection q:quit"
                                 .to_string();
                     }
                     _ => {}
@@ -673,7 +1584,10 @@ impl App {
             }
             FocusedPane::ItemList => {
                 self.item_state.select_next();
-                self.auto_mark_selected_as_read();
+                self.on_item_selected();
+            }
+            FocusedPane::Preview => {
+                self.preview_scroll = self.preview_scroll.saturating_add(1);
             }
             _ => {}
         }
@@ -691,7 +1605,10 @@ impl App {
             }
             FocusedPane::ItemList => {
                 self.item_state.select_prev();
-                self.auto_mark_selected_as_read();
+                self.on_item_selected();
+            }
+            FocusedPane::Preview => {
+                self.preview_scroll = self.preview_scroll.saturating_sub(1);
             }
             _ => {}
         }
@@ -708,7 +1625,10 @@ impl App {
             }
             FocusedPane::ItemList => {
                 self.item_state.select_first();
-                self.auto_mark_selected_as_read();
+                self.on_item_selected();
+            }
+            FocusedPane::Preview => {
+                self.preview_scroll = 0;
             }
             _ => {}
         }
@@ -725,13 +1645,54 @@ impl App {
             }
             FocusedPane::ItemList => {
                 self.item_state.select_last();
-                self.auto_mark_selected_as_read();
+                self.on_item_selected();
             }
             _ => {}
         }
     }
 
     fn fetch_items_for_selected_stream(&mut self) {
+        if self.viewing_queue() {
+            // Show the queue we have, at the item to resume, while the daemon's loads
+            self.show_queue(None);
+            let _ = self.cmd_tx.send(DaemonCommand::FetchQueue);
+            self.status_message = format!(
+                "Reading queue: {} item(s), ~{} min",
+                self.queue.entries.len(),
+                self.queue.total_minutes
+            );
+            return;
+        }
+
+        if self.viewing_pinned() {
+            // Show the pins we have while the daemon's list loads
+            self.items = self.pinned.clone();
+            self.item_state = ListState::new(self.items.len());
+            self.item_state.select_first();
+            let _ = self.cmd_tx.send(DaemonCommand::FetchPinned);
+            self.status_message = format!("{} pinned item(s)", self.pinned.len());
+            return;
+        }
+
+        if self.viewing_alerts() {
+            // Show the matches we have while the daemon's list loads
+            self.items = self.alerts.clone();
+            self.item_state = ListState::new(self.items.len());
+            self.item_state.select_first();
+            let _ = self.cmd_tx.send(DaemonCommand::FetchAlerts);
+            self.status_message = format!("{} alert match(es)", self.alerts.len());
+            return;
+        }
+
+        if let Some(tag) = self.viewing_tag() {
+            self.status_message = format!("Loading bookmarks tagged #{}...", tag);
+            let _ = self.cmd_tx.send(DaemonCommand::FetchTagItems {
+                provider_id: TAG_PROVIDER_ID.to_string(),
+                tag,
+            });
+            return;
+        }
+
         if let Some(idx) = self.stream_state.selected {
             if let Some(stream) = self.streams.get(idx) {
                 let _ = self
@@ -747,6 +1708,155 @@ impl App {
         }
     }
 
+    /// The "Pinned" stream entry, counting the pinned items.
+    fn pinned_stream(&self) -> Stream {
+        Stream {
+            id: StreamId(PINNED_STREAM_ID.to_string()),
+            name: "Pinned".to_string(),
+            provider_id: "local".to_string(),
+            stream_type: StreamType::SavedItems,
+            icon: None,
+            unread_count: Some(self.pinned.iter().filter(|i| !i.is_read).count() as u32),
+            total_count: Some(self.pinned.len() as u32),
+            last_updated: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Whether the "Pinned" entry is the selected stream.
+    fn viewing_pinned(&self) -> bool {
+        self.stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx))
+            .is_some_and(|s| s.id.0 == PINNED_STREAM_ID)
+    }
+
+    /// The "Alerts" stream entry, counting the items saved searches matched.
+    fn alerts_stream(&self) -> Stream {
+        Stream {
+            id: StreamId(ALERTS_STREAM_ID.to_string()),
+            name: "Alerts".to_string(),
+            provider_id: "local".to_string(),
+            stream_type: StreamType::Feed,
+            icon: None,
+            unread_count: Some(self.alerts.iter().filter(|i| !i.is_read).count() as u32),
+            total_count: Some(self.alerts.len() as u32),
+            last_updated: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Whether the "Alerts" entry is the selected stream.
+    fn viewing_alerts(&self) -> bool {
+        self.stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx))
+            .is_some_and(|s| s.id.0 == ALERTS_STREAM_ID)
+    }
+
+    /// The "Reading Queue" stream entry, counting the queued items.
+    fn queue_stream(&self) -> Stream {
+        Stream {
+            id: StreamId(QUEUE_STREAM_ID.to_string()),
+            name: "Reading Queue".to_string(),
+            provider_id: "local".to_string(),
+            stream_type: StreamType::SavedItems,
+            icon: None,
+            unread_count: None,
+            total_count: Some(self.queue.entries.len() as u32),
+            last_updated: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Whether the "Reading Queue" entry is the selected stream.
+    fn viewing_queue(&self) -> bool {
+        self.stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx))
+            .is_some_and(|s| s.id.0 == QUEUE_STREAM_ID)
+    }
+
+    /// List the queued items, with the cursor on `item_id` if it's still
+    /// queued and on the item to resume otherwise.
+    fn show_queue(&mut self, item_id: Option<String>) {
+        self.items = self
+            .queue
+            .entries
+            .iter()
+            .map(|entry| entry.item.clone())
+            .collect();
+        self.item_state = ListState::new(self.items.len());
+        let target = item_id
+            .filter(|id| self.queue_entry(id).is_some())
+            .or_else(|| self.queue.resume.clone());
+        match target.and_then(|id| self.items.iter().position(|i| i.id.as_str() == id)) {
+            Some(pos) => self.item_state.selected = Some(pos),
+            None => self.item_state.select_first(),
+        }
+    }
+
+    fn queue_entry(&self, item_id: &str) -> Option<&QueueEntry> {
+        self.queue
+            .entries
+            .iter()
+            .find(|entry| entry.item.id.as_str() == item_id)
+    }
+
+    /// One entry per bookmark tag, sized by how many bookmarks carry it.
+    fn tag_streams(&self) -> Vec<Stream> {
+        self.tags
+            .iter()
+            .map(|tag| Stream {
+                id: StreamId(format!("{}{}", TAG_STREAM_PREFIX, tag.name)),
+                name: format!("#{}", tag.name),
+                provider_id: TAG_PROVIDER_ID.to_string(),
+                stream_type: StreamType::Collection,
+                icon: None,
+                unread_count: None,
+                total_count: Some(tag.count),
+                last_updated: None,
+                metadata: HashMap::new(),
+            })
+            .collect()
+    }
+
+    /// The tag whose entry is the selected stream, if one is.
+    fn viewing_tag(&self) -> Option<String> {
+        self.stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx))
+            .and_then(|s| s.id.0.strip_prefix(TAG_STREAM_PREFIX))
+            .map(str::to_string)
+    }
+
+    fn is_pinned(&self, item: &Item) -> bool {
+        self.pinned.iter().any(|p| p.id == item.id)
+    }
+
+    /// Pin or unpin the selected item. The daemon's reply reorders the
+    /// list and refreshes the "Pinned" entry.
+    fn toggle_pin_item(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to pin/unpin".to_string();
+            return;
+        }
+
+        let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) else {
+            return;
+        };
+        let item_id = item.id.as_str().to_string();
+        if self.is_pinned(item) {
+            let _ = self.cmd_tx.send(DaemonCommand::UnpinItem(item_id));
+            self.status_message = "Item unpinned".to_string();
+            self.add_toast(Toast::success("Unpinned"));
+        } else {
+            let _ = self.cmd_tx.send(DaemonCommand::PinItem(item_id));
+            self.status_message = "Item pinned".to_string();
+            self.add_toast(Toast::success("Pinned"));
+        }
+    }
+
     fn toggle_save_item(&mut self) {
         // Only toggle if we're focused on item list and have a selected item
         if self.focused != FocusedPane::ItemList {
@@ -772,6 +1882,8 @@ impl App {
                     self.status_message = "Item saved".to_string();
                     self.add_toast(Toast::success("Saved!"));
                 }
+                // Saved items make up the reading queue
+                let _ = self.cmd_tx.send(DaemonCommand::FetchQueue);
             }
         }
     }
@@ -790,6 +1902,8 @@ impl App {
                 // Update local state immediately for responsive UI
                 item.is_read = new_read_status;
 
+                let note = self.local_only_note(&item_id);
+
                 // Send command to daemon
                 let cmd = if new_read_status {
                     DaemonCommand::MarkItemRead(item_id)
@@ -799,13 +1913,357 @@ impl App {
                 let _ = self.cmd_tx.send(cmd);
 
                 self.status_message = format!(
-                    "Marked as {}",
-                    if new_read_status { "read" } else { "unread" }
+                    "Marked as {}{}",
+                    if new_read_status { "read" } else { "unread" },
+                    note
                 );
             }
         }
     }
 
+    /// Status suffix for actions that the item's provider cannot write back.
+    fn local_only_note(&self, item_id: &str) -> &'static str {
+        let provider_id = item_id.split(':').next().unwrap_or_default();
+        match self.provider_capabilities.get(provider_id) {
+            Some(caps) if !caps.supports_write_actions => " (local only)",
+            _ => "",
+        }
+    }
+
+    fn send_selected_to_notes(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to send to notes".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::SendToNotes(item.id.as_str().to_string()));
+            self.status_message = "Sending to notes...".to_string();
+        }
+    }
+
+    /// Accept or decline the meeting invitation in the selected email.
+    fn respond_to_invite(&mut self, accept: bool) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to answer an invitation".to_string();
+            return;
+        }
+
+        let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) else {
+            return;
+        };
+        if !CalendarInvite::from_item(item).is_some_and(|invite| invite.is_request()) {
+            self.status_message = "No invitation to answer".to_string();
+            return;
+        }
+        let _ = self.cmd_tx.send(DaemonCommand::RespondInvite {
+            item_id: item.id.as_str().to_string(),
+            accept,
+        });
+        self.status_message = if accept {
+            "Accepting invitation...".to_string()
+        } else {
+            "Declining invitation...".to_string()
+        };
+    }
+
+    fn copy_selected_link(&mut self) {
+        let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) else {
+            return;
+        };
+        let Some(url) = item.url.clone() else {
+            self.status_message = "Item has no link".to_string();
+            return;
+        };
+
+        match self.clipboard.get_or_insert_with(Clipboard::new).copy(&url) {
+            Ok(backend) => self.add_toast(Toast::success(format!("Copied link via {}", backend))),
+            Err(e) => self.add_toast(Toast::error(format!("Copy failed: {}", e))),
+        }
+    }
+
+    fn show_share_picker(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to share".to_string();
+            return;
+        }
+
+        if self.item_state.selected.is_none() {
+            self.status_message = "No item selected".to_string();
+            return;
+        }
+
+        self.share_picker_active = true;
+        if self.share_targets.is_none() {
+            let _ = self.cmd_tx.send(DaemonCommand::FetchShareTargets);
+            self.status_message = "Loading share targets...".to_string();
+        } else {
+            self.prompt_share_target();
+        }
+    }
+
+    fn prompt_share_target(&mut self) {
+        let targets = self.share_targets.as_deref().unwrap_or_default();
+        if targets.is_empty() {
+            self.share_picker_active = false;
+            self.status_message = "No share targets configured".to_string();
+            return;
+        }
+
+        let choices: Vec<String> = targets
+            .iter()
+            .take(9)
+            .enumerate()
+            .map(|(idx, target)| format!("{}:{}", idx + 1, target.name))
+            .collect();
+        self.status_message = format!("Share to {} (Esc to cancel)", choices.join(" "));
+    }
+
+    fn share_selected_item(&mut self, target_idx: usize) {
+        let Some(target) = self
+            .share_targets
+            .as_ref()
+            .and_then(|targets| targets.get(target_idx))
+        else {
+            return;
+        };
+        self.share_picker_active = false;
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self.cmd_tx.send(DaemonCommand::ShareItem {
+                item_id: item.id.as_str().to_string(),
+                target: target.id.clone(),
+            });
+            self.status_message = format!("Sharing to {}...", target.name);
+        }
+    }
+
+    fn summarize_selected_item(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to summarize".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            if item.metadata.contains_key("summary") {
+                self.status_message = "Item already summarized".to_string();
+                return;
+            }
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::SummarizeItem(item.id.as_str().to_string()));
+            self.status_message = "Summarizing...".to_string();
+        }
+    }
+
+    fn open_selected_item(&mut self) {
+        if self.focused == FocusedPane::StreamList {
+            self.focused = FocusedPane::ItemList;
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::OpenItem(item.id.as_str().to_string()));
+            self.status_message = "Opening...".to_string();
+        }
+    }
+
+    fn play_selected_item(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to play an item".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::PlayItem(item.id.as_str().to_string()));
+            self.status_message = "Starting player...".to_string();
+        }
+    }
+
+    fn download_selected_item(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to download an item".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::DownloadItem(item.id.as_str().to_string()));
+            self.status_message = "Queueing download...".to_string();
+        }
+    }
+
+    /// Pick or unpick the selected stream for the next focus session.
+    fn toggle_focus_mark(&mut self) {
+        let Some(stream) = self
+            .stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx))
+        else {
+            return;
+        };
+        if stream.id.0 == PINNED_STREAM_ID {
+            self.status_message = "Pinned items can't be a focus stream".to_string();
+            return;
+        }
+        if let Some(pos) = self.focus_marked.iter().position(|id| *id == stream.id.0) {
+            self.focus_marked.remove(pos);
+        } else {
+            self.focus_marked.push(stream.id.0.clone());
+        }
+        self.status_message = format!("{} stream(s) marked for focus", self.focus_marked.len());
+    }
+
+    /// Start a focus session on the marked streams, or on the selected
+    /// stream if none are marked.
+    fn start_focus(&mut self, minutes: Option<u32>) {
+        if self.focus_session.is_some() {
+            self.status_message = "A focus session is already running".to_string();
+            return;
+        }
+
+        let stream_ids = if self.focus_marked.is_empty() {
+            match self
+                .stream_state
+                .selected
+                .and_then(|idx| self.streams.get(idx))
+            {
+                Some(stream) if stream.id.0 != PINNED_STREAM_ID => vec![stream.id.0.clone()],
+                _ => {
+                    self.status_message = "Select a stream to focus on".to_string();
+                    return;
+                }
+            }
+        } else {
+            self.focus_marked.clone()
+        };
+        let _ = self.cmd_tx.send(DaemonCommand::StartFocus {
+            stream_ids,
+            minutes,
+        });
+    }
+
+    /// Clear the focus session and tell the user what was processed.
+    ///
+    /// Both the `focus.stop` reply and the `focus_ended` event land here;
+    /// only the first of them reports.
+    fn end_focus(&mut self, report: &FocusReport) {
+        if self.focus_session.take().is_none() {
+            return;
+        }
+        let message = format!(
+            "Focus session {}: {} read, {} saved",
+            if report.completed { "done" } else { "stopped" },
+            report.read,
+            report.saved
+        );
+        self.status_message = message.clone();
+        self.add_toast(Toast::success(message));
+    }
+
+    /// Show reading statistics for the last `days` days, refetched each time.
+    fn open_analytics(&mut self, days: u32) {
+        self.analytics_view_active = true;
+        self.stats_days = days;
+        self.reading_stats = None;
+        let _ = self.cmd_tx.send(DaemonCommand::FetchReadingStats(days));
+    }
+
+    /// Send a triage decision on the item triage is showing.
+    fn triage_current(&mut self, action: TriageAction) {
+        let item = self.triage.as_ref().and_then(|view| view.item.as_ref());
+        if let Some(item) = item {
+            let _ = self.cmd_tx.send(DaemonCommand::TriageItem {
+                item_id: item.id.as_str().to_string(),
+                action,
+            });
+        }
+    }
+
+    fn open_links_view(&mut self) {
+        if self.focused == FocusedPane::StreamList {
+            self.status_message = "Focus on item list to list links".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::FetchLinks(item.id.as_str().to_string()));
+            self.links_view_active = true;
+            self.links = None;
+            self.link_state = ListState::new(0);
+            self.status_message = "Loading links...".to_string();
+        }
+    }
+
+    /// The item the links view was opened on and the selected link's URL.
+    fn selected_link(&self) -> Option<(String, String)> {
+        let (item_id, links) = self.links.as_ref()?;
+        let link = self.link_state.selected.and_then(|idx| links.get(idx))?;
+        Some((item_id.clone(), link.url.clone()))
+    }
+
+    /// Ask for the selected link's title, the first time it's selected.
+    fn fetch_selected_link_title(&mut self) {
+        let Some((item_id, links)) = &self.links else {
+            return;
+        };
+        let Some(link) = self.link_state.selected.and_then(|idx| links.get(idx)) else {
+            return;
+        };
+        if link.title.is_none() && self.requested_link_titles.insert(link.url.clone()) {
+            let _ = self.cmd_tx.send(DaemonCommand::FetchLinkTitle {
+                item_id: item_id.clone(),
+                url: link.url.clone(),
+            });
+        }
+    }
+
+    fn selected_download(&self) -> Option<&Download> {
+        self.download_state
+            .selected
+            .and_then(|idx| self.downloads.get(idx))
+    }
+
+    fn fetch_selected_transcript(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to fetch a transcript".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            if item.metadata.contains_key("transcript") {
+                self.status_message = "Transcript already loaded".to_string();
+                return;
+            }
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::FetchTranscript(item.id.as_str().to_string()));
+            self.status_message = "Fetching transcript...".to_string();
+        }
+    }
+
+    fn read_selected_aloud(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to read aloud".to_string();
+            return;
+        }
+
+        if let Some(item) = self.item_state.selected.and_then(|idx| self.items.get(idx)) {
+            let _ = self
+                .cmd_tx
+                .send(DaemonCommand::ReadAloud(item.id.as_str().to_string()));
+        }
+    }
+
     fn archive_selected_item(&mut self) {
         if self.focused != FocusedPane::ItemList {
             self.status_message = "Focus on item list to archive".to_string();
@@ -815,8 +2273,8 @@ impl App {
         if let Some(idx) = self.item_state.selected {
             if let Some(item) = self.items.get(idx) {
                 let item_id = item.id.as_str().to_string();
+                self.status_message = format!("Item archived{}", self.local_only_note(&item_id));
                 let _ = self.cmd_tx.send(DaemonCommand::ArchiveItem(item_id));
-                self.status_message = "Item archived".to_string();
                 self.add_toast(Toast::success("Archived"));
 
                 // Remove from current view
@@ -827,6 +2285,136 @@ impl App {
         }
     }
 
+    /// Report the selected email as spam, or as not spam when it already sits
+    /// in a Junk folder. Either way it leaves the current view.
+    fn toggle_spam_selected_item(&mut self) {
+        if self.focused != FocusedPane::ItemList {
+            self.status_message = "Focus on item list to report spam".to_string();
+            return;
+        }
+
+        let Some(idx) = self.item_state.selected else {
+            return;
+        };
+        let Some(item) = self.items.get(idx) else {
+            return;
+        };
+        let Some(mailbox) = item.metadata.get("mailbox") else {
+            self.status_message = "Spam reporting is only available for email".to_string();
+            return;
+        };
+
+        let lower = mailbox.to_lowercase();
+        let item_id = item.id.as_str().to_string();
+        if lower.contains("junk") || lower.contains("spam") {
+            self.status_message = "Marked as not spam".to_string();
+            let _ = self.cmd_tx.send(DaemonCommand::MarkNotSpam(item_id));
+            self.add_toast(Toast::success("Not spam"));
+        } else {
+            self.status_message = "Marked as spam".to_string();
+            let _ = self.cmd_tx.send(DaemonCommand::MarkSpam(item_id));
+            self.add_toast(Toast::success("Reported spam"));
+        }
+
+        self.items.remove(idx);
+        self.item_state.update_len(self.items.len());
+    }
+
+    /// Mark the newly selected item as read and look up its related items,
+    /// and what changed in it if it was updated.
+    fn on_item_selected(&mut self) {
+        self.auto_mark_selected_as_read();
+        let selected = self.item_state.selected.and_then(|idx| self.items.get(idx));
+        if let Some(item) = selected {
+            let item_id = item.id.as_str().to_string();
+            if item.metadata.contains_key("changed_at") {
+                let _ = self
+                    .cmd_tx
+                    .send(DaemonCommand::FetchChanges(item_id.clone()));
+            }
+            if self.related.as_ref().is_none_or(|(id, _)| *id != item_id) {
+                let _ = self.cmd_tx.send(DaemonCommand::FetchRelated(item_id));
+            }
+        }
+        self.sync_preview_scroll();
+    }
+
+    /// Start the preview of a newly selected item at the top, or, for a
+    /// queued item, where the reader left off. The queue records how far
+    /// the item left behind was scrolled and that the new one was opened.
+    fn sync_preview_scroll(&mut self) {
+        let selected_id = self
+            .item_state
+            .selected
+            .and_then(|idx| self.items.get(idx))
+            .map(|item| item.id.as_str().to_string());
+        if selected_id == self.preview_item {
+            return;
+        }
+
+        self.record_preview_position();
+        let queued = selected_id.as_deref().and_then(|id| {
+            self.queue
+                .entries
+                .iter()
+                .position(|e| e.item.id.as_str() == id)
+        });
+        self.preview_scroll = 0;
+        if let Some(idx) = queued {
+            let entry = &self.queue.entries[idx];
+            self.preview_scroll = u16::try_from(entry.offset).unwrap_or(u16::MAX);
+            let _ = self.cmd_tx.send(DaemonCommand::QueueProgress {
+                item_id: entry.item.id.as_str().to_string(),
+                offset: entry.offset,
+            });
+        }
+        self.preview_item = selected_id;
+        self.show_queue_position();
+    }
+
+    /// Record how far the previewed item was scrolled if it's queued.
+    fn record_preview_position(&mut self) {
+        let offset = u32::from(self.preview_scroll);
+        let Some(id) = self.preview_item.as_deref() else {
+            return;
+        };
+        let entry = self
+            .queue
+            .entries
+            .iter_mut()
+            .find(|e| e.item.id.as_str() == id);
+        if let Some(entry) = entry.filter(|entry| entry.offset != offset) {
+            entry.offset = offset;
+            let _ = self.cmd_tx.send(DaemonCommand::QueueProgress {
+                item_id: entry.item.id.as_str().to_string(),
+                offset,
+            });
+        }
+    }
+
+    /// Show where the selected item is in the reading queue and how long the
+    /// rest of the queue takes, while the queue is listed.
+    fn show_queue_position(&mut self) {
+        if !self.viewing_queue() {
+            return;
+        }
+        let selected = self.item_state.selected.and_then(|idx| self.items.get(idx));
+        let Some(idx) =
+            selected.and_then(|item| self.queue.entries.iter().position(|e| e.item.id == item.id))
+        else {
+            return;
+        };
+        let entry = &self.queue.entries[idx];
+        let to_go: u32 = self.queue.entries[idx..].iter().map(|e| e.minutes).sum();
+        self.status_message = format!(
+            "Queue {}/{} · ~{} min · ~{} min to go",
+            entry.position,
+            self.queue.entries.len(),
+            entry.minutes,
+            to_go
+        );
+    }
+
     fn auto_mark_selected_as_read(&mut self) {
         if let Some(idx) = self.item_state.selected {
             if let Some(item) = self.items.get_mut(idx) {
@@ -937,6 +2525,9 @@ impl App {
                 self.status_message = "Refreshing...".to_string();
                 self.add_toast(Toast::info("Refreshing..."));
                 let _ = self.cmd_tx.send(DaemonCommand::FetchStreams);
+                let _ = self
+                    .cmd_tx
+                    .send(DaemonCommand::FetchTags(TAG_PROVIDER_ID.to_string()));
             }
             Some(Command::Help) => {
                 self.status_message = "Help: Type :h for commands, / for search".to_string();
@@ -952,6 +2543,30 @@ impl App {
             Some(Command::Theme(theme_cmd)) => {
                 self.handle_theme_command(theme_cmd);
             }
+            Some(Command::Focus(command::FocusCommand::Start(minutes))) => {
+                self.start_focus(minutes);
+            }
+            Some(Command::Focus(command::FocusCommand::Stop)) => {
+                let _ = self.cmd_tx.send(DaemonCommand::StopFocus);
+            }
+            Some(Command::Tag(tag_cmd)) => {
+                self.handle_tag_command(tag_cmd);
+            }
+            Some(Command::Duplicates(command::DuplicateCommand::Find)) => {
+                self.status_message = "Looking for duplicate bookmarks...".to_string();
+                let _ = self.cmd_tx.send(DaemonCommand::FetchDuplicates);
+            }
+            Some(Command::Duplicates(command::DuplicateCommand::Merge)) => {
+                self.merge_selected_duplicates();
+            }
+            Some(Command::Queue(command)) => self.queue_command(command),
+            Some(Command::Publish { format, path }) => {
+                self.publish_selected_stream(format, path);
+            }
+            Some(Command::Alert { name, query }) => {
+                self.status_message = format!("Saving alert {}...", name);
+                let _ = self.cmd_tx.send(DaemonCommand::CreateAlert { name, query });
+            }
             None => {
                 self.status_message = format!("Unknown command: {}", input);
                 self.add_toast(Toast::warning(format!("Unknown command: {}", input)));
@@ -959,6 +2574,121 @@ impl App {
         }
     }
 
+    /// Rename, merge, or delete bookmark tags; the daemon's reply reloads the tag entries.
+    fn handle_tag_command(&mut self, cmd: command::TagCommand) {
+        use command::TagCommand;
+        let provider_id = TAG_PROVIDER_ID.to_string();
+        let command = match cmd {
+            TagCommand::Rename { from, to } => {
+                self.status_message = format!("Renaming #{} to #{}...", from, to);
+                DaemonCommand::RenameTag {
+                    provider_id,
+                    from,
+                    to,
+                }
+            }
+            TagCommand::Merge { sources, into } => {
+                self.status_message = format!("Merging {} tag(s) into #{}...", sources.len(), into);
+                DaemonCommand::MergeTags {
+                    provider_id,
+                    sources,
+                    into,
+                }
+            }
+            TagCommand::Delete(tag) => {
+                self.status_message = format!("Deleting #{}...", tag);
+                DaemonCommand::DeleteTag { provider_id, tag }
+            }
+        };
+        let _ = self.cmd_tx.send(command);
+    }
+
+    /// Show the reading queue, or move or finish the selected queued item.
+    fn queue_command(&mut self, command: command::QueueCommand) {
+        use command::QueueCommand;
+
+        if command == QueueCommand::Show {
+            if let Some(pos) = self.streams.iter().position(|s| s.id.0 == QUEUE_STREAM_ID) {
+                self.stream_state.selected = Some(pos);
+                self.focused = FocusedPane::ItemList;
+                self.fetch_items_for_selected_stream();
+            }
+            return;
+        }
+
+        let selected = self.item_state.selected.and_then(|idx| self.items.get(idx));
+        let Some(entry) = selected.and_then(|item| self.queue_entry(item.id.as_str())) else {
+            self.status_message = "Select an item in the reading queue".to_string();
+            return;
+        };
+        let item_id = entry.item.id.as_str().to_string();
+        let last = self.queue.entries.len() as u32;
+        let position = match command {
+            QueueCommand::Up => entry.position.saturating_sub(1).max(1),
+            QueueCommand::Down => (entry.position + 1).min(last),
+            QueueCommand::Top => 1,
+            QueueCommand::Bottom => last,
+            QueueCommand::MoveTo(position) => position.min(last),
+            QueueCommand::Done => {
+                self.status_message = format!("Finished {}", entry.item.title);
+                let _ = self.cmd_tx.send(DaemonCommand::FinishQueued(item_id));
+                return;
+            }
+            QueueCommand::Show => unreachable!("handled above"),
+        };
+        let _ = self
+            .cmd_tx
+            .send(DaemonCommand::MoveQueued { item_id, position });
+    }
+
+    /// Keep the selected bookmark and merge the rest of its duplicate group into it.
+    fn merge_selected_duplicates(&mut self) {
+        let selected = self.item_state.selected.and_then(|idx| self.items.get(idx));
+        let group = selected.and_then(|item| {
+            self.duplicates
+                .iter()
+                .find(|g| g.items.iter().any(|i| i.id == item.id))
+                .map(|g| (item, g))
+        });
+        let Some((keep, group)) = group else {
+            self.status_message = "Select a bookmark listed by :duplicates to keep".to_string();
+            return;
+        };
+        let duplicates: Vec<String> = group
+            .items
+            .iter()
+            .filter(|i| i.id != keep.id)
+            .map(|i| i.id.as_str().to_string())
+            .collect();
+        self.status_message = format!(
+            "Merging {} bookmark(s) into {}...",
+            duplicates.len(),
+            keep.title
+        );
+        let _ = self.cmd_tx.send(DaemonCommand::MergeBookmarks {
+            keep: keep.id.as_str().to_string(),
+            duplicates,
+        });
+    }
+
+    /// Write the selected stream, such as a bookmarks folder, out as a feed or page to host.
+    fn publish_selected_stream(&mut self, format: command::PublishFormat, path: String) {
+        let stream = self
+            .stream_state
+            .selected
+            .and_then(|idx| self.streams.get(idx));
+        let Some(stream) = stream.filter(|s| s.id.0 != PINNED_STREAM_ID) else {
+            self.status_message = "Select a stream or folder to publish".to_string();
+            return;
+        };
+        self.status_message = format!("Publishing {}...", stream.name);
+        let _ = self.cmd_tx.send(DaemonCommand::PublishStream {
+            stream_id: stream.id.0.clone(),
+            format: format.as_str(),
+            path,
+        });
+    }
+
     /// Handle plugin management commands.
     fn handle_plugin_command(&mut self, cmd: command::PluginCommand) {
         use command::PluginCommand;
@@ -1012,22 +2742,12 @@ impl App {
         }
     }
 
-    /// Execute a search query.
+    /// Execute a search query. The daemon parses the query language and
+    /// sends back the results with what matched in each.
     fn execute_search(&mut self, query: search::SearchQuery) {
-        if query.has_advanced_syntax {
-            self.status_message = format!("Searching with filters: {}", query.text);
-            self.active_search_filter = Some(query.text.clone());
-            self.add_toast(Toast::info(format!("Searching: {}", query.text)));
-            // TODO: Send search RPC to daemon
-            // For now, just show the query in status
-        } else {
-            // Simple search: filter items locally
-            self.status_message = format!("Search: {}", query.text);
-            self.active_search_filter = Some(query.text.clone());
-            self.add_toast(Toast::info(format!("Search: {}", query.text)));
-            // TODO: Filter self.items based on query.text
-            // For now, just show the search in status
-        }
+        self.status_message = format!("Searching: {}", query.text);
+        self.active_search_filter = Some(query.text.clone());
+        let _ = self.cmd_tx.send(DaemonCommand::Search(query.text));
     }
 
     /// Update command suggestions based on current omnibar input.
@@ -0,0 +1,528 @@
+//! Markdown rendering for the preview.
+//!
+//! [`render`] turns Markdown into styled lines: headings, emphasis, lists,
+//! block quotes, tables, and code blocks highlighted by [`highlight`].
+//! Links are numbered like footnotes, `text[1]`, and their URLs listed
+//! under the text, so they can be read and followed from a terminal.
+
+use fusabi_tui_core::style::{Modifier, Style};
+use fusabi_tui_widgets::text::{Line, Span};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use unicode_width::UnicodeWidthStr;
+
+use crate::highlight;
+use crate::theme::Theme;
+
+/// Width of a horizontal rule.
+const RULE_WIDTH: usize = 40;
+
+/// Render `markdown` into lines styled with `theme`.
+pub fn render(markdown: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer::new(theme);
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+/// A table being read, rendered once it's complete.
+struct Table {
+    alignments: Vec<Alignment>,
+    /// Header row first, when there is one
+    rows: Vec<Vec<String>>,
+    has_header: bool,
+    row: Vec<String>,
+    cell: String,
+}
+
+struct Renderer<'t> {
+    theme: &'t Theme,
+    lines: Vec<Line<'static>>,
+    /// The line being built
+    spans: Vec<Span<'static>>,
+    /// Whether a blank line goes before the next block
+    needs_blank: bool,
+
+    heading: Option<HeadingLevel>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    /// URL of the link being read
+    link: Option<String>,
+    /// Link URLs in order of first appearance; a link's number is its index + 1
+    links: Vec<String>,
+
+    quote_depth: usize,
+    /// The next number of each open list, `None` for bullets
+    lists: Vec<Option<u64>>,
+    /// Marker widths of the open list items, for indenting what follows
+    items: Vec<usize>,
+    /// The marker of an item whose first line hasn't been written yet
+    marker: Option<String>,
+
+    /// The language and text of the code block being read
+    code: Option<(Option<String>, String)>,
+    table: Option<Table>,
+}
+
+impl<'t> Renderer<'t> {
+    fn new(theme: &'t Theme) -> Self {
+        Self {
+            theme,
+            lines: Vec::new(),
+            spans: Vec::new(),
+            needs_blank: false,
+            heading: None,
+            strong: 0,
+            emphasis: 0,
+            strikethrough: 0,
+            link: None,
+            links: Vec::new(),
+            quote_depth: 0,
+            lists: Vec::new(),
+            items: Vec::new(),
+            marker: None,
+            code: None,
+            table: None,
+        }
+    }
+
+    fn muted(&self) -> Style {
+        Style::new().fg(self.theme.muted)
+    }
+
+    /// The style of inline text at this point.
+    fn style(&self) -> Style {
+        let mut style = Style::new();
+        match self.heading {
+            Some(HeadingLevel::H1 | HeadingLevel::H2) => {
+                style = style.fg(self.theme.accent).add_modifier(Modifier::BOLD);
+            }
+            Some(_) => style = style.add_modifier(Modifier::BOLD),
+            None => {}
+        }
+        if self.strong > 0 {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.emphasis > 0 {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if self.strikethrough > 0 {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        if self.link.is_some() {
+            style = style
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::UNDERLINED);
+        }
+        style
+    }
+
+    /// The number of a link, counting each URL once.
+    fn link_number(&mut self, url: String) -> usize {
+        match self.links.iter().position(|link| *link == url) {
+            Some(index) => index + 1,
+            None => {
+                self.links.push(url);
+                self.links.len()
+            }
+        }
+    }
+
+    /// Quote bars and list indentation for the next line.
+    fn prefix(&mut self) -> Vec<Span<'static>> {
+        let mut prefix = Vec::new();
+        if self.quote_depth > 0 {
+            prefix.push(Span::styled("│ ".repeat(self.quote_depth), self.muted()));
+        }
+        if let Some((last, outer)) = self.items.split_last() {
+            let indent: usize = outer.iter().sum();
+            match self.marker.take() {
+                Some(marker) => {
+                    prefix.push(Span::raw(" ".repeat(indent)));
+                    prefix.push(Span::styled(marker, Style::new().fg(self.theme.accent)));
+                }
+                None => prefix.push(Span::raw(" ".repeat(indent + last))),
+            }
+        }
+        prefix
+    }
+
+    fn push_line(&mut self, spans: Vec<Span<'static>>) {
+        let mut line = self.prefix();
+        line.extend(spans);
+        self.lines.push(Line::from(line));
+    }
+
+    /// End the line being built, if anything is on it.
+    fn flush(&mut self) {
+        if !self.spans.is_empty() || self.marker.is_some() {
+            let spans = std::mem::take(&mut self.spans);
+            self.push_line(spans);
+        }
+    }
+
+    /// Start a block: end the line and leave a blank line after the last
+    /// block.
+    fn start_block(&mut self) {
+        self.flush();
+        if self.needs_blank && !self.lines.is_empty() {
+            let prefix = if self.quote_depth > 0 {
+                vec![Span::styled("│ ".repeat(self.quote_depth), self.muted())]
+            } else {
+                vec![]
+            };
+            self.lines.push(Line::from(prefix));
+        }
+        self.needs_blank = false;
+    }
+
+    fn end_block(&mut self) {
+        self.flush();
+        self.needs_blank = true;
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, code)) = &mut self.code {
+            code.push_str(text);
+        } else if let Some(table) = &mut self.table {
+            table.cell.push_str(text);
+        } else {
+            let style = self.style();
+            self.spans.push(Span::styled(text.to_string(), style));
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                if let Some(table) = &mut self.table {
+                    table.cell.push_str(&code);
+                } else {
+                    self.spans.push(Span::styled(
+                        code.to_string(),
+                        Style::new().fg(self.theme.warning),
+                    ));
+                }
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.text(&html),
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.start_block();
+                let rule = Span::styled("─".repeat(RULE_WIDTH), self.muted());
+                self.push_line(vec![rule]);
+                self.needs_blank = true;
+            }
+            Event::TaskListMarker(done) => {
+                let marker = if done { "[x] " } else { "[ ] " };
+                self.spans.push(Span::styled(marker, self.muted()));
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                self.heading = Some(level);
+            }
+            Tag::BlockQuote(..) => {
+                self.start_block();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(str::to_string)
+                    }
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::List(start) => {
+                // A nested list continues its item rather than starting a block
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.items.push(marker.width());
+                self.marker = Some(marker);
+                self.needs_blank = false;
+            }
+            Tag::Table(alignments) => {
+                self.start_block();
+                self.table = Some(Table {
+                    alignments,
+                    rows: Vec::new(),
+                    has_header: false,
+                    row: Vec::new(),
+                    cell: String::new(),
+                });
+            }
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link { dest_url, .. } => self.link = Some(dest_url.to_string()),
+            Tag::Image { dest_url, .. } => {
+                self.link = Some(dest_url.to_string());
+                self.text("[image: ");
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_block(),
+            TagEnd::Heading(_) => {
+                self.end_block();
+                self.heading = None;
+            }
+            TagEnd::BlockQuote(..) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.needs_blank = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some((language, code)) = self.code.take() {
+                    for line in highlight::highlight(&code, language.as_deref(), self.theme) {
+                        self.push_line(line.spans);
+                    }
+                }
+                self.needs_blank = true;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                self.needs_blank = self.lists.is_empty();
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.items.pop();
+                self.needs_blank = false;
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(std::mem::take(&mut table.row));
+                    table.has_header = true;
+                }
+            }
+            TagEnd::TableRow => {
+                if let Some(table) = &mut self.table {
+                    table.rows.push(std::mem::take(&mut table.row));
+                }
+            }
+            TagEnd::TableCell => {
+                if let Some(table) = &mut self.table {
+                    let cell = std::mem::take(&mut table.cell);
+                    table.row.push(cell.trim().to_string());
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.table_lines(&table);
+                }
+                self.needs_blank = true;
+            }
+            TagEnd::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            TagEnd::Strong => self.strong = self.strong.saturating_sub(1),
+            TagEnd::Strikethrough => self.strikethrough = self.strikethrough.saturating_sub(1),
+            TagEnd::Link | TagEnd::Image => {
+                if matches!(tag, TagEnd::Image) {
+                    self.text("]");
+                }
+                if let Some(url) = self.link.take() {
+                    let footnote = format!("[{}]", self.link_number(url));
+                    let muted = self.muted();
+                    match &mut self.table {
+                        Some(table) => table.cell.push_str(&footnote),
+                        None => self.spans.push(Span::styled(footnote, muted)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Write a table with its columns padded to the widest cell.
+    fn table_lines(&mut self, table: &Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for (index, row) in table.rows.iter().enumerate() {
+            let is_header = table.has_header && index == 0;
+            let style = if is_header {
+                Style::new().add_modifier(Modifier::BOLD)
+            } else {
+                Style::new()
+            };
+            let mut spans = Vec::new();
+            for (column, width) in widths.iter().enumerate() {
+                if column > 0 {
+                    spans.push(Span::styled(" │ ", self.muted()));
+                }
+                let cell = row.get(column).map(String::as_str).unwrap_or_default();
+                let padding = width - cell.width();
+                let (left, right) = match table.alignments.get(column) {
+                    Some(Alignment::Right) => (padding, 0),
+                    Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                    _ => (0, padding),
+                };
+                let padded = format!("{}{}{}", " ".repeat(left), cell, " ".repeat(right));
+                spans.push(Span::styled(padded, style));
+            }
+            self.push_line(spans);
+
+            if is_header {
+                let separator: Vec<String> =
+                    widths.iter().map(|width| "─".repeat(*width)).collect();
+                let separator = Span::styled(separator.join("─┼─"), self.muted());
+                self.push_line(vec![separator]);
+            }
+        }
+    }
+
+    /// The rendered lines, followed by the links they number.
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush();
+        if !self.links.is_empty() {
+            self.lines.push(Line::from(""));
+            for (index, url) in self.links.iter().enumerate() {
+                self.lines.push(Line::from(Span::styled(
+                    format!("[{}] {}", index + 1, url),
+                    Style::new().fg(self.theme.muted),
+                )));
+            }
+        }
+        self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(lines: &[Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_headings_and_emphasis() {
+        let theme = Theme::default();
+        let lines = render("# Title\n\nSome *soft* and **bold** text.", &theme);
+        assert_eq!(
+            text_of(&lines),
+            vec!["Title", "", "Some soft and bold text."]
+        );
+
+        let title = &lines[0].spans[0];
+        assert_eq!(title.style.fg, Some(theme.accent));
+        assert!(title.style.add_modifier.contains(Modifier::BOLD));
+        let soft = lines[2]
+            .spans
+            .iter()
+            .find(|span| span.content == "soft")
+            .unwrap();
+        assert!(soft.style.add_modifier.contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn test_lists() {
+        let lines = render(
+            "- one\n- two\n  1. nested\n  2. again\n- [x] done\n\nAfter",
+            &Theme::default(),
+        );
+        assert_eq!(
+            text_of(&lines),
+            vec![
+                "• one",
+                "• two",
+                "  1. nested",
+                "  2. again",
+                "• [x] done",
+                "",
+                "After",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_links_are_numbered() {
+        let lines = render(
+            "See [the docs](https://a.example) and [again](https://a.example), \
+             then [more](https://b.example).",
+            &Theme::default(),
+        );
+        assert_eq!(
+            text_of(&lines),
+            vec![
+                "See the docs[1] and again[1], then more[2].",
+                "",
+                "[1] https://a.example",
+                "[2] https://b.example",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tables_are_aligned() {
+        let lines = render(
+            "| Name | Count |\n|------|------:|\n| apples | 3 |\n| figs | 12 |",
+            &Theme::default(),
+        );
+        assert_eq!(
+            text_of(&lines),
+            vec![
+                "Name   │ Count",
+                "───────┼──────",
+                "apples │     3",
+                "figs   │    12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quotes_and_code() {
+        let lines = render("> quoted\n\n```rust\nlet x = 1;\n```", &Theme::default());
+        assert_eq!(text_of(&lines), vec!["│ quoted", "", "  let x = 1;"]);
+    }
+}
@@ -54,6 +54,9 @@ pub enum ContentTypeFilter {
     Task,
     Event,
     Bookmark,
+    Image,
+    Message,
+    Patch,
 }
 
 impl ContentTypeFilter {
@@ -66,6 +69,9 @@ impl ContentTypeFilter {
             "task" | "todo" => Some(Self::Task),
             "event" | "calendar" => Some(Self::Event),
             "bookmark" => Some(Self::Bookmark),
+            "image" | "photo" | "gallery" => Some(Self::Image),
+            "message" | "chat" => Some(Self::Message),
+            "patch" | "diff" => Some(Self::Patch),
             _ => None,
         }
     }
@@ -0,0 +1,190 @@
+//! Reading analytics widget, shown in place of the preview pane.
+
+use crate::daemon_client::{DailyReadingStats, ReadingStats, StreamReadingStats};
+use crate::theme::Theme;
+use fusabi_tui_core::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+};
+use fusabi_tui_widgets::{
+    block::Block,
+    borders::Borders,
+    list::{List, ListItem, ListState as WidgetListState},
+    paragraph::Paragraph,
+    text::{Line, Span},
+    widget::Widget,
+};
+
+/// Read ratio below which a stream is highlighted as a pruning candidate.
+const LOW_READ_PERCENT: u64 = 10;
+
+/// Share of `received` that was read, as a whole percentage.
+fn read_percent(read: u64, received: u64) -> Option<u64> {
+    (received > 0).then(|| read.min(received) * 100 / received)
+}
+
+/// Format a wait as "45m", "3h", or "2d".
+fn format_wait(secs: u64) -> String {
+    match secs {
+        0..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+/// Sparkline of the running backlog change, one cell per day.
+fn backlog_sparkline(days: &[DailyReadingStats]) -> String {
+    const BARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let mut backlog = 0i64;
+    let totals: Vec<i64> = days
+        .iter()
+        .map(|day| {
+            backlog += day.received as i64 - day.read as i64;
+            backlog
+        })
+        .collect();
+    let (Some(&min), Some(&max)) = (totals.iter().min(), totals.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1);
+    totals
+        .iter()
+        .map(|total| BARS[((total - min) * (BARS.len() as i64 - 1) / range) as usize])
+        .collect()
+}
+
+/// Widget showing reading statistics: overall totals, then streams
+/// busiest first with their read ratio and backlog growth.
+pub struct AnalyticsWidget<'a> {
+    stats: Option<&'a ReadingStats>,
+    days: u32,
+    selected: Option<usize>,
+    theme: &'a Theme,
+}
+
+impl<'a> AnalyticsWidget<'a> {
+    pub fn new(
+        stats: Option<&'a ReadingStats>,
+        days: u32,
+        selected: Option<usize>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            stats,
+            days,
+            selected,
+            theme,
+        }
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'a>> {
+        let Some(stats) = self.stats else {
+            return vec![Line::from(Span::styled(
+                "Loading...",
+                Style::new().fg(self.theme.muted),
+            ))];
+        };
+
+        let received: u64 = stats.streams.iter().map(|s| s.received).sum();
+        let read: u64 = stats.streams.iter().map(|s| s.read).sum();
+        let saved: u64 = stats.streams.iter().map(|s| s.saved).sum();
+        let ratio = read_percent(read, received)
+            .map(|p| format!(" ({}%)", p))
+            .unwrap_or_default();
+
+        vec![
+            Line::from(format!(
+                "Received {}  Read {}{}  Saved {}",
+                received, read, ratio, saved
+            )),
+            Line::from(vec![
+                Span::raw(format!("Backlog {:+}  ", received as i64 - read as i64)),
+                Span::styled(
+                    backlog_sparkline(&stats.days),
+                    Style::new().fg(self.theme.accent),
+                ),
+            ]),
+        ]
+    }
+
+    fn stream_item(&self, stream: &'a StreamReadingStats, selected: bool) -> ListItem<'a> {
+        let percent = read_percent(stream.read, stream.received);
+        let ratio_color = match percent {
+            Some(p) if p < LOW_READ_PERCENT => self.theme.warning,
+            _ => self.theme.muted,
+        };
+
+        let mut spans = vec![
+            Span::raw(stream.name.as_deref().unwrap_or(&stream.stream_id)),
+            Span::styled(
+                format!("  {} in", stream.received),
+                Style::new().fg(self.theme.muted),
+            ),
+            Span::styled(
+                percent
+                    .map(|p| format!("  {}% read", p))
+                    .unwrap_or_default(),
+                Style::new().fg(ratio_color),
+            ),
+            Span::styled(
+                format!("  {:+}", stream.received as i64 - stream.read as i64),
+                Style::new().fg(self.theme.muted),
+            ),
+        ];
+        if let Some(secs) = stream.avg_secs_to_read {
+            spans.push(Span::styled(
+                format!("  ~{} to read", format_wait(secs)),
+                Style::new().fg(self.theme.muted),
+            ));
+        }
+
+        let style = if selected {
+            Style::new()
+                .bg(self.theme.selection_bg)
+                .fg(self.theme.selection_fg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::new()
+        };
+        ListItem::new(Line::from(spans)).style(style)
+    }
+
+    pub fn render(self, area: Rect, buffer: &mut Buffer) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(&[Constraint::Length(4), Constraint::Min(0)])
+            .split(area);
+
+        let summary = Block::default()
+            .title(format!(
+                " Reading, last {} days (t:window Esc:close) ",
+                self.days
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(self.theme.border_focused));
+        Paragraph::new(self.summary_lines())
+            .block(summary)
+            .render(chunks[0], buffer);
+
+        let items: Vec<ListItem> = self
+            .stats
+            .map(|stats| stats.streams.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .map(|(i, stream)| self.stream_item(stream, self.selected == Some(i)))
+            .collect();
+
+        let block = Block::default()
+            .title(" Busiest feeds ")
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(self.theme.border_focused));
+        let list = List::new(items).block(block);
+        let mut list_state = WidgetListState::default();
+        if let Some(selected) = self.selected {
+            list_state.select(Some(selected));
+        }
+        fusabi_tui_widgets::StatefulWidget::render(&list, chunks[1], buffer, &mut list_state);
+    }
+}
@@ -0,0 +1,132 @@
+//! Downloads widget, shown in place of the preview pane.
+
+use crate::daemon_client::{Download, DownloadState};
+use crate::theme::Theme;
+use fusabi_tui_core::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+};
+use fusabi_tui_widgets::{
+    block::Block,
+    borders::Borders,
+    list::{List, ListItem, ListState as WidgetListState},
+    text::{Line, Span},
+};
+
+/// Format a byte count as "512 B", "3.4 MB", and so on.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Progress of a download: a percentage while running with a known size,
+/// otherwise what the state calls for.
+fn progress(download: &Download) -> String {
+    match download.state {
+        DownloadState::Downloading => match download.total_bytes {
+            Some(total) if total > 0 => format!(
+                "{:>3}% of {}",
+                download.bytes.min(total) * 100 / total,
+                format_bytes(total)
+            ),
+            _ => format_bytes(download.bytes),
+        },
+        DownloadState::Completed => format_bytes(download.bytes),
+        DownloadState::Failed => download
+            .error
+            .clone()
+            .unwrap_or_else(|| "failed".to_string()),
+        DownloadState::Queued => "queued".to_string(),
+        DownloadState::Cancelled => "cancelled".to_string(),
+        DownloadState::Unknown => String::new(),
+    }
+}
+
+/// Widget listing the daemon's downloads.
+pub struct DownloadsWidget<'a> {
+    downloads: &'a [Download],
+    selected: Option<usize>,
+    focused: bool,
+    theme: &'a Theme,
+}
+
+impl<'a> DownloadsWidget<'a> {
+    pub fn new(downloads: &'a [Download], selected: Option<usize>, theme: &'a Theme) -> Self {
+        Self {
+            downloads,
+            selected,
+            focused: false,
+            theme,
+        }
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn render(self, area: Rect, buffer: &mut Buffer) {
+        let border_color = if self.focused {
+            self.theme.border_focused
+        } else {
+            self.theme.border
+        };
+
+        let block = Block::default()
+            .title(" Downloads (x:cancel R:retry Esc:close) ")
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(border_color));
+
+        let items: Vec<ListItem> = self
+            .downloads
+            .iter()
+            .enumerate()
+            .map(|(i, download)| {
+                let (icon, color) = match download.state {
+                    DownloadState::Queued => ("…", self.theme.muted),
+                    DownloadState::Downloading => ("↓", self.theme.accent),
+                    DownloadState::Completed => ("✓", self.theme.success),
+                    DownloadState::Failed => ("✗", self.theme.error),
+                    DownloadState::Cancelled | DownloadState::Unknown => ("-", self.theme.muted),
+                };
+
+                let spans = vec![
+                    Span::styled(format!("{} ", icon), Style::new().fg(color)),
+                    Span::raw(&download.file_name),
+                    Span::styled(
+                        format!("  {}", progress(download)),
+                        Style::new().fg(self.theme.muted),
+                    ),
+                ];
+
+                let style = if self.selected == Some(i) {
+                    Style::new()
+                        .bg(self.theme.selection_bg)
+                        .fg(self.theme.selection_fg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::new()
+                };
+
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect();
+
+        let list = List::new(items).block(block);
+        let mut list_state = WidgetListState::default();
+        if let Some(selected) = self.selected {
+            list_state.select(Some(selected));
+        }
+        fusabi_tui_widgets::StatefulWidget::render(&list, area, buffer, &mut list_state);
+    }
+}
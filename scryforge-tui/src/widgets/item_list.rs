@@ -7,9 +7,12 @@ use fusabi_tui_core::{
     style::{Modifier, Style},
 };
 use fusabi_tui_widgets::{
-    block::Block, borders::Borders, list::{List, ListItem, ListState as WidgetListState}, text::{Line, Span},
+    block::Block,
+    borders::Borders,
+    list::{List, ListItem, ListState as WidgetListState},
+    text::{Line, Span},
 };
-use scryforge_provider_core::Item;
+use scryforge_provider_core::{Item, SearchResult};
 
 /// Format view count in a compact, human-readable format.
 /// Examples: 1.2K, 45K, 1.5M, 3.2B
@@ -36,6 +39,10 @@ pub struct ItemListWidget<'a> {
     items: &'a [Item],
     selected: Option<usize>,
     focused: bool,
+    /// Pinned items, marked wherever they are listed
+    pinned: &'a [Item],
+    /// What matched in each item, when the items are search results
+    search_results: &'a [SearchResult],
     theme: &'a Theme,
 }
 
@@ -45,6 +52,8 @@ impl<'a> ItemListWidget<'a> {
             items,
             selected,
             focused: false,
+            pinned: &[],
+            search_results: &[],
             theme,
         }
     }
@@ -54,6 +63,16 @@ impl<'a> ItemListWidget<'a> {
         self
     }
 
+    pub fn pinned(mut self, pinned: &'a [Item]) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    pub fn search_results(mut self, search_results: &'a [SearchResult]) -> Self {
+        self.search_results = search_results;
+        self
+    }
+
     pub fn render(self, area: Rect, buffer: &mut Buffer) {
         let border_color = if self.focused {
             self.theme.border_focused
@@ -72,6 +91,13 @@ impl<'a> ItemListWidget<'a> {
             .enumerate()
             .flat_map(|(i, item)| {
                 let is_selected = self.selected == Some(i);
+                let matched = self
+                    .search_results
+                    .iter()
+                    .find(|result| result.item.id == item.id);
+                let match_style = Style::new()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD);
 
                 let mut lines = vec![];
 
@@ -85,6 +111,27 @@ impl<'a> ItemListWidget<'a> {
                     title_spans.push(Span::styled("○ ", Style::new().fg(self.theme.muted)));
                 }
 
+                // Pin indicator
+                if self.pinned.iter().any(|p| p.id == item.id) {
+                    title_spans.push(Span::styled("📌 ", Style::new().fg(self.theme.accent)));
+                }
+
+                // Removed at the source
+                if item.metadata.contains_key("removed") {
+                    title_spans.push(Span::styled(
+                        "[deleted] ",
+                        Style::new().fg(self.theme.muted),
+                    ));
+                }
+
+                // Updated since last seen
+                if item.metadata.contains_key("changed_at") {
+                    title_spans.push(Span::styled(
+                        "✎ updated ",
+                        Style::new().fg(self.theme.accent),
+                    ));
+                }
+
                 // Saved/starred indicator
                 if item.is_saved {
                     title_spans.push(Span::styled("★ ", Style::new().fg(self.theme.accent)));
@@ -96,7 +143,17 @@ impl<'a> ItemListWidget<'a> {
                 } else {
                     Style::new()
                 };
-                title_spans.push(Span::styled(&item.title, title_style));
+                match matched.filter(|result| !result.title_highlights.is_empty()) {
+                    Some(result) => {
+                        for (text, hit) in result.title_parts() {
+                            title_spans.push(Span::styled(
+                                text,
+                                if hit { match_style } else { title_style },
+                            ));
+                        }
+                    }
+                    None => title_spans.push(Span::styled(&item.title, title_style)),
+                }
 
                 // Duration for video items (color-coded)
                 if let scryforge_provider_core::ItemContent::Video {
@@ -109,9 +166,7 @@ impl<'a> ItemListWidget<'a> {
                     title_spans.push(Span::raw("  "));
                     title_spans.push(Span::styled(
                         duration_str,
-                        Style::new()
-                            .fg(duration_color)
-                            .add_modifier(Modifier::BOLD),
+                        Style::new().fg(duration_color).add_modifier(Modifier::BOLD),
                     ));
                 }
 
@@ -176,6 +231,20 @@ impl<'a> ItemListWidget<'a> {
                     lines.push(ListItem::new(Line::from(metadata_spans)).style(metadata_style));
                 }
 
+                // Third line, for search results: the text around the match
+                if let Some(result) = matched.filter(|result| result.snippet.is_some()) {
+                    let mut snippet_spans = vec![Span::raw("  ")];
+                    for (text, hit) in result.snippet_parts() {
+                        let style = if hit {
+                            match_style
+                        } else {
+                            Style::new().fg(self.theme.muted)
+                        };
+                        snippet_spans.push(Span::styled(text, style));
+                    }
+                    lines.push(ListItem::new(Line::from(snippet_spans)).style(metadata_style));
+                }
+
                 lines
             })
             .collect();
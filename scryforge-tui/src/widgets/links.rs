@@ -0,0 +1,93 @@
+//! Links widget, listing the links in an item in place of the preview pane.
+
+use crate::daemon_client::ItemLink;
+use crate::theme::Theme;
+use fusabi_tui_core::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+};
+use fusabi_tui_widgets::{
+    block::Block,
+    borders::Borders,
+    list::{List, ListItem, ListState as WidgetListState},
+    text::{Line, Span},
+};
+
+/// Widget listing the links in an item's body.
+pub struct LinksWidget<'a> {
+    links: &'a [ItemLink],
+    selected: Option<usize>,
+    focused: bool,
+    theme: &'a Theme,
+}
+
+impl<'a> LinksWidget<'a> {
+    pub fn new(links: &'a [ItemLink], selected: Option<usize>, theme: &'a Theme) -> Self {
+        Self {
+            links,
+            selected,
+            focused: false,
+            theme,
+        }
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn render(self, area: Rect, buffer: &mut Buffer) {
+        let border_color = if self.focused {
+            self.theme.border_focused
+        } else {
+            self.theme.border
+        };
+
+        let block = Block::default()
+            .title(" Links (Enter:open b:bookmark y:copy Esc:close) ")
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(border_color));
+
+        let items: Vec<ListItem> = self
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                let mut spans = vec![Span::styled(
+                    format!("{:>2}. ", i + 1),
+                    Style::new().fg(self.theme.muted),
+                )];
+                // The page's title once fetched, else the text it was linked from
+                match link.title.as_deref().or(link.text.as_deref()) {
+                    Some(label) => {
+                        spans.push(Span::raw(label));
+                        spans.push(Span::styled(
+                            format!("  {}", link.url),
+                            Style::new().fg(self.theme.muted),
+                        ));
+                    }
+                    None => spans.push(Span::styled(&link.url, Style::new().fg(self.theme.accent))),
+                }
+
+                let style = if self.selected == Some(i) {
+                    Style::new()
+                        .bg(self.theme.selection_bg)
+                        .fg(self.theme.selection_fg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::new()
+                };
+
+                ListItem::new(Line::from(spans)).style(style)
+            })
+            .collect();
+
+        let list = List::new(items).block(block);
+        let mut list_state = WidgetListState::default();
+        if let Some(selected) = self.selected {
+            list_state.select(Some(selected));
+        }
+        fusabi_tui_widgets::StatefulWidget::render(&list, area, buffer, &mut list_state);
+    }
+}
@@ -1,13 +1,19 @@
 //! Custom widgets for Scryforge TUI.
 
+pub mod analytics;
+pub mod downloads;
 pub mod item_list;
+pub mod links;
 pub mod omnibar;
 pub mod preview;
 pub mod status_bar;
 pub mod stream_list;
 pub mod toast;
 
+pub use analytics::AnalyticsWidget;
+pub use downloads::DownloadsWidget;
 pub use item_list::ItemListWidget;
+pub use links::LinksWidget;
 pub use omnibar::OmnibarWidget;
 pub use preview::PreviewWidget;
 pub use status_bar::{ProviderStatus, ProviderSyncStatus, StatusBarWidget};
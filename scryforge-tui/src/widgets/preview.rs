@@ -1,5 +1,8 @@
 //! Preview widget for item detail display.
 
+use crate::daemon_client::{ItemChange, RelatedItem, Relation};
+use crate::highlight::{self, Block};
+use crate::markdown;
 use crate::theme::Theme;
 use fusabi_tui_core::{
     buffer::Buffer,
@@ -7,9 +10,14 @@ use fusabi_tui_core::{
     style::{Modifier, Style},
 };
 use fusabi_tui_widgets::{
-    block::Block, borders::Borders, paragraph::Paragraph, text::{Line, Span}, widget::Widget,
+    block::Block,
+    borders::Borders,
+    paragraph::Paragraph,
+    text::{Line, Span},
+    widget::Widget,
 };
-use scryforge_provider_core::Item;
+use scryforge_provider_core::diff::DiffLine;
+use scryforge_provider_core::{CalendarInvite, Item, ItemContent};
 
 fn extract_preview_text(content: &scryforge_provider_core::ItemContent) -> String {
     use scryforge_provider_core::ItemContent::*;
@@ -48,14 +56,135 @@ fn extract_preview_text(content: &scryforge_provider_core::ItemContent) -> Strin
             }
         }
         Bookmark { description } => description.clone().unwrap_or_default(),
+        Image {
+            url,
+            alt,
+            width,
+            height,
+        } => describe_image(url, alt.as_deref(), *width, *height),
+        Gallery { images } => {
+            let mut text = format!("Gallery ({} images)", images.len());
+            for (i, image) in images.iter().enumerate() {
+                let described =
+                    describe_image(&image.url, image.alt.as_deref(), image.width, image.height);
+                text.push_str(&format!("\n\n{}. {}", i + 1, described));
+            }
+            text
+        }
+        Message {
+            text,
+            sender,
+            channel,
+            thread_id,
+            reactions,
+        } => {
+            let mut header = sender.clone();
+            if let Some(channel) = channel {
+                header.push_str(&format!(" in {channel}"));
+            }
+            if thread_id.is_some() {
+                header.push_str(" (thread reply)");
+            }
+            let mut out = format!("{header}\n\n{text}");
+            if !reactions.is_empty() {
+                let reactions: Vec<String> = reactions
+                    .iter()
+                    .map(|r| format!("{} {}", r.emoji, r.count))
+                    .collect();
+                out.push_str(&format!("\n\n{}", reactions.join("  ")));
+            }
+            out
+        }
+        Patch {
+            description, diff, ..
+        } => match description {
+            Some(description) => format!("{description}\n\n{diff}"),
+            None => diff.clone(),
+        },
         Generic { body } => body.clone().unwrap_or_default(),
     }
 }
 
+/// A patch's description, a diffstat line, and the diff colored the way
+/// `git diff --color` colors it.
+fn patch_lines(
+    description: Option<&str>,
+    diff: &str,
+    files_changed: &[String],
+    additions: u32,
+    deletions: u32,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+    if let Some(description) = description {
+        for line in description.lines() {
+            lines.push(Line::from(line.to_string()));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let files = match files_changed.len() {
+        1 => "1 file changed".to_string(),
+        n => format!("{n} files changed"),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(format!("{files}, "), Style::new().fg(theme.muted)),
+        Span::styled(format!("+{additions}"), Style::new().fg(theme.success)),
+        Span::styled(" ", Style::new().fg(theme.muted)),
+        Span::styled(format!("-{deletions}"), Style::new().fg(theme.error)),
+    ]));
+    lines.push(Line::from(""));
+
+    for (kind, line) in scryforge_provider_core::diff::lines(diff) {
+        let style = match kind {
+            DiffLine::FileHeader => Style::new().add_modifier(Modifier::BOLD),
+            DiffLine::HunkHeader => Style::new().fg(theme.accent),
+            DiffLine::Added => Style::new().fg(theme.success),
+            DiffLine::Removed => Style::new().fg(theme.error),
+            DiffLine::Context => Style::new(),
+            DiffLine::Other => Style::new().fg(theme.muted),
+        };
+        lines.push(Line::from(Span::styled(line.to_string(), style)));
+    }
+    lines
+}
+
+/// Lines of an item's body, with its code blocks highlighted.
+fn body_lines(body: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![];
+    for block in highlight::blocks(body) {
+        match block {
+            Block::Prose(text) => {
+                lines.extend(text.lines().map(|line| Line::from(line.to_string())));
+            }
+            Block::Code { language, code } => {
+                lines.extend(highlight::highlight(&code, language.as_deref(), theme));
+            }
+        }
+    }
+    lines
+}
+
+/// Image placeholder: size and alt text, with the URL on its own line.
+fn describe_image(url: &str, alt: Option<&str>, width: Option<u32>, height: Option<u32>) -> String {
+    let mut text = match (width, height) {
+        (Some(w), Some(h)) => format!("[Image {w}×{h}]"),
+        _ => "[Image]".to_string(),
+    };
+    if let Some(alt) = alt {
+        text.push_str(&format!(" {alt}"));
+    }
+    text.push_str(&format!("\n{url}"));
+    text
+}
+
 /// Widget displaying a preview of the selected item.
 pub struct PreviewWidget<'a> {
     item: Option<&'a Item>,
+    related: &'a [RelatedItem],
+    changes: &'a [ItemChange],
     focused: bool,
+    scroll: u16,
     theme: &'a Theme,
 }
 
@@ -63,7 +192,10 @@ impl<'a> PreviewWidget<'a> {
     pub fn new(item: Option<&'a Item>, theme: &'a Theme) -> Self {
         Self {
             item,
+            related: &[],
+            changes: &[],
             focused: false,
+            scroll: 0,
             theme,
         }
     }
@@ -73,6 +205,24 @@ impl<'a> PreviewWidget<'a> {
         self
     }
 
+    /// Items from other providers linked to the item, listed under "Related".
+    pub fn related(mut self, related: &'a [RelatedItem]) -> Self {
+        self.related = related;
+        self
+    }
+
+    /// What syncs changed in the item, newest first, listed under "What changed".
+    pub fn changes(mut self, changes: &'a [ItemChange]) -> Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Lines scrolled off the top of the preview.
+    pub fn scroll(mut self, lines: u16) -> Self {
+        self.scroll = lines;
+        self
+    }
+
     pub fn render(self, area: Rect, buffer: &mut Buffer) {
         let border_color = if self.focused {
             self.theme.border_focused
@@ -115,12 +265,198 @@ impl<'a> PreviewWidget<'a> {
                     )));
                 }
 
+                if let Some(reason) = item.metadata.get("removed") {
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "[deleted] Removed at the source ({}); showing the last copy seen",
+                            reason
+                        ),
+                        Style::new().fg(self.theme.error),
+                    )));
+                }
+
+                if let Some(minutes) = item.metadata.get("reading_minutes") {
+                    lines.push(Line::from(Span::styled(
+                        format!("Length: ~{} min", minutes),
+                        Style::new().fg(self.theme.muted),
+                    )));
+                }
+
+                if let Some(newsletter) = item.metadata.get("newsletter") {
+                    lines.push(Line::from(Span::styled(
+                        format!("From newsletter: {}", newsletter),
+                        Style::new().fg(self.theme.muted),
+                    )));
+                } else if let Some(stories) = item.metadata.get("stories") {
+                    lines.push(Line::from(Span::styled(
+                        format!("Newsletter: {} stories listed separately", stories),
+                        Style::new().fg(self.theme.muted),
+                    )));
+                }
+
                 lines.push(Line::from(""));
 
+                if let Some(invite) = CalendarInvite::from_item(item) {
+                    let heading = if invite.is_request() {
+                        "Invitation:"
+                    } else {
+                        "Cancelled:"
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            heading,
+                            Style::new()
+                                .fg(self.theme.accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(format!(" {}", invite.summary)),
+                    ]));
+                    let when = match (&invite.start, &invite.end) {
+                        (Some(start), Some(end)) => Some(format!("{} - {}", start, end)),
+                        (Some(start), None) => Some(start.clone()),
+                        _ => None,
+                    };
+                    let details = [
+                        ("When", when),
+                        ("Where", invite.location.clone()),
+                        ("Organizer", invite.organizer.clone()),
+                        (
+                            "Attendees",
+                            Some(invite.attendees.join(", ")).filter(|a| !a.is_empty()),
+                        ),
+                    ];
+                    for (label, value) in details {
+                        if let Some(value) = value {
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    format!("{label}: "),
+                                    Style::new().fg(self.theme.muted),
+                                ),
+                                Span::raw(value),
+                            ]));
+                        }
+                    }
+                    if invite.is_request() {
+                        lines.push(Line::from(Span::styled(
+                            "Y: accept  N: decline",
+                            Style::new().fg(self.theme.muted),
+                        )));
+                    }
+                    lines.push(Line::from(""));
+                }
+
+                if !self.changes.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        "What changed:",
+                        Style::new()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    for change in self.changes {
+                        lines.push(Line::from(Span::styled(
+                            change.changed_at.format("%Y-%m-%d %H:%M").to_string(),
+                            Style::new().fg(self.theme.muted),
+                        )));
+                        for field in &change.changes {
+                            let label = field.field.replace('_', " ");
+                            let value = match (&field.diff, &field.before, &field.after) {
+                                (Some(diff), _, _) => diff.clone(),
+                                (None, before, after) => format!(
+                                    "{} → {}",
+                                    before.as_deref().unwrap_or("none"),
+                                    after.as_deref().unwrap_or("none")
+                                ),
+                            };
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    format!("  {label}: "),
+                                    Style::new().fg(self.theme.muted),
+                                ),
+                                Span::raw(value),
+                            ]));
+                        }
+                    }
+                    lines.push(Line::from(""));
+                }
+
+                if let Some(summary) = item.metadata.get("summary") {
+                    lines.push(Line::from(Span::styled(
+                        "Summary:",
+                        Style::new()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    for line in summary.lines() {
+                        lines.push(Line::from(line.to_string()));
+                    }
+                    lines.push(Line::from(""));
+                }
+
                 // Extract text content based on item type
-                let body = extract_preview_text(&item.content);
-                for line in body.lines() {
-                    lines.push(Line::from(line.to_string()));
+                if let ItemContent::Patch {
+                    description,
+                    diff,
+                    files_changed,
+                    additions,
+                    deletions,
+                } = &item.content
+                {
+                    lines.extend(patch_lines(
+                        description.as_deref(),
+                        diff,
+                        files_changed,
+                        *additions,
+                        *deletions,
+                        self.theme,
+                    ));
+                } else if let ItemContent::Markdown(markdown) = &item.content {
+                    lines.extend(markdown::render(markdown, self.theme));
+                } else {
+                    let body = extract_preview_text(&item.content);
+                    lines.extend(body_lines(&body, self.theme));
+                }
+
+                if !self.related.is_empty() {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        "Related:",
+                        Style::new()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    for related in self.related {
+                        let label = match related.relation {
+                            Relation::SameLink => "Same link",
+                            Relation::LinksTo => "Links to",
+                            Relation::LinkedFrom => "Linked from",
+                            Relation::Unknown => "Related",
+                        };
+                        let source = related
+                            .item
+                            .id
+                            .as_str()
+                            .split(':')
+                            .next()
+                            .unwrap_or_default();
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("{label}: "), Style::new().fg(self.theme.muted)),
+                            Span::raw(related.item.title.clone()),
+                            Span::styled(format!(" ({source})"), Style::new().fg(self.theme.muted)),
+                        ]));
+                    }
+                }
+
+                if let Some(transcript) = item.metadata.get("transcript") {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        "Transcript:",
+                        Style::new()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                    for line in transcript.lines() {
+                        lines.push(Line::from(line.to_string()));
+                    }
                 }
 
                 lines
@@ -133,7 +469,8 @@ impl<'a> PreviewWidget<'a> {
 
         let paragraph = Paragraph::new(content)
             .block(block)
-            .wrap(fusabi_tui_widgets::Wrap::Wrap);
+            .wrap(fusabi_tui_widgets::Wrap::Wrap)
+            .scroll(0, self.scroll);
 
         paragraph.render(area, buffer);
     }
@@ -2,7 +2,11 @@
 
 use crate::theme::Theme;
 use fusabi_tui_core::{buffer::Buffer, layout::Rect, style::Style};
-use fusabi_tui_widgets::{paragraph::Paragraph, text::{Line, Span}, widget::Widget};
+use fusabi_tui_widgets::{
+    paragraph::Paragraph,
+    text::{Line, Span},
+    widget::Widget,
+};
 
 /// Status of a provider's sync state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,6 +57,7 @@ pub struct StatusBarWidget<'a> {
     provider_statuses: &'a [ProviderStatus],
     unread_count: u32,
     search_filter: Option<&'a str>,
+    focus_remaining_secs: Option<u64>,
     theme: &'a Theme,
 }
 
@@ -64,6 +69,7 @@ impl<'a> StatusBarWidget<'a> {
             provider_statuses: &[],
             unread_count: 0,
             search_filter: None,
+            focus_remaining_secs: None,
             theme,
         }
     }
@@ -86,6 +92,12 @@ impl<'a> StatusBarWidget<'a> {
         self
     }
 
+    /// Set the time left in a running focus session
+    pub fn focus_remaining(mut self, secs: Option<u64>) -> Self {
+        self.focus_remaining_secs = secs;
+        self
+    }
+
     pub fn render(self, area: Rect, buffer: &mut Buffer) {
         let mut spans = vec![];
 
@@ -142,8 +154,17 @@ impl<'a> StatusBarWidget<'a> {
             ));
         }
 
-        let paragraph = Paragraph::new(Line::from(spans))
-            .style(Style::new().bg(self.theme.selection_bg));
+        // Focus session countdown
+        if let Some(secs) = self.focus_remaining_secs {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                format!("Focus {}", crate::time::format_duration(secs)),
+                Style::new().fg(self.theme.accent),
+            ));
+        }
+
+        let paragraph =
+            Paragraph::new(Line::from(spans)).style(Style::new().bg(self.theme.selection_bg));
 
         paragraph.render(area, buffer);
     }
@@ -7,13 +7,18 @@ use fusabi_tui_core::{
     style::{Modifier, Style},
 };
 use fusabi_tui_widgets::{
-    block::Block, borders::Borders, list::{List, ListItem, ListState as WidgetListState}, text::{Line, Span},
+    block::Block,
+    borders::Borders,
+    list::{List, ListItem, ListState as WidgetListState},
+    text::{Line, Span},
 };
 use scryforge_provider_core::Stream;
+use std::collections::HashSet;
 
 /// Get provider icon/symbol based on provider name or type.
 fn get_provider_icon(provider_id: &str) -> &'static str {
     match provider_id.to_lowercase().as_str() {
+        "local" => "📌",
         id if id.contains("email") || id.contains("gmail") || id.contains("imap") => "📧",
         id if id.contains("rss") || id.contains("feed") => "📰",
         id if id.contains("spotify") => "🎵",
@@ -33,6 +38,12 @@ pub struct StreamListWidget<'a> {
     streams: &'a [Stream],
     selected: Option<usize>,
     focused: bool,
+    /// Streams of the running focus session; the others are dimmed
+    focus_streams: Option<&'a [String]>,
+    /// Streams picked for the next focus session
+    marked: &'a [String],
+    /// Streams that failed in their provider's last sync
+    failed: Option<&'a HashSet<String>>,
     theme: &'a Theme,
 }
 
@@ -42,6 +53,9 @@ impl<'a> StreamListWidget<'a> {
             streams,
             selected,
             focused: false,
+            focus_streams: None,
+            marked: &[],
+            failed: None,
             theme,
         }
     }
@@ -51,6 +65,24 @@ impl<'a> StreamListWidget<'a> {
         self
     }
 
+    /// Dim streams outside a running focus session and hide their counts.
+    pub fn focus_streams(mut self, streams: Option<&'a [String]>) -> Self {
+        self.focus_streams = streams;
+        self
+    }
+
+    /// Mark streams picked for the next focus session.
+    pub fn marked(mut self, marked: &'a [String]) -> Self {
+        self.marked = marked;
+        self
+    }
+
+    /// Badge streams that failed in their provider's last sync.
+    pub fn failed(mut self, failed: &'a HashSet<String>) -> Self {
+        self.failed = Some(failed);
+        self
+    }
+
     pub fn render(self, area: Rect, buffer: &mut Buffer) {
         let border_color = if self.focused {
             self.theme.border_focused
@@ -70,18 +102,41 @@ impl<'a> StreamListWidget<'a> {
             .map(|(i, stream)| {
                 let is_selected = self.selected == Some(i);
                 let unread = stream.unread_count.unwrap_or(0);
+                let in_focus = self
+                    .focus_streams
+                    .is_none_or(|ids| ids.contains(&stream.id.0));
 
                 let mut spans = vec![];
 
+                // Marker for streams picked for a focus session
+                if self.marked.contains(&stream.id.0) {
+                    spans.push(Span::styled("◆ ", Style::new().fg(self.theme.accent)));
+                }
+
                 // Provider icon
                 let icon = get_provider_icon(&stream.provider_id);
                 spans.push(Span::raw(format!("{} ", icon)));
 
                 // Stream name
-                spans.push(Span::raw(&stream.name));
+                if in_focus {
+                    spans.push(Span::raw(&stream.name));
+                } else {
+                    spans.push(Span::styled(
+                        &stream.name,
+                        Style::new().fg(self.theme.muted),
+                    ));
+                }
+
+                // Badge for a stream that failed while its provider synced
+                if self
+                    .failed
+                    .is_some_and(|failed| failed.contains(&stream.id.0))
+                {
+                    spans.push(Span::styled(" ⚠", Style::new().fg(self.theme.error)));
+                }
 
-                // Unread count badge
-                if unread > 0 {
+                // Unread count badge, hidden outside a focus session
+                if unread > 0 && in_focus {
                     spans.push(Span::styled(
                         format!(" [{}]", unread),
                         Style::new()
@@ -90,6 +145,14 @@ impl<'a> StreamListWidget<'a> {
                     ));
                 }
 
+                // Size of streams without read state, such as tags
+                if let (None, Some(total)) = (stream.unread_count, stream.total_count) {
+                    spans.push(Span::styled(
+                        format!(" ({})", total),
+                        Style::new().fg(self.theme.muted),
+                    ));
+                }
+
                 let style = if is_selected {
                     Style::new()
                         .bg(self.theme.selection_bg)